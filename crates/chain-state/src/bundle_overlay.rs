@@ -0,0 +1,426 @@
+use alloy_primitives::{
+    keccak256, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
+};
+use reth_errors::ProviderResult;
+use reth_primitives_traits::{Account, Bytecode};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
+    StateProvider, StateRootProvider, StorageRootProvider,
+};
+use reth_trie::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, TrieInput,
+};
+use revm_database::{
+    states::{AccountRevert, RevertToSlot, StorageSlot},
+    BundleAccount, BundleState,
+};
+use std::{
+    mem::size_of,
+    sync::{Arc, OnceLock},
+};
+
+/// A state provider that layers speculative [`BundleState`]s on top of a historical state
+/// provider.
+///
+/// This is the state of executing arbitrary changes on top of a block without persisting them,
+/// for example the bundles of `eth_callMany`, the transactions of a payload that is being built or
+/// the calls of a simulation. The overlay can itself be used as the historical provider of another
+/// overlay.
+///
+/// Cloning the provider is cheap: the historical provider and all layers are shared. Layers are
+/// copy-on-write, so changes committed to a clone never affect the provider it was cloned from.
+/// This allows forking the speculative state, e.g. to simulate alternative continuations of the
+/// same bundles.
+///
+/// The estimated memory used by the layers can be limited with
+/// [`BundleOverlayStateProviderRef::with_memory_limit`].
+#[expect(missing_debug_implementations)]
+#[derive(Clone)]
+pub struct BundleOverlayStateProviderRef<'a> {
+    /// Historical state provider for state lookups that are not found in the layers.
+    historical: Arc<dyn StateProvider + 'a>,
+    /// The speculative layers. Expected order is oldest to newest.
+    layers: Vec<Arc<BundleState>>,
+    /// Estimated memory used by the layers, in bytes.
+    memory_usage: usize,
+    /// Maximum memory the layers may use, in bytes.
+    memory_limit: Option<usize>,
+    /// Lazy-loaded hashed state of all layers.
+    hashed_state: OnceLock<HashedPostState>,
+}
+
+/// A state provider that layers speculative [`BundleState`]s on top of a historical state
+/// provider.
+pub type BundleOverlayStateProvider = BundleOverlayStateProviderRef<'static>;
+
+impl<'a> BundleOverlayStateProviderRef<'a> {
+    /// Creates a new overlay without any layers on top of the given historical state provider.
+    pub fn new(historical: Box<dyn StateProvider + 'a>) -> Self {
+        Self {
+            historical: historical.into(),
+            layers: Vec::new(),
+            memory_usage: 0,
+            memory_limit: None,
+            hashed_state: OnceLock::new(),
+        }
+    }
+
+    /// Sets the maximum memory the layers may use, in bytes.
+    ///
+    /// Adding a layer or committing changes that exceed the limit fails.
+    pub const fn with_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = Some(memory_limit);
+        self
+    }
+
+    /// Turn this state provider into a state provider
+    pub fn boxed(self) -> Box<dyn StateProvider + 'a> {
+        Box::new(self)
+    }
+
+    /// Returns the speculative layers, from oldest to newest.
+    pub fn layers(&self) -> &[Arc<BundleState>] {
+        &self.layers
+    }
+
+    /// Returns the estimated memory used by the layers, in bytes.
+    ///
+    /// Layers shared with clones of this provider are included.
+    pub const fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+
+    /// Adds a new layer on top of the existing ones.
+    ///
+    /// The layer can be shared with other providers.
+    pub fn push_layer(
+        &mut self,
+        layer: impl Into<Arc<BundleState>>,
+    ) -> Result<(), OverlayMemoryLimitExceeded> {
+        let layer = layer.into();
+        let usage = self.memory_usage + bundle_memory_usage(&layer);
+        if let Some(limit) = self.memory_limit.filter(|limit| usage > *limit) {
+            return Err(OverlayMemoryLimitExceeded { usage, limit })
+        }
+
+        self.memory_usage = usage;
+        self.layers.push(layer);
+        self.hashed_state = OnceLock::new();
+        Ok(())
+    }
+
+    /// Merges the changes into the newest layer, or adds them as the first layer if there is none.
+    ///
+    /// The newest layer is copied first if it is shared with another provider. The memory limit
+    /// is checked against the combined size of the layers and the changes, before merging.
+    pub fn commit(&mut self, changes: BundleState) -> Result<(), OverlayMemoryLimitExceeded> {
+        let Some(top) = self.layers.last_mut() else { return self.push_layer(changes) };
+        let usage = self.memory_usage + bundle_memory_usage(&changes);
+        if let Some(limit) = self.memory_limit.filter(|limit| usage > *limit) {
+            return Err(OverlayMemoryLimitExceeded { usage, limit })
+        }
+
+        let top_usage = bundle_memory_usage(top);
+        let top = Arc::make_mut(top);
+        top.extend(changes);
+        self.memory_usage = self.memory_usage - top_usage + bundle_memory_usage(top);
+        self.hashed_state = OnceLock::new();
+        Ok(())
+    }
+
+    /// Removes the newest layer and returns it.
+    pub fn pop_layer(&mut self) -> Option<Arc<BundleState>> {
+        let layer = self.layers.pop()?;
+        self.memory_usage -= bundle_memory_usage(&layer);
+        self.hashed_state = OnceLock::new();
+        Some(layer)
+    }
+
+    /// Returns the newest change of the account in the layers.
+    fn account(&self, address: &Address) -> Option<&BundleAccount> {
+        self.layers.iter().rev().find_map(|layer| layer.account(address))
+    }
+
+    /// Return lazy-loaded hashed state aggregated from all layers.
+    fn hashed_state(&self) -> &HashedPostState {
+        self.hashed_state.get_or_init(|| {
+            let mut state = HashedPostState::default();
+            for layer in &self.layers {
+                state.extend(self.historical.hashed_post_state(layer));
+            }
+            state
+        })
+    }
+
+    /// Returns the storage of the account in the layers, extended with the given storage.
+    fn hashed_storage(&self, address: Address, storage: HashedStorage) -> HashedStorage {
+        let mut hashed_storage =
+            self.hashed_state().storages.get(&keccak256(address)).cloned().unwrap_or_default();
+        hashed_storage.extend(&storage);
+        hashed_storage
+    }
+}
+
+/// Returns the estimated memory used by the bundle state, in bytes.
+pub fn bundle_memory_usage(bundle: &BundleState) -> usize {
+    let state = bundle
+        .state
+        .values()
+        .map(|account| {
+            size_of::<(Address, BundleAccount)>() +
+                account.storage.len() * size_of::<(U256, StorageSlot)>()
+        })
+        .sum::<usize>();
+    let contracts = bundle
+        .contracts
+        .values()
+        .map(|bytecode| size_of::<(B256, Bytecode)>() + bytecode.len())
+        .sum::<usize>();
+    let reverts = bundle
+        .reverts
+        .iter()
+        .flatten()
+        .map(|(_, revert)| {
+            size_of::<(Address, AccountRevert)>() +
+                revert.storage.len() * size_of::<(U256, RevertToSlot)>()
+        })
+        .sum::<usize>();
+
+    state + contracts + reverts
+}
+
+/// Error returned if the layers of a [`BundleOverlayStateProviderRef`] would exceed its memory
+/// limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+#[display("speculative state would use {usage} bytes, exceeding the limit of {limit} bytes")]
+pub struct OverlayMemoryLimitExceeded {
+    /// Estimated memory the layers would use, in bytes.
+    pub usage: usize,
+    /// The memory limit, in bytes.
+    pub limit: usize,
+}
+
+impl BlockHashReader for BundleOverlayStateProviderRef<'_> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.historical.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.historical.canonical_hashes_range(start, end)
+    }
+}
+
+impl AccountReader for BundleOverlayStateProviderRef<'_> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.account(address) {
+            return Ok(account.info.as_ref().map(Into::into));
+        }
+
+        self.historical.basic_account(address)
+    }
+}
+
+impl StateRootProvider for BundleOverlayStateProviderRef<'_> {
+    fn state_root(&self, state: HashedPostState) -> ProviderResult<B256> {
+        self.state_root_from_nodes(TrieInput::from_state(state))
+    }
+
+    fn state_root_from_nodes(&self, mut input: TrieInput) -> ProviderResult<B256> {
+        input.prepend(self.hashed_state().clone());
+        self.historical.state_root_from_nodes(input)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state_root_from_nodes_with_updates(TrieInput::from_state(state))
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        mut input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        input.prepend(self.hashed_state().clone());
+        self.historical.state_root_from_nodes_with_updates(input)
+    }
+}
+
+impl StorageRootProvider for BundleOverlayStateProviderRef<'_> {
+    fn storage_root(&self, address: Address, storage: HashedStorage) -> ProviderResult<B256> {
+        self.historical.storage_root(address, self.hashed_storage(address, storage))
+    }
+
+    fn storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        storage: HashedStorage,
+    ) -> ProviderResult<reth_trie::StorageProof> {
+        self.historical.storage_proof(address, slot, self.hashed_storage(address, storage))
+    }
+
+    fn storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        self.historical.storage_multiproof(address, slots, self.hashed_storage(address, storage))
+    }
+}
+
+impl StateProofProvider for BundleOverlayStateProviderRef<'_> {
+    fn proof(
+        &self,
+        mut input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        input.prepend(self.hashed_state().clone());
+        self.historical.proof(input, address, slots)
+    }
+
+    fn multiproof(
+        &self,
+        mut input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        input.prepend(self.hashed_state().clone());
+        self.historical.multiproof(input, targets)
+    }
+
+    fn witness(&self, mut input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        input.prepend(self.hashed_state().clone());
+        self.historical.witness(input, target)
+    }
+}
+
+impl HashedPostStateProvider for BundleOverlayStateProviderRef<'_> {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.historical.hashed_post_state(bundle_state)
+    }
+}
+
+impl StateProvider for BundleOverlayStateProviderRef<'_> {
+    fn storage(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) =
+            self.account(&address).and_then(|account| account.storage_slot(storage_key.into()))
+        {
+            return Ok(Some(value));
+        }
+
+        self.historical.storage(address, storage_key)
+    }
+}
+
+impl BytecodeReader for BundleOverlayStateProviderRef<'_> {
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        if let Some(bytecode) = self.layers.iter().rev().find_map(|layer| layer.bytecode(code_hash))
+        {
+            return Ok(Some(Bytecode(bytecode)));
+        }
+
+        self.historical.bytecode_by_hash(code_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::map::HashMap;
+    use reth_storage_api::noop::NoopProvider;
+
+    fn account(nonce: u64) -> Account {
+        Account { nonce, ..Default::default() }
+    }
+
+    fn bundle(address: Address, nonce: u64, storage: &[(u64, u64)]) -> BundleState {
+        BundleState::builder(0..=0)
+            .state_present_account_info(address, account(nonce).into())
+            .state_storage(
+                address,
+                storage
+                    .iter()
+                    .map(|(slot, value)| (U256::from(*slot), (U256::ZERO, U256::from(*value))))
+                    .collect::<HashMap<_, _>>(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn layered_lookups() {
+        let address = Address::random();
+        let mut provider = BundleOverlayStateProvider::new(Box::new(NoopProvider::default()));
+        assert_eq!(provider.basic_account(&address).unwrap(), None);
+
+        provider.push_layer(bundle(address, 1, &[(1, 1), (2, 2)])).unwrap();
+        provider.push_layer(bundle(address, 2, &[(2, 3)])).unwrap();
+
+        assert_eq!(provider.basic_account(&address).unwrap(), Some(account(2)));
+        assert_eq!(
+            provider.storage(address, B256::with_last_byte(1)).unwrap(),
+            Some(U256::from(1))
+        );
+        assert_eq!(
+            provider.storage(address, B256::with_last_byte(2)).unwrap(),
+            Some(U256::from(3))
+        );
+        assert_eq!(provider.storage(address, B256::with_last_byte(3)).unwrap(), None);
+
+        provider.pop_layer();
+        assert_eq!(provider.basic_account(&address).unwrap(), Some(account(1)));
+        assert_eq!(
+            provider.storage(address, B256::with_last_byte(2)).unwrap(),
+            Some(U256::from(2))
+        );
+    }
+
+    #[test]
+    fn copy_on_write() {
+        let address = Address::random();
+        let mut provider = BundleOverlayStateProvider::new(Box::new(NoopProvider::default()));
+        provider.push_layer(bundle(address, 1, &[])).unwrap();
+
+        let mut fork = provider.clone();
+        assert!(Arc::ptr_eq(&provider.layers()[0], &fork.layers()[0]));
+
+        fork.commit(bundle(address, 2, &[])).unwrap();
+        assert!(!Arc::ptr_eq(&provider.layers()[0], &fork.layers()[0]));
+        assert_eq!(fork.basic_account(&address).unwrap(), Some(account(2)));
+        assert_eq!(provider.basic_account(&address).unwrap(), Some(account(1)));
+    }
+
+    #[test]
+    fn memory_limit() {
+        let address = Address::random();
+        let layer = bundle(address, 1, &[(1, 1)]);
+        let usage = bundle_memory_usage(&layer);
+
+        let mut provider = BundleOverlayStateProvider::new(Box::new(NoopProvider::default()))
+            .with_memory_limit(usage * 2);
+        provider.push_layer(layer.clone()).unwrap();
+        provider.push_layer(layer.clone()).unwrap();
+        assert_eq!(provider.memory_usage(), usage * 2);
+
+        assert_eq!(
+            provider.push_layer(layer),
+            Err(OverlayMemoryLimitExceeded { usage: usage * 3, limit: usage * 2 })
+        );
+        assert!(provider.commit(bundle(address, 2, &[(2, 2)])).is_err());
+        assert_eq!(provider.layers().len(), 2);
+        assert_eq!(provider.basic_account(&address).unwrap(), Some(account(1)));
+
+        provider.pop_layer();
+        assert_eq!(provider.memory_usage(), usage);
+    }
+}
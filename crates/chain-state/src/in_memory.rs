@@ -1,13 +1,13 @@
 //! Types for tracking the canonical chain state in memory.
 
 use crate::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
-    ChainInfoTracker, MemoryOverlayStateProvider,
+    CanonStateNotification, CanonStateNotificationHistory, CanonStateNotificationSender,
+    CanonStateNotifications, ChainInfoTracker, MemoryOverlayStateProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber, BlockNumHash};
 use alloy_primitives::{map::HashMap, TxHash, B256};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use reth_chainspec::ChainInfo;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::{Chain, ExecutionOutcome};
@@ -139,6 +139,11 @@ pub(crate) struct CanonicalInMemoryStateInner<N: NodePrimitives> {
     pub(crate) in_memory_state: InMemoryState<N>,
     /// A broadcast stream that emits events when the canonical chain is updated.
     pub(crate) canon_state_notification_sender: CanonStateNotificationSender<N>,
+    /// The most recent notifications sent over the broadcast stream.
+    ///
+    /// The lock is held while sending, so that subscribers can atomically read the history and
+    /// subscribe.
+    pub(crate) canon_state_history: Mutex<CanonStateNotificationHistory<N>>,
 }
 
 impl<N: NodePrimitives> CanonicalInMemoryStateInner<N> {
@@ -192,6 +197,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 chain_info_tracker,
                 in_memory_state,
                 canon_state_notification_sender,
+                canon_state_history: Default::default(),
             }),
         }
     }
@@ -216,6 +222,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             chain_info_tracker,
             in_memory_state,
             canon_state_notification_sender,
+            canon_state_history: Default::default(),
         };
 
         Self { inner: Arc::new(inner) }
@@ -491,6 +498,20 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.canon_state_notification_sender.subscribe()
     }
 
+    /// Subscribe to new blocks events, returning the most recent events that were sent before
+    /// subscribing, oldest first.
+    pub fn subscribe_canon_state_with_history(
+        &self,
+    ) -> (Vec<CanonStateNotification<N>>, CanonStateNotifications<N>) {
+        let history = self.inner.canon_state_history.lock();
+        (history.to_vec(), self.inner.canon_state_notification_sender.subscribe())
+    }
+
+    /// Sets the number of recent events that are kept for late subscribers.
+    pub fn set_canon_state_history_capacity(&self, capacity: usize) {
+        self.inner.canon_state_history.lock().set_capacity(capacity);
+    }
+
     /// Subscribe to new safe block events.
     pub fn subscribe_safe_block(&self) -> watch::Receiver<Option<SealedHeader<N::BlockHeader>>> {
         self.inner.chain_info_tracker.subscribe_safe_block()
@@ -505,6 +526,8 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
 
     /// Attempts to send a new [`CanonStateNotification`] to all active Receiver handles.
     pub fn notify_canon_state(&self, event: CanonStateNotification<N>) {
+        let mut history = self.inner.canon_state_history.lock();
+        history.push(event.clone());
         self.inner.canon_state_notification_sender.send(event).ok();
     }
 
@@ -1527,6 +1550,30 @@ mod tests {
         assert_eq!(block_state_chain[0].block().recovered_block().number, 1);
     }
 
+    #[test]
+    fn test_canon_state_history() {
+        let mut test_block_builder: TestBlockBuilder = TestBlockBuilder::default();
+        let notifications = test_block_builder
+            .get_executed_blocks(0..4)
+            .map(|block| NewCanonicalChain::Commit { new: vec![block] }.to_chain_notification())
+            .collect::<Vec<_>>();
+
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+        state.set_canon_state_history_capacity(2);
+        for notification in &notifications[..3] {
+            state.notify_canon_state(notification.clone());
+        }
+
+        // only the most recent notifications are kept
+        let (history, mut rx) = state.subscribe_canon_state_with_history();
+        assert_eq!(history, notifications[1..3]);
+
+        // notifications sent after subscribing are received
+        state.notify_canon_state(notifications[3].clone());
+        assert_eq!(rx.try_recv().unwrap(), notifications[3]);
+        assert!(rx.try_recv().is_err());
+    }
+
     #[test]
     fn test_to_chain_notification() {
         // Generate 4 blocks
@@ -18,14 +18,21 @@ pub use chain_info::ChainInfoTracker;
 
 mod notifications;
 pub use notifications::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications, ForkChoiceStream,
-    ForkChoiceSubscriptions,
+    CanonStateNotification, CanonStateNotificationHistory, CanonStateNotificationSender,
+    CanonStateNotificationStream, CanonStateNotifications, CanonStateSubscriptions,
+    ForkChoiceNotifications, ForkChoiceStream, ForkChoiceSubscriptions,
+    DEFAULT_CANON_STATE_NOTIFICATION_HISTORY,
 };
 
 mod memory_overlay;
 pub use memory_overlay::{MemoryOverlayStateProvider, MemoryOverlayStateProviderRef};
 
+mod bundle_overlay;
+pub use bundle_overlay::{
+    bundle_memory_usage, BundleOverlayStateProvider, BundleOverlayStateProviderRef,
+    OverlayMemoryLimitExceeded,
+};
+
 #[cfg(any(test, feature = "test-utils"))]
 /// Common test helpers
 pub mod test_utils;
@@ -6,6 +6,7 @@ use reth_execution_types::{BlockReceipts, Chain};
 use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
 use reth_storage_api::NodePrimitivesProvider;
 use std::{
+    collections::VecDeque,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
@@ -32,12 +33,41 @@ pub trait CanonStateSubscriptions: NodePrimitivesProvider + Send + Sync {
     /// A canonical chain be one or more blocks, a reorg or a revert.
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications<Self::Primitives>;
 
+    /// Get notified when a new canonical chain was imported, including the most recent
+    /// notifications that were sent before subscribing.
+    ///
+    /// Returns the recent notifications, oldest first, and a receiver for all notifications that
+    /// are sent afterwards. No notification is both returned and received.
+    ///
+    /// By default, no recent notifications are returned.
+    fn subscribe_to_canonical_state_with_history(
+        &self,
+    ) -> (Vec<CanonStateNotification<Self::Primitives>>, CanonStateNotifications<Self::Primitives>)
+    {
+        (Vec::new(), self.subscribe_to_canonical_state())
+    }
+
     /// Convenience method to get a stream of [`CanonStateNotification`].
     fn canonical_state_stream(&self) -> CanonStateNotificationStream<Self::Primitives> {
         CanonStateNotificationStream {
+            history: VecDeque::new(),
             st: BroadcastStream::new(self.subscribe_to_canonical_state()),
         }
     }
+
+    /// Convenience method to get a stream of [`CanonStateNotification`] that first yields the most
+    /// recent notifications that were sent before subscribing.
+    ///
+    /// See also [`Self::subscribe_to_canonical_state_with_history`].
+    fn canonical_state_stream_with_history(
+        &self,
+    ) -> CanonStateNotificationStream<Self::Primitives> {
+        let (history, notifications) = self.subscribe_to_canonical_state_with_history();
+        CanonStateNotificationStream {
+            history: history.into(),
+            st: BroadcastStream::new(notifications),
+        }
+    }
 }
 
 impl<T: CanonStateSubscriptions> CanonStateSubscriptions for &T {
@@ -45,9 +75,93 @@ impl<T: CanonStateSubscriptions> CanonStateSubscriptions for &T {
         (*self).subscribe_to_canonical_state()
     }
 
+    fn subscribe_to_canonical_state_with_history(
+        &self,
+    ) -> (Vec<CanonStateNotification<Self::Primitives>>, CanonStateNotifications<Self::Primitives>)
+    {
+        (*self).subscribe_to_canonical_state_with_history()
+    }
+
     fn canonical_state_stream(&self) -> CanonStateNotificationStream<Self::Primitives> {
         (*self).canonical_state_stream()
     }
+
+    fn canonical_state_stream_with_history(
+        &self,
+    ) -> CanonStateNotificationStream<Self::Primitives> {
+        (*self).canonical_state_stream_with_history()
+    }
+}
+
+/// Default number of recent notifications kept by a [`CanonStateNotificationHistory`].
+pub const DEFAULT_CANON_STATE_NOTIFICATION_HISTORY: usize = 16;
+
+/// A ring buffer of the most recent [`CanonStateNotification`]s.
+///
+/// Subscribers that start after the node started importing blocks, drain the buffered
+/// notifications before they receive new ones. This way they don't miss the notifications that
+/// were sent between reading the chain head and subscribing.
+#[derive(Debug, Clone)]
+pub struct CanonStateNotificationHistory<
+    N: NodePrimitives = reth_ethereum_primitives::EthPrimitives,
+> {
+    notifications: VecDeque<CanonStateNotification<N>>,
+    capacity: usize,
+}
+
+impl<N: NodePrimitives> CanonStateNotificationHistory<N> {
+    /// Creates a new history that keeps at most `capacity` notifications.
+    pub fn new(capacity: usize) -> Self {
+        Self { notifications: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    /// Returns the maximum number of notifications that are kept.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of notifications that are kept, dropping the oldest notifications
+    /// that exceed it.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    /// Adds a notification, dropping the oldest notification if the history is full.
+    pub fn push(&mut self, notification: CanonStateNotification<N>) {
+        if self.capacity == 0 {
+            return
+        }
+        self.notifications.push_back(notification);
+        self.truncate();
+    }
+
+    /// Returns the kept notifications, oldest first.
+    pub fn to_vec(&self) -> Vec<CanonStateNotification<N>> {
+        self.notifications.iter().cloned().collect()
+    }
+
+    /// Returns the number of kept notifications.
+    pub fn len(&self) -> usize {
+        self.notifications.len()
+    }
+
+    /// Returns `true` if no notifications are kept.
+    pub fn is_empty(&self) -> bool {
+        self.notifications.is_empty()
+    }
+
+    fn truncate(&mut self) {
+        while self.notifications.len() > self.capacity {
+            self.notifications.pop_front();
+        }
+    }
+}
+
+impl<N: NodePrimitives> Default for CanonStateNotificationHistory<N> {
+    fn default() -> Self {
+        Self::new(DEFAULT_CANON_STATE_NOTIFICATION_HISTORY)
+    }
 }
 
 /// A Stream of [`CanonStateNotification`].
@@ -55,6 +169,8 @@ impl<T: CanonStateSubscriptions> CanonStateSubscriptions for &T {
 #[pin_project::pin_project]
 pub struct CanonStateNotificationStream<N: NodePrimitives = reth_ethereum_primitives::EthPrimitives>
 {
+    /// Notifications that were sent before subscribing, yielded first.
+    history: VecDeque<CanonStateNotification<N>>,
     #[pin]
     st: BroadcastStream<CanonStateNotification<N>>,
 }
@@ -63,6 +179,9 @@ impl<N: NodePrimitives> Stream for CanonStateNotificationStream<N> {
     type Item = CanonStateNotification<N>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(notification) = self.as_mut().project().history.pop_front() {
+            return Poll::Ready(Some(notification))
+        }
         loop {
             return match ready!(self.as_mut().project().st.poll_next(cx)) {
                 Some(Ok(notification)) => Poll::Ready(Some(notification)),
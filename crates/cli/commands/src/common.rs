@@ -66,7 +66,8 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
     where
         C: ChainSpecParser<ChainSpec = N::ChainSpec>,
     {
-        let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
+        let mut config = self.load_config();
+        let data_dir = self.resolve_datadir(&config);
         let db_path = data_dir.db();
         let sf_path = data_dir.static_files();
 
@@ -75,14 +76,6 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
             reth_fs_util::create_dir_all(&sf_path)?;
         }
 
-        let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
-
-        let mut config = Config::from_path(config_path)
-            .inspect_err(
-                |err| warn!(target: "reth::cli", %err, "Failed to load config file, using default"),
-            )
-            .unwrap_or_default();
-
         // Make sure ETL doesn't default to /tmp/, but to whatever datadir is set to
         if config.stages.etl.dir.is_none() {
             config.stages.etl.dir = Some(EtlConfig::from_datadir(data_dir.data_dir()));
@@ -91,20 +84,21 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
             config.stages.era = config.stages.era.with_datadir(data_dir.data_dir());
         }
 
+        let db_args = self.db.database_args_with_config(&config.db)?;
+
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
         let (db, sfp) = match access {
-            AccessRights::RW => (
-                Arc::new(init_db(db_path, self.db.database_args())?),
-                StaticFileProvider::read_write(sf_path)?,
-            ),
+            AccessRights::RW | AccessRights::RWNoGenesis => {
+                (Arc::new(init_db(db_path, db_args)?), StaticFileProvider::read_write(sf_path)?)
+            }
             AccessRights::RO => (
-                Arc::new(open_db_read_only(&db_path, self.db.database_args())?),
+                Arc::new(open_db_read_only(&db_path, db_args)?),
                 StaticFileProvider::read_only(sf_path, false)?,
             ),
         };
 
         let provider_factory = self.create_provider_factory(&config, db, sfp)?;
-        if access.is_read_write() {
+        if matches!(access, AccessRights::RW) {
             debug!(target: "reth::cli", chain=%self.chain.chain(), genesis=?self.chain.genesis_hash(), "Initializing genesis");
             init_genesis(&provider_factory)?;
         }
@@ -112,6 +106,28 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         Ok(Environment { config, provider_factory, data_dir })
     }
 
+    /// Loads the configuration file, falling back to the default configuration if it can't be
+    /// loaded.
+    pub fn load_config(&self) -> Config {
+        let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
+        let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
+
+        Config::from_path(config_path)
+            .inspect_err(
+                |err| warn!(target: "reth::cli", %err, "Failed to load config file, using default"),
+            )
+            .unwrap_or_default()
+    }
+
+    /// Resolves the datadir, using the static files path of the configuration file if
+    /// `--datadir.static-files` is not set.
+    pub fn resolve_datadir(&self, config: &Config) -> ChainPath<DataDirPath> {
+        self.datadir
+            .clone()
+            .or_static_files_path(config.static_files.path.clone())
+            .resolve_datadir(self.chain.chain())
+    }
+
     /// Returns a [`ProviderFactory`] after executing consistency checks.
     ///
     /// If it's a read-write environment and an issue is found, it will attempt to heal (including a
@@ -134,7 +150,8 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
             self.chain.clone(),
             static_file_provider,
         )
-        .with_prune_modes(prune_modes.clone());
+        .with_prune_modes(prune_modes.clone())
+        .with_sender_transactions_index(config.stages.index_sender_transactions.enabled);
 
         // Check for consistency between database and static files.
         if let Some(unwind_target) = factory
@@ -198,6 +215,8 @@ pub struct Environment<N: NodeTypes> {
 pub enum AccessRights {
     /// Read-write access
     RW,
+    /// Read-write access, without initializing the genesis state of an empty database
+    RWNoGenesis,
     /// Read-only access
     RO,
 }
@@ -205,7 +224,7 @@ pub enum AccessRights {
 impl AccessRights {
     /// Returns `true` if it requires read-write access to the environment.
     pub const fn is_read_write(&self) -> bool {
-        matches!(self, Self::RW)
+        matches!(self, Self::RW | Self::RWNoGenesis)
     }
 }
 
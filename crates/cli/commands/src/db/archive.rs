@@ -0,0 +1,333 @@
+//! Table archives, exported by `reth db drop --archive` or `reth db clear mdbx --archive` and
+//! restored by `reth db import-archive`.
+//!
+//! An archive is a lz4 compressed stream, that starts with [`ARCHIVE_MAGIC`] and the
+//! length-prefixed JSON encoded [`ArchiveMetadata`], followed by the length-prefixed raw keys and
+//! values of all table entries.
+
+use clap::Parser;
+use eyre::{eyre, WrapErr};
+use reth_db::version::DB_VERSION;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+    RawKey, RawTable, RawValue, TableViewer, Tables,
+};
+use reth_node_core::version::SHORT_VERSION;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Magic bytes at the start of every table archive.
+const ARCHIVE_MAGIC: &[u8; 8] = b"RETHTBL1";
+
+/// File extension of table archives.
+const ARCHIVE_EXTENSION: &str = "tbl.lz4";
+
+/// Log progress every this many entries.
+const LOG_INTERVAL: usize = 1_000_000;
+
+/// Commit the imported entries every this many entries.
+const IMPORT_BATCH_SIZE: usize = 100_000;
+
+/// Metadata at the start of a table archive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ArchiveMetadata {
+    /// Name of the archived table.
+    table: String,
+    /// Whether the archived table is a `DUPSORT` table.
+    dupsort: bool,
+    /// Version of the database the table was exported from.
+    db_version: u64,
+    /// Version of the client that exported the table.
+    client_version: String,
+}
+
+/// Exports every non-empty table of the database into an archive in the given directory.
+pub(crate) fn export_tables<DB: Database>(db: &DB, dir: &Path) -> eyre::Result<()> {
+    for table in Tables::ALL {
+        export_table(db, *table, dir)?;
+    }
+
+    Ok(())
+}
+
+/// Exports the table into an archive in the given directory, unless it's empty.
+///
+/// Returns the number of archived entries.
+pub(crate) fn export_table<DB: Database>(
+    db: &DB,
+    table: Tables,
+    dir: &Path,
+) -> eyre::Result<usize> {
+    reth_fs_util::create_dir_all(dir)?;
+
+    let path = dir.join(format!("{}.{ARCHIVE_EXTENSION}", table.name()));
+    let entries = table.view(&ExportViewer { db, table, path: &path })?;
+    if entries > 0 {
+        info!(target: "reth::cli", %table, entries, ?path, "Archived table");
+    }
+    Ok(entries)
+}
+
+/// Streams a table into an archive, skipping empty tables.
+struct ExportViewer<'a, DB: Database> {
+    db: &'a DB,
+    table: Tables,
+    path: &'a Path,
+}
+
+impl<DB: Database> TableViewer<usize> for ExportViewer<'_, DB> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        let mut tx = self.db.tx()?;
+        tx.disable_long_read_transaction_safety();
+        if tx.entries::<T>()? == 0 {
+            return Ok(0)
+        }
+
+        let file = reth_fs_util::create_file(self.path)?;
+        let mut writer = lz4::EncoderBuilder::new().build(BufWriter::new(file))?;
+        write_metadata(
+            &mut writer,
+            &ArchiveMetadata {
+                table: self.table.name().to_string(),
+                dupsort: self.table.is_dupsort(),
+                db_version: DB_VERSION,
+                client_version: SHORT_VERSION.to_string(),
+            },
+        )?;
+
+        let mut entries = 0;
+        for entry in tx.cursor_read::<RawTable<T>>()?.walk(None)? {
+            let (key, value) = entry?;
+            write_chunk(&mut writer, key.raw_key())?;
+            write_chunk(&mut writer, value.raw_value())?;
+
+            entries += 1;
+            if entries % LOG_INTERVAL == 0 {
+                info!(target: "reth::cli", table = %self.table, entries, "Archiving table");
+            }
+        }
+
+        let (mut writer, result) = writer.finish();
+        result?;
+        writer.flush()?;
+
+        Ok(entries)
+    }
+}
+
+/// `reth db import-archive` command
+#[derive(Parser, Debug)]
+pub struct ImportCommand {
+    /// The path to the table archive, as exported by `reth db drop --archive` or
+    /// `reth db clear mdbx --archive`.
+    path: PathBuf,
+}
+
+impl ImportCommand {
+    /// Execute `db import-archive` command
+    ///
+    /// The entries are committed in batches. If the import fails, the table keeps the entries
+    /// that were committed, and needs to be cleared before importing the archive again.
+    pub fn execute<DB: Database>(self, db: &DB) -> eyre::Result<()> {
+        let file = reth_fs_util::open(&self.path)?;
+        let mut reader = lz4::Decoder::new(BufReader::new(file))?;
+        let metadata = read_metadata(&mut reader)
+            .wrap_err_with(|| format!("Invalid table archive: {:?}", self.path))?;
+
+        eyre::ensure!(
+            metadata.db_version == DB_VERSION,
+            "Archive has database version {}, expected {DB_VERSION}",
+            metadata.db_version
+        );
+        let table: Tables = metadata.table.parse().map_err(|err: String| eyre!(err))?;
+        eyre::ensure!(
+            table.is_dupsort() == metadata.dupsort,
+            "Archive of table {table} does not match its DUPSORT flag"
+        );
+
+        info!(
+            target: "reth::cli",
+            %table,
+            client_version = %metadata.client_version,
+            "Importing table archive"
+        );
+        let entries = table.view(&ImportViewer { db, table, reader: RefCell::new(reader) })?;
+        info!(target: "reth::cli", %table, entries, "Imported table archive");
+
+        Ok(())
+    }
+}
+
+/// Inserts the entries of an archive into an empty table.
+struct ImportViewer<'a, DB: Database, R> {
+    db: &'a DB,
+    table: Tables,
+    reader: RefCell<R>,
+}
+
+impl<DB: Database, R: Read> TableViewer<usize> for ImportViewer<'_, DB, R> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        let mut tx = self.db.tx_mut()?;
+        eyre::ensure!(
+            tx.entries::<T>()? == 0,
+            "Table {} is not empty, clear it before importing the archive",
+            self.table
+        );
+
+        let mut reader = self.reader.borrow_mut();
+        let mut entries = 0;
+        while let Some(key) = read_chunk(&mut *reader)? {
+            let value =
+                read_chunk(&mut *reader)?.ok_or_else(|| eyre!("Truncated table archive"))?;
+            tx.put::<RawTable<T>>(RawKey::from_vec(key), RawValue::from_vec(value))?;
+
+            entries += 1;
+            if entries % IMPORT_BATCH_SIZE == 0 {
+                tx.commit()?;
+                tx = self.db.tx_mut()?;
+            }
+            if entries % LOG_INTERVAL == 0 {
+                info!(
+                    target: "reth::cli",
+                    table = %self.table,
+                    entries,
+                    "Importing table archive"
+                );
+            }
+        }
+
+        tx.commit()?;
+        Ok(entries)
+    }
+}
+
+/// Writes the magic bytes and the metadata of an archive.
+fn write_metadata(writer: &mut impl Write, metadata: &ArchiveMetadata) -> eyre::Result<()> {
+    writer.write_all(ARCHIVE_MAGIC)?;
+    write_chunk(writer, &serde_json::to_vec(metadata)?)?;
+    Ok(())
+}
+
+/// Reads and validates the magic bytes, and reads the metadata of an archive.
+fn read_metadata(reader: &mut impl Read) -> eyre::Result<ArchiveMetadata> {
+    let mut magic = [0; ARCHIVE_MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    eyre::ensure!(&magic == ARCHIVE_MAGIC, "Not a table archive");
+
+    let metadata = read_chunk(reader)?.ok_or_else(|| eyre!("Missing archive metadata"))?;
+    Ok(serde_json::from_slice(&metadata)?)
+}
+
+/// Writes a length-prefixed chunk of bytes.
+fn write_chunk(writer: &mut impl Write, chunk: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(chunk.len()).map_err(io::Error::other)?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(chunk)
+}
+
+/// Reads a length-prefixed chunk of bytes, returning `None` at the end of the stream.
+fn read_chunk(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+    let mut len = [0; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+
+    let mut chunk = vec![0; u32::from_le_bytes(len) as usize];
+    reader.read_exact(&mut chunk)?;
+    Ok(Some(chunk))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::tables;
+
+    #[test]
+    fn archive_roundtrip() {
+        let metadata = ArchiveMetadata {
+            table: Tables::PlainStorageState.name().to_string(),
+            dupsort: true,
+            db_version: DB_VERSION,
+            client_version: SHORT_VERSION.to_string(),
+        };
+        let entries = [(vec![1u8; 20], vec![2u8; 64]), (vec![3u8; 20], vec![])];
+
+        let mut writer = lz4::EncoderBuilder::new().build(Vec::new()).unwrap();
+        write_metadata(&mut writer, &metadata).unwrap();
+        for (key, value) in &entries {
+            write_chunk(&mut writer, key).unwrap();
+            write_chunk(&mut writer, value).unwrap();
+        }
+        let (archive, result) = writer.finish();
+        result.unwrap();
+
+        let mut reader = lz4::Decoder::new(archive.as_slice()).unwrap();
+        assert_eq!(read_metadata(&mut reader).unwrap(), metadata);
+        for (key, value) in &entries {
+            assert_eq!(read_chunk(&mut reader).unwrap().as_ref(), Some(key));
+            assert_eq!(read_chunk(&mut reader).unwrap().as_ref(), Some(value));
+        }
+        assert_eq!(read_chunk(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_archive() {
+        let mut writer = lz4::EncoderBuilder::new().build(Vec::new()).unwrap();
+        writer.write_all(b"NOTANARCHIVE").unwrap();
+        let (archive, result) = writer.finish();
+        result.unwrap();
+
+        let mut reader = lz4::Decoder::new(archive.as_slice()).unwrap();
+        assert!(read_metadata(&mut reader).is_err());
+    }
+
+    fn canonical_headers(db: &impl Database) -> Vec<(u64, B256)> {
+        let tx = db.tx().unwrap();
+        let mut cursor = tx.cursor_read::<tables::CanonicalHeaders>().unwrap();
+        let entries = cursor.walk(None).unwrap().collect::<Result<_, _>>().unwrap();
+        entries
+    }
+
+    #[test]
+    fn export_import_roundtrip() {
+        let source = create_test_rw_db();
+        let tx = source.tx_mut().unwrap();
+        for number in 0..10u64 {
+            tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8)).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(export_table(&*source, Tables::CanonicalHeaders, dir.path()).unwrap(), 10);
+        // empty tables are not archived
+        assert_eq!(export_table(&*source, Tables::Bytecodes, dir.path()).unwrap(), 0);
+
+        let path =
+            dir.path().join(format!("{}.{ARCHIVE_EXTENSION}", Tables::CanonicalHeaders.name()));
+        let target = create_test_rw_db();
+        ImportCommand { path: path.clone() }.execute(&*target).unwrap();
+
+        let source_entries = canonical_headers(&*source);
+        assert_eq!(source_entries.len(), 10);
+        assert_eq!(canonical_headers(&*target), source_entries);
+
+        // the table has to be cleared before importing again
+        assert!(ImportCommand { path }.execute(&*target).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::common::CliNodeTypes;
+use alloy_primitives::hex;
+use clap::Parser;
+use reth_chainspec::EthereumHardforks;
+use reth_db::DatabaseEnv;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    table::{Encode, Table},
+    transaction::DbTx,
+    RawKey, RawTable, TableViewer, Tables,
+};
+use reth_db_common::DbTool;
+use reth_node_builder::{NodeTypesWithDB, NodeTypesWithDBAdapter};
+use reth_provider::{providers::ProviderNodeTypes, DBProvider};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db audit-keys` command
+pub struct Command {
+    /// The table to audit. Audits all tables if not set.
+    table: Option<Tables>,
+
+    /// The maximum number of entries that are audited per table.
+    #[arg(long)]
+    limit: Option<usize>,
+}
+
+impl Command {
+    /// Execute `db audit-keys` command
+    pub fn execute<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
+        self,
+        tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    ) -> eyre::Result<()> {
+        warn!("This command should be run without the node running!");
+        let viewer = AuditKeysViewer { tool, limit: self.limit.unwrap_or(usize::MAX) };
+
+        let tables = self.table.as_ref().map_or(Tables::ALL, std::slice::from_ref);
+        let mut violations = 0;
+        for table in tables {
+            violations += table.view(&viewer)?;
+        }
+
+        eyre::ensure!(violations == 0, "Found {violations} keys with an invalid encoding");
+        info!("All keys are encoded in order");
+        Ok(())
+    }
+}
+
+/// Walks a table in the order of the encoded keys, and checks that every key decodes, encodes to
+/// the same bytes again, and is ordered after the previous key.
+struct AuditKeysViewer<'a, N: NodeTypesWithDB> {
+    tool: &'a DbTool<N>,
+    limit: usize,
+}
+
+impl<N: ProviderNodeTypes> TableViewer<usize> for AuditKeysViewer<'_, N> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        let provider =
+            self.tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+        let tx = provider.tx_ref();
+        let mut cursor = tx.cursor_read::<RawTable<T>>()?;
+
+        let mut previous: Option<(RawKey<T::Key>, T::Key)> = None;
+        let mut total = 0;
+        let mut violations = 0;
+        for (index, entry) in cursor.walk(None)?.take(self.limit).enumerate() {
+            let (raw_key, _) = entry?;
+            total = index + 1;
+
+            if index % 1_000_000 == 0 {
+                info!(table = T::NAME, "Audited {index} keys.");
+            }
+
+            let key = match raw_key.key() {
+                Ok(key) => key,
+                Err(err) => {
+                    warn!(
+                        table = T::NAME,
+                        key = hex::encode(raw_key.raw_key()),
+                        %err,
+                        "Failed to decode key"
+                    );
+                    violations += 1;
+                    continue
+                }
+            };
+
+            if key.clone().encode().as_ref() != raw_key.raw_key() {
+                warn!(
+                    table = T::NAME,
+                    ?key,
+                    raw_key = hex::encode(raw_key.raw_key()),
+                    "Key does not encode to the stored bytes"
+                );
+                violations += 1;
+            }
+
+            if let Some((previous_raw_key, previous_key)) = &previous {
+                if previous_key.cmp(&key) != previous_raw_key.raw_key().cmp(raw_key.raw_key()) {
+                    warn!(
+                        table = T::NAME,
+                        ?previous_key,
+                        ?key,
+                        "Keys are ordered differently than their encodings"
+                    );
+                    violations += 1;
+                }
+            }
+            previous = Some((raw_key, key));
+        }
+
+        info!(table = T::NAME, total, violations, "Audited table keys");
+        Ok(violations)
+    }
+}
@@ -1,3 +1,4 @@
+use super::archive;
 use clap::{Parser, Subcommand};
 use reth_db::static_file::iter_static_files;
 use reth_db_api::{
@@ -9,6 +10,7 @@ use reth_db_api::{
 use reth_node_builder::NodeTypesWithDB;
 use reth_provider::{ProviderFactory, StaticFileProviderFactory};
 use reth_static_file_types::StaticFileSegment;
+use std::path::PathBuf;
 
 /// The arguments for the `reth db clear` command
 #[derive(Parser, Debug)]
@@ -24,7 +26,10 @@ impl Command {
         provider_factory: ProviderFactory<N>,
     ) -> eyre::Result<()> {
         match self.subcommand {
-            Subcommands::Mdbx { table } => {
+            Subcommands::Mdbx { table, archive } => {
+                if let Some(archive_dir) = archive {
+                    archive::export_table(provider_factory.db_ref(), table, &archive_dir)?;
+                }
                 table.view(&ClearViewer { db: provider_factory.db_ref() })?
             }
             Subcommands::StaticFile { segment } => {
@@ -46,7 +51,13 @@ impl Command {
 #[derive(Subcommand, Debug)]
 enum Subcommands {
     /// Deletes all database table entries
-    Mdbx { table: Tables },
+    Mdbx {
+        table: Tables,
+        /// Exports the table into a compressed archive in the given directory before clearing
+        /// it. The archive can be restored with `reth db import-archive`.
+        #[arg(long, value_name = "DIR")]
+        archive: Option<PathBuf>,
+    },
     /// Deletes all static file segment entries
     StaticFile { segment: StaticFileSegment },
 }
@@ -0,0 +1,67 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::{version::db_version_file_path, DatabaseEnv};
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tracing::info;
+
+/// Name of the MDBX data file inside of the database directory.
+const MDBX_DATA_FILE: &str = "mdbx.dat";
+
+/// The arguments for the `reth db compact` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the compacted database to. It must be empty or not exist yet.
+    ///
+    /// Once the copy is complete, the node can be stopped and the database directory replaced
+    /// with this one to reclaim the free pages.
+    #[arg(long, value_name = "PATH")]
+    to: PathBuf,
+}
+
+impl Command {
+    /// Execute `db compact` command
+    ///
+    /// The database is copied from a consistent read snapshot, so this can run while the node is
+    /// running. Writes made by the node after the snapshot was taken are not part of the copy.
+    pub fn execute(self, db: &DatabaseEnv, db_path: &Path) -> eyre::Result<()> {
+        let to = self.to;
+        if to.exists() {
+            eyre::ensure!(
+                reth_fs_util::read_dir(&to)?.next().is_none(),
+                "Target directory {to:?} is not empty"
+            );
+        }
+        reth_fs_util::create_dir_all(&to)?;
+
+        let source = db_path.join(MDBX_DATA_FILE);
+        let target = to.join(MDBX_DATA_FILE);
+        let source_size = reth_fs_util::metadata(&source)?.len();
+
+        info!(target: "reth::cli", ?source, ?target, size = %human_bytes(source_size as f64), "Compacting database");
+        let start = Instant::now();
+        db.copy(&target, true)?;
+
+        // The version file is needed to open the compacted copy as the node's database.
+        let version_file = db_version_file_path(db_path);
+        if version_file.exists() {
+            std::fs::copy(&version_file, db_version_file_path(&to))?;
+        }
+
+        let compacted_size = reth_fs_util::metadata(&target)?.len();
+        let saved = source_size.saturating_sub(compacted_size);
+        info!(
+            target: "reth::cli",
+            elapsed = ?start.elapsed(),
+            "Compacted database from {} to {}, saving {} ({:.2}%)",
+            human_bytes(source_size as f64),
+            human_bytes(compacted_size as f64),
+            human_bytes(saved as f64),
+            if source_size == 0 { 0.0 } else { saved as f64 / source_size as f64 * 100.0 },
+        );
+
+        Ok(())
+    }
+}
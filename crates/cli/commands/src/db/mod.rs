@@ -6,16 +6,23 @@ use reth_db::version::{get_db_version, DatabaseVersionError, DB_VERSION};
 use reth_db_common::DbTool;
 use std::{
     io::{self, Write},
+    path::PathBuf,
     sync::Arc,
 };
+mod archive;
+mod audit_keys;
 mod checksum;
 mod clear;
+mod compact;
 mod diff;
 mod get;
 mod list;
+mod prune_status;
+mod relocate_static_files;
 mod stats;
 /// DB List TUI
 mod tui;
+mod verify_static_files;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -36,18 +43,37 @@ pub enum Subcommands {
     List(list::Command),
     /// Calculates the content checksum of a table
     Checksum(checksum::Command),
+    /// Checks that the keys of a table are decodable and stored in the order of the decoded keys
+    AuditKeys(audit_keys::Command),
     /// Create a diff between two database tables or two entire databases.
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Shows the prune checkpoint, configured mode and last pruner run of every prune segment
+    PruneStatus(prune_status::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
         #[arg(short, long)]
         force: bool,
+        /// Exports every non-empty table into a compressed archive in the given directory before
+        /// dropping the database. Archives can be restored with `reth db import-archive`.
+        ///
+        /// Static files and the ExEx WAL are not archived.
+        #[arg(long, value_name = "DIR")]
+        archive: Option<PathBuf>,
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Imports a table archive exported by `reth db drop --archive` or
+    /// `reth db clear mdbx --archive` into the empty table
+    ImportArchive(archive::ImportCommand),
+    /// Writes a compacted copy of the database without free pages, reporting the size savings
+    Compact(compact::Command),
+    /// Moves the static files to another directory, e.g. on a separate volume
+    RelocateStaticFiles(relocate_static_files::Command),
+    /// Verifies the checksums of all finalized static files
+    VerifyStaticFiles(verify_static_files::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -69,8 +95,6 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
         let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
         let db_path = data_dir.db();
-        let static_files_path = data_dir.static_files();
-        let exex_wal_path = data_dir.exex_wal();
 
         // ensure the provided datadir exist
         eyre::ensure!(
@@ -82,6 +106,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         // ensure the provided database exist
         eyre::ensure!(db_path.is_dir(), "Database does not exist: {:?}", db_path);
 
+        // the static files may have been relocated in the config file
+        let data_dir = self.env.resolve_datadir(&self.env.load_config());
+        let static_files_path = data_dir.static_files();
+        let exex_wal_path = data_dir.exex_wal();
+
         match self.command {
             // TODO: We'll need to add this on the DB trait.
             Subcommands::Stats(command) => {
@@ -99,6 +128,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::AuditKeys(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Diff(command) => {
                 db_ro_exec!(self.env, tool, N, {
                     command.execute(&tool)?;
@@ -109,7 +143,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
-            Subcommands::Drop { force } => {
+            Subcommands::PruneStatus(command) => {
+                let Environment { config, provider_factory, .. } =
+                    self.env.init::<N>(AccessRights::RO)?;
+                command.execute(provider_factory, config.prune)?;
+            }
+            Subcommands::Drop { force, archive } => {
                 if !force {
                     // Ask for confirmation
                     print!(
@@ -129,12 +168,36 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
 
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 let tool = DbTool::new(provider_factory)?;
+                if let Some(archive_dir) = archive {
+                    archive::export_tables(tool.provider_factory.db_ref(), &archive_dir)?;
+                }
                 tool.drop(db_path, static_files_path, exex_wal_path)?;
             }
             Subcommands::Clear(command) => {
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::ImportArchive(command) => {
+                // the genesis state would be written into the empty tables of a dropped database
+                let Environment { provider_factory, .. } =
+                    self.env.init::<N>(AccessRights::RWNoGenesis)?;
+                command.execute(provider_factory.db_ref())?;
+            }
+            Subcommands::Compact(command) => {
+                // the copy is taken from a read snapshot, so this can run next to a node
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+                command.execute(provider_factory.db_ref(), &db_path)?;
+            }
+            Subcommands::RelocateStaticFiles(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::VerifyStaticFiles(command) => {
+                // verification records checksums, while listing can run next to a node
+                let access = if command.list { AccessRights::RO } else { AccessRights::RW };
+                let Environment { provider_factory, .. } = self.env.init::<N>(access)?;
+                command.execute(provider_factory)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
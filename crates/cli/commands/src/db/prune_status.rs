@@ -0,0 +1,115 @@
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use reth_chainspec::EthereumHardforks;
+use reth_config::PruneConfig;
+use reth_node_core::args::PruningArgs;
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockNumReader, ChainSpecProvider, ProviderFactory,
+    PruneCheckpointReader,
+};
+use reth_prune::{PruneModes, PruneSegment};
+use std::{collections::BTreeMap, time::Duration};
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db prune-status` command
+pub struct Command {
+    /// The prune configuration of the node, which takes precedence over the config file.
+    #[command(flatten)]
+    pruning: PruningArgs,
+}
+
+impl Command {
+    /// Execute `db prune-status` command
+    pub fn execute<N: ProviderNodeTypes<ChainSpec: EthereumHardforks>>(
+        self,
+        provider_factory: ProviderFactory<N>,
+        prune_config: Option<PruneConfig>,
+    ) -> eyre::Result<()> {
+        // the modes are resolved like on node launch
+        let chain_spec = provider_factory.chain_spec();
+        let prune_modes = match self.pruning.prune_config(&*chain_spec) {
+            Some(mut config) => {
+                config.merge(prune_config);
+                config.segments
+            }
+            None => prune_config.unwrap_or_default().segments,
+        };
+
+        let tip = provider_factory.best_block_number()?;
+        let checkpoints =
+            provider_factory.get_prune_checkpoints()?.into_iter().collect::<BTreeMap<_, _>>();
+        let runs = provider_factory.get_prune_runs()?.into_iter().collect::<BTreeMap<_, _>>();
+
+        let mut segments = configured_modes(&prune_modes).collect::<BTreeMap<_, _>>();
+        for segment in checkpoints.keys().chain(runs.keys()) {
+            segments.entry(*segment).or_insert(None);
+        }
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header([
+            "Segment",
+            "Configured Mode",
+            "Pruned Up To",
+            "Lag",
+            "Last Run Tip",
+            "Last Run Duration",
+            "Deleted Entries",
+            "Finished",
+        ]);
+
+        for (segment, mode) in segments {
+            let pruned_block = checkpoints.get(&segment).and_then(|c| c.block_number);
+            let run = runs.get(&segment);
+
+            let mut row = Row::new();
+            row.add_cell(Cell::new(segment))
+                .add_cell(Cell::new(or_dash(mode)))
+                .add_cell(Cell::new(or_dash(pruned_block)))
+                .add_cell(Cell::new(or_dash(pruned_block.map(|block| tip.saturating_sub(block)))))
+                .add_cell(Cell::new(or_dash(run.map(|run| run.tip_block_number))))
+                .add_cell(Cell::new(or_dash(
+                    run.map(|run| format!("{:?}", Duration::from_millis(run.elapsed_ms))),
+                )))
+                .add_cell(Cell::new(or_dash(run.map(|run| run.pruned))))
+                .add_cell(Cell::new(or_dash(run.map(|run| run.finished))));
+            table.add_row(row);
+        }
+
+        println!("Tip block number: {tip}");
+        println!("{table}");
+
+        Ok(())
+    }
+}
+
+/// Returns the formatted prune modes of the configured segments.
+///
+/// The receipts log filter is shown as the mode of the contract logs segment, with the mode of
+/// every address.
+fn configured_modes(modes: &PruneModes) -> impl Iterator<Item = (PruneSegment, Option<String>)> {
+    let receipts_log_filter = (!modes.receipts_log_filter.is_empty()).then(|| {
+        modes
+            .receipts_log_filter
+            .iter()
+            .map(|(address, mode)| format!("{address}: {mode:?}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    });
+
+    [
+        (PruneSegment::SenderRecovery, modes.sender_recovery),
+        (PruneSegment::TransactionLookup, modes.transaction_lookup),
+        (PruneSegment::Receipts, modes.receipts),
+        (PruneSegment::AccountHistory, modes.account_history),
+        (PruneSegment::StorageHistory, modes.storage_history),
+        (PruneSegment::Transactions, modes.bodies_history),
+    ]
+    .into_iter()
+    .filter_map(|(segment, mode)| Some((segment, Some(format!("{:?}", mode?)))))
+    .chain(receipts_log_filter.map(|filter| (PruneSegment::ContractLogs, Some(filter))))
+}
+
+fn or_dash(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
@@ -0,0 +1,132 @@
+use clap::Parser;
+use reth_db::lockfile::LOCKFILE_NAME;
+use reth_fs_util::FsPathError;
+use reth_node_builder::NodeTypesWithDB;
+use reth_provider::{providers::StaticFileProvider, ProviderFactory, StaticFileProviderFactory};
+use std::{fs::File, io::ErrorKind, path::PathBuf};
+use tracing::info;
+
+/// The arguments for the `reth db relocate-static-files` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to move the static files to. It must be empty or not exist yet.
+    #[arg(long, value_name = "PATH")]
+    to: PathBuf,
+}
+
+impl Command {
+    /// Execute `db relocate-static-files` command
+    pub fn execute<N: NodeTypesWithDB>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let static_file_provider = provider_factory.static_file_provider();
+        let from = static_file_provider.directory().to_path_buf();
+        let to = self.to;
+
+        eyre::ensure!(
+            reth_fs_util::read_dir(&from)?.next().is_some(),
+            "No static files found at {from:?}"
+        );
+        if to.exists() {
+            eyre::ensure!(
+                reth_fs_util::read_dir(&to)?.next().is_none(),
+                "Target directory {to:?} is not empty"
+            );
+        }
+        reth_fs_util::create_dir_all(&to)?;
+
+        let mut files = Vec::new();
+        for entry in reth_fs_util::read_dir(&from)? {
+            let entry = entry?;
+            // The storage lock belongs to the running command and is removed when it's released.
+            if entry.file_type()?.is_file() && entry.file_name() != LOCKFILE_NAME {
+                files.push(entry.path());
+            }
+        }
+
+        // Copy every file and make sure it reached the disk before touching the originals.
+        for source in &files {
+            let target = to.join(source.file_name().expect("read from a directory"));
+            let copied = std::fs::copy(source, &target)?;
+            File::open(&target)?.sync_all()?;
+
+            let expected = reth_fs_util::metadata(source)?.len();
+            eyre::ensure!(
+                copied == expected && reth_fs_util::metadata(&target)?.len() == expected,
+                "Copy of {source:?} to {target:?} is incomplete"
+            );
+            info!(target: "reth::cli", ?source, ?target, size = expected, "Copied static file");
+        }
+
+        // The relocated segments need to cover the same blocks as the original ones.
+        let expected = static_file_provider.get_highest_static_files();
+        let relocated = StaticFileProvider::<N::Primitives>::read_only(&to, false)?;
+        let actual = relocated.get_highest_static_files();
+        eyre::ensure!(
+            actual == expected,
+            "Relocated static files {actual:?} don't match the original ones {expected:?}"
+        );
+        drop(relocated);
+        drop(static_file_provider);
+        drop(provider_factory);
+
+        for source in &files {
+            match std::fs::remove_file(source) {
+                Err(err) if err.kind() != ErrorKind::NotFound => {
+                    return Err(FsPathError::remove_file(err, source).into())
+                }
+                _ => {}
+            }
+        }
+
+        println!("Moved {} static files from {} to {}.", files.len(), from.display(), to.display());
+        println!(
+            "Start reth with `--datadir.static-files {}`, or set `static_files.path` in the \
+             configuration file.",
+            to.display()
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_static_file_types::StaticFileSegment;
+
+    #[test]
+    fn relocates_static_files() {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+
+        let from = provider_factory.static_file_provider().directory().to_path_buf();
+        let to = tempfile::tempdir().unwrap().keep().join("static_files");
+        let mut expected = reth_fs_util::read_dir(&from)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name != LOCKFILE_NAME)
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+        assert!(!expected.is_empty());
+
+        Command { to: to.clone() }.execute(provider_factory).unwrap();
+
+        let mut relocated = reth_fs_util::read_dir(&to)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        relocated.sort_unstable();
+        assert_eq!(relocated, expected);
+        assert!(reth_fs_util::read_dir(&from)
+            .unwrap()
+            .all(|entry| entry.unwrap().file_name() == LOCKFILE_NAME));
+
+        let provider = StaticFileProvider::<EthPrimitives>::read_only(&to, false).unwrap();
+        assert_eq!(provider.get_highest_static_file_block(StaticFileSegment::Headers), Some(0));
+    }
+}
@@ -6,7 +6,9 @@ use human_bytes::human_bytes;
 use itertools::Itertools;
 use reth_chainspec::EthereumHardforks;
 use reth_db::{mdbx, static_file::iter_static_files, DatabaseEnv};
-use reth_db_api::{database::Database, TableViewer, Tables};
+use reth_db_api::{
+    cursor::DbCursorRO, database::Database, tables, transaction::DbTx, TableViewer, Tables,
+};
 use reth_db_common::DbTool;
 use reth_fs_util as fs;
 use reth_node_builder::{NodePrimitives, NodeTypesWithDB, NodeTypesWithDBAdapter};
@@ -34,6 +36,13 @@ pub struct Command {
     /// For individual table checksums, use the `reth db checksum` command.
     #[arg(long, default_value_t = false)]
     checksum: bool,
+
+    /// Show the entry counts and sizes of the tables as tracked by write transactions, instead of
+    /// reading them from the database.
+    ///
+    /// Tables that haven't been modified since the tracking was introduced are not shown.
+    #[arg(long, default_value_t = false)]
+    tracked: bool,
 }
 
 impl Command {
@@ -54,7 +63,8 @@ impl Command {
 
         println!("\n");
 
-        let db_stats_table = self.db_stats_table(tool)?;
+        let db_stats_table =
+            if self.tracked { self.tracked_stats_table(tool)? } else { self.db_stats_table(tool)? };
         println!("{db_stats_table}");
 
         Ok(())
@@ -143,6 +153,44 @@ impl Command {
         Ok(table)
     }
 
+    fn tracked_stats_table<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
+        &self,
+        tool: &DbTool<N>,
+    ) -> eyre::Result<ComfyTable> {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Table Name", "# Entries", "Total Size"]);
+
+        let stats = tool.provider_factory.db_ref().view(|tx| {
+            tx.cursor_read::<tables::TableStats>()?.walk(None)?.collect::<Result<Vec<_>, _>>()
+        })??;
+
+        let mut total_size = 0;
+        for (db_table, stat) in stats {
+            total_size += stat.size_bytes;
+            let mut row = Row::new();
+            row.add_cell(Cell::new(db_table))
+                .add_cell(Cell::new(stat.entries))
+                .add_cell(Cell::new(human_bytes(stat.size_bytes as f64)));
+            table.add_row(row);
+        }
+
+        let max_widths = table.column_max_content_widths();
+        let mut separator = Row::new();
+        for width in max_widths {
+            separator.add_cell(Cell::new("-".repeat(width as usize)));
+        }
+        table.add_row(separator);
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new("Tables"))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(human_bytes(total_size as f64)));
+        table.add_row(row);
+
+        Ok(table)
+    }
+
     fn static_files_stats_table<N: NodePrimitives>(
         &self,
         data_dir: ChainPath<DataDirPath>,
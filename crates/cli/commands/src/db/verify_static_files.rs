@@ -0,0 +1,45 @@
+use clap::Parser;
+use reth_node_builder::NodeTypesWithDB;
+use reth_provider::{
+    providers::StaticFileVerification, ProviderFactory, StaticFileProviderFactory,
+};
+
+/// The arguments for the `reth db verify-static-files` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only lists the finalized static files that have not been verified yet, without verifying
+    /// them.
+    #[arg(long)]
+    pub(crate) list: bool,
+}
+
+impl Command {
+    /// Execute `db verify-static-files` command
+    pub fn execute<N: NodeTypesWithDB>(
+        self,
+        provider_factory: ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let static_file_provider = provider_factory.static_file_provider();
+
+        if self.list {
+            let unverified = static_file_provider.unverified_static_files()?;
+            for (segment, range) in &unverified {
+                println!("{segment} {range}");
+            }
+            println!("{} unverified static files", unverified.len());
+            return Ok(())
+        }
+
+        let mut corrupted = 0;
+        for (segment, range) in static_file_provider.finalized_static_files()? {
+            let status = static_file_provider.verify_static_file(segment, range)?;
+            if status == StaticFileVerification::Corrupted {
+                corrupted += 1;
+            }
+            println!("{segment} {range}: {}", status.as_str());
+        }
+
+        eyre::ensure!(corrupted == 0, "{corrupted} corrupted static files");
+        Ok(())
+    }
+}
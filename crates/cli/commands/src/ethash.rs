@@ -0,0 +1,443 @@
+//! Ethash proof-of-work seal verification for pre-merge blocks.
+//!
+//! Regular sync trusts the proof-of-work of historical headers, since they are anchored by the
+//! merge block. The [`EthashVerifier`] recomputes the seals with the light verification method, so
+//! that imported pre-merge history can be validated end-to-end.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{keccak256, B256, U256};
+use alloy_rlp::Encodable;
+use parking_lot::Mutex;
+use rayon::prelude::*;
+use sha3::{Digest, Keccak512};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, OnceLock},
+};
+use tracing::debug;
+
+/// Number of blocks per ethash epoch.
+pub const EPOCH_LENGTH: u64 = 30_000;
+
+const WORD_BYTES: usize = 4;
+const HASH_BYTES: usize = 64;
+const HASH_WORDS: usize = HASH_BYTES / WORD_BYTES;
+const MIX_BYTES: usize = 128;
+const MIX_WORDS: usize = MIX_BYTES / WORD_BYTES;
+const DATASET_BYTES_INIT: u64 = 1 << 30;
+const DATASET_BYTES_GROWTH: u64 = 1 << 23;
+const CACHE_BYTES_INIT: u64 = 1 << 24;
+const CACHE_BYTES_GROWTH: u64 = 1 << 17;
+const CACHE_ROUNDS: usize = 3;
+const DATASET_PARENTS: u32 = 256;
+const ACCESSES: u32 = 64;
+
+/// A 64 byte node of the cache or the dataset, as little endian words.
+type Node = [u32; HASH_WORDS];
+
+/// Errors of the proof-of-work seal verification.
+#[derive(Debug, thiserror::Error)]
+pub enum EthashError {
+    /// The header has a difficulty but no mix hash or nonce.
+    #[error("block {number} has no proof-of-work seal")]
+    MissingSeal {
+        /// The block number.
+        number: u64,
+    },
+    /// The mix hash of the header doesn't match the computed one.
+    #[error("block {number} has an invalid mix hash: got {got}, expected {expected}")]
+    MixHashMismatch {
+        /// The block number.
+        number: u64,
+        /// The mix hash of the header.
+        got: B256,
+        /// The computed mix hash.
+        expected: B256,
+    },
+    /// The seal doesn't satisfy the difficulty of the header.
+    #[error("block {number} has insufficient proof-of-work for difficulty {difficulty}")]
+    InsufficientWork {
+        /// The block number.
+        number: u64,
+        /// The difficulty of the header.
+        difficulty: U256,
+    },
+}
+
+/// Verifies ethash proof-of-work seals of pre-merge headers.
+///
+/// Verification uses the light method, which only requires the cache of the epoch instead of the
+/// full dataset. Caches are generated on demand, and the most recently used ones are kept in
+/// memory, since each of them takes several seconds to generate and 16 MiB or more of memory.
+#[derive(Debug)]
+pub struct EthashVerifier {
+    /// Maximum number of caches kept in memory.
+    max_caches: usize,
+    /// The cached epochs, ordered from least to most recently used.
+    caches: Mutex<VecDeque<(u64, Arc<OnceLock<Arc<EpochCache>>>)>>,
+}
+
+impl Default for EthashVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EthashVerifier {
+    /// Default number of caches kept in memory.
+    pub const DEFAULT_MAX_CACHES: usize = 3;
+
+    /// Creates a new verifier that keeps [`Self::DEFAULT_MAX_CACHES`] caches in memory.
+    pub fn new() -> Self {
+        Self { max_caches: Self::DEFAULT_MAX_CACHES, caches: Mutex::new(VecDeque::new()) }
+    }
+
+    /// Sets the maximum number of caches kept in memory.
+    pub fn with_max_caches(mut self, max_caches: usize) -> Self {
+        self.max_caches = max_caches.max(1);
+        self
+    }
+
+    /// Verifies the proof-of-work seal of a single header.
+    ///
+    /// Headers without difficulty are post-merge and always pass.
+    pub fn verify_header<H: BlockHeader>(&self, header: &H) -> Result<(), EthashError> {
+        if header.difficulty().is_zero() {
+            return Ok(())
+        }
+        self.cache(header.number() / EPOCH_LENGTH).verify(header)
+    }
+
+    /// Verifies the proof-of-work seals of the given headers, and returns the number of verified
+    /// pre-merge headers.
+    ///
+    /// Headers are grouped by epoch, and the headers of each epoch are verified in parallel, so
+    /// that only a single cache is generated at a time.
+    pub fn verify_headers<H: BlockHeader + Sync>(
+        &self,
+        headers: &[H],
+    ) -> Result<usize, EthashError> {
+        let mut epochs = BTreeMap::<u64, Vec<&H>>::new();
+        for header in headers.iter().filter(|header| !header.difficulty().is_zero()) {
+            epochs.entry(header.number() / EPOCH_LENGTH).or_default().push(header);
+        }
+
+        let mut verified = 0;
+        for (epoch, headers) in epochs {
+            let cache = self.cache(epoch);
+            headers.par_iter().try_for_each(|header| cache.verify(*header))?;
+            verified += headers.len();
+        }
+        Ok(verified)
+    }
+
+    /// Returns the cache of the given epoch, generating it if it isn't in memory.
+    fn cache(&self, epoch: u64) -> Arc<EpochCache> {
+        let cache = {
+            let mut caches = self.caches.lock();
+            if let Some(position) = caches.iter().position(|(cached, _)| *cached == epoch) {
+                let entry = caches.remove(position).expect("position exists");
+                caches.push_back(entry);
+            } else {
+                caches.push_back((epoch, Arc::default()));
+                while caches.len() > self.max_caches {
+                    caches.pop_front();
+                }
+            }
+            caches.back().expect("not empty").1.clone()
+        };
+
+        // generate outside of the lock, concurrent callers for the same epoch wait for it
+        cache
+            .get_or_init(|| {
+                debug!(target: "reth::cli::ethash", epoch, "Generating ethash cache");
+                Arc::new(EpochCache::new(epoch))
+            })
+            .clone()
+    }
+}
+
+/// The light verification cache of a single epoch.
+#[derive(Debug)]
+struct EpochCache {
+    /// The cache nodes.
+    nodes: Vec<Node>,
+    /// Size of the full dataset of the epoch in bytes.
+    full_size: u64,
+}
+
+impl EpochCache {
+    /// Generates the cache of the given epoch.
+    fn new(epoch: u64) -> Self {
+        Self {
+            nodes: make_cache(cache_size(epoch), seed_hash(epoch)),
+            full_size: dataset_size(epoch),
+        }
+    }
+
+    /// Verifies the seal of the header against this cache.
+    fn verify<H: BlockHeader>(&self, header: &H) -> Result<(), EthashError> {
+        let number = header.number();
+        let (Some(mix_hash), Some(nonce)) = (header.mix_hash(), header.nonce()) else {
+            return Err(EthashError::MissingSeal { number })
+        };
+
+        let (digest, result) = hashimoto_light(
+            &self.nodes,
+            self.full_size,
+            seal_hash(header),
+            u64::from_be_bytes(nonce.0),
+        );
+        if digest != mix_hash {
+            return Err(EthashError::MixHashMismatch { number, got: mix_hash, expected: digest })
+        }
+
+        let difficulty = header.difficulty();
+        if U256::from_be_bytes(result.0) > target(difficulty) {
+            return Err(EthashError::InsufficientWork { number, difficulty })
+        }
+        Ok(())
+    }
+}
+
+/// Returns the hash of the header without its seal, which is the input of the proof-of-work.
+fn seal_hash<H: BlockHeader>(header: &H) -> B256 {
+    let mut payload = Vec::new();
+    header.parent_hash().encode(&mut payload);
+    header.ommers_hash().encode(&mut payload);
+    header.beneficiary().encode(&mut payload);
+    header.state_root().encode(&mut payload);
+    header.transactions_root().encode(&mut payload);
+    header.receipts_root().encode(&mut payload);
+    header.logs_bloom().encode(&mut payload);
+    header.difficulty().encode(&mut payload);
+    header.number().encode(&mut payload);
+    header.gas_limit().encode(&mut payload);
+    header.gas_used().encode(&mut payload);
+    header.timestamp().encode(&mut payload);
+    header.extra_data().encode(&mut payload);
+    // london blocks were still mined before the merge
+    if let Some(base_fee) = header.base_fee_per_gas() {
+        base_fee.encode(&mut payload);
+    }
+
+    let mut out = Vec::with_capacity(payload.len() + alloy_rlp::length_of_length(payload.len()));
+    alloy_rlp::Header { list: true, payload_length: payload.len() }.encode(&mut out);
+    out.extend_from_slice(&payload);
+    keccak256(out)
+}
+
+/// Returns the largest proof-of-work result that satisfies the difficulty, i.e. `2^256 /
+/// difficulty`.
+fn target(difficulty: U256) -> U256 {
+    let target = U256::MAX / difficulty;
+    // `U256::MAX` is one less than `2^256`, which only makes a difference for exact divisors
+    if difficulty.is_power_of_two() && difficulty > U256::from(1) {
+        target + U256::from(1)
+    } else {
+        target
+    }
+}
+
+/// Returns the size of the verification cache of the epoch in bytes.
+fn cache_size(epoch: u64) -> u64 {
+    let mut size = CACHE_BYTES_INIT + CACHE_BYTES_GROWTH * epoch - HASH_BYTES as u64;
+    while !is_prime(size / HASH_BYTES as u64) {
+        size -= 2 * HASH_BYTES as u64;
+    }
+    size
+}
+
+/// Returns the size of the full dataset of the epoch in bytes.
+fn dataset_size(epoch: u64) -> u64 {
+    let mut size = DATASET_BYTES_INIT + DATASET_BYTES_GROWTH * epoch - MIX_BYTES as u64;
+    while !is_prime(size / MIX_BYTES as u64) {
+        size -= 2 * MIX_BYTES as u64;
+    }
+    size
+}
+
+/// Returns the seed of the epoch, which is the keccak256 hash of zeros applied once per epoch.
+fn seed_hash(epoch: u64) -> B256 {
+    (0..epoch).fold(B256::ZERO, |seed, _| keccak256(seed))
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2 && (2..).take_while(|i| i * i <= n).all(|i| n % i != 0)
+}
+
+/// The FNV-like mixing function of ethash.
+const fn fnv(a: u32, b: u32) -> u32 {
+    a.wrapping_mul(0x01000193) ^ b
+}
+
+fn keccak512(data: &[u8]) -> Node {
+    let hash = Keccak512::digest(data);
+    let mut node = [0; HASH_WORDS];
+    for (word, bytes) in node.iter_mut().zip(hash.chunks_exact(WORD_BYTES)) {
+        *word = u32::from_le_bytes(bytes.try_into().expect("4 bytes"));
+    }
+    node
+}
+
+fn node_bytes(node: &Node) -> [u8; HASH_BYTES] {
+    let mut bytes = [0; HASH_BYTES];
+    for (chunk, word) in bytes.chunks_exact_mut(WORD_BYTES).zip(node) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+/// Generates the verification cache with the given size from the seed.
+fn make_cache(size: u64, seed: B256) -> Vec<Node> {
+    let n = (size / HASH_BYTES as u64) as usize;
+
+    let mut nodes = Vec::with_capacity(n);
+    nodes.push(keccak512(seed.as_slice()));
+    for i in 1..n {
+        let node = keccak512(&node_bytes(&nodes[i - 1]));
+        nodes.push(node);
+    }
+
+    for _ in 0..CACHE_ROUNDS {
+        for i in 0..n {
+            let v = nodes[i][0] as usize % n;
+            let mut mixed = nodes[(i + n - 1) % n];
+            for (word, other) in mixed.iter_mut().zip(&nodes[v]) {
+                *word ^= other;
+            }
+            nodes[i] = keccak512(&node_bytes(&mixed));
+        }
+    }
+    nodes
+}
+
+/// Computes a single node of the full dataset from the cache.
+fn dataset_item(cache: &[Node], index: u32) -> Node {
+    let n = cache.len();
+    let mut mix = cache[index as usize % n];
+    mix[0] ^= index;
+    mix = keccak512(&node_bytes(&mix));
+
+    for j in 0..DATASET_PARENTS {
+        let parent = fnv(index ^ j, mix[j as usize % HASH_WORDS]) as usize % n;
+        for (word, other) in mix.iter_mut().zip(&cache[parent]) {
+            *word = fnv(*word, *other);
+        }
+    }
+    keccak512(&node_bytes(&mix))
+}
+
+/// Runs the hashimoto algorithm with dataset nodes computed from the cache, and returns the mix
+/// digest and the proof-of-work result.
+fn hashimoto_light(cache: &[Node], full_size: u64, seal_hash: B256, nonce: u64) -> (B256, B256) {
+    let mut seed = [0; 40];
+    seed[..32].copy_from_slice(seal_hash.as_slice());
+    seed[32..].copy_from_slice(&nonce.to_le_bytes());
+    let seed = keccak512(&seed);
+
+    let mut mix = [0u32; MIX_WORDS];
+    for (i, word) in mix.iter_mut().enumerate() {
+        *word = seed[i % HASH_WORDS];
+    }
+
+    let rows = (full_size / MIX_BYTES as u64) as u32;
+    for i in 0..ACCESSES {
+        let parent = fnv(i ^ seed[0], mix[i as usize % MIX_WORDS]) % rows;
+        for (j, chunk) in mix.chunks_exact_mut(HASH_WORDS).enumerate() {
+            let item = dataset_item(cache, 2 * parent + j as u32);
+            for (word, other) in chunk.iter_mut().zip(&item) {
+                *word = fnv(*word, *other);
+            }
+        }
+    }
+
+    let mut digest = [0; 32];
+    for (i, chunk) in mix.chunks_exact(4).enumerate() {
+        let word = fnv(fnv(fnv(chunk[0], chunk[1]), chunk[2]), chunk[3]);
+        digest[i * WORD_BYTES..(i + 1) * WORD_BYTES].copy_from_slice(&word.to_le_bytes());
+    }
+
+    let mut result = [0; HASH_BYTES + 32];
+    result[..HASH_BYTES].copy_from_slice(&node_bytes(&seed));
+    result[HASH_BYTES..].copy_from_slice(&digest);
+    (B256::from(digest), keccak256(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
+    use alloy_primitives::{address, b256, b64, bytes};
+
+    /// Mainnet block 1.
+    fn block_1() -> Header {
+        Header {
+            parent_hash: b256!(
+                "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+            ),
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            beneficiary: address!("0x05a56e2d52c817161883f50c441c3228cfe54d9f"),
+            state_root: b256!("0xd67e4d450343046425ae4271474353857ab860dbc0a1dde64b41b5cd3a532bf3"),
+            transactions_root: EMPTY_ROOT_HASH,
+            receipts_root: EMPTY_ROOT_HASH,
+            difficulty: U256::from(0x3ff800000u64),
+            number: 1,
+            gas_limit: 5000,
+            timestamp: 1438269988,
+            extra_data: bytes!("476574682f76312e302e302f6c696e75782f676f312e342e32"),
+            mix_hash: b256!("0x969b900de27b6ac6a67742365dd65f55a0526c41fd18e1b16f1a1215c2e66f59"),
+            nonce: b64!("0x539bd4979fef1ec4"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn epoch_sizes() {
+        assert_eq!(cache_size(0), 16776896);
+        assert_eq!(dataset_size(0), 1073739904);
+        assert_eq!(cache_size(1), 16907456);
+        assert_eq!(dataset_size(1), 1082130304);
+        assert_eq!(
+            seed_hash(1),
+            b256!("0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563")
+        );
+    }
+
+    #[test]
+    fn target_of_difficulty() {
+        assert_eq!(target(U256::from(1)), U256::MAX);
+        assert_eq!(target(U256::from(2)), U256::from(1) << 255);
+        assert_eq!(target(U256::from(3)), U256::MAX / U256::from(3));
+    }
+
+    #[test]
+    fn verify_mainnet_seal() {
+        let header = block_1();
+        assert_eq!(
+            header.hash_slow(),
+            b256!("0x88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6")
+        );
+
+        let verifier = EthashVerifier::new().with_max_caches(1);
+        assert_eq!(verifier.verify_headers(&[header.clone()]).unwrap(), 1);
+
+        // post-merge headers are skipped
+        let post_merge = Header { difficulty: U256::ZERO, ..Default::default() };
+        assert_eq!(verifier.verify_headers(&[post_merge]).unwrap(), 0);
+
+        let mut wrong_nonce = header.clone();
+        wrong_nonce.nonce = b64!("0x539bd4979fef1ec5");
+        assert!(matches!(
+            verifier.verify_header(&wrong_nonce),
+            Err(EthashError::MixHashMismatch { number: 1, .. })
+        ));
+
+        let mut wrong_mix_hash = header;
+        wrong_mix_hash.mix_hash = B256::ZERO;
+        assert!(matches!(
+            verifier.verify_header(&wrong_mix_hash),
+            Err(EthashError::MixHashMismatch { number: 1, .. })
+        ));
+    }
+}
@@ -26,6 +26,13 @@ pub struct ImportCommand<C: ChainSpecParser> {
     #[arg(long, value_name = "CHUNK_LEN", verbatim_doc_comment)]
     chunk_len: Option<u64>,
 
+    /// Verifies the proof-of-work seals of pre-merge blocks.
+    ///
+    /// Seals are recomputed with ethash light verification in parallel, which is considerably
+    /// slower than the regular import.
+    #[arg(long, verbatim_doc_comment)]
+    verify_pow: bool,
+
     /// The path to a block file for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
@@ -50,7 +57,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportComm
 
         let components = components(provider_factory.chain_spec());
 
-        let import_config = ImportConfig { no_state: self.no_state, chunk_len: self.chunk_len };
+        let import_config = ImportConfig {
+            no_state: self.no_state,
+            chunk_len: self.chunk_len,
+            verify_pow: self.verify_pow,
+        };
 
         let executor = components.evm_config().clone();
         let consensus = Arc::new(components.consensus().clone());
@@ -1,9 +1,10 @@
 //! Core import functionality without CLI dependencies.
 
-use alloy_primitives::B256;
+use crate::ethash::EthashVerifier;
+use alloy_primitives::{BlockNumber, B256};
 use futures::StreamExt;
 use reth_config::Config;
-use reth_consensus::FullConsensus;
+use reth_consensus::{Consensus, ConsensusError, FullConsensus};
 use reth_db_api::{tables, transaction::DbTx};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -15,8 +16,9 @@ use reth_network_p2p::{
     bodies::downloader::BodyDownloader,
     headers::downloader::{HeaderDownloader, SyncTarget},
 };
-use reth_node_api::BlockTy;
+use reth_node_api::{BlockTy, HeaderTy};
 use reth_node_events::node::NodeEvent;
+use reth_primitives_traits::SealedHeader;
 use reth_provider::{
     providers::ProviderNodeTypes, BlockNumReader, HeaderProvider, ProviderError, ProviderFactory,
     StageCheckpointReader,
@@ -24,7 +26,7 @@ use reth_provider::{
 use reth_prune::PruneModes;
 use reth_stages::{prelude::*, Pipeline, StageId, StageSet};
 use reth_static_file::StaticFileProducer;
-use std::{path::Path, sync::Arc};
+use std::{future::Future, ops::RangeInclusive, path::Path, sync::Arc};
 use tokio::sync::watch;
 use tracing::{debug, error, info};
 
@@ -35,6 +37,8 @@ pub struct ImportConfig {
     pub no_state: bool,
     /// Chunk byte length to read from file.
     pub chunk_len: Option<u64>,
+    /// Verifies the proof-of-work seals of pre-merge blocks before importing them.
+    pub verify_pow: bool,
 }
 
 /// Result of an import operation.
@@ -44,6 +48,8 @@ pub struct ImportResult {
     pub total_decoded_blocks: usize,
     /// Total number of transactions decoded from the file.
     pub total_decoded_txns: usize,
+    /// Total number of decoded transactions that were filtered out by the importer.
+    pub total_filtered_txns: usize,
     /// Total number of blocks imported into the database.
     pub total_imported_blocks: usize,
     /// Total number of transactions imported into the database.
@@ -54,7 +60,90 @@ impl ImportResult {
     /// Returns true if all blocks and transactions were imported successfully.
     pub fn is_complete(&self) -> bool {
         self.total_decoded_blocks == self.total_imported_blocks &&
-            self.total_decoded_txns == self.total_imported_txns
+            self.total_decoded_txns == self.total_imported_txns + self.total_filtered_txns
+    }
+}
+
+/// A source of blocks that can be imported with [`import_blocks`].
+///
+/// Implementations decode blocks from a chain export, for example an RLP file or a dump of
+/// another client, and can customize how the decoded blocks are imported. The pipeline, the
+/// progress reporting and the consistency checks of the import are shared by all importers.
+pub trait ChainImporter<N: ProviderNodeTypes>: Send {
+    /// Decodes the next chunk of blocks, which must be on top of the given parent header.
+    ///
+    /// Returns `None` if all blocks were decoded.
+    fn next_chunk(
+        &mut self,
+        consensus: Arc<dyn Consensus<BlockTy<N>, Error = ConsensusError>>,
+        parent: SealedHeader<HeaderTy<N>>,
+    ) -> impl Future<Output = eyre::Result<Option<FileClient<BlockTy<N>>>>> + Send;
+
+    /// Returns `true` if the stages that require state should be disabled, for chain segments
+    /// whose state transition can't be executed by reth.
+    fn no_state(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the proof-of-work seals of the blocks should be verified before they are
+    /// imported.
+    fn verify_pow(&self) -> bool {
+        false
+    }
+
+    /// Invoked with every decoded chunk before it's imported, for example to remove transactions
+    /// that must not be imported.
+    ///
+    /// Returns the number of transactions that were removed from the chunk.
+    fn on_chunk(&mut self, _file_client: &mut FileClient<BlockTy<N>>) -> eyre::Result<usize> {
+        Ok(0)
+    }
+
+    /// Invoked after a chunk was imported with the range of imported blocks, for example to
+    /// backfill the receipts of the blocks from another source.
+    fn on_chunk_imported(
+        &mut self,
+        _provider_factory: &ProviderFactory<N>,
+        _blocks: RangeInclusive<BlockNumber>,
+    ) -> eyre::Result<()> {
+        Ok(())
+    }
+}
+
+/// A [`ChainImporter`] for files of RLP-encoded blocks.
+#[derive(Debug)]
+pub struct RlpFileImporter {
+    reader: ChunkedFileReader,
+    import_config: ImportConfig,
+}
+
+impl RlpFileImporter {
+    /// Opens the file at the given path.
+    pub async fn new(path: &Path, import_config: ImportConfig) -> eyre::Result<Self> {
+        debug!(target: "reth::import",
+            chunk_byte_len=import_config.chunk_len.unwrap_or(DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE),
+            "Chunking chain import"
+        );
+        let reader = ChunkedFileReader::new(path, import_config.chunk_len).await?;
+        Ok(Self { reader, import_config })
+    }
+}
+
+impl<N: ProviderNodeTypes> ChainImporter<N> for RlpFileImporter {
+    async fn next_chunk(
+        &mut self,
+        consensus: Arc<dyn Consensus<BlockTy<N>, Error = ConsensusError>>,
+        parent: SealedHeader<HeaderTy<N>>,
+    ) -> eyre::Result<Option<FileClient<BlockTy<N>>>> {
+        Ok(self.reader.next_chunk(consensus, Some(parent)).await?)
+    }
+
+    fn no_state(&self) -> bool {
+        self.import_config.no_state
+    }
+
+    fn verify_pow(&self) -> bool {
+        self.import_config.verify_pow
     }
 }
 
@@ -76,30 +165,47 @@ pub async fn import_blocks_from_file<N>(
 where
     N: ProviderNodeTypes,
 {
-    if import_config.no_state {
+    let importer = RlpFileImporter::new(path, import_config).await?;
+    import_blocks(importer, provider_factory, config, executor, consensus).await
+}
+
+/// Imports the blocks of the given [`ChainImporter`] into the database.
+///
+/// The blocks are imported chunk by chunk using the pipeline infrastructure.
+pub async fn import_blocks<N, I>(
+    mut importer: I,
+    provider_factory: ProviderFactory<N>,
+    config: &Config,
+    executor: impl ConfigureEvm<Primitives = N::Primitives> + 'static,
+    consensus: Arc<
+        impl FullConsensus<N::Primitives, Error = reth_consensus::ConsensusError> + 'static,
+    >,
+) -> eyre::Result<ImportResult>
+where
+    N: ProviderNodeTypes,
+    I: ChainImporter<N>,
+{
+    let no_state = importer.no_state();
+    if no_state {
         info!(target: "reth::import", "Disabled stages requiring state");
     }
 
-    debug!(target: "reth::import",
-        chunk_byte_len=import_config.chunk_len.unwrap_or(DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE),
-        "Chunking chain import"
-    );
-
     info!(target: "reth::import", "Consensus engine initialized");
 
-    // open file
-    let mut reader = ChunkedFileReader::new(path, import_config.chunk_len).await?;
+    let pow_verifier = importer.verify_pow().then(|| {
+        info!(target: "reth::import", "Enabled proof-of-work seal verification");
+        Arc::new(EthashVerifier::new())
+    });
 
     let mut total_decoded_blocks = 0;
     let mut total_decoded_txns = 0;
+    let mut total_filtered_txns = 0;
 
     let mut sealed_header = provider_factory
         .sealed_header(provider_factory.last_block_number()?)?
         .expect("should have genesis");
 
-    while let Some(file_client) =
-        reader.next_chunk::<BlockTy<N>>(consensus.clone(), Some(sealed_header)).await?
-    {
+    while let Some(mut file_client) = importer.next_chunk(consensus.clone(), sealed_header).await? {
         // create a new FileClient from chunk read from file
         info!(target: "reth::import",
             "Importing chain file chunk"
@@ -110,6 +216,15 @@ where
 
         total_decoded_blocks += file_client.headers_len();
         total_decoded_txns += file_client.total_transactions();
+        total_filtered_txns += importer.on_chunk(&mut file_client)?;
+        let chunk_blocks = file_client.min_block().zip(file_client.max_block());
+
+        if let Some(verifier) = pow_verifier.clone() {
+            let headers = file_client.headers_iter().cloned().collect::<Vec<_>>();
+            let verified =
+                tokio::task::spawn_blocking(move || verifier.verify_headers(&headers)).await??;
+            info!(target: "reth::import", verified, "Verified proof-of-work seals of chunk");
+        }
 
         let (mut pipeline, events) = build_import_pipeline_impl(
             config,
@@ -117,7 +232,7 @@ where
             &consensus,
             Arc::new(file_client),
             StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
-            import_config.no_state,
+            no_state,
             executor.clone(),
         )?;
 
@@ -141,9 +256,13 @@ where
             },
         }
 
-        sealed_header = provider_factory
-            .sealed_header(provider_factory.last_block_number()?)?
-            .expect("should have genesis");
+        let last_block_number = provider_factory.last_block_number()?;
+        if let Some((first, last)) = chunk_blocks {
+            importer.on_chunk_imported(&provider_factory, first..=last.min(last_block_number))?;
+        }
+
+        sealed_header =
+            provider_factory.sealed_header(last_block_number)?.expect("should have genesis");
     }
 
     let provider = provider_factory.provider()?;
@@ -154,6 +273,7 @@ where
     let result = ImportResult {
         total_decoded_blocks,
         total_decoded_txns,
+        total_filtered_txns,
         total_imported_blocks,
         total_imported_txns,
     };
@@ -163,6 +283,7 @@ where
             total_decoded_blocks,
             total_imported_blocks,
             total_decoded_txns,
+            total_filtered_txns,
             total_imported_txns,
             "Chain was partially imported"
         );
@@ -170,6 +291,7 @@ where
         info!(target: "reth::import",
             total_imported_blocks,
             total_imported_txns,
+            total_filtered_txns,
             "Chain file imported"
         );
     }
@@ -252,3 +374,80 @@ where
 
     Ok((pipeline, events))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_consensus::noop::NoopConsensus;
+    use reth_provider::test_utils::MockNodeTypesWithDB;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn import_result_counts_filtered_transactions() {
+        let result = ImportResult {
+            total_decoded_blocks: 2,
+            total_decoded_txns: 5,
+            total_filtered_txns: 1,
+            total_imported_blocks: 2,
+            total_imported_txns: 4,
+        };
+        assert!(result.is_complete());
+
+        let result = ImportResult { total_filtered_txns: 0, ..result };
+        assert!(!result.is_complete());
+    }
+
+    #[tokio::test]
+    async fn rlp_file_importer_decodes_blocks() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=3,
+            BlockRangeParams { tx_count: 0..3, ..Default::default() },
+        );
+
+        let mut encoded = Vec::new();
+        for block in &blocks[1..] {
+            block.clone_block().encode(&mut encoded);
+        }
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), encoded).unwrap();
+
+        let import_config = ImportConfig { no_state: true, ..Default::default() };
+        let mut importer = RlpFileImporter::new(file.path(), import_config).await.unwrap();
+        assert!(ChainImporter::<MockNodeTypesWithDB>::no_state(&importer));
+        assert!(!ChainImporter::<MockNodeTypesWithDB>::verify_pow(&importer));
+
+        let consensus = Arc::new(NoopConsensus::default());
+        let genesis = blocks[0].clone_sealed_header();
+        let mut file_client = ChainImporter::<MockNodeTypesWithDB>::next_chunk(
+            &mut importer,
+            consensus.clone(),
+            genesis,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(file_client.headers_len(), 3);
+        assert_eq!((file_client.min_block(), file_client.max_block()), (Some(1), Some(3)));
+        assert_eq!(file_client.tip(), Some(blocks[3].hash()));
+        assert_eq!(
+            file_client.total_transactions(),
+            blocks[1..].iter().map(|block| block.body().transactions.len()).sum::<usize>()
+        );
+
+        // the default hook doesn't filter any transactions
+        assert_eq!(
+            ChainImporter::<MockNodeTypesWithDB>::on_chunk(&mut importer, &mut file_client)
+                .unwrap(),
+            0
+        );
+
+        let parent = blocks[3].clone_sealed_header();
+        assert!(ChainImporter::<MockNodeTypesWithDB>::next_chunk(&mut importer, consensus, parent)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
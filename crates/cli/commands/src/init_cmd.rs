@@ -1,11 +1,15 @@
 //! Command that initializes the node from a genesis file.
 
 use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
 use clap::Parser;
-use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_chainspec::{
+    ChainSpec, EthChainSpec, EthereumHardforks, HOLESKY, HOODI, MAINNET, SEPOLIA,
+};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_provider::BlockHashReader;
-use std::sync::Arc;
+use std::{fmt, fmt::Write, str::FromStr, sync::Arc};
 use tracing::info;
 
 /// Initializes the database with the genesis block.
@@ -13,6 +17,14 @@ use tracing::info;
 pub struct InitCommand<C: ChainSpecParser> {
     #[command(flatten)]
     env: EnvironmentArgs<C>,
+
+    /// The expected genesis hash of the chain.
+    ///
+    /// Accepts either a hash or the name of a known network (mainnet, sepolia, holesky, hoodi).
+    /// If the genesis hash computed from the chain specification doesn't match, the command fails
+    /// before writing to the database and prints the genesis header fields that differ.
+    #[arg(long, value_name = "HASH_OR_NETWORK", value_parser = parse_expected_genesis_hash)]
+    expected_genesis_hash: Option<B256>,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitCommand<C> {
@@ -20,6 +32,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitComman
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
         info!(target: "reth::cli", "reth init starting");
 
+        if let Some(expected) = self.expected_genesis_hash {
+            verify_genesis_hash(self.env.chain.as_ref(), expected)?;
+            info!(target: "reth::cli", hash = ?expected, "Genesis hash matches the expected hash");
+        }
+
         let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
 
         let hash = provider_factory
@@ -37,3 +54,144 @@ impl<C: ChainSpecParser> InitCommand<C> {
         Some(&self.env.chain)
     }
 }
+
+/// Networks whose genesis can be used as the expected genesis.
+fn known_networks() -> [(&'static str, &'static Arc<ChainSpec>); 4] {
+    [("mainnet", &*MAINNET), ("sepolia", &*SEPOLIA), ("holesky", &*HOLESKY), ("hoodi", &*HOODI)]
+}
+
+/// Parses the expected genesis hash from either a hash or the name of a known network.
+fn parse_expected_genesis_hash(value: &str) -> eyre::Result<B256> {
+    if let Some((_, spec)) = known_networks().into_iter().find(|(name, _)| *name == value) {
+        return Ok(spec.genesis_hash())
+    }
+
+    B256::from_str(value).map_err(|_| {
+        let names = known_networks().map(|(name, _)| name).join(", ");
+        eyre::eyre!("expected a genesis hash or one of the known networks: {names}")
+    })
+}
+
+/// Verifies that the genesis hash of the chain matches the expected hash.
+///
+/// If the expected hash belongs to a known network, the returned error contains the genesis header
+/// fields that differ from the genesis header of that network.
+fn verify_genesis_hash<Spec: EthChainSpec>(chain: &Spec, expected: B256) -> eyre::Result<()> {
+    let computed = chain.genesis_hash();
+    if computed == expected {
+        return Ok(())
+    }
+
+    let mut message = format!("Genesis hash mismatch: expected {expected}, computed {computed}");
+    match known_networks().into_iter().find(|(_, spec)| spec.genesis_hash() == expected) {
+        Some((name, spec)) => {
+            let _ = write!(message, "\nGenesis header fields that differ from {name}:");
+            for diff in genesis_header_diff(spec.genesis_header(), chain.genesis_header()) {
+                let _ = write!(message, "\n  {diff}");
+            }
+        }
+        None => {
+            let _ = write!(message, "\nComputed genesis header: {:?}", chain.genesis_header());
+        }
+    }
+
+    Err(eyre::eyre!(message))
+}
+
+/// A genesis header field that differs between the expected and the computed genesis header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GenesisFieldDiff {
+    /// Name of the header field.
+    field: &'static str,
+    /// Value of the field in the expected genesis header.
+    expected: String,
+    /// Value of the field in the computed genesis header.
+    computed: String,
+}
+
+impl fmt::Display for GenesisFieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected {}, computed {}", self.field, self.expected, self.computed)
+    }
+}
+
+/// Returns the fields that differ between the expected and the computed genesis header.
+fn genesis_header_diff<H1: BlockHeader, H2: BlockHeader>(
+    expected: &H1,
+    computed: &H2,
+) -> Vec<GenesisFieldDiff> {
+    let mut diffs = Vec::new();
+    let mut compare = |field, expected: &dyn fmt::Debug, computed: &dyn fmt::Debug| {
+        let (expected, computed) = (format!("{expected:?}"), format!("{computed:?}"));
+        if expected != computed {
+            diffs.push(GenesisFieldDiff { field, expected, computed });
+        }
+    };
+
+    compare("parentHash", &expected.parent_hash(), &computed.parent_hash());
+    compare("ommersHash", &expected.ommers_hash(), &computed.ommers_hash());
+    compare("beneficiary", &expected.beneficiary(), &computed.beneficiary());
+    compare("stateRoot", &expected.state_root(), &computed.state_root());
+    compare("transactionsRoot", &expected.transactions_root(), &computed.transactions_root());
+    compare("receiptsRoot", &expected.receipts_root(), &computed.receipts_root());
+    compare("logsBloom", &expected.logs_bloom(), &computed.logs_bloom());
+    compare("difficulty", &expected.difficulty(), &computed.difficulty());
+    compare("number", &expected.number(), &computed.number());
+    compare("gasLimit", &expected.gas_limit(), &computed.gas_limit());
+    compare("gasUsed", &expected.gas_used(), &computed.gas_used());
+    compare("timestamp", &expected.timestamp(), &computed.timestamp());
+    compare("extraData", &expected.extra_data(), &computed.extra_data());
+    compare("mixHash", &expected.mix_hash(), &computed.mix_hash());
+    compare("nonce", &expected.nonce(), &computed.nonce());
+    compare("baseFeePerGas", &expected.base_fee_per_gas(), &computed.base_fee_per_gas());
+    compare("withdrawalsRoot", &expected.withdrawals_root(), &computed.withdrawals_root());
+    compare("blobGasUsed", &expected.blob_gas_used(), &computed.blob_gas_used());
+    compare("excessBlobGas", &expected.excess_blob_gas(), &computed.excess_blob_gas());
+    compare(
+        "parentBeaconBlockRoot",
+        &expected.parent_beacon_block_root(),
+        &computed.parent_beacon_block_root(),
+    );
+    compare("requestsHash", &expected.requests_hash(), &computed.requests_hash());
+
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::ChainSpecBuilder;
+
+    #[test]
+    fn parse_expected_genesis_hash_known_network() {
+        assert_eq!(parse_expected_genesis_hash("mainnet").unwrap(), MAINNET.genesis_hash());
+        assert_eq!(
+            parse_expected_genesis_hash(&SEPOLIA.genesis_hash().to_string()).unwrap(),
+            SEPOLIA.genesis_hash()
+        );
+        assert!(parse_expected_genesis_hash("unknown").is_err());
+    }
+
+    #[test]
+    fn verify_genesis_hash_diff() {
+        assert!(verify_genesis_hash(&**MAINNET, MAINNET.genesis_hash()).is_ok());
+
+        let mut genesis = MAINNET.genesis.clone();
+        genesis.gas_limit += 1;
+        let chain = ChainSpecBuilder::mainnet().genesis(genesis).build();
+
+        let diffs = genesis_header_diff(MAINNET.genesis_header(), chain.genesis_header());
+        assert_eq!(
+            diffs,
+            vec![GenesisFieldDiff {
+                field: "gasLimit",
+                expected: MAINNET.genesis_header().gas_limit.to_string(),
+                computed: chain.genesis_header().gas_limit.to_string(),
+            }]
+        );
+
+        let err = verify_genesis_hash(&chain, MAINNET.genesis_hash()).unwrap_err().to_string();
+        assert!(err.contains("Genesis header fields that differ from mainnet:"));
+        assert!(err.contains("gasLimit: expected"));
+    }
+}
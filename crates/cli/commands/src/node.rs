@@ -2,16 +2,18 @@
 
 use crate::launcher::Launcher;
 use clap::{value_parser, Args, Parser};
+use eyre::WrapErr;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use reth_cli_util::parse_socket_address;
+use reth_config::Config;
 use reth_db::init_db;
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, NetworkArgs,
-        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
+        BlockExportArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs,
+        ExExArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
@@ -101,6 +103,13 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten)]
     pub dev: DevArgs,
 
+    /// Path to a JSON file with payload attributes overrides applied to every mined block.
+    ///
+    /// Supported fields are `suggestedFeeRecipient`, `prevRandao`, `withdrawals` and
+    /// `parentBeaconBlockRoot`.
+    #[arg(long = "dev.payload-attributes", value_name = "FILE", help_heading = "Dev testnet")]
+    pub dev_payload_attributes: Option<PathBuf>,
+
     /// All pruning related arguments
     #[command(flatten)]
     pub pruning: PruningArgs,
@@ -113,6 +122,14 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "ERA")]
     pub era: EraArgs,
 
+    /// All `ExEx` related arguments with --exex prefix
+    #[command(flatten, next_help_heading = "ExEx")]
+    pub exex: ExExArgs,
+
+    /// All block export related arguments with --block-export prefix
+    #[command(flatten, next_help_heading = "Block export")]
+    pub block_export: BlockExportArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -164,10 +181,13 @@ where
             debug,
             db,
             dev,
+            dev_payload_attributes,
             pruning,
             ext,
             engine,
             era,
+            exex,
+            block_export,
         } = self;
 
         // set up node config
@@ -184,16 +204,27 @@ where
             debug,
             db,
             dev,
+            dev_payload_attributes,
             pruning,
             engine,
             era,
+            exex,
+            block_export,
         };
 
         let data_dir = node_config.datadir();
         let db_path = data_dir.db();
 
+        // the database settings of the config file are needed before the node is launched
+        let config_path = node_config.config.clone().unwrap_or_else(|| data_dir.config());
+        let toml_config = Config::from_path(&config_path)
+            .wrap_err_with(|| format!("Could not load config file {config_path:?}"))?;
+        let db_args = node_config.db.database_args_with_config(&toml_config.db)?;
+        node_config.datadir =
+            node_config.datadir.or_static_files_path(toml_config.static_files.path.clone());
+
         tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
+        let database = Arc::new(init_db(db_path.clone(), db_args)?.with_metrics());
 
         if with_unused_ports {
             node_config = node_config.with_unused_ports();
@@ -315,6 +346,19 @@ mod tests {
         assert!(config_path.ends_with(end), "{:?}", cmd.config);
     }
 
+    #[test]
+    fn parse_dev_payload_attributes() {
+        let cmd: NodeCommand<EthereumChainSpecParser> = NodeCommand::try_parse_args_from([
+            "reth",
+            "--dev",
+            "--dev.payload-attributes",
+            "attributes.json",
+        ])
+        .unwrap();
+        assert!(cmd.dev.dev);
+        assert_eq!(cmd.dev_payload_attributes, Some(PathBuf::from("attributes.json")));
+    }
+
     #[test]
     fn parse_db_path() {
         let cmd: NodeCommand<EthereumChainSpecParser> =
@@ -0,0 +1,451 @@
+//! Fetches a batch of blocks from multiple peers and compares the responses.
+
+use alloy_eips::BlockHashOrNumber;
+use alloy_primitives::{keccak256, B256};
+use clap::Parser;
+use futures::{future::join_all, stream, StreamExt};
+use reth_cli_util::hash_or_num_value_parser;
+use reth_network::{
+    types::{GetBlockBodies, GetBlockHeaders, HeadersDirection},
+    NetworkHandle, NetworkPrimitives, PeerRequest, Peers, PeersInfo,
+};
+use reth_network_peers::PeerId;
+use reth_primitives_traits::{Block, SealedHeader};
+use reth_provider::BlockReader;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::sync::oneshot;
+
+/// Arguments for fetching a batch of blocks from multiple peers.
+#[derive(Debug, Clone, Parser)]
+#[command(next_help_heading = "Batch")]
+pub struct BatchArgs {
+    /// Path to a file with one block number or hash per line.
+    ///
+    /// Every block is requested from multiple peers and the responses are compared with each
+    /// other. Peers that respond with different data than the majority are reported. Empty lines
+    /// and lines starting with `#` are ignored.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+    pub from_file: Option<PathBuf>,
+
+    /// The number of peers every block is requested from.
+    #[arg(long = "batch.peers", default_value_t = 3, requires = "from_file")]
+    pub peers: usize,
+
+    /// The maximum number of blocks that are fetched concurrently.
+    #[arg(long = "batch.concurrency", default_value_t = 16, requires = "from_file")]
+    pub concurrency: usize,
+
+    /// Timeout of a single request to a peer, in seconds.
+    #[arg(long = "batch.request-timeout", default_value_t = 10, requires = "from_file")]
+    pub request_timeout: u64,
+
+    /// Compare the responses against the local database instead of the majority of the peers.
+    ///
+    /// Blocks that are missing from the local database are compared against the majority.
+    #[arg(long = "batch.check-local", requires = "from_file")]
+    pub check_local: bool,
+}
+
+/// Reads the block numbers and hashes from the given file.
+pub(crate) fn read_block_ids(path: &Path) -> eyre::Result<Vec<BlockHashOrNumber>> {
+    parse_block_ids(&reth_fs_util::read_to_string(path)?)
+}
+
+/// Parses one block number or hash per line.
+fn parse_block_ids(content: &str) -> eyre::Result<Vec<BlockHashOrNumber>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            hash_or_num_value_parser(line)
+                .map_err(|err| eyre::eyre!("invalid block number or hash {line:?}: {err}"))
+        })
+        .collect()
+}
+
+/// The data fetched for every block of the batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BatchKind {
+    /// Block headers
+    Header,
+    /// Block bodies
+    Body,
+}
+
+/// Fetches blocks from a fixed set of peers and compares the responses.
+#[derive(Debug)]
+pub(crate) struct BatchFetcher<N: NetworkPrimitives, P> {
+    /// The network to send the requests to.
+    network: NetworkHandle<N>,
+    /// The peers every block is requested from.
+    peers: Vec<PeerId>,
+    /// Timeout of a single request.
+    request_timeout: Duration,
+    /// The local database, if the responses are compared against it.
+    local: Option<P>,
+}
+
+impl<N, P> BatchFetcher<N, P>
+where
+    N: NetworkPrimitives,
+    P: BlockReader<Header = N::BlockHeader, Block = N::Block>,
+{
+    /// Waits until enough peers are connected and creates the fetcher.
+    pub(crate) async fn new(
+        network: NetworkHandle<N>,
+        args: &BatchArgs,
+        local: Option<P>,
+    ) -> eyre::Result<Self> {
+        let num_peers = args.peers.max(1);
+        while network.num_connected_peers() < num_peers {
+            println!("Waiting for peers. Connected: {}/{num_peers}", network.num_connected_peers());
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+
+        let mut peers = network.get_all_peers().await?;
+        peers.truncate(num_peers);
+        for peer in &peers {
+            println!("Requesting blocks from peer {} ({})", peer.remote_id, peer.client_version);
+        }
+
+        Ok(Self {
+            network,
+            peers: peers.into_iter().map(|peer| peer.remote_id).collect(),
+            request_timeout: Duration::from_secs(args.request_timeout),
+            local,
+        })
+    }
+
+    /// Fetches all blocks, prints the blocks the peers disagree on and returns the report.
+    pub(crate) async fn run(
+        &self,
+        kind: BatchKind,
+        ids: Vec<BlockHashOrNumber>,
+        concurrency: usize,
+    ) -> eyre::Result<BatchReport> {
+        let mut report = BatchReport::default();
+        let mut checks = stream::iter(ids)
+            .map(|id| async move {
+                match kind {
+                    BatchKind::Header => Ok(vec![self.check_header(id).await?]),
+                    BatchKind::Body => self.check_body(id).await,
+                }
+            })
+            .buffered(concurrency.max(1));
+
+        while let Some(checks) = checks.next().await {
+            for check in checks? {
+                check.print();
+                report.record(&check);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Requests the header from all peers and compares the hashes of the responses.
+    async fn check_header(&self, id: BlockHashOrNumber) -> eyre::Result<BlockCheck> {
+        let local = match (&self.local, id) {
+            (Some(local), BlockHashOrNumber::Number(number)) => {
+                local.sealed_header(number)?.map(|header| header.hash())
+            }
+            _ => None,
+        };
+
+        let responses = join_all(self.peers.iter().map(|peer| async move {
+            let (tx, rx) = oneshot::channel();
+            let request = GetBlockHeaders {
+                start_block: id,
+                limit: 1,
+                skip: 0,
+                direction: HeadersDirection::Rising,
+            };
+            self.network
+                .send_request(*peer, PeerRequest::GetBlockHeaders { request, response: tx });
+            let response = self.response(rx).await.map(|headers| {
+                headers.0.into_iter().next().map(|header| SealedHeader::seal_slow(header).hash())
+            });
+            (*peer, response.into())
+        }))
+        .await;
+
+        // a header requested by hash must have that hash
+        let expected = match id {
+            BlockHashOrNumber::Hash(hash) => Some(hash),
+            BlockHashOrNumber::Number(_) => local.or_else(|| majority(&responses)),
+        };
+
+        Ok(BlockCheck { kind: BatchKind::Header, id, expected, responses })
+    }
+
+    /// Requests the body from all peers and compares the hashes of the encoded responses.
+    ///
+    /// Bodies are requested by hash, so for block numbers the header is checked first.
+    async fn check_body(&self, id: BlockHashOrNumber) -> eyre::Result<Vec<BlockCheck>> {
+        let mut checks = Vec::with_capacity(2);
+        let hash = match id {
+            BlockHashOrNumber::Hash(hash) => hash,
+            BlockHashOrNumber::Number(_) => {
+                let header = self.check_header(id).await?;
+                let expected = header.expected;
+                checks.push(header);
+                let Some(hash) = expected else { return Ok(checks) };
+                hash
+            }
+        };
+
+        let local = match &self.local {
+            Some(local) => local.block(hash.into())?.map(|block| body_hash(block.body())),
+            None => None,
+        };
+
+        let responses = join_all(self.peers.iter().map(|peer| async move {
+            let (tx, rx) = oneshot::channel();
+            let request = GetBlockBodies(vec![hash]);
+            self.network.send_request(*peer, PeerRequest::GetBlockBodies { request, response: tx });
+            let response = self
+                .response(rx)
+                .await
+                .map(|bodies| bodies.0.into_iter().next().map(|body| body_hash(&body)));
+            (*peer, response.into())
+        }))
+        .await;
+
+        let expected = local.or_else(|| majority(&responses));
+        checks.push(BlockCheck { kind: BatchKind::Body, id: hash.into(), expected, responses });
+
+        Ok(checks)
+    }
+
+    /// Waits for the response of a peer.
+    async fn response<T, E: std::fmt::Display>(
+        &self,
+        rx: oneshot::Receiver<Result<T, E>>,
+    ) -> Result<T, String> {
+        match tokio::time::timeout(self.request_timeout, rx).await {
+            Ok(Ok(Ok(response))) => Ok(response),
+            Ok(Ok(Err(err))) => Err(err.to_string()),
+            Ok(Err(_)) => Err("session closed".to_string()),
+            Err(_) => Err("request timed out".to_string()),
+        }
+    }
+}
+
+/// Returns the hash of the RLP encoded body.
+fn body_hash(body: &impl alloy_rlp::Encodable) -> B256 {
+    keccak256(alloy_rlp::encode(body))
+}
+
+/// Returns the hash most peers responded with, if there is a single one.
+fn majority(responses: &[(PeerId, PeerResponse)]) -> Option<B256> {
+    let mut counts = HashMap::<B256, usize>::new();
+    for (_, response) in responses {
+        if let PeerResponse::Found(hash) = response {
+            *counts.entry(*hash).or_default() += 1;
+        }
+    }
+
+    let mut counts = counts.into_iter().collect::<Vec<_>>();
+    counts.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+    match counts.as_slice() {
+        [] => None,
+        [(hash, _)] => Some(*hash),
+        [(hash, first), (_, second), ..] => (first > second).then_some(*hash),
+    }
+}
+
+/// The response of a peer to a request for a single block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PeerResponse {
+    /// The peer responded with data of the given hash.
+    Found(B256),
+    /// The peer responded without the block.
+    Missing,
+    /// The request failed.
+    Failed(String),
+}
+
+impl From<Result<Option<B256>, String>> for PeerResponse {
+    fn from(response: Result<Option<B256>, String>) -> Self {
+        match response {
+            Ok(Some(hash)) => Self::Found(hash),
+            Ok(None) => Self::Missing,
+            Err(err) => Self::Failed(err),
+        }
+    }
+}
+
+/// The responses of all peers for a single block.
+#[derive(Debug)]
+struct BlockCheck {
+    /// The data that was requested.
+    kind: BatchKind,
+    /// The requested block.
+    id: BlockHashOrNumber,
+    /// The hash the responses are expected to have, if it could be determined.
+    expected: Option<B256>,
+    /// The responses of the peers.
+    responses: Vec<(PeerId, PeerResponse)>,
+}
+
+impl BlockCheck {
+    /// Returns the peers that responded with data that doesn't match the expected hash.
+    fn mismatching_peers(&self) -> impl Iterator<Item = (PeerId, B256)> + '_ {
+        self.responses.iter().filter_map(|(peer, response)| match response {
+            PeerResponse::Found(hash) if Some(*hash) != self.expected => Some((*peer, *hash)),
+            _ => None,
+        })
+    }
+
+    /// Prints the check if the peers disagree or the block couldn't be fetched.
+    fn print(&self) {
+        let Some(expected) = self.expected else {
+            println!("Could not determine {:?} of block {:?}", self.kind, self.id);
+            for (peer, response) in &self.responses {
+                println!("  {peer}: {response:?}");
+            }
+            return
+        };
+
+        let mut mismatching = self.mismatching_peers().peekable();
+        if mismatching.peek().is_some() {
+            println!("Mismatching {:?} for block {:?}. Expected: {expected}", self.kind, self.id);
+            for (peer, hash) in mismatching {
+                println!("  {peer}: {hash}");
+            }
+        }
+    }
+}
+
+/// Summary of a batch of fetched blocks.
+#[derive(Debug, Default)]
+pub(crate) struct BatchReport {
+    /// Number of blocks all responding peers agreed on.
+    consistent: usize,
+    /// Number of blocks at least one peer responded with different data for.
+    mismatching: usize,
+    /// Number of blocks the expected data couldn't be determined for.
+    unresolved: usize,
+    /// Responses of every peer that didn't match.
+    peers: HashMap<PeerId, PeerStats>,
+}
+
+/// Responses of a single peer that didn't match.
+#[derive(Debug, Default)]
+struct PeerStats {
+    /// Number of responses with different data.
+    mismatching: usize,
+    /// Number of responses without the block.
+    missing: usize,
+    /// Number of failed requests.
+    failed: usize,
+}
+
+impl BatchReport {
+    /// Records the responses for a single block.
+    fn record(&mut self, check: &BlockCheck) {
+        for (peer, response) in &check.responses {
+            let stats = self.peers.entry(*peer).or_default();
+            match response {
+                PeerResponse::Found(hash) if check.expected.is_some_and(|e| e != *hash) => {
+                    stats.mismatching += 1
+                }
+                PeerResponse::Found(_) => {}
+                PeerResponse::Missing => stats.missing += 1,
+                PeerResponse::Failed(_) => stats.failed += 1,
+            }
+        }
+
+        if check.expected.is_none() {
+            self.unresolved += 1;
+        } else if check.mismatching_peers().next().is_some() {
+            self.mismatching += 1;
+        } else {
+            self.consistent += 1;
+        }
+    }
+
+    /// Returns the peers that responded with different data at least once.
+    pub(crate) fn mismatching_peers(&self) -> impl Iterator<Item = PeerId> + '_ {
+        self.peers.iter().filter(|(_, stats)| stats.mismatching > 0).map(|(peer, _)| *peer)
+    }
+
+    /// Prints the summary.
+    pub(crate) fn print(&self) {
+        println!(
+            "Checked {} blocks. Consistent: {}, mismatching: {}, unresolved: {}",
+            self.consistent + self.mismatching + self.unresolved,
+            self.consistent,
+            self.mismatching,
+            self.unresolved
+        );
+        for (peer, stats) in &self.peers {
+            println!(
+                "  {peer}: mismatching: {}, missing: {}, failed: {}",
+                stats.mismatching, stats.missing, stats.failed
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn responses(hashes: &[Option<u8>]) -> Vec<(PeerId, PeerResponse)> {
+        hashes
+            .iter()
+            .map(|hash| {
+                let response = match hash {
+                    Some(hash) => PeerResponse::Found(B256::with_last_byte(*hash)),
+                    None => PeerResponse::Missing,
+                };
+                (PeerId::random(), response)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn majority_response() {
+        assert_eq!(majority(&responses(&[])), None);
+        assert_eq!(majority(&responses(&[None])), None);
+        assert_eq!(majority(&responses(&[Some(1), None])), Some(B256::with_last_byte(1)));
+        assert_eq!(
+            majority(&responses(&[Some(1), Some(2), Some(2)])),
+            Some(B256::with_last_byte(2))
+        );
+        // a tie can't be resolved
+        assert_eq!(majority(&responses(&[Some(1), Some(2)])), None);
+    }
+
+    #[test]
+    fn report_mismatching_peers() {
+        let responses = responses(&[Some(1), Some(1), Some(2), None]);
+        let bad_peer = responses[2].0;
+        let check = BlockCheck {
+            kind: BatchKind::Header,
+            id: 1u64.into(),
+            expected: majority(&responses),
+            responses,
+        };
+
+        let mut report = BatchReport::default();
+        report.record(&check);
+        assert_eq!(report.mismatching, 1);
+        assert_eq!(report.mismatching_peers().collect::<Vec<_>>(), vec![bad_peer]);
+    }
+
+    #[test]
+    fn parse_block_id_lines() {
+        let hash = B256::with_last_byte(1);
+        let ids = parse_block_ids(&format!("# blocks\n1\n\n  {hash}  \n")).unwrap();
+        assert_eq!(ids, vec![1u64.into(), hash.into()]);
+
+        assert!(parse_block_ids("foo").is_err());
+    }
+}
@@ -1,6 +1,9 @@
 //! P2P Debugging tool
 
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use crate::common::CliNodeTypes;
 use alloy_eips::BlockHashOrNumber;
@@ -10,16 +13,23 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_util::{get_secret_key, hash_or_num_value_parser};
 use reth_config::Config;
+use reth_db::{open_db_read_only, DatabaseEnv};
 use reth_network::{BlockDownloaderProvider, NetworkConfigBuilder};
 use reth_network_p2p::bodies::client::BodiesClient;
+use reth_node_builder::NodeTypesWithDBAdapter;
 use reth_node_core::{
-    args::{DatadirArgs, NetworkArgs},
+    args::{DatabaseArgs, DatadirArgs, NetworkArgs},
     utils::get_single_header,
 };
+use reth_provider::{providers::StaticFileProvider, ProviderFactory};
 
+mod batch;
 pub mod bootnode;
 pub mod rlpx;
 
+pub use batch::BatchArgs;
+use batch::{BatchFetcher, BatchKind};
+
 /// `reth p2p` command
 #[derive(Debug, Parser)]
 pub struct Command<C: ChainSpecParser> {
@@ -31,7 +41,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
     /// Execute `p2p` command
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
         match self.command {
-            Subcommands::Header { args, id } => {
+            Subcommands::Header { args, id, batch } => {
+                if let Some(path) = &batch.from_file {
+                    return args.fetch_batch::<N>(&batch, path, BatchKind::Header).await
+                }
+                let id = id.expect("required without --from-file");
+
                 let handle = args.launch_network::<N>().await?;
                 let fetch_client = handle.fetch_client().await?;
                 let backoff = args.backoff();
@@ -43,7 +58,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                 println!("Successfully downloaded header: {header:?}");
             }
 
-            Subcommands::Body { args, id } => {
+            Subcommands::Body { args, id, batch } => {
+                if let Some(path) = &batch.from_file {
+                    return args.fetch_batch::<N>(&batch, path, BatchKind::Body).await
+                }
+                let id = id.expect("required without --from-file");
+
                 let handle = args.launch_network::<N>().await?;
                 let fetch_client = handle.fetch_client().await?;
                 let backoff = args.backoff();
@@ -111,16 +131,20 @@ pub enum Subcommands<C: ChainSpecParser> {
         #[command(flatten)]
         args: DownloadArgs<C>,
         /// The header number or hash
-        #[arg(value_parser = hash_or_num_value_parser)]
-        id: BlockHashOrNumber,
+        #[arg(value_parser = hash_or_num_value_parser, required_unless_present = "from_file")]
+        id: Option<BlockHashOrNumber>,
+        #[command(flatten)]
+        batch: BatchArgs,
     },
     /// Download block body
     Body {
         #[command(flatten)]
         args: DownloadArgs<C>,
         /// The block number or hash
-        #[arg(value_parser = hash_or_num_value_parser)]
-        id: BlockHashOrNumber,
+        #[arg(value_parser = hash_or_num_value_parser, required_unless_present = "from_file")]
+        id: Option<BlockHashOrNumber>,
+        #[command(flatten)]
+        batch: BatchArgs,
     },
     // RLPx utilities
     Rlpx(rlpx::Command),
@@ -208,6 +232,59 @@ impl<C: ChainSpecParser> DownloadArgs<C> {
     pub fn backoff(&self) -> ConstantBuilder {
         ConstantBuilder::default().with_max_times(self.retries.max(1))
     }
+
+    /// Opens the local database read-only.
+    fn provider_factory<N>(
+        &self,
+    ) -> eyre::Result<ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
+        let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
+        let config = Config::from_path(&config_path).unwrap_or_default();
+
+        let db = open_db_read_only(
+            &data_dir.db(),
+            DatabaseArgs::default().database_args_with_config(&config.db)?,
+        )?;
+        let data_dir = self
+            .datadir
+            .clone()
+            .or_static_files_path(config.static_files.path.clone())
+            .resolve_datadir(self.chain.chain());
+        let static_file_provider = StaticFileProvider::read_only(data_dir.static_files(), false)?;
+
+        Ok(ProviderFactory::new(Arc::new(db), self.chain.clone(), static_file_provider))
+    }
+
+    /// Fetches the blocks listed in the file from multiple peers and reports the peers that
+    /// respond with mismatching data.
+    async fn fetch_batch<N>(
+        &self,
+        batch: &BatchArgs,
+        path: &Path,
+        kind: BatchKind,
+    ) -> eyre::Result<()>
+    where
+        C::ChainSpec: EthChainSpec + Hardforks + EthereumHardforks + Send + Sync + 'static,
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let ids = batch::read_block_ids(path)?;
+        let local = batch.check_local.then(|| self.provider_factory::<N>()).transpose()?;
+        let handle = self.launch_network::<N>().await?;
+
+        let fetcher = BatchFetcher::new(handle, batch, local).await?;
+        let report = fetcher.run(kind, ids, batch.concurrency).await?;
+        report.print();
+
+        let mismatching = report.mismatching_peers().collect::<Vec<_>>();
+        if !mismatching.is_empty() {
+            eyre::bail!("{} peers responded with mismatching data", mismatching.len())
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -226,4 +303,27 @@ mod tests {
         let _args: Command<EthereumChainSpecParser> =
             Command::parse_from(["reth", "body", "--chain", "mainnet", "1000"]);
     }
+
+    #[test]
+    fn parse_batch_cmd() {
+        let _args: Command<EthereumChainSpecParser> = Command::parse_from([
+            "reth",
+            "body",
+            "--from-file",
+            "blocks.txt",
+            "--batch.peers",
+            "5",
+            "--batch.check-local",
+        ]);
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from(["reth", "header"]).is_err());
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "header",
+            "1000",
+            "--batch.peers",
+            "5"
+        ])
+        .is_err());
+    }
 }
@@ -4,21 +4,27 @@ use crate::common::{
     AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, Environment,
     EnvironmentArgs,
 };
-use alloy_consensus::{BlockHeader, TxReceipt};
-use clap::Parser;
+use alloy_consensus::{BlockHeader, Transaction, TxReceipt};
+use alloy_rpc_types_trace::geth::{CallConfig, GethTrace, PreStateConfig, TraceResult};
+use clap::{Parser, ValueEnum};
 use eyre::WrapErr;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_consensus::FullConsensus;
-use reth_evm::{execute::Executor, ConfigureEvm};
-use reth_primitives_traits::{format_gas_throughput, BlockBody, GotExpected, SignedTransaction};
+use reth_evm::{execute::Executor, system_calls::SystemCaller, ConfigureEvm, Evm};
+use reth_primitives_traits::{
+    format_gas_throughput, BlockBody, GotExpected, NodePrimitives, RecoveredBlock,
+    SignedTransaction,
+};
 use reth_provider::{
     BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory, ReceiptProvider,
-    StaticFileProviderFactory, TransactionVariant,
+    StateProvider, StaticFileProviderFactory, TransactionVariant,
 };
-use reth_revm::database::StateProviderDatabase;
+use reth_revm::{database::StateProviderDatabase, db::CacheDB, DatabaseCommit};
 use reth_stages::stages::calculate_gas_used_from_headers;
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
 use std::{
+    path::PathBuf,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -44,6 +50,27 @@ pub struct Command<C: ChainSpecParser> {
     /// Number of tasks to run in parallel
     #[arg(long, default_value = "10")]
     num_tasks: u64,
+
+    /// Directory to write the traces of all re-executed blocks to.
+    ///
+    /// One `<block number>.json` file is written per block, containing the traces of all
+    /// transactions of the block in the same format as `debug_traceBlockByNumber`.
+    #[arg(long, value_name = "DIR")]
+    trace_output: Option<PathBuf>,
+
+    /// The tracer used to generate the traces written to `--trace-output`.
+    #[arg(long, value_enum, default_value_t = Tracer::Call, requires = "trace_output")]
+    tracer: Tracer,
+}
+
+/// The tracers supported by `reth re-execute --trace-output`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Tracer {
+    /// Call traces of all transactions, see the geth `callTracer`.
+    #[default]
+    Call,
+    /// State diffs of all transactions, see the geth `prestateTracer` in diff mode.
+    Prestate,
 }
 
 impl<C: ChainSpecParser> Command<C> {
@@ -74,6 +101,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
         )?;
         let blocks_per_task = total_blocks / self.num_tasks;
 
+        if let Some(trace_output) = &self.trace_output {
+            reth_fs_util::create_dir_all(trace_output)?;
+            info!(path = %trace_output.display(), tracer = ?self.tracer, "Writing block traces");
+        }
+
         let db_at = {
             let provider_factory = provider_factory.clone();
             move |block_number: u64| {
@@ -97,6 +129,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             let consensus = components.consensus().clone();
             let db_at = db_at.clone();
             let stats_tx = stats_tx.clone();
+            let chain_spec = provider_factory.chain_spec();
+            let trace_output = self.trace_output.clone();
+            let tracer = self.tracer;
             tasks.spawn_blocking(move || {
                 let mut executor = evm_config.batch_executor(db_at(start_block - 1));
                 for block in start_block..end_block {
@@ -152,6 +187,22 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
 
                         return Err(err);
                     }
+
+                    if let Some(trace_output) = &trace_output {
+                        let traces = trace_block(
+                            &evm_config,
+                            chain_spec.clone(),
+                            db_at(block.number() - 1),
+                            &block,
+                            tracer,
+                        )
+                        .wrap_err_with(|| format!("Failed to trace block {}", block.number()))?;
+                        reth_fs_util::write_json_file(
+                            &trace_output.join(format!("{}.json", block.number())),
+                            &traces,
+                        )?;
+                    }
+
                     let _ = stats_tx.send(block.gas_used());
 
                     // Reset DB once in a while to avoid OOM
@@ -220,3 +271,145 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
         Ok(())
     }
 }
+
+/// Replays the block on top of the state of its parent and returns the traces of all transactions.
+///
+/// A single [`TracingInspector`] is reused for all transactions of the block to avoid reallocating
+/// its buffers.
+fn trace_block<E, ChainSpec>(
+    evm_config: &E,
+    chain_spec: ChainSpec,
+    db: StateProviderDatabase<impl StateProvider>,
+    block: &RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+    tracer: Tracer,
+) -> eyre::Result<Vec<TraceResult>>
+where
+    E: ConfigureEvm,
+    ChainSpec: EthereumHardforks,
+{
+    let evm_env = evm_config.evm_env(block.header());
+    let mut db = CacheDB::new(db);
+
+    let mut system_caller = SystemCaller::new(chain_spec);
+    let mut evm = evm_config.evm_with_env(&mut db, evm_env.clone());
+    system_caller.apply_pre_execution_changes(block.header(), &mut evm)?;
+    drop(evm);
+
+    let inspector_config = match tracer {
+        Tracer::Call => TracingInspectorConfig::from_geth_call_config(&CallConfig::default()),
+        Tracer::Prestate => {
+            TracingInspectorConfig::from_geth_prestate_config(&PreStateConfig::default())
+        }
+    };
+    let prestate_config = PreStateConfig { diff_mode: Some(true), ..Default::default() };
+
+    let mut inspector = TracingInspector::new(inspector_config);
+    let mut results = Vec::with_capacity(block.body().transaction_count());
+    for tx in block.transactions_recovered() {
+        let (tx_hash, gas_limit) = (*tx.tx_hash(), tx.gas_limit());
+        let res = evm_config
+            .evm_with_env_and_inspector(&mut db, evm_env.clone(), &mut inspector)
+            .transact(evm_config.tx_env(tx))?;
+
+        inspector.set_transaction_gas_limit(gas_limit);
+        let builder = inspector.geth_builder();
+        let trace: GethTrace = match tracer {
+            Tracer::Call => {
+                builder.geth_call_traces(CallConfig::default(), res.result.gas_used()).into()
+            }
+            Tracer::Prestate => builder.geth_prestate_traces(&res, &prestate_config, &db)?.into(),
+        };
+        results.push(TraceResult::Success { result: trace, tx_hash: Some(tx_hash) });
+        inspector = inspector.fused();
+
+        db.commit(res.state);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_primitives::{address, map::HashMap, Address, Bytes, Signature, TxKind, B256, U256};
+    use alloy_rpc_types_trace::geth::{CallFrame, PreStateFrame};
+    use reth_chainspec::MAINNET;
+    use reth_ethereum_primitives::{Block, BlockBody, Transaction, TransactionSigned};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::Account;
+    use reth_revm::test_utils::StateProviderTest;
+
+    const SENDER: Address = address!("0x1000000000000000000000000000000000000001");
+    const CONTRACT: Address = address!("0x2000000000000000000000000000000000000002");
+
+    /// Returns a block that calls a contract which stores `1` in slot `0`, and the state of its
+    /// parent.
+    fn fixture() -> (RecoveredBlock<Block>, StateProviderTest) {
+        let mut state = StateProviderTest::default();
+        state.insert_account(
+            SENDER,
+            Account { balance: U256::from(1_000_000_000u64), ..Default::default() },
+            None,
+            HashMap::default(),
+        );
+        // PUSH1 0x01 PUSH1 0x00 SSTORE STOP
+        let code = Bytes::from_static(&[0x60, 0x01, 0x60, 0x00, 0x55, 0x00]);
+        state.insert_account(CONTRACT, Account::default(), Some(code), HashMap::default());
+
+        let tx = TransactionSigned::new_unhashed(
+            Transaction::Legacy(TxLegacy {
+                gas_limit: 100_000,
+                to: TxKind::Call(CONTRACT),
+                ..Default::default()
+            }),
+            Signature::test_signature(),
+        );
+        let header = Header { number: 1, gas_limit: 1_000_000, ..Default::default() };
+        let block =
+            Block { header, body: BlockBody { transactions: vec![tx], ..Default::default() } };
+
+        (RecoveredBlock::new_unhashed(block, vec![SENDER]), state)
+    }
+
+    #[test]
+    fn trace_fixture_block() {
+        let evm_config = EthEvmConfig::mainnet();
+        let (block, state) = fixture();
+
+        let traces = trace_block(
+            &evm_config,
+            MAINNET.clone(),
+            StateProviderDatabase::new(state.clone()),
+            &block,
+            Tracer::Call,
+        )
+        .unwrap();
+        let [TraceResult::Success { result: GethTrace::CallTracer(frame), tx_hash }] =
+            traces.as_slice()
+        else {
+            panic!("expected a call trace, got {traces:?}")
+        };
+        assert_eq!(*tx_hash, Some(*block.body().transactions[0].tx_hash()));
+        let CallFrame { from, to, error, .. } = frame;
+        assert_eq!((*from, *to, error), (SENDER, Some(CONTRACT), &None));
+
+        let traces = trace_block(
+            &evm_config,
+            MAINNET.clone(),
+            StateProviderDatabase::new(state),
+            &block,
+            Tracer::Prestate,
+        )
+        .unwrap();
+        let [TraceResult::Success {
+            result: GethTrace::PreStateTracer(PreStateFrame::Diff(diff)),
+            ..
+        }] = traces.as_slice()
+        else {
+            panic!("expected a prestate diff, got {traces:?}")
+        };
+        let storage = &diff.post[&CONTRACT].storage;
+        assert_eq!(storage.get(&B256::ZERO), Some(&B256::with_last_byte(1)));
+    }
+}
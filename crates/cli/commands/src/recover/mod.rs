@@ -7,6 +7,7 @@ use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use std::sync::Arc;
 
+mod storage_root;
 mod storage_tries;
 
 /// `reth recover` command
@@ -21,6 +22,8 @@ pub struct Command<C: ChainSpecParser> {
 pub enum Subcommands<C: ChainSpecParser> {
     /// Recover the node by deleting dangling storage tries.
     StorageTries(storage_tries::Command<C>),
+    /// Verify the storage roots of the given accounts and repair corrupted storage tries.
+    StorageRoot(storage_root::Command<C>),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
@@ -31,6 +34,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     ) -> eyre::Result<()> {
         match self.command {
             Subcommands::StorageTries(command) => command.execute::<N>(ctx).await,
+            Subcommands::StorageRoot(command) => command.execute::<N>(ctx).await,
         }
     }
 }
@@ -40,6 +44,7 @@ impl<C: ChainSpecParser> Command<C> {
     pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
         match &self.command {
             Subcommands::StorageTries(command) => command.chain_spec(),
+            Subcommands::StorageRoot(command) => command.chain_spec(),
         }
     }
 }
@@ -0,0 +1,80 @@
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_primitives::{keccak256, map::B256Map, Address};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use reth_provider::{providers::ConsistentDbView, StorageTrieWriter};
+use reth_trie_parallel::storage_root::ParallelStorageRoots;
+use std::sync::Arc;
+use tracing::*;
+
+/// `reth recover storage-root` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The addresses of the accounts to verify.
+    #[arg(value_name = "ADDRESS", required = true)]
+    addresses: Vec<Address>,
+
+    /// Only verify the storage roots without repairing corrupted storage tries.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `storage-root` recovery command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(
+        self,
+        _ctx: CliContext,
+    ) -> eyre::Result<()> {
+        let access = if self.dry_run { AccessRights::RO } else { AccessRights::RW };
+        let Environment { provider_factory, .. } = self.env.init::<N>(access)?;
+
+        let addresses = self
+            .addresses
+            .iter()
+            .map(|address| (keccak256(address), *address))
+            .collect::<B256Map<_>>();
+
+        info!(target: "reth::cli", accounts = addresses.len(), "Verifying storage roots");
+        let view = ConsistentDbView::new_with_latest_tip(provider_factory.clone())?;
+        let checks = ParallelStorageRoots::new(view, addresses.keys().copied()).verify()?;
+
+        let mut corrupted = B256Map::default();
+        for check in checks {
+            let address = addresses[&check.hashed_address];
+            if check.is_valid() {
+                info!(target: "reth::cli", %address, root = ?check.computed_root, slots = check.slots, "Storage root is valid");
+            } else {
+                warn!(target: "reth::cli", %address, stored = ?check.stored_root, computed = ?check.computed_root, slots = check.slots, "Storage trie is corrupted");
+                corrupted.insert(check.hashed_address, check.updates);
+            }
+        }
+
+        if corrupted.is_empty() {
+            info!(target: "reth::cli", "All storage roots are valid");
+            return Ok(())
+        }
+
+        if self.dry_run {
+            eyre::bail!("Found {} corrupted storage tries", corrupted.len());
+        }
+
+        let provider_rw = provider_factory.provider_rw()?;
+        let entries = provider_rw.write_storage_trie_updates(&corrupted)?;
+        provider_rw.commit()?;
+        info!(target: "reth::cli", repaired = corrupted.len(), entries, "Finished recovery");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
@@ -0,0 +1,322 @@
+//! Block data that a dumped stage reads from the stages before it.
+
+use alloy_primitives::{BlockNumber, TxNumber};
+use reth_db::DatabaseEnv;
+use reth_db_api::{
+    database::Database, table::TableImporter, tables, transaction::DbTxMut, DatabaseError,
+};
+use reth_db_common::DbTool;
+use reth_primitives_traits::{AlloyBlockHeader, HeaderTy, TxTy};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, HeaderProvider, TransactionsProvider,
+};
+use reth_stages::{StageCheckpoint, StageId};
+use std::ops::{Range, RangeInclusive};
+use tracing::info;
+
+/// Number of block hashes before a block that the `BLOCKHASH` opcode can access.
+const BLOCK_HASH_HISTORY: u64 = 256;
+
+/// Number of headers or transactions that are copied in a single database transaction.
+const COPY_BATCH_SIZE: u64 = 10_000;
+
+/// The stages whose output a dumped stage reads in its block range.
+///
+/// The output of these stages is copied into the dumped database together with the tables of the
+/// dumped stage, so that the stage can be run from the dumped datadir without the source datadir.
+/// Headers and transactions are read through the provider, so they are copied from static files
+/// as well.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StageDependencies {
+    /// The stages whose output is read, their checkpoints are set to the end of the range.
+    stages: &'static [StageId],
+    /// Number of canonical block hashes before the range that are read.
+    block_hashes_lookback: u64,
+}
+
+impl StageDependencies {
+    /// Execution reads headers, including the hashes of the previous blocks, transactions and
+    /// senders.
+    pub(crate) const EXECUTION: Self = Self {
+        stages: &[StageId::Headers, StageId::Bodies, StageId::SenderRecovery],
+        block_hashes_lookback: BLOCK_HASH_HISTORY,
+    };
+
+    /// Account and storage hashing read the changesets of the execution stage.
+    pub(crate) const HASHING: Self =
+        Self { stages: &[StageId::Execution], block_hashes_lookback: 0 };
+
+    /// Merkle reads the hashed state and changesets, and the headers to verify the state root.
+    pub(crate) const MERKLE: Self = Self {
+        stages: &[
+            StageId::Headers,
+            StageId::Execution,
+            StageId::AccountHashing,
+            StageId::StorageHashing,
+        ],
+        block_hashes_lookback: 0,
+    };
+
+    /// Returns `true` if the output of the given stage is read.
+    fn contains(&self, stage: StageId) -> bool {
+        self.stages.contains(&stage)
+    }
+
+    /// Copies the headers, bodies and senders that are read in the given range into the output
+    /// database and sets the checkpoints of the stages that produced them.
+    pub(crate) fn copy<N: ProviderNodeTypes>(
+        &self,
+        db_tool: &DbTool<N>,
+        output_db: &DatabaseEnv,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> eyre::Result<()> {
+        if self.contains(StageId::Headers) {
+            copy_headers(db_tool, output_db, from.saturating_sub(self.block_hashes_lookback)..=to)?;
+        }
+
+        if self.contains(StageId::Bodies) || self.contains(StageId::SenderRecovery) {
+            let tx_range = tx_range(db_tool, from, to)?;
+            if self.contains(StageId::Bodies) {
+                copy_bodies(db_tool, output_db, from, to, tx_range.clone())?;
+            }
+            if self.contains(StageId::SenderRecovery) {
+                copy_senders(db_tool, output_db, tx_range)?;
+            }
+        }
+
+        output_db.update(|tx| {
+            for stage in self.stages {
+                tx.put::<tables::StageCheckpoints>(stage.to_string(), StageCheckpoint::new(to))?;
+            }
+            Ok::<_, DatabaseError>(())
+        })??;
+
+        info!(target: "reth::cli", stages = ?self.stages, "Copied stage dependencies");
+
+        Ok(())
+    }
+}
+
+/// Returns the range of transactions in the blocks `from..=to`.
+fn tx_range<N: ProviderNodeTypes>(
+    db_tool: &DbTool<N>,
+    from: BlockNumber,
+    to: BlockNumber,
+) -> eyre::Result<Range<TxNumber>> {
+    let provider = db_tool.provider_factory.provider()?;
+    let from_block = provider
+        .block_body_indices(from)?
+        .ok_or_else(|| eyre::eyre!("BlockBody {from} does not exist."))?;
+    let to_block = provider
+        .block_body_indices(to)?
+        .ok_or_else(|| eyre::eyre!("BlockBody {to} does not exist."))?;
+    Ok(from_block.first_tx_num..to_block.next_tx_num())
+}
+
+/// Copies the canonical headers, their hashes and total difficulties in the range.
+fn copy_headers<N: ProviderNodeTypes>(
+    db_tool: &DbTool<N>,
+    output_db: &DatabaseEnv,
+    range: RangeInclusive<BlockNumber>,
+) -> eyre::Result<()> {
+    for start in range.clone().step_by(COPY_BATCH_SIZE as usize) {
+        let end = (start + COPY_BATCH_SIZE - 1).min(*range.end());
+        let provider = db_tool.provider_factory.provider()?;
+        let headers = provider.sealed_headers_range(start..=end)?;
+        if headers.len() as u64 != end - start + 1 {
+            eyre::bail!("Headers {start}..={end} do not exist.")
+        }
+        let difficulties = (start..=end)
+            .map(|number| provider.header_td_by_number(number))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        output_db.update(|tx| {
+            for (header, difficulty) in headers.into_iter().zip(difficulties) {
+                let (header, hash) = header.split();
+                let number = header.number();
+                tx.put::<tables::CanonicalHeaders>(number, hash)?;
+                tx.put::<tables::HeaderNumbers>(hash, number)?;
+                tx.put::<tables::Headers<HeaderTy<N::Primitives>>>(number, header)?;
+                if let Some(difficulty) = difficulty {
+                    tx.put::<tables::HeaderTerminalDifficulties>(number, difficulty.into())?;
+                }
+            }
+            Ok::<_, DatabaseError>(())
+        })??;
+    }
+
+    Ok(())
+}
+
+/// Copies the ommers, withdrawals and transactions of the blocks `from..=to`.
+fn copy_bodies<N: ProviderNodeTypes>(
+    db_tool: &DbTool<N>,
+    output_db: &DatabaseEnv,
+    from: BlockNumber,
+    to: BlockNumber,
+    tx_range: Range<TxNumber>,
+) -> eyre::Result<()> {
+    output_db.update(|tx| {
+        tx.import_table_with_range::<tables::BlockOmmers, _>(
+            &db_tool.provider_factory.db_ref().tx()?,
+            Some(from),
+            to,
+        )
+    })??;
+    output_db.update(|tx| {
+        tx.import_table_with_range::<tables::BlockWithdrawals, _>(
+            &db_tool.provider_factory.db_ref().tx()?,
+            Some(from),
+            to,
+        )
+    })??;
+
+    for start in tx_range.clone().step_by(COPY_BATCH_SIZE as usize) {
+        let end = (start + COPY_BATCH_SIZE).min(tx_range.end);
+        let transactions =
+            db_tool.provider_factory.provider()?.transactions_by_tx_range(start..end)?;
+
+        output_db.update(|tx| {
+            for (tx_num, transaction) in (start..end).zip(transactions) {
+                tx.put::<tables::Transactions<TxTy<N::Primitives>>>(tx_num, transaction)?;
+            }
+            Ok::<_, DatabaseError>(())
+        })??;
+    }
+
+    Ok(())
+}
+
+/// Copies the senders of the transactions in the range.
+fn copy_senders<N: ProviderNodeTypes>(
+    db_tool: &DbTool<N>,
+    output_db: &DatabaseEnv,
+    tx_range: Range<TxNumber>,
+) -> eyre::Result<()> {
+    for start in tx_range.clone().step_by(COPY_BATCH_SIZE as usize) {
+        let end = (start + COPY_BATCH_SIZE).min(tx_range.end);
+        let senders = db_tool.provider_factory.provider()?.senders_by_tx_range(start..end)?;
+
+        output_db.update(|tx| {
+            for (tx_num, sender) in (start..end).zip(senders) {
+                tx.put::<tables::TransactionSenders>(tx_num, sender)?;
+            }
+            Ok::<_, DatabaseError>(())
+        })??;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, BlockWriter, DBProvider, StorageLocation,
+    };
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn copy_execution_dependencies() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=270,
+            BlockRangeParams { tx_count: 1..3, ..Default::default() },
+        );
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in &blocks {
+            provider_rw
+                .insert_block(block.clone().try_recover().unwrap(), StorageLocation::Database)
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let db_tool = DbTool::new(factory).unwrap();
+        let output_db = create_test_rw_db();
+        let (from, to) = (260, 265);
+        StageDependencies::EXECUTION.copy(&db_tool, output_db.db(), from, to).unwrap();
+
+        let tx_range = tx_range(&db_tool, from, to).unwrap();
+        let expected_tx_range = {
+            let provider = db_tool.provider_factory.provider().unwrap();
+            provider.block_body_indices(from).unwrap().unwrap().first_tx_num..
+                provider.block_body_indices(to).unwrap().unwrap().next_tx_num()
+        };
+        assert_eq!(tx_range, expected_tx_range);
+        assert!(!tx_range.is_empty());
+
+        let tx = output_db.db().tx().unwrap();
+
+        // the headers of the range and the block hashes accessible by `BLOCKHASH` are copied
+        let header_numbers = tx
+            .cursor_read::<tables::CanonicalHeaders>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(header_numbers, (from - BLOCK_HASH_HISTORY..=to).collect::<Vec<_>>());
+        assert_eq!(tx.entries::<tables::Headers>().unwrap(), header_numbers.len());
+        assert_eq!(
+            tx.get::<tables::CanonicalHeaders>(to).unwrap(),
+            Some(blocks[to as usize].hash())
+        );
+
+        // only the transactions and senders of the range are copied
+        for table_tx_numbers in [
+            tx.cursor_read::<tables::Transactions>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|entry| entry.unwrap().0)
+                .collect::<Vec<_>>(),
+            tx.cursor_read::<tables::TransactionSenders>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|entry| entry.unwrap().0)
+                .collect::<Vec<_>>(),
+        ] {
+            assert_eq!(table_tx_numbers, tx_range.clone().collect::<Vec<_>>());
+        }
+
+        for stage in StageDependencies::EXECUTION.stages {
+            assert_eq!(
+                tx.get::<tables::StageCheckpoints>(stage.to_string()).unwrap(),
+                Some(StageCheckpoint::new(to))
+            );
+        }
+        assert_eq!(
+            tx.get::<tables::StageCheckpoints>(StageId::Execution.to_string()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn copy_block_hashes_from_genesis() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=10, BlockRangeParams::default());
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in blocks {
+            provider_rw
+                .insert_block(block.try_recover().unwrap(), StorageLocation::Database)
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let db_tool = DbTool::new(factory).unwrap();
+        let output_db = create_test_rw_db();
+        StageDependencies::EXECUTION.copy(&db_tool, output_db.db(), 5, 8).unwrap();
+
+        // the lookback is capped at the genesis block
+        let tx = output_db.db().tx().unwrap();
+        assert_eq!(tx.entries::<tables::CanonicalHeaders>().unwrap(), 9);
+        assert!(tx.get::<tables::CanonicalHeaders>(0).unwrap().is_some());
+    }
+}
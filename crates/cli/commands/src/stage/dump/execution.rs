@@ -1,12 +1,9 @@
-use super::setup;
+use super::{setup, StageDependencies};
 use reth_consensus::{noop::NoopConsensus, ConsensusError, FullConsensus};
 use reth_db::DatabaseEnv;
-use reth_db_api::{
-    cursor::DbCursorRO, database::Database, table::TableImporter, tables, transaction::DbTx,
-};
+use reth_db_api::{database::Database, table::TableImporter, tables};
 use reth_db_common::DbTool;
 use reth_evm::ConfigureEvm;
-use reth_node_builder::NodeTypesWithDB;
 use reth_node_core::dirs::{ChainPath, DataDirPath};
 use reth_provider::{
     providers::{ProviderNodeTypes, StaticFileProvider},
@@ -30,9 +27,8 @@ where
     E: ConfigureEvm<Primitives = N::Primitives> + 'static,
     C: FullConsensus<E::Primitives, Error = ConsensusError> + 'static,
 {
-    let (output_db, tip_block_number) = setup(from, to, &output_datadir.db(), db_tool)?;
-
-    import_tables_with_range(&output_db, db_tool, from, to)?;
+    let (output_db, tip_block_number) =
+        setup(from, to, &output_datadir.db(), db_tool, StageDependencies::EXECUTION)?;
 
     unwind_and_copy(db_tool, from, tip_block_number, &output_db, evm_config.clone())?;
 
@@ -53,84 +49,6 @@ where
     Ok(())
 }
 
-/// Imports all the tables that can be copied over a range.
-fn import_tables_with_range<N: NodeTypesWithDB>(
-    output_db: &DatabaseEnv,
-    db_tool: &DbTool<N>,
-    from: u64,
-    to: u64,
-) -> eyre::Result<()> {
-    //  We're not sharing the transaction in case the memory grows too much.
-
-    output_db.update(|tx| {
-        tx.import_table_with_range::<tables::CanonicalHeaders, _>(
-            &db_tool.provider_factory.db_ref().tx()?,
-            Some(from),
-            to,
-        )
-    })??;
-    output_db.update(|tx| {
-        tx.import_table_with_range::<tables::HeaderTerminalDifficulties, _>(
-            &db_tool.provider_factory.db_ref().tx()?,
-            Some(from),
-            to,
-        )
-    })??;
-    output_db.update(|tx| {
-        tx.import_table_with_range::<tables::Headers, _>(
-            &db_tool.provider_factory.db_ref().tx()?,
-            Some(from),
-            to,
-        )
-    })??;
-    output_db.update(|tx| {
-        tx.import_table_with_range::<tables::BlockBodyIndices, _>(
-            &db_tool.provider_factory.db_ref().tx()?,
-            Some(from),
-            to,
-        )
-    })??;
-    output_db.update(|tx| {
-        tx.import_table_with_range::<tables::BlockOmmers, _>(
-            &db_tool.provider_factory.db_ref().tx()?,
-            Some(from),
-            to,
-        )
-    })??;
-
-    // Find range of transactions that need to be copied over
-    let (from_tx, to_tx) = db_tool.provider_factory.db_ref().view(|read_tx| {
-        let mut read_cursor = read_tx.cursor_read::<tables::BlockBodyIndices>()?;
-        let (_, from_block) =
-            read_cursor.seek(from)?.ok_or(eyre::eyre!("BlockBody {from} does not exist."))?;
-        let (_, to_block) =
-            read_cursor.seek(to)?.ok_or(eyre::eyre!("BlockBody {to} does not exist."))?;
-
-        Ok::<(u64, u64), eyre::ErrReport>((
-            from_block.first_tx_num,
-            to_block.first_tx_num + to_block.tx_count,
-        ))
-    })??;
-
-    output_db.update(|tx| {
-        tx.import_table_with_range::<tables::Transactions, _>(
-            &db_tool.provider_factory.db_ref().tx()?,
-            Some(from_tx),
-            to_tx,
-        )
-    })??;
-
-    output_db.update(|tx| {
-        tx.import_table_with_range::<tables::TransactionSenders, _>(
-            &db_tool.provider_factory.db_ref().tx()?,
-            Some(from_tx),
-            to_tx,
-        )
-    })??;
-
-    Ok(())
-}
-
 /// Dry-run an unwind to FROM block, so we can get the `PlainStorageState` and
 /// `PlainAccountState` safely. There might be some state dependency from an address
 /// which hasn't been changed in the given range.
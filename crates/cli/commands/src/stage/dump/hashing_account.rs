@@ -1,4 +1,4 @@
-use super::setup;
+use super::{setup, StageDependencies};
 use alloy_primitives::BlockNumber;
 use eyre::Result;
 use reth_db::DatabaseEnv;
@@ -20,7 +20,8 @@ pub(crate) async fn dump_hashing_account_stage<N: ProviderNodeTypes<DB = Arc<Dat
     output_datadir: ChainPath<DataDirPath>,
     should_run: bool,
 ) -> Result<()> {
-    let (output_db, tip_block_number) = setup(from, to, &output_datadir.db(), db_tool)?;
+    let (output_db, tip_block_number) =
+        setup(from, to, &output_datadir.db(), db_tool, StageDependencies::HASHING)?;
 
     // Import relevant AccountChangeSets
     output_db.update(|tx| {
@@ -1,4 +1,4 @@
-use super::setup;
+use super::{setup, StageDependencies};
 use eyre::Result;
 use reth_db::DatabaseEnv;
 use reth_db_api::{database::Database, table::TableImporter, tables};
@@ -19,7 +19,8 @@ pub(crate) async fn dump_hashing_storage_stage<N: ProviderNodeTypes<DB = Arc<Dat
     output_datadir: ChainPath<DataDirPath>,
     should_run: bool,
 ) -> Result<()> {
-    let (output_db, tip_block_number) = setup(from, to, &output_datadir.db(), db_tool)?;
+    let (output_db, tip_block_number) =
+        setup(from, to, &output_datadir.db(), db_tool, StageDependencies::HASHING)?;
 
     unwind_and_copy(db_tool, from, tip_block_number, &output_db)?;
 
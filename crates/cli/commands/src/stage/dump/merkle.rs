@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
-use super::setup;
+use super::{setup, StageDependencies};
 use alloy_primitives::BlockNumber;
 use eyre::Result;
-use reth_config::config::EtlConfig;
+use reth_config::config::HashingMode;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db::DatabaseEnv;
 use reth_db_api::{database::Database, table::TableImporter, tables};
@@ -36,15 +36,8 @@ pub(crate) async fn dump_merkle_stage<N>(
 where
     N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>,
 {
-    let (output_db, tip_block_number) = setup(from, to, &output_datadir.db(), db_tool)?;
-
-    output_db.update(|tx| {
-        tx.import_table_with_range::<tables::Headers, _>(
-            &db_tool.provider_factory.db_ref().tx()?,
-            Some(from),
-            to,
-        )
-    })??;
+    let (output_db, tip_block_number) =
+        setup(from, to, &output_datadir.db(), db_tool, StageDependencies::MERKLE)?;
 
     output_db.update(|tx| {
         tx.import_table_with_range::<tables::AccountChangeSets, _>(
@@ -125,14 +118,16 @@ fn unwind_and_copy<N: ProviderNodeTypes>(
     AccountHashingStage {
         clean_threshold: u64::MAX,
         commit_threshold: u64::MAX,
-        etl_config: EtlConfig::default(),
+        mode: HashingMode::Incremental,
+        ..Default::default()
     }
     .execute(&provider, execute_input)
     .unwrap();
     StorageHashingStage {
         clean_threshold: u64::MAX,
         commit_threshold: u64::MAX,
-        etl_config: EtlConfig::default(),
+        mode: HashingMode::Incremental,
+        ..Default::default()
     }
     .execute(&provider, execute_input)
     .unwrap();
@@ -9,14 +9,17 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_db_common::DbTool;
-use reth_node_builder::NodeTypesWithDB;
 use reth_node_core::{
     args::DatadirArgs,
     dirs::{DataDirPath, PlatformPath},
 };
+use reth_provider::providers::ProviderNodeTypes;
 use std::{path::PathBuf, sync::Arc};
 use tracing::info;
 
+mod dependencies;
+use dependencies::StageDependencies;
+
 mod hashing_storage;
 use hashing_storage::dump_hashing_storage_stage;
 
@@ -123,13 +126,14 @@ impl<C: ChainSpecParser> Command<C> {
     }
 }
 
-/// Sets up the database and initial state on [`tables::BlockBodyIndices`]. Also returns the tip
-/// block number.
-pub(crate) fn setup<N: NodeTypesWithDB>(
+/// Sets up the database and initial state on [`tables::BlockBodyIndices`], and copies the
+/// [`StageDependencies`] of the dumped stage. Also returns the tip block number.
+pub(crate) fn setup<N: ProviderNodeTypes>(
     from: u64,
     to: u64,
     output_db: &PathBuf,
     db_tool: &DbTool<N>,
+    dependencies: StageDependencies,
 ) -> eyre::Result<(DatabaseEnv, u64)> {
     assert!(from < to, "FROM block should be lower than TO block.");
 
@@ -145,6 +149,8 @@ pub(crate) fn setup<N: NodeTypesWithDB>(
         )
     })??;
 
+    dependencies.copy(db_tool, &output_datadir, from, to)?;
+
     let (tip_block_number, _) = db_tool
         .provider_factory
         .db_ref()
@@ -0,0 +1,306 @@
+//! Generates engine API test vectors from blocks of the local database.
+//!
+//! The vectors contain the `engine_newPayload` and `engine_forkchoiceUpdated` calls that import
+//! a range of canonical blocks, together with the responses a client must return. Any client that
+//! is synced to the parent of the range can replay them.
+
+use crate::common::{
+    AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, Environment,
+    EnvironmentArgs,
+};
+use alloy_consensus::BlockHeader;
+use alloy_eips::{
+    eip7685::{Requests, EMPTY_REQUESTS_HASH},
+    BlockNumHash,
+};
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{
+    ExecutionPayload, ExecutionPayloadSidecar, ForkchoiceState, ForkchoiceUpdated, PayloadStatus,
+    PayloadStatusEnum,
+};
+use clap::Parser;
+use eyre::{eyre, OptionExt};
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_fs_util as fs;
+use reth_primitives_traits::Block;
+use reth_provider::{
+    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, TransactionVariant,
+};
+use reth_revm::database::StateProviderDatabase;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// Engine API test vectors for a range of canonical blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineTestVectors {
+    /// The chain id of the chain the blocks belong to.
+    pub chain_id: u64,
+    /// The genesis hash of the chain the blocks belong to.
+    pub genesis_hash: B256,
+    /// The block a client must be synced to before replaying the calls.
+    ///
+    /// This block is used as the safe and finalized block of all forkchoice updates.
+    pub parent: BlockNumHash,
+    /// The engine API calls, in the order they must be sent.
+    pub calls: Vec<EngineCall>,
+}
+
+/// An engine API call and the response a client must return.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineCall {
+    /// The name of the engine API method.
+    pub method: String,
+    /// The parameters of the call.
+    pub params: Vec<Value>,
+    /// The expected result of the call.
+    pub expected: Value,
+}
+
+/// `reth test-vectors engine` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block of the range.
+    #[arg(long)]
+    from: u64,
+
+    /// The last block of the range. Defaults to the latest block.
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// The file to write the test vectors to.
+    #[arg(long, short, default_value = "engine-test-vectors.json")]
+    output: PathBuf,
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
+    /// Execute `test-vectors engine` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let components = components(provider_factory.chain_spec());
+
+        let provider = provider_factory.provider()?;
+        let to = self.to.unwrap_or(provider.best_block_number()?);
+        if self.from == 0 || self.from > to {
+            return Err(eyre!("invalid block range {}..={}", self.from, to))
+        }
+
+        let parent_number = self.from - 1;
+        let parent_hash = provider
+            .block_hash(parent_number)?
+            .ok_or_eyre(format!("missing parent block {parent_number}"))?;
+        let parent = BlockNumHash::new(parent_number, parent_hash);
+
+        let mut calls = Vec::with_capacity(2 * (to - self.from + 1) as usize);
+        for number in self.from..=to {
+            let block = provider
+                .recovered_block(number.into(), TransactionVariant::WithHash)?
+                .ok_or_eyre(format!("missing block {number}"))?;
+            let hash = block.hash();
+
+            // execution requests are not stored, so blocks with requests are re-executed
+            let requests = match block.header().requests_hash() {
+                Some(EMPTY_REQUESTS_HASH) => Some(Requests::default()),
+                Some(_) => {
+                    let state = provider_factory.history_by_block_number(number - 1)?;
+                    let mut executor =
+                        components.evm_config().batch_executor(StateProviderDatabase::new(state));
+                    Some(executor.execute_one(&block)?.requests)
+                }
+                None => None,
+            };
+
+            let block = block.into_sealed_block().into_block().into_ethereum_block();
+            let (payload, sidecar) = ExecutionPayload::from_block_unchecked(hash, &block);
+            let version = payload_version(&payload);
+            calls.push(new_payload_call(payload, &sidecar, requests, hash)?);
+            calls.push(forkchoice_updated_call(version, hash, parent_hash)?);
+        }
+
+        let vectors = EngineTestVectors {
+            chain_id: provider_factory.chain_spec().chain_id(),
+            genesis_hash: provider_factory.chain_spec().genesis_hash(),
+            parent,
+            calls,
+        };
+        fs::write(&self.output, serde_json::to_vec_pretty(&vectors)?)?;
+
+        info!(
+            target: "reth::cli",
+            from = self.from,
+            to,
+            calls = vectors.calls.len(),
+            path = %self.output.display(),
+            "Wrote engine API test vectors"
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the version of the engine API that must be used for the payload.
+const fn payload_version(payload: &ExecutionPayload) -> u8 {
+    match payload {
+        ExecutionPayload::V1(_) => 1,
+        ExecutionPayload::V2(_) => 2,
+        ExecutionPayload::V3(_) => 3,
+    }
+}
+
+/// Returns the `engine_newPayload` call that imports the payload.
+fn new_payload_call(
+    payload: ExecutionPayload,
+    sidecar: &ExecutionPayloadSidecar,
+    requests: Option<Requests>,
+    hash: B256,
+) -> eyre::Result<EngineCall> {
+    let (method, params) = match payload {
+        ExecutionPayload::V1(payload) => {
+            ("engine_newPayloadV1", vec![serde_json::to_value(payload)?])
+        }
+        ExecutionPayload::V2(payload) => {
+            ("engine_newPayloadV2", vec![serde_json::to_value(payload)?])
+        }
+        ExecutionPayload::V3(payload) => {
+            let mut params = vec![
+                serde_json::to_value(payload)?,
+                serde_json::to_value(sidecar.versioned_hashes().cloned().unwrap_or_default())?,
+                serde_json::to_value(sidecar.parent_beacon_block_root())?,
+            ];
+            if let Some(requests) = requests {
+                params.push(serde_json::to_value(requests.take())?);
+                ("engine_newPayloadV4", params)
+            } else {
+                ("engine_newPayloadV3", params)
+            }
+        }
+    };
+
+    let expected = PayloadStatus::new(PayloadStatusEnum::Valid, Some(hash));
+    Ok(EngineCall { method: method.to_string(), params, expected: serde_json::to_value(expected)? })
+}
+
+/// Returns the `engine_forkchoiceUpdated` call that makes the block the canonical head.
+fn forkchoice_updated_call(version: u8, head: B256, finalized: B256) -> eyre::Result<EngineCall> {
+    let state = ForkchoiceState {
+        head_block_hash: head,
+        safe_block_hash: finalized,
+        finalized_block_hash: finalized,
+    };
+    let expected = ForkchoiceUpdated::new(PayloadStatus::new(PayloadStatusEnum::Valid, Some(head)));
+    Ok(EngineCall {
+        method: format!("engine_forkchoiceUpdatedV{version}"),
+        params: vec![serde_json::to_value(state)?, Value::Null],
+        expected: serde_json::to_value(expected)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_eips::eip4895::Withdrawals;
+    use alloy_primitives::Bytes;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_ethereum_primitives::{Block, BlockBody};
+
+    /// Returns the payload and sidecar of a block with the given header and withdrawals.
+    fn payload(
+        header: Header,
+        withdrawals: Option<Withdrawals>,
+    ) -> (B256, ExecutionPayload, ExecutionPayloadSidecar) {
+        let hash = header.hash_slow();
+        let block = Block { header, body: BlockBody { withdrawals, ..Default::default() } };
+        let (payload, sidecar) = ExecutionPayload::from_block_unchecked(hash, &block);
+        (hash, payload, sidecar)
+    }
+
+    #[test]
+    fn parse_engine_command() {
+        let cmd =
+            Command::<EthereumChainSpecParser>::parse_from(["reth", "--from", "1", "--to", "5"]);
+        assert_eq!((cmd.from, cmd.to), (1, Some(5)));
+        assert_eq!(cmd.output, PathBuf::from("engine-test-vectors.json"));
+    }
+
+    #[test]
+    fn new_payload_calls_follow_the_payload_version() {
+        // pre-Shanghai blocks use V1
+        let (hash, payload_v1, sidecar) = payload(Header::default(), None);
+        assert_eq!(payload_version(&payload_v1), 1);
+        let call = new_payload_call(payload_v1, &sidecar, None, hash).unwrap();
+        assert_eq!(call.method, "engine_newPayloadV1");
+        assert_eq!(call.params.len(), 1);
+
+        // Shanghai blocks with withdrawals use V2
+        let (hash, payload_v2, sidecar) = payload(Header::default(), Some(Withdrawals::default()));
+        assert_eq!(payload_version(&payload_v2), 2);
+        let call = new_payload_call(payload_v2, &sidecar, None, hash).unwrap();
+        assert_eq!(call.method, "engine_newPayloadV2");
+        assert_eq!(call.params.len(), 1);
+
+        // Cancun blocks pass the versioned hashes and the parent beacon block root
+        let cancun = Header {
+            parent_beacon_block_root: Some(B256::with_last_byte(1)),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        };
+        let (hash, payload_v3, sidecar) = payload(cancun.clone(), Some(Withdrawals::default()));
+        assert_eq!(payload_version(&payload_v3), 3);
+        let call = new_payload_call(payload_v3.clone(), &sidecar, None, hash).unwrap();
+        assert_eq!(call.method, "engine_newPayloadV3");
+        assert_eq!(
+            call.params[1..],
+            [serde_json::json!([]), serde_json::to_value(B256::with_last_byte(1)).unwrap()]
+        );
+
+        // Prague blocks additionally pass the execution requests
+        let requests = Requests::new(vec![Bytes::from_static(&[1, 2])]);
+        let call = new_payload_call(payload_v3, &sidecar, Some(requests.clone()), hash).unwrap();
+        assert_eq!(call.method, "engine_newPayloadV4");
+        assert_eq!(call.params.len(), 4);
+        assert_eq!(call.params[3], serde_json::to_value(requests.take()).unwrap());
+
+        // every payload is expected to be valid
+        assert_eq!(
+            call.expected,
+            serde_json::to_value(PayloadStatus::new(PayloadStatusEnum::Valid, Some(hash))).unwrap()
+        );
+    }
+
+    #[test]
+    fn forkchoice_updated_call_keeps_the_parent_finalized() {
+        let (head, parent) = (B256::with_last_byte(2), B256::with_last_byte(1));
+        let call = forkchoice_updated_call(3, head, parent).unwrap();
+        assert_eq!(call.method, "engine_forkchoiceUpdatedV3");
+
+        let state: ForkchoiceState = serde_json::from_value(call.params[0].clone()).unwrap();
+        assert_eq!(state.head_block_hash, head);
+        assert_eq!(state.safe_block_hash, parent);
+        assert_eq!(state.finalized_block_hash, parent);
+        assert_eq!(call.params[1], Value::Null);
+
+        let expected: ForkchoiceUpdated = serde_json::from_value(call.expected).unwrap();
+        assert_eq!(expected.payload_status.status, PayloadStatusEnum::Valid);
+        assert_eq!(expected.payload_status.latest_valid_hash, Some(head));
+        assert_eq!(expected.payload_id, None);
+    }
+}
@@ -1,20 +1,25 @@
 //! Command for generating test vectors.
 
+use crate::common::{CliComponentsBuilder, CliNodeTypes};
 use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use std::sync::Arc;
 
 pub mod compact;
+pub mod engine;
 pub mod tables;
 
 /// Generate test-vectors for different data types.
 #[derive(Debug, Parser)]
-pub struct Command {
+pub struct Command<C: ChainSpecParser> {
     #[command(subcommand)]
-    command: Subcommands,
+    command: Subcommands<C>,
 }
 
 #[derive(Subcommand, Debug)]
 /// `reth test-vectors` subcommands
-pub enum Subcommands {
+pub enum Subcommands<C: ChainSpecParser> {
     /// Generates test vectors for specified tables. If no table is specified, generate for all.
     Tables {
         /// List of table names. Case-sensitive.
@@ -36,11 +41,29 @@ pub enum Subcommands {
         #[arg(long)]
         read: bool,
     },
+    /// Generates engine API test vectors from a range of blocks of the local database.
+    ///
+    /// The vectors contain the `engine_newPayload` and `engine_forkchoiceUpdated` calls that
+    /// import the blocks, together with the expected responses.
+    Engine(Box<engine::Command<C>>),
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match &self.command {
+            Subcommands::Engine(command) => command.chain_spec(),
+            _ => None,
+        }
+    }
 }
 
-impl Command {
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
     /// Execute the command
-    pub async fn execute(self) -> eyre::Result<()> {
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
         match self.command {
             Subcommands::Tables { names } => {
                 tables::generate_vectors(names)?;
@@ -52,6 +75,7 @@ impl Command {
                     compact::read_vectors()?;
                 }
             }
+            Subcommands::Engine(command) => command.execute::<N>(components).await?,
         }
         Ok(())
     }
@@ -67,11 +67,12 @@ impl CliRunner {
             error!(target: "reth::cli", "shutting down due to error");
         } else {
             debug!(target: "reth::cli", "shutting down gracefully");
-            // after the command has finished or exit signal was received we shutdown the task
-            // manager which fires the shutdown signal to all tasks spawned via the task
-            // executor and awaiting on tasks spawned with graceful shutdown
-            task_manager.graceful_shutdown_with_timeout(Duration::from_secs(5));
         }
+        // after the command has finished, an exit signal was received or a critical task panicked
+        // we shutdown the task manager which fires the shutdown signal to all tasks spawned via
+        // the task executor and awaiting on tasks spawned with graceful shutdown, so that e.g.
+        // pending writes are flushed even if the node is going down due to an error
+        task_manager.graceful_shutdown_with_timeout(Duration::from_secs(5));
 
         // `drop(tokio_runtime)` would block the current thread until its pools
         // (including blocking pool) are shutdown. Since we want to exit as soon as possible, drop
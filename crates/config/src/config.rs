@@ -29,6 +29,12 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the database.
+    pub db: DatabaseConfig,
+    /// Configuration for the static files.
+    pub static_files: StaticFilesConfig,
+    /// Configuration for the supervision of subsystems that may be restarted after a panic.
+    pub supervision: SupervisionConfig,
 }
 
 impl Config {
@@ -125,8 +131,14 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Sender Transactions stage configuration.
+    pub index_sender_transactions: IndexSenderTransactionsConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
+    /// Static file producer configuration.
+    pub static_file_producer: StaticFileProducerConfig,
+    /// Stall detection configuration.
+    pub stall_detection: StallDetectionConfig,
 }
 
 impl StageConfig {
@@ -326,17 +338,48 @@ impl Default for PruneStageConfig {
 pub struct HashingConfig {
     /// The threshold (in number of blocks) for switching between
     /// incremental hashing and full hashing.
+    ///
+    /// Above this threshold the hashing is always done from scratch, because the changesets of
+    /// the blocks may be pruned.
     pub clean_threshold: u64,
     /// The maximum number of entities to process before committing progress to the database.
     pub commit_threshold: u64,
+    /// How to choose between incremental hashing and full hashing below the `clean_threshold`.
+    pub mode: HashingMode,
+    /// In [`HashingMode::Auto`], the hashing is done from scratch if the changesets of the blocks
+    /// are estimated to have more entries than this percentage of the entries of the plain state
+    /// table.
+    pub clean_changeset_percent: u64,
 }
 
 impl Default for HashingConfig {
     fn default() -> Self {
-        Self { clean_threshold: 500_000, commit_threshold: 100_000 }
+        Self {
+            clean_threshold: 500_000,
+            commit_threshold: 100_000,
+            mode: HashingMode::default(),
+            clean_changeset_percent: 10,
+        }
     }
 }
 
+/// How the hashing stages choose between hashing only the entries that changed in the synced
+/// blocks (incremental hashing) and hashing the whole plain state from scratch (full hashing).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum HashingMode {
+    /// Compare the number of changeset entries of the synced blocks with the size of the plain
+    /// state, see [`HashingConfig::clean_changeset_percent`].
+    #[default]
+    Auto,
+    /// Always hash incrementally, unless more than `clean_threshold` blocks are synced or the
+    /// hashing starts from genesis.
+    Incremental,
+    /// Always hash from scratch.
+    Full,
+}
+
 /// Merkle stage configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -411,6 +454,64 @@ impl EtlConfig {
     }
 }
 
+/// Static file producer configuration.
+///
+/// Controls how data is moved from the database to static files during live sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StaticFileProducerConfig {
+    /// The maximum number of blocks to copy and commit at once.
+    ///
+    /// `0` disables throttling and copies every segment at once.
+    pub chunk_size: u64,
+    /// The time to pause before copying each chunk.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub pause: Duration,
+    /// The maximum time to defer copying of a chunk while the engine is processing a payload.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub max_defer: Duration,
+}
+
+impl Default for StaticFileProducerConfig {
+    fn default() -> Self {
+        Self { chunk_size: 0, pause: Duration::ZERO, max_defer: Duration::from_secs(2) }
+    }
+}
+
+impl StaticFileProducerConfig {
+    /// Returns `true` if the copying is throttled.
+    pub const fn is_throttled(&self) -> bool {
+        self.chunk_size > 0
+    }
+}
+
+/// Configuration of the detection of stages that make no progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StallDetectionConfig {
+    /// The time after which a stage that made no checkpoint progress is reported as stalled.
+    ///
+    /// A zero timeout disables stall detection.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub timeout: Duration,
+}
+
+impl Default for StallDetectionConfig {
+    fn default() -> Self {
+        // 30 minutes
+        Self { timeout: Duration::from_secs(30 * 60) }
+    }
+}
+
+impl StallDetectionConfig {
+    /// Returns the stall timeout, or `None` if stall detection is disabled.
+    pub fn timeout(&self) -> Option<Duration> {
+        (!self.timeout.is_zero()).then_some(self.timeout)
+    }
+}
+
 /// History stage configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -426,6 +527,26 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Sender transactions index configuration.
+///
+/// The index is disabled by default. If enabled, the node maintains a mapping from sender to the
+/// transactions it sent, both during pipeline sync and live sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexSenderTransactionsConfig {
+    /// Whether the sender transactions index is maintained.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexSenderTransactionsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -487,6 +608,143 @@ impl PruneConfig {
     }
 }
 
+/// Database configuration.
+///
+/// Unset values fall back to the defaults of the database, and values passed on the command line
+/// take precedence over the configuration file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DatabaseConfig {
+    /// The maximum size of the database in bytes.
+    pub max_size: Option<usize>,
+    /// The size in bytes by which the database file grows when it runs out of space.
+    pub growth_step: Option<usize>,
+    /// How durably commits are written to disk.
+    pub sync_mode: DatabaseSyncMode,
+    /// Whether the OS reads ahead of the accessed database pages.
+    pub read_ahead: bool,
+}
+
+impl DatabaseConfig {
+    /// Checks that the configured values are consistent.
+    pub const fn validate(&self) -> Result<(), DatabaseConfigError> {
+        if let Some(0) = self.max_size {
+            return Err(DatabaseConfigError::ZeroMaxSize)
+        }
+        if let Some(0) = self.growth_step {
+            return Err(DatabaseConfigError::ZeroGrowthStep)
+        }
+        if let (Some(growth_step), Some(max_size)) = (self.growth_step, self.max_size) {
+            if growth_step > max_size {
+                return Err(DatabaseConfigError::GrowthStepExceedsMaxSize { growth_step, max_size })
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Static files configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StaticFilesConfig {
+    /// The directory the static files are stored in, e.g. on a separate volume.
+    ///
+    /// Defaults to `<DIR>/<CHAIN_ID>/static_files`. `--datadir.static-files` takes precedence.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub path: Option<PathBuf>,
+}
+
+/// Supervision configuration of the subsystems that may be restarted after a panic.
+///
+/// A panic of any other critical task, e.g. the engine, shuts the node down.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SupervisionConfig {
+    /// Restart configuration of the pruner.
+    pub pruner: RestartConfig,
+    /// Restart configuration of the static file producer.
+    pub static_file_producer: RestartConfig,
+}
+
+/// Restart configuration of a supervised subsystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RestartConfig {
+    /// Whether the subsystem is restarted after a panic. If disabled, which is the default, a
+    /// panic shuts the node down.
+    pub enabled: bool,
+    /// The backoff after the first panic. It is doubled on every consecutive panic.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub initial_backoff: Duration,
+    /// The maximum backoff between two runs.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub max_backoff: Duration,
+    /// The maximum number of restarts. Once exhausted, the next panic shuts the node down.
+    pub max_restarts: usize,
+}
+
+impl Default for RestartConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            max_restarts: 10,
+        }
+    }
+}
+
+/// How durably database commits are written to disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DatabaseSyncMode {
+    /// Data and metadata are flushed to disk on every commit. The database survives a system
+    /// crash at any time without losing committed data.
+    #[default]
+    Durable,
+    /// Data is flushed to disk on every commit, but metadata is not. A system crash may undo the
+    /// last commit, but can't corrupt the database.
+    NoMetaSync,
+    /// Flushing is left to the OS. A system crash may undo the commits since the last flush, but
+    /// can't corrupt the database.
+    SafeNoSync,
+}
+
+/// Error returned by [`DatabaseConfig::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseConfigError {
+    /// The maximum size is zero.
+    ZeroMaxSize,
+    /// The growth step is zero.
+    ZeroGrowthStep,
+    /// The growth step exceeds the maximum size.
+    GrowthStepExceedsMaxSize {
+        /// The configured growth step.
+        growth_step: usize,
+        /// The configured maximum size.
+        max_size: usize,
+    },
+}
+
+impl std::fmt::Display for DatabaseConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroMaxSize => f.write_str("database max size must be greater than zero"),
+            Self::ZeroGrowthStep => f.write_str("database growth step must be greater than zero"),
+            Self::GrowthStepExceedsMaxSize { growth_step, max_size } => {
+                write!(f, "database growth step {growth_step} exceeds the max size {max_size}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DatabaseConfigError {}
+
 /// Helper type to support older versions of Duration deserialization.
 #[cfg(feature = "serde")]
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
@@ -508,7 +766,9 @@ where
 
 #[cfg(all(test, feature = "serde"))]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{
+        Config, DatabaseConfig, DatabaseConfigError, DatabaseSyncMode, RestartConfig, EXTENSION,
+    };
     use crate::PruneConfig;
     use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
@@ -1078,4 +1338,75 @@ connect_trusted_nodes_only = true
             assert!(conf.peers.trusted_nodes.contains(&node));
         }
     }
+
+    #[test]
+    fn test_database_config() {
+        let s = r"#
+[db]
+max_size = 8796093022208
+growth_step = 4294967296
+sync_mode = 'safe_no_sync'
+read_ahead = true
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        assert_eq!(
+            conf.db,
+            DatabaseConfig {
+                max_size: Some(8796093022208),
+                growth_step: Some(4294967296),
+                sync_mode: DatabaseSyncMode::SafeNoSync,
+                read_ahead: true,
+            }
+        );
+        assert_eq!(conf.db.validate(), Ok(()));
+
+        let conf = DatabaseConfig { max_size: Some(1024), growth_step: Some(2048), ..conf.db };
+        assert_eq!(
+            conf.validate(),
+            Err(DatabaseConfigError::GrowthStepExceedsMaxSize {
+                growth_step: 2048,
+                max_size: 1024
+            })
+        );
+        assert_eq!(
+            DatabaseConfig { growth_step: Some(0), ..Default::default() }.validate(),
+            Err(DatabaseConfigError::ZeroGrowthStep)
+        );
+    }
+
+    #[test]
+    fn test_static_files_config() {
+        let s = r"#
+[static_files]
+path = '/mnt/static_files'
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        assert_eq!(conf.static_files.path, Some("/mnt/static_files".into()));
+
+        let conf: Config = toml::from_str("").unwrap();
+        assert_eq!(conf.static_files.path, None);
+    }
+
+    #[test]
+    fn test_supervision_config() {
+        let s = r"#
+[supervision.pruner]
+initial_backoff = '5s'
+max_restarts = 3
+
+[supervision.static_file_producer]
+enabled = true
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        assert_eq!(
+            conf.supervision.pruner,
+            RestartConfig {
+                initial_backoff: Duration::from_secs(5),
+                max_restarts: 3,
+                ..Default::default()
+            }
+        );
+        assert!(conf.supervision.static_file_producer.enabled);
+        assert_eq!(conf.supervision.static_file_producer.max_backoff, Duration::from_secs(60));
+    }
 }
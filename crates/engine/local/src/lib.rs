@@ -11,5 +11,7 @@
 pub mod miner;
 pub mod payload;
 
-pub use miner::{LocalMiner, MiningMode};
-pub use payload::LocalPayloadAttributesBuilder;
+pub use miner::{LocalMiner, LocalMinerHandle, MiningMode};
+pub use payload::{
+    LocalPayloadAttributesBuilder, PayloadAttributesInjector, PayloadAttributesOverrides,
+};
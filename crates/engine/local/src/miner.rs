@@ -1,10 +1,11 @@
 //! Contains the implementation of the mining mode for the local engine.
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{TxHash, B256};
+use alloy_primitives::{TxHash, B256, U256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use eyre::OptionExt;
 use futures_util::{stream::Fuse, StreamExt};
+use parking_lot::Mutex;
 use reth_engine_primitives::BeaconConsensusEngineHandle;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
@@ -13,12 +14,17 @@ use reth_payload_primitives::{
 use reth_provider::BlockReader;
 use reth_transaction_pool::TransactionPool;
 use std::{
+    collections::BTreeMap,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::{Duration, UNIX_EPOCH},
 };
-use tokio::time::Interval;
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Interval,
+};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::error;
 
@@ -69,6 +75,100 @@ impl Future for MiningMode {
     }
 }
 
+/// A command sent to the [`LocalMiner`] by a [`LocalMinerHandle`].
+#[derive(Debug)]
+enum MinerCommand {
+    /// Mine a block, optionally with the given timestamp.
+    Mine { timestamp: Option<u64>, tx: oneshot::Sender<eyre::Result<B256>> },
+    /// Move the clock of the miner forward by the given number of seconds.
+    IncreaseTime { seconds: u64, tx: oneshot::Sender<i64> },
+    /// Set the clock of the miner to the given timestamp.
+    SetTime { timestamp: u64, tx: oneshot::Sender<i64> },
+    /// Snapshot the current chain state.
+    Snapshot { tx: oneshot::Sender<U256> },
+    /// Revert the chain to the given snapshot.
+    Revert { id: U256, tx: oneshot::Sender<eyre::Result<bool>> },
+}
+
+/// A handle to control a running [`LocalMiner`].
+///
+/// The handle can be created before the miner, see [`LocalMiner::with_handle`].
+#[derive(Debug, Clone)]
+pub struct LocalMinerHandle {
+    /// Sender of the commands to the miner.
+    to_miner: mpsc::UnboundedSender<MinerCommand>,
+    /// The receiving half, until it is taken by the miner.
+    from_handle: Arc<Mutex<Option<mpsc::UnboundedReceiver<MinerCommand>>>>,
+}
+
+impl Default for LocalMinerHandle {
+    fn default() -> Self {
+        let (to_miner, from_handle) = mpsc::unbounded_channel();
+        Self { to_miner, from_handle: Arc::new(Mutex::new(Some(from_handle))) }
+    }
+}
+
+impl LocalMinerHandle {
+    /// Mines a new block and returns its hash.
+    ///
+    /// If a timestamp is given, it is used as the timestamp of the block and must be greater than
+    /// the timestamp of the current head.
+    pub async fn mine(&self, timestamp: Option<u64>) -> eyre::Result<B256> {
+        self.request(|tx| MinerCommand::Mine { timestamp, tx }).await?
+    }
+
+    /// Moves the clock of the miner forward by the given number of seconds.
+    ///
+    /// Returns the total time adjustment, in seconds.
+    pub async fn increase_time(&self, seconds: u64) -> eyre::Result<i64> {
+        self.request(|tx| MinerCommand::IncreaseTime { seconds, tx }).await
+    }
+
+    /// Sets the clock of the miner to the given timestamp.
+    ///
+    /// Returns the total time adjustment, in seconds.
+    pub async fn set_time(&self, timestamp: u64) -> eyre::Result<i64> {
+        self.request(|tx| MinerCommand::SetTime { timestamp, tx }).await
+    }
+
+    /// Snapshots the current chain state and returns the id of the snapshot.
+    pub async fn snapshot(&self) -> eyre::Result<U256> {
+        self.request(|tx| MinerCommand::Snapshot { tx }).await
+    }
+
+    /// Reverts the chain to the snapshot with the given id.
+    ///
+    /// This deletes the snapshot and all snapshots taken after it. Returns `false` if the snapshot
+    /// does not exist.
+    pub async fn revert(&self, id: U256) -> eyre::Result<bool> {
+        self.request(|tx| MinerCommand::Revert { id, tx }).await?
+    }
+
+    /// Sends a command to the miner and waits for the response.
+    async fn request<R>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<R>) -> MinerCommand,
+    ) -> eyre::Result<R> {
+        // the miner takes the receiver once it is created
+        eyre::ensure!(self.from_handle.lock().is_none(), "Local miner is not running");
+
+        let (tx, rx) = oneshot::channel();
+        self.to_miner.send(command(tx)).map_err(|_| eyre::eyre!("Local miner is not running"))?;
+        rx.await.map_err(|_| eyre::eyre!("Local miner dropped the request"))
+    }
+}
+
+/// The state of the [`LocalMiner`] at the time of a snapshot.
+#[derive(Debug)]
+struct ChainSnapshot {
+    /// Timestamp of the head block.
+    last_timestamp: u64,
+    /// Time adjustment of the miner clock.
+    time_offset: i64,
+    /// Latest mined blocks.
+    last_block_hashes: Vec<B256>,
+}
+
 /// Local miner advancing the chain
 #[derive(Debug)]
 pub struct LocalMiner<T: PayloadTypes, B> {
@@ -84,6 +184,16 @@ pub struct LocalMiner<T: PayloadTypes, B> {
     last_timestamp: u64,
     /// Stores latest mined blocks.
     last_block_hashes: Vec<B256>,
+    /// Time adjustment in seconds that is applied to the system time for new blocks.
+    time_offset: i64,
+    /// Handle to control the miner.
+    handle: LocalMinerHandle,
+    /// Receiver of the commands sent by [`LocalMinerHandle`]s.
+    commands: mpsc::UnboundedReceiver<MinerCommand>,
+    /// Snapshots of the chain, by id.
+    snapshots: BTreeMap<U256, ChainSnapshot>,
+    /// The id of the next snapshot.
+    next_snapshot_id: U256,
 }
 
 impl<T, B> LocalMiner<T, B>
@@ -101,6 +211,8 @@ where
     ) -> Self {
         let latest_header =
             provider.sealed_header(provider.best_block_number().unwrap()).unwrap().unwrap();
+        let handle = LocalMinerHandle::default();
+        let commands = handle.from_handle.lock().take().expect("new handle");
 
         Self {
             payload_attributes_builder,
@@ -109,7 +221,30 @@ where
             payload_builder,
             last_timestamp: latest_header.timestamp(),
             last_block_hashes: vec![latest_header.hash()],
+            time_offset: 0,
+            handle,
+            commands,
+            snapshots: BTreeMap::new(),
+            next_snapshot_id: U256::ZERO,
+        }
+    }
+
+    /// Controls the miner with the given handle, instead of the one created by
+    /// [`LocalMiner::new`].
+    ///
+    /// This has no effect if the handle is already used by another miner.
+    pub fn with_handle(mut self, handle: LocalMinerHandle) -> Self {
+        let commands = handle.from_handle.lock().take();
+        if let Some(commands) = commands {
+            self.commands = commands;
+            self.handle = handle;
         }
+        self
+    }
+
+    /// Returns a handle to control the miner.
+    pub fn handle(&self) -> LocalMinerHandle {
+        self.handle.clone()
     }
 
     /// Runs the [`LocalMiner`] in a loop, polling the miner and building payloads.
@@ -119,10 +254,14 @@ where
             tokio::select! {
                 // Wait for the interval or the pool to receive a transaction
                 _ = &mut self.mode => {
-                    if let Err(e) = self.advance().await {
+                    if let Err(e) = self.advance(None).await {
                         error!(target: "engine::local", "Error advancing the chain: {:?}", e);
                     }
                 }
+                // handle requests of the miner handles
+                Some(command) = self.commands.recv() => {
+                    self.on_command(command).await;
+                }
                 // send FCU once in a while
                 _ = fcu_interval.tick() => {
                     if let Err(e) = self.update_forkchoice_state().await {
@@ -133,6 +272,58 @@ where
         }
     }
 
+    /// Handles a command sent by a [`LocalMinerHandle`].
+    async fn on_command(&mut self, command: MinerCommand) {
+        match command {
+            MinerCommand::Mine { timestamp, tx } => {
+                let _ = tx.send(self.advance(timestamp).await);
+            }
+            MinerCommand::IncreaseTime { seconds, tx } => {
+                self.time_offset = self.time_offset.saturating_add_unsigned(seconds);
+                let _ = tx.send(self.time_offset);
+            }
+            MinerCommand::SetTime { timestamp, tx } => {
+                self.time_offset = (timestamp as i64).saturating_sub(now() as i64);
+                let _ = tx.send(self.time_offset);
+            }
+            MinerCommand::Snapshot { tx } => {
+                let id = self.next_snapshot_id;
+                self.next_snapshot_id += U256::from(1);
+                self.snapshots.insert(
+                    id,
+                    ChainSnapshot {
+                        last_timestamp: self.last_timestamp,
+                        time_offset: self.time_offset,
+                        last_block_hashes: self.last_block_hashes.clone(),
+                    },
+                );
+                let _ = tx.send(id);
+            }
+            MinerCommand::Revert { id, tx } => {
+                let _ = tx.send(self.revert(id).await);
+            }
+        }
+    }
+
+    /// Reverts the chain to the snapshot with the given id, deleting it and all later snapshots.
+    async fn revert(&mut self, id: U256) -> eyre::Result<bool> {
+        if !self.snapshots.contains_key(&id) {
+            return Ok(false)
+        }
+        let snapshot = self.snapshots.split_off(&id).remove(&id).expect("snapshot exists");
+
+        let ChainSnapshot { last_timestamp, time_offset, last_block_hashes } = snapshot;
+        let last_block_hashes = std::mem::replace(&mut self.last_block_hashes, last_block_hashes);
+        if let Err(err) = self.update_forkchoice_state().await {
+            self.last_block_hashes = last_block_hashes;
+            return Err(err)
+        }
+        self.last_timestamp = last_timestamp;
+        self.time_offset = time_offset;
+
+        Ok(true)
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
@@ -164,14 +355,23 @@ where
 
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
     /// through newPayload.
-    async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
-            self.last_timestamp + 1,
-            std::time::SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("cannot be earlier than UNIX_EPOCH")
-                .as_secs(),
-        );
+    ///
+    /// Returns the hash of the new block.
+    async fn advance(&mut self, timestamp: Option<u64>) -> eyre::Result<B256> {
+        let timestamp = match timestamp {
+            Some(timestamp) => {
+                eyre::ensure!(
+                    timestamp > self.last_timestamp,
+                    "Timestamp {timestamp} must be greater than the head timestamp {}",
+                    self.last_timestamp
+                );
+                timestamp
+            }
+            None => std::cmp::max(
+                self.last_timestamp + 1,
+                now().saturating_add_signed(self.time_offset),
+            ),
+        };
 
         let res = self
             .to_engine
@@ -195,6 +395,7 @@ where
         };
 
         let block = payload.block();
+        let block_hash = block.hash();
 
         let payload = T::block_to_payload(payload.block().clone());
         let res = self.to_engine.new_payload(payload).await?;
@@ -204,13 +405,21 @@ where
         }
 
         self.last_timestamp = timestamp;
-        self.last_block_hashes.push(block.hash());
+        self.last_block_hashes.push(block_hash);
         // ensure we keep at most 64 blocks
         if self.last_block_hashes.len() > 64 {
             self.last_block_hashes =
                 self.last_block_hashes.split_off(self.last_block_hashes.len() - 64);
         }
 
-        Ok(())
+        Ok(block_hash)
     }
 }
+
+/// Returns the current unix timestamp in seconds.
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("cannot be earlier than UNIX_EPOCH")
+        .as_secs()
+}
@@ -1,11 +1,14 @@
 //! The implementation of the [`PayloadAttributesBuilder`] for the
 //! [`LocalMiner`](super::LocalMiner).
 
+use alloy_eips::eip4895::Withdrawal;
 use alloy_primitives::{Address, B256};
+use parking_lot::Mutex;
 use reth_chainspec::EthereumHardforks;
 use reth_ethereum_engine_primitives::EthPayloadAttributes;
 use reth_payload_primitives::PayloadAttributesBuilder;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, sync::Arc};
 
 /// The attributes builder for local Ethereum payload.
 #[derive(Debug)]
@@ -13,12 +16,20 @@ use std::sync::Arc;
 pub struct LocalPayloadAttributesBuilder<ChainSpec> {
     /// The chainspec
     pub chain_spec: Arc<ChainSpec>,
+    /// Optional injector for overriding the built attributes.
+    pub injector: Option<PayloadAttributesInjector>,
 }
 
 impl<ChainSpec> LocalPayloadAttributesBuilder<ChainSpec> {
     /// Creates a new instance of the builder.
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self { chain_spec, injector: None }
+    }
+
+    /// Applies the overrides of the given [`PayloadAttributesInjector`] to the built attributes.
+    pub fn with_injector(mut self, injector: PayloadAttributesInjector) -> Self {
+        self.injector = Some(injector);
+        self
     }
 }
 
@@ -28,7 +39,7 @@ where
     ChainSpec: Send + Sync + EthereumHardforks + 'static,
 {
     fn build(&self, timestamp: u64) -> EthPayloadAttributes {
-        EthPayloadAttributes {
+        let mut attributes = EthPayloadAttributes {
             timestamp,
             prev_randao: B256::random(),
             suggested_fee_recipient: Address::random(),
@@ -40,7 +51,13 @@ where
                 .chain_spec
                 .is_cancun_active_at_timestamp(timestamp)
                 .then(B256::random),
+        };
+
+        if let Some(injector) = &self.injector {
+            injector.next_overrides().apply(&mut attributes);
         }
+
+        attributes
     }
 }
 
@@ -64,3 +81,147 @@ where
         }
     }
 }
+
+/// Overrides for the payload attributes built by the [`LocalPayloadAttributesBuilder`].
+///
+/// Fields that are not set keep the built value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadAttributesOverrides {
+    /// Address of the fee recipient.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_fee_recipient: Option<Address>,
+    /// Value for the `prevRandao` field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_randao: Option<B256>,
+    /// Withdrawals to include in the block.
+    ///
+    /// Only applied if Shanghai is active for the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Root of the parent beacon block.
+    ///
+    /// Only applied if Cancun is active for the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<B256>,
+}
+
+impl PayloadAttributesOverrides {
+    /// Returns the overrides of `other`, falling back to the overrides of `self` for fields that
+    /// are not set in `other`.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            suggested_fee_recipient: other.suggested_fee_recipient.or(self.suggested_fee_recipient),
+            prev_randao: other.prev_randao.or(self.prev_randao),
+            withdrawals: other.withdrawals.or(self.withdrawals),
+            parent_beacon_block_root: other
+                .parent_beacon_block_root
+                .or(self.parent_beacon_block_root),
+        }
+    }
+
+    /// Applies the overrides to the given payload attributes.
+    pub fn apply(self, attributes: &mut EthPayloadAttributes) {
+        if let Some(suggested_fee_recipient) = self.suggested_fee_recipient {
+            attributes.suggested_fee_recipient = suggested_fee_recipient;
+        }
+        if let Some(prev_randao) = self.prev_randao {
+            attributes.prev_randao = prev_randao;
+        }
+        if let Some(withdrawals) = self.withdrawals {
+            if attributes.withdrawals.is_some() {
+                attributes.withdrawals = Some(withdrawals);
+            }
+        }
+        if let Some(parent_beacon_block_root) = self.parent_beacon_block_root {
+            if attributes.parent_beacon_block_root.is_some() {
+                attributes.parent_beacon_block_root = Some(parent_beacon_block_root);
+            }
+        }
+    }
+}
+
+/// A shared handle to inject [`PayloadAttributesOverrides`] into the payload attributes built by
+/// the [`LocalPayloadAttributesBuilder`].
+///
+/// Overrides can either be applied to every block, or be queued for the next blocks only. Queued
+/// overrides are consumed in order, one per block, and take precedence over the overrides applied
+/// to every block.
+#[derive(Debug, Clone, Default)]
+pub struct PayloadAttributesInjector {
+    inner: Arc<Mutex<InjectorInner>>,
+}
+
+#[derive(Debug, Default)]
+struct InjectorInner {
+    /// Overrides applied to every block.
+    every_block: PayloadAttributesOverrides,
+    /// Overrides queued for the next blocks.
+    next_blocks: VecDeque<PayloadAttributesOverrides>,
+}
+
+impl PayloadAttributesInjector {
+    /// Creates a new injector that applies the given overrides to every block.
+    pub fn new(every_block: PayloadAttributesOverrides) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(InjectorInner {
+                every_block,
+                next_blocks: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Sets the overrides applied to every block.
+    pub fn set_every_block(&self, overrides: PayloadAttributesOverrides) {
+        self.inner.lock().every_block = overrides;
+    }
+
+    /// Queues overrides for the next block that has no queued overrides yet.
+    pub fn inject_next_block(&self, overrides: PayloadAttributesOverrides) {
+        self.inner.lock().next_blocks.push_back(overrides);
+    }
+
+    /// Returns the overrides for the next block, consuming the queued overrides, if any.
+    pub fn next_overrides(&self) -> PayloadAttributesOverrides {
+        let mut inner = self.inner.lock();
+        let every_block = inner.every_block.clone();
+        match inner.next_blocks.pop_front() {
+            Some(next_block) => every_block.merge(next_block),
+            None => every_block,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use reth_chainspec::MAINNET;
+
+    #[test]
+    fn inject_overrides() {
+        let fee_recipient = address!("0x0000000000000000000000000000000000000001");
+        let withdrawal =
+            Withdrawal { index: 1, validator_index: 2, address: fee_recipient, amount: 3 };
+
+        let injector = PayloadAttributesInjector::new(PayloadAttributesOverrides {
+            suggested_fee_recipient: Some(fee_recipient),
+            ..Default::default()
+        });
+        injector.inject_next_block(PayloadAttributesOverrides {
+            withdrawals: Some(vec![withdrawal]),
+            ..Default::default()
+        });
+
+        let builder = LocalPayloadAttributesBuilder::new(MAINNET.clone()).with_injector(injector);
+        let timestamp = u64::MAX;
+
+        let attributes: EthPayloadAttributes = builder.build(timestamp);
+        assert_eq!(attributes.suggested_fee_recipient, fee_recipient);
+        assert_eq!(attributes.withdrawals, Some(vec![withdrawal]));
+
+        let attributes: EthPayloadAttributes = builder.build(timestamp);
+        assert_eq!(attributes.suggested_fee_recipient, fee_recipient);
+        assert_eq!(attributes.withdrawals, Some(vec![]));
+    }
+}
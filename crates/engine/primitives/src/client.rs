@@ -0,0 +1,208 @@
+//! Tracking of the consensus clients that are connected to the engine API.
+
+use alloc::{sync::Arc, vec::Vec};
+use alloy_rpc_types_engine::ClientVersionV1;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, PoisonError};
+
+/// The maximum number of consensus clients that are remembered at once.
+pub const MAX_CONSENSUS_CLIENTS: usize = 16;
+
+/// A consensus client that identified itself via `engine_getClientVersionV1`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsensusClientInfo {
+    /// Identifier of the engine API connection the client used for the version exchange, if
+    /// known.
+    pub connection_id: Option<u64>,
+    /// The version reported by the consensus client.
+    pub version: ClientVersionV1,
+    /// Unix timestamp in seconds of the first version exchange with this client.
+    pub first_seen: u64,
+    /// Unix timestamp in seconds of the most recent version exchange with this client.
+    pub last_seen: u64,
+}
+
+/// Outcome of recording a version exchange in [`ConsensusClients`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsensusClientUpdate {
+    /// A client that was not known before identified itself.
+    New,
+    /// A known client reported a different version on the same connection, e.g. after an
+    /// upgrade.
+    Changed {
+        /// The version that was previously reported on the connection.
+        previous: ClientVersionV1,
+    },
+    /// A known client repeated the exchange with the same version.
+    Unchanged,
+}
+
+/// Keeps track of the consensus clients that are connected to the engine API.
+///
+/// Clients are identified by the connection they used for `engine_getClientVersionV1`, so that
+/// multiple consensus clients of the same version are tracked separately. Only if the connection
+/// is unknown, a client is identified by its reported version. At most [`MAX_CONSENSUS_CLIENTS`]
+/// are retained, the least recently seen client is evicted first.
+///
+/// This type is cheap to clone and all clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct ConsensusClients {
+    clients: Arc<Mutex<Vec<ConsensusClientInfo>>>,
+}
+
+impl ConsensusClients {
+    /// Records a version exchange with a consensus client at the given unix timestamp.
+    pub fn on_client_version(
+        &self,
+        connection_id: Option<u64>,
+        version: ClientVersionV1,
+        now: u64,
+    ) -> ConsensusClientUpdate {
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let existing = match connection_id {
+            Some(id) => clients.iter().position(|client| client.connection_id == Some(id)),
+            None => clients
+                .iter()
+                .position(|client| client.connection_id.is_none() && client.version == version),
+        };
+        if let Some(idx) = existing {
+            let client = &mut clients[idx];
+            client.connection_id = connection_id;
+            client.last_seen = now;
+            if client.version == version {
+                return ConsensusClientUpdate::Unchanged
+            }
+            client.first_seen = now;
+            let previous = core::mem::replace(&mut client.version, version);
+            return ConsensusClientUpdate::Changed { previous }
+        }
+
+        if clients.len() >= MAX_CONSENSUS_CLIENTS {
+            if let Some(idx) = clients
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, client)| client.last_seen)
+                .map(|(idx, _)| idx)
+            {
+                clients.remove(idx);
+            }
+        }
+        clients.push(ConsensusClientInfo {
+            connection_id,
+            version,
+            first_seen: now,
+            last_seen: now,
+        });
+        ConsensusClientUpdate::New
+    }
+
+    /// Returns the version reported by the consensus client on the given connection, if any.
+    pub fn client_version(&self, connection_id: u64) -> Option<ClientVersionV1> {
+        self.clients
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|client| client.connection_id == Some(connection_id))
+            .map(|client| client.version.clone())
+    }
+
+    /// Returns all known consensus clients, most recently seen first.
+    pub fn clients(&self) -> Vec<ConsensusClientInfo> {
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        clients.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+        clients
+    }
+
+    /// Returns the number of known consensus clients.
+    pub fn len(&self) -> usize {
+        self.clients.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Returns `true` if no consensus client identified itself yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::ClientCode;
+
+    fn version(code: ClientCode, version: &str) -> ClientVersionV1 {
+        ClientVersionV1 {
+            code,
+            name: code.client_name().to_string(),
+            version: version.to_string(),
+            commit: "00000000".to_string(),
+        }
+    }
+
+    #[test]
+    fn tracks_clients_per_connection() {
+        let clients = ConsensusClients::default();
+        let lighthouse = version(ClientCode::LH, "v7.0.0");
+        let teku = version(ClientCode::TK, "25.6.0");
+
+        assert_eq!(
+            clients.on_client_version(Some(1), lighthouse.clone(), 10),
+            ConsensusClientUpdate::New
+        );
+        assert_eq!(
+            clients.on_client_version(Some(2), teku.clone(), 20),
+            ConsensusClientUpdate::New
+        );
+        assert_eq!(
+            clients.on_client_version(Some(1), lighthouse.clone(), 30),
+            ConsensusClientUpdate::Unchanged
+        );
+        assert_eq!(clients.len(), 2);
+
+        // a second client of the same version is tracked separately
+        assert_eq!(
+            clients.on_client_version(Some(3), teku.clone(), 40),
+            ConsensusClientUpdate::New
+        );
+        assert_eq!(clients.len(), 3);
+        assert_eq!(clients.client_version(3), Some(teku.clone()));
+
+        // upgrade on the same connection
+        let upgraded = version(ClientCode::LH, "v7.1.0");
+        assert_eq!(
+            clients.on_client_version(Some(1), upgraded.clone(), 50),
+            ConsensusClientUpdate::Changed { previous: lighthouse }
+        );
+
+        let known = clients.clients();
+        assert_eq!(known[0].version, upgraded);
+        assert_eq!(known[0].first_seen, 50);
+        assert_eq!(known[1].connection_id, Some(3));
+        assert_eq!(known[1].first_seen, 40);
+        assert_eq!(known[2].connection_id, Some(2));
+
+        // clients without a known connection are identified by their version
+        assert_eq!(clients.on_client_version(None, teku.clone(), 60), ConsensusClientUpdate::New);
+        assert_eq!(
+            clients.on_client_version(None, teku.clone(), 70),
+            ConsensusClientUpdate::Unchanged
+        );
+        assert_eq!(clients.len(), 4);
+    }
+
+    #[test]
+    fn evicts_least_recently_seen() {
+        let clients = ConsensusClients::default();
+        for i in 0..MAX_CONSENSUS_CLIENTS as u64 {
+            clients.on_client_version(Some(i), version(ClientCode::LH, &i.to_string()), i);
+        }
+        clients.on_client_version(Some(0), version(ClientCode::LH, "0"), 100);
+        clients.on_client_version(Some(100), version(ClientCode::PM, "new"), 101);
+
+        let known = clients.clients();
+        assert_eq!(known.len(), MAX_CONSENSUS_CLIENTS);
+        assert!(known.iter().all(|client| client.connection_id != Some(1)));
+        assert!(known.iter().any(|client| client.connection_id == Some(0)));
+    }
+}
@@ -95,6 +95,27 @@ pub struct TreeConfig {
     /// where immediate payload regeneration is desired despite the head not changing or moving to
     /// an ancestor.
     always_process_payload_attributes_on_canonical_head: bool,
+    /// Maximum number of `newPayload` requests that are queued while the engine is busy.
+    ///
+    /// If the queue is full, the payload with the lowest priority is answered with `SYNCING` or
+    /// `ACCEPTED` without being processed. `None` means that the queue is unbounded.
+    max_queued_payloads: Option<usize>,
+    /// Number of queued `newPayload` requests from which on only payloads that extend the
+    /// canonical head are queued.
+    ///
+    /// Other payloads are answered with `SYNCING` or `ACCEPTED` without being processed, so that
+    /// side-chain payloads can't delay the execution of the canonical chain. `None` disables the
+    /// limit.
+    soft_max_queued_payloads: Option<usize>,
+    /// Whether to record the execution witnesses of executed blocks, which are emitted once the
+    /// blocks are canonicalized.
+    emit_execution_witnesses: bool,
+    /// Whether a forkchoice update to an ancestor of the canonical head unwinds the canonical
+    /// chain to that ancestor.
+    ///
+    /// By default such an update is skipped, as allowed by the Engine API specification. This is
+    /// used by dev mode to revert the chain to an earlier block.
+    unwind_canonical_header: bool,
 }
 
 impl Default for TreeConfig {
@@ -117,6 +138,10 @@ impl Default for TreeConfig {
             precompile_cache_disabled: false,
             state_root_fallback: false,
             always_process_payload_attributes_on_canonical_head: false,
+            max_queued_payloads: None,
+            soft_max_queued_payloads: None,
+            emit_execution_witnesses: false,
+            unwind_canonical_header: false,
         }
     }
 }
@@ -142,6 +167,10 @@ impl TreeConfig {
         precompile_cache_disabled: bool,
         state_root_fallback: bool,
         always_process_payload_attributes_on_canonical_head: bool,
+        max_queued_payloads: Option<usize>,
+        soft_max_queued_payloads: Option<usize>,
+        emit_execution_witnesses: bool,
+        unwind_canonical_header: bool,
     ) -> Self {
         Self {
             persistence_threshold,
@@ -161,6 +190,10 @@ impl TreeConfig {
             precompile_cache_disabled,
             state_root_fallback,
             always_process_payload_attributes_on_canonical_head,
+            max_queued_payloads,
+            soft_max_queued_payloads,
+            emit_execution_witnesses,
+            unwind_canonical_header,
         }
     }
 
@@ -257,6 +290,28 @@ impl TreeConfig {
         self.always_process_payload_attributes_on_canonical_head
     }
 
+    /// Return the maximum number of queued `newPayload` requests, if the queue is bounded.
+    pub const fn max_queued_payloads(&self) -> Option<usize> {
+        self.max_queued_payloads
+    }
+
+    /// Return the number of queued `newPayload` requests from which on only payloads that extend
+    /// the canonical head are queued, if limited.
+    pub const fn soft_max_queued_payloads(&self) -> Option<usize> {
+        self.soft_max_queued_payloads
+    }
+
+    /// Returns whether the execution witnesses of canonical blocks should be recorded and emitted.
+    pub const fn emit_execution_witnesses(&self) -> bool {
+        self.emit_execution_witnesses
+    }
+
+    /// Returns whether a forkchoice update to an ancestor of the canonical head unwinds the
+    /// canonical chain.
+    pub const fn unwind_canonical_header(&self) -> bool {
+        self.unwind_canonical_header
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -375,6 +430,35 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for the maximum number of queued `newPayload` requests.
+    pub const fn with_max_queued_payloads(mut self, max_queued_payloads: Option<usize>) -> Self {
+        self.max_queued_payloads = max_queued_payloads;
+        self
+    }
+
+    /// Setter for the number of queued `newPayload` requests from which on only payloads that
+    /// extend the canonical head are queued.
+    pub const fn with_soft_max_queued_payloads(
+        mut self,
+        soft_max_queued_payloads: Option<usize>,
+    ) -> Self {
+        self.soft_max_queued_payloads = soft_max_queued_payloads;
+        self
+    }
+
+    /// Setter for whether to record and emit the execution witnesses of canonical blocks.
+    pub const fn with_emit_execution_witnesses(mut self, emit_execution_witnesses: bool) -> Self {
+        self.emit_execution_witnesses = emit_execution_witnesses;
+        self
+    }
+
+    /// Setter for whether a forkchoice update to an ancestor of the canonical head unwinds the
+    /// canonical chain.
+    pub const fn with_unwind_canonical_header(mut self, unwind_canonical_header: bool) -> Self {
+        self.unwind_canonical_header = unwind_canonical_header;
+        self
+    }
+
     /// Whether or not to use state root task
     pub const fn use_state_root_task(&self) -> bool {
         self.has_enough_parallelism && !self.legacy_state_root
@@ -32,6 +32,13 @@ pub use message::*;
 mod event;
 pub use event::*;
 
+#[cfg(feature = "std")]
+mod client;
+#[cfg(feature = "std")]
+pub use client::{
+    ConsensusClientInfo, ConsensusClientUpdate, ConsensusClients, MAX_CONSENSUS_CLIENTS,
+};
+
 mod invalid_block_hook;
 pub use invalid_block_hook::InvalidBlockHook;
 
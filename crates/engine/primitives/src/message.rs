@@ -160,6 +160,11 @@ pub enum BeaconEngineMessage<Payload: PayloadTypes> {
         /// The sender for returning forkchoice updated result.
         tx: oneshot::Sender<RethResult<OnForkChoiceUpdated>>,
     },
+    /// Message to forget all blocks that are known to be invalid, including the persisted ones.
+    ClearInvalidBlocks {
+        /// The sender for returning the number of removed persisted invalid blocks.
+        tx: oneshot::Sender<usize>,
+    },
 }
 
 impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
@@ -183,6 +188,7 @@ impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
                     payload_attrs.is_some()
                 )
             }
+            Self::ClearInvalidBlocks { .. } => f.write_str("ClearInvalidBlocks"),
         }
     }
 }
@@ -253,4 +259,14 @@ where
         });
         rx
     }
+
+    /// Tells the beacon consensus engine to forget all blocks that are known to be invalid, so
+    /// that they are validated again when they are received.
+    ///
+    /// Returns the number of removed persisted invalid blocks.
+    pub async fn clear_invalid_blocks(&self) -> Result<usize, oneshot::error::RecvError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::ClearInvalidBlocks { tx });
+        rx.await
+    }
 }
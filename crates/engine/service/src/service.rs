@@ -8,7 +8,9 @@ use reth_engine_tree::{
     download::BasicBlockDownloader,
     engine::{EngineApiKind, EngineApiRequest, EngineApiRequestHandler, EngineHandler},
     persistence::PersistenceHandle,
-    tree::{EngineApiTreeHandler, EngineValidator, InvalidBlockHook, TreeConfig},
+    tree::{
+        EngineApiTreeHandler, EngineValidator, ExecutionWitnessSink, InvalidBlockHook, TreeConfig,
+    },
 };
 pub use reth_engine_tree::{
     chain::{ChainEvent, ChainOrchestrator},
@@ -83,6 +85,7 @@ where
         payload_validator: V,
         tree_config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N::Primitives>>,
+        execution_witness_sink: Box<dyn ExecutionWitnessSink<N::Primitives>>,
         sync_metrics_tx: MetricEventsSender,
         evm_config: C,
     ) -> Self
@@ -109,6 +112,7 @@ where
             canonical_in_memory_state,
             tree_config,
             invalid_block_hook,
+            execution_witness_sink,
             engine_kind,
             evm_config,
         );
@@ -150,7 +154,10 @@ mod tests {
     use super::*;
     use reth_chainspec::{ChainSpecBuilder, MAINNET};
     use reth_engine_primitives::BeaconEngineMessage;
-    use reth_engine_tree::{test_utils::TestPipelineBuilder, tree::NoopInvalidBlockHook};
+    use reth_engine_tree::{
+        test_utils::TestPipelineBuilder,
+        tree::{NoopExecutionWitnessSink, NoopInvalidBlockHook},
+    };
     use reth_ethereum_consensus::EthBeaconConsensus;
     use reth_ethereum_engine_primitives::EthEngineTypes;
     use reth_evm_ethereum::EthEvmConfig;
@@ -211,6 +218,7 @@ mod tests {
             engine_payload_validator,
             TreeConfig::default(),
             Box::new(NoopInvalidBlockHook::default()),
+            Box::new(NoopExecutionWitnessSink::default()),
             sync_metrics_tx,
             evm_config,
         );
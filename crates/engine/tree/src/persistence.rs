@@ -1,13 +1,14 @@
 use crate::metrics::PersistenceMetrics;
 use alloy_consensus::BlockHeader;
-use alloy_eips::BlockNumHash;
+use alloy_eips::{eip1898::BlockWithParent, BlockNumHash};
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_errors::ProviderError;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    providers::ProviderNodeTypes, writer::UnifiedStorageWriter, BlockHashReader,
-    ChainStateBlockWriter, DatabaseProviderFactory, ProviderFactory, StaticFileProviderFactory,
+    providers::ProviderNodeTypes, writer::UnifiedStorageWriter, BlockHashReader, BlockNumReader,
+    ChainStateBlockReader, ChainStateBlockWriter, DatabaseProviderFactory, InvalidBlockWriter,
+    ProviderFactory, StaticFileProviderFactory,
 };
 use reth_prune::{PrunerError, PrunerOutput, PrunerWithFactory};
 use reth_stages_api::{MetricEvent, MetricEventsSender};
@@ -41,6 +42,10 @@ where
     metrics: PersistenceMetrics,
     /// Sender for sync metrics - we only submit sync metrics for persisted blocks
     sync_metrics_tx: MetricEventsSender,
+    /// The latest finalized block number received, which may not be persisted yet.
+    finalized_block: Option<u64>,
+    /// The latest safe block number received, which may not be persisted yet.
+    safe_block: Option<u64>,
 }
 
 impl<N> PersistenceService<N>
@@ -54,7 +59,15 @@ where
         pruner: PrunerWithFactory<ProviderFactory<N>>,
         sync_metrics_tx: MetricEventsSender,
     ) -> Self {
-        Self { provider, incoming, pruner, metrics: PersistenceMetrics::default(), sync_metrics_tx }
+        Self {
+            provider,
+            incoming,
+            pruner,
+            metrics: PersistenceMetrics::default(),
+            sync_metrics_tx,
+            finalized_block: None,
+            safe_block: None,
+        }
     }
 
     /// Prunes block data before the given block hash according to the configured prune
@@ -107,14 +120,25 @@ where
                     }
                 }
                 PersistenceAction::SaveFinalizedBlock(finalized_block) => {
-                    let provider = self.provider.database_provider_rw()?;
-                    provider.save_finalized_block_number(finalized_block)?;
-                    provider.commit()?;
+                    self.finalized_block = Some(finalized_block);
+                    self.on_save_chain_state()?;
                 }
                 PersistenceAction::SaveSafeBlock(safe_block) => {
-                    let provider = self.provider.database_provider_rw()?;
-                    provider.save_safe_block_number(safe_block)?;
-                    provider.commit()?;
+                    self.safe_block = Some(safe_block);
+                    self.on_save_chain_state()?;
+                }
+                PersistenceAction::SaveInvalidBlock(block, max_blocks) => {
+                    let provider_rw = self.provider.database_provider_rw()?;
+                    provider_rw.save_invalid_block(block, max_blocks)?;
+                    provider_rw.commit()?;
+                }
+                PersistenceAction::ClearInvalidBlocks(sender) => {
+                    let provider_rw = self.provider.database_provider_rw()?;
+                    let removed = provider_rw.clear_invalid_blocks()?;
+                    provider_rw.commit()?;
+                    debug!(target: "engine::persistence", removed, "Cleared invalid blocks");
+                    // we ignore the error because the caller may or may not care about the result
+                    let _ = sender.send(removed);
                 }
             }
         }
@@ -132,6 +156,13 @@ where
 
         let new_tip_hash = provider_rw.block_hash(new_tip_num)?;
         UnifiedStorageWriter::from(&provider_rw, &sf_provider).remove_blocks_above(new_tip_num)?;
+        // the persisted markers must not point to removed blocks
+        if provider_rw.last_finalized_block_number()?.is_some_and(|number| number > new_tip_num) {
+            provider_rw.save_finalized_block_number(new_tip_num)?;
+        }
+        if provider_rw.last_safe_block_number()?.is_some_and(|number| number > new_tip_num) {
+            provider_rw.save_safe_block_number(new_tip_num)?;
+        }
         UnifiedStorageWriter::commit_unwind(provider_rw)?;
 
         debug!(target: "engine::persistence", ?new_tip_num, ?new_tip_hash, "Removed blocks from disk");
@@ -155,11 +186,43 @@ where
             let static_file_provider = self.provider.static_file_provider();
 
             UnifiedStorageWriter::from(&provider_rw, &static_file_provider).save_blocks(blocks)?;
+            if let Some(last) = last_block_hash_num {
+                self.write_chain_state(&provider_rw, last.number)?;
+            }
             UnifiedStorageWriter::commit(provider_rw)?;
         }
         self.metrics.save_blocks_duration_seconds.record(start_time.elapsed());
         Ok(last_block_hash_num)
     }
+
+    /// Persists the finalized and safe block numbers up to the last persisted block.
+    fn on_save_chain_state(&self) -> Result<(), PersistenceError> {
+        let provider_rw = self.provider.database_provider_rw()?;
+        let last_block_number = provider_rw.last_block_number()?;
+        self.write_chain_state(&provider_rw, last_block_number)?;
+        provider_rw.commit()?;
+        Ok(())
+    }
+
+    /// Writes the finalized and safe block numbers, capped at the given persisted block number.
+    ///
+    /// The finalized and safe blocks can be ahead of the blocks on disk. Capping them ensures that
+    /// the persisted markers always point to persisted blocks, so that they can be restored
+    /// immediately after a restart. Since the markers are also written together with every range
+    /// of saved blocks, they catch up with the tracked blocks as they are persisted.
+    fn write_chain_state<P: ChainStateBlockWriter>(
+        &self,
+        provider_rw: &P,
+        last_block_number: u64,
+    ) -> Result<(), PersistenceError> {
+        if let Some(finalized_block) = self.finalized_block {
+            provider_rw.save_finalized_block_number(finalized_block.min(last_block_number))?;
+        }
+        if let Some(safe_block) = self.safe_block {
+            provider_rw.save_safe_block_number(safe_block.min(last_block_number))?;
+        }
+        Ok(())
+    }
 }
 
 /// One of the errors that can happen when using the persistence service.
@@ -190,11 +253,26 @@ pub enum PersistenceAction<N: NodePrimitives = EthPrimitives> {
     /// static files.
     RemoveBlocksAbove(u64, oneshot::Sender<Option<BlockNumHash>>),
 
-    /// Update the persisted finalized block on disk
+    /// Update the persisted finalized block on disk.
+    ///
+    /// If the block is not persisted yet, the last persisted block is stored instead until the
+    /// block is persisted.
     SaveFinalizedBlock(u64),
 
-    /// Update the persisted safe block on disk
+    /// Update the persisted safe block on disk.
+    ///
+    /// If the block is not persisted yet, the last persisted block is stored instead until the
+    /// block is persisted.
     SaveSafeBlock(u64),
+
+    /// Persists a block that was found to be invalid, keeping at most the given number of
+    /// invalid blocks on disk.
+    SaveInvalidBlock(BlockWithParent, usize),
+
+    /// Removes all persisted invalid blocks from disk.
+    ///
+    /// The number of removed blocks is returned in the sender.
+    ClearInvalidBlocks(oneshot::Sender<usize>),
 }
 
 /// A handle to the persistence service
@@ -273,7 +351,7 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
         self.send_action(PersistenceAction::SaveFinalizedBlock(finalized_block))
     }
 
-    /// Persists the finalized block number on disk.
+    /// Persists the safe block number on disk.
     pub fn save_safe_block_number(
         &self,
         safe_block: u64,
@@ -281,6 +359,27 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
         self.send_action(PersistenceAction::SaveSafeBlock(safe_block))
     }
 
+    /// Persists the invalid block on disk, so that it is still known to be invalid after a
+    /// restart.
+    pub fn save_invalid_block(
+        &self,
+        block: BlockWithParent,
+        max_blocks: usize,
+    ) -> Result<(), SendError<PersistenceAction<T>>> {
+        self.send_action(PersistenceAction::SaveInvalidBlock(block, max_blocks))
+    }
+
+    /// Removes all invalid blocks from disk.
+    ///
+    /// When the operation completes, the number of removed blocks is returned in the receiver end
+    /// of the sender argument.
+    pub fn clear_invalid_blocks(
+        &self,
+        tx: oneshot::Sender<usize>,
+    ) -> Result<(), SendError<PersistenceAction<T>>> {
+        self.send_action(PersistenceAction::ClearInvalidBlocks(tx))
+    }
+
     /// Tells the persistence service to remove blocks above a certain block number. The removed
     /// blocks are returned by the service.
     ///
@@ -301,13 +400,17 @@ mod tests {
     use alloy_primitives::B256;
     use reth_chain_state::test_utils::TestBlockBuilder;
     use reth_exex_types::FinishedExExHeight;
-    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_provider::test_utils::{create_test_provider_factory, MockNodeTypesWithDB};
     use reth_prune::Pruner;
     use tokio::sync::mpsc::unbounded_channel;
 
     fn default_persistence_handle() -> PersistenceHandle<EthPrimitives> {
-        let provider = create_test_provider_factory();
+        persistence_handle(create_test_provider_factory())
+    }
 
+    fn persistence_handle(
+        provider: ProviderFactory<MockNodeTypesWithDB>,
+    ) -> PersistenceHandle<EthPrimitives> {
         let (_finished_exex_height_tx, finished_exex_height_rx) =
             tokio::sync::watch::channel(FinishedExExHeight::NoExExs);
 
@@ -390,4 +493,42 @@ mod tests {
             assert_eq!(last_hash, actual_hash);
         }
     }
+
+    #[tokio::test]
+    async fn test_save_chain_state_ahead_of_blocks() {
+        reth_tracing::init_test_tracing();
+        let provider = create_test_provider_factory();
+        let persistence_handle = persistence_handle(provider.clone());
+        let mut test_block_builder = TestBlockBuilder::eth();
+
+        let mut save_blocks = async |range| {
+            let blocks = test_block_builder.get_executed_blocks(range).collect::<Vec<_>>();
+            let (tx, rx) = oneshot::channel();
+            persistence_handle.save_blocks(blocks, tx).unwrap();
+            rx.await.unwrap().unwrap();
+        };
+
+        persistence_handle.save_finalized_block_number(3).unwrap();
+        save_blocks(0..5).await;
+        let db_provider = provider.provider().unwrap();
+        assert_eq!(db_provider.last_finalized_block_number().unwrap(), Some(3));
+        assert_eq!(db_provider.last_safe_block_number().unwrap(), None);
+        drop(db_provider);
+
+        // the markers are capped at the persisted blocks until the blocks are saved
+        persistence_handle.save_finalized_block_number(6).unwrap();
+        persistence_handle.save_safe_block_number(10).unwrap();
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(vec![], tx).unwrap();
+        rx.await.unwrap();
+        let db_provider = provider.provider().unwrap();
+        assert_eq!(db_provider.last_finalized_block_number().unwrap(), Some(4));
+        assert_eq!(db_provider.last_safe_block_number().unwrap(), Some(4));
+        drop(db_provider);
+
+        save_blocks(5..8).await;
+        let db_provider = provider.provider().unwrap();
+        assert_eq!(db_provider.last_finalized_block_number().unwrap(), Some(6));
+        assert_eq!(db_provider.last_safe_block_number().unwrap(), Some(7));
+    }
 }
@@ -3,6 +3,8 @@ use reth_engine_primitives::InvalidBlockHook;
 use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
 use reth_provider::BlockExecutionOutput;
 use reth_trie::updates::TrieUpdates;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use tracing::warn;
 
 /// A no-op [`InvalidBlockHook`] that does nothing.
 #[derive(Debug, Default)]
@@ -21,8 +23,50 @@ impl<N: NodePrimitives> InvalidBlockHook<N> for NoopInvalidBlockHook {
 }
 
 /// Multiple [`InvalidBlockHook`]s that are executed in order.
+///
+/// Hooks are isolated from each other: if a hook panics, the panic is caught and logged, and the
+/// remaining hooks are still invoked.
 pub struct InvalidBlockHooks<N: NodePrimitives>(pub Vec<Box<dyn InvalidBlockHook<N>>>);
 
+impl<N: NodePrimitives> InvalidBlockHooks<N> {
+    /// Creates an empty chain of hooks.
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Appends a hook to the end of the chain.
+    pub fn push(&mut self, hook: impl InvalidBlockHook<N> + 'static) {
+        self.0.push(Box::new(hook));
+    }
+
+    /// Appends a hook to the end of the chain and returns the chain.
+    pub fn with_hook(mut self, hook: impl InvalidBlockHook<N> + 'static) -> Self {
+        self.push(hook);
+        self
+    }
+
+    /// Appends all hooks of `other` to the end of the chain.
+    pub fn extend(&mut self, other: impl IntoIterator<Item = Box<dyn InvalidBlockHook<N>>>) {
+        self.0.extend(other);
+    }
+
+    /// Returns the number of hooks in the chain.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the chain contains no hooks.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<N: NodePrimitives> Default for InvalidBlockHooks<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<N: NodePrimitives> std::fmt::Debug for InvalidBlockHooks<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("InvalidBlockHooks").field("len", &self.0.len()).finish()
@@ -37,8 +81,78 @@ impl<N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockHooks<N> {
         output: &BlockExecutionOutput<N::Receipt>,
         trie_updates: Option<(&TrieUpdates, B256)>,
     ) {
-        for hook in &self.0 {
-            hook.on_invalid_block(parent_header, block, output, trie_updates);
+        for (index, hook) in self.0.iter().enumerate() {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                hook.on_invalid_block(parent_header, block, output, trie_updates)
+            }));
+            if result.is_err() {
+                warn!(
+                    target: "engine::tree",
+                    index,
+                    block_hash = %block.hash(),
+                    "Invalid block hook panicked"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_provider::BlockExecutionResult;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    struct CountingHook(Arc<AtomicUsize>);
+
+    impl InvalidBlockHook<EthPrimitives> for CountingHook {
+        fn on_invalid_block(
+            &self,
+            _parent_header: &SealedHeader<alloy_consensus::Header>,
+            _block: &RecoveredBlock<reth_ethereum_primitives::Block>,
+            _output: &BlockExecutionOutput<reth_ethereum_primitives::Receipt>,
+            _trie_updates: Option<(&TrieUpdates, B256)>,
+        ) {
+            self.0.fetch_add(1, Ordering::SeqCst);
         }
     }
+
+    struct PanickingHook;
+
+    impl InvalidBlockHook<EthPrimitives> for PanickingHook {
+        fn on_invalid_block(
+            &self,
+            _parent_header: &SealedHeader<alloy_consensus::Header>,
+            _block: &RecoveredBlock<reth_ethereum_primitives::Block>,
+            _output: &BlockExecutionOutput<reth_ethereum_primitives::Receipt>,
+            _trie_updates: Option<(&TrieUpdates, B256)>,
+        ) {
+            panic!("hook failure")
+        }
+    }
+
+    #[test]
+    fn hooks_are_isolated() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = InvalidBlockHooks::<EthPrimitives>::new()
+            .with_hook(CountingHook(calls.clone()))
+            .with_hook(PanickingHook)
+            .with_hook(CountingHook(calls.clone()));
+        assert_eq!(hooks.len(), 3);
+
+        let output = BlockExecutionOutput {
+            result: BlockExecutionResult {
+                receipts: Vec::new(),
+                requests: Default::default(),
+                gas_used: 0,
+            },
+            state: Default::default(),
+        };
+        hooks.on_invalid_block(&SealedHeader::default(), &RecoveredBlock::default(), &output, None);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
 }
@@ -78,6 +78,20 @@ impl InvalidHeaderCache {
             self.metrics.count.set(self.headers.len() as f64);
         }
     }
+
+    /// Inserts invalid ancestors that were persisted by a previous run.
+    pub fn extend(&mut self, invalid_ancestors: impl IntoIterator<Item = BlockWithParent>) {
+        for invalid_ancestor in invalid_ancestors {
+            self.insert_entry(invalid_ancestor.block.hash, invalid_ancestor);
+        }
+        self.metrics.count.set(self.headers.len() as f64);
+    }
+
+    /// Removes all invalid headers from the cache.
+    pub fn clear(&mut self) {
+        self.headers.clear();
+        self.metrics.count.set(0.0);
+    }
 }
 
 struct HeaderEntry {
@@ -57,6 +57,23 @@ pub(crate) struct EngineMetrics {
     // TODO add latency metrics
 }
 
+/// Metrics for the queue of `newPayload` requests.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.beacon")]
+pub(crate) struct PayloadQueueMetrics {
+    /// The number of queued payloads that extend the canonical head.
+    pub(crate) queued_payloads_extending_canonical_head: Gauge,
+    /// The number of queued payloads whose parent is a block in the tree.
+    pub(crate) queued_payloads_with_known_parent: Gauge,
+    /// The number of queued payloads whose parent is unknown.
+    pub(crate) queued_disconnected_payloads: Gauge,
+    /// The total count of payloads that were rejected because the queue was full, or reached
+    /// its soft limit.
+    pub(crate) rejected_payloads: Counter,
+    /// The time a payload waited in the queue before it was processed.
+    pub(crate) payload_queue_wait: Histogram,
+}
+
 /// Metrics for non-execution related block validation.
 #[derive(Metrics)]
 #[metrics(scope = "sync.block_validation")]
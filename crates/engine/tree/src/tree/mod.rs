@@ -8,7 +8,7 @@ use crate::{
     },
 };
 use alloy_consensus::BlockHeader;
-use alloy_eips::{merge::EPOCH_SLOTS, BlockNumHash, NumHash};
+use alloy_eips::{eip1898::BlockWithParent, merge::EPOCH_SLOTS, BlockNumHash, NumHash};
 use alloy_evm::block::BlockExecutor;
 use alloy_primitives::{Address, B256};
 use alloy_rpc_types_engine::{
@@ -17,6 +17,7 @@ use alloy_rpc_types_engine::{
 use error::{InsertBlockError, InsertBlockErrorKind, InsertBlockFatalError};
 use instrumented_state::InstrumentedStateProvider;
 use payload_processor::sparse_trie::StateRootComputeOutcome;
+use payload_queue::{PayloadPriority, PayloadQueue};
 use persistence_state::CurrentPersistenceAction;
 use precompile_cache::{CachedPrecompile, CachedPrecompileMetrics, PrecompileCacheMap};
 use reth_chain_state::{
@@ -38,11 +39,11 @@ use reth_primitives_traits::{
 };
 use reth_provider::{
     providers::ConsistentDbView, BlockNumReader, BlockReader, DBProvider, DatabaseProviderFactory,
-    ExecutionOutcome, HashedPostStateProvider, ProviderError, StateCommitmentProvider,
-    StateProvider, StateProviderBox, StateProviderFactory, StateReader, StateRootProvider,
-    TransactionVariant,
+    ExecutionOutcome, HashedPostStateProvider, InvalidBlockReader, ProviderError,
+    StateCommitmentProvider, StateProvider, StateProviderBox, StateProviderFactory, StateReader,
+    StateRootProvider, TransactionVariant,
 };
-use reth_revm::{database::StateProviderDatabase, State};
+use reth_revm::{database::StateProviderDatabase, witness::ExecutionWitnessRecord, State};
 use reth_stages_api::ControlFlow;
 use reth_trie::{updates::TrieUpdates, HashedPostState, TrieInput};
 use reth_trie_db::{DatabaseHashedPostState, StateCommitment};
@@ -72,6 +73,7 @@ mod invalid_block_hook;
 mod invalid_headers;
 mod metrics;
 mod payload_processor;
+mod payload_queue;
 pub mod payload_validator;
 mod persistence_state;
 pub mod precompile_cache;
@@ -80,6 +82,7 @@ mod tests;
 // TODO(alexey): compare trie updates in `insert_block_inner`
 #[expect(unused)]
 mod trie_updates;
+mod witness_sink;
 
 use crate::tree::error::AdvancePersistenceError;
 pub use block_buffer::BlockBuffer;
@@ -90,6 +93,11 @@ pub use payload_validator::{EngineValidator, TreePayloadValidator};
 pub use persistence_state::PersistenceState;
 pub use reth_engine_primitives::TreeConfig;
 use reth_evm::execute::BlockExecutionOutput;
+use witness_sink::ExecutionWitnessJob;
+pub use witness_sink::{
+    CanonicalExecutionWitness, ChannelExecutionWitnessSink, ExecutionWitnessSink,
+    ExecutionWitnessSinks, FileExecutionWitnessSink, NoopExecutionWitnessSink,
+};
 
 pub mod state;
 
@@ -249,6 +257,9 @@ where
     incoming_tx: Sender<FromEngine<EngineApiRequest<T, N>, N::Block>>,
     /// Incoming engine API requests.
     incoming: Receiver<FromEngine<EngineApiRequest<T, N>, N::Block>>,
+    /// Received engine API requests that are waiting to be processed, with `newPayload` requests
+    /// ordered by priority.
+    queued: PayloadQueue<FromEngine<EngineApiRequest<T, N>, N::Block>>,
     /// Outgoing events that are emitted to the handler.
     outgoing: UnboundedSender<EngineApiEvent<N>>,
     /// Channels to the persistence layer.
@@ -269,6 +280,13 @@ where
     metrics: EngineApiMetrics,
     /// An invalid block hook.
     invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
+    /// Sends the execution witnesses of canonicalized blocks to the task that builds them and
+    /// forwards them to the [`ExecutionWitnessSink`].
+    execution_witness_tx: Option<Sender<ExecutionWitnessJob<N, P>>>,
+    /// State accessed during the execution of blocks that haven't been canonicalized yet.
+    ///
+    /// Only recorded if [`TreeConfig::emit_execution_witnesses`] is enabled.
+    execution_witnesses: HashMap<B256, ExecutionWitnessRecord>,
     /// The engine API variant of this handler
     engine_kind: EngineApiKind,
     /// The type responsible for processing new payloads
@@ -302,6 +320,7 @@ where
             .field("config", &self.config)
             .field("metrics", &self.metrics)
             .field("invalid_block_hook", &format!("{:p}", self.invalid_block_hook))
+            .field("execution_witness_tx", &self.execution_witness_tx)
             .field("engine_kind", &self.engine_kind)
             .field("payload_processor", &self.payload_processor)
             .field("evm_config", &self.evm_config)
@@ -321,7 +340,7 @@ where
         + Clone
         + 'static,
     <P as DatabaseProviderFactory>::Provider:
-        BlockReader<Block = N::Block, Header = N::BlockHeader>,
+        BlockReader<Block = N::Block, Header = N::BlockHeader> + InvalidBlockReader,
     C: ConfigureEvm<Primitives = N> + 'static,
     T: PayloadTypes,
     V: EngineValidator<T, Block = N::Block>,
@@ -343,6 +362,8 @@ where
         evm_config: C,
     ) -> Self {
         let (incoming_tx, incoming) = std::sync::mpsc::channel();
+        let queued =
+            PayloadQueue::new(config.max_queued_payloads(), config.soft_max_queued_payloads());
 
         let precompile_cache_map = PrecompileCacheMap::default();
 
@@ -358,6 +379,7 @@ where
             consensus,
             payload_validator,
             incoming,
+            queued,
             outgoing,
             persistence,
             persistence_state,
@@ -369,6 +391,8 @@ where
             metrics: Default::default(),
             incoming_tx,
             invalid_block_hook: Box::new(NoopInvalidBlockHook),
+            execution_witness_tx: None,
+            execution_witnesses: HashMap::new(),
             engine_kind,
             payload_processor,
             evm_config,
@@ -382,6 +406,14 @@ where
         self.invalid_block_hook = invalid_block_hook;
     }
 
+    /// Sets the sink of the execution witnesses of canonical blocks.
+    ///
+    /// The witnesses are built and passed to the sink on a dedicated thread, off the engine
+    /// thread.
+    fn set_execution_witness_sink(&mut self, sink: Box<dyn ExecutionWitnessSink<N>>) {
+        self.execution_witness_tx = Some(witness_sink::spawn_execution_witness_task(sink));
+    }
+
     /// Creates a new [`EngineApiTreeHandler`] instance and spawns it in its
     /// own thread.
     ///
@@ -397,6 +429,7 @@ where
         canonical_in_memory_state: CanonicalInMemoryState<N>,
         config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
+        execution_witness_sink: Box<dyn ExecutionWitnessSink<N>>,
         kind: EngineApiKind,
         evm_config: C,
     ) -> (Sender<FromEngine<EngineApiRequest<T, N>, N::Block>>, UnboundedReceiver<EngineApiEvent<N>>)
@@ -410,13 +443,27 @@ where
         };
 
         let (tx, outgoing) = unbounded_channel();
-        let state = EngineApiTreeState::new(
+        let mut state = EngineApiTreeState::new(
             config.block_buffer_limit(),
             config.max_invalid_header_cache_length(),
             header.num_hash(),
             kind,
         );
 
+        // restore the invalid blocks from the previous run, so that the CL can't make us execute
+        // known invalid blocks again
+        match provider.database_provider_ro().and_then(|provider| provider.invalid_blocks()) {
+            Ok(mut invalid_blocks) => {
+                debug!(target: "engine::tree", count=invalid_blocks.len(), "Restoring invalid blocks");
+                // insert the highest blocks last, so that they are kept if the cache is full
+                invalid_blocks.sort_unstable_by_key(|block| block.block.number);
+                state.invalid_headers.extend(invalid_blocks);
+            }
+            Err(err) => {
+                warn!(target: "engine::tree", %err, "Failed to restore invalid blocks");
+            }
+        }
+
         let mut task = Self::new(
             provider,
             consensus,
@@ -432,6 +479,7 @@ where
             evm_config,
         );
         task.set_invalid_block_hook(invalid_block_hook);
+        task.set_execution_witness_sink(execution_witness_sink);
         let incoming = task.incoming_tx.clone();
         std::thread::Builder::new().name("Tree Task".to_string()).spawn(|| task.run()).unwrap();
         (incoming, outgoing)
@@ -898,6 +946,26 @@ where
         if let Ok(Some(canonical_header)) = self.find_canonical_header(state.head_block_hash) {
             debug!(target: "engine::tree", head = canonical_header.number(), "fcu head block is already canonical");
 
+            // If configured, make the ancestor the new canonical head instead of skipping the
+            // update.
+            if self.config.unwind_canonical_header() {
+                self.unwind_canonical_head(&canonical_header)?;
+
+                // update the safe and finalized blocks and ensure their values are valid
+                if let Err(outcome) = self.ensure_consistent_forkchoice_state(state) {
+                    // safe or finalized hashes are invalid
+                    return Ok(TreeOutcome::new(outcome))
+                }
+
+                if let Some(attr) = attrs {
+                    let updated =
+                        self.process_payload_attributes(attr, &canonical_header, state, version);
+                    return Ok(TreeOutcome::new(updated))
+                }
+
+                return Ok(valid_outcome(state.head_block_hash))
+            }
+
             // For OpStack the proposers are allowed to reorg their own chain at will, so we need to
             // always trigger a new payload job if requested.
             // Also allow forcing this behavior via a config flag.
@@ -977,21 +1045,90 @@ where
     /// received in time.
     ///
     /// Returns an error if the engine channel is disconnected.
+    ///
+    /// All requests that are already waiting in the channel are queued, so that `newPayload`
+    /// requests can be processed by priority, see [`PayloadQueue`].
     #[expect(clippy::type_complexity)]
     fn try_recv_engine_message(
-        &self,
+        &mut self,
     ) -> Result<Option<FromEngine<EngineApiRequest<T, N>, N::Block>>, RecvError> {
-        if self.persistence_state.in_progress() {
+        if let Some(msg) = self.queued.pop() {
+            return Ok(Some(msg))
+        }
+
+        let msg = if self.persistence_state.in_progress() {
             // try to receive the next request with a timeout to not block indefinitely
             match self.incoming.recv_timeout(std::time::Duration::from_millis(500)) {
-                Ok(msg) => Ok(Some(msg)),
+                Ok(msg) => msg,
                 Err(err) => match err {
-                    RecvTimeoutError::Timeout => Ok(None),
-                    RecvTimeoutError::Disconnected => Err(RecvError),
+                    RecvTimeoutError::Timeout => return Ok(None),
+                    RecvTimeoutError::Disconnected => return Err(RecvError),
                 },
             }
         } else {
-            self.incoming.recv().map(Some)
+            self.incoming.recv()?
+        };
+
+        self.queue_engine_message(msg);
+        while let Ok(msg) = self.incoming.try_recv() {
+            self.queue_engine_message(msg);
+        }
+        Ok(self.queued.pop())
+    }
+
+    /// Queues a received engine API request.
+    ///
+    /// `newPayload` requests are prioritized by the relation of the payload to the canonical head,
+    /// or by the priority of their parent if it's queued as well. If the queue is full, the payload
+    /// with the lowest priority is not processed. Once the soft limit of the queue is reached, this
+    /// also applies to new payloads that don't extend the canonical head. Skipped payloads are
+    /// answered with `ACCEPTED` if their parent is known, and with `SYNCING` otherwise.
+    fn queue_engine_message(&mut self, msg: FromEngine<EngineApiRequest<T, N>, N::Block>) {
+        let FromEngine::Request(EngineApiRequest::Beacon(BeaconEngineMessage::NewPayload {
+            payload,
+            ..
+        })) = &msg
+        else {
+            self.queued.push(msg);
+            return
+        };
+
+        let parent_hash = payload.parent_hash();
+        let priority = if parent_hash == self.state.tree_state.canonical_block_hash() {
+            PayloadPriority::ExtendsCanonicalHead
+        } else if self.state.tree_state.executed_block_by_hash(parent_hash).is_some() {
+            PayloadPriority::KnownParent
+        } else if let Some(priority) = self.queued.payload_priority(parent_hash) {
+            // the parent is queued as well, so the payload is processed after it
+            priority
+        } else {
+            PayloadPriority::Disconnected
+        };
+        let number = payload.block_number();
+        let hash = payload.block_hash();
+
+        let Some((rejected, priority)) = self.queued.push_payload(msg, priority, number, hash)
+        else {
+            return
+        };
+        if let FromEngine::Request(EngineApiRequest::Beacon(BeaconEngineMessage::NewPayload {
+            payload,
+            tx,
+        })) = rejected
+        {
+            warn!(
+                target: "engine::tree",
+                block_hash = %payload.block_hash(),
+                block_number = payload.block_number(),
+                capacity = ?self.queued.capacity(),
+                soft_capacity = ?self.queued.soft_capacity(),
+                "Skipping payload because the newPayload queue reached its limit"
+            );
+            let status = match priority {
+                PayloadPriority::Disconnected => PayloadStatusEnum::Syncing,
+                _ => PayloadStatusEnum::Accepted,
+            };
+            let _ = tx.send(Ok(PayloadStatus::from_status(status)));
         }
     }
 
@@ -1180,6 +1317,9 @@ where
                                 // handle the event if any
                                 self.on_maybe_tree_event(maybe_event)?;
                             }
+                            BeaconEngineMessage::ClearInvalidBlocks { tx } => {
+                                self.on_clear_invalid_blocks(tx);
+                            }
                         }
                     }
                 }
@@ -1217,7 +1357,7 @@ where
         let backfill_height = if let ControlFlow::Unwind { bad_block, target } = &ctrl {
             warn!(target: "engine::tree", invalid_block=?bad_block, "Bad block detected in unwind");
             // update the `invalid_headers` cache with the new invalid header
-            self.state.invalid_headers.insert(**bad_block);
+            self.insert_invalid_block(**bad_block);
 
             // if this was an unwind then the target is the new height
             Some(*target)
@@ -1896,6 +2036,44 @@ where
         Ok(Some(persisted.number))
     }
 
+    /// Unwinds the canonical chain to the given ancestor of the current canonical head.
+    ///
+    /// The blocks above the new head are reorged out of the canonical chain. Blocks that were
+    /// already persisted are removed from the database once the on-disk reorg is detected.
+    fn unwind_canonical_head(
+        &mut self,
+        new_head: &SealedHeader<N::BlockHeader>,
+    ) -> ProviderResult<()> {
+        let current_head = self.state.tree_state.current_canonical_head;
+        debug!(target: "engine::tree", from = current_head.number, to = new_head.number(), "unwinding canonical head");
+
+        // collect the blocks that are removed from the canonical chain
+        let mut old = Vec::new();
+        let mut current_hash = current_head.hash;
+        while current_hash != new_head.hash() {
+            let block = self
+                .canonical_block_by_hash(current_hash)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(current_hash.into()))?;
+            current_hash = block.recovered_block().parent_hash();
+            old.push(block);
+        }
+        old.reverse();
+
+        let head = match self.state.tree_state.executed_block_by_hash(new_head.hash()) {
+            Some(block) => block.clone(),
+            None => {
+                // the new head is already persisted, its trie updates are not needed anymore
+                let block = self
+                    .canonical_block_by_hash(new_head.hash())?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(new_head.hash().into()))?;
+                ExecutedBlockWithTrieUpdates { block, trie: ExecutedTrieUpdates::Missing }
+            }
+        };
+
+        self.on_canonical_chain_update(NewCanonicalChain::Reorg { new: vec![head], old });
+        Ok(())
+    }
+
     /// Invoked when we the canonical chain has been updated.
     ///
     /// This is invoked on a valid forkchoice update, or if we can make the target block canonical.
@@ -1909,6 +2087,8 @@ where
         let tip = chain_update.tip().clone_sealed_header();
         let notification = chain_update.to_chain_notification();
 
+        self.emit_execution_witnesses(&chain_update);
+
         // reinsert any missing reorged blocks
         if let NewCanonicalChain::Reorg { new, old } = &chain_update {
             let new_first = new.first().map(|first| first.recovered_block().num_hash());
@@ -1955,6 +2135,39 @@ where
         ));
     }
 
+    /// Sends the recorded execution witnesses of the newly canonical blocks to the witness task,
+    /// and drops the witnesses of blocks that were removed from the tree.
+    fn emit_execution_witnesses(&mut self, chain_update: &NewCanonicalChain<N>) {
+        if self.execution_witnesses.is_empty() {
+            return
+        }
+
+        let new_blocks = match chain_update {
+            NewCanonicalChain::Commit { new } | NewCanonicalChain::Reorg { new, .. } => new,
+        };
+        for block in new_blocks {
+            let block = block.recovered_block.clone();
+            let Some(record) = self.execution_witnesses.remove(&block.hash()) else { continue };
+            let Some(tx) = &self.execution_witness_tx else { continue };
+            match self.execution_witness_job(block.clone(), record) {
+                Ok(job) => {
+                    let _ = tx.send(job);
+                }
+                Err(err) => {
+                    warn!(
+                        target: "engine::tree",
+                        %err,
+                        block=?block.num_hash(),
+                        "Failed to record execution witness"
+                    );
+                }
+            }
+        }
+
+        let tree_state = &self.state.tree_state;
+        self.execution_witnesses.retain(|hash, _| tree_state.block_by_hash(*hash).is_some());
+    }
+
     /// This updates metrics based on the given reorg length.
     fn update_reorg_metrics(&self, old_chain_length: usize) {
         self.metrics.tree.reorgs.increment(1);
@@ -1976,6 +2189,24 @@ where
         }
     }
 
+    /// Marks the block as invalid and persists it, so that it is still known to be invalid after a
+    /// restart.
+    fn insert_invalid_block(&mut self, block: BlockWithParent) {
+        self.state.invalid_headers.insert(block);
+        let _ = self
+            .persistence
+            .save_invalid_block(block, self.config.max_invalid_header_cache_length() as usize);
+    }
+
+    /// Forgets all invalid blocks, so that they are validated again if they are received.
+    ///
+    /// The number of removed persisted invalid blocks is sent to the given sender.
+    fn on_clear_invalid_blocks(&mut self, tx: oneshot::Sender<usize>) {
+        info!(target: "engine::tree", "Clearing invalid blocks");
+        self.state.invalid_headers.clear();
+        let _ = self.persistence.clear_invalid_blocks(tx);
+    }
+
     /// Invoke the invalid block hook if this is a new invalid block.
     fn on_invalid_block(
         &mut self,
@@ -2262,16 +2493,13 @@ where
             handle.cache_metrics(),
         );
 
-        let (output, execution_finish) = if self.config.state_provider_metrics() {
+        let (output, execution_finish, witness) = if self.config.state_provider_metrics() {
             let state_provider = InstrumentedStateProvider::from_state_provider(&state_provider);
-            let (output, execution_finish) =
-                ensure_ok!(self.execute_block(&state_provider, &block, &handle));
+            let result = ensure_ok!(self.execute_block(&state_provider, &block, &handle));
             state_provider.record_total_latency();
-            (output, execution_finish)
+            result
         } else {
-            let (output, execution_finish) =
-                ensure_ok!(self.execute_block(&state_provider, &block, &handle));
-            (output, execution_finish)
+            ensure_ok!(self.execute_block(&state_provider, &block, &handle))
         };
 
         // after executing the block we can stop executing transactions
@@ -2412,6 +2640,9 @@ where
         }
 
         self.state.tree_state.insert_executed(executed.clone());
+        if let Some(witness) = witness {
+            self.execution_witnesses.insert(block_num_hash.hash, witness);
+        }
         self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
 
         // emit insert event
@@ -2427,13 +2658,20 @@ where
         Ok(InsertPayloadOk::Inserted(BlockStatus::Valid))
     }
 
-    /// Executes a block with the given state provider
+    /// Executes a block with the given state provider.
+    ///
+    /// Also returns the state accessed during execution, if
+    /// [`TreeConfig::emit_execution_witnesses`] is enabled.
+    #[expect(clippy::type_complexity)]
     fn execute_block<S: StateProvider>(
         &mut self,
         state_provider: S,
         block: &RecoveredBlock<N::Block>,
         handle: &PayloadHandle,
-    ) -> Result<(BlockExecutionOutput<N::Receipt>, Instant), InsertBlockErrorKind> {
+    ) -> Result<
+        (BlockExecutionOutput<N::Receipt>, Instant, Option<ExecutionWitnessRecord>),
+        InsertBlockErrorKind,
+    > {
         debug!(target: "engine::tree", block=?block.num_hash(), "Executing block");
         let mut db = State::builder()
             .with_database(StateProviderDatabase::new(&state_provider))
@@ -2467,7 +2705,47 @@ where
         let execution_finish = Instant::now();
         let execution_time = execution_finish.duration_since(execution_start);
         debug!(target: "engine::tree", elapsed = ?execution_time, number=?block.number(), "Executed block");
-        Ok((output, execution_finish))
+
+        let witness = self.config.emit_execution_witnesses().then(|| {
+            let mut record = ExecutionWitnessRecord::from_executed_state(&db);
+            // the bundle state was already taken from the database, so the contracts created in
+            // the block are only in the execution output
+            record.codes.extend(output.state.contracts.values().map(|code| code.original_bytes()));
+            record
+        });
+
+        Ok((output, execution_finish, witness))
+    }
+
+    /// Prepares building the execution witness of a canonical block from the state accessed
+    /// during its execution.
+    ///
+    /// The state proofs are computed by the witness task.
+    fn execution_witness_job(
+        &self,
+        block: Arc<RecoveredBlock<N::Block>>,
+        record: ExecutionWitnessRecord,
+    ) -> ProviderResult<ExecutionWitnessJob<N, P>> {
+        let state_provider = self
+            .state_provider_builder(block.parent_hash())?
+            .ok_or_else(|| ProviderError::StateForHashNotFound(block.parent_hash()))?;
+
+        // include the headers of all ancestors referenced by the BLOCKHASH opcode, or only the
+        // parent header if it wasn't called
+        let lowest_block_number =
+            record.lowest_block_number.unwrap_or_else(|| block.number().saturating_sub(1));
+        let mut headers = Vec::new();
+        let mut hash = block.parent_hash();
+        while let Some(header) = self.sealed_header_by_hash(hash)? {
+            headers.push(alloy_rlp::encode(header.header()).into());
+            if header.number() <= lowest_block_number {
+                break
+            }
+            hash = header.parent_hash();
+        }
+        headers.reverse();
+
+        Ok(ExecutionWitnessJob { block, record, headers, state_provider })
     }
 
     /// Compute state root for the given hashed post state in parallel.
@@ -2622,7 +2900,7 @@ where
         let latest_valid_hash = self.latest_valid_hash_for_invalid_payload(block.parent_hash())?;
 
         // keep track of the invalid header
-        self.state.invalid_headers.insert(block.block_with_parent());
+        self.insert_invalid_block(block.block_with_parent());
         self.emit_event(EngineApiEvent::BeaconConsensus(BeaconConsensusEngineEvent::InvalidBlock(
             Box::new(block),
         )));
@@ -0,0 +1,298 @@
+//! Queue for engine messages that prioritizes `newPayload` requests.
+
+use crate::tree::metrics::PayloadQueueMetrics;
+use alloy_primitives::{BlockNumber, B256};
+use std::{collections::VecDeque, time::Instant};
+
+/// The priority of a queued `newPayload` request, determined by the relation of the payload to
+/// the canonical head when it was received.
+///
+/// Payloads whose parent is queued as well, e.g. because the consensus client pipelines its
+/// requests, have the priority of their parent.
+///
+/// Variants are ordered from the highest to the lowest priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum PayloadPriority {
+    /// The payload extends the canonical head.
+    ExtendsCanonicalHead,
+    /// The parent of the payload is a block in the tree, but not the canonical head.
+    KnownParent,
+    /// The parent of the payload is unknown.
+    Disconnected,
+}
+
+/// A queued `newPayload` request.
+#[derive(Debug, Clone, Copy)]
+struct QueuedPayload {
+    priority: PayloadPriority,
+    number: BlockNumber,
+    hash: B256,
+}
+
+impl QueuedPayload {
+    /// Returns the key the payloads are ordered by.
+    const fn key(&self) -> (PayloadPriority, BlockNumber) {
+        (self.priority, self.number)
+    }
+}
+
+/// A queued engine message.
+#[derive(Debug)]
+struct QueuedMessage<M> {
+    /// The queued `newPayload` request, `None` for other messages.
+    payload: Option<QueuedPayload>,
+    message: M,
+    /// The time the message was queued.
+    queued_at: Instant,
+}
+
+/// A bounded queue of engine messages that are waiting to be processed.
+///
+/// Consecutive `newPayload` requests are ordered by their [`PayloadPriority`] and then by block
+/// number, so that payloads that extend the canonical chain are processed first. Payloads are
+/// never moved across other messages, e.g. forkchoice updates, which are processed in the order
+/// they were received.
+///
+/// Only payloads count towards the capacity, if any. If it is exceeded, the payload with the lowest
+/// priority is rejected. Once the soft capacity is reached, if any, only payloads that extend the
+/// canonical head are queued, so that side-chain payloads can't starve the canonical chain.
+#[derive(Debug)]
+pub(crate) struct PayloadQueue<M> {
+    messages: VecDeque<QueuedMessage<M>>,
+    /// The number of queued payloads, indexed by [`PayloadPriority`].
+    queued_payloads: [usize; 3],
+    /// The maximum number of queued payloads, `None` if the queue is unbounded.
+    capacity: Option<usize>,
+    /// The number of queued payloads from which on only payloads that extend the canonical head
+    /// are queued, `None` if all payloads are queued up to the capacity.
+    soft_capacity: Option<usize>,
+    metrics: PayloadQueueMetrics,
+}
+
+impl<M> PayloadQueue<M> {
+    /// Creates a new queue that holds at most `capacity` payloads, or any number of payloads if
+    /// `capacity` is `None`, and only queues payloads that extend the canonical head once it holds
+    /// `soft_capacity` payloads.
+    pub(crate) fn new(capacity: Option<usize>, soft_capacity: Option<usize>) -> Self {
+        Self {
+            messages: VecDeque::new(),
+            queued_payloads: [0; 3],
+            capacity,
+            soft_capacity,
+            metrics: PayloadQueueMetrics::default(),
+        }
+    }
+
+    /// Returns the maximum number of queued payloads, if the queue is bounded.
+    pub(crate) const fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Returns the number of queued payloads from which on only payloads that extend the canonical
+    /// head are queued, if limited.
+    pub(crate) const fn soft_capacity(&self) -> Option<usize> {
+        self.soft_capacity
+    }
+
+    /// Returns the number of queued payloads.
+    pub(crate) fn payload_count(&self) -> usize {
+        self.queued_payloads.iter().sum()
+    }
+
+    /// Returns the priority of the queued payload with the given block hash, if any.
+    pub(crate) fn payload_priority(&self, hash: B256) -> Option<PayloadPriority> {
+        self.messages
+            .iter()
+            .find_map(|queued| queued.payload.filter(|payload| payload.hash == hash))
+            .map(|payload| payload.priority)
+    }
+
+    /// Queues a message that is not a `newPayload` request.
+    pub(crate) fn push(&mut self, message: M) {
+        self.messages.push_back(QueuedMessage {
+            payload: None,
+            message,
+            queued_at: Instant::now(),
+        });
+    }
+
+    /// Queues a `newPayload` request after all other messages, and before the queued payloads with
+    /// a lower priority.
+    ///
+    /// Returns the payload that was rejected if the queue is full, which is either an already
+    /// queued payload or the given one, together with its priority. The given payload is rejected
+    /// as well if it doesn't extend the canonical head and the soft capacity is reached.
+    pub(crate) fn push_payload(
+        &mut self,
+        message: M,
+        priority: PayloadPriority,
+        number: BlockNumber,
+        hash: B256,
+    ) -> Option<(M, PayloadPriority)> {
+        if priority != PayloadPriority::ExtendsCanonicalHead &&
+            self.soft_capacity.is_some_and(|soft_capacity| self.payload_count() >= soft_capacity)
+        {
+            self.metrics.rejected_payloads.increment(1);
+            return Some((message, priority))
+        }
+
+        let payload = QueuedPayload { priority, number, hash };
+        let position = self
+            .messages
+            .iter()
+            .rposition(|queued| queued.payload.is_none_or(|queued| queued.key() <= payload.key()))
+            .map_or(0, |position| position + 1);
+        self.messages.insert(
+            position,
+            QueuedMessage { payload: Some(payload), message, queued_at: Instant::now() },
+        );
+        self.queued_payloads[priority as usize] += 1;
+
+        let mut rejected = None;
+        if self.capacity.is_some_and(|capacity| self.payload_count() > capacity) {
+            rejected = self.remove_lowest_priority();
+            self.metrics.rejected_payloads.increment(1);
+        }
+        self.update_metrics();
+        rejected
+    }
+
+    /// Removes and returns the next message.
+    pub(crate) fn pop(&mut self) -> Option<M> {
+        let queued = self.messages.pop_front()?;
+        if let Some(payload) = queued.payload {
+            self.queued_payloads[payload.priority as usize] -= 1;
+            self.metrics.payload_queue_wait.record(queued.queued_at.elapsed());
+            self.update_metrics();
+        }
+        Some(queued.message)
+    }
+
+    /// Removes the payload with the lowest priority, preferring the most recent one.
+    fn remove_lowest_priority(&mut self) -> Option<(M, PayloadPriority)> {
+        let (index, payload) = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter_map(|(index, queued)| Some((index, queued.payload?)))
+            .max_by_key(|(index, payload)| (payload.key(), *index))?;
+        self.queued_payloads[payload.priority as usize] -= 1;
+        self.messages.remove(index).map(|queued| (queued.message, payload.priority))
+    }
+
+    fn update_metrics(&self) {
+        let [extends_canonical_head, known_parent, disconnected] = self.queued_payloads;
+        self.metrics.queued_payloads_extending_canonical_head.set(extends_canonical_head as f64);
+        self.metrics.queued_payloads_with_known_parent.set(known_parent as f64);
+        self.metrics.queued_disconnected_payloads.set(disconnected as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(queue: &mut PayloadQueue<&'static str>) -> Vec<&'static str> {
+        std::iter::from_fn(|| queue.pop()).collect()
+    }
+
+    fn hash(number: BlockNumber) -> B256 {
+        B256::with_last_byte(number as u8)
+    }
+
+    #[test]
+    fn orders_consecutive_payloads_by_priority() {
+        let mut queue = PayloadQueue::new(None, None);
+        assert_eq!(
+            queue.push_payload("disconnected", PayloadPriority::Disconnected, 12, hash(12)),
+            None
+        );
+        assert_eq!(queue.push_payload("fork", PayloadPriority::KnownParent, 11, hash(11)), None);
+        assert_eq!(
+            queue.push_payload("head", PayloadPriority::ExtendsCanonicalHead, 11, hash(11)),
+            None
+        );
+        queue.push("fcu");
+        assert_eq!(
+            queue.push_payload("late fork", PayloadPriority::KnownParent, 12, hash(12)),
+            None
+        );
+        assert_eq!(
+            queue.push_payload("late head", PayloadPriority::ExtendsCanonicalHead, 12, hash(12)),
+            None
+        );
+        assert_eq!(
+            queue.push_payload("head sibling", PayloadPriority::ExtendsCanonicalHead, 12, hash(12)),
+            None
+        );
+        assert_eq!(queue.payload_count(), 6);
+
+        assert_eq!(
+            drain(&mut queue),
+            ["head", "fork", "disconnected", "fcu", "late head", "head sibling", "late fork"]
+        );
+        assert_eq!(queue.payload_count(), 0);
+    }
+
+    #[test]
+    fn rejects_lowest_priority_payload() {
+        let mut queue = PayloadQueue::new(Some(2), None);
+        assert_eq!(queue.push_payload("first", PayloadPriority::Disconnected, 12, hash(12)), None);
+        assert_eq!(queue.push_payload("second", PayloadPriority::Disconnected, 12, hash(12)), None);
+        queue.push("fcu");
+        assert_eq!(
+            queue.push_payload("head", PayloadPriority::ExtendsCanonicalHead, 11, hash(11)),
+            Some(("second", PayloadPriority::Disconnected))
+        );
+        assert_eq!(
+            queue.push_payload("other", PayloadPriority::Disconnected, 13, hash(13)),
+            Some(("other", PayloadPriority::Disconnected))
+        );
+        assert_eq!(Some(queue.payload_count()), queue.capacity());
+
+        assert_eq!(drain(&mut queue), ["first", "fcu", "head"]);
+    }
+
+    #[test]
+    fn queues_only_canonical_payloads_above_soft_capacity() {
+        let mut queue = PayloadQueue::new(Some(3), Some(1));
+        assert_eq!(queue.push_payload("fork", PayloadPriority::KnownParent, 11, hash(11)), None);
+        assert_eq!(
+            queue.push_payload("other fork", PayloadPriority::KnownParent, 11, hash(11)),
+            Some(("other fork", PayloadPriority::KnownParent))
+        );
+        assert_eq!(
+            queue.push_payload("disconnected", PayloadPriority::Disconnected, 20, hash(20)),
+            Some(("disconnected", PayloadPriority::Disconnected))
+        );
+        assert_eq!(
+            queue.push_payload("head", PayloadPriority::ExtendsCanonicalHead, 11, hash(11)),
+            None
+        );
+        assert_eq!(
+            queue.push_payload("next", PayloadPriority::ExtendsCanonicalHead, 12, hash(12)),
+            None
+        );
+
+        // the hard limit still applies to canonical payloads, and evicts side-chain payloads first
+        assert_eq!(
+            queue.push_payload("last", PayloadPriority::ExtendsCanonicalHead, 13, hash(13)),
+            Some(("fork", PayloadPriority::KnownParent))
+        );
+        assert_eq!(drain(&mut queue), ["head", "next", "last"]);
+    }
+
+    #[test]
+    fn returns_priority_of_queued_payloads() {
+        let mut queue = PayloadQueue::new(None, None);
+        assert_eq!(queue.payload_priority(hash(11)), None);
+
+        queue.push_payload("head", PayloadPriority::ExtendsCanonicalHead, 11, hash(11));
+        queue.push_payload("disconnected", PayloadPriority::Disconnected, 20, hash(20));
+        assert_eq!(queue.payload_priority(hash(11)), Some(PayloadPriority::ExtendsCanonicalHead));
+        assert_eq!(queue.payload_priority(hash(20)), Some(PayloadPriority::Disconnected));
+
+        assert_eq!(queue.pop(), Some("head"));
+        assert_eq!(queue.payload_priority(hash(11)), None);
+    }
+}
@@ -774,6 +774,38 @@ async fn test_get_canonical_blocks_to_persist() {
     );
 }
 
+#[tokio::test]
+async fn test_engine_tree_fcu_unwind_canonical_head() {
+    let chain_spec = MAINNET.clone();
+    let mut test_harness = TestHarness::new(chain_spec);
+    test_harness.tree.config = test_harness.tree.config.clone().with_unwind_canonical_header(true);
+
+    let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..5).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+
+    let new_head = blocks[2].recovered_block().clone_sealed_header();
+    test_harness.send_fcu(new_head.hash(), ForkchoiceStatus::Valid).await;
+
+    // the blocks above the ancestor are reorged out of the canonical chain
+    let event = test_harness.from_tree_rx.recv().await.unwrap();
+    match event {
+        EngineApiEvent::BeaconConsensus(BeaconConsensusEngineEvent::CanonicalChainCommitted(
+            header,
+            _,
+        )) => {
+            assert_eq!(header.hash(), new_head.hash());
+        }
+        _ => panic!("Unexpected event: {event:#?}"),
+    }
+    test_harness.check_fcu(new_head.hash(), ForkchoiceStatus::Valid).await;
+
+    assert_eq!(test_harness.tree.state.tree_state.canonical_head(), &new_head.num_hash());
+    assert_eq!(
+        test_harness.tree.canonical_in_memory_state.get_canonical_head().hash(),
+        new_head.hash()
+    );
+}
+
 #[tokio::test]
 async fn test_engine_tree_fcu_missing_head() {
     let chain_spec = MAINNET.clone();
@@ -0,0 +1,200 @@
+use super::StateProviderBuilder;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::Bytes;
+use alloy_rpc_types_debug::ExecutionWitness;
+use reth_errors::ProviderResult;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock};
+use reth_provider::{BlockReader, StateCommitmentProvider, StateProviderFactory, StateReader};
+use reth_revm::witness::ExecutionWitnessRecord;
+use std::{
+    path::PathBuf,
+    sync::{mpsc::Sender, Arc},
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tracing::warn;
+
+/// Receives the execution witnesses of blocks once they are canonicalized.
+///
+/// Witnesses are only recorded during block execution if
+/// [`TreeConfig::emit_execution_witnesses`](crate::tree::TreeConfig::emit_execution_witnesses) is
+/// enabled, so the witness of a block is emitted without executing it again.
+pub trait ExecutionWitnessSink<N: NodePrimitives>: Send + Sync {
+    /// Invoked when a block with a recorded witness becomes canonical.
+    fn on_canonical_witness(&self, block: &RecoveredBlock<N::Block>, witness: &ExecutionWitness);
+}
+
+impl<F, N> ExecutionWitnessSink<N> for F
+where
+    N: NodePrimitives,
+    F: Fn(&RecoveredBlock<N::Block>, &ExecutionWitness) + Send + Sync,
+{
+    fn on_canonical_witness(&self, block: &RecoveredBlock<N::Block>, witness: &ExecutionWitness) {
+        self(block, witness)
+    }
+}
+
+/// A no-op [`ExecutionWitnessSink`] that drops the witnesses.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct NoopExecutionWitnessSink;
+
+impl<N: NodePrimitives> ExecutionWitnessSink<N> for NoopExecutionWitnessSink {
+    fn on_canonical_witness(&self, _block: &RecoveredBlock<N::Block>, _witness: &ExecutionWitness) {
+    }
+}
+
+/// Multiple [`ExecutionWitnessSink`]s that are invoked in order.
+pub struct ExecutionWitnessSinks<N: NodePrimitives>(pub Vec<Box<dyn ExecutionWitnessSink<N>>>);
+
+impl<N: NodePrimitives> std::fmt::Debug for ExecutionWitnessSinks<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecutionWitnessSinks").field("len", &self.0.len()).finish()
+    }
+}
+
+impl<N: NodePrimitives> ExecutionWitnessSink<N> for ExecutionWitnessSinks<N> {
+    fn on_canonical_witness(&self, block: &RecoveredBlock<N::Block>, witness: &ExecutionWitness) {
+        for sink in &self.0 {
+            sink.on_canonical_witness(block, witness);
+        }
+    }
+}
+
+/// Writes the witnesses into a directory, as `<number>_<hash>.witness.json` files.
+#[derive(Debug, Clone)]
+pub struct FileExecutionWitnessSink {
+    output_directory: PathBuf,
+}
+
+impl FileExecutionWitnessSink {
+    /// Creates a new sink writing into the given directory.
+    pub const fn new(output_directory: PathBuf) -> Self {
+        Self { output_directory }
+    }
+}
+
+impl<N: NodePrimitives> ExecutionWitnessSink<N> for FileExecutionWitnessSink {
+    fn on_canonical_witness(&self, block: &RecoveredBlock<N::Block>, witness: &ExecutionWitness) {
+        let block = block.num_hash();
+        let path =
+            self.output_directory.join(format!("{}_{}.witness.json", block.number, block.hash));
+        if let Err(err) = reth_fs_util::create_dir_all(&self.output_directory)
+            .and_then(|_| reth_fs_util::write_json_file(&path, witness))
+        {
+            warn!(target: "engine::tree", %err, ?block, "Failed to write execution witness");
+        }
+    }
+}
+
+/// The recorded state of a canonical block to build its execution witness from.
+pub(super) struct ExecutionWitnessJob<N: NodePrimitives, P> {
+    /// The canonical block.
+    pub(super) block: Arc<RecoveredBlock<N::Block>>,
+    /// The state accessed during the execution of the block.
+    pub(super) record: ExecutionWitnessRecord,
+    /// The RLP encoded ancestor headers referenced by the block.
+    pub(super) headers: Vec<Bytes>,
+    /// Builds the state provider of the parent block.
+    pub(super) state_provider: StateProviderBuilder<N, P>,
+}
+
+impl<N, P> ExecutionWitnessJob<N, P>
+where
+    N: NodePrimitives,
+    P: BlockReader + StateProviderFactory + StateReader + StateCommitmentProvider + Clone,
+{
+    /// Computes the state proofs of the recorded state and returns the witness.
+    fn witness(self) -> ProviderResult<ExecutionWitness> {
+        let ExecutionWitnessRecord { hashed_state, codes, keys, .. } = self.record;
+        let state = self.state_provider.build()?.witness(Default::default(), hashed_state)?;
+        Ok(ExecutionWitness { state, codes, keys, headers: self.headers })
+    }
+}
+
+/// Spawns the thread that builds the witnesses of canonical blocks, in the order they are sent,
+/// and forwards them to the sink.
+///
+/// The thread exits once the returned sender is dropped.
+pub(super) fn spawn_execution_witness_task<N, P>(
+    sink: Box<dyn ExecutionWitnessSink<N>>,
+) -> Sender<ExecutionWitnessJob<N, P>>
+where
+    N: NodePrimitives,
+    P: BlockReader + StateProviderFactory + StateReader + StateCommitmentProvider + Clone + 'static,
+{
+    let (tx, rx) = std::sync::mpsc::channel::<ExecutionWitnessJob<N, P>>();
+    std::thread::Builder::new()
+        .name("Witness Task".to_string())
+        .spawn(move || {
+            for job in rx {
+                let block = job.block.clone();
+                match job.witness() {
+                    Ok(witness) => sink.on_canonical_witness(&block, &witness),
+                    Err(err) => warn!(
+                        target: "engine::tree",
+                        %err,
+                        block=?block.num_hash(),
+                        "Failed to build execution witness"
+                    ),
+                }
+            }
+        })
+        .unwrap();
+    tx
+}
+
+/// The execution witness of a canonical block, sent by the [`ChannelExecutionWitnessSink`].
+#[derive(Debug, Clone)]
+pub struct CanonicalExecutionWitness {
+    /// The canonical block.
+    pub block: BlockNumHash,
+    /// The execution witness of the block.
+    pub witness: ExecutionWitness,
+}
+
+/// Sends the witnesses to a channel, e.g. to be consumed by an `ExEx`.
+///
+/// Witnesses are dropped once the receiver is closed.
+#[derive(Debug, Clone)]
+pub struct ChannelExecutionWitnessSink {
+    tx: UnboundedSender<CanonicalExecutionWitness>,
+}
+
+impl ChannelExecutionWitnessSink {
+    /// Creates a new sink and the receiver of its witnesses.
+    pub fn channel() -> (Self, UnboundedReceiver<CanonicalExecutionWitness>) {
+        let (tx, rx) = unbounded_channel();
+        (Self { tx }, rx)
+    }
+}
+
+impl<N: NodePrimitives> ExecutionWitnessSink<N> for ChannelExecutionWitnessSink {
+    fn on_canonical_witness(&self, block: &RecoveredBlock<N::Block>, witness: &ExecutionWitness) {
+        let _ = self
+            .tx
+            .send(CanonicalExecutionWitness { block: block.num_hash(), witness: witness.clone() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_primitives::{Block, EthPrimitives};
+
+    #[test]
+    fn sinks_forward_witness() {
+        let (first, mut first_rx) = ChannelExecutionWitnessSink::channel();
+        let (second, mut second_rx) = ChannelExecutionWitnessSink::channel();
+        let sinks = ExecutionWitnessSinks::<EthPrimitives>(vec![Box::new(first), Box::new(second)]);
+
+        let block = RecoveredBlock::<Block>::default();
+        let witness = ExecutionWitness { keys: vec![vec![1].into()], ..Default::default() };
+        sinks.on_canonical_witness(&block, &witness);
+
+        for rx in [&mut first_rx, &mut second_rx] {
+            let received = rx.try_recv().unwrap();
+            assert_eq!(received.block, block.num_hash());
+            assert_eq!(received.witness, witness);
+        }
+    }
+}
@@ -86,6 +86,8 @@ impl EngineMessageStore {
                     })?,
                 )?;
             }
+            // not a consensus message, nothing to replay
+            BeaconEngineMessage::ClearInvalidBlocks { .. } => {}
         };
         Ok(())
     }
@@ -26,6 +26,9 @@ use skip_new_payload::EngineSkipNewPayload;
 pub mod reorg;
 use reorg::EngineReorg;
 
+pub mod self_check;
+use self_check::EngineSelfCheck;
+
 /// The collection of stream extensions for engine API message stream.
 pub trait EngineMessageStreamExt<T: PayloadTypes>: Stream<Item = BeaconEngineMessage<T>> {
     /// Skips the specified number of [`BeaconEngineMessage::ForkchoiceUpdated`] messages from the
@@ -147,6 +150,41 @@ pub trait EngineMessageStreamExt<T: PayloadTypes>: Stream<Item = BeaconEngineMes
             Either::Right(self)
         }
     }
+
+    /// Builds a payload locally for every new payload and logs how it compares to the received
+    /// payload.
+    fn self_check<Provider, Evm, Validator, Pool>(
+        self,
+        provider: Provider,
+        evm_config: Evm,
+        payload_validator: Validator,
+        pool: Pool,
+    ) -> EngineSelfCheck<Self, Provider, Evm, Validator, Pool>
+    where
+        Self: Sized,
+    {
+        EngineSelfCheck::new(self, provider, evm_config, payload_validator, pool)
+    }
+
+    /// If enabled, returns the stream that builds a payload locally for every new payload and
+    /// logs how it compares to the received payload. Otherwise, returns `Self`.
+    fn maybe_self_check<Provider, Evm, Validator, Pool>(
+        self,
+        provider: Provider,
+        evm_config: Evm,
+        payload_validator: Validator,
+        pool: Pool,
+        enabled: bool,
+    ) -> Either<EngineSelfCheck<Self, Provider, Evm, Validator, Pool>, Self>
+    where
+        Self: Sized,
+    {
+        if enabled {
+            Either::Left(self.self_check(provider, evm_config, payload_validator, pool))
+        } else {
+            Either::Right(self)
+        }
+    }
 }
 
 impl<T, S> EngineMessageStreamExt<T> for S
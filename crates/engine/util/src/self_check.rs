@@ -0,0 +1,348 @@
+//! Stream wrapper that compares new payloads with payloads built locally.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{TxHash, U256};
+use futures::{Stream, StreamExt};
+use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload as _, PayloadValidator};
+use reth_errors::{BlockExecutionError, BlockValidationError, RethError, RethResult};
+use reth_evm::{execute::BlockBuilder, ConfigureEvm, Evm as _};
+use reth_payload_primitives::PayloadTypes;
+use reth_primitives_traits::{
+    transaction::error::InvalidTransactionError, BlockTy, HeaderTy, RecoveredBlock,
+    SignedTransaction, TxTy,
+};
+use reth_revm::{database::StateProviderDatabase, db::State};
+use reth_storage_api::{errors::ProviderError, BlockReader, StateProviderFactory};
+use reth_transaction_pool::{
+    error::InvalidPoolTransactionError, BestTransactionsAttributes, PoolTransaction,
+    TransactionPool,
+};
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::Semaphore;
+use tracing::*;
+
+/// Maximum number of payloads that are self-checked at the same time.
+pub const MAX_CONCURRENT_SELF_CHECKS: usize = 1;
+
+/// Engine API stream wrapper that builds a payload locally for every new payload and logs how it
+/// compares to the received payload.
+///
+/// The local payload is built on top of the same parent with the same block environment
+/// (timestamp, fee recipient, prevrandao, gas limit and withdrawals) from the best transactions
+/// of the pool, which quantifies what local block building would have produced instead. Blob
+/// transactions are not included in the local payload.
+///
+/// The comparison runs on a blocking task and doesn't delay the forwarded messages. At most
+/// [`MAX_CONCURRENT_SELF_CHECKS`] payloads are compared at the same time, new payloads that arrive
+/// while all comparisons are busy are not checked.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct EngineSelfCheck<S, Provider, Evm, Validator, Pool> {
+    /// Underlying stream
+    #[pin]
+    stream: S,
+    /// Database provider.
+    provider: Provider,
+    /// Evm configuration.
+    evm_config: Evm,
+    /// Payload validator.
+    payload_validator: Validator,
+    /// Transaction pool to build local payloads from.
+    pool: Pool,
+    /// Permits for the running comparisons.
+    permits: Arc<Semaphore>,
+}
+
+impl<S, Provider, Evm, Validator, Pool> EngineSelfCheck<S, Provider, Evm, Validator, Pool> {
+    /// Creates new [`EngineSelfCheck`] stream wrapper.
+    pub fn new(
+        stream: S,
+        provider: Provider,
+        evm_config: Evm,
+        payload_validator: Validator,
+        pool: Pool,
+    ) -> Self {
+        Self {
+            stream,
+            provider,
+            evm_config,
+            payload_validator,
+            pool,
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_SELF_CHECKS)),
+        }
+    }
+}
+
+impl<S, T, Provider, Evm, Validator, Pool> Stream
+    for EngineSelfCheck<S, Provider, Evm, Validator, Pool>
+where
+    S: Stream<Item = BeaconEngineMessage<T>>,
+    T: PayloadTypes,
+    Provider: BlockReader<Header = HeaderTy<Evm::Primitives>, Block = BlockTy<Evm::Primitives>>
+        + StateProviderFactory
+        + Clone
+        + 'static,
+    Evm: ConfigureEvm + 'static,
+    Validator: PayloadValidator<ExecutionData = T::ExecutionData, Block = BlockTy<Evm::Primitives>>
+        + Clone
+        + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Evm::Primitives>>>
+        + Clone
+        + 'static,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        let item = ready!(this.stream.poll_next_unpin(cx));
+        if let Some(BeaconEngineMessage::NewPayload { payload, .. }) = &item {
+            let Ok(permit) = this.permits.clone().try_acquire_owned() else {
+                debug!(target: "engine::stream::self_check", parent_hash = %payload.parent_hash(), "Skipping self-check, previous payloads are still being checked");
+                return Poll::Ready(item)
+            };
+            let payload = payload.clone();
+            let provider = this.provider.clone();
+            let evm_config = this.evm_config.clone();
+            let payload_validator = this.payload_validator.clone();
+            let pool = this.pool.clone();
+            tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                let parent_hash = payload.parent_hash();
+                let block = match payload_validator.ensure_well_formed_payload(payload) {
+                    Ok(block) => block,
+                    Err(error) => {
+                        debug!(target: "engine::stream::self_check", %parent_hash, %error, "Skipping self-check of malformed payload");
+                        return
+                    }
+                };
+                if let Err(error) = self_check(&provider, &evm_config, &pool, &block) {
+                    warn!(target: "engine::stream::self_check", number = block.number(), hash = %block.hash(), %error, "Failed to self-check payload");
+                }
+            });
+        }
+        Poll::Ready(item)
+    }
+}
+
+/// Summary of the transactions executed for a payload.
+#[derive(Debug, Default)]
+struct PayloadSummary {
+    /// Total gas used by the transactions.
+    gas_used: u64,
+    /// Total priority fees paid to the fee recipient.
+    fees: U256,
+    /// Hashes of the executed transactions.
+    transactions: HashSet<TxHash>,
+}
+
+impl PayloadSummary {
+    fn record(&mut self, tx: &impl SignedTransaction, base_fee: u64, gas_used: u64) {
+        let tip = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+        self.gas_used += gas_used;
+        self.fees += U256::from(tip) * U256::from(gas_used);
+        self.transactions.insert(*tx.tx_hash());
+    }
+}
+
+/// Executes the received block and a block built from the pool on the same parent, and logs the
+/// comparison.
+fn self_check<Provider, Evm, Pool>(
+    provider: &Provider,
+    evm_config: &Evm,
+    pool: &Pool,
+    block: &RecoveredBlock<BlockTy<Evm::Primitives>>,
+) -> RethResult<()>
+where
+    Provider: BlockReader<Header = HeaderTy<Evm::Primitives>, Block = BlockTy<Evm::Primitives>>
+        + StateProviderFactory,
+    Evm: ConfigureEvm,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Evm::Primitives>>>,
+{
+    let parent = provider
+        .sealed_header_by_hash(block.parent_hash())?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block.parent_hash().into()))?;
+    let state_provider = provider.state_by_block_hash(block.parent_hash())?;
+    let base_fee = block.base_fee_per_gas().unwrap_or_default();
+
+    // Execute the received block.
+    let mut received = PayloadSummary::default();
+    {
+        let mut state = State::builder()
+            .with_database_ref(StateProviderDatabase::new(&state_provider))
+            .with_bundle_update()
+            .build();
+        let ctx = evm_config.context_for_block(block.sealed_block());
+        let evm = evm_config.evm_for_block(&mut state, block.header());
+        let mut builder = evm_config.create_block_builder(evm, &parent, ctx);
+        builder.apply_pre_execution_changes()?;
+
+        for tx in block.clone_transactions_recovered() {
+            let gas_used = builder.execute_transaction(tx.clone())?;
+            received.record(tx.inner(), base_fee, gas_used);
+        }
+    }
+
+    // Build a block from the pool with the same block environment.
+    let mut local = PayloadSummary::default();
+    {
+        let mut state = State::builder()
+            .with_database_ref(StateProviderDatabase::new(&state_provider))
+            .with_bundle_update()
+            .build();
+        let ctx = evm_config.context_for_block(block.sealed_block());
+        let evm = evm_config.evm_for_block(&mut state, block.header());
+        let mut builder = evm_config.create_block_builder(evm, &parent, ctx);
+        builder.apply_pre_execution_changes()?;
+
+        let block_gas_limit = builder.evm_mut().block().gas_limit;
+        let mut best_txs =
+            pool.best_transactions_with_attributes(BestTransactionsAttributes::base_fee(base_fee));
+        best_txs.skip_blobs();
+
+        while let Some(pool_tx) = best_txs.next() {
+            if local.gas_used + pool_tx.gas_limit() > block_gas_limit {
+                best_txs.mark_invalid(
+                    &pool_tx,
+                    InvalidPoolTransactionError::ExceedsGasLimit(
+                        pool_tx.gas_limit(),
+                        block_gas_limit,
+                    ),
+                );
+                continue
+            }
+
+            let tx = pool_tx.to_consensus();
+            let gas_used = match builder.execute_transaction(tx.clone()) {
+                Ok(gas_used) => gas_used,
+                Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
+                    error,
+                    ..
+                })) => {
+                    if !error.is_nonce_too_low() {
+                        best_txs.mark_invalid(
+                            &pool_tx,
+                            InvalidPoolTransactionError::Consensus(
+                                InvalidTransactionError::TxTypeNotSupported,
+                            ),
+                        );
+                    }
+                    continue
+                }
+                Err(error) => return Err(RethError::Execution(error)),
+            };
+            local.record(tx.inner(), base_fee, gas_used);
+        }
+    }
+
+    let overlap = received.transactions.intersection(&local.transactions).count();
+    info!(
+        target: "engine::stream::self_check",
+        number = block.number(),
+        hash = %block.hash(),
+        received_txs = received.transactions.len(),
+        local_txs = local.transactions.len(),
+        overlap,
+        received_gas_used = received.gas_used,
+        local_gas_used = local.gas_used,
+        received_fees = %received.fees,
+        local_fees = %local.fees,
+        "Compared payload with locally built payload"
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::{ExecutionData, ExecutionPayloadSidecar, ExecutionPayloadV1};
+    use reth_engine_primitives::PayloadValidator;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_ethereum_primitives::Block;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_payload_primitives::NewPayloadError;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_transaction_pool::noop::NoopTransactionPool;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    };
+    use tokio::sync::oneshot;
+
+    /// Validator that counts the checked payloads and rejects them once released.
+    #[derive(Debug, Clone)]
+    struct BlockingValidator {
+        calls: Arc<AtomicUsize>,
+        release: Arc<Mutex<mpsc::Receiver<()>>>,
+    }
+
+    impl PayloadValidator for BlockingValidator {
+        type Block = Block;
+        type ExecutionData = ExecutionData;
+
+        fn ensure_well_formed_payload(
+            &self,
+            _payload: ExecutionData,
+        ) -> Result<RecoveredBlock<Block>, NewPayloadError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.release.lock().unwrap().recv().ok();
+            Err(NewPayloadError::other(std::io::Error::other("rejected")))
+        }
+    }
+
+    fn new_payload() -> BeaconEngineMessage<EthEngineTypes> {
+        BeaconEngineMessage::NewPayload {
+            payload: ExecutionData {
+                payload: ExecutionPayloadV1::from_block_slow(&Block::default()).into(),
+                sidecar: ExecutionPayloadSidecar::none(),
+            },
+            tx: oneshot::channel().0,
+        }
+    }
+
+    #[tokio::test]
+    async fn skips_payloads_while_busy() {
+        let (release_tx, release_rx) = mpsc::channel();
+        let validator = BlockingValidator {
+            calls: Arc::new(AtomicUsize::new(0)),
+            release: Arc::new(Mutex::new(release_rx)),
+        };
+        let (to_stream, from_test) = futures::channel::mpsc::unbounded();
+        let mut stream = EngineSelfCheck::new(
+            from_test,
+            MockEthProvider::default(),
+            EthEvmConfig::mainnet(),
+            validator.clone(),
+            NoopTransactionPool::default(),
+        );
+
+        // the messages are forwarded while the first payload is being checked
+        for _ in 0..2 {
+            to_stream.unbounded_send(new_payload()).unwrap();
+            assert!(matches!(stream.next().await, Some(BeaconEngineMessage::NewPayload { .. })));
+        }
+
+        release_tx.send(()).unwrap();
+        drop(release_tx);
+        while stream.permits.available_permits() < MAX_CONCURRENT_SELF_CHECKS {
+            tokio::task::yield_now().await;
+        }
+
+        // only the first payload was checked, the second one arrived while it was busy
+        assert_eq!(validator.calls.load(Ordering::SeqCst), 1);
+
+        // payloads are checked again once the previous check is done
+        to_stream.unbounded_send(new_payload()).unwrap();
+        assert!(stream.next().await.is_some());
+        while stream.permits.available_permits() < MAX_CONCURRENT_SELF_CHECKS {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(validator.calls.load(Ordering::SeqCst), 2);
+    }
+}
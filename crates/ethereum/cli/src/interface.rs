@@ -222,7 +222,9 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
             }
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute::<N>()),
             #[cfg(feature = "dev")]
-            Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::TestVectors(command) => {
+                runner.run_until_ctrl_c(command.execute::<N>(components))
+            }
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Recover(command) => {
                 runner.run_command_until_exit(|ctx| command.execute::<N>(ctx))
@@ -283,7 +285,7 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Generate Test Vectors
     #[cfg(feature = "dev")]
     #[command(name = "test-vectors")]
-    TestVectors(reth_cli_commands::test_vectors::Command),
+    TestVectors(reth_cli_commands::test_vectors::Command<C>),
     /// Write config to stdout
     #[command(name = "config")]
     Config(config_cmd::Command),
@@ -314,7 +316,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
             Self::Stage(cmd) => cmd.chain_spec(),
             Self::P2P(cmd) => cmd.chain_spec(),
             #[cfg(feature = "dev")]
-            Self::TestVectors(_) => None,
+            Self::TestVectors(cmd) => cmd.chain_spec(),
             Self::Config(_) => None,
             Self::Recover(cmd) => cmd.chain_spec(),
             Self::Prune(cmd) => cmd.chain_spec(),
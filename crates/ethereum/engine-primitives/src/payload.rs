@@ -16,7 +16,7 @@ use alloy_rpc_types_engine::{
 };
 use core::convert::Infallible;
 use reth_ethereum_primitives::{Block, EthPrimitives};
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
+use reth_payload_primitives::{BuiltPayload, ExternalPayload, PayloadBuilderAttributes};
 use reth_primitives_traits::SealedBlock;
 
 use crate::BuiltPayloadConversionError;
@@ -174,6 +174,13 @@ impl BuiltPayload for EthBuiltPayload {
     }
 }
 
+impl From<ExternalPayload<Block>> for EthBuiltPayload {
+    fn from(payload: ExternalPayload<Block>) -> Self {
+        let ExternalPayload { id, block, value, requests } = payload;
+        Self::new(id, block, value, requests)
+    }
+}
+
 // V1 engine_getPayloadV1 response
 impl From<EthBuiltPayload> for ExecutionPayloadV1 {
     fn from(value: EthBuiltPayload) -> Self {
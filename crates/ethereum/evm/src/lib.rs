@@ -29,8 +29,8 @@ use core::{convert::Infallible, fmt::Debug};
 use reth_chainspec::{ChainSpec, EthChainSpec, MAINNET};
 use reth_ethereum_primitives::{Block, EthPrimitives, TransactionSigned};
 use reth_evm::{
-    precompiles::PrecompilesMap, ConfigureEvm, EvmEnv, EvmFactory, NextBlockEnvAttributes,
-    TransactionEnv,
+    precompiles::PrecompilesMap, ConfigureEvm, CustomPrecompiles, CustomPrecompilesEvmFactory,
+    EvmEnv, EvmFactory, NextBlockEnvAttributes, TransactionEnv,
 };
 use reth_primitives_traits::{SealedBlock, SealedHeader};
 use revm::{
@@ -119,6 +119,30 @@ impl<ChainSpec, EvmFactory> EthEvmConfig<ChainSpec, EvmFactory> {
     }
 }
 
+impl<ChainSpec, EvmF: EvmFactory + Clone> EthEvmConfig<ChainSpec, EvmF> {
+    /// Installs the given precompiles into every EVM created by this configuration, in addition to
+    /// the precompiles of the active spec.
+    ///
+    /// See [`CustomPrecompiles`] for more details.
+    pub fn with_custom_precompiles(
+        self,
+        precompiles: CustomPrecompiles<EvmF::Spec>,
+    ) -> EthEvmConfig<ChainSpec, CustomPrecompilesEvmFactory<EvmF>> {
+        let evm_factory = CustomPrecompilesEvmFactory::new(
+            self.executor_factory.evm_factory().clone(),
+            precompiles,
+        );
+        EthEvmConfig {
+            executor_factory: EthBlockExecutorFactory::new(
+                RethReceiptBuilder::default(),
+                self.chain_spec().clone(),
+                evm_factory,
+            ),
+            block_assembler: self.block_assembler,
+        }
+    }
+}
+
 impl<ChainSpec, EvmF> ConfigureEvm for EthEvmConfig<ChainSpec, EvmF>
 where
     ChainSpec: EthExecutorSpec + EthChainSpec<Header = Header> + Hardforks + 'static,
@@ -15,7 +15,7 @@ use reth_payload_primitives::{
     validate_execution_requests, validate_version_specific_fields, EngineApiMessageVersion,
     EngineObjectValidationError, NewPayloadError, PayloadOrAttributes,
 };
-use reth_primitives_traits::RecoveredBlock;
+use reth_primitives_traits::{transaction::sender_cache::SenderRecoveryCache, RecoveredBlock};
 use std::sync::Arc;
 
 /// Validator for the ethereum engine API.
@@ -49,7 +49,9 @@ where
         payload: ExecutionData,
     ) -> Result<RecoveredBlock<Self::Block>, NewPayloadError> {
         let sealed_block = self.inner.ensure_well_formed_payload(payload)?;
-        sealed_block.try_recover().map_err(|e| NewPayloadError::Other(e.into()))
+        sealed_block
+            .try_recover_with_cache(SenderRecoveryCache::global())
+            .map_err(|e| NewPayloadError::Other(e.into()))
     }
 }
 
@@ -6,7 +6,7 @@ use alloy_eips::{eip7840::BlobParams, merge::EPOCH_SLOTS};
 use alloy_network::Ethereum;
 use alloy_rpc_types_engine::ExecutionData;
 use reth_chainspec::{ChainSpec, EthChainSpec, EthereumHardforks, Hardforks};
-use reth_engine_local::LocalPayloadAttributesBuilder;
+use reth_engine_local::{LocalPayloadAttributesBuilder, PayloadAttributesInjector};
 use reth_engine_primitives::EngineTypes;
 use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::{
@@ -14,8 +14,8 @@ use reth_ethereum_engine_primitives::{
 };
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{
-    eth::spec::EthExecutorSpec, ConfigureEvm, EvmFactory, EvmFactoryFor, NextBlockEnvAttributes,
-    TxEnvFor,
+    eth::spec::EthExecutorSpec, ConfigureEvm, CustomPrecompiles, CustomPrecompilesEvmFactory,
+    EthEvmFactory, EvmFactory, EvmFactoryFor, NextBlockEnvAttributes, TxEnvFor,
 };
 use reth_network::{primitives::BasicNetworkPrimitives, NetworkHandle, PeersInfo};
 use reth_node_api::{
@@ -30,7 +30,7 @@ use reth_node_builder::{
     node::{FullNodeTypes, NodeTypes},
     rpc::{
         BasicEngineApiBuilder, EngineApiBuilder, EngineValidatorAddOn, EngineValidatorBuilder,
-        EthApiBuilder, EthApiCtx, Identity, RethRpcAddOns, RpcAddOns, RpcHandle,
+        EthApiBuilder, EthApiCtx, Identity, MapEthApiBuilder, RethRpcAddOns, RpcAddOns, RpcHandle,
     },
     BuilderContext, DebugNode, Node, NodeAdapter, PayloadBuilderConfig, PayloadTypes,
 };
@@ -44,7 +44,7 @@ use reth_rpc_builder::{config::RethRpcServerConfig, middleware::RethRpcMiddlewar
 use reth_rpc_eth_api::{
     helpers::pending_block::BuildPendingEnv, RpcConvert, RpcTypes, SignableTxRequest,
 };
-use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
+use reth_rpc_eth_types::{error::FromEvmError, EthApiError, TipSuggester};
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
@@ -226,6 +226,43 @@ where
         EthereumAddOns { inner: inner.with_engine_validator(engine_validator_builder) }
     }
 
+    /// Replace the `EthApi` builder.
+    ///
+    /// See [`RpcAddOns::with_eth_api_builder`] for more details.
+    pub fn with_eth_api_builder<T>(
+        self,
+        eth_api_builder: T,
+    ) -> EthereumAddOns<N, T, EV, EB, RpcMiddleware>
+    where
+        T: EthApiBuilder<N>,
+    {
+        let Self { inner } = self;
+        EthereumAddOns { inner: inner.with_eth_api_builder(eth_api_builder) }
+    }
+
+    /// Maps the built `EthApi`, e.g. to decorate it.
+    ///
+    /// See [`RpcAddOns::map_eth_api`] for more details.
+    pub fn map_eth_api<F, T>(
+        self,
+        f: F,
+    ) -> EthereumAddOns<N, MapEthApiBuilder<EthB, F>, EV, EB, RpcMiddleware>
+    where
+        F: FnOnce(EthB::EthApi) -> eyre::Result<T>,
+        MapEthApiBuilder<EthB, F>: EthApiBuilder<N, EthApi = T>,
+    {
+        let Self { inner } = self;
+        EthereumAddOns { inner: inner.map_eth_api(f) }
+    }
+
+    /// Sets a custom algorithm for priority fee suggestions.
+    ///
+    /// See [`RpcAddOns::with_tip_suggester`] for more details.
+    pub fn with_tip_suggester(self, tip_suggester: Arc<dyn TipSuggester>) -> Self {
+        let Self { inner } = self;
+        Self { inner: inner.with_tip_suggester(tip_suggester) }
+    }
+
     /// Sets rpc middleware
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> EthereumAddOns<N, EthB, EV, EB, T>
     where
@@ -365,6 +402,13 @@ impl<N: FullNodeComponents<Types = Self>> DebugNode<N> for EthereumNode {
     ) -> impl PayloadAttributesBuilder<<Self::Payload as PayloadTypes>::PayloadAttributes> {
         LocalPayloadAttributesBuilder::new(Arc::new(chain_spec.clone()))
     }
+
+    fn local_payload_attributes_builder_with_injector(
+        chain_spec: &Self::ChainSpec,
+        injector: PayloadAttributesInjector,
+    ) -> impl PayloadAttributesBuilder<<Self::Payload as PayloadTypes>::PayloadAttributes> {
+        LocalPayloadAttributesBuilder::new(Arc::new(chain_spec.clone())).with_injector(injector)
+    }
 }
 
 /// A regular ethereum evm and executor builder.
@@ -389,6 +433,44 @@ where
     }
 }
 
+impl EthereumExecutorBuilder {
+    /// Installs the given precompiles into the EVM of the node, in addition to the precompiles of
+    /// the active spec.
+    pub const fn with_custom_precompiles(
+        self,
+        precompiles: CustomPrecompiles,
+    ) -> EthereumCustomPrecompilesExecutorBuilder {
+        EthereumCustomPrecompilesExecutorBuilder { precompiles }
+    }
+}
+
+/// An ethereum evm and executor builder that installs additional precompiles for custom chains.
+///
+/// The precompiles apply to every EVM of the node, see [`CustomPrecompiles`].
+#[derive(Debug, Clone)]
+pub struct EthereumCustomPrecompilesExecutorBuilder {
+    /// The additional precompiles.
+    precompiles: CustomPrecompiles,
+}
+
+impl<Types, Node> ExecutorBuilder<Node> for EthereumCustomPrecompilesExecutorBuilder
+where
+    Types: NodeTypes<
+        ChainSpec: Hardforks + EthExecutorSpec + EthereumHardforks,
+        Primitives = EthPrimitives,
+    >,
+    Node: FullNodeTypes<Types = Types>,
+{
+    type EVM = EthEvmConfig<Types::ChainSpec, CustomPrecompilesEvmFactory<EthEvmFactory>>;
+
+    async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
+        let evm_config = EthEvmConfig::new(ctx.chain_spec())
+            .with_custom_precompiles(self.precompiles)
+            .with_extra_data(ctx.payload_builder_config().extra_data_bytes());
+        Ok(evm_config)
+    }
+}
+
 /// A basic ethereum transaction pool.
 ///
 /// This contains various settings that can be configured and take precedence over the node's
@@ -4,7 +4,7 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
+use reth_ethereum_payload_builder::{EthPayloadTransactions, EthereumBuilderConfig};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::ConfigureEvm;
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
@@ -16,9 +16,23 @@ use reth_transaction_pool::{PoolTransaction, TransactionPool};
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder<Txs = ()> {
+    /// The type responsible for yielding the best transactions for the payload.
+    best_transactions: Txs,
+}
+
+impl<Txs> EthereumPayloadBuilder<Txs> {
+    /// Configures the type responsible for yielding the transactions that should be included in the
+    /// payload.
+    ///
+    /// This can be used to plug custom transaction selection into the payload builder.
+    pub fn with_transactions<T>(self, best_transactions: T) -> EthereumPayloadBuilder<T> {
+        EthereumPayloadBuilder { best_transactions }
+    }
+}
 
-impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for EthereumPayloadBuilder
+impl<Types, Node, Pool, Evm, Txs> PayloadBuilderBuilder<Node, Pool, Evm>
+    for EthereumPayloadBuilder<Txs>
 where
     Types: NodeTypes<ChainSpec: EthereumHardforks, Primitives = EthPrimitives>,
     Node: FullNodeTypes<Types = Types>,
@@ -34,9 +48,10 @@ where
         PayloadAttributes = EthPayloadAttributes,
         PayloadBuilderAttributes = EthPayloadBuilderAttributes,
     >,
+    Txs: EthPayloadTransactions<Pool::Transaction>,
 {
     type PayloadBuilder =
-        reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Node::Provider, Evm>;
+        reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Node::Provider, Evm, Txs>;
 
     async fn build_payload_builder(
         self,
@@ -53,6 +68,7 @@ where
             pool,
             evm_config,
             EthereumBuilderConfig::new().with_gas_limit(gas_limit),
-        ))
+        )
+        .with_transactions(self.best_transactions))
     }
 }
@@ -2,13 +2,17 @@
 
 use std::sync::Arc;
 
+use alloy_primitives::Address;
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadAttributes};
 use reth_db::{
     test_utils::{create_test_rw_db, TempDatabase},
     DatabaseEnv,
 };
-use reth_node_api::NodeTypesWithDBAdapter;
-use reth_node_builder::{EngineNodeLauncher, FullNodeComponents, NodeBuilder, NodeConfig};
-use reth_node_ethereum::node::{EthereumAddOns, EthereumNode};
+use reth_node_api::{EngineObjectValidationError, NodeTypesWithDBAdapter};
+use reth_node_builder::{
+    rpc::BasicEngineApiBuilder, EngineNodeLauncher, FullNodeComponents, NodeBuilder, NodeConfig,
+};
+use reth_node_ethereum::node::{EthereumAddOns, EthereumEngineValidatorBuilder, EthereumNode};
 use reth_provider::providers::BlockchainProvider;
 use reth_rpc_builder::Identity;
 use reth_tasks::TaskManager;
@@ -44,6 +48,45 @@ fn test_basic_setup() {
         .check_launch();
 }
 
+#[test]
+fn test_map_eth_api() {
+    let config = NodeConfig::test();
+    let db = create_test_rw_db();
+    let _builder = NodeBuilder::new(config)
+        .with_database(db)
+        .with_types::<EthereumNode>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default().map_eth_api(Ok))
+        .extend_rpc_modules(|ctx| {
+            let _ = ctx.registry.eth_api();
+            Ok(())
+        })
+        .check_launch();
+}
+
+#[test]
+fn test_payload_attributes_hook() {
+    let fee_recipient = Address::with_last_byte(1);
+    let hook = move |_: &ForkchoiceState, mut attributes: PayloadAttributes| {
+        attributes.suggested_fee_recipient = fee_recipient;
+        Ok::<_, EngineObjectValidationError>(attributes)
+    };
+
+    let config = NodeConfig::test();
+    let db = create_test_rw_db();
+    let _builder = NodeBuilder::new(config)
+        .with_database(db)
+        .with_types::<EthereumNode>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(
+            EthereumAddOns::default().with_engine_api(
+                BasicEngineApiBuilder::<EthereumEngineValidatorBuilder>::default()
+                    .with_payload_attributes_hook(hook),
+            ),
+        )
+        .check_launch();
+}
+
 #[tokio::test]
 async fn test_eth_launcher() {
     let tasks = TaskManager::current();
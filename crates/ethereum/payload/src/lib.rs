@@ -35,7 +35,7 @@ use reth_transaction_pool::{
     ValidPoolTransaction,
 };
 use revm::context_interface::Block as _;
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 use tracing::{debug, trace, warn};
 
 mod config;
@@ -44,13 +44,14 @@ pub use config::*;
 pub mod validator;
 pub use validator::EthereumExecutionPayloadValidator;
 
-type BestTransactionsIter<Pool> = Box<
+/// The iterator of the best transactions that are considered for inclusion in the payload.
+pub type BestTransactionsIter<Pool> = Box<
     dyn BestTransactions<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>,
 >;
 
 /// Ethereum payload builder
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
+pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig, Txs = ()> {
     /// Client providing access to node state.
     client: Client,
     /// Transaction pool.
@@ -59,6 +60,8 @@ pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
     evm_config: EvmConfig,
     /// Payload builder configuration.
     builder_config: EthereumBuilderConfig,
+    /// The type responsible for yielding the transactions that should be included in the payload.
+    best_transactions: Txs,
 }
 
 impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
@@ -69,16 +72,30 @@ impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
         evm_config: EvmConfig,
         builder_config: EthereumBuilderConfig,
     ) -> Self {
-        Self { client, pool, evm_config, builder_config }
+        Self { client, pool, evm_config, builder_config, best_transactions: () }
+    }
+}
+
+impl<Pool, Client, EvmConfig, Txs> EthereumPayloadBuilder<Pool, Client, EvmConfig, Txs> {
+    /// Configures the type responsible for yielding the transactions that should be included in the
+    /// payload.
+    pub fn with_transactions<T>(
+        self,
+        best_transactions: T,
+    ) -> EthereumPayloadBuilder<Pool, Client, EvmConfig, T> {
+        let Self { client, pool, evm_config, builder_config, .. } = self;
+        EthereumPayloadBuilder { client, pool, evm_config, builder_config, best_transactions }
     }
 }
 
 // Default implementation of [PayloadBuilder] for unit type
-impl<Pool, Client, EvmConfig> PayloadBuilder for EthereumPayloadBuilder<Pool, Client, EvmConfig>
+impl<Pool, Client, EvmConfig, Txs> PayloadBuilder
+    for EthereumPayloadBuilder<Pool, Client, EvmConfig, Txs>
 where
     EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+    Txs: EthPayloadTransactions<Pool::Transaction>,
 {
     type Attributes = EthPayloadBuilderAttributes;
     type BuiltPayload = EthBuiltPayload;
@@ -93,7 +110,7 @@ where
             self.pool.clone(),
             self.builder_config.clone(),
             args,
-            |attributes| self.pool.best_transactions_with_attributes(attributes),
+            |attributes| self.best_transactions.best_transactions(self.pool.clone(), attributes),
         )
     }
 
@@ -120,13 +137,39 @@ where
             self.pool.clone(),
             self.builder_config.clone(),
             args,
-            |attributes| self.pool.best_transactions_with_attributes(attributes),
+            |attributes| self.best_transactions.best_transactions(self.pool.clone(), attributes),
         )?
         .into_payload()
         .ok_or_else(|| PayloadBuilderError::MissingPayload)
     }
 }
 
+/// A type that returns the [`BestTransactions`] that should be included in the payload.
+///
+/// This allows plugging custom transaction selection into the [`EthereumPayloadBuilder`], e.g. to
+/// filter the transactions of the pool.
+pub trait EthPayloadTransactions<Transaction: PoolTransaction>:
+    Clone + Send + Sync + Unpin + 'static
+{
+    /// Returns an iterator that yields the transaction in the order they should get included in the
+    /// new payload.
+    fn best_transactions<Pool: TransactionPool<Transaction = Transaction>>(
+        &self,
+        pool: Pool,
+        attr: BestTransactionsAttributes,
+    ) -> BestTransactionsIter<Pool>;
+}
+
+impl<T: PoolTransaction> EthPayloadTransactions<T> for () {
+    fn best_transactions<Pool: TransactionPool<Transaction = T>>(
+        &self,
+        pool: Pool,
+        attr: BestTransactionsAttributes,
+    ) -> BestTransactionsIter<Pool> {
+        pool.best_transactions_with_attributes(attr)
+    }
+}
+
 /// Constructs an Ethereum transaction payload using the best transactions from the pool.
 ///
 /// Given build arguments including an Ethereum client, transaction pool,
@@ -147,7 +190,7 @@ where
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
     F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
 {
-    let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
+    let BuildArguments { mut cached_reads, config, cancel, best_payload, deadline } = args;
     let PayloadConfig { parent_header, attributes } = config;
 
     let state_provider = client.state_by_block_hash(parent_header.hash())?;
@@ -199,6 +242,12 @@ where
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
     while let Some(pool_tx) = best_txs.next() {
+        // stop selecting transactions once the time budget of this build is used up
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            debug!(target: "payload_builder", id=%attributes.id, "payload build deadline reached");
+            break
+        }
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -342,3 +391,89 @@ where
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_eips::eip1559::MIN_PROTOCOL_BASE_FEE;
+    use alloy_primitives::Address;
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use reth_primitives_traits::SealedHeader;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction};
+
+    /// Skips all transactions of the given sender.
+    #[derive(Debug, Clone)]
+    struct SkipSender(Address);
+
+    impl<T: PoolTransaction> EthPayloadTransactions<T> for SkipSender {
+        fn best_transactions<Pool: TransactionPool<Transaction = T>>(
+            &self,
+            pool: Pool,
+            attr: BestTransactionsAttributes,
+        ) -> BestTransactionsIter<Pool> {
+            let sender = self.0;
+            Box::new(
+                pool.best_transactions_with_attributes(attr)
+                    .filter_transactions(move |tx| tx.sender() != sender),
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn builds_payload_with_selected_transactions() {
+        let client = MockEthProvider::default();
+        let pool = testing_pool();
+
+        let included = MockTransaction::eip1559().with_gas_limit(21_000);
+        let skipped = MockTransaction::eip1559().with_gas_limit(21_000);
+        for tx in [&included, &skipped] {
+            client.add_account(tx.sender(), ExtendedAccount::new(0, U256::from(u64::MAX)));
+            pool.add_external_transaction(tx.clone()).await.unwrap();
+        }
+
+        let parent = Arc::new(SealedHeader::seal_slow(Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(MIN_PROTOCOL_BASE_FEE),
+            // cancun
+            timestamp: 1_720_000_000,
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(Default::default()),
+            ..Default::default()
+        }));
+        let attributes = EthPayloadBuilderAttributes::new(
+            parent.hash(),
+            PayloadAttributes {
+                timestamp: parent.timestamp + 12,
+                prev_randao: Default::default(),
+                suggested_fee_recipient: Address::random(),
+                withdrawals: Some(Vec::new()),
+                parent_beacon_block_root: Some(Default::default()),
+            },
+        );
+
+        let builder = EthereumPayloadBuilder::new(
+            client.clone(),
+            pool,
+            EthEvmConfig::new(client.chain_spec()),
+            EthereumBuilderConfig::new(),
+        )
+        .with_transactions(SkipSender(skipped.sender()));
+        let args = BuildArguments::new(
+            Default::default(),
+            PayloadConfig::new(parent, attributes),
+            Default::default(),
+            None,
+        );
+
+        let BuildOutcome::Better { payload, .. } = builder.try_build(args).unwrap() else {
+            panic!("expected a new payload")
+        };
+        let transactions =
+            payload.block().body().transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>();
+        assert_eq!(transactions, vec![*included.hash()]);
+    }
+}
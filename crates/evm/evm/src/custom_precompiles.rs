@@ -0,0 +1,197 @@
+//! Additional precompiles for custom chains.
+
+use crate::{
+    precompiles::{DynPrecompile, PrecompilesMap},
+    Database, Evm, EvmEnv, EvmFactory,
+};
+use alloc::{sync::Arc, vec::Vec};
+use alloy_primitives::Address;
+use revm::{
+    inspector::{Inspector, NoOpInspector},
+    primitives::hardfork::SpecId,
+};
+
+/// A precompile that is installed in addition to the precompiles of the EVM spec.
+#[derive(Debug, Clone)]
+pub struct CustomPrecompile<Spec = SpecId> {
+    /// Address of the precompile.
+    pub address: Address,
+    /// The first spec the precompile is active in.
+    pub activation: Spec,
+    /// Implementation of the precompile.
+    pub precompile: DynPrecompile,
+}
+
+/// Additional precompiles of a custom chain, keyed by address and activation spec.
+///
+/// The precompiles are installed by the [`CustomPrecompilesEvmFactory`] into every EVM it creates.
+/// Live execution, backfill, tracing and `eth_call` all create their EVMs through the factory of
+/// the [`ConfigureEvm`](crate::ConfigureEvm), so the precompiles apply consistently across them.
+///
+/// A precompile registered at the address of a precompile of the EVM spec replaces it.
+///
+/// Note: if the engine's precompile cache is enabled, the precompiles must be pure functions of
+/// their input and gas limit.
+#[derive(Debug, Clone)]
+pub struct CustomPrecompiles<Spec = SpecId> {
+    precompiles: Vec<CustomPrecompile<Spec>>,
+}
+
+impl<Spec> Default for CustomPrecompiles<Spec> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Spec> CustomPrecompiles<Spec> {
+    /// Creates an empty set of precompiles.
+    pub const fn new() -> Self {
+        Self { precompiles: Vec::new() }
+    }
+
+    /// Registers a precompile at the given address, active from the `activation` spec onwards.
+    ///
+    /// Registering a precompile at the same address twice replaces the earlier one in the specs
+    /// both are active in.
+    pub fn with_precompile(
+        mut self,
+        address: Address,
+        activation: Spec,
+        precompile: impl Into<DynPrecompile>,
+    ) -> Self {
+        self.precompiles.push(CustomPrecompile {
+            address,
+            activation,
+            precompile: precompile.into(),
+        });
+        self
+    }
+
+    /// Returns `true` if no precompiles are registered.
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty()
+    }
+
+    /// Returns an iterator over the registered precompiles.
+    pub fn iter(&self) -> impl Iterator<Item = &CustomPrecompile<Spec>> {
+        self.precompiles.iter()
+    }
+
+    /// Installs the precompiles that are active in the given spec.
+    pub fn apply(&self, spec: &Spec, precompiles: &mut PrecompilesMap)
+    where
+        Spec: PartialOrd,
+    {
+        for custom in self.precompiles.iter().filter(|custom| *spec >= custom.activation) {
+            let precompile = custom.precompile.clone();
+            precompiles.apply_precompile(&custom.address, |_| Some(precompile));
+        }
+    }
+}
+
+/// [`EvmFactory`] that installs [`CustomPrecompiles`] into the EVMs of an inner factory.
+#[derive(Debug, Clone)]
+pub struct CustomPrecompilesEvmFactory<F: EvmFactory> {
+    /// The factory creating the EVMs.
+    inner: F,
+    /// The precompiles installed into the created EVMs.
+    precompiles: Arc<CustomPrecompiles<F::Spec>>,
+}
+
+impl<F: EvmFactory> CustomPrecompilesEvmFactory<F> {
+    /// Creates a new factory that installs the precompiles into the EVMs created by `inner`.
+    pub fn new(inner: F, precompiles: CustomPrecompiles<F::Spec>) -> Self {
+        Self { inner, precompiles: Arc::new(precompiles) }
+    }
+
+    /// Returns the inner factory.
+    pub const fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// Returns the installed precompiles.
+    pub fn precompiles(&self) -> &CustomPrecompiles<F::Spec> {
+        &self.precompiles
+    }
+}
+
+impl<F> EvmFactory for CustomPrecompilesEvmFactory<F>
+where
+    F: EvmFactory<Precompiles = PrecompilesMap>,
+    F::Spec: PartialOrd,
+{
+    type Evm<DB: Database, I: Inspector<Self::Context<DB>>> = F::Evm<DB, I>;
+    type Context<DB: Database> = F::Context<DB>;
+    type Tx = F::Tx;
+    type Error<DBError: core::error::Error + Send + Sync + 'static> = F::Error<DBError>;
+    type HaltReason = F::HaltReason;
+    type Spec = F::Spec;
+    type Precompiles = PrecompilesMap;
+
+    fn create_evm<DB: Database>(
+        &self,
+        db: DB,
+        input: EvmEnv<Self::Spec>,
+    ) -> Self::Evm<DB, NoOpInspector> {
+        let spec = input.cfg_env.spec;
+        let mut evm = self.inner.create_evm(db, input);
+        self.precompiles.apply(&spec, evm.precompiles_mut());
+        evm
+    }
+
+    fn create_evm_with_inspector<DB: Database, I: Inspector<Self::Context<DB>>>(
+        &self,
+        db: DB,
+        input: EvmEnv<Self::Spec>,
+        inspector: I,
+    ) -> Self::Evm<DB, I> {
+        let spec = input.cfg_env.spec;
+        let mut evm = self.inner.create_evm_with_inspector(db, input, inspector);
+        self.precompiles.apply(&spec, evm.precompiles_mut());
+        evm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{precompiles::PrecompileInput, EthEvmFactory};
+    use alloy_primitives::Bytes;
+    use revm::{
+        context::TxEnv,
+        database_interface::EmptyDB,
+        precompile::{PrecompileOutput, PrecompileResult},
+    };
+
+    fn call(factory: &impl EvmFactory<Tx = TxEnv, Spec = SpecId>, spec: SpecId) -> Bytes {
+        let mut evm_env = EvmEnv::default();
+        evm_env.cfg_env.spec = spec;
+        let mut evm = factory.create_evm(EmptyDB::default(), evm_env);
+        evm.transact_raw(TxEnv {
+            gas_limit: 100_000,
+            kind: Address::with_last_byte(0xff).into(),
+            ..Default::default()
+        })
+        .unwrap()
+        .result
+        .into_output()
+        .unwrap()
+    }
+
+    #[test]
+    fn installs_precompiles_from_activation() {
+        let precompile: DynPrecompile = (|_input: PrecompileInput<'_>| -> PrecompileResult {
+            Ok(PrecompileOutput::new(100, Bytes::from_static(b"custom")))
+        })
+        .into();
+        let precompiles = CustomPrecompiles::new().with_precompile(
+            Address::with_last_byte(0xff),
+            SpecId::PRAGUE,
+            precompile,
+        );
+        let factory = CustomPrecompilesEvmFactory::new(EthEvmFactory::default(), precompiles);
+
+        assert!(call(&factory, SpecId::CANCUN).is_empty());
+        assert_eq!(call(&factory, SpecId::PRAGUE).as_ref(), b"custom");
+    }
+}
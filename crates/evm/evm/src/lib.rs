@@ -44,6 +44,9 @@ pub mod execute;
 mod aliases;
 pub use aliases::*;
 
+mod custom_precompiles;
+pub use custom_precompiles::*;
+
 #[cfg(feature = "metrics")]
 pub mod metrics;
 pub mod noop;
@@ -0,0 +1,205 @@
+use std::{fmt, ops::RangeInclusive};
+
+use alloy_primitives::BlockNumber;
+use reth_provider::{
+    BlockNumReader, ProviderError, ProviderResult, PruneCheckpointReader, StaticFileProviderFactory,
+};
+use reth_prune_types::PruneSegment;
+
+/// Data that is required to backfill a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackfillData {
+    /// Account history, required for the state before the first block of the range.
+    AccountHistory,
+    /// Storage history, required for the state before the first block of the range.
+    StorageHistory,
+    /// Block transactions, which are removed from static files when history expires.
+    Transactions,
+    /// Blocks above the highest block of the node.
+    Blocks,
+}
+
+impl fmt::Display for BackfillData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AccountHistory => f.write_str("account history"),
+            Self::StorageHistory => f.write_str("storage history"),
+            Self::Transactions => f.write_str("transactions"),
+            Self::Blocks => f.write_str("blocks"),
+        }
+    }
+}
+
+/// Blocks of a backfill range that can't be executed, because the data is missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingBackfillData {
+    /// The missing data.
+    pub data: BackfillData,
+    /// The blocks of the requested range that are affected.
+    pub range: RangeInclusive<BlockNumber>,
+}
+
+impl fmt::Display for MissingBackfillData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} missing for blocks {}..={}", self.data, self.range.start(), self.range.end())
+    }
+}
+
+/// Errors of creating a backfill job with an availability check.
+#[derive(Debug, thiserror::Error)]
+pub enum BackfillRangeError {
+    /// The data required to execute the range isn't available.
+    #[error(
+        "cannot backfill blocks {}..={}: {}",
+        .range.start(),
+        .range.end(),
+        display_missing(.missing)
+    )]
+    Unavailable {
+        /// The requested range.
+        range: RangeInclusive<BlockNumber>,
+        /// The missing data, and the blocks it affects.
+        missing: Vec<MissingBackfillData>,
+    },
+    /// Failed to read the availability from the provider.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+fn display_missing(missing: &[MissingBackfillData]) -> String {
+    missing.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+}
+
+/// Returns the data that is missing to execute the given range.
+pub(super) fn missing_data<P>(
+    provider: &P,
+    range: &RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<MissingBackfillData>>
+where
+    P: BlockNumReader + PruneCheckpointReader + StaticFileProviderFactory,
+{
+    let (start, end) = (*range.start(), *range.end());
+    let mut missing = Vec::new();
+
+    // Executing the first block requires the state after the previous block, which is only
+    // available from the block after the highest pruned block.
+    for (data, segment) in [
+        (BackfillData::AccountHistory, PruneSegment::AccountHistory),
+        (BackfillData::StorageHistory, PruneSegment::StorageHistory),
+    ] {
+        let Some(pruned) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        else {
+            continue
+        };
+        let first_executable = pruned + 2;
+        if start < first_executable {
+            missing
+                .push(MissingBackfillData { data, range: start..=end.min(first_executable - 1) });
+        }
+    }
+
+    let earliest_transactions = provider.static_file_provider().earliest_history_height();
+    if start < earliest_transactions {
+        missing.push(MissingBackfillData {
+            data: BackfillData::Transactions,
+            range: start..=end.min(earliest_transactions - 1),
+        });
+    }
+
+    let last = provider.last_block_number()?;
+    if end > last {
+        missing.push(MissingBackfillData {
+            data: BackfillData::Blocks,
+            range: start.max(last + 1)..=end,
+        });
+    }
+
+    Ok(missing)
+}
+
+/// Returns the part of the range that isn't affected by the missing data, if any.
+pub(super) fn clamp_range(
+    range: &RangeInclusive<BlockNumber>,
+    missing: &[MissingBackfillData],
+) -> Option<RangeInclusive<BlockNumber>> {
+    let (mut start, mut end) = (*range.start(), *range.end());
+    for missing in missing {
+        if missing.data == BackfillData::Blocks {
+            end = end.min(missing.range.start().saturating_sub(1));
+        } else {
+            start = start.max(missing.range.end() + 1);
+        }
+    }
+    (start <= end).then_some(start..=end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backfill::test_utils::{blocks_and_execution_outputs, chain_spec},
+        BackfillJobFactory,
+    };
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::crypto::secp256k1::public_key_to_address;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+        DatabaseProviderFactory, PruneCheckpointWriter,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+    use reth_testing_utils::generators;
+
+    #[test]
+    fn test_backfill_availability() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+        let chain_spec = chain_spec(address);
+
+        let executor = EthEvmConfig::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        blocks_and_execution_outputs(provider_factory.clone(), chain_spec, key_pair)?;
+
+        // the account history of the genesis block is pruned
+        let provider_rw = provider_factory.database_provider_rw()?;
+        provider_rw.save_prune_checkpoint(
+            PruneSegment::AccountHistory,
+            PruneCheckpoint {
+                block_number: Some(0),
+                tx_number: None,
+                prune_mode: PruneMode::Before(1),
+            },
+        )?;
+        provider_rw.commit()?;
+
+        let blockchain_db = BlockchainProvider::new(provider_factory)?;
+        let factory = BackfillJobFactory::new(executor, blockchain_db);
+
+        factory.check_availability(2..=2)?;
+
+        let Err(BackfillRangeError::Unavailable { missing, .. }) =
+            factory.check_availability(1..=4)
+        else {
+            panic!("expected the range to be unavailable")
+        };
+        assert_eq!(
+            missing,
+            vec![
+                MissingBackfillData { data: BackfillData::AccountHistory, range: 1..=1 },
+                MissingBackfillData { data: BackfillData::Blocks, range: 3..=4 },
+            ]
+        );
+
+        assert!(matches!(factory.try_backfill(1..=2), Err(BackfillRangeError::Unavailable { .. })));
+
+        let factory = factory.with_clamp_unavailable(true);
+        assert_eq!(factory.try_backfill(1..=4)?.range, 2..=2);
+        assert!(matches!(factory.try_backfill(3..=4), Err(BackfillRangeError::Unavailable { .. })));
+
+        Ok(())
+    }
+}
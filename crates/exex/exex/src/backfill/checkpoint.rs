@@ -0,0 +1,528 @@
+use super::{job::BackfillJobResult, stream::StreamBackfillJob};
+use crate::{BackfillJob, BackfillProgress};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{hex, BlockNumber, B256};
+use futures::Stream;
+use reth_evm::{execute::BlockExecutionError, ConfigureEvm};
+use reth_fs_util::FsPathError;
+use reth_node_api::NodePrimitives;
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{
+    BlockHashReader, BlockReader, Chain, HeaderProvider, ProviderError, StateProviderFactory,
+};
+use reth_tracing::tracing::debug;
+use std::{
+    io::{ErrorKind, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+static FILE_EXTENSION: &str = "checkpoint";
+
+/// Size of an encoded [`BackfillCheckpoint`]: the range bounds and the parent hash.
+const CHECKPOINT_SIZE: usize = 8 + 8 + 32;
+
+/// Result type for [`BackfillCheckpoints`] operations.
+pub type BackfillCheckpointResult<T> = Result<T, BackfillCheckpointError>;
+
+/// Errors that can occur when reading or writing backfill checkpoints.
+#[derive(Debug, thiserror::Error)]
+pub enum BackfillCheckpointError {
+    /// Filesystem error at the path.
+    #[error(transparent)]
+    FsPathError(#[from] FsPathError),
+    /// Error when looking up the canonical chain.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The checkpoint file could not be decoded.
+    #[error("invalid backfill checkpoint {}", _0.display())]
+    Invalid(PathBuf),
+    /// The last processed block of the checkpoint is not canonical anymore, i.e. the chain was
+    /// reorged since the checkpoint was saved.
+    #[error(
+        "backfill checkpoint is on a stale fork: block {number} is {expected}, canonical is {got:?}"
+    )]
+    StaleFork {
+        /// Number of the last processed block.
+        number: BlockNumber,
+        /// Hash of the last processed block stored in the checkpoint.
+        expected: B256,
+        /// Hash of the canonical block with the same number, if any.
+        got: Option<B256>,
+    },
+    /// The backfill job factory was not configured with checkpoints.
+    #[error("backfill checkpoints are not configured")]
+    NotConfigured,
+}
+
+/// Progress of the backfill of an ExEx.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillCheckpoint {
+    /// The range of blocks that are left to backfill.
+    pub range: RangeInclusive<BlockNumber>,
+    /// Hash of the block before the range, i.e. the last processed block. [`B256::ZERO`] if the
+    /// range starts at genesis.
+    pub parent_hash: B256,
+}
+
+impl BackfillCheckpoint {
+    /// Returns the number and hash of the block before the range, if the range doesn't start at
+    /// genesis.
+    pub fn parent(&self) -> Option<BlockNumHash> {
+        self.range.start().checked_sub(1).map(|number| BlockNumHash::new(number, self.parent_hash))
+    }
+
+    /// Creates a checkpoint for the given range with the canonical hash of the block before it.
+    pub(crate) fn canonical<P: BlockHashReader>(
+        provider: &P,
+        range: RangeInclusive<BlockNumber>,
+    ) -> BackfillCheckpointResult<Self> {
+        let parent_hash = match range.start().checked_sub(1) {
+            Some(number) => provider
+                .block_hash(number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?,
+            None => B256::ZERO,
+        };
+        Ok(Self { range, parent_hash })
+    }
+
+    /// Checks that the block before the range is still canonical.
+    ///
+    /// Returns [`BackfillCheckpointError::StaleFork`] otherwise, because the blocks processed
+    /// before the checkpoint are not part of the canonical chain anymore.
+    pub(crate) fn verify<P: BlockHashReader>(&self, provider: &P) -> BackfillCheckpointResult<()> {
+        let Some(parent) = self.parent() else { return Ok(()) };
+
+        let got = provider.block_hash(parent.number)?;
+        if got != Some(parent.hash) {
+            return Err(BackfillCheckpointError::StaleFork {
+                number: parent.number,
+                expected: parent.hash,
+                got,
+            })
+        }
+
+        Ok(())
+    }
+}
+
+/// Persistent store of the backfill progress of ExExes, backed by a directory of files.
+///
+/// Each ExEx has a single file that contains the range of blocks that are left to backfill and the
+/// hash of the last processed block. The file is removed once the backfill is finished.
+#[derive(Debug, Clone)]
+pub struct BackfillCheckpoints {
+    path: PathBuf,
+}
+
+impl BackfillCheckpoints {
+    /// Creates a new instance of [`BackfillCheckpoints`] backed by the given directory and creates
+    /// it if it doesn't exist.
+    pub fn new(path: impl AsRef<Path>) -> BackfillCheckpointResult<Self> {
+        reth_fs_util::create_dir_all(&path)?;
+
+        Ok(Self { path: path.as_ref().to_path_buf() })
+    }
+
+    /// ExEx IDs are arbitrary strings, so they're hex-encoded to be used as file names.
+    fn file_path(&self, exex_id: &str) -> PathBuf {
+        self.path.join(format!("{}.{FILE_EXTENSION}", hex::encode(exex_id)))
+    }
+
+    /// Returns the backfill progress of the given ExEx, if any.
+    pub fn get(&self, exex_id: &str) -> BackfillCheckpointResult<Option<BackfillCheckpoint>> {
+        let path = self.file_path(exex_id);
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(FsPathError::read(err, path).into()),
+        };
+
+        let Ok(data) = <[u8; CHECKPOINT_SIZE]>::try_from(data.as_slice()) else {
+            return Err(BackfillCheckpointError::Invalid(path))
+        };
+        let start = BlockNumber::from_be_bytes(data[..8].try_into().expect("8 bytes"));
+        let end = BlockNumber::from_be_bytes(data[8..16].try_into().expect("8 bytes"));
+        let parent_hash = B256::from_slice(&data[16..]);
+        Ok(Some(BackfillCheckpoint { range: start..=end, parent_hash }))
+    }
+
+    /// Atomically saves the backfill progress of the given ExEx.
+    pub fn save(
+        &self,
+        exex_id: &str,
+        checkpoint: &BackfillCheckpoint,
+    ) -> BackfillCheckpointResult<()> {
+        let mut data = [0; CHECKPOINT_SIZE];
+        data[..8].copy_from_slice(&checkpoint.range.start().to_be_bytes());
+        data[8..16].copy_from_slice(&checkpoint.range.end().to_be_bytes());
+        data[16..].copy_from_slice(checkpoint.parent_hash.as_slice());
+
+        reth_fs_util::atomic_write_file(&self.file_path(exex_id), |file| file.write_all(&data))?;
+        debug!(target: "exex::backfill", %exex_id, ?checkpoint, "Saved backfill checkpoint");
+
+        Ok(())
+    }
+
+    /// Removes the checkpoint of the given ExEx, if any.
+    pub fn remove(&self, exex_id: &str) -> BackfillCheckpointResult<()> {
+        let path = self.file_path(exex_id);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                debug!(target: "exex::backfill", %exex_id, "Removed backfill checkpoint");
+                Ok(())
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(FsPathError::remove_file(err, path).into()),
+        }
+    }
+}
+
+/// Progress of a checkpointed job that is shared by the iterator and the stream.
+#[derive(Debug)]
+struct CheckpointState {
+    checkpoints: BackfillCheckpoints,
+    exex_id: String,
+    /// The last block of the backfilled range.
+    end: BlockNumber,
+    /// The tip of the last yielded chain.
+    yielded: Option<BlockNumHash>,
+}
+
+impl CheckpointState {
+    /// Saves the given checkpoint and tracks the progress until the end of its range.
+    fn new(
+        checkpoints: BackfillCheckpoints,
+        exex_id: String,
+        checkpoint: &BackfillCheckpoint,
+    ) -> BackfillCheckpointResult<Self> {
+        checkpoints.save(&exex_id, checkpoint)?;
+        Ok(Self { checkpoints, exex_id, end: *checkpoint.range.end(), yielded: None })
+    }
+
+    /// Records the chain that is yielded from the job.
+    fn on_yielded<N: NodePrimitives>(&mut self, chain: &BackfillJobResult<Chain<N>>) {
+        if let Ok(chain) = chain {
+            self.yielded = Some(chain.tip().num_hash());
+        }
+    }
+
+    /// Persists the progress up to the last yielded chain.
+    fn commit(&mut self) -> BackfillCheckpointResult<()> {
+        let Some(tip) = self.yielded.take() else { return Ok(()) };
+
+        if tip.number >= self.end {
+            self.checkpoints.remove(&self.exex_id)
+        } else {
+            let checkpoint =
+                BackfillCheckpoint { range: tip.number + 1..=self.end, parent_hash: tip.hash };
+            self.checkpoints.save(&self.exex_id, &checkpoint)
+        }
+    }
+}
+
+/// Backfill job that persists its progress to [`BackfillCheckpoints`], so that it can be resumed
+/// with [`BackfillJobFactory::resume`](crate::BackfillJobFactory::resume) after a restart.
+///
+/// A yielded [`Chain`] is considered processed once the next one is requested, so the blocks of
+/// the last yielded chain are executed again after a restart. The hash of the last processed block
+/// is saved as well, so that resuming after a reorg of the processed blocks fails instead of
+/// continuing on top of a stale fork. When the range is finished, the checkpoint is removed.
+#[derive(Debug)]
+pub struct CheckpointedBackfillJob<E, P> {
+    job: BackfillJob<E, P>,
+    state: CheckpointState,
+}
+
+impl<E, P> CheckpointedBackfillJob<E, P> {
+    /// Creates a new [`CheckpointedBackfillJob`] and saves the checkpoint for the range of the
+    /// given job.
+    pub(crate) fn new(
+        job: BackfillJob<E, P>,
+        checkpoints: BackfillCheckpoints,
+        exex_id: String,
+        parent_hash: B256,
+    ) -> BackfillCheckpointResult<Self> {
+        let checkpoint = BackfillCheckpoint { range: job.range.clone(), parent_hash };
+        let state = CheckpointState::new(checkpoints, exex_id, &checkpoint)?;
+        Ok(Self { job, state })
+    }
+
+    /// Returns the ID of the ExEx that the progress is saved for.
+    pub fn exex_id(&self) -> &str {
+        &self.state.exex_id
+    }
+
+    /// Returns the range of blocks that are left to backfill.
+    pub const fn range(&self) -> &RangeInclusive<BlockNumber> {
+        &self.job.range
+    }
+
+    /// Returns a handle to observe the progress of the job, see [`BackfillJob::progress`].
+    pub fn progress(&self) -> BackfillProgress {
+        self.job.progress()
+    }
+
+    /// Also records the progress of the job as metrics, labeled with the ID of the ExEx.
+    pub fn with_progress_metrics(mut self) -> Self {
+        self.job = self.job.with_progress_metrics(self.state.exex_id.clone());
+        self
+    }
+}
+
+impl<E, P> CheckpointedBackfillJob<E, P>
+where
+    E: ConfigureEvm,
+{
+    /// Converts the backfill job into a stream that persists its progress in the same way.
+    pub fn into_stream(self) -> CheckpointedStreamBackfillJob<E, P> {
+        CheckpointedStreamBackfillJob { stream: self.job.into_stream(), state: self.state }
+    }
+}
+
+impl<E, P> Iterator for CheckpointedBackfillJob<E, P>
+where
+    E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + 'static,
+    P: HeaderProvider
+        + BlockReader<Transaction: SignedTransaction>
+        + StateProviderFactory
+        + Clone
+        + 'static,
+{
+    type Item = BackfillJobResult<Chain<E::Primitives>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(err) = self.state.commit() {
+            return Some(Err(BlockExecutionError::other(err)))
+        }
+
+        let chain = self.job.next()?;
+        self.state.on_yielded(&chain);
+        Some(chain)
+    }
+}
+
+/// Stream of a [`CheckpointedBackfillJob`], created with
+/// [`CheckpointedBackfillJob::into_stream`].
+///
+/// The chains are executed by a [`StreamBackfillJob`], and a yielded [`Chain`] is considered
+/// processed once the next one is polled.
+#[derive(Debug)]
+pub struct CheckpointedStreamBackfillJob<E: ConfigureEvm, P> {
+    stream: StreamBackfillJob<E, P, Chain<E::Primitives>>,
+    state: CheckpointState,
+}
+
+impl<E: ConfigureEvm, P> CheckpointedStreamBackfillJob<E, P> {
+    /// Returns the ID of the ExEx that the progress is saved for.
+    pub fn exex_id(&self) -> &str {
+        &self.state.exex_id
+    }
+}
+
+impl<E, P> Stream for CheckpointedStreamBackfillJob<E, P>
+where
+    E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + 'static,
+    P: BlockReader + StateProviderFactory + Clone + Unpin + 'static,
+{
+    type Item = BackfillJobResult<Chain<E::Primitives>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if let Err(err) = this.state.commit() {
+            return Poll::Ready(Some(Err(BlockExecutionError::other(err))))
+        }
+
+        let chain = ready!(Pin::new(&mut this.stream).poll_next(cx));
+        if let Some(chain) = &chain {
+            this.state.on_yielded(chain);
+        }
+        Poll::Ready(chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BackfillCheckpoint, BackfillCheckpoints};
+    use crate::{
+        backfill::test_utils::{blocks_and_execution_outputs, chain_spec},
+        BackfillCheckpointError, BackfillJobFactory,
+    };
+    use alloy_primitives::B256;
+    use futures::StreamExt;
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::crypto::secp256k1::public_key_to_address;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+    };
+    use reth_stages_api::ExecutionStageThresholds;
+    use reth_testing_utils::generators;
+
+    #[test]
+    fn checkpoints() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let checkpoints = BackfillCheckpoints::new(&temp_dir)?;
+
+        assert_eq!(checkpoints.get("exex")?, None);
+
+        let checkpoint = BackfillCheckpoint { range: 1..=10, parent_hash: B256::repeat_byte(1) };
+        let other = BackfillCheckpoint { range: 5..=6, parent_hash: B256::repeat_byte(2) };
+        checkpoints.save("exex", &checkpoint)?;
+        checkpoints.save("other/exex", &other)?;
+        assert_eq!(checkpoints.get("exex")?, Some(checkpoint));
+        assert_eq!(checkpoints.get("other/exex")?, Some(other.clone()));
+
+        checkpoints.remove("exex")?;
+        assert_eq!(checkpoints.get("exex")?, None);
+        assert_eq!(checkpoints.get("other/exex")?, Some(other));
+
+        Ok(())
+    }
+
+    #[test]
+    fn resume_backfill() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthEvmConfig::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        let blocks_and_execution_outputs =
+            blocks_and_execution_outputs(provider_factory, chain_spec, key_pair)?;
+        let (block1, _) = blocks_and_execution_outputs[0].clone();
+        let (block2, _) = blocks_and_execution_outputs[1].clone();
+
+        let temp_dir = tempfile::tempdir()?;
+        let checkpoints = BackfillCheckpoints::new(&temp_dir)?;
+        let factory = BackfillJobFactory::new(executor, blockchain_db)
+            .with_thresholds(ExecutionStageThresholds { max_blocks: Some(1), ..Default::default() })
+            .with_checkpoints(checkpoints.clone());
+
+        // Nothing to resume
+        assert!(factory.resume("exex")?.is_none());
+
+        // Execute the first block and "crash" before requesting the next one, the first block
+        // should be executed again after the restart
+        let mut job = factory.checkpointed("exex", 1..=2)?;
+        assert_eq!(job.next().transpose()?.map(|chain| chain.tip().number), Some(1));
+        drop(job);
+        assert_eq!(checkpoints.get("exex")?.map(|checkpoint| checkpoint.range), Some(1..=2));
+
+        // Execute the first block again and request the next one, the first block should be
+        // committed
+        let mut job = factory.resume("exex")?.unwrap();
+        assert_eq!(job.next().transpose()?.map(|chain| chain.tip().number), Some(1));
+        assert_eq!(job.next().transpose()?.map(|chain| chain.tip().number), Some(2));
+        drop(job);
+        assert_eq!(
+            checkpoints.get("exex")?,
+            Some(BackfillCheckpoint { range: 2..=2, parent_hash: block1.hash() })
+        );
+
+        // Resume from the second block and finish the backfill
+        let mut job = factory.resume("exex")?.unwrap();
+        let chain = job.next().transpose()?.unwrap();
+        assert_eq!(chain.blocks().values().collect::<Vec<_>>(), vec![&block2]);
+        assert!(job.next().is_none());
+        assert_eq!(checkpoints.get("exex")?, None);
+        assert!(factory.resume("exex")?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn resume_stale_fork() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthEvmConfig::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        let blocks_and_execution_outputs =
+            blocks_and_execution_outputs(provider_factory, chain_spec, key_pair)?;
+        let (block1, _) = blocks_and_execution_outputs[0].clone();
+
+        let temp_dir = tempfile::tempdir()?;
+        let checkpoints = BackfillCheckpoints::new(&temp_dir)?;
+        let factory =
+            BackfillJobFactory::new(executor, blockchain_db).with_checkpoints(checkpoints.clone());
+
+        // The first block was processed on a fork that is not canonical anymore
+        let stale = BackfillCheckpoint { range: 2..=2, parent_hash: B256::repeat_byte(1) };
+        checkpoints.save("exex", &stale)?;
+
+        let err = factory.resume("exex").unwrap_err();
+        assert!(matches!(
+            err,
+            BackfillCheckpointError::StaleFork { number: 1, expected, got }
+                if expected == stale.parent_hash && got == Some(block1.hash())
+        ));
+        // The checkpoint is kept
+        assert_eq!(checkpoints.get("exex")?, Some(stale));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resume_backfill_stream() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthEvmConfig::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        let blocks_and_execution_outputs =
+            blocks_and_execution_outputs(provider_factory, chain_spec, key_pair)?;
+        let (block1, _) = blocks_and_execution_outputs[0].clone();
+
+        let temp_dir = tempfile::tempdir()?;
+        let checkpoints = BackfillCheckpoints::new(&temp_dir)?;
+        let factory = BackfillJobFactory::new(executor, blockchain_db)
+            .with_thresholds(ExecutionStageThresholds { max_blocks: Some(1), ..Default::default() })
+            .with_stream_parallelism(1)
+            .with_checkpoints(checkpoints.clone());
+
+        // The first chain is committed once the second one is polled
+        let mut stream = factory.checkpointed("exex", 1..=2)?.into_stream();
+        assert_eq!(stream.next().await.transpose()?.map(|chain| chain.tip().number), Some(1));
+        assert_eq!(checkpoints.get("exex")?.map(|checkpoint| checkpoint.range), Some(1..=2));
+        assert_eq!(stream.next().await.transpose()?.map(|chain| chain.tip().number), Some(2));
+        drop(stream);
+        assert_eq!(
+            checkpoints.get("exex")?,
+            Some(BackfillCheckpoint { range: 2..=2, parent_hash: block1.hash() })
+        );
+
+        // Resume from the second block and finish the backfill
+        let mut stream = factory.resume("exex")?.unwrap().into_stream();
+        assert_eq!(stream.next().await.transpose()?.map(|chain| chain.tip().number), Some(2));
+        assert!(stream.next().await.is_none());
+        assert_eq!(checkpoints.get("exex")?, None);
+
+        Ok(())
+    }
+}
@@ -1,12 +1,23 @@
-use crate::BackfillJob;
+use crate::{
+    BackfillCheckpoint, BackfillCheckpointError, BackfillCheckpointResult, BackfillCheckpoints,
+    BackfillJob, CheckpointedBackfillJob, InspectorBackfillJob,
+};
 use std::{ops::RangeInclusive, time::Duration};
 
 use alloy_primitives::BlockNumber;
 use reth_node_api::FullNodeComponents;
+use reth_provider::{
+    BlockHashReader, BlockNumReader, PruneCheckpointReader, StaticFileProviderFactory,
+};
 use reth_prune_types::PruneModes;
 use reth_stages_api::ExecutionStageThresholds;
+use reth_tracing::tracing::warn;
 
-use super::stream::DEFAULT_PARALLELISM;
+use super::{
+    availability::{clamp_range, missing_data, BackfillRangeError},
+    progress::BackfillProgressReporter,
+    stream::DEFAULT_PARALLELISM,
+};
 
 /// Factory for creating new backfill jobs.
 #[derive(Debug, Clone)]
@@ -16,6 +27,9 @@ pub struct BackfillJobFactory<E, P> {
     prune_modes: PruneModes,
     thresholds: ExecutionStageThresholds,
     stream_parallelism: usize,
+    prefetch_depth: usize,
+    clamp_unavailable: bool,
+    checkpoints: Option<BackfillCheckpoints>,
 }
 
 impl<E, P> BackfillJobFactory<E, P> {
@@ -35,6 +49,9 @@ impl<E, P> BackfillJobFactory<E, P> {
                 ..Default::default()
             },
             stream_parallelism: DEFAULT_PARALLELISM,
+            prefetch_depth: 0,
+            clamp_unavailable: false,
+            checkpoints: None,
         }
     }
 
@@ -58,6 +75,30 @@ impl<E, P> BackfillJobFactory<E, P> {
         self.stream_parallelism = stream_parallelism;
         self
     }
+
+    /// Sets the number of blocks that are fetched ahead of the execution.
+    ///
+    /// If non-zero, the blocks of a job are fetched by a single worker into a bounded channel of
+    /// this size, while the execution consumes them across batches. Defaults to `0`, which fetches
+    /// blocks in the execution thread.
+    pub const fn with_prefetch_depth(mut self, prefetch_depth: usize) -> Self {
+        self.prefetch_depth = prefetch_depth;
+        self
+    }
+
+    /// Sets whether [`Self::try_backfill`] clamps ranges to the blocks that can be executed,
+    /// instead of failing if any block can't be executed.
+    pub const fn with_clamp_unavailable(mut self, clamp_unavailable: bool) -> Self {
+        self.clamp_unavailable = clamp_unavailable;
+        self
+    }
+
+    /// Sets the store that [`Self::checkpointed`] and [`Self::resume`] persist the backfill
+    /// progress to.
+    pub fn with_checkpoints(mut self, checkpoints: BackfillCheckpoints) -> Self {
+        self.checkpoints = Some(checkpoints);
+        self
+    }
 }
 
 impl<E: Clone, P: Clone> BackfillJobFactory<E, P> {
@@ -67,11 +108,128 @@ impl<E: Clone, P: Clone> BackfillJobFactory<E, P> {
             evm_config: self.evm_config.clone(),
             provider: self.provider.clone(),
             prune_modes: self.prune_modes.clone(),
-            range,
             thresholds: self.thresholds.clone(),
             stream_parallelism: self.stream_parallelism,
+            prefetch_depth: self.prefetch_depth,
+            prefetcher: None,
+            progress: BackfillProgressReporter::new(range.clone()),
+            range,
+        }
+    }
+
+    /// Creates a new backfill job for the given range that executes every block with an inspector
+    /// created by the given factory.
+    ///
+    /// See [`InspectorBackfillJob`].
+    pub fn backfill_with_inspector<F>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        inspector_factory: F,
+    ) -> InspectorBackfillJob<E, P, F> {
+        self.backfill(range).with_inspector(inspector_factory)
+    }
+}
+
+impl<E: Clone, P: BlockHashReader + Clone> BackfillJobFactory<E, P> {
+    /// Creates a new backfill job for the given range that persists its progress for the given
+    /// ExEx, replacing any previous progress.
+    ///
+    /// The block before the range must be canonical, its hash is saved with the progress.
+    ///
+    /// Returns [`BackfillCheckpointError::NotConfigured`] if the factory has no
+    /// [checkpoints](Self::with_checkpoints).
+    pub fn checkpointed(
+        &self,
+        exex_id: impl Into<String>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> BackfillCheckpointResult<CheckpointedBackfillJob<E, P>> {
+        let checkpoints = self.checkpoints.clone().ok_or(BackfillCheckpointError::NotConfigured)?;
+        let checkpoint = BackfillCheckpoint::canonical(&self.provider, range)?;
+        CheckpointedBackfillJob::new(
+            self.backfill(checkpoint.range),
+            checkpoints,
+            exex_id.into(),
+            checkpoint.parent_hash,
+        )
+    }
+
+    /// Resumes the backfill of the given ExEx from its persisted progress.
+    ///
+    /// Returns `None` if there's no unfinished backfill for the ExEx, and
+    /// [`BackfillCheckpointError::NotConfigured`] if the factory has no
+    /// [checkpoints](Self::with_checkpoints). If the last processed block is not canonical
+    /// anymore, returns [`BackfillCheckpointError::StaleFork`] and keeps the checkpoint, so that
+    /// the ExEx can revert its processed blocks and start a new backfill.
+    pub fn resume(
+        &self,
+        exex_id: impl Into<String>,
+    ) -> BackfillCheckpointResult<Option<CheckpointedBackfillJob<E, P>>> {
+        let exex_id = exex_id.into();
+        let checkpoints =
+            self.checkpoints.as_ref().ok_or(BackfillCheckpointError::NotConfigured)?;
+        let Some(checkpoint) = checkpoints.get(&exex_id)? else { return Ok(None) };
+        checkpoint.verify(&self.provider)?;
+
+        CheckpointedBackfillJob::new(
+            self.backfill(checkpoint.range),
+            checkpoints.clone(),
+            exex_id,
+            checkpoint.parent_hash,
+        )
+        .map(Some)
+    }
+}
+
+impl<E, P> BackfillJobFactory<E, P>
+where
+    E: Clone,
+    P: BlockNumReader + PruneCheckpointReader + StaticFileProviderFactory + Clone,
+{
+    /// Checks that the data required to execute the given range is available.
+    ///
+    /// Returns [`BackfillRangeError::Unavailable`] with the missing data if the range reaches below
+    /// the pruned state history or the expired transactions, or above the highest block.
+    pub fn check_availability(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), BackfillRangeError> {
+        let missing = missing_data(&self.provider, &range)?;
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(BackfillRangeError::Unavailable { range, missing })
         }
     }
+
+    /// Creates a new backfill job for the given range, after checking that the data required to
+    /// execute it is available.
+    ///
+    /// If [clamping](Self::with_clamp_unavailable) is enabled, the range is reduced to the blocks
+    /// that can be executed with a warning instead, and only fails if no block can be executed.
+    pub fn try_backfill(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<BackfillJob<E, P>, BackfillRangeError> {
+        let missing = missing_data(&self.provider, &range)?;
+        if missing.is_empty() {
+            return Ok(self.backfill(range))
+        }
+
+        if self.clamp_unavailable {
+            if let Some(clamped) = clamp_range(&range, &missing) {
+                warn!(
+                    target: "exex::backfill",
+                    requested = ?range,
+                    ?clamped,
+                    ?missing,
+                    "Clamping backfill range to available blocks"
+                );
+                return Ok(self.backfill(clamped))
+            }
+        }
+
+        Err(BackfillRangeError::Unavailable { range, missing })
+    }
 }
 
 impl BackfillJobFactory<(), ()> {
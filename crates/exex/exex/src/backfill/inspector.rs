@@ -0,0 +1,122 @@
+use super::job::{BackfillDb, BackfillJobResult};
+use crate::{BackfillJob, BackfillProgress};
+use reth_evm::{execute::BlockExecutor, ConfigureEvm, InspectorFor};
+use reth_node_api::NodePrimitives;
+use reth_primitives_traits::{RecoveredBlock, SignedTransaction};
+use reth_provider::{BlockReader, Chain, HeaderProvider, StateProviderFactory};
+
+/// Database the blocks of an [`InspectorBackfillJob`] are executed against.
+pub type InspectorBackfillDb = BackfillDb;
+
+/// Backfill job that executes every block with a custom inspector.
+///
+/// It implements [`Iterator`] that executes blocks in batches according to the thresholds of the
+/// [`BackfillJob`] it was created from, and yields the [`Chain`] together with the inspectors used
+/// to execute each of its blocks, in block order.
+///
+/// A new inspector is created for every block by the inspector factory.
+#[derive(Debug)]
+pub struct InspectorBackfillJob<E, P, F> {
+    pub(crate) job: BackfillJob<E, P>,
+    pub(crate) inspector_factory: F,
+}
+
+impl<E, P, F> InspectorBackfillJob<E, P, F> {
+    /// Returns a handle to observe the progress of the job, see [`BackfillJob::progress`].
+    pub fn progress(&self) -> BackfillProgress {
+        self.job.progress()
+    }
+}
+
+impl<E, P, F, I> Iterator for InspectorBackfillJob<E, P, F>
+where
+    E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + 'static,
+    P: HeaderProvider
+        + BlockReader<Transaction: SignedTransaction>
+        + StateProviderFactory
+        + Clone
+        + 'static,
+    F: FnMut(&RecoveredBlock<P::Block>) -> I,
+    I: for<'a> InspectorFor<E, &'a mut InspectorBackfillDb>,
+{
+    type Item = BackfillJobResult<(Chain<E::Primitives>, Vec<I>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.job.range.is_empty() {
+            return None
+        }
+
+        let Self { job, inspector_factory } = self;
+        Some(job.execute_range_with(|evm_config, db, block| {
+            let mut inspector = inspector_factory(block);
+            let evm = evm_config.evm_with_env_and_inspector(
+                &mut *db,
+                evm_config.evm_env(block.header()),
+                &mut inspector,
+            );
+            let ctx = evm_config.context_for_block(block);
+            let result = evm_config
+                .create_executor(evm, ctx)
+                .execute_block(block.transactions_recovered())?;
+            Ok((result, inspector))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backfill::test_utils::{blocks_and_execution_outputs, chain_spec},
+        BackfillJobFactory,
+    };
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::crypto::secp256k1::public_key_to_address;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+    };
+    use reth_revm::inspector::NoOpInspector;
+    use reth_testing_utils::generators;
+
+    #[test]
+    fn test_backfill_with_inspector() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthEvmConfig::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        blocks_and_execution_outputs(provider_factory, chain_spec, key_pair)?;
+
+        let factory = BackfillJobFactory::new(executor, blockchain_db);
+        let chains = factory.backfill(1..=2).collect::<Result<Vec<_>, _>>()?;
+
+        let mut inspected_blocks = Vec::new();
+        let inspected = factory
+            .backfill_with_inspector(1..=2, |block| {
+                inspected_blocks.push(block.number);
+                NoOpInspector
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Assert that the inspector doesn't change the execution, and that an inspector was
+        // created for every block
+        assert_eq!(inspected.len(), chains.len());
+        for ((mut inspected_chain, inspectors), mut chain) in inspected.into_iter().zip(chains) {
+            inspected_chain.execution_outcome_mut().bundle.reverts.sort();
+            chain.execution_outcome_mut().bundle.reverts.sort();
+            assert_eq!(inspected_chain, chain);
+            assert_eq!(inspectors.len(), chain.len());
+        }
+        assert_eq!(inspected_blocks, vec![1, 2]);
+
+        Ok(())
+    }
+}
@@ -1,32 +1,48 @@
-use crate::StreamBackfillJob;
+use super::progress::BackfillProgressReporter;
+use crate::{BackfillProgress, InspectorBackfillJob, ParallelBackfillJob, StreamBackfillJob};
+use parking_lot::Mutex;
 use reth_evm::ConfigureEvm;
 use std::{
+    any::Any,
     ops::RangeInclusive,
+    sync::mpsc,
     time::{Duration, Instant},
 };
 
 use alloy_consensus::BlockHeader;
 use alloy_primitives::BlockNumber;
-use reth_ethereum_primitives::Receipt;
-use reth_evm::execute::{BlockExecutionError, BlockExecutionOutput, Executor};
+use reth_evm::execute::{BlockExecutionError, BlockExecutionOutput, BlockExecutor, Executor};
 use reth_node_api::{Block as _, BlockBody as _, NodePrimitives};
-use reth_primitives_traits::{format_gas_throughput, RecoveredBlock, SignedTransaction};
+use reth_primitives_traits::{format_gas_throughput, Block, RecoveredBlock, SignedTransaction};
 use reth_provider::{
-    BlockReader, Chain, ExecutionOutcome, HeaderProvider, ProviderError, StateProviderFactory,
-    TransactionVariant,
+    BlockExecutionResult, BlockReader, Chain, ExecutionOutcome, HeaderProvider, ProviderError,
+    StateProviderBox, StateProviderFactory, TransactionVariant,
 };
 use reth_prune_types::PruneModes;
-use reth_revm::database::StateProviderDatabase;
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{states::bundle_state::BundleRetention, State},
+};
 use reth_stages_api::ExecutionStageThresholds;
 use reth_tracing::tracing::{debug, trace};
 
 pub(super) type BackfillJobResult<T> = Result<T, BlockExecutionError>;
 
+/// Database the blocks of a backfill job are executed against.
+pub(super) type BackfillDb = State<StateProviderDatabase<StateProviderBox>>;
+
+/// Result of executing a single block of a backfill job.
+type BlockResult<E> =
+    BlockExecutionResult<<<E as ConfigureEvm>::Primitives as NodePrimitives>::Receipt>;
+
 /// Backfill job started for a specific range.
 ///
 /// It implements [`Iterator`] that executes blocks in batches according to the provided thresholds
 /// and yields [`Chain`]. In other words, this iterator can yield multiple items for the given range
 /// depending on the configured thresholds.
+///
+/// If a prefetch depth is configured, the blocks of the range are fetched by a single worker that
+/// stays up to that many blocks ahead of the execution, across batches.
 #[derive(Debug)]
 pub struct BackfillJob<E, P> {
     pub(crate) evm_config: E,
@@ -35,12 +51,21 @@ pub struct BackfillJob<E, P> {
     pub(crate) thresholds: ExecutionStageThresholds,
     pub(crate) range: RangeInclusive<BlockNumber>,
     pub(crate) stream_parallelism: usize,
+    pub(crate) prefetch_depth: usize,
+    /// The [`BlockPrefetcher`] of the job's blocks, started with the first batch. It's type-erased
+    /// because the job doesn't bound the provider.
+    pub(crate) prefetcher: Option<Box<dyn Any + Send + Sync>>,
+    pub(crate) progress: BackfillProgressReporter,
 }
 
 impl<E, P> Iterator for BackfillJob<E, P>
 where
     E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + 'static,
-    P: HeaderProvider + BlockReader<Transaction: SignedTransaction> + StateProviderFactory,
+    P: HeaderProvider
+        + BlockReader<Transaction: SignedTransaction>
+        + StateProviderFactory
+        + Clone
+        + 'static,
 {
     type Item = BackfillJobResult<Chain<E::Primitives>>;
 
@@ -53,6 +78,23 @@ where
     }
 }
 
+impl<E, P> BackfillJob<E, P> {
+    /// Returns a handle to observe the progress of the job.
+    ///
+    /// This includes the current block, the throughput and the estimated time until the range of
+    /// the job is executed. The handle keeps observing the job after it's converted, e.g. into a
+    /// stream.
+    pub fn progress(&self) -> BackfillProgress {
+        self.progress.progress()
+    }
+
+    /// Also records the progress of the job as metrics, labeled with the given `job` name.
+    pub fn with_progress_metrics(mut self, name: impl Into<String>) -> Self {
+        self.progress = self.progress.with_metrics(name.into());
+        self
+    }
+}
+
 impl<E, P> BackfillJob<E, P>
 where
     E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + 'static,
@@ -68,40 +110,121 @@ where
         self.into()
     }
 
+    /// Converts the backfill job into a job that executes sub-ranges of the range in parallel,
+    /// see [`ParallelBackfillJob`].
+    pub fn into_parallel(self) -> ParallelBackfillJob<E, P> {
+        self.into()
+    }
+
+    /// Converts the backfill job into a job that executes every block with an inspector created
+    /// by the given factory, see [`InspectorBackfillJob`].
+    pub const fn with_inspector<F>(self, inspector_factory: F) -> InspectorBackfillJob<E, P, F> {
+        InspectorBackfillJob { job: self, inspector_factory }
+    }
+}
+
+impl<E, P> BackfillJob<E, P>
+where
+    E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + 'static,
+    P: BlockReader<Transaction: SignedTransaction>
+        + HeaderProvider
+        + StateProviderFactory
+        + Clone
+        + 'static,
+{
     fn execute_range(&mut self) -> BackfillJobResult<Chain<E::Primitives>> {
+        let (chain, _) = self.execute_range_with(|evm_config, db, block| {
+            let result = evm_config
+                .executor_for_block(db, block)
+                .execute_block(block.transactions_recovered())?;
+            Ok((result, ()))
+        })?;
+        Ok(chain)
+    }
+
+    /// Executes the next batch of blocks of the range, executing every block with the given
+    /// closure.
+    ///
+    /// Returns the executed chain, and the outputs of the closure in block order.
+    pub(super) fn execute_range_with<T>(
+        &mut self,
+        mut execute_block: impl FnMut(
+            &E,
+            &mut BackfillDb,
+            &RecoveredBlock<P::Block>,
+        ) -> BackfillJobResult<(BlockResult<E>, T)>,
+    ) -> BackfillJobResult<(Chain<E::Primitives>, Vec<T>)> {
         debug!(
             target: "exex::backfill",
             range = ?self.range,
+            prefetch_depth = self.prefetch_depth,
             "Executing block range"
         );
+        self.progress.on_started();
+
+        let (chain, outputs) = if self.prefetch_depth == 0 {
+            let blocks = self.range.clone().map(|number| fetch_block(&self.provider, number));
+            self.execute_blocks(blocks, &mut execute_block)?
+        } else {
+            // The prefetcher is kept across batches, blocks that were fetched beyond the end of
+            // this batch are executed in the next one
+            let mut prefetcher = match self.prefetcher.take() {
+                Some(prefetcher) => prefetcher,
+                None => Box::new(BlockPrefetcher::spawn(
+                    self.provider.clone(),
+                    self.range.clone(),
+                    self.prefetch_depth,
+                )?),
+            };
+            let blocks = prefetcher
+                .downcast_mut::<BlockPrefetcher<P::Block>>()
+                .expect("prefetcher fetches the blocks of the provider")
+                .blocks
+                .get_mut();
+            let result = self.execute_blocks(blocks.iter(), &mut execute_block);
+            self.prefetcher = Some(prefetcher);
+            result?
+        };
+
+        self.range = chain.tip().number() + 1..=*self.range.end();
+        Ok((chain, outputs))
+    }
 
-        let mut executor = self.evm_config.batch_executor(StateProviderDatabase::new(
-            self.provider
-                .history_by_block_number(self.range.start().saturating_sub(1))
-                .map_err(BlockExecutionError::other)?,
-        ));
+    /// Executes the given blocks of the range, until the batch thresholds are reached.
+    fn execute_blocks<T>(
+        &self,
+        mut blocks: impl Iterator<Item = BackfillJobResult<RecoveredBlock<P::Block>>>,
+        execute_block: &mut impl FnMut(
+            &E,
+            &mut BackfillDb,
+            &RecoveredBlock<P::Block>,
+        ) -> BackfillJobResult<(BlockResult<E>, T)>,
+    ) -> BackfillJobResult<(Chain<E::Primitives>, Vec<T>)> {
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(
+                self.provider
+                    .history_by_block_number(self.range.start().saturating_sub(1))
+                    .map_err(BlockExecutionError::other)?,
+            ))
+            .with_bundle_update()
+            .without_state_clear()
+            .build();
 
         let mut fetch_block_duration = Duration::default();
         let mut execution_duration = Duration::default();
         let mut cumulative_gas = 0;
         let batch_start = Instant::now();
 
-        let mut blocks = Vec::new();
+        let mut executed_blocks = Vec::new();
         let mut results = Vec::new();
-        for block_number in self.range.clone() {
-            // Fetch the block
+        let mut outputs = Vec::new();
+        loop {
+            // Fetch the block, or wait for the prefetched block
             let fetch_block_start = Instant::now();
-
-            // we need the block's transactions along with their hashes
-            let block = self
-                .provider
-                .sealed_block_with_senders(block_number.into(), TransactionVariant::WithHash)
-                .map_err(BlockExecutionError::other)?
-                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))
-                .map_err(BlockExecutionError::other)?;
-
+            let Some(block) = blocks.next().transpose()? else { break };
             fetch_block_duration += fetch_block_start.elapsed();
 
+            let block_number = block.number();
             cumulative_gas += block.gas_used();
 
             // Configure the executor to use the current state.
@@ -109,23 +232,20 @@ where
 
             // Execute the block
             let execute_start = Instant::now();
-
-            // Unseal the block for execution
-            let (block, senders) = block.split_sealed();
-            let (header, body) = block.split_sealed_header_body();
-            let block = P::Block::new_sealed(header, body).with_senders(senders);
-
-            results.push(executor.execute_one(&block)?);
+            let (result, output) = execute_block(&self.evm_config, &mut db, &block)?;
+            db.merge_transitions(BundleRetention::Reverts);
+            results.push(result);
+            outputs.push(output);
             execution_duration += execute_start.elapsed();
 
-            // TODO(alexey): report gas metrics using `block.header.gas_used`
+            self.progress.on_block_executed(block_number, block.gas_used());
 
             // Seal the block back and save it
-            blocks.push(block);
+            executed_blocks.push(block);
             // Check if we should commit now
             if self.thresholds.is_end_of_batch(
                 block_number - *self.range.start() + 1,
-                executor.size_hint() as u64,
+                db.bundle_state.size_hint() as u64,
                 cumulative_gas,
                 batch_start.elapsed(),
             ) {
@@ -133,6 +253,7 @@ where
             }
         }
 
+        let blocks = executed_blocks;
         let first_block_number = blocks.first().expect("blocks should not be empty").number();
         let last_block_number = blocks.last().expect("blocks should not be empty").number();
         debug!(
@@ -143,18 +264,77 @@ where
             throughput = format_gas_throughput(cumulative_gas, execution_duration),
             "Finished executing block range"
         );
-        self.range = last_block_number + 1..=*self.range.end();
 
-        let outcome = ExecutionOutcome::from_blocks(
-            first_block_number,
-            executor.into_state().take_bundle(),
-            results,
-        );
+        let outcome = ExecutionOutcome::from_blocks(first_block_number, db.take_bundle(), results);
         let chain = Chain::new(blocks, outcome, None);
-        Ok(chain)
+        Ok((chain, outputs))
     }
 }
 
+/// Fetches the blocks of a backfill job ahead of the execution.
+///
+/// The blocks of the whole range are fetched by a single worker into a channel bounded by the
+/// prefetch depth. The worker runs on the blocking pool of the current runtime, or on a dedicated
+/// thread if there is none, and stops once the prefetcher is dropped.
+#[derive(Debug)]
+struct BlockPrefetcher<B: Block> {
+    /// The fetched blocks, only accessed mutably. The mutex makes the job [`Sync`], as required
+    /// by stream jobs.
+    blocks: Mutex<mpsc::Receiver<BackfillJobResult<RecoveredBlock<B>>>>,
+}
+
+impl<B: Block> BlockPrefetcher<B> {
+    /// Spawns the worker fetching the given range.
+    fn spawn<P>(
+        provider: P,
+        range: RangeInclusive<BlockNumber>,
+        depth: usize,
+    ) -> BackfillJobResult<Self>
+    where
+        P: BlockReader<Block = B> + 'static,
+    {
+        let (tx, blocks) = mpsc::sync_channel(depth);
+        let fetch = move || {
+            for number in range {
+                let block = fetch_block(&provider, number);
+                let failed = block.is_err();
+                if tx.send(block).is_err() || failed {
+                    break
+                }
+            }
+        };
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => drop(handle.spawn_blocking(fetch)),
+            Err(_) => {
+                std::thread::Builder::new()
+                    .name("backfill-prefetch".to_string())
+                    .spawn(fetch)
+                    .map_err(BlockExecutionError::other)?;
+            }
+        }
+        Ok(Self { blocks: Mutex::new(blocks) })
+    }
+}
+
+/// Fetches the block with the given number for execution.
+fn fetch_block<P: BlockReader>(
+    provider: &P,
+    block_number: BlockNumber,
+) -> BackfillJobResult<RecoveredBlock<P::Block>> {
+    // we need the block's transactions along with their hashes
+    let block = provider
+        .sealed_block_with_senders(block_number.into(), TransactionVariant::WithHash)
+        .map_err(BlockExecutionError::other)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))
+        .map_err(BlockExecutionError::other)?;
+
+    // Unseal the block for execution
+    let (block, senders) = block.split_sealed();
+    let (header, body) = block.split_sealed_header_body();
+    Ok(P::Block::new_sealed(header, body).with_senders(senders))
+}
+
 /// Single block Backfill job started for a specific range.
 ///
 /// It implements [`Iterator`] which executes a block each time the
@@ -165,6 +345,7 @@ pub struct SingleBlockBackfillJob<E, P> {
     pub(crate) provider: P,
     pub(crate) range: RangeInclusive<BlockNumber>,
     pub(crate) stream_parallelism: usize,
+    pub(crate) progress: BackfillProgressReporter,
 }
 
 impl<E, P> Iterator for SingleBlockBackfillJob<E, P>
@@ -178,7 +359,9 @@ where
     )>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.range.next().map(|block_number| self.execute_block(block_number))
+        let block_number = self.range.next()?;
+        self.progress.on_started();
+        Some(self.execute_block(block_number))
     }
 }
 
@@ -193,7 +376,10 @@ where
     ) -> StreamBackfillJob<
         E,
         P,
-        (RecoveredBlock<reth_ethereum_primitives::Block>, BlockExecutionOutput<Receipt>),
+        (
+            RecoveredBlock<P::Block>,
+            BlockExecutionOutput<<E::Primitives as NodePrimitives>::Receipt>,
+        ),
     > {
         self.into()
     }
@@ -224,6 +410,7 @@ where
         trace!(target: "exex::backfill", number = block_number, txs = block_with_senders.body().transaction_count(), "Executing block");
 
         let block_execution_output = executor.execute(&block_with_senders)?;
+        self.progress.on_block_executed(block_number, block_with_senders.gas_used());
 
         Ok((block_with_senders, block_execution_output))
     }
@@ -236,6 +423,7 @@ impl<E, P> From<BackfillJob<E, P>> for SingleBlockBackfillJob<E, P> {
             provider: job.provider,
             range: job.range,
             stream_parallelism: job.stream_parallelism,
+            progress: job.progress,
         }
     }
 }
@@ -362,6 +550,7 @@ mod tests {
             ExecutionStageThresholds { max_blocks: Some(1), ..Default::default() },
         );
         let job = factory.backfill(1..=2);
+        let progress = job.progress();
         let chains = job.collect::<Result<Vec<_>, _>>()?;
 
         // Assert two chains, each with one block
@@ -377,6 +566,56 @@ mod tests {
         assert_eq!(chain2.blocks(), &[(2, block2)].into());
         assert_eq!(chain2.execution_outcome(), &to_execution_outcome(2, &output2));
 
+        // Assert that the progress covers both batches
+        let status = progress.status();
+        assert_eq!(status.range, 1..=2);
+        assert_eq!(status.current_block, Some(2));
+        assert_eq!(status.blocks_executed, 2);
+        assert!(status.is_finished());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_backfill_with_prefetch() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthEvmConfig::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        let blocks_and_execution_outputs =
+            blocks_and_execution_outputs(provider_factory, chain_spec, key_pair)?;
+        let (block1, output1) = blocks_and_execution_outputs[0].clone();
+        let (block2, output2) = blocks_and_execution_outputs[1].clone();
+
+        // Backfill with max_blocks=1 and a prefetch depth larger than the batch, expect the block
+        // prefetched beyond the first batch to be executed in the second chain
+        let factory = BackfillJobFactory::new(executor, blockchain_db)
+            .with_thresholds(ExecutionStageThresholds { max_blocks: Some(1), ..Default::default() })
+            .with_prefetch_depth(2);
+        let job = factory.backfill(1..=2);
+        let chains = job.collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(chains.len(), 2);
+
+        let mut chain1 = chains[0].clone();
+        chain1.execution_outcome_mut().bundle.reverts.sort();
+        assert_eq!(chain1.blocks(), &[(1, block1)].into());
+        assert_eq!(chain1.execution_outcome(), &to_execution_outcome(1, &output1));
+
+        let mut chain2 = chains[1].clone();
+        chain2.execution_outcome_mut().bundle.reverts.sort();
+        assert_eq!(chain2.blocks(), &[(2, block2)].into());
+        assert_eq!(chain2.execution_outcome(), &to_execution_outcome(2, &output2));
+
         Ok(())
     }
 }
@@ -1,9 +1,22 @@
+mod availability;
+mod checkpoint;
 mod factory;
+mod inspector;
 mod job;
+mod parallel;
+mod progress;
 mod stream;
 #[cfg(test)]
 mod test_utils;
 
+pub use availability::{BackfillData, BackfillRangeError, MissingBackfillData};
+pub use checkpoint::{
+    BackfillCheckpoint, BackfillCheckpointError, BackfillCheckpointResult, BackfillCheckpoints,
+    CheckpointedBackfillJob, CheckpointedStreamBackfillJob,
+};
 pub use factory::BackfillJobFactory;
+pub use inspector::{InspectorBackfillDb, InspectorBackfillJob};
 pub use job::{BackfillJob, SingleBlockBackfillJob};
+pub use parallel::{ParallelBackfillJob, DEFAULT_MEMORY_BUDGET, DEFAULT_SUB_RANGE_SIZE};
+pub use progress::{BackfillProgress, BackfillStatus};
 pub use stream::StreamBackfillJob;
@@ -0,0 +1,164 @@
+use super::{job::BackfillJobResult, stream::DEFAULT_PARALLELISM};
+use crate::{BackfillJob, StreamBackfillJob};
+use futures::StreamExt;
+use reth_evm::ConfigureEvm;
+use reth_node_api::NodePrimitives;
+use reth_provider::{BlockReader, Chain, StateProviderFactory};
+use std::num::NonZeroUsize;
+use tokio::runtime::{Handle, Runtime};
+
+/// The default number of blocks in the sub-ranges of [`ParallelBackfillJob`].
+pub const DEFAULT_SUB_RANGE_SIZE: u64 = 1_000;
+/// The default memory budget of [`ParallelBackfillJob`] in bytes.
+pub const DEFAULT_MEMORY_BUDGET: usize = 512 * 1024 * 1024;
+
+/// Backfill job that executes a range on a pool of worker threads.
+///
+/// The range is split into disjoint sub-ranges of [`DEFAULT_SUB_RANGE_SIZE`] blocks that are
+/// executed concurrently by a [`StreamBackfillJob`]. Every sub-range is executed the same way as
+/// [`BackfillJob`]: in batches according to the thresholds, against the historical state at the
+/// start of each batch. The [`Chain`]s are yielded in order.
+///
+/// Every sub-range holds at most one executed chain until it's yielded. No new sub-ranges are
+/// started while the estimated size of the buffered chains, including their state changes, exceeds
+/// the memory budget.
+///
+/// The iterator blocks until the next chain is executed, so it should be consumed from a blocking
+/// context. The sub-ranges are executed on the blocking threads of the current Tokio runtime, or
+/// of a runtime owned by the job if there is none.
+#[derive(Debug)]
+pub struct ParallelBackfillJob<E: ConfigureEvm, P> {
+    stream: StreamBackfillJob<E, P, Chain<E::Primitives>>,
+    /// The runtime that executes the sub-ranges if the job is consumed outside of a runtime.
+    runtime: Option<Runtime>,
+    /// Whether a sub-range failed, which stops the job.
+    failed: bool,
+}
+
+impl<E: ConfigureEvm, P> ParallelBackfillJob<E, P> {
+    /// Configures the number of sub-ranges that are executed or buffered at the same time.
+    ///
+    /// Defaults to the available parallelism.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.stream = self.stream.with_parallelism(workers.max(1));
+        self
+    }
+
+    /// Configures the number of blocks in each sub-range.
+    pub fn with_sub_range_size(mut self, sub_range_size: u64) -> Self {
+        self.stream = self.stream.with_batch_size(sub_range_size.max(1) as usize);
+        self
+    }
+
+    /// Configures the memory budget in bytes for the executed chains that are not yielded yet.
+    pub fn with_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.stream = self.stream.with_memory_budget(memory_budget);
+        self
+    }
+}
+
+impl<E, P> Iterator for ParallelBackfillJob<E, P>
+where
+    E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + 'static,
+    P: BlockReader + StateProviderFactory + Clone + Unpin + 'static,
+{
+    type Item = BackfillJobResult<Chain<E::Primitives>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None
+        }
+
+        let result = match Handle::try_current() {
+            Ok(handle) => handle.block_on(self.stream.next()),
+            Err(_) => self
+                .runtime
+                .get_or_insert_with(|| {
+                    tokio::runtime::Builder::new_current_thread()
+                        .build()
+                        .expect("failed to create backfill runtime")
+                })
+                .block_on(self.stream.next()),
+        };
+
+        // Stop the remaining sub-ranges, the chains after the failed block can't be yielded in
+        // order anyway
+        self.failed = result.as_ref().is_some_and(Result::is_err);
+        result
+    }
+}
+
+impl<E: ConfigureEvm, P> From<BackfillJob<E, P>> for ParallelBackfillJob<E, P> {
+    fn from(job: BackfillJob<E, P>) -> Self {
+        let workers =
+            std::thread::available_parallelism().map_or(DEFAULT_PARALLELISM, NonZeroUsize::get);
+        let stream = StreamBackfillJob::from(job)
+            .with_parallelism(workers)
+            .with_batch_size(DEFAULT_SUB_RANGE_SIZE as usize)
+            .with_memory_budget(DEFAULT_MEMORY_BUDGET);
+        Self { stream, runtime: None, failed: false }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backfill::test_utils::{blocks_and_execution_outputs, chain_spec, to_execution_outcome},
+        BackfillJobFactory,
+    };
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::crypto::secp256k1::public_key_to_address;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+    };
+    use reth_stages_api::ExecutionStageThresholds;
+    use reth_testing_utils::generators;
+
+    #[test]
+    fn test_parallel_backfill() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthEvmConfig::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        let blocks_and_execution_outputs =
+            blocks_and_execution_outputs(provider_factory, chain_spec, key_pair)?;
+
+        let factory = BackfillJobFactory::new(executor, blockchain_db).with_thresholds(
+            ExecutionStageThresholds { max_blocks: Some(1), ..Default::default() },
+        );
+
+        // Execute every block in its own sub-range, with and without a memory budget for the
+        // buffered chains
+        for memory_budget in [usize::MAX, 0] {
+            let job = factory
+                .backfill(1..=2)
+                .into_parallel()
+                .with_workers(2)
+                .with_sub_range_size(1)
+                .with_memory_budget(memory_budget);
+            let chains = job.collect::<Result<Vec<_>, _>>()?;
+
+            // Assert that the chains are yielded in order
+            assert_eq!(chains.len(), 2);
+            for (mut chain, (block, output)) in
+                chains.into_iter().zip(&blocks_and_execution_outputs)
+            {
+                chain.execution_outcome_mut().bundle.reverts.sort();
+                assert_eq!(chain.blocks(), &[(block.number, block.clone())].into());
+                assert_eq!(chain.execution_outcome(), &to_execution_outcome(block.number, output));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,306 @@
+use alloy_primitives::BlockNumber;
+use metrics::{Counter, Gauge};
+use reth_metrics::Metrics;
+use std::{
+    collections::BTreeSet,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::watch;
+
+/// Progress of a backfill job.
+///
+/// Blocks can be executed out of order, e.g. by the batches of a stream or a parallel job, so the
+/// progress tracks the executed blocks and [`Self::current_block`] only advances over the blocks
+/// that are executed without gaps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackfillStatus {
+    /// The range of blocks of the job.
+    pub range: RangeInclusive<BlockNumber>,
+    /// The highest block up to which all blocks of the range are executed, `None` if the first
+    /// block of the range isn't executed yet.
+    pub current_block: Option<BlockNumber>,
+    /// Number of executed blocks of the range.
+    pub blocks_executed: u64,
+    /// Gas used by the executed blocks of the range.
+    pub gas_used: u64,
+    /// Executed blocks per second since the job was started.
+    pub blocks_per_second: f64,
+    /// Gas used per second since the job was started.
+    pub gas_per_second: f64,
+    /// Estimated time until all blocks of the range are executed.
+    pub eta: Option<Duration>,
+    /// The time at which the job was started.
+    started_at: Option<Instant>,
+    /// Executed blocks after a gap above [`Self::current_block`].
+    executed_ahead: BTreeSet<BlockNumber>,
+}
+
+impl BackfillStatus {
+    /// Creates the status of a job that wasn't started yet.
+    const fn new(range: RangeInclusive<BlockNumber>) -> Self {
+        Self {
+            range,
+            current_block: None,
+            blocks_executed: 0,
+            gas_used: 0,
+            blocks_per_second: 0.0,
+            gas_per_second: 0.0,
+            eta: None,
+            started_at: None,
+            executed_ahead: BTreeSet::new(),
+        }
+    }
+
+    /// Returns `true` if all blocks of the range are executed.
+    pub fn is_finished(&self) -> bool {
+        self.remaining_blocks() == 0
+    }
+
+    /// Returns the number of blocks of the range that are not executed yet.
+    pub fn remaining_blocks(&self) -> u64 {
+        let total =
+            if self.range.is_empty() { 0 } else { self.range.end() - self.range.start() + 1 };
+        total.saturating_sub(self.blocks_executed)
+    }
+
+    /// Records the executed block and returns `false` if it was already recorded.
+    fn on_block_executed(&mut self, block_number: BlockNumber) -> bool {
+        if !self.range.contains(&block_number) ||
+            self.current_block.is_some_and(|current| block_number <= current) ||
+            !self.executed_ahead.insert(block_number)
+        {
+            return false
+        }
+
+        // Advance over the blocks that are executed without gaps
+        let mut next = self.current_block.map_or(*self.range.start(), |current| current + 1);
+        while self.executed_ahead.remove(&next) {
+            self.current_block = Some(next);
+            next += 1;
+        }
+        self.blocks_executed += 1;
+        true
+    }
+}
+
+/// Handle to observe the [`BackfillStatus`] of a backfill job.
+///
+/// Returned by [`BackfillJob::progress`](crate::BackfillJob::progress).
+#[derive(Debug, Clone)]
+pub struct BackfillProgress {
+    receiver: watch::Receiver<BackfillStatus>,
+}
+
+impl BackfillProgress {
+    /// Returns the current status.
+    pub fn status(&self) -> BackfillStatus {
+        self.receiver.borrow().clone()
+    }
+
+    /// Waits for the next update of the status and returns it.
+    ///
+    /// Returns `None` if the job was dropped.
+    pub async fn changed(&mut self) -> Option<BackfillStatus> {
+        self.receiver.changed().await.ok()?;
+        Some(self.receiver.borrow_and_update().clone())
+    }
+
+    /// Returns a new receiver of the status updates.
+    pub fn subscribe(&self) -> watch::Receiver<BackfillStatus> {
+        self.receiver.clone()
+    }
+}
+
+/// Reports the progress of a backfill job to its [`BackfillProgress`] handles.
+///
+/// Each job has its own reporter, that is shared with the batches and sub-ranges the job is split
+/// into.
+#[derive(Debug, Clone)]
+pub(crate) struct BackfillProgressReporter {
+    sender: Arc<watch::Sender<BackfillStatus>>,
+    metrics: Option<Arc<BackfillMetrics>>,
+}
+
+impl BackfillProgressReporter {
+    /// Creates a new reporter for a job that executes the given range.
+    pub(crate) fn new(range: RangeInclusive<BlockNumber>) -> Self {
+        Self { sender: Arc::new(watch::Sender::new(BackfillStatus::new(range))), metrics: None }
+    }
+
+    /// Also records the progress as metrics with the given `job` label.
+    pub(crate) fn with_metrics(mut self, label: String) -> Self {
+        self.metrics = Some(Arc::new(BackfillMetrics::new_with_labels(&[("job", label)])));
+        self
+    }
+
+    /// Returns a new handle to observe the progress.
+    pub(crate) fn progress(&self) -> BackfillProgress {
+        BackfillProgress { receiver: self.sender.subscribe() }
+    }
+
+    /// Records that the job started executing, if it wasn't started yet.
+    pub(crate) fn on_started(&self) {
+        self.sender.send_if_modified(|status| {
+            if status.started_at.is_some() {
+                return false
+            }
+            status.started_at = Some(Instant::now());
+            true
+        });
+    }
+
+    /// Records that a block of the range was executed.
+    pub(crate) fn on_block_executed(&self, block_number: BlockNumber, gas_used: u64) {
+        self.sender.send_if_modified(|status| {
+            if !status.on_block_executed(block_number) {
+                return false
+            }
+            status.gas_used += gas_used;
+
+            let elapsed =
+                status.started_at.map_or(0.0, |started_at| started_at.elapsed().as_secs_f64());
+            if elapsed > 0.0 {
+                status.blocks_per_second = status.blocks_executed as f64 / elapsed;
+                status.gas_per_second = status.gas_used as f64 / elapsed;
+            }
+            status.eta = estimate_eta(status);
+
+            if let Some(metrics) = &self.metrics {
+                if let Some(current_block) = status.current_block {
+                    metrics.current_block.set(current_block as f64);
+                }
+                metrics.blocks_executed_total.increment(1);
+                metrics.gas_used_total.increment(gas_used);
+                metrics.remaining_blocks.set(status.remaining_blocks() as f64);
+                metrics.blocks_per_second.set(status.blocks_per_second);
+                metrics.gas_per_second.set(status.gas_per_second);
+                metrics.eta_seconds.set(status.eta.map_or(0.0, |eta| eta.as_secs_f64()));
+            }
+            true
+        });
+    }
+}
+
+/// Estimates the time until the remaining blocks are executed at the current rate.
+fn estimate_eta(status: &BackfillStatus) -> Option<Duration> {
+    if status.is_finished() {
+        return Some(Duration::ZERO)
+    }
+    (status.blocks_per_second > 0.0).then(|| {
+        Duration::from_secs_f64(status.remaining_blocks() as f64 / status.blocks_per_second)
+    })
+}
+
+/// Metrics for a backfill job, labeled with the `job` it belongs to.
+#[derive(Metrics)]
+#[metrics(scope = "exex.backfill")]
+pub(crate) struct BackfillMetrics {
+    /// The highest block up to which all blocks are executed
+    current_block: Gauge,
+    /// Total number of executed blocks
+    blocks_executed_total: Counter,
+    /// Total gas used by executed blocks
+    gas_used_total: Counter,
+    /// Number of blocks that are not executed yet
+    remaining_blocks: Gauge,
+    /// Executed blocks per second since the job was started
+    blocks_per_second: Gauge,
+    /// Gas used per second since the job was started
+    gas_per_second: Gauge,
+    /// Estimated seconds until all blocks are executed
+    eta_seconds: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_range() {
+        let reporter = BackfillProgressReporter::new(1..=10);
+        let progress = reporter.progress();
+
+        reporter.on_started();
+        reporter.on_block_executed(1, 100);
+        reporter.on_block_executed(2, 50);
+
+        let status = progress.status();
+        assert_eq!(status.range, 1..=10);
+        assert_eq!(status.current_block, Some(2));
+        assert_eq!(status.blocks_executed, 2);
+        assert_eq!(status.gas_used, 150);
+        assert_eq!(status.remaining_blocks(), 8);
+        assert!(!status.is_finished());
+
+        // blocks outside of the range and executed again are ignored
+        reporter.on_block_executed(2, 50);
+        reporter.on_block_executed(11, 50);
+        let status = progress.status();
+        assert_eq!(status.blocks_executed, 2);
+        assert_eq!(status.gas_used, 150);
+
+        for block in 3..=10 {
+            reporter.on_block_executed(block, 0);
+        }
+        let status = progress.status();
+        assert_eq!(status.current_block, Some(10));
+        assert!(status.is_finished());
+        assert_eq!(status.eta, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn tracks_blocks_out_of_order() {
+        let reporter = BackfillProgressReporter::new(1..=4);
+        let progress = reporter.progress();
+
+        // a later sub-range finishes first, the current block doesn't advance over the gap
+        reporter.on_started();
+        reporter.on_block_executed(3, 0);
+        reporter.on_block_executed(4, 0);
+        let status = progress.status();
+        assert_eq!(status.current_block, None);
+        assert_eq!(status.blocks_executed, 2);
+        assert_eq!(status.remaining_blocks(), 2);
+        assert!(!status.is_finished());
+        assert_ne!(status.eta, Some(Duration::ZERO));
+
+        reporter.on_block_executed(1, 0);
+        assert_eq!(progress.status().current_block, Some(1));
+
+        reporter.on_block_executed(2, 0);
+        let status = progress.status();
+        assert_eq!(status.current_block, Some(4));
+        assert!(status.is_finished());
+    }
+
+    #[test]
+    fn reports_per_job() {
+        let first = BackfillProgressReporter::new(1..=2);
+        let second = BackfillProgressReporter::new(100..=101);
+
+        first.on_block_executed(1, 0);
+        second.on_block_executed(100, 0);
+        second.on_block_executed(101, 0);
+
+        assert_eq!(first.progress().status().current_block, Some(1));
+        assert!(!first.progress().status().is_finished());
+        assert!(second.progress().status().is_finished());
+    }
+
+    #[tokio::test]
+    async fn notifies_changes() {
+        let reporter = BackfillProgressReporter::new(1..=2);
+        let mut progress = reporter.progress();
+
+        reporter.on_started();
+        assert_eq!(progress.changed().await.unwrap().range, 1..=2);
+
+        reporter.on_block_executed(1, 21_000);
+        assert_eq!(progress.changed().await.unwrap().current_block, Some(1));
+
+        drop(reporter);
+        assert!(progress.changed().await.is_none());
+    }
+}
@@ -1,17 +1,17 @@
-use super::job::BackfillJobResult;
+use super::{job::BackfillJobResult, progress::BackfillProgressReporter};
 use crate::{BackfillJob, SingleBlockBackfillJob};
 use alloy_primitives::BlockNumber;
 use futures::{
     stream::{FuturesOrdered, Stream},
     StreamExt,
 };
-use reth_ethereum_primitives::EthPrimitives;
+use reth_chain_state::bundle_memory_usage;
 use reth_evm::{
     execute::{BlockExecutionError, BlockExecutionOutput},
     ConfigureEvm,
 };
 use reth_node_api::NodePrimitives;
-use reth_primitives_traits::RecoveredBlock;
+use reth_primitives_traits::{InMemorySize, RecoveredBlock};
 use reth_provider::{BlockReader, Chain, StateProviderFactory};
 use reth_prune_types::PruneModes;
 use reth_stages_api::ExecutionStageThresholds;
@@ -19,6 +19,10 @@ use reth_tracing::tracing::debug;
 use std::{
     ops::RangeInclusive,
     pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
 };
 use tokio::task::JoinHandle;
@@ -41,16 +45,20 @@ struct BackfillTaskOutput<T> {
 /// Ordered queue of [`JoinHandle`]s that yield [`BackfillTaskOutput`]s.
 type BackfillTasks<T> = FuturesOrdered<JoinHandle<BackfillTaskOutput<T>>>;
 
-type SingleBlockStreamItem<N = EthPrimitives> = (
+type SingleBlockStreamItem<N> = (
     RecoveredBlock<<N as NodePrimitives>::Block>,
     BlockExecutionOutput<<N as NodePrimitives>::Receipt>,
 );
-type BatchBlockStreamItem<N = EthPrimitives> = Chain<N>;
+type BatchBlockStreamItem<N> = Chain<N>;
 
 /// Stream for processing backfill jobs asynchronously.
 ///
 /// This struct manages the execution of [`SingleBlockBackfillJob`] tasks, allowing blocks to be
 /// processed asynchronously but in order within a specified range.
+///
+/// Every task holds at most one executed item until it's yielded, so at most `parallelism` items
+/// are buffered. Streams of [`Chain`]s additionally don't start new batches while the estimated
+/// size of the buffered chains exceeds the memory budget.
 #[derive(Debug)]
 pub struct StreamBackfillJob<E, P, T> {
     evm_config: E,
@@ -60,7 +68,12 @@ pub struct StreamBackfillJob<E, P, T> {
     tasks: BackfillTasks<T>,
     parallelism: usize,
     batch_size: usize,
+    prefetch_depth: usize,
     thresholds: ExecutionStageThresholds,
+    progress: BackfillProgressReporter,
+    memory_budget: usize,
+    /// Estimated size of the executed chains that are not yielded yet.
+    buffered_size: Arc<AtomicUsize>,
 }
 
 impl<E, P, T> StreamBackfillJob<E, P, T>
@@ -79,6 +92,20 @@ where
         self
     }
 
+    /// Configures the number of blocks that each batch task fetches ahead of the execution.
+    pub const fn with_prefetch_depth(mut self, prefetch_depth: usize) -> Self {
+        self.prefetch_depth = prefetch_depth;
+        self
+    }
+
+    /// Configures the memory budget in bytes for the executed chains that are not yielded yet.
+    ///
+    /// Only applies to streams of [`Chain`]s, and is unlimited by default.
+    pub const fn with_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.memory_budget = memory_budget;
+        self
+    }
+
     /// Spawns a new task calling the [`BackfillTaskIterator::next`] method and pushes it to the end
     /// of the [`BackfillTasks`] queue.
     fn push_back(&mut self, mut job: BackfillTaskIterator<T>) {
@@ -125,6 +152,7 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
+        this.progress.on_started();
 
         // Spawn new tasks only if we are below the parallelism configured.
         while this.tasks.len() < this.parallelism {
@@ -141,6 +169,7 @@ where
                 provider: this.provider.clone(),
                 range: block_number..=block_number,
                 stream_parallelism: this.parallelism,
+                progress: this.progress.clone(),
             }) as BackfillTaskIterator<_>;
             this.push_back(job);
         }
@@ -158,10 +187,15 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
+        this.progress.on_started();
 
         loop {
-            // Spawn new tasks only if we are below the parallelism configured.
-            while this.tasks.len() < this.parallelism {
+            // Spawn new tasks only if we are below the parallelism configured, and the buffered
+            // chains are within the memory budget.
+            while this.tasks.len() < this.parallelism &&
+                (this.tasks.is_empty() ||
+                    this.buffered_size.load(Ordering::Relaxed) < this.memory_budget)
+            {
                 // Take the next `batch_size` blocks from the range and calculate the range bounds
                 let mut range = this.range.by_ref().take(this.batch_size);
                 let start = range.next();
@@ -175,21 +209,33 @@ where
 
                 // Spawn a new task for that range
                 debug!(target: "exex::backfill", tasks = %this.tasks.len(), ?range, "Spawning new block batch backfill task");
-                let job = Box::new(BackfillJob {
+                let buffered_size = this.buffered_size.clone();
+                let job = BackfillJob {
                     evm_config: this.evm_config.clone(),
                     provider: this.provider.clone(),
                     prune_modes: this.prune_modes.clone(),
                     thresholds: this.thresholds.clone(),
                     range,
                     stream_parallelism: this.parallelism,
-                }) as BackfillTaskIterator<_>;
-                this.push_back(job);
+                    prefetch_depth: this.prefetch_depth,
+                    prefetcher: None,
+                    progress: this.progress.clone(),
+                }
+                .inspect(move |result| {
+                    if let Ok(chain) = result {
+                        buffered_size.fetch_add(estimated_size(chain), Ordering::Relaxed);
+                    }
+                });
+                this.push_back(Box::new(job));
             }
 
             let res = ready!(this.poll_next_task(cx));
 
-            if res.is_some() {
-                return Poll::Ready(res);
+            if let Some(res) = res {
+                if let Ok(chain) = &res {
+                    this.buffered_size.fetch_sub(estimated_size(chain), Ordering::Relaxed);
+                }
+                return Poll::Ready(Some(res));
             }
 
             if this.range.is_empty() {
@@ -200,7 +246,11 @@ where
     }
 }
 
-impl<E, P> From<SingleBlockBackfillJob<E, P>> for StreamBackfillJob<E, P, SingleBlockStreamItem> {
+impl<E, P> From<SingleBlockBackfillJob<E, P>>
+    for StreamBackfillJob<E, P, SingleBlockStreamItem<E::Primitives>>
+where
+    E: ConfigureEvm,
+{
     fn from(job: SingleBlockBackfillJob<E, P>) -> Self {
         Self {
             evm_config: job.evm_config,
@@ -210,7 +260,11 @@ impl<E, P> From<SingleBlockBackfillJob<E, P>> for StreamBackfillJob<E, P, Single
             tasks: FuturesOrdered::new(),
             parallelism: job.stream_parallelism,
             batch_size: 1,
+            prefetch_depth: 0,
             thresholds: ExecutionStageThresholds { max_blocks: Some(1), ..Default::default() },
+            progress: job.progress,
+            memory_budget: usize::MAX,
+            buffered_size: Arc::default(),
         }
     }
 }
@@ -229,14 +283,25 @@ where
             tasks: FuturesOrdered::new(),
             parallelism: job.stream_parallelism,
             batch_size,
+            prefetch_depth: job.prefetch_depth,
             thresholds: ExecutionStageThresholds {
                 max_blocks: Some(batch_size as u64),
                 ..job.thresholds
             },
+            progress: job.progress,
+            memory_budget: usize::MAX,
+            buffered_size: Arc::default(),
         }
     }
 }
 
+/// Estimates the in-memory size of the blocks, receipts and state changes of the chain.
+fn estimated_size<N: NodePrimitives>(chain: &Chain<N>) -> usize {
+    chain.blocks_iter().map(InMemorySize::size).sum::<usize>() +
+        chain.execution_outcome().receipts.iter().flatten().map(InMemorySize::size).sum::<usize>() +
+        bundle_memory_usage(&chain.execution_outcome().bundle)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -1,10 +1,15 @@
-use crate::{ExExContextDyn, ExExEvent, ExExNotifications, ExExNotificationsStream};
+use crate::{
+    BackfillCheckpointResult, BackfillCheckpoints, ExExContextDyn, ExExEvent, ExExLagPolicy,
+    ExExNotification, ExExNotifications, ExExNotificationsStream,
+};
 use alloy_eips::BlockNumHash;
+use alloy_primitives::{BlockHash, BlockNumber};
+use reth_chain_state::BundleOverlayStateProvider;
 use reth_exex_types::ExExHead;
 use reth_node_api::{FullNodeComponents, NodePrimitives, NodeTypes, PrimitivesTy};
 use reth_node_core::node_config::NodeConfig;
 use reth_payload_builder::PayloadBuilderHandle;
-use reth_provider::BlockReader;
+use reth_provider::{BlockReader, Chain, ProviderResult, StateProviderBox, StateProviderFactory};
 use reth_tasks::TaskExecutor;
 use std::fmt::Debug;
 use tokio::sync::mpsc::{error::SendError, UnboundedSender};
@@ -93,6 +98,52 @@ where
         self.components.network()
     }
 
+    /// Returns a state provider for the latest state of the node, including the blocks that are
+    /// not persisted yet.
+    pub fn latest_state(&self) -> ProviderResult<StateProviderBox> {
+        self.provider().latest()
+    }
+
+    /// Returns a state provider for the state after the block with the given hash, including the
+    /// blocks that are not persisted yet.
+    pub fn state_by_block_hash(&self, hash: BlockHash) -> ProviderResult<StateProviderBox> {
+        self.provider().state_by_block_hash(hash)
+    }
+
+    /// Returns a state provider for the state after the canonical block with the given number,
+    /// including the blocks that are not persisted yet.
+    pub fn state_by_block_number(&self, number: BlockNumber) -> ProviderResult<StateProviderBox> {
+        self.provider().history_by_block_number(number)
+    }
+
+    /// Returns a state provider for the state after the tip of the chain.
+    ///
+    /// The state changes of the chain are overlaid on the state of its fork block, so the state
+    /// is consistent with the chain even if the node has moved on, e.g. the chain was reorged
+    /// out in the meantime.
+    ///
+    /// Note: the hashes of the blocks of the chain are looked up in the state of the fork block,
+    /// so they are only available if the blocks are canonical.
+    pub fn state_at_chain_tip(
+        &self,
+        chain: &Chain<PrimitivesTy<Node::Types>>,
+    ) -> ProviderResult<StateProviderBox> {
+        state_at_chain_tip(self.provider(), chain)
+    }
+
+    /// Returns a state provider for the state the notification leaves the chain in.
+    ///
+    /// This is the state after the tip of the committed chain, see
+    /// [`state_at_chain_tip`](Self::state_at_chain_tip), or if the notification only reverts a
+    /// chain, the state of the fork block of the reverted chain. For notifications without a
+    /// chain, like [`ExExNotification::Shutdown`], this is the latest state.
+    pub fn state_at_notification(
+        &self,
+        notification: &ExExNotification<PrimitivesTy<Node::Types>>,
+    ) -> ProviderResult<StateProviderBox> {
+        state_at_notification(self.provider(), notification)
+    }
+
     /// Returns the handle to the payload builder service.
     pub fn payload_builder_handle(
         &self,
@@ -107,6 +158,15 @@ where
         self.components.task_executor()
     }
 
+    /// Returns the [`BackfillCheckpoints`] stored alongside the ExEx WAL.
+    ///
+    /// Pass them to
+    /// [`BackfillJobFactory::with_checkpoints`](crate::BackfillJobFactory::with_checkpoints) to
+    /// resume long backfills after a restart.
+    pub fn backfill_checkpoints(&self) -> BackfillCheckpointResult<BackfillCheckpoints> {
+        self.notifications.backfill_checkpoints()
+    }
+
     /// Sets notifications stream to [`crate::ExExNotificationsWithoutHead`], a stream of
     /// notifications without a head.
     pub fn set_notifications_without_head(&mut self) {
@@ -119,6 +179,14 @@ where
         self.notifications.set_with_head(head);
     }
 
+    /// Sets notifications stream to [`crate::ExExNotificationsReplay`], a stream of notifications
+    /// that replays the canonical chain from the provided block, inclusive.
+    ///
+    /// Returns an error if the block before it is not canonical.
+    pub fn replay_from(&mut self, block_number: BlockNumber) -> eyre::Result<()> {
+        self.notifications.set_replay_from(block_number)
+    }
+
     /// Sends an [`ExExEvent::FinishedHeight`] to the ExEx task manager letting it know that this
     /// ExEx has processed the corresponding block.
     ///
@@ -129,14 +197,94 @@ where
     ) -> Result<(), SendError<BlockNumHash>> {
         self.events.send(ExExEvent::FinishedHeight(height)).map_err(|_| SendError(height))
     }
+
+    /// Sends an [`ExExEvent::ShutdownAcknowledged`] to the ExEx task manager letting it know that
+    /// this ExEx has processed the
+    /// [`ExExNotification::Shutdown`](crate::ExExNotification::Shutdown) notification and is
+    /// ready for the node to shut down.
+    ///
+    /// Returns an error if the channel was closed (ExEx task manager panicked).
+    pub fn acknowledge_shutdown(&self) -> Result<(), SendError<()>> {
+        self.events.send(ExExEvent::ShutdownAcknowledged).map_err(|_| SendError(()))
+    }
+
+    /// Sends an [`ExExEvent::LagPolicy`] to the ExEx task manager, declaring how it should treat
+    /// this ExEx when it falls behind the node.
+    ///
+    /// Returns an error if the channel was closed (ExEx task manager panicked).
+    pub fn set_lag_policy(&self, policy: ExExLagPolicy) -> Result<(), SendError<ExExLagPolicy>> {
+        self.events.send(ExExEvent::LagPolicy(policy)).map_err(|_| SendError(policy))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ExExContext;
+    use super::{state_at_chain_tip, state_at_notification};
+    use crate::{ExExContext, ExExNotification};
+    use alloy_primitives::{Address, U256};
+    use reth_ethereum_primitives::EthPrimitives;
     use reth_exex_types::ExExHead;
     use reth_node_api::FullNodeComponents;
-    use reth_provider::BlockReader;
+    use reth_primitives_traits::Account;
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        AccountReader, BlockReader, Chain, ExecutionOutcome,
+    };
+    use reth_revm::db::BundleState;
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use std::sync::Arc;
+
+    /// Returns a chain of a single block that sets the nonce of the account.
+    fn chain(address: Address, nonce: u64) -> Arc<Chain<EthPrimitives>> {
+        let mut rng = generators::rng();
+        let block =
+            random_block(&mut rng, 1, BlockParams { tx_count: Some(0), ..Default::default() });
+        let bundle = BundleState::builder(1..=1)
+            .state_present_account_info(address, Account { nonce, ..Default::default() }.into())
+            .build();
+        Arc::new(Chain::new(
+            [block.try_recover().unwrap()],
+            ExecutionOutcome::new(bundle, vec![vec![]], 1, vec![]),
+            None,
+        ))
+    }
+
+    #[test]
+    fn state_at_chain_tip_overlays_chain_state() {
+        let (changed, unchanged) = (Address::random(), Address::random());
+        let provider = MockEthProvider::default();
+        provider.add_account(changed, ExtendedAccount::new(1, U256::from(1)));
+        provider.add_account(unchanged, ExtendedAccount::new(5, U256::from(5)));
+
+        let state = state_at_chain_tip(&provider, &chain(changed, 2)).unwrap();
+        assert_eq!(state.basic_account(&changed).unwrap().map(|account| account.nonce), Some(2));
+        // accounts that the chain didn't touch are read from the state of the fork block
+        assert_eq!(
+            state.basic_account(&unchanged).unwrap(),
+            Some(Account { nonce: 5, balance: U256::from(5), bytecode_hash: None })
+        );
+    }
+
+    #[test]
+    fn state_at_notification_follows_the_notification() {
+        let address = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(address, ExtendedAccount::new(1, U256::ZERO));
+        let nonce = |notification: &ExExNotification| {
+            state_at_notification(&provider, notification)
+                .unwrap()
+                .basic_account(&address)
+                .unwrap()
+                .map(|account| account.nonce)
+        };
+
+        let (old, new) = (chain(address, 2), chain(address, 3));
+        assert_eq!(nonce(&ExExNotification::ChainCommitted { new: new.clone() }), Some(3));
+        assert_eq!(nonce(&ExExNotification::ChainReorged { old: old.clone(), new }), Some(3));
+        // a reverted chain leaves the state of its fork block
+        assert_eq!(nonce(&ExExNotification::ChainReverted { old }), Some(1));
+        assert_eq!(nonce(&ExExNotification::Shutdown), Some(1));
+    }
 
     /// <https://github.com/paradigmxyz/reth/issues/12054>
     #[test]
@@ -155,10 +303,14 @@ mod tests {
                 self.ctx.evm_config();
                 self.ctx.provider();
                 self.ctx.network();
+                self.ctx.latest_state()?;
+                self.ctx.state_by_block_hash(Default::default())?;
+                self.ctx.state_by_block_number(Default::default())?;
                 self.ctx.payload_builder_handle();
                 self.ctx.task_executor();
                 self.ctx.set_notifications_without_head();
                 self.ctx.set_notifications_with_head(ExExHead { block: Default::default() });
+                self.ctx.replay_from(Default::default())?;
                 Ok(())
             }
         }
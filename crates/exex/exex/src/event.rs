@@ -1,7 +1,11 @@
+use crate::ExExLagPolicy;
 use alloy_eips::BlockNumHash;
 
 /// Events emitted by an `ExEx`.
+///
+/// New variants may be added in the future, so matches on the event need a wildcard arm.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ExExEvent {
     /// Highest block processed by the `ExEx`.
     ///
@@ -10,4 +14,11 @@ pub enum ExExEvent {
     ///
     /// On reorgs, it's possible for the height to go down.
     FinishedHeight(BlockNumHash),
+    /// The `ExEx` has processed the `ExExNotification::Shutdown` notification and flushed its
+    /// pending work, so the node can shut down.
+    ShutdownAcknowledged,
+    /// Sets how the `ExEx` manager treats the `ExEx` when it falls behind the node.
+    ///
+    /// Defaults to [`ExExLagPolicy::Block`].
+    LagPolicy(ExExLagPolicy),
 }
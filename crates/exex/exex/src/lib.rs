@@ -23,6 +23,28 @@
 //! event. To clarify: if the `ExEx` emits `ExExEvent::FinishedHeight(0)` it will receive
 //! notifications for any `block_number > 0`.
 //!
+//! # Lagging behind
+//!
+//! By default, a slow `ExEx` applies backpressure to the node: once the notification buffer of the
+//! manager is full, the node waits for the `ExEx` to catch up. An `ExEx` that doesn't need to keep
+//! up with the tip, such as a non-critical indexer, can emit an
+//! `ExExEvent::LagPolicy(ExExLagPolicy::DropAndBackfill { .. })` event instead. Live notifications
+//! are then skipped while it is too far behind, and the skipped blocks are backfilled once it
+//! catches up.
+//!
+//! # Shutdown
+//!
+//! When the node shuts down, the `ExEx` receives an `ExExNotification::Shutdown` as the last
+//! notification. The `ExEx` should flush any pending work and emit an
+//! `ExExEvent::ShutdownAcknowledged` event. Reth waits for the acknowledgement for at most
+//! `--exex.shutdown-timeout` before shutting down. `ExEx`'s that stop and drop their
+//! `ExExContext` are not waited for, but `ExEx`'s that keep running without acknowledging the
+//! shutdown delay it by the full timeout.
+//!
+//! Note: adding the shutdown notification and event is a breaking change for `ExEx`'s that match
+//! on `ExExNotification` or `ExExEvent` exhaustively. Both enums are `#[non_exhaustive]` now, so
+//! matches on them need a wildcard arm, and adding further variants is no longer breaking.
+//!
 //! # Examples, Assumptions, and Invariants
 //!
 //! ## Examples
@@ -106,6 +128,9 @@ pub use manager::*;
 mod notifications;
 pub use notifications::*;
 
+mod remote;
+pub use remote::*;
+
 mod wal;
 pub use wal::*;
 
@@ -1,8 +1,10 @@
 use crate::{
-    wal::Wal, ExExEvent, ExExNotification, ExExNotifications, FinishedExExHeight, WalHandle,
+    notifications::MissedBlocks, wal::Wal, ExExEvent, ExExNotification, ExExNotifications,
+    FinishedExExHeight, WalHandle,
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use futures::StreamExt;
 use itertools::Itertools;
 use metrics::Gauge;
@@ -12,19 +14,20 @@ use reth_evm::ConfigureEvm;
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_node_api::NodePrimitives;
 use reth_primitives_traits::SealedHeader;
-use reth_provider::HeaderProvider;
+use reth_provider::{Chain, HeaderProvider};
 use reth_tracing::tracing::{debug, warn};
 use std::{
     collections::VecDeque,
     fmt::Debug,
     future::{poll_fn, Future},
-    ops::Not,
+    ops::{Not, RangeInclusive},
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
     task::{ready, Context, Poll},
+    time::Duration,
 };
 use tokio::sync::{
     mpsc::{self, error::SendError, UnboundedReceiver, UnboundedSender},
@@ -56,12 +59,38 @@ pub enum ExExNotificationSource {
     BlockchainTree,
 }
 
+/// How the [`ExExManager`] treats an `ExEx` that falls behind the node.
+///
+/// An `ExEx` declares its policy with an [`ExExEvent::LagPolicy`] event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExExLagPolicy {
+    /// Buffer notifications until the `ExEx` processes them. Once the buffer of the manager is
+    /// full, the node is blocked from sending new notifications until the `ExEx` catches up.
+    #[default]
+    Block,
+    /// Skip live notifications once the `ExEx` is more than `max_lag_blocks` blocks behind the
+    /// latest notification, so it never blocks the node.
+    ///
+    /// Once the `ExEx` has processed its pending notifications, the skipped blocks are backfilled
+    /// by its [`ExExNotifications`] stream before the next live notification is emitted.
+    /// Reorgs, reverts and the shutdown notification are never skipped.
+    DropAndBackfill {
+        /// The number of blocks the `ExEx` is allowed to fall behind before notifications are
+        /// skipped.
+        max_lag_blocks: u64,
+    },
+}
+
 /// Metrics for an `ExEx`.
 #[derive(Metrics)]
 #[metrics(scope = "exex")]
 struct ExExMetrics {
     /// The total number of notifications sent to an `ExEx`.
     notifications_sent_total: Counter,
+    /// The total number of notifications skipped because the `ExEx` fell behind.
+    notifications_skipped_total: Counter,
+    /// The total number of backfills scheduled for blocks skipped because the `ExEx` fell behind.
+    missed_blocks_backfills_total: Counter,
     /// The total number of events an `ExEx` has sent to the manager.
     events_sent_total: Counter,
 }
@@ -87,6 +116,16 @@ pub struct ExExHandle<N: NodePrimitives = EthPrimitives> {
     ///
     /// If this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
     finished_height: Option<BlockNumHash>,
+    /// Whether the `ExEx` has acknowledged the [`ExExNotification::Shutdown`] notification.
+    shutdown_acknowledged: bool,
+    /// How the `ExEx` is treated when it falls behind.
+    lag_policy: ExExLagPolicy,
+    /// Blocks of the committed notifications skipped since the `ExEx` fell behind.
+    ///
+    /// If this is `Some`, the `ExEx` is currently lagging behind.
+    skipped_blocks: Option<RangeInclusive<BlockNumber>>,
+    /// Blocks to backfill, shared with the [`ExExNotifications`] stream of the `ExEx`.
+    missed_blocks: MissedBlocks,
 }
 
 impl<N: NodePrimitives> ExExHandle<N> {
@@ -105,6 +144,7 @@ impl<N: NodePrimitives> ExExHandle<N> {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
         let notifications =
             ExExNotifications::new(node_head, provider, evm_config, notification_rx, wal_handle);
+        let missed_blocks = notifications.missed_blocks().clone();
 
         (
             Self {
@@ -114,12 +154,144 @@ impl<N: NodePrimitives> ExExHandle<N> {
                 receiver: event_rx,
                 next_notification_id: 0,
                 finished_height: None,
+                shutdown_acknowledged: false,
+                lag_policy: ExExLagPolicy::default(),
+                skipped_blocks: None,
+                missed_blocks,
             },
             event_tx,
             notifications,
         )
     }
 
+    /// Returns `true` if the `ExEx` acknowledged the shutdown, or stopped without acknowledging it
+    /// and dropped its events sender.
+    fn has_shut_down(&self) -> bool {
+        self.shutdown_acknowledged || self.receiver.is_closed()
+    }
+
+    /// Returns `true` if the `ExEx` is lagging behind and live notifications are skipped.
+    const fn is_lagging(&self) -> bool {
+        self.skipped_blocks.is_some()
+    }
+
+    /// Skips the notification according to the [`ExExLagPolicy`] of the `ExEx`, if it lags more
+    /// than allowed behind the `canonical_tip` of the manager.
+    ///
+    /// Once the `ExEx` has capacity for new notifications again, the skipped blocks are handed
+    /// over to its notification stream to be backfilled.
+    fn skip_lagging(
+        &mut self,
+        cx: &mut Context<'_>,
+        (notification_id, notification): &(usize, ExExNotification<N>),
+        canonical_tip: Option<BlockNumber>,
+    ) -> Delivery<N> {
+        let ExExLagPolicy::DropAndBackfill { max_lag_blocks } = self.lag_policy else {
+            return Delivery::Deliver
+        };
+
+        let ExExNotification::ChainCommitted { new } = notification else {
+            // Reorgs, reverts and the shutdown are always delivered, once the ExEx has capacity
+            let Some(skipped) = self.skipped_blocks.clone() else { return Delivery::Deliver };
+            if self.sender.poll_reserve(cx).is_pending() {
+                return Delivery::Deliver
+            }
+            self.skipped_blocks = None;
+
+            // Skipped blocks that were reverted are no longer canonical and don't need to be
+            // backfilled
+            let end = notification
+                .reverted_chain()
+                .map(|chain| chain.first().number().saturating_sub(1))
+                .map_or(*skipped.end(), |reverted| reverted.min(*skipped.end()));
+            self.schedule_backfill(*skipped.start()..=end);
+
+            // The ExEx only needs to revert the blocks that were delivered to it
+            let delivered_tip = skipped.start().saturating_sub(1);
+            return match notification {
+                ExExNotification::ChainReorged { old, new }
+                    if old.tip().number() > delivered_tip =>
+                {
+                    match chain_up_to(old, delivered_tip) {
+                        Some(old) => Delivery::Replace(ExExNotification::ChainReorged {
+                            old,
+                            new: new.clone(),
+                        }),
+                        None => {
+                            Delivery::Replace(ExExNotification::ChainCommitted { new: new.clone() })
+                        }
+                    }
+                }
+                ExExNotification::ChainReverted { old } if old.tip().number() > delivered_tip => {
+                    match chain_up_to(old, delivered_tip) {
+                        Some(old) => Delivery::Replace(ExExNotification::ChainReverted { old }),
+                        None => {
+                            debug!(
+                                target: "exex::manager",
+                                exex_id = %self.id,
+                                %notification_id,
+                                "Skipping revert of blocks that were not delivered to lagging ExEx"
+                            );
+                            self.next_notification_id = notification_id + 1;
+                            Delivery::Skip
+                        }
+                    }
+                }
+                _ => Delivery::Deliver,
+            }
+        };
+
+        let exex_tip = new.first().number().saturating_sub(1);
+        let lag = canonical_tip.unwrap_or_default().saturating_sub(exex_tip);
+        if !self.is_lagging() && lag <= max_lag_blocks {
+            return Delivery::Deliver
+        }
+
+        if self.sender.poll_reserve(cx).is_ready() {
+            // The ExEx has capacity, backfill the skipped blocks before the notification
+            if let Some(skipped) = self.skipped_blocks.take() {
+                self.schedule_backfill(skipped);
+            }
+            return Delivery::Deliver
+        }
+
+        match &mut self.skipped_blocks {
+            Some(skipped) => *skipped = *skipped.start()..=new.tip().number(),
+            None => {
+                warn!(
+                    target: "exex::manager",
+                    exex_id = %self.id,
+                    %exex_tip,
+                    %lag,
+                    %max_lag_blocks,
+                    "ExEx fell behind, skipping notifications until it catches up"
+                );
+                self.skipped_blocks = Some(new.first().number()..=new.tip().number());
+            }
+        }
+        debug!(
+            target: "exex::manager",
+            exex_id = %self.id,
+            %notification_id,
+            skipped_blocks = ?self.skipped_blocks,
+            "Skipping notification for lagging ExEx"
+        );
+        self.next_notification_id = notification_id + 1;
+        self.metrics.notifications_skipped_total.increment(1);
+        Delivery::Skip
+    }
+
+    /// Hands the skipped blocks over to the notification stream of the `ExEx` to be backfilled.
+    fn schedule_backfill(&mut self, blocks: RangeInclusive<BlockNumber>) {
+        if blocks.is_empty() {
+            return
+        }
+
+        debug!(target: "exex::manager", exex_id = %self.id, ?blocks, "Scheduling backfill of skipped blocks");
+        self.missed_blocks.push(blocks);
+        self.metrics.missed_blocks_backfills_total.increment(1);
+    }
+
     /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
@@ -152,8 +324,9 @@ impl<N: NodePrimitives> ExExHandle<N> {
                 // Do not handle [ExExNotification::ChainReorged] and
                 // [ExExNotification::ChainReverted] cases and always send the
                 // notification, because the ExEx should be aware of the reorgs and reverts lower
-                // than its finished height
-                ExExNotification::ChainReorged { .. } | ExExNotification::ChainReverted { .. } => {}
+                // than its finished height. Notifications without a chain, like
+                // [ExExNotification::Shutdown], are always sent as well.
+                _ => {}
             }
         }
 
@@ -239,9 +412,13 @@ pub struct ExExManager<P, N: NodePrimitives> {
 
     /// Whether the manager is ready to receive new notifications.
     is_ready: watch::Sender<bool>,
+    /// Whether the manager is shutting down and no longer accepts new notifications.
+    is_shutting_down: bool,
 
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Sender<FinishedExExHeight>,
+    /// The tip of the canonical chain according to the latest notification in the buffer.
+    canonical_tip: Option<BlockNumber>,
 
     /// Write-Ahead Log for the [`ExExNotification`]s.
     wal: Wal<N>,
@@ -302,7 +479,9 @@ where
             current_capacity: Arc::clone(&current_capacity),
 
             is_ready: is_ready_tx,
+            is_shutting_down: false,
             finished_height: finished_height_tx,
+            canonical_tip: None,
 
             wal,
             finalized_header_stream,
@@ -340,6 +519,12 @@ where
     /// Pushes a new notification into the managers internal buffer, assigning the notification a
     /// unique ID.
     fn push_notification(&mut self, notification: ExExNotification<N>) {
+        if let Some(chain) = notification.committed_chain() {
+            self.canonical_tip = Some(chain.tip().number());
+        } else if let Some(chain) = notification.reverted_chain() {
+            self.canonical_tip = Some(chain.first().number().saturating_sub(1));
+        }
+
         let next_id = self.next_id;
         self.buffer.push_back((next_id, notification));
         self.next_id += 1;
@@ -420,6 +605,62 @@ where
     }
 }
 
+impl<P, N> ExExManager<P, N>
+where
+    P: HeaderProvider + Unpin + 'static,
+    N: NodePrimitives,
+{
+    /// Gracefully shuts down the `ExEx`'s.
+    ///
+    /// Stops accepting new notifications from [`ExExManagerHandle`]s, delivers the already
+    /// buffered notifications followed by an [`ExExNotification::Shutdown`] to all `ExEx`'s, and
+    /// waits until all of them acknowledged it with an [`ExExEvent::ShutdownAcknowledged`], but
+    /// no longer than the given timeout. `ExEx`'s that stopped and dropped their events sender
+    /// are not waited for.
+    ///
+    /// `ExEx`'s that don't handle the shutdown notification delay the shutdown by the full
+    /// timeout.
+    ///
+    /// Returns `true` if all `ExEx`'s acknowledged the shutdown in time.
+    pub async fn shutdown(mut self, timeout: Duration) -> eyre::Result<bool> {
+        debug!(target: "exex::manager", ?timeout, "Shutting down ExExes");
+        self.is_shutting_down = true;
+        self.push_notification(ExExNotification::Shutdown);
+
+        let drain = poll_fn(|cx| {
+            if let Poll::Ready(res) = Pin::new(&mut self).poll(cx) {
+                return Poll::Ready(res)
+            }
+
+            if self.exex_handles.iter().all(|exex| exex.has_shut_down()) {
+                return Poll::Ready(Ok(()))
+            }
+
+            Poll::Pending
+        });
+        let res = tokio::time::timeout(timeout, drain).await;
+
+        match res {
+            Ok(res) => res.map(|()| true),
+            Err(_) => {
+                let pending_exexes = self
+                    .exex_handles
+                    .iter()
+                    .filter(|exex| !exex.has_shut_down())
+                    .map(|exex| &exex.id)
+                    .join(", ");
+                warn!(
+                    target: "exex::manager",
+                    %pending_exexes,
+                    ?timeout,
+                    "ExExes did not acknowledge the shutdown in time"
+                );
+                Ok(false)
+            }
+        }
+    }
+}
+
 impl<P, N> Future for ExExManager<P, N>
 where
     P: HeaderProvider + Unpin + 'static,
@@ -432,7 +673,7 @@ where
     ///    the latest state of [`ExExEvent::FinishedHeight`] events.
     /// 2. Finalize the WAL with the finalized header, if necessary.
     /// 3. Drain [`ExExManagerHandle`] notifications, push them to the internal buffer and update
-    ///    the internal buffer capacity.
+    ///    the internal buffer capacity. Skipped if the manager is shutting down.
     /// 5. Send notifications from the internal buffer to those ExExes that are ready to receive new
     ///    notifications.
     /// 5. Remove notifications from the internal buffer that have been sent to **all** ExExes and
@@ -448,6 +689,8 @@ where
                 exex.metrics.events_sent_total.increment(1);
                 match event {
                     ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::ShutdownAcknowledged => exex.shutdown_acknowledged = true,
+                    ExExEvent::LagPolicy(policy) => exex.lag_policy = policy,
                 }
             }
         }
@@ -461,8 +704,8 @@ where
             this.finalize_wal(header)?;
         }
 
-        // Drain handle notifications
-        while this.buffer.len() < this.max_capacity {
+        // Drain handle notifications, unless the manager is shutting down
+        while !this.is_shutting_down && this.buffer.len() < this.max_capacity {
             if let Poll::Ready(Some((source, notification))) = this.handle_rx.poll_recv(cx) {
                 let committed_tip =
                     notification.committed_chain().map(|chain| chain.tip().number());
@@ -497,15 +740,25 @@ where
 
             // It is a logic error for this to ever underflow since the manager manages the
             // notification IDs
-            let notification_index = exex
+            let mut notification_index = exex
                 .next_notification_id
                 .checked_sub(this.min_id)
                 .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = this.buffer.get(notification_index) {
-                if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
+            while let Some(notification) = this.buffer.get(notification_index) {
+                // Skip all notifications that a lagging ExEx doesn't have capacity for
+                let sent = match exex.skip_lagging(cx, notification, this.canonical_tip) {
+                    Delivery::Deliver => exex.send(cx, notification),
+                    Delivery::Replace(replaced) => exex.send(cx, &(notification.0, replaced)),
+                    Delivery::Skip => {
+                        notification_index += 1;
+                        continue
+                    }
+                };
+                if let Poll::Ready(Err(err)) = sent {
                     // The channel was closed, which is irrecoverable for the manager
                     return Poll::Ready(Err(err.into()))
                 }
+                break
             }
             min_id = min_id.min(exex.next_notification_id);
             this.exex_handles.push(exex);
@@ -531,6 +784,25 @@ where
     }
 }
 
+/// How a buffered notification is delivered to an `ExEx`, see [`ExExHandle::skip_lagging`].
+#[derive(Debug, PartialEq, Eq)]
+enum Delivery<N: NodePrimitives> {
+    /// The notification is delivered as is.
+    Deliver,
+    /// The notification is skipped.
+    Skip,
+    /// The notification is replaced with one that only reverts the blocks delivered to the `ExEx`.
+    Replace(ExExNotification<N>),
+}
+
+/// Returns the blocks of the chain up to and including the given block, or `None` if the chain
+/// starts after it.
+fn chain_up_to<N: NodePrimitives>(chain: &Chain<N>, block: BlockNumber) -> Option<Arc<Chain<N>>> {
+    let execution_outcome = chain.execution_outcome_at_block(block)?;
+    let blocks = chain.blocks().range(..=block).map(|(_, block)| block.clone());
+    Some(Arc::new(Chain::new(blocks, execution_outcome, None)))
+}
+
 /// A handle to communicate with the [`ExExManager`].
 #[derive(Debug)]
 pub struct ExExManagerHandle<N: NodePrimitives = EthPrimitives> {
@@ -1242,6 +1514,91 @@ mod tests {
         assert_eq!(exex_handle.next_notification_id, 23);
     }
 
+    #[tokio::test]
+    async fn test_skips_notifications_for_lagging_exex() {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider::new(provider_factory).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (mut exex_handle, _, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            provider,
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+        exex_handle.lag_policy = ExExLagPolicy::DropAndBackfill { max_lag_blocks: 2 };
+
+        let chain = |blocks: RangeInclusive<BlockNumber>| {
+            let blocks = blocks.map(|number| {
+                let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+                block.set_hash(B256::random());
+                block.set_block_number(number);
+                block
+            });
+            Arc::new(Chain::new(blocks, Default::default(), Default::default()))
+        };
+        let committed = |number| ExExNotification::ChainCommitted { new: chain(number..=number) };
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        // The ExEx is within the allowed lag, the notification is delivered
+        let notification = (0, committed(1));
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &notification, Some(1)), Delivery::Deliver);
+        assert!(exex_handle.send(&mut cx, &notification).is_ready());
+
+        // The ExEx didn't process the first notification yet and falls behind
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &(1, committed(2)), Some(5)), Delivery::Skip);
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &(2, committed(3)), Some(5)), Delivery::Skip);
+        assert!(exex_handle.is_lagging());
+        assert_eq!(exex_handle.next_notification_id, 3);
+
+        // Once the ExEx catches up, the skipped blocks are scheduled for backfill and live
+        // notifications are delivered again
+        assert_eq!(notifications.next().await.unwrap().unwrap(), notification.1);
+        let notification = (3, committed(4));
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &notification, Some(5)), Delivery::Deliver);
+        assert!(!exex_handle.is_lagging());
+        assert_eq!(exex_handle.missed_blocks.take(), Some(2..=3));
+        assert!(exex_handle.send(&mut cx, &notification).is_ready());
+
+        // Reorgs are delivered once the ExEx has capacity
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &(4, committed(5)), Some(10)), Delivery::Skip);
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &(5, committed(6)), Some(10)), Delivery::Skip);
+        let reorged = (6, ExExNotification::ChainReorged { old: chain(3..=6), new: chain(3..=3) });
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &reorged, Some(10)), Delivery::Deliver);
+        assert!(exex_handle.is_lagging());
+        assert_eq!(notifications.next().await.unwrap().unwrap(), notification.1);
+
+        // Only the delivered blocks are reverted and skipped blocks that were reverted are not
+        // backfilled
+        let Delivery::Replace(ExExNotification::ChainReorged { old, new }) =
+            exex_handle.skip_lagging(&mut cx, &reorged, Some(10))
+        else {
+            panic!("expected the reorg to be trimmed")
+        };
+        assert_eq!(old.range(), 3..=4);
+        assert_eq!(new.range(), 3..=3);
+        assert!(!exex_handle.is_lagging());
+        assert_eq!(exex_handle.missed_blocks.take(), None);
+        let notification = (6, ExExNotification::ChainReorged { old, new });
+        assert!(exex_handle.send(&mut cx, &notification).is_ready());
+
+        // Reverts of skipped blocks only are not delivered, and the reverted blocks are not
+        // backfilled
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &(7, committed(4)), Some(10)), Delivery::Skip);
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &(8, committed(5)), Some(10)), Delivery::Skip);
+        assert_eq!(notifications.next().await.unwrap().unwrap(), notification.1);
+        let reverted = ExExNotification::ChainReverted { old: chain(5..=5) };
+        assert_eq!(exex_handle.skip_lagging(&mut cx, &(9, reverted), Some(10)), Delivery::Skip);
+        assert!(!exex_handle.is_lagging());
+        assert_eq!(exex_handle.next_notification_id, 10);
+        assert_eq!(exex_handle.missed_blocks.take(), Some(4..=4));
+    }
+
     #[tokio::test]
     async fn test_sends_chain_reverted_notification() {
         let provider_factory = create_test_provider_factory();
@@ -1282,6 +1639,103 @@ mod tests {
         assert_eq!(exex_handle.next_notification_id, 23);
     }
 
+    #[tokio::test]
+    async fn test_shutdown() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider::new(provider_factory).unwrap();
+
+        let (exex_handle, event_tx, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            provider.clone(),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+
+        let mut exex_manager =
+            ExExManager::new(provider, vec![exex_handle], 10, wal, empty_finalized_header_stream());
+
+        let notification = ExExNotification::ChainCommitted { new: Arc::new(Chain::default()) };
+        exex_manager.push_notification(notification.clone());
+
+        let exex = tokio::spawn(async move {
+            let mut received = Vec::new();
+            while let Some(notification) = notifications.try_next().await.unwrap() {
+                if notification == ExExNotification::Shutdown {
+                    event_tx.send(ExExEvent::ShutdownAcknowledged).unwrap();
+                }
+                received.push(notification);
+            }
+            received
+        });
+
+        // The buffered notification is delivered before the shutdown notification
+        assert!(exex_manager.shutdown(Duration::from_secs(10)).await.unwrap());
+        assert_eq!(exex.await.unwrap(), vec![notification, ExExNotification::Shutdown]);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_timeout() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider::new(provider_factory).unwrap();
+
+        let (exex_handle, _event_tx, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            provider.clone(),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+
+        let exex_manager =
+            ExExManager::new(provider, vec![exex_handle], 10, wal, empty_finalized_header_stream());
+
+        // The ExEx receives the shutdown notification, but never acknowledges it
+        let exex = tokio::spawn(async move { notifications.next().await.unwrap().unwrap() });
+
+        assert!(!exex_manager.shutdown(Duration::from_millis(100)).await.unwrap());
+        assert_eq!(exex.await.unwrap(), ExExNotification::Shutdown);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stopped_exex() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider::new(provider_factory).unwrap();
+
+        let (exex_handle, event_tx, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            provider.clone(),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+
+        let exex_manager =
+            ExExManager::new(provider, vec![exex_handle], 10, wal, empty_finalized_header_stream());
+
+        // The ExEx stops on the shutdown notification without acknowledging it
+        let exex = tokio::spawn(async move {
+            let notification = notifications.next().await.unwrap().unwrap();
+            drop(event_tx);
+            notification
+        });
+
+        assert!(exex_manager.shutdown(Duration::from_secs(10)).await.unwrap());
+        assert_eq!(exex.await.unwrap(), ExExNotification::Shutdown);
+    }
+
     #[tokio::test]
     async fn test_exex_wal() -> eyre::Result<()> {
         reth_tracing::init_test_tracing();
@@ -1,15 +1,22 @@
-use crate::{BackfillJobFactory, ExExNotification, StreamBackfillJob, WalHandle};
+use crate::{
+    BackfillCheckpointResult, BackfillCheckpoints, BackfillJobFactory, ExExNotification,
+    StreamBackfillJob, WalHandle,
+};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::ConfigureEvm;
 use reth_exex_types::ExExHead;
 use reth_node_api::NodePrimitives;
-use reth_provider::{BlockReader, Chain, HeaderProvider, StateProviderFactory};
+use reth_provider::{BlockHashReader, BlockReader, Chain, HeaderProvider, StateProviderFactory};
 use reth_tracing::tracing::debug;
 use std::{
+    collections::VecDeque,
     fmt::Debug,
+    ops::RangeInclusive,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
@@ -19,12 +26,49 @@ use tokio::sync::mpsc::Receiver;
 /// A stream of [`ExExNotification`]s. The stream will emit notifications for all blocks. If the
 /// stream is configured with a head via [`ExExNotifications::set_with_head`] or
 /// [`ExExNotifications::with_head`], it will run backfill jobs to catch up to the node head.
+///
+/// The stream can also replay the canonical chain from a given block via
+/// [`ExExNotifications::set_replay_from`], so that a new `ExEx` can bootstrap its state.
+///
+/// Blocks that the [`ExExManager`](crate::ExExManager) did not deliver because the `ExEx` fell
+/// behind (see [`ExExLagPolicy::DropAndBackfill`](crate::ExExLagPolicy::DropAndBackfill)) are
+/// backfilled before the next notification is emitted.
 #[derive(Debug)]
 pub struct ExExNotifications<P, E>
 where
     E: ConfigureEvm,
 {
     inner: ExExNotificationsInner<P, E>,
+    /// Blocks that were skipped by the manager and need to be backfilled.
+    missed_blocks: MissedBlocks,
+    /// The backfill job for the blocks that were skipped by the manager.
+    missed_blocks_job: Option<StreamBackfillJob<E, P, Chain<E::Primitives>>>,
+    /// The notification that was received after the skipped blocks, emitted once they are
+    /// backfilled.
+    pending_notification: Option<ExExNotification<E::Primitives>>,
+}
+
+/// Range of blocks that the [`ExExManager`](crate::ExExManager) skipped for an `ExEx` that fell
+/// behind, shared between the manager and the `ExEx`'s notification stream.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MissedBlocks(Arc<Mutex<Option<RangeInclusive<BlockNumber>>>>);
+
+impl MissedBlocks {
+    /// Records a range of skipped blocks, merging it with a range that was not backfilled yet.
+    pub(crate) fn push(&self, range: RangeInclusive<BlockNumber>) {
+        let mut missed = self.0.lock();
+        *missed = Some(match missed.take() {
+            Some(existing) => {
+                (*existing.start()).min(*range.start())..=(*existing.end()).max(*range.end())
+            }
+            None => range,
+        });
+    }
+
+    /// Takes the range of skipped blocks, if any.
+    pub(crate) fn take(&self) -> Option<RangeInclusive<BlockNumber>> {
+        self.0.lock().take()
+    }
 }
 
 /// A trait, that represents a stream of [`ExExNotification`]s. The stream will emit notifications
@@ -73,8 +117,7 @@ where
     /// A stream of [`ExExNotification`]s. The stream will only emit notifications for blocks that
     /// are committed or reverted after the given head.
     WithHead(Box<ExExNotificationsWithHead<P, E>>),
-    /// Internal state used when transitioning between [`ExExNotificationsInner::WithoutHead`] and
-    /// [`ExExNotificationsInner::WithHead`].
+    /// Internal state used when transitioning between the other variants.
     Invalid,
 }
 
@@ -83,7 +126,7 @@ where
     E: ConfigureEvm,
 {
     /// Creates a new stream of [`ExExNotifications`] without a head.
-    pub const fn new(
+    pub fn new(
         node_head: BlockNumHash,
         provider: P,
         evm_config: E,
@@ -98,28 +141,134 @@ where
                 notifications,
                 wal_handle,
             )),
+            missed_blocks: MissedBlocks::default(),
+            missed_blocks_job: None,
+            pending_notification: None,
         }
     }
+
+    /// Returns the [`BackfillCheckpoints`] stored alongside the ExEx WAL.
+    pub fn backfill_checkpoints(&self) -> BackfillCheckpointResult<BackfillCheckpoints> {
+        self.inner.provider_and_wal().1.backfill_checkpoints()
+    }
+
+    /// Returns the blocks skipped by the manager, shared with the
+    /// [`ExExHandle`](crate::ExExHandle).
+    pub(crate) const fn missed_blocks(&self) -> &MissedBlocks {
+        &self.missed_blocks
+    }
 }
 
-impl<P, E> ExExNotificationsStream<E::Primitives> for ExExNotifications<P, E>
+impl<P, E> ExExNotificationsInner<P, E>
 where
-    P: BlockReader + HeaderProvider + StateProviderFactory + Clone + Unpin + 'static,
-    E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + Clone + Unpin + 'static,
+    P: Clone,
+    E: ConfigureEvm,
 {
-    fn set_without_head(&mut self) {
-        let current = std::mem::replace(&mut self.inner, ExExNotificationsInner::Invalid);
-        self.inner = ExExNotificationsInner::WithoutHead(match current {
-            ExExNotificationsInner::WithoutHead(notifications) => notifications,
-            ExExNotificationsInner::WithHead(notifications) => ExExNotificationsWithoutHead::new(
+    /// Returns a [`BackfillJobFactory`] with the provider and EVM config of the stream.
+    fn backfill_job_factory(&self) -> BackfillJobFactory<E, P> {
+        match self {
+            Self::WithoutHead(notifications) => BackfillJobFactory::new(
+                notifications.evm_config.clone(),
+                notifications.provider.clone(),
+            ),
+            Self::WithHead(notifications) => BackfillJobFactory::new(
+                notifications.evm_config.clone(),
+                notifications.provider.clone(),
+            ),
+            Self::Invalid => unreachable!(),
+        }
+    }
+}
+
+impl<P, E> ExExNotificationsInner<P, E>
+where
+    E: ConfigureEvm,
+{
+    /// Returns the provider and the WAL handle of the stream.
+    fn provider_and_wal(&self) -> (&P, &WalHandle<E::Primitives>) {
+        match self {
+            Self::WithoutHead(notifications) => {
+                (&notifications.provider, &notifications.wal_handle)
+            }
+            Self::WithHead(notifications) => (&notifications.provider, &notifications.wal_handle),
+            Self::Invalid => unreachable!(),
+        }
+    }
+
+    /// Converts the stream into a stream without a head.
+    fn into_without_head(self) -> ExExNotificationsWithoutHead<P, E> {
+        match self {
+            Self::WithoutHead(notifications) => notifications,
+            Self::WithHead(notifications) => ExExNotificationsWithoutHead::new(
                 notifications.initial_local_head,
                 notifications.provider,
                 notifications.evm_config,
                 notifications.notifications,
                 notifications.wal_handle,
             ),
-            ExExNotificationsInner::Invalid => unreachable!(),
-        });
+            Self::Invalid => unreachable!(),
+        }
+    }
+}
+
+impl<P, E> ExExNotifications<P, E>
+where
+    P: BlockReader + HeaderProvider + StateProviderFactory + Clone + Unpin + 'static,
+    E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + Clone + Unpin + 'static,
+{
+    /// Sets the stream to a stream of [`ExExNotification`]s that replays the canonical chain
+    /// from the given block, inclusive, and then continues with the live notifications.
+    ///
+    /// Blocks that are still in the WAL are replayed from their committed notifications, so they
+    /// don't need to be executed again. The notifications are read from the WAL one at a time, as
+    /// the stream advances. Older blocks are backfilled from the node database. Forks
+    /// that were reverted are not replayed, the stream only emits the blocks that are canonical
+    /// when the replay is started, followed by the live notifications. Every block is delivered
+    /// exactly once, see [`ExExNotificationsWithHead`].
+    ///
+    /// The genesis block is never replayed, so replaying from block 0 starts at block 1.
+    pub fn set_replay_from(&mut self, block_number: BlockNumber) -> eyre::Result<()> {
+        let block_number = block_number.max(1);
+        let (provider, wal_handle) = self.inner.provider_and_wal();
+        let head_hash = provider
+            .block_hash(block_number - 1)?
+            .ok_or_else(|| eyre::eyre!("block {} is not canonical", block_number - 1))?;
+        let replay = WalReplay::new(wal_handle, block_number);
+        debug!(
+            target: "exex::notifications",
+            block_number,
+            wal_notifications = replay.notifications.len(),
+            "Replaying canonical chain"
+        );
+
+        let current = std::mem::replace(&mut self.inner, ExExNotificationsInner::Invalid);
+        self.inner = ExExNotificationsInner::WithHead(Box::new(
+            current
+                .into_without_head()
+                .with_head(ExExHead { block: (block_number - 1, head_hash).into() })
+                .with_replay(replay),
+        ));
+        Ok(())
+    }
+
+    /// Returns a new stream of [`ExExNotification`]s that replays the canonical chain from the
+    /// given block.
+    ///
+    /// See [`Self::set_replay_from`].
+    pub fn replay_from(mut self, block_number: BlockNumber) -> eyre::Result<Self> {
+        self.set_replay_from(block_number)?;
+        Ok(self)
+    }
+}
+
+impl<P, E> ExExNotificationsStream<E::Primitives> for ExExNotifications<P, E>
+where
+    P: BlockReader + HeaderProvider + StateProviderFactory + Clone + Unpin + 'static,
+    E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + Clone + Unpin + 'static,
+{
+    fn set_without_head(&mut self) {
+        let current = std::mem::replace(&mut self.inner, ExExNotificationsInner::Invalid);
+        self.inner = ExExNotificationsInner::WithoutHead(current.into_without_head());
     }
 
     fn set_with_head(&mut self, exex_head: ExExHead) {
@@ -164,12 +313,43 @@ where
         self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        match &mut self.get_mut().inner {
-            ExExNotificationsInner::WithoutHead(notifications) => {
-                notifications.poll_next_unpin(cx).map(|result| result.map(Ok))
+        let this = self.get_mut();
+
+        loop {
+            // Backfill the blocks skipped by the manager before emitting the notification that
+            // followed them
+            if let Some(backfill_job) = &mut this.missed_blocks_job {
+                if let Some(chain) = ready!(backfill_job.poll_next_unpin(cx)).transpose()? {
+                    debug!(target: "exex::notifications", range = ?chain.range(), "Backfill job for missed blocks returned a chain");
+                    return Poll::Ready(Some(Ok(ExExNotification::ChainCommitted {
+                        new: Arc::new(chain),
+                    })))
+                }
+
+                this.missed_blocks_job = None;
+                if let Some(notification) = this.pending_notification.take() {
+                    return Poll::Ready(Some(Ok(notification)))
+                }
             }
-            ExExNotificationsInner::WithHead(notifications) => notifications.poll_next_unpin(cx),
-            ExExNotificationsInner::Invalid => unreachable!(),
+
+            let notification = match &mut this.inner {
+                ExExNotificationsInner::WithoutHead(notifications) => {
+                    ready!(notifications.poll_next_unpin(cx)).map(Ok)
+                }
+                ExExNotificationsInner::WithHead(notifications) => {
+                    ready!(notifications.poll_next_unpin(cx))
+                }
+                ExExNotificationsInner::Invalid => unreachable!(),
+            };
+            let Some(Ok(notification)) = notification else { return Poll::Ready(notification) };
+
+            let Some(missed_blocks) = this.missed_blocks.take() else {
+                return Poll::Ready(Some(Ok(notification)))
+            };
+            debug!(target: "exex::notifications", ?missed_blocks, "Backfilling blocks missed while lagging behind");
+            this.missed_blocks_job =
+                Some(this.inner.backfill_job_factory().backfill(missed_blocks).into_stream());
+            this.pending_notification = Some(notification);
         }
     }
 }
@@ -246,6 +426,20 @@ where
 /// `exex_head.number == 10`, then the first notification will be with `block.number == 11`. An
 /// `exex_head.number` of 10 indicates that the ExEx has processed up to block 10, and is ready to
 /// process block 11.
+///
+/// Every block is delivered exactly once, also if the node head advances while the stream
+/// backfills to it:
+///
+/// - Committed blocks that were already delivered are skipped, chains that overlap with the
+///   delivered blocks are trimmed to the blocks after them.
+/// - If a committed chain does not connect to the delivered blocks, the gap is backfilled before
+///   the chain is emitted.
+/// - Only reverted blocks that were delivered are emitted, reverts of blocks that were never
+///   delivered are dropped.
+/// - If a committed chain conflicts with the delivered blocks, the delivered blocks are reverted
+///   using the WAL before the chain is emitted.
+///
+/// Trimmed chains don't carry trie updates.
 #[derive(Debug)]
 pub struct ExExNotificationsWithHead<P, E>
 where
@@ -257,8 +451,8 @@ where
     evm_config: E,
     notifications: Receiver<ExExNotification<E::Primitives>>,
     wal_handle: WalHandle<E::Primitives>,
-    /// The exex head at launch
-    initial_exex_head: ExExHead,
+    /// The last block that was delivered to the ExEx, initially the ExEx head at launch.
+    exex_head: BlockNumHash,
 
     /// If true, then we need to check if the ExEx head is on the canonical chain and if not,
     /// revert its head.
@@ -268,6 +462,11 @@ where
     pending_check_backfill: bool,
     /// The backfill job to run before consuming any notifications.
     backfill_job: Option<StreamBackfillJob<E, P, Chain<E::Primitives>>>,
+    /// The committed chain that is emitted once the blocks before it are backfilled or the
+    /// conflicting delivered blocks are reverted.
+    pending_chain: Option<Arc<Chain<E::Primitives>>>,
+    /// The canonical chains from the WAL that are replayed before the live notifications.
+    replay: WalReplay,
 }
 
 impl<P, E> ExExNotificationsWithHead<P, E>
@@ -289,12 +488,23 @@ where
             evm_config,
             notifications,
             wal_handle,
-            initial_exex_head: exex_head,
+            exex_head: exex_head.block,
             pending_check_canonical: true,
             pending_check_backfill: true,
             backfill_job: None,
+            pending_chain: None,
+            replay: WalReplay::default(),
         }
     }
+
+    /// Replays the canonical chains from the WAL before the live notifications.
+    ///
+    /// The ExEx head must be on the canonical chain. Blocks before the first chain are backfilled.
+    fn with_replay(mut self, replay: WalReplay) -> Self {
+        self.pending_check_canonical = false;
+        self.replay = replay;
+        self
+    }
 }
 
 impl<P, E> ExExNotificationsWithHead<P, E>
@@ -308,8 +518,8 @@ where
     /// we're not on the canonical chain and we need to revert the notification with the ExEx
     /// head block.
     fn check_canonical(&mut self) -> eyre::Result<Option<ExExNotification<E::Primitives>>> {
-        if self.provider.is_known(&self.initial_exex_head.block.hash)? &&
-            self.initial_exex_head.block.number <= self.initial_local_head.number
+        if self.provider.is_known(&self.exex_head.hash)? &&
+            self.exex_head.number <= self.initial_local_head.number
         {
             // we have the targeted block and that block is below the current head
             debug!(target: "exex::notifications", "ExEx head is on the canonical chain");
@@ -320,19 +530,18 @@ where
         // chain.
 
         // Get the committed notification for the head block from the WAL.
-        let Some(notification) = self
-            .wal_handle
-            .get_committed_notification_by_block_hash(&self.initial_exex_head.block.hash)?
+        let Some(notification) =
+            self.wal_handle.get_committed_notification_by_block_hash(&self.exex_head.hash)?
         else {
             // it's possible that the exex head is further ahead
-            if self.initial_exex_head.block.number > self.initial_local_head.number {
+            if self.exex_head.number > self.initial_local_head.number {
                 debug!(target: "exex::notifications", "ExEx head is ahead of the canonical chain");
                 return Ok(None);
             }
 
             return Err(eyre::eyre!(
                 "Could not find notification for block hash {:?} in the WAL",
-                self.initial_exex_head.block.hash
+                self.exex_head.hash
             ))
         };
 
@@ -340,8 +549,8 @@ where
         let committed_chain = notification.committed_chain().unwrap();
         let new_exex_head =
             (committed_chain.first().parent_hash(), committed_chain.first().number() - 1).into();
-        debug!(target: "exex::notifications", old_exex_head = ?self.initial_exex_head.block, new_exex_head = ?new_exex_head, "ExEx head updated");
-        self.initial_exex_head.block = new_exex_head;
+        debug!(target: "exex::notifications", old_exex_head = ?self.exex_head, new_exex_head = ?new_exex_head, "ExEx head updated");
+        self.exex_head = new_exex_head;
 
         // Return an inverted notification. See the documentation for
         // `ExExNotification::into_inverted`.
@@ -358,19 +567,18 @@ where
     ///   node database.
     /// - ExEx is at the same block number as the node head (`node_head.number ==
     ///   exex_head.number`). Nothing to do.
+    ///
+    /// If chains are replayed from the WAL, the backfill ends before the first of them.
     fn check_backfill(&mut self) -> eyre::Result<()> {
-        let backfill_job_factory =
-            BackfillJobFactory::new(self.evm_config.clone(), self.provider.clone());
-        match self.initial_exex_head.block.number.cmp(&self.initial_local_head.number) {
+        let backfill_to = self
+            .replay
+            .first_block(&self.provider)?
+            .map_or(self.initial_local_head.number, |first| first.saturating_sub(1));
+        match self.exex_head.number.cmp(&backfill_to) {
             std::cmp::Ordering::Less => {
                 // ExEx is behind the node head, start backfill
                 debug!(target: "exex::notifications", "ExEx is behind the node head and on the canonical chain, starting backfill");
-                let backfill = backfill_job_factory
-                    .backfill(
-                        self.initial_exex_head.block.number + 1..=self.initial_local_head.number,
-                    )
-                    .into_stream();
-                self.backfill_job = Some(backfill);
+                self.backfill(self.exex_head.number + 1..=backfill_to);
             }
             std::cmp::Ordering::Equal => {
                 debug!(target: "exex::notifications", "ExEx is at the node head");
@@ -382,6 +590,97 @@ where
 
         Ok(())
     }
+
+    /// Starts a backfill job for the given range.
+    fn backfill(&mut self, range: RangeInclusive<BlockNumber>) {
+        let backfill_job_factory =
+            BackfillJobFactory::new(self.evm_config.clone(), self.provider.clone());
+        self.backfill_job = Some(backfill_job_factory.backfill(range).into_stream());
+    }
+
+    /// Returns `true` if the chain extends the delivered blocks or contains the last of them.
+    fn connects(&self, chain: &Chain<E::Primitives>) -> bool {
+        chain.block_number(self.exex_head.hash) == Some(self.exex_head.number) ||
+            (chain.first().number() == self.exex_head.number + 1 &&
+                chain.first().parent_hash() == self.exex_head.hash)
+    }
+
+    /// Returns the blocks of the reverted chain that were delivered, and marks them as no longer
+    /// delivered.
+    fn on_revert(&mut self, old: Arc<Chain<E::Primitives>>) -> Option<Arc<Chain<E::Primitives>>> {
+        if old.block_number(self.exex_head.hash) != Some(self.exex_head.number) {
+            debug!(target: "exex::notifications", range = ?old.range(), exex_head = ?self.exex_head, "Skipping revert of blocks that were not delivered");
+            return None
+        }
+
+        let old = if old.tip().number() > self.exex_head.number {
+            Arc::new(blocks_up_to(Arc::unwrap_or_clone(old), self.exex_head.number))
+        } else {
+            old
+        };
+        self.exex_head = (old.first().parent_hash(), old.first().number() - 1).into();
+        Some(old)
+    }
+
+    /// Returns the blocks of the committed chain that were not delivered yet, and marks them as
+    /// delivered.
+    ///
+    /// If the chain does not connect to the delivered blocks, it's stored to be emitted after the
+    /// gap before it is backfilled or the conflicting delivered blocks are reverted.
+    fn on_commit(&mut self, new: Arc<Chain<E::Primitives>>) -> Option<Arc<Chain<E::Primitives>>> {
+        if new.tip().number() <= self.exex_head.number {
+            debug!(target: "exex::notifications", range = ?new.range(), exex_head = ?self.exex_head, "Skipping commit of delivered blocks");
+            return None
+        }
+
+        if new.first().number() > self.exex_head.number + 1 {
+            debug!(target: "exex::notifications", range = ?new.range(), exex_head = ?self.exex_head, "Backfilling blocks before the committed chain");
+            self.backfill(self.exex_head.number + 1..=new.first().number() - 1);
+            self.pending_chain = Some(new);
+            return None
+        }
+
+        if !self.connects(&new) {
+            self.pending_chain = Some(new);
+            return None
+        }
+
+        let new = if new.first().number() <= self.exex_head.number {
+            Arc::new(blocks_after(Arc::unwrap_or_clone(new), self.exex_head.number))
+        } else {
+            new
+        };
+        self.exex_head = new.tip().num_hash();
+        Some(new)
+    }
+
+    /// Reverts the delivered blocks that conflict with the given committed chain using their
+    /// committed notification from the WAL.
+    fn revert_conflicting(
+        &mut self,
+        new: &Chain<E::Primitives>,
+    ) -> eyre::Result<Arc<Chain<E::Primitives>>> {
+        let notification = self
+            .wal_handle
+            .get_committed_notification_by_block_hash(&self.exex_head.hash)?
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "committed chain {:?} conflicts with delivered block {:?} that is not in the WAL",
+                    new.range(),
+                    self.exex_head
+                )
+            })?;
+        let old = notification.committed_chain().unwrap();
+        debug!(target: "exex::notifications", range = ?new.range(), exex_head = ?self.exex_head, "Reverting delivered blocks that conflict with the committed chain");
+
+        let old = if old.tip().number() > self.exex_head.number {
+            Arc::new(blocks_up_to(Arc::unwrap_or_clone(old), self.exex_head.number))
+        } else {
+            old
+        };
+        self.exex_head = (old.first().parent_hash(), old.first().number() - 1).into();
+        Ok(old)
+    }
 }
 
 impl<P, E> Stream for ExExNotificationsWithHead<P, E>
@@ -410,54 +709,196 @@ where
             this.pending_check_backfill = false;
         }
 
-        // 3. If backfill is in progress yield new notifications
-        if let Some(backfill_job) = &mut this.backfill_job {
-            debug!(target: "exex::notifications", "Polling backfill job");
-            if let Some(chain) = ready!(backfill_job.poll_next_unpin(cx)).transpose()? {
-                debug!(target: "exex::notifications", range = ?chain.range(), "Backfill job returned a chain");
-                return Poll::Ready(Some(Ok(ExExNotification::ChainCommitted {
-                    new: Arc::new(chain),
-                })))
+        loop {
+            // 3. If backfill is in progress yield new notifications
+            if let Some(backfill_job) = &mut this.backfill_job {
+                debug!(target: "exex::notifications", "Polling backfill job");
+                if let Some(chain) = ready!(backfill_job.poll_next_unpin(cx)).transpose()? {
+                    debug!(target: "exex::notifications", range = ?chain.range(), "Backfill job returned a chain");
+                    if !this.connects(&chain) {
+                        return Poll::Ready(Some(Err(eyre::eyre!(
+                            "backfilled chain {:?} does not connect to delivered block {:?}",
+                            chain.range(),
+                            this.exex_head
+                        ))))
+                    }
+                    this.exex_head = chain.tip().num_hash();
+                    return Poll::Ready(Some(Ok(ExExNotification::ChainCommitted {
+                        new: Arc::new(chain),
+                    })))
+                }
+
+                // Backfill job is done, remove it
+                this.backfill_job = None;
             }
 
-            // Backfill job is done, remove it
-            this.backfill_job = None;
-        }
+            // 4. Emit the committed chain that was waiting for the backfill or for the revert of
+            // the conflicting delivered blocks
+            if let Some(new) = this.pending_chain.take() {
+                if new.first().number() <= this.exex_head.number + 1 && !this.connects(&new) {
+                    let old = this.revert_conflicting(&new)?;
+                    this.pending_chain = Some(new);
+                    return Poll::Ready(Some(Ok(ExExNotification::ChainReverted { old })))
+                }
+                if let Some(new) = this.on_commit(new) {
+                    return Poll::Ready(Some(Ok(ExExNotification::ChainCommitted { new })))
+                }
+                continue
+            }
 
-        // 4. Otherwise advance the regular event stream
-        loop {
-            let Some(notification) = ready!(this.notifications.poll_recv(cx)) else {
-                return Poll::Ready(None)
+            // 5. Otherwise replay the chains from the WAL and advance the regular event stream,
+            // emitting only blocks that were not delivered yet and reverts of blocks that were
+            // delivered
+            let notification = match this.replay.next(&this.provider, &this.wal_handle)? {
+                Some(new) => ExExNotification::ChainCommitted { new },
+                None => {
+                    let Some(notification) = ready!(this.notifications.poll_recv(cx)) else {
+                        return Poll::Ready(None)
+                    };
+                    notification
+                }
             };
 
-            // 5. In case the exex is ahead of the new tip, we must skip it
-            if let Some(committed) = notification.committed_chain() {
-                // inclusive check because we should start with `exex.head + 1`
-                if this.initial_exex_head.block.number >= committed.tip().number() {
-                    continue
+            let (old, new) = match notification {
+                ExExNotification::ChainCommitted { new } => (None, Some(new)),
+                ExExNotification::ChainReorged { old, new } => (Some(old), Some(new)),
+                ExExNotification::ChainReverted { old } => (Some(old), None),
+                // notifications without a chain, like the shutdown notification, are passed
+                // through
+                notification => return Poll::Ready(Some(Ok(notification))),
+            };
+
+            let old = old.and_then(|old| this.on_revert(old));
+            let new = new.and_then(|new| this.on_commit(new));
+            let notification = match (old, new) {
+                (Some(old), Some(new)) => ExExNotification::ChainReorged { old, new },
+                (Some(old), None) => ExExNotification::ChainReverted { old },
+                (None, Some(new)) => ExExNotification::ChainCommitted { new },
+                (None, None) => continue,
+            };
+            return Poll::Ready(Some(Ok(notification)))
+        }
+    }
+}
+
+/// Canonical chains of the WAL that are replayed before the live notifications.
+///
+/// The notifications to replay are selected using the block cache of the WAL and read one at a
+/// time when they are replayed, so the WAL is not loaded into memory at once.
+#[derive(Debug, Default)]
+struct WalReplay {
+    /// The file IDs of the notifications to replay with their committed blocks, ordered by the
+    /// first committed block.
+    notifications: VecDeque<(u32, Vec<BlockNumHash>)>,
+}
+
+impl WalReplay {
+    /// Creates a new replay of the notifications whose committed chain reaches the given block.
+    fn new<N: NodePrimitives>(wal_handle: &WalHandle<N>, from: BlockNumber) -> Self {
+        Self { notifications: wal_handle.committed_blocks_from(from).into() }
+    }
+
+    /// Returns the first block of the next chain to replay.
+    ///
+    /// Notifications whose first committed block is not canonical anymore are skipped.
+    fn first_block<P: BlockHashReader>(
+        &mut self,
+        provider: &P,
+    ) -> eyre::Result<Option<BlockNumber>> {
+        while let Some((_, blocks)) = self.notifications.front() {
+            let first = blocks[0];
+            if provider.block_hash(first.number)? == Some(first.hash) {
+                return Ok(Some(first.number))
+            }
+            self.notifications.pop_front();
+        }
+        Ok(None)
+    }
+
+    /// Reads the next notification from the WAL and returns its committed chain, trimmed to its
+    /// canonical blocks.
+    ///
+    /// Notifications that were reverted entirely or removed from the WAL in the meantime are
+    /// skipped, the blocks of removed notifications are backfilled instead.
+    fn next<P, N>(
+        &mut self,
+        provider: &P,
+        wal_handle: &WalHandle<N>,
+    ) -> eyre::Result<Option<Arc<Chain<N>>>>
+    where
+        P: BlockHashReader,
+        N: NodePrimitives,
+    {
+        while let Some((file_id, blocks)) = self.notifications.pop_front() {
+            // Blocks after a reverted block were reverted as well, so the canonical blocks of the
+            // chain are its first blocks
+            let mut canonical_tip = None;
+            for block in blocks {
+                if provider.block_hash(block.number)? != Some(block.hash) {
+                    break
                 }
+                canonical_tip = Some(block.number);
             }
+            let Some(canonical_tip) = canonical_tip else { continue };
+
+            let Some(chain) = wal_handle
+                .read_notification(file_id)?
+                .and_then(|notification| notification.committed_chain())
+            else {
+                debug!(target: "exex::notifications", file_id, "Skipping replay of notification that was removed from the WAL");
+                continue
+            };
 
-            return Poll::Ready(Some(Ok(notification)))
+            if canonical_tip == chain.tip().number() {
+                return Ok(Some(chain))
+            }
+            return Ok(Some(Arc::new(blocks_up_to(Arc::unwrap_or_clone(chain), canonical_tip))))
         }
+        Ok(None)
     }
 }
 
+/// Returns the chain with only the blocks after the given block number.
+fn blocks_after<N: NodePrimitives>(chain: Chain<N>, number: BlockNumber) -> Chain<N> {
+    let (blocks, mut execution_outcome, _) = chain.into_inner();
+    if execution_outcome.block_number_to_index(number + 1).is_some() {
+        execution_outcome = execution_outcome.split_at(number + 1).1;
+    }
+    Chain::new(
+        blocks.into_blocks().filter(|block| block.number() > number),
+        execution_outcome,
+        None,
+    )
+}
+
+/// Returns the chain with only the blocks up to and including the given block number.
+fn blocks_up_to<N: NodePrimitives>(chain: Chain<N>, number: BlockNumber) -> Chain<N> {
+    let (blocks, mut execution_outcome, _) = chain.into_inner();
+    execution_outcome.revert_to(number);
+    Chain::new(
+        blocks.into_blocks().filter(|block| block.number() <= number),
+        execution_outcome,
+        None,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::Wal;
     use alloy_consensus::Header;
     use alloy_eips::BlockNumHash;
+    use alloy_primitives::B256;
     use eyre::OptionExt;
     use futures::StreamExt;
     use reth_db_common::init::init_genesis;
     use reth_ethereum_primitives::Block;
     use reth_evm_ethereum::EthEvmConfig;
-    use reth_primitives_traits::Block as _;
+    use reth_primitives_traits::{Block as _, RecoveredBlock, SealedBlock};
     use reth_provider::{
-        providers::BlockchainProvider, test_utils::create_test_provider_factory, BlockWriter,
-        Chain, DatabaseProviderFactory, StorageLocation,
+        providers::BlockchainProvider,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        BlockWriter, Chain, DatabaseProviderFactory, StorageLocation,
     };
     use reth_testing_utils::generators::{self, random_block, BlockParams};
     use tokio::sync::mpsc;
@@ -754,4 +1195,342 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn exex_notifications_replay_from() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+
+        let block_1 = random_block(
+            &mut rng,
+            1,
+            BlockParams { parent: Some(genesis_hash), tx_count: Some(0), ..Default::default() },
+        );
+        let block_2 = random_block(
+            &mut rng,
+            2,
+            BlockParams { parent: Some(block_1.hash()), tx_count: Some(0), ..Default::default() },
+        );
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw.insert_block(block_1.clone().try_recover()?, StorageLocation::Database)?;
+        provider_rw.insert_block(block_2.clone().try_recover()?, StorageLocation::Database)?;
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider::new(provider_factory)?;
+
+        // The WAL contains the canonical block 2 and a fork of it
+        fn committed(block: &SealedBlock<Block>) -> eyre::Result<ExExNotification> {
+            Ok(ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(
+                    vec![block.clone().try_recover()?],
+                    Default::default(),
+                    None,
+                )),
+            })
+        }
+        let block_2_notification = committed(&block_2)?;
+        wal.commit(&block_2_notification)?;
+        let fork_block_2 = random_block(
+            &mut rng,
+            2,
+            BlockParams { parent: Some(block_1.hash()), ..Default::default() },
+        );
+        wal.commit(&committed(&fork_block_2)?)?;
+
+        // The live notifications repeat block 2 and continue with block 3
+        let block_3_notification = committed(&random_block(
+            &mut rng,
+            3,
+            BlockParams { parent: Some(block_2.hash()), ..Default::default() },
+        ))?;
+        let (notifications_tx, notifications_rx) = mpsc::channel(2);
+        notifications_tx.send(block_2_notification.clone()).await?;
+        notifications_tx.send(block_3_notification.clone()).await?;
+
+        let mut notifications = ExExNotifications::new(
+            block_2.num_hash(),
+            provider.clone(),
+            EthEvmConfig::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        )
+        .replay_from(1)?;
+
+        // Block 1 is not in the WAL, so it's backfilled
+        assert_eq!(
+            notifications.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted {
+                new: Arc::new(
+                    BackfillJobFactory::new(EthEvmConfig::mainnet(), provider)
+                        .backfill(1..=1)
+                        .next()
+                        .ok_or_eyre("failed to backfill")??
+                )
+            })
+        );
+
+        // Block 2 is replayed from the WAL, without the fork
+        assert_eq!(notifications.next().await.transpose()?, Some(block_2_notification));
+
+        // The repeated block 2 is skipped
+        assert_eq!(notifications.next().await.transpose()?, Some(block_3_notification));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exex_notifications_replay_from_finalized_wal() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (provider, blocks) = provider_with_blocks(&mut rng, 2)?;
+        let notification = |block: &RecoveredBlock<Block>| ExExNotification::ChainCommitted {
+            new: chain(std::slice::from_ref(block)),
+        };
+        wal.commit(&notification(&blocks[1]))?;
+        let block_2_notification = notification(&blocks[2]);
+        wal.commit(&block_2_notification)?;
+
+        let (_notifications_tx, notifications_rx) = mpsc::channel(1);
+        let mut notifications = ExExNotifications::new(
+            blocks[2].num_hash(),
+            provider.clone(),
+            EthEvmConfig::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        )
+        .replay_from(1)?;
+
+        // The notification of block 1 is removed from the WAL after the replay was started, so
+        // the block is backfilled instead
+        wal.finalize(blocks[1].num_hash())?;
+        assert_eq!(
+            notifications.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted {
+                new: Arc::new(
+                    BackfillJobFactory::new(EthEvmConfig::mainnet(), provider)
+                        .backfill(1..=1)
+                        .next()
+                        .ok_or_eyre("failed to backfill")??
+                )
+            })
+        );
+
+        // Block 2 is read from the WAL when it's replayed
+        assert_eq!(notifications.next().await.transpose()?, Some(block_2_notification));
+
+        Ok(())
+    }
+
+    /// Returns `count` random blocks on top of the given parent.
+    fn random_blocks(
+        rng: &mut impl rand::Rng,
+        mut parent: BlockNumHash,
+        count: u64,
+    ) -> eyre::Result<Vec<RecoveredBlock<Block>>> {
+        let mut blocks = Vec::new();
+        for _ in 0..count {
+            let block = random_block(
+                rng,
+                parent.number + 1,
+                BlockParams { parent: Some(parent.hash), tx_count: Some(0), ..Default::default() },
+            );
+            parent = block.num_hash();
+            blocks.push(block.try_recover()?);
+        }
+        Ok(blocks)
+    }
+
+    fn chain(blocks: &[RecoveredBlock<Block>]) -> Arc<Chain> {
+        Arc::new(Chain::new(blocks.to_vec(), Default::default(), None))
+    }
+
+    fn block_numbers(chain: &Chain) -> Vec<BlockNumber> {
+        chain.blocks().keys().copied().collect()
+    }
+
+    /// Creates a provider with `count` random blocks on top of genesis as the canonical chain,
+    /// and returns it together with the canonical blocks including genesis.
+    fn provider_with_blocks(
+        rng: &mut impl rand::Rng,
+        count: u64,
+    ) -> eyre::Result<(BlockchainProvider<MockNodeTypesWithDB>, Vec<RecoveredBlock<Block>>)> {
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+        let genesis = provider_factory
+            .block(genesis_hash.into())?
+            .ok_or_eyre("genesis block not found")?
+            .seal_slow()
+            .try_recover()?;
+        let blocks = random_blocks(rng, genesis.num_hash(), count)?;
+
+        let provider_rw = provider_factory.provider_rw()?;
+        for block in &blocks {
+            provider_rw.insert_block(block.clone(), StorageLocation::Database)?;
+        }
+        provider_rw.commit()?;
+
+        let mut canonical = vec![genesis];
+        canonical.extend(blocks);
+        Ok((BlockchainProvider::new(provider_factory)?, canonical))
+    }
+
+    /// Returns a stream with the given head over the given live notifications.
+    async fn notifications_with_head(
+        provider: BlockchainProvider<MockNodeTypesWithDB>,
+        wal: &Wal,
+        node_head: BlockNumHash,
+        exex_head: BlockNumHash,
+        live: Vec<ExExNotification>,
+    ) -> eyre::Result<
+        ExExNotificationsWithHead<BlockchainProvider<MockNodeTypesWithDB>, EthEvmConfig>,
+    > {
+        let (notifications_tx, notifications_rx) = mpsc::channel(live.len().max(1));
+        for notification in live {
+            notifications_tx.send(notification).await?;
+        }
+
+        Ok(ExExNotificationsWithoutHead::new(
+            node_head,
+            provider,
+            EthEvmConfig::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        )
+        .with_head(ExExHead { block: exex_head }))
+    }
+
+    #[tokio::test]
+    async fn exex_notifications_skip_delivered_blocks() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (provider, mut blocks) = provider_with_blocks(&mut rng, 2)?;
+        blocks.extend(random_blocks(&mut rng, blocks[2].num_hash(), 2)?);
+
+        // the head advanced while the backfill was running, the live notifications overlap with
+        // the backfilled blocks
+        let live = vec![
+            ExExNotification::ChainCommitted { new: chain(&blocks[1..2]) },
+            ExExNotification::ChainCommitted { new: chain(&blocks[2..4]) },
+            ExExNotification::ChainCommitted { new: chain(&blocks[4..5]) },
+        ];
+        let mut notifications = notifications_with_head(
+            provider,
+            &wal,
+            blocks[2].num_hash(),
+            blocks[0].num_hash(),
+            live,
+        )
+        .await?;
+
+        let mut delivered = Vec::new();
+        while let Some(notification) = notifications.next().await.transpose()? {
+            delivered.extend(block_numbers(&notification.committed_chain().unwrap()));
+        }
+        assert_eq!(delivered, vec![1, 2, 3, 4]);
+        assert_eq!(notifications.exex_head, blocks[4].num_hash());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exex_notifications_fill_gaps() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        // the node head at launch is the ExEx head, so the live notification doesn't connect to
+        // the ExEx head
+        let (provider, blocks) = provider_with_blocks(&mut rng, 3)?;
+        let live = vec![ExExNotification::ChainCommitted { new: chain(&blocks[3..4]) }];
+        let mut notifications = notifications_with_head(
+            provider,
+            &wal,
+            blocks[0].num_hash(),
+            blocks[0].num_hash(),
+            live,
+        )
+        .await?;
+
+        let mut delivered = Vec::new();
+        while let Some(notification) = notifications.next().await.transpose()? {
+            delivered.extend(block_numbers(&notification.committed_chain().unwrap()));
+        }
+        assert_eq!(delivered, vec![1, 2, 3]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exex_notifications_revert_only_delivered_blocks() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (provider, blocks) = provider_with_blocks(&mut rng, 2)?;
+        let fork = random_blocks(&mut rng, blocks[1].num_hash(), 2)?;
+        let orphaned = random_blocks(&mut rng, BlockNumHash::new(2, B256::random()), 1)?;
+        let live = vec![
+            // never delivered, must not be reverted
+            ExExNotification::ChainReverted { old: chain(&orphaned) },
+            ExExNotification::ChainReorged { old: chain(&blocks[2..3]), new: chain(&fork) },
+        ];
+        let mut notifications = notifications_with_head(
+            provider,
+            &wal,
+            blocks[2].num_hash(),
+            blocks[2].num_hash(),
+            live,
+        )
+        .await?;
+
+        let notification = notifications.next().await.transpose()?.unwrap();
+        assert_eq!(block_numbers(&notification.reverted_chain().unwrap()), vec![2]);
+        assert_eq!(block_numbers(&notification.committed_chain().unwrap()), vec![2, 3]);
+        assert_eq!(notifications.exex_head, fork[1].num_hash());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exex_notifications_revert_conflicting_blocks() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (provider, blocks) = provider_with_blocks(&mut rng, 2)?;
+        wal.commit(&ExExNotification::ChainCommitted { new: chain(&blocks[2..3]) })?;
+
+        // the fork replaces the delivered block 2 without reverting it
+        let fork = random_blocks(&mut rng, blocks[1].num_hash(), 2)?;
+        let live = vec![ExExNotification::ChainCommitted { new: chain(&fork) }];
+        let mut notifications = notifications_with_head(
+            provider,
+            &wal,
+            blocks[2].num_hash(),
+            blocks[2].num_hash(),
+            live,
+        )
+        .await?;
+
+        assert_eq!(
+            notifications.next().await.transpose()?,
+            Some(ExExNotification::ChainReverted { old: chain(&blocks[2..3]) })
+        );
+        assert_eq!(
+            notifications.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted { new: chain(&fork) })
+        );
+
+        Ok(())
+    }
 }
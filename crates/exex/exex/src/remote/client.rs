@@ -0,0 +1,162 @@
+use super::{decode_notification, encode_head, framed, HEAD_ACCEPTED, MAX_FRAME_LENGTH};
+use crate::ExExNotificationsStream;
+use futures::{Future, SinkExt, Stream, StreamExt};
+use reth_ethereum_primitives::EthPrimitives;
+use reth_exex_types::{ExExHead, ExExNotification};
+use reth_node_api::NodePrimitives;
+use std::{
+    fmt, io,
+    marker::PhantomData,
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::net::TcpStream;
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+/// A stream of [`ExExNotification`]s received from an [`ExExServer`](super::ExExServer) running
+/// on a remote node.
+///
+/// The stream connects lazily on the first poll. If it is configured with a head, the server
+/// replays the notifications that follow the head. If the head is no longer buffered by the
+/// server, the stream yields an error, and the missed blocks need to be backfilled before
+/// reconnecting. Without a head, only new notifications are received.
+///
+/// The stream yields an error and ends if the connection fails. Call
+/// [`RemoteExExNotifications::reconnect`] with the latest processed head to resume it.
+pub struct RemoteExExNotifications<N: NodePrimitives = EthPrimitives> {
+    /// Address of the server.
+    addr: SocketAddr,
+    /// Head sent to the server when connecting.
+    head: Option<ExExHead>,
+    /// The connection to the server.
+    connection: Connection,
+    _primitives: PhantomData<fn() -> N>,
+}
+
+impl<N: NodePrimitives> RemoteExExNotifications<N> {
+    /// Creates a new stream of notifications from the server at the given address, without a
+    /// head.
+    pub const fn new(addr: SocketAddr) -> Self {
+        Self { addr, head: None, connection: Connection::Idle, _primitives: PhantomData }
+    }
+
+    /// Returns the address of the server.
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Closes the current connection, and connects again with the given head on the next poll.
+    pub fn reconnect(&mut self, head: Option<ExExHead>) {
+        self.head = head;
+        self.connection = Connection::Idle;
+    }
+}
+
+impl<N: NodePrimitives> fmt::Debug for RemoteExExNotifications<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteExExNotifications")
+            .field("addr", &self.addr)
+            .field("head", &self.head)
+            .field("connection", &self.connection)
+            .finish()
+    }
+}
+
+impl<N: NodePrimitives> ExExNotificationsStream<N> for RemoteExExNotifications<N> {
+    fn set_without_head(&mut self) {
+        if self.head.is_some() {
+            self.reconnect(None);
+        }
+    }
+
+    fn set_with_head(&mut self, exex_head: ExExHead) {
+        if self.head.is_none() {
+            self.reconnect(Some(exex_head));
+        }
+    }
+
+    fn without_head(mut self) -> Self {
+        self.set_without_head();
+        self
+    }
+
+    fn with_head(mut self, exex_head: ExExHead) -> Self {
+        self.set_with_head(exex_head);
+        self
+    }
+}
+
+impl<N: NodePrimitives> Stream for RemoteExExNotifications<N> {
+    type Item = eyre::Result<ExExNotification<N>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match &mut this.connection {
+                Connection::Idle => {
+                    let (addr, head) = (this.addr, this.head);
+                    this.connection = Connection::Connecting(Box::pin(async move {
+                        let mut framed = framed(TcpStream::connect(addr).await?, MAX_FRAME_LENGTH);
+                        framed.send(encode_head(head)).await?;
+                        match framed.next().await.transpose()?.as_deref() {
+                            Some([HEAD_ACCEPTED]) => Ok(framed),
+                            Some(_) => Err(io::Error::other(
+                                "the server doesn't buffer the notifications after the head",
+                            )),
+                            None => Err(io::ErrorKind::UnexpectedEof.into()),
+                        }
+                    }));
+                }
+                Connection::Connecting(fut) => match ready!(fut.as_mut().poll(cx)) {
+                    Ok(framed) => this.connection = Connection::Connected(framed),
+                    Err(err) => {
+                        this.connection = Connection::Terminated;
+                        return Poll::Ready(Some(Err(err.into())))
+                    }
+                },
+                Connection::Connected(framed) => {
+                    return match ready!(framed.poll_next_unpin(cx)) {
+                        Some(Ok(frame)) => Poll::Ready(Some(decode_notification(&frame))),
+                        Some(Err(err)) => {
+                            this.connection = Connection::Terminated;
+                            Poll::Ready(Some(Err(err.into())))
+                        }
+                        None => {
+                            this.connection = Connection::Terminated;
+                            Poll::Ready(None)
+                        }
+                    }
+                }
+                Connection::Terminated => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+type ConnectFuture =
+    Pin<Box<dyn Future<Output = io::Result<Framed<TcpStream, LengthDelimitedCodec>>> + Send>>;
+
+/// Connection state of [`RemoteExExNotifications`].
+enum Connection {
+    /// Not connected yet.
+    Idle,
+    /// Connecting and sending the head.
+    Connecting(ConnectFuture),
+    /// Receiving notifications.
+    Connected(Framed<TcpStream, LengthDelimitedCodec>),
+    /// The connection was closed.
+    Terminated,
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Idle => f.write_str("Idle"),
+            Self::Connecting(_) => f.write_str("Connecting"),
+            Self::Connected(_) => f.write_str("Connected"),
+            Self::Terminated => f.write_str("Terminated"),
+        }
+    }
+}
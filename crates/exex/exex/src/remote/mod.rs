@@ -0,0 +1,215 @@
+//! Support for running ExExes on a different machine than the node.
+//!
+//! The node installs an [`ExExServer`] as a regular ExEx, which serves its notifications over TCP.
+//! Remote ExExes connect to it with [`RemoteExExNotifications`], a stream that implements
+//! [`ExExNotificationsStream`](crate::ExExNotificationsStream) just like the notifications of a
+//! local [`ExExContext`](crate::ExExContext).
+//!
+//! Every message is a length-delimited frame. After connecting, the client sends a single frame
+//! with its [`ExExHead`], which is empty if the client has no head. The server answers with a
+//! single byte frame that tells whether it can serve the notifications after the head, and then
+//! sends one frame per [`ExExNotification`], encoded the same way as in the [`Wal`](crate::Wal).
+//!
+//! The server is not authenticated, so it should only be reachable from trusted networks.
+
+mod client;
+pub use client::RemoteExExNotifications;
+
+mod server;
+pub use server::ExExServer;
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
+use bytes::{BufMut, Bytes, BytesMut};
+use reth_exex_types::{ExExHead, ExExNotification};
+use reth_node_api::NodePrimitives;
+use tokio::net::TcpStream;
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+/// Maximum size of a single encoded notification.
+const MAX_FRAME_LENGTH: usize = 256 * 1024 * 1024;
+
+/// Length of an encoded [`ExExHead`].
+const HEAD_LENGTH: usize = 8 + 32;
+
+/// Response of the server if it replays the notifications after the head of the client.
+const HEAD_ACCEPTED: u8 = 0;
+/// Response of the server if the head of the client is not buffered.
+const HEAD_UNKNOWN: u8 = 1;
+
+/// Wraps the stream into length-delimited frames of at most the given length.
+fn framed(stream: TcpStream, max_frame_length: usize) -> Framed<TcpStream, LengthDelimitedCodec> {
+    Framed::new(
+        stream,
+        LengthDelimitedCodec::builder().max_frame_length(max_frame_length).new_codec(),
+    )
+}
+
+/// Encodes the head sent by the client after connecting.
+fn encode_head(head: Option<ExExHead>) -> Bytes {
+    let mut buf = BytesMut::new();
+    if let Some(head) = head {
+        buf.put_u64(head.block.number);
+        buf.put_slice(head.block.hash.as_slice());
+    }
+    buf.freeze()
+}
+
+/// Decodes the head sent by the client after connecting.
+fn decode_head(frame: &[u8]) -> eyre::Result<Option<ExExHead>> {
+    match frame.len() {
+        0 => Ok(None),
+        HEAD_LENGTH => {
+            let (number, hash) = frame.split_at(8);
+            let number = u64::from_be_bytes(number.try_into().expect("8 bytes"));
+            Ok(Some(ExExHead::new(BlockNumHash::new(number, B256::from_slice(hash)))))
+        }
+        len => eyre::bail!("invalid ExEx head length: {len}"),
+    }
+}
+
+/// Encodes a notification into a MessagePack frame.
+fn encode_notification<N: NodePrimitives>(
+    notification: &ExExNotification<N>,
+) -> eyre::Result<Bytes> {
+    let notification =
+        reth_exex_types::serde_bincode_compat::ExExNotification::<N>::from(notification);
+    Ok(rmp_serde::encode::to_vec(&notification)?.into())
+}
+
+/// Decodes a notification from a MessagePack frame.
+fn decode_notification<N: NodePrimitives>(frame: &[u8]) -> eyre::Result<ExExNotification<N>> {
+    let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_, N> =
+        rmp_serde::decode::from_slice(frame)?;
+    Ok(notification.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExExNotificationsStream;
+    use futures::StreamExt;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::sync::Arc;
+
+    fn notifications() -> eyre::Result<Vec<ExExNotification>> {
+        let mut rng = generators::rng();
+        random_block_range(&mut rng, 0..=2, BlockRangeParams::default())
+            .into_iter()
+            .map(|block| {
+                Ok(ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(vec![block.try_recover()?], Default::default(), None)),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn head_roundtrip() -> eyre::Result<()> {
+        assert_eq!(decode_head(&encode_head(None))?, None);
+
+        let head = ExExHead::new(BlockNumHash::new(1, B256::random()));
+        assert_eq!(decode_head(&encode_head(Some(head)))?, Some(head));
+
+        assert!(decode_head(&[0; 8]).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn serve_notifications() -> eyre::Result<()> {
+        let notifications = notifications()?;
+
+        let server = ExExServer::bind("127.0.0.1:0").await?;
+        let addr = server.local_addr()?;
+        let (state, accept) = server.into_parts();
+        let accept = tokio::spawn(accept);
+
+        // the first notification is sent before anyone is connected
+        state.publish(&notifications[0])?;
+
+        // a client without a head only receives new notifications
+        let mut live = RemoteExExNotifications::<EthPrimitives>::new(addr);
+        // a client with a head receives the buffered notifications after its head
+        let mut replayed = RemoteExExNotifications::<EthPrimitives>::new(addr)
+            .with_head(ExExHead::new(notifications[0].committed_chain().unwrap().tip().num_hash()));
+        // wait for both clients to be connected
+        while state.connections() < 2 {
+            assert!(futures::poll!(live.next()).is_pending());
+            assert!(futures::poll!(replayed.next()).is_pending());
+            tokio::task::yield_now().await;
+        }
+
+        state.publish(&notifications[1])?;
+        state.publish(&notifications[2])?;
+
+        for stream in [&mut live, &mut replayed] {
+            assert_eq!(stream.next().await.transpose()?.as_ref(), Some(&notifications[1]));
+            assert_eq!(stream.next().await.transpose()?.as_ref(), Some(&notifications[2]));
+        }
+
+        // reconnecting with a head replays the missed notifications
+        let mut reconnected = RemoteExExNotifications::<EthPrimitives>::new(addr)
+            .with_head(ExExHead::new(notifications[1].committed_chain().unwrap().tip().num_hash()));
+        assert_eq!(reconnected.next().await.transpose()?.as_ref(), Some(&notifications[2]));
+
+        // connecting with a head that isn't buffered fails
+        let mut unknown = RemoteExExNotifications::<EthPrimitives>::new(addr)
+            .with_head(ExExHead::new(BlockNumHash::new(1, B256::random())));
+        assert!(unknown.next().await.unwrap().is_err());
+        assert!(unknown.next().await.is_none());
+
+        accept.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reject_connections_above_limit() -> eyre::Result<()> {
+        let notifications = notifications()?;
+
+        let server = ExExServer::bind("127.0.0.1:0").await?.with_max_connections(1);
+        let addr = server.local_addr()?;
+        let (state, accept) = server.into_parts();
+        let accept = tokio::spawn(accept);
+
+        let mut first = RemoteExExNotifications::<EthPrimitives>::new(addr);
+        while state.connections() < 1 {
+            assert!(futures::poll!(first.next()).is_pending());
+            tokio::task::yield_now().await;
+        }
+
+        // the second connection is closed before the head is accepted
+        let mut second = RemoteExExNotifications::<EthPrimitives>::new(addr);
+        assert!(second.next().await.unwrap().is_err());
+        assert!(second.next().await.is_none());
+
+        // the first connection is still served
+        state.publish(&notifications[0])?;
+        assert_eq!(first.next().await.transpose()?.as_ref(), Some(&notifications[0]));
+
+        accept.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn close_connections_on_shutdown() -> eyre::Result<()> {
+        let server = ExExServer::bind("127.0.0.1:0").await?;
+        let addr = server.local_addr()?;
+        let (state, accept) = server.into_parts();
+        let accept = tokio::spawn(accept);
+
+        let mut client = RemoteExExNotifications::<EthPrimitives>::new(addr);
+        while state.connections() < 1 {
+            assert!(futures::poll!(client.next()).is_pending());
+            tokio::task::yield_now().await;
+        }
+
+        // stopping the server closes the connection, even though the state is still alive
+        accept.abort();
+        assert!(client.next().await.is_none());
+        assert_eq!(state.connections(), 0);
+
+        Ok(())
+    }
+}
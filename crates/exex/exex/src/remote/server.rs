@@ -0,0 +1,288 @@
+use super::{
+    decode_head, encode_notification, framed, HEAD_ACCEPTED, HEAD_LENGTH, HEAD_UNKNOWN,
+    MAX_FRAME_LENGTH,
+};
+use crate::ExExContext;
+use alloy_eips::BlockNumHash;
+use bytes::Bytes;
+use futures::{Future, SinkExt, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
+use reth_exex_types::{ExExHead, ExExNotification};
+use reth_node_api::{FullNodeComponents, NodePrimitives};
+use reth_tracing::tracing::{debug, warn};
+use std::{
+    collections::VecDeque,
+    io,
+    net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::broadcast::{self, error::RecvError},
+    task::JoinSet,
+};
+
+/// Serves the [`ExExNotification`]s of the node to
+/// [`RemoteExExNotifications`](super::RemoteExExNotifications) clients over TCP.
+///
+/// The server is installed as a regular ExEx and forwards every notification it receives to all
+/// connected clients. It keeps the last [`ExExServer::with_replay_capacity`] notifications in
+/// memory, up to [`ExExServer::with_max_replay_bytes`], so that clients that reconnect with a head
+/// can catch up on what they missed. Clients that connect with a head that is not buffered are
+/// rejected.
+///
+/// The server reports every committed block as finished right away, so remote ExExes don't hold
+/// back pruning on the node. Remote ExExes that fall behind the replay buffer need to backfill from
+/// the node on their own.
+///
+/// At most [`ExExServer::with_max_connections`] clients are served at the same time, further
+/// connections are closed right away. All connections are closed when the server stops.
+///
+/// The server is not authenticated, so it should only be reachable from trusted networks.
+/// [`ExExServer::bind_localhost`] only accepts connections from the same machine.
+///
+/// ```no_run
+/// # use reth_exex::ExExServer;
+/// # use reth_node_api::FullNodeComponents;
+/// # async fn install<Node: FullNodeComponents>(ctx: reth_exex::ExExContext<Node>) -> eyre::Result<()> {
+/// let server = ExExServer::bind_localhost(10000).await?;
+/// server.run(ctx).await
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ExExServer {
+    /// Listener for incoming connections.
+    listener: TcpListener,
+    /// Maximum number of notifications kept for replaying.
+    replay_capacity: usize,
+    /// Maximum total size of the encoded notifications kept for replaying.
+    max_replay_bytes: usize,
+    /// Maximum number of clients served at the same time.
+    max_connections: usize,
+}
+
+impl ExExServer {
+    /// Default number of notifications kept for replaying.
+    pub const DEFAULT_REPLAY_CAPACITY: usize = 128;
+
+    /// Default maximum total size of the encoded notifications kept for replaying.
+    pub const DEFAULT_MAX_REPLAY_BYTES: usize = 512 * 1024 * 1024;
+
+    /// Default maximum number of clients served at the same time.
+    pub const DEFAULT_MAX_CONNECTIONS: usize = 16;
+
+    /// Binds the server to the given address.
+    ///
+    /// The server is not authenticated, so the address should only be reachable from trusted
+    /// networks.
+    pub async fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr).await?,
+            replay_capacity: Self::DEFAULT_REPLAY_CAPACITY,
+            max_replay_bytes: Self::DEFAULT_MAX_REPLAY_BYTES,
+            max_connections: Self::DEFAULT_MAX_CONNECTIONS,
+        })
+    }
+
+    /// Binds the server to the given port on the loopback interface.
+    pub async fn bind_localhost(port: u16) -> io::Result<Self> {
+        Self::bind((Ipv4Addr::LOCALHOST, port)).await
+    }
+
+    /// Sets the number of notifications kept for replaying to reconnecting clients.
+    ///
+    /// This is also the number of notifications a client can fall behind before it is
+    /// disconnected.
+    pub fn with_replay_capacity(mut self, replay_capacity: usize) -> Self {
+        self.replay_capacity = replay_capacity.max(1);
+        self
+    }
+
+    /// Sets the maximum total size of the encoded notifications kept for replaying. The oldest
+    /// notifications are dropped first once it is exceeded.
+    pub const fn with_max_replay_bytes(mut self, max_replay_bytes: usize) -> Self {
+        self.max_replay_bytes = max_replay_bytes;
+        self
+    }
+
+    /// Sets the maximum number of clients served at the same time. Connections beyond it are
+    /// closed right away.
+    pub const fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Returns the address the server is listening on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves the notifications of the given ExEx context until the node shuts down.
+    pub async fn run<Node: FullNodeComponents>(
+        self,
+        mut ctx: ExExContext<Node>,
+    ) -> eyre::Result<()> {
+        let (state, accept) = self.into_parts();
+        let accept = ctx.task_executor().spawn(accept);
+
+        let res = async {
+            while let Some(notification) = ctx.notifications.try_next().await? {
+                if let ExExNotification::Shutdown = notification {
+                    ctx.acknowledge_shutdown()?;
+                    break
+                }
+
+                state.publish(&notification)?;
+
+                if let Some(committed_chain) = notification.committed_chain() {
+                    ctx.send_finished_height(committed_chain.tip().num_hash())?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        accept.abort();
+        res
+    }
+
+    /// Splits the server into its shared state and the future accepting new connections.
+    ///
+    /// The future serves the connections as well, dropping it closes all of them.
+    pub(super) fn into_parts(self) -> (Arc<ServerState>, impl Future<Output = ()> + Send) {
+        let (sender, _) = broadcast::channel(self.replay_capacity);
+        let state = Arc::new(ServerState {
+            replay_capacity: self.replay_capacity,
+            max_replay_bytes: self.max_replay_bytes,
+            replay: Mutex::new(ReplayBuffer::default()),
+            sender,
+        });
+
+        let accept = {
+            let state = state.clone();
+            async move {
+                // aborts the connections when the accept future is dropped
+                let mut connections = JoinSet::new();
+                loop {
+                    match self.listener.accept().await {
+                        Ok((stream, peer)) => {
+                            while connections.try_join_next().is_some() {}
+                            if connections.len() >= self.max_connections {
+                                debug!(target: "exex::remote", %peer, max_connections = self.max_connections, "Rejected remote ExEx connection, too many connections");
+                                continue
+                            }
+                            debug!(target: "exex::remote", %peer, "Accepted remote ExEx connection");
+                            connections.spawn(state.clone().serve(stream, peer));
+                        }
+                        Err(err) => {
+                            warn!(target: "exex::remote", %err, "Failed to accept remote ExEx connection");
+                        }
+                    }
+                }
+            }
+        };
+
+        (state, accept)
+    }
+}
+
+/// State shared between the [`ExExServer`] and its connections.
+#[derive(Debug)]
+pub(super) struct ServerState {
+    /// Maximum number of notifications kept for replaying.
+    replay_capacity: usize,
+    /// Maximum total size of the encoded notifications kept for replaying.
+    max_replay_bytes: usize,
+    /// The last encoded notifications.
+    replay: Mutex<ReplayBuffer>,
+    /// Sends encoded notifications to the connections.
+    sender: broadcast::Sender<Bytes>,
+}
+
+/// The last encoded notifications, with the tips of their committed chains.
+#[derive(Debug, Default)]
+struct ReplayBuffer {
+    frames: VecDeque<(Option<BlockNumHash>, Bytes)>,
+    /// Total size of the frames.
+    size: usize,
+}
+
+impl ServerState {
+    /// Sends the notification to all connected clients.
+    pub(super) fn publish<N: NodePrimitives>(
+        &self,
+        notification: &ExExNotification<N>,
+    ) -> eyre::Result<()> {
+        let frame = encode_notification(notification)?;
+        let tip = notification.committed_chain().map(|chain| chain.tip().num_hash());
+
+        // hold the lock while sending, so that connecting clients don't miss the notification
+        let mut replay = self.replay.lock();
+        replay.size += frame.len();
+        replay.frames.push_back((tip, frame.clone()));
+        while replay.frames.len() > self.replay_capacity || replay.size > self.max_replay_bytes {
+            let Some((_, evicted)) = replay.frames.pop_front() else { break };
+            replay.size -= evicted.len();
+        }
+        // there may be no connected clients
+        let _ = self.sender.send(frame);
+
+        Ok(())
+    }
+
+    /// Returns the number of connected clients.
+    pub(super) fn connections(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Serves a single client connection.
+    async fn serve(self: Arc<Self>, stream: TcpStream, peer: SocketAddr) {
+        if let Err(err) = self.try_serve(stream).await {
+            debug!(target: "exex::remote", %peer, %err, "Remote ExEx connection closed");
+        }
+    }
+
+    async fn try_serve(&self, stream: TcpStream) -> eyre::Result<()> {
+        // the client only sends its head, so don't accept larger frames from it
+        let mut framed = framed(stream, HEAD_LENGTH);
+        let Some(head) = framed.next().await.transpose()? else { return Ok(()) };
+        let head = decode_head(&head)?;
+        framed.codec_mut().set_max_frame_length(MAX_FRAME_LENGTH);
+
+        let (replay, mut notifications) = {
+            let replay = self.replay.lock();
+            let frames = match head {
+                Some(head) => Self::replay_after(&replay, head),
+                None => Some(Vec::new()),
+            };
+            (frames, self.sender.subscribe())
+        };
+
+        let Some(replay) = replay else {
+            framed.send(Bytes::from_static(&[HEAD_UNKNOWN])).await?;
+            eyre::bail!("head {head:?} is not buffered")
+        };
+        framed.send(Bytes::from_static(&[HEAD_ACCEPTED])).await?;
+
+        for frame in replay {
+            framed.send(frame).await?;
+        }
+
+        loop {
+            match notifications.recv().await {
+                Ok(frame) => framed.send(frame).await?,
+                Err(RecvError::Closed) => return Ok(()),
+                Err(RecvError::Lagged(skipped)) => {
+                    eyre::bail!("client fell behind by {skipped} notifications")
+                }
+            }
+        }
+    }
+
+    /// Returns the buffered notifications that follow the notification that committed the head,
+    /// or `None` if the head isn't buffered.
+    fn replay_after(replay: &ReplayBuffer, head: ExExHead) -> Option<Vec<Bytes>> {
+        let start = replay.frames.iter().rposition(|(tip, _)| *tip == Some(head.block))? + 1;
+        Some(replay.frames.iter().skip(start).map(|(_, frame)| frame.clone()).collect())
+    }
+}
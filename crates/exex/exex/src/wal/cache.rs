@@ -1,6 +1,6 @@
 use std::{
     cmp::Reverse,
-    collections::{BinaryHeap, HashSet},
+    collections::{BTreeMap, BinaryHeap, HashSet},
 };
 
 use alloy_consensus::BlockHeader;
@@ -92,6 +92,34 @@ impl BlockCache {
         self.committed_blocks.get(block_hash).map(|entry| entry.0)
     }
 
+    /// Returns the committed blocks of the notifications whose committed chain reaches the given
+    /// block number, along with the file ID of the notification.
+    ///
+    /// The blocks of each notification are sorted by number, and the notifications by their first
+    /// committed block.
+    pub(super) fn committed_blocks_from(
+        &self,
+        block_number: BlockNumber,
+    ) -> Vec<(u32, Vec<BlockNumHash>)> {
+        let mut files = BTreeMap::<u32, Vec<BlockNumHash>>::new();
+        for (file_id, block) in self.committed_blocks.values() {
+            files.entry(*file_id).or_default().push(block.block);
+        }
+
+        let mut files = files
+            .into_iter()
+            .filter_map(|(file_id, mut blocks)| {
+                blocks.sort_unstable_by_key(|block| block.number);
+                blocks
+                    .last()
+                    .is_some_and(|tip| tip.number >= block_number)
+                    .then_some((file_id, blocks))
+            })
+            .collect::<Vec<_>>();
+        files.sort_by_key(|(file_id, blocks)| (blocks[0].number, *file_id));
+        files
+    }
+
     /// Inserts the blocks from the notification into the cache with the given file ID.
     pub(super) fn insert_notification_blocks_with_file_id<N: NodePrimitives>(
         &mut self,
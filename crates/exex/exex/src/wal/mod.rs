@@ -19,8 +19,9 @@ use std::{
     },
 };
 
+use crate::{BackfillCheckpointResult, BackfillCheckpoints};
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use parking_lot::{RwLock, RwLockReadGuard};
 use reth_exex_types::ExExNotification;
 use reth_tracing::tracing::{debug, instrument};
@@ -216,6 +217,11 @@ impl<N> WalHandle<N>
 where
     N: NodePrimitives,
 {
+    /// Returns the [`BackfillCheckpoints`] stored in the `backfill` subdirectory of the WAL.
+    pub fn backfill_checkpoints(&self) -> BackfillCheckpointResult<BackfillCheckpoints> {
+        BackfillCheckpoints::new(self.wal.storage.path().join("backfill"))
+    }
+
     /// Returns the notification for the given committed block hash if it exists.
     pub fn get_committed_notification_by_block_hash(
         &self,
@@ -231,6 +237,30 @@ where
             .read_notification(file_id)
             .map(|entry| entry.map(|(notification, _)| notification))
     }
+
+    /// Returns the committed blocks of the notifications whose committed chain reaches the given
+    /// block number, grouped by notification and ordered by the first committed block.
+    ///
+    /// The notifications are not read, so they can be read one by one with
+    /// [`Self::read_notification`].
+    pub(crate) fn committed_blocks_from(
+        &self,
+        block_number: BlockNumber,
+    ) -> Vec<(u32, Vec<BlockNumHash>)> {
+        self.wal.block_cache().committed_blocks_from(block_number)
+    }
+
+    /// Reads the notification with the given file ID, if it wasn't removed by finalization.
+    pub(crate) fn read_notification(&self, file_id: u32) -> WalResult<Option<ExExNotification<N>>> {
+        Ok(self.wal.storage.read_notification(file_id)?.map(|(notification, _)| notification))
+    }
+
+    /// Returns an iterator over all notifications in the WAL, in the order they were committed.
+    pub fn iter_notifications(
+        &self,
+    ) -> WalResult<Box<dyn Iterator<Item = WalResult<ExExNotification<N>>> + '_>> {
+        self.wal.iter_notifications()
+    }
 }
 
 #[cfg(test)]
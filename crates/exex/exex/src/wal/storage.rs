@@ -36,6 +36,11 @@ where
         Ok(Self { path: path.as_ref().to_path_buf(), _pd: std::marker::PhantomData })
     }
 
+    /// Returns the path to the WAL directory.
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
     fn file_path(&self, id: u32) -> PathBuf {
         self.path.join(format!("{id}.{FILE_EXTENSION}"))
     }
@@ -5,8 +5,11 @@ use reth_execution_types::Chain;
 use reth_primitives_traits::NodePrimitives;
 
 /// Notifications sent to an `ExEx`.
+///
+/// New variants may be added in the future, so matches on the notification need a wildcard arm.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum ExExNotification<N: NodePrimitives = reth_chain_state::EthPrimitives> {
     /// Chain got committed without a reorg, and only the new chain is returned.
     ChainCommitted {
@@ -25,6 +28,11 @@ pub enum ExExNotification<N: NodePrimitives = reth_chain_state::EthPrimitives> {
         /// The old chain before reversion.
         old: Arc<Chain<N>>,
     },
+    /// The node is shutting down.
+    ///
+    /// This is the last notification sent to the `ExEx`. The `ExEx` should flush any pending work
+    /// and acknowledge the shutdown by sending an `ExExEvent::ShutdownAcknowledged`.
+    Shutdown,
 }
 
 impl<N: NodePrimitives> ExExNotification<N> {
@@ -33,7 +41,7 @@ impl<N: NodePrimitives> ExExNotification<N> {
     pub fn committed_chain(&self) -> Option<Arc<Chain<N>>> {
         match self {
             Self::ChainCommitted { new } | Self::ChainReorged { old: _, new } => Some(new.clone()),
-            Self::ChainReverted { .. } => None,
+            Self::ChainReverted { .. } | Self::Shutdown => None,
         }
     }
 
@@ -42,7 +50,7 @@ impl<N: NodePrimitives> ExExNotification<N> {
     pub fn reverted_chain(&self) -> Option<Arc<Chain<N>>> {
         match self {
             Self::ChainReorged { old, new: _ } | Self::ChainReverted { old } => Some(old.clone()),
-            Self::ChainCommitted { .. } => None,
+            Self::ChainCommitted { .. } | Self::Shutdown => None,
         }
     }
 
@@ -52,11 +60,13 @@ impl<N: NodePrimitives> ExExNotification<N> {
     /// - For [`Self::ChainReverted`], it's [`Self::ChainCommitted`].
     /// - For [`Self::ChainReorged`], it's [`Self::ChainReorged`] with the new chain as the old
     ///   chain and the old chain as the new chain.
+    /// - For [`Self::Shutdown`], it's [`Self::Shutdown`].
     pub fn into_inverted(self) -> Self {
         match self {
             Self::ChainCommitted { new } => Self::ChainReverted { old: new },
             Self::ChainReverted { old } => Self::ChainCommitted { new: old },
             Self::ChainReorged { old, new } => Self::ChainReorged { old: new, new: old },
+            Self::Shutdown => Self::Shutdown,
         }
     }
 }
@@ -106,6 +116,7 @@ pub(super) mod serde_bincode_compat {
         ChainCommitted { new: Chain<'a, N> },
         ChainReorged { old: Chain<'a, N>, new: Chain<'a, N> },
         ChainReverted { old: Chain<'a, N> },
+        Shutdown,
     }
 
     impl<'a, N> From<&'a super::ExExNotification<N>> for ExExNotification<'a, N>
@@ -126,6 +137,7 @@ pub(super) mod serde_bincode_compat {
                 super::ExExNotification::ChainReverted { old } => {
                     ExExNotification::ChainReverted { old: Chain::from(old.as_ref()) }
                 }
+                super::ExExNotification::Shutdown => ExExNotification::Shutdown,
             }
         }
     }
@@ -145,6 +157,7 @@ pub(super) mod serde_bincode_compat {
                 ExExNotification::ChainReverted { old } => {
                     Self::ChainReverted { old: Arc::new(old.into()) }
                 }
+                ExExNotification::Shutdown => Self::Shutdown,
             }
         }
     }
@@ -10,7 +10,11 @@
 
 type PeerId = alloy_primitives::B512;
 
-use std::{collections::HashMap, net::IpAddr, time::Instant};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 /// Determines whether or not the IP is globally routable.
 /// Should be replaced with [`IpAddr::is_global`](std::net::IpAddr::is_global) once it is stable.
@@ -159,6 +163,131 @@ impl BanList {
             self.banned_ips.insert(ip, until);
         }
     }
+
+    /// Returns all banned ips with the time until which they are banned, `None` if the ban is
+    /// indefinite.
+    pub fn banned_ips(&self) -> impl Iterator<Item = (IpAddr, Option<Instant>)> + '_ {
+        self.banned_ips.iter().map(|(ip, until)| (*ip, *until))
+    }
+
+    /// Returns all banned peers with the time until which they are banned, `None` if the ban is
+    /// indefinite.
+    pub fn banned_peers(&self) -> impl Iterator<Item = (PeerId, Option<Instant>)> + '_ {
+        self.banned_peers.iter().map(|(peer, until)| (*peer, *until))
+    }
+
+    /// Returns `true` if no peers or ips are banned.
+    pub fn is_empty(&self) -> bool {
+        self.banned_ips.is_empty() && self.banned_peers.is_empty()
+    }
+
+    /// Adds all bans of `other` to this ban list.
+    ///
+    /// If an entry is banned in both lists, the longer ban is kept.
+    pub fn extend(&mut self, other: &Self) {
+        fn merge<K: Copy + Eq + core::hash::Hash>(
+            target: &mut HashMap<K, Option<Instant>>,
+            source: &HashMap<K, Option<Instant>>,
+        ) {
+            for (key, until) in source {
+                target
+                    .entry(*key)
+                    .and_modify(|current| {
+                        *current = match (*current, *until) {
+                            (Some(a), Some(b)) => Some(a.max(b)),
+                            _ => None,
+                        }
+                    })
+                    .or_insert(*until);
+            }
+        }
+        merge(&mut self.banned_ips, &other.banned_ips);
+        merge(&mut self.banned_peers, &other.banned_peers);
+    }
+
+    /// Returns a snapshot of all bans that can be persisted across restarts.
+    ///
+    /// Timeouts are converted to wall clock time, bans that already expired are skipped.
+    pub fn to_persisted(&self) -> PersistedBanList {
+        let now = Instant::now();
+        let wall_now = SystemTime::now();
+        let to_unix = |until: &Option<Instant>| -> Option<Option<u64>> {
+            match until {
+                None => Some(None),
+                Some(until) => {
+                    let remaining = until.checked_duration_since(now)?;
+                    let until = (wall_now + remaining).duration_since(UNIX_EPOCH).ok()?;
+                    Some(Some(until.as_secs()))
+                }
+            }
+        };
+        PersistedBanList {
+            ips: self
+                .banned_ips
+                .iter()
+                .filter_map(|(ip, until)| Some(PersistedBan { id: *ip, until: to_unix(until)? }))
+                .collect(),
+            peers: self
+                .banned_peers
+                .iter()
+                .filter_map(|(peer, until)| {
+                    Some(PersistedBan { id: *peer, until: to_unix(until)? })
+                })
+                .collect(),
+        }
+    }
+
+    /// Creates a ban list from a persisted snapshot, see [`Self::to_persisted`].
+    ///
+    /// Bans that expired in the meantime are skipped.
+    pub fn from_persisted(persisted: PersistedBanList) -> Self {
+        let now = Instant::now();
+        let unix_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let to_instant = |until: Option<u64>| -> Option<Option<Instant>> {
+            match until {
+                None => Some(None),
+                Some(until) if until > unix_now => {
+                    Some(Some(now + Duration::from_secs(until - unix_now)))
+                }
+                Some(_) => None,
+            }
+        };
+
+        let mut ban_list = Self::default();
+        for PersistedBan { id, until } in persisted.ips {
+            if let Some(until) = to_instant(until) {
+                ban_list.ban_ip_with(id, until);
+            }
+        }
+        for PersistedBan { id, until } in persisted.peers {
+            if let Some(until) = to_instant(until) {
+                ban_list.ban_peer_with(id, until);
+            }
+        }
+        ban_list
+    }
+}
+
+/// A single entry of a [`PersistedBanList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedBan<T> {
+    /// The banned peer id or ip address.
+    pub id: T,
+    /// Unix timestamp in seconds until which the ban is active, `None` if the ban is indefinite.
+    pub until: Option<u64>,
+}
+
+/// A snapshot of a [`BanList`] that can be persisted across restarts.
+///
+/// Unlike [`BanList`], this uses wall clock time for ban timeouts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedBanList {
+    /// Banned ip addresses.
+    pub ips: Vec<PersistedBan<IpAddr>>,
+    /// Banned peer ids.
+    pub peers: Vec<PersistedBan<PeerId>>,
 }
 
 #[cfg(test)]
@@ -185,6 +314,43 @@ mod tests {
         assert!(!banlist.is_banned_ip(&ip));
     }
 
+    #[test]
+    fn persisted_roundtrip() {
+        let peer = PeerId::random();
+        let expired = PeerId::random();
+        let ip = IpAddr::from([1, 1, 1, 1]);
+        let now = Instant::now();
+
+        let mut banlist = BanList::default();
+        banlist.ban_peer(peer);
+        banlist.ban_ip_until(ip, now + Duration::from_secs(60 * 60));
+        banlist.ban_peer_until(expired, now);
+
+        let persisted = banlist.to_persisted();
+        assert_eq!(persisted.peers, vec![PersistedBan { id: peer, until: None }]);
+        assert_eq!(persisted.ips.len(), 1);
+
+        let restored = BanList::from_persisted(persisted);
+        assert!(restored.is_banned_peer(&peer));
+        assert!(restored.is_banned_ip(&ip));
+        assert!(!restored.is_banned_peer(&expired));
+    }
+
+    #[test]
+    fn extend_keeps_longer_ban() {
+        let ip = IpAddr::from([1, 1, 1, 1]);
+        let now = Instant::now();
+
+        let mut banlist = BanList::default();
+        banlist.ban_ip_until(ip, now);
+        let mut other = BanList::default();
+        other.ban_ip(ip);
+
+        banlist.extend(&other);
+        banlist.evict(now + Duration::from_secs(1));
+        assert!(banlist.is_banned_ip(&ip));
+    }
+
     #[test]
     fn cannot_ban_non_global() {
         let mut ip = IpAddr::from([0, 0, 0, 0]);
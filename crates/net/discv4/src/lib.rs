@@ -75,7 +75,11 @@ pub use config::{Discv4Config, Discv4ConfigBuilder};
 mod node;
 use node::{kad_key, NodeKey};
 
+mod metrics;
+use metrics::Discv4Metrics;
+
 mod table;
+pub use table::TableEntry;
 
 // reexport NodeRecord primitive
 pub use reth_network_peers::NodeRecord;
@@ -414,6 +418,14 @@ impl Discv4 {
         Ok(rx.await?)
     }
 
+    /// Returns a snapshot of all nodes in the routing table.
+    pub async fn table_entries(&self) -> Result<Vec<TableEntry>, Discv4Error> {
+        let (tx, rx) = oneshot::channel();
+        let cmd = Discv4Command::Table(tx);
+        self.to_service.send(cmd)?;
+        Ok(rx.await?)
+    }
+
     /// Terminates the spawned [`Discv4Service`].
     pub fn terminate(&self) {
         self.send_to_service(Discv4Command::Terminated);
@@ -503,6 +515,8 @@ pub struct Discv4Service {
     received_pongs: PongTable,
     /// Interval used to expire additionally tracked nodes
     expire_interval: Interval,
+    /// Discovery metrics.
+    metrics: Discv4Metrics,
 }
 
 impl Discv4Service {
@@ -603,6 +617,7 @@ impl Discv4Service {
             queued_events: Default::default(),
             received_pongs: Default::default(),
             expire_interval: tokio::time::interval(EXPIRE_DURATION),
+            metrics: Default::default(),
         }
     }
 
@@ -834,6 +849,9 @@ impl Discv4Service {
     ///
     /// Removes all listeners that are closed.
     fn notify(&mut self, update: DiscoveryUpdate) {
+        if self.metrics.on_update(&update) {
+            self.update_table_metrics();
+        }
         self.update_listeners.retain_mut(|listener| match listener.try_send(update.clone()) {
             Ok(()) => true,
             Err(err) => match err {
@@ -902,6 +920,38 @@ impl Discv4Service {
         self.kbuckets.buckets_iter().fold(0, |count, bucket| count + bucket.num_connected())
     }
 
+    /// Returns a snapshot of all nodes in the routing table.
+    pub fn table_entries(&self) -> Vec<TableEntry> {
+        let local_key = kad_key(self.local_node_record.id);
+        self.kbuckets
+            .iter_ref()
+            .map(|entry| {
+                let node = entry.node.value;
+                TableEntry {
+                    record: node.record,
+                    distance: local_key.log2_distance(entry.node.key),
+                    connected: entry.status.is_connected(),
+                    last_seen: node.last_seen.elapsed(),
+                    last_enr_seq: node.last_enr_seq,
+                    fork_id: node.fork_id,
+                    has_endpoint_proof: node.has_endpoint_proof,
+                }
+            })
+            .collect()
+    }
+
+    /// Updates the routing table gauges.
+    fn update_table_metrics(&self) {
+        let (mut nodes, mut connected, mut buckets) = (0, 0, 0);
+        for bucket in self.kbuckets.buckets_iter() {
+            let num_entries = bucket.num_entries();
+            nodes += num_entries;
+            connected += bucket.num_connected();
+            buckets += (num_entries > 0) as usize;
+        }
+        self.metrics.set_table(nodes, connected, buckets);
+    }
+
     /// Check if the peer has an active bond.
     fn has_bond(&self, remote_id: PeerId, remote_ip: IpAddr) -> bool {
         if let Some(timestamp) = self.received_pongs.last_pong(remote_id, remote_ip) {
@@ -1063,6 +1113,7 @@ impl Discv4Service {
     pub(crate) fn send_packet(&self, msg: Message, to: SocketAddr) -> B256 {
         let (payload, hash) = msg.encode(&self.secret_key);
         trace!(target: "discv4", r#type=?msg.msg_type(), ?to, ?hash, "sending packet");
+        self.metrics.sent_packets.increment(1);
         let _ = self.egress.try_send((payload, to)).map_err(|err| {
             debug!(
                 target: "discv4",
@@ -1272,7 +1323,7 @@ impl Discv4Service {
             return
         }
 
-        let PingRequest { node, reason, .. } = match self.pending_pings.entry(remote_id) {
+        let PingRequest { node, reason, sent_at, .. } = match self.pending_pings.entry(remote_id) {
             Entry::Occupied(entry) => {
                 {
                     let request = entry.get();
@@ -1288,6 +1339,7 @@ impl Discv4Service {
 
         // keep track of the pong
         self.received_pongs.on_pong(remote_id, remote_addr.ip());
+        self.metrics.ping_rtt.record(sent_at.elapsed());
 
         match reason {
             PingReason::InitialInsert => {
@@ -1714,6 +1766,7 @@ impl Discv4Service {
             // re-ping some peers
             while self.ping_interval.poll_tick(cx).is_ready() {
                 self.re_ping_oldest();
+                self.update_table_metrics();
             }
 
             if let Some(Poll::Ready(Some(ip))) =
@@ -1739,6 +1792,9 @@ impl Discv4Service {
                         let rx = self.update_stream();
                         let _ = tx.send(rx);
                     }
+                    Discv4Command::Table(tx) => {
+                        let _ = tx.send(self.table_entries());
+                    }
                     Discv4Command::BanPeer(node_id) => self.ban_node(node_id),
                     Discv4Command::Remove(node_id) => {
                         self.remove_node(node_id);
@@ -1783,9 +1839,11 @@ impl Discv4Service {
                     }
                     IngressEvent::BadPacket(from, err, data) => {
                         trace!(target: "discv4", ?from, %err, packet=?hex::encode(&data), "bad packet");
+                        self.metrics.bad_packets.increment(1);
                     }
                     IngressEvent::Packet(remote_addr, Packet { msg, node_id, hash }) => {
                         trace!(target: "discv4", r#type=?msg.msg_type(), from=?remote_addr,"received packet");
+                        self.metrics.received_packets.increment(1);
                         let event = match msg {
                             Message::Ping(ping) => {
                                 self.on_ping(ping, remote_addr, node_id, hash);
@@ -2053,6 +2111,7 @@ enum Discv4Command {
     Lookup { node_id: Option<PeerId>, tx: Option<NodeRecordSender> },
     SetLookupInterval(Duration),
     Updates(OneshotSender<ReceiverStream<DiscoveryUpdate>>),
+    Table(OneshotSender<Vec<TableEntry>>),
     Terminated,
 }
 
@@ -2506,6 +2565,25 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_table_entries() {
+        let (_, mut service) = create_discv4().await;
+
+        let node = NodeRecord::new(service.local_addr(), PeerId::random());
+        assert!(service.add_node(node));
+
+        let entries = service.table_entries();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.record, node);
+        assert_eq!(
+            entry.distance,
+            kad_key(*service.local_peer_id()).log2_distance(&kad_key(node.id))
+        );
+        assert!(!entry.has_endpoint_proof);
+        assert_eq!(entry.fork_id, None);
+    }
+
     // Bootstraps with mainnet boot nodes
     #[tokio::test(flavor = "multi_thread")]
     #[ignore]
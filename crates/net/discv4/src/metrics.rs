@@ -0,0 +1,63 @@
+//! Tracks the routing table and packet flow of [`Discv4`](crate::Discv4).
+
+use crate::DiscoveryUpdate;
+use metrics::{Counter, Gauge, Histogram};
+use reth_metrics::Metrics;
+
+/// Metrics tracked by the [`Discv4Service`](crate::Discv4Service).
+#[derive(Metrics, Clone)]
+#[metrics(scope = "discv4")]
+pub(crate) struct Discv4Metrics {
+    /// Number of nodes currently in the routing table.
+    table_nodes: Gauge,
+    /// Number of nodes in the routing table that are considered connected.
+    table_connected_nodes: Gauge,
+    /// Number of buckets of the routing table that hold at least one node.
+    occupied_buckets: Gauge,
+    /// Total number of nodes inserted into the routing table.
+    inserted_nodes: Counter,
+    /// Total number of nodes removed from the routing table.
+    removed_nodes: Counter,
+    /// Total number of [`ForkId`](reth_ethereum_forks::ForkId) updates received via EIP-868 ENR
+    /// responses.
+    enr_updates: Counter,
+    /// Total number of sent packets.
+    pub(crate) sent_packets: Counter,
+    /// Total number of received packets.
+    pub(crate) received_packets: Counter,
+    /// Total number of received packets that could not be decoded.
+    pub(crate) bad_packets: Counter,
+    /// Round trip time of a `Ping` answered by a `Pong`.
+    pub(crate) ping_rtt: Histogram,
+}
+
+impl Discv4Metrics {
+    /// Sets the routing table gauges.
+    pub(crate) fn set_table(&self, nodes: usize, connected: usize, occupied_buckets: usize) {
+        self.table_nodes.set(nodes as f64);
+        self.table_connected_nodes.set(connected as f64);
+        self.occupied_buckets.set(occupied_buckets as f64);
+    }
+
+    /// Records the given update and returns `true` if it changed the routing table.
+    pub(crate) fn on_update(&self, update: &DiscoveryUpdate) -> bool {
+        match update {
+            DiscoveryUpdate::Added(_) => {
+                self.inserted_nodes.increment(1);
+                true
+            }
+            DiscoveryUpdate::Removed(_) => {
+                self.removed_nodes.increment(1);
+                true
+            }
+            DiscoveryUpdate::EnrForkId(..) => {
+                self.enr_updates.increment(1);
+                false
+            }
+            DiscoveryUpdate::DiscoveredAtCapacity(_) => false,
+            DiscoveryUpdate::Batch(updates) => {
+                updates.iter().fold(false, |changed, update| self.on_update(update) || changed)
+            }
+        }
+    }
+}
@@ -1,7 +1,31 @@
 //! Additional support for tracking nodes.
 
-use reth_network_peers::PeerId;
-use std::{collections::HashMap, net::IpAddr, time::Instant};
+use reth_ethereum_forks::ForkId;
+use reth_network_peers::{NodeRecord, PeerId};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+/// A snapshot of a node in the routing table of [`Discv4`](crate::Discv4).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableEntry {
+    /// The node record of the node.
+    pub record: NodeRecord,
+    /// The log2 distance of the node to the local node, this is the index of its bucket.
+    pub distance: Option<u64>,
+    /// Whether the node is considered connected.
+    pub connected: bool,
+    /// Time elapsed since the node was last seen.
+    pub last_seen: Duration,
+    /// The last ENR sequence number of the node, if retrieved.
+    pub last_enr_seq: Option<u64>,
+    /// The [`ForkId`] of the node, if retrieved via EIP-868.
+    pub fork_id: Option<ForkId>,
+    /// Whether the endpoint of the node is proven.
+    pub has_endpoint_proof: bool,
+}
 
 /// Keeps track of nodes from which we have received a `Pong` message.
 #[derive(Debug, Clone, Default)]
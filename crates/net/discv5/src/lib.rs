@@ -13,7 +13,7 @@ use std::{
     fmt,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use ::enr::Enr;
@@ -146,6 +146,25 @@ impl Discv5 {
         self.discv5.ban_ip(ip, None);
     }
 
+    /// Adds the peer to the ban list until the given time, or indefinitely if `None`.
+    pub fn ban_peer_until(&self, peer_id: PeerId, until: Option<Instant>) {
+        match discv4_id_to_discv5_id(peer_id) {
+            Ok(node_id) => self.discv5.ban_node(
+                &node_id,
+                until.map(|until| until.saturating_duration_since(Instant::now())),
+            ),
+            Err(err) => error!(target: "net::discv5",
+                %err,
+                "failed to ban peer"
+            ),
+        }
+    }
+
+    /// Adds the ip to the ban list until the given time, or indefinitely if `None`.
+    pub fn ban_ip_until(&self, ip: IpAddr, until: Option<Instant>) {
+        self.discv5.ban_ip(ip, until.map(|until| until.saturating_duration_since(Instant::now())));
+    }
+
     /// Returns the [`NodeRecord`] of the local node.
     ///
     /// This includes the currently tracked external IP address of the node.
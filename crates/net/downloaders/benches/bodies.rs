@@ -0,0 +1,94 @@
+#![allow(missing_docs)]
+
+use alloy_primitives::B256;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use futures::StreamExt;
+use reth_chainspec::MAINNET;
+use reth_db::test_utils::{create_test_rw_db, create_test_static_files_dir};
+use reth_downloaders::{
+    bodies::{
+        bodies::BodiesDownloaderBuilder, test_utils::insert_headers,
+        DEFAULT_BODY_VALIDATION_THREADS,
+    },
+    test_utils::TestBodiesClient,
+};
+use reth_ethereum_consensus::EthBeaconConsensus;
+use reth_ethereum_primitives::{Block, BlockBody};
+use reth_network_p2p::bodies::downloader::BodyDownloader;
+use reth_primitives_traits::SealedHeader;
+use reth_provider::{
+    providers::StaticFileProvider, test_utils::MockNodeTypesWithDB, ProviderFactory,
+};
+use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+use std::{collections::HashMap, sync::Arc};
+use tokio::runtime::Runtime;
+
+/// Generates blocks that all have a non-empty body.
+fn full_bodies(num_blocks: u64) -> (Vec<SealedHeader>, HashMap<B256, BlockBody>) {
+    let mut rng = generators::rng();
+    let blocks = random_block_range(
+        &mut rng,
+        0..=num_blocks - 1,
+        BlockRangeParams { parent: Some(B256::ZERO), tx_count: 20..50, ..Default::default() },
+    );
+
+    let headers = blocks.iter().map(|block| block.clone_sealed_header()).collect();
+    let bodies = blocks.into_iter().map(|block| (block.hash(), block.into_body())).collect();
+    (headers, bodies)
+}
+
+/// Downloads and validates ranges of full bodies, with validation on the downloader task and
+/// offloaded to the validation pool.
+fn full_bodies_range(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("Bodies Downloader");
+    group.sample_size(10);
+
+    for num_blocks in [500, 2_000] {
+        let (headers, bodies) = full_bodies(num_blocks);
+
+        let db = create_test_rw_db();
+        insert_headers(db.db(), &headers);
+        let (_static_dir, static_dir_path) = create_test_static_files_dir();
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            db,
+            MAINNET.clone(),
+            StaticFileProvider::read_write(static_dir_path).unwrap(),
+        );
+
+        for validation_threads in [0, DEFAULT_BODY_VALIDATION_THREADS] {
+            let id =
+                BenchmarkId::new(format!("validation threads {validation_threads}"), num_blocks);
+            group.bench_function(id, |b| {
+                b.iter_batched(
+                    || {
+                        let client = TestBodiesClient::default()
+                            .with_bodies(bodies.clone())
+                            .with_max_batch_size(64);
+                        let mut downloader = BodiesDownloaderBuilder::default()
+                            .with_validation_threads(validation_threads)
+                            .build::<Block, _, _>(
+                                client,
+                                Arc::new(EthBeaconConsensus::new(MAINNET.clone())),
+                                factory.clone(),
+                            );
+                        downloader.set_download_range(0..=num_blocks - 1).unwrap();
+                        downloader
+                    },
+                    |mut downloader| {
+                        runtime.block_on(async {
+                            let mut downloaded = 0;
+                            while downloaded < num_blocks as usize {
+                                downloaded += downloader.next().await.unwrap().unwrap().len();
+                            }
+                        })
+                    },
+                    BatchSize::PerIteration,
+                )
+            });
+        }
+    }
+}
+
+criterion_group!(benches, full_bodies_range);
+criterion_main!(benches);
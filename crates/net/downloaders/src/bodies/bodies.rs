@@ -1,4 +1,9 @@
-use super::queue::BodiesRequestQueue;
+use super::{
+    queue::BodiesRequestQueue,
+    validation::{
+        BodyValidationPool, DEFAULT_BODY_VALIDATION_THREADS, DEFAULT_MAX_QUEUED_BODY_VALIDATIONS,
+    },
+};
 use crate::{bodies::task::TaskDownloader, metrics::BodyDownloaderMetrics};
 use alloy_consensus::BlockHeader;
 use alloy_primitives::BlockNumber;
@@ -511,6 +516,11 @@ pub struct BodiesDownloaderBuilder {
     pub max_buffered_blocks_size_bytes: usize,
     /// The maximum number of requests to send concurrently.
     pub concurrent_requests_range: RangeInclusive<usize>,
+    /// The number of threads used to validate downloaded bodies. If `0`, bodies are validated on
+    /// the downloader task.
+    pub validation_threads: usize,
+    /// The maximum number of responses that are queued for validation at once.
+    pub max_queued_validations: usize,
 }
 
 impl BodiesDownloaderBuilder {
@@ -535,6 +545,8 @@ impl Default for BodiesDownloaderBuilder {
             stream_batch_size: 1_000,
             max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
             concurrent_requests_range: 5..=100,
+            validation_threads: DEFAULT_BODY_VALIDATION_THREADS,
+            max_queued_validations: DEFAULT_MAX_QUEUED_BODY_VALIDATIONS,
         }
     }
 }
@@ -570,6 +582,20 @@ impl BodiesDownloaderBuilder {
         self
     }
 
+    /// Set the number of threads used to validate downloaded bodies.
+    ///
+    /// If `0`, bodies are validated on the downloader task.
+    pub const fn with_validation_threads(mut self, validation_threads: usize) -> Self {
+        self.validation_threads = validation_threads;
+        self
+    }
+
+    /// Set the maximum number of responses that are queued for validation at once.
+    pub const fn with_max_queued_validations(mut self, max_queued_validations: usize) -> Self {
+        self.max_queued_validations = max_queued_validations;
+        self
+    }
+
     /// Consume self and return the concurrent downloader.
     pub fn build<B, C, Provider>(
         self,
@@ -587,9 +613,13 @@ impl BodiesDownloaderBuilder {
             stream_batch_size,
             concurrent_requests_range,
             max_buffered_blocks_size_bytes,
+            validation_threads,
+            max_queued_validations,
         } = self;
         let metrics = BodyDownloaderMetrics::default();
-        let in_progress_queue = BodiesRequestQueue::new(metrics.clone());
+        let validation_pool = (validation_threads > 0)
+            .then(|| BodyValidationPool::new(validation_threads, max_queued_validations));
+        let in_progress_queue = BodiesRequestQueue::new(metrics.clone(), validation_pool);
         BodiesDownloader {
             client: Arc::new(client),
             consensus,
@@ -10,6 +10,9 @@ pub mod task;
 
 mod queue;
 mod request;
+mod validation;
+
+pub use validation::{DEFAULT_BODY_VALIDATION_THREADS, DEFAULT_MAX_QUEUED_BODY_VALIDATIONS};
 
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
@@ -1,4 +1,4 @@
-use super::request::BodiesRequestFuture;
+use super::{request::BodiesRequestFuture, validation::BodyValidationPool};
 use crate::metrics::BodyDownloaderMetrics;
 use alloy_consensus::BlockHeader;
 use alloy_primitives::BlockNumber;
@@ -24,6 +24,8 @@ pub(crate) struct BodiesRequestQueue<B: Block, C: BodiesClient<Body = B::Body>>
     inner: FuturesUnordered<BodiesRequestFuture<B, C>>,
     /// The downloader metrics.
     metrics: BodyDownloaderMetrics,
+    /// The pool to validate bodies on, if any.
+    validation_pool: Option<BodyValidationPool>,
     /// Last requested block number.
     pub(crate) last_requested_block_number: Option<BlockNumber>,
 }
@@ -34,8 +36,16 @@ where
     C: BodiesClient<Body = B::Body> + 'static,
 {
     /// Create new instance of request queue.
-    pub(crate) fn new(metrics: BodyDownloaderMetrics) -> Self {
-        Self { metrics, inner: Default::default(), last_requested_block_number: None }
+    pub(crate) fn new(
+        metrics: BodyDownloaderMetrics,
+        validation_pool: Option<BodyValidationPool>,
+    ) -> Self {
+        Self {
+            metrics,
+            validation_pool,
+            inner: Default::default(),
+            last_requested_block_number: None,
+        }
     }
 
     /// Returns `true` if the queue is empty.
@@ -72,7 +82,9 @@ where
 
         // Create request and push into the queue.
         self.inner.push(
-            BodiesRequestFuture::new(client, consensus, self.metrics.clone()).with_headers(request),
+            BodiesRequestFuture::new(client, consensus, self.metrics.clone())
+                .with_validation_pool(self.validation_pool.clone())
+                .with_headers(request),
         )
     }
 }
@@ -1,3 +1,4 @@
+use super::validation::{validate_bodies, BodyValidationPool, ValidatedBodies};
 use crate::metrics::{BodyDownloaderMetrics, ResponseMetrics};
 use alloy_consensus::BlockHeader;
 use alloy_primitives::B256;
@@ -5,7 +6,7 @@ use futures::{Future, FutureExt};
 use reth_consensus::{Consensus, ConsensusError};
 use reth_network_p2p::{
     bodies::{client::BodiesClient, response::BlockResponse},
-    error::{DownloadError, DownloadResult},
+    error::{DownloadError, DownloadResult, RequestError},
     priority::Priority,
 };
 use reth_network_peers::{PeerId, WithPeerId};
@@ -17,6 +18,8 @@ use std::{
     sync::Arc,
     task::{ready, Context, Poll},
 };
+use tokio::sync::oneshot;
+use tokio_util::sync::PollSemaphore;
 
 /// Body request implemented as a [Future].
 ///
@@ -25,7 +28,8 @@ use std::{
 /// will issue another request until all bodies are collected.
 ///
 /// It then proceeds to verify the downloaded bodies. In case of a validation error,
-/// the future will start over.
+/// the future will start over. If the future was created with a [`BodyValidationPool`], the
+/// bodies of a response are verified on the pool while the next request is already in flight.
 ///
 /// The future will filter out any empty headers (see [`alloy_consensus::Header::is_empty`]) from
 /// the request. If [`BodiesRequestFuture`] was initialized with all empty headers, no request will
@@ -51,6 +55,13 @@ pub(crate) struct BodiesRequestFuture<B: Block, C: BodiesClient<Body = B::Body>>
     fut: Option<C::Output>,
     /// Tracks how many bodies we requested in the last request.
     last_request_len: Option<usize>,
+    /// The pool to validate bodies on, and the semaphore bounding its queue. If unset, bodies are
+    /// validated inline.
+    validation_pool: Option<(BodyValidationPool, PollSemaphore)>,
+    /// Blocks of a response that wait for a free slot in the validation queue.
+    queued_validation: Option<(PeerId, Vec<BlockResponse<B>>)>,
+    /// Blocks of a response that are being validated on the pool.
+    pending_validation: Option<(PeerId, oneshot::Receiver<ValidatedBodies<B>>)>,
 }
 
 impl<B, C> BodiesRequestFuture<B, C>
@@ -73,9 +84,21 @@ where
             buffer: Default::default(),
             last_request_len: None,
             fut: None,
+            validation_pool: None,
+            queued_validation: None,
+            pending_validation: None,
         }
     }
 
+    /// Validates bodies on the given pool instead of inline.
+    pub(crate) fn with_validation_pool(mut self, pool: Option<BodyValidationPool>) -> Self {
+        self.validation_pool = pool.map(|pool| {
+            let permits = PollSemaphore::new(pool.permits());
+            (pool, permits)
+        });
+        self
+    }
+
     pub(crate) fn with_headers(mut self, headers: Vec<SealedHeader<B::Header>>) -> Self {
         self.buffer.reserve_exact(headers.len());
         self.pending_headers = VecDeque::from(headers);
@@ -144,8 +167,15 @@ where
             }))
         }
 
-        // Buffer block responses
-        self.try_buffer_blocks(bodies)?;
+        let blocks = self.take_blocks(bodies);
+
+        if self.validation_pool.is_some() {
+            // Validate the blocks on the pool and submit the next request right away, so that
+            // downloading the next bodies overlaps with the validation.
+            self.queued_validation = Some((peer_id, blocks));
+        } else {
+            self.on_validated_bodies(validate_bodies(self.consensus.as_ref(), blocks))?;
+        }
 
         // Submit next request if any
         if let Some(req) = self.next_request() {
@@ -157,51 +187,39 @@ where
         Ok(())
     }
 
-    /// Attempt to buffer body responses. Returns an error if body response fails validation.
-    /// Every body preceding the failed one will be buffered.
+    /// Pairs the bodies of a response with the pending headers.
     ///
-    /// This method removes headers from the internal collection.
-    /// If the response fails validation, then the header will be put back.
-    fn try_buffer_blocks(&mut self, bodies: Vec<C::Body>) -> DownloadResult<()>
+    /// This method removes the paired headers from the internal collection. If the bodies fail
+    /// validation, the headers are put back by [`Self::on_validated_bodies`].
+    fn take_blocks(&mut self, bodies: Vec<C::Body>) -> Vec<BlockResponse<B>>
     where
         C::Body: InMemorySize,
     {
         let bodies_capacity = bodies.capacity();
         let bodies_len = bodies.len();
         let mut bodies = bodies.into_iter().peekable();
+        let mut blocks = Vec::with_capacity(bodies_len);
 
         let mut total_size = bodies_capacity * mem::size_of::<C::Body>();
         while bodies.peek().is_some() {
-            let next_header = match self.pending_headers.pop_front() {
-                Some(header) => header,
-                None => return Ok(()), // no more headers
+            let Some(next_header) = self.pending_headers.pop_front() else {
+                break // no more headers
             };
 
             if next_header.is_empty() {
                 // increment empty block body metric
                 total_size += mem::size_of::<C::Body>();
-                self.buffer.push(BlockResponse::Empty(next_header));
+                blocks.push(BlockResponse::Empty(next_header));
             } else {
                 let next_body = bodies.next().unwrap();
 
                 // increment full block body metric
                 total_size += next_body.size();
 
-                let block = SealedBlock::from_sealed_parts(next_header, next_body);
-
-                if let Err(error) = self.consensus.validate_block_pre_execution(&block) {
-                    // Body is invalid, put the header back and return an error
-                    let hash = block.hash();
-                    let number = block.number();
-                    self.pending_headers.push_front(block.into_sealed_header());
-                    return Err(DownloadError::BodyValidation {
-                        hash,
-                        number,
-                        error: Box::new(error),
-                    })
-                }
-
-                self.buffer.push(BlockResponse::Full(block));
+                blocks.push(BlockResponse::Full(SealedBlock::from_sealed_parts(
+                    next_header,
+                    next_body,
+                )));
             }
         }
 
@@ -209,8 +227,32 @@ where
         self.response_metrics.response_size_bytes.set(total_size as f64);
         self.response_metrics.response_length.set(bodies_len as f64);
 
+        blocks
+    }
+
+    /// Buffers the validated blocks. Returns an error if a body failed validation.
+    ///
+    /// Every block preceding the invalid one is buffered, the headers of the invalid block and
+    /// the blocks following it are put back.
+    fn on_validated_bodies(&mut self, validated: ValidatedBodies<B>) -> DownloadResult<()> {
+        let ValidatedBodies { valid, invalid } = validated;
+        self.buffer.extend(valid);
+
+        if let Some((error, headers)) = invalid {
+            let (hash, number) = (headers[0].hash(), headers[0].number());
+            for header in headers.into_iter().rev() {
+                self.pending_headers.push_front(header);
+            }
+            return Err(DownloadError::BodyValidation { hash, number, error: Box::new(error) })
+        }
+
         Ok(())
     }
+
+    /// Returns `true` if there are blocks that wait for or undergo validation.
+    const fn is_validating(&self) -> bool {
+        self.queued_validation.is_some() || self.pending_validation.is_some()
+    }
 }
 
 impl<B, C> Future for BodiesRequestFuture<B, C>
@@ -224,10 +266,36 @@ where
         let this = self.get_mut();
 
         loop {
-            if this.pending_headers.is_empty() {
+            if this.pending_headers.is_empty() && !this.is_validating() {
                 return Poll::Ready(Ok(std::mem::take(&mut this.buffer)))
             }
 
+            // Wait for the blocks of the previous response to be validated, so that blocks are
+            // buffered in order.
+            if let Some((peer_id, rx)) = this.pending_validation.as_mut() {
+                let peer_id = *peer_id;
+                let validated = match ready!(rx.poll_unpin(cx)) {
+                    Ok(validated) => validated,
+                    Err(_) => return Poll::Ready(Err(RequestError::ChannelClosed.into())),
+                };
+                this.pending_validation = None;
+                if let Err(error) = this.on_validated_bodies(validated) {
+                    this.on_error(error, Some(peer_id));
+                }
+                continue
+            }
+
+            if this.queued_validation.is_some() {
+                let (pool, permits) = this.validation_pool.as_mut().expect("pool is set");
+                let Some(permit) = ready!(permits.poll_acquire(cx)) else {
+                    return Poll::Ready(Err(RequestError::ChannelClosed.into()))
+                };
+                let (peer_id, blocks) = this.queued_validation.take().expect("is some");
+                let rx = pool.spawn(this.consensus.clone(), blocks, permit);
+                this.pending_validation = Some((peer_id, rx));
+                continue
+            }
+
             // Check if there is a pending requests. It might not exist if all
             // headers are empty and there is nothing to download.
             if let Some(fut) = this.fut.as_mut() {
@@ -248,8 +316,10 @@ where
                 }
             }
 
-            // Buffer any empty headers
-            while this.pending_headers.front().is_some_and(|h| h.is_empty()) {
+            // Buffer any empty headers, unless they have to wait for preceding blocks
+            while !this.is_validating() &&
+                this.pending_headers.front().is_some_and(|h| h.is_empty())
+            {
                 let header = this.pending_headers.pop_front().unwrap();
                 this.buffer.push(BlockResponse::Empty(header));
             }
@@ -313,4 +383,41 @@ mod tests {
             (headers.into_iter().filter(|h| !h.is_empty()).count() as u64).div_ceil(2)
         );
     }
+
+    /// Check that the request future validates bodies on the pool and buffers them in order.
+    #[tokio::test]
+    async fn request_validates_on_pool() {
+        let (headers, mut bodies) = generate_bodies(0..=19);
+
+        let client = Arc::new(
+            TestBodiesClient::default().with_bodies(bodies.clone()).with_max_batch_size(3),
+        );
+        let fut = BodiesRequestFuture::<Block, _>::new(
+            client.clone(),
+            Arc::new(TestConsensus::default()),
+            BodyDownloaderMetrics::default(),
+        )
+        .with_validation_pool(Some(BodyValidationPool::new(2, 1)))
+        .with_headers(headers.clone());
+
+        assert_eq!(fut.await.unwrap(), zip_blocks(headers.iter(), &mut bodies));
+    }
+
+    /// Check that the headers of an invalid block and all following blocks are returned.
+    #[test]
+    fn validate_bodies_stops_at_invalid() {
+        let (headers, mut bodies) = generate_bodies(0..=9);
+        let blocks = zip_blocks::<Block>(headers.iter(), &mut bodies);
+
+        let consensus = TestConsensus::default();
+        let ValidatedBodies { valid, invalid } = validate_bodies(&consensus, blocks.clone());
+        assert_eq!(valid, blocks);
+        assert!(invalid.is_none());
+
+        consensus.set_fail_validation(true);
+        let first_full = blocks.iter().position(|b| matches!(b, BlockResponse::Full(_))).unwrap();
+        let ValidatedBodies { valid, invalid } = validate_bodies(&consensus, blocks.clone());
+        assert_eq!(valid, blocks[..first_full]);
+        assert_eq!(invalid.unwrap().1, headers[first_full..]);
+    }
 }
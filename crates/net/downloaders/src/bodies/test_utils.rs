@@ -41,8 +41,9 @@ pub(crate) fn create_raw_bodies(
         .collect()
 }
 
+/// Inserts the given headers into the database as canonical headers.
 #[inline]
-pub(crate) fn insert_headers(db: &DatabaseEnv, headers: &[SealedHeader]) {
+pub fn insert_headers(db: &DatabaseEnv, headers: &[SealedHeader]) {
     db.update(|tx| {
         for header in headers {
             tx.put::<tables::CanonicalHeaders>(header.number, header.hash()).unwrap();
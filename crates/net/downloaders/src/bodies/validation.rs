@@ -0,0 +1,94 @@
+use reth_consensus::{Consensus, ConsensusError};
+use reth_network_p2p::bodies::response::BlockResponse;
+use reth_primitives_traits::{Block, SealedHeader};
+use std::sync::Arc;
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+
+/// Default number of threads used to validate downloaded bodies.
+pub const DEFAULT_BODY_VALIDATION_THREADS: usize = 2;
+
+/// Default maximum number of responses that are queued for validation at once.
+pub const DEFAULT_MAX_QUEUED_BODY_VALIDATIONS: usize = 64;
+
+/// A pool of worker threads that validates downloaded bodies against their headers.
+///
+/// Pre-execution validation of a body requires computing the transaction and withdrawals roots,
+/// which can take a while for large blocks. Running it on dedicated threads keeps the downloader
+/// task free to process peer responses.
+///
+/// The number of responses that can be queued for validation is bounded, request futures wait
+/// for a free slot before they submit a response.
+#[derive(Debug, Clone)]
+pub(crate) struct BodyValidationPool {
+    pool: Arc<rayon::ThreadPool>,
+    permits: Arc<Semaphore>,
+}
+
+impl BodyValidationPool {
+    /// Creates a new pool with the given number of threads and queue capacity.
+    pub(crate) fn new(threads: usize, max_queued: usize) -> Self {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("body-validation-{i:02}"))
+            .build()
+            .expect("failed to create body validation thread pool");
+        Self { pool: Arc::new(pool), permits: Arc::new(Semaphore::new(max_queued.max(1))) }
+    }
+
+    /// Returns the semaphore that bounds the number of queued validations.
+    pub(crate) fn permits(&self) -> Arc<Semaphore> {
+        self.permits.clone()
+    }
+
+    /// Spawns the validation of the given blocks on the pool.
+    ///
+    /// The permit is released once the validation finished.
+    pub(crate) fn spawn<B: Block + 'static>(
+        &self,
+        consensus: Arc<dyn Consensus<B, Error = ConsensusError>>,
+        blocks: Vec<BlockResponse<B>>,
+        permit: OwnedSemaphorePermit,
+    ) -> oneshot::Receiver<ValidatedBodies<B>> {
+        let (tx, rx) = oneshot::channel();
+        self.pool.spawn(move || {
+            let _ = tx.send(validate_bodies(consensus.as_ref(), blocks));
+            drop(permit);
+        });
+        rx
+    }
+}
+
+/// Outcome of validating the bodies of a single response.
+#[derive(Debug)]
+pub(crate) struct ValidatedBodies<B: Block> {
+    /// Blocks preceding the first invalid one, in order.
+    pub(crate) valid: Vec<BlockResponse<B>>,
+    /// The validation error of the first invalid block, and the headers of the invalid block and
+    /// all blocks following it, in order.
+    pub(crate) invalid: Option<(ConsensusError, Vec<SealedHeader<B::Header>>)>,
+}
+
+/// Validates the given blocks in order, stopping at the first invalid block.
+pub(crate) fn validate_bodies<B: Block>(
+    consensus: &dyn Consensus<B, Error = ConsensusError>,
+    blocks: Vec<BlockResponse<B>>,
+) -> ValidatedBodies<B> {
+    let mut valid = Vec::with_capacity(blocks.len());
+    let mut blocks = blocks.into_iter();
+    while let Some(response) = blocks.next() {
+        if let BlockResponse::Full(block) = &response {
+            if let Err(error) = consensus.validate_block_pre_execution(block) {
+                let headers = std::iter::once(response)
+                    .chain(blocks)
+                    .map(|response| match response {
+                        BlockResponse::Full(block) => block.into_sealed_header(),
+                        BlockResponse::Empty(header) => header,
+                    })
+                    .collect();
+                return ValidatedBodies { valid, invalid: Some((error, headers)) }
+            }
+        }
+        valid.push(response);
+    }
+    ValidatedBodies { valid, invalid: None }
+}
@@ -29,7 +29,8 @@ pub struct TestBodiesClient {
 }
 
 impl TestBodiesClient {
-    pub(crate) fn with_bodies(mut self, bodies: HashMap<B256, BlockBody>) -> Self {
+    /// Sets the bodies the client responds with, keyed by block hash.
+    pub fn with_bodies(mut self, bodies: HashMap<B256, BlockBody>) -> Self {
         self.bodies = Arc::new(Mutex::new(bodies));
         self
     }
@@ -46,7 +47,8 @@ impl TestBodiesClient {
         self
     }
 
-    pub(crate) const fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+    /// Sets the maximum number of bodies the client responds with at once.
+    pub const fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
         self.max_batch_size = Some(max_batch_size);
         self
     }
@@ -38,6 +38,36 @@ pub struct GetBlockHeaders {
     pub direction: HeadersDirection,
 }
 
+impl GetBlockHeaders {
+    /// Creates a request for at most `limit` contiguous headers starting at `start_block` and
+    /// traversing towards the latest block.
+    pub fn new(start_block: impl Into<BlockHashOrNumber>, limit: u64) -> Self {
+        Self {
+            start_block: start_block.into(),
+            limit,
+            skip: 0,
+            direction: HeadersDirection::Rising,
+        }
+    }
+
+    /// Sets the number of headers to skip between two returned headers.
+    pub const fn with_skip(mut self, skip: u32) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    /// Sets the direction in which the headers are returned.
+    pub const fn with_direction(mut self, direction: HeadersDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Returns the headers traversing towards the genesis block.
+    pub const fn reversed(self) -> Self {
+        self.with_direction(HeadersDirection::Falling)
+    }
+}
+
 /// The response to [`GetBlockHeaders`], containing headers if any headers were found.
 #[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -503,4 +533,18 @@ mod tests {
         let decoded = BlockBodies::<BlockBody>::decode(&mut buf.as_slice()).unwrap();
         assert_eq!(body, decoded);
     }
+
+    #[test]
+    fn get_block_headers_builder() {
+        let request = GetBlockHeaders::new(100u64, 10).with_skip(2).reversed();
+        assert_eq!(
+            request,
+            GetBlockHeaders {
+                start_block: BlockHashOrNumber::Number(100),
+                limit: 10,
+                skip: 2,
+                direction: HeadersDirection::Falling,
+            }
+        );
+    }
 }
@@ -359,7 +359,7 @@ pub struct NewPooledTransactionHashes68 {
     pub hashes: Vec<B256>,
 }
 
-#[cfg(feature = "arbitrary")]
+#[cfg(any(test, feature = "arbitrary"))]
 impl proptest::prelude::Arbitrary for NewPooledTransactionHashes68 {
     type Parameters = ();
     fn arbitrary_with(_args: ()) -> Self::Strategy {
@@ -395,6 +395,25 @@ impl NewPooledTransactionHashes68 {
         self.hashes.iter().zip(self.types.iter().copied().zip(self.sizes.iter().copied()))
     }
 
+    /// Appends the announcement of a transaction with the given hash, type and encoded size.
+    ///
+    /// The size is the length of the transaction's consensus encoding, see
+    /// [`Encodable2718::encode_2718_len`](alloy_eips::eip2718::Encodable2718::encode_2718_len).
+    pub fn push_announcement(&mut self, hash: B256, ty: u8, size: usize) {
+        self.hashes.push(hash);
+        self.types.push(ty);
+        self.sizes.push(size);
+    }
+
+    /// Consumes and appends the announcement of a transaction with the given hash, type and
+    /// encoded size.
+    ///
+    /// See also [`Self::push_announcement`].
+    pub fn with_announcement(mut self, hash: B256, ty: u8, size: usize) -> Self {
+        self.push_announcement(hash, ty, size);
+        self
+    }
+
     /// Appends a transaction
     pub fn push<T: SignedTransaction>(&mut self, tx: &T) {
         self.hashes.push(*tx.tx_hash());
@@ -789,6 +808,7 @@ impl FromIterator<(TxHash, Eth68TxMetadata)> for RequestTxHashes {
 #[derive(Clone, Debug, PartialEq, Eq, Default, RlpEncodable, RlpDecodable)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 pub struct BlockRangeUpdate {
     /// The earliest block which is available.
     pub earliest: u64,
@@ -1063,6 +1083,19 @@ mod tests {
         assert_eq!(tx_hashes.hashes[0], *tx.tx_hash());
     }
 
+    #[test]
+    fn test_pooled_tx_hashes_68_with_announcement() {
+        let tx = signed_transaction();
+        let tx_hashes =
+            NewPooledTransactionHashes68 { types: vec![], sizes: vec![], hashes: vec![] }
+                .with_announcement(*tx.tx_hash(), tx.ty(), tx.encode_2718_len());
+        assert_eq!(
+            tx_hashes,
+            NewPooledTransactionHashes68 { types: vec![], sizes: vec![], hashes: vec![] }
+                .with_transaction(&tx)
+        );
+    }
+
     #[test]
     fn test_pooled_tx_hashes_68_extend() {
         let tx = signed_transaction();
@@ -0,0 +1,103 @@
+//! Proptest strategies and roundtrip helpers for `eth` protocol messages.
+//!
+//! The strategies generate messages that are valid for a given [`EthVersion`], which can be used
+//! to fuzz implementations of the `eth` protocol or to check that messages crafted with reth types
+//! survive the wire encoding.
+
+use crate::{
+    message::{MessageError, RequestPair},
+    BlockBodies, BlockHeaders, BlockRangeUpdate, EthMessage, EthNetworkPrimitives, EthVersion,
+    GetBlockBodies, GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts,
+    NetworkPrimitives, NewBlock, NewBlockHashes, NewPooledTransactionHashes66,
+    NewPooledTransactionHashes68, NodeData, ProtocolMessage, Receipts, Receipts69, Transactions,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use alloy_rlp::Encodable;
+use proptest::{
+    prelude::{any, BoxedStrategy, Strategy},
+    strategy::Union,
+};
+use proptest_arbitrary_interop::arb;
+
+/// Returns a strategy that generates [`EthMessage`]s which are valid for the given version.
+///
+/// The `Status` handshake message and `PooledTransactions` responses are not generated.
+pub fn arb_eth_message(version: EthVersion) -> BoxedStrategy<EthMessage<EthNetworkPrimitives>> {
+    let mut strategies = vec![
+        arb::<NewBlockHashes>().prop_map(EthMessage::NewBlockHashes).boxed(),
+        arb::<NewBlock>().prop_map(|block| EthMessage::NewBlock(Box::new(block))).boxed(),
+        arb::<Transactions>().prop_map(EthMessage::Transactions).boxed(),
+        arb::<RequestPair<GetBlockHeaders>>().prop_map(EthMessage::GetBlockHeaders).boxed(),
+        arb::<RequestPair<BlockHeaders>>().prop_map(EthMessage::BlockHeaders).boxed(),
+        arb::<RequestPair<GetBlockBodies>>().prop_map(EthMessage::GetBlockBodies).boxed(),
+        arb::<RequestPair<BlockBodies>>().prop_map(EthMessage::BlockBodies).boxed(),
+        arb::<RequestPair<GetPooledTransactions>>()
+            .prop_map(EthMessage::GetPooledTransactions)
+            .boxed(),
+        arb::<RequestPair<GetReceipts>>().prop_map(EthMessage::GetReceipts).boxed(),
+    ];
+
+    if version >= EthVersion::Eth68 {
+        strategies.push(
+            any::<NewPooledTransactionHashes68>()
+                .prop_map(EthMessage::NewPooledTransactionHashes68)
+                .boxed(),
+        );
+    } else {
+        strategies.push(
+            arb::<NewPooledTransactionHashes66>()
+                .prop_map(EthMessage::NewPooledTransactionHashes66)
+                .boxed(),
+        );
+    }
+
+    if version < EthVersion::Eth67 {
+        strategies
+            .push(arb::<RequestPair<GetNodeData>>().prop_map(EthMessage::GetNodeData).boxed());
+        strategies.push(arb::<RequestPair<NodeData>>().prop_map(EthMessage::NodeData).boxed());
+    }
+
+    if version < EthVersion::Eth69 {
+        strategies.push(arb::<RequestPair<Receipts>>().prop_map(EthMessage::Receipts).boxed());
+    } else {
+        strategies.push(arb::<RequestPair<Receipts69>>().prop_map(EthMessage::Receipts69).boxed());
+        strategies.push(arb::<BlockRangeUpdate>().prop_map(EthMessage::BlockRangeUpdate).boxed());
+    }
+
+    Union::new(strategies).boxed()
+}
+
+/// Encodes the message as it is sent over the wire and decodes it for the given version.
+pub fn roundtrip<N: NetworkPrimitives>(
+    version: EthVersion,
+    message: EthMessage<N>,
+) -> Result<ProtocolMessage<N>, MessageError> {
+    let message = ProtocolMessage::from(message);
+    let mut buf = Vec::with_capacity(message.length());
+    message.encode(&mut buf);
+    ProtocolMessage::decode_message(version, &mut &buf[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{
+        prop_assert_eq,
+        test_runner::{Config, TestRunner},
+    };
+
+    #[test]
+    fn eth_message_roundtrip() {
+        for version in EthVersion::ALL_VERSIONS {
+            let mut runner = TestRunner::new(Config::with_cases(64));
+            runner
+                .run(&arb_eth_message(*version), |message| {
+                    let decoded = roundtrip(*version, message.clone()).unwrap();
+                    prop_assert_eq!(decoded.message_type, message.message_id());
+                    prop_assert_eq!(decoded.message, message);
+                    Ok(())
+                })
+                .unwrap();
+        }
+    }
+}
@@ -50,6 +50,9 @@ pub use primitives::*;
 pub mod snap;
 pub use snap::*;
 
+#[cfg(any(test, feature = "arbitrary"))]
+pub mod fuzz;
+
 /// re-export for convenience
 pub use alloy_eips::eip1898::{BlockHashOrNumber, HashOrNumber};
 pub use alloy_eips::eip2718::Encodable2718;
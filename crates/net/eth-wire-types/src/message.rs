@@ -578,6 +578,11 @@ pub struct RequestPair<T> {
 }
 
 impl<T> RequestPair<T> {
+    /// Creates a new request or response message with the given request id.
+    pub const fn new(request_id: u64, message: T) -> Self {
+        Self { request_id, message }
+    }
+
     /// Converts the message type with the given closure.
     pub fn map<F, R>(self, f: F) -> RequestPair<R>
     where
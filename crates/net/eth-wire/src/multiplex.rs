@@ -27,8 +27,12 @@ use bytes::{Bytes, BytesMut};
 use futures::{Sink, SinkExt, Stream, StreamExt, TryStream, TryStreamExt};
 use reth_eth_wire_types::NetworkPrimitives;
 use reth_ethereum_forks::ForkFilter;
-use tokio::sync::{mpsc, mpsc::UnboundedSender};
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio::sync::{
+    mpsc,
+    mpsc::{error::TrySendError, UnboundedSender},
+};
+use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
+use tokio_util::sync::PollSender;
 
 /// A Stream and Sink type that wraps a raw rlpx stream [`P2PStream`] and handles message ID
 /// multiplexing.
@@ -62,7 +66,26 @@ impl<St> RlpxProtocolMultiplexer<St> {
         F: FnOnce(ProtocolConnection) -> Proto,
         Proto: Stream<Item = BytesMut> + Send + 'static,
     {
-        self.inner.install_protocol(cap, f)
+        self.inner.install_protocol(cap, None, f)
+    }
+
+    /// Installs a new protocol on top of the raw p2p stream with a bounded inbound queue.
+    ///
+    /// Same as [`Self::install_protocol`], but at most `capacity` received messages are queued
+    /// for the protocol. If the protocol falls behind, the multiplexer stops reading from the
+    /// connection until the protocol has consumed queued messages, which applies backpressure to
+    /// the remote peer instead of buffering without limit.
+    pub fn install_protocol_with_capacity<F, Proto>(
+        &mut self,
+        cap: &Capability,
+        capacity: usize,
+        f: F,
+    ) -> Result<(), UnsupportedCapabilityError>
+    where
+        F: FnOnce(ProtocolConnection) -> Proto,
+        Proto: Stream<Item = BytesMut> + Send + 'static,
+    {
+        self.inner.install_protocol(cap, Some(capacity), f)
     }
 
     /// Returns the [`SharedCapabilities`] of the underlying raw p2p stream
@@ -241,8 +264,8 @@ impl<St> MultiplexInner<St> {
     }
 
     /// Delegates a message to the matching protocol.
-    fn delegate_message(&self, cap: &SharedCapability, msg: BytesMut) -> bool {
-        for proto in &self.protocols {
+    fn delegate_message(&mut self, cap: &SharedCapability, msg: BytesMut) -> bool {
+        for proto in &mut self.protocols {
             if proto.shared_cap == *cap {
                 proto.send_raw(msg);
                 return true
@@ -254,6 +277,7 @@ impl<St> MultiplexInner<St> {
     fn install_protocol<F, Proto>(
         &mut self,
         cap: &Capability,
+        capacity: Option<usize>,
         f: F,
     ) -> Result<(), UnsupportedCapabilityError>
     where
@@ -262,10 +286,20 @@ impl<St> MultiplexInner<St> {
     {
         let shared_cap =
             self.conn.shared_capabilities().ensure_matching_capability(cap).cloned()?;
-        let (to_satellite, rx) = mpsc::unbounded_channel();
-        let proto_conn = ProtocolConnection { from_wire: UnboundedReceiverStream::new(rx) };
-        let st = f(proto_conn);
-        let st = ProtocolStream { shared_cap, to_satellite, satellite_st: Box::pin(st) };
+        let (to_satellite, from_wire) = if let Some(capacity) = capacity {
+            let (tx, rx) = mpsc::channel(capacity.max(1));
+            (ToSatellite::Bounded(PollSender::new(tx)), FromWire::Bounded(ReceiverStream::new(rx)))
+        } else {
+            let (tx, rx) = mpsc::unbounded_channel();
+            (ToSatellite::Unbounded(tx), FromWire::Unbounded(UnboundedReceiverStream::new(rx)))
+        };
+        let st = f(ProtocolConnection { from_wire });
+        let st = ProtocolStream {
+            shared_cap,
+            to_satellite,
+            backlog: VecDeque::new(),
+            satellite_st: Box::pin(st),
+        };
         self.protocols.push(st);
         Ok(())
     }
@@ -382,17 +416,34 @@ impl CanDisconnect<Bytes> for ProtocolProxy {
 /// This is a [Stream] that returns raw bytes of the received messages for this protocol.
 #[derive(Debug)]
 pub struct ProtocolConnection {
-    from_wire: UnboundedReceiverStream<BytesMut>,
+    from_wire: FromWire,
 }
 
 impl Stream for ProtocolConnection {
     type Item = BytesMut;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.from_wire.poll_next_unpin(cx)
+        match &mut self.from_wire {
+            FromWire::Unbounded(rx) => rx.poll_next_unpin(cx),
+            FromWire::Bounded(rx) => rx.poll_next_unpin(cx),
+        }
     }
 }
 
+/// Receiving half of the channel that delivers messages from the wire to a satellite.
+#[derive(Debug)]
+enum FromWire {
+    Unbounded(UnboundedReceiverStream<BytesMut>),
+    Bounded(ReceiverStream<BytesMut>),
+}
+
+/// Sending half of the channel that delivers messages from the wire to a satellite.
+#[derive(Debug)]
+enum ToSatellite {
+    Unbounded(UnboundedSender<BytesMut>),
+    Bounded(PollSender<BytesMut>),
+}
+
 /// A Stream and Sink type that acts as a wrapper around a primary `RLPx` subprotocol (e.g. "eth")
 /// [`EthStream`] and can also handle additional subprotocols.
 #[derive(Debug)]
@@ -415,7 +466,26 @@ impl<St, Primary> RlpxSatelliteStream<St, Primary> {
         F: FnOnce(ProtocolConnection) -> Proto,
         Proto: Stream<Item = BytesMut> + Send + 'static,
     {
-        self.inner.install_protocol(cap, f)
+        self.inner.install_protocol(cap, None, f)
+    }
+
+    /// Installs a new protocol on top of the raw p2p stream with a bounded inbound queue.
+    ///
+    /// Same as [`Self::install_protocol`], but at most `capacity` received messages are queued
+    /// for the protocol. If the protocol falls behind, the multiplexer stops reading from the
+    /// connection until the protocol has consumed queued messages, which applies backpressure to
+    /// the remote peer instead of buffering without limit.
+    pub fn install_protocol_with_capacity<F, Proto>(
+        &mut self,
+        cap: &Capability,
+        capacity: usize,
+        f: F,
+    ) -> Result<(), UnsupportedCapabilityError>
+    where
+        F: FnOnce(ProtocolConnection) -> Proto,
+        Proto: Stream<Item = BytesMut> + Send + 'static,
+    {
+        self.inner.install_protocol(cap, Some(capacity), f)
     }
 
     /// Returns the primary protocol.
@@ -527,8 +597,15 @@ where
                 }
             }
 
+            // deliver messages that are held back for satellites with a full inbound queue first,
+            // the connection is only read if all satellites have capacity
+            let mut backpressured = false;
+            for proto in &mut this.inner.protocols {
+                backpressured |= proto.poll_flush_backlog(cx).is_pending();
+            }
+
             let mut delegated = false;
-            loop {
+            while !backpressured {
                 // pull messages from connection
                 match this.inner.conn.poll_next_unpin(cx) {
                     Poll::Ready(Some(Ok(msg))) => {
@@ -547,9 +624,10 @@ where
                                 let _ = this.primary.to_primary.send(msg);
                             } else {
                                 // delegate to installed satellite if any
-                                for proto in &this.inner.protocols {
+                                for proto in &mut this.inner.protocols {
                                     if proto.shared_cap == *cap {
                                         proto.send_raw(msg);
+                                        backpressured = proto.poll_flush_backlog(cx).is_pending();
                                         break
                                     }
                                 }
@@ -613,7 +691,9 @@ where
 struct ProtocolStream {
     shared_cap: SharedCapability,
     /// the channel shared with the satellite stream
-    to_satellite: UnboundedSender<BytesMut>,
+    to_satellite: ToSatellite,
+    /// Unmasked messages that are waiting for capacity in the bounded satellite channel.
+    backlog: VecDeque<BytesMut>,
     satellite_st: Pin<Box<dyn Stream<Item = BytesMut> + Send>>,
 }
 
@@ -645,8 +725,49 @@ impl ProtocolStream {
     }
 
     /// Sends the message to the satellite stream.
-    fn send_raw(&self, msg: BytesMut) {
-        let _ = self.unmask_id(msg).map(|msg| self.to_satellite.send(msg));
+    ///
+    /// If the satellite channel is bounded and full, the message is added to the backlog that is
+    /// delivered by [`Self::poll_flush_backlog`].
+    fn send_raw(&mut self, msg: BytesMut) {
+        let Ok(msg) = self.unmask_id(msg) else { return };
+        match &mut self.to_satellite {
+            ToSatellite::Unbounded(tx) => {
+                let _ = tx.send(msg);
+            }
+            ToSatellite::Bounded(tx) => {
+                if !self.backlog.is_empty() {
+                    self.backlog.push_back(msg);
+                    return
+                }
+                if let Some(Err(TrySendError::Full(msg))) = tx.get_ref().map(|tx| tx.try_send(msg))
+                {
+                    self.backlog.push_back(msg);
+                }
+            }
+        }
+    }
+
+    /// Delivers the backlog to the satellite stream.
+    ///
+    /// Returns [`Poll::Pending`] until the satellite had capacity for all messages of the backlog.
+    fn poll_flush_backlog(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let ToSatellite::Bounded(tx) = &mut self.to_satellite else { return Poll::Ready(()) };
+        while let Some(msg) = self.backlog.pop_front() {
+            match tx.poll_reserve(cx) {
+                Poll::Ready(Ok(())) => {
+                    let _ = tx.send_item(msg);
+                }
+                Poll::Ready(Err(_)) => {
+                    // satellite dropped its connection
+                    self.backlog.clear();
+                }
+                Poll::Pending => {
+                    self.backlog.push_front(msg);
+                    return Poll::Pending
+                }
+            }
+        }
+        Poll::Ready(())
     }
 }
 
@@ -809,4 +930,80 @@ mod tests {
             }
         }
     }
+
+    /// A test that a satellite with a bounded inbound queue receives all messages in order if it
+    /// consumes them slower than they arrive.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn eth_test_protocol_satellite_bounded() {
+        reth_tracing::init_test_tracing();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let (status, fork_filter) = eth_handshake();
+        let other_status = status;
+        let other_fork_filter = fork_filter.clone();
+        let num_messages = 10;
+        let _handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = crate::PassthroughCodec::default().framed(incoming);
+            let (server_hello, _) = test_hello();
+            let (conn, _) = UnauthedP2PStream::new(stream).handshake(server_hello).await.unwrap();
+
+            let (mut st, _their_status) = RlpxProtocolMultiplexer::new(conn)
+                .into_eth_satellite_stream::<EthNetworkPrimitives>(other_status, other_fork_filter)
+                .await
+                .unwrap();
+
+            st.install_protocol(&TestProtoMessage::capability(), move |_conn| {
+                async_stream::stream! {
+                    for i in 0..num_messages {
+                        yield TestProtoMessage::message(i.to_string()).encoded();
+                    }
+
+                    futures::future::pending::<()>().await;
+                    unreachable!()
+                }
+            })
+            .unwrap();
+
+            loop {
+                let _ = st.next().await;
+            }
+        });
+
+        let conn = connect_passthrough(local_addr, test_hello().0).await;
+        let (mut st, _their_status) = RlpxProtocolMultiplexer::new(conn)
+            .into_eth_satellite_stream::<EthNetworkPrimitives>(status, fork_filter)
+            .await
+            .unwrap();
+
+        let (tx, mut rx) = oneshot::channel();
+
+        st.install_protocol_with_capacity(&TestProtoMessage::capability(), 1, move |mut conn| {
+            async_stream::stream! {
+                for i in 0..num_messages {
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                    let msg = conn.next().await.unwrap();
+                    let msg = TestProtoMessage::decode_message(&mut &msg[..]).unwrap();
+                    assert_eq!(msg, TestProtoMessage::message(i.to_string()));
+                }
+
+                yield TestProtoMessage::pong().encoded();
+                tx.send(()).unwrap();
+
+                futures::future::pending::<()>().await;
+                unreachable!()
+            }
+        })
+        .unwrap();
+
+        loop {
+            tokio::select! {
+                _ = &mut rx => {
+                    break
+                }
+               _ = st.next() => {
+                }
+            }
+        }
+    }
 }
@@ -25,7 +25,9 @@ use test_utils::PeersHandleProvider;
 
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 pub use reth_network_p2p::{BlockClient, HeadersClient};
-pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+pub use reth_network_types::{
+    OutboundBandwidthWeights, PeerKind, Reputation, ReputationChangeKind,
+};
 
 pub use downloaders::BlockDownloaderProvider;
 pub use error::NetworkError;
@@ -38,9 +40,10 @@ use reth_eth_wire_types::{
     capability::Capabilities, Capability, DisconnectReason, EthVersion, NetworkPrimitives,
     UnifiedStatus,
 };
+use reth_ethereum_forks::ForkId;
 use reth_network_p2p::sync::NetworkSyncUpdater;
 use reth_network_peers::NodeRecord;
-use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
+use std::{future::Future, net::SocketAddr, num::NonZeroU64, sync::Arc, time::Instant};
 
 /// The `PeerId` type.
 pub type PeerId = alloy_primitives::B512;
@@ -91,6 +94,13 @@ pub trait NetworkInfo: Send + Sync {
 
     /// Returns `true` when the node is undergoing the very first Pipeline sync.
     fn is_initially_syncing(&self) -> bool;
+
+    /// Returns a snapshot of the routing tables of the enabled discovery services.
+    ///
+    /// Returns an empty table by default, for networks without discovery services.
+    fn discovery_table(&self) -> impl Future<Output = Result<DiscoveryTable, NetworkError>> + Send {
+        async { Ok(DiscoveryTable::default()) }
+    }
 }
 
 /// Provides general purpose information about Peers in the network.
@@ -183,6 +193,35 @@ pub trait Peers: PeersInfo {
         peer_ids: Vec<PeerId>,
     ) -> impl Future<Output = Result<Vec<PeerInfo>, NetworkError>> + Send;
 
+    /// Returns the status and connection history of all trusted and static peers.
+    fn get_static_peers(
+        &self,
+    ) -> impl Future<Output = Result<Vec<StaticPeerStatus>, NetworkError>> + Send;
+
+    /// Returns the global outbound bandwidth limit and the traffic sent per class.
+    ///
+    /// Returns an unlimited status without traffic by default, for networks without an outbound
+    /// bandwidth limit.
+    fn outbound_bandwidth(
+        &self,
+    ) -> impl Future<Output = Result<OutboundBandwidthStatus, NetworkError>> + Send {
+        async { Ok(OutboundBandwidthStatus::default()) }
+    }
+
+    /// Sets the maximum number of bytes per second that are sent to all peers combined.
+    ///
+    /// `None` removes the limit. Does nothing by default.
+    fn set_outbound_bandwidth(&self, max_bytes_per_sec: Option<NonZeroU64>) {
+        let _ = max_bytes_per_sec;
+    }
+
+    /// Sets the weights of the traffic classes that share the outbound bandwidth limit.
+    ///
+    /// Does nothing by default.
+    fn set_outbound_bandwidth_weights(&self, weights: OutboundBandwidthWeights) {
+        let _ = weights;
+    }
+
     /// Removes a peer from the peer set that corresponds to given kind.
     fn remove_peer(&self, peer: PeerId, kind: PeerKind);
 
@@ -289,3 +328,109 @@ pub struct NetworkStatus {
     /// The list of supported capabilities and their versions.
     pub capabilities: Vec<Capability>,
 }
+
+/// Status and connection history of a trusted or static peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StaticPeerStatus {
+    /// The identifier of the peer.
+    pub id: PeerId,
+    /// The node record of the peer.
+    pub enode: NodeRecord,
+    /// Whether the peer is trusted, otherwise it's static.
+    pub trusted: bool,
+    /// Whether a session with the peer is currently active.
+    pub connected: bool,
+    /// Seconds until the peer is redialed, if it's backed off after a failed connection attempt.
+    pub backoff_remaining_secs: Option<u64>,
+    /// Number of sessions established with the peer.
+    pub sessions_established: u64,
+    /// Number of sessions with the peer that were closed.
+    pub sessions_closed: u64,
+    /// Number of failed connection attempts to the peer.
+    pub connection_failures: u64,
+    /// Number of failed connection attempts since the last established session.
+    pub consecutive_failures: u32,
+    /// Seconds elapsed since the last session was established, if any.
+    pub last_established_secs: Option<u64>,
+    /// Seconds elapsed since the last session was closed, if any.
+    pub last_closed_secs: Option<u64>,
+    /// The last error that closed a session or failed a connection attempt.
+    pub last_error: Option<String>,
+}
+
+/// The global outbound bandwidth limit and the traffic sent per class.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OutboundBandwidthStatus {
+    /// Maximum number of bytes per second that are sent to all peers combined, if limited.
+    pub max_bytes_per_sec: Option<u64>,
+    /// New block announcements, as well as requests and control messages.
+    pub block_propagation: OutboundBandwidthClassStatus,
+    /// Transaction broadcasts, announcements and pooled transaction responses.
+    pub transaction_gossip: OutboundBandwidthClassStatus,
+    /// Responses to header, body, receipt and node data requests.
+    pub historical_serving: OutboundBandwidthClassStatus,
+}
+
+impl Default for OutboundBandwidthStatus {
+    /// Returns an unlimited status without traffic and with the default weights.
+    fn default() -> Self {
+        let weights = OutboundBandwidthWeights::default();
+        let class_status = |weight| OutboundBandwidthClassStatus { weight, ..Default::default() };
+        Self {
+            max_bytes_per_sec: None,
+            block_propagation: class_status(weights.block_propagation),
+            transaction_gossip: class_status(weights.transaction_gossip),
+            historical_serving: class_status(weights.historical_serving),
+        }
+    }
+}
+
+/// The weight and sent traffic of an outbound traffic class.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OutboundBandwidthClassStatus {
+    /// The weight of the class.
+    pub weight: u32,
+    /// Total number of bytes sent.
+    pub bytes_sent: u64,
+    /// Number of times sending a message of the class was delayed by the limit.
+    pub throttled: u64,
+}
+
+/// A snapshot of the routing tables of the discovery services.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiscoveryTable {
+    /// The nodes in the discv4 routing table, `None` if discv4 is disabled.
+    pub discv4: Option<Vec<DiscoveryTableEntry>>,
+    /// The nodes in the discv5 routing table, `None` if discv5 is disabled.
+    pub discv5: Option<Vec<DiscoveryTableEntry>>,
+}
+
+/// A node in the routing table of a discovery service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DiscoveryTableEntry {
+    /// The identifier of the node.
+    pub id: PeerId,
+    /// The node record of the node, if known.
+    pub enode: Option<NodeRecord>,
+    /// The ENR of the node, if known.
+    pub enr: Option<String>,
+    /// The log2 distance of the node to the local node, this is the index of its bucket.
+    pub distance: Option<u64>,
+    /// Whether the node is considered connected by the discovery service.
+    pub connected: bool,
+    /// Seconds elapsed since the node was last seen, if tracked.
+    pub last_seen_secs: Option<u64>,
+    /// The [`ForkId`] advertised by the node via EIP-868, if known.
+    pub fork_id: Option<ForkId>,
+    /// Whether the endpoint of the node is proven, if tracked.
+    pub endpoint_proven: Option<bool>,
+}
@@ -11,7 +11,7 @@ use crate::{
     test_utils::{PeersHandle, PeersHandleProvider},
     BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    PeersInfo, StaticPeerStatus,
 };
 use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
@@ -139,6 +139,10 @@ where
         Ok(vec![])
     }
 
+    async fn get_static_peers(&self) -> Result<Vec<StaticPeerStatus>, NetworkError> {
+        Ok(vec![])
+    }
+
     fn remove_peer(&self, _peer: PeerId, _kind: PeerKind) {}
 
     fn disconnect_peer(&self, _peer: PeerId) {}
@@ -30,6 +30,9 @@ pub use peers::{
         DEFAULT_REPUTATION,
     },
     state::PeerConnectionState,
-    ConnectionsConfig, Peer, PeersConfig,
+    ConnectionHistory, ConnectionsConfig, DialSchedulerConfig, InboundRateLimitConfig, Peer,
+    PeersConfig, StaticPeersConfig,
+};
+pub use session::{
+    OutboundBandwidthConfig, OutboundBandwidthWeights, SessionLimits, SessionsConfig,
 };
-pub use session::{SessionLimits, SessionsConfig};
@@ -3,6 +3,7 @@
 use std::{
     collections::HashSet,
     io::{self, ErrorKind},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
     path::Path,
     time::Duration,
 };
@@ -95,6 +96,57 @@ impl Default for PeerBackoffDurations {
     }
 }
 
+/// Configures how trusted and static peers are redialed after losing the connection to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StaticPeersConfig {
+    /// How long to backoff a trusted or static peer after a failed connection attempt.
+    ///
+    /// The backoff is doubled on every consecutive failed attempt.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub redial_initial_backoff: Duration,
+    /// Maximum backoff between two connection attempts to a trusted or static peer.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub redial_max_backoff: Duration,
+    /// How often to check for disconnected trusted and static peers.
+    ///
+    /// Disconnected peers that aren't backed off are redialed, even if there are no free
+    /// outbound slots.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub health_check_interval: Duration,
+}
+
+impl StaticPeersConfig {
+    /// Returns the backoff after the given number of consecutive failed connection attempts.
+    pub fn redial_backoff(&self, consecutive_failures: u32) -> Duration {
+        self.redial_initial_backoff
+            .saturating_mul(2u32.saturating_pow(consecutive_failures.saturating_sub(1)))
+            .min(self.redial_max_backoff)
+    }
+
+    /// Returns settings for testing.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub const fn test() -> Self {
+        Self {
+            redial_initial_backoff: Duration::from_millis(100),
+            redial_max_backoff: Duration::from_millis(400),
+            health_check_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+impl Default for StaticPeersConfig {
+    fn default() -> Self {
+        Self {
+            redial_initial_backoff: Duration::from_secs(15),
+            // 5min
+            redial_max_backoff: Duration::from_secs(60 * 5),
+            health_check_interval: Duration::from_secs(30),
+        }
+    }
+}
+
 /// Tracks stats about connected nodes
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
@@ -118,6 +170,203 @@ impl Default for ConnectionsConfig {
     }
 }
 
+/// Default maximum number of outbound connections to peers within the same subnet.
+pub const DEFAULT_MAX_OUTBOUND_PER_SUBNET: usize = 4;
+
+/// Default maximum number of outbound connections to peers within the same network group.
+pub const DEFAULT_MAX_OUTBOUND_PER_GROUP: usize = 8;
+
+/// Configures how the `PeersManager` selects peers to dial for outbound connections.
+///
+/// Outbound slots are filled with the best unconnected peers. To avoid clustering all outbound
+/// connections in a single network, which makes the node easier to eclipse, peers are bucketed by
+/// the prefixes of their addresses:
+///  - a subnet, `/24` for IPv4 by default, which usually belongs to a single operator
+///  - a network group, `/16` for IPv4 by default, which approximates an autonomous system
+///
+/// Only a limited number of outbound connections to peers within the same subnet and within the
+/// same network group are allowed. Among candidates with the same reputation, peers in the least
+/// used network group are preferred, then peers with a lower measured latency.
+///
+/// Note: trusted and static peers are not subject to these rules, and neither are peers in
+/// loopback or private networks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DialSchedulerConfig {
+    /// Maximum number of outbound connections, pending or active, to peers within the same
+    /// subnet. `0` disables the subnet diversity check.
+    pub max_outbound_per_subnet: usize,
+    /// Prefix length that determines the subnet of an IPv4 peer.
+    pub ipv4_subnet_prefix_len: u8,
+    /// Prefix length that determines the subnet of an IPv6 peer.
+    pub ipv6_subnet_prefix_len: u8,
+    /// Maximum number of outbound connections, pending or active, to peers within the same
+    /// network group. `0` disables the network group diversity check.
+    pub max_outbound_per_group: usize,
+    /// Prefix length that determines the network group of an IPv4 peer.
+    pub ipv4_group_prefix_len: u8,
+    /// Prefix length that determines the network group of an IPv6 peer.
+    pub ipv6_group_prefix_len: u8,
+    /// Whether peers with a lower latency, measured when establishing previous outbound sessions,
+    /// should be preferred over peers with the same reputation.
+    pub prefer_low_latency: bool,
+}
+
+impl DialSchedulerConfig {
+    /// Returns a config that disables subnet diversity and latency based selection.
+    pub const fn disabled() -> Self {
+        Self {
+            max_outbound_per_subnet: 0,
+            ipv4_subnet_prefix_len: 24,
+            ipv6_subnet_prefix_len: 48,
+            max_outbound_per_group: 0,
+            ipv4_group_prefix_len: 16,
+            ipv6_group_prefix_len: 32,
+            prefer_low_latency: false,
+        }
+    }
+
+    /// Returns the subnet of the given address that is used for the diversity check, or `None` if
+    /// the address is not subject to it.
+    pub fn subnet(&self, ip: IpAddr) -> Option<IpAddr> {
+        if self.max_outbound_per_subnet == 0 {
+            return None
+        }
+        public_subnet(ip, self.ipv4_subnet_prefix_len, self.ipv6_subnet_prefix_len)
+    }
+
+    /// Returns the network group of the given address that is used for the diversity check, or
+    /// `None` if the address is not subject to it.
+    pub fn group(&self, ip: IpAddr) -> Option<IpAddr> {
+        if self.max_outbound_per_group == 0 {
+            return None
+        }
+        public_subnet(ip, self.ipv4_group_prefix_len, self.ipv6_group_prefix_len)
+    }
+}
+
+impl Default for DialSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            max_outbound_per_subnet: DEFAULT_MAX_OUTBOUND_PER_SUBNET,
+            ipv4_subnet_prefix_len: 24,
+            ipv6_subnet_prefix_len: 48,
+            max_outbound_per_group: DEFAULT_MAX_OUTBOUND_PER_GROUP,
+            ipv4_group_prefix_len: 16,
+            ipv6_group_prefix_len: 32,
+            prefer_low_latency: true,
+        }
+    }
+}
+
+/// Maximum number of inbound connection attempts per IP address within
+/// [`DEFAULT_INBOUND_RATE_LIMIT_INTERVAL`] of [`InboundRateLimitConfig::enabled`].
+pub const DEFAULT_MAX_INBOUND_ATTEMPTS_PER_IP: usize = 10;
+
+/// Maximum number of inbound connection attempts per subnet within
+/// [`DEFAULT_INBOUND_RATE_LIMIT_INTERVAL`] of [`InboundRateLimitConfig::enabled`].
+pub const DEFAULT_MAX_INBOUND_ATTEMPTS_PER_SUBNET: usize = 60;
+
+/// Default interval over which inbound connection attempts are counted.
+pub const DEFAULT_INBOUND_RATE_LIMIT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Configures the rate limit of inbound connection attempts, which is applied before the RLPx
+/// handshake.
+///
+/// Attempts are counted per IP address and per subnet over a fixed interval. Once an IP address
+/// exceeds its limit it is banned for the configured ban duration, attempts from a subnet that
+/// exceeds its limit are rejected until the interval elapses.
+///
+/// Rate limiting is disabled by default, see [`InboundRateLimitConfig::enabled`].
+///
+/// Note: connections from loopback or private networks and from trusted peers are not rate
+/// limited, and attempts that are rejected because the IP address is banned or throttled are not
+/// counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InboundRateLimitConfig {
+    /// The interval over which connection attempts are counted.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub interval: Duration,
+    /// Maximum number of connection attempts per IP address within the interval. `0` disables
+    /// the limit.
+    pub max_attempts_per_ip: usize,
+    /// Maximum number of connection attempts per subnet within the interval. `0` disables the
+    /// limit.
+    pub max_attempts_per_subnet: usize,
+    /// Prefix length that determines the subnet of an IPv4 address.
+    pub ipv4_subnet_prefix_len: u8,
+    /// Prefix length that determines the subnet of an IPv6 address.
+    pub ipv6_subnet_prefix_len: u8,
+}
+
+impl InboundRateLimitConfig {
+    /// Returns a config that disables rate limiting of inbound connection attempts.
+    pub const fn disabled() -> Self {
+        Self {
+            interval: DEFAULT_INBOUND_RATE_LIMIT_INTERVAL,
+            max_attempts_per_ip: 0,
+            max_attempts_per_subnet: 0,
+            ipv4_subnet_prefix_len: 24,
+            ipv6_subnet_prefix_len: 48,
+        }
+    }
+
+    /// Returns a config that limits inbound connection attempts to
+    /// [`DEFAULT_MAX_INBOUND_ATTEMPTS_PER_IP`] per IP address and
+    /// [`DEFAULT_MAX_INBOUND_ATTEMPTS_PER_SUBNET`] per subnet.
+    pub const fn enabled() -> Self {
+        Self {
+            max_attempts_per_ip: DEFAULT_MAX_INBOUND_ATTEMPTS_PER_IP,
+            max_attempts_per_subnet: DEFAULT_MAX_INBOUND_ATTEMPTS_PER_SUBNET,
+            ..Self::disabled()
+        }
+    }
+
+    /// Returns true if inbound connection attempts are rate limited.
+    pub const fn is_enabled(&self) -> bool {
+        self.max_attempts_per_ip > 0 || self.max_attempts_per_subnet > 0
+    }
+
+    /// Returns the subnet of the given address that is used for the rate limit, or `None` if the
+    /// address is not subject to it.
+    pub fn subnet(&self, ip: IpAddr) -> Option<IpAddr> {
+        public_subnet(ip, self.ipv4_subnet_prefix_len, self.ipv6_subnet_prefix_len)
+    }
+}
+
+impl Default for InboundRateLimitConfig {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+/// Returns the subnet of the given address with the given prefix lengths, or `None` if the
+/// address is a loopback or private address.
+fn public_subnet(ip: IpAddr, ipv4_prefix_len: u8, ipv6_prefix_len: u8) -> Option<IpAddr> {
+    match ip {
+        IpAddr::V4(ip) => {
+            if ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified() {
+                return None
+            }
+            let mask = u32::MAX.checked_shl(32 - ipv4_prefix_len.min(32) as u32);
+            Some(IpAddr::V4(Ipv4Addr::from(u32::from(ip) & mask.unwrap_or_default())))
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return public_subnet(IpAddr::V4(ip), ipv4_prefix_len, ipv6_prefix_len)
+            }
+            if ip.is_loopback() || ip.is_unspecified() {
+                return None
+            }
+            let mask = u128::MAX.checked_shl(128 - ipv6_prefix_len.min(128) as u32);
+            Some(IpAddr::V6(Ipv6Addr::from(u128::from(ip) & mask.unwrap_or_default())))
+        }
+    }
+}
+
 /// Config type for initiating a `PeersManager` instance.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -166,6 +415,15 @@ pub struct PeersConfig {
     /// This acts as an IP based rate limit.
     #[cfg_attr(feature = "serde", serde(default, with = "humantime_serde"))]
     pub incoming_ip_throttle_duration: Duration,
+    /// How to select peers for outbound connections.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dial_scheduler: DialSchedulerConfig,
+    /// How to rate limit inbound connection attempts.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inbound_rate_limit: InboundRateLimitConfig,
+    /// How to redial trusted and static peers.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub static_peers: StaticPeersConfig,
 }
 
 impl Default for PeersConfig {
@@ -184,6 +442,9 @@ impl Default for PeersConfig {
             basic_nodes: Default::default(),
             max_backoff_count: 5,
             incoming_ip_throttle_duration: INBOUND_IP_THROTTLE_DURATION,
+            dial_scheduler: Default::default(),
+            inbound_rate_limit: Default::default(),
+            static_peers: Default::default(),
         }
     }
 }
@@ -280,6 +541,27 @@ impl PeersConfig {
         self
     }
 
+    /// Configures how peers are selected for outbound connections.
+    pub const fn with_dial_scheduler(mut self, dial_scheduler: DialSchedulerConfig) -> Self {
+        self.dial_scheduler = dial_scheduler;
+        self
+    }
+
+    /// Configures how inbound connection attempts are rate limited.
+    pub const fn with_inbound_rate_limit(
+        mut self,
+        inbound_rate_limit: InboundRateLimitConfig,
+    ) -> Self {
+        self.inbound_rate_limit = inbound_rate_limit;
+        self
+    }
+
+    /// Configures how trusted and static peers are redialed.
+    pub const fn with_static_peers(mut self, static_peers: StaticPeersConfig) -> Self {
+        self.static_peers = static_peers;
+        self
+    }
+
     /// Returns the maximum number of peers, inbound and outbound.
     pub const fn max_peers(&self) -> usize {
         self.connection_info.max_outbound + self.connection_info.max_inbound
@@ -307,8 +589,61 @@ impl PeersConfig {
         Self {
             refill_slots_interval: Duration::from_millis(100),
             backoff_durations: PeerBackoffDurations::test(),
+            static_peers: StaticPeersConfig::test(),
             ban_duration: Duration::from_millis(200),
             ..Default::default()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dial_scheduler_subnet() {
+        let config = DialSchedulerConfig::default();
+        assert_eq!(config.subnet("1.2.3.4".parse().unwrap()), Some("1.2.3.0".parse().unwrap()));
+        assert_eq!(
+            config.subnet("::ffff:1.2.3.4".parse().unwrap()),
+            Some("1.2.3.0".parse().unwrap())
+        );
+        assert_eq!(
+            config.subnet("2001:db8:1:2::1".parse().unwrap()),
+            Some("2001:db8:1::".parse().unwrap())
+        );
+        assert_eq!(config.subnet("127.0.0.1".parse().unwrap()), None);
+        assert_eq!(config.subnet("192.168.1.1".parse().unwrap()), None);
+        assert_eq!(DialSchedulerConfig::disabled().subnet("1.2.3.4".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn dial_scheduler_group() {
+        let config = DialSchedulerConfig::default();
+        assert_eq!(config.group("1.2.3.4".parse().unwrap()), Some("1.2.0.0".parse().unwrap()));
+        assert_eq!(
+            config.group("::ffff:1.2.3.4".parse().unwrap()),
+            Some("1.2.0.0".parse().unwrap())
+        );
+        assert_eq!(
+            config.group("2001:db8:1:2::1".parse().unwrap()),
+            Some("2001:db8::".parse().unwrap())
+        );
+        assert_eq!(config.group("10.0.0.1".parse().unwrap()), None);
+        assert_eq!(DialSchedulerConfig::disabled().group("1.2.3.4".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn static_peers_redial_backoff() {
+        let config = StaticPeersConfig {
+            redial_initial_backoff: Duration::from_secs(15),
+            redial_max_backoff: Duration::from_secs(60),
+            ..Default::default()
+        };
+        assert_eq!(config.redial_backoff(0), Duration::from_secs(15));
+        assert_eq!(config.redial_backoff(1), Duration::from_secs(15));
+        assert_eq!(config.redial_backoff(2), Duration::from_secs(30));
+        assert_eq!(config.redial_backoff(3), Duration::from_secs(60));
+        assert_eq!(config.redial_backoff(u32::MAX), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,49 @@
+//! Connection history of a peer.
+
+use std::time::Instant;
+
+/// Tracks the sessions and failed connection attempts of a peer.
+///
+/// This is only tracked for trusted and static peers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionHistory {
+    /// Number of sessions established with the peer.
+    pub sessions_established: u64,
+    /// Number of sessions with the peer that were closed, gracefully or due to an error.
+    pub sessions_closed: u64,
+    /// Number of connection attempts to the peer that failed before a session was established.
+    pub connection_failures: u64,
+    /// Number of failed connection attempts since the last established session.
+    pub consecutive_failures: u32,
+    /// When the last session was established.
+    pub last_established: Option<Instant>,
+    /// When the last session was closed.
+    pub last_closed: Option<Instant>,
+    /// The last error that closed a session or failed a connection attempt.
+    pub last_error: Option<String>,
+}
+
+impl ConnectionHistory {
+    /// Records a newly established session.
+    pub fn on_session_established(&mut self) {
+        self.sessions_established += 1;
+        self.consecutive_failures = 0;
+        self.last_established = Some(Instant::now());
+    }
+
+    /// Records a closed session, with the error that closed it, if any.
+    pub fn on_session_closed(&mut self, error: Option<String>) {
+        self.sessions_closed += 1;
+        self.last_closed = Some(Instant::now());
+        if error.is_some() {
+            self.last_error = error;
+        }
+    }
+
+    /// Records a failed connection attempt.
+    pub fn on_connection_failure(&mut self, error: String) {
+        self.connection_failures += 1;
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.last_error = Some(error);
+    }
+}
@@ -1,13 +1,18 @@
 pub mod addr;
 pub mod config;
+pub mod history;
 pub mod kind;
 pub mod reputation;
 pub mod state;
 
-pub use config::{ConnectionsConfig, PeersConfig};
+pub use config::{
+    ConnectionsConfig, DialSchedulerConfig, InboundRateLimitConfig, PeersConfig, StaticPeersConfig,
+};
+pub use history::ConnectionHistory;
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
 
 use alloy_eip2124::ForkId;
+use std::time::Duration;
 use tracing::debug;
 
 use crate::{
@@ -35,6 +40,11 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// Smoothed time it took to establish outbound sessions with this peer, if any were
+    /// established yet.
+    pub latency: Option<Duration>,
+    /// History of the sessions with the peer, only tracked for trusted and static peers.
+    pub history: ConnectionHistory,
 }
 
 // === impl Peer ===
@@ -66,9 +76,21 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            latency: None,
+            history: Default::default(),
         }
     }
 
+    /// Records the time it took to establish an outbound session with the peer.
+    ///
+    /// The latency is smoothed with previous measurements.
+    pub fn record_latency(&mut self, sample: Duration) {
+        self.latency = Some(match self.latency {
+            Some(latency) => (latency * 3 + sample) / 4,
+            None => sample,
+        });
+    }
+
     /// Returns a new peer for given [`PeerAddr`] and [`PeerKind`].
     pub fn with_kind(addr: PeerAddr, kind: PeerKind) -> Self {
         Self { kind, ..Self::new(addr) }
@@ -139,4 +161,10 @@ impl Peer {
     pub const fn is_static(&self) -> bool {
         matches!(self.kind, PeerKind::Static)
     }
+
+    /// Returns whether this peer is trusted or static
+    #[inline]
+    pub const fn is_trusted_or_static(&self) -> bool {
+        self.is_trusted() || self.is_static()
+    }
 }
@@ -55,6 +55,12 @@ impl PeerConnectionState {
         matches!(self, Self::Idle)
     }
 
+    /// Returns true if this is an active or pending outgoing connection.
+    #[inline]
+    pub const fn is_outgoing(&self) -> bool {
+        matches!(self, Self::Out | Self::PendingOut)
+    }
+
     /// Returns true if there's currently an outbound dial to that peer.
     #[inline]
     pub const fn is_pending_out(&self) -> bool {
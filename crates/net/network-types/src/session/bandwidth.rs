@@ -0,0 +1,53 @@
+//! Configuration types for the outbound bandwidth limit of peer sessions.
+
+use std::num::NonZeroU64;
+
+/// Configuration of the global outbound bandwidth limit that is shared by all peer sessions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OutboundBandwidthConfig {
+    /// Maximum number of bytes per second that are sent to all peers combined.
+    ///
+    /// By default, outbound traffic is not limited.
+    pub max_bytes_per_sec: Option<NonZeroU64>,
+    /// Weights of the traffic classes that share the limit.
+    pub weights: OutboundBandwidthWeights,
+}
+
+impl OutboundBandwidthConfig {
+    /// Sets the maximum number of bytes per second that are sent to all peers combined.
+    pub const fn with_max_bytes_per_sec(mut self, max_bytes_per_sec: Option<NonZeroU64>) -> Self {
+        self.max_bytes_per_sec = max_bytes_per_sec;
+        self
+    }
+
+    /// Sets the weights of the traffic classes.
+    pub const fn with_weights(mut self, weights: OutboundBandwidthWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+}
+
+/// Relative weights of the outbound traffic classes.
+///
+/// If the limit is saturated, each class gets a share of the limit that is proportional to its
+/// weight. Bandwidth that is left unused by a class is available to all other classes, a class with
+/// weight 0 only gets the bandwidth that is left unused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OutboundBandwidthWeights {
+    /// Weight of new block announcements, as well as requests and control messages.
+    pub block_propagation: u32,
+    /// Weight of transaction broadcasts, announcements and pooled transaction responses.
+    pub transaction_gossip: u32,
+    /// Weight of responses to header, body, receipt and node data requests.
+    pub historical_serving: u32,
+}
+
+impl Default for OutboundBandwidthWeights {
+    fn default() -> Self {
+        Self { block_propagation: 4, transaction_gossip: 2, historical_serving: 1 }
+    }
+}
@@ -1,6 +1,9 @@
 //! Configuration types for peer sessions manager.
 
-use crate::peers::config::{DEFAULT_MAX_COUNT_PEERS_INBOUND, DEFAULT_MAX_COUNT_PEERS_OUTBOUND};
+use crate::{
+    peers::config::{DEFAULT_MAX_COUNT_PEERS_INBOUND, DEFAULT_MAX_COUNT_PEERS_OUTBOUND},
+    session::OutboundBandwidthConfig,
+};
 use std::time::Duration;
 
 /// Default request timeout for a single request.
@@ -50,6 +53,8 @@ pub struct SessionsConfig {
     pub protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pub pending_session_timeout: Duration,
+    /// The global limit of the outbound bandwidth of all sessions.
+    pub outbound_bandwidth: OutboundBandwidthConfig,
 }
 
 impl Default for SessionsConfig {
@@ -69,6 +74,7 @@ impl Default for SessionsConfig {
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
             pending_session_timeout: PENDING_SESSION_TIMEOUT,
+            outbound_bandwidth: Default::default(),
         }
     }
 }
@@ -100,6 +106,12 @@ impl SessionsConfig {
         }
         self
     }
+
+    /// Sets the global limit of the outbound bandwidth of all sessions.
+    pub const fn with_outbound_bandwidth(mut self, config: OutboundBandwidthConfig) -> Self {
+        self.outbound_bandwidth = config;
+        self
+    }
 }
 
 /// Limits for sessions.
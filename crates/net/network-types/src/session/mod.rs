@@ -1,4 +1,6 @@
 //! Peer sessions configuration.
 
+pub mod bandwidth;
 pub mod config;
+pub use bandwidth::{OutboundBandwidthConfig, OutboundBandwidthWeights};
 pub use config::{SessionLimits, SessionsConfig};
@@ -16,12 +16,13 @@ use reth_eth_wire::{
     UnifiedStatus,
 };
 use reth_ethereum_forks::{ForkFilter, Head};
+use reth_net_banlist::BanList;
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_storage_api::{noop::NoopProvider, BlockNumReader, BlockReader, HeaderProvider};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use secp256k1::SECP256K1;
-use std::{collections::HashSet, net::SocketAddr, sync::Arc};
+use std::{collections::HashSet, net::SocketAddr, path::PathBuf, sync::Arc};
 
 // re-export for convenience
 use crate::protocol::{IntoRlpxSubProtocol, RlpxSubProtocols};
@@ -56,6 +57,8 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     pub listener_addr: SocketAddr,
     /// How to instantiate peer manager.
     pub peers_config: PeersConfig,
+    /// The file the ban list of the peer manager is written to whenever it changes.
+    pub ban_list_file: Option<PathBuf>,
     /// How to configure the [`SessionManager`](crate::session::SessionManager).
     pub sessions_config: SessionsConfig,
     /// The chain id
@@ -194,6 +197,10 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     listener_addr: Option<SocketAddr>,
     /// How to instantiate peer manager.
     peers_config: Option<PeersConfig>,
+    /// Additional bans, shared by the peer manager and discovery.
+    ban_list: BanList,
+    /// The file the ban list is written to whenever it changes.
+    ban_list_file: Option<PathBuf>,
     /// How to configure the sessions manager
     sessions_config: Option<SessionsConfig>,
     /// The default mode of the network.
@@ -246,6 +253,8 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             discovery_addr: None,
             listener_addr: None,
             peers_config: None,
+            ban_list: Default::default(),
+            ban_list_file: None,
             sessions_config: None,
             network_mode: Default::default(),
             executor: None,
@@ -334,6 +343,24 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Adds the given bans, for example restored from a previous run.
+    ///
+    /// The bans are applied to both the peer manager and discovery.
+    pub fn ban_list(mut self, ban_list: BanList) -> Self {
+        self.ban_list.extend(&ban_list);
+        self
+    }
+
+    /// Sets the file the ban list is written to whenever it changes, so that the bans survive
+    /// restarts.
+    ///
+    /// The file can be loaded on startup with [`BanList::from_persisted`] and passed to
+    /// [`Self::ban_list`].
+    pub fn persistent_ban_list_file(mut self, file: Option<PathBuf>) -> Self {
+        self.ban_list_file = file;
+        self
+    }
+
     /// Sets a custom config for how sessions are handled.
     pub const fn sessions_config(mut self, config: SessionsConfig) -> Self {
         self.sessions_config = Some(config);
@@ -586,6 +613,8 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             discovery_addr,
             listener_addr,
             peers_config,
+            ban_list,
+            ban_list_file,
             sessions_config,
             network_mode,
             executor,
@@ -618,6 +647,16 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
 
         let listener_addr = listener_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS);
 
+        // share the bans of the peer manager with discovery, so that banned peers are filtered
+        // before we spend any work on them. The bans are applied to discovery v5 once it runs
+        let mut peers_config = peers_config.unwrap_or_default();
+        peers_config.ban_list.extend(&ban_list);
+        let discovery_v4_config = discovery_v4_builder.map(|builder| {
+            let mut config = builder.build();
+            config.ban_list.extend(&peers_config.ban_list);
+            config
+        });
+
         let mut hello_message =
             hello_message.unwrap_or_else(|| HelloMessage::builder(peer_id).build());
         hello_message.port = listener_addr.port();
@@ -647,11 +686,12 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             secret_key,
             boot_nodes,
             dns_discovery_config,
-            discovery_v4_config: discovery_v4_builder.map(|builder| builder.build()),
+            discovery_v4_config,
             discovery_v5_config: discovery_v5_builder.map(|builder| builder.build()),
             discovery_v4_addr: discovery_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS),
             listener_addr,
-            peers_config: peers_config.unwrap_or_default(),
+            peers_config,
+            ban_list_file,
             sessions_config: sessions_config.unwrap_or_default(),
             chain_id,
             block_import: block_import.unwrap_or_else(|| Box::<ProofOfStakeBlockImport>::default()),
@@ -12,6 +12,7 @@ use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
+use reth_net_banlist::BanList;
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerAddr;
@@ -167,6 +168,19 @@ impl Discovery {
         }
     }
 
+    /// Applies all bans of the given [`BanList`] to discovery v5.
+    ///
+    /// Discovery v4 is configured with the ban list on startup, see [`Discv4Config::ban_list`].
+    pub(crate) fn ban_all_discv5(&self, ban_list: &BanList) {
+        let Some(discv5) = &self.discv5 else { return };
+        for (ip, until) in ban_list.banned_ips() {
+            discv5.ban_ip_until(ip, until);
+        }
+        for (peer_id, until) in ban_list.banned_peers() {
+            discv5.ban_peer_until(peer_id, until);
+        }
+    }
+
     /// Bans the [`PeerId`] and [`IpAddr`] in the discovery service.
     pub(crate) fn ban(&self, peer_id: PeerId, ip: IpAddr) {
         if let Some(discv4) = &self.discv4 {
@@ -87,6 +87,12 @@ where
     fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<C::Header> {
         let GetBlockHeaders { start_block, limit, skip, direction } = request;
 
+        if skip == 0 {
+            if let Some(headers) = self.get_contiguous_headers(start_block, limit, direction) {
+                return headers
+            }
+        }
+
         let mut headers = Vec::new();
 
         let mut block: BlockHashOrNumber = match start_block {
@@ -143,6 +149,57 @@ where
         headers
     }
 
+    /// Returns the requested headers with a single range read if the request starts at a canonical
+    /// block and doesn't skip any blocks.
+    ///
+    /// Returns `None` if the request can't be served this way, in which case the headers are
+    /// looked up one by one.
+    fn get_contiguous_headers(
+        &self,
+        start_block: BlockHashOrNumber,
+        limit: u64,
+        direction: HeadersDirection,
+    ) -> Option<Vec<C::Header>> {
+        let start = match start_block {
+            BlockHashOrNumber::Hash(hash) => self.client.block_number(hash).ok()??,
+            BlockHashOrNumber::Number(num) => num,
+        };
+        let count = limit.min(MAX_HEADERS_SERVE as u64);
+        if count == 0 {
+            return Some(Vec::new())
+        }
+
+        let mut headers = match direction {
+            HeadersDirection::Rising => {
+                self.client.headers_range_vectored(start..=start.saturating_add(count - 1)).ok()?
+            }
+            HeadersDirection::Falling => {
+                let mut headers = self
+                    .client
+                    .headers_range_vectored(start.saturating_sub(count - 1)..=start)
+                    .ok()?;
+                headers.reverse();
+                headers
+            }
+        };
+        if headers.first().map(|header| header.number()) != Some(start) {
+            return None
+        }
+
+        let mut total_bytes = 0;
+        let mut len = 0;
+        for header in &headers {
+            total_bytes += header.length();
+            len += 1;
+            if total_bytes > SOFT_RESPONSE_LIMIT {
+                break
+            }
+        }
+        headers.truncate(len);
+
+        Some(headers)
+    }
+
     fn on_headers_request(
         &self,
         _peer_id: PeerId,
@@ -0,0 +1,158 @@
+//! Rate limiting of inbound connection attempts.
+
+use reth_net_banlist::is_global;
+use reth_network_types::InboundRateLimitConfig;
+use std::{collections::HashMap, net::IpAddr, time::Instant};
+
+/// Reason an inbound connection attempt was rate limited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InboundRateLimitExceeded {
+    /// The IP address exceeded its limit.
+    Ip,
+    /// The subnet of the IP address exceeded its limit.
+    Subnet,
+}
+
+/// Number of connection attempts within the current interval.
+#[derive(Debug, Clone, Copy)]
+struct Attempts {
+    /// Start of the current interval.
+    since: Instant,
+    /// Connection attempts since the start of the interval.
+    count: usize,
+}
+
+impl Attempts {
+    /// Records an attempt at `now` and returns the number of attempts in the current interval.
+    fn record(&mut self, now: Instant, config: &InboundRateLimitConfig) -> usize {
+        if now.saturating_duration_since(self.since) >= config.interval {
+            *self = Self { since: now, count: 0 };
+        }
+        self.count += 1;
+        self.count
+    }
+}
+
+/// Counts inbound connection attempts per IP address and per subnet.
+///
+/// This is consulted for every accepted TCP connection, before any RLPx handshake work is done.
+#[derive(Debug)]
+pub(crate) struct InboundRateLimiter {
+    config: InboundRateLimitConfig,
+    /// Attempts per IP address.
+    ips: HashMap<IpAddr, Attempts>,
+    /// Attempts per subnet.
+    subnets: HashMap<IpAddr, Attempts>,
+}
+
+impl InboundRateLimiter {
+    /// Creates a new rate limiter with the given config.
+    pub(crate) fn new(config: InboundRateLimitConfig) -> Self {
+        Self { config, ips: Default::default(), subnets: Default::default() }
+    }
+
+    /// Returns true if any limit is configured.
+    pub(crate) const fn is_enabled(&self) -> bool {
+        self.config.is_enabled()
+    }
+
+    /// Records a connection attempt from the given IP address.
+    ///
+    /// Returns an error if the IP address or its subnet exceeded its limit.
+    pub(crate) fn on_attempt(
+        &mut self,
+        ip: IpAddr,
+        now: Instant,
+    ) -> Result<(), InboundRateLimitExceeded> {
+        if !is_global(&ip) {
+            return Ok(())
+        }
+        let config = self.config;
+
+        if config.max_attempts_per_ip > 0 {
+            let attempts = self
+                .ips
+                .entry(ip)
+                .or_insert(Attempts { since: now, count: 0 })
+                .record(now, &config);
+            if attempts > config.max_attempts_per_ip {
+                return Err(InboundRateLimitExceeded::Ip)
+            }
+        }
+
+        if config.max_attempts_per_subnet > 0 {
+            if let Some(subnet) = config.subnet(ip) {
+                let attempts = self
+                    .subnets
+                    .entry(subnet)
+                    .or_insert(Attempts { since: now, count: 0 })
+                    .record(now, &config);
+                if attempts > config.max_attempts_per_subnet {
+                    return Err(InboundRateLimitExceeded::Subnet)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes all counters whose interval elapsed.
+    pub(crate) fn evict(&mut self, now: Instant) {
+        let interval = self.config.interval;
+        let is_active =
+            |attempts: &Attempts| now.saturating_duration_since(attempts.since) < interval;
+        self.ips.retain(|_, attempts| is_active(attempts));
+        self.subnets.retain(|_, attempts| is_active(attempts));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn limits_ip_and_subnet() {
+        let config = InboundRateLimitConfig {
+            max_attempts_per_ip: 2,
+            max_attempts_per_subnet: 3,
+            ..Default::default()
+        };
+        let mut limiter = InboundRateLimiter::new(config);
+        let now = Instant::now();
+
+        let ip: IpAddr = "1.2.3.4".parse().unwrap();
+        assert_eq!(limiter.on_attempt(ip, now), Ok(()));
+        assert_eq!(limiter.on_attempt(ip, now), Ok(()));
+        assert_eq!(limiter.on_attempt(ip, now), Err(InboundRateLimitExceeded::Ip));
+
+        // the rejected attempt does not count against the subnet
+        let neighbour: IpAddr = "1.2.3.5".parse().unwrap();
+        assert_eq!(limiter.on_attempt(neighbour, now), Ok(()));
+        assert_eq!(limiter.on_attempt(neighbour, now), Err(InboundRateLimitExceeded::Subnet));
+
+        // other subnets and private ips are not affected
+        assert_eq!(limiter.on_attempt("1.2.4.4".parse().unwrap(), now), Ok(()));
+        for _ in 0..10 {
+            assert_eq!(limiter.on_attempt("192.168.0.1".parse().unwrap(), now), Ok(()));
+        }
+
+        // counters reset once the interval elapsed
+        let later = now + config.interval + Duration::from_secs(1);
+        limiter.evict(later);
+        assert!(limiter.ips.is_empty() && limiter.subnets.is_empty());
+        assert_eq!(limiter.on_attempt(ip, later), Ok(()));
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut limiter = InboundRateLimiter::new(Default::default());
+        assert!(!limiter.is_enabled());
+
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert_eq!(limiter.on_attempt("1.2.3.4".parse().unwrap(), now), Ok(()));
+        }
+        assert!(InboundRateLimiter::new(InboundRateLimitConfig::enabled()).is_enabled());
+    }
+}
@@ -136,6 +136,7 @@ mod builder;
 mod discovery;
 mod fetch;
 mod flattened_response;
+mod inbound_rate_limit;
 mod listener;
 mod manager;
 mod metrics;
@@ -147,6 +148,7 @@ mod trusted_peers_resolver;
 
 pub use reth_eth_wire::{DisconnectReason, HelloMessageWithProtocols};
 pub use reth_eth_wire_types::{primitives, EthNetworkPrimitives, NetworkPrimitives};
+pub use reth_net_banlist::BanList;
 pub use reth_network_api::{
     events, BlockDownloaderProvider, DiscoveredEvent, DiscoveryEvent, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, PeerRequest, PeerRequestSender, Peers, PeersInfo,
@@ -41,6 +41,7 @@ use reth_chainspec::EnrForkIdEntry;
 use reth_eth_wire::{DisconnectReason, EthNetworkPrimitives, NetworkPrimitives};
 use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
+use reth_net_banlist::BanList;
 use reth_network_api::{
     events::{PeerEvent, SessionInfo},
     test_utils::PeersHandle,
@@ -54,7 +55,7 @@ use reth_tokio_util::EventSender;
 use secp256k1::SecretKey;
 use std::{
     net::SocketAddr,
-    path::Path,
+    path::{Path, PathBuf},
     pin::Pin,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
@@ -63,7 +64,10 @@ use std::{
     task::{Context, Poll},
     time::{Duration, Instant},
 };
-use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::{
+    sync::mpsc::{self, error::TrySendError},
+    time::Interval,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, error, trace, warn};
 
@@ -138,6 +142,23 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
     disconnect_metrics: DisconnectMetrics,
+    /// Writes the ban list to a file whenever it changes, if configured.
+    ban_list_persistence: Option<BanListPersistence>,
+}
+
+/// Interval at which the ban list is checked for changes that need to be written to its file.
+const BAN_LIST_PERSIST_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Writes the ban list of the [`PeersManager`] to a file whenever it changes, at most once per
+/// [`BAN_LIST_PERSIST_INTERVAL`].
+#[derive(Debug)]
+struct BanListPersistence {
+    /// The file the ban list is written to.
+    file: PathBuf,
+    /// The ban list as it was last written to the file.
+    written: BanList,
+    /// Interval at which the ban list is checked for changes.
+    interval: Interval,
 }
 
 impl NetworkManager {
@@ -235,6 +256,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             mut discovery_v5_config,
             listener_addr,
             peers_config,
+            ban_list_file,
             sessions_config,
             chain_id,
             block_import,
@@ -245,7 +267,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             status,
             fork_filter,
             dns_discovery_config,
-            extra_protocols,
+            mut extra_protocols,
             tx_gossip_disabled,
             transactions_manager_config: _,
             nat,
@@ -254,6 +276,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
 
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
+        extra_protocols.set_peers_handle(peers_handle.clone());
 
         let incoming = ConnectionListener::bind(listener_addr).await.map_err(|err| {
             NetworkError::from_io_error(err, ServiceKind::Listener(listener_addr))
@@ -292,6 +315,14 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         let local_peer_id = discovery.local_id();
         let discv4 = discovery.discv4();
         let discv5 = discovery.discv5();
+        // discovery v4 is configured with the bans, discovery v5 only once it's running
+        discovery.ban_all_discv5(peers_manager.ban_list());
+
+        let ban_list_persistence = ban_list_file.map(|file| BanListPersistence {
+            file,
+            written: peers_manager.ban_list().clone(),
+            interval: tokio::time::interval(BAN_LIST_PERSIST_INTERVAL),
+        });
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
@@ -346,6 +377,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
+            ban_list_persistence,
         })
     }
 
@@ -433,6 +465,42 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         Ok(())
     }
 
+    /// Write the currently banned peers and ips of the [`NetworkManager`] to the given
+    /// `persistent_ban_list_file`.
+    ///
+    /// The file can be loaded on startup via [`NetworkConfigBuilder::ban_list`] to restore the
+    /// bans.
+    ///
+    /// [`NetworkConfigBuilder::ban_list`]: crate::NetworkConfigBuilder::ban_list
+    pub fn write_ban_list_to_file(
+        &self,
+        persistent_ban_list_file: &Path,
+    ) -> Result<(), FsPathError> {
+        write_ban_list(self.swarm.state().peers().ban_list(), persistent_ban_list_file)
+    }
+
+    /// Writes the ban list to the configured file if it changed since it was last written.
+    fn poll_ban_list_persistence(&mut self, cx: &mut Context<'_>) {
+        let Some(persistence) = self.ban_list_persistence.as_mut() else { return };
+        if persistence.interval.poll_tick(cx).is_pending() {
+            return
+        }
+
+        let ban_list = self.swarm.state().peers().ban_list();
+        if *ban_list == persistence.written {
+            return
+        }
+        match write_ban_list(ban_list, &persistence.file) {
+            Ok(()) => {
+                trace!(target: "net", file=?persistence.file, "Wrote ban list to file");
+                persistence.written = ban_list.clone();
+            }
+            Err(err) => {
+                warn!(target: "net", %err, "Failed to write ban list to file");
+            }
+        }
+    }
+
     /// Returns a new [`FetchClient`] that can be cloned and shared.
     ///
     /// The [`FetchClient`] is the entrypoint for sending requests to the network.
@@ -693,6 +761,18 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::ReputationChange(peer_id, kind) => {
                 self.swarm.state_mut().peers_mut().apply_reputation_change(&peer_id, kind);
             }
+            NetworkHandleMessage::GetStaticPeers(tx) => {
+                let _ = tx.send(self.swarm.state().peers().static_peers_status());
+            }
+            NetworkHandleMessage::GetOutboundBandwidth(tx) => {
+                let _ = tx.send(self.swarm.sessions().outbound_bandwidth().status());
+            }
+            NetworkHandleMessage::SetOutboundBandwidth(max_bytes_per_sec) => {
+                self.swarm.sessions().outbound_bandwidth().set_max_bytes_per_sec(max_bytes_per_sec);
+            }
+            NetworkHandleMessage::SetOutboundBandwidthWeights(weights) => {
+                self.swarm.sessions().outbound_bandwidth().set_weights(weights);
+            }
             NetworkHandleMessage::GetReputationById(peer_id, tx) => {
                 let _ = tx.send(self.swarm.state_mut().peers().get_reputation(&peer_id));
             }
@@ -1079,6 +1159,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
     }
 }
 
+/// Writes the given ban list to the file, see [`BanList::to_persisted`].
+fn write_ban_list(ban_list: &BanList, file: &Path) -> Result<(), FsPathError> {
+    file.parent().map(fs::create_dir_all).transpose()?;
+    fs::write_json_file(file, &ban_list.to_persisted())
+}
+
 impl<N: NetworkPrimitives> Future for NetworkManager<N> {
     type Output = ();
 
@@ -1145,6 +1231,8 @@ impl<N: NetworkPrimitives> Future for NetworkManager<N> {
             return Poll::Pending
         }
 
+        this.poll_ban_list_persistence(cx);
+
         this.update_poll_metrics(start, poll_durations);
 
         Poll::Pending
@@ -98,6 +98,13 @@ pub struct TransactionsManagerMetrics {
     pub(crate) propagated_transactions: Counter,
     /// Total number of reported bad transactions
     pub(crate) reported_bad_transactions: Counter,
+    /// Time hashes of new pending transactions were delayed by before they were announced to a
+    /// peer, measured from the first hash of an announcement.
+    ///
+    /// Duration in seconds.
+    pub(crate) announcement_latency: Histogram,
+    /// Total number of announcements that were sent from a batch of hashes.
+    pub(crate) batched_announcements: Counter,
 
     /* -- Freq txns already marked as seen by peer -- */
     /// Total number of messages from a peer, announcing transactions that have already been
@@ -131,6 +138,9 @@ pub struct TransactionsManagerMetrics {
     /// capacity. Note, this is not a limit to the number of inflight requests, but a health
     /// measure.
     pub(crate) capacity_pending_pool_imports: Counter,
+    /// Total number of transactions dropped before import, because the validation budget of the
+    /// peer they were received from was exhausted.
+    pub(crate) validation_budget_exceeded_by_peer: Counter,
 
     /* ================ POLL DURATION ================ */
 
@@ -212,6 +222,9 @@ pub struct TransactionFetcherMetrics {
     /// [`PooledTransactions`](reth_eth_wire::PooledTransactions) responses, that weren't
     /// requested.
     pub(crate) unsolicited_transactions: Counter,
+    /// Total number of fetched transactions that didn't match the type or size the peer
+    /// announced for them.
+    pub(crate) announcement_mismatch_transactions: Counter,
     /* ================ SEARCH DURATION ================ */
     /// Time spent searching for an idle peer in call to
     /// [`TransactionFetcher::find_any_idle_fallback_peer_for_any_pending_hash`](crate::transactions::TransactionFetcher::find_any_idle_fallback_peer_for_any_pending_hash).
@@ -7,7 +7,7 @@ use enr::Enr;
 use futures::StreamExt;
 use parking_lot::Mutex;
 use reth_discv4::{Discv4, NatResolver};
-use reth_discv5::Discv5;
+use reth_discv5::{enr_to_discv4_id, Discv5};
 use reth_eth_wire::{
     BlockRangeUpdate, DisconnectReason, EthNetworkPrimitives, NetworkPrimitives,
     NewPooledTransactionHashes, SharedTransactions,
@@ -16,9 +16,10 @@ use reth_ethereum_forks::Head;
 use reth_network_api::{
     events::{NetworkPeersEvents, PeerEvent, PeerEventStream},
     test_utils::{PeersHandle, PeersHandleProvider},
-    BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
-    NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    BlockDownloaderProvider, DiscoveryEvent, DiscoveryTable, DiscoveryTableEntry, NetworkError,
+    NetworkEvent, NetworkEventListenerProvider, NetworkInfo, NetworkStatus,
+    OutboundBandwidthStatus, OutboundBandwidthWeights, PeerInfo, PeerRequest, Peers, PeersInfo,
+    StaticPeerStatus,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
@@ -27,6 +28,7 @@ use reth_tokio_util::{EventSender, EventStream};
 use secp256k1::SecretKey;
 use std::{
     net::SocketAddr,
+    num::NonZeroU64,
     sync::{
         atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
@@ -312,6 +314,26 @@ impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
         Ok(rx.await?)
     }
 
+    async fn get_static_peers(&self) -> Result<Vec<StaticPeerStatus>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetStaticPeers(tx));
+        Ok(rx.await?)
+    }
+
+    async fn outbound_bandwidth(&self) -> Result<OutboundBandwidthStatus, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetOutboundBandwidth(tx));
+        Ok(rx.await?)
+    }
+
+    fn set_outbound_bandwidth(&self, max_bytes_per_sec: Option<NonZeroU64>) {
+        self.send_message(NetworkHandleMessage::SetOutboundBandwidth(max_bytes_per_sec));
+    }
+
+    fn set_outbound_bandwidth_weights(&self, weights: OutboundBandwidthWeights) {
+        self.send_message(NetworkHandleMessage::SetOutboundBandwidthWeights(weights));
+    }
+
     /// Sends a message to the [`NetworkManager`](crate::NetworkManager) to remove a peer from the
     /// set corresponding to given kind.
     fn remove_peer(&self, peer: PeerId, kind: PeerKind) {
@@ -386,6 +408,54 @@ impl<N: NetworkPrimitives> NetworkInfo for NetworkHandle<N> {
     fn is_initially_syncing(&self) -> bool {
         SyncStateProvider::is_initially_syncing(self)
     }
+
+    async fn discovery_table(&self) -> Result<DiscoveryTable, NetworkError> {
+        let discv4 = match &self.inner.discv4 {
+            Some(discv4) => {
+                let entries =
+                    discv4.table_entries().await.map_err(|_| NetworkError::ChannelClosed)?;
+                Some(
+                    entries
+                        .into_iter()
+                        .map(|entry| DiscoveryTableEntry {
+                            id: entry.record.id,
+                            enode: Some(entry.record),
+                            enr: None,
+                            distance: entry.distance,
+                            connected: entry.connected,
+                            last_seen_secs: Some(entry.last_seen.as_secs()),
+                            fork_id: entry.fork_id,
+                            endpoint_proven: Some(entry.has_endpoint_proof),
+                        })
+                        .collect(),
+                )
+            }
+            None => None,
+        };
+
+        let discv5 = self.inner.discv5.as_ref().map(|discv5| {
+            let (local_id, entries) =
+                discv5.with_discv5(|discv5| (discv5.local_enr().node_id(), discv5.table_entries()));
+            let local_key = discv5::kbucket::Key::from(local_id);
+            entries
+                .into_iter()
+                .filter_map(|(node_id, enr, status)| {
+                    Some(DiscoveryTableEntry {
+                        id: enr_to_discv4_id(&enr)?,
+                        enode: None,
+                        enr: Some(enr.to_base64()),
+                        distance: local_key.log2_distance(&discv5::kbucket::Key::from(node_id)),
+                        connected: status.is_connected(),
+                        last_seen_secs: None,
+                        fork_id: discv5.get_fork_id(&enr).ok(),
+                        endpoint_proven: None,
+                    })
+                })
+                .collect()
+        });
+
+        Ok(DiscoveryTable { discv4, discv5 })
+    }
 }
 
 impl<N: NetworkPrimitives> SyncStateProvider for NetworkHandle<N> {
@@ -532,6 +602,14 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     GetPeerInfoById(PeerId, oneshot::Sender<Option<PeerInfo>>),
     /// Gets `PeerInfo` for a specific peer kind via a oneshot sender.
     GetPeerInfosByPeerKind(PeerKind, oneshot::Sender<Vec<PeerInfo>>),
+    /// Gets the status and connection history of all trusted and static peers.
+    GetStaticPeers(oneshot::Sender<Vec<StaticPeerStatus>>),
+    /// Gets the global outbound bandwidth limit and the traffic sent per class.
+    GetOutboundBandwidth(oneshot::Sender<OutboundBandwidthStatus>),
+    /// Sets the maximum number of bytes per second that are sent to all peers combined.
+    SetOutboundBandwidth(Option<NonZeroU64>),
+    /// Sets the weights of the outbound traffic classes.
+    SetOutboundBandwidthWeights(OutboundBandwidthWeights),
     /// Gets the reputation for a specific peer via a oneshot sender.
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
@@ -2,6 +2,7 @@
 
 use crate::{
     error::SessionError,
+    inbound_rate_limit::{InboundRateLimitExceeded, InboundRateLimiter},
     session::{Direction, PendingSessionHandshakeError},
     swarm::NetworkConnectionState,
     trusted_peers_resolver::TrustedPeersResolver,
@@ -11,7 +12,10 @@ use futures::StreamExt;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_ethereum_forks::ForkId;
 use reth_net_banlist::BanList;
-use reth_network_api::test_utils::{PeerCommand, PeersHandle};
+use reth_network_api::{
+    test_utils::{PeerCommand, PeersHandle},
+    StaticPeerStatus,
+};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
     is_connection_failed_reputation,
@@ -19,8 +23,9 @@ use reth_network_types::{
         config::PeerBackoffDurations,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
-    ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    ConnectionsConfig, DialSchedulerConfig, Peer, PeerAddr, PeerConnectionState, PeerKind,
+    PeersConfig, ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    StaticPeersConfig,
 };
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
@@ -36,7 +41,7 @@ use tokio::{
     time::{Instant, Interval},
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use tracing::{trace, warn};
+use tracing::{debug, trace, warn};
 
 /// Maintains the state of _all_ the peers known to the network.
 ///
@@ -90,6 +95,16 @@ pub struct PeersManager {
     net_connection_state: NetworkConnectionState,
     /// How long to temporarily ban ip on an incoming connection attempt.
     incoming_ip_throttle_duration: Duration,
+    /// How peers are selected for outbound connections.
+    dial_scheduler: DialSchedulerConfig,
+    /// Tracks when outbound connections to peers were scheduled, used to measure their latency.
+    pending_dials: HashMap<PeerId, std::time::Instant>,
+    /// Rate limits inbound connection attempts per ip and subnet.
+    inbound_rate_limiter: InboundRateLimiter,
+    /// How to redial trusted and static peers.
+    static_peers: StaticPeersConfig,
+    /// Interval at which to check for disconnected trusted and static peers.
+    health_check_interval: Interval,
 }
 
 impl PeersManager {
@@ -108,6 +123,9 @@ impl PeersManager {
             basic_nodes,
             max_backoff_count,
             incoming_ip_throttle_duration,
+            dial_scheduler,
+            inbound_rate_limit,
+            static_peers,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -161,6 +179,14 @@ impl PeersManager {
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
             incoming_ip_throttle_duration,
+            dial_scheduler,
+            pending_dials: Default::default(),
+            inbound_rate_limiter: InboundRateLimiter::new(inbound_rate_limit),
+            static_peers,
+            health_check_interval: tokio::time::interval_at(
+                now + static_peers.health_check_interval,
+                static_peers.health_check_interval,
+            ),
         }
     }
 
@@ -231,6 +257,14 @@ impl PeersManager {
         self.backed_off_peers.len()
     }
 
+    /// Returns true if the ip address belongs to a trusted peer.
+    fn is_trusted_ip(&self, ip: IpAddr) -> bool {
+        self.trusted_peer_ids
+            .iter()
+            .filter_map(|peer_id| self.peers.get(peer_id))
+            .any(|peer| peer.addr.tcp().ip() == ip)
+    }
+
     /// Returns the number of idle trusted peers.
     fn num_idle_trusted_peers(&self) -> usize {
         self.peers.iter().filter(|(_, peer)| peer.kind.is_trusted() && peer.state.is_idle()).count()
@@ -238,7 +272,9 @@ impl PeersManager {
 
     /// Invoked when a new _incoming_ tcp connection is accepted.
     ///
-    /// returns an error if the inbound ip address is on the ban list
+    /// returns an error if the inbound ip address is on the ban list or exceeded the rate limit of
+    /// inbound connection attempts, if enabled. An ip address that exceeds its rate limit is
+    /// banned.
     pub(crate) fn on_incoming_pending_session(
         &mut self,
         addr: IpAddr,
@@ -247,6 +283,26 @@ impl PeersManager {
             return Err(InboundConnectionError::IpBanned)
         }
 
+        // only attempts that passed the ban list are counted, so that peers that retry while
+        // throttled are not banned, and trusted peers are never rate limited
+        if self.inbound_rate_limiter.is_enabled() && !self.is_trusted_ip(addr) {
+            match self.inbound_rate_limiter.on_attempt(addr, std::time::Instant::now()) {
+                Ok(()) => {}
+                Err(InboundRateLimitExceeded::Ip) => {
+                    trace!(
+                        target: "net::peers",
+                        ?addr,
+                        "Banning ip that exceeded the inbound rate limit"
+                    );
+                    self.ban_ip(addr);
+                    return Err(InboundConnectionError::RateLimited)
+                }
+                Err(InboundRateLimitExceeded::Subnet) => {
+                    return Err(InboundConnectionError::RateLimited)
+                }
+            }
+        }
+
         // check if we even have slots for a new incoming connection
         if !self.connection_info.has_in_capacity() {
             if self.trusted_peer_ids.is_empty() {
@@ -354,6 +410,9 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                if peer.is_trusted_or_static() {
+                    peer.history.on_session_established();
+                }
 
                 is_trusted = is_trusted || peer.is_trusted();
             }
@@ -395,6 +454,11 @@ impl PeersManager {
         self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
     }
 
+    /// Returns the list of banned peers and ips.
+    pub(crate) const fn ban_list(&self) -> &BanList {
+        &self.ban_list
+    }
+
     /// Bans the IP temporarily with the configured ban timeout
     fn ban_ip(&mut self, ip: IpAddr) {
         self.ban_list.ban_ip_until(ip, std::time::Instant::now() + self.ban_duration);
@@ -526,6 +590,9 @@ impl PeersManager {
         match self.peers.entry(peer_id) {
             Entry::Occupied(mut entry) => {
                 self.connection_info.decr_state(entry.get().state);
+                if entry.get().is_trusted_or_static() {
+                    entry.get_mut().history.on_session_closed(None);
+                }
 
                 if entry.get().remove_after_disconnect && !entry.get().is_trusted() {
                     // this peer should be removed from the set
@@ -552,6 +619,12 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            if peer.is_trusted_or_static() {
+                peer.history.on_session_established();
+            }
+            if let Some(dialed_at) = self.pending_dials.remove(&peer_id) {
+                peer.record_latency(dialed_at.elapsed());
+            }
         }
     }
 
@@ -604,6 +677,16 @@ impl PeersManager {
     ) {
         trace!(target: "net::peers", ?remote_addr, ?peer_id, %err, "handling failed connection");
 
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            if peer.is_trusted_or_static() {
+                if peer.state.is_pending_out() {
+                    peer.history.on_connection_failure(err.to_string());
+                } else {
+                    peer.history.on_session_closed(Some(err.to_string()));
+                }
+            }
+        }
+
         if err.is_fatal_protocol_error() {
             trace!(target: "net::peers", ?remote_addr, ?peer_id, %err, "fatal connection error");
             // remove the peer to which we can't establish a connection due to protocol related
@@ -634,10 +717,11 @@ impl PeersManager {
 
             if let Some(peer) = self.peers.get_mut(peer_id) {
                 if let Some(kind) = err.should_backoff() {
-                    if peer.is_trusted() || peer.is_static() {
-                        // provide a bit more leeway for trusted peers and use a lower backoff so
-                        // that we keep re-trying them after backing off shortly
-                        let backoff = self.backoff_durations.low / 2;
+                    if peer.is_trusted_or_static() {
+                        // provide a bit more leeway for trusted peers and use the configured redial
+                        // backoff so that we keep re-trying them after backing off shortly
+                        let backoff =
+                            self.static_peers.redial_backoff(peer.history.consecutive_failures);
                         backoff_until = Some(std::time::Instant::now() + backoff);
                     } else {
                         // Increment peer.backoff_counter
@@ -883,21 +967,23 @@ impl PeersManager {
     /// If `trusted_nodes_only` is enabled, see [`PeersConfig`], then this will only consider
     /// `trusted` peers.
     ///
+    /// Peers in a subnet or network group that already reached the outbound limit of the
+    /// [`DialSchedulerConfig`] are skipped, unless they're trusted or static. Among peers with the
+    /// same reputation, the one in the least used network group is preferred, then the one with
+    /// the lowest measured latency, if enabled.
+    ///
     /// Returns `None` if no peer is available.
-    fn best_unconnected(&mut self) -> Option<(PeerId, &mut Peer)> {
-        let mut unconnected = self.peers.iter_mut().filter(|(_, peer)| {
+    fn best_unconnected(&mut self, outbound: &OutboundPrefixes) -> Option<(PeerId, &mut Peer)> {
+        let dial_scheduler = self.dial_scheduler;
+        let unconnected = self.peers.iter_mut().filter(|(_, peer)| {
             !peer.is_backed_off() &&
                 !peer.is_banned() &&
                 peer.state.is_unconnected() &&
                 (!self.trusted_nodes_only || peer.is_trusted())
         });
 
-        // keep track of the best peer, if there's one
-        let mut best_peer = unconnected.next()?;
-
-        if best_peer.1.is_trusted() || best_peer.1.is_static() {
-            return Some((*best_peer.0, best_peer.1))
-        }
+        // keep track of the best peer and the load of its network group, if there's one
+        let mut best_peer: Option<(&PeerId, &mut Peer, usize)> = None;
 
         for maybe_better in unconnected {
             // if the peer is trusted or static, return it immediately
@@ -905,12 +991,22 @@ impl PeersManager {
                 return Some((*maybe_better.0, maybe_better.1))
             }
 
-            // otherwise we keep track of the best peer using the reputation
-            if maybe_better.1.reputation > best_peer.1.reputation {
-                best_peer = maybe_better;
+            // skip peers in subnets or network groups we're already connected to enough
+            let Some(group_load) =
+                outbound.group_load(&dial_scheduler, maybe_better.1.addr.tcp().ip())
+            else {
+                continue
+            };
+
+            // otherwise we keep track of the best peer using the reputation, the load of the
+            // network group and latency
+            if best_peer.as_ref().is_none_or(|(_, best, best_group_load)| {
+                is_better_dial(&dial_scheduler, maybe_better.1, group_load, best, *best_group_load)
+            }) {
+                best_peer = Some((maybe_better.0, maybe_better.1, group_load));
             }
         }
-        Some((*best_peer.0, best_peer.1))
+        best_peer.map(|(peer_id, peer, _)| (*peer_id, peer))
     }
 
     /// If there's capacity for new outbound connections, this will queue new
@@ -926,26 +1022,113 @@ impl PeersManager {
             return
         }
 
+        // count the outbound connections per subnet and network group, pending or active
+        let mut outbound = OutboundPrefixes::default();
+        for peer in self.peers.values().filter(|peer| peer.state.is_outgoing()) {
+            outbound.insert(&self.dial_scheduler, peer.addr.tcp().ip());
+        }
+
+        // drop measurements of dials that didn't result in an outbound session
+        let peers = &self.peers;
+        self.pending_dials.retain(|peer_id, _| {
+            peers.get(peer_id).is_some_and(|peer| peer.state.is_pending_out())
+        });
+
         // as long as there are slots available fill them with the best peers
         while self.connection_info.has_out_capacity() {
-            let action = {
-                let (peer_id, peer) = match self.best_unconnected() {
+            let dial_scheduler = self.dial_scheduler;
+            let (peer_id, action) = {
+                let (peer_id, peer) = match self.best_unconnected(&outbound) {
                     Some(peer) => peer,
                     _ => break,
                 };
 
-                trace!(target: "net::peers", ?peer_id, addr=?peer.addr, "schedule outbound connection");
+                trace!(target: "net::peers", ?peer_id, addr=?peer.addr, latency=?peer.latency, "schedule outbound connection");
+
+                outbound.insert(&dial_scheduler, peer.addr.tcp().ip());
 
                 peer.state = PeerConnectionState::PendingOut;
-                PeerAction::Connect { peer_id, remote_addr: peer.addr.tcp() }
+                (peer_id, PeerAction::Connect { peer_id, remote_addr: peer.addr.tcp() })
             };
 
+            self.pending_dials.insert(peer_id, std::time::Instant::now());
+
             self.connection_info.inc_pending_out();
 
             self.queued_actions.push_back(action);
         }
     }
 
+    /// Redials disconnected trusted and static peers, even if there are no free outbound slots.
+    ///
+    /// Peers that are banned or backed off after a failed connection attempt are skipped. The
+    /// liveness of established sessions is checked by the p2p pings of the sessions, which are
+    /// closed if the peer stops responding.
+    fn redial_static_peers(&mut self) {
+        if !self.net_connection_state.is_active() {
+            return
+        }
+
+        let trusted_nodes_only = self.trusted_nodes_only;
+        let ban_list = &self.ban_list;
+        let disconnected = self.peers.iter_mut().filter(|(peer_id, peer)| {
+            peer.is_trusted_or_static() &&
+                peer.state.is_unconnected() &&
+                !peer.is_backed_off() &&
+                !peer.is_banned() &&
+                !ban_list.is_banned_peer(peer_id) &&
+                (!trusted_nodes_only || peer.is_trusted())
+        });
+
+        for (peer_id, peer) in disconnected {
+            debug!(target: "net::peers", ?peer_id, addr=?peer.addr, consecutive_failures=peer.history.consecutive_failures, "redialing disconnected static peer");
+
+            peer.state = PeerConnectionState::PendingOut;
+            self.pending_dials.insert(*peer_id, std::time::Instant::now());
+            self.connection_info.inc_pending_out();
+            self.queued_actions
+                .push_back(PeerAction::Connect { peer_id: *peer_id, remote_addr: peer.addr.tcp() });
+        }
+    }
+
+    /// Returns the status and connection history of all trusted and static peers.
+    pub(crate) fn static_peers_status(&self) -> Vec<StaticPeerStatus> {
+        self.peers
+            .iter()
+            .filter(|(_, peer)| peer.is_trusted_or_static())
+            .map(|(peer_id, peer)| {
+                let history = &peer.history;
+                StaticPeerStatus {
+                    id: *peer_id,
+                    enode: NodeRecord::new_with_ports(
+                        peer.addr.tcp().ip(),
+                        peer.addr.tcp().port(),
+                        peer.addr.udp().map(|addr| addr.port()),
+                        *peer_id,
+                    ),
+                    trusted: peer.is_trusted(),
+                    connected: peer.state.is_incoming() || peer.state == PeerConnectionState::Out,
+                    backoff_remaining_secs: self
+                        .backed_off_peers
+                        .get(peer_id)
+                        .map(|until| until.saturating_duration_since(std::time::Instant::now()))
+                        .map(|backoff| backoff.as_secs()),
+                    sessions_established: history.sessions_established,
+                    sessions_closed: history.sessions_closed,
+                    connection_failures: history.connection_failures,
+                    consecutive_failures: history.consecutive_failures,
+                    last_established_secs: history
+                        .last_established
+                        .map(|instant| instant.elapsed().as_secs()),
+                    last_closed_secs: history
+                        .last_closed
+                        .map(|instant| instant.elapsed().as_secs()),
+                    last_error: history.last_error.clone(),
+                }
+            })
+            .collect()
+    }
+
     fn on_resolved_peer(&mut self, peer_id: PeerId, new_record: NodeRecord) {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             let new_addr = PeerAddr::new_with_ports(
@@ -1008,6 +1191,7 @@ impl PeersManager {
             if self.release_interval.poll_tick(cx).is_ready() {
                 let now = std::time::Instant::now();
                 let (_, unbanned_peers) = self.ban_list.evict(now);
+                self.inbound_rate_limiter.evict(now);
 
                 for peer_id in unbanned_peers {
                     if let Some(peer) = self.peers.get_mut(&peer_id) {
@@ -1029,6 +1213,10 @@ impl PeersManager {
                 })
             }
 
+            if self.health_check_interval.poll_tick(cx).is_ready() {
+                self.redial_static_peers();
+            }
+
             while self.refill_slots_interval.poll_tick(cx).is_ready() {
                 self.fill_outbound_slots();
             }
@@ -1050,6 +1238,69 @@ impl Default for PeersManager {
     }
 }
 
+/// Number of outbound connections, pending or active, per subnet and network group of the
+/// [`DialSchedulerConfig`].
+#[derive(Debug, Default)]
+struct OutboundPrefixes {
+    subnets: HashMap<IpAddr, usize>,
+    groups: HashMap<IpAddr, usize>,
+}
+
+impl OutboundPrefixes {
+    /// Records an outbound connection to the given address.
+    fn insert(&mut self, config: &DialSchedulerConfig, ip: IpAddr) {
+        if let Some(subnet) = config.subnet(ip) {
+            *self.subnets.entry(subnet).or_default() += 1;
+        }
+        if let Some(group) = config.group(ip) {
+            *self.groups.entry(group).or_default() += 1;
+        }
+    }
+
+    /// Returns the number of outbound connections in the network group of the given address, or
+    /// `None` if its subnet or network group already reached the limit.
+    fn group_load(&self, config: &DialSchedulerConfig, ip: IpAddr) -> Option<usize> {
+        if let Some(subnet) = config.subnet(ip) {
+            if self.subnets.get(&subnet).copied().unwrap_or_default() >=
+                config.max_outbound_per_subnet
+            {
+                return None
+            }
+        }
+        let Some(group) = config.group(ip) else { return Some(0) };
+        let load = self.groups.get(&group).copied().unwrap_or_default();
+        (load < config.max_outbound_per_group).then_some(load)
+    }
+}
+
+/// Returns true if `peer` should be dialed before `best` according to the [`DialSchedulerConfig`].
+///
+/// Peers are ranked by reputation first, then by the number of outbound connections in their
+/// network group and, if enabled, by their measured latency. Peers without a latency measurement
+/// rank behind peers with one.
+fn is_better_dial(
+    config: &DialSchedulerConfig,
+    peer: &Peer,
+    group_load: usize,
+    best: &Peer,
+    best_group_load: usize,
+) -> bool {
+    if peer.reputation != best.reputation {
+        return peer.reputation > best.reputation
+    }
+    if group_load != best_group_load {
+        return group_load < best_group_load
+    }
+    if !config.prefer_low_latency {
+        return false
+    }
+    match (peer.latency, best.latency) {
+        (Some(latency), Some(best_latency)) => latency < best_latency,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
 /// Tracks stats about connected nodes
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct ConnectionInfo {
@@ -1194,6 +1445,8 @@ pub enum InboundConnectionError {
     IpBanned,
     /// No capacity for new inbound connections
     ExceedsCapacity,
+    /// The remote's ip address or subnet exceeded the rate limit of inbound connection attempts
+    RateLimited,
 }
 
 impl Display for InboundConnectionError {
@@ -1213,7 +1466,8 @@ mod tests {
     use reth_network_api::Direction;
     use reth_network_peers::{PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, ReputationChangeKind,
+        peers::reputation::DEFAULT_REPUTATION, BackoffKind, InboundRateLimitConfig, Peer, PeerKind,
+        ReputationChangeKind,
     };
     use std::{
         future::{poll_fn, Future},
@@ -2595,6 +2849,68 @@ mod tests {
         assert!(!peer.remove_after_disconnect);
     }
 
+    #[tokio::test]
+    async fn test_incoming_attempts_rate_limit() {
+        let config = PeersConfig::test().with_inbound_rate_limit(InboundRateLimitConfig {
+            max_attempts_per_ip: 2,
+            ..Default::default()
+        });
+        let mut peers = PeersManager::new(config);
+
+        let addr = IpAddr::V4(Ipv4Addr::new(168, 0, 1, 2));
+        assert!(peers.on_incoming_pending_session(addr).is_ok());
+        peers.on_incoming_pending_session_rejected_internally();
+
+        // attempts while throttled are rejected, but not counted
+        for _ in 0..5 {
+            assert_eq!(
+                peers.on_incoming_pending_session(addr).unwrap_err(),
+                InboundConnectionError::IpBanned
+            );
+        }
+        peers.ban_list.unban_ip(&addr);
+        assert!(peers.on_incoming_pending_session(addr).is_ok());
+        peers.on_incoming_pending_session_rejected_internally();
+
+        // exceeding the limit escalates to a ban
+        peers.ban_list.unban_ip(&addr);
+        assert_eq!(
+            peers.on_incoming_pending_session(addr).unwrap_err(),
+            InboundConnectionError::RateLimited
+        );
+        assert!(peers.ban_list.is_banned_ip(&addr));
+    }
+
+    #[tokio::test]
+    async fn test_incoming_attempts_rate_limit_disabled_by_default() {
+        let mut peers = PeersManager::new(PeersConfig::test());
+
+        let addr = IpAddr::V4(Ipv4Addr::new(168, 0, 1, 2));
+        for _ in 0..20 {
+            // lift the throttle of the previous attempt
+            peers.ban_list.unban_ip(&addr);
+            assert!(peers.on_incoming_pending_session(addr).is_ok());
+            peers.on_incoming_pending_session_rejected_internally();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_incoming_attempts_rate_limit_trusted_peer() {
+        let config = PeersConfig::test().with_inbound_rate_limit(InboundRateLimitConfig {
+            max_attempts_per_ip: 1,
+            ..Default::default()
+        });
+        let mut peers = PeersManager::new(config);
+
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(168, 0, 1, 2)), 30303);
+        peers.add_trusted_peer(PeerId::random(), PeerAddr::from_tcp(addr));
+        for _ in 0..3 {
+            peers.ban_list.unban_ip(&addr.ip());
+            assert!(peers.on_incoming_pending_session(addr.ip()).is_ok());
+            peers.on_incoming_pending_session_rejected_internally();
+        }
+    }
+
     #[tokio::test]
     async fn test_incoming_outgoing_already_connected() {
         let peer_id = PeerId::random();
@@ -2733,6 +3049,102 @@ mod tests {
         assert_eq!(peer_manager.connection_info.num_pending_out, 0);
     }
 
+    #[tokio::test]
+    async fn test_dial_subnet_diversity() {
+        let config = PeersConfig::default();
+        let max_per_subnet = config.dial_scheduler.max_outbound_per_subnet;
+        let mut peer_manager = PeersManager::new(config);
+
+        // more peers in the same public /24 than allowed
+        for i in 0..max_per_subnet * 2 {
+            let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, i as u8 + 1));
+            peer_manager.add_peer(PeerId::random(), PeerAddr::from_tcp((ip, 30303).into()), None);
+        }
+        // a peer in a different subnet
+        let other = PeerId::random();
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 4, 1));
+        peer_manager.add_peer(other, PeerAddr::from_tcp((ip, 30303).into()), None);
+
+        peer_manager.fill_outbound_slots();
+        assert_eq!(peer_manager.connection_info.num_pending_out, max_per_subnet + 1);
+        assert!(peer_manager.peers.get(&other).unwrap().state.is_pending_out());
+
+        // establishing the sessions doesn't free up slots in the subnet
+        let pending = peer_manager
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.state.is_pending_out())
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+        for peer_id in pending {
+            peer_manager.on_active_outgoing_established(peer_id);
+            assert!(peer_manager.peers.get(&peer_id).unwrap().latency.is_some());
+        }
+        peer_manager.fill_outbound_slots();
+        assert_eq!(peer_manager.connection_info.num_pending_out, 0);
+    }
+
+    #[tokio::test]
+    async fn test_dial_group_diversity() {
+        let config = PeersConfig::default();
+        let max_per_group = config.dial_scheduler.max_outbound_per_group;
+        let mut peer_manager = PeersManager::new(config.clone());
+
+        // more peers in distinct subnets of the same public /16 than allowed
+        for i in 0..max_per_group * 2 {
+            let ip = IpAddr::V4(Ipv4Addr::new(1, 2, i as u8, 1));
+            peer_manager.add_peer(PeerId::random(), PeerAddr::from_tcp((ip, 30303).into()), None);
+        }
+        // a peer in a different network group
+        let other = PeerId::random();
+        let ip = IpAddr::V4(Ipv4Addr::new(5, 6, 7, 8));
+        peer_manager.add_peer(other, PeerAddr::from_tcp((ip, 30303).into()), None);
+
+        peer_manager.fill_outbound_slots();
+        assert_eq!(peer_manager.connection_info.num_pending_out, max_per_group + 1);
+        assert!(peer_manager.peers.get(&other).unwrap().state.is_pending_out());
+
+        // with only two outbound slots, the second one goes to the unused network group
+        let mut peer_manager = PeersManager::new(config.with_max_outbound(2));
+        for i in 0..max_per_group * 2 {
+            let ip = IpAddr::V4(Ipv4Addr::new(1, 2, i as u8, 1));
+            peer_manager.add_peer(PeerId::random(), PeerAddr::from_tcp((ip, 30303).into()), None);
+        }
+        peer_manager.add_peer(other, PeerAddr::from_tcp((ip, 30303).into()), None);
+
+        peer_manager.fill_outbound_slots();
+        assert_eq!(peer_manager.connection_info.num_pending_out, 2);
+        assert!(peer_manager.peers.get(&other).unwrap().state.is_pending_out());
+    }
+
+    #[tokio::test]
+    async fn test_dial_prefers_low_latency() {
+        let mut peer_manager = PeersManager::new(PeersConfig::default());
+        let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
+        let peer_addr = PeerAddr::from_tcp(SocketAddr::new(ip, 8008));
+
+        let slow = PeerId::random();
+        let fast = PeerId::random();
+        let unknown = PeerId::random();
+        for peer_id in [slow, fast, unknown] {
+            peer_manager.add_peer(peer_id, peer_addr, None);
+        }
+        peer_manager.peers.get_mut(&slow).unwrap().latency = Some(Duration::from_millis(500));
+        peer_manager.peers.get_mut(&fast).unwrap().latency = Some(Duration::from_millis(50));
+
+        let (best, _) = peer_manager.best_unconnected(&OutboundPrefixes::default()).unwrap();
+        assert_eq!(best, fast);
+
+        peer_manager.peers.get_mut(&fast).unwrap().state = PeerConnectionState::PendingOut;
+        let (best, _) = peer_manager.best_unconnected(&OutboundPrefixes::default()).unwrap();
+        assert_eq!(best, slow);
+
+        // reputation takes precedence over latency
+        peer_manager.peers.get_mut(&unknown).unwrap().reputation += 1;
+        let (best, _) = peer_manager.best_unconnected(&OutboundPrefixes::default()).unwrap();
+        assert_eq!(best, unknown);
+    }
+
     #[tokio::test]
     async fn test_connect() {
         let peer = PeerId::random();
@@ -2895,4 +3307,92 @@ mod tests {
         let updated_peer = manager.peers.get(&peer_id).unwrap();
         assert_eq!(updated_peer.addr.tcp().ip(), updated_ip);
     }
+
+    #[tokio::test]
+    async fn test_static_peer_redial_backoff() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_peer_kind(peer, PeerKind::Static, PeerAddr::from_tcp(socket_addr), None);
+
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        let err = io::Error::new(io::ErrorKind::ConnectionRefused, "");
+        peers.on_outgoing_connection_failure(&socket_addr, &peer, &err);
+
+        let p = peers.peers.get(&peer).unwrap();
+        assert!(p.is_backed_off());
+        assert_eq!(p.history.connection_failures, 1);
+        assert_eq!(p.history.consecutive_failures, 1);
+        assert!(p.history.last_error.is_some());
+        let first_backoff = *peers.backed_off_peers.get(&peer).unwrap();
+
+        // the backoff doubles on consecutive failures
+        peers.peers.get_mut(&peer).unwrap().state = PeerConnectionState::PendingOut;
+        peers.connection_info.inc_pending_out();
+        peers.on_outgoing_connection_failure(&socket_addr, &peer, &err);
+        let p = peers.peers.get(&peer).unwrap();
+        assert_eq!(p.history.consecutive_failures, 2);
+        let second_backoff = *peers.backed_off_peers.get(&peer).unwrap();
+        assert!(
+            second_backoff - first_backoff >=
+                peers.static_peers.redial_backoff(2) - peers.static_peers.redial_backoff(1)
+        );
+
+        // an established session resets the consecutive failures
+        peers.peers.get_mut(&peer).unwrap().state = PeerConnectionState::PendingOut;
+        peers.connection_info.inc_pending_out();
+        peers.on_active_outgoing_established(peer);
+        peers.on_active_session_gracefully_closed(peer);
+
+        let status = peers.static_peers_status();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].id, peer);
+        assert!(!status[0].trusted);
+        assert!(!status[0].connected);
+        assert_eq!(status[0].sessions_established, 1);
+        assert_eq!(status[0].sessions_closed, 1);
+        assert_eq!(status[0].connection_failures, 2);
+        assert_eq!(status[0].consecutive_failures, 0);
+        assert!(status[0].last_established_secs.is_some());
+        assert!(status[0].last_closed_secs.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_static_peer_redialed_without_outbound_capacity() {
+        let static_peer = PeerId::random();
+        let basic_peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test().with_max_outbound(0));
+        peers.add_peer(basic_peer, PeerAddr::from_tcp(socket_addr), None);
+        peers.add_peer_kind(static_peer, PeerKind::Static, PeerAddr::from_tcp(socket_addr), None);
+
+        for _ in 0..2 {
+            match event!(peers) {
+                PeerAction::PeerAdded(_) => {}
+                _ => unreachable!(),
+            }
+        }
+
+        // only the static peer is dialed by the health check
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, static_peer);
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(peers.peers.get(&static_peer).unwrap().state, PeerConnectionState::PendingOut);
+        assert_eq!(peers.peers.get(&basic_peer).unwrap().state, PeerConnectionState::Idle);
+    }
 }
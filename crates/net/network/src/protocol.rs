@@ -7,7 +7,7 @@ use futures::Stream;
 use reth_eth_wire::{
     capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
 };
-use reth_network_api::{Direction, PeerId};
+use reth_network_api::{test_utils::PeersHandle, Direction, PeerId, ReputationChangeKind};
 use std::{
     fmt,
     net::SocketAddr,
@@ -68,6 +68,72 @@ pub trait ConnectionHandler: Send + Sync + 'static {
         peer_id: PeerId,
         conn: ProtocolConnection,
     ) -> Self::Connection;
+
+    /// Invoked when the `RLPx` connection was established, with a [`ProtocolPeerHandle`] that can
+    /// be used to penalize or disconnect the peer if it misbehaves on this protocol.
+    ///
+    /// By default this calls [`ConnectionHandler::into_connection`].
+    fn into_connection_with_peer(
+        self,
+        direction: Direction,
+        peer: ProtocolPeerHandle,
+        conn: ProtocolConnection,
+    ) -> Self::Connection
+    where
+        Self: Sized,
+    {
+        self.into_connection(direction, peer.peer_id(), conn)
+    }
+
+    /// Returns the maximum number of received messages that are queued for this protocol.
+    ///
+    /// If the protocol doesn't consume messages fast enough, the session stops reading from the
+    /// connection until the queue has capacity again, which also delays messages of the other
+    /// protocols of the connection.
+    ///
+    /// By default the queue is unbounded.
+    fn inbound_capacity(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// A handle to the remote peer of a sub-protocol connection.
+///
+/// This allows sub-protocols to affect the reputation of the peer in the peer set of the network.
+#[derive(Debug, Clone)]
+pub struct ProtocolPeerHandle {
+    peer_id: PeerId,
+    peers: Option<PeersHandle>,
+}
+
+impl ProtocolPeerHandle {
+    /// Creates a new handle for the given peer.
+    ///
+    /// If no [`PeersHandle`] is provided, reputation changes and removals are ignored.
+    pub const fn new(peer_id: PeerId, peers: Option<PeersHandle>) -> Self {
+        Self { peer_id, peers }
+    }
+
+    /// Returns the id of the remote peer.
+    pub const fn peer_id(&self) -> PeerId {
+        self.peer_id
+    }
+
+    /// Applies a reputation change to the peer.
+    ///
+    /// Peers whose reputation falls below the ban threshold are disconnected and banned.
+    pub fn reputation_change(&self, kind: ReputationChangeKind) {
+        if let Some(peers) = &self.peers {
+            peers.reputation_change(self.peer_id, kind);
+        }
+    }
+
+    /// Removes the peer from the peer set, which disconnects its session.
+    pub fn remove_peer(&self) {
+        if let Some(peers) = &self.peers {
+            peers.remove_peer(self.peer_id);
+        }
+    }
 }
 
 /// What to do when a protocol is not supported by the remote.
@@ -110,6 +176,8 @@ impl IntoRlpxSubProtocol for RlpxSubProtocol {
 pub struct RlpxSubProtocols {
     /// All extra protocols
     protocols: Vec<RlpxSubProtocol>,
+    /// Handle to the peer set, passed to the protocol connections.
+    peers: Option<PeersHandle>,
 }
 
 impl RlpxSubProtocols {
@@ -118,15 +186,22 @@ impl RlpxSubProtocols {
         self.protocols.push(protocol.into_rlpx_sub_protocol());
     }
 
+    /// Sets the handle to the peer set that protocol connections use to score peers.
+    pub(crate) fn set_peers_handle(&mut self, peers: PeersHandle) {
+        self.peers = Some(peers);
+    }
+
     /// Returns all additional protocol handlers that should be announced to the remote during the
     /// Rlpx handshake on an incoming connection.
     pub(crate) fn on_incoming(&self, socket_addr: SocketAddr) -> RlpxSubProtocolHandlers {
-        RlpxSubProtocolHandlers(
-            self.protocols
+        RlpxSubProtocolHandlers {
+            handlers: self
+                .protocols
                 .iter()
                 .filter_map(|protocol| protocol.0.on_incoming(socket_addr))
                 .collect(),
-        )
+            peers: self.peers.clone(),
+        }
     }
 
     /// Returns all additional protocol handlers that should be announced to the remote during the
@@ -136,23 +211,33 @@ impl RlpxSubProtocols {
         socket_addr: SocketAddr,
         peer_id: PeerId,
     ) -> RlpxSubProtocolHandlers {
-        RlpxSubProtocolHandlers(
-            self.protocols
+        RlpxSubProtocolHandlers {
+            handlers: self
+                .protocols
                 .iter()
                 .filter_map(|protocol| protocol.0.on_outgoing(socket_addr, peer_id))
                 .collect(),
-        )
+            peers: self.peers.clone(),
+        }
     }
 }
 
 /// A set of additional RLPx-based sub-protocol connection handlers.
 #[derive(Default)]
-pub(crate) struct RlpxSubProtocolHandlers(pub(crate) Vec<Box<dyn DynConnectionHandler>>);
+pub(crate) struct RlpxSubProtocolHandlers {
+    handlers: Vec<Box<dyn DynConnectionHandler>>,
+    peers: Option<PeersHandle>,
+}
 
 impl RlpxSubProtocolHandlers {
+    /// Returns a [`ProtocolPeerHandle`] for the given remote peer.
+    pub(crate) fn peer_handle(&self, peer_id: PeerId) -> ProtocolPeerHandle {
+        ProtocolPeerHandle::new(peer_id, self.peers.clone())
+    }
+
     /// Returns all handlers.
     pub(crate) fn into_iter(self) -> impl Iterator<Item = Box<dyn DynConnectionHandler>> {
-        self.0.into_iter()
+        self.handlers.into_iter()
     }
 }
 
@@ -160,13 +245,13 @@ impl Deref for RlpxSubProtocolHandlers {
     type Target = Vec<Box<dyn DynConnectionHandler>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.handlers
     }
 }
 
 impl DerefMut for RlpxSubProtocolHandlers {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.handlers
     }
 }
 
@@ -210,9 +295,11 @@ pub(crate) trait DynConnectionHandler: Send + Sync + 'static {
     fn into_connection(
         self: Box<Self>,
         direction: Direction,
-        peer_id: PeerId,
+        peer: ProtocolPeerHandle,
         conn: ProtocolConnection,
     ) -> Pin<Box<dyn Stream<Item = BytesMut> + Send + 'static>>;
+
+    fn inbound_capacity(&self) -> Option<usize>;
 }
 
 impl<T: ConnectionHandler> DynConnectionHandler for T {
@@ -232,9 +319,13 @@ impl<T: ConnectionHandler> DynConnectionHandler for T {
     fn into_connection(
         self: Box<Self>,
         direction: Direction,
-        peer_id: PeerId,
+        peer: ProtocolPeerHandle,
         conn: ProtocolConnection,
     ) -> Pin<Box<dyn Stream<Item = BytesMut> + Send + 'static>> {
-        Box::pin(T::into_connection(*self, direction, peer_id, conn))
+        Box::pin(T::into_connection_with_peer(*self, direction, peer, conn))
+    }
+
+    fn inbound_capacity(&self) -> Option<usize> {
+        T::inbound_capacity(self)
     }
 }
@@ -14,12 +14,14 @@ use std::{
 use crate::{
     message::{NewBlockMessage, PeerMessage, PeerResponse, PeerResponseResult},
     session::{
+        bandwidth::{BandwidthClass, OutboundBandwidthLimiter},
         conn::EthRlpxConnection,
         handle::{ActiveSessionMessage, SessionCommand},
         BlockRangeInfo, EthVersion, SessionId,
     },
 };
 use alloy_primitives::Sealable;
+use alloy_rlp::Encodable;
 use futures::{stream::Fuse, SinkExt, StreamExt};
 use metrics::Gauge;
 use reth_eth_wire::{
@@ -27,7 +29,7 @@ use reth_eth_wire::{
     message::{EthBroadcastMessage, MessageError, RequestPair},
     Capabilities, DisconnectP2P, DisconnectReason, EthMessage, NetworkPrimitives, NewBlockPayload,
 };
-use reth_eth_wire_types::RawCapabilityMessage;
+use reth_eth_wire_types::{EthMessageID, RawCapabilityMessage};
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::PeerRequest;
 use reth_network_p2p::error::RequestError;
@@ -37,7 +39,7 @@ use reth_primitives_traits::Block;
 use rustc_hash::FxHashMap;
 use tokio::{
     sync::{mpsc::error::TrySendError, oneshot},
-    time::Interval,
+    time::{Interval, Sleep},
 };
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::PollSender;
@@ -73,6 +75,12 @@ const TIMEOUT_SCALING: u32 = 3;
 /// before reading any more messages from the remote peer, throttling the peer.
 const MAX_QUEUED_OUTGOING_RESPONSES: usize = 4;
 
+/// The maximum number of queued transaction gossip messages of a session.
+///
+/// Transaction broadcasts and announcements that are throttled by the outbound bandwidth limit
+/// are dropped once this many are queued. Responses to requests are never dropped.
+const MAX_QUEUED_TRANSACTION_GOSSIP: usize = 128;
+
 /// The type that advances an established session by listening for incoming messages (from local
 /// node or read from connection) and emitting events back to the
 /// [`SessionManager`](super::SessionManager).
@@ -151,7 +159,7 @@ impl<N: NetworkPrimitives> ActiveSession<N> {
 
     /// Returns how many responses we've currently queued up.
     fn queued_response_count(&self) -> usize {
-        self.queued_outgoing.messages.iter().filter(|m| m.is_response()).count()
+        self.queued_outgoing.iter().filter(|m| m.is_response()).count()
     }
 
     /// Handle a message read from the connection.
@@ -619,7 +627,7 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
 
             // Send messages by advancing the sink and queuing in buffered messages
             while this.conn.poll_ready_unpin(cx).is_ready() {
-                if let Some(msg) = this.queued_outgoing.pop_front() {
+                if let Poll::Ready(Some(msg)) = this.queued_outgoing.poll_pop(cx) {
                     progress = true;
                     let res = match msg {
                         OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
@@ -632,7 +640,8 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                         return this.close_on_error(err, cx)
                     }
                 } else {
-                    // no more messages to send over the wire
+                    // no more messages to send over the wire, or the outbound bandwidth limit is
+                    // exhausted
                     break
                 }
             }
@@ -674,7 +683,7 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                 }
 
                 // we also need to check if we have multiple responses queued up
-                if this.queued_outgoing.messages.len() > MAX_QUEUED_OUTGOING_RESPONSES &&
+                if this.queued_outgoing.len() > MAX_QUEUED_OUTGOING_RESPONSES &&
                     this.queued_response_count() > MAX_QUEUED_OUTGOING_RESPONSES
                 {
                     // if we've queued up more responses than allowed, we don't poll for new
@@ -848,6 +857,40 @@ impl<N: NetworkPrimitives> OutgoingMessage<N> {
             _ => false,
         }
     }
+
+    /// Returns the class of the message that is accounted against the outbound bandwidth limit.
+    ///
+    /// Requests and control messages count towards block propagation, so that they are not
+    /// delayed by bulk traffic.
+    const fn bandwidth_class(&self) -> BandwidthClass {
+        match self {
+            Self::Eth(msg) => match msg.message_id() {
+                EthMessageID::Transactions |
+                EthMessageID::NewPooledTransactionHashes |
+                EthMessageID::PooledTransactions => BandwidthClass::TransactionGossip,
+                EthMessageID::BlockHeaders |
+                EthMessageID::BlockBodies |
+                EthMessageID::NodeData |
+                EthMessageID::Receipts => BandwidthClass::HistoricalServing,
+                _ => BandwidthClass::BlockPropagation,
+            },
+            Self::Broadcast(EthBroadcastMessage::Transactions(_)) => {
+                BandwidthClass::TransactionGossip
+            }
+            Self::Broadcast(EthBroadcastMessage::NewBlock(_)) | Self::Raw(_) => {
+                BandwidthClass::BlockPropagation
+            }
+        }
+    }
+
+    /// Returns the length of the encoded message.
+    fn length(&self) -> usize {
+        match self {
+            Self::Eth(msg) => msg.length(),
+            Self::Broadcast(msg) => msg.length(),
+            Self::Raw(msg) => msg.payload.len(),
+        }
+    }
 }
 
 impl<N: NetworkPrimitives> From<EthMessage<N>> for OutgoingMessage<N> {
@@ -874,27 +917,104 @@ fn calculate_new_timeout(current_timeout: Duration, estimated_rtt: Duration) ->
 }
 
 /// A helper struct that wraps the queue of outgoing messages and a metric to track their count
+///
+/// Messages are released within the global outbound bandwidth limit.
 pub(crate) struct QueuedOutgoingMessages<N: NetworkPrimitives> {
-    messages: VecDeque<OutgoingMessage<N>>,
+    /// Queued messages per [`BandwidthClass`], with the sequence number of each message.
+    messages: [VecDeque<(u64, OutgoingMessage<N>)>; 3],
+    /// The sequence number of the next queued message, used to send messages of different classes
+    /// in order while they're within the limit.
+    next_seq: u64,
     count: Gauge,
+    /// The outbound bandwidth limit shared by all sessions.
+    bandwidth: Arc<OutboundBandwidthLimiter>,
+    /// Delay until sending is retried after the bandwidth limit was exhausted.
+    throttle: Option<Pin<Box<Sleep>>>,
 }
 
 impl<N: NetworkPrimitives> QueuedOutgoingMessages<N> {
-    pub(crate) const fn new(metric: Gauge) -> Self {
-        Self { messages: VecDeque::new(), count: metric }
+    pub(crate) fn new(metric: Gauge, bandwidth: Arc<OutboundBandwidthLimiter>) -> Self {
+        Self { messages: Default::default(), next_seq: 0, count: metric, bandwidth, throttle: None }
     }
 
+    /// Queues the message.
+    ///
+    /// Transaction broadcasts and announcements are dropped if
+    /// [`MAX_QUEUED_TRANSACTION_GOSSIP`] transaction gossip messages are already queued.
     pub(crate) fn push_back(&mut self, message: OutgoingMessage<N>) {
-        self.messages.push_back(message);
+        let class = message.bandwidth_class();
+        let queue = &mut self.messages[class.index()];
+        if class == BandwidthClass::TransactionGossip &&
+            !message.is_response() &&
+            queue.len() >= MAX_QUEUED_TRANSACTION_GOSSIP
+        {
+            trace!(target: "net::session", "dropping transaction gossip, too many queued messages");
+            return
+        }
+
+        queue.push_back((self.next_seq, message));
+        self.next_seq += 1;
         self.count.increment(1);
     }
 
-    pub(crate) fn pop_front(&mut self) -> Option<OutgoingMessage<N>> {
-        self.messages.pop_front().inspect(|_| self.count.decrement(1))
+    /// Returns the number of queued messages.
+    pub(crate) fn len(&self) -> usize {
+        self.messages.iter().map(VecDeque::len).sum()
+    }
+
+    /// Returns an iterator over all queued messages.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &OutgoingMessage<N>> {
+        self.messages.iter().flatten().map(|(_, msg)| msg)
+    }
+
+    /// Pops the oldest message whose [`BandwidthClass`] is within the outbound bandwidth limit.
+    ///
+    /// Messages of the same class are sent in order. Returns `Poll::Pending` if the limit is
+    /// exhausted for all queued messages, in which case the waker is woken once sending should be
+    /// retried.
+    pub(crate) fn poll_pop(&mut self, cx: &mut Context<'_>) -> Poll<Option<OutgoingMessage<N>>> {
+        if let Some(throttle) = self.throttle.as_mut() {
+            ready!(throttle.as_mut().poll(cx));
+            self.throttle = None;
+        }
+
+        loop {
+            // the classes with queued messages, ordered by their oldest message
+            let mut classes = BandwidthClass::ALL
+                .into_iter()
+                .filter_map(|class| {
+                    self.messages[class.index()].front().map(|(seq, _)| (*seq, class))
+                })
+                .collect::<smallvec::SmallVec<[_; 3]>>();
+            classes.sort_unstable_by_key(|(seq, _)| *seq);
+
+            let mut delay = None::<Duration>;
+            for (_, class) in classes {
+                let queue = &mut self.messages[class.index()];
+                let length = queue.front().map(|(_, msg)| msg.length()).unwrap_or_default();
+                match self.bandwidth.try_acquire(class, length) {
+                    Ok(()) => {
+                        let (_, msg) = queue.pop_front().expect("queue is not empty");
+                        self.count.decrement(1);
+                        return Poll::Ready(Some(msg))
+                    }
+                    Err(wait) => delay = Some(delay.map_or(wait, |delay| delay.min(wait))),
+                }
+            }
+
+            let Some(delay) = delay else { return Poll::Ready(None) };
+            let mut throttle = Box::pin(tokio::time::sleep(delay));
+            if throttle.as_mut().poll(cx).is_pending() {
+                self.throttle = Some(throttle);
+                return Poll::Pending
+            }
+        }
     }
 
     pub(crate) fn shrink_to_fit(&mut self) {
-        self.messages.shrink_to_fit();
+        for queue in &mut self.messages {
+            queue.shrink_to_fit();
+        }
     }
 }
 
@@ -907,8 +1027,8 @@ mod tests {
     use reth_ecies::stream::ECIESStream;
     use reth_eth_wire::{
         handshake::EthHandshake, EthNetworkPrimitives, EthStream, GetBlockBodies,
-        HelloMessageWithProtocols, P2PStream, StatusBuilder, UnauthedEthStream, UnauthedP2PStream,
-        UnifiedStatus,
+        HelloMessageWithProtocols, P2PStream, PooledTransactions, StatusBuilder, UnauthedEthStream,
+        UnauthedP2PStream, UnifiedStatus,
     };
     use reth_ethereum_forks::EthereumHardfork;
     use reth_network_peers::pk2id;
@@ -1029,7 +1149,10 @@ mod tests {
                         internal_request_rx: ReceiverStream::new(messages_rx).fuse(),
                         inflight_requests: Default::default(),
                         conn,
-                        queued_outgoing: QueuedOutgoingMessages::new(Gauge::noop()),
+                        queued_outgoing: QueuedOutgoingMessages::new(
+                            Gauge::noop(),
+                            Default::default(),
+                        ),
                         received_requests_from_remote: Default::default(),
                         internal_request_timeout_interval: tokio::time::interval(
                             INITIAL_REQUEST_TIMEOUT,
@@ -1306,4 +1429,49 @@ mod tests {
         assert!(calculate_new_timeout(timeout, rtt * 2) > timeout);
         assert!(calculate_new_timeout(timeout, rtt * 2) < timeout * 2);
     }
+
+    #[test]
+    fn drops_excess_transaction_gossip() {
+        let mut queue =
+            QueuedOutgoingMessages::<EthNetworkPrimitives>::new(Gauge::noop(), Default::default());
+        for _ in 0..=MAX_QUEUED_TRANSACTION_GOSSIP {
+            queue.push_back(EthMessage::NewPooledTransactionHashes66(Default::default()).into());
+        }
+        assert_eq!(queue.len(), MAX_QUEUED_TRANSACTION_GOSSIP);
+
+        // responses are never dropped
+        queue.push_back(
+            EthMessage::PooledTransactions(RequestPair {
+                request_id: 0,
+                message: PooledTransactions(Vec::new()),
+            })
+            .into(),
+        );
+        assert_eq!(queue.len(), MAX_QUEUED_TRANSACTION_GOSSIP + 1);
+    }
+
+    #[test]
+    fn pops_messages_in_order() {
+        let mut queue =
+            QueuedOutgoingMessages::<EthNetworkPrimitives>::new(Gauge::noop(), Default::default());
+        queue.push_back(EthMessage::NewPooledTransactionHashes66(Default::default()).into());
+        queue.push_back(
+            EthMessage::GetBlockBodies(RequestPair {
+                request_id: 0,
+                message: GetBlockBodies(Vec::new()),
+            })
+            .into(),
+        );
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        let Poll::Ready(Some(OutgoingMessage::Eth(first))) = queue.poll_pop(&mut cx) else {
+            panic!("expected a message")
+        };
+        assert_eq!(first.message_id(), EthMessageID::NewPooledTransactionHashes);
+        let Poll::Ready(Some(OutgoingMessage::Eth(second))) = queue.poll_pop(&mut cx) else {
+            panic!("expected a message")
+        };
+        assert_eq!(second.message_id(), EthMessageID::GetBlockBodies);
+        assert!(matches!(queue.poll_pop(&mut cx), Poll::Ready(None)));
+    }
 }
@@ -0,0 +1,335 @@
+//! Global limit of the outbound bandwidth of all peer sessions.
+
+use parking_lot::Mutex;
+use reth_network_api::{OutboundBandwidthClassStatus, OutboundBandwidthStatus};
+use reth_network_types::{OutboundBandwidthConfig, OutboundBandwidthWeights};
+use std::{
+    num::NonZeroU64,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// The minimum delay before a throttled session retries to send.
+const MIN_THROTTLE_DELAY: Duration = Duration::from_millis(5);
+
+/// The maximum delay before a throttled session retries to send.
+const MAX_THROTTLE_DELAY: Duration = Duration::from_secs(1);
+
+/// Classes of outbound traffic that share the outbound bandwidth limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BandwidthClass {
+    /// New block announcements, as well as requests and control messages.
+    BlockPropagation,
+    /// Transaction broadcasts, announcements and pooled transaction responses.
+    TransactionGossip,
+    /// Responses to header, body, receipt and node data requests.
+    HistoricalServing,
+}
+
+impl BandwidthClass {
+    /// All classes, ordered by index.
+    pub(crate) const ALL: [Self; 3] =
+        [Self::BlockPropagation, Self::TransactionGossip, Self::HistoricalServing];
+
+    /// Returns the index of the class.
+    pub(crate) const fn index(self) -> usize {
+        match self {
+            Self::BlockPropagation => 0,
+            Self::TransactionGossip => 1,
+            Self::HistoricalServing => 2,
+        }
+    }
+
+    /// Returns the weight of the class.
+    const fn weight(self, weights: &OutboundBandwidthWeights) -> u32 {
+        match self {
+            Self::BlockPropagation => weights.block_propagation,
+            Self::TransactionGossip => weights.transaction_gossip,
+            Self::HistoricalServing => weights.historical_serving,
+        }
+    }
+}
+
+/// Enforces the global outbound bandwidth limit that is shared by all active sessions.
+///
+/// Every [`BandwidthClass`] has a token bucket that is refilled with a share of the limit that is
+/// proportional to the weight of the class, and holds at most one second worth of that share.
+/// Tokens that overflow a full bucket are moved to a spare bucket that all classes draw from, so
+/// bandwidth left unused by one class is available to the others.
+///
+/// A message can be sent as soon as its class has tokens available, large messages put the bucket
+/// into debt that is paid off by subsequent refills.
+///
+/// Without a limit, which is the default, messages are only counted and the buckets are never
+/// locked.
+#[derive(Debug)]
+pub(crate) struct OutboundBandwidthLimiter {
+    /// Whether a limit is configured.
+    limited: AtomicBool,
+    /// Total bytes sent per class.
+    bytes_sent: [AtomicU64; 3],
+    /// Number of throttled send attempts per class.
+    throttled: [AtomicU64; 3],
+    state: Mutex<LimiterState>,
+}
+
+impl OutboundBandwidthLimiter {
+    /// Creates a new limiter with the given configuration.
+    pub(crate) fn new(config: OutboundBandwidthConfig) -> Self {
+        let mut state = LimiterState {
+            max_bytes_per_sec: config.max_bytes_per_sec.map(NonZeroU64::get),
+            weights: config.weights,
+            buckets: [0.0; 3],
+            spare: 0.0,
+            last_refill: Instant::now(),
+        };
+        state.reset(Instant::now());
+        Self {
+            limited: AtomicBool::new(config.max_bytes_per_sec.is_some()),
+            bytes_sent: Default::default(),
+            throttled: Default::default(),
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Sets the maximum number of bytes per second, `None` removes the limit.
+    pub(crate) fn set_max_bytes_per_sec(&self, max_bytes_per_sec: Option<NonZeroU64>) {
+        let mut state = self.state.lock();
+        state.max_bytes_per_sec = max_bytes_per_sec.map(NonZeroU64::get);
+        state.reset(Instant::now());
+        self.limited.store(max_bytes_per_sec.is_some(), Ordering::Relaxed);
+    }
+
+    /// Sets the weights of the traffic classes.
+    pub(crate) fn set_weights(&self, weights: OutboundBandwidthWeights) {
+        let mut state = self.state.lock();
+        state.weights = weights;
+        state.reset(Instant::now());
+    }
+
+    /// Returns the configured limit and the traffic sent per class.
+    pub(crate) fn status(&self) -> OutboundBandwidthStatus {
+        let state = self.state.lock();
+        let class_status = |class: BandwidthClass| OutboundBandwidthClassStatus {
+            weight: class.weight(&state.weights),
+            bytes_sent: self.bytes_sent[class.index()].load(Ordering::Relaxed),
+            throttled: self.throttled[class.index()].load(Ordering::Relaxed),
+        };
+        OutboundBandwidthStatus {
+            max_bytes_per_sec: state.max_bytes_per_sec,
+            block_propagation: class_status(BandwidthClass::BlockPropagation),
+            transaction_gossip: class_status(BandwidthClass::TransactionGossip),
+            historical_serving: class_status(BandwidthClass::HistoricalServing),
+        }
+    }
+
+    /// Records a message of the given class with the given encoded length if it can be sent now.
+    ///
+    /// Returns the delay after which sending should be retried otherwise.
+    pub(crate) fn try_acquire(&self, class: BandwidthClass, bytes: usize) -> Result<(), Duration> {
+        if !self.limited.load(Ordering::Relaxed) {
+            self.bytes_sent[class.index()].fetch_add(bytes as u64, Ordering::Relaxed);
+            return Ok(())
+        }
+        self.try_acquire_at(class, bytes, Instant::now())
+    }
+
+    fn try_acquire_at(
+        &self,
+        class: BandwidthClass,
+        bytes: usize,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut state = self.state.lock();
+        if state.max_bytes_per_sec.is_some() {
+            state.refill(now);
+            if let Err(delay) = state.check(class) {
+                self.throttled[class.index()].fetch_add(1, Ordering::Relaxed);
+                return Err(delay)
+            }
+            state.consume(class, bytes);
+        }
+        self.bytes_sent[class.index()].fetch_add(bytes as u64, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl Default for OutboundBandwidthLimiter {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+/// The token buckets of the [`OutboundBandwidthLimiter`].
+#[derive(Debug)]
+struct LimiterState {
+    /// The configured limit, if any.
+    max_bytes_per_sec: Option<u64>,
+    /// The configured weights of the classes.
+    weights: OutboundBandwidthWeights,
+    /// Available bytes per class, negative if in debt.
+    buckets: [f64; 3],
+    /// Available bytes that overflowed the class buckets.
+    spare: f64,
+    /// The last time the buckets were refilled.
+    last_refill: Instant,
+}
+
+impl LimiterState {
+    /// Checks whether a message of the given class can be sent.
+    ///
+    /// Returns the delay after which sending should be retried otherwise.
+    fn check(&self, class: BandwidthClass) -> Result<(), Duration> {
+        let bucket = self.buckets[class.index()];
+        if bucket > 0.0 || self.spare > 0.0 {
+            return Ok(())
+        }
+
+        let class_rate = self.class_rate(class);
+        let delay = if class_rate > 0.0 {
+            Duration::from_secs_f64(-bucket / class_rate)
+        } else {
+            MAX_THROTTLE_DELAY
+        };
+        Err(delay.clamp(MIN_THROTTLE_DELAY, MAX_THROTTLE_DELAY))
+    }
+
+    /// Takes the bytes of a sent message of the given class from the buckets.
+    fn consume(&mut self, class: BandwidthClass, bytes: usize) {
+        let mut remaining = bytes as f64;
+        let bucket = &mut self.buckets[class.index()];
+        if *bucket > 0.0 {
+            let taken = bucket.min(remaining);
+            *bucket -= taken;
+            remaining -= taken;
+        }
+        if self.spare > 0.0 {
+            let taken = self.spare.min(remaining);
+            self.spare -= taken;
+            remaining -= taken;
+        }
+        // the debt is paid off by the bucket that is refilled for the class
+        if self.class_rate(class) > 0.0 {
+            self.buckets[class.index()] -= remaining;
+        } else {
+            self.spare -= remaining;
+        }
+    }
+
+    /// Returns the bytes per second reserved for the given class.
+    fn class_rate(&self, class: BandwidthClass) -> f64 {
+        let Some(rate) = self.max_bytes_per_sec else { return 0.0 };
+        let total =
+            BandwidthClass::ALL.iter().map(|c| u64::from(c.weight(&self.weights))).sum::<u64>();
+        if total == 0 {
+            return 0.0
+        }
+        rate as f64 * f64::from(class.weight(&self.weights)) / total as f64
+    }
+
+    /// Fills up the buckets of all classes.
+    fn reset(&mut self, now: Instant) {
+        for class in BandwidthClass::ALL {
+            self.buckets[class.index()] = self.class_rate(class);
+        }
+        self.spare = 0.0;
+        self.last_refill = now;
+    }
+
+    /// Refills the buckets with the bytes that became available since the last refill.
+    fn refill(&mut self, now: Instant) {
+        let Some(rate) = self.max_bytes_per_sec else { return };
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.last_refill = now;
+
+        let mut overflow = 0.0;
+        let mut reserved = 0.0;
+        for class in BandwidthClass::ALL {
+            let class_rate = self.class_rate(class);
+            reserved += class_rate;
+            let bucket = &mut self.buckets[class.index()];
+            *bucket += class_rate * elapsed;
+            if *bucket > class_rate {
+                overflow += *bucket - class_rate;
+                *bucket = class_rate;
+            }
+        }
+        // if no class has a weight, the entire limit is spare
+        overflow += (rate as f64 - reserved).max(0.0) * elapsed;
+        self.spare = (self.spare + overflow).min(rate as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(max_bytes_per_sec: u64) -> OutboundBandwidthLimiter {
+        OutboundBandwidthLimiter::new(
+            OutboundBandwidthConfig::default()
+                .with_max_bytes_per_sec(NonZeroU64::new(max_bytes_per_sec)),
+        )
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let limiter = OutboundBandwidthLimiter::default();
+        assert!(limiter.try_acquire(BandwidthClass::HistoricalServing, 10_000_000).is_ok());
+        assert!(limiter.try_acquire(BandwidthClass::HistoricalServing, 10_000_000).is_ok());
+        assert_eq!(limiter.status().historical_serving.bytes_sent, 20_000_000);
+    }
+
+    #[test]
+    fn throttles_class_in_debt() {
+        let limiter = limiter(7_000);
+        let now = Instant::now();
+        limiter.state.lock().last_refill = now;
+
+        // historical serving reserves 1/7 of the limit
+        assert!(limiter.try_acquire_at(BandwidthClass::HistoricalServing, 2_000, now).is_ok());
+        let delay = limiter.try_acquire_at(BandwidthClass::HistoricalServing, 1, now).unwrap_err();
+        assert_eq!(delay, Duration::from_secs(1));
+
+        // other classes are not affected
+        assert!(limiter.try_acquire_at(BandwidthClass::BlockPropagation, 1, now).is_ok());
+        assert!(limiter.try_acquire_at(BandwidthClass::TransactionGossip, 1, now).is_ok());
+
+        // the debt is paid off after a second
+        let later = now + Duration::from_millis(1_001);
+        assert!(limiter.try_acquire_at(BandwidthClass::HistoricalServing, 1, later).is_ok());
+
+        let status = limiter.status();
+        assert_eq!(status.historical_serving.throttled, 1);
+        assert_eq!(status.historical_serving.bytes_sent, 2_001);
+    }
+
+    #[test]
+    fn unused_bandwidth_is_shared() {
+        let limiter = limiter(7_000);
+        let now = Instant::now();
+        limiter.state.lock().last_refill = now;
+
+        assert!(limiter.try_acquire_at(BandwidthClass::HistoricalServing, 1_000, now).is_ok());
+        assert!(limiter.try_acquire_at(BandwidthClass::HistoricalServing, 1, now).is_err());
+
+        // the full buckets of the idle classes overflow into the spare bucket
+        let later = now + Duration::from_millis(100);
+        assert!(limiter.try_acquire_at(BandwidthClass::HistoricalServing, 800, later).is_ok());
+        assert!(limiter.try_acquire_at(BandwidthClass::HistoricalServing, 1, later).is_err());
+        assert!(limiter.try_acquire_at(BandwidthClass::BlockPropagation, 1, later).is_ok());
+    }
+
+    #[test]
+    fn removing_the_limit_releases_throttled_classes() {
+        let limiter = limiter(7_000);
+        let now = Instant::now();
+        limiter.state.lock().last_refill = now;
+
+        assert!(limiter.try_acquire_at(BandwidthClass::HistoricalServing, 2_000, now).is_ok());
+        assert!(limiter.try_acquire_at(BandwidthClass::HistoricalServing, 1, now).is_err());
+
+        limiter.set_max_bytes_per_sec(None);
+        assert!(limiter.try_acquire(BandwidthClass::HistoricalServing, 1).is_ok());
+        assert_eq!(limiter.status().max_bytes_per_sec, None);
+    }
+}
@@ -1,6 +1,7 @@
 //! Support for handling peer sessions.
 
 mod active;
+mod bandwidth;
 mod conn;
 mod counter;
 mod handle;
@@ -14,6 +15,7 @@ use crate::{
     session::active::ActiveSession,
 };
 use active::QueuedOutgoingMessages;
+pub(crate) use bandwidth::OutboundBandwidthLimiter;
 use counter::SessionCounter;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
@@ -120,6 +122,8 @@ pub struct SessionManager<N: NetworkPrimitives> {
     /// Shared local range information that gets propagated to active sessions.
     /// This represents the range of blocks that this node can serve to other peers.
     local_range_info: BlockRangeInfo,
+    /// The global outbound bandwidth limit that is shared by all active sessions.
+    outbound_bandwidth: Arc<OutboundBandwidthLimiter>,
 }
 
 // === impl SessionManager ===
@@ -171,6 +175,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             metrics: Default::default(),
             handshake,
             local_range_info,
+            outbound_bandwidth: Arc::new(OutboundBandwidthLimiter::new(config.outbound_bandwidth)),
         }
     }
 
@@ -207,6 +212,11 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         &self.active_sessions
     }
 
+    /// Returns the global outbound bandwidth limit of all active sessions.
+    pub(crate) const fn outbound_bandwidth(&self) -> &Arc<OutboundBandwidthLimiter> {
+        &self.outbound_bandwidth
+    }
+
     /// Returns the session hello message.
     pub fn hello_message(&self) -> HelloMessageWithProtocols {
         self.hello_message.clone()
@@ -560,6 +570,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     conn,
                     queued_outgoing: QueuedOutgoingMessages::new(
                         self.metrics.queued_outgoing_messages.clone(),
+                        Arc::clone(&self.outbound_bandwidth),
                     ),
                     received_requests_from_remote: Default::default(),
                     internal_request_timeout_interval: tokio::time::interval(
@@ -1139,15 +1150,20 @@ async fn authenticate_stream<N: NetworkPrimitives>(
         let mut multiplex_stream = RlpxProtocolMultiplexer::new(p2p_stream);
 
         // install additional handlers
+        let peer = extra_handlers.peer_handle(their_hello.id);
         for handler in extra_handlers.into_iter() {
             let cap = handler.protocol().cap;
-            let remote_peer_id = their_hello.id;
+            let capacity = handler.inbound_capacity();
+            let peer = peer.clone();
+            let into_connection = move |conn| handler.into_connection(direction, peer, conn);
 
-            multiplex_stream
-                .install_protocol(&cap, move |conn| {
-                    handler.into_connection(direction, remote_peer_id, conn)
-                })
-                .ok();
+            match capacity {
+                Some(capacity) => {
+                    multiplex_stream.install_protocol_with_capacity(&cap, capacity, into_connection)
+                }
+                None => multiplex_stream.install_protocol(&cap, into_connection),
+            }
+            .ok();
         }
 
         let (multiplex_stream, their_status) =
@@ -197,6 +197,9 @@ impl<N: NetworkPrimitives> Swarm<N> {
                         InboundConnectionError::IpBanned => {
                             trace!(target: "net", ?remote_addr, "The incoming ip address is in the ban list");
                         }
+                        InboundConnectionError::RateLimited => {
+                            trace!(target: "net", ?remote_addr, "The incoming ip address exceeded the rate limit");
+                        }
                         InboundConnectionError::ExceedsCapacity => {
                             trace!(target: "net", ?remote_addr, "No capacity for incoming connection");
                             self.sessions.try_disconnect_incoming_connection(
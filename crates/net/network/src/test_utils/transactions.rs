@@ -64,6 +64,8 @@ pub fn buffer_hash_to_tx_fetcher(
             retries,
             LruCache::new(DEFAULT_MAX_COUNT_FALLBACK_PEERS as u32),
             tx_encoded_length,
+            None,
+            None,
         )
     }) {
         Some(metadata) => {
@@ -0,0 +1,321 @@
+//! Adaptive batching of transaction hash announcements.
+//!
+//! During mempool storms, announcing the hashes of every batch of new pending transactions right
+//! away results in many small [`NewPooledTransactionHashes`] messages per peer. Instead, the
+//! [`AnnouncementBatcher`] buffers hashes per peer and announces them in one message, once the
+//! batch is full or its delay elapsed. The delay is a fraction of the peer's round trip time, that
+//! grows with the rate of new pending transactions, and is bounded by
+//! [`AnnouncementBatchConfig::max_delay`]. This way, announcements are only delayed when there is
+//! something to aggregate, and never by more than it takes the peer to request the transactions.
+
+use super::{
+    config::AnnouncementBatchConfig,
+    constants::tx_manager::{DEFAULT_ANNOUNCEMENT_PEER_RTT, MIN_ANNOUNCEMENT_DELAY},
+};
+use reth_eth_wire::NewPooledTransactionHashes;
+use reth_network_peers::PeerId;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+
+/// Time constant of the moving average of the rate of new pending transactions.
+const INGRESS_RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Batches transaction hash announcements per peer.
+#[derive(Debug)]
+pub(crate) struct AnnouncementBatcher {
+    config: AnnouncementBatchConfig,
+    /// Rate of new pending transactions.
+    ingress: IngressRate,
+    /// Announcements waiting to be sent, by peer.
+    pending: HashMap<PeerId, PendingAnnouncement>,
+    /// Fires at the earliest deadline of the pending announcements.
+    timer: Option<Pin<Box<Sleep>>>,
+}
+
+impl AnnouncementBatcher {
+    /// Creates a new batcher with the given configuration.
+    pub(crate) fn new(config: AnnouncementBatchConfig) -> Self {
+        Self { config, ingress: Default::default(), pending: Default::default(), timer: None }
+    }
+
+    /// Records new pending transactions, that are about to be propagated.
+    pub(crate) fn on_new_pending_transactions(&mut self, count: usize) {
+        self.ingress.record(count, Instant::now());
+    }
+
+    /// Returns the delay of an announcement to a peer with the given round trip time, or `None`
+    /// if the announcement should be sent right away.
+    pub(crate) fn delay(&self, rtt: Option<Duration>) -> Option<Duration> {
+        let rtt = rtt.unwrap_or(DEFAULT_ANNOUNCEMENT_PEER_RTT);
+        let saturation =
+            self.ingress.rate(Instant::now()) / self.config.saturation_rate.max(1) as f64;
+        let delay = (rtt / 2).mul_f64(saturation.min(1.0)).min(self.config.max_delay);
+
+        (delay >= MIN_ANNOUNCEMENT_DELAY).then_some(delay)
+    }
+
+    /// Queues an announcement to the peer, to be sent after the given delay at the latest.
+    ///
+    /// Returns the announcements to the peer that are ready to be sent right away, because its
+    /// batch is full.
+    pub(crate) fn queue(
+        &mut self,
+        peer_id: PeerId,
+        hashes: NewPooledTransactionHashes,
+        delay: Duration,
+    ) -> Vec<ReadyAnnouncement> {
+        let now = Instant::now();
+        let mut ready = Vec::new();
+
+        // flush the batch first, if the hashes don't fit into it
+        if self
+            .pending
+            .get(&peer_id)
+            .is_some_and(|batch| batch.hashes.len() + hashes.len() > self.config.max_batch_size)
+        {
+            ready.extend(self.remove_ready(peer_id, now));
+        }
+
+        let batch = match self.pending.entry(peer_id) {
+            Entry::Occupied(entry) => {
+                let batch = entry.into_mut();
+                batch.append(hashes);
+                batch.deadline = batch.deadline.min(now + delay);
+                batch
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(PendingAnnouncement { hashes, queued_at: now, deadline: now + delay })
+            }
+        };
+
+        if batch.hashes.len() >= self.config.max_batch_size {
+            ready.extend(self.remove_ready(peer_id, now));
+        }
+
+        ready
+    }
+
+    /// Drops the pending announcement to the peer.
+    pub(crate) fn remove_peer(&mut self, peer_id: &PeerId) {
+        self.pending.remove(peer_id);
+    }
+
+    /// Returns the number of peers with a pending announcement.
+    #[cfg(test)]
+    pub(crate) fn num_pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns the announcements whose delay elapsed, and arms the timer for the next deadline.
+    pub(crate) fn poll_expired(&mut self, cx: &mut Context<'_>) -> Poll<Vec<ReadyAnnouncement>> {
+        let mut ready = Vec::new();
+
+        loop {
+            let now = Instant::now();
+            let expired = self
+                .pending
+                .iter()
+                .filter(|(_, batch)| batch.deadline <= now)
+                .map(|(peer_id, _)| *peer_id)
+                .collect::<Vec<_>>();
+            for peer_id in expired {
+                ready.extend(self.remove_ready(peer_id, now));
+            }
+
+            let Some(deadline) = self.pending.values().map(|batch| batch.deadline).min() else {
+                self.timer = None;
+                break
+            };
+
+            let timer =
+                self.timer.get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+            if timer.deadline() != deadline {
+                timer.as_mut().reset(deadline);
+            }
+            if timer.as_mut().poll(cx).is_pending() {
+                break
+            }
+        }
+
+        if ready.is_empty() {
+            Poll::Pending
+        } else {
+            Poll::Ready(ready)
+        }
+    }
+
+    fn remove_ready(&mut self, peer_id: PeerId, now: Instant) -> Option<ReadyAnnouncement> {
+        let batch = self.pending.remove(&peer_id)?;
+        Some(ReadyAnnouncement {
+            peer_id,
+            hashes: batch.hashes,
+            latency: now.saturating_duration_since(batch.queued_at),
+        })
+    }
+}
+
+/// An announcement that is ready to be sent to a peer.
+#[derive(Debug)]
+pub(crate) struct ReadyAnnouncement {
+    /// The peer to send the announcement to.
+    pub(crate) peer_id: PeerId,
+    /// The announced hashes.
+    pub(crate) hashes: NewPooledTransactionHashes,
+    /// How long the first hash of the announcement was delayed.
+    pub(crate) latency: Duration,
+}
+
+/// Announcement to a peer that is waiting to be sent.
+#[derive(Debug)]
+struct PendingAnnouncement {
+    hashes: NewPooledTransactionHashes,
+    /// When the first hash was queued.
+    queued_at: Instant,
+    /// When the announcement is sent at the latest.
+    deadline: Instant,
+}
+
+impl PendingAnnouncement {
+    /// Appends the hashes to the announcement.
+    ///
+    /// Note: the announcements to a peer are always built for the negotiated version of the
+    /// session, so both messages have the same version.
+    fn append(&mut self, hashes: NewPooledTransactionHashes) {
+        debug_assert_eq!(self.hashes.version(), hashes.version());
+
+        match (&mut self.hashes, hashes) {
+            (NewPooledTransactionHashes::Eth68(batch), NewPooledTransactionHashes::Eth68(msg)) => {
+                batch.hashes.extend(msg.hashes);
+                batch.types.extend(msg.types);
+                batch.sizes.extend(msg.sizes);
+            }
+            (batch, msg) => batch.hashes_mut().extend(msg.into_iter_hashes()),
+        }
+    }
+}
+
+/// Exponentially decaying rate of events per second.
+#[derive(Debug, Default)]
+struct IngressRate {
+    /// The rate at the last update.
+    rate: f64,
+    /// When the rate was last updated.
+    updated_at: Option<Instant>,
+}
+
+impl IngressRate {
+    /// Records the given number of events.
+    fn record(&mut self, count: usize, now: Instant) {
+        self.rate = self.rate(now) + count as f64 / INGRESS_RATE_WINDOW.as_secs_f64();
+        self.updated_at = Some(now);
+    }
+
+    /// Returns the rate at the given time.
+    fn rate(&self, now: Instant) -> f64 {
+        let Some(updated_at) = self.updated_at else { return 0.0 };
+        let elapsed = now.saturating_duration_since(updated_at);
+        self.rate * (-elapsed.as_secs_f64() / INGRESS_RATE_WINDOW.as_secs_f64()).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use futures::future::poll_fn;
+    use reth_eth_wire::{NewPooledTransactionHashes66, NewPooledTransactionHashes68};
+
+    fn hashes68(count: usize) -> NewPooledTransactionHashes {
+        let mut msg = NewPooledTransactionHashes68::default();
+        for i in 0..count {
+            msg.push_announcement(B256::with_last_byte(i as u8), 2, 100);
+        }
+        msg.into()
+    }
+
+    #[tokio::test]
+    async fn adapts_delay_to_ingress_rate() {
+        let config = AnnouncementBatchConfig::default()
+            .with_max_delay(Duration::from_millis(100))
+            .with_saturation_rate(1_000);
+        let mut batcher = AnnouncementBatcher::new(config);
+        let rtt = Some(Duration::from_millis(80));
+
+        // quiet mempool, announce right away
+        assert_eq!(batcher.delay(rtt), None);
+        batcher.on_new_pending_transactions(1);
+        assert_eq!(batcher.delay(rtt), None);
+
+        // mempool storm, delay by up to half the rtt
+        batcher.on_new_pending_transactions(10_000);
+        let delay = batcher.delay(rtt).unwrap();
+        assert!(delay <= Duration::from_millis(40));
+        assert!(delay > Duration::from_millis(39));
+
+        // bounded by the max delay
+        let delay = batcher.delay(Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(delay, Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn flushes_full_batches() {
+        let config = AnnouncementBatchConfig::default().with_max_batch_size(10);
+        let mut batcher = AnnouncementBatcher::new(config);
+        let peer_id = PeerId::random();
+        let delay = Duration::from_secs(10);
+
+        assert!(batcher.queue(peer_id, hashes68(4), delay).is_empty());
+        assert!(batcher.queue(peer_id, hashes68(4), delay).is_empty());
+
+        // doesn't fit into the batch
+        let ready = batcher.queue(peer_id, hashes68(4), delay);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].peer_id, peer_id);
+        assert_eq!(ready[0].hashes.len(), 8);
+        assert!(ready[0].hashes.as_eth68().unwrap().metadata_iter().all(|(_, m)| m == (2, 100)));
+
+        // fills the batch
+        let ready = batcher.queue(peer_id, hashes68(6), delay);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].hashes.len(), 10);
+        assert_eq!(batcher.num_pending(), 0);
+
+        // eth66 announcements are merged as well
+        let hashes: NewPooledTransactionHashes =
+            NewPooledTransactionHashes66::from(vec![B256::ZERO; 3]).into();
+        assert!(batcher.queue(peer_id, hashes.clone(), delay).is_empty());
+        assert!(batcher.queue(peer_id, hashes, delay).is_empty());
+        batcher.remove_peer(&peer_id);
+        assert_eq!(batcher.num_pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn sends_batches_after_delay() {
+        let mut batcher = AnnouncementBatcher::new(AnnouncementBatchConfig::default());
+        let (first, second) = (PeerId::random(), PeerId::random());
+
+        assert!(batcher.queue(first, hashes68(1), Duration::from_millis(10)).is_empty());
+        assert!(batcher.queue(second, hashes68(1), Duration::from_secs(10)).is_empty());
+        // a later, shorter delay advances the deadline of the batch
+        assert!(batcher.queue(second, hashes68(1), Duration::from_millis(20)).is_empty());
+
+        let ready = poll_fn(|cx| batcher.poll_expired(cx)).await;
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].peer_id, first);
+        assert!(ready[0].latency >= Duration::from_millis(10));
+
+        let ready = poll_fn(|cx| batcher.poll_expired(cx)).await;
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].peer_id, second);
+        assert_eq!(ready[0].hashes.len(), 2);
+        assert!(ready[0].latency < Duration::from_secs(10));
+
+        assert_eq!(batcher.num_pending(), 0);
+    }
+}
@@ -1,9 +1,11 @@
-use std::{fmt::Debug, marker::PhantomData, str::FromStr};
+use std::{fmt::Debug, marker::PhantomData, str::FromStr, time::Duration};
 
 use super::{
-    PeerMetadata, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+    PeerMetadata, DEFAULT_ANNOUNCEMENT_MAX_DELAY, DEFAULT_ANNOUNCEMENT_SATURATION_RATE,
+    DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
     DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
+    SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE,
 };
 use crate::transactions::constants::tx_fetcher::{
     DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
@@ -13,6 +15,7 @@ use alloy_primitives::B256;
 use derive_more::{Constructor, Display};
 use reth_eth_wire::NetworkPrimitives;
 use reth_ethereum_primitives::TxType;
+use reth_transaction_pool::validate::ValidationBudgetConfig;
 
 /// Configuration for managing transactions within the network.
 #[derive(Debug, Clone)]
@@ -25,6 +28,22 @@ pub struct TransactionsManagerConfig {
     /// How new pending transactions are propagated.
     #[cfg_attr(feature = "serde", serde(default))]
     pub propagation_mode: TransactionPropagationMode,
+    /// Validation cost budget for transactions received from a single peer, `None` disables the
+    /// budget.
+    ///
+    /// Transactions exceeding the budget of the peer they were received from are dropped before
+    /// they are imported into the pool, see
+    /// [`validation_cost`](reth_transaction_pool::validate::validation_cost).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub peer_validation_budget: Option<ValidationBudgetConfig>,
+    /// How hashes of new pending transactions are batched into announcements, `None` announces
+    /// them immediately.
+    #[cfg_attr(feature = "serde", serde(default = "default_announcement_batching"))]
+    pub announcement_batching: Option<AnnouncementBatchConfig>,
+    /// Whether to disconnect peers that deliver transactions with a different type or size than
+    /// they announced. Such peers are always penalized.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub disconnect_on_announcement_mismatch: bool,
 }
 
 impl Default for TransactionsManagerConfig {
@@ -33,10 +52,68 @@ impl Default for TransactionsManagerConfig {
             transaction_fetcher_config: TransactionFetcherConfig::default(),
             max_transactions_seen_by_peer_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             propagation_mode: TransactionPropagationMode::default(),
+            peer_validation_budget: None,
+            announcement_batching: default_announcement_batching(),
+            disconnect_on_announcement_mismatch: false,
         }
     }
 }
 
+/// Returns the default announcement batching.
+fn default_announcement_batching() -> Option<AnnouncementBatchConfig> {
+    Some(AnnouncementBatchConfig::default())
+}
+
+/// Configuration for batching hashes of new pending transactions into announcements.
+///
+/// Announcements to a peer are delayed by a fraction of the peer's round trip time, that grows
+/// with the rate new pending transactions arrive at, so that hashes are aggregated into fewer
+/// messages during mempool storms, while they are announced without delay when the mempool is
+/// quiet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnouncementBatchConfig {
+    /// Upper bound on the delay of an announcement.
+    pub max_delay: Duration,
+    /// Max number of hashes in a batched announcement. A batch that is full is announced right
+    /// away.
+    pub max_batch_size: usize,
+    /// Rate of new pending transactions per second, at which announcements are delayed by half
+    /// of the peer's round trip time. At lower rates, the delay shrinks proportionally.
+    pub saturation_rate: u64,
+}
+
+impl Default for AnnouncementBatchConfig {
+    fn default() -> Self {
+        Self {
+            max_delay: DEFAULT_ANNOUNCEMENT_MAX_DELAY,
+            max_batch_size: SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE,
+            saturation_rate: DEFAULT_ANNOUNCEMENT_SATURATION_RATE,
+        }
+    }
+}
+
+impl AnnouncementBatchConfig {
+    /// Sets the upper bound on the delay of an announcement.
+    pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the max number of hashes in a batched announcement.
+    pub const fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// Sets the rate of new pending transactions per second, at which announcements are delayed
+    /// by half of the peer's round trip time.
+    pub const fn with_saturation_rate(mut self, saturation_rate: u64) -> Self {
+        self.saturation_rate = saturation_rate;
+        self
+    }
+}
+
 /// Determines how new pending transactions are propagated to other peers in full.
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -36,6 +36,7 @@ pub const SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE: usize = 2 * 1024 *
 /// Constants used by [`TransactionsManager`](super::TransactionsManager).
 pub mod tx_manager {
     use super::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
+    use std::time::Duration;
 
     /// Default limit for number of transactions to keep track of for a single peer.
     ///
@@ -53,6 +54,27 @@ pub mod tx_manager {
     ///
     /// Default is 100 KiB, i.e. 3 200 transaction hashes.
     pub const DEFAULT_MAX_COUNT_BAD_IMPORTS: u32 = 100 * 1024 / 32;
+
+    /// Default upper bound on the delay of a batched transaction hash announcement.
+    ///
+    /// Default is 100 milliseconds.
+    pub const DEFAULT_ANNOUNCEMENT_MAX_DELAY: Duration = Duration::from_millis(100);
+
+    /// Default rate of new pending transactions per second, at which transaction hash
+    /// announcements are delayed by half of the peer's round trip time.
+    ///
+    /// Default is 1 000 transactions per second.
+    pub const DEFAULT_ANNOUNCEMENT_SATURATION_RATE: u64 = 1_000;
+
+    /// Round trip time assumed for peers, that no round trip time has been measured for yet.
+    ///
+    /// Default is 100 milliseconds.
+    pub const DEFAULT_ANNOUNCEMENT_PEER_RTT: Duration = Duration::from_millis(100);
+
+    /// Announcements are sent right away, if they would be delayed by less than this.
+    ///
+    /// Default is 1 millisecond.
+    pub const MIN_ANNOUNCEMENT_DELAY: Duration = Duration::from_millis(1);
 }
 
 /// Constants used by [`TransactionFetcher`](super::TransactionFetcher).
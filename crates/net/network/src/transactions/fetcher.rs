@@ -36,6 +36,7 @@ use crate::{
     metrics::TransactionFetcherMetrics,
 };
 use alloy_consensus::transaction::PooledTransaction;
+use alloy_eips::{eip2718::Encodable2718, Typed2718};
 use alloy_primitives::TxHash;
 use derive_more::{Constructor, Deref};
 use futures::{stream::FuturesUnordered, Future, FutureExt, Stream, StreamExt};
@@ -54,7 +55,7 @@ use std::{
     collections::HashMap,
     pin::Pin,
     task::{ready, Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc::error::TrySendError, oneshot, oneshot::error::RecvError};
 use tracing::trace;
@@ -516,9 +517,9 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
         new_announced_hashes.retain(|hash, metadata| {
 
             // occupied entry
-            if let Some(TxFetchMetadata{ tx_encoded_length: previously_seen_size, ..}) = self.hashes_fetch_inflight_and_pending_fetch.peek_mut(hash) {
+            if let Some(TxFetchMetadata{ tx_encoded_length: previously_seen_size, tx_type, announced_by, ..}) = self.hashes_fetch_inflight_and_pending_fetch.peek_mut(hash) {
                 // update size metadata if available
-                if let Some((_ty, size)) = metadata {
+                if let Some((ty, size)) = metadata {
                     if let Some(prev_size) = previously_seen_size {
                         // check if this peer is announcing a different size than a previous peer
                         if size != prev_size {
@@ -534,6 +535,8 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
                     }
                     // believe the most recent peer to announce tx
                     *previously_seen_size = Some(*size);
+                    *tx_type = Some(*ty);
+                    *announced_by = Some(*peer_id);
                 }
 
                 // hash has been seen but is not inflight
@@ -552,8 +555,15 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
 
             previously_unseen_hashes_count += 1;
 
+            let (tx_type, tx_encoded_length) = (*metadata).unzip();
             if self.hashes_fetch_inflight_and_pending_fetch.get_or_insert(*hash, ||
-                TxFetchMetadata{retries: 0, fallback_peers: LruCache::new(DEFAULT_MAX_COUNT_FALLBACK_PEERS as u32), tx_encoded_length: None}
+                TxFetchMetadata{
+                    retries: 0,
+                    fallback_peers: LruCache::new(DEFAULT_MAX_COUNT_FALLBACK_PEERS as u32),
+                    tx_encoded_length,
+                    tx_type,
+                    announced_by: metadata.is_some().then_some(*peer_id),
+                }
             ).is_none() {
 
                 trace!(target: "net::tx",
@@ -840,6 +850,52 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
         }
     }
 
+    /// Returns the number of transactions in the response that don't match the type or size
+    /// the peer announced for them in an [`Eth68`](reth_eth_wire::EthVersion::Eth68)
+    /// announcement.
+    ///
+    /// Transactions for which the most recent announcement was made by another peer aren't
+    /// checked.
+    fn count_announcement_mismatches(
+        &self,
+        payload: &PartiallyValidData<N::PooledTransaction>,
+        peer_id: &PeerId,
+    ) -> usize {
+        payload
+            .iter()
+            .filter(|(hash, tx)| {
+                let Some(TxFetchMetadata {
+                    tx_encoded_length: Some(announced_size),
+                    tx_type: Some(announced_ty),
+                    announced_by: Some(announced_by),
+                    ..
+                }) = self.hashes_fetch_inflight_and_pending_fetch.peek(*hash)
+                else {
+                    return false
+                };
+                if announced_by != peer_id {
+                    return false
+                }
+
+                let (ty, size) = (tx.ty(), tx.encode_2718_len());
+                if ty == *announced_ty && size == *announced_size {
+                    return false
+                }
+
+                trace!(target: "net::tx",
+                    peer_id=format!("{peer_id:#}"),
+                    %hash,
+                    announced_ty,
+                    ty,
+                    announced_size,
+                    size,
+                    "peer sent transaction with a different type or size than announced"
+                );
+                true
+            })
+            .count()
+    }
+
     /// Processes a resolved [`GetPooledTransactions`] request. Queues the outcome as a
     /// [`FetchEvent`], which will then be streamed by
     /// [`TransactionsManager`](super::TransactionsManager).
@@ -849,7 +905,7 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
     ) -> FetchEvent<N::PooledTransaction> {
         // update peer activity, requests for buffered hashes can only be made to idle
         // fallback peers
-        let GetPooledTxResponse { peer_id, mut requested_hashes, result } = response;
+        let GetPooledTxResponse { peer_id, mut requested_hashes, result, response_time } = response;
 
         self.decrement_inflight_request_count_for(&peer_id);
 
@@ -907,11 +963,6 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
 
                 let valid_payload = verified_payload.dedup();
 
-                // todo: validate based on announced tx size/type and report peer for sending
-                // invalid response <https://github.com/paradigmxyz/reth/issues/6529>. requires
-                // passing the rlp encoded length down from active session along with the decoded
-                // tx.
-
                 if valid_payload.len() != unvalidated_payload_len {
                     trace!(target: "net::tx",
                     peer_id=format!("{peer_id:#}"),
@@ -920,6 +971,16 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
                     "received `PooledTransactions` response from peer with duplicate entries, filtered them out"
                     );
                 }
+
+                // check transactions against the size and type the peer announced for them
+                let announcement_mismatches =
+                    self.count_announcement_mismatches(&valid_payload, &peer_id);
+                if announcement_mismatches > 0 {
+                    self.metrics
+                        .announcement_mismatch_transactions
+                        .increment(announcement_mismatches as u64);
+                }
+
                 // valid payload will have at least one transaction at this point. even if the tx
                 // size/type announced by the peer is different to the actual tx size/type, pass on
                 // to pending pool imports pipeline for validation.
@@ -956,7 +1017,13 @@ impl<N: NetworkPrimitives> TransactionFetcher<N> {
 
                 let transactions = valid_payload.into_data().into_values().collect();
 
-                FetchEvent::TransactionsFetched { peer_id, transactions, report_peer }
+                FetchEvent::TransactionsFetched {
+                    peer_id,
+                    transactions,
+                    report_peer,
+                    announcement_mismatches,
+                    response_time,
+                }
             }
             Ok(Err(req_err)) => {
                 self.try_buffer_hashes_for_retry(requested_hashes, &peer_id);
@@ -1013,10 +1080,12 @@ pub struct TxFetchMetadata {
     /// Peers that have announced the hash, but to which a request attempt has not yet been made.
     fallback_peers: LruCache<PeerId>,
     /// Size metadata of the transaction if it has been seen in an eth68 announcement.
-    // todo: store all seen sizes as a `(size, peer_id)` tuple to catch peers that respond with
-    // another size tx than they announced. alt enter in request (won't catch peers announcing
-    // wrong size for requests assembled from hashes pending fetch if stored in request fut)
     tx_encoded_length: Option<usize>,
+    /// Type metadata of the transaction if it has been seen in an eth68 announcement.
+    tx_type: Option<u8>,
+    /// The peer that made the most recent eth68 announcement of the transaction, which the type
+    /// and size metadata was taken from.
+    announced_by: Option<PeerId>,
 }
 
 impl TxFetchMetadata {
@@ -1046,6 +1115,12 @@ pub enum FetchEvent<T = PooledTransaction> {
         /// Whether the peer should be penalized for sending unsolicited transactions or for
         /// misbehavior.
         report_peer: bool,
+        /// Number of fetched transactions that don't match the type or size the peer announced
+        /// for them.
+        announcement_mismatches: usize,
+        /// Time it took the peer to respond to the request, an estimate of the round trip time
+        /// to the peer.
+        response_time: Duration,
     },
     /// Triggered when there is an error in fetching transactions.
     FetchError {
@@ -1068,6 +1143,8 @@ pub struct GetPooledTxRequest<T = PooledTransaction> {
     /// Transaction hashes that were requested, for cleanup purposes
     requested_hashes: RequestTxHashes,
     response: oneshot::Receiver<RequestResult<PooledTransactions<T>>>,
+    /// When the request was sent.
+    sent_at: Instant,
 }
 
 /// Upon reception of a response, a [`GetPooledTxRequest`] is deconstructed to form a
@@ -1079,6 +1156,8 @@ pub struct GetPooledTxResponse<T = PooledTransaction> {
     /// subset of requested hashes.
     requested_hashes: RequestTxHashes,
     result: Result<RequestResult<PooledTransactions<T>>, RecvError>,
+    /// Time it took to receive the response.
+    response_time: Duration,
 }
 
 /// Stores the response receiver made by sending a [`GetPooledTransactions`] request to a peer's
@@ -1093,12 +1172,19 @@ pub struct GetPooledTxRequestFut<T = PooledTransaction> {
 
 impl<T> GetPooledTxRequestFut<T> {
     #[inline]
-    const fn new(
+    fn new(
         peer_id: PeerId,
         requested_hashes: RequestTxHashes,
         response: oneshot::Receiver<RequestResult<PooledTransactions<T>>>,
     ) -> Self {
-        Self { inner: Some(GetPooledTxRequest { peer_id, requested_hashes, response }) }
+        Self {
+            inner: Some(GetPooledTxRequest {
+                peer_id,
+                requested_hashes,
+                response,
+                sent_at: Instant::now(),
+            }),
+        }
     }
 }
 
@@ -1112,6 +1198,7 @@ impl<T> Future for GetPooledTxRequestFut<T> {
                 peer_id: req.peer_id,
                 requested_hashes: req.requested_hashes,
                 result,
+                response_time: req.sent_at.elapsed(),
             }),
             Poll::Pending => {
                 self.project().inner.set(Some(req));
@@ -1495,4 +1582,46 @@ mod test {
         assert_eq!(1, verified_payload.len());
         assert!(verified_payload.contains(&signed_tx_1));
     }
+
+    #[test]
+    fn count_announcement_mismatches() {
+        let input = hex!(
+            "02f871018302a90f808504890aef60826b6c94ddf4c5025d1a5742cf12f74eec246d4432c295e487e09c3bbcc12b2b80c080a0f21a4eacd0bf8fea9c5105c543be5a1d8c796516875710fafafdf16d16d8ee23a001280915021bb446d1973501a67f93d2b38894a514b976e7b46dc2fe54598daa"
+        );
+        let tx: PooledTransaction =
+            TransactionSigned::decode(&mut &input[..]).unwrap().try_into().unwrap();
+        let hash = *tx.hash();
+        let peer_1 = PeerId::new([1; 64]);
+        let peer_2 = PeerId::new([2; 64]);
+
+        let mut tx_fetcher = TransactionFetcher::<EthNetworkPrimitives>::default();
+        let announce = |tx_fetcher: &mut TransactionFetcher,
+                        peer_id: PeerId,
+                        ty: u8,
+                        size: usize| {
+            let mut announcement = ValidAnnouncementData::from_partially_valid_data(
+                PartiallyValidData::from_raw_data(
+                    [(hash, Some((ty, size)))].into_iter().collect(),
+                    Some(EthVersion::Eth68),
+                ),
+            );
+            tx_fetcher.filter_unseen_and_pending_hashes(&mut announcement, |_| false, &peer_id, "");
+        };
+        let payload =
+            || PartiallyValidData::from_raw_data([(hash, tx.clone())].into_iter().collect(), None);
+
+        // announcement matches the transaction
+        announce(&mut tx_fetcher, peer_1, tx.ty(), tx.encode_2718_len());
+        assert_eq!(tx_fetcher.count_announcement_mismatches(&payload(), &peer_1), 0);
+
+        // peer announced a different size
+        announce(&mut tx_fetcher, peer_1, tx.ty(), tx.encode_2718_len() + 1);
+        assert_eq!(tx_fetcher.count_announcement_mismatches(&payload(), &peer_1), 1);
+
+        // peer announced a different type, the transaction is only checked against the most
+        // recent announcement
+        announce(&mut tx_fetcher, peer_2, 3, tx.encode_2718_len());
+        assert_eq!(tx_fetcher.count_announcement_mismatches(&payload(), &peer_1), 0);
+        assert_eq!(tx_fetcher.count_announcement_mismatches(&payload(), &peer_2), 1);
+    }
 }
@@ -1,5 +1,6 @@
 //! Transactions management for the p2p network.
 
+mod announcement;
 /// Aggregation on configurable parameters for [`TransactionsManager`].
 pub mod config;
 /// Default and spec'd bounds.
@@ -15,11 +16,12 @@ pub use self::constants::{
 };
 use config::{AnnouncementAcceptance, StrictEthAnnouncementFilter, TransactionPropagationKind};
 pub use config::{
-    AnnouncementFilteringPolicy, TransactionFetcherConfig, TransactionPropagationMode,
-    TransactionPropagationPolicy, TransactionsManagerConfig,
+    AnnouncementBatchConfig, AnnouncementFilteringPolicy, TransactionFetcherConfig,
+    TransactionPropagationMode, TransactionPropagationPolicy, TransactionsManagerConfig,
 };
 use policy::{NetworkPolicies, TransactionPolicies};
 
+use announcement::{AnnouncementBatcher, ReadyAnnouncement};
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
 
 use self::constants::{tx_manager::*, DEFAULT_SOFT_LIMIT_BYTE_SIZE_TRANSACTIONS_BROADCAST_MESSAGE};
@@ -57,10 +59,11 @@ use reth_network_p2p::{
 };
 use reth_network_peers::PeerId;
 use reth_network_types::ReputationChangeKind;
-use reth_primitives_traits::SignedTransaction;
+use reth_primitives_traits::{transaction::sender_cache::SenderRecoveryCache, SignedTransaction};
 use reth_tokio_util::EventStream;
 use reth_transaction_pool::{
     error::{PoolError, PoolResult},
+    validate::{validation_cost, ValidationBudget, ValidationBudgetConfig},
     AddedTransactionOutcome, GetPooledTransactionLimit, PoolTransaction, PropagateKind,
     PropagatedTransactions, TransactionPool, ValidPoolTransaction,
 };
@@ -320,6 +323,8 @@ pub struct TransactionsManager<
     bad_imports: LruCache<TxHash>,
     /// All the connected peers.
     peers: HashMap<PeerId, PeerMetadata<N>>,
+    /// Batches hash announcements of new pending transactions, if enabled.
+    announcements: Option<AnnouncementBatcher>,
     /// Send half for the command channel.
     ///
     /// This is kept so that a new [`TransactionsHandle`] can be created at any time.
@@ -418,6 +423,9 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
             ),
             bad_imports: LruCache::new(DEFAULT_MAX_COUNT_BAD_IMPORTS),
             peers: Default::default(),
+            announcements: transactions_manager_config
+                .announcement_batching
+                .map(AnnouncementBatcher::new),
             command_tx,
             command_rx: UnboundedReceiverStream::new(command_rx),
             pending_transactions: ReceiverStream::new(pending),
@@ -863,6 +871,10 @@ where
 
         trace!(target: "net::tx", num_hashes=?hashes.len(), "Start propagating transactions");
 
+        if let Some(announcements) = &mut self.announcements {
+            announcements.on_new_pending_transactions(hashes.len());
+        }
+
         self.propagate_all(hashes);
     }
 
@@ -1022,6 +1034,7 @@ where
 
         // send full transactions to a set of the connected peers based on the configured mode
         let max_num_full = self.config.propagation_mode.full_peer_count(self.peers.len());
+        let mut ready_announcements = Vec::new();
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
         for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
@@ -1071,10 +1084,32 @@ where
                     peer.seen_transactions.insert(hash);
                 }
 
-                trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
+                // batch hashes of transactions, unless propagation is forced
+                let delay = self
+                    .announcements
+                    .as_ref()
+                    .filter(|_| !propagation_mode.is_forced())
+                    .and_then(|announcements| announcements.delay(peer.rtt));
+                if let (Some(announcements), Some(delay)) = (&mut self.announcements, delay) {
+                    trace!(target: "net::tx",
+                        ?peer_id,
+                        num_txs=?new_pooled_hashes.len(),
+                        ?delay,
+                        "Queueing tx hashes announcement to peer"
+                    );
 
-                // send hashes of transactions
-                self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
+                    ready_announcements.extend(announcements.queue(
+                        *peer_id,
+                        new_pooled_hashes,
+                        delay,
+                    ));
+                } else {
+                    trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
+
+                    // send hashes of transactions
+                    self.metrics.announcement_latency.record(0.0);
+                    self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
+                }
             }
 
             // send full transactions, if any
@@ -1096,12 +1131,31 @@ where
             }
         }
 
+        for announcement in ready_announcements {
+            self.send_announcement(announcement);
+        }
+
         // Update propagated transactions metrics
         self.metrics.propagated_transactions.increment(propagated.0.len() as u64);
 
         propagated
     }
 
+    /// Sends a batched announcement of transaction hashes to the peer.
+    fn send_announcement(&self, announcement: ReadyAnnouncement) {
+        let ReadyAnnouncement { peer_id, hashes, latency } = announcement;
+        trace!(target: "net::tx",
+            ?peer_id,
+            num_txs=?hashes.len(),
+            ?latency,
+            "Propagating batched tx hashes to peer"
+        );
+
+        self.metrics.announcement_latency.record(latency.as_secs_f64());
+        self.metrics.batched_announcements.increment(1);
+        self.network.send_transactions_hashes(peer_id, hashes);
+    }
+
     /// Propagates the given transactions to the peers
     ///
     /// This fetches all transaction from the pool, including the 4844 blob transactions but
@@ -1200,13 +1254,16 @@ where
         let SessionInfo { peer_id, client_version, version, .. } = info;
 
         // Insert a new peer into the peerset.
-        let peer = PeerMetadata::<N>::new(
+        let mut peer = PeerMetadata::<N>::new(
             messages,
             version,
             client_version,
             self.config.max_transactions_seen_by_peer_history,
             info.peer_kind,
         );
+        if let Some(config) = self.config.peer_validation_budget {
+            peer = peer.with_validation_budget(config);
+        }
         let peer = match self.peers.entry(peer_id) {
             Entry::Occupied(mut entry) => {
                 entry.insert(peer);
@@ -1257,6 +1314,9 @@ where
                     self.policies.propagation_policy_mut().on_session_closed(&mut peer);
                 }
                 self.transaction_fetcher.remove_peer(&peer_id);
+                if let Some(announcements) = &mut self.announcements {
+                    announcements.remove_peer(&peer_id);
+                }
             }
             NetworkEvent::ActivePeerSession { info, messages } => {
                 // process active peer session and broadcast available transaction from the pool
@@ -1364,12 +1424,12 @@ where
         let mut new_txs = Vec::with_capacity(transactions.len());
         for tx in transactions {
             // recover transaction
-            let tx = match tx.try_into_recovered() {
-                Ok(tx) => tx,
-                Err(badtx) => {
+            let tx = match SenderRecoveryCache::global().recover_signer(&tx) {
+                Ok(signer) => tx.with_signer(signer),
+                Err(_) => {
                     trace!(target: "net::tx",
                         peer_id=format!("{peer_id:#}"),
-                        hash=%badtx.tx_hash(),
+                        hash=%tx.tx_hash(),
                         client_version=%peer.client_version,
                         "failed ecrecovery for transaction"
                     );
@@ -1396,6 +1456,23 @@ where
                         // this is a new transaction that should be imported into the pool
 
                         let pool_transaction = Pool::Transaction::from_pooled(tx);
+
+                        // drop transactions exceeding the validation budget of the peer, without
+                        // tracking them so that they can be received again from other peers
+                        let within_budget = peer.validation_budget.as_mut().is_none_or(|budget| {
+                            budget.try_consume(validation_cost(&pool_transaction))
+                        });
+                        if !within_budget {
+                            trace!(target: "net::tx",
+                                peer_id=format!("{peer_id:#}"),
+                                hash=%pool_transaction.hash(),
+                                client_version=%peer.client_version,
+                                "peer exceeded its validation budget, dropping transaction"
+                            );
+                            self.metrics.validation_budget_exceeded_by_peer.increment(1);
+                            continue
+                        }
+
                         new_txs.push(pool_transaction);
 
                         entry.insert(HashSet::from([peer_id]));
@@ -1454,14 +1531,36 @@ where
         }
     }
 
+    /// Penalizes a peer that delivered transactions with a different type or size than it
+    /// announced for them, and disconnects it if configured.
+    fn on_announcement_mismatch(&self, peer_id: PeerId, mismatches: usize) {
+        trace!(target: "net::tx", ?peer_id, mismatches, "peer delivered transactions that don't match its announcement");
+        self.report_peer(peer_id, ReputationChangeKind::BadAnnouncement);
+        if self.config.disconnect_on_announcement_mismatch {
+            self.network.disconnect_peer(peer_id);
+        }
+    }
+
     /// Processes a [`FetchEvent`].
     fn on_fetch_event(&mut self, fetch_event: FetchEvent<N::PooledTransaction>) {
         match fetch_event {
-            FetchEvent::TransactionsFetched { peer_id, transactions, report_peer } => {
+            FetchEvent::TransactionsFetched {
+                peer_id,
+                transactions,
+                report_peer,
+                announcement_mismatches,
+                response_time,
+            } => {
+                if let Some(peer) = self.peers.get_mut(&peer_id) {
+                    peer.on_response_time(response_time);
+                }
                 self.import_transactions(peer_id, transactions, TransactionSource::Response);
                 if report_peer {
                     self.report_peer(peer_id, ReputationChangeKind::BadTransactions);
                 }
+                if announcement_mismatches > 0 {
+                    self.on_announcement_mismatch(peer_id, announcement_mismatches);
+                }
             }
             FetchEvent::FetchError { peer_id, error } => {
                 trace!(target: "net::tx", ?peer_id, %error, "requesting transactions from peer failed");
@@ -1624,6 +1723,15 @@ where
             |cmd| this.on_command(cmd)
         );
 
+        // Send batched announcements whose delay elapsed.
+        if let Some(Poll::Ready(ready)) =
+            this.announcements.as_mut().map(|announcements| announcements.poll_expired(cx))
+        {
+            for announcement in ready {
+                this.send_announcement(announcement);
+            }
+        }
+
         this.transaction_fetcher.update_metrics();
 
         // all channels are fully drained and import futures pending
@@ -1943,6 +2051,10 @@ pub struct PeerMetadata<N: NetworkPrimitives = EthNetworkPrimitives> {
     client_version: Arc<str>,
     /// The kind of peer.
     peer_kind: PeerKind,
+    /// Validation cost budget for transactions received from the peer.
+    validation_budget: Option<ValidationBudget>,
+    /// Moving average of the time it took the peer to respond to requests.
+    rtt: Option<Duration>,
 }
 
 impl<N: NetworkPrimitives> PeerMetadata<N> {
@@ -1960,9 +2072,17 @@ impl<N: NetworkPrimitives> PeerMetadata<N> {
             version,
             client_version,
             peer_kind,
+            validation_budget: None,
+            rtt: None,
         }
     }
 
+    /// Sets the validation cost budget for transactions received from the peer.
+    pub fn with_validation_budget(mut self, config: ValidationBudgetConfig) -> Self {
+        self.validation_budget = Some(ValidationBudget::new(config));
+        self
+    }
+
     /// Returns a reference to the peer's request sender channel.
     pub const fn request_tx(&self) -> &PeerRequestSender<PeerRequest<N>> {
         &self.request_tx
@@ -1987,6 +2107,20 @@ impl<N: NetworkPrimitives> PeerMetadata<N> {
     pub const fn peer_kind(&self) -> PeerKind {
         self.peer_kind
     }
+
+    /// Returns the estimated round trip time to the peer, if any requests to the peer were
+    /// answered yet.
+    pub const fn rtt(&self) -> Option<Duration> {
+        self.rtt
+    }
+
+    /// Updates the estimated round trip time with the response time of a request to the peer.
+    fn on_response_time(&mut self, response_time: Duration) {
+        self.rtt = Some(match self.rtt {
+            Some(rtt) => (rtt * 7 + response_time) / 8,
+            None => response_time,
+        });
+    }
 }
 
 /// Commands to send to the [`TransactionsManager`]
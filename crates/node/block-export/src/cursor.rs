@@ -0,0 +1,104 @@
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
+use std::{collections::VecDeque, path::PathBuf};
+
+/// Number of the most recently exported blocks that are kept in the [`ExportCursor`].
+///
+/// This bounds how deep a reorg that happened while the node wasn't running can be reverted.
+pub const EXPORT_CURSOR_HISTORY: usize = 256;
+
+/// Positions of the most recent blocks that were acknowledged by the message bus, persisted in a
+/// file.
+///
+/// The last [`EXPORT_CURSOR_HISTORY`] exported blocks are kept, so that the common ancestor with
+/// the canonical chain can be found if the exported blocks were reorged out.
+#[derive(Debug, Clone)]
+pub struct ExportCursor {
+    path: PathBuf,
+    blocks: VecDeque<BlockNumHash>,
+}
+
+impl ExportCursor {
+    /// Opens the cursor that is stored at the given path, it is empty if nothing was exported yet.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, reth_fs_util::FsPathError> {
+        let path = path.into();
+        let blocks =
+            if path.exists() { reth_fs_util::read_json_file(&path)? } else { VecDeque::new() };
+        Ok(Self { path, blocks })
+    }
+
+    /// Returns the last exported block, `None` if nothing was exported yet.
+    pub fn last(&self) -> Option<BlockNumHash> {
+        self.blocks.back().copied()
+    }
+
+    /// Returns the most recently exported blocks, in ascending order.
+    pub const fn blocks(&self) -> &VecDeque<BlockNumHash> {
+        &self.blocks
+    }
+
+    /// Records the given exported blocks, which are expected in ascending order, and atomically
+    /// stores the cursor.
+    ///
+    /// Blocks that were exported before at the same or a higher height are replaced.
+    pub fn push(
+        &mut self,
+        blocks: impl IntoIterator<Item = BlockNumHash>,
+    ) -> Result<(), reth_fs_util::FsPathError> {
+        let mut blocks = blocks.into_iter().peekable();
+        if let Some(first) = blocks.peek() {
+            let first = first.number;
+            self.blocks.retain(|block| block.number < first);
+        }
+        self.blocks.extend(blocks);
+        if let Some(excess) = self.blocks.len().checked_sub(EXPORT_CURSOR_HISTORY) {
+            self.blocks.drain(..excess);
+        }
+        self.store()
+    }
+
+    /// Removes all blocks above the given block number and atomically stores the cursor.
+    pub fn truncate(&mut self, number: BlockNumber) -> Result<(), reth_fs_util::FsPathError> {
+        self.blocks.retain(|block| block.number <= number);
+        self.store()
+    }
+
+    /// Atomically stores the cursor.
+    fn store(&self) -> Result<(), reth_fs_util::FsPathError> {
+        reth_fs_util::atomic_write_file(&self.path, |file| {
+            serde_json::to_writer(file, &self.blocks)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn store_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cursor.json");
+        let mut cursor = ExportCursor::open(&path).unwrap();
+        assert_eq!(cursor.last(), None);
+
+        let block = |number| BlockNumHash::new(number, B256::repeat_byte(number as u8));
+        cursor.push([block(10), block(11), block(12)]).unwrap();
+        assert_eq!(ExportCursor::open(&path).unwrap().last(), Some(block(12)));
+
+        // blocks at the same height are replaced
+        let replaced = BlockNumHash::new(11, B256::ZERO);
+        cursor.push([replaced]).unwrap();
+        assert_eq!(ExportCursor::open(&path).unwrap().blocks(), &[block(10), replaced]);
+
+        cursor.truncate(10).unwrap();
+        assert_eq!(ExportCursor::open(&path).unwrap().blocks(), &[block(10)]);
+
+        // only the most recent blocks are kept
+        cursor.push((11..11 + EXPORT_CURSOR_HISTORY as u64).map(block)).unwrap();
+        let cursor = ExportCursor::open(&path).unwrap();
+        assert_eq!(cursor.blocks().len(), EXPORT_CURSOR_HISTORY);
+        assert_eq!(cursor.blocks().front(), Some(&block(11)));
+    }
+}
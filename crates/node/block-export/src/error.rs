@@ -0,0 +1,26 @@
+use alloy_primitives::BlockNumber;
+use reth_fs_util::FsPathError;
+use reth_storage_errors::provider::ProviderError;
+
+/// Errors that can occur while exporting blocks.
+#[derive(Debug, thiserror::Error)]
+pub enum BlockExportError {
+    /// The message bus URL is invalid or its scheme is not supported.
+    #[error("invalid message bus url: {0}")]
+    InvalidUrl(String),
+    /// A block that should be exported is missing from the database.
+    #[error("block {0} is missing from the database")]
+    MissingBlock(BlockNumber),
+    /// Failed to read the chain from the database.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// Failed to read or write the export cursor.
+    #[error(transparent)]
+    Cursor(#[from] FsPathError),
+    /// Failed to serialize a message.
+    #[error(transparent)]
+    Serialization(#[from] serde_json::Error),
+    /// The message bus failed to acknowledge a message.
+    #[error("failed to publish message: {0}")]
+    Sink(Box<dyn core::error::Error + Send + Sync>),
+}
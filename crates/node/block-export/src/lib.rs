@@ -0,0 +1,39 @@
+//! Export of the canonical chain to an external message bus.
+//!
+//! The [`BlockExportService`] follows the canonical state notifications of the node and publishes
+//! every canonical block together with its receipts and state diff to a [`BlockExportSink`], as an
+//! alternative to writing a custom `ExEx` for streaming the chain to other systems.
+//!
+//! Delivery is at-least-once: the positions of the last blocks that were acknowledged by the
+//! message bus are stored in an [`ExportCursor`] file, and on restart the service resumes right
+//! after them, reverting exported blocks that were reorged out and backfilling any blocks that
+//! were missed from the database. Consumers should therefore be
+//! prepared to receive the same message more than once, see [`ExportMessage::id`].
+//!
+//! Built-in sinks are available behind feature flags:
+//!
+//! - `kafka`: [`KafkaSink`] publishes to a Kafka topic.
+//! - `nats`: [`NatsSink`] publishes to a NATS `JetStream` subject.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod cursor;
+pub use cursor::{ExportCursor, EXPORT_CURSOR_HISTORY};
+
+mod error;
+pub use error::BlockExportError;
+
+mod message;
+pub use message::{AccountDiff, ExportMessage, ExportedBlock, StateDiff};
+
+mod service;
+pub use service::BlockExportService;
+
+mod sink;
+pub use sink::*;
@@ -0,0 +1,186 @@
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, B256, U256};
+use reth_execution_types::ExecutionOutcome;
+use reth_primitives_traits::{Account, NodePrimitives, RecoveredBlock};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A message published to the message bus.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", bound = "")]
+pub enum ExportMessage<N: NodePrimitives> {
+    /// Blocks that were appended to the canonical chain, in ascending order.
+    Commit {
+        /// The committed blocks.
+        blocks: Vec<ExportedBlock<N>>,
+        /// The state changes of all committed blocks combined.
+        state: StateDiff,
+    },
+    /// Blocks that were removed from the canonical chain, in descending order.
+    ///
+    /// This is followed by a [`ExportMessage::Commit`] with the new canonical blocks in case of a
+    /// reorg.
+    Revert {
+        /// The reverted blocks.
+        blocks: Vec<BlockNumHash>,
+    },
+}
+
+impl<N: NodePrimitives> ExportMessage<N> {
+    /// Creates a commit message for the given blocks and their execution outcome.
+    pub fn commit<'a>(
+        blocks: impl IntoIterator<Item = &'a RecoveredBlock<N::Block>>,
+        outcome: &ExecutionOutcome<N::Receipt>,
+    ) -> Self {
+        let blocks = blocks
+            .into_iter()
+            .map(|block| ExportedBlock::new(block, outcome.receipts_by_block(block.number())))
+            .collect();
+        Self::Commit { blocks, state: StateDiff::from(outcome) }
+    }
+
+    /// Creates a revert message for the given blocks, which are expected in descending order.
+    pub const fn revert(blocks: Vec<BlockNumHash>) -> Self {
+        Self::Revert { blocks }
+    }
+
+    /// Returns the last block of the message, which is the block the cursor is moved to after a
+    /// commit.
+    pub fn last_block(&self) -> Option<BlockNumHash> {
+        match self {
+            Self::Commit { blocks, .. } => {
+                blocks.last().map(|block| BlockNumHash::new(block.header.number(), block.hash))
+            }
+            Self::Revert { blocks } => blocks.last().copied(),
+        }
+    }
+
+    /// Returns a unique identifier of the message.
+    ///
+    /// Messages that are published again after a restart have the same identifier, so consumers
+    /// can use it to deduplicate messages.
+    pub fn id(&self) -> String {
+        let (kind, block) = match self {
+            Self::Commit { .. } => ("commit", self.last_block()),
+            Self::Revert { blocks } => ("revert", blocks.first().copied()),
+        };
+        let block = block.unwrap_or_default();
+        format!("{kind}-{}-{}", block.number, block.hash)
+    }
+}
+
+/// A canonical block with its receipts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", bound = "")]
+pub struct ExportedBlock<N: NodePrimitives> {
+    /// Hash of the block.
+    pub hash: B256,
+    /// Header of the block.
+    pub header: N::BlockHeader,
+    /// Body of the block.
+    pub body: N::BlockBody,
+    /// Senders of the transactions in the block.
+    pub senders: Vec<Address>,
+    /// Receipts of the transactions in the block.
+    pub receipts: Vec<N::Receipt>,
+}
+
+impl<N: NodePrimitives> ExportedBlock<N> {
+    /// Creates a new exported block from a recovered block and its receipts.
+    pub fn new(block: &RecoveredBlock<N::Block>, receipts: &[N::Receipt]) -> Self {
+        Self {
+            hash: block.hash(),
+            header: block.header().clone(),
+            body: block.body().clone(),
+            senders: block.senders().to_vec(),
+            receipts: receipts.to_vec(),
+        }
+    }
+}
+
+/// The state changes of one or more blocks.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDiff {
+    /// The changed accounts.
+    pub accounts: BTreeMap<Address, AccountDiff>,
+}
+
+/// The changes of a single account.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountDiff {
+    /// The account after the changes, `None` if the account was destroyed.
+    pub info: Option<Account>,
+    /// The changed storage slots with their new values.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+impl<R> From<&ExecutionOutcome<R>> for StateDiff {
+    fn from(outcome: &ExecutionOutcome<R>) -> Self {
+        let accounts = outcome
+            .bundle_accounts_iter()
+            .map(|(address, account)| {
+                let storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(key, slot)| (*key, slot.present_value))
+                    .collect();
+                (address, AccountDiff { info: account.info.as_ref().map(Account::from), storage })
+            })
+            .collect();
+        Self { accounts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::map::HashMap;
+    use reth_ethereum_primitives::{Block, EthPrimitives, Receipt};
+    use reth_revm::{db::BundleState, state::AccountInfo};
+
+    #[test]
+    fn commit_message() {
+        let address = Address::repeat_byte(1);
+        let info = AccountInfo { balance: U256::from(10), nonce: 1, ..Default::default() };
+        let bundle = BundleState::new(
+            [(
+                address,
+                None,
+                Some(info.clone()),
+                HashMap::from_iter([
+                    (U256::from(1), (U256::ZERO, U256::from(2))),
+                    (U256::from(2), (U256::from(3), U256::from(3))),
+                ]),
+            )],
+            vec![vec![(address, Some(None), vec![(U256::from(1), U256::ZERO)])]],
+            vec![],
+        );
+        let receipt = Receipt { cumulative_gas_used: 21_000, success: true, ..Default::default() };
+        let outcome = ExecutionOutcome::new(bundle, vec![vec![receipt.clone()]], 0, vec![]);
+        let block = RecoveredBlock::<Block>::default();
+
+        let message = ExportMessage::<EthPrimitives>::commit([&block], &outcome);
+        let ExportMessage::Commit { blocks, state } = &message else { panic!("expected commit") };
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].hash, block.hash());
+        assert_eq!(blocks[0].receipts, vec![receipt]);
+        assert_eq!(
+            state.accounts,
+            BTreeMap::from_iter([(
+                address,
+                AccountDiff {
+                    info: Some(Account::from(info)),
+                    storage: BTreeMap::from_iter([(U256::from(1), U256::from(2))]),
+                }
+            )])
+        );
+        assert_eq!(message.last_block(), Some(block.num_hash()));
+        assert_eq!(message.id(), format!("commit-0-{}", block.hash()));
+
+        let json = serde_json::to_string(&message).unwrap();
+        let decoded: ExportMessage<EthPrimitives> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.id(), message.id());
+    }
+}
@@ -0,0 +1,338 @@
+use crate::{BlockExportError, BlockExportSink, ExportCursor, ExportMessage};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::BlockNumber;
+use reth_chain_state::{CanonStateNotification, CanonStateSubscriptions};
+use reth_primitives_traits::{BlockTy, ReceiptTy};
+use reth_storage_api::{BlockReader, StateReader, TransactionVariant};
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, warn};
+
+/// Maximum delay between attempts to publish a message.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Publishes the canonical chain to a [`BlockExportSink`].
+///
+/// The service first exports all blocks between the [`ExportCursor`] and the current tip from the
+/// database, and then follows the canonical state notifications. Reorgs are published as a
+/// [`ExportMessage::Revert`] of the reverted blocks, followed by a [`ExportMessage::Commit`] of
+/// the new canonical blocks.
+///
+/// A message is retried until it is acknowledged by the message bus, the cursor is only advanced
+/// afterwards. Exported blocks that are no longer canonical when the service starts, or after it
+/// missed notifications because it lagged behind, are reverted back to the common ancestor with
+/// the canonical chain.
+#[derive(Debug)]
+pub struct BlockExportService<P, S> {
+    provider: P,
+    sink: S,
+    cursor: ExportCursor,
+    start_block: Option<BlockNumber>,
+}
+
+impl<P, S> BlockExportService<P, S>
+where
+    P: CanonStateSubscriptions
+        + BlockReader<Block = BlockTy<P::Primitives>>
+        + StateReader<Receipt = ReceiptTy<P::Primitives>>
+        + 'static,
+    S: BlockExportSink,
+{
+    /// Creates a new service that publishes blocks to the given sink.
+    pub const fn new(provider: P, sink: S, cursor: ExportCursor) -> Self {
+        Self { provider, sink, cursor, start_block: None }
+    }
+
+    /// Sets the first block to export, if nothing was exported yet.
+    ///
+    /// By default, only blocks that are appended to the canonical chain after the first start of
+    /// the service are exported.
+    pub const fn with_start_block(mut self, start_block: BlockNumber) -> Self {
+        self.start_block = Some(start_block);
+        self
+    }
+
+    /// Runs the service until the canonical state notifications end.
+    pub async fn run(mut self) -> Result<(), BlockExportError> {
+        // subscribe before catching up, so that no notification is missed
+        let mut notifications = self.provider.subscribe_to_canonical_state();
+
+        let mut next = match self.cursor.last() {
+            Some(last) => self.revert_non_canonical(last.number + 1).await?,
+            None => match self.start_block {
+                Some(start_block) => start_block,
+                None => self.provider.best_block_number()? + 1,
+            },
+        };
+        info!(target: "block_export", next, "Starting block export");
+
+        next = self.backfill(next, self.provider.best_block_number()?).await?;
+
+        loop {
+            match notifications.recv().await {
+                Ok(notification) => next = self.on_notification(next, notification).await?,
+                Err(RecvError::Lagged(skipped)) => {
+                    // the missed notifications can contain reorgs, so the exported blocks are
+                    // checked against the canonical chain before catching up from the database
+                    warn!(target: "block_export", skipped, "Canonical state notifications lagged behind");
+                    next = self.revert_non_canonical(next).await?;
+                    next = self.backfill(next, self.provider.best_block_number()?).await?;
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            }
+        }
+    }
+
+    /// Reverts the exported blocks that are no longer canonical, returns the next block to export.
+    ///
+    /// The exported blocks are compared with the canonical chain from the most recent one
+    /// backwards, until the common ancestor is found.
+    async fn revert_non_canonical(
+        &mut self,
+        next: BlockNumber,
+    ) -> Result<BlockNumber, BlockExportError> {
+        let mut reverted = Vec::new();
+        for block in self.cursor.blocks().iter().rev() {
+            if self.provider.block_hash(block.number)? == Some(block.hash) {
+                break
+            }
+            reverted.push(*block);
+        }
+        let Some(oldest) = reverted.last().copied() else { return Ok(next) };
+
+        if reverted.len() == self.cursor.blocks().len() {
+            warn!(target: "block_export", ?oldest, "No common ancestor with the canonical chain among the exported blocks");
+        }
+        warn!(target: "block_export", ?oldest, count = reverted.len(), "Exported blocks are no longer canonical");
+        self.publish(&ExportMessage::revert(reverted)).await?;
+        self.cursor.truncate(oldest.number.saturating_sub(1))?;
+
+        Ok(oldest.number)
+    }
+
+    /// Exports the given canonical state notification, returns the next block to export.
+    async fn on_notification(
+        &mut self,
+        mut next: BlockNumber,
+        notification: CanonStateNotification<P::Primitives>,
+    ) -> Result<BlockNumber, BlockExportError> {
+        if let Some(reverted) = notification.reverted() {
+            // only blocks that were exported need to be reverted
+            let blocks = reverted
+                .blocks()
+                .values()
+                .rev()
+                .filter(|block| block.number() < next)
+                .map(|block| block.num_hash())
+                .collect::<Vec<_>>();
+            if !blocks.is_empty() {
+                let fork_block = reverted.fork_block();
+                self.publish(&ExportMessage::revert(blocks)).await?;
+                self.cursor.truncate(fork_block.number)?;
+                next = fork_block.number + 1;
+            }
+        }
+
+        let committed = notification.committed();
+        if committed.is_empty() || committed.tip().number() < next {
+            return Ok(next)
+        }
+
+        // blocks can be missing if the notification stream lagged behind
+        if committed.first().number() > next {
+            next = self.backfill(next, committed.first().number() - 1).await?;
+        }
+
+        // blocks that were already exported, e.g. by a backfill that raced with the notification,
+        // are not published again
+        let blocks = || committed.blocks_iter().filter(|block| block.number() >= next);
+        let message = if committed.first().number() < next {
+            let (_, outcome) = committed.execution_outcome().clone().split_at(next);
+            ExportMessage::commit(blocks(), &outcome)
+        } else {
+            ExportMessage::commit(blocks(), committed.execution_outcome())
+        };
+        self.publish(&message).await?;
+        self.cursor.push(blocks().map(|block| block.num_hash()))?;
+
+        Ok(committed.tip().number() + 1)
+    }
+
+    /// Exports the blocks in the given range from the database, returns the next block to export.
+    async fn backfill(
+        &mut self,
+        next: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<BlockNumber, BlockExportError> {
+        if next > to {
+            return Ok(next)
+        }
+
+        info!(target: "block_export", from = next, to, "Exporting blocks from the database");
+        for number in next..=to {
+            let block = self
+                .provider
+                .recovered_block(number.into(), TransactionVariant::WithHash)?
+                .ok_or(BlockExportError::MissingBlock(number))?;
+            let outcome =
+                self.provider.get_state(number)?.ok_or(BlockExportError::MissingBlock(number))?;
+
+            self.publish(&ExportMessage::commit([&block], &outcome)).await?;
+            self.cursor.push([block.num_hash()])?;
+        }
+
+        Ok(to + 1)
+    }
+
+    /// Publishes a message, retrying until the message bus acknowledged it.
+    async fn publish(
+        &self,
+        message: &ExportMessage<P::Primitives>,
+    ) -> Result<(), BlockExportError> {
+        let id = message.id();
+        let payload = serde_json::to_vec(message)?;
+
+        let mut backoff = Duration::from_millis(100);
+        while let Err(err) = self.sink.publish(&id, payload.clone()).await {
+            warn!(target: "block_export", %id, %err, ?backoff, "Failed to publish message, retrying");
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+        }
+        debug!(target: "block_export", %id, "Published message");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::B256;
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::Block;
+    use reth_execution_types::Chain;
+    use reth_primitives_traits::{Block as _, RecoveredBlock};
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        BlockHashReader, BlockWriter, StorageLocation,
+    };
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use std::sync::{Arc, Mutex};
+
+    /// A sink that records the ids of the published messages.
+    #[derive(Debug, Clone, Default)]
+    struct TestSink(Arc<Mutex<Vec<String>>>);
+
+    impl TestSink {
+        fn take(&self) -> Vec<String> {
+            std::mem::take(&mut *self.0.lock().unwrap())
+        }
+    }
+
+    impl BlockExportSink for TestSink {
+        async fn publish(&self, id: &str, _payload: Vec<u8>) -> Result<(), BlockExportError> {
+            self.0.lock().unwrap().push(id.to_string());
+            Ok(())
+        }
+    }
+
+    /// Returns a chain of empty blocks with the given numbers on top of the given parent.
+    fn blocks(
+        numbers: impl IntoIterator<Item = BlockNumber>,
+        mut parent: B256,
+    ) -> Vec<RecoveredBlock<Block>> {
+        let mut rng = generators::rng();
+        numbers
+            .into_iter()
+            .map(|number| {
+                let block = random_block(
+                    &mut rng,
+                    number,
+                    BlockParams { parent: Some(parent), tx_count: Some(0), ..Default::default() },
+                );
+                parent = block.hash();
+                block.try_recover().unwrap()
+            })
+            .collect()
+    }
+
+    fn chain(blocks: &[RecoveredBlock<Block>]) -> Arc<Chain> {
+        Arc::new(Chain::new(blocks.to_vec(), Default::default(), None))
+    }
+
+    /// Returns a provider whose canonical chain consists of the genesis and the given number of
+    /// blocks.
+    fn provider(
+        count: BlockNumber,
+    ) -> (BlockchainProvider<MockNodeTypesWithDB>, Vec<RecoveredBlock<Block>>) {
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory).unwrap();
+        let blocks = blocks(1..=count, genesis_hash);
+
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        for block in &blocks {
+            provider_rw.insert_block(block.clone(), StorageLocation::Database).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        (BlockchainProvider::new(provider_factory).unwrap(), blocks)
+    }
+
+    #[tokio::test]
+    async fn reverts_non_canonical_blocks_to_common_ancestor() {
+        let (provider, canonical) = provider(3);
+        let forked = blocks(2..=4, canonical[0].hash());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cursor.json");
+        let mut cursor = ExportCursor::open(&path).unwrap();
+        cursor.push([canonical[0].num_hash()]).unwrap();
+        cursor.push(forked.iter().map(|block| block.num_hash())).unwrap();
+
+        let sink = TestSink::default();
+        let mut service = BlockExportService::new(provider, sink.clone(), cursor);
+
+        // all forked blocks are reverted in one message, newest first
+        assert_eq!(service.revert_non_canonical(5).await.unwrap(), 2);
+        assert_eq!(sink.take(), vec![format!("revert-4-{}", forked[2].hash())]);
+        assert_eq!(ExportCursor::open(&path).unwrap().last(), Some(canonical[0].num_hash()));
+
+        // nothing is reverted if the exported blocks are canonical
+        assert_eq!(service.revert_non_canonical(2).await.unwrap(), 2);
+        assert!(sink.take().is_empty());
+    }
+
+    #[tokio::test]
+    async fn exports_reorgs() {
+        let (provider, _) = provider(0);
+        let genesis = provider.block_hash(0).unwrap().unwrap();
+        let old = blocks(1..=2, genesis);
+        let new = blocks(2..=3, old[0].hash());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cursor.json");
+        let cursor = ExportCursor::open(&path).unwrap();
+        let sink = TestSink::default();
+        let mut service = BlockExportService::new(provider, sink.clone(), cursor);
+
+        let notification = CanonStateNotification::Commit { new: chain(&old) };
+        assert_eq!(service.on_notification(1, notification).await.unwrap(), 3);
+        assert_eq!(sink.take(), vec![format!("commit-2-{}", old[1].hash())]);
+
+        // the reorged block is reverted before the new blocks are committed
+        let notification =
+            CanonStateNotification::Reorg { old: chain(&old[1..]), new: chain(&new) };
+        assert_eq!(service.on_notification(3, notification).await.unwrap(), 4);
+        assert_eq!(
+            sink.take(),
+            vec![format!("revert-2-{}", old[1].hash()), format!("commit-3-{}", new[1].hash())]
+        );
+
+        let cursor = ExportCursor::open(&path).unwrap();
+        let exported = [old[0].num_hash(), new[0].num_hash(), new[1].num_hash()];
+        assert_eq!(cursor.blocks(), &exported);
+        assert_eq!(cursor.last(), Some(BlockNumHash::new(3, new[1].hash())));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::BlockExportError;
+use std::future::Future;
+
+/// A message bus that exported blocks are published to.
+pub trait BlockExportSink: Send + Sync + 'static {
+    /// Publishes a message and resolves once the message bus acknowledged it.
+    ///
+    /// The `id` uniquely identifies the message, see
+    /// [`ExportMessage::id`](crate::ExportMessage::id).
+    fn publish(
+        &self,
+        id: &str,
+        payload: Vec<u8>,
+    ) -> impl Future<Output = Result<(), BlockExportError>> + Send;
+}
+
+/// A [`BlockExportSink`] that is selected by the scheme of a URL.
+///
+/// Supported schemes are `kafka://<brokers>` with the `kafka` feature and `nats://<server>` with
+/// the `nats` feature.
+#[derive(Debug)]
+pub enum MessageBusSink {
+    /// Publishes to Kafka.
+    #[cfg(feature = "kafka")]
+    Kafka(KafkaSink),
+    /// Publishes to NATS `JetStream`.
+    #[cfg(feature = "nats")]
+    Nats(NatsSink),
+}
+
+impl MessageBusSink {
+    /// Connects to the message bus at the given URL, messages are published to the given topic or
+    /// subject.
+    ///
+    /// The partition only applies to Kafka, see [`KafkaSink::with_partition`].
+    #[cfg_attr(not(any(feature = "kafka", feature = "nats")), allow(unused_variables))]
+    pub async fn connect(
+        url: &str,
+        topic: &str,
+        partition: Option<i32>,
+    ) -> Result<Self, BlockExportError> {
+        match url.split_once("://").map(|(scheme, _)| scheme) {
+            #[cfg(feature = "kafka")]
+            Some("kafka") => {
+                let mut sink = KafkaSink::new(&url["kafka://".len()..], topic)?;
+                if let Some(partition) = partition {
+                    sink = sink.with_partition(partition);
+                }
+                Ok(Self::Kafka(sink))
+            }
+            #[cfg(feature = "nats")]
+            Some("nats") => Ok(Self::Nats(NatsSink::connect(url, topic).await?)),
+            Some(scheme) if ["kafka", "nats"].contains(&scheme) => {
+                Err(BlockExportError::InvalidUrl(format!(
+                    "{url}, reth was built without the `{scheme}` feature"
+                )))
+            }
+            _ => Err(BlockExportError::InvalidUrl(url.to_string())),
+        }
+    }
+}
+
+impl BlockExportSink for MessageBusSink {
+    #[cfg_attr(not(any(feature = "kafka", feature = "nats")), allow(unused_variables))]
+    async fn publish(&self, id: &str, payload: Vec<u8>) -> Result<(), BlockExportError> {
+        match *self {
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref sink) => sink.publish(id, payload).await,
+            #[cfg(feature = "nats")]
+            Self::Nats(ref sink) => sink.publish(id, payload).await,
+        }
+    }
+}
+
+#[cfg(feature = "kafka")]
+pub use kafka::{KafkaSink, MESSAGE_ID_HEADER};
+
+#[cfg(feature = "kafka")]
+mod kafka {
+    use super::BlockExportSink;
+    use crate::BlockExportError;
+    use rdkafka::{
+        message::{Header, OwnedHeaders},
+        producer::{FutureProducer, FutureRecord},
+        util::Timeout,
+        ClientConfig,
+    };
+
+    /// Name of the Kafka header that contains the message id.
+    pub const MESSAGE_ID_HEADER: &str = "message-id";
+
+    /// Publishes messages to a Kafka topic.
+    ///
+    /// All messages are written to the same partition of the topic to preserve their order: the
+    /// configured partition, or otherwise the partition that the partitioner of the producer
+    /// assigns to the topic name, which is used as the key of all messages. The message id is
+    /// sent in the [`MESSAGE_ID_HEADER`].
+    ///
+    /// Messages are only considered published once they were acknowledged by all in-sync
+    /// replicas.
+    pub struct KafkaSink {
+        producer: FutureProducer,
+        topic: String,
+        partition: Option<i32>,
+    }
+
+    impl KafkaSink {
+        /// Creates a producer for the given comma separated list of brokers.
+        pub fn new(brokers: &str, topic: impl Into<String>) -> Result<Self, BlockExportError> {
+            let producer = ClientConfig::new()
+                .set("bootstrap.servers", brokers)
+                .set("acks", "all")
+                .set("enable.idempotence", "true")
+                .create()
+                .map_err(|err| BlockExportError::Sink(err.into()))?;
+            Ok(Self { producer, topic: topic.into(), partition: None })
+        }
+
+        /// Publishes all messages to the given partition of the topic.
+        pub const fn with_partition(mut self, partition: i32) -> Self {
+            self.partition = Some(partition);
+            self
+        }
+    }
+
+    impl core::fmt::Debug for KafkaSink {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("KafkaSink")
+                .field("topic", &self.topic)
+                .field("partition", &self.partition)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl BlockExportSink for KafkaSink {
+        async fn publish(&self, id: &str, payload: Vec<u8>) -> Result<(), BlockExportError> {
+            let headers =
+                OwnedHeaders::new().insert(Header { key: MESSAGE_ID_HEADER, value: Some(id) });
+            let mut record = FutureRecord::to(&self.topic)
+                .key(self.topic.as_str())
+                .headers(headers)
+                .payload(&payload);
+            if let Some(partition) = self.partition {
+                record = record.partition(partition);
+            }
+            self.producer
+                .send(record, Timeout::Never)
+                .await
+                .map_err(|(err, _)| BlockExportError::Sink(err.into()))?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "nats")]
+pub use nats::NatsSink;
+
+#[cfg(feature = "nats")]
+mod nats {
+    use super::BlockExportSink;
+    use crate::BlockExportError;
+    use async_nats::{jetstream, HeaderMap};
+
+    /// Publishes messages to a NATS `JetStream` subject.
+    ///
+    /// The message id is set as `Nats-Msg-Id` header, so `JetStream` discards messages that are
+    /// published again within the duplicate window of the stream.
+    #[derive(Debug)]
+    pub struct NatsSink {
+        jetstream: jetstream::Context,
+        subject: String,
+    }
+
+    impl NatsSink {
+        /// Connects to the NATS server at the given URL.
+        pub async fn connect(
+            url: &str,
+            subject: impl Into<String>,
+        ) -> Result<Self, BlockExportError> {
+            let client =
+                async_nats::connect(url).await.map_err(|err| BlockExportError::Sink(err.into()))?;
+            Ok(Self { jetstream: jetstream::new(client), subject: subject.into() })
+        }
+    }
+
+    impl BlockExportSink for NatsSink {
+        async fn publish(&self, id: &str, payload: Vec<u8>) -> Result<(), BlockExportError> {
+            let mut headers = HeaderMap::new();
+            headers.insert("Nats-Msg-Id", id);
+            self.jetstream
+                .publish_with_headers(self.subject.clone(), headers, payload.into())
+                .await
+                .map_err(|err| BlockExportError::Sink(err.into()))?
+                .await
+                .map_err(|err| BlockExportError::Sink(err.into()))?;
+            Ok(())
+        }
+    }
+}
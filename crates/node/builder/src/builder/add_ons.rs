@@ -1,6 +1,7 @@
 //! Node add-ons. Depend on core [`NodeComponents`](crate::NodeComponents).
 
-use reth_node_api::{FullNodeComponents, NodeAddOns};
+use reth_engine_tree::tree::InvalidBlockHook;
+use reth_node_api::{FullNodeComponents, NodeAddOns, PrimitivesTy};
 
 use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks};
 
@@ -12,6 +13,9 @@ pub struct AddOns<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     pub hooks: NodeHooks<Node, AddOns>,
     /// The `ExExs` (execution extensions) of the node.
     pub exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    /// Additional [`InvalidBlockHook`]s of the node, invoked in order after the hooks configured
+    /// via the CLI.
+    pub invalid_block_hooks: Vec<Box<dyn InvalidBlockHook<PrimitivesTy<Node::Types>>>>,
     /// Additional captured addons.
     pub add_ons: AddOns,
 }
@@ -14,15 +14,16 @@ use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli_util::get_secret_key;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
+use reth_engine_tree::tree::InvalidBlockHook;
 use reth_exex::ExExContext;
 use reth_network::{
     transactions::{TransactionPropagationPolicy, TransactionsManagerConfig},
-    NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
+    BanList, NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
     NetworkPrimitives,
 };
 use reth_node_api::{
     FullNodePrimitives, FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes,
-    NodeTypesWithDBAdapter,
+    NodeTypesWithDBAdapter, PrimitivesTy,
 };
 use reth_node_core::{
     cli::config::{PayloadBuilderConfig, RethTransactionPoolConfig},
@@ -624,6 +625,19 @@ where
         }
     }
 
+    /// Installs an [`InvalidBlockHook`] in the node.
+    ///
+    /// See [`NodeBuilderWithComponents::install_invalid_block_hook`].
+    pub fn install_invalid_block_hook<H>(self, hook: H) -> Self
+    where
+        H: InvalidBlockHook<PrimitivesTy<T::Types>> + 'static,
+    {
+        Self {
+            builder: self.builder.install_invalid_block_hook(hook),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Launches the node with the given launcher.
     pub async fn launch_with<L>(self, launcher: L) -> eyre::Result<L::Node>
     where
@@ -831,6 +845,7 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
 
         let default_peers_path = self.config().datadir().known_peers();
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
+        let banned_peers_file = self.banned_peers_file();
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
             |shutdown| {
@@ -847,6 +862,16 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
                             }
                         }
                     }
+                    if let Some(banned_peers_file) = banned_peers_file {
+                        match network.write_ban_list_to_file(banned_peers_file.as_path()) {
+                            Ok(_) => {
+                                info!(target: "reth::cli", ?banned_peers_file, "Wrote banned peers to file");
+                            }
+                            Err(err) => {
+                                warn!(target: "reth::cli", %err, "Failed to write banned peers to file");
+                            }
+                        }
+                    }
                 })
             },
         );
@@ -854,6 +879,29 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         handle
     }
 
+    /// Returns the file the ban list is persisted to, if enabled.
+    fn banned_peers_file(&self) -> Option<std::path::PathBuf> {
+        self.config().network.persistent_banned_peers_file(self.config().datadir().banned_peers())
+    }
+
+    /// Loads the bans persisted by a previous run, see [`NetworkManager::write_ban_list_to_file`].
+    fn persisted_ban_list(&self) -> BanList {
+        let Some(banned_peers_file) = self.banned_peers_file() else { return BanList::default() };
+        if !banned_peers_file.exists() {
+            return BanList::default()
+        }
+        match reth_fs_util::read_json_file(&banned_peers_file) {
+            Ok(persisted) => {
+                info!(target: "reth::cli", ?banned_peers_file, "Loading banned peers");
+                BanList::from_persisted(persisted)
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", %err, "Failed to read banned peers from file");
+                BanList::default()
+            }
+        }
+    }
+
     /// Get the network secret from the given data dir
     fn network_secret(&self, data_dir: &ChainPath<DataDirPath>) -> eyre::Result<SecretKey> {
         let network_secret_path =
@@ -912,7 +960,9 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
                 default_peers_path,
             )
             .with_task_executor(Box::new(self.executor.clone()))
-            .set_head(self.head);
+            .set_head(self.head)
+            .ban_list(self.persisted_ban_list())
+            .persistent_ban_list_file(self.banned_peers_file());
 
         Ok(builder)
     }
@@ -13,8 +13,9 @@ use crate::{
     AddOns, ComponentsFor, FullNode,
 };
 
+use reth_engine_tree::tree::InvalidBlockHook;
 use reth_exex::ExExContext;
-use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
+use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes, PrimitivesTy};
 use reth_node_core::node_config::NodeConfig;
 use reth_tasks::TaskExecutor;
 use std::{fmt, fmt::Debug, future::Future};
@@ -47,7 +48,12 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons: () },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                invalid_block_hooks: Vec::new(),
+                add_ons: (),
+            },
         }
     }
 }
@@ -173,7 +179,12 @@ where
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                invalid_block_hooks: Vec::new(),
+                add_ons,
+            },
         }
     }
 }
@@ -219,6 +230,19 @@ where
         self
     }
 
+    /// Installs an [`InvalidBlockHook`] in the node.
+    ///
+    /// Installed hooks are invoked in installation order, after the hooks configured via
+    /// `--debug.invalid-block-hook`. A panic in one hook does not prevent the remaining hooks
+    /// from running.
+    pub fn install_invalid_block_hook<H>(mut self, hook: H) -> Self
+    where
+        H: InvalidBlockHook<PrimitivesTy<T::Types>> + 'static,
+    {
+        self.add_ons.invalid_block_hooks.push(Box::new(hook));
+        self
+    }
+
     /// Launches the node with the given closure.
     pub fn launch_with_fn<L, R>(self, launcher: L) -> R
     where
@@ -247,6 +247,8 @@ where
             reth_transaction_pool::maintain::MaintainPoolConfig {
                 max_tx_lifetime: pool_config.max_queued_lifetime,
                 no_local_exemptions: pool_config.local_transactions_config.no_exemptions,
+                // transactions of blocks reverted via `evm_revert` are dropped in dev mode
+                reinject_reorged_transactions: !ctx.is_dev(),
                 ..Default::default()
             },
         ),
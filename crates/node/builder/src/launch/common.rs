@@ -40,19 +40,22 @@ use alloy_primitives::{BlockNumber, B256};
 use eyre::{Context, OptionExt};
 use rayon::ThreadPoolBuilder;
 use reth_chainspec::{Chain, EthChainSpec, EthereumHardfork, EthereumHardforks};
-use reth_config::{config::EtlConfig, PruneConfig};
+use reth_config::{config::EtlConfig, PruneConfig, RestartConfig};
 use reth_consensus::noop::NoopConsensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis, InitStorageError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_local::MiningMode;
-use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
+use reth_engine_tree::tree::{
+    ExecutionWitnessSink, FileExecutionWitnessSink, InvalidBlockHook, InvalidBlockHooks,
+    NoopExecutionWitnessSink, NoopInvalidBlockHook,
+};
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_exex::ExExManagerHandle;
 use reth_fs_util as fs;
 use reth_invalid_block_hooks::InvalidBlockWitnessHook;
 use reth_network_p2p::headers::client::HeadersClient;
-use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
+use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter, ReceiptTy};
 use reth_node_core::{
     args::{DefaultEraHost, InvalidBlockHookType},
     dirs::{ChainPath, DataDirPath},
@@ -73,7 +76,7 @@ use reth_node_metrics::{
 use reth_provider::{
     providers::{NodeTypesForProvider, ProviderNodeTypes, StaticFileProvider},
     BlockHashReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider, ProviderError,
-    ProviderFactory, ProviderResult, StageCheckpointReader, StateProviderFactory,
+    ProviderFactory, ProviderResult, StageCheckpointReader, StateProviderFactory, StateReader,
     StaticFileProviderFactory,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
@@ -85,7 +88,10 @@ use reth_stages::{
     StageId,
 };
 use reth_static_file::StaticFileProducer;
-use reth_tasks::TaskExecutor;
+use reth_tasks::{
+    supervisor::{RestartPolicy, SupervisionPolicy},
+    TaskExecutor,
+};
 use reth_tracing::tracing::{debug, error, info, warn};
 use reth_transaction_pool::TransactionPool;
 use std::{sync::Arc, thread::available_parallelism};
@@ -95,6 +101,7 @@ use tokio::sync::{
 };
 
 use futures::{future::Either, stream, Stream, StreamExt};
+use reth_node_block_export::{BlockExportService, ExportCursor, MessageBusSink};
 use reth_node_ethstats::EthStatsService;
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node::NodeEvent};
 
@@ -438,6 +445,7 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
         PrunerBuilder::new(self.prune_config().unwrap_or_default())
             .delete_limit(self.chain_spec().prune_delete_limit())
             .timeout(PrunerBuilder::DEFAULT_TIMEOUT)
+            .supervision(supervision_policy(self.toml_config().supervision.pruner))
     }
 
     /// Loads the JWT secret for the engine API
@@ -470,13 +478,22 @@ where
         N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
         Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
     {
+        let sender_transactions_index = self.toml_config().stages.index_sender_transactions.enabled;
+        if sender_transactions_index &&
+            self.prune_modes().sender_recovery.is_some_and(|mode| mode.is_full())
+        {
+            warn!(target: "reth::cli", "Sender recovery is fully pruned, the sender transactions index is not maintained");
+        }
+
         let factory = ProviderFactory::new(
             self.right().clone(),
             self.chain_spec(),
             StaticFileProvider::read_write(self.data_dir().static_files())?,
         )
         .with_prune_modes(self.prune_modes())
-        .with_static_files_metrics();
+        .with_sender_transactions_index(sender_transactions_index)
+        .with_static_files_metrics()
+        .with_static_files_verification();
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
@@ -833,7 +850,12 @@ where
     pub fn static_file_producer(
         &self,
     ) -> StaticFileProducer<ProviderFactory<NodeTypesWithDBAdapter<T::Types, T::DB>>> {
-        StaticFileProducer::new(self.provider_factory().clone(), self.prune_modes())
+        let static_file_producer =
+            StaticFileProducer::new(self.provider_factory().clone(), self.prune_modes());
+        static_file_producer.lock().set_supervision(supervision_policy(
+            self.toml_config().supervision.static_file_producer,
+        ));
+        static_file_producer
     }
 
     /// Returns the current head block.
@@ -1064,6 +1086,37 @@ where
 
         Ok(())
     }
+
+    /// Spawns the [`BlockExportService`] if a message bus is configured.
+    pub async fn spawn_block_export(&self) -> eyre::Result<()>
+    where
+        T::Provider: StateReader<Receipt = ReceiptTy<T::Types>>,
+    {
+        let args = &self.node_config().block_export;
+        let Some(url) = args.url.as_ref() else { return Ok(()) };
+
+        info!(target: "reth::cli", %url, topic = %args.topic, "Starting block export");
+
+        let sink = MessageBusSink::connect(url, &args.topic, args.partition).await?;
+        let cursor =
+            ExportCursor::open(self.node_config().datadir().data_dir().join("block-export.json"))?;
+        let mut service =
+            BlockExportService::new(self.node_adapter().provider.clone(), sink, cursor);
+        if let Some(start_block) = args.start_block {
+            service = service.with_start_block(start_block);
+        }
+
+        self.task_executor().spawn_critical(
+            "block export",
+            Box::pin(async move {
+                if let Err(err) = service.run().await {
+                    panic!("Block export failed: {err}");
+                }
+            }),
+        );
+
+        Ok(())
+    }
 }
 
 impl<T, CB>
@@ -1078,16 +1131,23 @@ where
     CB: NodeComponentsBuilder<T>,
 {
     /// Returns the [`InvalidBlockHook`] to use for the node.
+    ///
+    /// The hooks configured via the CLI are invoked first, followed by the given `installed`
+    /// hooks in order.
     pub async fn invalid_block_hook(
         &self,
+        installed: Vec<Box<dyn InvalidBlockHook<<T::Types as NodeTypes>::Primitives>>>,
     ) -> eyre::Result<Box<dyn InvalidBlockHook<<T::Types as NodeTypes>::Primitives>>> {
         let Some(ref hook) = self.node_config().debug.invalid_block_hook else {
-            return Ok(Box::new(NoopInvalidBlockHook::default()))
+            if installed.is_empty() {
+                return Ok(Box::new(NoopInvalidBlockHook::default()))
+            }
+            return Ok(Box::new(InvalidBlockHooks(installed)))
         };
         let healthy_node_rpc_client = self.get_healthy_node_client().await?;
 
         let output_directory = self.data_dir().invalid_block_hooks();
-        let hooks = hook
+        let mut hooks = hook
             .iter()
             .copied()
             .map(|hook| {
@@ -1106,11 +1166,23 @@ where
                     }
                 } as Box<dyn InvalidBlockHook<_>>)
             })
-            .collect::<Result<_, _>>()?;
+            .collect::<Result<Vec<_>, _>>()?;
+        hooks.extend(installed);
 
         Ok(Box::new(InvalidBlockHooks(hooks)))
     }
 
+    /// Returns the [`ExecutionWitnessSink`] that receives the execution witnesses of canonical
+    /// blocks.
+    pub fn execution_witness_sink(
+        &self,
+    ) -> Box<dyn ExecutionWitnessSink<<T::Types as NodeTypes>::Primitives>> {
+        match self.node_config().engine.execution_witness_dir.clone() {
+            Some(output_directory) => Box::new(FileExecutionWitnessSink::new(output_directory)),
+            None => Box::new(NoopExecutionWitnessSink::default()),
+        }
+    }
+
     /// Returns an RPC client for the healthy node, if configured in the node config.
     async fn get_healthy_node_client(
         &self,
@@ -1241,6 +1313,19 @@ where
     head: Head,
 }
 
+/// Returns the [`SupervisionPolicy`] of a subsystem from its [`RestartConfig`].
+const fn supervision_policy(config: RestartConfig) -> SupervisionPolicy {
+    if config.enabled {
+        SupervisionPolicy::Restart(RestartPolicy {
+            initial_backoff: config.initial_backoff,
+            max_backoff: config.max_backoff,
+            max_restarts: config.max_restarts,
+        })
+    } else {
+        SupervisionPolicy::Shutdown
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{LaunchContext, NodeConfig};
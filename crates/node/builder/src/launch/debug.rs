@@ -4,7 +4,7 @@ use alloy_provider::network::AnyNetwork;
 use jsonrpsee::core::{DeserializeOwned, Serialize};
 use reth_chainspec::EthChainSpec;
 use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider};
-use reth_engine_local::LocalMiner;
+use reth_engine_local::{LocalMiner, PayloadAttributesInjector};
 use reth_node_api::{BlockTy, FullNodeComponents, PayloadAttributesBuilder, PayloadTypes};
 use std::sync::Arc;
 use tracing::info;
@@ -69,6 +69,20 @@ pub trait DebugNode<N: FullNodeComponents>: Node<N> {
     ) -> impl PayloadAttributesBuilder<
         <<Self as reth_node_api::NodeTypes>::Payload as PayloadTypes>::PayloadAttributes,
     >;
+
+    /// Creates a payload attributes builder for local mining in dev mode that applies the
+    /// overrides supplied by the given [`PayloadAttributesInjector`], e.g. withdrawals.
+    ///
+    /// By default, the injector is ignored and the builder of
+    /// [`DebugNode::local_payload_attributes_builder`] is used.
+    fn local_payload_attributes_builder_with_injector(
+        chain_spec: &Self::ChainSpec,
+        _injector: PayloadAttributesInjector,
+    ) -> impl PayloadAttributesBuilder<
+        <<Self as reth_node_api::NodeTypes>::Payload as PayloadTypes>::PayloadAttributes,
+    > {
+        Self::local_payload_attributes_builder(chain_spec)
+    }
 }
 
 /// Node launcher with support for launching various debugging utilities.
@@ -95,12 +109,23 @@ pub trait DebugNode<N: FullNodeComponents>: Node<N> {
 #[derive(Debug, Clone)]
 pub struct DebugNodeLauncher<L = EngineNodeLauncher> {
     inner: L,
+    /// Injector for the payload attributes of blocks mined in dev mode.
+    payload_attributes_injector: Option<PayloadAttributesInjector>,
 }
 
 impl<L> DebugNodeLauncher<L> {
     /// Creates a new instance of the [`DebugNodeLauncher`].
     pub const fn new(inner: L) -> Self {
-        Self { inner }
+        Self { inner, payload_attributes_injector: None }
+    }
+
+    /// Sets the [`PayloadAttributesInjector`] used by the local miner in dev mode.
+    ///
+    /// This allows injecting withdrawals and other payload attributes overrides into the mined
+    /// blocks through a clone of the injector while the node is running.
+    pub fn with_payload_attributes_injector(mut self, injector: PayloadAttributesInjector) -> Self {
+        self.payload_attributes_injector = Some(injector);
+        self
     }
 }
 
@@ -113,7 +138,8 @@ where
     type Node = NodeHandle<N, AddOns>;
 
     async fn launch_node(self, target: Target) -> eyre::Result<Self::Node> {
-        let handle = self.inner.launch_node(target).await?;
+        let Self { inner, payload_attributes_injector } = self;
+        let handle = inner.launch_node(target).await?;
 
         let config = &handle.node.config;
         if let Some(ws_url) = config.debug.rpc_consensus_ws.clone() {
@@ -176,18 +202,24 @@ where
             let blockchain_db = handle.node.provider.clone();
             let chain_spec = config.chain.clone();
             let beacon_engine_handle = handle.node.add_ons_handle.beacon_engine_handle.clone();
+            let local_miner = handle.node.add_ons_handle.local_miner.clone().unwrap_or_default();
             let pool = handle.node.pool.clone();
             let payload_builder_handle = handle.node.payload_builder_handle.clone();
 
             let dev_mining_mode = handle.node.config.dev_mining_mode(pool);
+            let injector = payload_attributes_injector.unwrap_or_default();
+            if let Some(overrides) = config.dev_payload_attributes_overrides()? {
+                injector.set_every_block(overrides);
+            }
             handle.node.task_executor.spawn_critical("local engine", async move {
                 LocalMiner::new(
                     blockchain_db,
-                    N::Types::local_payload_attributes_builder(&chain_spec),
+                    N::Types::local_payload_attributes_builder_with_injector(&chain_spec, injector),
                     beacon_engine_handle,
                     dev_mining_mode,
                     payload_builder_handle,
                 )
+                .with_handle(local_miner)
                 .run()
                 .await
             });
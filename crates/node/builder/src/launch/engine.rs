@@ -5,8 +5,9 @@ use crate::{
     hooks::NodeHooks,
     rpc::{EngineValidatorAddOn, RethRpcAddOns, RpcHandle},
     setup::build_networked_pipeline,
-    AddOns, AddOnsContext, FullNode, LaunchContext, LaunchNode, NodeAdapter,
-    NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
+    AddOns, AddOnsContext, BeaconConsensusEngineEvent, FullNode, LaunchContext, LaunchNode,
+    NodeAdapter, NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle,
+    NodeTypesAdapter,
 };
 use alloy_consensus::BlockHeader;
 use futures::{stream_select, StreamExt};
@@ -27,13 +28,14 @@ use reth_node_api::{
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
     exit::NodeExitFuture,
-    primitives::Head,
+    primitives::{hash_cache::AddressHashCache, Head},
 };
 use reth_node_events::node;
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     BlockNumReader,
 };
+use reth_static_file::{BlockProcessingActivity, StaticFileProducerThrottle};
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
@@ -86,10 +88,14 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, invalid_block_hooks, add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
+        let canon_state_history = config.engine.canon_state_history;
+
+        // size the shared address hash cache before any state is hashed
+        AddressHashCache::init_global(config.engine.address_hash_cache_slots);
 
         // setup the launch context
         let ctx = ctx
@@ -119,7 +125,11 @@ where
             // passing FullNodeTypes as type parameter here so that we can build
             // later the components.
             .with_blockchain_db::<T, _>(move |provider_factory| {
-                Ok(BlockchainProvider::new(provider_factory)?)
+                let provider = BlockchainProvider::new(provider_factory)?;
+                provider
+                    .canonical_in_memory_state()
+                    .set_canon_state_history_capacity(canon_state_history);
+                Ok(provider)
             })?
             .with_components(components_builder, on_component_initialized).await?;
 
@@ -156,7 +166,7 @@ where
             ctx.sync_metrics_tx(),
             ctx.prune_config(),
             max_block,
-            static_file_producer,
+            static_file_producer.clone(),
             ctx.components().evm_config().clone(),
             maybe_exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty),
             ctx.era_import_source(),
@@ -165,6 +175,18 @@ where
         // The new engine writes directly to static files. This ensures that they're up to the tip.
         pipeline.move_to_static_files()?;
 
+        // Once the static files are up to the tip, throttle the producer so it doesn't compete
+        // with the engine for resources during live sync
+        let block_processing = BlockProcessingActivity::default();
+        let static_file_producer_config = ctx.toml_config().stages.static_file_producer;
+        if static_file_producer_config.is_throttled() {
+            let throttle = StaticFileProducerThrottle::new(static_file_producer_config.chunk_size)
+                .with_pause(static_file_producer_config.pause)
+                .with_activity(block_processing.clone(), static_file_producer_config.max_defer);
+            static_file_producer.lock().set_throttle(Some(throttle));
+            info!(target: "reth::cli", config = ?static_file_producer_config, "StaticFileProducer throttled");
+        }
+
         let pipeline_events = pipeline.events();
 
         let mut pruner_builder = ctx.pruner_builder();
@@ -189,6 +211,7 @@ where
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            consensus_clients: Default::default(),
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
@@ -202,6 +225,13 @@ where
                 node_config.debug.reorg_frequency,
                 node_config.debug.reorg_depth,
             )
+            .maybe_self_check(
+                ctx.blockchain_db().clone(),
+                ctx.components().evm_config().clone(),
+                engine_payload_validator.clone(),
+                ctx.components().pool().clone(),
+                node_config.debug.self_check_payloads,
+            )
             // Store messages _after_ skipping so that `replay-engine` command
             // would replay only the messages that were observed by the engine
             // during this run.
@@ -219,8 +249,10 @@ where
             pruner,
             ctx.components().payload_builder_handle().clone(),
             engine_payload_validator,
-            engine_tree_config,
-            ctx.invalid_block_hook().await?,
+            // the dev mode `evm_revert` endpoint reverts the chain by unwinding the canonical head
+            engine_tree_config.with_unwind_canonical_header(ctx.is_dev()),
+            ctx.invalid_block_hook(invalid_block_hooks).await?,
+            ctx.execution_witness_sink(),
             ctx.sync_metrics_tx(),
             ctx.components().evm_config().clone(),
         );
@@ -244,8 +276,13 @@ where
             ),
         );
 
-        let RpcHandle { rpc_server_handles, rpc_registry, engine_events, beacon_engine_handle } =
-            add_ons.launch_add_ons(add_ons_ctx).await?;
+        let RpcHandle {
+            rpc_server_handles,
+            rpc_registry,
+            engine_events,
+            beacon_engine_handle,
+            local_miner,
+        } = add_ons.launch_add_ons(add_ons_ctx).await?;
 
         // Run consensus engine to completion
         let initial_target = ctx.initial_backfill_target()?;
@@ -300,6 +337,17 @@ where
                                 break
                             }
                             ChainEvent::Handler(ev) => {
+                                match &ev {
+                                    BeaconConsensusEngineEvent::BlockReceived(_) => {
+                                        block_processing.set_active(true);
+                                    }
+                                    BeaconConsensusEngineEvent::CanonicalBlockAdded(..) |
+                                    BeaconConsensusEngineEvent::ForkBlockAdded(..) |
+                                    BeaconConsensusEngineEvent::InvalidBlock(_) => {
+                                        block_processing.set_active(false);
+                                    }
+                                    _ => {}
+                                }
                                 if let Some(head) = ev.canonical_header() {
                                     // Once we're progressing via live sync, we can consider the node is not syncing anymore
                                     network_handle.update_sync_state(SyncState::Idle);
@@ -343,12 +391,14 @@ where
                 rpc_registry,
                 engine_events,
                 beacon_engine_handle,
+                local_miner,
             },
         };
         // Notify on node started
         on_node_started.on_event(FullNode::clone(&full_node))?;
 
         ctx.spawn_ethstats().await?;
+        ctx.spawn_block_export().await?;
 
         let handle = NodeHandle {
             node_exit_future: NodeExitFuture::new(
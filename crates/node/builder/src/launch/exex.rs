@@ -10,8 +10,9 @@ use reth_exex::{
 };
 use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
 use reth_provider::CanonStateSubscriptions;
-use reth_tracing::tracing::{debug, info};
-use std::{fmt, fmt::Debug};
+use reth_tasks::shutdown::GracefulShutdown;
+use reth_tracing::tracing::{debug, error, info, warn};
+use std::{fmt, fmt::Debug, future::Future, pin::pin, time::Duration};
 use tracing::Instrument;
 
 use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
@@ -60,6 +61,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 .exex_wal(),
         )?;
 
+        let shutdown_timeout = config_container.config.exex.shutdown_timeout;
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexes = Vec::with_capacity(extensions.len());
 
@@ -93,17 +95,9 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 let exex = exex.launch(context).instrument(span.clone()).await.unwrap();
 
                 // spawn it as a crit task
-                executor.spawn_critical(
-                    "exex",
-                    async move {
-                        info!(target: "reth::cli", "ExEx started");
-                        match exex.await {
-                            Ok(_) => panic!("ExEx {id} finished. ExExes should run indefinitely"),
-                            Err(err) => panic!("ExEx {id} crashed: {err}"),
-                        }
-                    }
-                    .instrument(span),
-                );
+                executor.spawn_critical_with_graceful_shutdown_signal("exex", |shutdown| {
+                    run_exex(id, exex, shutdown, shutdown_timeout).instrument(span)
+                });
             });
         }
 
@@ -119,16 +113,42 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
             components.provider().finalized_block_stream(),
         );
         let exex_manager_handle = exex_manager.handle();
-        components.task_executor().spawn_critical("exex manager", async move {
-            exex_manager.await.expect("exex manager crashed");
-        });
+        components.task_executor().spawn_critical_with_graceful_shutdown_signal(
+            "exex manager",
+            |shutdown| async move {
+                let mut exex_manager = exex_manager;
+                let guard = tokio::select! {
+                    res = &mut exex_manager => {
+                        res.expect("exex manager crashed");
+                        return
+                    }
+                    guard = shutdown => guard,
+                };
+
+                match exex_manager.shutdown(shutdown_timeout).await {
+                    Ok(true) => debug!(target: "reth::cli", "All ExExes acknowledged shutdown"),
+                    Ok(false) => {}
+                    Err(err) => error!(target: "reth::cli", %err, "Failed to shut down ExExes"),
+                }
+                drop(guard);
+            },
+        );
 
         // send notifications from the blockchain tree to exex manager
-        let mut canon_state_notifications = components.provider().subscribe_to_canonical_state();
+        // replay the recent notifications first, so that ExExes don't miss any that were emitted
+        // before the subscription
+        let (history, mut canon_state_notifications) =
+            components.provider().subscribe_to_canonical_state_with_history();
         let mut handle = exex_manager_handle.clone();
         components.task_executor().spawn_critical(
             "exex manager blockchain tree notifications",
             async move {
+                for notification in history {
+                    handle
+                        .send_async(ExExNotificationSource::BlockchainTree, notification.into())
+                        .await
+                        .expect("blockchain tree notification could not be sent to exex manager");
+                }
                 while let Ok(notification) = canon_state_notifications.recv().await {
                     handle
                         .send_async(ExExNotificationSource::BlockchainTree, notification.into())
@@ -144,6 +164,36 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     }
 }
 
+/// Runs the `ExEx` until the node shuts down.
+///
+/// On shutdown, the [`ExExManager`] sends the shutdown notification to the `ExEx` and closes its
+/// notifications channel afterwards, so the `ExEx` is given up to `shutdown_timeout` to finish on
+/// its own.
+async fn run_exex(
+    id: String,
+    exex: impl Future<Output = eyre::Result<()>>,
+    shutdown: GracefulShutdown,
+    shutdown_timeout: Duration,
+) {
+    info!(target: "reth::cli", "ExEx started");
+    let mut exex = pin!(exex);
+
+    let guard = tokio::select! {
+        res = exex.as_mut() => match res {
+            Ok(_) => panic!("ExEx {id} finished. ExExes should run indefinitely"),
+            Err(err) => panic!("ExEx {id} crashed: {err}"),
+        },
+        guard = shutdown => guard,
+    };
+
+    match tokio::time::timeout(shutdown_timeout, exex).await {
+        Ok(Ok(_)) => info!(target: "reth::cli", "ExEx finished"),
+        Ok(Err(err)) => error!(target: "reth::cli", %err, "ExEx failed to shut down"),
+        Err(_) => warn!(target: "reth::cli", ?shutdown_timeout, "ExEx did not finish in time"),
+    }
+    drop(guard);
+}
+
 impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ExExLauncher")
@@ -9,32 +9,39 @@ use alloy_rpc_types_engine::ExecutionData;
 use jsonrpsee::{core::middleware::layer::Either, RpcModule};
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
+use reth_engine_local::LocalMinerHandle;
 use reth_engine_tree::tree::EngineValidator;
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineTypes, FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes,
-    PayloadTypes, PrimitivesTy,
+    PayloadAttributesHook, PayloadTypes, PrimitivesTy,
 };
 use reth_node_core::{
     node_config::NodeConfig,
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
-use reth_rpc::eth::{core::EthRpcConverterFor, EthApiTypes, FullEthApiServer};
-use reth_rpc_api::{eth::helpers::AddDevSigners, IntoEngineApiRpcModule};
+use reth_rpc::{
+    eth::{core::EthRpcConverterFor, EthApiTypes, FullEthApiServer},
+    AdminEngineApi, EvmApi,
+};
+use reth_rpc_api::{
+    eth::helpers::AddDevSigners, AdminEngineApiServer, GanacheApiServer, IntoEngineApiRpcModule,
+};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
     TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
-use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
+use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache, TipSuggester};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, info};
 use std::{
     fmt::{self, Debug},
     future::Future,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
 /// Contains the handles to the spawned RPC servers.
@@ -327,6 +334,8 @@ pub struct RpcHandle<Node: FullNodeComponents, EthApi: EthApiTypes> {
         EventSender<BeaconConsensusEngineEvent<<Node::Types as NodeTypes>::Primitives>>,
     /// Handle to the beacon consensus engine.
     pub beacon_engine_handle: BeaconConsensusEngineHandle<<Node::Types as NodeTypes>::Payload>,
+    /// Handle to the local miner that serves the `evm_` namespace, only set in dev mode.
+    pub local_miner: Option<LocalMinerHandle>,
 }
 
 impl<Node: FullNodeComponents, EthApi: EthApiTypes> Clone for RpcHandle<Node, EthApi> {
@@ -336,6 +345,7 @@ impl<Node: FullNodeComponents, EthApi: EthApiTypes> Clone for RpcHandle<Node, Et
             rpc_registry: self.rpc_registry.clone(),
             engine_events: self.engine_events.clone(),
             beacon_engine_handle: self.beacon_engine_handle.clone(),
+            local_miner: self.local_miner.clone(),
         }
     }
 }
@@ -407,6 +417,7 @@ struct RpcSetupContext<'a, Node: FullNodeComponents, EthApi: EthApiTypes> {
     on_rpc_started: Box<dyn OnRpcStarted<Node, EthApi>>,
     engine_events: EventSender<BeaconConsensusEngineEvent<<Node::Types as NodeTypes>::Primitives>>,
     engine_handle: BeaconConsensusEngineHandle<<Node::Types as NodeTypes>::Payload>,
+    local_miner: Option<LocalMinerHandle>,
 }
 
 /// Node add-ons containing RPC server configuration, with customizable eth API handler.
@@ -439,6 +450,8 @@ pub struct RpcAddOns<
     /// This middleware is applied to all RPC requests across all transports (HTTP, WS, IPC).
     /// See [`RpcAddOns::with_rpc_middleware`] for more details.
     rpc_middleware: RpcMiddleware,
+    /// Custom algorithm for `eth_maxPriorityFeePerGas` and `eth_gasPrice` suggestions.
+    tip_suggester: Option<Arc<dyn TipSuggester>>,
 }
 
 impl<Node, EthB, EV, EB, RpcMiddleware> Debug for RpcAddOns<Node, EthB, EV, EB, RpcMiddleware>
@@ -455,6 +468,7 @@ where
             .field("engine_validator_builder", &self.engine_validator_builder)
             .field("engine_api_builder", &self.engine_api_builder)
             .field("rpc_middleware", &"...")
+            .field("tip_suggester", &self.tip_suggester)
             .finish()
     }
 }
@@ -477,6 +491,7 @@ where
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            tip_suggester: None,
         }
     }
 
@@ -485,13 +500,21 @@ where
         self,
         engine_api_builder: T,
     ) -> RpcAddOns<Node, EthB, EV, T, RpcMiddleware> {
-        let Self { hooks, eth_api_builder, engine_validator_builder, rpc_middleware, .. } = self;
+        let Self {
+            hooks,
+            eth_api_builder,
+            engine_validator_builder,
+            rpc_middleware,
+            tip_suggester,
+            ..
+        } = self;
         RpcAddOns {
             hooks,
             eth_api_builder,
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            tip_suggester,
         }
     }
 
@@ -500,13 +523,85 @@ where
         self,
         engine_validator_builder: T,
     ) -> RpcAddOns<Node, EthB, T, EB, RpcMiddleware> {
-        let Self { hooks, eth_api_builder, engine_api_builder, rpc_middleware, .. } = self;
+        let Self {
+            hooks, eth_api_builder, engine_api_builder, rpc_middleware, tip_suggester, ..
+        } = self;
         RpcAddOns {
             hooks,
             eth_api_builder,
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            tip_suggester,
+        }
+    }
+
+    /// Replaces the [`EthApiBuilder`] that builds the `eth` namespace API.
+    ///
+    /// The hooks are typed on the `EthApi`, so hooks configured before this call are discarded.
+    /// Configure them with [`RpcAddOns::on_rpc_started`] and [`RpcAddOns::extend_rpc_modules`]
+    /// afterwards.
+    pub fn with_eth_api_builder<T>(
+        self,
+        eth_api_builder: T,
+    ) -> RpcAddOns<Node, T, EV, EB, RpcMiddleware>
+    where
+        T: EthApiBuilder<Node>,
+    {
+        let Self {
+            engine_validator_builder,
+            engine_api_builder,
+            rpc_middleware,
+            tip_suggester,
+            ..
+        } = self;
+        RpcAddOns {
+            hooks: RpcHooks::default(),
+            eth_api_builder,
+            engine_validator_builder,
+            engine_api_builder,
+            rpc_middleware,
+            tip_suggester,
+        }
+    }
+
+    /// Maps the `EthApi` built by the configured [`EthApiBuilder`] with the given closure.
+    ///
+    /// This can be used to decorate the default `EthApi`, for example with a type that delegates
+    /// to the wrapped `EthApi` but forwards `eth_sendRawTransaction` to a private relay, or to
+    /// replace it with a differently configured instance, without reimplementing the builder.
+    ///
+    /// See [`RpcAddOns::with_eth_api_builder`] for how this affects configured hooks.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rpc_add_ons =
+    ///     rpc_add_ons.map_eth_api(move |eth_api| Ok(RelayEthApi::new(eth_api, relay_url)));
+    /// ```
+    pub fn map_eth_api<F, T>(
+        self,
+        f: F,
+    ) -> RpcAddOns<Node, MapEthApiBuilder<EthB, F>, EV, EB, RpcMiddleware>
+    where
+        F: FnOnce(EthB::EthApi) -> eyre::Result<T>,
+        MapEthApiBuilder<EthB, F>: EthApiBuilder<Node, EthApi = T>,
+    {
+        let Self {
+            eth_api_builder,
+            engine_validator_builder,
+            engine_api_builder,
+            rpc_middleware,
+            tip_suggester,
+            ..
+        } = self;
+        RpcAddOns {
+            hooks: RpcHooks::default(),
+            eth_api_builder: MapEthApiBuilder::new(eth_api_builder, f),
+            engine_validator_builder,
+            engine_api_builder,
+            rpc_middleware,
+            tip_suggester,
         }
     }
 
@@ -549,14 +644,21 @@ where
     /// - The default middleware is `Identity` (no-op), which passes through requests unchanged
     /// - Middleware layers are applied in the order they are added via `.layer()`
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> RpcAddOns<Node, EthB, EV, EB, T> {
-        let Self { hooks, eth_api_builder, engine_validator_builder, engine_api_builder, .. } =
-            self;
+        let Self {
+            hooks,
+            eth_api_builder,
+            engine_validator_builder,
+            engine_api_builder,
+            tip_suggester,
+            ..
+        } = self;
         RpcAddOns {
             hooks,
             eth_api_builder,
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            tip_suggester,
         }
     }
 
@@ -571,6 +673,7 @@ where
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            tip_suggester,
         } = self;
         let rpc_middleware = Stack::new(rpc_middleware, layer);
         RpcAddOns {
@@ -579,6 +682,7 @@ where
             engine_validator_builder,
             engine_api_builder,
             rpc_middleware,
+            tip_suggester,
         }
     }
 
@@ -591,6 +695,16 @@ where
         self.layer_rpc_middleware(layer)
     }
 
+    /// Sets a custom algorithm that suggests priority fees for `eth_maxPriorityFeePerGas` and
+    /// `eth_gasPrice`, replacing the algorithm configured with `--gpo.algorithm`.
+    ///
+    /// This only takes effect if the [`EthApiBuilder`] builds the `EthApi` with
+    /// [`EthApiCtx::eth_api_builder`].
+    pub fn with_tip_suggester(mut self, tip_suggester: Arc<dyn TipSuggester>) -> Self {
+        self.tip_suggester = Some(tip_suggester);
+        self
+    }
+
     /// Sets the hook that is run once the rpc server is started.
     pub fn on_rpc_started<F>(mut self, hook: F) -> Self
     where
@@ -615,7 +729,7 @@ where
 impl<Node, EthB, EV, EB> Default for RpcAddOns<Node, EthB, EV, EB, Identity>
 where
     Node: FullNodeComponents,
-    EthB: EthApiBuilder<Node>,
+    EthB: EthApiBuilder<Node> + Default,
     EV: Default,
     EB: Default,
 {
@@ -658,6 +772,7 @@ where
             on_rpc_started,
             engine_events,
             engine_handle,
+            local_miner: _,
         } = setup_ctx;
 
         let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
@@ -724,6 +839,7 @@ where
             on_rpc_started,
             engine_events,
             engine_handle,
+            local_miner,
         } = setup_ctx;
 
         let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
@@ -760,6 +876,7 @@ where
             rpc_registry: registry,
             engine_events,
             beacon_engine_handle: engine_handle,
+            local_miner,
         })
     }
 
@@ -772,10 +889,17 @@ where
     where
         F: FnOnce(RpcModuleContainer<'_, N, EthB::EthApi>) -> eyre::Result<()>,
     {
-        let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
+        let Self { eth_api_builder, engine_api_builder, hooks, tip_suggester, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            consensus_clients,
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -785,7 +909,7 @@ where
             node.task_executor().clone(),
         );
 
-        let new_canonical_blocks = node.provider().canonical_state_stream();
+        let new_canonical_blocks = node.provider().canonical_state_stream_with_history();
         let c = cache.clone();
         node.task_executor().spawn_critical(
             "cache canonical blocks task",
@@ -794,10 +918,21 @@ where
             }),
         );
 
-        let ctx = EthApiCtx { components: &node, config: config.rpc.eth_config(), cache };
+        let ctx =
+            EthApiCtx { components: &node, config: config.rpc.eth_config(), cache, tip_suggester };
         let eth_api = eth_api_builder.build_eth_api(ctx).await?;
 
         let auth_config = config.rpc.auth_server_config(jwt_secret)?;
+
+        #[cfg(unix)]
+        if !config.rpc.disable_auth_server {
+            let path = config.rpc.auth_jwtsecret.clone().unwrap_or_else(|| config.datadir().jwt());
+            node.task_executor().spawn(Box::pin(reload_jwt_secret_on_sighup(
+                auth_config.secrets().clone(),
+                path,
+                config.rpc.auth_jwtsecret_grace_period,
+            )));
+        }
         let module_config = config.rpc.transport_rpc_module_config();
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
@@ -810,11 +945,25 @@ where
             .with_consensus(node.consensus().clone())
             .build_with_auth_server(module_config, engine_api, eth_api);
 
+        // admin methods that are handled by the engine
+        modules.merge_if_module_configured(
+            RethRpcModule::Admin,
+            AdminEngineApi::new(beacon_engine_handle.clone())
+                .with_consensus_clients(consensus_clients)
+                .into_rpc(),
+        )?;
+
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             registry.eth_api().with_dev_accounts();
         }
 
+        // in dev mode the `evm_` namespace controls the local miner
+        let local_miner = config.dev.dev.then(LocalMinerHandle::default);
+        if let Some(local_miner) = &local_miner {
+            modules.merge_configured(EvmApi::new(local_miner.clone()).into_rpc())?;
+        }
+
         let mut registry = RpcRegistry { registry };
         let ctx = RpcContext {
             node: node.clone(),
@@ -843,6 +992,7 @@ where
             on_rpc_started,
             engine_events,
             engine_handle: beacon_engine_handle,
+            local_miner,
         })
     }
 
@@ -955,25 +1105,32 @@ pub struct EthApiCtx<'a, N: FullNodeTypes> {
     pub config: EthConfig,
     /// Cache for eth state
     pub cache: EthStateCache<PrimitivesTy<N::Types>>,
+    /// Custom algorithm for priority fee suggestions, see [`RpcAddOns::with_tip_suggester`]
+    pub tip_suggester: Option<Arc<dyn TipSuggester>>,
 }
 
 impl<'a, N: FullNodeComponents<Types: NodeTypes<ChainSpec: EthereumHardforks>>> EthApiCtx<'a, N> {
     /// Provides a [`EthApiBuilder`] with preconfigured config and components.
     pub fn eth_api_builder(self) -> reth_rpc::EthApiBuilder<N, EthRpcConverterFor<N>> {
-        reth_rpc::EthApiBuilder::new_with_components(self.components.clone())
+        let builder = reth_rpc::EthApiBuilder::new_with_components(self.components.clone())
             .eth_cache(self.cache)
             .task_spawner(self.components.task_executor().clone())
             .gas_cap(self.config.rpc_gas_cap.into())
             .max_simulate_blocks(self.config.rpc_max_simulate_blocks)
             .eth_proof_window(self.config.eth_proof_window)
+            .send_raw_transaction_sync_timeout(self.config.send_raw_transaction_sync_timeout)
             .fee_history_cache_config(self.config.fee_history_cache)
             .proof_permits(self.config.proof_permits)
-            .gas_oracle_config(self.config.gas_oracle)
+            .gas_oracle_config(self.config.gas_oracle);
+        match self.tip_suggester {
+            Some(tip_suggester) => builder.tip_suggester(tip_suggester),
+            None => builder,
+        }
     }
 }
 
 /// A `EthApi` that knows how to build `eth` namespace API from [`FullNodeComponents`].
-pub trait EthApiBuilder<N: FullNodeComponents>: Default + Send + 'static {
+pub trait EthApiBuilder<N: FullNodeComponents>: Send + 'static {
     /// The Ethapi implementation this builder will build.
     type EthApi: EthApiTypes
         + FullEthApiServer<Provider = N::Provider, Pool = N::Pool>
@@ -988,6 +1145,49 @@ pub trait EthApiBuilder<N: FullNodeComponents>: Default + Send + 'static {
     ) -> impl Future<Output = eyre::Result<Self::EthApi>> + Send;
 }
 
+/// An [`EthApiBuilder`] that maps the `EthApi` built by another [`EthApiBuilder`].
+///
+/// See [`RpcAddOns::map_eth_api`].
+pub struct MapEthApiBuilder<EthB, F> {
+    /// The builder of the `EthApi` to map.
+    inner: EthB,
+    /// Maps the built `EthApi`.
+    f: F,
+}
+
+impl<EthB, F> MapEthApiBuilder<EthB, F> {
+    /// Creates a new builder that maps the `EthApi` built by `inner` with `f`.
+    pub const fn new(inner: EthB, f: F) -> Self {
+        Self { inner, f }
+    }
+}
+
+impl<EthB: Debug, F> Debug for MapEthApiBuilder<EthB, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapEthApiBuilder").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<N, EthB, F, EthApi> EthApiBuilder<N> for MapEthApiBuilder<EthB, F>
+where
+    N: FullNodeComponents,
+    EthB: EthApiBuilder<N>,
+    F: FnOnce(EthB::EthApi) -> eyre::Result<EthApi> + Send + 'static,
+    EthApi: EthApiTypes
+        + FullEthApiServer<Provider = N::Provider, Pool = N::Pool>
+        + AddDevSigners
+        + Unpin
+        + 'static,
+{
+    type EthApi = EthApi;
+
+    async fn build_eth_api(self, ctx: EthApiCtx<'_, N>) -> eyre::Result<Self::EthApi> {
+        let Self { inner, f } = self;
+        let eth_api = inner.build_eth_api(ctx).await?;
+        f(eth_api)
+    }
+}
+
 /// Helper trait that provides the validator for the engine API
 pub trait EngineValidatorAddOn<Node: FullNodeComponents>: Send {
     /// The Validator type to use for the engine API.
@@ -1073,12 +1273,35 @@ pub trait EngineApiBuilder<Node: FullNodeComponents>: Send + Sync {
 /// This provides a basic default implementation for opstack and ethereum engine API via
 /// [`EngineTypes`] and uses the general purpose [`EngineApi`] implementation as the builder's
 /// output.
-#[derive(Debug, Default)]
-pub struct BasicEngineApiBuilder<EV> {
+#[derive(Default)]
+pub struct BasicEngineApiBuilder<EV, H = ()> {
     engine_validator_builder: EV,
+    payload_attributes_hook: Option<H>,
+}
+
+impl<EV, H> BasicEngineApiBuilder<EV, H> {
+    /// Configures the [`PayloadAttributesHook`] that is invoked with the payload attributes of
+    /// forkchoice updates before they are sent to the payload builder.
+    ///
+    /// See also [`EngineApi::with_payload_attributes_hook`].
+    pub fn with_payload_attributes_hook<T>(self, hook: T) -> BasicEngineApiBuilder<EV, T> {
+        BasicEngineApiBuilder {
+            engine_validator_builder: self.engine_validator_builder,
+            payload_attributes_hook: Some(hook),
+        }
+    }
 }
 
-impl<N, EV> EngineApiBuilder<N> for BasicEngineApiBuilder<EV>
+impl<EV: Debug, H> Debug for BasicEngineApiBuilder<EV, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BasicEngineApiBuilder")
+            .field("engine_validator_builder", &self.engine_validator_builder)
+            .field("payload_attributes_hook", &self.payload_attributes_hook.is_some())
+            .finish()
+    }
+}
+
+impl<N, EV, H> EngineApiBuilder<N> for BasicEngineApiBuilder<EV, H>
 where
     N: FullNodeComponents<
         Types: NodeTypes<
@@ -1087,6 +1310,7 @@ where
         >,
     >,
     EV: EngineValidatorBuilder<N>,
+    H: PayloadAttributesHook<<<N::Types as NodeTypes>::Payload as PayloadTypes>::PayloadAttributes>,
 {
     type EngineApi = EngineApi<
         N::Provider,
@@ -1097,7 +1321,7 @@ where
     >;
 
     async fn build_engine_api(self, ctx: &AddOnsContext<'_, N>) -> eyre::Result<Self::EngineApi> {
-        let Self { engine_validator_builder } = self;
+        let Self { engine_validator_builder, payload_attributes_hook } = self;
 
         let engine_validator = engine_validator_builder.build(ctx).await?;
         let client = ClientVersionV1 {
@@ -1106,7 +1330,7 @@ where
             version: CARGO_PKG_VERSION.to_string(),
             commit: VERGEN_GIT_SHA.to_string(),
         };
-        Ok(EngineApi::new(
+        let engine_api = EngineApi::new(
             ctx.node.provider().clone(),
             ctx.config.chain.clone(),
             ctx.beacon_engine_handle.clone(),
@@ -1117,7 +1341,13 @@ where
             EngineCapabilities::default(),
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
-        ))
+        )
+        .with_consensus_clients(ctx.consensus_clients.clone());
+
+        Ok(match payload_attributes_hook {
+            Some(hook) => engine_api.with_payload_attributes_hook(hook),
+            None => engine_api,
+        })
     }
 }
 
@@ -1151,3 +1381,53 @@ impl IntoEngineApiRpcModule for NoopEngineApi {
         RpcModule::new(())
     }
 }
+
+/// Reloads the JWT secret of the auth server from the file whenever the process receives `SIGHUP`.
+///
+/// The previous secret is accepted during the grace period, so that the consensus layer client
+/// can be updated independently.
+#[cfg(unix)]
+async fn reload_jwt_secret_on_sighup(
+    secrets: reth_rpc_layer::JwtSecrets,
+    path: std::path::PathBuf,
+    grace_period: std::time::Duration,
+) {
+    use reth_tracing::tracing::warn;
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sighup = match signal(SignalKind::hangup()) {
+        Ok(sighup) => sighup,
+        Err(err) => {
+            warn!(
+                target: "reth::cli",
+                %err,
+                "Failed to listen for SIGHUP, JWT secret reloading is disabled"
+            );
+            return
+        }
+    };
+
+    while sighup.recv().await.is_some() {
+        match secrets.reload_from_file(&path, grace_period) {
+            Ok(true) => {
+                info!(
+                    target: "reth::cli",
+                    path = %path.display(),
+                    ?grace_period,
+                    "Reloaded JWT secret, the previous secret is accepted during the grace period"
+                );
+            }
+            Ok(false) => {
+                debug!(target: "reth::cli", path = %path.display(), "JWT secret is unchanged");
+            }
+            Err(err) => {
+                warn!(
+                    target: "reth::cli",
+                    %err,
+                    path = %path.display(),
+                    "Failed to reload JWT secret"
+                );
+            }
+        }
+    }
+}
@@ -108,6 +108,10 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    if let Some(stall_timeout) = stage_config.stall_detection.timeout() {
+        builder = builder.with_stall_timeout(stall_timeout)
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
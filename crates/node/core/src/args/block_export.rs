@@ -0,0 +1,94 @@
+//! clap [Args](clap::Args) for exporting canonical blocks to a message bus
+
+use clap::Args;
+
+/// The default topic or subject canonical blocks are published to.
+pub const DEFAULT_BLOCK_EXPORT_TOPIC: &str = "reth.blocks";
+
+/// Parameters for exporting canonical blocks to a message bus
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Block export")]
+pub struct BlockExportArgs {
+    /// URL of the message bus canonical blocks, receipts and state diffs are published to.
+    ///
+    /// Supported are `kafka://<brokers>` and `nats://<server>`, which require reth to be built
+    /// with the `block-export-kafka` and `block-export-nats` features respectively.
+    #[arg(long = "block-export.url", value_name = "URL")]
+    pub url: Option<String>,
+
+    /// Kafka topic or NATS subject to publish to.
+    #[arg(
+        long = "block-export.topic",
+        value_name = "TOPIC",
+        default_value = DEFAULT_BLOCK_EXPORT_TOPIC
+    )]
+    pub topic: String,
+
+    /// Kafka partition to publish to.
+    ///
+    /// Defaults to the partition that Kafka assigns to the topic name, which is used as the key of
+    /// all messages. All messages are published to the same partition to preserve their order.
+    #[arg(long = "block-export.partition", value_name = "PARTITION")]
+    pub partition: Option<i32>,
+
+    /// First block to export if nothing was exported yet.
+    ///
+    /// Defaults to the first block that is appended to the canonical chain after startup. Once
+    /// blocks were exported, the export resumes after the last exported block.
+    #[arg(long = "block-export.start-block", value_name = "BLOCK_NUMBER")]
+    pub start_block: Option<u64>,
+}
+
+impl Default for BlockExportArgs {
+    fn default() -> Self {
+        Self {
+            url: None,
+            topic: DEFAULT_BLOCK_EXPORT_TOPIC.to_string(),
+            partition: None,
+            start_block: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn block_export_args_default_sanity_check() {
+        let default_args = BlockExportArgs::default();
+        let args = CommandParser::<BlockExportArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+    }
+
+    #[test]
+    fn test_parse_block_export_args() {
+        let args = CommandParser::<BlockExportArgs>::parse_from([
+            "reth",
+            "--block-export.url",
+            "kafka://localhost:9092",
+            "--block-export.partition",
+            "3",
+            "--block-export.start-block",
+            "100",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            BlockExportArgs {
+                url: Some("kafka://localhost:9092".to_string()),
+                topic: DEFAULT_BLOCK_EXPORT_TOPIC.to_string(),
+                partition: Some(3),
+                start_block: Some(100),
+            }
+        );
+    }
+}
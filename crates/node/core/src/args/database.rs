@@ -8,7 +8,11 @@ use clap::{
     error::ErrorKind,
     Arg, Args, Command, Error,
 };
-use reth_db::{mdbx::MaxReadTransactionDuration, ClientVersion};
+use reth_config::config::{DatabaseConfig, DatabaseSyncMode};
+use reth_db::{
+    mdbx::{MaxReadTransactionDuration, SyncMode},
+    ClientVersion,
+};
 use reth_storage_errors::db::LogLevel;
 
 /// Parameters for database configuration
@@ -58,6 +62,34 @@ impl DatabaseArgs {
             .with_geometry_max_size(self.max_size)
             .with_growth_step(self.growth_step)
     }
+
+    /// Returns the database arguments with the settings of the `[db]` section of the configuration
+    /// file applied. Values passed on the command line take precedence over the configuration
+    /// file.
+    ///
+    /// Returns an error if the resulting configuration is invalid.
+    pub fn database_args_with_config(
+        &self,
+        config: &DatabaseConfig,
+    ) -> eyre::Result<reth_db::mdbx::DatabaseArguments> {
+        let config = DatabaseConfig {
+            max_size: self.max_size.or(config.max_size),
+            growth_step: self.growth_step.or(config.growth_step),
+            ..*config
+        };
+        config.validate()?;
+
+        let sync_mode = match config.sync_mode {
+            DatabaseSyncMode::Durable => SyncMode::Durable,
+            DatabaseSyncMode::NoMetaSync => SyncMode::NoMetaSync,
+            DatabaseSyncMode::SafeNoSync => SyncMode::SafeNoSync,
+        };
+
+        Ok(Self { max_size: config.max_size, growth_step: config.growth_step, ..*self }
+            .database_args()
+            .with_sync_mode(sync_mode)
+            .with_read_ahead(config.read_ahead))
+    }
 }
 
 /// clap value parser for [`LogLevel`].
@@ -191,6 +223,21 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_database_args_with_config() {
+        let config = DatabaseConfig {
+            max_size: Some(TERABYTE),
+            growth_step: Some(GIGABYTE),
+            ..Default::default()
+        };
+        let args = CommandParser::<DatabaseArgs>::parse_from(["reth"]).args;
+        assert!(args.database_args_with_config(&config).is_ok());
+
+        // command line arguments take precedence over the configuration file
+        let args = CommandParser::<DatabaseArgs>::parse_from(["reth", "--db.max-size", "1MB"]).args;
+        assert!(args.database_args_with_config(&config).is_err());
+    }
+
     #[test]
     fn test_command_parser_with_valid_max_size() {
         let cmd = CommandParser::<DatabaseArgs>::try_parse_from([
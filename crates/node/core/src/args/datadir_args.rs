@@ -20,6 +20,8 @@ pub struct DatadirArgs {
     pub datadir: MaybePlatformPath<DataDirPath>,
 
     /// The absolute path to store static files in.
+    ///
+    /// Takes precedence over `static_files.path` of the configuration file.
     #[arg(
         long = "datadir.static-files",
         alias = "datadir.static_files",
@@ -30,6 +32,12 @@ pub struct DatadirArgs {
 }
 
 impl DatadirArgs {
+    /// Falls back to the given static files path if none was passed on the command line.
+    pub fn or_static_files_path(mut self, path: Option<PathBuf>) -> Self {
+        self.static_files_path = self.static_files_path.or(path);
+        self
+    }
+
     /// Resolves the final datadir path.
     pub fn resolve_datadir(self, chain: Chain) -> ChainPath<DataDirPath> {
         let datadir = self.datadir.clone();
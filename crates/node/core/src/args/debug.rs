@@ -62,6 +62,11 @@ pub struct DebugArgs {
     #[arg(long = "debug.reorg-depth", requires = "reorg_frequency", help_heading = "Debug")]
     pub reorg_depth: Option<usize>,
 
+    /// If enabled, the node builds a payload from its own transaction pool for every new payload
+    /// it receives and logs how the local payload compares to the received one.
+    #[arg(long = "debug.self-check-payloads", help_heading = "Debug")]
+    pub self_check_payloads: bool,
+
     /// The path to store engine API messages at.
     /// If specified, all of the intercepted engine API messages
     /// will be written to specified location.
@@ -111,6 +116,7 @@ impl Default for DebugArgs {
             skip_new_payload: None,
             reorg_frequency: None,
             reorg_depth: None,
+            self_check_payloads: false,
             engine_api_store: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
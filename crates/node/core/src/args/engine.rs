@@ -2,10 +2,13 @@
 
 use clap::Args;
 use reth_engine_primitives::TreeConfig;
+use reth_primitives_traits::hash_cache::DEFAULT_ADDRESS_HASH_CACHE_SLOTS;
+use std::path::PathBuf;
 
 use crate::node_config::{
-    DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB, DEFAULT_MAX_PROOF_TASK_CONCURRENCY,
-    DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
+    DEFAULT_CANON_STATE_HISTORY, DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB,
+    DEFAULT_MAX_PROOF_TASK_CONCURRENCY, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+    DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
 };
 
 /// Parameters for configuring the engine driver.
@@ -89,6 +92,47 @@ pub struct EngineArgs {
         default_value = "false"
     )]
     pub always_process_payload_attributes_on_canonical_head: bool,
+
+    /// Configure the number of recent canonical state notifications that are kept in memory and
+    /// replayed to new subscribers, so that late subscribers don't miss chain updates.
+    ///
+    /// Set to 0 to disable.
+    #[arg(long = "engine.canon-state-history", default_value_t = DEFAULT_CANON_STATE_HISTORY)]
+    pub canon_state_history: usize,
+
+    /// Configure the maximum number of `newPayload` requests that are queued while the engine is
+    /// busy.
+    ///
+    /// Payloads that extend the canonical head, directly or through queued payloads, are processed
+    /// first. If the queue is full, the payload with the lowest priority is answered with
+    /// `SYNCING` or `ACCEPTED` without being processed.
+    ///
+    /// The queue is unbounded by default.
+    #[arg(long = "engine.max-queued-payloads")]
+    pub max_queued_payloads: Option<usize>,
+
+    /// Configure the number of queued `newPayload` requests from which on only payloads that
+    /// extend the canonical head are queued.
+    ///
+    /// Other payloads are answered with `SYNCING` or `ACCEPTED` without being processed, so that
+    /// side-chain payloads can't delay the execution of the canonical chain.
+    ///
+    /// Disabled by default.
+    #[arg(long = "engine.soft-max-queued-payloads")]
+    pub soft_max_queued_payloads: Option<usize>,
+
+    /// Write the execution witness of every canonical block into this directory, as
+    /// `<number>_<hash>.witness.json` files.
+    ///
+    /// Witnesses are recorded while the blocks are executed, which adds overhead to block
+    /// validation.
+    #[arg(long = "engine.execution-witness-dir", value_name = "DIR")]
+    pub execution_witness_dir: Option<PathBuf>,
+
+    /// Configure the number of slots of the cache of hashed addresses, which is shared by the
+    /// state root computation and the hashing stages.
+    #[arg(long = "engine.address-hash-cache-slots", default_value_t = DEFAULT_ADDRESS_HASH_CACHE_SLOTS)]
+    pub address_hash_cache_slots: usize,
 }
 
 #[allow(deprecated)]
@@ -111,6 +155,11 @@ impl Default for EngineArgs {
             precompile_cache_disabled: false,
             state_root_fallback: false,
             always_process_payload_attributes_on_canonical_head: false,
+            canon_state_history: DEFAULT_CANON_STATE_HISTORY,
+            max_queued_payloads: None,
+            soft_max_queued_payloads: None,
+            execution_witness_dir: None,
+            address_hash_cache_slots: DEFAULT_ADDRESS_HASH_CACHE_SLOTS,
         }
     }
 }
@@ -134,6 +183,9 @@ impl EngineArgs {
             .with_always_process_payload_attributes_on_canonical_head(
                 self.always_process_payload_attributes_on_canonical_head,
             )
+            .with_max_queued_payloads(self.max_queued_payloads)
+            .with_soft_max_queued_payloads(self.soft_max_queued_payloads)
+            .with_emit_execution_witnesses(self.execution_witness_dir.is_some())
     }
 }
 
@@ -1,3 +1,4 @@
+use reth_prune_types::ParseAgeError;
 use std::num::ParseIntError;
 
 /// Error while parsing a `ReceiptsLogPruneConfig`
@@ -10,7 +11,7 @@ pub(crate) enum ReceiptsLogError {
     /// Address is invalid.
     #[error("address is invalid: {0}")]
     InvalidAddress(String),
-    /// The prune mode is not one of full, distance, before.
+    /// The prune mode is not one of full, distance, before, age.
     #[error("prune mode is invalid: {0}")]
     InvalidPruneMode(String),
     /// The distance value supplied is invalid.
@@ -19,4 +20,7 @@ pub(crate) enum ReceiptsLogError {
     /// The block number supplied is invalid.
     #[error("block number is invalid: {0}")]
     InvalidBlockNumber(ParseIntError),
+    /// The age supplied is invalid.
+    #[error(transparent)]
+    InvalidAge(ParseAgeError),
 }
@@ -0,0 +1,62 @@
+//! clap [Args](clap::Args) for execution extensions
+
+use clap::Args;
+use humantime::parse_duration;
+use std::time::Duration;
+
+/// The default time to wait for execution extensions to acknowledge the shutdown.
+///
+/// Kept below the time the node waits for all graceful tasks to finish on shutdown.
+pub const DEFAULT_EXEX_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Parameters for configuring execution extensions
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "ExEx")]
+pub struct ExExArgs {
+    /// Maximum time to wait for execution extensions to acknowledge the shutdown notification
+    /// before the node shuts down.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --exex.shutdown-timeout 3s
+    #[arg(
+        long = "exex.shutdown-timeout",
+        value_parser = parse_duration,
+        default_value = "3s",
+        verbatim_doc_comment
+    )]
+    pub shutdown_timeout: Duration,
+}
+
+impl Default for ExExArgs {
+    fn default() -> Self {
+        Self { shutdown_timeout: DEFAULT_EXEX_SHUTDOWN_TIMEOUT }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn exex_args_default_sanity_check() {
+        let default_args = ExExArgs::default();
+        let args = CommandParser::<ExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+    }
+
+    #[test]
+    fn test_parse_exex_args() {
+        let args =
+            CommandParser::<ExExArgs>::parse_from(["reth", "--exex.shutdown-timeout", "500ms"])
+                .args;
+        assert_eq!(args, ExExArgs { shutdown_timeout: Duration::from_millis(500) });
+    }
+}
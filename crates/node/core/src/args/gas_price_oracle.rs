@@ -1,9 +1,9 @@
 use alloy_primitives::U256;
-use clap::Args;
-use reth_rpc_eth_types::GasPriceOracleConfig;
+use clap::{Args, ValueEnum};
+use reth_rpc_eth_types::{GasPriceOracleConfig, TipSuggestionAlgorithm};
 use reth_rpc_server_types::constants::gas_oracle::{
-    DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE, DEFAULT_IGNORE_GAS_PRICE,
-    DEFAULT_MAX_GAS_PRICE,
+    DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_EWMA_SMOOTHING, DEFAULT_GAS_PRICE_PERCENTILE,
+    DEFAULT_IGNORE_GAS_PRICE, DEFAULT_MAX_GAS_PRICE,
 };
 
 /// Parameters to configure Gas Price Oracle
@@ -25,17 +25,59 @@ pub struct GasPriceOracleArgs {
     /// The percentile of gas prices to use for the estimate
     #[arg(long = "gpo.percentile", default_value_t = DEFAULT_GAS_PRICE_PERCENTILE)]
     pub percentile: u32,
+
+    /// The algorithm used to suggest a priority fee from the sampled gas prices
+    #[arg(long = "gpo.algorithm", value_enum, default_value_t = GasPriceOracleAlgorithm::Percentile)]
+    pub algorithm: GasPriceOracleAlgorithm,
+
+    /// The weight of the newest block in percent, used by the `ewma` algorithm
+    #[arg(long = "gpo.ewma-smoothing", default_value_t = DEFAULT_GAS_PRICE_EWMA_SMOOTHING)]
+    pub ewma_smoothing: u32,
+
+    /// Minimum priority fee to be recommended by gpo, used by the `minimum-floor` algorithm
+    #[arg(long = "gpo.minprice", default_value_t = 0)]
+    pub min_price: u64,
+}
+
+/// The algorithm used by the gas price oracle to suggest a priority fee.
+#[derive(Debug, Clone, Copy, Default, ValueEnum, PartialEq, Eq)]
+pub enum GasPriceOracleAlgorithm {
+    /// The configured percentile of the sampled gas prices
+    #[default]
+    Percentile,
+    /// An exponentially weighted moving average of the configured percentile of each block
+    Ewma,
+    /// The configured percentile of the sampled gas prices, but at least the minimum price
+    MinimumFloor,
 }
 
 impl GasPriceOracleArgs {
     /// Returns a [`GasPriceOracleConfig`] from the arguments.
     pub fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
-        let Self { blocks, ignore_price, max_price, percentile } = self;
+        let Self {
+            blocks,
+            ignore_price,
+            max_price,
+            percentile,
+            algorithm,
+            ewma_smoothing,
+            min_price,
+        } = self;
+        let algorithm = match algorithm {
+            GasPriceOracleAlgorithm::Percentile => TipSuggestionAlgorithm::Percentile,
+            GasPriceOracleAlgorithm::Ewma => {
+                TipSuggestionAlgorithm::Ewma { smoothing: *ewma_smoothing }
+            }
+            GasPriceOracleAlgorithm::MinimumFloor => {
+                TipSuggestionAlgorithm::MinimumFloor { floor: U256::from(*min_price) }
+            }
+        };
         GasPriceOracleConfig {
             max_price: Some(U256::from(*max_price)),
             ignore_price: Some(U256::from(*ignore_price)),
             percentile: *percentile,
             blocks: *blocks,
+            algorithm,
             ..Default::default()
         }
     }
@@ -48,6 +90,9 @@ impl Default for GasPriceOracleArgs {
             ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
             max_price: DEFAULT_MAX_GAS_PRICE.to(),
             percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+            algorithm: GasPriceOracleAlgorithm::Percentile,
+            ewma_smoothing: DEFAULT_GAS_PRICE_EWMA_SMOOTHING,
+            min_price: 0,
         }
     }
 }
@@ -73,10 +118,29 @@ mod tests {
                 ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
                 max_price: DEFAULT_MAX_GAS_PRICE.to(),
                 percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+                algorithm: GasPriceOracleAlgorithm::Percentile,
+                ewma_smoothing: DEFAULT_GAS_PRICE_EWMA_SMOOTHING,
+                min_price: 0,
             }
         );
     }
 
+    #[test]
+    fn test_parse_gpo_algorithm() {
+        let args = CommandParser::<GasPriceOracleArgs>::parse_from([
+            "reth",
+            "--gpo.algorithm",
+            "minimum-floor",
+            "--gpo.minprice",
+            "1000",
+        ])
+        .args;
+        assert_eq!(
+            args.gas_price_oracle_config().algorithm,
+            TipSuggestionAlgorithm::MinimumFloor { floor: U256::from(1000) }
+        );
+    }
+
     #[test]
     fn gpo_args_default_sanity_test() {
         let default_args = GasPriceOracleArgs::default();
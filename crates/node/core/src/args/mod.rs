@@ -34,7 +34,7 @@ pub use stage::StageEnum;
 
 /// Gas price oracle related arguments
 mod gas_price_oracle;
-pub use gas_price_oracle::GasPriceOracleArgs;
+pub use gas_price_oracle::{GasPriceOracleAlgorithm, GasPriceOracleArgs};
 
 /// TxPoolArgs for configuring the transaction pool
 mod txpool;
@@ -68,5 +68,13 @@ pub use ress_args::RessArgs;
 mod era;
 pub use era::{DefaultEraHost, EraArgs, EraSourceArgs};
 
+/// `ExExArgs` for configuring execution extensions.
+mod exex;
+pub use exex::{ExExArgs, DEFAULT_EXEX_SHUTDOWN_TIMEOUT};
+
+/// `BlockExportArgs` for exporting canonical blocks to a message bus.
+mod block_export;
+pub use block_export::{BlockExportArgs, DEFAULT_BLOCK_EXPORT_TOPIC};
+
 mod error;
 pub mod types;
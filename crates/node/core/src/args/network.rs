@@ -2,11 +2,14 @@
 
 use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    num::NonZeroU64,
     ops::Not,
     path::PathBuf,
+    time::Duration,
 };
 
 use clap::Args;
+use humantime::parse_duration;
 use reth_chainspec::EthChainSpec;
 use reth_config::Config;
 use reth_discv4::{NodeRecord, DEFAULT_DISCOVERY_ADDR, DEFAULT_DISCOVERY_PORT};
@@ -24,16 +27,18 @@ use reth_network::{
                 DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             },
             tx_manager::{
-                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+                DEFAULT_ANNOUNCEMENT_MAX_DELAY, DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
+                DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             },
         },
-        TransactionFetcherConfig, TransactionsManagerConfig,
+        AnnouncementBatchConfig, TransactionFetcherConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
     HelloMessageWithProtocols, NetworkConfigBuilder, NetworkPrimitives, SessionsConfig,
 };
 use reth_network_peers::{mainnet_nodes, TrustedPeer};
+use reth_transaction_pool::validate::ValidationBudgetConfig;
 use secp256k1::SecretKey;
 use tracing::error;
 
@@ -73,6 +78,12 @@ pub struct NetworkArgs {
     #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
     pub peers_file: Option<PathBuf>,
 
+    /// The path to the banned peers file. Banned peers and ips are written to this file whenever
+    /// they change and on node shutdown, and read on startup. Cannot be used with
+    /// `--no-persist-peers`.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
+    pub banned_peers_file: Option<PathBuf>,
+
     /// Custom node identity
     #[arg(long, value_name = "IDENTITY", default_value = P2P_CLIENT_VERSION)]
     pub identity: String,
@@ -161,6 +172,36 @@ pub struct NetworkArgs {
     /// The policy determines which peers transactions are gossiped to.
     #[arg(long = "tx-propagation-policy", default_value_t = TransactionPropagationKind::All)]
     pub tx_propagation_policy: TransactionPropagationKind,
+
+    /// Validation cost budget per second for transactions received from a single peer.
+    ///
+    /// Transactions exceeding the budget are dropped before they are validated. Disabled by
+    /// default, 0 disables the budget.
+    #[arg(long = "tx-validation-budget-per-peer", value_name = "COST", default_value_t = 0)]
+    pub tx_validation_budget_per_peer: u64,
+
+    /// Max delay of transaction hash announcements.
+    ///
+    /// Hashes of new pending transactions are batched per peer, and announced after a fraction of
+    /// the peer's round trip time that grows with the rate of new pending transactions, bounded by
+    /// this delay. 0 disables batching.
+    #[arg(long = "tx-announcement-max-delay", value_name = "DURATION", value_parser = parse_duration, default_value = "100ms")]
+    pub tx_announcement_max_delay: Duration,
+
+    /// Disconnect peers that deliver transactions with a different type or size than they
+    /// announced.
+    ///
+    /// Such peers are always penalized.
+    #[arg(long = "tx-announcement-mismatch-disconnect")]
+    pub tx_announcement_mismatch_disconnect: bool,
+
+    /// Maximum number of bytes per second sent to all peers combined.
+    ///
+    /// The limit is shared by block propagation, transaction gossip and serving historical data,
+    /// weighted in that order. It can be changed at runtime with `admin_setOutboundBandwidth`.
+    /// Unlimited by default.
+    #[arg(long = "max-outbound-bandwidth", value_name = "BYTES_PER_SEC")]
+    pub max_outbound_bandwidth: Option<NonZeroU64>,
 }
 
 impl NetworkArgs {
@@ -203,6 +244,12 @@ impl NetworkArgs {
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
             propagation_mode: Default::default(),
+            peer_validation_budget: (self.tx_validation_budget_per_peer > 0)
+                .then(|| ValidationBudgetConfig::per_second(self.tx_validation_budget_per_peer)),
+            announcement_batching: self.tx_announcement_max_delay.is_zero().not().then(|| {
+                AnnouncementBatchConfig::default().with_max_delay(self.tx_announcement_max_delay)
+            }),
+            disconnect_on_announcement_mismatch: self.tx_announcement_mismatch_disconnect,
         }
     }
 
@@ -244,7 +291,14 @@ impl NetworkArgs {
             ))
             .external_ip_resolver(self.nat)
             .sessions_config(
-                SessionsConfig::default().with_upscaled_event_buffer(peers_config.max_peers()),
+                SessionsConfig::default()
+                    .with_upscaled_event_buffer(peers_config.max_peers())
+                    .with_outbound_bandwidth(
+                        config.sessions.outbound_bandwidth.with_max_bytes_per_sec(
+                            self.max_outbound_bandwidth
+                                .or(config.sessions.outbound_bandwidth.max_bytes_per_sec),
+                        ),
+                    ),
             )
             .peer_config(peers_config)
             .boot_nodes(chain_bootnodes.clone())
@@ -279,6 +333,17 @@ impl NetworkArgs {
         self.no_persist_peers.not().then_some(peers_file)
     }
 
+    /// If `no_persist_peers` is false then this returns the path to the persistent banned peers
+    /// file, using `default_banned_peers_file` if no `banned_peers_file` is provided.
+    pub fn persistent_banned_peers_file(
+        &self,
+        default_banned_peers_file: PathBuf,
+    ) -> Option<PathBuf> {
+        self.no_persist_peers
+            .not()
+            .then(|| self.banned_peers_file.clone().unwrap_or(default_banned_peers_file))
+    }
+
     /// Sets the p2p port to zero, to allow the OS to assign a random unused port when
     /// the network components bind to a socket.
     pub const fn with_unused_p2p_port(mut self) -> Self {
@@ -325,6 +390,7 @@ impl Default for NetworkArgs {
             bootnodes: None,
             dns_retries: 0,
             peers_file: None,
+            banned_peers_file: None,
             identity: P2P_CLIENT_VERSION.to_string(),
             p2p_secret_key: None,
             no_persist_peers: false,
@@ -342,7 +408,11 @@ impl Default for NetworkArgs {
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
             net_if: None,
-            tx_propagation_policy: TransactionPropagationKind::default()
+            tx_propagation_policy: TransactionPropagationKind::default(),
+            tx_validation_budget_per_peer: 0,
+            tx_announcement_max_delay: DEFAULT_ANNOUNCEMENT_MAX_DELAY,
+            tx_announcement_mismatch_disconnect: false,
+            max_outbound_bandwidth: None,
         }
     }
 }
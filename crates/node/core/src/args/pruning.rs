@@ -5,7 +5,9 @@ use alloy_primitives::{Address, BlockNumber};
 use clap::{builder::RangedU64ValueParser, Args};
 use reth_chainspec::EthereumHardforks;
 use reth_config::config::PruneConfig;
-use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig, MINIMUM_PRUNING_DISTANCE};
+use reth_prune_types::{
+    parse_age, PruneMode, PruneModes, ReceiptsLogPruneConfig, MINIMUM_PRUNING_DISTANCE,
+};
 use std::collections::BTreeMap;
 
 /// Parameters for pruning and full node
@@ -22,87 +24,111 @@ pub struct PruningArgs {
 
     // Sender Recovery
     /// Prunes all sender recovery data.
-    #[arg(long = "prune.senderrecovery.full", conflicts_with_all = &["sender_recovery_distance", "sender_recovery_before"])]
+    #[arg(long = "prune.senderrecovery.full", conflicts_with_all = &["sender_recovery_distance", "sender_recovery_before", "sender_recovery_age"])]
     pub sender_recovery_full: bool,
     /// Prune sender recovery data before the `head-N` block number. In other words, keep last N +
     /// 1 blocks.
-    #[arg(long = "prune.senderrecovery.distance", value_name = "BLOCKS", conflicts_with_all = &["sender_recovery_full", "sender_recovery_before"])]
+    #[arg(long = "prune.senderrecovery.distance", value_name = "BLOCKS", conflicts_with_all = &["sender_recovery_full", "sender_recovery_before", "sender_recovery_age"])]
     pub sender_recovery_distance: Option<u64>,
     /// Prune sender recovery data before the specified block number. The specified block number is
     /// not pruned.
-    #[arg(long = "prune.senderrecovery.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance"])]
+    #[arg(long = "prune.senderrecovery.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance", "sender_recovery_age"])]
     pub sender_recovery_before: Option<BlockNumber>,
+    /// Prune sender recovery data of blocks older than the given age, e.g. `30d` or `1w12h`. The
+    /// age is translated to a block number using the block timestamps when pruning.
+    #[arg(long = "prune.senderrecovery.age", value_name = "DURATION", value_parser = parse_age, conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance", "sender_recovery_before"])]
+    pub sender_recovery_age: Option<u64>,
 
     // Transaction Lookup
     /// Prunes all transaction lookup data.
-    #[arg(long = "prune.transactionlookup.full", conflicts_with_all = &["transaction_lookup_distance", "transaction_lookup_before"])]
+    #[arg(long = "prune.transactionlookup.full", conflicts_with_all = &["transaction_lookup_distance", "transaction_lookup_before", "transaction_lookup_age"])]
     pub transaction_lookup_full: bool,
     /// Prune transaction lookup data before the `head-N` block number. In other words, keep last N
     /// + 1 blocks.
-    #[arg(long = "prune.transactionlookup.distance", value_name = "BLOCKS", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_before"])]
+    #[arg(long = "prune.transactionlookup.distance", value_name = "BLOCKS", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_before", "transaction_lookup_age"])]
     pub transaction_lookup_distance: Option<u64>,
     /// Prune transaction lookup data before the specified block number. The specified block number
     /// is not pruned.
-    #[arg(long = "prune.transactionlookup.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance"])]
+    #[arg(long = "prune.transactionlookup.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance", "transaction_lookup_age"])]
     pub transaction_lookup_before: Option<BlockNumber>,
+    /// Prune transaction lookup data of blocks older than the given age, e.g. `30d` or `1w12h`.
+    /// The age is translated to a block number using the block timestamps when pruning.
+    #[arg(long = "prune.transactionlookup.age", value_name = "DURATION", value_parser = parse_age, conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance", "transaction_lookup_before"])]
+    pub transaction_lookup_age: Option<u64>,
 
     // Receipts
     /// Prunes all receipt data.
-    #[arg(long = "prune.receipts.full", conflicts_with_all = &["receipts_pre_merge", "receipts_distance", "receipts_before"])]
+    #[arg(long = "prune.receipts.full", conflicts_with_all = &["receipts_pre_merge", "receipts_distance", "receipts_before", "receipts_age"])]
     pub receipts_full: bool,
     /// Prune receipts before the merge block.
-    #[arg(long = "prune.receipts.pre-merge", conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_before"])]
+    #[arg(long = "prune.receipts.pre-merge", conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_before", "receipts_age"])]
     pub receipts_pre_merge: bool,
     /// Prune receipts before the `head-N` block number. In other words, keep last N + 1 blocks.
-    #[arg(long = "prune.receipts.distance", value_name = "BLOCKS", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_before"])]
+    #[arg(long = "prune.receipts.distance", value_name = "BLOCKS", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_before", "receipts_age"])]
     pub receipts_distance: Option<u64>,
     /// Prune receipts before the specified block number. The specified block number is not pruned.
-    #[arg(long = "prune.receipts.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance"])]
+    #[arg(long = "prune.receipts.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance", "receipts_age"])]
     pub receipts_before: Option<BlockNumber>,
+    /// Prune receipts of blocks older than the given age, e.g. `30d` or `1w12h`. The age is
+    /// translated to a block number using the block timestamps when pruning.
+    #[arg(long = "prune.receipts.age", value_name = "DURATION", value_parser = parse_age, conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance", "receipts_before"])]
+    pub receipts_age: Option<u64>,
     // Receipts Log Filter
     /// Configure receipts log filter. Format:
     /// <`address`>:<`prune_mode`>[,<`address`>:<`prune_mode`>...] Where <`prune_mode`> can be
-    /// 'full', 'distance:<`blocks`>', or 'before:<`block_number`>'
-    #[arg(long = "prune.receiptslogfilter", value_name = "FILTER_CONFIG", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance",  "receipts_before"], value_parser = parse_receipts_log_filter)]
+    /// 'full', 'distance:<`blocks`>', 'before:<`block_number`>', or 'age:<`duration`>'
+    #[arg(long = "prune.receiptslogfilter", value_name = "FILTER_CONFIG", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance", "receipts_before", "receipts_age"], value_parser = parse_receipts_log_filter)]
     pub receipts_log_filter: Option<ReceiptsLogPruneConfig>,
 
     // Account History
     /// Prunes all account history.
-    #[arg(long = "prune.accounthistory.full", conflicts_with_all = &["account_history_distance", "account_history_before"])]
+    #[arg(long = "prune.accounthistory.full", conflicts_with_all = &["account_history_distance", "account_history_before", "account_history_age"])]
     pub account_history_full: bool,
     /// Prune account before the `head-N` block number. In other words, keep last N + 1 blocks.
-    #[arg(long = "prune.accounthistory.distance", value_name = "BLOCKS", conflicts_with_all = &["account_history_full", "account_history_before"])]
+    #[arg(long = "prune.accounthistory.distance", value_name = "BLOCKS", conflicts_with_all = &["account_history_full", "account_history_before", "account_history_age"])]
     pub account_history_distance: Option<u64>,
     /// Prune account history before the specified block number. The specified block number is not
     /// pruned.
-    #[arg(long = "prune.accounthistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["account_history_full", "account_history_distance"])]
+    #[arg(long = "prune.accounthistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["account_history_full", "account_history_distance", "account_history_age"])]
     pub account_history_before: Option<BlockNumber>,
+    /// Prune account history of blocks older than the given age, e.g. `30d` or `1w12h`. The age is
+    /// translated to a block number using the block timestamps when pruning.
+    #[arg(long = "prune.accounthistory.age", value_name = "DURATION", value_parser = parse_age, conflicts_with_all = &["account_history_full", "account_history_distance", "account_history_before"])]
+    pub account_history_age: Option<u64>,
 
     // Storage History
     /// Prunes all storage history data.
-    #[arg(long = "prune.storagehistory.full", conflicts_with_all = &["storage_history_distance", "storage_history_before"])]
+    #[arg(long = "prune.storagehistory.full", conflicts_with_all = &["storage_history_distance", "storage_history_before", "storage_history_age"])]
     pub storage_history_full: bool,
     /// Prune storage history before the `head-N` block number. In other words, keep last N + 1
     /// blocks.
-    #[arg(long = "prune.storagehistory.distance", value_name = "BLOCKS", conflicts_with_all = &["storage_history_full", "storage_history_before"])]
+    #[arg(long = "prune.storagehistory.distance", value_name = "BLOCKS", conflicts_with_all = &["storage_history_full", "storage_history_before", "storage_history_age"])]
     pub storage_history_distance: Option<u64>,
     /// Prune storage history before the specified block number. The specified block number is not
     /// pruned.
-    #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
+    #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance", "storage_history_age"])]
     pub storage_history_before: Option<BlockNumber>,
+    /// Prune storage history of blocks older than the given age, e.g. `30d` or `1w12h`. The age is
+    /// translated to a block number using the block timestamps when pruning.
+    #[arg(long = "prune.storagehistory.age", value_name = "DURATION", value_parser = parse_age, conflicts_with_all = &["storage_history_full", "storage_history_distance", "storage_history_before"])]
+    pub storage_history_age: Option<u64>,
 
     // Bodies
     /// Prune bodies before the merge block.
-    #[arg(long = "prune.bodies.pre-merge", value_name = "BLOCKS", conflicts_with_all = &["bodies_distance", "bodies_before"])]
+    #[arg(long = "prune.bodies.pre-merge", value_name = "BLOCKS", conflicts_with_all = &["bodies_distance", "bodies_before", "bodies_age"])]
     pub bodies_pre_merge: bool,
     /// Prune bodies before the `head-N` block number. In other words, keep last N + 1
     /// blocks.
-    #[arg(long = "prune.bodies.distance", value_name = "BLOCKS", conflicts_with_all = &["bodies_pre_merge", "bodies_before"])]
+    #[arg(long = "prune.bodies.distance", value_name = "BLOCKS", conflicts_with_all = &["bodies_pre_merge", "bodies_before", "bodies_age"])]
     pub bodies_distance: Option<u64>,
     /// Prune storage history before the specified block number. The specified block number is not
     /// pruned.
-    #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge"])]
+    #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge", "bodies_age"])]
     pub bodies_before: Option<BlockNumber>,
+    /// Prune bodies of blocks older than the given age, e.g. `30d` or `1w12h`. The age is
+    /// translated to a block number using the block timestamps when pruning.
+    #[arg(long = "prune.bodies.age", value_name = "DURATION", value_parser = parse_age, conflicts_with_all = &["bodies_distance", "bodies_pre_merge", "bodies_before"])]
+    pub bodies_age: Option<u64>,
 }
 
 impl PruningArgs {
@@ -176,8 +202,10 @@ impl PruningArgs {
                 .map(PruneMode::Before)
         } else if let Some(distance) = self.bodies_distance {
             Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.bodies_before {
+            Some(PruneMode::Before(block_number))
         } else {
-            self.bodies_before.map(PruneMode::Before)
+            self.bodies_age.map(PruneMode::Age)
         }
     }
 
@@ -188,6 +216,8 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.sender_recovery_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.sender_recovery_age {
+            Some(PruneMode::Age(age))
         } else {
             None
         }
@@ -200,6 +230,8 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.transaction_lookup_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.transaction_lookup_age {
+            Some(PruneMode::Age(age))
         } else {
             None
         }
@@ -218,8 +250,10 @@ impl PruningArgs {
             Some(PruneMode::Full)
         } else if let Some(distance) = self.receipts_distance {
             Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.receipts_before {
+            Some(PruneMode::Before(block_number))
         } else {
-            self.receipts_before.map(PruneMode::Before)
+            self.receipts_age.map(PruneMode::Age)
         }
     }
 
@@ -230,6 +264,8 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.account_history_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.account_history_age {
+            Some(PruneMode::Age(age))
         } else {
             None
         }
@@ -242,6 +278,8 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.storage_history_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.storage_history_age {
+            Some(PruneMode::Age(age))
         } else {
             None
         }
@@ -284,6 +322,12 @@ pub(crate) fn parse_receipts_log_filter(
                     parts[2].parse::<u64>().map_err(ReceiptsLogError::InvalidBlockNumber)?;
                 PruneMode::Before(block_number)
             }
+            s if s.starts_with("age") => {
+                if parts.len() < 3 {
+                    return Err(ReceiptsLogError::InvalidFilterFormat(filter.to_string()));
+                }
+                PruneMode::Age(parse_age(parts[2]).map_err(ReceiptsLogError::InvalidAge)?)
+            }
             _ => return Err(ReceiptsLogError::InvalidPruneMode(parts[1].to_string())),
         };
         config.insert(address, prune_mode);
@@ -332,13 +376,14 @@ mod tests {
         let filter1 = "0x0000000000000000000000000000000000000001:full";
         let filter2 = "0x0000000000000000000000000000000000000002:distance:1000";
         let filter3 = "0x0000000000000000000000000000000000000003:before:5000000";
-        let filters = [filter1, filter2, filter3].join(",");
+        let filter4 = "0x0000000000000000000000000000000000000004:age:30d";
+        let filters = [filter1, filter2, filter3, filter4].join(",");
 
         // Args can be parsed.
         let result = parse_receipts_log_filter(&filters);
         assert!(result.is_ok());
         let config = result.unwrap();
-        assert_eq!(config.0.len(), 3);
+        assert_eq!(config.0.len(), 4);
 
         // Check that the args were parsed correctly.
         let addr1: Address = "0x0000000000000000000000000000000000000001".parse().unwrap();
@@ -348,6 +393,40 @@ mod tests {
         assert_eq!(config.0.get(&addr1), Some(&PruneMode::Full));
         assert_eq!(config.0.get(&addr2), Some(&PruneMode::Distance(1000)));
         assert_eq!(config.0.get(&addr3), Some(&PruneMode::Before(5000000)));
+        assert_eq!(
+            config.0.get(&address!("0x0000000000000000000000000000000000000004")),
+            Some(&PruneMode::Age(30 * 24 * 60 * 60))
+        );
+    }
+
+    #[test]
+    fn parse_prune_age() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--prune.receipts.age",
+            "30d",
+            "--prune.accounthistory.age",
+            "1w12h",
+        ])
+        .args;
+        let config = args.prune_config(reth_chainspec::MAINNET.as_ref()).unwrap();
+        assert_eq!(config.segments.receipts, Some(PruneMode::Age(30 * 24 * 60 * 60)));
+        assert_eq!(config.segments.account_history, Some(PruneMode::Age((7 * 24 + 12) * 60 * 60)));
+
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--prune.receipts.age",
+            "30d",
+            "--prune.receipts.distance",
+            "100000",
+        ])
+        .is_err());
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--prune.receipts.age",
+            "30 days"
+        ])
+        .is_err());
     }
 
     #[test]
@@ -377,6 +456,13 @@ mod tests {
         assert!(matches!(result, Err(ReceiptsLogError::InvalidDistance(_))));
     }
 
+    #[test]
+    fn test_parse_receipts_log_filter_invalid_age() {
+        let result =
+            parse_receipts_log_filter("0x0000000000000000000000000000000000000000:age:30x");
+        assert!(matches!(result, Err(ReceiptsLogError::InvalidAge(_))));
+    }
+
     #[test]
     fn test_parse_receipts_log_filter_invalid_block_number() {
         let result = parse_receipts_log_filter(
@@ -5,6 +5,7 @@ use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    time::Duration,
 };
 
 use alloy_primitives::Address;
@@ -13,6 +14,7 @@ use clap::{
     builder::{PossibleValue, RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
+use humantime::parse_duration;
 use rand::Rng;
 use reth_cli_util::parse_ether_value;
 use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
@@ -27,6 +29,9 @@ use super::types::MaxOr;
 /// Default max number of subscriptions per connection.
 pub(crate) const RPC_DEFAULT_MAX_SUBS_PER_CONN: u32 = 1024;
 
+/// Default max number of buffered messages per connection.
+pub(crate) const RPC_DEFAULT_MAX_BUFFERED_NOTIFICATIONS: u32 = 1024;
+
 /// Default max request size in MB.
 pub(crate) const RPC_DEFAULT_MAX_REQUEST_SIZE_MB: u32 = 15;
 
@@ -38,6 +43,9 @@ pub(crate) const RPC_DEFAULT_MAX_RESPONSE_SIZE_MB: u32 = 160;
 /// Default number of incoming connections.
 pub(crate) const RPC_DEFAULT_MAX_CONNECTIONS: u32 = 500;
 
+/// Default duration for which the previous JWT secret of the auth server is accepted.
+pub(crate) const AUTH_DEFAULT_JWT_SECRET_GRACE_PERIOD: Duration = Duration::from_secs(10 * 60);
+
 /// Parameters for configuring the rpc more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "RPC")]
@@ -63,9 +71,17 @@ pub struct RpcServerArgs {
     pub http_api: Option<RpcModuleSelection>,
 
     /// Http Corsdomain to allow request from
+    ///
+    /// Subdomains can be matched with a leading wildcard, e.g. `https://*.example.com`.
     #[arg(long = "http.corsdomain")]
     pub http_corsdomain: Option<String>,
 
+    /// Http Corsdomain to allow requests to the admin, debug and miner modules from
+    ///
+    /// If set, requests from the `--http.corsdomain` origins can't call these modules.
+    #[arg(long = "http.corsdomain.admin")]
+    pub http_corsdomain_admin: Option<String>,
+
     /// Enable the WS-RPC server
     #[arg(long)]
     pub ws: bool,
@@ -79,13 +95,74 @@ pub struct RpcServerArgs {
     pub ws_port: u16,
 
     /// Origins from which to accept `WebSocket` requests
+    ///
+    /// Subdomains can be matched with a leading wildcard, e.g. `https://*.example.com`.
     #[arg(id = "ws.origins", long = "ws.origins", alias = "ws.corsdomain")]
     pub ws_allowed_origins: Option<String>,
 
+    /// Origins from which to accept `WebSocket` requests to the admin, debug and miner modules
+    ///
+    /// If set, requests from the `--ws.origins` origins can't call these modules.
+    #[arg(id = "ws.origins.admin", long = "ws.origins.admin", alias = "ws.corsdomain.admin")]
+    pub ws_allowed_origins_admin: Option<String>,
+
     /// Rpc Modules to be configured for the WS server
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Interval of the pings sent to WS clients to keep idle connections alive, e.g. behind load
+    /// balancers with an idle timeout
+    ///
+    /// Pings are disabled if not set.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --ws.ping-interval 30s
+    #[arg(
+        long = "ws.ping-interval",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub ws_ping_interval: Option<Duration>,
+
+    /// Time after which a WS connection that did not respond to a ping is closed
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --ws.idle-timeout 1m
+    #[arg(
+        long = "ws.idle-timeout",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        requires = "ws_ping_interval",
+        verbatim_doc_comment
+    )]
+    pub ws_idle_timeout: Option<Duration>,
+
+    /// Maximum lifetime of a WS connection, after which it is closed
+    ///
+    /// If the WS server shares its port with the HTTP server, this applies to the HTTP
+    /// connections as well.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --ws.max-lifetime 24h
+    #[arg(
+        long = "ws.max-lifetime",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub ws_max_lifetime: Option<Duration>,
+
+    /// Path to a PEM encoded certificate chain to serve the HTTP and WS servers over TLS.
+    ///
+    /// The certificate is reloaded when the certificate or key file changes.
+    #[arg(long = "rpc.tls-cert", value_name = "PATH", requires = "rpc_tls_key")]
+    pub rpc_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM encoded private key of the HTTP and WS server certificate.
+    #[arg(long = "rpc.tls-key", value_name = "PATH", requires = "rpc_tls_cert")]
+    pub rpc_tls_key: Option<PathBuf>,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
@@ -117,6 +194,40 @@ pub struct RpcServerArgs {
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false)]
     pub auth_jwtsecret: Option<PathBuf>,
 
+    /// Path to a secondary JWT secret for the authenticated engine-API RPC server.
+    ///
+    /// Tokens signed with this secret are accepted during the grace period, see
+    /// `--authrpc.jwtsecret.grace-period`. This allows rotating the JWT secret without updating
+    /// the consensus layer client at the same time.
+    #[arg(long = "authrpc.jwtsecret.secondary", value_name = "PATH", required = false)]
+    pub auth_jwtsecret_secondary: Option<PathBuf>,
+
+    /// How long the previous JWT secret of the authenticated engine-API RPC server is accepted.
+    ///
+    /// This applies to the secondary secret, and to the previous secret after the JWT secret file
+    /// was reloaded on `SIGHUP`.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --authrpc.jwtsecret.grace-period 10m
+    #[arg(
+        long = "authrpc.jwtsecret.grace-period",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "10m",
+        verbatim_doc_comment
+    )]
+    pub auth_jwtsecret_grace_period: Duration,
+
+    /// Path to a PEM encoded certificate chain to serve the auth server over TLS.
+    ///
+    /// The certificate is reloaded when the certificate or key file changes.
+    #[arg(long = "authrpc.tls-cert", value_name = "PATH", requires = "auth_tls_key")]
+    pub auth_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM encoded private key of the auth server certificate.
+    #[arg(long = "authrpc.tls-key", value_name = "PATH", requires = "auth_tls_cert")]
+    pub auth_tls_key: Option<PathBuf>,
+
     /// Enable auth engine API over IPC
     #[arg(long)]
     pub auth_ipc: bool,
@@ -152,10 +263,48 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-subscriptions-per-connection", alias = "rpc-max-subscriptions-per-connection", default_value_t = RPC_DEFAULT_MAX_SUBS_PER_CONN.into())]
     pub rpc_max_subscriptions_per_connection: MaxU32,
 
+    /// Set the maximum number of messages buffered per connection, including subscription
+    /// notifications.
+    ///
+    /// Subscriptions wait for the client to catch up if the buffer of their connection is full,
+    /// unless `--rpc.close-slow-subscriptions` is set.
+    #[arg(long = "rpc.max-buffered-notifications", value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_BUFFERED_NOTIFICATIONS.into())]
+    pub rpc_max_buffered_notifications: MaxU32,
+
+    /// Close subscriptions if the buffer of their connection is full, instead of waiting for the
+    /// client to catch up.
+    ///
+    /// The client is notified about the reason before the subscription is closed.
+    #[arg(long = "rpc.close-slow-subscriptions")]
+    pub rpc_close_slow_subscriptions: bool,
+
+    /// Maximum number of subscription notification bytes per second sent to a single connection.
+    /// (0 = no limit)
+    ///
+    /// Subscriptions that would exceed the limit of their connection are closed, and the client is
+    /// notified about the reason.
+    #[arg(long = "rpc.max-subscription-bytes-per-sec", value_name = "BYTES", default_value_t = ZeroAsNoneU64(None))]
+    pub rpc_max_subscription_bytes_per_sec: ZeroAsNoneU64,
+
+    /// Maximum number of transactions buffered for a `newPendingTransactions` subscription with
+    /// full transaction objects.
+    ///
+    /// If the subscriber can't keep up, the oldest buffered transactions are dropped and the
+    /// subscriber receives an error notification with the number of dropped transactions.
+    #[arg(long = "rpc.pending-tx-subscription-buffer", value_name = "COUNT", default_value_t = constants::DEFAULT_PENDING_TX_SUBSCRIPTION_BUFFER)]
+    pub rpc_pending_tx_subscription_buffer: usize,
+
     /// Maximum number of RPC server connections.
     #[arg(long = "rpc.max-connections", alias = "rpc-max-connections", value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONNECTIONS.into())]
     pub rpc_max_connections: MaxU32,
 
+    /// Request header whose value labels the RPC call metrics by client, e.g. a header set by a
+    /// proxy in front of the node.
+    ///
+    /// Every distinct header value creates new metric series.
+    #[arg(long = "rpc.metrics-client-header", value_name = "HEADER")]
+    pub rpc_metrics_client_header: Option<String>,
+
     /// Maximum number of concurrent tracing requests.
     ///
     /// By default this chooses a sensible value based on the number of available cores.
@@ -165,6 +314,62 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-tracing-requests", alias = "rpc-max-tracing-requests", value_name = "COUNT", default_value_t = constants::default_max_tracing_requests())]
     pub rpc_max_tracing_requests: usize,
 
+    /// Maximum number of concurrently executing `debug_` and `trace_` calls over HTTP and WS.
+    /// (0 = unlimited)
+    ///
+    /// Calls exceeding the limit are queued and served in turns across connections, so a single
+    /// client can't monopolize the tracing capacity.
+    #[arg(long = "rpc.max-trace-calls", value_name = "COUNT", default_value_t = 0)]
+    pub rpc_max_trace_calls: usize,
+
+    /// Maximum number of queued `debug_` and `trace_` calls when `--rpc.max-trace-calls` is
+    /// reached. Calls exceeding the queue are rejected.
+    #[arg(long = "rpc.max-queued-trace-calls", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_QUEUED_TRACE_CALLS)]
+    pub rpc_max_queued_trace_calls: usize,
+
+    /// Reject `eth_` calls with malformed parameters over HTTP and WS, e.g. quantities with
+    /// leading zeros, data with an odd number of hex digits or invalid block tags.
+    ///
+    /// The error message contains the path of the offending field. By default, parameters are
+    /// parsed leniently.
+    #[arg(long = "rpc.strict-validation", default_value_t = false)]
+    pub rpc_strict_validation: bool,
+
+    /// Maximum cost of a single batch request over HTTP and WS. (0 = no limit)
+    ///
+    /// The cost of a batch is the sum of the weights of its calls, see `--rpc.method-weights`.
+    /// Batches exceeding the limit are rejected with an error for each call.
+    #[arg(long = "rpc.max-batch-cost", value_name = "COST", default_value_t = ZeroAsNoneU64(None))]
+    pub rpc_max_batch_cost: ZeroAsNoneU64,
+
+    /// Maximum cost of all calls and batches of a single connection per second over HTTP and WS.
+    /// (0 = no limit)
+    ///
+    /// The cost of a call is the weight of its method, see `--rpc.method-weights`. Calls and
+    /// batches exceeding the remaining budget of the connection are rejected. HTTP requests share
+    /// the budget of their keep-alive connection.
+    #[arg(long = "rpc.max-connection-cost", value_name = "COST", default_value_t = ZeroAsNoneU64(None))]
+    pub rpc_max_connection_cost: ZeroAsNoneU64,
+
+    /// Weights of RPC methods for `--rpc.max-batch-cost` and `--rpc.max-connection-cost`.
+    ///
+    /// A weight can be set for a single method, e.g. `eth_getLogs=200`, or for all methods of a
+    /// namespace, e.g. `trace_*=50`. The weights override the built-in weights of expensive
+    /// methods such as `eth_getLogs` and `eth_call`, all other methods have a weight of 1.
+    #[arg(long = "rpc.method-weights", value_name = "METHOD=WEIGHT", value_delimiter = ',', value_parser = parse_method_weight)]
+    pub rpc_method_weights: Vec<(String, u64)>,
+
+    /// Methods whose constant responses are served from pre-serialized results over HTTP and WS.
+    ///
+    /// The first successful response of each method is stored and returned for subsequent calls
+    /// without parameters, bypassing the method dispatch. Only methods whose result never changes
+    /// while the node is running should be listed, e.g. `eth_chainId,net_version`.
+    ///
+    /// Stored responses are served before any custom RPC middleware of the node, so these calls
+    /// bypass such middleware once their first response is stored. Disabled by default.
+    #[arg(long = "rpc.static-responses", value_name = "METHODS", value_delimiter = ',')]
+    pub rpc_static_responses: Vec<String>,
+
     /// Maximum number of blocks for `trace_filter` requests.
     #[arg(long = "rpc.max-trace-filter-blocks", alias = "rpc-max-trace-filter-blocks", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS)]
     pub rpc_max_trace_filter_blocks: u64,
@@ -177,6 +382,22 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maximum number of addresses in a logs filter. (0 = no limit)
+    #[arg(long = "rpc.max-addresses-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_ADDRESSES_PER_FILTER as u64))]
+    pub rpc_max_addresses_per_filter: ZeroAsNoneU64,
+
+    /// Maximum number of topics in each topic position of a logs filter. (0 = no limit)
+    #[arg(long = "rpc.max-topics-per-position", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_TOPICS_PER_POSITION as u64))]
+    pub rpc_max_topics_per_position: ZeroAsNoneU64,
+
+    /// Request header whose value overrides the maximum number of blocks scanned by an
+    /// `eth_getLogs` request over HTTP (`--rpc.max-blocks-per-filter`). (0 = entire chain)
+    ///
+    /// The header allows clients to bypass the block range limit, so it should only be set by a
+    /// trusted proxy in front of the node.
+    #[arg(long = "rpc.block-range-override-header", value_name = "HEADER")]
+    pub rpc_block_range_override_header: Option<String>,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long = "rpc.gascap",
@@ -215,6 +436,20 @@ pub struct RpcServerArgs {
     )]
     pub rpc_eth_proof_window: u64,
 
+    /// Maximum duration `eth_sendRawTransactionSync` waits for the transaction to be included in
+    /// a block. Requests can ask for a shorter timeout.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rpc.send-raw-transaction-sync-timeout 30s
+    #[arg(
+        long = "rpc.send-raw-transaction-sync-timeout",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "30s",
+        verbatim_doc_comment
+    )]
+    pub rpc_send_raw_transaction_sync_timeout: Duration,
+
     /// Maximum number of concurrent getproof requests.
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
@@ -336,17 +571,28 @@ impl Default for RpcServerArgs {
             http_disable_compression: false,
             http_api: None,
             http_corsdomain: None,
+            http_corsdomain_admin: None,
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
+            ws_allowed_origins_admin: None,
             ws_api: None,
+            ws_ping_interval: None,
+            ws_idle_timeout: None,
+            ws_max_lifetime: None,
+            rpc_tls_cert: None,
+            rpc_tls_key: None,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
             ipc_socket_permissions: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
+            auth_jwtsecret_secondary: None,
+            auth_jwtsecret_grace_period: AUTH_DEFAULT_JWT_SECRET_GRACE_PERIOD,
+            auth_tls_cert: None,
+            auth_tls_key: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
             disable_auth_server: false,
@@ -354,15 +600,33 @@ impl Default for RpcServerArgs {
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
+            rpc_max_buffered_notifications: RPC_DEFAULT_MAX_BUFFERED_NOTIFICATIONS.into(),
+            rpc_close_slow_subscriptions: false,
+            rpc_max_subscription_bytes_per_sec: ZeroAsNoneU64(None),
+            rpc_pending_tx_subscription_buffer: constants::DEFAULT_PENDING_TX_SUBSCRIPTION_BUFFER,
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
+            rpc_metrics_client_header: None,
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
+            rpc_max_trace_calls: 0,
+            rpc_max_queued_trace_calls: constants::DEFAULT_MAX_QUEUED_TRACE_CALLS,
+            rpc_strict_validation: false,
+            rpc_max_batch_cost: ZeroAsNoneU64(None),
+            rpc_max_connection_cost: ZeroAsNoneU64(None),
+            rpc_method_weights: Vec::new(),
+            rpc_static_responses: Vec::new(),
             rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_max_addresses_per_filter: (constants::DEFAULT_MAX_ADDRESSES_PER_FILTER as u64)
+                .into(),
+            rpc_max_topics_per_position: (constants::DEFAULT_MAX_TOPICS_PER_POSITION as u64).into(),
+            rpc_block_range_override_header: None,
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_tx_fee_cap: constants::DEFAULT_TX_FEE_CAP_WEI,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_send_raw_transaction_sync_timeout:
+                constants::DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
@@ -403,6 +667,17 @@ impl TypedValueParser for RpcModuleSelectionValueParser {
     }
 }
 
+/// Parses a method weight in the form `<METHOD>=<WEIGHT>`.
+fn parse_method_weight(value: &str) -> eyre::Result<(String, u64)> {
+    let (method, weight) =
+        value.split_once('=').ok_or_else(|| eyre::eyre!("expected <METHOD>=<WEIGHT>"))?;
+    let method = method.trim();
+    if method.is_empty() {
+        eyre::bail!("method name must not be empty")
+    }
+    Ok((method.to_string(), weight.trim().parse()?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -427,6 +702,69 @@ mod tests {
         assert_eq!(apis, expected);
     }
 
+    #[test]
+    fn test_rpc_method_weights_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.max-batch-cost",
+            "1000",
+            "--rpc.method-weights",
+            "eth_getLogs=200,trace_*=20",
+        ])
+        .args;
+
+        assert_eq!(args.rpc_max_batch_cost.0, Some(1000));
+        assert_eq!(args.rpc_max_connection_cost.0, None);
+        assert_eq!(
+            args.rpc_method_weights,
+            vec![("eth_getLogs".to_string(), 200), ("trace_*".to_string(), 20)]
+        );
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.method-weights",
+            "eth_getLogs"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_static_responses_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rpc_static_responses.is_empty());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.static-responses",
+            "eth_chainId,net_version",
+        ])
+        .args;
+        assert_eq!(args.rpc_static_responses, ["eth_chainId", "net_version"]);
+    }
+
+    #[test]
+    fn test_rpc_tls_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.tls-cert",
+            "cert.pem",
+            "--rpc.tls-key",
+            "key.pem",
+        ])
+        .args;
+        assert_eq!(args.rpc_tls_cert, Some("cert.pem".into()));
+        assert_eq!(args.rpc_tls_key, Some("key.pem".into()));
+        assert_eq!(args.auth_tls_cert, None);
+
+        // the certificate requires a key
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--authrpc.tls-cert",
+            "cert.pem"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_rpc_server_eth_call_bundle_args() {
         let args =
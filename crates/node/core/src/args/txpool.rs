@@ -8,8 +8,10 @@ use reth_cli_util::parse_duration_from_secs_or_ms;
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
-    pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
-    validate::DEFAULT_MAX_TX_INPUT_BYTES,
+    pool::{
+        NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE, POOL_LISTENER_BUFFER_SIZE,
+    },
+    validate::{ValidationBudgetConfig, DEFAULT_MAX_TX_INPUT_BYTES},
     LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
     DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
@@ -121,6 +123,20 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs_or_ms, default_value = "10800", value_name = "DURATION")]
     pub max_queued_lifetime: Duration,
 
+    /// Validation cost budget per second for transactions received over p2p.
+    ///
+    /// Transactions exceeding the budget are rejected before they are validated. Disabled by
+    /// default, 0 disables the budget.
+    #[arg(long = "txpool.p2p-validation-budget", value_name = "COST", default_value_t = 0)]
+    pub p2p_validation_budget: u64,
+
+    /// Validation cost budget per second for transactions submitted over RPC.
+    ///
+    /// Transactions exceeding the budget are rejected before they are validated. Disabled by
+    /// default, 0 disables the budget.
+    #[arg(long = "txpool.rpc-validation-budget", value_name = "COST", default_value_t = 0)]
+    pub rpc_validation_budget: u64,
+
     /// Path to store the local transaction backup at, to survive node restarts.
     #[arg(long = "txpool.transactions-backup", alias = "txpool.journal", value_name = "PATH")]
     pub transactions_backup_path: Option<std::path::PathBuf>,
@@ -163,6 +179,8 @@ impl Default for TxPoolArgs {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            p2p_validation_budget: 0,
+            rpc_validation_budget: 0,
             transactions_backup_path: None,
             disable_transactions_backup: false,
         }
@@ -205,8 +223,13 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             gas_limit: self.enforced_gas_limit,
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
+            pool_listener_buffer_size: POOL_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
+            p2p_validation_budget: (self.p2p_validation_budget > 0)
+                .then(|| ValidationBudgetConfig::per_second(self.p2p_validation_budget)),
+            rpc_validation_budget: (self.rpc_validation_budget > 0)
+                .then(|| ValidationBudgetConfig::per_second(self.rpc_validation_budget)),
         }
     }
 }
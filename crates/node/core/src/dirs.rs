@@ -315,6 +315,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("known-peers.json")
     }
 
+    /// Returns the path to the banned peers file for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/banned-peers.json`
+    pub fn banned_peers(&self) -> PathBuf {
+        self.data_dir().join("banned-peers.json")
+    }
+
     /// Returns the path to the blobstore directory for this chain where blobs of unfinalized
     /// transactions are stored.
     ///
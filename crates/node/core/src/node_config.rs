@@ -2,8 +2,8 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        BlockExportArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, ExExArgs,
+        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -14,7 +14,7 @@ use alloy_primitives::{BlockNumber, B256};
 use eyre::eyre;
 use reth_chainspec::{ChainSpec, EthChainSpec, MAINNET};
 use reth_config::config::PruneConfig;
-use reth_engine_local::MiningMode;
+use reth_engine_local::{MiningMode, PayloadAttributesOverrides};
 use reth_ethereum_forks::{EthereumHardforks, Head};
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_primitives_traits::SealedHeader;
@@ -45,6 +45,9 @@ pub const DEFAULT_PERSISTENCE_THRESHOLD: u64 = 2;
 /// Default size of cross-block cache in megabytes.
 pub const DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB: u64 = 4 * 1024;
 
+/// Default number of recent canonical state notifications that are replayed to new subscribers.
+pub const DEFAULT_CANON_STATE_HISTORY: usize = 16;
+
 /// This includes all necessary configuration to launch the node.
 /// The individual configuration options can be overwritten before launching the node.
 ///
@@ -146,6 +149,9 @@ pub struct NodeConfig<ChainSpec> {
     /// All dev related arguments with --dev prefix
     pub dev: DevArgs,
 
+    /// Path to the payload attributes overrides applied to every block mined in --dev mode
+    pub dev_payload_attributes: Option<PathBuf>,
+
     /// All pruning related arguments
     pub pruning: PruningArgs,
 
@@ -154,6 +160,12 @@ pub struct NodeConfig<ChainSpec> {
 
     /// All ERA import related arguments with --era prefix
     pub era: EraArgs,
+
+    /// All `ExEx` related arguments with --exex prefix
+    pub exex: ExExArgs,
+
+    /// All block export related arguments with --block-export prefix
+    pub block_export: BlockExportArgs,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -180,10 +192,13 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             debug: DebugArgs::default(),
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
+            dev_payload_attributes: None,
             pruning: PruningArgs::default(),
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
             era: EraArgs::default(),
+            exex: ExExArgs::default(),
+            block_export: BlockExportArgs::default(),
         }
     }
 
@@ -283,12 +298,30 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the path to the payload attributes overrides applied to every block mined in --dev mode
+    pub fn with_dev_payload_attributes(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dev_payload_attributes = Some(path.into());
+        self
+    }
+
     /// Set the pruning args for the node
     pub fn with_pruning(mut self, pruning: PruningArgs) -> Self {
         self.pruning = pruning;
         self
     }
 
+    /// Set the `ExEx` args for the node
+    pub const fn with_exex(mut self, exex: ExExArgs) -> Self {
+        self.exex = exex;
+        self
+    }
+
+    /// Set the block export args for the node
+    pub fn with_block_export(mut self, block_export: BlockExportArgs) -> Self {
+        self.block_export = block_export;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig>
     where
@@ -487,9 +520,12 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             debug: self.debug,
             db: self.db,
             dev: self.dev,
+            dev_payload_attributes: self.dev_payload_attributes,
             pruning: self.pruning,
             engine: self.engine,
             era: self.era,
+            exex: self.exex,
+            block_export: self.block_export,
         }
     }
 
@@ -501,6 +537,18 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             MiningMode::instant(pool)
         }
     }
+
+    /// Returns the payload attributes overrides of `--dev.payload-attributes` that should be
+    /// applied to every block mined in --dev mode, if set.
+    pub fn dev_payload_attributes_overrides(
+        &self,
+    ) -> eyre::Result<Option<PayloadAttributesOverrides>> {
+        let Some(path) = &self.dev_payload_attributes else { return Ok(None) };
+        let overrides = reth_fs_util::read_json_file(path).map_err(|err| {
+            eyre!("Failed to read payload attributes overrides from {}: {err}", path.display())
+        })?;
+        Ok(Some(overrides))
+    }
 }
 
 impl Default for NodeConfig<ChainSpec> {
@@ -523,10 +571,13 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             debug: self.debug.clone(),
             db: self.db,
             dev: self.dev,
+            dev_payload_attributes: self.dev_payload_attributes.clone(),
             pruning: self.pruning.clone(),
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
             era: self.era.clone(),
+            exex: self.exex.clone(),
+            block_export: self.block_export.clone(),
         }
     }
 }
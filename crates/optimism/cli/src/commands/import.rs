@@ -4,22 +4,19 @@ use clap::Parser;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
-    import::build_import_pipeline,
+    import_op::{import_blocks, ChainImporter, ImportConfig, RlpFileImporter},
 };
-use reth_consensus::noop::NoopConsensus;
-use reth_db_api::{tables, transaction::DbTx};
-use reth_downloaders::file_client::{ChunkedFileReader, DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE};
-use reth_node_builder::BlockTy;
+use reth_consensus::{noop::NoopConsensus, Consensus, ConsensusError};
+use reth_downloaders::file_client::FileClient;
+use reth_node_builder::{BlockTy, HeaderTy};
 use reth_node_core::version::SHORT_VERSION;
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_evm::OpExecutorProvider;
 use reth_optimism_primitives::{bedrock::is_dup_tx, OpPrimitives};
-use reth_provider::{BlockNumReader, ChainSpecProvider, HeaderProvider, StageCheckpointReader};
-use reth_prune::PruneModes;
-use reth_stages::StageId;
-use reth_static_file::StaticFileProducer;
+use reth_primitives_traits::SealedHeader;
+use reth_provider::{providers::ProviderNodeTypes, ChainSpecProvider};
 use std::{path::PathBuf, sync::Arc};
-use tracing::{debug, error, info};
+use tracing::info;
 
 /// Syncs RLP encoded blocks from a file.
 #[derive(Debug, Parser)]
@@ -50,111 +47,57 @@ impl<C: ChainSpecParser<ChainSpec = OpChainSpec>> ImportOpCommand<C> {
             "Disabled stages requiring state, since cannot execute OVM state changes"
         );
 
-        debug!(target: "reth::cli",
-            chunk_byte_len=self.chunk_len.unwrap_or(DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE),
-            "Chunking chain import"
-        );
-
         let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
 
         // we use noop here because we expect the inputs to be valid
         let consensus = Arc::new(NoopConsensus::default());
 
-        // open file
-        let mut reader = ChunkedFileReader::new(&self.path, self.chunk_len).await?;
-
-        let mut total_decoded_blocks = 0;
-        let mut total_decoded_txns = 0;
-        let mut total_filtered_out_dup_txns = 0;
-
-        let mut sealed_header = provider_factory
-            .sealed_header(provider_factory.last_block_number()?)?
-            .expect("should have genesis");
-
-        while let Some(mut file_client) =
-            reader.next_chunk::<BlockTy<N>>(consensus.clone(), Some(sealed_header)).await?
-        {
-            // create a new FileClient from chunk read from file
-            info!(target: "reth::cli",
-                "Importing chain file chunk"
-            );
-
-            let tip = file_client.tip().ok_or_else(|| eyre::eyre!("file client has no tip"))?;
-            info!(target: "reth::cli", "Chain file chunk read");
+        let import_config =
+            ImportConfig { no_state: true, chunk_len: self.chunk_len, ..Default::default() };
+        let importer =
+            OpBedrockImporter { inner: RlpFileImporter::new(&self.path, import_config).await? };
+        let executor = OpExecutorProvider::optimism(provider_factory.chain_spec());
 
-            total_decoded_blocks += file_client.headers_len();
-            total_decoded_txns += file_client.total_transactions();
+        import_blocks(importer, provider_factory, &config, executor, consensus).await?;
 
-            for (block_number, body) in file_client.bodies_iter_mut() {
-                body.transactions.retain(|_| {
-                    if is_dup_tx(block_number) {
-                        total_filtered_out_dup_txns += 1;
-                        return false
-                    }
-                    true
-                })
-            }
-
-            let (mut pipeline, events) = build_import_pipeline(
-                &config,
-                provider_factory.clone(),
-                &consensus,
-                Arc::new(file_client),
-                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
-                true,
-                OpExecutorProvider::optimism(provider_factory.chain_spec()),
-            )?;
+        Ok(())
+    }
+}
 
-            // override the tip
-            pipeline.set_tip(tip);
-            debug!(target: "reth::cli", ?tip, "Tip manually set");
+/// A [`ChainImporter`] for the OP Mainnet chain segment below Bedrock.
+///
+/// Removes the duplicate transactions of the pre-Bedrock chain, see [`is_dup_tx`], and disables
+/// the stages requiring state, since the OVM state changes can't be executed.
+#[derive(Debug)]
+struct OpBedrockImporter {
+    inner: RlpFileImporter,
+}
 
-            let provider = provider_factory.provider()?;
+impl<N> ChainImporter<N> for OpBedrockImporter
+where
+    N: ProviderNodeTypes<Primitives = OpPrimitives>,
+{
+    async fn next_chunk(
+        &mut self,
+        consensus: Arc<dyn Consensus<BlockTy<N>, Error = ConsensusError>>,
+        parent: SealedHeader<HeaderTy<N>>,
+    ) -> eyre::Result<Option<FileClient<BlockTy<N>>>> {
+        ChainImporter::<N>::next_chunk(&mut self.inner, consensus, parent).await
+    }
 
-            let latest_block_number =
-                provider.get_stage_checkpoint(StageId::Finish)?.map(|ch| ch.block_number);
-            tokio::spawn(reth_node_events::node::handle_events(None, latest_block_number, events));
+    fn no_state(&self) -> bool {
+        true
+    }
 
-            // Run pipeline
-            info!(target: "reth::cli", "Starting sync pipeline");
-            tokio::select! {
-                res = pipeline.run() => res?,
-                _ = tokio::signal::ctrl_c() => {},
+    fn on_chunk(&mut self, file_client: &mut FileClient<BlockTy<N>>) -> eyre::Result<usize> {
+        let mut filtered = 0;
+        for (block_number, body) in file_client.bodies_iter_mut() {
+            if is_dup_tx(block_number) {
+                filtered += body.transactions.len();
+                body.transactions.clear();
             }
-
-            sealed_header = provider_factory
-                .sealed_header(provider_factory.last_block_number()?)?
-                .expect("should have genesis");
-        }
-
-        let provider = provider_factory.provider()?;
-
-        let total_imported_blocks = provider.tx_ref().entries::<tables::HeaderNumbers>()?;
-        let total_imported_txns = provider.tx_ref().entries::<tables::TransactionHashNumbers>()?;
-
-        if total_decoded_blocks != total_imported_blocks ||
-            total_decoded_txns != total_imported_txns + total_filtered_out_dup_txns
-        {
-            error!(target: "reth::cli",
-                total_decoded_blocks,
-                total_imported_blocks,
-                total_decoded_txns,
-                total_filtered_out_dup_txns,
-                total_imported_txns,
-                "Chain was partially imported"
-            );
         }
-
-        info!(target: "reth::cli",
-            total_imported_blocks,
-            total_imported_txns,
-            total_decoded_blocks,
-            total_decoded_txns,
-            total_filtered_out_dup_txns,
-            "Chain file imported"
-        );
-
-        Ok(())
+        Ok(filtered)
     }
 }
 
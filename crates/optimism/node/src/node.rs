@@ -9,7 +9,7 @@ use crate::{
 use op_alloy_consensus::{interop::SafetyLevel, OpPooledTransaction};
 use op_alloy_rpc_types_engine::OpExecutionData;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, Hardforks};
-use reth_engine_local::LocalPayloadAttributesBuilder;
+use reth_engine_local::{LocalPayloadAttributesBuilder, PayloadAttributesInjector};
 use reth_evm::ConfigureEvm;
 use reth_network::{
     types::BasicNetworkPrimitives, NetworkConfig, NetworkHandle, NetworkManager, NetworkPrimitives,
@@ -28,7 +28,8 @@ use reth_node_builder::{
     node::{FullNodeTypes, NodeTypes},
     rpc::{
         EngineApiBuilder, EngineValidatorAddOn, EngineValidatorBuilder, EthApiBuilder, Identity,
-        RethRpcAddOns, RethRpcMiddleware, RethRpcServerHandles, RpcAddOns, RpcContext, RpcHandle,
+        MapEthApiBuilder, RethRpcAddOns, RethRpcMiddleware, RethRpcServerHandles, RpcAddOns,
+        RpcContext, RpcHandle,
     },
     BuilderContext, DebugNode, Node, NodeAdapter, NodeComponentsBuilder,
 };
@@ -255,6 +256,13 @@ where
     ) -> impl PayloadAttributesBuilder<<Self::Payload as PayloadTypes>::PayloadAttributes> {
         LocalPayloadAttributesBuilder::new(Arc::new(chain_spec.clone()))
     }
+
+    fn local_payload_attributes_builder_with_injector(
+        chain_spec: &Self::ChainSpec,
+        injector: PayloadAttributesInjector,
+    ) -> impl PayloadAttributesBuilder<<Self::Payload as PayloadTypes>::PayloadAttributes> {
+        LocalPayloadAttributesBuilder::new(Arc::new(chain_spec.clone())).with_injector(injector)
+    }
 }
 
 impl NodeTypes for OpNode {
@@ -381,6 +389,67 @@ where
         }
     }
 
+    /// Maps the [`EthApiBuilder`] builder type.
+    ///
+    /// See also [`RpcAddOns::with_eth_api_builder`].
+    pub fn with_eth_api_builder<T>(
+        self,
+        eth_api_builder: T,
+    ) -> OpAddOns<N, T, EV, EB, RpcMiddleware>
+    where
+        T: EthApiBuilder<N>,
+    {
+        let Self {
+            rpc_add_ons,
+            da_config,
+            sequencer_url,
+            sequencer_headers,
+            enable_tx_conditional,
+            min_suggested_priority_fee,
+            historical_rpc,
+        } = self;
+        OpAddOns {
+            rpc_add_ons: rpc_add_ons.with_eth_api_builder(eth_api_builder),
+            da_config,
+            sequencer_url,
+            sequencer_headers,
+            enable_tx_conditional,
+            min_suggested_priority_fee,
+            historical_rpc,
+        }
+    }
+
+    /// Maps the built `EthApi`, e.g. to decorate it.
+    ///
+    /// See also [`RpcAddOns::map_eth_api`].
+    pub fn map_eth_api<F, T>(
+        self,
+        f: F,
+    ) -> OpAddOns<N, MapEthApiBuilder<EthB, F>, EV, EB, RpcMiddleware>
+    where
+        F: FnOnce(EthB::EthApi) -> eyre::Result<T>,
+        MapEthApiBuilder<EthB, F>: EthApiBuilder<N, EthApi = T>,
+    {
+        let Self {
+            rpc_add_ons,
+            da_config,
+            sequencer_url,
+            sequencer_headers,
+            enable_tx_conditional,
+            min_suggested_priority_fee,
+            historical_rpc,
+        } = self;
+        OpAddOns {
+            rpc_add_ons: rpc_add_ons.map_eth_api(f),
+            da_config,
+            sequencer_url,
+            sequencer_headers,
+            enable_tx_conditional,
+            min_suggested_priority_fee,
+            historical_rpc,
+        }
+    }
+
     /// Sets the RPC middleware stack for processing RPC requests.
     ///
     /// This method configures a custom middleware stack that will be applied to all RPC requests
@@ -588,11 +657,11 @@ where
     }
 }
 
-impl<N, NetworkT, EV, EB, RpcMiddleware> EngineValidatorAddOn<N>
-    for OpAddOns<N, OpEthApiBuilder<NetworkT>, EV, EB, RpcMiddleware>
+impl<N, EthB, EV, EB, RpcMiddleware> EngineValidatorAddOn<N>
+    for OpAddOns<N, EthB, EV, EB, RpcMiddleware>
 where
     N: FullNodeComponents<Types: OpFullNodeTypes>,
-    OpEthApiBuilder<NetworkT>: EthApiBuilder<N>,
+    EthB: EthApiBuilder<N>,
     EV: EngineValidatorBuilder<N> + Default,
     EB: EngineApiBuilder<N>,
     RpcMiddleware: Send,
@@ -6,7 +6,9 @@ use crate::OP_NAME_CLIENT;
 use alloy_rpc_types_engine::ClientVersionV1;
 use op_alloy_rpc_types_engine::OpExecutionData;
 use reth_chainspec::EthereumHardforks;
-use reth_node_api::{AddOnsContext, EngineTypes, FullNodeComponents, NodeTypes};
+use reth_node_api::{
+    AddOnsContext, EngineTypes, FullNodeComponents, NodeTypes, PayloadAttributesHook, PayloadTypes,
+};
 use reth_node_builder::rpc::{EngineApiBuilder, EngineValidatorBuilder};
 use reth_node_core::version::{CARGO_PKG_VERSION, CLIENT_CODE, VERGEN_GIT_SHA};
 use reth_optimism_rpc::engine::OP_ENGINE_CAPABILITIES;
@@ -14,12 +16,33 @@ use reth_payload_builder::PayloadStore;
 use reth_rpc_engine_api::{EngineApi, EngineCapabilities};
 
 /// Builder for basic [`OpEngineApi`] implementation.
-#[derive(Debug, Default, Clone)]
-pub struct OpEngineApiBuilder<EV> {
+#[derive(Default, Clone)]
+pub struct OpEngineApiBuilder<EV, H = ()> {
     engine_validator_builder: EV,
+    payload_attributes_hook: Option<H>,
 }
 
-impl<N, EV> EngineApiBuilder<N> for OpEngineApiBuilder<EV>
+impl<EV, H> OpEngineApiBuilder<EV, H> {
+    /// Configures the [`PayloadAttributesHook`] that is invoked with the payload attributes of
+    /// forkchoice updates before they are sent to the payload builder.
+    pub fn with_payload_attributes_hook<T>(self, hook: T) -> OpEngineApiBuilder<EV, T> {
+        OpEngineApiBuilder {
+            engine_validator_builder: self.engine_validator_builder,
+            payload_attributes_hook: Some(hook),
+        }
+    }
+}
+
+impl<EV: core::fmt::Debug, H> core::fmt::Debug for OpEngineApiBuilder<EV, H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OpEngineApiBuilder")
+            .field("engine_validator_builder", &self.engine_validator_builder)
+            .field("payload_attributes_hook", &self.payload_attributes_hook.is_some())
+            .finish()
+    }
+}
+
+impl<N, EV, H> EngineApiBuilder<N> for OpEngineApiBuilder<EV, H>
 where
     N: FullNodeComponents<
         Types: NodeTypes<
@@ -28,6 +51,7 @@ where
         >,
     >,
     EV: EngineValidatorBuilder<N>,
+    H: PayloadAttributesHook<<<N::Types as NodeTypes>::Payload as PayloadTypes>::PayloadAttributes>,
 {
     type EngineApi = OpEngineApi<
         N::Provider,
@@ -38,7 +62,7 @@ where
     >;
 
     async fn build_engine_api(self, ctx: &AddOnsContext<'_, N>) -> eyre::Result<Self::EngineApi> {
-        let Self { engine_validator_builder } = self;
+        let Self { engine_validator_builder, payload_attributes_hook } = self;
 
         let engine_validator = engine_validator_builder.build(ctx).await?;
         let client = ClientVersionV1 {
@@ -58,7 +82,12 @@ where
             EngineCapabilities::new(OP_ENGINE_CAPABILITIES.iter().copied()),
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
-        );
+        )
+        .with_consensus_clients(ctx.consensus_clients.clone());
+        let inner = match payload_attributes_hook {
+            Some(hook) => inner.with_payload_attributes_hook(hook),
+            None => inner,
+        };
 
         Ok(OpEngineApi::new(inner))
     }
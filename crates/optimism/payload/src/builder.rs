@@ -40,7 +40,7 @@ use reth_revm::{
 use reth_storage_api::{errors::ProviderError, StateProvider, StateProviderFactory};
 use reth_transaction_pool::{BestTransactionsAttributes, PoolTransaction, TransactionPool};
 use revm::context::{Block, BlockEnv};
-use std::{marker::PhantomData, sync::Arc};
+use std::{marker::PhantomData, sync::Arc, time::Instant};
 use tracing::{debug, trace, warn};
 
 /// Optimism's payload builder
@@ -181,7 +181,7 @@ where
         Txs:
             PayloadTransactions<Transaction: PoolTransaction<Consensus = N::SignedTx> + OpPooledTx>,
     {
-        let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
+        let BuildArguments { mut cached_reads, config, cancel, best_payload, deadline } = args;
 
         let ctx = OpPayloadBuilderCtx {
             evm_config: self.evm_config.clone(),
@@ -190,6 +190,7 @@ where
             config,
             cancel,
             best_payload,
+            deadline,
         };
 
         let builder = OpBuilder::new(best);
@@ -226,6 +227,7 @@ where
             config,
             cancel: Default::default(),
             best_payload: Default::default(),
+            deadline: None,
         };
 
         let state_provider = self.client.state_by_block_hash(ctx.parent().hash())?;
@@ -280,6 +282,7 @@ where
             cached_reads: Default::default(),
             cancel: Default::default(),
             best_payload: None,
+            deadline: None,
         };
         self.build_payload(args, |_| NoopPayloadTransactions::<Pool::Transaction>::default())?
             .into_payload()
@@ -545,6 +548,8 @@ pub struct OpPayloadBuilderCtx<
     pub cancel: CancelOnDrop,
     /// The currently best payload.
     pub best_payload: Option<OpBuiltPayload<Evm::Primitives>>,
+    /// The point in time after which no more pool transactions should be selected.
+    pub deadline: Option<Instant>,
 }
 
 impl<Evm, ChainSpec, Attrs> OpPayloadBuilderCtx<Evm, ChainSpec, Attrs>
@@ -665,6 +670,12 @@ where
         let base_fee = builder.evm_mut().block().basefee;
 
         while let Some(tx) = best_txs.next(()) {
+            // stop selecting transactions once the time budget of this build is used up
+            if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                debug!(target: "payload_builder", "payload build deadline reached");
+                break
+            }
+
             let interop = tx.interop_deadline();
             let tx_da_size = tx.estimated_da_size();
             let tx = tx.into_consensus();
@@ -7,7 +7,7 @@ use alloy_rpc_types_engine::{
     ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus,
 };
 use derive_more::Constructor;
-use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::{proc_macros::rpc, ConnectionId, Extensions};
 use jsonrpsee_core::{server::RpcModule, RpcResult};
 use op_alloy_rpc_types_engine::{
     OpExecutionData, OpExecutionPayloadV4, ProtocolVersion, ProtocolVersionFormatV0,
@@ -233,7 +233,7 @@ pub trait OpEngineApi<Engine: EngineTypes> {
     /// > The `client_version` parameter identifies the consensus client.
     ///
     /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/identification.md#engine_getclientversionv1>
-    #[method(name = "getClientVersionV1")]
+    #[method(name = "getClientVersionV1", with_extensions)]
     async fn get_client_version_v1(
         &self,
         client_version: ClientVersionV1,
@@ -390,10 +390,12 @@ where
 
     async fn get_client_version_v1(
         &self,
+        ext: &Extensions,
         client: ClientVersionV1,
     ) -> RpcResult<Vec<ClientVersionV1>> {
         trace!(target: "rpc::engine", "Serving engine_getClientVersionV1");
-        Ok(self.inner.get_client_version_v1(client)?)
+        let connection_id = ext.get::<ConnectionId>().map(|id| id.0 as u64);
+        Ok(self.inner.get_client_version_v1(connection_id, client)?)
     }
 
     async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> RpcResult<Vec<String>> {
@@ -9,6 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use crate::metrics::PayloadBuilderMetrics;
+use alloy_consensus::BlockHeader;
 use alloy_eips::merge::SLOT_DURATION;
 use alloy_primitives::{B256, U256};
 use futures_core::ready;
@@ -28,7 +29,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
     sync::{oneshot, Semaphore},
@@ -163,19 +164,34 @@ where
         let until = self.job_deadline(config.attributes.timestamp());
         let deadline = Box::pin(tokio::time::sleep_until(until));
 
+        // the payload is expected to be requested by the time of its timestamp
+        let time_until_timestamp =
+            duration_until(config.attributes.timestamp()).min(self.config.deadline);
+        let payload_due = Instant::now() + time_until_timestamp;
+        let slot_duration =
+            self.config.slot_duration(parent_header.timestamp(), config.attributes.timestamp());
+        let interval = self.config.build_interval(slot_duration, time_until_timestamp);
+
         let cached_reads = self.maybe_pre_cached(parent_header.hash());
 
+        let metrics = PayloadBuilderMetrics::default();
+        if self.config.is_late(slot_duration, time_until_timestamp) {
+            debug!(target: "payload_builder", id=%config.payload_id(), ?slot_duration, ?time_until_timestamp, ?interval, "payload attributes arrived late");
+            metrics.inc_late_payload_jobs();
+        }
+
         let mut job = BasicPayloadJob {
             config,
             executor: self.executor.clone(),
             deadline,
+            payload_due,
             // ticks immediately
-            interval: tokio::time::interval(self.config.interval),
+            interval: tokio::time::interval(interval),
             best_payload: PayloadState::Missing,
             pending_block: None,
             cached_reads,
             payload_task_guard: self.payload_task_guard.clone(),
-            metrics: Default::default(),
+            metrics,
             builder: self.builder.clone(),
         };
 
@@ -248,6 +264,12 @@ pub struct BasicPayloadJobGeneratorConfig {
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
+    /// The minimum number of payload builds a job should attempt before its payload is due.
+    ///
+    /// If the payload attributes arrive late, the interval is shortened accordingly.
+    min_build_iterations: u32,
+    /// The lower bound for the shortened interval of jobs whose payload attributes arrived late.
+    min_interval: Duration,
 }
 
 // === impl BasicPayloadJobGeneratorConfig ===
@@ -275,6 +297,61 @@ impl BasicPayloadJobGeneratorConfig {
         self.max_payload_tasks = max_payload_tasks;
         self
     }
+
+    /// Sets the minimum number of payload builds a job should attempt before its payload is due.
+    ///
+    /// # Panics
+    ///
+    /// If `min_build_iterations` is 0.
+    pub fn min_build_iterations(mut self, min_build_iterations: u32) -> Self {
+        assert!(min_build_iterations > 0, "min_build_iterations must be greater than 0");
+        self.min_build_iterations = min_build_iterations;
+        self
+    }
+
+    /// Sets the lower bound for the shortened interval of jobs whose payload attributes arrived
+    /// late.
+    ///
+    /// # Panics
+    ///
+    /// If `min_interval` is zero.
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        assert!(!min_interval.is_zero(), "min_interval must be greater than 0");
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Returns the slot duration of the chain, which is the time between the parent block and the
+    /// payload.
+    ///
+    /// This is derived from the block timestamps instead of assuming 12s slots, so that chains
+    /// with shorter block times are supported. It is capped by the configured deadline, e.g. if
+    /// the parent block is old.
+    fn slot_duration(&self, parent_timestamp: u64, timestamp: u64) -> Duration {
+        let slot_duration = Duration::from_secs(timestamp.saturating_sub(parent_timestamp));
+        if slot_duration.is_zero() {
+            return self.deadline
+        }
+        slot_duration.min(self.deadline)
+    }
+
+    /// Returns the interval at which a job should build new payloads, given the slot duration of
+    /// the chain and the time that is left until the timestamp of its payload.
+    ///
+    /// The configured interval is shortened so that the job can attempt
+    /// [`Self::min_build_iterations`] builds before the payload is due, but never below
+    /// [`Self::min_interval`].
+    fn build_interval(&self, slot_duration: Duration, time_until_timestamp: Duration) -> Duration {
+        (time_until_timestamp.min(slot_duration) / self.min_build_iterations)
+            .min(self.interval)
+            .max(self.min_interval)
+    }
+
+    /// Returns `true` if the payload attributes arrived late, which is the case if less than half
+    /// of the slot is left until the timestamp of the payload.
+    fn is_late(&self, slot_duration: Duration, time_until_timestamp: Duration) -> bool {
+        time_until_timestamp < slot_duration / 2
+    }
 }
 
 impl Default for BasicPayloadJobGeneratorConfig {
@@ -284,6 +361,8 @@ impl Default for BasicPayloadJobGeneratorConfig {
             // 12s slot time
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            min_build_iterations: 3,
+            min_interval: Duration::from_millis(100),
         }
     }
 }
@@ -309,6 +388,11 @@ where
     executor: Tasks,
     /// The deadline when this job should resolve.
     deadline: Pin<Box<Sleep>>,
+    /// The point in time at which the payload is expected to be requested.
+    ///
+    /// Build attempts are given the time until then, but at least one interval, to select
+    /// transactions.
+    payload_due: Instant,
     /// The interval at which the job should build a new payload after the last.
     interval: Interval,
     /// The best payload so far and its state.
@@ -349,12 +433,24 @@ where
         self.metrics.inc_initiated_payload_builds();
         let cached_reads = self.cached_reads.take().unwrap_or_default();
         let builder = self.builder.clone();
+        let deadline = self.payload_due.max(Instant::now() + self.interval.period());
+        let metrics = self.metrics.clone();
         self.executor.spawn_blocking(Box::pin(async move {
             // acquire the permit for executing the task
             let _permit = guard.acquire().await;
-            let args =
-                BuildArguments { cached_reads, config: payload_config, cancel, best_payload };
+            let started_at = Instant::now();
+            let args = BuildArguments {
+                cached_reads,
+                config: payload_config,
+                cancel,
+                best_payload,
+                deadline: Some(deadline),
+            };
             let result = builder.try_build(args);
+            metrics.record_payload_build(
+                started_at.elapsed(),
+                deadline.saturating_duration_since(started_at),
+            );
             let _ = tx.send(result);
         }));
 
@@ -476,6 +572,7 @@ where
                 config: self.config.clone(),
                 cancel: CancelOnDrop::default(),
                 best_payload: None,
+                deadline: None,
             };
 
             match self.builder.on_missing_payload(args) {
@@ -787,6 +884,10 @@ pub struct BuildArguments<Attributes, Payload: BuiltPayload> {
     pub cancel: CancelOnDrop,
     /// The best payload achieved so far.
     pub best_payload: Option<Payload>,
+    /// The point in time after which no more transactions should be selected for the payload.
+    ///
+    /// This is set if the payload is due soon, e.g. because the payload attributes arrived late.
+    pub deadline: Option<Instant>,
 }
 
 impl<Attributes, Payload: BuiltPayload> BuildArguments<Attributes, Payload> {
@@ -797,7 +898,13 @@ impl<Attributes, Payload: BuiltPayload> BuildArguments<Attributes, Payload> {
         cancel: CancelOnDrop,
         best_payload: Option<Payload>,
     ) -> Self {
-        Self { cached_reads, config, cancel, best_payload }
+        Self { cached_reads, config, cancel, best_payload, deadline: None }
+    }
+
+    /// Sets the point in time after which no more transactions should be selected.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
     }
 }
 
@@ -899,3 +1006,59 @@ fn duration_until(unix_timestamp_secs: u64) -> Duration {
     let timestamp = Duration::from_secs(unix_timestamp_secs);
     timestamp.saturating_sub(unix_now)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_duration_from_timestamps() {
+        let config = BasicPayloadJobGeneratorConfig::default();
+        assert_eq!(config.slot_duration(100, 112), Duration::from_secs(12));
+        assert_eq!(config.slot_duration(100, 102), Duration::from_secs(2));
+
+        // capped by the deadline
+        assert_eq!(config.slot_duration(100, 1000), SLOT_DURATION);
+        assert_eq!(config.slot_duration(100, 100), SLOT_DURATION);
+    }
+
+    #[test]
+    fn late_attributes_on_short_slots() {
+        let config = BasicPayloadJobGeneratorConfig::default();
+        let slot = config.slot_duration(100, 102);
+
+        // attributes that arrive at the start of a 2s slot are not late
+        let time_until_timestamp = Duration::from_millis(1900);
+        assert!(!config.is_late(slot, time_until_timestamp));
+        assert_eq!(
+            config.build_interval(slot, time_until_timestamp),
+            Duration::from_millis(1900) / 3
+        );
+
+        let time_until_timestamp = Duration::from_millis(600);
+        assert!(config.is_late(slot, time_until_timestamp));
+        assert_eq!(config.build_interval(slot, time_until_timestamp), Duration::from_millis(200));
+
+        // never below the minimum interval
+        let time_until_timestamp = Duration::from_millis(90);
+        assert_eq!(config.build_interval(slot, time_until_timestamp), config.min_interval);
+    }
+
+    #[test]
+    fn late_attributes_on_mainnet_slots() {
+        let config = BasicPayloadJobGeneratorConfig::default();
+        let slot = config.slot_duration(100, 112);
+
+        let time_until_timestamp = Duration::from_millis(11_500);
+        assert!(!config.is_late(slot, time_until_timestamp));
+        assert_eq!(config.build_interval(slot, time_until_timestamp), config.interval);
+
+        let time_until_timestamp = Duration::from_secs(4);
+        assert!(config.is_late(slot, time_until_timestamp));
+        assert_eq!(config.build_interval(slot, time_until_timestamp), config.interval);
+
+        let time_until_timestamp = Duration::from_millis(1500);
+        assert!(config.is_late(slot, time_until_timestamp));
+        assert_eq!(config.build_interval(slot, time_until_timestamp), Duration::from_millis(500));
+    }
+}
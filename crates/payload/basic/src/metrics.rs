@@ -1,9 +1,13 @@
 //! Metrics for the payload builder impl
 
-use reth_metrics::{metrics::Counter, Metrics};
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use std::time::Duration;
 
 /// Transaction pool metrics
-#[derive(Metrics)]
+#[derive(Clone, Metrics)]
 #[metrics(scope = "payloads")]
 pub(crate) struct PayloadBuilderMetrics {
     /// Total number of times an empty payload was returned because a built one was not ready.
@@ -12,6 +16,12 @@ pub(crate) struct PayloadBuilderMetrics {
     pub(crate) initiated_payload_builds: Counter,
     /// Total number of failed payload build attempts.
     pub(crate) failed_payload_builds: Counter,
+    /// Total number of payload jobs whose attributes arrived with less than half of the slot left.
+    pub(crate) late_payload_jobs: Counter,
+    /// The time a payload build attempt took.
+    pub(crate) payload_build_duration: Histogram,
+    /// The share of the time budget of a payload build attempt that was used.
+    pub(crate) payload_build_budget_utilization: Histogram,
 }
 
 impl PayloadBuilderMetrics {
@@ -26,4 +36,16 @@ impl PayloadBuilderMetrics {
     pub(crate) fn inc_failed_payload_builds(&self) {
         self.failed_payload_builds.increment(1);
     }
+
+    pub(crate) fn inc_late_payload_jobs(&self) {
+        self.late_payload_jobs.increment(1);
+    }
+
+    pub(crate) fn record_payload_build(&self, elapsed: Duration, budget: Duration) {
+        self.payload_build_duration.record(elapsed);
+        if !budget.is_zero() {
+            self.payload_build_budget_utilization
+                .record(elapsed.as_secs_f64() / budget.as_secs_f64());
+        }
+    }
 }
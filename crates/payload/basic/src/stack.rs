@@ -203,6 +203,7 @@ where
                         attributes: left_attr.clone(),
                     },
                     cancel: args.cancel.clone(),
+                    deadline: args.deadline,
                     best_payload: args.best_payload.clone().and_then(|payload| {
                         if let Either::Left(p) = payload {
                             Some(p)
@@ -222,6 +223,7 @@ where
                         attributes: right_attr.clone(),
                     },
                     cancel: args.cancel.clone(),
+                    deadline: args.deadline,
                     best_payload: args.best_payload.clone().and_then(|payload| {
                         if let Either::Right(p) = payload {
                             Some(p)
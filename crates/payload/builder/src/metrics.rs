@@ -23,6 +23,8 @@ pub(crate) struct PayloadBuilderServiceMetrics {
     pub(crate) resolved_revenue: Gauge,
     /// Current block returned as the resolved payload
     pub(crate) resolved_block: Gauge,
+    /// Total number of resolved payloads that were externally built candidates
+    pub(crate) resolved_candidates: Counter,
 }
 
 impl PayloadBuilderServiceMetrics {
@@ -34,6 +36,10 @@ impl PayloadBuilderServiceMetrics {
         self.failed_jobs.increment(1);
     }
 
+    pub(crate) fn inc_resolved_candidates(&self) {
+        self.resolved_candidates.increment(1);
+    }
+
     pub(crate) fn set_active_jobs(&self, value: usize) {
         self.active_jobs.set(value as f64)
     }
@@ -2,7 +2,7 @@
 
 use crate::{service::PayloadServiceCommand, PayloadBuilderHandle};
 use futures_util::{ready, StreamExt};
-use reth_payload_primitives::{PayloadBuilderAttributes, PayloadTypes};
+use reth_payload_primitives::{PayloadBuilderAttributes, PayloadBuilderError, PayloadTypes};
 use std::{
     future::Future,
     pin::Pin,
@@ -53,6 +53,9 @@ where
                 PayloadServiceCommand::PayloadAttributes(_, tx) => tx.send(None).ok(),
                 PayloadServiceCommand::Resolve(_, _, tx) => tx.send(None).ok(),
                 PayloadServiceCommand::Subscribe(_) => None,
+                PayloadServiceCommand::SubmitCandidate(_, _, tx) => {
+                    tx.send(Err(PayloadBuilderError::MissingPayload)).ok()
+                }
             };
         }
     }
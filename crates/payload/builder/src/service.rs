@@ -15,6 +15,7 @@ use reth_payload_builder_primitives::{Events, PayloadBuilderError, PayloadEvents
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind, PayloadTypes};
 use reth_primitives_traits::NodePrimitives;
 use std::{
+    collections::{hash_map::Entry, HashMap},
     fmt,
     future::Future,
     pin::Pin,
@@ -82,6 +83,16 @@ where
     ) -> Option<Result<T::PayloadBuilderAttributes, PayloadBuilderError>> {
         self.inner.payload_attributes(id).await
     }
+
+    /// Submits an externally built payload as a candidate for the payload job with the given
+    /// identifier, see [`PayloadBuilderHandle::submit_candidate`].
+    pub async fn submit_candidate(
+        &self,
+        id: PayloadId,
+        payload: T::BuiltPayload,
+    ) -> Result<(), PayloadBuilderError> {
+        self.inner.submit_candidate(id, payload).await
+    }
 }
 
 impl<T> PayloadStore<T>
@@ -177,6 +188,27 @@ impl<T: PayloadTypes> PayloadBuilderHandle<T> {
         self.to_service.send(PayloadServiceCommand::PayloadAttributes(id, tx)).ok()?;
         rx.await.ok()?
     }
+
+    /// Submits an externally built payload as a candidate for the payload job with the given
+    /// identifier.
+    ///
+    /// When the job is resolved, the most valuable candidate is returned instead of the locally
+    /// built payload if its [`BuiltPayload::fees`] are higher, or if the local job failed.
+    ///
+    /// Note: the payload must have been validated by the caller.
+    ///
+    /// Returns an error if there's no such job.
+    pub async fn submit_candidate(
+        &self,
+        id: PayloadId,
+        payload: T::BuiltPayload,
+    ) -> Result<(), PayloadBuilderError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_service
+            .send(PayloadServiceCommand::SubmitCandidate(id, payload, tx))
+            .map_err(|_| PayloadBuilderError::ChannelClosed)?;
+        rx.await?
+    }
 }
 
 impl<T> Clone for PayloadBuilderHandle<T>
@@ -208,6 +240,8 @@ where
     generator: Gen,
     /// All active payload jobs.
     payload_jobs: Vec<(Gen::Job, PayloadId)>,
+    /// The most valuable externally built payload submitted for each active job.
+    candidates: HashMap<PayloadId, T::BuiltPayload>,
     /// Copy of the sender half, so new [`PayloadBuilderHandle`] can be created on demand.
     service_tx: mpsc::UnboundedSender<PayloadServiceCommand<T>>,
     /// Receiver half of the command channel.
@@ -244,6 +278,7 @@ where
         let service = Self {
             generator,
             payload_jobs: Vec::new(),
+            candidates: HashMap::default(),
             service_tx,
             command_rx: UnboundedReceiverStream::new(command_rx),
             metrics: Default::default(),
@@ -291,10 +326,13 @@ where
         let job = self.payload_jobs.iter().position(|(_, job_id)| *job_id == id)?;
         let (fut, keep_alive) = self.payload_jobs[job].0.resolve_kind(kind);
 
-        if keep_alive == KeepPayloadJobAlive::No {
+        let candidate = if keep_alive == KeepPayloadJobAlive::No {
             let (_, id) = self.payload_jobs.swap_remove(job);
             debug!(target: "payload_builder", %id, "terminated resolved job");
-        }
+            self.candidates.remove(&id)
+        } else {
+            self.candidates.get(&id).cloned()
+        };
 
         // Since the fees will not be known until the payload future is resolved / awaited, we wrap
         // the future in a new future that will update the metrics.
@@ -302,16 +340,28 @@ where
         let payload_events = self.payload_events.clone();
 
         let fut = async move {
-            let res = fut.await;
+            let res = match (fut.await.map(Into::into), candidate) {
+                (Ok(payload), Some(candidate)) if candidate.fees() > payload.fees() => {
+                    debug!(target: "payload_builder", %id, "resolved to more valuable candidate");
+                    resolved_metrics.inc_resolved_candidates();
+                    Ok(candidate)
+                }
+                (Err(err), Some(candidate)) => {
+                    debug!(target: "payload_builder", %id, %err, "resolved to candidate after job failed");
+                    resolved_metrics.inc_resolved_candidates();
+                    Ok(candidate)
+                }
+                (res, _) => res,
+            };
             if let Ok(payload) = &res {
                 if payload_events.receiver_count() > 0 {
-                    payload_events.send(Events::BuiltPayload(payload.clone().into())).ok();
+                    payload_events.send(Events::BuiltPayload(payload.clone())).ok();
                 }
 
                 resolved_metrics
                     .set_resolved_revenue(payload.block().number(), f64::from(payload.fees()));
             }
-            res.map(|p| p.into())
+            res
         };
 
         Some(Box::pin(fut))
@@ -342,6 +392,32 @@ where
 
         attributes
     }
+
+    /// Keeps the given externally built payload if it's the most valuable candidate for the job.
+    fn submit_candidate(
+        &mut self,
+        id: PayloadId,
+        payload: T::BuiltPayload,
+    ) -> Result<(), PayloadBuilderError> {
+        if !self.contains_payload(id) {
+            return Err(PayloadBuilderError::MissingPayload)
+        }
+
+        let fees = payload.fees();
+        match self.candidates.entry(id) {
+            Entry::Occupied(mut entry) => {
+                if fees > entry.get().fees() {
+                    entry.insert(payload);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(payload);
+            }
+        }
+        debug!(target: "payload_builder", %id, %fees, "received payload candidate");
+
+        Ok(())
+    }
 }
 
 impl<Gen, St, T, N> Future for PayloadBuilderService<Gen, St, T>
@@ -373,11 +449,13 @@ where
                 // drain better payloads from the job
                 match job.poll_unpin(cx) {
                     Poll::Ready(Ok(_)) => {
+                        this.candidates.remove(&id);
                         this.metrics.set_active_jobs(this.payload_jobs.len());
                         trace!(target: "payload_builder", %id, "payload job finished");
                     }
                     Poll::Ready(Err(err)) => {
                         warn!(target: "payload_builder",%err, ?id, "Payload builder job failed; resolving payload");
+                        this.candidates.remove(&id);
                         this.metrics.inc_failed_jobs();
                         this.metrics.set_active_jobs(this.payload_jobs.len());
                     }
@@ -436,6 +514,9 @@ where
                         let new_rx = this.payload_events.subscribe();
                         let _ = tx.send(new_rx);
                     }
+                    PayloadServiceCommand::SubmitCandidate(id, payload, tx) => {
+                        let _ = tx.send(this.submit_candidate(id, payload));
+                    }
                 }
             }
 
@@ -468,6 +549,8 @@ pub enum PayloadServiceCommand<T: PayloadTypes> {
     ),
     /// Payload service events
     Subscribe(oneshot::Sender<broadcast::Receiver<Events<T>>>),
+    /// Submit an externally built payload as a candidate for the payload
+    SubmitCandidate(PayloadId, T::BuiltPayload, oneshot::Sender<Result<(), PayloadBuilderError>>),
 }
 
 impl<T> fmt::Debug for PayloadServiceCommand<T>
@@ -487,6 +570,59 @@ where
             }
             Self::Resolve(f0, f1, _f2) => f.debug_tuple("Resolve").field(&f0).field(&f1).finish(),
             Self::Subscribe(f0) => f.debug_tuple("Subscribe").field(&f0).finish(),
+            Self::SubmitCandidate(f0, f1, f2) => {
+                f.debug_tuple("SubmitCandidate").field(&f0).field(&f1).field(&f2).finish()
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::spawn_test_payload_service, EthBuiltPayload};
+    use alloy_consensus::Block;
+    use alloy_primitives::{Address, B256, U256};
+    use alloy_rpc_types::engine::PayloadAttributes;
+    use reth_ethereum_engine_primitives::{EthPayloadBuilderAttributes, EthPayloadTypes};
+    use reth_primitives_traits::Block as _;
+
+    fn payload(id: PayloadId, fees: u64) -> EthBuiltPayload {
+        EthBuiltPayload::new(
+            id,
+            Arc::new(Block::<_>::default().seal_slow()),
+            U256::from(fees),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn resolve_more_valuable_candidate() {
+        let handle = spawn_test_payload_service::<EthPayloadTypes>();
+        let attributes = EthPayloadBuilderAttributes::new(
+            B256::ZERO,
+            PayloadAttributes {
+                timestamp: 1,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            },
+        );
+        let id = handle.send_new_payload(attributes).await.unwrap().unwrap();
+
+        // candidates are only accepted for active jobs
+        assert!(handle.submit_candidate(PayloadId::new([0xff; 8]), payload(id, 1)).await.is_err());
+
+        // only the most valuable candidate is kept
+        handle.submit_candidate(id, payload(id, 2)).await.unwrap();
+        handle.submit_candidate(id, payload(id, 1)).await.unwrap();
+
+        // the test job builds a payload without fees
+        let resolved = handle.resolve_kind(id, PayloadKind::Earliest).await.unwrap().unwrap();
+        assert_eq!(resolved.fees(), U256::from(2));
+
+        // the candidate is gone with the job
+        assert!(handle.submit_candidate(id, payload(id, 3)).await.is_err());
+    }
+}
@@ -91,6 +91,10 @@ pub enum EngineObjectValidationError {
     /// timestamp.
     #[error("Unsupported fork")]
     UnsupportedFork,
+    /// Thrown if well-formed `PayloadAttributes` were rejected by a custom
+    /// [`PayloadAttributesHook`](crate::PayloadAttributesHook).
+    #[error("Payload attributes rejected: {0}")]
+    RejectedPayloadAttributes(Box<dyn core::error::Error + Send + Sync>),
     /// Another type of error that is not covered by the above variants.
     #[error("Invalid params: {0}")]
     InvalidParams(#[from] Box<dyn core::error::Error + Send + Sync>),
@@ -169,6 +173,14 @@ impl EngineObjectValidationError {
     {
         Self::InvalidParams(Box::new(error))
     }
+
+    /// Creates an instance of the `RejectedPayloadAttributes` variant with the given error.
+    pub fn rejected_payload_attributes<E>(error: E) -> Self
+    where
+        E: core::error::Error + Send + Sync + 'static,
+    {
+        Self::RejectedPayloadAttributes(Box::new(error))
+    }
 }
 
 /// Thrown when validating the correctness of a payloadattributes object.
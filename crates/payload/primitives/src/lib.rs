@@ -26,12 +26,12 @@ pub use error::{
 
 mod traits;
 pub use traits::{
-    BuildNextEnv, BuiltPayload, PayloadAttributes, PayloadAttributesBuilder,
+    BuildNextEnv, BuiltPayload, PayloadAttributes, PayloadAttributesBuilder, PayloadAttributesHook,
     PayloadBuilderAttributes,
 };
 
 mod payload;
-pub use payload::{ExecutionPayload, PayloadOrAttributes};
+pub use payload::{ExecutionPayload, ExternalPayload, PayloadOrAttributes};
 
 /// Core trait that defines the associated types for working with execution payloads.
 pub trait PayloadTypes: Send + Sync + Unpin + core::fmt::Debug + Clone + 'static {
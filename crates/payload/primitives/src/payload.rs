@@ -1,11 +1,12 @@
 //! Types and traits for execution payload data structures.
 
 use crate::{MessageValidationKind, PayloadAttributes};
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
 use alloy_eips::{eip4895::Withdrawal, eip7685::Requests};
-use alloy_primitives::B256;
-use alloy_rpc_types_engine::ExecutionData;
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_engine::{ExecutionData, PayloadId};
 use core::fmt::Debug;
+use reth_primitives_traits::{Block, SealedBlock};
 use serde::{de::DeserializeOwned, Serialize};
 
 /// Represents the core data structure of an execution payload.
@@ -72,6 +73,22 @@ impl ExecutionPayload for ExecutionData {
     }
 }
 
+/// A payload that was built outside of the node, e.g. by an external block builder, and submitted
+/// as a candidate for a payload job.
+///
+/// Node specific [`BuiltPayload`](crate::BuiltPayload) types can be created from it.
+#[derive(Debug, Clone)]
+pub struct ExternalPayload<B: Block> {
+    /// Identifier of the payload job the payload was submitted for.
+    pub id: PayloadId,
+    /// The built block.
+    pub block: Arc<SealedBlock<B>>,
+    /// The value of the block, computed by the node.
+    pub value: U256,
+    /// The EIP-7685 requests of the block.
+    pub requests: Option<Requests>,
+}
+
 /// A unified type for handling both execution payloads and payload attributes.
 ///
 /// Enables generic validation and processing logic for both complete payloads
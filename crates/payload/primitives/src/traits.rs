@@ -6,12 +6,14 @@ use alloy_eips::{
     eip7685::Requests,
 };
 use alloy_primitives::{Address, B256, U256};
-use alloy_rpc_types_engine::{PayloadAttributes as EthPayloadAttributes, PayloadId};
+use alloy_rpc_types_engine::{
+    ForkchoiceState, PayloadAttributes as EthPayloadAttributes, PayloadId,
+};
 use core::fmt;
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_primitives_traits::{NodePrimitives, SealedBlock, SealedHeader};
 
-use crate::PayloadBuilderError;
+use crate::{EngineObjectValidationError, PayloadBuilderError};
 
 /// Represents a successfully built execution payload (block).
 ///
@@ -147,6 +149,52 @@ pub trait PayloadAttributesBuilder<Attributes>: Send + Sync + 'static {
     fn build(&self, timestamp: u64) -> Attributes;
 }
 
+/// Hook that is invoked with the payload attributes of a forkchoice update before they are handed
+/// to the payload builder.
+///
+/// This allows enforcing constraints on the attributes that are not part of the engine API, e.g.
+/// an allowlist of fee recipients or constraints imposed by an external block builder, and can
+/// modify the attributes before a payload build process is started.
+///
+/// The hook is only invoked with attributes that already passed the engine API validation. If it
+/// returns an error, the forkchoice update is still applied, but no payload build process is
+/// started.
+pub trait PayloadAttributesHook<Attributes>: Send + Sync + 'static {
+    /// Validates the payload attributes for the given forkchoice state and returns the attributes
+    /// that should be used to build the payload.
+    fn on_payload_attributes(
+        &self,
+        state: &ForkchoiceState,
+        attributes: Attributes,
+    ) -> Result<Attributes, EngineObjectValidationError>;
+}
+
+impl<Attributes> PayloadAttributesHook<Attributes> for () {
+    fn on_payload_attributes(
+        &self,
+        _state: &ForkchoiceState,
+        attributes: Attributes,
+    ) -> Result<Attributes, EngineObjectValidationError> {
+        Ok(attributes)
+    }
+}
+
+impl<Attributes, F> PayloadAttributesHook<Attributes> for F
+where
+    F: Fn(&ForkchoiceState, Attributes) -> Result<Attributes, EngineObjectValidationError>
+        + Send
+        + Sync
+        + 'static,
+{
+    fn on_payload_attributes(
+        &self,
+        state: &ForkchoiceState,
+        attributes: Attributes,
+    ) -> Result<Attributes, EngineObjectValidationError> {
+        self(state, attributes)
+    }
+}
+
 /// Trait to build the EVM environment for the next block from the given payload attributes.
 ///
 /// Accepts payload attributes from CL, parent header and additional payload builder context.
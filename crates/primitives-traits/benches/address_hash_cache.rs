@@ -0,0 +1,56 @@
+#![allow(missing_docs)]
+use alloy_primitives::{keccak256, Address};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rand::Rng;
+use reth_primitives_traits::hash_cache::{AddressHashCache, DEFAULT_ADDRESS_HASH_CACHE_SLOTS};
+use std::hint::black_box;
+
+/// Hashes a block worth of accounts, where a share of the accounts is drawn from a small set of
+/// hot accounts that are touched in every block.
+pub fn address_hash_cache(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Address Hash Cache");
+    let mut rng = rand::rng();
+    let hot = (0..512).map(|_| Address::from(rng.random::<[u8; 20]>())).collect::<Vec<_>>();
+
+    for hot_percent in [0, 50, 90] {
+        let addresses = (0..4096)
+            .map(|i| {
+                if rng.random_range(0..100) < hot_percent {
+                    hot[i % hot.len()]
+                } else {
+                    Address::from(rng.random::<[u8; 20]>())
+                }
+            })
+            .collect::<Vec<_>>();
+
+        group.bench_function(BenchmarkId::new("keccak256", hot_percent), |b| {
+            b.iter(|| {
+                for address in &addresses {
+                    black_box(keccak256(address));
+                }
+            })
+        });
+
+        // the cache only holds the hot accounts of previous blocks
+        group.bench_function(BenchmarkId::new("cached", hot_percent), |b| {
+            b.iter_batched(
+                || {
+                    let cache = AddressHashCache::new(DEFAULT_ADDRESS_HASH_CACHE_SLOTS);
+                    hot.iter().for_each(|address| {
+                        cache.hash(address);
+                    });
+                    cache
+                },
+                |cache| {
+                    for address in &addresses {
+                        black_box(cache.hash(address));
+                    }
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+}
+
+criterion_group!(benches, address_hash_cache);
+criterion_main!(benches);
@@ -1,6 +1,8 @@
 //! Block header data primitive.
 
-use crate::{InMemorySize, MaybeCompact, MaybeSerde, MaybeSerdeBincodeCompat};
+use crate::{
+    header::NoExtraFields, InMemorySize, MaybeCompact, MaybeSerde, MaybeSerdeBincodeCompat,
+};
 use alloy_primitives::Sealable;
 use core::{fmt, hash::Hash};
 
@@ -13,6 +15,49 @@ pub trait FullBlockHeader: BlockHeader + MaybeCompact {}
 
 impl<T> FullBlockHeader for T where T: BlockHeader + MaybeCompact {}
 
+/// Chain-specific fields that extend a block header.
+///
+/// The fields are part of the header's RLP and compact encodings, see
+/// [`HeaderWithExtra`](crate::header::HeaderWithExtra) for a header that extends the ethereum
+/// header with extra fields.
+pub trait HeaderExtraFields:
+    Send
+    + Sync
+    + Unpin
+    + Clone
+    + Hash
+    + Default
+    + fmt::Debug
+    + PartialEq
+    + Eq
+    + alloy_rlp::Encodable
+    + alloy_rlp::Decodable
+    + InMemorySize
+    + MaybeSerde
+    + MaybeCompact
+    + 'static
+{
+}
+
+impl<T> HeaderExtraFields for T where
+    T: Send
+        + Sync
+        + Unpin
+        + Clone
+        + Hash
+        + Default
+        + fmt::Debug
+        + PartialEq
+        + Eq
+        + alloy_rlp::Encodable
+        + alloy_rlp::Decodable
+        + InMemorySize
+        + MaybeSerde
+        + MaybeCompact
+        + 'static
+{
+}
+
 /// Abstraction of a block header.
 pub trait BlockHeader:
     Send
@@ -37,3 +82,20 @@ pub trait BlockHeader:
 }
 
 impl BlockHeader for alloy_consensus::Header {}
+
+/// A [`BlockHeader`] that exposes its chain-specific extra fields.
+pub trait ExtendedBlockHeader: BlockHeader {
+    /// Chain-specific fields that extend the header, [`NoExtraFields`] if the header has none.
+    type ExtraFields: HeaderExtraFields;
+
+    /// Returns the chain-specific extra fields of the header.
+    fn extra_fields(&self) -> &Self::ExtraFields;
+}
+
+impl ExtendedBlockHeader for alloy_consensus::Header {
+    type ExtraFields = NoExtraFields;
+
+    fn extra_fields(&self) -> &Self::ExtraFields {
+        &NoExtraFields
+    }
+}
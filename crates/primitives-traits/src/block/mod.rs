@@ -38,7 +38,8 @@ use alloy_rlp::{Decodable, Encodable};
 
 use crate::{
     block::error::BlockRecoveryError, transaction::signed::RecoveryError, BlockBody, BlockHeader,
-    FullBlockBody, FullBlockHeader, InMemorySize, MaybeSerde, SealedHeader, SignedTransaction,
+    ExtendedBlockHeader, FullBlockBody, FullBlockHeader, InMemorySize, MaybeSerde, SealedHeader,
+    SignedTransaction,
 };
 
 /// Bincode-compatible header type serde implementations.
@@ -122,6 +123,14 @@ pub trait Block:
     /// Returns reference to block body.
     fn body(&self) -> &Self::Body;
 
+    /// Returns the chain-specific extra fields of the block header.
+    fn header_extra_fields(&self) -> &<Self::Header as ExtendedBlockHeader>::ExtraFields
+    where
+        Self::Header: ExtendedBlockHeader,
+    {
+        self.header().extra_fields()
+    }
+
     /// Splits the block into its header and body.
     fn split(self) -> (Self::Header, Self::Body);
 
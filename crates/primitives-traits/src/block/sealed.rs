@@ -162,6 +162,24 @@ impl<B: Block> SealedBlock<B> {
         RecoveredBlock::try_recover_sealed_unchecked(self)
     }
 
+    /// Recovers the senders from the transactions in the block like [`Self::try_recover`], using
+    /// the cached sender of transactions that were recovered before.
+    ///
+    /// Returns an error if any of the transactions fail to recover the sender.
+    #[cfg(feature = "std")]
+    pub fn try_recover_with_cache(
+        self,
+        cache: &crate::transaction::sender_cache::SenderRecoveryCache,
+    ) -> Result<RecoveredBlock<B>, BlockRecoveryError<Self>> {
+        match crate::transaction::recover::recover_signers_with_cache(
+            self.body().transactions(),
+            cache,
+        ) {
+            Ok(senders) => Ok(RecoveredBlock::new_sealed(self, senders)),
+            Err(_) => Err(BlockRecoveryError::new(self)),
+        }
+    }
+
     /// Returns reference to block header.
     pub const fn header(&self) -> &B::Header {
         self.header.header()
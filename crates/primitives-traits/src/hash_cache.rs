@@ -0,0 +1,114 @@
+//! Cache of hashed addresses.
+//!
+//! Accounts are keyed by `keccak256(address)` in the hashed state and in the state trie, so the
+//! addresses of hot accounts are hashed over and over again: when the hashed post state of a block
+//! is built, when the state root is computed and when the hashing stages run. [`AddressHashCache`]
+//! keeps the hashes of recently hashed addresses in a small, fixed-size table.
+
+use alloy_primitives::{keccak256, Address, B256};
+use parking_lot::Mutex;
+use std::sync::OnceLock;
+
+/// Default number of slots of the [global](AddressHashCache::global) cache.
+pub const DEFAULT_ADDRESS_HASH_CACHE_SLOTS: usize = 1 << 13;
+
+/// The cache shared by all subsystems of the node.
+static GLOBAL: OnceLock<AddressHashCache> = OnceLock::new();
+
+/// A fixed-size, lossy cache of `keccak256(address)`.
+///
+/// Every address maps to a single slot, so an address evicts the previous address of its slot.
+/// Addresses are uniformly distributed, which makes the cache behave like a probabilistic set of
+/// the most recently hashed addresses. Lookups never block: if the slot of an address is locked by
+/// another thread, the address is hashed without the cache.
+#[derive(Debug)]
+pub struct AddressHashCache {
+    slots: Box<[Mutex<Option<(Address, B256)>>]>,
+    mask: usize,
+}
+
+impl AddressHashCache {
+    /// Creates a new cache with the given number of slots, rounded up to the next power of two.
+    pub fn new(slots: usize) -> Self {
+        let slots = slots.max(1).next_power_of_two();
+        Self { slots: (0..slots).map(|_| Mutex::new(None)).collect(), mask: slots - 1 }
+    }
+
+    /// Initializes the [global](Self::global) cache with the given number of slots.
+    ///
+    /// Returns `false` if the global cache was already initialized.
+    pub fn init_global(slots: usize) -> bool {
+        GLOBAL.set(Self::new(slots)).is_ok()
+    }
+
+    /// Returns the cache that is shared by the hashing stages and the state root computation.
+    ///
+    /// Unless configured with [`Self::init_global`], the cache has
+    /// [`DEFAULT_ADDRESS_HASH_CACHE_SLOTS`] slots.
+    pub fn global() -> &'static Self {
+        GLOBAL.get_or_init(|| Self::new(DEFAULT_ADDRESS_HASH_CACHE_SLOTS))
+    }
+
+    /// Returns the number of slots of the cache.
+    pub fn slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `keccak256(address)`, using the cached hash if the address was recently hashed.
+    pub fn hash(&self, address: &Address) -> B256 {
+        let slot = &self.slots[self.slot_index(address)];
+        if let Some(entry) = slot.try_lock() {
+            if let Some((cached, hash)) = *entry {
+                if cached == *address {
+                    return hash
+                }
+            }
+        }
+
+        let hash = keccak256(address);
+        if let Some(mut entry) = slot.try_lock() {
+            *entry = Some((*address, hash));
+        }
+        hash
+    }
+
+    fn slot_index(&self, address: &Address) -> usize {
+        let mut index = [0; 8];
+        index.copy_from_slice(&address[..8]);
+        u64::from_le_bytes(index) as usize & self.mask
+    }
+}
+
+impl Default for AddressHashCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_ADDRESS_HASH_CACHE_SLOTS)
+    }
+}
+
+/// Returns `keccak256(address)`, using the [global](AddressHashCache::global) cache.
+#[inline]
+pub fn keccak256_address(address: &Address) -> B256 {
+    AddressHashCache::global().hash(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_hash_cache() {
+        let cache = AddressHashCache::new(3);
+        assert_eq!(cache.slots(), 4);
+
+        let address = Address::with_last_byte(1);
+        assert_eq!(cache.hash(&address), keccak256(address));
+        // served from the cache
+        assert_eq!(cache.hash(&address), keccak256(address));
+
+        // addresses that share a slot evict each other
+        let other = Address::with_last_byte(2);
+        assert_eq!(cache.slot_index(&address), cache.slot_index(&other));
+        assert_eq!(cache.hash(&other), keccak256(other));
+        assert_eq!(cache.hash(&address), keccak256(address));
+    }
+}
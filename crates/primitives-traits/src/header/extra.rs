@@ -0,0 +1,354 @@
+//! Headers with chain-specific extra fields.
+
+use crate::{BlockHeader, ExtendedBlockHeader, HeaderExtraFields, InMemorySize};
+use alloy_consensus::Header;
+use alloy_primitives::{keccak256, Address, BlockNumber, Bloom, Bytes, Sealable, B256, B64, U256};
+use alloy_rlp::{Decodable, Encodable};
+use bytes::BufMut;
+use derive_more::{AsRef, Deref};
+
+/// Extra fields of headers that don't extend the regular header.
+///
+/// This is encoded as nothing, both in RLP and compact encoding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoExtraFields;
+
+impl Encodable for NoExtraFields {
+    fn encode(&self, _out: &mut dyn BufMut) {}
+
+    fn length(&self) -> usize {
+        0
+    }
+}
+
+impl Decodable for NoExtraFields {
+    fn decode(_buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl InMemorySize for NoExtraFields {
+    fn size(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for NoExtraFields {
+    fn to_compact<B>(&self, _buf: &mut B) -> usize
+    where
+        B: BufMut + AsMut<[u8]>,
+    {
+        0
+    }
+
+    fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        (Self, buf)
+    }
+}
+
+/// An ethereum [`Header`] that is extended with chain-specific extra fields.
+///
+/// This removes the need to wrap the header in a newtype that implements all header traits, a
+/// chain only needs to define its extra fields:
+///
+/// ```rust
+/// # use alloy_rlp::{RlpDecodable, RlpEncodable};
+/// # use reth_primitives_traits::HeaderWithExtra;
+/// #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable)]
+/// struct MyExtraFields {
+///     l1_block_number: u64,
+/// }
+///
+/// type MyHeader = HeaderWithExtra<MyExtraFields>;
+///
+/// let header = MyHeader::new(Default::default(), MyExtraFields { l1_block_number: 1 });
+/// assert_eq!(header.extra().l1_block_number, 1);
+/// ```
+///
+/// The header implements [`BlockHeader`] if the extra fields implement [`HeaderExtraFields`].
+/// The header is RLP encoded as a list of the regular header followed by the extra fields, which
+/// also defines its hash. With the `serde` feature, the extra fields are flattened into the
+/// regular header fields, so they must serialize as a map.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, AsRef, Deref)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeaderWithExtra<X = NoExtraFields> {
+    /// The regular ethereum header.
+    #[as_ref]
+    #[deref]
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub inner: Header,
+    /// The chain-specific extra fields.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub extra: X,
+}
+
+impl<X> HeaderWithExtra<X> {
+    /// Creates a new header from the regular header and the extra fields.
+    pub const fn new(inner: Header, extra: X) -> Self {
+        Self { inner, extra }
+    }
+
+    /// Returns the regular ethereum header.
+    pub const fn inner(&self) -> &Header {
+        &self.inner
+    }
+
+    /// Returns the chain-specific extra fields.
+    pub const fn extra(&self) -> &X {
+        &self.extra
+    }
+
+    /// Splits the header into the regular header and the extra fields.
+    pub fn into_parts(self) -> (Header, X) {
+        (self.inner, self.extra)
+    }
+
+    /// Returns the RLP payload length of the header.
+    fn payload_length(&self) -> usize
+    where
+        X: Encodable,
+    {
+        self.inner.length() + self.extra.length()
+    }
+}
+
+impl<X: Default> From<Header> for HeaderWithExtra<X> {
+    fn from(inner: Header) -> Self {
+        Self { inner, extra: X::default() }
+    }
+}
+
+impl<X> AsRef<Self> for HeaderWithExtra<X> {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl<X: Encodable> Encodable for HeaderWithExtra<X> {
+    fn encode(&self, out: &mut dyn BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.inner.encode(out);
+        self.extra.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl<X: Decodable> Decodable for HeaderWithExtra<X> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let rlp_head = alloy_rlp::Header::decode(buf)?;
+        if !rlp_head.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+        let this = Self { inner: Header::decode(buf)?, extra: X::decode(buf)? };
+        let consumed = started_len - buf.len();
+        if consumed != rlp_head.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: rlp_head.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(this)
+    }
+}
+
+impl<X: Encodable> Sealable for HeaderWithExtra<X> {
+    fn hash_slow(&self) -> B256 {
+        let mut out = alloc::vec::Vec::with_capacity(self.length());
+        self.encode(&mut out);
+        keccak256(&out)
+    }
+}
+
+impl<X: InMemorySize> InMemorySize for HeaderWithExtra<X> {
+    fn size(&self) -> usize {
+        self.inner.size() + self.extra.size()
+    }
+}
+
+/// The extra fields are encoded before the regular header, prefixed with their identifier, since
+/// the extra data of the regular header consumes the rest of the buffer.
+#[cfg(any(test, feature = "reth-codec"))]
+impl<X: reth_codecs::Compact> reth_codecs::Compact for HeaderWithExtra<X> {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: BufMut + AsMut<[u8]>,
+    {
+        let mut extra = alloc::vec::Vec::new();
+        let identifier = self.extra.to_compact(&mut extra);
+        let mut prefix = alloc::vec::Vec::new();
+        reth_codecs::encode_varuint(identifier, &mut prefix);
+
+        buf.put_slice(&prefix);
+        buf.put_slice(&extra);
+        prefix.len() + extra.len() + reth_codecs::Compact::to_compact(&self.inner, buf)
+    }
+
+    fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let (identifier, buf) = reth_codecs::decode_varuint(buf);
+        let (extra, buf) = X::from_compact(buf, identifier);
+        let (inner, buf) = reth_codecs::Compact::from_compact(buf, buf.len());
+        (Self { inner, extra }, buf)
+    }
+}
+
+#[cfg(feature = "serde-bincode-compat")]
+impl<X: Encodable + Decodable> crate::serde_bincode_compat::RlpBincode for HeaderWithExtra<X> {}
+
+impl<X> alloy_consensus::BlockHeader for HeaderWithExtra<X> {
+    fn parent_hash(&self) -> B256 {
+        self.inner.parent_hash()
+    }
+
+    fn ommers_hash(&self) -> B256 {
+        self.inner.ommers_hash()
+    }
+
+    fn beneficiary(&self) -> Address {
+        self.inner.beneficiary()
+    }
+
+    fn state_root(&self) -> B256 {
+        self.inner.state_root()
+    }
+
+    fn transactions_root(&self) -> B256 {
+        self.inner.transactions_root()
+    }
+
+    fn receipts_root(&self) -> B256 {
+        self.inner.receipts_root()
+    }
+
+    fn withdrawals_root(&self) -> Option<B256> {
+        self.inner.withdrawals_root()
+    }
+
+    fn logs_bloom(&self) -> Bloom {
+        self.inner.logs_bloom()
+    }
+
+    fn difficulty(&self) -> U256 {
+        self.inner.difficulty()
+    }
+
+    fn number(&self) -> BlockNumber {
+        self.inner.number()
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.inner.gas_limit()
+    }
+
+    fn gas_used(&self) -> u64 {
+        self.inner.gas_used()
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.inner.timestamp()
+    }
+
+    fn mix_hash(&self) -> Option<B256> {
+        self.inner.mix_hash()
+    }
+
+    fn nonce(&self) -> Option<B64> {
+        self.inner.nonce()
+    }
+
+    fn base_fee_per_gas(&self) -> Option<u64> {
+        self.inner.base_fee_per_gas()
+    }
+
+    fn blob_gas_used(&self) -> Option<u64> {
+        self.inner.blob_gas_used()
+    }
+
+    fn excess_blob_gas(&self) -> Option<u64> {
+        self.inner.excess_blob_gas()
+    }
+
+    fn parent_beacon_block_root(&self) -> Option<B256> {
+        self.inner.parent_beacon_block_root()
+    }
+
+    fn requests_hash(&self) -> Option<B256> {
+        self.inner.requests_hash()
+    }
+
+    fn extra_data(&self) -> &Bytes {
+        self.inner.extra_data()
+    }
+}
+
+impl<X: HeaderExtraFields> BlockHeader for HeaderWithExtra<X> {}
+
+impl<X: HeaderExtraFields> ExtendedBlockHeader for HeaderWithExtra<X> {
+    type ExtraFields = X;
+
+    fn extra_fields(&self) -> &Self::ExtraFields {
+        &self.extra
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rlp_roundtrip() {
+        let header = HeaderWithExtra::new(Header { number: 1, ..Default::default() }, 7u64);
+        let encoded = alloy_rlp::encode(&header);
+        assert_eq!(encoded.len(), header.length());
+        assert_eq!(HeaderWithExtra::<u64>::decode(&mut encoded.as_slice()).unwrap(), header);
+
+        // the hash commits to the extra fields
+        let other = HeaderWithExtra::new(header.inner.clone(), 8u64);
+        assert_ne!(header.hash_slow(), other.hash_slow());
+    }
+
+    #[test]
+    fn compact_roundtrip() {
+        use reth_codecs::Compact;
+
+        let inner = Header {
+            number: 1,
+            extra_data: Bytes::from_static(b"extra data"),
+            requests_hash: Some(B256::with_last_byte(1)),
+            ..Default::default()
+        };
+        for extra in [0u64, 7, u64::MAX] {
+            let header = HeaderWithExtra::new(inner.clone(), extra);
+            let mut buf = alloc::vec::Vec::new();
+            let len = header.to_compact(&mut buf);
+            assert_eq!(len, buf.len());
+
+            let (decoded, rest) = HeaderWithExtra::<u64>::from_compact(&buf, len);
+            assert_eq!(decoded, header);
+            assert!(rest.is_empty());
+        }
+
+        let header = HeaderWithExtra::<NoExtraFields>::from(inner);
+        let mut buf = alloc::vec::Vec::new();
+        let len = header.to_compact(&mut buf);
+        assert_eq!(HeaderWithExtra::<NoExtraFields>::from_compact(&buf, len).0, header);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn no_extra_fields() {
+        let header = HeaderWithExtra::<NoExtraFields>::from(Header::default());
+        assert_eq!(header.extra_fields(), &NoExtraFields);
+        assert_eq!(header.payload_length(), header.inner.length());
+
+        let json = serde_json::to_value(&header).unwrap();
+        assert_eq!(json, serde_json::to_value(&header.inner).unwrap());
+        assert_eq!(serde_json::from_value::<HeaderWithExtra>(json).unwrap(), header);
+    }
+}
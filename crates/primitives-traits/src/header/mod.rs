@@ -1,6 +1,9 @@
 mod sealed;
 pub use sealed::{Header, SealedHeader, SealedHeaderFor};
 
+mod extra;
+pub use extra::{HeaderWithExtra, NoExtraFields};
+
 #[cfg(any(test, feature = "test-utils", feature = "arbitrary"))]
 pub mod test_utils;
 
@@ -143,7 +143,9 @@ pub use transaction::{
 pub mod block;
 pub use block::{
     body::{BlockBody, FullBlockBody},
-    header::{AlloyBlockHeader, BlockHeader, FullBlockHeader},
+    header::{
+        AlloyBlockHeader, BlockHeader, ExtendedBlockHeader, FullBlockHeader, HeaderExtraFields,
+    },
     Block, FullBlock, RecoveredBlock, SealedBlock,
 };
 
@@ -152,6 +154,9 @@ pub use alloy_eips::eip2718::WithEncoded;
 
 pub mod crypto;
 
+#[cfg(feature = "std")]
+pub mod hash_cache;
+
 mod error;
 pub use error::{GotExpected, GotExpectedBoxed};
 
@@ -169,7 +174,7 @@ mod extended;
 pub use extended::Extended;
 /// Common header types
 pub mod header;
-pub use header::{Header, SealedHeader, SealedHeaderFor};
+pub use header::{Header, HeaderWithExtra, NoExtraFields, SealedHeader, SealedHeaderFor};
 
 /// Bincode-compatible serde implementations for common abstracted types in Reth.
 ///
@@ -187,7 +192,10 @@ pub use size::InMemorySize;
 
 /// Node traits
 pub mod node;
-pub use node::{BlockTy, BodyTy, FullNodePrimitives, HeaderTy, NodePrimitives, ReceiptTy, TxTy};
+pub use node::{
+    BlockTy, BodyTy, FullNodePrimitives, HeaderExtraFieldsTy, HeaderTy, NodePrimitives, ReceiptTy,
+    TxTy,
+};
 
 /// Helper trait that requires de-/serialize implementation since `serde` feature is enabled.
 #[cfg(feature = "serde")]
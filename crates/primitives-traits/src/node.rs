@@ -1,6 +1,6 @@
 use crate::{
-    Block, FullBlock, FullBlockBody, FullBlockHeader, FullReceipt, FullSignedTx,
-    MaybeSerdeBincodeCompat, Receipt,
+    Block, ExtendedBlockHeader, FullBlock, FullBlockBody, FullBlockHeader, FullReceipt,
+    FullSignedTx, MaybeSerdeBincodeCompat, Receipt,
 };
 use core::fmt;
 
@@ -69,6 +69,10 @@ impl<T> FullNodePrimitives for T where
 /// Helper adapter type for accessing [`NodePrimitives`] block header types.
 pub type HeaderTy<N> = <N as NodePrimitives>::BlockHeader;
 
+/// Helper adapter type for accessing the chain-specific extra fields of [`NodePrimitives`] block
+/// headers that implement [`ExtendedBlockHeader`].
+pub type HeaderExtraFieldsTy<N> = <HeaderTy<N> as ExtendedBlockHeader>::ExtraFields;
+
 /// Helper adapter type for accessing [`NodePrimitives`] block body types.
 pub type BodyTy<N> = <N as NodePrimitives>::BlockBody;
 
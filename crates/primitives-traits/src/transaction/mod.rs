@@ -18,6 +18,9 @@ pub mod signed;
 pub mod error;
 pub mod recover;
 
+#[cfg(feature = "std")]
+pub mod sender_cache;
+
 pub use alloy_consensus::transaction::{SignerRecoverable, TransactionInfo, TransactionMeta};
 
 use crate::{InMemorySize, MaybeCompact, MaybeSerde};
@@ -11,6 +11,9 @@ mod rayon {
     use crate::{transaction::signed::RecoveryError, SignedTransaction};
     use alloc::vec::Vec;
     use alloy_primitives::Address;
+
+    #[cfg(feature = "std")]
+    use crate::transaction::sender_cache::SenderRecoveryCache;
     use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
     /// Recovers a list of signers from a transaction list iterator.
@@ -35,6 +38,22 @@ mod rayon {
     {
         txes.into_par_iter().map(|tx| tx.recover_signer_unchecked()).collect()
     }
+
+    /// Recovers a list of signers from a transaction list iterator, using the cached sender of
+    /// transactions that were recovered before.
+    ///
+    /// Returns `Err(RecoveryError)`, if some transaction's signature is invalid.
+    #[cfg(feature = "std")]
+    pub fn recover_signers_with_cache<'a, I, T>(
+        txes: I,
+        cache: &SenderRecoveryCache,
+    ) -> Result<Vec<Address>, RecoveryError>
+    where
+        T: SignedTransaction,
+        I: IntoParallelIterator<Item = &'a T> + IntoIterator<Item = &'a T> + Send,
+    {
+        txes.into_par_iter().map(|tx| cache.recover_signer(tx)).collect()
+    }
 }
 
 #[cfg(not(feature = "rayon"))]
@@ -43,6 +62,9 @@ mod iter {
     use alloc::vec::Vec;
     use alloy_primitives::Address;
 
+    #[cfg(feature = "std")]
+    use crate::transaction::sender_cache::SenderRecoveryCache;
+
     /// Recovers a list of signers from a transaction list iterator.
     ///
     /// Returns `Err(RecoveryError)`, if some transaction's signature is invalid
@@ -65,4 +87,20 @@ mod iter {
     {
         txes.into_iter().map(|tx| tx.recover_signer_unchecked()).collect()
     }
+
+    /// Recovers a list of signers from a transaction list iterator, using the cached sender of
+    /// transactions that were recovered before.
+    ///
+    /// Returns `Err(RecoveryError)`, if some transaction's signature is invalid.
+    #[cfg(feature = "std")]
+    pub fn recover_signers_with_cache<'a, I, T>(
+        txes: I,
+        cache: &SenderRecoveryCache,
+    ) -> Result<Vec<Address>, RecoveryError>
+    where
+        T: SignedTransaction,
+        I: IntoIterator<Item = &'a T>,
+    {
+        txes.into_iter().map(|tx| cache.recover_signer(tx)).collect()
+    }
 }
@@ -0,0 +1,214 @@
+//! Cache of recovered transaction senders.
+//!
+//! Recovering the sender of a transaction is an expensive elliptic curve operation. The same
+//! transaction is typically recovered several times: when it's submitted via RPC or received from
+//! a peer, and again when the block that includes it is validated. [`SenderRecoveryCache`] maps
+//! transaction hashes to their recovered senders, so that a sender only needs to be recovered
+//! once.
+
+use crate::{transaction::signed::RecoveryError, SignedTransaction};
+use alloy_primitives::{Address, TxHash};
+use core::sync::atomic::{AtomicU64, Ordering};
+use parking_lot::Mutex;
+use schnellru::{ByLength, LruMap};
+use std::sync::{Arc, LazyLock};
+
+/// Default number of senders kept by the [global](SenderRecoveryCache::global) cache.
+pub const DEFAULT_SENDER_CACHE_SIZE: u32 = 100_000;
+
+/// Number of independently locked shards of the cache.
+const SHARDS: usize = 64;
+
+/// The cache shared by all subsystems of the node.
+static GLOBAL: LazyLock<SenderRecoveryCache> =
+    LazyLock::new(|| SenderRecoveryCache::new(DEFAULT_SENDER_CACHE_SIZE));
+
+/// A shared LRU cache of transaction hash to recovered sender.
+///
+/// Only senders recovered with [`SignedTransaction::recover_signer`] are cached, so a cached
+/// sender is valid for both the checked and the unchecked recovery of the transaction. The hash of
+/// a transaction commits to its signature, so the cached sender can't be stale.
+///
+/// The entries are split into shards by their hash, each with its own lock and LRU order, so that
+/// senders can be recovered concurrently, e.g. from rayon workers.
+///
+/// The cache is cheap to clone, clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct SenderRecoveryCache {
+    inner: Arc<SenderRecoveryCacheInner>,
+}
+
+#[derive(Debug)]
+struct SenderRecoveryCacheInner {
+    shards: Box<[Mutex<LruMap<TxHash, Address, ByLength>>]>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    #[cfg(feature = "metrics")]
+    metrics: SenderRecoveryCacheMetrics,
+}
+
+impl SenderRecoveryCache {
+    /// Creates a new cache that holds at most `max_senders` entries.
+    pub fn new(max_senders: u32) -> Self {
+        let shard_size = max_senders.div_ceil(SHARDS as u32);
+        Self {
+            inner: Arc::new(SenderRecoveryCacheInner {
+                shards: (0..SHARDS)
+                    .map(|_| Mutex::new(LruMap::new(ByLength::new(shard_size))))
+                    .collect(),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                #[cfg(feature = "metrics")]
+                metrics: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the cache that is shared by transaction pool validation, block execution and RPC.
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Returns the shard of the transaction with the given hash.
+    fn shard(&self, hash: &TxHash) -> &Mutex<LruMap<TxHash, Address, ByLength>> {
+        // transaction hashes are uniformly distributed
+        &self.inner.shards[hash[0] as usize % SHARDS]
+    }
+
+    /// Returns the cached sender of the transaction with the given hash, if any.
+    pub fn get(&self, hash: &TxHash) -> Option<Address> {
+        let sender = self.shard(hash).lock().get(hash).copied();
+        self.record(sender.is_some());
+        sender
+    }
+
+    /// Caches the sender of the transaction with the given hash.
+    ///
+    /// The sender must have been recovered with [`SignedTransaction::recover_signer`].
+    pub fn insert(&self, hash: TxHash, sender: Address) {
+        self.shard(&hash).lock().insert(hash, sender);
+    }
+
+    /// Recovers the sender of the transaction, or returns the cached sender.
+    ///
+    /// See also [`SignedTransaction::recover_signer`].
+    pub fn recover_signer<T: SignedTransaction>(&self, tx: &T) -> Result<Address, RecoveryError> {
+        let hash = *tx.tx_hash();
+        if let Some(sender) = self.get(&hash) {
+            return Ok(sender)
+        }
+        let sender = tx.recover_signer()?;
+        self.insert(hash, sender);
+        Ok(sender)
+    }
+
+    /// Returns the number of cached senders.
+    pub fn len(&self) -> usize {
+        self.inner.shards.iter().map(|shard| shard.lock().len()).sum()
+    }
+
+    /// Returns `true` if no senders are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the hit and miss counts of the cache.
+    pub fn stats(&self) -> SenderRecoveryCacheStats {
+        SenderRecoveryCacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn record(&self, hit: bool) {
+        if hit {
+            self.inner.hits.fetch_add(1, Ordering::Relaxed);
+            #[cfg(feature = "metrics")]
+            self.inner.metrics.hits.increment(1);
+        } else {
+            self.inner.misses.fetch_add(1, Ordering::Relaxed);
+            #[cfg(feature = "metrics")]
+            self.inner.metrics.misses.increment(1);
+        }
+    }
+}
+
+impl Default for SenderRecoveryCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_SENDER_CACHE_SIZE)
+    }
+}
+
+/// Hit and miss counts of a [`SenderRecoveryCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SenderRecoveryCacheStats {
+    /// Number of lookups that returned a cached sender.
+    pub hits: u64,
+    /// Number of lookups that required recovering the sender.
+    pub misses: u64,
+}
+
+impl SenderRecoveryCacheStats {
+    /// Returns the share of lookups that returned a cached sender, or `0.0` if there were no
+    /// lookups.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+/// Metrics for the [`SenderRecoveryCache`].
+#[cfg(feature = "metrics")]
+#[derive(reth_metrics::Metrics)]
+#[metrics(scope = "sender_recovery_cache")]
+struct SenderRecoveryCacheMetrics {
+    /// Number of lookups that returned a cached sender
+    hits: reth_metrics::metrics::Counter,
+    /// Number of lookups that required recovering the sender
+    misses: reth_metrics::metrics::Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Signed, TxEnvelope, TxLegacy};
+    use alloy_primitives::{address, Signature, TxKind, U256};
+
+    #[test]
+    fn recover_signer_cached() {
+        // example transaction of EIP-155
+        let tx = TxLegacy {
+            chain_id: Some(1),
+            nonce: 9,
+            gas_price: 20_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(address!("0x3535353535353535353535353535353535353535")),
+            value: U256::from(1_000_000_000_000_000_000u128),
+            input: Default::default(),
+        };
+        let signature = Signature::new(
+            "18515461264373351373200002665853028612451056578545711640558177340181847433846"
+                .parse()
+                .unwrap(),
+            "46948507304638947509940763649030358759909902576025900602547168820602576006531"
+                .parse()
+                .unwrap(),
+            false,
+        );
+        let tx = TxEnvelope::Legacy(Signed::new_unhashed(tx, signature));
+        let expected = address!("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+
+        let cache = SenderRecoveryCache::new(10);
+        assert_eq!(cache.recover_signer(&tx).unwrap(), expected);
+        assert_eq!(cache.stats(), SenderRecoveryCacheStats { hits: 0, misses: 1 });
+
+        // clones share the cached senders
+        assert_eq!(cache.clone().recover_signer(&tx).unwrap(), expected);
+        assert_eq!(cache.stats(), SenderRecoveryCacheStats { hits: 1, misses: 1 });
+        assert_eq!(cache.stats().hit_rate(), 0.5);
+        assert_eq!(cache.len(), 1);
+    }
+}
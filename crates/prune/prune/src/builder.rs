@@ -10,6 +10,7 @@ use reth_provider::{
     NodePrimitivesProvider, PruneCheckpointWriter, StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
+use reth_tasks::supervisor::SupervisionPolicy;
 use std::time::Duration;
 use tokio::sync::watch;
 
@@ -26,6 +27,8 @@ pub struct PrunerBuilder {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// The policy applied if a pruner run panics.
+    supervision: Option<SupervisionPolicy>,
 }
 
 impl PrunerBuilder {
@@ -75,6 +78,12 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the policy applied if a pruner run panics.
+    pub const fn supervision(mut self, policy: SupervisionPolicy) -> Self {
+        self.supervision = Some(policy);
+        self
+    }
+
     /// Builds a [Pruner] from the current configuration with the given provider factory.
     pub fn build_with_provider_factory<PF>(self, provider_factory: PF) -> Pruner<PF::ProviderRW, PF>
     where
@@ -91,14 +100,19 @@ impl PrunerBuilder {
         let segments =
             SegmentSet::from_components(provider_factory.static_file_provider(), self.segments);
 
-        Pruner::new_with_factory(
+        let pruner = Pruner::new_with_factory(
             provider_factory,
             segments.into_vec(),
             self.block_interval,
             self.delete_limit,
             self.timeout,
             self.finished_exex_height,
-        )
+        );
+        if let Some(policy) = self.supervision {
+            pruner.with_supervision(policy)
+        } else {
+            pruner
+        }
     }
 
     /// Builds a [Pruner] from the current configuration with the given static file provider.
@@ -133,6 +147,7 @@ impl Default for PrunerBuilder {
             delete_limit: MAINNET_PRUNE_DELETE_LIMIT,
             timeout: None,
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
+            supervision: None,
         }
     }
 }
@@ -1,15 +1,19 @@
 //! Support for pruning.
 
 use crate::{
-    segments::{PruneInput, Segment},
+    segments::{block_timestamp, PruneInput, Segment},
     Metrics, PruneLimiter, PrunerError, PrunerEvent,
 };
 use alloy_primitives::BlockNumber;
 use reth_exex_types::FinishedExExHeight;
 use reth_provider::{
-    DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
+    DBProvider, DatabaseProviderFactory, HeaderProvider, PruneCheckpointReader,
+    PruneCheckpointWriter,
 };
-use reth_prune_types::{PruneProgress, PrunedSegmentInfo, PrunerOutput};
+use reth_prune_types::{
+    PruneInterruptReason, PruneProgress, PruneRun, PrunedSegmentInfo, PrunerOutput,
+};
+use reth_tasks::supervisor::{SupervisionPolicy, Supervisor};
 use reth_tokio_util::{EventSender, EventStream};
 use std::time::{Duration, Instant};
 use tokio::sync::watch;
@@ -43,6 +47,8 @@ pub struct Pruner<Provider, PF> {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Supervises the pruner runs, see [`Pruner::with_supervision`].
+    supervisor: Option<Supervisor>,
     #[doc(hidden)]
     metrics: Metrics,
     event_sender: EventSender<PrunerEvent>,
@@ -65,6 +71,7 @@ impl<Provider> Pruner<Provider, ()> {
             delete_limit,
             timeout,
             finished_exex_height,
+            supervisor: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -92,15 +99,27 @@ where
             delete_limit,
             timeout,
             finished_exex_height,
+            supervisor: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
     }
 }
 
+impl<Provider, PF> Pruner<Provider, PF> {
+    /// Sets the policy applied if a [`Pruner::run`] panics.
+    ///
+    /// With [`SupervisionPolicy::Restart`], a panicked run is rolled back and the pruner returns
+    /// [`PruneInterruptReason::Panicked`] until the backoff has elapsed.
+    pub fn with_supervision(mut self, policy: SupervisionPolicy) -> Self {
+        self.supervisor = Some(Supervisor::new("pruner", policy));
+        self
+    }
+}
+
 impl<Provider, S> Pruner<Provider, S>
 where
-    Provider: PruneCheckpointReader + PruneCheckpointWriter,
+    Provider: PruneCheckpointReader + PruneCheckpointWriter + HeaderProvider,
 {
     /// Listen for events on the pruner.
     pub fn events(&self) -> EventStream<PrunerEvent> {
@@ -195,7 +214,17 @@ where
             if let Some((to_block, prune_mode)) = segment
                 .mode()
                 .map(|mode| {
-                    mode.prune_target_block(tip_block_number, segment.segment(), segment.purpose())
+                    let target = mode
+                        .resolve_age(tip_block_number, segment.segment(), segment.purpose(), |n| {
+                            block_timestamp(provider, n)
+                        })?
+                        .prune_target_block(
+                            tip_block_number,
+                            segment.segment(),
+                            segment.purpose(),
+                        )?;
+                    // the checkpoint keeps the configured mode, not the resolved one
+                    Ok::<_, PrunerError>(target.map(|(to_block, _)| (to_block, mode)))
                 })
                 .transpose()?
                 .flatten()
@@ -219,10 +248,20 @@ where
                     segment
                         .save_checkpoint(provider, checkpoint.as_prune_checkpoint(prune_mode))?;
                 }
+                let segment_elapsed = segment_start.elapsed();
+                provider.save_prune_run(
+                    segment.segment(),
+                    PruneRun {
+                        tip_block_number,
+                        elapsed_ms: segment_elapsed.as_millis() as u64,
+                        pruned: segment_output.pruned as u64,
+                        finished: segment_output.progress.is_finished(),
+                    },
+                )?;
                 self.metrics
                     .get_prune_segment_metrics(segment.segment())
                     .duration_seconds
-                    .record(segment_start.elapsed());
+                    .record(segment_elapsed);
                 if let Some(highest_pruned_block) =
                     segment_output.checkpoint.and_then(|checkpoint| checkpoint.block_number)
                 {
@@ -318,7 +357,9 @@ where
 
 impl<PF> Pruner<PF::ProviderRW, PF>
 where
-    PF: DatabaseProviderFactory<ProviderRW: PruneCheckpointWriter + PruneCheckpointReader>,
+    PF: DatabaseProviderFactory<
+        ProviderRW: PruneCheckpointWriter + PruneCheckpointReader + HeaderProvider,
+    >,
 {
     /// Run the pruner. This will only prune data up to the highest finished ExEx height, if there
     /// are no ExExes.
@@ -326,6 +367,19 @@ where
     /// Returns a [`PruneProgress`], indicating whether pruning is finished, or there is more data
     /// to prune.
     pub fn run(&mut self, tip_block_number: BlockNumber) -> PrunerResult {
+        let Some(mut supervisor) = self.supervisor.take() else {
+            return self.run_with_factory(tip_block_number)
+        };
+
+        // The database transaction of a panicked run is dropped without committing
+        let result = supervisor.run(|| self.run_with_factory(tip_block_number));
+        self.supervisor = Some(supervisor);
+        result.unwrap_or_else(|| {
+            Ok(PruneProgress::HasMoreData(PruneInterruptReason::Panicked).into())
+        })
+    }
+
+    fn run_with_factory(&mut self, tip_block_number: BlockNumber) -> PrunerResult {
         let provider = self.provider_factory.database_provider_rw()?;
         let result = self.run_with_provider(&provider, tip_block_number);
         provider.commit()?;
@@ -4,8 +4,12 @@ mod static_file;
 mod user;
 
 use crate::{PruneLimiter, PrunerError};
+use alloy_consensus::BlockHeader;
 use alloy_primitives::{BlockNumber, TxNumber};
-use reth_provider::{errors::provider::ProviderResult, BlockReader, PruneCheckpointWriter};
+use reth_provider::{
+    errors::provider::{ProviderError, ProviderResult},
+    BlockReader, HeaderProvider, PruneCheckpointWriter,
+};
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
 pub use set::SegmentSet;
 pub use static_file::{
@@ -52,6 +56,18 @@ pub trait Segment<Provider>: Debug + Send + Sync {
     }
 }
 
+/// Returns the timestamp of the block, to resolve [`PruneMode::Age`] with
+/// [`PruneMode::resolve_age`].
+pub(crate) fn block_timestamp<Provider: HeaderProvider>(
+    provider: &Provider,
+    block: BlockNumber,
+) -> ProviderResult<u64> {
+    Ok(provider
+        .header_by_number(block)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?
+        .timestamp())
+}
+
 /// Segment pruning input, see [`Segment::prune`].
 #[derive(Debug)]
 #[cfg_attr(test, derive(Clone))]
@@ -1,6 +1,6 @@
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{PruneInput, Segment},
+    segments::{block_timestamp, PruneInput, Segment},
     PrunerError,
 };
 use alloy_consensus::TxReceipt;
@@ -69,9 +69,25 @@ where
             None => 0,
         };
 
+        // Translate the ages into block numbers, so that they can be grouped by block
+        let config = ReceiptsLogPruneConfig(
+            self.config
+                .iter()
+                .map(|(address, mode)| {
+                    let mode = mode.resolve_age(
+                        input.to_block,
+                        PruneSegment::ContractLogs,
+                        PrunePurpose::User,
+                        |block| block_timestamp(provider, block),
+                    )?;
+                    Ok((*address, mode))
+                })
+                .collect::<Result<_, PrunerError>>()?,
+        );
+
         // Figure out what receipts have already been pruned, so we can have an accurate
         // `address_filter`
-        let address_filter = self.config.group_by_block(input.to_block, last_pruned_block)?;
+        let address_filter = config.group_by_block(input.to_block, last_pruned_block)?;
 
         // Splits all transactions in different block ranges. Each block range will have its own
         // filter address list and will check it while going through the table
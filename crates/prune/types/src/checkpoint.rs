@@ -15,3 +15,20 @@ pub struct PruneCheckpoint {
     /// Prune mode.
     pub prune_mode: PruneMode,
 }
+
+/// Saves the statistics of the last pruner run of a segment.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(any(test, feature = "test-utils"), derive(Default, arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+pub struct PruneRun {
+    /// Tip block number the pruner was run with.
+    pub tip_block_number: BlockNumber,
+    /// Time spent pruning the segment, in milliseconds.
+    pub elapsed_ms: u64,
+    /// Number of entries pruned, i.e. deleted from the database.
+    pub pruned: u64,
+    /// Whether the segment was pruned up to the target, or the run was interrupted.
+    pub finished: bool,
+}
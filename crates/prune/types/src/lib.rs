@@ -22,9 +22,9 @@ use alloc::{collections::BTreeMap, vec::Vec};
 use alloy_primitives::{Address, BlockNumber};
 use core::ops::Deref;
 
-pub use checkpoint::PruneCheckpoint;
+pub use checkpoint::{PruneCheckpoint, PruneRun};
 pub use event::PrunerEvent;
-pub use mode::PruneMode;
+pub use mode::{parse_age, ParseAgeError, PruneMode};
 pub use pruner::{
     PruneInterruptReason, PruneProgress, PrunedSegmentInfo, PrunerOutput, SegmentOutput,
     SegmentOutputCheckpoint,
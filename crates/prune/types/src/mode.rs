@@ -1,5 +1,7 @@
 use crate::{segment::PrunePurpose, PruneSegment, PruneSegmentError};
+use alloc::string::{String, ToString};
 use alloy_primitives::BlockNumber;
+use thiserror::Error;
 
 /// Prune mode.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -15,6 +17,12 @@ pub enum PruneMode {
     Distance(u64),
     /// Prune blocks before the specified block number. The specified block number is not pruned.
     Before(BlockNumber),
+    /// Prune blocks that are more than N seconds older than the `head` block. In other words, keep
+    /// the blocks of the last N seconds.
+    ///
+    /// The age is translated to [`PruneMode::Before`] using the block timestamps at the time of
+    /// pruning, see [`PruneMode::resolve_age`]. Until then, no blocks are pruned.
+    Age(#[cfg_attr(any(test, feature = "serde"), serde(with = "age_serde"))] u64),
 }
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -46,11 +54,13 @@ impl PruneMode {
             Self::Distance(distance) if *distance >= segment.min_blocks(purpose) => {
                 Some((tip - distance, *self))
             }
+            Self::Before(0) => None, // Nothing to prune
             Self::Before(n) if *n == tip + 1 && purpose.is_static_file() => Some((tip, *self)),
             Self::Before(n) if *n > tip => None, // Nothing to prune yet
             Self::Before(n) => {
                 (tip - n >= segment.min_blocks(purpose)).then(|| ((*n).saturating_sub(1), *self))
             }
+            Self::Age(_) => None, // Needs to be resolved first
             _ => return Err(PruneSegmentError::Configuration(segment)),
         };
         Ok(result)
@@ -67,7 +77,38 @@ impl PruneMode {
                 block < tip - *distance
             }
             Self::Before(n) => *n > block,
+            Self::Age(_) => false,
+        }
+    }
+
+    /// Translates [`PruneMode::Age`] into [`PruneMode::Before`] the oldest block that is not older
+    /// than the age, according to the provided block timestamps. Other modes are returned as is.
+    ///
+    /// The segment always keeps at least its minimum number of blocks, even if they are older.
+    ///
+    /// The block is found with a binary search between genesis and the tip, so the timestamp
+    /// lookup is called `O(log(tip))` times.
+    pub fn resolve_age<E>(
+        self,
+        tip: BlockNumber,
+        segment: PruneSegment,
+        purpose: PrunePurpose,
+        mut timestamp: impl FnMut(BlockNumber) -> Result<u64, E>,
+    ) -> Result<Self, E> {
+        let Self::Age(age) = self else { return Ok(self) };
+
+        let cutoff = timestamp(tip)?.saturating_sub(age);
+        let (mut low, mut high) = (0, tip);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if timestamp(mid)? < cutoff {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
         }
+
+        Ok(Self::Before(low.min(tip.saturating_sub(segment.min_blocks(purpose)))))
     }
 
     /// Returns true if the prune mode is [`PruneMode::Full`].
@@ -79,12 +120,106 @@ impl PruneMode {
     pub const fn is_distance(&self) -> bool {
         matches!(self, Self::Distance(_))
     }
+
+    /// Returns true if the prune mode is [`PruneMode::Age`].
+    pub const fn is_age(&self) -> bool {
+        matches!(self, Self::Age(_))
+    }
+}
+
+/// Error returned by [`parse_age`].
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+#[error("invalid age {0:?}, expected a number of seconds or a duration such as `30d` or `1w12h`")]
+pub struct ParseAgeError(String);
+
+/// Parses an age for [`PruneMode::Age`] into seconds.
+///
+/// The age is either a plain number of seconds, or a sequence of numbers followed by one of the
+/// units `s`, `m`, `h`, `d` or `w`, e.g. `30d` or `1w12h`.
+pub fn parse_age(value: &str) -> Result<u64, ParseAgeError> {
+    let err = || ParseAgeError(value.to_string());
+
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return Err(err())
+    }
+    if let Ok(seconds) = rest.parse() {
+        return Ok(seconds)
+    }
+
+    let mut seconds = 0u64;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let number = rest[..digits].parse::<u64>().map_err(|_| err())?;
+        rest = &rest[digits..];
+
+        let unit = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let multiplier = match &rest[..unit] {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            "w" => 7 * 24 * 60 * 60,
+            _ => return Err(err()),
+        };
+        rest = &rest[unit..];
+
+        seconds = number
+            .checked_mul(multiplier)
+            .and_then(|number| seconds.checked_add(number))
+            .ok_or_else(err)?;
+    }
+
+    Ok(seconds)
+}
+
+/// (De)serializes [`PruneMode::Age`] as a duration string such as `30d`. Deserializing a plain
+/// number of seconds is supported as well.
+#[cfg(any(test, feature = "serde"))]
+mod age_serde {
+    use super::parse_age;
+    use alloc::string::String;
+    use core::fmt;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(age: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&DisplayAge(*age))
+    }
+
+    /// Formats an age in the largest unit that represents it exactly, the inverse of
+    /// [`parse_age`].
+    struct DisplayAge(u64);
+
+    impl fmt::Display for DisplayAge {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let (number, unit) = [(24 * 60 * 60, "d"), (60 * 60, "h"), (60, "m")]
+                .into_iter()
+                .find(|(multiplier, _)| self.0 != 0 && self.0 % multiplier == 0)
+                .map_or((self.0, "s"), |(multiplier, unit)| (self.0 / multiplier, unit));
+            write!(f, "{number}{unit}")
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Age {
+            Seconds(u64),
+            Duration(String),
+        }
+
+        match Age::deserialize(deserializer)? {
+            Age::Seconds(seconds) => Ok(seconds),
+            Age::Duration(duration) => parse_age(&duration).map_err(D::Error::custom),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        PruneMode, PrunePurpose, PruneSegment, PruneSegmentError, MINIMUM_PRUNING_DISTANCE,
+        parse_age, PruneMode, PrunePurpose, PruneSegment, PruneSegmentError,
+        MINIMUM_PRUNING_DISTANCE,
     };
     use assert_matches::assert_matches;
     use serde::Deserialize;
@@ -173,6 +308,7 @@ mod tests {
         a = "full"
         b = { distance = 10 }
         c = { before = 20 }
+        d = { age = "30d" }
     "#;
 
         assert_matches!(
@@ -181,8 +317,80 @@ mod tests {
                 a: Some(PruneMode::Full),
                 b: Some(PruneMode::Distance(10)),
                 c: Some(PruneMode::Before(20)),
-                d: None
+                d: Some(PruneMode::Age(2_592_000))
             })
         );
     }
+
+    #[test]
+    fn prune_mode_age_serde() {
+        for (age, serialized) in [(2_592_000, "30d"), (7_200, "2h"), (90, "90s"), (0, "0s")] {
+            let json = serde_json::to_string(&PruneMode::Age(age)).unwrap();
+            assert_eq!(json, format!(r#"{{"age":"{serialized}"}}"#));
+            assert_eq!(serde_json::from_str::<PruneMode>(&json).unwrap(), PruneMode::Age(age));
+        }
+
+        assert_eq!(
+            serde_json::from_str::<PruneMode>(r#"{"age":3600}"#).unwrap(),
+            PruneMode::Age(3600)
+        );
+        assert!(serde_json::from_str::<PruneMode>(r#"{"age":"30 days"}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("3600"), Ok(3600));
+        assert_eq!(parse_age("30d"), Ok(30 * 24 * 60 * 60));
+        assert_eq!(parse_age("1w12h"), Ok(7 * 24 * 60 * 60 + 12 * 60 * 60));
+        assert_eq!(parse_age("5m30s"), Ok(330));
+
+        for invalid in ["", "d", "30x", "30dd", "-1d", "99999999999999999999w"] {
+            assert!(parse_age(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_age() {
+        // one block every 12 seconds, starting at timestamp 1000
+        let timestamp = |block: u64| Ok::<_, ()>(1000 + block * 12);
+        let tip = 100_000;
+        let segment = PruneSegment::SenderRecovery;
+
+        // keep the last hour, i.e. 300 blocks
+        assert_eq!(
+            PruneMode::Age(3600).resolve_age(tip, segment, PrunePurpose::User, timestamp),
+            Ok(PruneMode::Before(tip - 300))
+        );
+        // the age reaches past genesis
+        assert_eq!(
+            PruneMode::Age(u64::MAX).resolve_age(tip, segment, PrunePurpose::User, timestamp),
+            Ok(PruneMode::Before(0))
+        );
+        assert_eq!(
+            PruneMode::Before(0).prune_target_block(tip, segment, PrunePurpose::User),
+            Ok(None)
+        );
+        // the segment keeps at least its minimum number of blocks
+        assert_eq!(
+            PruneMode::Age(3600).resolve_age(
+                tip,
+                PruneSegment::AccountHistory,
+                PrunePurpose::User,
+                timestamp
+            ),
+            Ok(PruneMode::Before(tip - MINIMUM_PRUNING_DISTANCE))
+        );
+        // other modes are not changed
+        assert_eq!(
+            PruneMode::Distance(10).resolve_age(tip, segment, PrunePurpose::User, timestamp),
+            Ok(PruneMode::Distance(10))
+        );
+
+        // unresolved ages don't prune anything
+        assert_eq!(
+            PruneMode::Age(3600).prune_target_block(tip, segment, PrunePurpose::User),
+            Ok(None)
+        );
+        assert!(!PruneMode::Age(3600).should_prune(0, tip));
+    }
 }
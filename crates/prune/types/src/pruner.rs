@@ -97,6 +97,8 @@ pub enum PruneInterruptReason {
     Timeout,
     /// Limit on the number of deleted entries (rows in the database) per prune run was reached.
     DeletedEntriesLimitReached,
+    /// Prune run panicked, or is skipped while backing off after a panic.
+    Panicked,
     /// Unknown reason for stopping prune run.
     Unknown,
 }
@@ -1,6 +1,9 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_engine_primitives::ConsensusClientInfo;
+use reth_network_api::{DiscoveryTable, OutboundBandwidthStatus, StaticPeerStatus};
 use reth_network_peers::{AnyNode, NodeRecord};
+use std::num::NonZeroU64;
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -45,4 +48,48 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the nodes in the routing tables of the discv4 and discv5 discovery services.
+    #[method(name = "discoveryTable")]
+    async fn discovery_table(&self) -> RpcResult<DiscoveryTable>;
+
+    /// Returns the status and connection history of all trusted and static peers.
+    #[method(name = "staticPeers")]
+    async fn static_peers(&self) -> RpcResult<Vec<StaticPeerStatus>>;
+
+    /// Returns the global outbound bandwidth limit and the traffic sent per class.
+    #[method(name = "outboundBandwidth")]
+    async fn outbound_bandwidth(&self) -> RpcResult<OutboundBandwidthStatus>;
+
+    /// Sets the maximum number of bytes per second that are sent to all peers combined.
+    ///
+    /// `null` removes the limit, a limit of 0 is rejected.
+    #[method(name = "setOutboundBandwidth")]
+    fn set_outbound_bandwidth(&self, max_bytes_per_sec: Option<NonZeroU64>) -> RpcResult<bool>;
+
+    /// Sets the weights of the traffic classes that share the outbound bandwidth limit.
+    #[method(name = "setOutboundBandwidthWeights")]
+    fn set_outbound_bandwidth_weights(
+        &self,
+        block_propagation: u32,
+        transaction_gossip: u32,
+        historical_serving: u32,
+    ) -> RpcResult<bool>;
+}
+
+/// Admin namespace rpc interface to manage the engine of the node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminEngineApi {
+    /// Clears all blocks that are known to be invalid, including the ones that are persisted
+    /// across restarts, so that they are validated again when they are received.
+    ///
+    /// Returns the number of removed persisted invalid blocks.
+    #[method(name = "clearInvalidBlocks")]
+    async fn clear_invalid_blocks(&self) -> RpcResult<usize>;
+
+    /// Returns the consensus clients that identified themselves via `engine_getClientVersionV1`,
+    /// most recently seen first.
+    #[method(name = "consensusClients")]
+    async fn consensus_clients(&self) -> RpcResult<Vec<ConsensusClientInfo>>;
 }
@@ -40,14 +40,23 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "getBadBlocks")]
     async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
 
-    /// Returns the structured logs created during the execution of EVM between two blocks
-    /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
+    /// Creates a subscription that streams the structured logs created during the execution of
+    /// EVM between two blocks (excluding start), one [`BlockTraceResult`] per block in ascending
+    /// order. For the third parameter see [`GethDebugTracingOptions`].
+    ///
+    /// The range is limited to 1000 blocks. Tracing stops when the subscription is cancelled, and
+    /// the subscription is closed with the error if a block fails to trace.
+    #[subscription(
+        name = "traceChain",
+        unsubscribe = "traceChain_unsubscribe",
+        item = BlockTraceResult
+    )]
     async fn debug_trace_chain(
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
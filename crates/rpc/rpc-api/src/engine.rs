@@ -43,17 +43,17 @@ pub trait IntoEngineApiRpcModule {
 pub trait EngineApi<Engine: EngineTypes> {
     /// See also <https://github.com/ethereum/execution-apis/blob/6709c2a795b707202e93c4f2867fa0bf2640a84f/src/engine/paris.md#engine_newpayloadv1>
     /// Caution: This should not accept the `withdrawals` field
-    #[method(name = "newPayloadV1")]
+    #[method(name = "newPayloadV1", with_extensions)]
     async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> RpcResult<PayloadStatus>;
 
     /// See also <https://github.com/ethereum/execution-apis/blob/584905270d8ad665718058060267061ecfd79ca5/src/engine/shanghai.md#engine_newpayloadv2>
-    #[method(name = "newPayloadV2")]
+    #[method(name = "newPayloadV2", with_extensions)]
     async fn new_payload_v2(&self, payload: ExecutionPayloadInputV2) -> RpcResult<PayloadStatus>;
 
     /// Post Cancun payload handler
     ///
     /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_newpayloadv3>
-    #[method(name = "newPayloadV3")]
+    #[method(name = "newPayloadV3", with_extensions)]
     async fn new_payload_v3(
         &self,
         payload: ExecutionPayloadV3,
@@ -64,7 +64,7 @@ pub trait EngineApi<Engine: EngineTypes> {
     /// Post Prague payload handler
     ///
     /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/prague.md#engine_newpayloadv4>
-    #[method(name = "newPayloadV4")]
+    #[method(name = "newPayloadV4", with_extensions)]
     async fn new_payload_v4(
         &self,
         payload: ExecutionPayloadV3,
@@ -76,7 +76,7 @@ pub trait EngineApi<Engine: EngineTypes> {
     /// See also <https://github.com/ethereum/execution-apis/blob/6709c2a795b707202e93c4f2867fa0bf2640a84f/src/engine/paris.md#engine_forkchoiceupdatedv1>
     ///
     /// Caution: This should not accept the `withdrawals` field in the payload attributes.
-    #[method(name = "forkchoiceUpdatedV1")]
+    #[method(name = "forkchoiceUpdatedV1", with_extensions)]
     async fn fork_choice_updated_v1(
         &self,
         fork_choice_state: ForkchoiceState,
@@ -92,7 +92,7 @@ pub trait EngineApi<Engine: EngineTypes> {
     ///
     /// Caution: This should not accept the `parentBeaconBlockRoot` field in the payload
     /// attributes.
-    #[method(name = "forkchoiceUpdatedV2")]
+    #[method(name = "forkchoiceUpdatedV2", with_extensions)]
     async fn fork_choice_updated_v2(
         &self,
         fork_choice_state: ForkchoiceState,
@@ -106,7 +106,7 @@ pub trait EngineApi<Engine: EngineTypes> {
     /// are provided.
     ///
     /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_forkchoiceupdatedv3>
-    #[method(name = "forkchoiceUpdatedV3")]
+    #[method(name = "forkchoiceUpdatedV3", with_extensions)]
     async fn fork_choice_updated_v3(
         &self,
         fork_choice_state: ForkchoiceState,
@@ -214,7 +214,10 @@ pub trait EngineApi<Engine: EngineTypes> {
     /// - When connected to multiple execution clients via a multiplexer, the multiplexer **MUST**
     ///   concatenate the responses from each execution client into a single,
     /// flat array before returning the response to the consensus client.
-    #[method(name = "getClientVersionV1")]
+    ///
+    /// The connection the request was received on is available via the request extensions, which
+    /// allows identifying the consensus client per connection.
+    #[method(name = "getClientVersionV1", with_extensions)]
     async fn get_client_version_v1(
         &self,
         client_version: ClientVersionV1,
@@ -242,6 +245,28 @@ pub trait EngineApi<Engine: EngineTypes> {
     ) -> RpcResult<Option<Vec<BlobAndProofV2>>>;
 }
 
+/// Reth specific extensions of the engine API, served on the authenticated engine API endpoint.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethEngineApi<ExecutionData: RpcObject> {
+    /// Submits a payload that was built outside of the node, e.g. by an external block builder, as
+    /// a candidate for the payload job with the given id.
+    ///
+    /// The payload is validated like an `engine_newPayload` call, and it must be built on top of
+    /// the attributes of the job, including its fee recipient. If it's valid, `engine_getPayload`
+    /// returns the candidate instead of the locally built payload if its value is higher than the
+    /// fees of the local payload.
+    ///
+    /// The value of the candidate is computed from the block as a lower bound of the priority fees
+    /// it pays to the fee recipient.
+    #[method(name = "submitPayloadCandidate")]
+    async fn submit_payload_candidate(
+        &self,
+        payload_id: PayloadId,
+        payload: ExecutionData,
+    ) -> RpcResult<PayloadStatus>;
+}
+
 /// A subset of the ETH rpc interface: <https://ethereum.github.io/execution-apis/docs/reference/json-rpc-api>
 ///
 /// This also includes additional eth functions required by optimism.
@@ -31,20 +31,31 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use txpool::{TxpoolParkedReason, TxpoolStatusDetail, TxpoolSubPool};
+
+pub use reth::{
+    BlobFeeHistory, BlobFeeHistoryEntry, BlockField, BlockFields, PruneRunStatus,
+    PruneSegmentStatus, PruneStatus, ReceiptWithProof, SenderTransactionsPage,
+    TransactionWithProof,
+};
+
 /// re-export of all server traits
 pub use servers::*;
 
 /// Aggregates all server traits.
 pub mod servers {
     pub use crate::{
-        admin::AdminApiServer,
+        admin::{AdminApiServer, AdminEngineApiServer},
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
-        engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
+        engine::{
+            EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule, RethEngineApiServer,
+        },
+        ganache::GanacheApiServer,
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{RethApiServer, RethFeeApiServer},
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
@@ -65,17 +76,17 @@ pub use clients::*;
 #[cfg(feature = "client")]
 pub mod clients {
     pub use crate::{
-        admin::AdminApiClient,
+        admin::{AdminApiClient, AdminEngineApiClient},
         anvil::AnvilApiClient,
         debug::{DebugApiClient, DebugExecutionWitnessApiClient},
-        engine::{EngineApiClient, EngineEthApiClient},
+        engine::{EngineApiClient, EngineEthApiClient, RethEngineApiClient},
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
         mev::{MevFullApiClient, MevSimApiClient},
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{RethApiClient, RethFeeApiClient},
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
@@ -1,6 +1,8 @@
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{eip4895::Withdrawal, BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, Bytes, TxHash, B256, U128, U256, U64};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_prune_types::{PruneMode, PruneSegment};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 // Required for the subscription attribute below
@@ -17,6 +19,50 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
 
+    /// Returns a page of the transactions sent by the given address, oldest first.
+    ///
+    /// The `cursor` of the returned page can be passed to the next request to fetch the following
+    /// page. This requires the sender transactions index to be enabled on the node.
+    #[method(name = "getTransactionsBySender")]
+    async fn reth_get_transactions_by_sender(
+        &self,
+        sender: Address,
+        cursor: Option<U64>,
+        limit: Option<U64>,
+    ) -> RpcResult<SenderTransactionsPage>;
+
+    /// Returns the transaction with the given hash together with its inclusion proof against the
+    /// transactions root of its block, or `None` if the transaction is not included in a block.
+    #[method(name = "getTransactionWithProof")]
+    async fn reth_get_transaction_with_proof(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<TransactionWithProof>>;
+
+    /// Returns the receipt of the transaction with the given hash together with its inclusion
+    /// proof against the receipts root of its block, or `None` if the transaction is not included
+    /// in a block.
+    #[method(name = "getReceiptWithProof")]
+    async fn reth_get_receipt_with_proof(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<ReceiptWithProof>>;
+
+    /// Returns the requested fields of the given block, or `None` if the block is not found.
+    ///
+    /// Unlike `eth_getBlockByNumber`, the block body is only read if `withdrawals` or
+    /// `transactions` are requested, which makes polling header data cheap.
+    #[method(name = "getBlockFields")]
+    async fn reth_get_block_fields(
+        &self,
+        block_id: BlockId,
+        fields: Vec<BlockField>,
+    ) -> RpcResult<Option<BlockFields>>;
+
+    /// Returns the pruning status of every segment that has been pruned at least once.
+    #[method(name = "pruneStatus")]
+    async fn reth_prune_status(&self) -> RpcResult<PruneStatus>;
+
     /// Subscribe to json `ChainNotifications`
     #[subscription(
         name = "subscribeChainNotifications",
@@ -25,3 +71,240 @@ pub trait RethApi {
     )]
     async fn reth_subscribe_chain_notifications(&self) -> jsonrpsee::core::SubscriptionResult;
 }
+
+/// Reth API namespace for fee market methods that are served by the `eth` API.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethFeeApi {
+    /// Returns the blob base fee, blob gas used and excess blob gas of every block in the given
+    /// inclusive range.
+    ///
+    /// The range is limited to the same number of blocks as `eth_feeHistory`.
+    #[method(name = "blobFeeHistory")]
+    async fn reth_blob_fee_history(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<BlobFeeHistory>;
+}
+
+/// A page of transactions sent by an address, returned by `reth_getTransactionsBySender`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderTransactionsPage {
+    /// Hashes of the sender's transactions in the order they were included in the chain.
+    pub transactions: Vec<TxHash>,
+    /// Cursor to request the next page with, `None` if there are no more transactions.
+    pub cursor: Option<U64>,
+}
+
+/// A transaction with its inclusion proof, returned by `reth_getTransactionWithProof`.
+///
+/// The proof can be verified against the transactions root of the block with the RLP encoded
+/// transaction index as key and the encoded transaction as value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionWithProof {
+    /// Hash of the block that includes the transaction.
+    pub block_hash: B256,
+    /// Number of the block that includes the transaction.
+    pub block_number: U64,
+    /// Index of the transaction in the block.
+    pub transaction_index: U64,
+    /// Transactions root of the block.
+    pub transactions_root: B256,
+    /// The EIP-2718 encoded transaction.
+    pub transaction: Bytes,
+    /// Trie nodes on the path from the transactions root to the transaction.
+    pub proof: Vec<Bytes>,
+}
+
+/// A receipt with its inclusion proof, returned by `reth_getReceiptWithProof`.
+///
+/// The proof can be verified against the receipts root of the block with the RLP encoded
+/// transaction index as key and the encoded receipt as value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptWithProof {
+    /// Hash of the block that includes the transaction.
+    pub block_hash: B256,
+    /// Number of the block that includes the transaction.
+    pub block_number: U64,
+    /// Index of the transaction in the block.
+    pub transaction_index: U64,
+    /// Receipts root of the block.
+    pub receipts_root: B256,
+    /// The EIP-2718 encoded receipt, including the logs bloom.
+    pub receipt: Bytes,
+    /// Trie nodes on the path from the receipts root to the receipt.
+    pub proof: Vec<Bytes>,
+}
+
+/// Blob fee market of a range of blocks, returned by `reth_blobFeeHistory`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobFeeHistory {
+    /// Number of the first block of the range.
+    pub oldest_block: U64,
+    /// Blob fee market of every block of the range, oldest first.
+    pub blocks: Vec<BlobFeeHistoryEntry>,
+    /// Blob base fee of the block following the range, `None` pre-Cancun.
+    pub next_blob_base_fee: Option<U128>,
+}
+
+/// Blob fee market of a single block.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobFeeHistoryEntry {
+    /// Number of the block.
+    pub number: U64,
+    /// Blob base fee of the block, `None` pre-Cancun.
+    pub blob_base_fee: Option<U128>,
+    /// Blob gas used by the transactions of the block, `None` pre-Cancun.
+    pub blob_gas_used: Option<U64>,
+    /// Excess blob gas of the block, `None` pre-Cancun.
+    pub excess_blob_gas: Option<U64>,
+    /// Ratio of the blob gas used to the maximum blob gas of the block.
+    pub blob_gas_used_ratio: f64,
+}
+
+/// A block field that can be requested with `reth_getBlockFields`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockField {
+    /// Hash of the block.
+    Hash,
+    /// Hash of the parent block.
+    ParentHash,
+    /// Number of the block.
+    Number,
+    /// Timestamp of the block.
+    Timestamp,
+    /// Gas used by the transactions of the block.
+    GasUsed,
+    /// Gas limit of the block.
+    GasLimit,
+    /// Base fee per gas of the block, post-London only.
+    BaseFeePerGas,
+    /// Beneficiary of the block.
+    Miner,
+    /// State root after executing the block.
+    StateRoot,
+    /// Root of the transactions trie of the block.
+    TransactionsRoot,
+    /// Root of the receipts trie of the block.
+    ReceiptsRoot,
+    /// Blob gas used by the transactions of the block, post-Cancun only.
+    BlobGasUsed,
+    /// Excess blob gas of the block, post-Cancun only.
+    ExcessBlobGas,
+    /// Root of the withdrawals trie of the block, post-Shanghai only.
+    WithdrawalsRoot,
+    /// Withdrawals of the block, post-Shanghai only. Requires reading the block body.
+    Withdrawals,
+    /// Hashes of the transactions of the block. Requires reading the block body.
+    Transactions,
+}
+
+impl BlockField {
+    /// Returns `true` if the field is stored in the block body instead of the header.
+    pub const fn is_body_field(&self) -> bool {
+        matches!(self, Self::Withdrawals | Self::Transactions)
+    }
+}
+
+/// The requested fields of a block, returned by `reth_getBlockFields`.
+///
+/// Fields that were not requested, or that don't exist in the block, are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockFields {
+    /// Hash of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<B256>,
+    /// Hash of the parent block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_hash: Option<B256>,
+    /// Number of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<U64>,
+    /// Timestamp of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<U64>,
+    /// Gas used by the transactions of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<U64>,
+    /// Gas limit of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+    /// Base fee per gas of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U64>,
+    /// Beneficiary of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub miner: Option<Address>,
+    /// State root after executing the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_root: Option<B256>,
+    /// Root of the transactions trie of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transactions_root: Option<B256>,
+    /// Root of the receipts trie of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipts_root: Option<B256>,
+    /// Blob gas used by the transactions of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_gas_used: Option<U64>,
+    /// Excess blob gas of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excess_blob_gas: Option<U64>,
+    /// Root of the withdrawals trie of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<B256>,
+    /// Withdrawals of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Hashes of the transactions of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transactions: Option<Vec<TxHash>>,
+}
+
+/// Pruning status of the node, returned by `reth_pruneStatus`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneStatus {
+    /// Number of the latest block.
+    pub tip: U64,
+    /// Status of every segment that has been pruned at least once.
+    pub segments: Vec<PruneSegmentStatus>,
+}
+
+/// Pruning status of a single segment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneSegmentStatus {
+    /// The pruned segment.
+    pub segment: PruneSegment,
+    /// The prune mode the segment is configured with.
+    pub mode: PruneMode,
+    /// Highest pruned block number, `None` if the pruning of block `0` is not finished yet.
+    pub checkpoint: Option<U64>,
+    /// Number of blocks between the checkpoint and the latest block.
+    pub lag: Option<U64>,
+    /// Statistics of the last pruner run of the segment.
+    pub last_run: Option<PruneRunStatus>,
+}
+
+/// Statistics of the last pruner run of a segment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneRunStatus {
+    /// Tip block number the pruner was run with.
+    pub tip: U64,
+    /// Time spent pruning the segment, in milliseconds.
+    pub duration_ms: U64,
+    /// Number of entries deleted from the database.
+    pub deleted_entries: U64,
+    /// Whether the segment was pruned up to the target, or the run was interrupted.
+    pub finished: bool,
+}
@@ -1,7 +1,8 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash, U128, U256, U64};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +35,76 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Returns the sub-pool of the transaction with the given hash and all reasons why it is not
+    /// pending, e.g. a missing nonce or a fee cap below the base fee.
+    ///
+    /// Returns `null` if the transaction is not in the pool.
+    #[method(name = "statusDetail")]
+    async fn txpool_status_detail(&self, hash: TxHash) -> RpcResult<Option<TxpoolStatusDetail>>;
+}
+
+/// Status of a pooled transaction, returned by `txpool_statusDetail`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolStatusDetail {
+    /// The sub-pool that contains the transaction.
+    pub subpool: TxpoolSubPool,
+    /// All reasons that keep the transaction from being pending, empty if it is pending.
+    pub reasons: Vec<TxpoolParkedReason>,
+}
+
+/// The sub-pool of a pooled transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxpoolSubPool {
+    /// Ready to be included in the next block.
+    Pending,
+    /// Parked because of missing or parked ancestors, the sender's balance or the gas limit.
+    Queued,
+    /// Parked because the fee cap is below the base fee of the pending block.
+    BaseFee,
+    /// A blob transaction that is not pending.
+    Blob,
+}
+
+/// A reason why a pooled transaction is not pending.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TxpoolParkedReason {
+    /// A transaction of the sender with a lower nonce is missing.
+    NonceGap {
+        /// The lowest nonce of the sender that is neither mined nor in the pool.
+        missing_nonce: U64,
+    },
+    /// A transaction of the sender with a lower nonce is parked.
+    ParkedAncestor,
+    /// The sender's balance does not cover the cost of this and all prior transactions.
+    InsufficientBalance {
+        /// The balance of the sender.
+        balance: U256,
+        /// The cumulative cost of the sender's transactions up to and including this one.
+        cost: U256,
+    },
+    /// The gas limit of the transaction exceeds the block gas limit.
+    ExceedsBlockGasLimit {
+        /// The gas limit of the transaction.
+        gas_limit: U64,
+        /// The block gas limit.
+        block_gas_limit: U64,
+    },
+    /// The max fee per gas is below the base fee of the pending block.
+    FeeCapBelowBaseFee {
+        /// The max fee per gas of the transaction.
+        max_fee_per_gas: U128,
+        /// The base fee of the pending block.
+        base_fee: U64,
+    },
+    /// The max fee per blob gas is below the blob fee of the pending block.
+    BlobFeeCapBelowBlobFee {
+        /// The max fee per blob gas of the transaction.
+        max_fee_per_blob_gas: U128,
+        /// The blob fee of the pending block.
+        blob_fee: U128,
+    },
 }
@@ -0,0 +1,83 @@
+#![allow(missing_docs)]
+use alloy_primitives::U64;
+use criterion::{criterion_group, criterion_main, Criterion};
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorCode, ErrorObject, ErrorObjectOwned, Request},
+    ConnectionId, Extensions, MethodCallback, MethodResponse, Methods, RpcModule,
+};
+use reth_rpc_builder::static_responses::StaticResponsesLayer;
+use std::future::Future;
+use tower::Layer;
+
+const REQUEST: &str = r#"{"jsonrpc":"2.0","id":1,"method":"eth_chainId","params":[]}"#;
+
+/// Dispatches calls to the registered method handlers, like the service of the server.
+#[derive(Clone)]
+struct Dispatch {
+    methods: Methods,
+}
+
+impl RpcServiceT for Dispatch {
+    type MethodResponse = MethodResponse;
+    type NotificationResponse = MethodResponse;
+    type BatchResponse = MethodResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let methods = self.methods.clone();
+        async move {
+            let id = req.id().into_owned();
+            let params = req.params().into_owned();
+            match methods.method_with_name(req.method_name()) {
+                Some((_, MethodCallback::Async(callback))) => {
+                    callback(id, params, ConnectionId(0), usize::MAX, Extensions::new()).await
+                }
+                _ => MethodResponse::error(id, ErrorObject::from(ErrorCode::MethodNotFound)),
+            }
+        }
+    }
+
+    fn batch<'a>(&self, _: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        async { unimplemented!() }
+    }
+
+    fn notification<'a>(
+        &self,
+        _: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        async { unimplemented!() }
+    }
+}
+
+/// Compares dispatching `eth_chainId` to its handler with serving the stored response.
+fn static_responses(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+    let mut module = RpcModule::new(());
+    module
+        .register_async_method("eth_chainId", |_, _, _| async {
+            Ok::<_, ErrorObjectOwned>(Some(U64::from(1)))
+        })
+        .unwrap();
+    let dispatch = Dispatch { methods: module.into() };
+    let served = StaticResponsesLayer::new(["eth_chainId"]).layer(dispatch.clone());
+
+    let mut group = c.benchmark_group("Static Responses");
+    group.bench_function("dispatch", |b| {
+        b.iter(|| {
+            let request: Request<'_> = serde_json::from_str(REQUEST).unwrap();
+            runtime.block_on(dispatch.call(request))
+        })
+    });
+    group.bench_function("served", |b| {
+        b.iter(|| {
+            let request: Request<'_> = serde_json::from_str(REQUEST).unwrap();
+            runtime.block_on(served.call(request))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, static_responses);
+criterion_main!(benches);
@@ -1,6 +1,7 @@
 use crate::{
     error::{RpcError, ServerKind},
     middleware::RethRpcMiddleware,
+    tls, TlsConfig, DEFAULT_MAX_CONNECTIONS,
 };
 use http::header::AUTHORIZATION;
 use jsonrpsee::{
@@ -13,10 +14,11 @@ use jsonrpsee::{
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_types::EthSubscriptionIdProvider;
 use reth_rpc_layer::{
-    secret_to_bearer_header, AuthClientLayer, AuthLayer, JwtAuthValidator, JwtSecret,
+    secret_to_bearer_header, AuthClientLayer, AuthLayer, JwtAuthValidator, JwtSecret, JwtSecrets,
 };
 use reth_rpc_server_types::constants;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use tokio::net::TcpListener;
 use tower::layer::util::Identity;
 
 pub use jsonrpsee::server::ServerBuilder;
@@ -28,14 +30,18 @@ pub use reth_ipc::server::Builder as IpcServerBuilder;
 pub struct AuthServerConfig<RpcMiddleware = Identity> {
     /// Where the server should listen.
     pub(crate) socket_addr: SocketAddr,
-    /// The secret for the auth layer of the server.
-    pub(crate) secret: JwtSecret,
+    /// The secrets for the auth layer of the server.
+    pub(crate) secrets: JwtSecrets,
     /// Configs for JSON-RPC Http.
     pub(crate) server_config: ServerConfigBuilder,
     /// Configs for IPC server
     pub(crate) ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     /// IPC endpoint
     pub(crate) ipc_endpoint: Option<String>,
+    /// Certificate and key to terminate TLS
+    pub(crate) tls: Option<TlsConfig>,
+    /// Maximum number of TLS connections that are served at the same time
+    pub(crate) max_connections: u32,
     /// Configurable RPC middleware
     pub(crate) rpc_middleware: RpcMiddleware,
 }
@@ -44,7 +50,7 @@ pub struct AuthServerConfig<RpcMiddleware = Identity> {
 
 impl AuthServerConfig {
     /// Convenience function to create a new `AuthServerConfig`.
-    pub const fn builder(secret: JwtSecret) -> AuthServerConfigBuilder {
+    pub fn builder(secret: JwtSecret) -> AuthServerConfigBuilder {
         AuthServerConfigBuilder::new(secret)
     }
 }
@@ -54,15 +60,34 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
         self.socket_addr
     }
 
+    /// Returns the secrets that are accepted by the server.
+    ///
+    /// The secrets are shared with the started server, so they can be rotated while it is
+    /// running.
+    pub const fn secrets(&self) -> &JwtSecrets {
+        &self.secrets
+    }
+
     /// Configures the rpc middleware.
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> AuthServerConfig<T> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, .. } = self;
+        let Self {
+            socket_addr,
+            secrets,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+            tls,
+            max_connections,
+            ..
+        } = self;
         AuthServerConfig {
             socket_addr,
-            secret,
+            secrets,
             server_config,
             ipc_server_config,
             ipc_endpoint,
+            tls,
+            max_connections,
             rpc_middleware,
         }
     }
@@ -74,33 +99,67 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
     {
         let Self {
             socket_addr,
-            secret,
+            secrets,
             server_config,
             ipc_server_config,
             ipc_endpoint,
+            tls,
+            max_connections,
             rpc_middleware,
         } = self;
 
         // Create auth middleware.
-        let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+        let middleware = tower::ServiceBuilder::new()
+            .layer(AuthLayer::new(JwtAuthValidator::with_secrets(secrets.clone())));
 
         let rpc_middleware = RpcServiceBuilder::default().layer(rpc_middleware);
 
         // By default, both http and ws are enabled.
-        let server = ServerBuilder::new()
+        let builder = ServerBuilder::new()
             .set_config(server_config.build())
             .set_http_middleware(middleware)
-            .set_rpc_middleware(rpc_middleware)
-            .build(socket_addr)
-            .await
-            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
-
-        let local_addr = server
-            .local_addr()
-            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
-
-        let handle = server.start(module.inner.clone());
+            .set_rpc_middleware(rpc_middleware);
+
+        let (local_addr, handle) = if let Some(tls_config) = &tls {
+            // TLS connections are accepted by us and served by the server's service
+            let acceptor = tls_config.acceptor()?;
+            let listener = TcpListener::bind(socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            let local_addr = listener
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+
+            let service_builder = builder.to_service_builder();
+            let methods = module.inner.clone();
+            let handle = tls::serve_connections(
+                listener,
+                Some(acceptor),
+                None,
+                max_connections,
+                move |stop_handle| {
+                    let service =
+                        service_builder.clone().build(methods.clone(), stop_handle.clone());
+                    move |connection| {
+                        jsonrpsee::server::serve_with_graceful_shutdown(
+                            connection,
+                            service,
+                            stop_handle.shutdown(),
+                        )
+                    }
+                },
+            );
+            (local_addr, handle)
+        } else {
+            let server = builder
+                .build(socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            let local_addr = server
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            (local_addr, server.start(module.inner.clone()))
+        };
 
         let ipc_handle = if let Some(ipc_server_config) = ipc_server_config {
             let ipc_endpoint_str = ipc_endpoint
@@ -113,7 +172,14 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
             None
         };
 
-        Ok(AuthServerHandle { handle: Some(handle), local_addr, secret, ipc_endpoint, ipc_handle })
+        Ok(AuthServerHandle {
+            handle: Some(handle),
+            local_addr,
+            secrets,
+            ipc_endpoint,
+            ipc_handle,
+            tls: tls.is_some(),
+        })
     }
 }
 
@@ -121,10 +187,12 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
 #[derive(Debug)]
 pub struct AuthServerConfigBuilder<RpcMiddleware = Identity> {
     socket_addr: Option<SocketAddr>,
-    secret: JwtSecret,
+    secrets: JwtSecrets,
     server_config: Option<ServerConfigBuilder>,
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     ipc_endpoint: Option<String>,
+    tls: Option<TlsConfig>,
+    max_connections: u32,
     rpc_middleware: RpcMiddleware,
 }
 
@@ -132,13 +200,15 @@ pub struct AuthServerConfigBuilder<RpcMiddleware = Identity> {
 
 impl AuthServerConfigBuilder {
     /// Create a new `AuthServerConfigBuilder` with the given `secret`.
-    pub const fn new(secret: JwtSecret) -> Self {
+    pub fn new(secret: JwtSecret) -> Self {
         Self {
             socket_addr: None,
-            secret,
+            secrets: JwtSecrets::new(secret),
             server_config: None,
             ipc_server_config: None,
             ipc_endpoint: None,
+            tls: None,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
             rpc_middleware: Identity::new(),
         }
     }
@@ -147,13 +217,24 @@ impl AuthServerConfigBuilder {
 impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
     /// Configures the rpc middleware.
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> AuthServerConfigBuilder<T> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, .. } = self;
+        let Self {
+            socket_addr,
+            secrets,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+            tls,
+            max_connections,
+            ..
+        } = self;
         AuthServerConfigBuilder {
             socket_addr,
-            secret,
+            secrets,
             server_config,
             ipc_server_config,
             ipc_endpoint,
+            tls,
+            max_connections,
             rpc_middleware,
         }
     }
@@ -171,8 +252,15 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
     }
 
     /// Set the secret for the server.
-    pub const fn secret(mut self, secret: JwtSecret) -> Self {
-        self.secret = secret;
+    pub fn secret(mut self, secret: JwtSecret) -> Self {
+        self.secrets = JwtSecrets::new(secret);
+        self
+    }
+
+    /// Set the secrets that are accepted by the server, e.g. to accept a secondary secret while
+    /// the secret is rotated.
+    pub fn secrets(mut self, secrets: JwtSecrets) -> Self {
+        self.secrets = secrets;
         self
     }
 
@@ -199,13 +287,30 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
         self
     }
 
+    /// Configures the certificate and key to terminate TLS.
+    ///
+    /// The certificate is reloaded when its files change, see [`TlsConfig`].
+    pub fn with_tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Configures the maximum number of TLS connections that are served at the same time.
+    ///
+    /// TLS connections are accepted by the server itself, so the limit of the server config
+    /// doesn't apply to them. Defaults to [`DEFAULT_MAX_CONNECTIONS`].
+    pub const fn max_connections(mut self, max_connections: u32) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
     /// Build the `AuthServerConfig`.
     pub fn build(self) -> AuthServerConfig<RpcMiddleware> {
         AuthServerConfig {
             socket_addr: self.socket_addr.unwrap_or_else(|| {
                 SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), constants::DEFAULT_AUTH_PORT)
             }),
-            secret: self.secret,
+            secrets: self.secrets,
             server_config: self.server_config.unwrap_or_else(|| {
                 ServerConfig::builder()
                     // This needs to large enough to handle large eth_getLogs responses and
@@ -218,7 +323,7 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
                     // client that uses JWT, this should be
                     // more than enough so that the CL (or multiple CL nodes) will never get
                     // rate limited
-                    .max_connections(500)
+                    .max_connections(DEFAULT_MAX_CONNECTIONS)
                     // bump the default request size slightly, there aren't any methods exposed
                     // with dynamic request params that can exceed this
                     .max_request_body_size(128 * 1024 * 1024)
@@ -232,6 +337,8 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
                     .set_id_provider(EthSubscriptionIdProvider::default())
             }),
             ipc_endpoint: self.ipc_endpoint,
+            tls: self.tls,
+            max_connections: self.max_connections,
             rpc_middleware: self.rpc_middleware,
         }
     }
@@ -306,9 +413,11 @@ impl AuthRpcModule {
 pub struct AuthServerHandle {
     local_addr: SocketAddr,
     handle: Option<jsonrpsee::server::ServerHandle>,
-    secret: JwtSecret,
+    secrets: JwtSecrets,
     ipc_endpoint: Option<String>,
     ipc_handle: Option<jsonrpsee::server::ServerHandle>,
+    /// Whether the server terminates TLS
+    tls: bool,
 }
 
 // === impl AuthServerHandle ===
@@ -324,9 +433,10 @@ impl AuthServerHandle {
                 constants::DEFAULT_AUTH_PORT,
             ),
             handle: None,
-            secret: JwtSecret::random(),
+            secrets: JwtSecrets::new(JwtSecret::random()),
             ipc_endpoint: None,
             ipc_handle: None,
+            tls: false,
         }
     }
 
@@ -343,12 +453,14 @@ impl AuthServerHandle {
 
     /// Returns the url to the http server
     pub fn http_url(&self) -> String {
-        format!("http://{}", self.local_addr)
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("{scheme}://{}", self.local_addr)
     }
 
     /// Returns the url to the ws server
     pub fn ws_url(&self) -> String {
-        format!("ws://{}", self.local_addr)
+        let scheme = if self.tls { "wss" } else { "ws" };
+        format!("{scheme}://{}", self.local_addr)
     }
 
     /// Returns a http client connected to the server.
@@ -356,7 +468,7 @@ impl AuthServerHandle {
     /// This client uses the JWT token to authenticate requests.
     pub fn http_client(&self) -> impl SubscriptionClientT + Clone + Send + Sync + Unpin + 'static {
         // Create a middleware that adds a new JWT token to every request.
-        let secret_layer = AuthClientLayer::new(self.secret);
+        let secret_layer = AuthClientLayer::new(self.secrets.primary());
         let middleware = tower::ServiceBuilder::default().layer(secret_layer);
         jsonrpsee::http_client::HttpClientBuilder::default()
             .set_http_middleware(middleware)
@@ -370,7 +482,7 @@ impl AuthServerHandle {
         jsonrpsee::ws_client::WsClientBuilder::default()
             .set_headers(HeaderMap::from_iter([(
                 AUTHORIZATION,
-                secret_to_bearer_header(&self.secret),
+                secret_to_bearer_header(&self.secrets.primary()),
             )]))
             .build(self.ws_url())
             .await
@@ -0,0 +1,96 @@
+use reth_rpc_eth_api::MaxBlocksPerFilterOverride;
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// HTTP middleware that overrides the maximum block range of `eth_getLogs` requests with the value
+/// of a configured header.
+///
+/// A value of `0` removes the limit. Requests without the header, or with a value that isn't a
+/// number, use the configured maximum block range. The header should only be set by trusted
+/// infrastructure, e.g. a proxy in front of the node that authenticates the clients.
+#[derive(Debug, Clone)]
+pub(crate) struct BlockRangeOverrideLayer {
+    header: Arc<str>,
+}
+
+impl BlockRangeOverrideLayer {
+    /// Creates the layer that reads the maximum block range from the given header.
+    pub(crate) fn new(header: &str) -> Self {
+        Self { header: header.into() }
+    }
+}
+
+impl<S> Layer<S> for BlockRangeOverrideLayer {
+    type Service = BlockRangeOverrideService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BlockRangeOverrideService { inner, header: self.header.clone() }
+    }
+}
+
+/// Service of the [`BlockRangeOverrideLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct BlockRangeOverrideService<S> {
+    inner: S,
+    header: Arc<str>,
+}
+
+impl<S, B> Service<http::Request<B>> for BlockRangeOverrideService<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let max_blocks = req
+            .headers()
+            .get(&*self.header)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        if let Some(max_blocks) = max_blocks {
+            req.extensions_mut()
+                .insert(MaxBlocksPerFilterOverride((max_blocks != 0).then_some(max_blocks)));
+        }
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    async fn max_blocks_override(header: Option<&str>) -> Option<MaxBlocksPerFilterOverride> {
+        let mut service = BlockRangeOverrideLayer::new("x-max-block-range").layer(
+            tower::service_fn(|req: http::Request<()>| async move {
+                Ok::<_, Infallible>(req.extensions().get::<MaxBlocksPerFilterOverride>().copied())
+            }),
+        );
+
+        let mut req = http::Request::builder();
+        if let Some(header) = header {
+            req = req.header("x-max-block-range", header);
+        }
+        service.call(req.body(()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn parses_block_range_override() {
+        assert_eq!(max_blocks_override(None).await, None);
+        assert_eq!(max_blocks_override(Some("abc")).await, None);
+        assert_eq!(
+            max_blocks_override(Some(" 500 ")).await,
+            Some(MaxBlocksPerFilterOverride(Some(500)))
+        );
+        assert_eq!(max_blocks_override(Some("0")).await, Some(MaxBlocksPerFilterOverride(None)));
+    }
+}
@@ -2,15 +2,20 @@ use jsonrpsee::server::ServerConfigBuilder;
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
 use reth_rpc::ValidationApiConfig;
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
-use reth_rpc_layer::{JwtError, JwtSecret};
+use reth_rpc_layer::{JwtError, JwtSecret, JwtSecrets};
 use reth_rpc_server_types::RpcModuleSelection;
 use std::{net::SocketAddr, path::PathBuf};
 use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    auth::AuthServerConfig,
+    cost_budget::{CostBudgetLayer, MethodWeights},
+    error::RpcError,
+    static_responses::StaticResponsesLayer,
+    trace_limiter::TraceCallLimiter,
+    IpcServerBuilder, RpcModuleConfig, RpcServerConfig, TlsConfig, TransportRpcModuleConfig,
+    WsConnectionConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -75,6 +80,13 @@ pub trait RethRpcServerConfig {
     /// jwt secret in case the `auth_jwtsecret` argument is not provided.
     fn auth_jwt_secret(&self, default_jwt_path: PathBuf) -> Result<JwtSecret, JwtError>;
 
+    /// Returns the secrets that are accepted by the auth server, which are the given secret and,
+    /// if configured, the secondary secret during the grace period.
+    ///
+    /// If the secondary secret file cannot be read, or does not contain a hex-encoded key of 256
+    /// bits, this returns an error.
+    fn auth_jwt_secrets(&self, jwt_secret: JwtSecret) -> Result<JwtSecrets, JwtError>;
+
     /// Returns the configured jwt secret key for the regular rpc servers, if any.
     ///
     /// Note: this is not used for the auth server (engine API).
@@ -97,12 +109,18 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
+            .max_addresses_per_filter(self.rpc_max_addresses_per_filter.unwrap_or_max() as usize)
+            .max_topics_per_position(self.rpc_max_topics_per_position.unwrap_or_max() as usize)
             .eth_proof_window(self.rpc_eth_proof_window)
             .rpc_gas_cap(self.rpc_gas_cap)
             .rpc_max_simulate_blocks(self.rpc_max_simulate_blocks)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
             .proof_permits(self.rpc_proof_permits)
+            .max_subscription_bytes_per_sec(self.rpc_max_subscription_bytes_per_sec.0)
+            .close_slow_subscriptions(self.rpc_close_slow_subscriptions)
+            .pending_tx_subscription_buffer(self.rpc_pending_tx_subscription_buffer)
+            .send_raw_transaction_sync_timeout(self.rpc_send_raw_transaction_sync_timeout)
     }
 
     fn flashbots_config(&self) -> ValidationApiConfig {
@@ -166,11 +184,13 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_request_body_size(self.rpc_max_request_size_bytes())
             .max_response_body_size(self.rpc_max_response_size_bytes())
             .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get())
+            .set_message_buffer_capacity(self.rpc_max_buffered_notifications.get())
     }
 
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity> {
         IpcServerBuilder::default()
             .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get())
+            .set_message_buffer_capacity(self.rpc_max_buffered_notifications.get())
             .max_request_body_size(self.rpc_max_request_size_bytes())
             .max_response_body_size(self.rpc_max_response_size_bytes())
             .max_connections(self.rpc_max_connections.get())
@@ -178,7 +198,17 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let tls = self
+            .rpc_tls_cert
+            .clone()
+            .zip(self.rpc_tls_key.clone())
+            .map(|(cert_path, key_path)| TlsConfig::new(cert_path, key_path));
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_metrics_client_header(self.rpc_metrics_client_header.clone())
+            .with_block_range_override_header(self.rpc_block_range_override_header.clone())
+            .with_tls(tls)
+            .with_max_connections(self.rpc_max_connections.get());
 
         if self.http_api.is_some() && !self.http {
             warn!(
@@ -193,13 +223,23 @@ impl RethRpcServerConfig for RpcServerArgs {
                 .with_http_address(socket_address)
                 .with_http(self.http_ws_server_builder())
                 .with_http_cors(self.http_corsdomain.clone())
+                .with_http_admin_cors(self.http_corsdomain_admin.clone())
                 .with_http_disable_compression(self.http_disable_compression)
-                .with_ws_cors(self.ws_allowed_origins.clone());
+                .with_ws_cors(self.ws_allowed_origins.clone())
+                .with_ws_admin_cors(self.ws_allowed_origins_admin.clone());
         }
 
         if self.ws {
             let socket_address = SocketAddr::new(self.ws_addr, self.ws_port);
-            config = config.with_ws_address(socket_address).with_ws(self.http_ws_server_builder());
+            config = config
+                .with_ws_address(socket_address)
+                .with_ws(self.http_ws_server_builder())
+                .with_ws_connection_config(
+                    WsConnectionConfig::default()
+                        .with_ping_interval(self.ws_ping_interval)
+                        .with_idle_timeout(self.ws_idle_timeout)
+                        .with_max_lifetime(self.ws_max_lifetime),
+                );
         }
 
         if self.is_ipc_enabled() {
@@ -207,13 +247,47 @@ impl RethRpcServerConfig for RpcServerArgs {
                 config.with_ipc(self.ipc_server_builder()).with_ipc_endpoint(self.ipcpath.clone());
         }
 
+        if self.rpc_max_trace_calls > 0 {
+            config = config.with_trace_call_limiter(TraceCallLimiter::new(
+                self.rpc_max_trace_calls,
+                self.rpc_max_queued_trace_calls,
+            ));
+        }
+
+        config = config.with_strict_params_validation(self.rpc_strict_validation);
+        config = config.with_static_responses(StaticResponsesLayer::new(
+            self.rpc_static_responses.iter().filter(|method| !method.is_empty()).cloned(),
+        ));
+
+        if self.rpc_max_batch_cost.0.is_some() || self.rpc_max_connection_cost.0.is_some() {
+            let weights =
+                MethodWeights::default().with_weights(self.rpc_method_weights.iter().cloned());
+            config = config.with_cost_budget(CostBudgetLayer::new(
+                weights,
+                self.rpc_max_batch_cost.0,
+                self.rpc_max_connection_cost.0,
+            ));
+        }
+
         config
     }
 
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
-        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        let secrets = self.auth_jwt_secrets(jwt_secret).map_err(|err| {
+            RpcError::Custom(format!("failed to read secondary JWT secret: {err}"))
+        })?;
+
+        let tls = self
+            .auth_tls_cert
+            .clone()
+            .zip(self.auth_tls_key.clone())
+            .map(|(cert_path, key_path)| TlsConfig::new(cert_path, key_path));
+        let mut builder = AuthServerConfig::builder(jwt_secret)
+            .secrets(secrets)
+            .socket_addr(address)
+            .with_tls(tls);
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
@@ -232,6 +306,15 @@ impl RethRpcServerConfig for RpcServerArgs {
         }
     }
 
+    fn auth_jwt_secrets(&self, jwt_secret: JwtSecret) -> Result<JwtSecrets, JwtError> {
+        let secrets = JwtSecrets::new(jwt_secret);
+        let Some(fpath) = self.auth_jwtsecret_secondary.as_ref() else { return Ok(secrets) };
+
+        debug!(target: "reth::cli", user_path=?fpath, "Reading secondary JWT auth secret file");
+        let secondary = JwtSecret::from_file(fpath)?;
+        Ok(secrets.with_secondary(secondary, self.auth_jwtsecret_grace_period))
+    }
+
     fn rpc_secret_key(&self) -> Option<JwtSecret> {
         self.rpc_jwtsecret
     }
@@ -379,11 +462,17 @@ mod tests {
             "100",
             "--rpc-max-logs-per-response",
             "200",
+            "--rpc.max-addresses-per-filter",
+            "10",
+            "--rpc.max-topics-per-position",
+            "0",
         ])
         .args;
 
         let config = args.eth_config().filter_config();
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
+        assert_eq!(config.max_addresses_per_filter, Some(10));
+        assert_eq!(config.max_topics_per_position, Some(usize::MAX));
     }
 }
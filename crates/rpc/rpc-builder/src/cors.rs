@@ -1,6 +1,27 @@
-use http::{HeaderValue, Method};
+use http::{header::ORIGIN, HeaderValue, Method};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Id, Request},
+    MethodResponse,
+};
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
+use std::{
+    future::Future,
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
+/// Modules that are only available to origins of the admin CORS domains, if those are configured.
+pub const DEFAULT_CORS_ADMIN_MODULES: [RethRpcModule; 3] =
+    [RethRpcModule::Admin, RethRpcModule::Debug, RethRpcModule::Miner];
+
+/// Error code of calls that are rejected because of their origin.
+const ORIGIN_NOT_ALLOWED_CODE: i32 = -32001;
+
 /// Error thrown when parsing cors domains went wrong
 #[derive(Debug, thiserror::Error)]
 pub enum CorsDomainError {
@@ -17,37 +38,362 @@ pub enum CorsDomainError {
         /// The input string containing the incorrectly used wildcard
         input: String,
     },
+
+    /// Indicates that a wildcard was used anywhere but as the leading label of a domain
+    #[error("{domain} is an invalid wildcard domain, only a leading `*.` label is supported")]
+    InvalidWildcard {
+        /// The domain that contains the invalid wildcard
+        domain: String,
+    },
+}
+
+/// A single allowed origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OriginPattern {
+    /// Matches the origin exactly.
+    Exact(HeaderValue),
+    /// Matches all subdomains of a domain, e.g. `https://*.example.com`.
+    Subdomain {
+        /// The required scheme, any scheme matches if not set.
+        scheme: Option<String>,
+        /// The lowercase domain and port, including the leading dot.
+        suffix: String,
+    },
+}
+
+impl OriginPattern {
+    fn parse(domain: &str) -> Result<Self, CorsDomainError> {
+        let (scheme, host) = match domain.split_once("://") {
+            Some((scheme, host)) => (Some(scheme), host),
+            None => (None, domain),
+        };
+
+        if let Some(suffix) = host.strip_prefix("*.") {
+            if suffix.is_empty() || suffix.contains('*') {
+                return Err(CorsDomainError::InvalidWildcard { domain: domain.to_string() })
+            }
+            return Ok(Self::Subdomain {
+                scheme: scheme.map(str::to_ascii_lowercase),
+                suffix: format!(".{}", suffix.to_ascii_lowercase()),
+            })
+        }
+        if domain.contains('*') {
+            return Err(CorsDomainError::InvalidWildcard { domain: domain.to_string() })
+        }
+
+        domain
+            .parse::<HeaderValue>()
+            .map(Self::Exact)
+            .map_err(|_| CorsDomainError::InvalidHeader { domain: domain.to_string() })
+    }
+
+    fn matches(&self, origin: &HeaderValue) -> bool {
+        match self {
+            Self::Exact(allowed) => allowed == origin,
+            Self::Subdomain { scheme, suffix } => {
+                let Ok(origin) = origin.to_str() else { return false };
+                let Some((origin_scheme, host)) = origin.split_once("://") else { return false };
+                if scheme.as_ref().is_some_and(|scheme| !scheme.eq_ignore_ascii_case(origin_scheme))
+                {
+                    return false
+                }
+                host.len() > suffix.len() && host.to_ascii_lowercase().ends_with(suffix.as_str())
+            }
+        }
+    }
+}
+
+/// Origins that are allowed to make cross-origin requests.
+///
+/// Parsed from a comma separated list of origins, or `*` to allow any origin. Origins can match all
+/// subdomains of a domain with a leading wildcard label, e.g. `https://*.example.com`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowedOrigins {
+    /// The allowed origins, `None` if any origin is allowed.
+    patterns: Option<Vec<OriginPattern>>,
 }
 
-/// Creates a [`CorsLayer`] from the given domains
-pub(crate) fn create_cors_layer(http_cors_domains: &str) -> Result<CorsLayer, CorsDomainError> {
-    let cors = match http_cors_domains.trim() {
-        "*" => CorsLayer::new()
-            .allow_methods([Method::GET, Method::POST])
-            .allow_origin(Any)
-            .allow_headers(Any),
-        _ => {
-            let iter = http_cors_domains.split(',');
-            if iter.clone().any(|o| o == "*") {
-                return Err(CorsDomainError::WildCardNotAllowed {
-                    input: http_cors_domains.to_string(),
-                })
+impl AllowedOrigins {
+    /// Returns true if any origin is allowed.
+    pub const fn is_any(&self) -> bool {
+        self.patterns.is_none()
+    }
+
+    /// Returns true if the given origin is allowed.
+    pub fn allows(&self, origin: &HeaderValue) -> bool {
+        self.patterns
+            .as_ref()
+            .is_none_or(|patterns| patterns.iter().any(|pattern| pattern.matches(origin)))
+    }
+
+    /// Returns the origins allowed by either `self` or `other`.
+    fn union(self, other: Self) -> Self {
+        let patterns = match (self.patterns, other.patterns) {
+            (Some(mut patterns), Some(other)) => {
+                patterns.extend(other.into_iter().filter(|pattern| !patterns.contains(pattern)));
+                Some(patterns)
             }
+            _ => None,
+        };
+        Self { patterns }
+    }
+
+    /// Converts the origins into the [`AllowOrigin`] of a [`CorsLayer`].
+    fn into_allow_origin(self) -> AllowOrigin {
+        let Some(patterns) = self.patterns else { return AllowOrigin::any() };
+        if patterns.iter().all(|pattern| matches!(pattern, OriginPattern::Exact(_))) {
+            return AllowOrigin::list(patterns.into_iter().map(|pattern| match pattern {
+                OriginPattern::Exact(origin) => origin,
+                OriginPattern::Subdomain { .. } => unreachable!("all patterns are exact"),
+            }))
+        }
+        let origins = Self { patterns: Some(patterns) };
+        AllowOrigin::predicate(move |origin, _| origins.allows(origin))
+    }
+}
+
+impl FromStr for AllowedOrigins {
+    type Err = CorsDomainError;
 
-            let origins = iter
-                .map(|domain| {
-                    domain
-                        .parse::<HeaderValue>()
-                        .map_err(|_| CorsDomainError::InvalidHeader { domain: domain.to_string() })
-                })
-                .collect::<Result<Vec<HeaderValue>, _>>()?;
+    fn from_str(domains: &str) -> Result<Self, Self::Err> {
+        if domains.trim() == "*" {
+            return Ok(Self { patterns: None })
+        }
 
-            let origin = AllowOrigin::list(origins);
-            CorsLayer::new()
-                .allow_methods([Method::GET, Method::POST])
-                .allow_origin(origin)
-                .allow_headers(Any)
+        let iter = domains.split(',');
+        if iter.clone().any(|o| o == "*") {
+            return Err(CorsDomainError::WildCardNotAllowed { input: domains.to_string() })
         }
+
+        let patterns = iter.map(OriginPattern::parse).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { patterns: Some(patterns) })
+    }
+}
+
+/// Creates a [`CorsLayer`] from the given domains, if any.
+///
+/// If admin domains are configured, the layer allows the origins of both lists. Calls to admin
+/// modules are then restricted by the [`CorsModulesLayer`] and [`CorsModulesRpcLayer`].
+pub(crate) fn create_cors_layer(
+    cors_domains: Option<&str>,
+    admin_cors_domains: Option<&str>,
+) -> Result<Option<CorsLayer>, CorsDomainError> {
+    let origins = match (cors_domains, admin_cors_domains) {
+        (Some(domains), Some(admin)) => domains.parse::<AllowedOrigins>()?.union(admin.parse()?),
+        (Some(domains), None) | (None, Some(domains)) => domains.parse()?,
+        (None, None) => return Ok(None),
     };
-    Ok(cors)
+
+    let cors = CorsLayer::new().allow_methods([Method::GET, Method::POST]).allow_headers(Any);
+    Ok(Some(if origins.is_any() {
+        cors.allow_origin(Any)
+    } else {
+        cors.allow_origin(origins.into_allow_origin())
+    }))
+}
+
+/// Marks requests from an origin that is not allowed to call admin modules.
+#[derive(Debug, Clone, Copy)]
+struct RestrictedOrigin;
+
+/// HTTP middleware that marks requests whose origin is not one of the admin CORS domains.
+///
+/// The mark is picked up by the [`CorsModulesRpcLayer`], which rejects calls to admin modules.
+/// Requests without an `Origin` header aren't cross-origin requests and are never restricted.
+#[derive(Debug, Clone)]
+pub(crate) struct CorsModulesLayer {
+    admin: Arc<AllowedOrigins>,
+}
+
+impl CorsModulesLayer {
+    /// Creates the layer for the given admin domains.
+    pub(crate) fn new(admin_cors_domains: &str) -> Result<Self, CorsDomainError> {
+        Ok(Self { admin: Arc::new(admin_cors_domains.parse()?) })
+    }
+}
+
+impl<S> Layer<S> for CorsModulesLayer {
+    type Service = CorsModulesService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CorsModulesService { inner, admin: self.admin.clone() }
+    }
+}
+
+/// Service of the [`CorsModulesLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct CorsModulesService<S> {
+    inner: S,
+    admin: Arc<AllowedOrigins>,
+}
+
+impl<S, B> Service<http::Request<B>> for CorsModulesService<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        if req.headers().get(ORIGIN).is_some_and(|origin| !self.admin.allows(origin)) {
+            req.extensions_mut().insert(RestrictedOrigin);
+        }
+        self.inner.call(req)
+    }
+}
+
+/// RPC middleware that rejects calls to admin modules from origins marked by the
+/// [`CorsModulesLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct CorsModulesRpcLayer {
+    admin_modules: Arc<RpcModuleSelection>,
+}
+
+impl CorsModulesRpcLayer {
+    /// Creates the layer that restricts the given modules.
+    pub(crate) fn new(admin_modules: RpcModuleSelection) -> Self {
+        Self { admin_modules: Arc::new(admin_modules) }
+    }
+}
+
+impl<S> Layer<S> for CorsModulesRpcLayer {
+    type Service = CorsModulesRpcService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CorsModulesRpcService { inner, admin_modules: self.admin_modules.clone() }
+    }
+}
+
+/// Service of the [`CorsModulesRpcLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct CorsModulesRpcService<S> {
+    inner: S,
+    admin_modules: Arc<RpcModuleSelection>,
+}
+
+impl<S> CorsModulesRpcService<S> {
+    /// Returns true if the call is to an admin module from a restricted origin.
+    fn is_denied(&self, req: &Request<'_>) -> bool {
+        req.extensions().get::<RestrictedOrigin>().is_some() &&
+            req.method_name()
+                .split_once('_')
+                .and_then(|(module, _)| module.parse::<RethRpcModule>().ok())
+                .is_some_and(|module| self.admin_modules.contains(&module))
+    }
+}
+
+impl<S> RpcServiceT for CorsModulesRpcService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let denied = self.is_denied(&req);
+        let id = req.id().into_owned();
+        let inner = self.inner.clone();
+        async move {
+            if denied {
+                return MethodResponse::error(id, origin_not_allowed())
+            }
+            inner.call(req).await
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let denied = requests.iter().any(|entry| match entry {
+            Ok(BatchEntry::Call(req)) => self.is_denied(req),
+            _ => false,
+        });
+        let inner = self.inner.clone();
+        async move {
+            if denied {
+                return MethodResponse::error(Id::Null, origin_not_allowed())
+            }
+            inner.batch(requests).await
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+fn origin_not_allowed() -> ErrorObject<'static> {
+    ErrorObject::owned(
+        ORIGIN_NOT_ALLOWED_CODE,
+        "origin is not allowed to call this method",
+        None::<()>,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(origin: &'static str) -> HeaderValue {
+        HeaderValue::from_static(origin)
+    }
+
+    #[test]
+    fn parse_allowed_origins() {
+        assert!("*".parse::<AllowedOrigins>().unwrap().is_any());
+        assert!(matches!(
+            "https://a.com,*".parse::<AllowedOrigins>(),
+            Err(CorsDomainError::WildCardNotAllowed { .. })
+        ));
+        assert!(matches!(
+            "https://a.*.com".parse::<AllowedOrigins>(),
+            Err(CorsDomainError::InvalidWildcard { .. })
+        ));
+        assert!(matches!(
+            "https://*.".parse::<AllowedOrigins>(),
+            Err(CorsDomainError::InvalidWildcard { .. })
+        ));
+    }
+
+    #[test]
+    fn match_wildcard_subdomains() {
+        let origins = "https://app.com,https://*.example.com".parse::<AllowedOrigins>().unwrap();
+        assert!(origins.allows(&origin("https://app.com")));
+        assert!(origins.allows(&origin("https://a.example.com")));
+        assert!(origins.allows(&origin("https://a.b.Example.com")));
+        assert!(!origins.allows(&origin("https://example.com")));
+        assert!(!origins.allows(&origin("https://badexample.com")));
+        assert!(!origins.allows(&origin("http://a.example.com")));
+        assert!(!origins.allows(&origin("https://a.example.com:8080")));
+
+        let any_scheme = "*.example.com".parse::<AllowedOrigins>().unwrap();
+        assert!(any_scheme.allows(&origin("http://a.example.com")));
+        assert!(any_scheme.allows(&origin("https://a.example.com")));
+    }
+
+    #[test]
+    fn union_of_origins() {
+        let public = "https://a.com".parse::<AllowedOrigins>().unwrap();
+        let admin = "https://*.b.com".parse::<AllowedOrigins>().unwrap();
+        let origins = public.clone().union(admin);
+        assert!(origins.allows(&origin("https://a.com")));
+        assert!(origins.allows(&origin("https://x.b.com")));
+        assert!(!origins.allows(&origin("https://c.com")));
+
+        assert!(public.union("*".parse().unwrap()).is_any());
+    }
 }
@@ -0,0 +1,483 @@
+//! [`jsonrpsee`] helper layer for cost-weighted budgets of RPC calls.
+
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Request},
+    ConnectionId, MethodResponse,
+};
+use parking_lot::Mutex;
+use reth_metrics::{metrics::Counter, Metrics};
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tower::Layer;
+
+/// The weight of methods that have no configured weight.
+pub const DEFAULT_METHOD_WEIGHT: u64 = 1;
+
+/// The interval over which the cost of the calls of a connection is accounted.
+pub const DEFAULT_CONNECTION_BUDGET_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Error code of calls that are rejected because they exceed a cost budget.
+pub const COST_BUDGET_EXCEEDED_CODE: i32 = -32005;
+
+/// The weights of RPC methods that are used to compute the cost of calls.
+///
+/// A weight can be configured for a single method, e.g. `eth_getLogs`, or for all methods of a
+/// namespace, e.g. `trace_*`. The weight of a method takes precedence over the weight of its
+/// namespace. Methods without a configured weight have the [`DEFAULT_METHOD_WEIGHT`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodWeights {
+    /// Weight of methods without a configured weight.
+    default: u64,
+    /// Weights of methods and namespaces.
+    weights: HashMap<String, u64>,
+}
+
+impl MethodWeights {
+    /// Creates weights where every method has the given weight.
+    pub fn uniform(default: u64) -> Self {
+        Self { default, weights: HashMap::new() }
+    }
+
+    /// Sets the weight of a method, or of a namespace if the name ends with `_*`.
+    pub fn with_weight(mut self, method: impl Into<String>, weight: u64) -> Self {
+        self.weights.insert(method.into(), weight);
+        self
+    }
+
+    /// Sets the weights of the given methods or namespaces.
+    pub fn with_weights(
+        mut self,
+        weights: impl IntoIterator<Item = (impl Into<String>, u64)>,
+    ) -> Self {
+        self.weights.extend(weights.into_iter().map(|(method, weight)| (method.into(), weight)));
+        self
+    }
+
+    /// Returns the weight of the given method.
+    pub fn weight(&self, method: &str) -> u64 {
+        if let Some(weight) = self.weights.get(method) {
+            return *weight
+        }
+        method
+            .split_once('_')
+            .and_then(|(namespace, _)| self.weights.get(&format!("{namespace}_*")))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+impl Default for MethodWeights {
+    /// Weights that reflect the relative cost of the methods that are expensive to serve.
+    fn default() -> Self {
+        Self::uniform(DEFAULT_METHOD_WEIGHT).with_weights([
+            ("eth_getLogs", 100),
+            ("eth_getFilterLogs", 100),
+            ("eth_simulateV1", 50),
+            ("eth_callMany", 50),
+            ("eth_call", 10),
+            ("eth_estimateGas", 10),
+            ("eth_createAccessList", 10),
+            ("eth_getProof", 10),
+            ("eth_getBlockReceipts", 10),
+            ("eth_feeHistory", 5),
+            ("debug_*", 50),
+            ("trace_*", 50),
+        ])
+    }
+}
+
+/// Enforces cost budgets on the calls of each connection.
+///
+/// The cost of a call is the weight of its method, see [`MethodWeights`], and the cost of a batch
+/// is the sum of the costs of its entries. Two budgets are enforced:
+///
+/// - the cost of a single batch, so that a batch can't be used to smuggle an arbitrary amount of
+///   expensive calls into a single request,
+/// - the cost of all calls and batches of a connection within an interval, so that splitting the
+///   calls into batches or sending them one by one doesn't bypass the limit.
+///
+/// Calls that exceed a budget are rejected with a [`COST_BUDGET_EXCEEDED_CODE`] error whose data
+/// contains the cost and the exceeded limit. Batches that exceed a budget are rejected as a whole,
+/// with such an error for each call of the batch.
+///
+/// The connection budget is keyed by the [`ConnectionId`] of the requests, so that HTTP requests
+/// sent over the same keep-alive connection share a budget although the service is created per
+/// request. Requests without a [`ConnectionId`] are accounted per service.
+#[derive(Debug, Clone)]
+pub struct CostBudgetLayer {
+    inner: Arc<CostBudgetLayerInner>,
+}
+
+impl CostBudgetLayer {
+    /// Creates a new layer with the given method weights, batch budget and connection budget.
+    ///
+    /// The connection budget is accounted over the [`DEFAULT_CONNECTION_BUDGET_INTERVAL`].
+    pub fn new(
+        weights: MethodWeights,
+        max_batch_cost: Option<u64>,
+        max_connection_cost: Option<u64>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(CostBudgetLayerInner {
+                weights,
+                max_batch_cost,
+                max_connection_cost,
+                interval: DEFAULT_CONNECTION_BUDGET_INTERVAL,
+                budgets: Default::default(),
+                metrics: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the cost of the batch with the given methods.
+    fn batch_cost<'a>(&self, methods: impl IntoIterator<Item = &'a str>) -> u64 {
+        methods
+            .into_iter()
+            .fold(0u64, |cost, method| cost.saturating_add(self.inner.weights.weight(method)))
+    }
+
+    /// Returns the budget of the connection with the given id, or the fallback budget of the
+    /// service if the connection is unknown.
+    fn connection_budget(
+        &self,
+        id: Option<ConnectionId>,
+        fallback: &ConnectionBudget,
+        now: Instant,
+    ) -> ConnectionBudget {
+        let Some(id) = id else { return fallback.clone() };
+        self.inner.budgets.lock().get_or_insert(id, self.inner.interval, now)
+    }
+
+    /// Checks the cost of a call or batch against the budgets and charges the connection budget.
+    fn charge(
+        &self,
+        budget: &ConnectionBudget,
+        cost: u64,
+        is_batch: bool,
+        now: Instant,
+    ) -> Result<(), CostBudgetExceeded> {
+        if is_batch {
+            if let Some(limit) = self.inner.max_batch_cost {
+                if cost > limit {
+                    self.inner.metrics.rejected_batches_total.increment(1);
+                    return Err(CostBudgetExceeded { kind: BudgetKind::Batch, cost, limit })
+                }
+            }
+        }
+        if let Some(limit) = self.inner.max_connection_cost {
+            if !budget.try_spend(cost, limit, self.inner.interval, now) {
+                if is_batch {
+                    self.inner.metrics.rejected_batches_total.increment(1);
+                } else {
+                    self.inner.metrics.rejected_calls_total.increment(1);
+                }
+                return Err(CostBudgetExceeded { kind: BudgetKind::Connection, cost, limit })
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S> Layer<S> for CostBudgetLayer {
+    type Service = CostBudgetService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        // the service is created per connection for WS and per request for HTTP, the budget of the
+        // service is only used for requests without a connection id
+        CostBudgetService { inner, layer: self.clone(), budget: Default::default() }
+    }
+}
+
+#[derive(Debug)]
+struct CostBudgetLayerInner {
+    /// Weights of the methods.
+    weights: MethodWeights,
+    /// Maximum cost of a single batch.
+    max_batch_cost: Option<u64>,
+    /// Maximum cost of the calls of a connection within the interval.
+    max_connection_cost: Option<u64>,
+    /// Interval over which the connection budget is accounted.
+    interval: Duration,
+    /// Budgets of the connections.
+    budgets: Mutex<ConnectionBudgets>,
+    /// Metrics of the layer.
+    metrics: CostBudgetMetrics,
+}
+
+/// The budgets of the connections, keyed by their [`ConnectionId`].
+#[derive(Debug, Default)]
+struct ConnectionBudgets {
+    /// Budgets of the connections that made calls recently.
+    budgets: HashMap<ConnectionId, ConnectionBudget>,
+    /// The last time the budgets of idle connections were removed.
+    pruned_at: Option<Instant>,
+}
+
+impl ConnectionBudgets {
+    /// Returns the budget of the given connection, creating it if necessary.
+    ///
+    /// Budgets whose interval has ended are removed once per interval, they would be reset on the
+    /// next call anyway. This bounds the map to the connections that were active recently.
+    fn get_or_insert(
+        &mut self,
+        id: ConnectionId,
+        interval: Duration,
+        now: Instant,
+    ) -> ConnectionBudget {
+        if self.pruned_at.is_none_or(|at| now.saturating_duration_since(at) >= interval) {
+            self.budgets.retain(|_, budget| !budget.is_expired(interval, now));
+            self.pruned_at = Some(now);
+        }
+        self.budgets.entry(id).or_default().clone()
+    }
+}
+
+/// The cost spent by a connection in the current interval.
+#[derive(Debug, Clone, Default)]
+struct ConnectionBudget {
+    inner: Arc<Mutex<Option<(Instant, u64)>>>,
+}
+
+impl ConnectionBudget {
+    /// Returns true if the current interval of the budget has ended.
+    fn is_expired(&self, interval: Duration, now: Instant) -> bool {
+        self.inner.lock().is_none_or(|(start, _)| now.saturating_duration_since(start) >= interval)
+    }
+
+    /// Spends the given cost if it fits into the budget of the current interval.
+    fn try_spend(&self, cost: u64, limit: u64, interval: Duration, now: Instant) -> bool {
+        let mut state = self.inner.lock();
+        let (start, spent) = match *state {
+            Some((start, spent)) if now.saturating_duration_since(start) < interval => {
+                (start, spent)
+            }
+            _ => (now, 0),
+        };
+        let spent = spent.saturating_add(cost);
+        if spent > limit {
+            return false
+        }
+        *state = Some((start, spent));
+        true
+    }
+}
+
+/// The budget that was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BudgetKind {
+    /// The budget of a single batch.
+    Batch,
+    /// The budget of a connection within an interval.
+    Connection,
+}
+
+/// A call or batch that exceeds a cost budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CostBudgetExceeded {
+    /// The exceeded budget.
+    kind: BudgetKind,
+    /// The cost of the call or batch.
+    cost: u64,
+    /// The limit of the budget.
+    limit: u64,
+}
+
+impl CostBudgetExceeded {
+    fn into_error_object(self) -> ErrorObject<'static> {
+        let budget = match self.kind {
+            BudgetKind::Batch => "batch",
+            BudgetKind::Connection => "connection",
+        };
+        let data = json!({ "budget": budget, "cost": self.cost, "limit": self.limit });
+        ErrorObject::owned(COST_BUDGET_EXCEEDED_CODE, self.to_string(), Some(data))
+    }
+}
+
+impl fmt::Display for CostBudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            BudgetKind::Batch => {
+                write!(f, "batch cost {} exceeds the limit of {}", self.cost, self.limit)
+            }
+            BudgetKind::Connection => write!(
+                f,
+                "request cost {} exceeds the remaining budget of the connection, limit is {}",
+                self.cost, self.limit
+            ),
+        }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that enforces cost budgets, see [`CostBudgetLayer`].
+#[derive(Debug, Clone)]
+pub struct CostBudgetService<S> {
+    /// The inner service being wrapped
+    inner: S,
+    /// The layer shared by all connections
+    layer: CostBudgetLayer,
+    /// The budget of requests without a connection id
+    budget: ConnectionBudget,
+}
+
+impl<S> RpcServiceT for CostBudgetService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let cost = self.layer.inner.weights.weight(req.method_name());
+        let now = Instant::now();
+        let connection_id = req.extensions().get::<ConnectionId>().copied();
+        let budget = self.layer.connection_budget(connection_id, &self.budget, now);
+        let exceeded = self.layer.charge(&budget, cost, false, now).err();
+        let id = req.id().into_owned();
+        let inner = self.inner.clone();
+        async move {
+            if let Some(exceeded) = exceeded {
+                return MethodResponse::error(id, exceeded.into_error_object())
+            }
+            inner.call(req).await
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let cost = self.layer.batch_cost(requests.iter().filter_map(|entry| match entry {
+            Ok(BatchEntry::Call(req)) => Some(req.method_name()),
+            Ok(BatchEntry::Notification(n)) => Some(n.method.as_ref()),
+            Err(_) => None,
+        }));
+        let now = Instant::now();
+        let connection_id = requests.iter().find_map(|entry| match entry {
+            Ok(BatchEntry::Call(req)) => req.extensions().get::<ConnectionId>().copied(),
+            _ => None,
+        });
+        let budget = self.layer.connection_budget(connection_id, &self.budget, now);
+        if let Err(exceeded) = self.layer.charge(&budget, cost, true, now) {
+            // replace every call of the batch with an error response for its id
+            let error = exceeded.into_error_object();
+            for entry in requests.iter_mut() {
+                let id = match entry {
+                    Ok(BatchEntry::Call(req)) => Some(req.id().into_owned()),
+                    _ => None,
+                };
+                if let Some(id) = id {
+                    *entry = Err(BatchEntryErr::new(id, error.clone()));
+                }
+            }
+        }
+        self.inner.batch(requests)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Metrics for the [`CostBudgetLayer`].
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.cost_budget")]
+struct CostBudgetMetrics {
+    /// The number of calls rejected because they exceeded the connection budget
+    rejected_calls_total: Counter,
+    /// The number of batches rejected because they exceeded the batch or connection budget
+    rejected_batches_total: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_weights() {
+        let weights = MethodWeights::default().with_weight("trace_block", 20);
+        assert_eq!(weights.weight("eth_chainId"), DEFAULT_METHOD_WEIGHT);
+        assert_eq!(weights.weight("eth_getLogs"), 100);
+        assert_eq!(weights.weight("trace_filter"), 50);
+        assert_eq!(weights.weight("trace_block"), 20);
+        assert_eq!(weights.weight("nomethod"), DEFAULT_METHOD_WEIGHT);
+    }
+
+    #[test]
+    fn batch_budget() {
+        let layer = CostBudgetLayer::new(MethodWeights::default(), Some(250), None);
+        let budget = ConnectionBudget::default();
+        let now = Instant::now();
+
+        let cost = layer.batch_cost(["eth_getLogs", "eth_getLogs", "eth_chainId"]);
+        assert_eq!(cost, 201);
+        assert_eq!(layer.charge(&budget, cost, true, now), Ok(()));
+
+        let cost = layer.batch_cost(["eth_getLogs"; 3]);
+        assert_eq!(
+            layer.charge(&budget, cost, true, now),
+            Err(CostBudgetExceeded { kind: BudgetKind::Batch, cost: 300, limit: 250 })
+        );
+
+        // the batch budget doesn't apply to single calls
+        assert_eq!(layer.charge(&budget, 300, false, now), Ok(()));
+    }
+
+    #[test]
+    fn connection_budget() {
+        let layer = CostBudgetLayer::new(MethodWeights::default(), None, Some(200));
+        let budget = ConnectionBudget::default();
+        let now = Instant::now();
+
+        assert_eq!(layer.charge(&budget, 100, false, now), Ok(()));
+        assert_eq!(layer.charge(&budget, 100, true, now), Ok(()));
+        assert_eq!(
+            layer.charge(&budget, 1, false, now),
+            Err(CostBudgetExceeded { kind: BudgetKind::Connection, cost: 1, limit: 200 })
+        );
+
+        // other connections have their own budget
+        assert_eq!(layer.charge(&ConnectionBudget::default(), 200, false, now), Ok(()));
+
+        // the budget is replenished after the interval
+        let later = now + DEFAULT_CONNECTION_BUDGET_INTERVAL;
+        assert_eq!(layer.charge(&budget, 200, false, later), Ok(()));
+    }
+
+    #[test]
+    fn connection_budget_by_id() {
+        let layer = CostBudgetLayer::new(MethodWeights::default(), None, Some(200));
+        let now = Instant::now();
+
+        // requests of the same connection share the budget, even if served by other services
+        let budget = layer.connection_budget(Some(ConnectionId(1)), &Default::default(), now);
+        assert_eq!(layer.charge(&budget, 200, false, now), Ok(()));
+        let budget = layer.connection_budget(Some(ConnectionId(1)), &Default::default(), now);
+        assert!(layer.charge(&budget, 1, false, now).is_err());
+
+        let budget = layer.connection_budget(Some(ConnectionId(2)), &Default::default(), now);
+        assert_eq!(layer.charge(&budget, 200, false, now), Ok(()));
+        assert_eq!(layer.inner.budgets.lock().budgets.len(), 2);
+
+        // budgets of idle connections are removed after the interval
+        let later = now + DEFAULT_CONNECTION_BUDGET_INTERVAL;
+        let budget = layer.connection_budget(Some(ConnectionId(3)), &Default::default(), later);
+        assert_eq!(layer.charge(&budget, 200, false, later), Ok(()));
+        assert_eq!(layer.inner.budgets.lock().budgets.len(), 1);
+    }
+}
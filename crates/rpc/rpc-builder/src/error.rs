@@ -1,4 +1,4 @@
-use crate::{cors::CorsDomainError, RethRpcModule};
+use crate::{cors::CorsDomainError, RethRpcModule, TlsError};
 use reth_ipc::server::IpcServerStartError;
 use std::{
     collections::HashSet,
@@ -70,6 +70,9 @@ pub enum RpcError {
     /// Thrown when IPC server fails to start.
     #[error(transparent)]
     IpcServerError(#[from] IpcServerStartError),
+    /// Thrown when the TLS certificate or key can't be loaded.
+    #[error("failed to load TLS certificate: {0}")]
+    Tls(#[from] TlsError),
     /// Custom error.
     #[error("{0}")]
     Custom(String),
@@ -124,6 +127,17 @@ pub enum WsHttpSamePortError {
         /// Ws cors domains.
         ws_cors_domains: Option<String>,
     },
+    /// Ws and http server configured on same port but with different admin cors domains.
+    #[error(
+        "admin CORS domains for HTTP and WS are different, but they are on the same port: \
+         HTTP: {http_admin_cors_domains:?}, WS: {ws_admin_cors_domains:?}"
+    )]
+    ConflictingAdminCorsDomains {
+        /// Http admin cors domains.
+        http_admin_cors_domains: Option<String>,
+        /// Ws admin cors domains.
+        ws_admin_cors_domains: Option<String>,
+    },
     /// Ws and http server configured on same port but with different modules.
     #[error("{0}")]
     ConflictingModules(Box<ConflictingModules>),
@@ -28,7 +28,7 @@ where
     ) -> Self {
         let filter = EthFilter::new(eth_api.clone(), config.filter_config(), executor.clone());
 
-        let pubsub = EthPubSub::with_spawner(eth_api.clone(), executor);
+        let pubsub = EthPubSub::with_config(eth_api.clone(), executor, config.pubsub_config());
 
         Self { api: eth_api, filter, pubsub }
     }
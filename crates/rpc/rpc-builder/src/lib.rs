@@ -19,7 +19,13 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-use crate::{auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequestMetrics};
+use crate::{
+    auth::AuthRpcModule,
+    block_range_override::BlockRangeOverrideLayer,
+    cors::{CorsModulesLayer, CorsModulesRpcLayer},
+    error::WsHttpSamePortError,
+    metrics::{RpcClientLabelLayer, RpcRequestMetrics},
+};
 use alloy_network::Ethereum;
 use alloy_provider::{fillers::RecommendedFillers, Provider, ProviderBuilder};
 use core::marker::PhantomData;
@@ -37,13 +43,13 @@ use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc::{
     AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig, Web3Api,
+    OtterscanApi, RPCApi, RethApi, RethFeeApi, TraceApi, TxPoolApi, ValidationApiConfig, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{
-        pending_block::PendingEnvBuilder, Call, EthApiSpec, EthTransactions, LoadPendingBlock,
-        TraceExt,
+        pending_block::PendingEnvBuilder, Call, EthApiSpec, EthFees, EthTransactions,
+        LoadPendingBlock, TraceExt,
     },
     node::RpcNodeCoreAdapter,
     EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcConvert, RpcConverter, RpcHeader,
@@ -53,7 +59,7 @@ use reth_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig, EthSubscriptio
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 use reth_storage_api::{
     AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, ProviderBlock,
-    StateProviderFactory,
+    SenderTransactionsProvider, StateProviderFactory,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
@@ -64,9 +70,10 @@ use std::{
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
+use tokio::net::TcpListener;
 use tower_http::cors::CorsLayer;
 
-pub use cors::CorsDomainError;
+pub use cors::{AllowedOrigins, CorsDomainError, DEFAULT_CORS_ADMIN_MODULES};
 
 // re-export for convenience
 pub use jsonrpsee::server::ServerBuilder;
@@ -89,6 +96,9 @@ pub mod middleware;
 /// Cors utilities.
 mod cors;
 
+// Per request override of the `eth_getLogs` block range limit
+mod block_range_override;
+
 /// Rpc error utilities.
 pub mod error;
 
@@ -99,13 +109,37 @@ pub use eth::EthHandlers;
 // Rpc server metrics
 mod metrics;
 use crate::middleware::RethRpcMiddleware;
-pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
+pub use metrics::{MeteredRequestFuture, RpcMethodMetricsRegistry, RpcRequestMetricsService};
 use reth_chain_state::CanonStateSubscriptions;
 use reth_rpc::eth::sim_bundle::EthSimBundle;
 
 // Rpc rate limiter
 pub mod rate_limiter;
 
+// Limiter for concurrent tracing calls
+pub mod trace_limiter;
+use trace_limiter::TraceCallLimiter;
+
+// Strict validation of call parameters
+pub mod strict_params;
+use strict_params::StrictParamsLayer;
+
+// Cost-weighted budgets of calls and batches
+pub mod cost_budget;
+use cost_budget::CostBudgetLayer;
+
+// Pre-serialized responses of constant methods
+pub mod static_responses;
+use static_responses::StaticResponsesLayer;
+
+// Keepalive and lifetime of WS connections
+mod ws;
+pub use ws::WsConnectionConfig;
+
+// TLS termination of the http, ws and auth servers
+mod tls;
+pub use tls::{TlsConfig, TlsError, DEFAULT_MAX_CONNECTIONS, DEFAULT_TLS_RELOAD_INTERVAL};
+
 /// A builder type to configure the RPC module: See [`RpcModule`]
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
@@ -309,7 +343,8 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + SenderTransactionsProvider,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
@@ -660,6 +695,7 @@ where
             Transaction = N::SignedTx,
         > + AccountReader
         + ChangeSetReader
+        + SenderTransactionsProvider
         + CanonStateSubscriptions,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiServer<
@@ -754,6 +790,22 @@ where
         self
     }
 
+    /// Register Reth namespace, including the fee market methods served by the `eth` API, like
+    /// `reth_blobFeeHistory`.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime.
+    pub fn register_reth_with_fees(&mut self) -> &mut Self
+    where
+        EthApi: EthFees + 'static,
+    {
+        let mut module = self.reth_api().into_rpc();
+        module.merge(RethFeeApi::new(self.eth_api().clone()).into_rpc()).expect("No conflicts");
+        self.modules.insert(RethRpcModule::Reth, module.into());
+        self
+    }
+
     /// Instantiates `OtterscanApi`
     ///
     /// # Panics
@@ -775,7 +827,8 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + SenderTransactionsProvider,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
@@ -837,7 +890,8 @@ where
     Provider: FullRpcProvider<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + SenderTransactionsProvider,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: FullEthApiServer,
@@ -974,9 +1028,13 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), self.executor.clone())
-                                .into_rpc()
-                                .into()
+                            let mut module =
+                                RethApi::new(self.provider.clone(), self.executor.clone())
+                                    .into_rpc();
+                            module
+                                .merge(RethFeeApi::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+                            module.into()
                         }
                         // only relevant for Ethereum and configured in `EthereumAddOns`
                         // implementation
@@ -1012,6 +1070,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     http_server_config: Option<ServerConfigBuilder>,
     /// Allowed CORS Domains for http
     http_cors_domains: Option<String>,
+    /// Allowed CORS Domains for the admin modules over http
+    http_admin_cors_domains: Option<String>,
     /// Address where to bind the http server to
     http_addr: Option<SocketAddr>,
     /// Control whether http responses should be compressed
@@ -1020,6 +1080,12 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ws_server_config: Option<ServerConfigBuilder>,
     /// Allowed CORS Domains for ws.
     ws_cors_domains: Option<String>,
+    /// Allowed CORS Domains for the admin modules over ws.
+    ws_admin_cors_domains: Option<String>,
+    /// Keepalive and lifetime of ws connections
+    ws_connection_config: WsConnectionConfig,
+    /// Modules that are restricted to the admin CORS domains.
+    cors_admin_modules: RpcModuleSelection,
     /// Address where to bind the ws server to
     ws_addr: Option<SocketAddr>,
     /// Configs for JSON-RPC IPC server
@@ -1028,6 +1094,23 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Limiter for concurrent `debug_` and `trace_` calls over http and ws
+    trace_call_limiter: Option<TraceCallLimiter>,
+    /// Strict validation of `eth_` call parameters over http and ws
+    strict_params: Option<StrictParamsLayer>,
+    /// Cost budgets of calls and batches over http and ws
+    cost_budget: Option<CostBudgetLayer>,
+    /// Pre-serialized responses of constant methods over http and ws
+    static_responses: Option<StaticResponsesLayer>,
+    /// Header to label the RPC metrics of http and ws calls by client
+    metrics_client_header: Option<String>,
+    /// Header to override the maximum block range of `eth_getLogs` calls over http
+    block_range_override_header: Option<String>,
+    /// Certificate and key to terminate TLS over http and ws
+    tls: Option<TlsConfig>,
+    /// Maximum number of connections that are served at the same time by the http and ws servers
+    /// that accept their connections themselves, i.e. with TLS or a maximum connection lifetime
+    max_connections: u32,
     /// Configurable RPC middleware
     rpc_middleware: RpcMiddleware,
 }
@@ -1040,14 +1123,26 @@ impl Default for RpcServerConfig<Identity> {
         Self {
             http_server_config: None,
             http_cors_domains: None,
+            http_admin_cors_domains: None,
             http_addr: None,
             http_disable_compression: false,
             ws_server_config: None,
             ws_cors_domains: None,
+            ws_admin_cors_domains: None,
+            ws_connection_config: WsConnectionConfig::default(),
+            cors_admin_modules: RpcModuleSelection::from(DEFAULT_CORS_ADMIN_MODULES),
             ws_addr: None,
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            trace_call_limiter: None,
+            strict_params: None,
+            cost_budget: None,
+            static_responses: None,
+            metrics_client_header: None,
+            block_range_override_header: None,
+            tls: None,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
             rpc_middleware: Default::default(),
         }
     }
@@ -1104,18 +1199,99 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         RpcServerConfig {
             http_server_config: self.http_server_config,
             http_cors_domains: self.http_cors_domains,
+            http_admin_cors_domains: self.http_admin_cors_domains,
             http_addr: self.http_addr,
             http_disable_compression: self.http_disable_compression,
             ws_server_config: self.ws_server_config,
             ws_cors_domains: self.ws_cors_domains,
+            ws_admin_cors_domains: self.ws_admin_cors_domains,
+            ws_connection_config: self.ws_connection_config,
+            cors_admin_modules: self.cors_admin_modules,
             ws_addr: self.ws_addr,
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            trace_call_limiter: self.trace_call_limiter,
+            strict_params: self.strict_params,
+            cost_budget: self.cost_budget,
+            static_responses: self.static_responses,
+            metrics_client_header: self.metrics_client_header,
+            block_range_override_header: self.block_range_override_header,
+            tls: self.tls,
+            max_connections: self.max_connections,
             rpc_middleware,
         }
     }
 
+    /// Configures a [`TraceCallLimiter`] for `debug_` and `trace_` calls over http and ws.
+    pub fn with_trace_call_limiter(mut self, trace_call_limiter: TraceCallLimiter) -> Self {
+        self.trace_call_limiter = Some(trace_call_limiter);
+        self
+    }
+
+    /// Enables strict validation of `eth_` call parameters over http and ws, see
+    /// [`StrictParamsLayer`].
+    pub const fn with_strict_params_validation(mut self, enabled: bool) -> Self {
+        self.strict_params = if enabled { Some(StrictParamsLayer::new()) } else { None };
+        self
+    }
+
+    /// Configures the cost budgets of calls and batches over http and ws, see [`CostBudgetLayer`].
+    pub fn with_cost_budget(mut self, cost_budget: CostBudgetLayer) -> Self {
+        self.cost_budget = Some(cost_budget);
+        self
+    }
+
+    /// Configures the methods whose constant responses are served from pre-serialized results over
+    /// http and ws, see [`StaticResponsesLayer`].
+    ///
+    /// No methods are served if the layer is empty.
+    ///
+    /// Note: the layer wraps the middleware configured with
+    /// [`RpcServerConfig::set_rpc_middleware`], stored responses are therefore returned without
+    /// invoking that middleware.
+    pub fn with_static_responses(mut self, static_responses: StaticResponsesLayer) -> Self {
+        self.static_responses = (!static_responses.is_empty()).then_some(static_responses);
+        self
+    }
+
+    /// Configures the request header that labels the RPC metrics of http and ws calls by client.
+    ///
+    /// Every distinct header value creates new metric series, so the header should only be set by
+    /// trusted infrastructure, e.g. a proxy in front of the node.
+    pub fn with_metrics_client_header(mut self, header: Option<String>) -> Self {
+        self.metrics_client_header = header;
+        self
+    }
+
+    /// Configures the request header that overrides the maximum block range of `eth_getLogs` calls
+    /// over http.
+    ///
+    /// The header allows clients to bypass the block range limit, so it should only be set by
+    /// trusted infrastructure, e.g. a proxy in front of the node.
+    pub fn with_block_range_override_header(mut self, header: Option<String>) -> Self {
+        self.block_range_override_header = header;
+        self
+    }
+
+    /// Configures the certificate and key to terminate TLS over http and ws.
+    ///
+    /// The certificate is reloaded when its files change, see [`TlsConfig`].
+    pub fn with_tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Configures the maximum number of connections that are served at the same time when TLS or
+    /// a maximum connection lifetime is configured.
+    ///
+    /// The connections are accepted by the server itself in this case, so the limit of the
+    /// [`ServerConfigBuilder`] doesn't apply to them. This should match that limit.
+    pub const fn with_max_connections(mut self, max_connections: u32) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
     /// Configure the cors domains for http _and_ ws
     pub fn with_cors(self, cors_domain: Option<String>) -> Self {
         self.with_http_cors(cors_domain.clone()).with_ws_cors(cors_domain)
@@ -1127,6 +1303,12 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configure the keepalive and lifetime of WS connections
+    pub const fn with_ws_connection_config(mut self, config: WsConnectionConfig) -> Self {
+        self.ws_connection_config = config;
+        self
+    }
+
     /// Configure whether HTTP responses should be compressed
     pub const fn with_http_disable_compression(mut self, http_disable_compression: bool) -> Self {
         self.http_disable_compression = http_disable_compression;
@@ -1139,6 +1321,39 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configure the cors domains that may call the admin modules over http _and_ ws
+    ///
+    /// See also [`Self::with_http_admin_cors`].
+    pub fn with_admin_cors(self, cors_domain: Option<String>) -> Self {
+        self.with_http_admin_cors(cors_domain.clone()).with_ws_admin_cors(cors_domain)
+    }
+
+    /// Configure the cors domains that may call the admin modules over HTTP
+    ///
+    /// If set, cross-origin requests are accepted from both these and the regular cors domains,
+    /// but only requests from these domains may call the modules configured with
+    /// [`Self::with_cors_admin_modules`].
+    pub fn with_http_admin_cors(mut self, cors_domain: Option<String>) -> Self {
+        self.http_admin_cors_domains = cors_domain;
+        self
+    }
+
+    /// Configure the cors domains that may call the admin modules over WS
+    ///
+    /// See also [`Self::with_http_admin_cors`].
+    pub fn with_ws_admin_cors(mut self, cors_domain: Option<String>) -> Self {
+        self.ws_admin_cors_domains = cors_domain;
+        self
+    }
+
+    /// Configure the modules that are restricted to the admin cors domains
+    ///
+    /// Default is [`DEFAULT_CORS_ADMIN_MODULES`].
+    pub fn with_cors_admin_modules(mut self, modules: impl Into<RpcModuleSelection>) -> Self {
+        self.cors_admin_modules = modules.into();
+        self
+    }
+
     /// Configures the [`SocketAddr`] of the http server
     ///
     /// Default is [`Ipv4Addr::LOCALHOST`] and
@@ -1232,8 +1447,23 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
     }
 
     /// Creates the [`CorsLayer`] if any
-    fn maybe_cors_layer(cors: Option<String>) -> Result<Option<CorsLayer>, CorsDomainError> {
-        cors.as_deref().map(cors::create_cors_layer).transpose()
+    fn maybe_cors_layer(
+        cors: Option<&str>,
+        admin_cors: Option<&str>,
+    ) -> Result<Option<CorsLayer>, CorsDomainError> {
+        cors::create_cors_layer(cors, admin_cors)
+    }
+
+    /// Creates the layers that restrict the admin modules to the admin cors domains, if any
+    fn maybe_cors_modules_layers(
+        admin_cors: Option<&str>,
+        admin_modules: &RpcModuleSelection,
+    ) -> Result<(Option<CorsModulesLayer>, Option<CorsModulesRpcLayer>), CorsDomainError> {
+        let Some(admin_cors) = admin_cors else { return Ok((None, None)) };
+        Ok((
+            Some(CorsModulesLayer::new(admin_cors)?),
+            Some(CorsModulesRpcLayer::new(admin_modules.clone())),
+        ))
     }
 
     /// Creates the [`AuthLayer`] if any
@@ -1274,9 +1504,14 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             constants::DEFAULT_WS_RPC_PORT,
         )));
 
-        let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
+        let metrics = modules
+            .ipc
+            .as_ref()
+            .map(|ipc| RpcRequestMetrics::ipc(ipc, &modules.method_metrics))
+            .unwrap_or_default();
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
+        let tls = self.tls.as_ref().map(TlsConfig::acceptor).transpose()?;
 
         if let Some(builder) = self.ipc_server_config {
             let ipc = builder
@@ -1304,16 +1539,48 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 (a, b) => a.or(b),
             }
             .cloned();
+            let admin_cors = match (
+                self.ws_admin_cors_domains.as_ref(),
+                self.http_admin_cors_domains.as_ref(),
+            ) {
+                (Some(ws_cors), Some(http_cors)) => {
+                    if ws_cors.trim() != http_cors.trim() {
+                        return Err(WsHttpSamePortError::ConflictingAdminCorsDomains {
+                            http_admin_cors_domains: Some(http_cors.clone()),
+                            ws_admin_cors_domains: Some(ws_cors.clone()),
+                        }
+                        .into());
+                    }
+                    Some(ws_cors)
+                }
+                (a, b) => a.or(b),
+            }
+            .cloned();
+            let (cors_modules, cors_modules_rpc) =
+                Self::maybe_cors_modules_layers(admin_cors.as_deref(), &self.cors_admin_modules)?;
 
             // we merge this into one server using the http setup
             modules.config.ensure_ws_http_identical()?;
 
             if let Some(config) = self.http_server_config {
-                let server = ServerBuilder::new()
+                let config = self.ws_connection_config.apply_ping_config(config);
+                let builder = ServerBuilder::new()
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
-                            .option_layer(Self::maybe_cors_layer(cors)?)
+                            .option_layer(Self::maybe_cors_layer(
+                                cors.as_deref(),
+                                admin_cors.as_deref(),
+                            )?)
+                            .option_layer(cors_modules)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(
+                                self.metrics_client_header.as_deref().map(RpcClientLabelLayer::new),
+                            )
+                            .option_layer(
+                                self.block_range_override_header
+                                    .as_deref()
+                                    .map(BlockRangeOverrideLayer::new),
+                            )
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
                             )),
@@ -1325,33 +1592,73 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                                     .http
                                     .as_ref()
                                     .or(modules.ws.as_ref())
-                                    .map(RpcRequestMetrics::same_port)
+                                    .map(|module| {
+                                        RpcRequestMetrics::same_port(
+                                            module,
+                                            &modules.method_metrics,
+                                        )
+                                    })
                                     .unwrap_or_default(),
                             )
+                            .option_layer(cors_modules_rpc)
+                            .option_layer(self.cost_budget.clone())
+                            .option_layer(self.trace_call_limiter.clone())
+                            .option_layer(self.strict_params)
+                            .option_layer(self.static_responses.clone())
                             .layer(self.rpc_middleware.clone()),
                     )
-                    .set_config(config.build())
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
+                    .set_config(config.build());
+                let module = modules.http.as_ref().or(modules.ws.as_ref());
+                let max_lifetime = self.ws_connection_config.max_lifetime;
+                let (addr, handle) = if tls.is_some() || max_lifetime.is_some() {
+                    // TLS connections and connections with a maximum lifetime are accepted by us
+                    // and served by the server's service
+                    let listener = TcpListener::bind(http_socket_addr).await.map_err(|err| {
                         RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
                     })?;
-                let addr = server.local_addr().map_err(|err| {
-                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                })?;
-                if let Some(module) = modules.http.as_ref().or(modules.ws.as_ref()) {
-                    let handle = server.start(module.clone());
-                    http_handle = Some(handle.clone());
-                    ws_handle = Some(handle);
-                }
+                    let addr = listener.local_addr().map_err(|err| {
+                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                    })?;
+                    let handle = module.cloned().map(|methods| {
+                        let service_builder = builder.to_service_builder();
+                        tls::serve_connections(
+                            listener,
+                            tls,
+                            max_lifetime,
+                            self.max_connections,
+                            move |stop_handle| {
+                                let service = service_builder
+                                    .clone()
+                                    .build(methods.clone(), stop_handle.clone());
+                                move |connection| {
+                                    jsonrpsee::server::serve_with_graceful_shutdown(
+                                        connection,
+                                        service,
+                                        stop_handle.shutdown(),
+                                    )
+                                }
+                            },
+                        )
+                    });
+                    (addr, handle)
+                } else {
+                    let server = builder.build(http_socket_addr).await.map_err(|err| {
+                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                    })?;
+                    let addr = server.local_addr().map_err(|err| {
+                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                    })?;
+                    (addr, module.map(|module| server.start(module.clone())))
+                };
                 return Ok(RpcServerHandle {
                     http_local_addr: Some(addr),
                     ws_local_addr: Some(addr),
-                    http: http_handle,
-                    ws: ws_handle,
+                    http: handle.clone(),
+                    ws: handle,
                     ipc_endpoint: self.ipc_endpoint.clone(),
                     ipc: ipc_handle,
                     jwt_secret: self.jwt_secret,
+                    tls: self.tls.is_some(),
                 });
             }
         }
@@ -1362,60 +1669,181 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let mut http_server = None;
 
         if let Some(config) = self.ws_server_config {
-            let server = ServerBuilder::new()
-                .set_config(config.ws_only().build())
+            let (cors_modules, cors_modules_rpc) = Self::maybe_cors_modules_layers(
+                self.ws_admin_cors_domains.as_deref(),
+                &self.cors_admin_modules,
+            )?;
+            let config = self.ws_connection_config.apply_ping_config(config.ws_only());
+            let builder = ServerBuilder::new()
+                .set_config(config.build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_cors_layer(
+                            self.ws_cors_domains.as_deref(),
+                            self.ws_admin_cors_domains.as_deref(),
+                        )?)
+                        .option_layer(cors_modules)
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(
+                            self.metrics_client_header.as_deref().map(RpcClientLabelLayer::new),
+                        ),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(
+                            modules
+                                .ws
+                                .as_ref()
+                                .map(|ws| RpcRequestMetrics::ws(ws, &modules.method_metrics))
+                                .unwrap_or_default(),
+                        )
+                        .option_layer(cors_modules_rpc)
+                        .option_layer(self.cost_budget.clone())
+                        .option_layer(self.trace_call_limiter.clone())
+                        .option_layer(self.strict_params)
+                        .option_layer(self.static_responses.clone())
                         .layer(self.rpc_middleware.clone()),
-                )
-                .build(ws_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
-
-            let addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+                );
+
+            let max_lifetime = self.ws_connection_config.max_lifetime;
+            if max_lifetime.is_some() || tls.is_some() {
+                // The server closes connections only once it stops and doesn't terminate TLS, so
+                // we accept the connections ourselves, and close each of them once it exceeds the
+                // maximum lifetime.
+                let listener = TcpListener::bind(ws_socket_addr)
+                    .await
+                    .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+                let addr = listener
+                    .local_addr()
+                    .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+
+                let service_builder = builder.to_service_builder();
+                let methods = modules.ws.clone().expect("ws server error");
+                let handle = tls::serve_connections(
+                    listener,
+                    tls.clone(),
+                    max_lifetime,
+                    self.max_connections,
+                    move |stop_handle| {
+                        let service =
+                            service_builder.clone().build(methods.clone(), stop_handle.clone());
+                        move |connection| {
+                            jsonrpsee::server::serve_with_graceful_shutdown(
+                                connection,
+                                service,
+                                stop_handle.shutdown(),
+                            )
+                        }
+                    },
+                );
+
+                ws_local_addr = Some(addr);
+                ws_handle = Some(handle);
+            } else {
+                let server = builder
+                    .build(ws_socket_addr)
+                    .await
+                    .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+                let addr = server
+                    .local_addr()
+                    .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
-            ws_local_addr = Some(addr);
-            ws_server = Some(server);
+                ws_local_addr = Some(addr);
+                ws_server = Some(server);
+            }
         }
 
         if let Some(config) = self.http_server_config {
-            let server = ServerBuilder::new()
+            let (cors_modules, cors_modules_rpc) = Self::maybe_cors_modules_layers(
+                self.http_admin_cors_domains.as_deref(),
+                &self.cors_admin_modules,
+            )?;
+            let builder = ServerBuilder::new()
                 .set_config(config.http_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
+                        .option_layer(Self::maybe_cors_layer(
+                            self.http_cors_domains.as_deref(),
+                            self.http_admin_cors_domains.as_deref(),
+                        )?)
+                        .option_layer(cors_modules)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(
+                            self.metrics_client_header.as_deref().map(RpcClientLabelLayer::new),
+                        )
+                        .option_layer(
+                            self.block_range_override_header
+                                .as_deref()
+                                .map(BlockRangeOverrideLayer::new),
+                        )
                         .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
                         .layer(
-                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                            modules
+                                .http
+                                .as_ref()
+                                .map(|http| RpcRequestMetrics::http(http, &modules.method_metrics))
+                                .unwrap_or_default(),
                         )
+                        .option_layer(cors_modules_rpc)
+                        .option_layer(self.cost_budget.clone())
+                        .option_layer(self.trace_call_limiter.clone())
+                        .option_layer(self.strict_params)
+                        .option_layer(self.static_responses.clone())
                         .layer(self.rpc_middleware.clone()),
-                )
-                .build(http_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
-            let local_addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
-            http_local_addr = Some(local_addr);
-            http_server = Some(server);
+                );
+
+            if let Some(tls) = tls {
+                // TLS connections are accepted by us and served by the server's service
+                let listener = TcpListener::bind(http_socket_addr).await.map_err(|err| {
+                    RpcError::server_error(err, ServerKind::Http(http_socket_addr))
+                })?;
+                let local_addr = listener.local_addr().map_err(|err| {
+                    RpcError::server_error(err, ServerKind::Http(http_socket_addr))
+                })?;
+
+                let service_builder = builder.to_service_builder();
+                let methods = modules.http.clone().expect("http server error");
+                let handle = tls::serve_connections(
+                    listener,
+                    Some(tls),
+                    None,
+                    self.max_connections,
+                    move |stop_handle| {
+                        let service =
+                            service_builder.clone().build(methods.clone(), stop_handle.clone());
+                        move |connection| {
+                            jsonrpsee::server::serve_with_graceful_shutdown(
+                                connection,
+                                service,
+                                stop_handle.shutdown(),
+                            )
+                        }
+                    },
+                );
+
+                http_local_addr = Some(local_addr);
+                http_handle = Some(handle);
+            } else {
+                let server = builder.build(http_socket_addr).await.map_err(|err| {
+                    RpcError::server_error(err, ServerKind::Http(http_socket_addr))
+                })?;
+                let local_addr = server.local_addr().map_err(|err| {
+                    RpcError::server_error(err, ServerKind::Http(http_socket_addr))
+                })?;
+                http_local_addr = Some(local_addr);
+                http_server = Some(server);
+            }
         }
 
-        http_handle = http_server
-            .map(|http_server| http_server.start(modules.http.clone().expect("http server error")));
-        ws_handle = ws_server
-            .map(|ws_server| ws_server.start(modules.ws.clone().expect("ws server error")));
+        if let Some(http_server) = http_server {
+            http_handle = Some(http_server.start(modules.http.clone().expect("http server error")));
+        }
+        if let Some(ws_server) = ws_server {
+            ws_handle = Some(ws_server.start(modules.ws.clone().expect("ws server error")));
+        }
         Ok(RpcServerHandle {
             http_local_addr,
             ws_local_addr,
@@ -1424,6 +1852,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_endpoint: self.ipc_endpoint.clone(),
             ipc: ipc_handle,
             jwt_secret: self.jwt_secret,
+            tls: self.tls.is_some(),
         })
     }
 }
@@ -1593,6 +2022,8 @@ pub struct TransportRpcModules<Context = ()> {
     ws: Option<RpcModule<Context>>,
     /// rpcs module for ipc
     ipc: Option<RpcModule<Context>>,
+    /// namespaces the methods are instrumented under
+    method_metrics: RpcMethodMetricsRegistry,
 }
 
 // === impl TransportRpcModules ===
@@ -1631,6 +2062,31 @@ impl TransportRpcModules {
         &self.config
     }
 
+    /// Returns the [`RpcMethodMetricsRegistry`] that records the namespace each method's metrics
+    /// are labeled with.
+    pub const fn method_metrics(&self) -> &RpcMethodMetricsRegistry {
+        &self.method_metrics
+    }
+
+    /// Returns a mutable reference to the [`RpcMethodMetricsRegistry`].
+    pub const fn method_metrics_mut(&mut self) -> &mut RpcMethodMetricsRegistry {
+        &mut self.method_metrics
+    }
+
+    /// Merge the given [`Methods`] in all configured transport modules and instrument them under
+    /// the given metrics namespace.
+    ///
+    /// Fails if any of the methods in other is present already.
+    pub fn merge_configured_with_namespace(
+        &mut self,
+        namespace: &'static str,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        self.method_metrics.register(namespace, &other);
+        self.merge_configured(other)
+    }
+
     /// Merge the given [`Methods`] in all configured transport modules if the given
     /// [`RethRpcModule`] is configured for the transport.
     ///
@@ -1661,7 +2117,9 @@ impl TransportRpcModules {
     /// Returns [Ok(false)] if no http transport is configured.
     pub fn merge_http(&mut self, other: impl Into<Methods>) -> Result<bool, RegisterMethodError> {
         if let Some(ref mut http) = self.http {
-            return http.merge(other.into()).map(|_| true)
+            let other = other.into();
+            self.method_metrics.register_default(&other);
+            return http.merge(other).map(|_| true)
         }
         Ok(false)
     }
@@ -1673,7 +2131,9 @@ impl TransportRpcModules {
     /// Returns [Ok(false)] if no ws transport is configured.
     pub fn merge_ws(&mut self, other: impl Into<Methods>) -> Result<bool, RegisterMethodError> {
         if let Some(ref mut ws) = self.ws {
-            return ws.merge(other.into()).map(|_| true)
+            let other = other.into();
+            self.method_metrics.register_default(&other);
+            return ws.merge(other).map(|_| true)
         }
         Ok(false)
     }
@@ -1685,7 +2145,9 @@ impl TransportRpcModules {
     /// Returns [Ok(false)] if no ipc transport is configured.
     pub fn merge_ipc(&mut self, other: impl Into<Methods>) -> Result<bool, RegisterMethodError> {
         if let Some(ref mut ipc) = self.ipc {
-            return ipc.merge(other.into()).map(|_| true)
+            let other = other.into();
+            self.method_metrics.register_default(&other);
+            return ipc.merge(other).map(|_| true)
         }
         Ok(false)
     }
@@ -1989,6 +2451,8 @@ pub struct RpcServerHandle {
     ipc_endpoint: Option<String>,
     ipc: Option<jsonrpsee::server::ServerHandle>,
     jwt_secret: Option<JwtSecret>,
+    /// Whether the http/ws server terminates TLS
+    tls: bool,
 }
 
 // === impl RpcServerHandle ===
@@ -2044,12 +2508,14 @@ impl RpcServerHandle {
 
     /// Returns the url to the http server
     pub fn http_url(&self) -> Option<String> {
-        self.http_local_addr.map(|addr| format!("http://{addr}"))
+        let scheme = if self.tls { "https" } else { "http" };
+        self.http_local_addr.map(|addr| format!("{scheme}://{addr}"))
     }
 
     /// Returns the url to the ws server
     pub fn ws_url(&self) -> Option<String> {
-        self.ws_local_addr.map(|addr| format!("ws://{addr}"))
+        let scheme = if self.tls { "wss" } else { "ws" };
+        self.ws_local_addr.map(|addr| format!("{scheme}://{addr}"))
     }
 
     /// Returns a http client connected to the server.
@@ -2299,6 +2765,24 @@ mod tests {
         module
     }
 
+    #[test]
+    fn test_merge_method_metrics_namespace() {
+        let mut modules =
+            TransportRpcModules { http: Some(create_test_module()), ..Default::default() };
+
+        let mut custom = RpcModule::new(());
+        custom.register_method("custom_hello", |_, _, _| "hello").unwrap();
+        modules.merge_http(custom).unwrap();
+        assert_eq!(modules.method_metrics().namespace("custom_hello"), "custom");
+        assert_eq!(modules.method_metrics().namespace("anything"), "anything");
+
+        let mut other = RpcModule::new(());
+        other.register_method("foo_bar", |_, _, _| "bar").unwrap();
+        modules.merge_configured_with_namespace("ext", other).unwrap();
+        assert_eq!(modules.method_metrics().namespace("foo_bar"), "ext");
+        assert!(modules.http.as_ref().unwrap().method("foo_bar").is_some());
+    }
+
     #[test]
     fn test_remove_http_method() {
         let mut modules =
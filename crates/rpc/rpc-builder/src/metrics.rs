@@ -2,7 +2,7 @@ use jsonrpsee::{
     core::middleware::{Batch, Notification},
     server::middleware::rpc::RpcServiceT,
     types::Request,
-    MethodResponse, RpcModule,
+    MethodResponse, Methods, RpcModule,
 };
 use reth_metrics::{
     metrics::{Counter, Histogram},
@@ -16,7 +16,10 @@ use std::{
     task::{Context, Poll},
     time::Instant,
 };
-use tower::Layer;
+use tower::{Layer, Service};
+
+/// Maximum length of a client label, longer header values are truncated.
+const MAX_CLIENT_LABEL_LEN: usize = 64;
 
 /// Metrics for the RPC server.
 ///
@@ -24,21 +27,33 @@ use tower::Layer;
 /// - Connection metrics: metrics for the connection (e.g. number of connections opened, relevant
 ///   for WS and IPC)
 /// - Request metrics: metrics for each RPC method (e.g. number of calls started, time taken to
-///   process a call)
+///   process a call, request and response sizes)
+///
+/// Call metrics are labeled by the namespace of the method, as recorded in the
+/// [`RpcMethodMetricsRegistry`], and the method name.
+///
+/// If the request was labeled by a [`RpcClientLabelLayer`], the number of calls and the time taken
+/// to process them are additionally recorded per method and client.
 #[derive(Default, Debug, Clone)]
 pub(crate) struct RpcRequestMetrics {
     inner: Arc<RpcServerMetricsInner>,
 }
 
 impl RpcRequestMetrics {
-    pub(crate) fn new(module: &RpcModule<()>, transport: RpcTransport) -> Self {
+    pub(crate) fn new(
+        module: &RpcModule<()>,
+        registry: &RpcMethodMetricsRegistry,
+        transport: RpcTransport,
+    ) -> Self {
         Self {
             inner: Arc::new(RpcServerMetricsInner {
                 connection_metrics: transport.connection_metrics(),
                 call_metrics: module
                     .method_names()
                     .map(|method| {
-                        (method, RpcServerCallMetrics::new_with_labels(&[("method", method)]))
+                        let labels =
+                            [("namespace", registry.namespace(method)), ("method", method)];
+                        (method, RpcServerCallMetrics::new_with_labels(&labels))
                     })
                     .collect(),
             }),
@@ -46,28 +61,67 @@ impl RpcRequestMetrics {
     }
 
     /// Creates a new instance of the metrics layer for HTTP.
-    pub(crate) fn http(module: &RpcModule<()>) -> Self {
-        Self::new(module, RpcTransport::Http)
+    pub(crate) fn http(module: &RpcModule<()>, registry: &RpcMethodMetricsRegistry) -> Self {
+        Self::new(module, registry, RpcTransport::Http)
     }
 
     /// Creates a new instance of the metrics layer for same port.
     ///
     /// Note: currently it's not possible to track transport specific metrics for a server that runs http and ws on the same port: <https://github.com/paritytech/jsonrpsee/issues/1345> until we have this feature we will use the http metrics for this case.
-    pub(crate) fn same_port(module: &RpcModule<()>) -> Self {
-        Self::http(module)
+    pub(crate) fn same_port(module: &RpcModule<()>, registry: &RpcMethodMetricsRegistry) -> Self {
+        Self::http(module, registry)
     }
 
     /// Creates a new instance of the metrics layer for Ws.
-    pub(crate) fn ws(module: &RpcModule<()>) -> Self {
-        Self::new(module, RpcTransport::WebSocket)
+    pub(crate) fn ws(module: &RpcModule<()>, registry: &RpcMethodMetricsRegistry) -> Self {
+        Self::new(module, registry, RpcTransport::WebSocket)
     }
 
     /// Creates a new instance of the metrics layer for Ws.
-    pub(crate) fn ipc(module: &RpcModule<()>) -> Self {
-        Self::new(module, RpcTransport::Ipc)
+    pub(crate) fn ipc(module: &RpcModule<()>, registry: &RpcMethodMetricsRegistry) -> Self {
+        Self::new(module, registry, RpcTransport::Ipc)
+    }
+}
+
+/// Records the namespace every RPC method is instrumented under.
+///
+/// The call metrics of each method are labeled by namespace and method name. Methods that are
+/// merged into the [`TransportRpcModules`](crate::TransportRpcModules) are registered under the
+/// prefix of their name, e.g. `eth` for `eth_call`, so that custom modules are instrumented
+/// without further setup. A different namespace can be assigned with [`Self::register`].
+#[derive(Debug, Clone, Default)]
+pub struct RpcMethodMetricsRegistry {
+    namespaces: HashMap<&'static str, &'static str>,
+}
+
+impl RpcMethodMetricsRegistry {
+    /// Records the given methods under the given namespace, replacing any previously registered
+    /// namespace of the methods.
+    pub fn register(&mut self, namespace: &'static str, methods: &Methods) {
+        for method in methods.method_names() {
+            self.namespaces.insert(method, namespace);
+        }
+    }
+
+    /// Records the given methods under the prefix of their name, unless they are already
+    /// registered.
+    pub fn register_default(&mut self, methods: &Methods) {
+        for method in methods.method_names() {
+            self.namespaces.entry(method).or_insert_with(|| default_namespace(method));
+        }
+    }
+
+    /// Returns the namespace the given method is instrumented under.
+    pub fn namespace(&self, method: &'static str) -> &'static str {
+        self.namespaces.get(method).copied().unwrap_or_else(|| default_namespace(method))
     }
 }
 
+/// Returns the namespace of a method, which is the prefix of the method name up to the first `_`.
+fn default_namespace(method: &'static str) -> &'static str {
+    method.split_once('_').map_or(method, |(namespace, _)| namespace)
+}
+
 impl<S> Layer<S> for RpcRequestMetrics {
     type Service = RpcRequestMetricsService<S>;
 
@@ -117,12 +171,24 @@ where
         let call_metrics = self.metrics.inner.call_metrics.get_key_value(req.method.as_ref());
         if let Some((_, call_metrics)) = &call_metrics {
             call_metrics.started_total.increment(1);
+            call_metrics
+                .request_size_bytes
+                .record(req.params.as_ref().map_or(0, |params| params.get().len()) as f64);
         }
+        let method = call_metrics.map(|(method, _)| *method);
+        let client_metrics =
+            method.zip(req.extensions().get::<RpcClientLabel>()).map(|(method, client)| {
+                RpcServerClientMetrics::new_with_labels(&[
+                    ("method", method.to_string()),
+                    ("client", client.0.to_string()),
+                ])
+            });
         MeteredRequestFuture {
             fut: self.inner.call(req),
             started_at: Instant::now(),
             metrics: self.metrics.clone(),
-            method: call_metrics.map(|(method, _)| *method),
+            method,
+            client_metrics,
         }
     }
 
@@ -156,6 +222,8 @@ pub struct MeteredRequestFuture<F> {
     metrics: RpcRequestMetrics,
     /// the method name if known
     method: Option<&'static str>,
+    /// metrics for the method call of the client, if the request was labeled
+    client_metrics: Option<RpcServerClientMetrics>,
 }
 
 impl<F> std::fmt::Debug for MeteredRequestFuture<F> {
@@ -183,17 +251,88 @@ impl<F: Future<Output = MethodResponse>> Future for MeteredRequestFuture<F> {
                 this.method.and_then(|method| this.metrics.inner.call_metrics.get(method))
             {
                 call_metrics.time_seconds.record(elapsed);
+                call_metrics.response_size_bytes.record(resp.to_json().get().len() as f64);
                 if resp.is_success() {
                     call_metrics.successful_total.increment(1);
                 } else {
                     call_metrics.failed_total.increment(1);
                 }
             }
+
+            if let Some(client_metrics) = this.client_metrics {
+                client_metrics.calls_total.increment(1);
+                client_metrics.time_seconds.record(elapsed);
+            }
         }
         res
     }
 }
 
+/// The client label of a request, extracted from a request header by the [`RpcClientLabelLayer`].
+#[derive(Debug, Clone)]
+struct RpcClientLabel(Arc<str>);
+
+/// HTTP middleware that labels requests with the value of a configured header, e.g. a header
+/// set by a proxy to identify the client.
+///
+/// The label is picked up by the [`RpcRequestMetrics`], which record the calls per method and
+/// client. Every distinct label creates new metric series, so the header should only be set by
+/// trusted infrastructure that uses a small number of distinct values.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcClientLabelLayer {
+    header: Arc<str>,
+}
+
+impl RpcClientLabelLayer {
+    /// Creates the layer that reads the label from the given header.
+    pub(crate) fn new(header: &str) -> Self {
+        Self { header: header.into() }
+    }
+}
+
+impl<S> Layer<S> for RpcClientLabelLayer {
+    type Service = RpcClientLabelService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientLabelService { inner, header: self.header.clone() }
+    }
+}
+
+/// Service of the [`RpcClientLabelLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct RpcClientLabelService<S> {
+    inner: S,
+    header: Arc<str>,
+}
+
+impl<S, B> Service<http::Request<B>> for RpcClientLabelService<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let label = req
+            .headers()
+            .get(&*self.header)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            // header values that are valid strings only contain visible ASCII characters
+            .map(|value| RpcClientLabel(value[..value.len().min(MAX_CLIENT_LABEL_LEN)].into()));
+        if let Some(label) = label {
+            req.extensions_mut().insert(label);
+        }
+        self.inner.call(req)
+    }
+}
+
 /// The transport protocol used for the RPC connection.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub(crate) enum RpcTransport {
@@ -246,4 +385,65 @@ struct RpcServerCallMetrics {
     failed_total: Counter,
     /// Response for a single call
     time_seconds: Histogram,
+    /// The size of the request parameters in bytes
+    request_size_bytes: Histogram,
+    /// The size of the response in bytes
+    response_size_bytes: Histogram,
+}
+
+/// Metrics for the RPC calls of a client
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.clients")]
+struct RpcServerClientMetrics {
+    /// The number of calls
+    calls_total: Counter,
+    /// Response for a single call
+    time_seconds: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+    use tower::ServiceExt;
+
+    /// Returns the label the [`RpcClientLabelLayer`] attaches to a request with the given header.
+    async fn client_label(value: Option<HeaderValue>) -> Option<Arc<str>> {
+        let service = RpcClientLabelLayer::new("x-client").layer(tower::service_fn(
+            |req: http::Request<()>| async move {
+                Ok::<_, std::convert::Infallible>(
+                    req.extensions().get::<RpcClientLabel>().map(|label| label.0.clone()),
+                )
+            },
+        ));
+        let mut req = http::Request::new(());
+        if let Some(value) = value {
+            req.headers_mut().insert("x-client", value);
+        }
+        service.oneshot(req).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn label_requests_by_client_header() {
+        assert_eq!(
+            client_label(Some(HeaderValue::from_static("indexer"))).await.as_deref(),
+            Some("indexer")
+        );
+        assert_eq!(
+            client_label(Some(HeaderValue::from_static("  indexer "))).await.as_deref(),
+            Some("indexer")
+        );
+
+        let long = "a".repeat(2 * MAX_CLIENT_LABEL_LEN);
+        let label = client_label(Some(HeaderValue::from_str(&long).unwrap())).await.unwrap();
+        assert_eq!(label.len(), MAX_CLIENT_LABEL_LEN);
+    }
+
+    #[tokio::test]
+    async fn ignore_missing_and_invalid_client_headers() {
+        assert_eq!(client_label(None).await, None);
+        assert_eq!(client_label(Some(HeaderValue::from_static(" "))).await, None);
+        // not visible ASCII
+        assert_eq!(client_label(Some(HeaderValue::from_bytes(&[0xff]).unwrap())).await, None);
+    }
 }
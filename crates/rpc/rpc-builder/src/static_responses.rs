@@ -0,0 +1,235 @@
+//! [`jsonrpsee`] helper layer that serves constant responses from pre-serialized results.
+
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{Request, ResponsePayload},
+    MethodResponse,
+};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, OnceLock},
+};
+use tower::Layer;
+
+/// Serves calls of methods that always return the same result from a pre-serialized result.
+///
+/// The first successful response of each configured method is stored, subsequent calls are
+/// answered with the stored result without dispatching the call to the method handler. This
+/// reduces the latency of calls that are issued at a high rate by health checks of load balancers.
+///
+/// Only calls without parameters are served, calls within batches are always dispatched. Methods
+/// must only be configured if their result never changes while the node is running.
+///
+/// The layer is applied outside of the custom RPC middleware of the server, which means that served
+/// calls never reach that middleware, e.g. for authentication or logging. Only methods that don't
+/// need to be observed by such middleware should be configured.
+#[derive(Debug, Clone, Default)]
+pub struct StaticResponsesLayer {
+    /// The pre-serialized results by method name.
+    results: Arc<HashMap<String, OnceLock<Box<RawValue>>>>,
+}
+
+impl StaticResponsesLayer {
+    /// Creates a new layer that serves the given methods.
+    pub fn new(methods: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let results = methods.into_iter().map(|method| (method.into(), OnceLock::new())).collect();
+        Self { results: Arc::new(results) }
+    }
+
+    /// Returns `true` if no methods are configured.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+impl<S> Layer<S> for StaticResponsesLayer {
+    type Service = StaticResponsesService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        StaticResponsesService { inner, results: Arc::clone(&self.results) }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that answers calls of constant methods from pre-serialized
+/// results, see [`StaticResponsesLayer`].
+#[derive(Debug, Clone)]
+pub struct StaticResponsesService<S> {
+    /// The inner service being wrapped
+    inner: S,
+    /// The pre-serialized results by method name.
+    results: Arc<HashMap<String, OnceLock<Box<RawValue>>>>,
+}
+
+impl<S> RpcServiceT for StaticResponsesService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let results = Arc::clone(&self.results);
+        let inner = self.inner.clone();
+        async move {
+            let has_params = req.params().as_str().is_some_and(|params| !is_empty_params(params));
+            let Some(result) = results.get(req.method_name()).filter(|_| !has_params) else {
+                return inner.call(req).await
+            };
+
+            if let Some(result) = result.get() {
+                return MethodResponse::response(
+                    req.id().into_owned(),
+                    ResponsePayload::success(&**result).into(),
+                    usize::MAX,
+                )
+            }
+
+            let response = inner.call(req).await;
+            if response.is_success() {
+                if let Some(raw) = extract_result(response.to_json().get()) {
+                    let _ = result.set(raw);
+                }
+            }
+            response
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(requests)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Returns `true` if the raw parameters don't contain any parameter.
+fn is_empty_params(params: &str) -> bool {
+    let params = params.trim();
+    if params.is_empty() || params == "null" {
+        return true
+    }
+    params
+        .strip_prefix('[')
+        .and_then(|params| params.strip_suffix(']'))
+        .or_else(|| params.strip_prefix('{').and_then(|params| params.strip_suffix('}')))
+        .is_some_and(|params| params.trim().is_empty())
+}
+
+/// Extracts the serialized result of a successful response.
+fn extract_result(response: &str) -> Option<Box<RawValue>> {
+    #[derive(Deserialize)]
+    struct Success<'a> {
+        #[serde(borrow)]
+        result: &'a RawValue,
+    }
+
+    serde_json::from_str::<Success<'_>>(response).ok().map(|success| success.result.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts the dispatched calls and answers every call with the number of previous calls.
+    #[derive(Clone, Default)]
+    struct CountingService {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl RpcServiceT for CountingService {
+        type MethodResponse = MethodResponse;
+        type NotificationResponse = MethodResponse;
+        type BatchResponse = MethodResponse;
+
+        fn call<'a>(
+            &self,
+            req: Request<'a>,
+        ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+            let count = self.calls.fetch_add(1, Ordering::Relaxed);
+            async move {
+                MethodResponse::response(
+                    req.id().into_owned(),
+                    ResponsePayload::success(count).into(),
+                    usize::MAX,
+                )
+            }
+        }
+
+        fn batch<'a>(
+            &self,
+            _requests: Batch<'a>,
+        ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+            async { unimplemented!() }
+        }
+
+        fn notification<'a>(
+            &self,
+            _n: Notification<'a>,
+        ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+            async { unimplemented!() }
+        }
+    }
+
+    fn request(json: &str) -> Request<'_> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn serves_stored_responses() {
+        let inner = CountingService::default();
+        let service = StaticResponsesLayer::new(["eth_chainId"]).layer(inner.clone());
+
+        for id in 1..=3 {
+            let json = format!(r#"{{"jsonrpc":"2.0","id":{id},"method":"eth_chainId"}}"#);
+            let response = service.call(request(&json)).await;
+            let response: serde_json::Value =
+                serde_json::from_str(response.to_json().get()).unwrap();
+            assert_eq!(response, serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": 0 }));
+        }
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 1);
+
+        // calls with parameters and other methods are always dispatched
+        let json = r#"{"jsonrpc":"2.0","id":4,"method":"eth_chainId","params":["x"]}"#;
+        service.call(request(json)).await;
+        let json = r#"{"jsonrpc":"2.0","id":5,"method":"net_version","params":[]}"#;
+        service.call(request(json)).await;
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn extracts_result() {
+        let result = extract_result(r#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#).unwrap();
+        assert_eq!(result.get(), r#""0x1""#);
+
+        let result = extract_result(r#"{"jsonrpc":"2.0","result":{"a":[1, 2]},"id":"x"}"#);
+        assert_eq!(result.unwrap().get(), r#"{"a":[1, 2]}"#);
+
+        assert!(extract_result(r#"{"jsonrpc":"2.0","id":1,"error":{"code":1}}"#).is_none());
+    }
+
+    #[test]
+    fn empty_params() {
+        assert!(is_empty_params("[]"));
+        assert!(is_empty_params(" [ ] "));
+        assert!(is_empty_params("null"));
+        assert!(!is_empty_params(r#"["latest"]"#));
+        assert!(!is_empty_params(r#"{"a":1}"#));
+    }
+}
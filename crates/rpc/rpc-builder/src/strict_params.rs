@@ -0,0 +1,619 @@
+//! [`jsonrpsee`] helper layer for strict validation of `eth_` call parameters.
+
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{error::INVALID_PARAMS_CODE, ErrorObject, Request},
+    MethodResponse,
+};
+use serde_json::{json, Map, Value};
+use std::{fmt, future::Future};
+use tower::Layer;
+
+/// Block tags that are accepted in place of a block number.
+const BLOCK_TAGS: [&str; 5] = ["latest", "earliest", "pending", "safe", "finalized"];
+
+/// Validates the parameters of `eth_` calls against the execution API specification before they
+/// are deserialized.
+///
+/// By default, parameters are deserialized leniently, e.g. quantities with leading zeros or data
+/// with an odd number of hex digits are accepted. In strict mode, such parameters are rejected
+/// with an error that points to the offending field, e.g. `params[0].gas`.
+///
+/// Invalid calls of a batch are rejected individually, the remaining calls are executed.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct StrictParamsLayer;
+
+impl StrictParamsLayer {
+    /// Creates a new layer.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for StrictParamsLayer {
+    type Service = StrictParamsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        StrictParamsService { inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that rejects `eth_` calls with malformed parameters, see
+/// [`StrictParamsLayer`].
+#[derive(Debug, Clone)]
+pub struct StrictParamsService<S> {
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcServiceT for StrictParamsService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let invalid = validate_request(&req, "params").err();
+        let id = req.id().into_owned();
+        let inner = self.inner.clone();
+        async move {
+            if let Some(invalid) = invalid {
+                return MethodResponse::error(id, invalid.into_error_object())
+            }
+            inner.call(req).await
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // replace invalid calls with an error response for their id
+        for entry in requests.iter_mut() {
+            let invalid = match entry {
+                Ok(BatchEntry::Call(req)) => validate_request(req, "params")
+                    .err()
+                    .map(|invalid| (req.id().into_owned(), invalid)),
+                _ => None,
+            };
+            if let Some((id, invalid)) = invalid {
+                *entry = Err(BatchEntryErr::new(id, invalid.into_error_object()));
+            }
+        }
+        self.inner.batch(requests)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// A parameter that doesn't conform to the specification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InvalidParam {
+    /// The method that was called.
+    method: String,
+    /// The path of the offending field, e.g. `params[0].gas`.
+    path: String,
+    /// Why the field is invalid.
+    reason: String,
+}
+
+impl InvalidParam {
+    fn new(path: &str, reason: impl Into<String>) -> Self {
+        Self { method: String::new(), path: path.to_string(), reason: reason.into() }
+    }
+
+    fn into_error_object(self) -> ErrorObject<'static> {
+        let data = json!({ "method": self.method, "path": self.path, "reason": self.reason });
+        ErrorObject::owned(INVALID_PARAMS_CODE, self.to_string(), Some(data))
+    }
+}
+
+impl fmt::Display for InvalidParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid params for {}: {}: {}", self.method, self.path, self.reason)
+    }
+}
+
+/// Validates the parameters of the request, if the method is known.
+fn validate_request(req: &Request<'_>, path: &str) -> Result<(), InvalidParam> {
+    let method = req.method_name();
+    let Some((params, required)) = method_params(method) else { return Ok(()) };
+    let value = match req.params().as_str() {
+        Some(params) => serde_json::from_str(params)
+            .map_err(|err| InvalidParam::new(path, format!("invalid JSON: {err}")))?,
+        None => Value::Array(Vec::new()),
+    };
+    validate_params(params, required, &value, path).map_err(|mut invalid| {
+        invalid.method = method.to_string();
+        invalid
+    })
+}
+
+/// Validates positional parameters against the expected parameters, of which the first
+/// `required` ones must be present.
+///
+/// Named parameters are not validated.
+fn validate_params(
+    params: &[Param],
+    required: usize,
+    value: &Value,
+    path: &str,
+) -> Result<(), InvalidParam> {
+    let values = match value {
+        Value::Array(values) => values.as_slice(),
+        Value::Null => &[],
+        _ => return Ok(()),
+    };
+
+    if values.len() < required {
+        return Err(InvalidParam::new(
+            &format!("{path}[{}]", values.len()),
+            format!("missing required parameter, expected at least {required} parameters"),
+        ))
+    }
+    if values.len() > params.len() {
+        return Err(InvalidParam::new(
+            &format!("{path}[{}]", params.len()),
+            format!("unexpected parameter, expected at most {} parameters", params.len()),
+        ))
+    }
+
+    for (index, (param, value)) in params.iter().zip(values).enumerate() {
+        // trailing optional parameters may be null
+        if index >= required && value.is_null() {
+            continue
+        }
+        param.validate(value, &format!("{path}[{index}]"))?;
+    }
+    Ok(())
+}
+
+/// Returns the expected parameters of a method, and how many of them are required.
+fn method_params(method: &str) -> Option<(&'static [Param], usize)> {
+    use Param::*;
+
+    let params: (&'static [Param], usize) = match method {
+        "eth_getBalance" | "eth_getTransactionCount" | "eth_getCode" => (&[Address, BlockId], 1),
+        "eth_getStorageAt" => (&[Address, StorageSlot, BlockId], 2),
+        "eth_getProof" => (&[Address, StorageSlots, BlockId], 2),
+        "eth_getBlockByHash" => (&[Hash, Bool], 2),
+        "eth_getBlockByNumber" => (&[BlockNumberOrTag, Bool], 2),
+        "eth_getBlockReceipts" => (&[BlockId], 1),
+        "eth_getBlockTransactionCountByHash" |
+        "eth_getUncleCountByBlockHash" |
+        "eth_getTransactionByHash" |
+        "eth_getRawTransactionByHash" |
+        "eth_getTransactionReceipt" => (&[Hash], 1),
+        "eth_getBlockTransactionCountByNumber" | "eth_getUncleCountByBlockNumber" => {
+            (&[BlockNumberOrTag], 1)
+        }
+        "eth_getTransactionByBlockHashAndIndex" |
+        "eth_getRawTransactionByBlockHashAndIndex" |
+        "eth_getUncleByBlockHashAndIndex" => (&[Hash, Quantity(64)], 2),
+        "eth_getTransactionByBlockNumberAndIndex" |
+        "eth_getRawTransactionByBlockNumberAndIndex" |
+        "eth_getUncleByBlockNumberAndIndex" => (&[BlockNumberOrTag, Quantity(64)], 2),
+        "eth_call" => (&[TransactionRequest, BlockId, Any, Any], 1),
+        "eth_estimateGas" => (&[TransactionRequest, BlockId, Any], 1),
+        "eth_createAccessList" => (&[TransactionRequest, BlockId, Any], 1),
+        "eth_getLogs" | "eth_newFilter" => (&[Filter], 1),
+        "eth_feeHistory" => (&[Quantity(64), BlockNumberOrTag, Any], 2),
+        "eth_sendRawTransaction" => (&[Data], 1),
+        _ => return None,
+    };
+    Some(params)
+}
+
+/// The kind of a parameter, or of a field of a parameter.
+#[derive(Debug, Clone, Copy)]
+enum Param {
+    /// Any value.
+    Any,
+    /// A boolean.
+    Bool,
+    /// A hex encoded unsigned integer without leading zeros, of at most the given number of bits.
+    Quantity(usize),
+    /// Hex encoded bytes.
+    Data,
+    /// A 20 byte address.
+    Address,
+    /// A 32 byte hash.
+    Hash,
+    /// A storage slot of at most 32 bytes.
+    StorageSlot,
+    /// A list of storage slots.
+    StorageSlots,
+    /// A block number or block tag.
+    BlockNumberOrTag,
+    /// A block number, block tag or EIP-1898 block identifier.
+    BlockId,
+    /// A transaction request object.
+    TransactionRequest,
+    /// A log filter object.
+    Filter,
+}
+
+impl Param {
+    /// Validates the value, where `path` is the path of the value within the request.
+    fn validate(self, value: &Value, path: &str) -> Result<(), InvalidParam> {
+        match self {
+            Self::Any => Ok(()),
+            Self::Bool => {
+                if value.is_boolean() {
+                    Ok(())
+                } else {
+                    Err(InvalidParam::new(path, "expected a boolean"))
+                }
+            }
+            Self::Quantity(bits) => validate_quantity(value, path, bits),
+            Self::Data => validate_data(value, path, None),
+            Self::Address => validate_data(value, path, Some(20)),
+            Self::Hash => validate_data(value, path, Some(32)),
+            Self::StorageSlot => {
+                let digits = hex_digits(value, path)?;
+                if digits.len() > 64 {
+                    return Err(InvalidParam::new(path, "storage slot exceeds 32 bytes"))
+                }
+                Ok(())
+            }
+            Self::StorageSlots => validate_array(value, path, Self::StorageSlot),
+            Self::BlockNumberOrTag => validate_block_number_or_tag(value, path),
+            Self::BlockId => match value {
+                Value::Object(object) => {
+                    let has_hash = object.contains_key("blockHash");
+                    if has_hash == object.contains_key("blockNumber") {
+                        return Err(InvalidParam::new(
+                            path,
+                            "expected exactly one of `blockHash` or `blockNumber`",
+                        ))
+                    }
+                    validate_fields(
+                        object,
+                        path,
+                        &[
+                            ("blockHash", Self::Hash),
+                            ("blockNumber", Self::BlockNumberOrTag),
+                            ("requireCanonical", Self::Bool),
+                        ],
+                    )
+                }
+                _ => validate_block_number_or_tag(value, path),
+            },
+            Self::TransactionRequest => {
+                let object = value
+                    .as_object()
+                    .ok_or_else(|| InvalidParam::new(path, "expected a transaction object"))?;
+                validate_fields(
+                    object,
+                    path,
+                    &[
+                        ("from", Self::Address),
+                        ("to", Self::Address),
+                        ("gas", Self::Quantity(64)),
+                        ("gasPrice", Self::Quantity(128)),
+                        ("maxFeePerGas", Self::Quantity(128)),
+                        ("maxPriorityFeePerGas", Self::Quantity(128)),
+                        ("maxFeePerBlobGas", Self::Quantity(128)),
+                        ("value", Self::Quantity(256)),
+                        ("nonce", Self::Quantity(64)),
+                        ("chainId", Self::Quantity(64)),
+                        ("type", Self::Quantity(8)),
+                        ("data", Self::Data),
+                        ("input", Self::Data),
+                    ],
+                )?;
+                if let Some(access_list) = object.get("accessList") {
+                    let items = access_list.as_array().ok_or_else(|| {
+                        InvalidParam::new(&format!("{path}.accessList"), "expected an array")
+                    })?;
+                    for (index, item) in items.iter().enumerate() {
+                        let path = format!("{path}.accessList[{index}]");
+                        let item = item
+                            .as_object()
+                            .ok_or_else(|| InvalidParam::new(&path, "expected an object"))?;
+                        validate_fields(
+                            item,
+                            &path,
+                            &[("address", Self::Address), ("storageKeys", Self::StorageSlots)],
+                        )?;
+                    }
+                }
+                if let Some(hashes) = object.get("blobVersionedHashes") {
+                    validate_array(hashes, &format!("{path}.blobVersionedHashes"), Self::Hash)?;
+                }
+                Ok(())
+            }
+            Self::Filter => {
+                let object = value
+                    .as_object()
+                    .ok_or_else(|| InvalidParam::new(path, "expected a filter object"))?;
+                validate_fields(
+                    object,
+                    path,
+                    &[
+                        ("fromBlock", Self::BlockNumberOrTag),
+                        ("toBlock", Self::BlockNumberOrTag),
+                        ("blockHash", Self::Hash),
+                    ],
+                )?;
+                if let Some(address) = object.get("address").filter(|address| !address.is_null()) {
+                    let path = format!("{path}.address");
+                    if address.is_array() {
+                        validate_array(address, &path, Self::Address)?;
+                    } else {
+                        Self::Address.validate(address, &path)?;
+                    }
+                }
+                if let Some(topics) = object.get("topics").filter(|topics| !topics.is_null()) {
+                    let path = format!("{path}.topics");
+                    let topics = topics
+                        .as_array()
+                        .ok_or_else(|| InvalidParam::new(&path, "expected an array"))?;
+                    if topics.len() > 4 {
+                        return Err(InvalidParam::new(&path, "expected at most 4 topics"))
+                    }
+                    for (index, topic) in topics.iter().enumerate() {
+                        let path = format!("{path}[{index}]");
+                        match topic {
+                            Value::Null => {}
+                            Value::Array(alternatives) => {
+                                for (index, topic) in alternatives.iter().enumerate() {
+                                    if !topic.is_null() {
+                                        Self::Hash.validate(topic, &format!("{path}[{index}]"))?;
+                                    }
+                                }
+                            }
+                            topic => Self::Hash.validate(topic, &path)?,
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Validates the fields of an object that are present and not null.
+fn validate_fields(
+    object: &Map<String, Value>,
+    path: &str,
+    fields: &[(&str, Param)],
+) -> Result<(), InvalidParam> {
+    for (field, param) in fields {
+        if let Some(value) = object.get(*field).filter(|value| !value.is_null()) {
+            param.validate(value, &format!("{path}.{field}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Validates every element of an array.
+fn validate_array(value: &Value, path: &str, param: Param) -> Result<(), InvalidParam> {
+    let values = value.as_array().ok_or_else(|| InvalidParam::new(path, "expected an array"))?;
+    for (index, value) in values.iter().enumerate() {
+        param.validate(value, &format!("{path}[{index}]"))?;
+    }
+    Ok(())
+}
+
+/// Returns the hex digits of a `0x` prefixed hex string.
+fn hex_digits<'a>(value: &'a Value, path: &str) -> Result<&'a str, InvalidParam> {
+    let value = value.as_str().ok_or_else(|| InvalidParam::new(path, "expected a hex string"))?;
+    let digits = value.strip_prefix("0x").ok_or_else(|| {
+        InvalidParam::new(path, format!("hex string {value:?} is missing the 0x prefix"))
+    })?;
+    if let Some(invalid) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(InvalidParam::new(path, format!("invalid hex digit {invalid:?}")))
+    }
+    Ok(digits)
+}
+
+fn validate_quantity(value: &Value, path: &str, bits: usize) -> Result<(), InvalidParam> {
+    let digits = hex_digits(value, path)?;
+    if digits.is_empty() {
+        return Err(InvalidParam::new(path, "quantity must have at least one digit, e.g. 0x0"))
+    }
+    if digits.len() > 1 && digits.starts_with('0') {
+        return Err(InvalidParam::new(path, "quantity must not have leading zeros"))
+    }
+    let first = u32::from_str_radix(&digits[..1], 16).expect("valid hex digit");
+    let significant_bits = (digits.len() - 1) * 4 + (u32::BITS - first.leading_zeros()) as usize;
+    if significant_bits > bits {
+        return Err(InvalidParam::new(path, format!("quantity exceeds {bits} bits")))
+    }
+    Ok(())
+}
+
+fn validate_data(value: &Value, path: &str, len: Option<usize>) -> Result<(), InvalidParam> {
+    let digits = hex_digits(value, path)?;
+    if digits.len() % 2 != 0 {
+        return Err(InvalidParam::new(path, "data must have an even number of hex digits"))
+    }
+    if let Some(len) = len {
+        if digits.len() != 2 * len {
+            return Err(InvalidParam::new(
+                path,
+                format!("expected {len} bytes, got {}", digits.len() / 2),
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn validate_block_number_or_tag(value: &Value, path: &str) -> Result<(), InvalidParam> {
+    match value.as_str() {
+        Some(tag) if !tag.starts_with("0x") => {
+            if BLOCK_TAGS.contains(&tag) {
+                Ok(())
+            } else {
+                Err(InvalidParam::new(
+                    path,
+                    format!(
+                        "invalid block tag {tag:?}, expected a block number or one of {}",
+                        BLOCK_TAGS.join(", ")
+                    ),
+                ))
+            }
+        }
+        _ => validate_quantity(value, path, 64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(method: &str, params: Value) -> Result<(), InvalidParam> {
+        let (expected, required) = method_params(method).unwrap();
+        validate_params(expected, required, &params, "params")
+    }
+
+    #[test]
+    fn valid_params() {
+        let address = format!("0x{}", "ab".repeat(20));
+        let hash = format!("0x{}", "cd".repeat(32));
+        assert!(validate("eth_getBalance", json!([address])).is_ok());
+        assert!(validate("eth_getBalance", json!([address, "0x1b4"])).is_ok());
+        assert!(validate("eth_getBalance", json!([address, { "blockHash": hash }])).is_ok());
+        assert!(validate("eth_getBlockByNumber", json!(["finalized", false])).is_ok());
+        assert!(validate("eth_getStorageAt", json!([address, "0x0", "latest"])).is_ok());
+        assert!(validate(
+            "eth_call",
+            json!([{ "to": address, "gas": "0x5208", "input": "0x" }, "latest", {}])
+        )
+        .is_ok());
+        assert!(validate(
+            "eth_getLogs",
+            json!([{ "fromBlock": "0x0", "address": [address], "topics": [null, [hash, null]] }])
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn invalid_params() {
+        let address = format!("0x{}", "ab".repeat(20));
+        let error = |method, params| {
+            let invalid = validate(method, params).unwrap_err();
+            (invalid.path, invalid.reason)
+        };
+
+        assert_eq!(
+            error("eth_getBalance", json!([address, "0x01"])),
+            ("params[1]".into(), "quantity must not have leading zeros".into())
+        );
+        assert_eq!(
+            error("eth_getBlockByNumber", json!(["Latest", false])).0,
+            "params[0]".to_string()
+        );
+        assert_eq!(
+            error("eth_getBlockByNumber", json!(["0x10000000000000000", false])),
+            ("params[0]".into(), "quantity exceeds 64 bits".into())
+        );
+        assert_eq!(
+            error("eth_call", json!([{ "to": address, "input": "0xabc" }])),
+            ("params[0].input".into(), "data must have an even number of hex digits".into())
+        );
+        assert_eq!(
+            error("eth_getBalance", json!(["0xabcd"])),
+            ("params[0]".into(), "expected 20 bytes, got 2".into())
+        );
+        assert_eq!(
+            error("eth_getLogs", json!([{ "topics": [["0x12"]] }])).0,
+            "params[0].topics[0][0]".to_string()
+        );
+        assert_eq!(error("eth_getBlockByHash", json!([])).0, "params[0]".to_string());
+        assert_eq!(
+            error("eth_getBalance", json!([address, "latest", true])).0,
+            "params[2]".to_string()
+        );
+    }
+
+    /// Returns a valid value of the parameter.
+    fn sample(param: Param) -> Value {
+        match param {
+            Param::Any => json!({}),
+            Param::Bool => json!(true),
+            Param::Quantity(_) | Param::StorageSlot => json!("0x1"),
+            Param::Data => json!("0x"),
+            Param::Address => json!(format!("0x{}", "ab".repeat(20))),
+            Param::Hash => json!(format!("0x{}", "cd".repeat(32))),
+            Param::StorageSlots => json!(["0x1"]),
+            Param::BlockNumberOrTag | Param::BlockId => json!("latest"),
+            Param::TransactionRequest | Param::Filter => json!({}),
+        }
+    }
+
+    #[test]
+    fn method_arities() {
+        // the required and total number of parameters of the methods of `EthApi`
+        let arities = [
+            ("eth_getBalance", 1, 2),
+            ("eth_getTransactionCount", 1, 2),
+            ("eth_getCode", 1, 2),
+            ("eth_getStorageAt", 2, 3),
+            ("eth_getProof", 2, 3),
+            ("eth_getBlockByHash", 2, 2),
+            ("eth_getBlockByNumber", 2, 2),
+            ("eth_getBlockReceipts", 1, 1),
+            ("eth_getBlockTransactionCountByHash", 1, 1),
+            ("eth_getUncleCountByBlockHash", 1, 1),
+            ("eth_getTransactionByHash", 1, 1),
+            ("eth_getRawTransactionByHash", 1, 1),
+            ("eth_getTransactionReceipt", 1, 1),
+            ("eth_getBlockTransactionCountByNumber", 1, 1),
+            ("eth_getUncleCountByBlockNumber", 1, 1),
+            ("eth_getTransactionByBlockHashAndIndex", 2, 2),
+            ("eth_getRawTransactionByBlockHashAndIndex", 2, 2),
+            ("eth_getUncleByBlockHashAndIndex", 2, 2),
+            ("eth_getTransactionByBlockNumberAndIndex", 2, 2),
+            ("eth_getRawTransactionByBlockNumberAndIndex", 2, 2),
+            ("eth_getUncleByBlockNumberAndIndex", 2, 2),
+            ("eth_call", 1, 4),
+            ("eth_estimateGas", 1, 3),
+            ("eth_createAccessList", 1, 3),
+            ("eth_getLogs", 1, 1),
+            ("eth_newFilter", 1, 1),
+            ("eth_feeHistory", 2, 3),
+            ("eth_sendRawTransaction", 1, 1),
+        ];
+
+        for (method, required, total) in arities {
+            let (params, expected_required) = method_params(method).unwrap();
+            assert_eq!((expected_required, params.len()), (required, total), "{method}");
+
+            let values = params.iter().map(|param| sample(*param)).collect::<Vec<_>>();
+            for len in required..=total {
+                assert!(validate(method, json!(values[..len])).is_ok(), "{method} with {len}");
+            }
+            if required > 0 {
+                assert!(validate(method, json!(values[..required - 1])).is_err(), "{method}");
+            }
+            let mut values = values;
+            values.push(json!(null));
+            values.push(json!(1));
+            assert!(validate(method, json!(values)).is_err(), "{method}");
+        }
+    }
+
+    #[test]
+    fn quantity_bits() {
+        assert!(validate_quantity(&json!("0xff"), "", 8).is_ok());
+        assert!(validate_quantity(&json!("0x1ff"), "", 8).is_err());
+        assert!(validate_quantity(&json!("0x1ff"), "", 9).is_ok());
+        assert!(validate_quantity(&json!("0x0"), "", 8).is_ok());
+        assert!(validate_quantity(&json!("0x"), "", 8).is_err());
+    }
+}
@@ -0,0 +1,345 @@
+//! TLS termination for the HTTP, WS and auth servers.
+//!
+//! The servers serve TLS connections with a certificate chain and private key that are read from
+//! PEM files. The files are checked for changes periodically, so renewed certificates are picked
+//! up without restarting the node.
+
+use crate::ws::{self, WsConnectionMetrics};
+use jsonrpsee::server::{stop_channel, ServerHandle, StopHandle};
+use parking_lot::RwLock;
+use rustls::{
+    crypto::ring,
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    server::{ClientHello, ResolvesServerCert},
+    sign::CertifiedKey,
+    ServerConfig,
+};
+use std::{
+    fmt::Display,
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Weak},
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::Semaphore,
+};
+use tokio_rustls::{server::TlsStream, TlsAcceptor};
+use tokio_util::either::Either;
+use tracing::{debug, info, warn};
+
+/// The default interval at which the certificate and key files are checked for changes.
+pub const DEFAULT_TLS_RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The default maximum number of connections that are served at the same time by servers that
+/// accept their connections themselves, e.g. to terminate TLS.
+pub const DEFAULT_MAX_CONNECTIONS: u32 = 500;
+
+/// The time a client has to complete the TLS handshake.
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The time to wait before accepting connections again after the listener failed, e.g. because
+/// the process ran out of file descriptors.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Certificate and private key of a server that terminates TLS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// Path to the PEM encoded certificate chain, starting with the certificate of the server.
+    pub cert_path: PathBuf,
+    /// Path to the PEM encoded private key of the certificate.
+    pub key_path: PathBuf,
+    /// The interval at which the files are checked for changes.
+    pub reload_interval: Duration,
+}
+
+impl TlsConfig {
+    /// Creates a new config with the given certificate chain and private key files.
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            reload_interval: DEFAULT_TLS_RELOAD_INTERVAL,
+        }
+    }
+
+    /// Sets the interval at which the files are checked for changes.
+    pub const fn with_reload_interval(mut self, reload_interval: Duration) -> Self {
+        self.reload_interval = reload_interval;
+        self
+    }
+
+    /// Loads the certificate and returns an acceptor for TLS connections.
+    ///
+    /// This spawns a task that reloads the certificate when the files change, until the acceptor
+    /// is dropped.
+    pub(crate) fn acceptor(&self) -> Result<TlsAcceptor, TlsError> {
+        let resolver = Arc::new(CertResolver::new(self.clone())?);
+        tokio::spawn(reload_on_change(Arc::downgrade(&resolver), self.reload_interval));
+
+        let mut config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_cert_resolver(resolver);
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
+
+/// Errors of loading the TLS certificate and private key.
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    /// A file could not be read or is not valid PEM.
+    #[error("failed to read {}: {error}", path.display())]
+    Pem {
+        /// Path of the file.
+        path: PathBuf,
+        /// The error.
+        error: rustls::pki_types::pem::Error,
+    },
+    /// The certificate file does not contain a certificate.
+    #[error("no certificate found in {}", _0.display())]
+    NoCertificate(PathBuf),
+    /// The private key is not supported, or does not match the certificate.
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+}
+
+/// Resolves the certificate of all connections to the most recently loaded certificate.
+#[derive(Debug)]
+struct CertResolver {
+    config: TlsConfig,
+    state: RwLock<CertState>,
+}
+
+/// The loaded certificate, and the modification times of the files it was loaded from.
+#[derive(Debug)]
+struct CertState {
+    key: Arc<CertifiedKey>,
+    modified: FilesModified,
+}
+
+/// Modification times of the certificate and key files.
+type FilesModified = (Option<SystemTime>, Option<SystemTime>);
+
+impl CertResolver {
+    fn new(config: TlsConfig) -> Result<Self, TlsError> {
+        let modified = files_modified(&config);
+        let key = Arc::new(load_certified_key(&config)?);
+        Ok(Self { config, state: RwLock::new(CertState { key, modified }) })
+    }
+
+    /// Reloads the certificate if the files changed since the last attempt.
+    ///
+    /// If the new certificate can't be loaded, e.g. because only one of the files was written
+    /// yet, the previous certificate remains in use.
+    fn reload_if_changed(&self) {
+        let modified = files_modified(&self.config);
+        if self.state.read().modified == modified {
+            return
+        }
+
+        match load_certified_key(&self.config) {
+            Ok(key) => {
+                info!(target: "rpc::tls", cert = %self.config.cert_path.display(), "Reloaded TLS certificate");
+                *self.state.write() = CertState { key: Arc::new(key), modified };
+            }
+            Err(err) => {
+                warn!(target: "rpc::tls", %err, "Failed to reload TLS certificate, keeping the previous one");
+                self.state.write().modified = modified;
+            }
+        }
+    }
+}
+
+impl ResolvesServerCert for CertResolver {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.state.read().key.clone())
+    }
+}
+
+/// Reloads the certificate of the resolver when its files change, until the resolver is dropped.
+async fn reload_on_change(resolver: Weak<CertResolver>, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // the first tick completes immediately
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let Some(resolver) = resolver.upgrade() else { break };
+        resolver.reload_if_changed();
+    }
+}
+
+fn files_modified(config: &TlsConfig) -> FilesModified {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    (modified(&config.cert_path), modified(&config.key_path))
+}
+
+fn load_certified_key(config: &TlsConfig) -> Result<CertifiedKey, TlsError> {
+    let pem_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |error| TlsError::Pem { path, error }
+    };
+
+    let certs = CertificateDer::pem_file_iter(&config.cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(pem_error(&config.cert_path))?;
+    if certs.is_empty() {
+        return Err(TlsError::NoCertificate(config.cert_path.clone()))
+    }
+    let key =
+        PrivateKeyDer::from_pem_file(&config.key_path).map_err(pem_error(&config.key_path))?;
+
+    let key = CertifiedKey::new(certs, ring::sign::any_supported_type(&key)?);
+    key.keys_match()?;
+    Ok(key)
+}
+
+/// A connection accepted by [`serve_connections`], with TLS if the server terminates TLS.
+pub(crate) type Connection = Either<TcpStream, TlsStream<TcpStream>>;
+
+/// Accepts the connections of the listener until the returned handle is stopped.
+///
+/// For each connection, `make_service` is called with the [`StopHandle`] of the connection and
+/// returns the function that serves it. If an acceptor is given, the TLS handshake is completed
+/// before the connection is served. If a maximum lifetime is given, connections are closed once
+/// they exceed it.
+///
+/// At most `max_connections` connections, including their TLS handshakes, are served at the same
+/// time. Further connections are only accepted once one of them is closed, and wait in the backlog
+/// of the listener until then.
+///
+/// If accepting fails for a reason other than the connection itself, accepting is paused for
+/// [`ACCEPT_ERROR_BACKOFF`] instead of retrying in a busy loop.
+pub(crate) fn serve_connections<F, S, Fut, E>(
+    listener: TcpListener,
+    tls: Option<TlsAcceptor>,
+    max_lifetime: Option<Duration>,
+    max_connections: u32,
+    make_service: F,
+) -> ServerHandle
+where
+    F: Fn(StopHandle) -> S + Send + 'static,
+    S: FnOnce(Connection) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: Display,
+{
+    let (stop_handle, server_handle) = stop_channel();
+    let metrics = WsConnectionMetrics::default();
+    let connections = Arc::new(Semaphore::new(max_connections as usize));
+    tokio::spawn(async move {
+        loop {
+            if connections.available_permits() == 0 {
+                debug!(target: "rpc", max_connections, "Reached the maximum number of connections");
+            }
+            let permit = tokio::select! {
+                permit = connections.clone().acquire_owned() => {
+                    permit.expect("semaphore is never closed")
+                }
+                _ = stop_handle.clone().shutdown() => break,
+            };
+            let accepted = tokio::select! {
+                accepted = listener.accept() => accepted,
+                _ = stop_handle.clone().shutdown() => break,
+            };
+            let stream = match accepted {
+                Ok((stream, _)) => stream,
+                Err(err) if is_connection_error(&err) => {
+                    debug!(target: "rpc", %err, "Failed to accept connection");
+                    continue
+                }
+                Err(err) => {
+                    warn!(target: "rpc", %err, backoff = ?ACCEPT_ERROR_BACKOFF, "Failed to accept connections, backing off");
+                    tokio::select! {
+                        _ = tokio::time::sleep(ACCEPT_ERROR_BACKOFF) => continue,
+                        _ = stop_handle.clone().shutdown() => break,
+                    }
+                }
+            };
+
+            let connection_stop_handle = if let Some(max_lifetime) = max_lifetime {
+                let (connection_stop_handle, connection_handle) = stop_channel();
+                tokio::spawn(ws::close_after_lifetime(
+                    connection_handle,
+                    stop_handle.clone().shutdown(),
+                    max_lifetime,
+                    metrics.clone(),
+                ));
+                connection_stop_handle
+            } else {
+                stop_handle.clone()
+            };
+
+            let serve = make_service(connection_stop_handle);
+            let tls = tls.clone();
+            tokio::spawn(async move {
+                // the connection is counted until it's closed
+                let _permit = permit;
+                let connection = match tls {
+                    Some(tls) => match accept_tls(&tls, stream).await {
+                        Ok(stream) => Either::Right(stream),
+                        Err(err) => {
+                            debug!(target: "rpc::tls", %err, "Failed TLS handshake");
+                            return
+                        }
+                    },
+                    None => Either::Left(stream),
+                };
+                if let Err(err) = serve(connection).await {
+                    debug!(target: "rpc", %err, "Failed to serve connection");
+                }
+            });
+        }
+    });
+    server_handle
+}
+
+/// Returns true if the accept error is specific to the accepted connection, so that the next
+/// connection can be accepted right away.
+fn is_connection_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionRefused |
+            io::ErrorKind::ConnectionAborted |
+            io::ErrorKind::ConnectionReset
+    )
+}
+
+/// Completes the TLS handshake of the connection.
+async fn accept_tls(tls: &TlsAcceptor, stream: TcpStream) -> io::Result<TlsStream<TcpStream>> {
+    tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, tls.accept(stream))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "TLS handshake timed out"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_errors() {
+        assert!(is_connection_error(&io::ErrorKind::ConnectionAborted.into()));
+        assert!(!is_connection_error(&io::Error::from_raw_os_error(24)));
+    }
+
+    #[test]
+    fn missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = TlsConfig::new(dir.path().join("cert.pem"), dir.path().join("key.pem"));
+        assert!(matches!(
+            load_certified_key(&config),
+            Err(TlsError::Pem { path, .. }) if path == config.cert_path
+        ));
+    }
+
+    #[test]
+    fn empty_certificate() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = TlsConfig::new(dir.path().join("cert.pem"), dir.path().join("key.pem"));
+        std::fs::write(&config.cert_path, "").unwrap();
+        assert!(matches!(load_certified_key(&config), Err(TlsError::NoCertificate(_))));
+    }
+}
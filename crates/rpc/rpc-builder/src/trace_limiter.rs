@@ -0,0 +1,322 @@
+//! [`jsonrpsee`] helper layer for limiting concurrent `debug_` and `trace_` calls.
+
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorCode, ErrorObject, Id, Request},
+    MethodResponse,
+};
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+use tokio::sync::oneshot;
+use tower::Layer;
+
+/// Limits the number of concurrently executing `debug_` and `trace_` calls.
+///
+/// Calls that exceed the limit are queued until a slot frees up. Queued calls are served in
+/// round-robin order across clients and in FIFO order for each client, so a single client can't
+/// monopolize the tracing capacity by flooding the server. Each connection is treated as a separate
+/// client. If the queue is full, calls are rejected with a "server is busy" error.
+///
+/// A batch that contains at least one such call occupies a single slot.
+#[derive(Debug, Clone)]
+pub struct TraceCallLimiter {
+    inner: Arc<TraceCallLimiterInner>,
+}
+
+impl TraceCallLimiter {
+    /// Creates a new limiter that executes at most `max_concurrent` calls at the same time and
+    /// queues at most `max_queued` additional calls.
+    pub fn new(max_concurrent: usize, max_queued: usize) -> Self {
+        Self {
+            inner: Arc::new(TraceCallLimiterInner {
+                max_concurrent,
+                max_queued,
+                next_client_id: AtomicU64::new(0),
+                state: Default::default(),
+                metrics: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns true if calls to the given method are subject to the limit.
+    pub fn is_limited_method(method: &str) -> bool {
+        method.starts_with("trace_") || method.starts_with("debug_")
+    }
+
+    /// Waits for a free slot for the given client.
+    ///
+    /// Returns `None` if the queue is full.
+    async fn acquire(&self, client: u64) -> Option<TraceCallPermit> {
+        let rx = {
+            let mut state = self.inner.state.lock();
+            if state.active < self.inner.max_concurrent && state.queued == 0 {
+                state.active += 1;
+                self.inner.metrics.active_calls.set(state.active as f64);
+                return Some(TraceCallPermit { limiter: Some(self.inner.clone()) })
+            }
+
+            if state.queued >= self.inner.max_queued {
+                // make room by dropping calls that were cancelled while waiting
+                state.remove_cancelled();
+                self.inner.metrics.queued_calls.set(state.queued as f64);
+                if state.queued >= self.inner.max_queued {
+                    self.inner.metrics.rejected_calls_total.increment(1);
+                    return None
+                }
+            }
+
+            let (tx, rx) = oneshot::channel();
+            state.enqueue(client, tx);
+            self.inner.metrics.queued_calls.set(state.queued as f64);
+            rx
+        };
+
+        let queued_at = Instant::now();
+        let permit = rx.await.ok()?;
+        self.inner.metrics.queue_time_seconds.record(queued_at.elapsed().as_secs_f64());
+        Some(permit)
+    }
+}
+
+impl<S> Layer<S> for TraceCallLimiter {
+    type Service = TraceCallLimitingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        // the service is created per connection, so this identifies the client
+        let client = self.inner.next_client_id.fetch_add(1, Ordering::Relaxed);
+        TraceCallLimitingService { inner, limiter: self.clone(), client }
+    }
+}
+
+#[derive(Debug)]
+struct TraceCallLimiterInner {
+    /// Maximum number of concurrently executing calls.
+    max_concurrent: usize,
+    /// Maximum number of queued calls.
+    max_queued: usize,
+    /// Identifier assigned to the next client.
+    next_client_id: AtomicU64,
+    /// Active and queued calls.
+    state: Mutex<TraceCallLimiterState>,
+    /// Metrics of the limiter.
+    metrics: TraceCallLimiterMetrics,
+}
+
+impl TraceCallLimiterInner {
+    /// Hands the slot of a finished call over to the next queued call, or frees it if there is
+    /// none.
+    fn release(self: &Arc<Self>) {
+        let mut state = self.state.lock();
+        while let Some(tx) = state.dequeue() {
+            let permit = TraceCallPermit { limiter: Some(self.clone()) };
+            match tx.send(permit) {
+                Ok(()) => {
+                    self.metrics.queued_calls.set(state.queued as f64);
+                    return
+                }
+                Err(mut permit) => {
+                    // the call was cancelled, disarm the permit so it doesn't release the slot
+                    permit.limiter = None;
+                }
+            }
+        }
+        state.active -= 1;
+        self.metrics.queued_calls.set(state.queued as f64);
+        self.metrics.active_calls.set(state.active as f64);
+    }
+}
+
+/// Active and queued calls of the [`TraceCallLimiter`].
+#[derive(Debug, Default)]
+struct TraceCallLimiterState {
+    /// Number of executing calls.
+    active: usize,
+    /// Number of queued calls.
+    queued: usize,
+    /// Queued calls per client.
+    clients: HashMap<u64, VecDeque<oneshot::Sender<TraceCallPermit>>>,
+    /// Clients with queued calls, in the order they are served.
+    order: VecDeque<u64>,
+}
+
+impl TraceCallLimiterState {
+    /// Queues a call of the given client.
+    fn enqueue(&mut self, client: u64, tx: oneshot::Sender<TraceCallPermit>) {
+        let queue = self.clients.entry(client).or_default();
+        if queue.is_empty() {
+            self.order.push_back(client);
+        }
+        queue.push_back(tx);
+        self.queued += 1;
+    }
+
+    /// Removes the next call to serve, taking turns between clients.
+    fn dequeue(&mut self) -> Option<oneshot::Sender<TraceCallPermit>> {
+        let client = self.order.pop_front()?;
+        let queue = self.clients.get_mut(&client)?;
+        let tx = queue.pop_front();
+        if queue.is_empty() {
+            self.clients.remove(&client);
+        } else {
+            self.order.push_back(client);
+        }
+        self.queued -= 1;
+        tx
+    }
+
+    /// Removes queued calls that were cancelled.
+    fn remove_cancelled(&mut self) {
+        for queue in self.clients.values_mut() {
+            queue.retain(|tx| !tx.is_closed());
+        }
+        self.clients.retain(|_, queue| !queue.is_empty());
+        self.order.retain(|client| self.clients.contains_key(client));
+        self.queued = self.clients.values().map(VecDeque::len).sum();
+    }
+}
+
+/// A slot of the [`TraceCallLimiter`] that is released on drop.
+#[derive(Debug)]
+struct TraceCallPermit {
+    limiter: Option<Arc<TraceCallLimiterInner>>,
+}
+
+impl Drop for TraceCallPermit {
+    fn drop(&mut self) {
+        if let Some(limiter) = self.limiter.take() {
+            limiter.release();
+        }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that limits concurrent `debug_` and `trace_` calls, see
+/// [`TraceCallLimiter`].
+#[derive(Debug, Clone)]
+pub struct TraceCallLimitingService<S> {
+    /// The inner service being wrapped
+    inner: S,
+    /// The limiter shared by all connections
+    limiter: TraceCallLimiter,
+    /// Identifies the client of this connection
+    client: u64,
+}
+
+impl<S> RpcServiceT for TraceCallLimitingService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let limiter = TraceCallLimiter::is_limited_method(req.method_name())
+            .then(|| (self.limiter.clone(), self.client));
+        let id = req.id().into_owned();
+        let fut = self.inner.call(req);
+        async move {
+            let Some((limiter, client)) = limiter else { return fut.await };
+            let Some(_permit) = limiter.acquire(client).await else {
+                return MethodResponse::error(id, ErrorObject::from(ErrorCode::ServerIsBusy))
+            };
+            fut.await
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let is_limited = requests.iter().any(|entry| match entry {
+            Ok(BatchEntry::Call(req)) => TraceCallLimiter::is_limited_method(req.method_name()),
+            _ => false,
+        });
+        let limiter = is_limited.then(|| (self.limiter.clone(), self.client));
+        let fut = self.inner.batch(requests);
+        async move {
+            let Some((limiter, client)) = limiter else { return fut.await };
+            let Some(_permit) = limiter.acquire(client).await else {
+                return MethodResponse::error(Id::Null, ErrorObject::from(ErrorCode::ServerIsBusy))
+            };
+            fut.await
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Metrics for the [`TraceCallLimiter`].
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.trace_calls")]
+struct TraceCallLimiterMetrics {
+    /// The number of executing calls
+    active_calls: Gauge,
+    /// The number of queued calls
+    queued_calls: Gauge,
+    /// The number of calls rejected because the queue was full
+    rejected_calls_total: Counter,
+    /// Time a call spent in the queue before it was executed
+    queue_time_seconds: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fair_queueing() {
+        let limiter = TraceCallLimiter::new(1, 3);
+        let permit = limiter.acquire(0).await.unwrap();
+
+        // client 0 queues two calls before client 1 queues one
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        for (client, call) in [(0, "a"), (0, "b"), (1, "c")] {
+            let limiter = limiter.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let permit = limiter.acquire(client).await.unwrap();
+                tx.send(call).unwrap();
+                drop(permit);
+            });
+            tokio::task::yield_now().await;
+        }
+        tokio::task::yield_now().await;
+        assert_eq!(limiter.inner.state.lock().queued, 3);
+
+        // the queue is full
+        assert!(limiter.acquire(2).await.is_none());
+
+        drop(permit);
+        let mut calls = Vec::new();
+        for _ in 0..3 {
+            calls.push(rx.recv().await.unwrap());
+        }
+        assert_eq!(calls, vec!["a", "c", "b"]);
+
+        let state = limiter.inner.state.lock();
+        assert_eq!(state.active, 0);
+        assert_eq!(state.queued, 0);
+    }
+}
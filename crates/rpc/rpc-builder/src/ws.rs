@@ -0,0 +1,82 @@
+//! Keepalive and lifetime configuration of WS connections.
+
+use jsonrpsee::server::{PingConfig, ServerConfigBuilder, ServerHandle};
+use reth_metrics::{metrics::Counter, Metrics};
+use std::{future::Future, time::Duration};
+
+/// Keepalive and lifetime configuration of the connections of the WS server.
+///
+/// Load balancers commonly close connections that are idle for some time, which silently ends the
+/// subscriptions of the client. Periodic pings keep these connections alive, and close the
+/// connections of clients that stopped responding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WsConnectionConfig {
+    /// The interval of the pings sent to the clients. Pings are disabled if `None`.
+    pub ping_interval: Option<Duration>,
+    /// The time after which a connection that did not respond to a ping is closed.
+    ///
+    /// Only applies if pings are enabled. Defaults to the jsonrpsee default if `None`.
+    pub idle_timeout: Option<Duration>,
+    /// The maximum lifetime of a connection, after which it is closed.
+    ///
+    /// If the WS server shares its port with the HTTP server, this applies to the HTTP
+    /// connections as well.
+    pub max_lifetime: Option<Duration>,
+}
+
+impl WsConnectionConfig {
+    /// Sets the interval of the pings sent to the clients.
+    pub const fn with_ping_interval(mut self, ping_interval: Option<Duration>) -> Self {
+        self.ping_interval = ping_interval;
+        self
+    }
+
+    /// Sets the time after which a connection that did not respond to a ping is closed.
+    pub const fn with_idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Sets the maximum lifetime of a connection.
+    pub const fn with_max_lifetime(mut self, max_lifetime: Option<Duration>) -> Self {
+        self.max_lifetime = max_lifetime;
+        self
+    }
+
+    /// Enables pings on the server config, if configured.
+    pub(crate) fn apply_ping_config(&self, config: ServerConfigBuilder) -> ServerConfigBuilder {
+        let Some(ping_interval) = self.ping_interval else { return config };
+        let mut ping_config = PingConfig::new().ping_interval(ping_interval);
+        if let Some(idle_timeout) = self.idle_timeout {
+            ping_config = ping_config.inactive_limit(idle_timeout);
+        }
+        config.enable_ws_ping(ping_config)
+    }
+}
+
+/// Closes the connection once it exceeds the maximum lifetime, or once the server stops.
+///
+/// Returns early if the connection is closed before.
+pub(crate) async fn close_after_lifetime(
+    connection: ServerHandle,
+    server_stopped: impl Future<Output = ()>,
+    max_lifetime: Duration,
+    metrics: WsConnectionMetrics,
+) {
+    tokio::select! {
+        _ = connection.clone().stopped() => return,
+        _ = server_stopped => {}
+        _ = tokio::time::sleep(max_lifetime) => {
+            metrics.connections_reaped_total.increment(1);
+        }
+    }
+    let _ = connection.stop();
+}
+
+/// Metrics for the connections of the WS server.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.ws")]
+pub(crate) struct WsConnectionMetrics {
+    /// The number of connections that were closed because they exceeded the maximum lifetime
+    connections_reaped_total: Counter,
+}
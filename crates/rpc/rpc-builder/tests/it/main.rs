@@ -5,6 +5,8 @@ mod http;
 mod middleware;
 mod serde;
 mod startup;
+mod tls;
 pub mod utils;
+mod ws;
 
 const fn main() {}
@@ -124,3 +124,29 @@ async fn test_launch_same_port_different_cors() {
         RpcError::WsHttpSamePortError(WsHttpSamePortError::ConflictingCorsDomains { .. })
     ));
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_launch_same_port_different_admin_cors() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let server = builder.build(
+        TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Eth])
+            .with_http(vec![RethRpcModule::Eth]),
+        eth_api,
+    );
+    let addr = test_address();
+    let res = RpcServerConfig::ws(Default::default())
+        .with_ws_address(addr)
+        .with_http(Default::default())
+        .with_cors(Some("*".to_string()))
+        .with_admin_cors(Some("https://*.example.com".to_string()))
+        .with_http_admin_cors(Some("https://admin.example.com".to_string()))
+        .with_http_address(addr)
+        .start(&server)
+        .await;
+    let err = res.unwrap_err();
+    assert!(matches!(
+        err,
+        RpcError::WsHttpSamePortError(WsHttpSamePortError::ConflictingAdminCorsDomains { .. })
+    ));
+}
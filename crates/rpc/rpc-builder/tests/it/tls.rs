@@ -0,0 +1,234 @@
+//! TLS termination tests
+
+use crate::utils::{launch_auth_with_config, test_address, test_rpc_builder};
+use reth_rpc_builder::{
+    auth::AuthServerConfig, RpcServerConfig, TlsConfig, TransportRpcModuleConfig,
+};
+use reth_rpc_layer::{secret_to_bearer_header, JwtSecret};
+use reth_rpc_server_types::RethRpcModule;
+use rustls::{
+    crypto::ring,
+    pki_types::{pem::PemObject, CertificateDer, ServerName},
+    ClientConfig, RootCertStore,
+};
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tokio_rustls::{client::TlsStream, TlsConnector};
+
+/// Returns the path of a file in the TLS test data.
+///
+/// `cert.pem` and `cert2.pem` are certificates for `localhost` with the keys `key.pem` and
+/// `key2.pem`, issued by the certificate authority `ca.pem`.
+fn testdata(file: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/tls").join(file)
+}
+
+fn certificate(file: &str) -> CertificateDer<'static> {
+    CertificateDer::from_pem_file(testdata(file)).unwrap()
+}
+
+/// Connects to the server on the given port and completes the TLS handshake, trusting the test
+/// certificate authority.
+async fn connect(addr: SocketAddr) -> io::Result<TlsStream<TcpStream>> {
+    let mut roots = RootCertStore::empty();
+    roots.add(certificate("ca.pem")).unwrap();
+    let config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    let stream = TcpStream::connect((Ipv4Addr::LOCALHOST, addr.port())).await?;
+    TlsConnector::from(Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), stream)
+        .await
+}
+
+/// Sends a JSON-RPC request over a new TLS connection and returns the raw HTTP response.
+async fn post(addr: SocketAddr, headers: &str, body: &str) -> String {
+    let mut stream = connect(addr).await.unwrap();
+    let request = format!(
+        "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n{headers}\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    // the server may close the connection without a TLS close notification, which is reported as
+    // an error after the response was read
+    let mut response = Vec::new();
+    let _ = stream.read_to_end(&mut response).await;
+    String::from_utf8(response).unwrap()
+}
+
+/// Upgrades a new TLS connection to a websocket and returns the raw HTTP response.
+async fn upgrade(addr: SocketAddr) -> String {
+    let mut stream = connect(addr).await.unwrap();
+    let request = "GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+                   Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                   Sec-WebSocket-Version: 13\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        let mut buf = [0; 1024];
+        let read = stream.read(&mut buf).await.unwrap();
+        assert_ne!(read, 0, "connection closed before the upgrade");
+        response.extend_from_slice(&buf[..read]);
+    }
+    String::from_utf8(response).unwrap()
+}
+
+const CLIENT_VERSION: &str =
+    r#"{"jsonrpc":"2.0","method":"web3_clientVersion","params":[],"id":1}"#;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_https_and_wss_same_port() {
+    reth_tracing::init_test_tracing();
+
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let server = builder.build(
+        TransportRpcModuleConfig::set_ws([RethRpcModule::Web3]).with_http([RethRpcModule::Web3]),
+        eth_api,
+    );
+    let handle = RpcServerConfig::ws(Default::default())
+        .with_ws_address(test_address())
+        .with_http(Default::default())
+        .with_http_address(test_address())
+        .with_tls(Some(TlsConfig::new(testdata("cert.pem"), testdata("key.pem"))))
+        .start(&server)
+        .await
+        .unwrap();
+    let addr = handle.http_local_addr().unwrap();
+    assert!(handle.http_url().unwrap().starts_with("https://"));
+    assert!(handle.ws_url().unwrap().starts_with("wss://"));
+
+    let response = post(addr, "", CLIENT_VERSION).await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.contains(r#""result":"#), "{response}");
+
+    let response = upgrade(addr).await;
+    assert!(response.starts_with("HTTP/1.1 101"), "{response}");
+
+    // plain connections are rejected
+    let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, addr.port())).await.unwrap();
+    stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+    let mut response = Vec::new();
+    let _ = stream.read_to_end(&mut response).await;
+    assert!(!response.starts_with(b"HTTP/1.1"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_https_auth_server() {
+    reth_tracing::init_test_tracing();
+
+    let secret = JwtSecret::random();
+    let config = AuthServerConfig::builder(secret)
+        .socket_addr(test_address())
+        .with_tls(Some(TlsConfig::new(testdata("cert.pem"), testdata("key.pem"))))
+        .build();
+    let handle = launch_auth_with_config(config).await;
+    assert!(handle.http_url().starts_with("https://"));
+
+    let request =
+        r#"{"jsonrpc":"2.0","method":"engine_exchangeCapabilities","params":[[]],"id":1}"#;
+    let response = post(handle.local_addr(), "", request).await;
+    assert!(response.starts_with("HTTP/1.1 401"), "{response}");
+
+    let authorization = secret_to_bearer_header(&secret);
+    let headers = format!("Authorization: {}\r\n", authorization.to_str().unwrap());
+    let response = post(handle.local_addr(), &headers, request).await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.contains(r#""result":"#), "{response}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_https_reloads_certificate() {
+    reth_tracing::init_test_tracing();
+
+    let dir = tempfile::tempdir().unwrap();
+    let config = TlsConfig::new(dir.path().join("cert.pem"), dir.path().join("key.pem"))
+        .with_reload_interval(Duration::from_millis(50));
+    std::fs::copy(testdata("cert.pem"), &config.cert_path).unwrap();
+    std::fs::copy(testdata("key.pem"), &config.key_path).unwrap();
+
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let server = builder.build(TransportRpcModuleConfig::set_http([RethRpcModule::Web3]), eth_api);
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_tls(Some(config.clone()))
+        .start(&server)
+        .await
+        .unwrap();
+    let addr = handle.http_local_addr().unwrap();
+
+    let peer_certificate = |stream: &TlsStream<TcpStream>| {
+        stream.get_ref().1.peer_certificates().unwrap()[0].clone().into_owned()
+    };
+    let stream = connect(addr).await.unwrap();
+    assert_eq!(peer_certificate(&stream), certificate("cert.pem"));
+
+    // an invalid certificate is not picked up, and the previous one remains in use
+    std::fs::write(&config.cert_path, "").unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let stream = connect(addr).await.unwrap();
+    assert_eq!(peer_certificate(&stream), certificate("cert.pem"));
+
+    std::fs::copy(testdata("key2.pem"), &config.key_path).unwrap();
+    std::fs::copy(testdata("cert2.pem"), &config.cert_path).unwrap();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let stream = connect(addr).await.unwrap();
+            if peer_certificate(&stream) == certificate("cert2.pem") {
+                break
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("certificate was not reloaded");
+
+    let response = post(addr, "", CLIENT_VERSION).await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_wss_max_connections() {
+    reth_tracing::init_test_tracing();
+
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let server = builder.build(TransportRpcModuleConfig::set_ws([RethRpcModule::Web3]), eth_api);
+    let handle = RpcServerConfig::ws(Default::default())
+        .with_ws_address(test_address())
+        .with_tls(Some(TlsConfig::new(testdata("cert.pem"), testdata("key.pem"))))
+        .with_max_connections(1)
+        .start(&server)
+        .await
+        .unwrap();
+    let addr = handle.ws_local_addr().unwrap();
+
+    let first = connect(addr).await.unwrap();
+    // the second connection is not accepted while the first one is open
+    let second = tokio::spawn(connect(addr));
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(!second.is_finished());
+
+    drop(first);
+    tokio::time::timeout(Duration::from_secs(10), second)
+        .await
+        .expect("connection was not accepted after the first one was closed")
+        .unwrap()
+        .unwrap();
+}
@@ -0,0 +1,72 @@
+//! WS connection tests
+
+use crate::utils::{test_address, test_rpc_builder};
+use reth_rpc_api::Web3ApiClient;
+use reth_rpc_builder::{
+    RpcServerConfig, RpcServerHandle, TransportRpcModuleConfig, WsConnectionConfig,
+};
+use reth_rpc_server_types::RethRpcModule;
+use std::time::Duration;
+
+const MAX_LIFETIME: Duration = Duration::from_millis(200);
+
+fn ws_connection_config() -> WsConnectionConfig {
+    WsConnectionConfig::default().with_max_lifetime(Some(MAX_LIFETIME))
+}
+
+/// Asserts that a WS connection is usable, and closed once it exceeds the maximum lifetime.
+async fn assert_closed_after_max_lifetime(handle: &RpcServerHandle) {
+    let client = handle.ws_client().await.unwrap();
+    Web3ApiClient::client_version(&client).await.unwrap();
+    assert!(client.is_connected());
+
+    tokio::time::timeout(MAX_LIFETIME * 20, client.on_disconnect())
+        .await
+        .expect("connection was not closed after the maximum lifetime");
+    assert!(!client.is_connected());
+
+    // new connections are accepted
+    let client = handle.ws_client().await.unwrap();
+    Web3ApiClient::client_version(&client).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_max_lifetime() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let server =
+        builder.build(TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Web3]), eth_api);
+    let handle = RpcServerConfig::ws(Default::default())
+        .with_ws_address(test_address())
+        .with_ws_connection_config(ws_connection_config())
+        .start(&server)
+        .await
+        .unwrap();
+
+    assert_closed_after_max_lifetime(&handle).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_max_lifetime_same_port() {
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let modules = vec![RethRpcModule::Web3];
+    let server = builder
+        .build(TransportRpcModuleConfig::set_ws(modules.clone()).with_http(modules), eth_api);
+    let addr = test_address();
+    let handle = RpcServerConfig::ws(Default::default())
+        .with_ws_address(addr)
+        .with_http(Default::default())
+        .with_http_address(addr)
+        .with_ws_connection_config(ws_connection_config())
+        .start(&server)
+        .await
+        .unwrap();
+    assert_eq!(handle.ws_local_addr(), handle.http_local_addr());
+
+    assert_closed_after_max_lifetime(&handle).await;
+
+    // the HTTP server is still served
+    let client = handle.http_client().unwrap();
+    Web3ApiClient::client_version(&client).await.unwrap();
+}
@@ -7,7 +7,7 @@ use alloy_eips::{
     eip4895::Withdrawals,
     eip7685::RequestsOrHash,
 };
-use alloy_primitives::{BlockHash, BlockNumber, B256, U64};
+use alloy_primitives::{BlockHash, BlockNumber, B256, U256, U64};
 use alloy_rpc_types_engine::{
     CancunPayloadFields, ClientVersionV1, ExecutionData, ExecutionPayloadBodiesV1,
     ExecutionPayloadBodyV1, ExecutionPayloadInputV2, ExecutionPayloadSidecar, ExecutionPayloadV1,
@@ -15,24 +15,35 @@ use alloy_rpc_types_engine::{
     PraguePayloadFields,
 };
 use async_trait::async_trait;
+use jsonrpsee::{ConnectionId, Extensions};
 use jsonrpsee_core::{server::RpcModule, RpcResult};
 use parking_lot::Mutex;
 use reth_chainspec::EthereumHardforks;
-use reth_engine_primitives::{BeaconConsensusEngineHandle, EngineTypes};
+use reth_engine_primitives::{
+    BeaconConsensusEngineHandle, ConsensusClientUpdate, ConsensusClients, EngineTypes,
+};
 use reth_engine_tree::tree::EngineValidator;
 use reth_payload_builder::PayloadStore;
 use reth_payload_primitives::{
-    validate_payload_timestamp, EngineApiMessageVersion, ExecutionPayload,
-    PayloadBuilderAttributes, PayloadOrAttributes, PayloadTypes,
+    validate_payload_timestamp, EngineApiMessageVersion, ExecutionPayload, ExternalPayload,
+    PayloadAttributesHook, PayloadBuilderAttributes, PayloadOrAttributes, PayloadTypes,
 };
-use reth_primitives_traits::{Block, BlockBody};
-use reth_rpc_api::{EngineApiServer, IntoEngineApiRpcModule};
+use reth_primitives_traits::{AlloyBlockHeader, Block, BlockBody, SealedBlock, Transaction};
+use reth_rpc_api::{EngineApiServer, IntoEngineApiRpcModule, RethEngineApiServer};
 use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 use tokio::sync::oneshot;
-use tracing::{debug, trace, warn};
+use tracing::{debug, info, trace, warn};
+
+/// Returns the id of the connection the request was received on.
+fn connection_id(ext: &Extensions) -> Option<u64> {
+    ext.get::<ConnectionId>().map(|id| id.0 as u64)
+}
 
 /// The Engine API response sender.
 pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
@@ -60,6 +71,10 @@ const MAX_BLOB_LIMIT: usize = 128;
 /// L1 implementation.
 pub struct EngineApi<Provider, PayloadT: PayloadTypes, Pool, Validator, ChainSpec> {
     inner: Arc<EngineApiInner<Provider, PayloadT, Pool, Validator, ChainSpec>>,
+    /// Optional hook that is invoked with well-formed forkchoice update payload attributes.
+    payload_attributes_hook: Option<Arc<dyn PayloadAttributesHook<PayloadT::PayloadAttributes>>>,
+    /// The consensus clients that identified themselves via `engine_getClientVersionV1`.
+    consensus_clients: ConsensusClients,
 }
 
 impl<Provider, PayloadT: PayloadTypes, Pool, Validator, ChainSpec>
@@ -69,6 +84,42 @@ impl<Provider, PayloadT: PayloadTypes, Pool, Validator, ChainSpec>
     pub fn chain_spec(&self) -> &Arc<ChainSpec> {
         &self.inner.chain_spec
     }
+
+    /// Installs a [`PayloadAttributesHook`] that is invoked with the payload attributes of every
+    /// forkchoice update after they passed the engine API validation.
+    ///
+    /// The hook can reject the attributes, in which case the forkchoice update is applied without
+    /// starting a payload build process, or modify them before they are sent to the payload
+    /// builder.
+    pub fn with_payload_attributes_hook<H>(mut self, hook: H) -> Self
+    where
+        H: PayloadAttributesHook<PayloadT::PayloadAttributes>,
+    {
+        self.payload_attributes_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Configures the [`ConsensusClients`] that record the consensus clients identifying
+    /// themselves via `engine_getClientVersionV1`.
+    ///
+    /// This allows sharing the connected consensus clients with other components, e.g. the admin
+    /// API.
+    pub fn with_consensus_clients(mut self, consensus_clients: ConsensusClients) -> Self {
+        self.consensus_clients = consensus_clients;
+        self
+    }
+
+    /// Returns the consensus clients that identified themselves via `engine_getClientVersionV1`.
+    pub const fn consensus_clients(&self) -> &ConsensusClients {
+        &self.consensus_clients
+    }
+
+    /// Returns the identity of the consensus client that sent the request, if it identified
+    /// itself on the connection of the request.
+    fn consensus_client(&self, ext: &Extensions) -> Option<String> {
+        let version = self.consensus_clients.client_version(connection_id(ext)?)?;
+        Some(format!("{}/{}-{}", version.name, version.version, version.commit))
+    }
 }
 
 impl<Provider, PayloadT, Pool, Validator, ChainSpec>
@@ -108,14 +159,62 @@ where
             latest_new_payload_response: Mutex::new(None),
             accept_execution_requests_hash,
         });
-        Self { inner }
+        Self {
+            inner,
+            payload_attributes_hook: None,
+            consensus_clients: ConsensusClients::default(),
+        }
     }
 
     /// Fetches the client version.
+    ///
+    /// The version reported by the consensus client is recorded for the connection with the given
+    /// id, see also [`ConsensusClients`].
     pub fn get_client_version_v1(
         &self,
-        _client: ClientVersionV1,
+        connection_id: Option<u64>,
+        client: ClientVersionV1,
     ) -> EngineApiResult<Vec<ClientVersionV1>> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        match self.consensus_clients.on_client_version(connection_id, client.clone(), now) {
+            ConsensusClientUpdate::New => {
+                info!(
+                    target: "rpc::engine",
+                    ?connection_id,
+                    code = %client.code,
+                    name = %client.name,
+                    version = %client.version,
+                    commit = %client.commit,
+                    "Consensus client connected"
+                );
+            }
+            ConsensusClientUpdate::Changed { previous } => {
+                info!(
+                    target: "rpc::engine",
+                    ?connection_id,
+                    code = %client.code,
+                    name = %client.name,
+                    version = %client.version,
+                    commit = %client.commit,
+                    previous_version = %previous.version,
+                    "Consensus client version changed"
+                );
+            }
+            ConsensusClientUpdate::Unchanged => {
+                trace!(
+                    target: "rpc::engine",
+                    ?connection_id,
+                    code = %client.code,
+                    version = %client.version,
+                    "Consensus client version unchanged"
+                );
+            }
+        }
+        self.inner
+            .metrics
+            .consensus_clients
+            .on_client_version(&client, self.consensus_clients.len());
+
         Ok(vec![self.inner.client.clone()])
     }
 
@@ -718,9 +817,14 @@ where
     ) -> EngineApiResult<ForkchoiceUpdated> {
         self.inner.record_elapsed_time_on_fcu();
 
-        if let Some(ref attrs) = payload_attrs {
+        let payload_attrs = if let Some(attrs) = payload_attrs {
             let attr_validation_res =
-                self.inner.validator.ensure_well_formed_attributes(version, attrs);
+                self.inner.validator.ensure_well_formed_attributes(version, &attrs).and_then(
+                    |()| match &self.payload_attributes_hook {
+                        Some(hook) => hook.on_payload_attributes(&state, attrs),
+                        None => Ok(attrs),
+                    },
+                );
 
             // From the engine API spec:
             //
@@ -731,21 +835,30 @@ where
             // update MUST NOT be rolled back.
             //
             // NOTE: This will also apply to the validation result for the cancun or
-            // shanghai-specific fields provided in the payload attributes.
+            // shanghai-specific fields provided in the payload attributes, as well as to attributes
+            // rejected by the configured payload attributes hook.
             //
             // To do this, we set the payload attrs to `None` if attribute validation failed, but
             // we still apply the forkchoice update.
-            if let Err(err) = attr_validation_res {
-                let fcu_res =
-                    self.inner.beacon_consensus.fork_choice_updated(state, None, version).await?;
-                // TODO: decide if we want this branch - the FCU INVALID response might be more
-                // useful than the payload attributes INVALID response
-                if fcu_res.is_invalid() {
-                    return Ok(fcu_res)
+            match attr_validation_res {
+                Ok(attrs) => Some(attrs),
+                Err(err) => {
+                    let fcu_res = self
+                        .inner
+                        .beacon_consensus
+                        .fork_choice_updated(state, None, version)
+                        .await?;
+                    // TODO: decide if we want this branch - the FCU INVALID response might be
+                    // more useful than the payload attributes INVALID response
+                    if fcu_res.is_invalid() {
+                        return Ok(fcu_res)
+                    }
+                    return Err(err.into())
                 }
-                return Err(err.into())
             }
-        }
+        } else {
+            None
+        };
 
         Ok(self.inner.beacon_consensus.fork_choice_updated(state, payload_attrs, version).await?)
     }
@@ -841,6 +954,114 @@ where
     }
 }
 
+impl<Provider, EngineT, Pool, Validator, ChainSpec>
+    EngineApi<Provider, EngineT, Pool, Validator, ChainSpec>
+where
+    Provider: HeaderProvider + BlockReader + StateProviderFactory + 'static,
+    EngineT: EngineTypes<
+        ExecutionData = ExecutionData,
+        BuiltPayload: From<ExternalPayload<Validator::Block>>,
+    >,
+    Pool: TransactionPool + 'static,
+    Validator: EngineValidator<EngineT>,
+    ChainSpec: EthereumHardforks + Send + Sync + 'static,
+{
+    /// Validates a payload that was built outside of the node and submits it as a candidate for
+    /// the payload job with the given id, see [`PayloadStore::submit_candidate`].
+    ///
+    /// The payload must be built on top of the attributes of the job, including its fee recipient,
+    /// and it's executed by the consensus engine like a new payload. Returns the status of the
+    /// payload, the candidate is only submitted if it's valid.
+    ///
+    /// The value of the candidate is not declared by the submitter, but computed from the block as
+    /// a lower bound of the priority fees it pays to the fee recipient.
+    pub async fn submit_payload_candidate(
+        &self,
+        payload_id: PayloadId,
+        payload: ExecutionData,
+    ) -> EngineApiResult<PayloadStatus> {
+        let attributes = self.get_payload_attributes(payload_id).await?;
+
+        if payload.parent_hash() != attributes.parent() {
+            return Err(EngineApiError::InvalidPayloadCandidate("parent hash mismatch"))
+        }
+        if self.inner.provider.header(&payload.parent_hash())?.is_none() {
+            return Err(EngineApiError::InvalidPayloadCandidate("unknown parent"))
+        }
+        if payload.payload.as_v1().fee_recipient != attributes.suggested_fee_recipient() {
+            return Err(EngineApiError::InvalidPayloadCandidate("fee recipient mismatch"))
+        }
+        if payload.payload.as_v1().gas_used > payload.payload.as_v1().gas_limit {
+            return Err(EngineApiError::InvalidPayloadCandidate("gas used exceeds gas limit"))
+        }
+        if payload.timestamp() != attributes.timestamp() {
+            return Err(EngineApiError::InvalidPayloadCandidate("timestamp mismatch"))
+        }
+        if payload.payload.as_v1().prev_randao != attributes.prev_randao() {
+            return Err(EngineApiError::InvalidPayloadCandidate("prev randao mismatch"))
+        }
+        if payload.parent_beacon_block_root() != attributes.parent_beacon_block_root() {
+            return Err(EngineApiError::InvalidPayloadCandidate("parent beacon block root mismatch"))
+        }
+        if payload.withdrawals().is_some_and(|withdrawals| {
+            withdrawals.as_slice() != attributes.withdrawals().as_slice()
+        }) {
+            return Err(EngineApiError::InvalidPayloadCandidate("withdrawals mismatch"))
+        }
+        // the blobs of the candidate are not available to `engine_getPayload`
+        if payload.sidecar.cancun().is_some_and(|cancun| !cancun.versioned_hashes.is_empty()) {
+            return Err(EngineApiError::InvalidPayloadCandidate(
+                "blob transactions are not supported",
+            ))
+        }
+
+        let requests = payload.sidecar.requests().cloned();
+        if requests.is_none() &&
+            self.inner.chain_spec.is_prague_active_at_timestamp(payload.timestamp())
+        {
+            return Err(EngineApiError::InvalidPayloadCandidate("missing execution requests"))
+        }
+
+        let status = self.inner.beacon_consensus.new_payload(payload.clone()).await?;
+        if !status.status.is_valid() {
+            return Ok(status)
+        }
+
+        let block = self
+            .inner
+            .validator
+            .ensure_well_formed_payload(payload)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+        let block = block.into_sealed_block();
+        let candidate = ExternalPayload {
+            id: payload_id,
+            value: candidate_value(&block),
+            block: Arc::new(block),
+            requests,
+        };
+        self.inner.payload_store.submit_candidate(payload_id, candidate.into()).await?;
+
+        Ok(status)
+    }
+}
+
+/// Returns a lower bound of the priority fees that the fee recipient of the block receives.
+///
+/// The gas used by the individual transactions is only known after executing them, so the gas used
+/// by the whole block is priced at the lowest effective tip of its transactions. Direct transfers
+/// to the fee recipient are not accounted for.
+fn candidate_value<B: Block>(block: &SealedBlock<B>) -> U256 {
+    let base_fee = block.header().base_fee_per_gas().unwrap_or_default();
+    let min_tip = block
+        .body()
+        .transactions()
+        .iter()
+        .map(|tx| tx.effective_tip_per_gas(base_fee).unwrap_or_default())
+        .min()
+        .unwrap_or_default();
+    U256::from(min_tip) * U256::from(block.header().gas_used())
+}
+
 // This is the concrete ethereum engine API implementation.
 #[async_trait]
 impl<Provider, EngineT, Pool, Validator, ChainSpec> EngineApiServer<EngineT>
@@ -855,8 +1076,12 @@ where
     /// Handler for `engine_newPayloadV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_newpayloadv1>
     /// Caution: This should not accept the `withdrawals` field
-    async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> RpcResult<PayloadStatus> {
-        trace!(target: "rpc::engine", "Serving engine_newPayloadV1");
+    async fn new_payload_v1(
+        &self,
+        ext: &Extensions,
+        payload: ExecutionPayloadV1,
+    ) -> RpcResult<PayloadStatus> {
+        trace!(target: "rpc::engine", client = ?self.consensus_client(ext), "Serving engine_newPayloadV1");
         let payload =
             ExecutionData { payload: payload.into(), sidecar: ExecutionPayloadSidecar::none() };
         Ok(self.new_payload_v1_metered(payload).await?)
@@ -864,8 +1089,12 @@ where
 
     /// Handler for `engine_newPayloadV2`
     /// See also <https://github.com/ethereum/execution-apis/blob/584905270d8ad665718058060267061ecfd79ca5/src/engine/shanghai.md#engine_newpayloadv2>
-    async fn new_payload_v2(&self, payload: ExecutionPayloadInputV2) -> RpcResult<PayloadStatus> {
-        trace!(target: "rpc::engine", "Serving engine_newPayloadV2");
+    async fn new_payload_v2(
+        &self,
+        ext: &Extensions,
+        payload: ExecutionPayloadInputV2,
+    ) -> RpcResult<PayloadStatus> {
+        trace!(target: "rpc::engine", client = ?self.consensus_client(ext), "Serving engine_newPayloadV2");
         let payload = ExecutionData {
             payload: payload.into_payload(),
             sidecar: ExecutionPayloadSidecar::none(),
@@ -878,11 +1107,12 @@ where
     /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_newpayloadv3>
     async fn new_payload_v3(
         &self,
+        ext: &Extensions,
         payload: ExecutionPayloadV3,
         versioned_hashes: Vec<B256>,
         parent_beacon_block_root: B256,
     ) -> RpcResult<PayloadStatus> {
-        trace!(target: "rpc::engine", "Serving engine_newPayloadV3");
+        trace!(target: "rpc::engine", client = ?self.consensus_client(ext), "Serving engine_newPayloadV3");
         let payload = ExecutionData {
             payload: payload.into(),
             sidecar: ExecutionPayloadSidecar::v3(CancunPayloadFields {
@@ -898,12 +1128,13 @@ where
     /// See also <https://github.com/ethereum/execution-apis/blob/03911ffc053b8b806123f1fc237184b0092a485a/src/engine/prague.md#engine_newpayloadv4>
     async fn new_payload_v4(
         &self,
+        ext: &Extensions,
         payload: ExecutionPayloadV3,
         versioned_hashes: Vec<B256>,
         parent_beacon_block_root: B256,
         requests: RequestsOrHash,
     ) -> RpcResult<PayloadStatus> {
-        trace!(target: "rpc::engine", "Serving engine_newPayloadV4");
+        trace!(target: "rpc::engine", client = ?self.consensus_client(ext), "Serving engine_newPayloadV4");
 
         // Accept requests as a hash only if it is explicitly allowed
         if requests.is_hash() && !self.inner.accept_execution_requests_hash {
@@ -927,10 +1158,11 @@ where
     /// Caution: This should not accept the `withdrawals` field
     async fn fork_choice_updated_v1(
         &self,
+        ext: &Extensions,
         fork_choice_state: ForkchoiceState,
         payload_attributes: Option<EngineT::PayloadAttributes>,
     ) -> RpcResult<ForkchoiceUpdated> {
-        trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedV1");
+        trace!(target: "rpc::engine", client = ?self.consensus_client(ext), "Serving engine_forkchoiceUpdatedV1");
         Ok(self.fork_choice_updated_v1_metered(fork_choice_state, payload_attributes).await?)
     }
 
@@ -938,10 +1170,11 @@ where
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/shanghai.md#engine_forkchoiceupdatedv2>
     async fn fork_choice_updated_v2(
         &self,
+        ext: &Extensions,
         fork_choice_state: ForkchoiceState,
         payload_attributes: Option<EngineT::PayloadAttributes>,
     ) -> RpcResult<ForkchoiceUpdated> {
-        trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedV2");
+        trace!(target: "rpc::engine", client = ?self.consensus_client(ext), "Serving engine_forkchoiceUpdatedV2");
         Ok(self.fork_choice_updated_v2_metered(fork_choice_state, payload_attributes).await?)
     }
 
@@ -950,10 +1183,11 @@ where
     /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_forkchoiceupdatedv3>
     async fn fork_choice_updated_v3(
         &self,
+        ext: &Extensions,
         fork_choice_state: ForkchoiceState,
         payload_attributes: Option<EngineT::PayloadAttributes>,
     ) -> RpcResult<ForkchoiceUpdated> {
-        trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedV3");
+        trace!(target: "rpc::engine", client = ?self.consensus_client(ext), "Serving engine_forkchoiceUpdatedV3");
         Ok(self.fork_choice_updated_v3_metered(fork_choice_state, payload_attributes).await?)
     }
 
@@ -1084,10 +1318,11 @@ where
     /// See also <https://github.com/ethereum/execution-apis/blob/03911ffc053b8b806123f1fc237184b0092a485a/src/engine/identification.md>
     async fn get_client_version_v1(
         &self,
+        ext: &Extensions,
         client: ClientVersionV1,
     ) -> RpcResult<Vec<ClientVersionV1>> {
         trace!(target: "rpc::engine", "Serving engine_getClientVersionV1");
-        Ok(Self::get_client_version_v1(self, client)?)
+        Ok(Self::get_client_version_v1(self, connection_id(ext), client)?)
     }
 
     /// Handler for `engine_exchangeCapabilitiesV1`
@@ -1113,14 +1348,42 @@ where
     }
 }
 
+#[async_trait]
+impl<Provider, EngineT, Pool, Validator, ChainSpec> RethEngineApiServer<ExecutionData>
+    for EngineApi<Provider, EngineT, Pool, Validator, ChainSpec>
+where
+    Provider: HeaderProvider + BlockReader + StateProviderFactory + 'static,
+    EngineT: EngineTypes<
+        ExecutionData = ExecutionData,
+        BuiltPayload: From<ExternalPayload<Validator::Block>>,
+    >,
+    Pool: TransactionPool + 'static,
+    Validator: EngineValidator<EngineT>,
+    ChainSpec: EthereumHardforks + Send + Sync + 'static,
+{
+    /// Handler for `reth_submitPayloadCandidate`
+    async fn submit_payload_candidate(
+        &self,
+        payload_id: PayloadId,
+        payload: ExecutionData,
+    ) -> RpcResult<PayloadStatus> {
+        trace!(target: "rpc::engine", "Serving reth_submitPayloadCandidate");
+        Ok(Self::submit_payload_candidate(self, payload_id, payload).await?)
+    }
+}
+
 impl<Provider, EngineT, Pool, Validator, ChainSpec> IntoEngineApiRpcModule
     for EngineApi<Provider, EngineT, Pool, Validator, ChainSpec>
 where
     EngineT: EngineTypes,
-    Self: EngineApiServer<EngineT>,
+    Self: EngineApiServer<EngineT> + RethEngineApiServer<EngineT::ExecutionData>,
 {
     fn into_rpc_module(self) -> RpcModule<()> {
-        self.into_rpc().remove_context()
+        let mut module = EngineApiServer::into_rpc(self.clone()).remove_context();
+        module
+            .merge(RethEngineApiServer::into_rpc(self).remove_context())
+            .expect("no conflicting methods");
+        module
     }
 }
 
@@ -1140,7 +1403,11 @@ where
     PayloadT: PayloadTypes,
 {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            payload_attributes_hook: self.payload_attributes_hook.clone(),
+            consensus_clients: self.consensus_clients.clone(),
+        }
     }
 }
 
@@ -1194,16 +1461,22 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_rpc_types_engine::{ClientCode, ClientVersionV1};
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_primitives::{Address, TxKind};
+    use alloy_rpc_types_engine::{
+        ClientCode, ClientVersionV1, PayloadAttributes, PayloadStatusEnum,
+    };
     use assert_matches::assert_matches;
     use reth_chainspec::{ChainSpec, MAINNET};
-    use reth_engine_primitives::BeaconEngineMessage;
-    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_engine_primitives::{BeaconEngineMessage, OnForkChoiceUpdated};
+    use reth_ethereum_engine_primitives::{EthEngineTypes, EthPayloadBuilderAttributes};
     use reth_ethereum_primitives::Block;
     use reth_node_ethereum::EthereumEngineValidator;
-    use reth_payload_builder::test_utils::spawn_test_payload_service;
+    use reth_payload_builder::{test_utils::spawn_test_payload_service, PayloadBuilderHandle};
+    use reth_payload_primitives::EngineObjectValidationError;
     use reth_provider::test_utils::MockEthProvider;
     use reth_tasks::TokioTaskExecutor;
+    use reth_testing_utils::generators;
     use reth_transaction_pool::noop::NoopTransactionPool;
     use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
@@ -1233,7 +1506,7 @@ mod tests {
             provider.clone(),
             chain_spec.clone(),
             BeaconConsensusEngineHandle::new(to_engine),
-            payload_store.into(),
+            payload_store.clone().into(),
             NoopTransactionPool::default(),
             task_executor,
             client,
@@ -1241,7 +1514,12 @@ mod tests {
             EthereumEngineValidator::new(chain_spec.clone()),
             false,
         );
-        let handle = EngineApiTestHandle { chain_spec, provider, from_api: engine_rx };
+        let handle = EngineApiTestHandle {
+            chain_spec,
+            provider,
+            payload_builder: payload_store,
+            from_api: engine_rx,
+        };
         (handle, api)
     }
 
@@ -1254,14 +1532,41 @@ mod tests {
             commit: "defa64b2".to_string(),
         };
         let (_, api) = setup_engine_api();
-        let res = api.get_client_version_v1(client.clone());
+        let res = api.get_client_version_v1(None, client.clone());
         assert_eq!(res.unwrap(), vec![client]);
     }
 
+    #[tokio::test]
+    async fn engine_client_version_v1_records_consensus_client() {
+        let (_, api) = setup_engine_api();
+        let lighthouse = ClientVersionV1 {
+            code: ClientCode::LH,
+            name: "Lighthouse".to_string(),
+            version: "v7.0.0".to_string(),
+            commit: "e42406d7".to_string(),
+        };
+        let teku = ClientVersionV1 {
+            code: ClientCode::TK,
+            name: "Teku".to_string(),
+            version: "25.6.0".to_string(),
+            commit: "3a1b2c4d".to_string(),
+        };
+
+        api.get_client_version_v1(Some(1), lighthouse.clone()).unwrap();
+        api.get_client_version_v1(Some(2), teku.clone()).unwrap();
+        api.get_client_version_v1(Some(1), lighthouse.clone()).unwrap();
+
+        let clients = api.consensus_clients().clients();
+        assert_eq!(clients.len(), 2);
+        assert!(clients.iter().any(|c| c.connection_id == Some(1) && c.version == lighthouse));
+        assert!(clients.iter().any(|c| c.connection_id == Some(2) && c.version == teku));
+    }
+
     struct EngineApiTestHandle {
         #[allow(dead_code)]
         chain_spec: Arc<ChainSpec>,
         provider: Arc<MockEthProvider>,
+        payload_builder: PayloadBuilderHandle<EthEngineTypes>,
         from_api: UnboundedReceiver<BeaconEngineMessage<EthEngineTypes>>,
     }
 
@@ -1281,6 +1586,164 @@ mod tests {
         assert_matches!(handle.from_api.recv().await, Some(BeaconEngineMessage::NewPayload { .. }));
     }
 
+    #[tokio::test]
+    async fn submit_payload_candidate() {
+        let (mut handle, api) = setup_engine_api();
+
+        let attributes = EthPayloadBuilderAttributes::new(
+            B256::ZERO,
+            PayloadAttributes {
+                timestamp: 1,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            },
+        );
+        let payload_id =
+            handle.payload_builder.send_new_payload(attributes).await.unwrap().unwrap();
+
+        // a block with a single transfer that pays a tip of 2 wei per gas
+        let tx = generators::sign_tx_with_random_key_pair(
+            &mut generators::rng(),
+            TxLegacy {
+                chain_id: Some(1),
+                gas_price: 3,
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::random()),
+                ..Default::default()
+            }
+            .into(),
+        );
+        let execution_data = move |timestamp, beneficiary| {
+            let mut block = Block::default();
+            block.header.timestamp = timestamp;
+            block.header.beneficiary = beneficiary;
+            block.header.base_fee_per_gas = Some(1);
+            block.header.gas_limit = 30_000_000;
+            block.header.gas_used = 21_000;
+            block.body.transactions.push(tx.clone());
+            ExecutionData {
+                payload: ExecutionPayloadV1::from_block_slow(&block).into(),
+                sidecar: ExecutionPayloadSidecar::none(),
+            }
+        };
+
+        // there's no such payload job
+        let res = api
+            .submit_payload_candidate(PayloadId::new([0xff; 8]), execution_data(1, Address::ZERO))
+            .await;
+        assert_matches!(res, Err(EngineApiError::UnknownPayload));
+
+        // the candidate isn't built on top of the payload attributes
+        let res = api.submit_payload_candidate(payload_id, execution_data(2, Address::ZERO)).await;
+        assert_matches!(res, Err(EngineApiError::InvalidPayloadCandidate("timestamp mismatch")));
+
+        // the parent isn't known
+        let res = api.submit_payload_candidate(payload_id, execution_data(1, Address::ZERO)).await;
+        assert_matches!(res, Err(EngineApiError::InvalidPayloadCandidate("unknown parent")));
+        handle.provider.add_header(B256::ZERO, Header::default());
+
+        // the fees are paid to another fee recipient
+        let res = api
+            .submit_payload_candidate(payload_id, execution_data(1, Address::with_last_byte(1)))
+            .await;
+        assert_matches!(
+            res,
+            Err(EngineApiError::InvalidPayloadCandidate("fee recipient mismatch"))
+        );
+
+        let submit = tokio::spawn({
+            let api = api.clone();
+            async move {
+                api.submit_payload_candidate(payload_id, execution_data(1, Address::ZERO)).await
+            }
+        });
+        let Some(BeaconEngineMessage::NewPayload { tx, .. }) = handle.from_api.recv().await else {
+            panic!("expected new payload")
+        };
+        tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Valid))).unwrap();
+        assert!(submit.await.unwrap().unwrap().status.is_valid());
+
+        // the candidate is more valuable than the locally built payload without fees
+        let payload = api.get_payload_v2(payload_id).await.unwrap();
+        assert_eq!(payload.block_value, U256::from(2 * 21_000));
+    }
+
+    #[tokio::test]
+    async fn payload_attributes_hook() {
+        let allowed = Address::with_last_byte(1);
+        let (mut handle, api) = setup_engine_api();
+        let api = api.with_payload_attributes_hook(
+            move |_: &ForkchoiceState, mut attrs: PayloadAttributes| {
+                if attrs.suggested_fee_recipient != Address::ZERO &&
+                    attrs.suggested_fee_recipient != allowed
+                {
+                    return Err(EngineObjectValidationError::rejected_payload_attributes(
+                        std::fmt::Error,
+                    ))
+                }
+                attrs.suggested_fee_recipient = allowed;
+                Ok(attrs)
+            },
+        );
+        let attributes = |suggested_fee_recipient| PayloadAttributes {
+            timestamp: 1,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient,
+            withdrawals: None,
+            parent_beacon_block_root: None,
+        };
+
+        // the hook modifies the attributes before they are forwarded
+        let fcu = tokio::spawn({
+            let api = api.clone();
+            async move {
+                api.fork_choice_updated_v1(
+                    ForkchoiceState::default(),
+                    Some(attributes(Address::ZERO)),
+                )
+                .await
+            }
+        });
+        let Some(BeaconEngineMessage::ForkchoiceUpdated { payload_attrs, tx, .. }) =
+            handle.from_api.recv().await
+        else {
+            panic!("expected forkchoice update")
+        };
+        assert_eq!(payload_attrs.unwrap().suggested_fee_recipient, allowed);
+        tx.send(Ok(OnForkChoiceUpdated::valid(PayloadStatus::from_status(
+            PayloadStatusEnum::Valid,
+        ))))
+        .unwrap();
+        assert!(fcu.await.unwrap().is_ok());
+
+        // rejected attributes are dropped, but the forkchoice update is still applied
+        let fcu = tokio::spawn(async move {
+            api.fork_choice_updated_v1(
+                ForkchoiceState::default(),
+                Some(attributes(Address::with_last_byte(2))),
+            )
+            .await
+        });
+        let Some(BeaconEngineMessage::ForkchoiceUpdated { payload_attrs, tx, .. }) =
+            handle.from_api.recv().await
+        else {
+            panic!("expected forkchoice update")
+        };
+        assert!(payload_attrs.is_none());
+        tx.send(Ok(OnForkChoiceUpdated::valid(PayloadStatus::from_status(
+            PayloadStatusEnum::Valid,
+        ))))
+        .unwrap();
+        assert_matches!(
+            fcu.await.unwrap(),
+            Err(EngineApiError::EngineObjectValidationError(
+                EngineObjectValidationError::RejectedPayloadAttributes(_)
+            ))
+        );
+    }
+
     // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
     mod get_payload_bodies {
         use super::*;
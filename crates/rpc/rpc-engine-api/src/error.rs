@@ -99,6 +99,9 @@ pub enum EngineApiError {
     /// Requests hash provided, but can't be accepted by the API.
     #[error("requests hash cannot be accepted by the API without `--engine.accept-execution-requests-hash` flag")]
     UnexpectedRequestsHash,
+    /// The payload candidate can't be used for its payload job.
+    #[error("invalid payload candidate: {0}")]
+    InvalidPayloadCandidate(&'static str),
     /// Any other rpc error
     #[error("{0}")]
     Other(jsonrpsee_types::ErrorObject<'static>),
@@ -133,7 +136,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 EngineObjectValidationError::Payload(_) |
                 EngineObjectValidationError::InvalidParams(_),
             ) |
-            EngineApiError::UnexpectedRequestsHash => {
+            EngineApiError::UnexpectedRequestsHash |
+            EngineApiError::InvalidPayloadCandidate(_) => {
                 // Note: the data field is not required by the spec, but is also included by other
                 // clients
                 jsonrpsee_types::error::ErrorObject::owned(
@@ -143,7 +147,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 )
             }
             EngineApiError::EngineObjectValidationError(
-                EngineObjectValidationError::PayloadAttributes(_),
+                EngineObjectValidationError::PayloadAttributes(_) |
+                EngineObjectValidationError::RejectedPayloadAttributes(_),
             ) => {
                 // Note: the data field is not required by the spec, but is also included by other
                 // clients
@@ -266,6 +271,14 @@ mod tests {
             )),
         );
 
+        ensure_engine_rpc_error(
+            INVALID_PAYLOAD_ATTRIBUTES,
+            "Invalid payload attributes",
+            EngineApiError::EngineObjectValidationError(
+                EngineObjectValidationError::rejected_payload_attributes(std::fmt::Error),
+            ),
+        );
+
         ensure_engine_rpc_error(
             UNKNOWN_PAYLOAD_CODE,
             "Unknown payload",
@@ -1,8 +1,10 @@
 use std::time::Duration;
 
 use crate::EngineApiError;
-use alloy_rpc_types_engine::{ForkchoiceUpdated, PayloadStatus, PayloadStatusEnum};
-use metrics::{Counter, Gauge, Histogram};
+use alloy_rpc_types_engine::{
+    ClientVersionV1, ForkchoiceUpdated, PayloadStatus, PayloadStatusEnum,
+};
+use metrics::{counter, Counter, Gauge, Histogram};
 use reth_metrics::Metrics;
 
 /// All beacon consensus engine metrics
@@ -16,6 +18,8 @@ pub(crate) struct EngineApiMetrics {
     pub(crate) new_payload_response: NewPayloadStatusResponseMetrics,
     /// Blob-related metrics
     pub(crate) blob_metrics: BlobMetrics,
+    /// Connected consensus client metrics
+    pub(crate) consensus_clients: ConsensusClientMetrics,
 }
 
 /// Beacon consensus engine latency metrics.
@@ -127,6 +131,27 @@ pub(crate) struct BlobMetrics {
     pub(crate) get_blobs_requests_failure_total: Counter,
 }
 
+/// Metrics for the consensus clients that identified themselves via `engine_getClientVersionV1`.
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc")]
+pub(crate) struct ConsensusClientMetrics {
+    /// The number of consensus clients that identified themselves.
+    pub(crate) consensus_clients: Gauge,
+}
+
+impl ConsensusClientMetrics {
+    /// Records a version exchange with the given consensus client.
+    ///
+    /// The exchanges are counted per client code, so that the connected consensus clients can be
+    /// told apart. The free-form name and version reported by the client are not used as labels,
+    /// to keep the number of series bounded.
+    pub(crate) fn on_client_version(&self, client: &ClientVersionV1, known_clients: usize) {
+        self.consensus_clients.set(known_clients as f64);
+        counter!("engine.rpc.client_version_exchanges", "code" => client.code.as_str())
+            .increment(1);
+    }
+}
+
 impl NewPayloadStatusResponseMetrics {
     /// Increment the newPayload counter based on the given rpc result
     pub(crate) fn update_response_metrics(
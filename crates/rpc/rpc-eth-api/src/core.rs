@@ -2,10 +2,10 @@
 //! the `eth_` namespace.
 use crate::{
     helpers::{EthApiSpec, EthBlocks, EthCall, EthFees, EthState, EthTransactions, FullEthApi},
-    RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    FromEthApiError, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
 };
 use alloy_dyn_abi::TypedData;
-use alloy_eips::{eip2930::AccessListResult, BlockId, BlockNumberOrTag};
+use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, B256, B64, U256, U64};
 use alloy_rpc_types_eth::{
@@ -17,7 +17,10 @@ use alloy_rpc_types_eth::{
 use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_rpc_convert::RpcTxReq;
+use reth_rpc_eth_types::AccessListWithGasResult;
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_storage_api::HistoryDataClass;
+use std::time::Duration;
 use tracing::trace;
 
 /// Helper trait, unifies functionality that must be supported to implement all RPC methods for
@@ -251,14 +254,17 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
     /// list is included. Like `eth_estimateGas`, this is an estimation; the list could change
     /// when the transaction is actually mined. Adding an accessList to your transaction does
     /// not necessary result in lower gas usage compared to a transaction without an access
-    /// list.
+    /// list, so the gas consumed without the generated access list is returned as well.
+    ///
+    /// The access list can be created at any historical block, on top of the given state
+    /// overrides.
     #[method(name = "createAccessList")]
     async fn create_access_list(
         &self,
         request: TxReq,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
-    ) -> RpcResult<AccessListResult>;
+    ) -> RpcResult<AccessListWithGasResult>;
 
     /// Generates and returns an estimate of how much gas is necessary to allow the transaction to
     /// complete.
@@ -341,9 +347,10 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
 
     /// Sends a signed transaction and awaits the transaction receipt.
     ///
-    /// This will return a timeout error if the transaction isn't included within some time period.
+    /// This will return a timeout error if the transaction isn't included within the timeout in
+    /// milliseconds. The timeout is capped at, and defaults to, the timeout configured on the node.
     #[method(name = "sendRawTransactionSync")]
-    async fn send_raw_transaction_sync(&self, bytes: Bytes) -> RpcResult<R>;
+    async fn send_raw_transaction_sync(&self, bytes: Bytes, timeout: Option<U64>) -> RpcResult<R>;
 
     /// Returns an Ethereum specific signature with: sign(keccak256("\x19Ethereum Signed Message:\n"
     /// + len(message) + message))).
@@ -437,7 +444,12 @@ where
         full: bool,
     ) -> RpcResult<Option<RpcBlock<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?hash, ?full, "Serving eth_getBlockByHash");
-        Ok(EthBlocks::rpc_block(self, hash.into(), full).await?)
+        let block = EthBlocks::rpc_block(self, hash.into(), full).await?;
+        if block.is_none() {
+            self.ensure_history_available(HistoryDataClass::Bodies, hash.into())
+                .map_err(T::Error::from_eth_err)?;
+        }
+        Ok(block)
     }
 
     /// Handler for: `eth_getBlockByNumber`
@@ -447,7 +459,12 @@ where
         full: bool,
     ) -> RpcResult<Option<RpcBlock<T::NetworkTypes>>> {
         trace!(target: "rpc::eth", ?number, ?full, "Serving eth_getBlockByNumber");
-        Ok(EthBlocks::rpc_block(self, number.into(), full).await?)
+        let block = EthBlocks::rpc_block(self, number.into(), full).await?;
+        if block.is_none() {
+            self.ensure_history_available(HistoryDataClass::Bodies, number.into())
+                .map_err(T::Error::from_eth_err)?;
+        }
+        Ok(block)
     }
 
     /// Handler for: `eth_getBlockTransactionCountByHash`
@@ -496,7 +513,12 @@ where
         block_id: BlockId,
     ) -> RpcResult<Option<Vec<RpcReceipt<T::NetworkTypes>>>> {
         trace!(target: "rpc::eth", ?block_id, "Serving eth_getBlockReceipts");
-        Ok(EthBlocks::block_receipts(self, block_id).await?)
+        let receipts = EthBlocks::block_receipts(self, block_id).await?;
+        if receipts.is_none() {
+            self.ensure_history_available(HistoryDataClass::Receipts, block_id)
+                .map_err(T::Error::from_eth_err)?;
+        }
+        Ok(receipts)
     }
 
     /// Handler for: `eth_getUncleByBlockHashAndIndex`
@@ -699,7 +721,7 @@ where
         request: RpcTxReq<T::NetworkTypes>,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
-    ) -> RpcResult<AccessListResult> {
+    ) -> RpcResult<AccessListWithGasResult> {
         trace!(target: "rpc::eth", ?request, ?block_number, ?state_override, "Serving eth_createAccessList");
         Ok(EthCall::create_access_list_at(self, request, block_number, state_override).await?)
     }
@@ -811,9 +833,14 @@ where
     }
 
     /// Handler for: `eth_sendRawTransactionSync`
-    async fn send_raw_transaction_sync(&self, tx: Bytes) -> RpcResult<RpcReceipt<T::NetworkTypes>> {
-        trace!(target: "rpc::eth", ?tx, "Serving eth_sendRawTransactionSync");
-        Ok(EthTransactions::send_raw_transaction_sync(self, tx).await?)
+    async fn send_raw_transaction_sync(
+        &self,
+        tx: Bytes,
+        timeout: Option<U64>,
+    ) -> RpcResult<RpcReceipt<T::NetworkTypes>> {
+        trace!(target: "rpc::eth", ?tx, ?timeout, "Serving eth_sendRawTransactionSync");
+        let timeout = timeout.map(|timeout| Duration::from_millis(timeout.to()));
+        Ok(EthTransactions::send_raw_transaction_sync(self, tx, timeout).await?)
     }
 
     /// Handler for: `eth_sign`
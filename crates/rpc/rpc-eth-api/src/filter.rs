@@ -37,7 +37,10 @@ pub trait EthFilterApi<T: RpcObject> {
     async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool>;
 
     /// Returns logs matching given filter object.
-    #[method(name = "getLogs")]
+    ///
+    /// The maximum block range can be overridden per request with a
+    /// [`MaxBlocksPerFilterOverride`] extension.
+    #[method(name = "getLogs", with_extensions)]
     async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
 }
 
@@ -48,6 +51,10 @@ pub struct QueryLimits {
     pub max_blocks_per_filter: Option<u64>,
     /// Maximum number of logs that can be returned in a response
     pub max_logs_per_response: Option<usize>,
+    /// Maximum number of addresses in a filter
+    pub max_addresses_per_filter: Option<usize>,
+    /// Maximum number of topics in each topic position of a filter
+    pub max_topics_per_position: Option<usize>,
 }
 
 impl QueryLimits {
@@ -57,6 +64,13 @@ impl QueryLimits {
     }
 }
 
+/// Request extension that overrides the maximum number of blocks that could be scanned by an
+/// `eth_getLogs` request.
+///
+/// `None` removes the limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxBlocksPerFilterOverride(pub Option<u64>);
+
 /// Rpc Interface for poll-based ethereum filter API, implementing only the `eth_getLogs` method.
 /// Used for the engine API, with possibility to specify [`QueryLimits`].
 pub trait EngineEthFilter: Send + Sync + 'static {
@@ -37,7 +37,7 @@ use reth_rpc_eth_types::{
     cache::db::{StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
     error::{api::FromEvmHalt, ensure_success, FromEthApiError},
     simulate::{self, EthSimulateError},
-    EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
+    AccessListWithGasResult, EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
 };
 use reth_storage_api::{BlockIdReader, ProviderTx};
 use revm::{
@@ -346,14 +346,14 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
         }
     }
 
-    /// Creates [`AccessListResult`] for the [`RpcTxReq`] at the given
+    /// Creates [`AccessListWithGasResult`] for the [`RpcTxReq`] at the given
     /// [`BlockId`], or latest block.
     fn create_access_list_at(
         &self,
         request: RpcTxReq<<Self::RpcConvert as RpcConvert>::Network>,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
-    ) -> impl Future<Output = Result<AccessListResult, Self::Error>> + Send
+    ) -> impl Future<Output = Result<AccessListWithGasResult, Self::Error>> + Send
     where
         Self: Trace,
     {
@@ -368,15 +368,17 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
         }
     }
 
-    /// Creates [`AccessListResult`] for the [`RpcTxReq`] at the given
+    /// Creates [`AccessListWithGasResult`] for the [`RpcTxReq`] at the given
     /// [`BlockId`].
+    ///
+    /// The state overrides are applied on top of the state at the given block.
     fn create_access_list_with(
         &self,
         mut evm_env: EvmEnvFor<Self::Evm>,
         at: BlockId,
         request: RpcTxReq<<Self::RpcConvert as RpcConvert>::Network>,
         state_override: Option<StateOverride>,
-    ) -> Result<AccessListResult, Self::Error>
+    ) -> Result<AccessListWithGasResult, Self::Error>
     where
         Self: Trace,
     {
@@ -416,15 +418,24 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
             self.inspect(&mut db, evm_env, tx_env, &mut inspector)?;
         let access_list = inspector.into_access_list();
         tx_env.set_access_list(access_list.clone());
+
+        // the inspected execution didn't include the generated access list yet
+        let gas_used_without_access_list = Some(U256::from(result.result.gas_used()));
         match result.result {
             ExecutionResult::Halt { reason, gas_used } => {
                 let error =
                     Some(Self::Error::from_evm_halt(reason, tx_env.gas_limit()).to_string());
-                return Ok(AccessListResult { access_list, gas_used: U256::from(gas_used), error })
+                return Ok(AccessListWithGasResult {
+                    inner: AccessListResult { access_list, gas_used: U256::from(gas_used), error },
+                    gas_used_without_access_list,
+                })
             }
             ExecutionResult::Revert { output, gas_used } => {
                 let error = Some(RevertError::new(output).to_string());
-                return Ok(AccessListResult { access_list, gas_used: U256::from(gas_used), error })
+                return Ok(AccessListWithGasResult {
+                    inner: AccessListResult { access_list, gas_used: U256::from(gas_used), error },
+                    gas_used_without_access_list,
+                })
             }
             ExecutionResult::Success { .. } => {}
         };
@@ -446,7 +457,7 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
             }
         };
 
-        Ok(res)
+        Ok(AccessListWithGasResult { inner: res, gas_used_without_access_list })
     }
 }
 
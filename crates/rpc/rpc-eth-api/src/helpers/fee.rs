@@ -169,7 +169,7 @@ pub trait EthFees:
             } else {
                 // read the requested header range
                 let headers = self.provider()
-                    .sealed_headers_range(start_block..=end_block)
+                    .sealed_headers_range_vectored(start_block..=end_block)
                     .map_err(Self::Error::from_eth_err)?;
                 if headers.len() != block_count as usize {
                     return Err(EthApiError::InvalidBlockRange.into())
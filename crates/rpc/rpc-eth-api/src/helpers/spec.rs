@@ -1,13 +1,17 @@
 //! Loads chain metadata.
 
+use alloy_eips::BlockId;
 use alloy_primitives::{Address, U256, U64};
 use alloy_rpc_types_eth::{Stage, SyncInfo, SyncStatus};
 use futures::Future;
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthereumHardforks};
-use reth_errors::{RethError, RethResult};
+use reth_errors::{ProviderError, RethError, RethResult};
 use reth_network_api::NetworkInfo;
 use reth_rpc_convert::{RpcTxReq, RpcTypes};
-use reth_storage_api::{BlockNumReader, StageCheckpointReader, TransactionsProvider};
+use reth_rpc_eth_types::EthApiError;
+use reth_storage_api::{
+    BlockIdReader, HistoryDataClass, StageCheckpointReader, TransactionsProvider,
+};
 
 use crate::{helpers::EthSigner, RpcNodeCore};
 
@@ -18,7 +22,7 @@ use crate::{helpers::EthSigner, RpcNodeCore};
 pub trait EthApiSpec:
     RpcNodeCore<
     Provider: ChainSpecProvider<ChainSpec: EthereumHardforks>
-                  + BlockNumReader
+                  + BlockIdReader
                   + StageCheckpointReader,
     Network: NetworkInfo,
 >
@@ -64,13 +68,16 @@ pub trait EthApiSpec:
     }
 
     /// Returns the [`SyncStatus`] of the network
+    ///
+    /// While syncing, the stages are followed by the earliest available block of every
+    /// [`HistoryDataClass`], named `Earliest<class>`, e.g. `EarliestReceipts`.
     fn sync_status(&self) -> RethResult<SyncStatus> {
         let status = if self.is_syncing() {
             let current_block = U256::from(
                 self.provider().chain_info().map(|info| info.best_number).unwrap_or_default(),
             );
 
-            let stages = self
+            let mut stages: Vec<_> = self
                 .provider()
                 .get_all_checkpoints()
                 .unwrap_or_default()
@@ -78,6 +85,15 @@ pub trait EthApiSpec:
                 .map(|(name, checkpoint)| Stage { name, block: checkpoint.block_number })
                 .collect();
 
+            if let Ok(availability) = self.provider().history_availability() {
+                stages.extend(availability.ranges().filter_map(|(data, range)| {
+                    range.map(|range| Stage {
+                        name: format!("Earliest{data}"),
+                        block: *range.start(),
+                    })
+                }));
+            }
+
             SyncStatus::Info(Box::new(SyncInfo {
                 starting_block: self.starting_block(),
                 current_block,
@@ -91,6 +107,33 @@ pub trait EthApiSpec:
         };
         Ok(status)
     }
+
+    /// Returns [`EthApiError::HistoryUnavailable`] if the given data of the block existed, but was
+    /// pruned or expired.
+    ///
+    /// This is used to distinguish pruned data from blocks that don't exist.
+    fn ensure_history_available(
+        &self,
+        data: HistoryDataClass,
+        block_id: BlockId,
+    ) -> Result<(), EthApiError> {
+        let Some(block) = self.provider().block_number_for_id(block_id)? else { return Ok(()) };
+        let availability = match self.provider().history_availability() {
+            Ok(availability) => availability,
+            // the availability is unknown, treat the data as missing
+            Err(ProviderError::UnsupportedProvider) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        if availability.is_pruned(data, block) {
+            return Err(EthApiError::HistoryUnavailable {
+                data,
+                block,
+                earliest_available: availability.range(data).map(|range| *range.start()),
+            })
+        }
+        Ok(())
+    }
 }
 
 /// A handle to [`EthSigner`]s with its generics set from [`EthApiSpec`].
@@ -25,6 +25,7 @@ use reth_rpc_eth_types::{
     utils::binary_search, EthApiError, EthApiError::TransactionConfirmationTimeout, SignError,
     TransactionSource,
 };
+use reth_rpc_server_types::constants::DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT;
 use reth_storage_api::{
     BlockNumReader, BlockReaderIdExt, ProviderBlock, ProviderReceipt, ProviderTx, ReceiptProvider,
     TransactionsProvider,
@@ -32,7 +33,7 @@ use reth_storage_api::{
 use reth_transaction_pool::{
     AddedTransactionOutcome, PoolTransaction, TransactionOrigin, TransactionPool,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 /// Transaction related functions for the [`EthApiServer`](crate::EthApiServer) trait in
 /// the `eth_` namespace.
@@ -70,22 +71,34 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         tx: Bytes,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send;
 
+    /// Returns the maximum duration [`EthTransactions::send_raw_transaction_sync`] waits for the
+    /// transaction to be included in a block.
+    ///
+    /// Defaults to [`DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT`].
+    fn send_raw_transaction_sync_timeout(&self) -> Duration {
+        DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT
+    }
+
     /// Decodes and recovers the transaction and submits it to the pool.
     ///
-    /// And awaits the receipt.
+    /// And awaits the receipt. The requested timeout is capped at
+    /// [`EthTransactions::send_raw_transaction_sync_timeout`], which is also used if no timeout is
+    /// requested.
     fn send_raw_transaction_sync(
         &self,
         tx: Bytes,
+        timeout: Option<Duration>,
     ) -> impl Future<Output = Result<RpcReceipt<Self::NetworkTypes>, Self::Error>> + Send
     where
         Self: LoadReceipt + 'static,
     {
         let this = self.clone();
+        let timeout = capped_sync_timeout(timeout, self.send_raw_transaction_sync_timeout());
         async move {
-            let hash = EthTransactions::send_raw_transaction(&this, tx).await?;
+            // subscribe before submitting, so that the block including the transaction isn't missed
             let mut stream = this.provider().canonical_state_stream();
-            const TIMEOUT_DURATION: tokio::time::Duration = tokio::time::Duration::from_secs(30);
-            tokio::time::timeout(TIMEOUT_DURATION, async {
+            let hash = EthTransactions::send_raw_transaction(&this, tx).await?;
+            tokio::time::timeout(timeout, async {
                 while let Some(notification) = stream.next().await {
                     let chain = notification.committed();
                     for block in chain.blocks_iter() {
@@ -98,14 +111,14 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
                 }
                 Err(Self::Error::from_eth_err(TransactionConfirmationTimeout {
                     hash,
-                    duration: TIMEOUT_DURATION,
+                    duration: timeout,
                 }))
             })
             .await
             .unwrap_or_else(|_elapsed| {
                 Err(Self::Error::from_eth_err(TransactionConfirmationTimeout {
                     hash,
-                    duration: TIMEOUT_DURATION,
+                    duration: timeout,
                 }))
             })
         }
@@ -622,3 +635,34 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
         }
     }
 }
+
+/// Returns the timeout [`EthTransactions::send_raw_transaction_sync`] waits for, given the
+/// optionally requested timeout and the node-level maximum.
+fn capped_sync_timeout(requested: Option<Duration>, max_timeout: Duration) -> Duration {
+    requested.map_or(max_timeout, |timeout| timeout.min(max_timeout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_timeout_defaults_to_node_limit() {
+        let max = Duration::from_secs(10);
+        assert_eq!(capped_sync_timeout(None, max), max);
+    }
+
+    #[test]
+    fn sync_timeout_below_node_limit_is_kept() {
+        let max = Duration::from_secs(10);
+        let requested = Duration::from_secs(3);
+        assert_eq!(capped_sync_timeout(Some(requested), max), requested);
+    }
+
+    #[test]
+    fn sync_timeout_is_capped_by_node_limit() {
+        let max = Duration::from_secs(10);
+        assert_eq!(capped_sync_timeout(Some(Duration::from_secs(60)), max), max);
+        assert_eq!(capped_sync_timeout(Some(Duration::MAX), max), max);
+    }
+}
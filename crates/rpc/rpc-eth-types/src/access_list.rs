@@ -0,0 +1,61 @@
+//! Response type of `eth_createAccessList`.
+
+use alloy_eips::eip2930::AccessListResult;
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+
+/// Response of `eth_createAccessList`.
+///
+/// Extends the [`AccessListResult`] with the gas used by the call without the generated access
+/// list, so callers can evaluate whether including the list lowers the gas used.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListWithGasResult {
+    /// The generated access list and the gas used with it.
+    #[serde(flatten)]
+    pub inner: AccessListResult,
+    /// The gas used by the call without the generated access list, but with the access list of the
+    /// request if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used_without_access_list: Option<U256>,
+}
+
+impl From<AccessListResult> for AccessListWithGasResult {
+    fn from(inner: AccessListResult) -> Self {
+        Self { inner, gas_used_without_access_list: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2930::{AccessList, AccessListItem};
+    use alloy_primitives::Address;
+
+    #[test]
+    fn serde_access_list_with_gas_result() {
+        let result = AccessListWithGasResult {
+            inner: AccessListResult {
+                access_list: AccessList(vec![AccessListItem {
+                    address: Address::with_last_byte(1),
+                    storage_keys: vec![],
+                }]),
+                gas_used: U256::from(21_000),
+                error: None,
+            },
+            gas_used_without_access_list: Some(U256::from(23_600)),
+        };
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["gasUsed"], "0x5208");
+        assert_eq!(json["gasUsedWithoutAccessList"], "0x5c30");
+        assert_eq!(serde_json::from_value::<AccessListWithGasResult>(json).unwrap(), result);
+
+        // Responses without the field are still valid access list results.
+        let json = serde_json::to_value(&result.inner).unwrap();
+        assert_eq!(
+            serde_json::from_value::<AccessListWithGasResult>(json).unwrap().inner,
+            result.inner
+        );
+    }
+}
@@ -6,9 +6,11 @@ use crate::{
     EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
 };
 use reth_rpc_server_types::constants::{
-    default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
-    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
-    DEFAULT_PROOF_PERMITS,
+    default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_ADDRESSES_PER_FILTER,
+    DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS,
+    DEFAULT_MAX_TOPICS_PER_POSITION, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+    DEFAULT_PENDING_TX_SUBSCRIPTION_BUFFER, DEFAULT_PROOF_PERMITS,
+    DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT,
 };
 use serde::{Deserialize, Serialize};
 
@@ -32,6 +34,10 @@ pub struct EthConfig {
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of addresses in a logs filter.
+    pub max_addresses_per_filter: usize,
+    /// Maximum number of topics in each topic position of a logs filter.
+    pub max_topics_per_position: usize,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [`RPC_DEFAULT_GAS_CAP`]
@@ -45,6 +51,19 @@ pub struct EthConfig {
     pub fee_history_cache: FeeHistoryCacheConfig,
     /// The maximum number of getproof calls that can be executed concurrently.
     pub proof_permits: usize,
+    /// Maximum number of subscription notification bytes per second sent to a single connection.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_subscription_bytes_per_sec: Option<u64>,
+    /// Whether subscriptions are closed if the buffer of their connection is full, instead of
+    /// waiting for the client to catch up.
+    pub close_slow_subscriptions: bool,
+    /// Maximum number of transactions buffered for a `newPendingTransactions` subscription with
+    /// full transaction objects before the oldest ones are dropped.
+    pub pending_tx_subscription_buffer: usize,
+    /// Maximum duration `eth_sendRawTransactionSync` waits for the transaction to be included in
+    /// a block.
+    pub send_raw_transaction_sync_timeout: Duration,
 }
 
 impl EthConfig {
@@ -53,8 +72,19 @@ impl EthConfig {
         EthFilterConfig::default()
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
+            .max_addresses_per_filter(self.max_addresses_per_filter)
+            .max_topics_per_position(self.max_topics_per_position)
             .stale_filter_ttl(self.stale_filter_ttl)
     }
+
+    /// Returns the pubsub config for the `eth_subscribe` handler.
+    pub fn pubsub_config(&self) -> EthPubSubConfig {
+        EthPubSubConfig {
+            max_bytes_per_sec_per_connection: self.max_subscription_bytes_per_sec,
+            close_slow_subscriptions: self.close_slow_subscriptions,
+            pending_tx_buffer: self.pending_tx_subscription_buffer,
+        }
+    }
 }
 
 impl Default for EthConfig {
@@ -67,11 +97,17 @@ impl Default for EthConfig {
             max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_addresses_per_filter: DEFAULT_MAX_ADDRESSES_PER_FILTER,
+            max_topics_per_position: DEFAULT_MAX_TOPICS_PER_POSITION,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             rpc_max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
+            max_subscription_bytes_per_sec: None,
+            close_slow_subscriptions: false,
+            pending_tx_subscription_buffer: DEFAULT_PENDING_TX_SUBSCRIPTION_BUFFER,
+            send_raw_transaction_sync_timeout: DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT,
         }
     }
 }
@@ -113,6 +149,18 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of addresses in a logs filter
+    pub const fn max_addresses_per_filter(mut self, max_addresses: usize) -> Self {
+        self.max_addresses_per_filter = max_addresses;
+        self
+    }
+
+    /// Configures the maximum number of topics in each topic position of a logs filter
+    pub const fn max_topics_per_position(mut self, max_topics: usize) -> Self {
+        self.max_topics_per_position = max_topics;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub const fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
@@ -136,6 +184,84 @@ impl EthConfig {
         self.proof_permits = permits;
         self
     }
+
+    /// Configures the maximum number of subscription notification bytes per second sent to a
+    /// single connection.
+    pub const fn max_subscription_bytes_per_sec(mut self, max_bytes: Option<u64>) -> Self {
+        self.max_subscription_bytes_per_sec = max_bytes;
+        self
+    }
+
+    /// Configures whether subscriptions are closed if the buffer of their connection is full.
+    pub const fn close_slow_subscriptions(mut self, close: bool) -> Self {
+        self.close_slow_subscriptions = close;
+        self
+    }
+
+    /// Configures the maximum number of transactions buffered for a `newPendingTransactions`
+    /// subscription with full transaction objects.
+    pub const fn pending_tx_subscription_buffer(mut self, max_buffered: usize) -> Self {
+        self.pending_tx_subscription_buffer = max_buffered;
+        self
+    }
+
+    /// Configures the maximum duration `eth_sendRawTransactionSync` waits for the transaction to
+    /// be included in a block.
+    pub const fn send_raw_transaction_sync_timeout(mut self, timeout: Duration) -> Self {
+        self.send_raw_transaction_sync_timeout = timeout;
+        self
+    }
+}
+
+/// Config for the `eth_subscribe` handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthPubSubConfig {
+    /// Maximum number of notification bytes per second sent to a single connection across all of
+    /// its subscriptions.
+    ///
+    /// Subscriptions that would exceed the limit are closed. If `None` then no limit is enforced.
+    pub max_bytes_per_sec_per_connection: Option<u64>,
+    /// Whether subscriptions are closed if the buffer of their connection is full.
+    ///
+    /// By default, subscriptions wait for the client to catch up.
+    pub close_slow_subscriptions: bool,
+    /// Maximum number of transactions buffered for a `newPendingTransactions` subscription with
+    /// full transaction objects.
+    ///
+    /// Instead of being closed when the connection buffer is full, these subscriptions buffer
+    /// transactions and drop the oldest ones once this limit is reached.
+    pub pending_tx_buffer: usize,
+}
+
+impl Default for EthPubSubConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes_per_sec_per_connection: None,
+            close_slow_subscriptions: false,
+            pending_tx_buffer: DEFAULT_PENDING_TX_SUBSCRIPTION_BUFFER,
+        }
+    }
+}
+
+impl EthPubSubConfig {
+    /// Sets the maximum number of notification bytes per second sent to a single connection.
+    pub const fn max_bytes_per_sec_per_connection(mut self, max_bytes: u64) -> Self {
+        self.max_bytes_per_sec_per_connection = Some(max_bytes);
+        self
+    }
+
+    /// Sets whether subscriptions are closed if the buffer of their connection is full.
+    pub const fn close_slow_subscriptions(mut self, close: bool) -> Self {
+        self.close_slow_subscriptions = close;
+        self
+    }
+
+    /// Sets the maximum number of transactions buffered for a full pending transactions
+    /// subscription.
+    pub const fn pending_tx_buffer(mut self, max_buffered: usize) -> Self {
+        self.pending_tx_buffer = max_buffered;
+        self
+    }
 }
 
 /// Config for the filter
@@ -149,6 +275,14 @@ pub struct EthFilterConfig {
     ///
     /// If `None` then no limit is enforced.
     pub max_logs_per_response: Option<usize>,
+    /// Maximum number of addresses in a logs filter.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_addresses_per_filter: Option<usize>,
+    /// Maximum number of topics in each topic position of a logs filter.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_topics_per_position: Option<usize>,
     /// How long a filter remains valid after the last poll.
     ///
     /// A filter is considered stale if it has not been polled for longer than this duration and
@@ -170,6 +304,18 @@ impl EthFilterConfig {
         self
     }
 
+    /// Sets the maximum number of addresses in a logs filter.
+    pub const fn max_addresses_per_filter(mut self, num: usize) -> Self {
+        self.max_addresses_per_filter = Some(num);
+        self
+    }
+
+    /// Sets the maximum number of topics in each topic position of a logs filter.
+    pub const fn max_topics_per_position(mut self, num: usize) -> Self {
+        self.max_topics_per_position = Some(num);
+        self
+    }
+
     /// Sets how long a filter remains valid after the last poll before it will be removed.
     pub const fn stale_filter_ttl(mut self, duration: Duration) -> Self {
         self.stale_filter_ttl = duration;
@@ -182,6 +328,8 @@ impl Default for EthFilterConfig {
         Self {
             max_blocks_per_filter: None,
             max_logs_per_response: None,
+            max_addresses_per_filter: None,
+            max_topics_per_position: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
         }
@@ -0,0 +1,166 @@
+//! Structured `data` payloads of RPC errors.
+
+use alloy_eips::BlockId;
+use alloy_primitives::{B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// Machine-readable context of an RPC error that is returned as the `data` field of the error
+/// object.
+///
+/// The `reason` field identifies the failure, so clients can branch on it without matching error
+/// messages. The remaining fields depend on the reason, for example:
+///
+/// ```json
+/// { "reason": "prunedHistory", "block": 100 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum RpcErrorData {
+    /// The requested historical data was pruned.
+    PrunedHistory {
+        /// The requested block whose data was pruned, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block: Option<u64>,
+        /// The class of the pruned data, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        data: Option<String>,
+        /// The earliest block the data is still available for, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        earliest_available: Option<u64>,
+    },
+    /// The requested block doesn't exist.
+    BlockNotFound {
+        /// The requested block.
+        block: BlockId,
+    },
+    /// The receipts of the requested block don't exist.
+    ReceiptsNotFound {
+        /// The requested block.
+        block: BlockId,
+    },
+    /// A block of the requested range doesn't exist.
+    BlockRangeNotFound {
+        /// The first block of the range.
+        start: BlockId,
+        /// The last block of the range.
+        end: BlockId,
+    },
+    /// The requested transaction doesn't exist.
+    TransactionNotFound,
+    /// The requested block is older than the configured proof window.
+    ProofWindowExceeded,
+    /// The execution exceeded the configured timeout.
+    ExecutionTimeout {
+        /// The configured timeout in milliseconds.
+        timeout_ms: u64,
+    },
+    /// The transaction was added to the pool, but not included in time.
+    ConfirmationTimeout {
+        /// The hash of the transaction.
+        hash: B256,
+        /// The time waited for the transaction in milliseconds.
+        timeout_ms: u64,
+    },
+    /// The nonce of the transaction is lower than the nonce of the sender.
+    NonceTooLow {
+        /// The nonce of the transaction.
+        tx_nonce: u64,
+        /// The next nonce of the sender.
+        state_nonce: u64,
+    },
+    /// The balance of the sender doesn't cover the cost of the transaction.
+    InsufficientFunds {
+        /// The cost of the transaction.
+        cost: U256,
+        /// The balance of the sender.
+        balance: U256,
+    },
+    /// The gas estimate exceeds the highest gas limit the call may use, which is capped by the
+    /// RPC gas cap, the block gas limit and the allowance of the caller.
+    GasCapExceeded {
+        /// The highest gas limit the call was executed with.
+        gas_limit: u64,
+    },
+    /// A logs query exceeds a configured limit.
+    QueryLimitExceeded {
+        /// The exceeded limit.
+        limit: QueryLimit,
+        /// The configured maximum.
+        max: u64,
+        /// The requested amount.
+        requested: u64,
+        /// The topic position whose topics exceed the limit.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        position: Option<usize>,
+    },
+}
+
+/// The limits of a logs query, see [`RpcErrorData::QueryLimitExceeded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueryLimit {
+    /// The maximum number of blocks in the queried range.
+    BlockRange,
+    /// The maximum number of addresses in the filter.
+    Addresses,
+    /// The maximum number of topics in each topic position of the filter.
+    TopicsPerPosition,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_rpc_error_data() {
+        let data =
+            RpcErrorData::PrunedHistory { block: Some(100), data: None, earliest_available: None };
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(json, serde_json::json!({ "reason": "prunedHistory", "block": 100 }));
+        assert_eq!(serde_json::from_value::<RpcErrorData>(json).unwrap(), data);
+
+        let data = RpcErrorData::PrunedHistory {
+            block: Some(100),
+            data: Some("Receipts".to_string()),
+            earliest_available: Some(200),
+        };
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "reason": "prunedHistory",
+                "block": 100,
+                "data": "Receipts",
+                "earliestAvailable": 200
+            })
+        );
+        assert_eq!(serde_json::from_value::<RpcErrorData>(json).unwrap(), data);
+
+        let data = RpcErrorData::GasCapExceeded { gas_limit: 50_000_000 };
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(json, serde_json::json!({ "reason": "gasCapExceeded", "gasLimit": 50_000_000 }));
+        assert_eq!(serde_json::from_value::<RpcErrorData>(json).unwrap(), data);
+
+        let data = RpcErrorData::QueryLimitExceeded {
+            limit: QueryLimit::TopicsPerPosition,
+            max: 10,
+            requested: 11,
+            position: Some(1),
+        };
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "reason": "queryLimitExceeded",
+                "limit": "topicsPerPosition",
+                "max": 10,
+                "requested": 11,
+                "position": 1
+            })
+        );
+        assert_eq!(serde_json::from_value::<RpcErrorData>(json).unwrap(), data);
+
+        let json = serde_json::to_value(RpcErrorData::TransactionNotFound).unwrap();
+        assert_eq!(json, serde_json::json!({ "reason": "transactionNotFound" }));
+    }
+}
@@ -1,20 +1,24 @@
 //! Implementation specific Errors for the `eth_` namespace.
 
 pub mod api;
+pub mod data;
 use crate::error::api::FromEvmHalt;
 use alloy_eips::BlockId;
 use alloy_evm::{call::CallError, overrides::StateOverrideError};
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
 use alloy_rpc_types_eth::{error::EthRpcErrorCode, request::TransactionInputError, BlockError};
 use alloy_sol_types::{ContractError, RevertReason};
 pub use api::{AsEthApiError, FromEthApiError, FromEvmError, IntoEthApiError};
 use core::time::Duration;
+pub use data::{QueryLimit, RpcErrorData};
 use reth_errors::{BlockExecutionError, RethError};
 use reth_primitives_traits::transaction::{error::InvalidTransactionError, signed::RecoveryError};
 use reth_rpc_convert::{CallFeesError, EthTxEnvError, TransactionConversionError};
 use reth_rpc_server_types::result::{
     block_id_to_str, internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code,
+    rpc_error_with_data,
 };
+use reth_storage_api::HistoryDataClass;
 use reth_transaction_pool::error::{
     Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     PoolError, PoolErrorKind, PoolTransactionError,
@@ -26,6 +30,10 @@ use revm_inspectors::tracing::MuxError;
 use std::convert::Infallible;
 use tracing::error;
 
+/// Error code of [`EthApiError::PrunedHistoryUnavailable`], see also
+/// <https://eips.ethereum.org/EIPS/eip-4444>
+pub const PRUNED_HISTORY_UNAVAILABLE_CODE: i32 = 4444;
+
 /// A trait to convert an error to an RPC error.
 pub trait ToRpcError: core::error::Error + Send + Sync + 'static {
     /// Converts the error to a JSON-RPC error object.
@@ -70,6 +78,20 @@ pub enum EthApiError {
     /// See also <https://eips.ethereum.org/EIPS/eip-4444>
     #[error("pruned history unavailable")]
     PrunedHistoryUnavailable,
+    /// Thrown when the state at the requested block is not available because it has been pruned.
+    #[error("state at block #{0} is pruned")]
+    StateAtBlockPruned(BlockNumber),
+    /// Thrown when the requested data of a block is not available because it has been pruned or
+    /// expired.
+    #[error("{data} of block #{block} are pruned")]
+    HistoryUnavailable {
+        /// The requested data.
+        data: HistoryDataClass,
+        /// The requested block.
+        block: BlockNumber,
+        /// The earliest block the data is still available for, if any.
+        earliest_available: Option<BlockNumber>,
+    },
     /// Receipts not found for block hash/number/tag
     #[error("receipts not found")]
     ReceiptsNotFound(BlockId),
@@ -224,7 +246,6 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::InvalidTransactionSignature |
             EthApiError::EmptyRawTransactionData |
             EthApiError::InvalidBlockRange |
-            EthApiError::ExceedsMaxProofWindow |
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
@@ -239,44 +260,84 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::InvalidBlockData(_) |
             EthApiError::Internal(_) |
             EthApiError::EvmCustom(_) => internal_rpc_err(error.to_string()),
-            EthApiError::UnknownBlockOrTxIndex | EthApiError::TransactionNotFound => {
+            EthApiError::ExceedsMaxProofWindow => rpc_error_with_data(
+                jsonrpsee_types::error::INVALID_PARAMS_CODE,
+                error.to_string(),
+                RpcErrorData::ProofWindowExceeded,
+            ),
+            EthApiError::UnknownBlockOrTxIndex => {
                 rpc_error_with_code(EthRpcErrorCode::ResourceNotFound.code(), error.to_string())
             }
+            EthApiError::TransactionNotFound => rpc_error_with_data(
+                EthRpcErrorCode::ResourceNotFound.code(),
+                error.to_string(),
+                RpcErrorData::TransactionNotFound,
+            ),
             // TODO(onbjerg): We rewrite the error message here because op-node does string matching
             // on the error message.
             //
             // Until https://github.com/ethereum-optimism/optimism/pull/11759 is released, this must be kept around.
-            EthApiError::HeaderNotFound(id) => rpc_error_with_code(
+            EthApiError::HeaderNotFound(id) => rpc_error_with_data(
                 EthRpcErrorCode::ResourceNotFound.code(),
                 format!("block not found: {}", block_id_to_str(id)),
+                RpcErrorData::BlockNotFound { block: id },
             ),
-            EthApiError::ReceiptsNotFound(id) => rpc_error_with_code(
+            EthApiError::ReceiptsNotFound(id) => rpc_error_with_data(
                 EthRpcErrorCode::ResourceNotFound.code(),
                 format!("{error}: {}", block_id_to_str(id)),
+                RpcErrorData::ReceiptsNotFound { block: id },
             ),
-            EthApiError::HeaderRangeNotFound(start_id, end_id) => rpc_error_with_code(
+            EthApiError::HeaderRangeNotFound(start_id, end_id) => rpc_error_with_data(
                 EthRpcErrorCode::ResourceNotFound.code(),
                 format!(
                     "{error}: start block: {}, end block: {}",
                     block_id_to_str(start_id),
                     block_id_to_str(end_id),
                 ),
+                RpcErrorData::BlockRangeNotFound { start: start_id, end: end_id },
+            ),
+            EthApiError::TransactionConfirmationTimeout { hash, duration } => rpc_error_with_data(
+                EthRpcErrorCode::TransactionRejected.code(),
+                error.to_string(),
+                RpcErrorData::ConfirmationTimeout { hash, timeout_ms: duration.as_millis() as u64 },
             ),
-            err @ EthApiError::TransactionConfirmationTimeout { .. } => {
-                rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), err.to_string())
-            }
             EthApiError::Unsupported(msg) => internal_rpc_err(msg),
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
-            err @ EthApiError::ExecutionTimedOut(_) => rpc_error_with_code(
+            EthApiError::ExecutionTimedOut(timeout) => rpc_error_with_data(
                 jsonrpsee_types::error::CALL_EXECUTION_FAILED_CODE,
-                err.to_string(),
+                error.to_string(),
+                RpcErrorData::ExecutionTimeout { timeout_ms: timeout.as_millis() as u64 },
             ),
             err @ (EthApiError::InternalBlockingTaskError | EthApiError::InternalEthError) => {
                 internal_rpc_err(err.to_string())
             }
             err @ EthApiError::TransactionInputError(_) => invalid_params_rpc_err(err.to_string()),
-            EthApiError::PrunedHistoryUnavailable => rpc_error_with_code(4444, error.to_string()),
+            EthApiError::PrunedHistoryUnavailable => rpc_error_with_data(
+                PRUNED_HISTORY_UNAVAILABLE_CODE,
+                error.to_string(),
+                RpcErrorData::PrunedHistory { block: None, data: None, earliest_available: None },
+            ),
+            EthApiError::StateAtBlockPruned(block) => rpc_error_with_data(
+                PRUNED_HISTORY_UNAVAILABLE_CODE,
+                error.to_string(),
+                RpcErrorData::PrunedHistory {
+                    block: Some(block),
+                    data: None,
+                    earliest_available: None,
+                },
+            ),
+            EthApiError::HistoryUnavailable { data, block, earliest_available } => {
+                rpc_error_with_data(
+                    PRUNED_HISTORY_UNAVAILABLE_CODE,
+                    error.to_string(),
+                    RpcErrorData::PrunedHistory {
+                        block: Some(block),
+                        data: Some(data.to_string()),
+                        earliest_available,
+                    },
+                )
+            }
             EthApiError::Other(err) => err.to_rpc_error(),
             EthApiError::MuxTracerError(msg) => internal_rpc_err(msg.to_string()),
         }
@@ -388,6 +449,14 @@ impl From<reth_errors::ProviderError> for EthApiError {
             ProviderError::TotalDifficultyNotFound(num) => Self::HeaderNotFound(num.into()),
             ProviderError::FinalizedBlockNotFound => Self::HeaderNotFound(BlockId::finalized()),
             ProviderError::SafeBlockNotFound => Self::HeaderNotFound(BlockId::safe()),
+            ProviderError::StateAtBlockPruned(block) => Self::StateAtBlockPruned(block),
+            ProviderError::ReceiptsPruned { block, earliest_available } => {
+                Self::HistoryUnavailable {
+                    data: HistoryDataClass::Receipts,
+                    block,
+                    earliest_available,
+                }
+            }
             err => Self::Internal(err.into()),
         }
     }
@@ -657,6 +726,23 @@ impl From<RpcInvalidTransactionError> for jsonrpsee_types::error::ErrorObject<'s
                 )
             }
             RpcInvalidTransactionError::Other(err) => err.to_rpc_error(),
+            RpcInvalidTransactionError::NonceTooLow { tx, state } => rpc_error_with_data(
+                err.error_code(),
+                err.to_string(),
+                RpcErrorData::NonceTooLow { tx_nonce: tx, state_nonce: state },
+            ),
+            RpcInvalidTransactionError::InsufficientFunds { cost, balance } => rpc_error_with_data(
+                err.error_code(),
+                err.to_string(),
+                RpcErrorData::InsufficientFunds { cost, balance },
+            ),
+            RpcInvalidTransactionError::GasRequiredExceedsAllowance { gas_limit } => {
+                rpc_error_with_data(
+                    err.error_code(),
+                    err.to_string(),
+                    RpcErrorData::GasCapExceeded { gas_limit },
+                )
+            }
             err => rpc_err(err.error_code(), err.to_string(), None),
         }
     }
@@ -817,6 +903,9 @@ pub enum RpcPoolError {
     /// When the transaction pool is full
     #[error("txpool is full")]
     TxPoolOverflow,
+    /// When the validation budget for transactions submitted over RPC is exhausted
+    #[error("transaction validation budget exceeded")]
+    ValidationBudgetExceeded,
     /// When the replacement transaction is underpriced
     #[error("replacement transaction underpriced")]
     ReplaceUnderpriced,
@@ -871,7 +960,7 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
     fn from(error: RpcPoolError) -> Self {
         match error {
             RpcPoolError::Invalid(err) => err.into(),
-            RpcPoolError::TxPoolOverflow => {
+            RpcPoolError::TxPoolOverflow | RpcPoolError::ValidationBudgetExceeded => {
                 rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), error.to_string())
             }
             RpcPoolError::AlreadyKnown |
@@ -906,6 +995,7 @@ impl From<PoolError> for RpcPoolError {
             PoolErrorKind::InvalidTransaction(err) => err.into(),
             PoolErrorKind::Other(err) => Self::Other(err),
             PoolErrorKind::AlreadyImported => Self::AlreadyKnown,
+            PoolErrorKind::ValidationBudgetExceeded => Self::ValidationBudgetExceeded,
             PoolErrorKind::ExistingConflictingTransactionType(_, _) => Self::AddressAlreadyReserved,
         }
     }
@@ -1032,6 +1122,57 @@ mod tests {
         assert_eq!(err.message(), "block not found: finalized");
     }
 
+    #[test]
+    fn error_data() {
+        fn data(err: impl Into<jsonrpsee_types::error::ErrorObject<'static>>) -> RpcErrorData {
+            let err = err.into();
+            serde_json::from_str(err.data().unwrap().get()).unwrap()
+        }
+
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::from(reth_errors::ProviderError::StateAtBlockPruned(100)).into();
+        assert_eq!(err.code(), PRUNED_HISTORY_UNAVAILABLE_CODE);
+        assert_eq!(err.message(), "state at block #100 is pruned");
+        assert_eq!(
+            data(err),
+            RpcErrorData::PrunedHistory { block: Some(100), data: None, earliest_available: None }
+        );
+
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::from(reth_errors::ProviderError::ReceiptsPruned {
+                block: 100,
+                earliest_available: Some(200),
+            })
+            .into();
+        assert_eq!(err.code(), PRUNED_HISTORY_UNAVAILABLE_CODE);
+        assert_eq!(err.message(), "Receipts of block #100 are pruned");
+        assert_eq!(
+            data(err),
+            RpcErrorData::PrunedHistory {
+                block: Some(100),
+                data: Some("Receipts".to_string()),
+                earliest_available: Some(200),
+            }
+        );
+
+        assert_eq!(
+            data(EthApiError::HeaderNotFound(BlockId::number(100))),
+            RpcErrorData::BlockNotFound { block: BlockId::number(100) }
+        );
+        assert_eq!(
+            data(EthApiError::ExecutionTimedOut(Duration::from_secs(10))),
+            RpcErrorData::ExecutionTimeout { timeout_ms: 10_000 }
+        );
+        assert_eq!(
+            data(RpcInvalidTransactionError::GasRequiredExceedsAllowance { gas_limit: 50_000_000 }),
+            RpcErrorData::GasCapExceeded { gas_limit: 50_000_000 }
+        );
+        assert_eq!(
+            data(RpcInvalidTransactionError::NonceTooLow { tx: 1, state: 2 }),
+            RpcErrorData::NonceTooLow { tx_nonce: 1, state_nonce: 2 }
+        );
+    }
+
     #[test]
     fn revert_err_display() {
         let revert = Revert::from("test_revert_reason");
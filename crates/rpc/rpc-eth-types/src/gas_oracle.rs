@@ -2,7 +2,10 @@
 //! previous blocks.
 
 use super::{EthApiError, EthResult, EthStateCache, RpcInvalidTransactionError};
-use alloy_consensus::{constants::GWEI_TO_WEI, BlockHeader, Transaction, TxReceipt};
+use alloy_consensus::{
+    constants::{EMPTY_ROOT_HASH, GWEI_TO_WEI},
+    BlockHeader, Transaction, TxReceipt,
+};
 use alloy_eips::BlockNumberOrTag;
 use alloy_primitives::{B256, U256};
 use alloy_rpc_types_eth::BlockId;
@@ -18,7 +21,10 @@ use reth_rpc_server_types::{
 use reth_storage_api::{BlockReaderIdExt, NodePrimitivesProvider};
 use schnellru::{ByLength, LruMap};
 use serde::{Deserialize, Serialize};
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
 use tokio::sync::Mutex;
 use tracing::warn;
 
@@ -56,6 +62,10 @@ pub struct GasPriceOracleConfig {
 
     /// The minimum gas price, under which the sample will be ignored
     pub ignore_price: Option<U256>,
+
+    /// The algorithm that suggests a tip from the sampled gas prices
+    #[serde(default)]
+    pub algorithm: TipSuggestionAlgorithm,
 }
 
 impl Default for GasPriceOracleConfig {
@@ -69,7 +79,160 @@ impl Default for GasPriceOracleConfig {
             default: None,
             max_price: Some(DEFAULT_MAX_GAS_PRICE),
             ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
+            algorithm: TipSuggestionAlgorithm::default(),
+        }
+    }
+}
+
+/// The built-in algorithms that suggest a tip from the gas prices sampled by the
+/// [`GasPriceOracle`].
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum TipSuggestionAlgorithm {
+    /// Suggests the configured percentile of all sampled gas prices, see
+    /// [`PercentileTipSuggester`].
+    #[default]
+    Percentile,
+    /// Suggests an exponentially weighted moving average of the configured percentile of each
+    /// sampled block, see [`EwmaTipSuggester`].
+    Ewma {
+        /// The weight of the newest block in percent.
+        smoothing: u32,
+    },
+    /// Suggests the configured percentile of all sampled gas prices, but never less than the
+    /// floor, see [`MinimumFloorTipSuggester`].
+    MinimumFloor {
+        /// The minimum suggested tip.
+        floor: U256,
+    },
+}
+
+impl TipSuggestionAlgorithm {
+    /// Returns the [`TipSuggester`] that implements the algorithm.
+    pub fn suggester(&self, percentile: u32) -> Arc<dyn TipSuggester> {
+        let percentile = PercentileTipSuggester::new(percentile);
+        match *self {
+            Self::Percentile => Arc::new(percentile),
+            Self::Ewma { smoothing } => Arc::new(EwmaTipSuggester::new(percentile, smoothing)),
+            Self::MinimumFloor { floor } => {
+                Arc::new(MinimumFloorTipSuggester::new(percentile, floor))
+            }
+        }
+    }
+}
+
+/// The gas prices sampled by the [`GasPriceOracle`] that a [`TipSuggester`] bases its suggestion
+/// on.
+#[derive(Debug, Clone, Copy)]
+pub struct TipSamples<'a> {
+    /// The lowest effective tips of each sampled block, newest block first. Blocks without
+    /// eligible transactions have no samples.
+    pub blocks: &'a [Vec<U256>],
+    /// The previously suggested tip.
+    pub last_price: U256,
+}
+
+/// An algorithm that suggests a tip from the gas prices of recent blocks.
+///
+/// The [`GasPriceOracle`] constrains the suggestion to the configured maximum price.
+pub trait TipSuggester: Debug + Send + Sync + 'static {
+    /// Suggests a tip from the sampled gas prices.
+    fn suggest(&self, samples: TipSamples<'_>) -> U256;
+}
+
+/// Suggests the configured percentile of all sampled gas prices.
+///
+/// Blocks without samples count as a single sample of the previously suggested tip. This is the
+/// default algorithm, and matches the gas price oracle of geth.
+#[derive(Debug, Clone, Copy)]
+pub struct PercentileTipSuggester {
+    percentile: u32,
+}
+
+impl PercentileTipSuggester {
+    /// Creates a new suggester for the given percentile, which is capped at 100.
+    pub const fn new(percentile: u32) -> Self {
+        Self { percentile: if percentile > 100 { 100 } else { percentile } }
+    }
+
+    /// Returns the percentile of the given gas prices, or `None` if there are none.
+    fn percentile_of(&self, mut prices: Vec<U256>) -> Option<U256> {
+        if prices.is_empty() {
+            return None
+        }
+        prices.sort_unstable();
+        Some(prices[(prices.len() - 1) * self.percentile as usize / 100])
+    }
+}
+
+impl TipSuggester for PercentileTipSuggester {
+    fn suggest(&self, samples: TipSamples<'_>) -> U256 {
+        let mut prices = Vec::new();
+        for block in samples.blocks {
+            if block.is_empty() {
+                prices.push(samples.last_price);
+            } else {
+                prices.extend_from_slice(block);
+            }
         }
+        self.percentile_of(prices).unwrap_or(samples.last_price)
+    }
+}
+
+/// Suggests an exponentially weighted moving average of the configured percentile of each sampled
+/// block, from the oldest to the newest block.
+///
+/// Blocks without samples are skipped, so that the suggestion of chains with little traffic isn't
+/// dragged towards the previous suggestion, and isn't thrown off by a single expensive block.
+#[derive(Debug, Clone, Copy)]
+pub struct EwmaTipSuggester {
+    percentile: PercentileTipSuggester,
+    smoothing: u32,
+}
+
+impl EwmaTipSuggester {
+    /// Creates a new suggester, where `smoothing` is the weight of the newest block in percent.
+    pub fn new(percentile: PercentileTipSuggester, smoothing: u32) -> Self {
+        Self { percentile, smoothing: smoothing.clamp(1, 100) }
+    }
+}
+
+impl TipSuggester for EwmaTipSuggester {
+    fn suggest(&self, samples: TipSamples<'_>) -> U256 {
+        let smoothing = U256::from(self.smoothing);
+        let hundred = U256::from(100);
+        samples
+            .blocks
+            .iter()
+            .rev()
+            .filter_map(|block| self.percentile.percentile_of(block.clone()))
+            .reduce(|average, price| {
+                (price * smoothing + average * (hundred - smoothing)) / hundred
+            })
+            .unwrap_or(samples.last_price)
+    }
+}
+
+/// Suggests the configured percentile of all sampled gas prices, but never less than the floor.
+///
+/// Useful for chains where the sampled gas prices are too low to get a transaction included in
+/// time.
+#[derive(Debug, Clone, Copy)]
+pub struct MinimumFloorTipSuggester {
+    percentile: PercentileTipSuggester,
+    floor: U256,
+}
+
+impl MinimumFloorTipSuggester {
+    /// Creates a new suggester with the given minimum suggested tip.
+    pub const fn new(percentile: PercentileTipSuggester, floor: U256) -> Self {
+        Self { percentile, floor }
+    }
+}
+
+impl TipSuggester for MinimumFloorTipSuggester {
+    fn suggest(&self, samples: TipSamples<'_>) -> U256 {
+        self.percentile.suggest(samples).max(self.floor)
     }
 }
 
@@ -87,6 +250,8 @@ where
     oracle_config: GasPriceOracleConfig,
     /// The price under which the sample will be ignored.
     ignore_price: Option<u128>,
+    /// Suggests a tip from the sampled gas prices
+    tip_suggester: Arc<dyn TipSuggester>,
     /// Stores the latest calculated price and its block hash and Cache stores the lowest effective
     /// tip values of recent blocks
     inner: Mutex<GasPriceOracleInner>,
@@ -118,7 +283,15 @@ where
             ))),
         });
 
-        Self { provider, oracle_config, cache, ignore_price, inner }
+        let tip_suggester = oracle_config.algorithm.suggester(oracle_config.percentile);
+
+        Self { provider, oracle_config, cache, ignore_price, tip_suggester, inner }
+    }
+
+    /// Replaces the configured [`TipSuggestionAlgorithm`] with a custom [`TipSuggester`].
+    pub fn with_tip_suggester(mut self, tip_suggester: Arc<dyn TipSuggester>) -> Self {
+        self.tip_suggester = tip_suggester;
+        self
     }
 
     /// Returns the configuration of the gas price oracle.
@@ -126,7 +299,8 @@ where
         &self.oracle_config
     }
 
-    /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
+    /// Suggests a gas price estimate based on recent blocks, using the configured
+    /// [`TipSuggester`].
     pub async fn suggest_tip_cap(&self) -> EthResult<U256> {
         let header = self
             .provider
@@ -146,8 +320,11 @@ where
         // we only return more than check_block blocks' worth of prices if one or more return empty
         // transactions
         let mut current_hash = header.hash();
-        let mut results = Vec::new();
+        let mut current_number = header.number();
+        let mut blocks = Vec::new();
         let mut populated_blocks = 0;
+        // headers of the next blocks to check, read in bulk, oldest first
+        let mut headers = Vec::new();
 
         // we only check a maximum of 2 * max_block_history, or the number of blocks in the chain
         let max_blocks = if self.oracle_config.max_block_history * 2 > header.number() {
@@ -162,23 +339,35 @@ where
                 if let Some(vals) = inner.lowest_effective_tip_cache.get(&current_hash) {
                     vals.to_owned()
                 } else {
-                    // Otherwise we fetch it using get_block_values
-                    let (parent_hash, block_values) = self
-                        .get_block_values(current_hash, SAMPLE_NUMBER)
-                        .await?
-                        .ok_or(EthApiError::HeaderNotFound(current_hash.into()))?;
+                    if headers.is_empty() {
+                        let start = current_number
+                            .saturating_sub(self.oracle_config.blocks.max(1) as u64 - 1);
+                        headers =
+                            self.provider.sealed_headers_range_vectored(start..=current_number)?;
+                    }
+                    let header = headers.pop().filter(|header| header.hash() == current_hash);
+
+                    let (parent_hash, block_values) = match header {
+                        // blocks without transactions have no values, no need to load them
+                        Some(header) if header.transactions_root() == EMPTY_ROOT_HASH => {
+                            (header.parent_hash(), Vec::new())
+                        }
+                        // Otherwise we fetch it using get_block_values
+                        _ => self
+                            .get_block_values(current_hash, SAMPLE_NUMBER)
+                            .await?
+                            .ok_or(EthApiError::HeaderNotFound(current_hash.into()))?,
+                    };
                     inner
                         .lowest_effective_tip_cache
                         .insert(current_hash, (parent_hash, block_values.clone()));
                     (parent_hash, block_values)
                 };
 
-            if block_values.is_empty() {
-                results.push(U256::from(inner.last_price.price));
-            } else {
-                results.extend(block_values);
+            if !block_values.is_empty() {
                 populated_blocks += 1;
             }
+            blocks.push(block_values);
 
             // break when we have enough populated blocks
             if populated_blocks >= self.oracle_config.blocks {
@@ -186,17 +375,14 @@ where
             }
 
             current_hash = parent_hash;
+            current_number = current_number.saturating_sub(1);
+            // the headers are only read for blocks that aren't cached
+            headers.retain(|header| header.number() <= current_number);
         }
 
-        // sort results then take the configured percentile result
-        let mut price = if results.is_empty() {
-            inner.last_price.price
-        } else {
-            results.sort_unstable();
-            *results.get((results.len() - 1) * self.oracle_config.percentile as usize / 100).expect(
-                "gas price index is a percent of nonzero array length, so a value always exists",
-            )
-        };
+        let mut price = self
+            .tip_suggester
+            .suggest(TipSamples { blocks: &blocks, last_price: inner.last_price.price });
 
         // constrain to the max price
         if let Some(max_price) = self.oracle_config.max_price {
@@ -441,4 +627,39 @@ mod tests {
     fn ignore_price_sanity() {
         assert_eq!(DEFAULT_IGNORE_GAS_PRICE, U256::from(2u64));
     }
+
+    #[test]
+    fn tip_suggestion_algorithms() {
+        let gwei = |n: u64| U256::from(n * GWEI_TO_WEI);
+        // newest block first, the second block has no samples
+        let blocks = vec![vec![gwei(4), gwei(6)], vec![], vec![gwei(1), gwei(2)]];
+        let samples = TipSamples { blocks: &blocks, last_price: gwei(3) };
+
+        let percentile = PercentileTipSuggester::new(50);
+        assert_eq!(percentile.suggest(samples), gwei(3));
+        assert_eq!(percentile.suggest(TipSamples { blocks: &[], last_price: gwei(3) }), gwei(3));
+
+        // (1 * 50% + 4 * 50%)
+        let ewma = EwmaTipSuggester::new(percentile, 50);
+        assert_eq!(ewma.suggest(samples), gwei(5) / U256::from(2));
+
+        let floor = MinimumFloorTipSuggester::new(percentile, gwei(10));
+        assert_eq!(floor.suggest(samples), gwei(10));
+    }
+
+    #[test]
+    fn tip_suggestion_algorithm_serde() {
+        let config: GasPriceOracleConfig = serde_json::from_str(
+            r#"{"blocks":20,"percentile":60,"maxHeaderHistory":1024,"maxBlockHistory":1024,"maxRewardPercentileCount":16384,"default":null,"maxPrice":null,"ignorePrice":null,"algorithm":{"type":"ewma","smoothing":25}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.algorithm, TipSuggestionAlgorithm::Ewma { smoothing: 25 });
+
+        // the algorithm is optional
+        let config: GasPriceOracleConfig = serde_json::from_str(
+            r#"{"blocks":20,"percentile":60,"maxHeaderHistory":1024,"maxBlockHistory":1024,"maxRewardPercentileCount":16384,"default":null,"maxPrice":null,"ignorePrice":null}"#,
+        )
+        .unwrap();
+        assert_eq!(config.algorithm, TipSuggestionAlgorithm::Percentile);
+    }
 }
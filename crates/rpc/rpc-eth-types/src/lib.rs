@@ -8,6 +8,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod access_list;
 pub mod builder;
 pub mod cache;
 pub mod error;
@@ -21,7 +22,8 @@ pub mod simulate;
 pub mod transaction;
 pub mod utils;
 
-pub use builder::config::{EthConfig, EthFilterConfig};
+pub use access_list::AccessListWithGasResult;
+pub use builder::config::{EthConfig, EthFilterConfig, EthPubSubConfig};
 pub use cache::{
     config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
     EthStateCache,
@@ -29,7 +31,9 @@ pub use cache::{
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
 pub use gas_oracle::{
-    GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, RPC_DEFAULT_GAS_CAP,
+    EwmaTipSuggester, GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult,
+    MinimumFloorTipSuggester, PercentileTipSuggester, TipSamples, TipSuggester,
+    TipSuggestionAlgorithm, RPC_DEFAULT_GAS_CAP,
 };
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
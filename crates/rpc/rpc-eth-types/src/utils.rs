@@ -1,7 +1,9 @@
 //! Commonly used code snippets
 
 use super::{EthApiError, EthResult};
-use reth_primitives_traits::{Recovered, SignedTransaction};
+use reth_primitives_traits::{
+    transaction::sender_cache::SenderRecoveryCache, Recovered, SignedTransaction,
+};
 use std::future::Future;
 
 /// Recovers a [`SignedTransaction`] from an enveloped encoded byte stream.
@@ -18,8 +20,10 @@ pub fn recover_raw_transaction<T: SignedTransaction>(mut data: &[u8]) -> EthResu
     let transaction =
         T::decode_2718(&mut data).map_err(|_| EthApiError::FailedToDecodeSignedTransaction)?;
 
-    SignedTransaction::try_into_recovered(transaction)
-        .or(Err(EthApiError::InvalidTransactionSignature))
+    let signer = SenderRecoveryCache::global()
+        .recover_signer(&transaction)
+        .map_err(|_| EthApiError::InvalidTransactionSignature)?;
+    Ok(transaction.with_signer(signer))
 }
 
 /// Performs a binary search within a given block range to find the desired block number.
@@ -0,0 +1,129 @@
+use crate::{JwtError, JwtSecret};
+use parking_lot::RwLock;
+use std::{
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The JWT secrets that are accepted by a [`JwtAuthValidator`](crate::JwtAuthValidator).
+///
+/// Besides the primary secret, a secondary secret is accepted until its grace period ends. This
+/// allows rotating the secret without updating the secret of all clients at the same time.
+///
+/// The secrets are shared by all clones, so they can be rotated while the server is running.
+#[derive(Debug, Clone)]
+pub struct JwtSecrets {
+    inner: Arc<RwLock<JwtSecretsInner>>,
+}
+
+#[derive(Debug)]
+struct JwtSecretsInner {
+    primary: JwtSecret,
+    /// The secondary secret and the instant until which it is accepted.
+    secondary: Option<(JwtSecret, Instant)>,
+}
+
+impl JwtSecrets {
+    /// Creates a new instance that only accepts the given secret.
+    pub fn new(primary: JwtSecret) -> Self {
+        Self { inner: Arc::new(RwLock::new(JwtSecretsInner { primary, secondary: None })) }
+    }
+
+    /// Accepts the given secondary secret for the grace period, starting now.
+    pub fn with_secondary(self, secondary: JwtSecret, grace_period: Duration) -> Self {
+        self.inner.write().secondary = Some((secondary, Instant::now() + grace_period));
+        self
+    }
+
+    /// Returns the primary secret.
+    pub fn primary(&self) -> JwtSecret {
+        self.inner.read().primary
+    }
+
+    /// Returns the secondary secret, if it is still accepted.
+    pub fn secondary(&self) -> Option<JwtSecret> {
+        let (secret, deadline) = self.inner.read().secondary?;
+        (Instant::now() < deadline).then_some(secret)
+    }
+
+    /// Replaces the primary secret.
+    ///
+    /// The previous primary secret becomes the secondary secret and is accepted for the grace
+    /// period, starting now. Returns `false` if the secret is already the primary secret.
+    pub fn rotate(&self, primary: JwtSecret, grace_period: Duration) -> bool {
+        let mut inner = self.inner.write();
+        if inner.primary == primary {
+            return false
+        }
+        let previous = std::mem::replace(&mut inner.primary, primary);
+        inner.secondary = Some((previous, Instant::now() + grace_period));
+        true
+    }
+
+    /// Reads the primary secret from the file and rotates to it, see [`JwtSecrets::rotate`].
+    pub fn reload_from_file(&self, path: &Path, grace_period: Duration) -> Result<bool, JwtError> {
+        let secret = JwtSecret::from_file(path)?;
+        Ok(self.rotate(secret, grace_period))
+    }
+
+    /// Validates the JWT against the primary secret and, if it is still accepted, the secondary
+    /// secret.
+    ///
+    /// Returns the error of the primary secret if the JWT is rejected by both secrets.
+    pub fn validate(&self, jwt: &str) -> Result<(), JwtError> {
+        let err = match self.primary().validate(jwt) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        match self.secondary() {
+            Some(secondary) if secondary.validate(jwt).is_ok() => Ok(()),
+            _ => Err(err),
+        }
+    }
+}
+
+impl From<JwtSecret> for JwtSecrets {
+    fn from(primary: JwtSecret) -> Self {
+        Self::new(primary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Claims;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn jwt(secret: &JwtSecret) -> String {
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        secret.encode(&Claims { iat, exp: Some(10000000000) }).unwrap()
+    }
+
+    #[test]
+    fn accepts_secondary_during_grace_period() {
+        let (old, new) = (JwtSecret::random(), JwtSecret::random());
+
+        let secrets = JwtSecrets::new(new).with_secondary(old, Duration::from_secs(60));
+        assert!(secrets.validate(&jwt(&new)).is_ok());
+        assert!(secrets.validate(&jwt(&old)).is_ok());
+        assert!(secrets.validate(&jwt(&JwtSecret::random())).is_err());
+
+        let secrets = JwtSecrets::new(new).with_secondary(old, Duration::ZERO);
+        assert!(secrets.validate(&jwt(&new)).is_ok());
+        assert!(secrets.validate(&jwt(&old)).is_err());
+    }
+
+    #[test]
+    fn rotate_keeps_previous_secret() {
+        let (old, new) = (JwtSecret::random(), JwtSecret::random());
+        let secrets = JwtSecrets::new(old);
+        let shared = secrets.clone();
+
+        assert!(secrets.rotate(new, Duration::from_secs(60)));
+        assert!(!secrets.rotate(new, Duration::from_secs(60)));
+        assert_eq!(shared.primary(), new);
+        assert_eq!(shared.secondary(), Some(old));
+        assert!(shared.validate(&jwt(&old)).is_ok());
+    }
+}
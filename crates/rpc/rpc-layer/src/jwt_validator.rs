@@ -1,4 +1,4 @@
-use crate::{AuthValidator, JwtError, JwtSecret};
+use crate::{AuthValidator, JwtError, JwtSecret, JwtSecrets};
 use http::{header, HeaderMap, Response, StatusCode};
 use jsonrpsee_http_client::{HttpBody, HttpResponse};
 use tracing::error;
@@ -8,22 +8,28 @@ use tracing::error;
 /// by implementing the [`AuthValidator`] trait.
 #[derive(Debug, Clone)]
 pub struct JwtAuthValidator {
-    secret: JwtSecret,
+    secrets: JwtSecrets,
 }
 
 impl JwtAuthValidator {
     /// Creates a new instance of [`JwtAuthValidator`].
     /// Validation logics are implemented by the `secret`
     /// argument (see [`JwtSecret`]).
-    pub const fn new(secret: JwtSecret) -> Self {
-        Self { secret }
+    pub fn new(secret: JwtSecret) -> Self {
+        Self::with_secrets(JwtSecrets::new(secret))
+    }
+
+    /// Creates a new instance of [`JwtAuthValidator`] that accepts tokens signed with any of the
+    /// `secrets` (see [`JwtSecrets`]).
+    pub const fn with_secrets(secrets: JwtSecrets) -> Self {
+        Self { secrets }
     }
 }
 
 impl AuthValidator for JwtAuthValidator {
     fn validate(&self, headers: &HeaderMap) -> Result<(), HttpResponse> {
         match get_bearer(headers) {
-            Some(jwt) => match self.secret.validate(&jwt) {
+            Some(jwt) => match self.secrets.validate(&jwt) {
                 Ok(_) => Ok(()),
                 Err(e) => {
                     error!(target: "engine::jwt-validator", "Invalid JWT: {e}");
@@ -14,6 +14,7 @@ use jsonrpsee_http_client::HttpResponse;
 mod auth_client_layer;
 mod auth_layer;
 mod compression_layer;
+mod jwt_secrets;
 mod jwt_validator;
 
 pub use auth_layer::{AuthService, ResponseFuture};
@@ -24,6 +25,7 @@ pub use alloy_rpc_types_engine::{Claims, JwtError, JwtSecret};
 
 pub use auth_client_layer::{secret_to_bearer_header, AuthClientLayer, AuthClientService};
 pub use auth_layer::AuthLayer;
+pub use jwt_secrets::JwtSecrets;
 pub use jwt_validator::JwtAuthValidator;
 
 /// General purpose trait to validate Http Authorization headers. It's supposed to be integrated as
@@ -1,4 +1,4 @@
-use std::cmp::max;
+use std::{cmp::max, time::Duration};
 
 /// The default port for the http server
 pub const DEFAULT_HTTP_RPC_PORT: u16 = 8545;
@@ -15,6 +15,12 @@ pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum number of addresses in a logs filter.
+pub const DEFAULT_MAX_ADDRESSES_PER_FILTER: usize = 1_000;
+
+/// The default maximum number of topics in each topic position of a logs filter.
+pub const DEFAULT_MAX_TOPICS_PER_POSITION: usize = 1_000;
+
 /// The default maximum number of blocks for `trace_filter` requests.
 pub const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 100;
 
@@ -29,6 +35,14 @@ pub fn default_max_tracing_requests() -> usize {
         .map_or(25, |cpus| max(cpus.get().saturating_sub(RESERVED), RESERVED))
 }
 
+/// The default maximum number of `debug_` and `trace_` calls that are queued when the limit of
+/// concurrent calls is reached.
+pub const DEFAULT_MAX_QUEUED_TRACE_CALLS: usize = 1024;
+
+/// The default maximum number of transactions buffered for a `newPendingTransactions`
+/// subscription with full transaction objects that can't keep up.
+pub const DEFAULT_PENDING_TX_SUBSCRIPTION_BUFFER: usize = 1024;
+
 /// The default number of getproof calls we are allowing to run concurrently.
 pub const DEFAULT_PROOF_PERMITS: usize = 25;
 
@@ -54,6 +68,10 @@ pub const DEFAULT_MAX_SIMULATE_BLOCKS: u64 = 256;
 /// The default eth historical proof window.
 pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
 
+/// The default maximum duration `eth_sendRawTransactionSync` waits for the transaction to be
+/// included in a block.
+pub const DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// The default eth tx fee cap is 1 ETH
 pub const DEFAULT_TX_FEE_CAP_WEI: u128 = 1_000_000_000_000_000_000u128;
 
@@ -80,6 +98,10 @@ pub mod gas_oracle {
     /// The percentile of gas prices to use for the estimate
     pub const DEFAULT_GAS_PRICE_PERCENTILE: u32 = 60;
 
+    /// The weight of the newest block in percent, for the exponentially weighted moving average
+    /// of gas prices
+    pub const DEFAULT_GAS_PRICE_EWMA_SMOOTHING: u32 = 20;
+
     /// Maximum transaction priority fee (or gas price before London Fork) to be recommended by the
     /// gas price oracle
     pub const DEFAULT_MAX_GAS_PRICE: U256 = U256::from_limbs([500_000_000_000u64, 0, 0, 0]);
@@ -136,6 +136,15 @@ pub fn rpc_error_with_code(
     rpc_err(code, msg, None)
 }
 
+/// Constructs a JSON-RPC error with code, message and a structured `data` object.
+pub fn rpc_error_with_data(
+    code: i32,
+    msg: impl Into<String>,
+    data: impl serde::Serialize,
+) -> jsonrpsee_types::error::ErrorObject<'static> {
+    jsonrpsee_types::error::ErrorObject::owned(code, msg.into(), Some(data))
+}
+
 /// Constructs a JSON-RPC error, consisting of `code`, `message` and optional `data`.
 pub fn rpc_err(
     code: i32,
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{num::NonZeroU64, sync::Arc};
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
@@ -8,11 +8,16 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
-use reth_network_api::{NetworkInfo, Peers};
+use reth_engine_primitives::{ConsensusClientInfo, ConsensusClients};
+use reth_network_api::{
+    DiscoveryTable, NetworkInfo, OutboundBandwidthStatus, OutboundBandwidthWeights, Peers,
+    StaticPeerStatus,
+};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_node_api::{BeaconConsensusEngineHandle, PayloadTypes};
+use reth_rpc_api::{AdminApiServer, AdminEngineApiServer};
+use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 
 /// `admin` API implementation.
 ///
@@ -182,6 +187,42 @@ where
         })
     }
 
+    /// Handler for `admin_discoveryTable`
+    async fn discovery_table(&self) -> RpcResult<DiscoveryTable> {
+        self.network.discovery_table().await.to_rpc_result()
+    }
+
+    /// Handler for `admin_staticPeers`
+    async fn static_peers(&self) -> RpcResult<Vec<StaticPeerStatus>> {
+        self.network.get_static_peers().await.to_rpc_result()
+    }
+
+    /// Handler for `admin_outboundBandwidth`
+    async fn outbound_bandwidth(&self) -> RpcResult<OutboundBandwidthStatus> {
+        self.network.outbound_bandwidth().await.to_rpc_result()
+    }
+
+    /// Handler for `admin_setOutboundBandwidth`
+    fn set_outbound_bandwidth(&self, max_bytes_per_sec: Option<NonZeroU64>) -> RpcResult<bool> {
+        self.network.set_outbound_bandwidth(max_bytes_per_sec);
+        Ok(true)
+    }
+
+    /// Handler for `admin_setOutboundBandwidthWeights`
+    fn set_outbound_bandwidth_weights(
+        &self,
+        block_propagation: u32,
+        transaction_gossip: u32,
+        historical_serving: u32,
+    ) -> RpcResult<bool> {
+        self.network.set_outbound_bandwidth_weights(OutboundBandwidthWeights {
+            block_propagation,
+            transaction_gossip,
+            historical_serving,
+        });
+        Ok(true)
+    }
+
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
@@ -196,3 +237,41 @@ impl<N, ChainSpec> std::fmt::Debug for AdminApi<N, ChainSpec> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// `admin` API implementation for managing the engine.
+#[derive(Debug)]
+pub struct AdminEngineApi<Payload: PayloadTypes> {
+    /// Handle to the beacon consensus engine.
+    engine: BeaconConsensusEngineHandle<Payload>,
+    /// The consensus clients that identified themselves to the engine API.
+    consensus_clients: ConsensusClients,
+}
+
+impl<Payload: PayloadTypes> AdminEngineApi<Payload> {
+    /// Creates a new instance of `AdminEngineApi`.
+    pub fn new(engine: BeaconConsensusEngineHandle<Payload>) -> Self {
+        Self { engine, consensus_clients: Default::default() }
+    }
+
+    /// Configures the consensus clients that are returned by `admin_consensusClients`.
+    pub fn with_consensus_clients(mut self, consensus_clients: ConsensusClients) -> Self {
+        self.consensus_clients = consensus_clients;
+        self
+    }
+}
+
+#[async_trait]
+impl<Payload> AdminEngineApiServer for AdminEngineApi<Payload>
+where
+    Payload: PayloadTypes,
+{
+    /// Handler for `admin_clearInvalidBlocks`
+    async fn clear_invalid_blocks(&self) -> RpcResult<usize> {
+        self.engine.clear_invalid_blocks().await.map_err(|_| internal_rpc_err("engine unavailable"))
+    }
+
+    /// Handler for `admin_consensusClients`
+    async fn consensus_clients(&self) -> RpcResult<Vec<ConsensusClientInfo>> {
+        Ok(self.consensus_clients.clients())
+    }
+}
@@ -13,7 +13,9 @@ use alloy_rpc_types_trace::geth::{
     NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::{Stream, StreamExt};
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
+use jsonrpsee_types::ErrorObject;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
 use reth_primitives_traits::{
@@ -31,10 +33,14 @@ use reth_rpc_eth_api::{
     EthApiTypes, FromEthApiError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
-    StateProofProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
+    BlockHashReader, BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock,
+    ReceiptProviderIdExt, StateProofProvider, StateProviderFactory, StateRootProvider,
+    TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
@@ -45,6 +51,13 @@ use revm_inspectors::tracing::{
 use std::sync::Arc;
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// The maximum number of blocks that are traced concurrently for a `debug_traceChain`
+/// subscription.
+const TRACE_CHAIN_CONCURRENCY: usize = 4;
+
+/// The maximum number of blocks a `debug_traceChain` subscription can trace.
+const MAX_TRACE_CHAIN_BLOCKS: u64 = 1_000;
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
@@ -199,6 +212,54 @@ where
         self.trace_block(block, evm_env, opts).await
     }
 
+    /// Returns a stream that replays all blocks after `start_exclusive` up to and including
+    /// `end_inclusive` and yields the traces of each block in ascending order.
+    ///
+    /// A bounded number of blocks is traced concurrently. No block is traced before the stream is
+    /// polled, and dropping the stream cancels all pending traces.
+    pub fn debug_trace_chain(
+        &self,
+        start_exclusive: u64,
+        end_inclusive: u64,
+        opts: GethDebugTracingOptions,
+    ) -> impl Stream<Item = Result<BlockTraceResult, Eth::Error>> + Send + 'static {
+        let this = self.clone();
+        futures::stream::iter(start_exclusive.saturating_add(1)..=end_inclusive)
+            .map(move |number| {
+                let this = this.clone();
+                let opts = opts.clone();
+                async move {
+                    let _permit = this.acquire_trace_permit().await;
+                    let hash = this
+                        .provider()
+                        .block_hash(number)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                    let traces = this.debug_trace_block(hash.into(), opts).await?;
+                    Ok::<_, Eth::Error>(BlockTraceResult { block: number.into(), hash, traces })
+                }
+            })
+            .buffered(TRACE_CHAIN_CONCURRENCY)
+    }
+
+    /// Resolves the block range of a `debug_traceChain` request.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> Result<(u64, u64), ErrorObject<'static>> {
+        let resolve = |block: BlockNumberOrTag| {
+            self.provider()
+                .convert_block_number(block)
+                .to_rpc_result()?
+                .ok_or_else(|| invalid_params_rpc_err("pending block not supported"))
+        };
+        let start_exclusive = resolve(start_exclusive)?;
+        let end_inclusive = resolve(end_inclusive)?;
+        ensure_trace_chain_range(start_exclusive, end_inclusive)?;
+        Ok((start_exclusive, end_inclusive))
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -976,10 +1037,27 @@ where
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let (start_exclusive, end_inclusive) =
+            match self.trace_chain_range(start_exclusive, end_inclusive) {
+                Ok(range) => range,
+                Err(err) => {
+                    pending.reject(err).await;
+                    return Ok(())
+                }
+            };
+
+        let sink = pending.accept().await?;
+        let stream =
+            Self::debug_trace_chain(self, start_exclusive, end_inclusive, opts.unwrap_or_default());
+
+        // a failed trace ends the subscription with the error
+        pipe_from_stream(sink, stream).await?;
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -1309,6 +1387,56 @@ impl<Eth> std::fmt::Debug for DebugApi<Eth> {
     }
 }
 
+/// Ensures that the block range of a `debug_traceChain` request is not empty and does not exceed
+/// [`MAX_TRACE_CHAIN_BLOCKS`].
+fn ensure_trace_chain_range(
+    start_exclusive: u64,
+    end_inclusive: u64,
+) -> Result<(), ErrorObject<'static>> {
+    if start_exclusive >= end_inclusive {
+        return Err(invalid_params_rpc_err("start block must be lower than end block"))
+    }
+    if end_inclusive - start_exclusive > MAX_TRACE_CHAIN_BLOCKS {
+        return Err(invalid_params_rpc_err(format!(
+            "block range too large; currently limited to {MAX_TRACE_CHAIN_BLOCKS} blocks"
+        )))
+    }
+    Ok(())
+}
+
+/// Pipes the traces of a `debug_traceChain` stream to the subscription sink.
+///
+/// Ends the subscription on the first error.
+async fn pipe_from_stream<St, E>(sink: SubscriptionSink, stream: St) -> Result<(), E>
+where
+    St: Stream<Item = Result<BlockTraceResult, E>>,
+{
+    let mut stream = std::pin::pin!(stream);
+    loop {
+        tokio::select! {
+            _ = sink.closed() => {
+                // connection dropped, dropping the stream cancels pending traces
+                break Ok(())
+            }
+            maybe_item = stream.next() => {
+                let Some(item) = maybe_item else {
+                    // all blocks traced
+                    break Ok(())
+                };
+                let item = item?;
+                let Ok(msg) =
+                    SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &item)
+                else {
+                    break Ok(())
+                };
+                if sink.send(msg).await.is_err() {
+                    break Ok(())
+                }
+            }
+        }
+    }
+}
+
 impl<Eth> Clone for DebugApi<Eth> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
@@ -1321,3 +1449,53 @@ struct DebugApiInner<Eth> {
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{core::SubscriptionResult, rpc_params, RpcModule};
+
+    fn block_trace(number: u64) -> BlockTraceResult {
+        BlockTraceResult {
+            block: number.into(),
+            hash: B256::with_last_byte(number as u8),
+            traces: vec![],
+        }
+    }
+
+    /// Pipes the traces of block 1 and a failed trace of block 2 to the subscription.
+    async fn pipe_traces(pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let stream = futures::stream::iter([
+            Ok(block_trace(1)),
+            Err("failed to trace block 2".to_string()),
+            Ok(block_trace(3)),
+        ]);
+        pipe_from_stream(sink, stream).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn trace_chain_ends_on_error() {
+        let mut module = RpcModule::new(());
+        module
+            .register_subscription("sub", "notif", "unsub", |_, pending, _, _| pipe_traces(pending))
+            .unwrap();
+        let mut sub = module.subscribe("sub", rpc_params![], 16).await.unwrap();
+
+        let (first, _) = sub.next::<serde_json::Value>().await.unwrap().unwrap();
+        assert_eq!(first, serde_json::to_value(block_trace(1)).unwrap());
+
+        // the subscription is closed with the error, the remaining blocks are not sent
+        assert!(!matches!(sub.next::<serde_json::Value>().await, Some(Ok(_))));
+    }
+
+    #[test]
+    fn trace_chain_range_is_capped() {
+        assert!(ensure_trace_chain_range(0, 1).is_ok());
+        assert!(ensure_trace_chain_range(0, MAX_TRACE_CHAIN_BLOCKS).is_ok());
+        assert!(ensure_trace_chain_range(0, MAX_TRACE_CHAIN_BLOCKS + 1).is_err());
+        assert!(ensure_trace_chain_range(1, 1).is_err());
+        assert!(ensure_trace_chain_range(2, 1).is_err());
+    }
+}
@@ -12,13 +12,14 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::{
     fee_history::fee_history_cache_new_blocks_task, receipt::EthReceiptConverter, EthStateCache,
     EthStateCacheConfig, FeeHistoryCache, FeeHistoryCacheConfig, GasCap, GasPriceOracle,
-    GasPriceOracleConfig,
+    GasPriceOracleConfig, TipSuggester,
 };
 use reth_rpc_server_types::constants::{
     DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT,
 };
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner, TokioTaskExecutor};
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 /// A helper to build the `EthApi` handler instance.
 ///
@@ -31,12 +32,14 @@ pub struct EthApiBuilder<N: RpcNodeCore, Rpc, NextEnv = ()> {
     gas_cap: GasCap,
     max_simulate_blocks: u64,
     eth_proof_window: u64,
+    send_raw_transaction_sync_timeout: Duration,
     fee_history_cache_config: FeeHistoryCacheConfig,
     proof_permits: usize,
     eth_state_cache_config: EthStateCacheConfig,
     eth_cache: Option<EthStateCache<N::Primitives>>,
     gas_oracle_config: GasPriceOracleConfig,
     gas_oracle: Option<GasPriceOracle<N::Provider>>,
+    tip_suggester: Option<Arc<dyn TipSuggester>>,
     blocking_task_pool: Option<BlockingTaskPool>,
     task_spawner: Box<dyn TaskSpawner + 'static>,
     next_env: NextEnv,
@@ -69,12 +72,14 @@ impl<N: RpcNodeCore, Rpc, NextEnv> EthApiBuilder<N, Rpc, NextEnv> {
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            send_raw_transaction_sync_timeout,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
             eth_cache,
             gas_oracle_config,
             gas_oracle,
+            tip_suggester,
             blocking_task_pool,
             task_spawner,
             next_env,
@@ -85,12 +90,14 @@ impl<N: RpcNodeCore, Rpc, NextEnv> EthApiBuilder<N, Rpc, NextEnv> {
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            send_raw_transaction_sync_timeout,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
             eth_cache,
             gas_oracle_config,
             gas_oracle,
+            tip_suggester,
             blocking_task_pool,
             task_spawner,
             next_env,
@@ -111,9 +118,11 @@ where
             rpc_converter,
             eth_cache: None,
             gas_oracle: None,
+            tip_suggester: None,
             gas_cap: GasCap::default(),
             max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            send_raw_transaction_sync_timeout: DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT,
             blocking_task_pool: None,
             fee_history_cache_config: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
@@ -146,11 +155,13 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            send_raw_transaction_sync_timeout,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
             eth_cache,
             gas_oracle,
+            tip_suggester,
             blocking_task_pool,
             task_spawner,
             gas_oracle_config,
@@ -162,11 +173,13 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            send_raw_transaction_sync_timeout,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
             eth_cache,
             gas_oracle,
+            tip_suggester,
             blocking_task_pool,
             task_spawner,
             gas_oracle_config,
@@ -185,11 +198,13 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            send_raw_transaction_sync_timeout,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
             eth_cache,
             gas_oracle,
+            tip_suggester,
             blocking_task_pool,
             task_spawner,
             gas_oracle_config,
@@ -201,11 +216,13 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            send_raw_transaction_sync_timeout,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
             eth_cache,
             gas_oracle,
+            tip_suggester,
             blocking_task_pool,
             task_spawner,
             gas_oracle_config,
@@ -236,6 +253,13 @@ where
         self
     }
 
+    /// Sets a custom [`TipSuggester`] for the gas oracle that will be used if no
+    /// [`GasPriceOracle`] is configured, replacing the configured algorithm.
+    pub fn tip_suggester(mut self, tip_suggester: Arc<dyn TipSuggester>) -> Self {
+        self.tip_suggester = Some(tip_suggester);
+        self
+    }
+
     /// Sets `gas_oracle` instance
     pub fn gas_oracle(mut self, gas_oracle: GasPriceOracle<N::Provider>) -> Self {
         self.gas_oracle = Some(gas_oracle);
@@ -260,6 +284,16 @@ where
         self
     }
 
+    /// Sets the maximum duration `eth_sendRawTransactionSync` waits for the transaction to be
+    /// included in a block.
+    pub const fn send_raw_transaction_sync_timeout(
+        mut self,
+        send_raw_transaction_sync_timeout: Duration,
+    ) -> Self {
+        self.send_raw_transaction_sync_timeout = send_raw_transaction_sync_timeout;
+        self
+    }
+
     /// Sets the blocking task pool.
     pub fn blocking_task_pool(mut self, blocking_task_pool: BlockingTaskPool) -> Self {
         self.blocking_task_pool = Some(blocking_task_pool);
@@ -301,9 +335,11 @@ where
             gas_oracle_config,
             eth_cache,
             gas_oracle,
+            tip_suggester,
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            send_raw_transaction_sync_timeout,
             blocking_task_pool,
             fee_history_cache_config,
             proof_permits,
@@ -316,11 +352,16 @@ where
         let eth_cache = eth_cache
             .unwrap_or_else(|| EthStateCache::spawn(provider.clone(), eth_state_cache_config));
         let gas_oracle = gas_oracle.unwrap_or_else(|| {
-            GasPriceOracle::new(provider.clone(), gas_oracle_config, eth_cache.clone())
+            let gas_oracle =
+                GasPriceOracle::new(provider.clone(), gas_oracle_config, eth_cache.clone());
+            match tip_suggester {
+                Some(tip_suggester) => gas_oracle.with_tip_suggester(tip_suggester),
+                None => gas_oracle,
+            }
         });
         let fee_history_cache =
             FeeHistoryCache::<HeaderTy<N::Primitives>>::new(fee_history_cache_config);
-        let new_canonical_blocks = provider.canonical_state_stream();
+        let new_canonical_blocks = provider.canonical_state_stream_with_history();
         let fhc = fee_history_cache.clone();
         let cache = eth_cache.clone();
         task_spawner.spawn_critical(
@@ -337,6 +378,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            send_raw_transaction_sync_timeout,
             blocking_task_pool.unwrap_or_else(|| {
                 BlockingTaskPool::build().expect("failed to build blocking task pool")
             }),
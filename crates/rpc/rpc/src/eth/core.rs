@@ -1,7 +1,7 @@
 //! Implementation of the [`jsonrpsee`] generated [`EthApiServer`](crate::EthApi) trait
 //! Handles RPC requests for the `eth_` namespace.
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use crate::{eth::helpers::types::EthRpcConverter, EthApiBuilder};
 use alloy_consensus::BlockHeader;
@@ -23,6 +23,7 @@ use reth_rpc_eth_types::{
     receipt::EthReceiptConverter, EthApiError, EthStateCache, FeeHistoryCache, GasCap,
     GasPriceOracle, PendingBlock,
 };
+use reth_rpc_server_types::constants::DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT;
 use reth_storage_api::{noop::NoopProvider, BlockReaderIdExt, ProviderHeader};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
@@ -155,6 +156,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            DEFAULT_SEND_RAW_TRANSACTION_SYNC_TIMEOUT,
             blocking_task_pool,
             fee_history_cache,
             TokioTaskExecutor::default().boxed(),
@@ -268,6 +270,9 @@ pub struct EthApiInner<N: RpcNodeCore, Rpc: RpcConvert> {
     max_simulate_blocks: u64,
     /// The maximum number of blocks into the past for generating state proofs.
     eth_proof_window: u64,
+    /// The maximum duration `eth_sendRawTransactionSync` waits for the transaction to be included
+    /// in a block.
+    send_raw_transaction_sync_timeout: Duration,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
@@ -306,6 +311,7 @@ where
         gas_cap: impl Into<GasCap>,
         max_simulate_blocks: u64,
         eth_proof_window: u64,
+        send_raw_transaction_sync_timeout: Duration,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache<ProviderHeader<N::Provider>>,
         task_spawner: Box<dyn TaskSpawner + 'static>,
@@ -335,6 +341,7 @@ where
             gas_cap: gas_cap.into().into(),
             max_simulate_blocks,
             eth_proof_window,
+            send_raw_transaction_sync_timeout,
             starting_block,
             task_spawner,
             pending_block: Default::default(),
@@ -456,6 +463,13 @@ where
         self.eth_proof_window
     }
 
+    /// Returns the maximum duration `eth_sendRawTransactionSync` waits for the transaction to be
+    /// included in a block.
+    #[inline]
+    pub const fn send_raw_transaction_sync_timeout(&self) -> Duration {
+        self.send_raw_transaction_sync_timeout
+    }
+
     /// Returns reference to [`BlockingTaskGuard`].
     #[inline]
     pub const fn blocking_task_guard(&self) -> &BlockingTaskGuard {
@@ -9,18 +9,22 @@ use alloy_rpc_types_eth::{
 use async_trait::async_trait;
 use futures::future::TryFutureExt;
 use itertools::Itertools;
-use jsonrpsee::{core::RpcResult, server::IdProvider};
+use jsonrpsee::{core::RpcResult, server::IdProvider, Extensions};
 use reth_errors::ProviderError;
 use reth_primitives_traits::{NodePrimitives, SealedHeader};
 use reth_rpc_eth_api::{
-    EngineEthFilter, EthApiTypes, EthFilterApiServer, FullEthApiTypes, QueryLimits, RpcConvert,
-    RpcNodeCoreExt, RpcTransaction,
+    EngineEthFilter, EthApiTypes, EthFilterApiServer, FullEthApiTypes, MaxBlocksPerFilterOverride,
+    QueryLimits, RpcConvert, RpcNodeCoreExt, RpcTransaction,
 };
 use reth_rpc_eth_types::{
+    error::{QueryLimit, RpcErrorData},
     logs_utils::{self, append_matching_block_logs, ProviderOrBlock},
     EthApiError, EthFilterConfig, EthStateCache, EthSubscriptionIdProvider,
 };
-use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
+use reth_rpc_server_types::{
+    result::{rpc_error_with_code, rpc_error_with_data},
+    ToRpcResult,
+};
 use reth_storage_api::{
     BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
     ProviderReceipt, ReceiptProvider,
@@ -127,8 +131,13 @@ where
     /// let filter = EthFilter::new(eth_api, Default::default(), TokioTaskExecutor::default().boxed());
     /// ```
     pub fn new(eth_api: Eth, config: EthFilterConfig, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            max_addresses_per_filter,
+            max_topics_per_position,
+            stale_filter_ttl,
+        } = config;
         let inner = EthFilterInner {
             eth_api,
             active_filters: ActiveFilters::new(),
@@ -136,7 +145,12 @@ where
             max_headers_range: MAX_HEADERS_RANGE,
             task_spawner,
             stale_filter_ttl,
-            query_limits: QueryLimits { max_blocks_per_filter, max_logs_per_response },
+            query_limits: QueryLimits {
+                max_blocks_per_filter,
+                max_logs_per_response,
+                max_addresses_per_filter,
+                max_topics_per_position,
+            },
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -316,6 +330,7 @@ where
     /// Handler for `eth_newFilter`
     async fn new_filter(&self, filter: Filter) -> RpcResult<FilterId> {
         trace!(target: "rpc::eth", "Serving eth_newFilter");
+        ensure_filter_within_limits(&filter, &self.inner.query_limits)?;
         self.inner
             .install_filter(FilterKind::<RpcTransaction<Eth::NetworkTypes>>::Log(Box::new(filter)))
             .await
@@ -392,9 +407,13 @@ where
     /// Returns logs matching given filter object.
     ///
     /// Handler for `eth_getLogs`
-    async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
+    async fn logs(&self, ext: &Extensions, filter: Filter) -> RpcResult<Vec<Log>> {
         trace!(target: "rpc::eth", "Serving eth_getLogs");
-        Ok(self.logs_for_filter(filter, self.inner.query_limits).await?)
+        let mut limits = self.inner.query_limits;
+        if let Some(MaxBlocksPerFilterOverride(max_blocks_per_filter)) = ext.get() {
+            limits.max_blocks_per_filter = *max_blocks_per_filter;
+        }
+        Ok(self.logs_for_filter(filter, limits).await?)
     }
 }
 
@@ -448,6 +467,8 @@ where
         filter: Filter,
         limits: QueryLimits,
     ) -> Result<Vec<Log>, EthFilterError> {
+        ensure_filter_within_limits(&filter, &limits)?;
+
         match filter.block_option {
             FilterBlockOption::AtBlockHash(block_hash) => {
                 // for all matching logs in the block
@@ -550,7 +571,10 @@ where
         if let Some(max_blocks_per_filter) =
             limits.max_blocks_per_filter.filter(|limit| to_block - from_block > *limit)
         {
-            return Err(EthFilterError::QueryExceedsMaxBlocks(max_blocks_per_filter))
+            return Err(EthFilterError::QueryExceedsMaxBlocks {
+                max: max_blocks_per_filter,
+                requested: to_block - from_block,
+            })
         }
 
         let (tx, rx) = oneshot::channel();
@@ -836,8 +860,31 @@ pub enum EthFilterError {
     #[error("invalid block range params")]
     InvalidBlockRangeParams,
     /// Query scope is too broad.
-    #[error("query exceeds max block range {0}")]
-    QueryExceedsMaxBlocks(u64),
+    #[error("query exceeds max block range {max}")]
+    QueryExceedsMaxBlocks {
+        /// Maximum block range allowed per query
+        max: u64,
+        /// Requested block range
+        requested: u64,
+    },
+    /// Filter contains too many addresses.
+    #[error("filter exceeds max number of addresses {max}, got {requested}")]
+    QueryExceedsMaxAddresses {
+        /// Maximum number of addresses allowed per filter
+        max: usize,
+        /// Number of addresses in the filter
+        requested: usize,
+    },
+    /// Filter contains too many topics in a topic position.
+    #[error("filter exceeds max number of topics {max} at position {position}, got {requested}")]
+    QueryExceedsMaxTopics {
+        /// The topic position
+        position: usize,
+        /// Maximum number of topics allowed per position
+        max: usize,
+        /// Number of topics at the position
+        requested: usize,
+    },
     /// Query result is too large.
     #[error("query exceeds max results {max_logs}, retry with the range {from_block}-{to_block}")]
     QueryExceedsMaxResults {
@@ -867,8 +914,39 @@ impl From<EthFilterError> for jsonrpsee::types::error::ErrorObject<'static> {
                 rpc_error_with_code(jsonrpsee::types::error::INTERNAL_ERROR_CODE, err.to_string())
             }
             EthFilterError::EthAPIError(err) => err.into(),
+            EthFilterError::QueryExceedsMaxBlocks { max, requested } => rpc_error_with_data(
+                jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                err.to_string(),
+                RpcErrorData::QueryLimitExceeded {
+                    limit: QueryLimit::BlockRange,
+                    max,
+                    requested,
+                    position: None,
+                },
+            ),
+            EthFilterError::QueryExceedsMaxAddresses { max, requested } => rpc_error_with_data(
+                jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                err.to_string(),
+                RpcErrorData::QueryLimitExceeded {
+                    limit: QueryLimit::Addresses,
+                    max: max as u64,
+                    requested: requested as u64,
+                    position: None,
+                },
+            ),
+            EthFilterError::QueryExceedsMaxTopics { position, max, requested } => {
+                rpc_error_with_data(
+                    jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                    err.to_string(),
+                    RpcErrorData::QueryLimitExceeded {
+                        limit: QueryLimit::TopicsPerPosition,
+                        max: max as u64,
+                        requested: requested as u64,
+                        position: Some(position),
+                    },
+                )
+            }
             err @ (EthFilterError::InvalidBlockRangeParams |
-            EthFilterError::QueryExceedsMaxBlocks(_) |
             EthFilterError::QueryExceedsMaxResults { .. }) => {
                 rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
             }
@@ -876,6 +954,30 @@ impl From<EthFilterError> for jsonrpsee::types::error::ErrorObject<'static> {
     }
 }
 
+/// Returns an error if the filter has more addresses or topics than allowed by the limits.
+fn ensure_filter_within_limits(
+    filter: &Filter,
+    limits: &QueryLimits,
+) -> Result<(), EthFilterError> {
+    if let Some(max) = limits.max_addresses_per_filter {
+        let requested = filter.address.len();
+        if requested > max {
+            return Err(EthFilterError::QueryExceedsMaxAddresses { max, requested })
+        }
+    }
+
+    if let Some(max) = limits.max_topics_per_position {
+        for (position, topics) in filter.topics.iter().enumerate() {
+            let requested = topics.len();
+            if requested > max {
+                return Err(EthFilterError::QueryExceedsMaxTopics { position, max, requested })
+            }
+        }
+    }
+
+    Ok(())
+}
+
 impl From<ProviderError> for EthFilterError {
     fn from(err: ProviderError) -> Self {
         Self::EthAPIError(err.into())
@@ -1175,6 +1277,37 @@ mod tests {
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
     use std::{collections::VecDeque, sync::Arc};
 
+    #[test]
+    fn test_filter_limits() {
+        use alloy_primitives::{Address, B256};
+
+        let limits = QueryLimits {
+            max_addresses_per_filter: Some(2),
+            max_topics_per_position: Some(1),
+            ..Default::default()
+        };
+
+        let filter = Filter::new()
+            .address((1..=2).map(Address::with_last_byte).collect::<Vec<_>>())
+            .event_signature(B256::ZERO);
+        assert!(ensure_filter_within_limits(&filter, &limits).is_ok());
+
+        let filter =
+            Filter::new().address((1..=3).map(Address::with_last_byte).collect::<Vec<_>>());
+        assert!(matches!(
+            ensure_filter_within_limits(&filter, &limits),
+            Err(EthFilterError::QueryExceedsMaxAddresses { max: 2, requested: 3 })
+        ));
+
+        let filter = Filter::new().topic1(vec![B256::ZERO, B256::with_last_byte(1)]);
+        assert!(matches!(
+            ensure_filter_within_limits(&filter, &limits),
+            Err(EthFilterError::QueryExceedsMaxTopics { position: 1, max: 1, requested: 2 })
+        ));
+
+        assert!(ensure_filter_within_limits(&filter, &QueryLimits::no_limits()).is_ok());
+    }
+
     #[test]
     fn test_block_range_iter() {
         let mut rng = generators::rng();
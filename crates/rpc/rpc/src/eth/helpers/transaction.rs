@@ -11,6 +11,7 @@ use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
 use reth_transaction_pool::{
     AddedTransactionOutcome, PoolTransaction, TransactionOrigin, TransactionPool,
 };
+use std::time::Duration;
 
 impl<N, Rpc> EthTransactions for EthApi<N, Rpc>
 where
@@ -23,6 +24,11 @@ where
         self.inner.signers()
     }
 
+    #[inline]
+    fn send_raw_transaction_sync_timeout(&self) -> Duration {
+        self.inner.send_raw_transaction_sync_timeout()
+    }
+
     /// Decodes and recovers the transaction and submits it to the pool.
     ///
     /// Returns the hash of the transaction.
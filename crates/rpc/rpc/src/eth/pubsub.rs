@@ -1,6 +1,10 @@
 //! `eth_` `PubSub` RPC handler implementation
 
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use alloy_primitives::{TxHash, U256};
 use alloy_rpc_types_eth::{
@@ -9,25 +13,41 @@ use alloy_rpc_types_eth::{
 };
 use futures::StreamExt;
 use jsonrpsee::{
-    server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
+    server::SubscriptionMessage,
+    types::{ErrorObject, SubscriptionId},
+    ConnectionId, PendingSubscriptionSink, SubscriptionSink,
 };
+use parking_lot::Mutex;
 use reth_chain_state::CanonStateSubscriptions;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
 use reth_network_api::NetworkInfo;
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc_eth_api::{
     pubsub::EthPubSubApiServer, EthApiTypes, RpcConvert, RpcNodeCore, RpcTransaction,
 };
-use reth_rpc_eth_types::logs_utils;
+use reth_rpc_eth_types::{logs_utils, EthPubSubConfig};
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_storage_api::BlockNumReader;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{NewTransactionEvent, PoolConsensusTx, TransactionPool};
 use serde::Serialize;
+use serde_json::value::RawValue;
 use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
     Stream,
 };
-use tracing::error;
+use tracing::{debug, error};
+
+/// How often a full pending transactions subscription retries to flush its backlog while the
+/// connection buffer is full.
+const BACKLOG_FLUSH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a subscription that is closed by the [`SubscriptionLimiter`] waits for the connection
+/// buffer to send the [`SubscriptionClosed`] notification.
+const CLOSE_NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(1);
 
 /// `Eth` pubsub RPC implementation.
 ///
@@ -50,7 +70,21 @@ impl<Eth> EthPubSub<Eth> {
 
     /// Creates a new, shareable instance.
     pub fn with_spawner(eth_api: Eth, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = EthPubSubInner { eth_api, subscription_task_spawner };
+        Self::with_config(eth_api, subscription_task_spawner, EthPubSubConfig::default())
+    }
+
+    /// Creates a new, shareable instance with the given per-connection limits.
+    pub fn with_config(
+        eth_api: Eth,
+        subscription_task_spawner: Box<dyn TaskSpawner>,
+        config: EthPubSubConfig,
+    ) -> Self {
+        let inner = EthPubSubInner {
+            eth_api,
+            subscription_task_spawner,
+            pending_tx_buffer: config.pending_tx_buffer,
+            limiter: SubscriptionLimiter::new(config),
+        };
         Self { inner: Arc::new(inner) }
     }
 }
@@ -103,7 +137,8 @@ where
     ) -> Result<(), ErrorObject<'static>> {
         match kind {
             SubscriptionKind::NewHeads => {
-                pipe_from_stream(accepted_sink, self.new_headers_stream()).await
+                pipe_from_stream(&self.inner.limiter, accepted_sink, self.new_headers_stream())
+                    .await
             }
             SubscriptionKind::Logs => {
                 // if no params are provided, used default filter params
@@ -114,7 +149,7 @@ where
                     }
                     _ => Default::default(),
                 };
-                pipe_from_stream(accepted_sink, self.log_stream(filter)).await
+                pipe_from_stream(&self.inner.limiter, accepted_sink, self.log_stream(filter)).await
             }
             SubscriptionKind::NewPendingTransactions => {
                 if let Some(params) = params {
@@ -139,7 +174,13 @@ where
                                 };
                                 std::future::ready(tx_value)
                             });
-                            return pipe_from_stream(accepted_sink, stream).await
+                            return pipe_with_backlog(
+                                &self.inner.limiter,
+                                accepted_sink,
+                                stream,
+                                self.inner.pending_tx_buffer,
+                            )
+                            .await
                         }
                         Params::Bool(false) | Params::None => {
                             // only hashes requested
@@ -152,26 +193,26 @@ where
                     }
                 }
 
-                pipe_from_stream(accepted_sink, self.pending_transaction_hashes_stream()).await
+                pipe_from_stream(
+                    &self.inner.limiter,
+                    accepted_sink,
+                    self.pending_transaction_hashes_stream(),
+                )
+                .await
             }
             SubscriptionKind::Syncing => {
                 // get new block subscription
                 let mut canon_state = BroadcastStream::new(
                     self.inner.eth_api.provider().subscribe_to_canonical_state(),
                 );
+                let _subscription = self.inner.limiter.subscribe(&accepted_sink);
+
                 // get current sync status
                 let mut initial_sync_status = self.inner.eth_api.network().is_syncing();
                 let current_sub_res = self.sync_status(initial_sync_status);
 
                 // send the current status immediately
-                let msg = SubscriptionMessage::new(
-                    accepted_sink.method_name(),
-                    accepted_sink.subscription_id(),
-                    &current_sub_res,
-                )
-                .map_err(SubscriptionSerializeError::new)?;
-
-                if accepted_sink.send(msg).await.is_err() {
+                if !self.inner.limiter.send(&accepted_sink, &current_sub_res).await? {
                     return Ok(())
                 }
 
@@ -184,14 +225,7 @@ where
 
                         // send a new message now that the status changed
                         let sync_status = self.sync_status(current_syncing);
-                        let msg = SubscriptionMessage::new(
-                            accepted_sink.method_name(),
-                            accepted_sink.subscription_id(),
-                            &sync_status,
-                        )
-                        .map_err(SubscriptionSerializeError::new)?;
-
-                        if accepted_sink.send(msg).await.is_err() {
+                        if !self.inner.limiter.send(&accepted_sink, &sync_status).await? {
                             break
                         }
                     }
@@ -252,6 +286,7 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
 
 /// Pipes all stream items to the subscription sink.
 async fn pipe_from_stream<T, St>(
+    limiter: &SubscriptionLimiter,
     sink: SubscriptionSink,
     mut stream: St,
 ) -> Result<(), ErrorObject<'static>>
@@ -259,6 +294,7 @@ where
     St: Stream<Item = T> + Unpin,
     T: Serialize,
 {
+    let _subscription = limiter.subscribe(&sink);
     loop {
         tokio::select! {
             _ = sink.closed() => {
@@ -273,13 +309,7 @@ where
                         break  Ok(())
                     },
                 };
-                let msg = SubscriptionMessage::new(
-                    sink.method_name(),
-                    sink.subscription_id(),
-                    &item
-                ).map_err(SubscriptionSerializeError::new)?;
-
-                if sink.send(msg).await.is_err() {
+                if !limiter.send(&sink, &item).await? {
                     break Ok(());
                 }
             }
@@ -287,6 +317,159 @@ where
     }
 }
 
+/// Last notification sent to a subscription that is closed, because its connection exceeded a
+/// limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SubscriptionClosed {
+    /// Why the subscription was closed.
+    pub reason: &'static str,
+}
+
+/// Error data of the notification sent to a `newPendingTransactions` subscription with full
+/// transaction objects after transactions were dropped, because the subscriber could not keep up.
+///
+/// The notification carries an `error` instead of a `result`, so it can't be mistaken for a
+/// transaction:
+///
+/// ```json
+/// {"subscription": "0x..", "error": {"code": -32000, "message": "..", "data": {"lagged": 3}}}
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PendingTransactionsLagged {
+    /// Number of transactions that were dropped since the last notification.
+    pub lagged: u64,
+}
+
+impl PendingTransactionsLagged {
+    /// Error code of the lag notification.
+    pub const CODE: i32 = -32000;
+    /// Error message of the lag notification.
+    pub const MESSAGE: &'static str = "subscriber lagged, pending transactions were dropped";
+
+    /// Returns the error notification of the subscription.
+    fn notification(
+        self,
+        method: &str,
+        subscription: &SubscriptionId<'_>,
+    ) -> Result<Box<RawValue>, SubscriptionSerializeError> {
+        let error = ErrorObject::owned(Self::CODE, Self::MESSAGE, Some(self));
+        serde_json::value::to_raw_value(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": { "subscription": subscription, "error": error },
+        }))
+        .map_err(SubscriptionSerializeError::new)
+    }
+}
+
+/// Bounded backlog of a subscription that drops its oldest items once it's full.
+#[derive(Debug)]
+struct Backlog<T> {
+    /// Items that were not sent yet.
+    items: VecDeque<T>,
+    /// Maximum number of buffered items.
+    max_items: usize,
+    /// Number of items dropped since the subscriber was last notified.
+    lagged: u64,
+}
+
+impl<T> Backlog<T> {
+    fn new(max_items: usize) -> Self {
+        Self { items: VecDeque::new(), max_items: max_items.max(1), lagged: 0 }
+    }
+
+    /// Appends the item, dropping the oldest one if the backlog is full.
+    ///
+    /// Returns `true` if an item was dropped.
+    fn push(&mut self, item: T) -> bool {
+        let dropped = self.items.len() >= self.max_items;
+        if dropped {
+            self.items.pop_front();
+            self.lagged += 1;
+        }
+        self.items.push_back(item);
+        dropped
+    }
+
+    /// Returns `true` if there's nothing left to send.
+    fn is_empty(&self) -> bool {
+        self.lagged == 0 && self.items.is_empty()
+    }
+}
+
+/// Pipes all stream items to the subscription sink, buffering up to `max_buffered` items while
+/// the connection buffer is full.
+///
+/// Unlike [`pipe_from_stream`], a slow subscriber does not close the subscription. Once the
+/// backlog is full, the oldest items are dropped and the subscriber receives a
+/// [`PendingTransactionsLagged`] error notification with the number of dropped items before the
+/// remaining backlog. The subscription ends once the stream ended and the backlog was sent.
+async fn pipe_with_backlog<T, St>(
+    limiter: &SubscriptionLimiter,
+    sink: SubscriptionSink,
+    mut stream: St,
+    max_buffered: usize,
+) -> Result<(), ErrorObject<'static>>
+where
+    St: Stream<Item = T> + Unpin,
+    T: Serialize,
+{
+    let _subscription = limiter.subscribe(&sink);
+    let mut backlog = Backlog::new(max_buffered);
+    let mut stream_ended = false;
+    let mut flush_interval = tokio::time::interval(BACKLOG_FLUSH_INTERVAL);
+    flush_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        // send as much of the backlog as the connection accepts, starting with the lag
+        // notification for the dropped items
+        loop {
+            let outcome = if backlog.lagged > 0 {
+                limiter.try_send_lagged(&sink, backlog.lagged)?
+            } else if let Some(item) = backlog.items.front() {
+                limiter.try_send(&sink, item)?
+            } else {
+                break
+            };
+
+            match outcome {
+                SendOutcome::Sent if backlog.lagged > 0 => backlog.lagged = 0,
+                SendOutcome::Sent => {
+                    backlog.items.pop_front();
+                }
+                SendOutcome::Full => break,
+                SendOutcome::RateLimited => {
+                    limiter.close(&sink, SubscriptionLimiter::RATE_LIMITED).await;
+                    return Ok(())
+                }
+                SendOutcome::Closed => return Ok(()),
+            }
+        }
+
+        if stream_ended && backlog.is_empty() {
+            break Ok(())
+        }
+
+        tokio::select! {
+            _ = sink.closed() => {
+                // connection dropped
+                break Ok(())
+            },
+            maybe_item = stream.next(), if !stream_ended => {
+                let Some(item) = maybe_item else {
+                    // stream ended, send the remaining backlog
+                    stream_ended = true;
+                    continue
+                };
+                if backlog.push(item) {
+                    limiter.metrics.dropped_backlog_notifications.increment(1);
+                }
+            }
+            _ = flush_interval.tick(), if !backlog.is_empty() => {}
+        }
+    }
+}
+
 impl<Eth> std::fmt::Debug for EthPubSub<Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("EthPubSub").finish_non_exhaustive()
@@ -294,12 +477,295 @@ impl<Eth> std::fmt::Debug for EthPubSub<Eth> {
 }
 
 /// Container type `EthPubSub`
-#[derive(Clone)]
 struct EthPubSubInner<EthApi> {
     /// The `eth` API.
     eth_api: EthApi,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
+    /// Maximum number of transactions buffered for a full pending transactions subscription.
+    pending_tx_buffer: usize,
+    /// Enforces the per-connection limits of all subscriptions.
+    limiter: SubscriptionLimiter,
+}
+
+/// Notification bytes sent to a connection within the current one second window.
+#[derive(Debug)]
+struct ConnectionUsage {
+    /// Number of active subscriptions of the connection.
+    subscriptions: usize,
+    /// Start of the current window.
+    window_start: Instant,
+    /// Bytes sent within the current window.
+    bytes: u64,
+}
+
+/// Enforces the per-connection limits of subscriptions.
+///
+/// The number of buffered notifications is bounded by the message buffer of the connection. If
+/// the buffer is full, subscriptions wait for the client to catch up, or they're closed if
+/// [`EthPubSubConfig::close_slow_subscriptions`] is set. Subscriptions that keep their own backlog
+/// never wait, see [`pipe_with_backlog`].
+///
+/// Subscriptions closed by the limiter receive a [`SubscriptionClosed`] notification with the
+/// reason.
+#[derive(Debug)]
+struct SubscriptionLimiter {
+    /// Maximum number of notification bytes per second sent to a single connection.
+    max_bytes_per_sec: Option<u64>,
+    /// Whether subscriptions are closed if the buffer of their connection is full.
+    close_slow_subscriptions: bool,
+    /// Usage of all connections with active subscriptions.
+    connections: Mutex<HashMap<ConnectionId, ConnectionUsage>>,
+    /// Subscription metrics.
+    metrics: EthPubSubMetrics,
+}
+
+impl SubscriptionLimiter {
+    /// Reason for closing a subscription whose connection exceeded the notification bytes limit.
+    const RATE_LIMITED: &'static str = "connection exceeded the notification bytes limit";
+    /// Reason for closing a subscription whose connection buffer is full.
+    const BUFFER_FULL: &'static str = "connection buffer is full";
+
+    fn new(config: EthPubSubConfig) -> Self {
+        Self {
+            max_bytes_per_sec: config.max_bytes_per_sec_per_connection,
+            close_slow_subscriptions: config.close_slow_subscriptions,
+            connections: Default::default(),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Registers the subscription until the returned guard is dropped.
+    fn subscribe(&self, sink: &SubscriptionSink) -> SubscriptionGuard<'_> {
+        let connection_id = sink.connection_id();
+        self.connections
+            .lock()
+            .entry(connection_id)
+            .or_insert_with(|| ConnectionUsage {
+                subscriptions: 0,
+                window_start: Instant::now(),
+                bytes: 0,
+            })
+            .subscriptions += 1;
+        self.metrics.active_subscriptions.increment(1);
+        SubscriptionGuard { limiter: self, connection_id }
+    }
+
+    /// Records `bytes` sent to the connection.
+    ///
+    /// Returns `false` without recording them if they would exceed the limit of the connection.
+    fn try_consume(&self, connection_id: ConnectionId, bytes: u64) -> bool {
+        let Some(max_bytes_per_sec) = self.max_bytes_per_sec else { return true };
+        let mut connections = self.connections.lock();
+        let Some(usage) = connections.get_mut(&connection_id) else { return true };
+
+        let now = Instant::now();
+        if now.duration_since(usage.window_start) >= Duration::from_secs(1) {
+            usage.window_start = now;
+            usage.bytes = 0;
+        }
+
+        if usage.bytes.saturating_add(bytes) > max_bytes_per_sec {
+            return false
+        }
+        usage.bytes += bytes;
+        true
+    }
+
+    /// Returns `bytes` recorded by [`Self::try_consume`] for a notification that was not sent.
+    fn refund(&self, connection_id: ConnectionId, bytes: u64) {
+        if self.max_bytes_per_sec.is_none() {
+            return
+        }
+        if let Some(usage) = self.connections.lock().get_mut(&connection_id) {
+            usage.bytes = usage.bytes.saturating_sub(bytes);
+        }
+    }
+
+    /// Serializes the item as a notification of the subscription and records its bytes.
+    ///
+    /// Returns `None` if the notification would exceed the bytes limit of the connection.
+    fn notification<T: Serialize>(
+        &self,
+        sink: &SubscriptionSink,
+        item: &T,
+    ) -> Result<Option<(SubscriptionMessage, u64)>, SubscriptionSerializeError> {
+        let item =
+            serde_json::value::to_raw_value(item).map_err(SubscriptionSerializeError::new)?;
+        let bytes = item.get().len() as u64;
+
+        if !self.try_consume(sink.connection_id(), bytes) {
+            return Ok(None)
+        }
+
+        let msg = SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &item)
+            .map_err(SubscriptionSerializeError::new)?;
+        Ok(Some((msg, bytes)))
+    }
+
+    /// Tries to send the item as a notification of the subscription without waiting for the
+    /// connection buffer.
+    fn try_send<T: Serialize>(
+        &self,
+        sink: &SubscriptionSink,
+        item: &T,
+    ) -> Result<SendOutcome, SubscriptionSerializeError> {
+        let Some((msg, bytes)) = self.notification(sink, item)? else {
+            return Ok(SendOutcome::RateLimited)
+        };
+        Ok(self.try_send_message(sink, msg, bytes))
+    }
+
+    /// Tries to send the [`PendingTransactionsLagged`] error notification without waiting for the
+    /// connection buffer.
+    fn try_send_lagged(
+        &self,
+        sink: &SubscriptionSink,
+        lagged: u64,
+    ) -> Result<SendOutcome, SubscriptionSerializeError> {
+        let notification = PendingTransactionsLagged { lagged }
+            .notification(sink.method_name(), &sink.subscription_id())?;
+        let bytes = notification.get().len() as u64;
+
+        if !self.try_consume(sink.connection_id(), bytes) {
+            return Ok(SendOutcome::RateLimited)
+        }
+        Ok(self.try_send_message(sink, SubscriptionMessage::from(notification), bytes))
+    }
+
+    /// Tries to send a notification whose `bytes` were recorded by [`Self::try_consume`].
+    fn try_send_message(
+        &self,
+        sink: &SubscriptionSink,
+        msg: SubscriptionMessage,
+        bytes: u64,
+    ) -> SendOutcome {
+        if sink.try_send(msg).is_err() {
+            if sink.is_closed() {
+                return SendOutcome::Closed
+            }
+            self.refund(sink.connection_id(), bytes);
+            return SendOutcome::Full
+        }
+
+        self.metrics.notification_bytes.increment(bytes);
+        SendOutcome::Sent
+    }
+
+    /// Sends the item as a notification of the subscription.
+    ///
+    /// Returns `false` if the subscription should end, because the connection was closed or the
+    /// notification would exceed a limit of the connection.
+    async fn send<T: Serialize>(
+        &self,
+        sink: &SubscriptionSink,
+        item: &T,
+    ) -> Result<bool, SubscriptionSerializeError> {
+        if self.close_slow_subscriptions {
+            return match self.try_send(sink, item)? {
+                SendOutcome::Sent => Ok(true),
+                SendOutcome::Full => {
+                    self.close(sink, Self::BUFFER_FULL).await;
+                    Ok(false)
+                }
+                SendOutcome::RateLimited => {
+                    self.close(sink, Self::RATE_LIMITED).await;
+                    Ok(false)
+                }
+                SendOutcome::Closed => Ok(false),
+            }
+        }
+
+        let Some((msg, bytes)) = self.notification(sink, item)? else {
+            self.close(sink, Self::RATE_LIMITED).await;
+            return Ok(false)
+        };
+        // wait for the client to catch up
+        if sink.send(msg).await.is_err() {
+            return Ok(false)
+        }
+        self.metrics.notification_bytes.increment(bytes);
+        Ok(true)
+    }
+
+    /// Sends the [`SubscriptionClosed`] notification with the reason before the subscription is
+    /// closed.
+    ///
+    /// Waits at most [`CLOSE_NOTIFICATION_TIMEOUT`] for the connection buffer.
+    async fn close(&self, sink: &SubscriptionSink, reason: &'static str) {
+        if reason == Self::BUFFER_FULL {
+            self.metrics.closed_buffer_full.increment(1);
+        } else {
+            self.metrics.closed_rate_limited.increment(1);
+        }
+        debug!(
+            target: "rpc::eth::pubsub",
+            subscription = ?sink.subscription_id(),
+            %reason,
+            "Closing subscription"
+        );
+
+        let Ok(msg) = SubscriptionMessage::new(
+            sink.method_name(),
+            sink.subscription_id(),
+            &SubscriptionClosed { reason },
+        ) else {
+            return
+        };
+        let _ = tokio::time::timeout(CLOSE_NOTIFICATION_TIMEOUT, sink.send(msg)).await;
+    }
+}
+
+/// Outcome of [`SubscriptionLimiter::try_send`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SendOutcome {
+    /// The notification was sent.
+    Sent,
+    /// The connection buffer is full, the notification can be retried later.
+    Full,
+    /// The notification would exceed the bytes limit of the connection, so the subscription should
+    /// be closed.
+    RateLimited,
+    /// The connection was closed.
+    Closed,
+}
+
+/// Unregisters a subscription from the [`SubscriptionLimiter`] on drop.
+#[derive(Debug)]
+struct SubscriptionGuard<'a> {
+    limiter: &'a SubscriptionLimiter,
+    connection_id: ConnectionId,
+}
+
+impl Drop for SubscriptionGuard<'_> {
+    fn drop(&mut self) {
+        let mut connections = self.limiter.connections.lock();
+        if let Some(usage) = connections.get_mut(&self.connection_id) {
+            usage.subscriptions -= 1;
+            if usage.subscriptions == 0 {
+                connections.remove(&self.connection_id);
+            }
+        }
+        self.limiter.metrics.active_subscriptions.decrement(1);
+    }
+}
+
+/// Metrics for `eth_subscribe` subscriptions.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth_pubsub")]
+struct EthPubSubMetrics {
+    /// The number of active subscriptions.
+    active_subscriptions: Gauge,
+    /// The number of notification bytes sent.
+    notification_bytes: Counter,
+    /// The number of subscriptions closed because the buffer of their connection was full.
+    closed_buffer_full: Counter,
+    /// The number of subscriptions closed because their connection exceeded the notification
+    /// bytes limit.
+    closed_rate_limited: Counter,
+    /// The number of notifications dropped from the backlog of full pending transactions
+    /// subscriptions that could not keep up.
+    dropped_backlog_notifications: Counter,
 }
 
 // == impl EthPubSubInner ===
@@ -387,3 +853,149 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{core::SubscriptionResult, rpc_params, RpcModule};
+
+    /// Pipes the numbers `0..3` to the subscription.
+    async fn pipe_numbers(
+        pending: PendingSubscriptionSink,
+        limiter: Arc<SubscriptionLimiter>,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        pipe_from_stream(&limiter, sink, futures::stream::iter(0..3u64)).await?;
+        Ok(())
+    }
+
+    /// Subscribes to the numbers with a connection buffer of `buffer` messages.
+    async fn subscribe(
+        config: EthPubSubConfig,
+        buffer: usize,
+    ) -> jsonrpsee::core::server::Subscription {
+        let mut module = RpcModule::new(SubscriptionLimiter::new(config));
+        module
+            .register_subscription("sub", "notif", "unsub", |_, pending, limiter, _| {
+                pipe_numbers(pending, limiter)
+            })
+            .unwrap();
+        module.subscribe("sub", rpc_params![], buffer).await.unwrap()
+    }
+
+    async fn next(sub: &mut jsonrpsee::core::server::Subscription) -> Option<serde_json::Value> {
+        sub.next::<serde_json::Value>().await.map(|res| res.unwrap().0)
+    }
+
+    #[tokio::test]
+    async fn slow_subscriptions_wait_by_default() {
+        let mut sub = subscribe(EthPubSubConfig::default(), 1).await;
+
+        // the subscriber only reads once the buffer is full
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        for i in 0..3u64 {
+            assert_eq!(next(&mut sub).await, Some(i.into()));
+        }
+        assert_eq!(next(&mut sub).await, None);
+    }
+
+    #[tokio::test]
+    async fn close_slow_subscriptions_with_reason() {
+        let config = EthPubSubConfig::default().close_slow_subscriptions(true);
+        let mut sub = subscribe(config, 1).await;
+
+        // the subscriber only reads once the buffer is full
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(next(&mut sub).await, Some(0.into()));
+        assert_eq!(
+            next(&mut sub).await,
+            Some(serde_json::json!({ "reason": SubscriptionLimiter::BUFFER_FULL }))
+        );
+        assert_eq!(next(&mut sub).await, None);
+    }
+
+    #[tokio::test]
+    async fn close_rate_limited_subscriptions_with_reason() {
+        // "0" and "1" are sent, "2" exceeds the limit
+        let config = EthPubSubConfig::default().max_bytes_per_sec_per_connection(2);
+        let mut sub = subscribe(config, 16).await;
+
+        assert_eq!(next(&mut sub).await, Some(0.into()));
+        assert_eq!(next(&mut sub).await, Some(1.into()));
+        assert_eq!(
+            next(&mut sub).await,
+            Some(serde_json::json!({ "reason": SubscriptionLimiter::RATE_LIMITED }))
+        );
+        assert_eq!(next(&mut sub).await, None);
+    }
+
+    #[test]
+    fn backlog_drops_oldest_items() {
+        let mut backlog = Backlog::new(2);
+        assert!(!backlog.push(0));
+        assert!(!backlog.push(1));
+        assert!(backlog.push(2));
+        assert!(backlog.push(3));
+
+        assert_eq!(backlog.items, [2, 3]);
+        assert_eq!(backlog.lagged, 2);
+
+        backlog.items.clear();
+        assert!(!backlog.is_empty());
+        backlog.lagged = 0;
+        assert!(backlog.is_empty());
+    }
+
+    #[test]
+    fn lagged_notification_is_an_error() {
+        let notification = PendingTransactionsLagged { lagged: 3 }
+            .notification("eth_subscription", &SubscriptionId::Num(1))
+            .unwrap();
+        let notification: serde_json::Value = serde_json::from_str(notification.get()).unwrap();
+
+        assert_eq!(
+            notification,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "eth_subscription",
+                "params": {
+                    "subscription": 1,
+                    "error": {
+                        "code": PendingTransactionsLagged::CODE,
+                        "message": PendingTransactionsLagged::MESSAGE,
+                        "data": { "lagged": 3 },
+                    },
+                },
+            })
+        );
+    }
+
+    /// Pipes the numbers `0..5` to the subscription through a backlog of four items.
+    async fn pipe_numbers_with_backlog(
+        pending: PendingSubscriptionSink,
+        limiter: Arc<SubscriptionLimiter>,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        pipe_with_backlog(&limiter, sink, futures::stream::iter(0..5u64), 4).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn backlog_is_sent_after_the_stream_ended() {
+        let mut module = RpcModule::new(SubscriptionLimiter::new(EthPubSubConfig::default()));
+        module
+            .register_subscription("sub", "notif", "unsub", |_, pending, limiter, _| {
+                pipe_numbers_with_backlog(pending, limiter)
+            })
+            .unwrap();
+        let mut sub = module.subscribe("sub", rpc_params![], 1).await.unwrap();
+
+        // the subscriber only reads once the stream ended, the backlog fits all items that
+        // don't fit into the connection buffer
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        for i in 0..5u64 {
+            assert_eq!(next(&mut sub).await, Some(i.into()));
+        }
+        assert_eq!(next(&mut sub).await, None);
+    }
+}
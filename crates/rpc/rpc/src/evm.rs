@@ -0,0 +1,69 @@
+use alloy_primitives::U256;
+use alloy_rpc_types_anvil::MineOptions;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_engine_local::LocalMinerHandle;
+use reth_rpc_api::GanacheApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+
+/// `evm` API implementation.
+///
+/// This type provides the dev mode functionality for handling `evm` related requests, like
+/// snapshotting and reverting the chain or controlling the clock of the local miner.
+#[derive(Clone, Debug)]
+pub struct EvmApi {
+    /// Handle to the local miner of the dev chain.
+    miner: LocalMinerHandle,
+}
+
+impl EvmApi {
+    /// Creates a new instance of `EvmApi`.
+    pub const fn new(miner: LocalMinerHandle) -> Self {
+        Self { miner }
+    }
+}
+
+#[async_trait]
+impl GanacheApiServer for EvmApi {
+    /// Handler for `evm_increaseTime`
+    async fn evm_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        let seconds =
+            u64::try_from(seconds).map_err(|_| invalid_params_rpc_err("seconds too large"))?;
+        self.miner.increase_time(seconds).await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `evm_mine`
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> RpcResult<String> {
+        let (mut timestamp, blocks) = match opts {
+            Some(MineOptions::Options { timestamp, blocks }) => (timestamp, blocks.unwrap_or(1)),
+            Some(MineOptions::Timestamp(timestamp)) => (timestamp, 1),
+            None => (None, 1),
+        };
+
+        for _ in 0..blocks {
+            // only the first block is mined with the given timestamp
+            self.miner
+                .mine(timestamp.take())
+                .await
+                .map_err(|err| internal_rpc_err(err.to_string()))?;
+        }
+
+        Ok("0x0".to_string())
+    }
+
+    /// Handler for `evm_revert`
+    async fn evm_revert(&self, snapshot_id: U256) -> RpcResult<bool> {
+        self.miner.revert(snapshot_id).await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `evm_setTime`
+    async fn evm_set_time(&self, timestamp: u64) -> RpcResult<bool> {
+        self.miner.set_time(timestamp).await.map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(true)
+    }
+
+    /// Handler for `evm_snapshot`
+    async fn evm_snapshot(&self) -> RpcResult<U256> {
+        self.miner.snapshot().await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
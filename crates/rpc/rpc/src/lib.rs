@@ -36,6 +36,7 @@ mod admin;
 mod debug;
 mod engine;
 pub mod eth;
+mod evm;
 mod miner;
 mod net;
 mod otterscan;
@@ -46,14 +47,15 @@ mod txpool;
 mod validation;
 mod web3;
 
-pub use admin::AdminApi;
+pub use admin::{AdminApi, AdminEngineApi};
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
+pub use evm::EvmApi;
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{RethApi, RethFeeApi};
 pub use reth_rpc_convert::RpcTypes;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
@@ -1,21 +1,41 @@
 use std::{collections::HashMap, future::Future, sync::Arc};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_eips::{eip7840::BlobParams, BlockId, BlockNumberOrTag, Encodable2718};
+use alloy_primitives::{Address, TxHash, U128, U256, U64};
 use async_trait::async_trait;
 use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use jsonrpsee_types::ErrorObject;
 use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_errors::RethResult;
-use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::RethApiServer;
+use reth_primitives_traits::{Block, BlockBody, NodePrimitives, SignedTransaction};
+use reth_rpc_api::{
+    BlobFeeHistory, BlobFeeHistoryEntry, BlockField, BlockFields, PruneRunStatus,
+    PruneSegmentStatus, PruneStatus, ReceiptWithProof, RethApiServer, RethFeeApiServer,
+    SenderTransactionsPage, TransactionWithProof,
+};
+use reth_rpc_eth_api::{
+    helpers::{EthFees, LoadFee, SpawnBlocking},
+    FromEthApiError, RpcNodeCore,
+};
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_rpc_server_types::result::internal_rpc_err;
-use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_storage_api::{
+    BlockIdReader, BlockReaderIdExt, ChangeSetReader, HeaderProvider, PruneCheckpointReader,
+    SenderTransactionsProvider, StateProviderFactory,
+};
 use reth_tasks::TaskSpawner;
+use reth_trie_common::root::ordered_trie_root_with_proof;
 use tokio::sync::oneshot;
 
+/// The default number of transactions returned by `reth_getTransactionsBySender`.
+const DEFAULT_SENDER_TRANSACTIONS_PAGE_SIZE: u64 = 100;
+
+/// The maximum number of transactions returned by `reth_getTransactionsBySender`.
+const MAX_SENDER_TRANSACTIONS_PAGE_SIZE: u64 = 1_000;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -40,7 +60,12 @@ impl<Provider> RethApi<Provider> {
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + SenderTransactionsProvider
+        + StateProviderFactory
+        + PruneCheckpointReader
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -88,6 +113,235 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns a page of the transactions sent by `sender`, starting after `cursor`.
+    pub async fn transactions_by_sender(
+        &self,
+        sender: Address,
+        cursor: Option<u64>,
+        limit: Option<u64>,
+    ) -> EthResult<SenderTransactionsPage> {
+        self.on_blocking_task(|this| async move {
+            this.try_transactions_by_sender(sender, cursor, limit)
+        })
+        .await
+    }
+
+    fn try_transactions_by_sender(
+        &self,
+        sender: Address,
+        cursor: Option<u64>,
+        limit: Option<u64>,
+    ) -> EthResult<SenderTransactionsPage> {
+        let limit = limit.unwrap_or(DEFAULT_SENDER_TRANSACTIONS_PAGE_SIZE);
+        if limit == 0 || limit > MAX_SENDER_TRANSACTIONS_PAGE_SIZE {
+            return Err(EthApiError::InvalidParams(format!(
+                "limit must be between 1 and {MAX_SENDER_TRANSACTIONS_PAGE_SIZE}"
+            )))
+        }
+        let limit = limit as usize;
+
+        // Fetch one more id than requested to find out if there is a next page.
+        let mut ids = self.provider().transaction_ids_by_sender(sender, cursor, limit + 1)?;
+        let next_cursor = if ids.len() > limit {
+            ids.truncate(limit);
+            ids.last().copied().map(U64::from)
+        } else {
+            None
+        };
+
+        let transactions = ids
+            .into_iter()
+            .map(|id| {
+                self.provider()
+                    .transaction_by_id_unhashed(id)?
+                    .map(|tx| *tx.tx_hash())
+                    .ok_or(EthApiError::TransactionNotFound)
+            })
+            .collect::<EthResult<Vec<_>>>()?;
+
+        Ok(SenderTransactionsPage { transactions, cursor: next_cursor })
+    }
+
+    /// Returns the transaction with the given hash and its inclusion proof against the
+    /// transactions root of its block.
+    pub async fn transaction_with_proof(
+        &self,
+        hash: TxHash,
+    ) -> EthResult<Option<TransactionWithProof>> {
+        self.on_blocking_task(|this| async move { this.try_transaction_with_proof(hash) }).await
+    }
+
+    fn try_transaction_with_proof(&self, hash: TxHash) -> EthResult<Option<TransactionWithProof>> {
+        let Some((_, meta)) = self.provider().transaction_by_hash_with_meta(hash)? else {
+            return Ok(None)
+        };
+        let block_id = BlockId::from(meta.block_hash);
+        let header = self
+            .provider()
+            .header(&meta.block_hash)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let transactions = self
+            .provider()
+            .transactions_by_block(meta.block_hash.into())?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+
+        let index = meta.index as usize;
+        let (root, proof) =
+            ordered_trie_root_with_proof(&transactions, index, |tx, buf| tx.encode_2718(buf))
+                .ok_or(EthApiError::TransactionNotFound)?;
+        if root != header.transactions_root() {
+            return Err(EthApiError::Unsupported("transactions root of the block can't be proven"))
+        }
+
+        Ok(Some(TransactionWithProof {
+            block_hash: meta.block_hash,
+            block_number: U64::from(meta.block_number),
+            transaction_index: U64::from(meta.index),
+            transactions_root: root,
+            transaction: transactions[index].encoded_2718().into(),
+            proof,
+        }))
+    }
+
+    /// Returns the receipt of the transaction with the given hash and its inclusion proof against
+    /// the receipts root of its block.
+    pub async fn receipt_with_proof(&self, hash: TxHash) -> EthResult<Option<ReceiptWithProof>> {
+        self.on_blocking_task(|this| async move { this.try_receipt_with_proof(hash) }).await
+    }
+
+    fn try_receipt_with_proof(&self, hash: TxHash) -> EthResult<Option<ReceiptWithProof>> {
+        let Some((_, meta)) = self.provider().transaction_by_hash_with_meta(hash)? else {
+            return Ok(None)
+        };
+        let block_id = BlockId::from(meta.block_hash);
+        let header = self
+            .provider()
+            .header(&meta.block_hash)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = self
+            .provider()
+            .receipts_by_block(meta.block_hash.into())?
+            .ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+
+        let index = meta.index as usize;
+        let (root, proof) = ordered_trie_root_with_proof(&receipts, index, |receipt, buf| {
+            receipt.with_bloom_ref().encode_2718(buf)
+        })
+        .ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+        // the receipts root of pre-Byzantium blocks commits to intermediate state roots, which
+        // aren't stored
+        if root != header.receipts_root() {
+            return Err(EthApiError::Unsupported("receipts root of the block can't be proven"))
+        }
+
+        Ok(Some(ReceiptWithProof {
+            block_hash: meta.block_hash,
+            block_number: U64::from(meta.block_number),
+            transaction_index: U64::from(meta.index),
+            receipts_root: root,
+            receipt: receipts[index].with_bloom_ref().encoded_2718().into(),
+            proof,
+        }))
+    }
+
+    /// Returns the requested fields of the given block.
+    pub async fn block_fields(
+        &self,
+        block_id: BlockId,
+        fields: Vec<BlockField>,
+    ) -> EthResult<Option<BlockFields>> {
+        self.on_blocking_task(|this| async move { this.try_block_fields(block_id, &fields) }).await
+    }
+
+    fn try_block_fields(
+        &self,
+        block_id: BlockId,
+        fields: &[BlockField],
+    ) -> EthResult<Option<BlockFields>> {
+        let Some(header) = self.provider().sealed_header_by_id(block_id)? else { return Ok(None) };
+
+        // header fields don't require decoding the body
+        let block = if fields.iter().any(BlockField::is_body_field) {
+            let Some(block) = self.provider().block_by_hash(header.hash())? else {
+                return Ok(None)
+            };
+            Some(block)
+        } else {
+            None
+        };
+
+        let mut result = BlockFields::default();
+        for field in fields {
+            match field {
+                BlockField::Hash => result.hash = Some(header.hash()),
+                BlockField::ParentHash => result.parent_hash = Some(header.parent_hash()),
+                BlockField::Number => result.number = Some(U64::from(header.number())),
+                BlockField::Timestamp => result.timestamp = Some(U64::from(header.timestamp())),
+                BlockField::GasUsed => result.gas_used = Some(U64::from(header.gas_used())),
+                BlockField::GasLimit => result.gas_limit = Some(U64::from(header.gas_limit())),
+                BlockField::BaseFeePerGas => {
+                    result.base_fee_per_gas = header.base_fee_per_gas().map(U64::from)
+                }
+                BlockField::Miner => result.miner = Some(header.beneficiary()),
+                BlockField::StateRoot => result.state_root = Some(header.state_root()),
+                BlockField::TransactionsRoot => {
+                    result.transactions_root = Some(header.transactions_root())
+                }
+                BlockField::ReceiptsRoot => result.receipts_root = Some(header.receipts_root()),
+                BlockField::BlobGasUsed => {
+                    result.blob_gas_used = header.blob_gas_used().map(U64::from)
+                }
+                BlockField::ExcessBlobGas => {
+                    result.excess_blob_gas = header.excess_blob_gas().map(U64::from)
+                }
+                BlockField::WithdrawalsRoot => result.withdrawals_root = header.withdrawals_root(),
+                BlockField::Withdrawals => {
+                    result.withdrawals = block
+                        .as_ref()
+                        .and_then(|block| block.body().withdrawals())
+                        .map(|withdrawals| withdrawals.to_vec())
+                }
+                BlockField::Transactions => {
+                    result.transactions = block.as_ref().map(|block| {
+                        block.body().transactions().iter().map(|tx| *tx.tx_hash()).collect()
+                    })
+                }
+            }
+        }
+
+        Ok(Some(result))
+    }
+
+    /// Returns the pruning status of every segment that has been pruned at least once.
+    pub async fn prune_status(&self) -> EthResult<PruneStatus> {
+        self.on_blocking_task(|this| async move { this.try_prune_status() }).await
+    }
+
+    fn try_prune_status(&self) -> EthResult<PruneStatus> {
+        let tip = self.provider().best_block_number()?;
+        let mut runs = self.provider().get_prune_runs()?.into_iter().collect::<HashMap<_, _>>();
+
+        let segments = self
+            .provider()
+            .get_prune_checkpoints()?
+            .into_iter()
+            .map(|(segment, checkpoint)| PruneSegmentStatus {
+                segment,
+                mode: checkpoint.prune_mode,
+                checkpoint: checkpoint.block_number.map(U64::from),
+                lag: checkpoint.block_number.map(|block| U64::from(tip.saturating_sub(block))),
+                last_run: runs.remove(&segment).map(|run| PruneRunStatus {
+                    tip: U64::from(run.tip_block_number),
+                    duration_ms: U64::from(run.elapsed_ms),
+                    deleted_entries: U64::from(run.pruned),
+                    finished: run.finished,
+                }),
+            })
+            .collect();
+
+        Ok(PruneStatus { tip: U64::from(tip), segments })
+    }
 }
 
 #[async_trait]
@@ -95,7 +349,9 @@ impl<Provider> RethApiServer for RethApi<Provider>
 where
     Provider: BlockReaderIdExt
         + ChangeSetReader
+        + SenderTransactionsProvider
         + StateProviderFactory
+        + PruneCheckpointReader
         + CanonStateSubscriptions
         + 'static,
 {
@@ -107,6 +363,52 @@ where
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
 
+    /// Handler for `reth_getTransactionsBySender`
+    async fn reth_get_transactions_by_sender(
+        &self,
+        sender: Address,
+        cursor: Option<U64>,
+        limit: Option<U64>,
+    ) -> RpcResult<SenderTransactionsPage> {
+        Ok(Self::transactions_by_sender(
+            self,
+            sender,
+            cursor.map(|cursor| cursor.to()),
+            limit.map(|limit| limit.to()),
+        )
+        .await?)
+    }
+
+    /// Handler for `reth_getTransactionWithProof`
+    async fn reth_get_transaction_with_proof(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<TransactionWithProof>> {
+        Ok(Self::transaction_with_proof(self, hash).await?)
+    }
+
+    /// Handler for `reth_getReceiptWithProof`
+    async fn reth_get_receipt_with_proof(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<ReceiptWithProof>> {
+        Ok(Self::receipt_with_proof(self, hash).await?)
+    }
+
+    /// Handler for `reth_getBlockFields`
+    async fn reth_get_block_fields(
+        &self,
+        block_id: BlockId,
+        fields: Vec<BlockField>,
+    ) -> RpcResult<Option<BlockFields>> {
+        Ok(Self::block_fields(self, block_id, fields).await?)
+    }
+
+    /// Handler for `reth_pruneStatus`
+    async fn reth_prune_status(&self) -> RpcResult<PruneStatus> {
+        Ok(Self::prune_status(self).await?)
+    }
+
     /// Handler for `reth_subscribeChainNotifications`
     async fn reth_subscribe_chain_notifications(
         &self,
@@ -122,6 +424,145 @@ where
     }
 }
 
+/// `reth` API implementation of the fee market methods, served by the `eth` API.
+///
+/// This shares the fee history cache of `eth_feeHistory`.
+#[derive(Debug, Clone)]
+pub struct RethFeeApi<Eth> {
+    eth_api: Eth,
+}
+
+impl<Eth> RethFeeApi<Eth> {
+    /// Create a new instance of the [`RethFeeApi`]
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+}
+
+impl<Eth: EthFees> RethFeeApi<Eth> {
+    /// Returns the blob fee market of every block in the given inclusive range.
+    pub async fn blob_fee_history(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> Result<BlobFeeHistory, Eth::Error> {
+        let from = self.block_number(from_block)?;
+        let to = self.block_number(to_block)?;
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange.into())
+        }
+
+        let max_blocks = self.eth_api.gas_oracle().config().max_header_history;
+        if to - from >= max_blocks {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the maximum of {max_blocks} blocks"
+            ))
+            .into())
+        }
+        let block_count = (to - from + 1) as usize;
+
+        // Serve from the fee history cache if it covers the range, otherwise read the headers
+        let (blocks, next_blob_base_fee) =
+            if let Some(entries) = self.eth_api.fee_history_cache().get_history(from, to).await {
+                if entries.len() != block_count {
+                    return Err(EthApiError::InvalidBlockRange.into())
+                }
+                let last = entries.last().expect("is not empty");
+                (
+                    entries
+                        .iter()
+                        .map(|entry| blob_fee_history_entry(&entry.header, entry.blob_params))
+                        .collect(),
+                    next_blob_base_fee(&last.header, last.blob_params),
+                )
+            } else {
+                self.eth_api
+                    .spawn_blocking_io(move |eth_api| {
+                        let provider = eth_api.provider();
+                        let headers = provider
+                            .sealed_headers_range(from..=to)
+                            .map_err(Eth::Error::from_eth_err)?;
+                        if headers.len() != block_count {
+                            return Err(EthApiError::InvalidBlockRange.into())
+                        }
+                        let chain_spec = provider.chain_spec();
+                        let headers = headers
+                            .iter()
+                            .map(|header| {
+                                let blob_params =
+                                    chain_spec.blob_params_at_timestamp(header.timestamp());
+                                (header.header(), blob_params)
+                            })
+                            .collect::<Vec<_>>();
+                        let (last, last_blob_params) = *headers.last().expect("is not empty");
+                        Ok((
+                            headers
+                                .iter()
+                                .map(|(header, blob_params)| {
+                                    blob_fee_history_entry(*header, *blob_params)
+                                })
+                                .collect(),
+                            next_blob_base_fee(last, last_blob_params),
+                        ))
+                    })
+                    .await?
+            };
+
+        Ok(BlobFeeHistory {
+            oldest_block: U64::from(from),
+            blocks,
+            next_blob_base_fee: next_blob_base_fee.map(U128::from),
+        })
+    }
+
+    /// Resolves the block number of the given tag. The pending block resolves to the latest one.
+    fn block_number(&self, block: BlockNumberOrTag) -> Result<u64, Eth::Error> {
+        let block = if block.is_pending() { BlockNumberOrTag::Latest } else { block };
+        self.eth_api
+            .provider()
+            .block_number_for_id(block.into())
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or_else(|| EthApiError::HeaderNotFound(block.into()).into())
+    }
+}
+
+/// Returns the blob fee market of the block with the given header.
+fn blob_fee_history_entry<H: BlockHeader>(
+    header: &H,
+    blob_params: Option<BlobParams>,
+) -> BlobFeeHistoryEntry {
+    BlobFeeHistoryEntry {
+        number: U64::from(header.number()),
+        blob_base_fee: blob_params.and_then(|params| header.blob_fee(params)).map(U128::from),
+        blob_gas_used: header.blob_gas_used().map(U64::from),
+        excess_blob_gas: header.excess_blob_gas().map(U64::from),
+        blob_gas_used_ratio: blob_params.map_or(0.0, |params| {
+            header.blob_gas_used().unwrap_or_default() as f64 /
+                params.max_blob_gas_per_block() as f64
+        }),
+    }
+}
+
+/// Returns the blob base fee of the block following the block with the given header.
+fn next_blob_base_fee<H: BlockHeader>(header: &H, blob_params: Option<BlobParams>) -> Option<u128> {
+    header.next_block_blob_fee(blob_params?)
+}
+
+#[async_trait]
+impl<Eth> RethFeeApiServer for RethFeeApi<Eth>
+where
+    Eth: EthFees + 'static,
+{
+    /// Handler for `reth_blobFeeHistory`
+    async fn reth_blob_fee_history(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<BlobFeeHistory> {
+        Ok(self.blob_fee_history(from_block, to_block).await.map_err(Into::into)?)
+    }
+}
+
 /// Pipes all stream items to the subscription sink.
 async fn pipe_from_stream<N: NodePrimitives>(
     sink: SubscriptionSink,
@@ -170,3 +611,169 @@ struct RethApiInner<Provider> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApiBuilder;
+    use alloy_consensus::Header;
+    use alloy_primitives::B256;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives_traits::SealedBlock;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    /// Timestamp of the Cancun activation on mainnet.
+    const CANCUN_TIMESTAMP: u64 = 1_710_338_135;
+
+    fn reth_api(provider: MockEthProvider) -> RethApi<MockEthProvider> {
+        RethApi::new(provider, Box::new(TokioTaskExecutor::default()))
+    }
+
+    fn random_block_with_body() -> SealedBlock<reth_ethereum_primitives::Block> {
+        let mut rng = generators::rng();
+        random_block(
+            &mut rng,
+            1,
+            BlockParams { tx_count: Some(2), withdrawals_count: Some(3), ..Default::default() },
+        )
+    }
+
+    #[tokio::test]
+    async fn block_fields_from_header() {
+        let block = random_block_with_body();
+        let provider = MockEthProvider::default();
+        // the block is only found by number, so reading its body by hash would fail
+        provider.add_block(B256::random(), block.clone_block());
+        let api = reth_api(provider);
+
+        let fields = vec![
+            BlockField::Hash,
+            BlockField::Number,
+            BlockField::GasUsed,
+            BlockField::StateRoot,
+            BlockField::WithdrawalsRoot,
+        ];
+        let result = api.block_fields(BlockId::number(1), fields).await.unwrap().unwrap();
+        assert_eq!(
+            result,
+            BlockFields {
+                hash: Some(block.hash()),
+                number: Some(U64::from(1)),
+                gas_used: Some(U64::from(block.header().gas_used)),
+                state_root: Some(block.header().state_root),
+                withdrawals_root: block.header().withdrawals_root,
+                ..Default::default()
+            }
+        );
+
+        // requesting a body field reads the body, which isn't found
+        let result = api.block_fields(BlockId::number(1), vec![BlockField::Transactions]).await;
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn block_fields_from_body() {
+        let block = random_block_with_body();
+        let provider = MockEthProvider::default();
+        provider.add_block(block.hash(), block.clone_block());
+        let api = reth_api(provider);
+
+        let fields = vec![BlockField::Number, BlockField::Transactions, BlockField::Withdrawals];
+        let result = api.block_fields(BlockId::hash(block.hash()), fields).await.unwrap().unwrap();
+        assert_eq!(
+            result,
+            BlockFields {
+                number: Some(U64::from(1)),
+                transactions: Some(
+                    block.body().transactions.iter().map(|tx| *tx.tx_hash()).collect()
+                ),
+                withdrawals: block.body().withdrawals.as_ref().map(|w| w.to_vec()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(result.transactions.unwrap().len(), 2);
+        assert_eq!(result.withdrawals.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn block_fields_of_unknown_block() {
+        let api = reth_api(MockEthProvider::default());
+
+        for fields in [vec![BlockField::Hash], vec![BlockField::Hash, BlockField::Transactions]] {
+            assert_eq!(api.block_fields(BlockId::number(1), fields.clone()).await.unwrap(), None);
+            assert_eq!(
+                api.block_fields(BlockId::hash(B256::random()), fields).await.unwrap(),
+                None
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn blob_fee_history_from_headers() {
+        let provider = MockEthProvider::default();
+        let headers = (0..4u64)
+            .map(|number| Header {
+                number,
+                timestamp: CANCUN_TIMESTAMP + number * 12,
+                blob_gas_used: Some(number * 131_072),
+                excess_blob_gas: Some(number * 1_000_000),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        for header in &headers {
+            provider.add_header(B256::with_last_byte(header.number as u8), header.clone());
+        }
+
+        let eth_api = EthApiBuilder::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build();
+        let api = RethFeeApi::new(eth_api);
+
+        let history =
+            api.blob_fee_history(BlockNumberOrTag::Number(1), BlockNumberOrTag::Number(3)).await;
+        let history = history.unwrap();
+        let chain_spec = provider.chain_spec();
+        let expected = headers[1..]
+            .iter()
+            .map(|header| {
+                blob_fee_history_entry(
+                    header,
+                    chain_spec.blob_params_at_timestamp(header.timestamp),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(history.oldest_block, U64::from(1));
+        assert_eq!(history.blocks, expected);
+        assert!(history.blocks.iter().all(|entry| entry.blob_base_fee.is_some()));
+        assert_eq!(
+            history.blocks.iter().map(|entry| entry.number).collect::<Vec<_>>(),
+            vec![U64::from(1), U64::from(2), U64::from(3)]
+        );
+        assert_eq!(
+            history.next_blob_base_fee,
+            next_blob_base_fee(
+                &headers[3],
+                chain_spec.blob_params_at_timestamp(headers[3].timestamp)
+            )
+            .map(U128::from)
+        );
+
+        // reversed and missing ranges are rejected
+        assert!(api
+            .blob_fee_history(BlockNumberOrTag::Number(3), BlockNumberOrTag::Number(1))
+            .await
+            .is_err());
+        assert!(api
+            .blob_fee_history(BlockNumberOrTag::Number(2), BlockNumberOrTag::Number(5))
+            .await
+            .is_err());
+    }
+}
@@ -2,18 +2,18 @@ use core::fmt;
 use std::collections::BTreeMap;
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash, U128, U64};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::TxPoolApiServer;
+use reth_rpc_api::{TxPoolApiServer, TxpoolParkedReason, TxpoolStatusDetail, TxpoolSubPool};
 use reth_rpc_convert::{RpcConvert, RpcTypes};
 use reth_rpc_eth_api::RpcTransaction;
 use reth_transaction_pool::{
-    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
+    AllPoolTransactions, ParkedReason, PoolConsensusTx, PoolTransaction, SubPool, TransactionPool,
 };
 use tracing::trace;
 
@@ -147,6 +147,53 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content().map_err(Into::into)?)
     }
+
+    /// Returns the sub-pool of the transaction and the reasons why it is not pending.
+    ///
+    /// Handler for `txpool_statusDetail`
+    async fn txpool_status_detail(&self, hash: TxHash) -> RpcResult<Option<TxpoolStatusDetail>> {
+        trace!(target: "rpc::eth", ?hash, "Serving txpool_statusDetail");
+        Ok(self.pool.transaction_status_detail(&hash).map(|detail| TxpoolStatusDetail {
+            subpool: match detail.subpool {
+                SubPool::Pending => TxpoolSubPool::Pending,
+                SubPool::Queued => TxpoolSubPool::Queued,
+                SubPool::BaseFee => TxpoolSubPool::BaseFee,
+                SubPool::Blob => TxpoolSubPool::Blob,
+            },
+            reasons: detail.parked_reasons.into_iter().map(parked_reason).collect(),
+        }))
+    }
+}
+
+/// Converts a [`ParkedReason`] of the pool into its RPC representation.
+fn parked_reason(reason: ParkedReason) -> TxpoolParkedReason {
+    match reason {
+        ParkedReason::NonceGap { missing_nonce } => {
+            TxpoolParkedReason::NonceGap { missing_nonce: U64::from(missing_nonce) }
+        }
+        ParkedReason::ParkedAncestor => TxpoolParkedReason::ParkedAncestor,
+        ParkedReason::InsufficientBalance { balance, cost } => {
+            TxpoolParkedReason::InsufficientBalance { balance, cost }
+        }
+        ParkedReason::ExceedsBlockGasLimit { gas_limit, block_gas_limit } => {
+            TxpoolParkedReason::ExceedsBlockGasLimit {
+                gas_limit: U64::from(gas_limit),
+                block_gas_limit: U64::from(block_gas_limit),
+            }
+        }
+        ParkedReason::FeeCapBelowBaseFee { max_fee_per_gas, base_fee } => {
+            TxpoolParkedReason::FeeCapBelowBaseFee {
+                max_fee_per_gas: U128::from(max_fee_per_gas),
+                base_fee: U64::from(base_fee),
+            }
+        }
+        ParkedReason::BlobFeeCapBelowBlobFee { max_fee_per_blob_gas, blob_fee } => {
+            TxpoolParkedReason::BlobFeeCapBelowBlobFee {
+                max_fee_per_blob_gas: U128::from(max_fee_per_blob_gas),
+                blob_fee: U128::from(blob_fee),
+            }
+        }
+    }
 }
 
 impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
@@ -0,0 +1,19 @@
+use std::fmt::Debug;
+
+/// Diagnostic state of a stage, reported if the stage stalls.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StageHealth {
+    /// The number of items queued by the stage or its downloader, by queue name.
+    pub queue_depths: Vec<(&'static str, usize)>,
+}
+
+/// Provides the [`StageHealth`] of a stage, see
+/// [`Stage::health_check`](crate::Stage::health_check).
+///
+/// The health check is queried by the stall watchdog of the pipeline while the stage may be
+/// running, so it must not require access to the stage itself.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait HealthCheck: Debug + Send + Sync {
+    /// Returns the current diagnostic state of the stage.
+    fn health(&self) -> StageHealth;
+}
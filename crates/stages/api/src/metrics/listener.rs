@@ -32,6 +32,11 @@ pub enum MetricEvent {
         /// The duration of stage iteration including database commit.
         elapsed: Duration,
     },
+    /// Stage made no checkpoint progress for longer than the stall timeout.
+    StageStalled {
+        /// Stage ID.
+        stage_id: StageId,
+    },
 }
 
 /// Metrics routine that listens to new metric events on the `events_rx` receiver.
@@ -81,6 +86,9 @@ impl MetricsListener {
                     stage_metrics.entities_total.set(total as f64);
                 }
             }
+            MetricEvent::StageStalled { stage_id } => {
+                self.sync_metrics.get_stage_metrics(stage_id).stalls.increment(1);
+            }
         }
     }
 }
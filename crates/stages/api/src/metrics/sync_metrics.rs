@@ -1,5 +1,8 @@
 use crate::StageId;
-use reth_metrics::{metrics::Gauge, Metrics};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
 use std::collections::HashMap;
 
 #[derive(Debug, Default)]
@@ -29,4 +32,7 @@ pub(crate) struct StageMetrics {
     pub(crate) entities_total: Gauge,
     /// The number of seconds spent executing the stage and committing the data.
     pub(crate) total_elapsed: Gauge,
+    /// The number of times the stage made no checkpoint progress for longer than the stall
+    /// timeout.
+    pub(crate) stalls: Counter,
 }
@@ -1,7 +1,12 @@
-use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageId, StageSet};
+use crate::{
+    pipeline::{BoxedStage, StallWatchdog},
+    MetricEventsSender, Pipeline, Stage, StageId, StageSet,
+};
 use alloy_primitives::{BlockNumber, B256};
 use reth_provider::{providers::ProviderNodeTypes, DatabaseProviderFactory, ProviderFactory};
 use reth_static_file::StaticFileProducer;
+use reth_tokio_util::EventSender;
+use std::time::Duration;
 use tokio::sync::watch;
 
 /// Builds a [`Pipeline`].
@@ -15,6 +20,8 @@ pub struct PipelineBuilder<Provider> {
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
     fail_on_unwind: bool,
+    /// The duration after which a stage without checkpoint progress is reported as stalled.
+    stall_timeout: Option<Duration>,
 }
 
 impl<Provider> PipelineBuilder<Provider> {
@@ -69,6 +76,16 @@ impl<Provider> PipelineBuilder<Provider> {
         self
     }
 
+    /// Set the duration after which a stage that made no checkpoint progress is reported as
+    /// stalled.
+    ///
+    /// Stalls are logged with the diagnostic state of the stage, counted in the `sync.stalls`
+    /// metric and emitted as [`PipelineEvent::Stalled`](crate::PipelineEvent::Stalled).
+    pub const fn with_stall_timeout(mut self, timeout: Duration) -> Self {
+        self.stall_timeout = Some(timeout);
+        self
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     pub fn build<N>(
         self,
@@ -79,19 +96,23 @@ impl<Provider> PipelineBuilder<Provider> {
         N: ProviderNodeTypes,
         ProviderFactory<N>: DatabaseProviderFactory<ProviderRW = Provider>,
     {
-        let Self { stages, max_block, tip_tx, metrics_tx, fail_on_unwind } = self;
+        let Self { stages, max_block, tip_tx, metrics_tx, fail_on_unwind, stall_timeout } = self;
+        let event_sender = EventSender::default();
+        let watchdog = stall_timeout
+            .map(|timeout| StallWatchdog::spawn(timeout, event_sender.clone(), metrics_tx.clone()));
         Pipeline {
             provider_factory,
             stages,
             max_block,
             static_file_producer,
             tip_tx,
-            event_sender: Default::default(),
+            event_sender,
             progress: Default::default(),
             metrics_tx,
             fail_on_unwind,
             last_detached_head_unwind_target: None,
             detached_head_attempts: 0,
+            watchdog,
         }
     }
 }
@@ -104,6 +125,7 @@ impl<Provider> Default for PipelineBuilder<Provider> {
             tip_tx: None,
             metrics_tx: None,
             fail_on_unwind: false,
+            stall_timeout: None,
         }
     }
 }
@@ -114,6 +136,7 @@ impl<Provider> std::fmt::Debug for PipelineBuilder<Provider> {
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
             .field("fail_on_unwind", &self.fail_on_unwind)
+            .field("stall_timeout", &self.stall_timeout)
             .finish()
     }
 }
@@ -1,9 +1,13 @@
 use crate::{
     stage::{ExecOutput, UnwindInput, UnwindOutput},
-    StageCheckpoint, StageId,
+    StageCheckpoint, StageHealth, StageId,
 };
 use alloy_primitives::BlockNumber;
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+    time::Duration,
+};
 
 /// An event emitted by a [Pipeline][crate::Pipeline].
 ///
@@ -73,6 +77,31 @@ pub enum PipelineEvent {
         /// The stage that was skipped.
         stage_id: StageId,
     },
+    /// Emitted when a stage made no checkpoint progress for longer than the stall timeout, see
+    /// [`PipelineBuilder::with_stall_timeout`](crate::PipelineBuilder::with_stall_timeout).
+    ///
+    /// This is emitted repeatedly, once per stall timeout, until the stage makes progress.
+    Stalled {
+        /// The stage that stalled.
+        stage_id: StageId,
+        /// The diagnostic state of the stage.
+        report: StallReport,
+    },
+}
+
+/// Diagnostic state of a stage that made no checkpoint progress for longer than the stall timeout.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StallReport {
+    /// The checkpoint of the stage.
+    pub checkpoint: Option<StageCheckpoint>,
+    /// The block range the stage is executing, if it has been prepared.
+    pub batch: Option<RangeInclusive<BlockNumber>>,
+    /// The time since the stage started or last made progress.
+    pub stalled_for: Duration,
+    /// The last error the stage encountered since it last made progress.
+    pub last_error: Option<String>,
+    /// The diagnostic state reported by the [`HealthCheck`](crate::HealthCheck) of the stage.
+    pub health: Option<StageHealth>,
 }
 
 /// Pipeline stages progress.
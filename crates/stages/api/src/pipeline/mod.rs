@@ -24,6 +24,7 @@ use tracing::*;
 mod builder;
 mod progress;
 mod set;
+mod watchdog;
 
 use crate::{
     BlockErrorKind, ExecInput, ExecOutput, MetricEvent, MetricEventsSender, PipelineError, Stage,
@@ -33,6 +34,7 @@ pub use builder::*;
 use progress::*;
 use reth_errors::RethResult;
 pub use set::*;
+use watchdog::StallWatchdog;
 
 /// A container for a queued stage.
 pub(crate) type BoxedStage<DB> = Box<dyn Stage<DB>>;
@@ -92,6 +94,8 @@ pub struct Pipeline<N: ProviderNodeTypes> {
     /// Number of consecutive unwind attempts due to [`StageError::DetachedHead`] for the current
     /// fork.
     detached_head_attempts: u64,
+    /// Reports stages that make no checkpoint progress, if a stall timeout is configured.
+    watchdog: Option<StallWatchdog>,
 }
 
 impl<N: ProviderNodeTypes> Pipeline<N> {
@@ -228,8 +232,16 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
             let stage = &self.stages[stage_index];
             let stage_id = stage.id();
 
+            if let Some(watchdog) = &self.watchdog {
+                watchdog.on_stage_started(stage_id, stage.health_check());
+            }
+
             trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
-            let next = self.execute_stage_to_completion(previous_stage, stage_index).await?;
+            let next = self.execute_stage_to_completion(previous_stage, stage_index).await;
+            if let Some(watchdog) = &self.watchdog {
+                watchdog.on_stage_finished();
+            }
+            let next = next?;
 
             trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
 
@@ -442,6 +454,9 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
             }
 
             let exec_input = ExecInput { target, checkpoint: prev_checkpoint };
+            if let Some(watchdog) = &self.watchdog {
+                watchdog.on_input(exec_input);
+            }
 
             self.event_sender.notify(PipelineEvent::Prepare {
                 pipeline_stages_progress: PipelineStagesProgress {
@@ -455,6 +470,9 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
             if let Err(err) = self.stage(stage_index).execute_ready(exec_input).await {
                 self.event_sender.notify(PipelineEvent::Error { stage_id });
+                if let Some(watchdog) = &self.watchdog {
+                    watchdog.on_error(&err);
+                }
                 match self.on_stage_error(stage_id, prev_checkpoint, err)? {
                     Some(ctrl) => return Ok(ctrl),
                     None => continue,
@@ -481,6 +499,9 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
                     // Commit processed data to the database.
                     UnifiedStorageWriter::commit(provider_rw)?;
+                    if let Some(watchdog) = &self.watchdog {
+                        watchdog.on_checkpoint(checkpoint);
+                    }
 
                     // Invoke stage post commit hook.
                     self.stage(stage_index).post_execute_commit()?;
@@ -517,6 +538,9 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                 Err(err) => {
                     drop(provider_rw);
                     self.event_sender.notify(PipelineEvent::Error { stage_id });
+                    if let Some(watchdog) = &self.watchdog {
+                        watchdog.on_error(&err);
+                    }
 
                     if let Some(ctrl) = self.on_stage_error(stage_id, prev_checkpoint, err)? {
                         return Ok(ctrl)
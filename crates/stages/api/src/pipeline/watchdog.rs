@@ -0,0 +1,209 @@
+use crate::{
+    ExecInput, HealthCheck, MetricEvent, MetricEventsSender, PipelineEvent, StageCheckpoint,
+    StageId, StallReport,
+};
+use reth_tokio_util::EventSender;
+use std::{
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+use tracing::*;
+
+/// The maximum interval between two stall checks.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Detects stages that make no checkpoint progress for longer than the stall timeout.
+///
+/// The pipeline reports the progress of the running stage to the watchdog, which is checked by a
+/// background thread. This is necessary because the pipeline thread is blocked by the stage while
+/// it stalls.
+///
+/// The background thread exits once the watchdog is dropped.
+#[derive(Debug)]
+pub(crate) struct StallWatchdog {
+    running: Arc<Mutex<Option<RunningStage>>>,
+}
+
+impl StallWatchdog {
+    /// Spawns the background thread that reports a stalled stage once per `timeout`.
+    pub(crate) fn spawn(
+        timeout: Duration,
+        event_sender: EventSender<PipelineEvent>,
+        metrics_tx: Option<MetricEventsSender>,
+    ) -> Self {
+        let running = Arc::new(Mutex::new(None));
+        let weak = Arc::downgrade(&running);
+
+        let spawned = std::thread::Builder::new()
+            .name("pipeline-watchdog".to_string())
+            .spawn(move || watch(weak, timeout, event_sender, metrics_tx));
+        if let Err(err) = spawned {
+            warn!(target: "sync::pipeline", %err, "Failed to spawn stall watchdog");
+        }
+
+        Self { running }
+    }
+
+    /// Starts watching the stage.
+    pub(crate) fn on_stage_started(
+        &self,
+        stage_id: StageId,
+        health_check: Option<Arc<dyn HealthCheck>>,
+    ) {
+        *self.lock() = Some(RunningStage {
+            stage_id,
+            checkpoint: None,
+            input: None,
+            last_error: None,
+            health_check,
+            last_progress: Instant::now(),
+            last_report: None,
+        });
+    }
+
+    /// Records the input the running stage is prepared to execute.
+    pub(crate) fn on_input(&self, input: ExecInput) {
+        if let Some(stage) = self.lock().as_mut() {
+            stage.checkpoint = input.checkpoint;
+            stage.input = Some(input);
+        }
+    }
+
+    /// Records the checkpoint of the running stage, resetting the stall timer if it changed.
+    pub(crate) fn on_checkpoint(&self, checkpoint: StageCheckpoint) {
+        if let Some(stage) = self.lock().as_mut() {
+            if stage.checkpoint != Some(checkpoint) {
+                stage.checkpoint = Some(checkpoint);
+                stage.last_error = None;
+                stage.last_progress = Instant::now();
+                stage.last_report = None;
+            }
+        }
+    }
+
+    /// Records an error of the running stage.
+    pub(crate) fn on_error(&self, err: &impl std::fmt::Display) {
+        if let Some(stage) = self.lock().as_mut() {
+            stage.last_error = Some(err.to_string());
+        }
+    }
+
+    /// Stops watching the running stage.
+    pub(crate) fn on_stage_finished(&self) {
+        self.lock().take();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<RunningStage>> {
+        self.running.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// The stage that is currently executed by the pipeline.
+#[derive(Debug)]
+struct RunningStage {
+    stage_id: StageId,
+    checkpoint: Option<StageCheckpoint>,
+    input: Option<ExecInput>,
+    last_error: Option<String>,
+    health_check: Option<Arc<dyn HealthCheck>>,
+    /// The instant the stage started or last made progress.
+    last_progress: Instant,
+    /// The instant the stall was last reported.
+    last_report: Option<Instant>,
+}
+
+impl RunningStage {
+    /// Returns the report if the stage stalled and the stall was not reported within `timeout`.
+    fn poll_stall(
+        &mut self,
+        timeout: Duration,
+    ) -> Option<(StageId, StallReport, Option<Arc<dyn HealthCheck>>)> {
+        let now = Instant::now();
+        let stalled_for = now.duration_since(self.last_progress);
+        if stalled_for < timeout ||
+            self.last_report.is_some_and(|reported| now.duration_since(reported) < timeout)
+        {
+            return None
+        }
+        self.last_report = Some(now);
+
+        let report = StallReport {
+            checkpoint: self.checkpoint,
+            batch: self.input.map(|input| input.next_block_range()),
+            stalled_for,
+            last_error: self.last_error.clone(),
+            health: None,
+        };
+        Some((self.stage_id, report, self.health_check.clone()))
+    }
+}
+
+/// Checks the running stage for stalls until the watchdog is dropped.
+fn watch(
+    running: Weak<Mutex<Option<RunningStage>>>,
+    timeout: Duration,
+    event_sender: EventSender<PipelineEvent>,
+    metrics_tx: Option<MetricEventsSender>,
+) {
+    loop {
+        std::thread::sleep(timeout.min(MAX_CHECK_INTERVAL));
+
+        let Some(running) = running.upgrade() else { return };
+        let stall = running
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .as_mut()
+            .and_then(|stage| stage.poll_stall(timeout));
+        drop(running);
+
+        let Some((stage_id, mut report, health_check)) = stall else { continue };
+        // Query the health check outside of the lock, so it can't block the pipeline.
+        report.health = health_check.map(|health_check| health_check.health());
+
+        warn!(
+            target: "sync::pipeline",
+            stage = %stage_id,
+            checkpoint = ?report.checkpoint.map(|checkpoint| checkpoint.block_number),
+            batch = ?report.batch,
+            stalled_for = ?report.stalled_for,
+            last_error = ?report.last_error,
+            queue_depths = ?report.health.as_ref().map(|health| &health.queue_depths),
+            "Stage made no progress"
+        );
+
+        if let Some(metrics_tx) = &metrics_tx {
+            let _ = metrics_tx.send(MetricEvent::StageStalled { stage_id });
+        }
+        event_sender.notify(PipelineEvent::Stalled { stage_id, report });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_stall_once_per_timeout() {
+        let timeout = Duration::from_millis(50);
+        let mut stage = RunningStage {
+            stage_id: StageId::Bodies,
+            checkpoint: Some(StageCheckpoint::new(10)),
+            input: Some(ExecInput { target: Some(20), checkpoint: Some(StageCheckpoint::new(10)) }),
+            last_error: Some("timeout".to_string()),
+            health_check: None,
+            last_progress: Instant::now(),
+            last_report: None,
+        };
+        assert!(stage.poll_stall(timeout).is_none());
+
+        std::thread::sleep(timeout);
+        let (stage_id, report, _) = stage.poll_stall(timeout).unwrap();
+        assert_eq!(stage_id, StageId::Bodies);
+        assert_eq!(report.batch, Some(11..=20));
+        assert_eq!(report.last_error.as_deref(), Some("timeout"));
+        assert!(stage.poll_stall(timeout).is_none());
+
+        std::thread::sleep(timeout);
+        assert!(stage.poll_stall(timeout).is_some());
+    }
+}
@@ -1,10 +1,11 @@
-use crate::{error::StageError, StageCheckpoint, StageId};
+use crate::{error::StageError, HealthCheck, StageCheckpoint, StageId};
 use alloy_primitives::{BlockNumber, TxNumber};
 use reth_provider::{BlockReader, ProviderError};
 use std::{
     cmp::{max, min},
     future::{poll_fn, Future},
     ops::{Range, RangeInclusive},
+    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -262,6 +263,12 @@ pub trait Stage<Provider>: Send + Sync {
     fn post_unwind_commit(&mut self) -> Result<(), StageError> {
         Ok(())
     }
+
+    /// Returns a [`HealthCheck`] that is queried to report the diagnostic state of the stage if it
+    /// stalls.
+    fn health_check(&self) -> Option<Arc<dyn HealthCheck>> {
+        None
+    }
 }
 
 /// [Stage] trait extension.
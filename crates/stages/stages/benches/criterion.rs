@@ -87,22 +87,36 @@ fn transaction_lookup(c: &mut Criterion, runtime: &Runtime) {
     let mut group = c.benchmark_group("Stages");
     // don't need to run each stage for that many times
     group.sample_size(10);
+
+    let (db, range) = setup::prepare_transaction_lookup(DEFAULT_NUM_BLOCKS);
+
+    // hashes of the whole range are sorted in memory
+    let stage =
+        TransactionLookupStage::new(TransactionLookupConfig::default(), EtlConfig::default(), None);
+    measure_stage(
+        runtime,
+        &mut group,
+        &db,
+        setup::stage_unwind,
+        stage,
+        range.clone(),
+        "TransactionLookup".to_string(),
+    );
+
+    // hashes are collected in chunks and sorted through ETL files
     let stage = TransactionLookupStage::new(
         TransactionLookupConfig { chunk_size: DEFAULT_NUM_BLOCKS },
         EtlConfig::default(),
         None,
     );
-
-    let db = setup::txs_testdata(DEFAULT_NUM_BLOCKS);
-
     measure_stage(
         runtime,
         &mut group,
         &db,
         setup::stage_unwind,
         stage,
-        0..=DEFAULT_NUM_BLOCKS,
-        "TransactionLookup".to_string(),
+        range,
+        "TransactionLookup-etl".to_string(),
     );
 }
 
@@ -33,7 +33,8 @@ pub fn prepare_account_hashing(
     (db, AccountHashingStage::default(), stage_range)
 }
 
-fn find_stage_range(db: &Path) -> RangeInclusive<BlockNumber> {
+/// Returns the range of blocks in the given database.
+pub(crate) fn find_stage_range(db: &Path) -> RangeInclusive<BlockNumber> {
     let mut stage_range = None;
     TestStageDB::new(db)
         .factory
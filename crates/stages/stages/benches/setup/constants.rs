@@ -1,2 +1,3 @@
 #![allow(unreachable_pub)]
 pub const ACCOUNT_HASHING_DB: &str = "ACCOUNT_HASHING_DB";
+pub const TRANSACTION_LOOKUP_DB: &str = "TRANSACTION_LOOKUP_DB";
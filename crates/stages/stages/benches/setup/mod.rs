@@ -1,5 +1,5 @@
 #![expect(unreachable_pub)]
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
 use itertools::concat;
 use reth_db::{test_utils::TempDatabase, Database, DatabaseEnv};
 use reth_db_api::{
@@ -20,7 +20,7 @@ use reth_testing_utils::generators::{
     random_eoa_accounts, BlockRangeParams,
 };
 use reth_trie::StateRoot;
-use std::{collections::BTreeMap, fs, path::Path};
+use std::{collections::BTreeMap, fs, ops::RangeInclusive, path::Path};
 use tokio::runtime::Handle;
 
 mod constants;
@@ -86,6 +86,23 @@ where
     provider.commit().unwrap();
 }
 
+/// Prepares a database for [`TransactionLookupStage`](reth_stages::stages::TransactionLookupStage).
+/// If the environment variable [`constants::TRANSACTION_LOOKUP_DB`] is set, it will use that one,
+/// e.g. a copy of a mainnet database, and will get the stage execution range from
+/// [`tables::BlockBodyIndices`]. Otherwise, it will use [`txs_testdata`].
+pub(crate) fn prepare_transaction_lookup(
+    num_blocks: u64,
+) -> (TestStageDB, RangeInclusive<BlockNumber>) {
+    match std::env::var(constants::TRANSACTION_LOOKUP_DB) {
+        Ok(db) => {
+            let path = Path::new(&db);
+            let range = find_stage_range(path);
+            (TestStageDB::new(path), range)
+        }
+        Err(_) => (txs_testdata(num_blocks), 0..=num_blocks),
+    }
+}
+
 // Helper for generating testdata for the benchmarks.
 // Returns the path to the database file.
 pub(crate) fn txs_testdata(num_blocks: u64) -> TestStageDB {
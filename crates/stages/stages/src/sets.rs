@@ -39,9 +39,9 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, EraImportSource, EraStage, ExecutionStage, FinishStage,
-        HeaderStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
-        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        HeaderStage, IndexAccountHistoryStage, IndexSenderTransactionsStage,
+        IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage, PruneStage,
+        SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -78,6 +78,7 @@ use tokio::sync::watch;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`IndexSenderTransactionsStage`] (if enabled)
 /// - [`PruneStage`] (execute)
 /// - [`FinishStage`]
 #[derive(Debug)]
@@ -419,6 +420,9 @@ where
 }
 
 /// A set containing all stages that do additional indexing for historical state.
+///
+/// The [`IndexSenderTransactionsStage`] is only included if it is enabled in the stage
+/// configuration.
 #[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct HistoryIndexingStages {
@@ -433,6 +437,7 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexSenderTransactionsStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -451,5 +456,11 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.account_history,
             ))
+            .add_stage_opt(self.stages_config.index_sender_transactions.enabled.then(|| {
+                IndexSenderTransactionsStage::new(
+                    self.stages_config.index_sender_transactions,
+                    self.stages_config.etl.clone(),
+                )
+            }))
     }
 }
@@ -1,4 +1,4 @@
-use super::missing_static_data_error;
+use super::{missing_static_data_error, QueueDepth};
 use futures_util::TryStreamExt;
 use reth_db_api::{
     cursor::DbCursorRO,
@@ -11,13 +11,14 @@ use reth_provider::{
     StaticFileProviderFactory, StatsReader, StorageLocation,
 };
 use reth_stages_api::{
-    EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
-    UnwindInput, UnwindOutput,
+    EntitiesCheckpoint, ExecInput, ExecOutput, HealthCheck, Stage, StageCheckpoint, StageError,
+    StageId, UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_errors::provider::ProviderResult;
 use std::{
     cmp::Ordering,
+    sync::Arc,
     task::{ready, Context, Poll},
 };
 use tracing::*;
@@ -58,12 +59,18 @@ pub struct BodyStage<D: BodyDownloader> {
     downloader: D,
     /// Block response buffer.
     buffer: Option<Vec<BlockResponse<D::Block>>>,
+    /// The number of blocks in the buffer, reported if the stage stalls.
+    buffered_blocks: Arc<QueueDepth>,
 }
 
 impl<D: BodyDownloader> BodyStage<D> {
     /// Create new bodies stage from downloader.
-    pub const fn new(downloader: D) -> Self {
-        Self { downloader, buffer: None }
+    pub fn new(downloader: D) -> Self {
+        Self {
+            downloader,
+            buffer: None,
+            buffered_blocks: Arc::new(QueueDepth::new("buffered_blocks")),
+        }
     }
 }
 
@@ -175,6 +182,7 @@ where
         // is a fatal error to prevent the pipeline from running forever.
         let response = match maybe_next_result {
             Some(Ok(downloaded)) => {
+                self.buffered_blocks.set(downloaded.len());
                 self.buffer = Some(downloaded);
                 Ok(())
             }
@@ -197,6 +205,7 @@ where
         debug!(target: "sync::stages::bodies", stage_progress = from_block, target = to_block, "Commencing sync");
 
         let buffer = self.buffer.take().ok_or(StageError::MissingDownloadBuffer)?;
+        self.buffered_blocks.set(0);
         trace!(target: "sync::stages::bodies", bodies_len = buffer.len(), "Writing blocks");
         let highest_block = buffer.last().map(|r| r.block_number()).unwrap_or(from_block);
 
@@ -228,6 +237,7 @@ where
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
         self.buffer.take();
+        self.buffered_blocks.set(0);
 
         ensure_consistency(provider, Some(input.unwind_to))?;
         provider.remove_bodies_above(input.unwind_to, StorageLocation::Both)?;
@@ -237,6 +247,10 @@ where
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
         })
     }
+
+    fn health_check(&self) -> Option<Arc<dyn HealthCheck>> {
+        Some(self.buffered_blocks.clone())
+    }
 }
 
 // TODO(alexey): ideally, we want to measure Bodies stage progress in bytes, but it's hard to know
@@ -1,8 +1,9 @@
+use crate::stages::utils::hashing_decision;
 use alloy_primitives::{keccak256, B256};
 use itertools::Itertools;
-use reth_config::config::{EtlConfig, HashingConfig};
+use reth_config::config::{EtlConfig, HashingConfig, HashingMode};
 use reth_db_api::{
-    cursor::{DbCursorRO, DbCursorRW},
+    cursor::{DbCursorRO, SortedAppendWriter},
     tables,
     transaction::{DbTx, DbTxMut},
     RawKey, RawTable, RawValue,
@@ -35,6 +36,11 @@ pub struct AccountHashingStage {
     /// The threshold (in number of blocks) for switching between incremental
     /// hashing and full storage hashing.
     pub clean_threshold: u64,
+    /// How to choose between incremental and full hashing below the clean threshold.
+    pub mode: HashingMode,
+    /// The percentage of plain state entries that the changesets need to exceed for full hashing
+    /// in [`HashingMode::Auto`].
+    pub clean_changeset_percent: u64,
     /// The maximum number of accounts to process before committing during unwind.
     pub commit_threshold: u64,
     /// ETL configuration
@@ -46,6 +52,8 @@ impl AccountHashingStage {
     pub const fn new(config: HashingConfig, etl_config: EtlConfig) -> Self {
         Self {
             clean_threshold: config.clean_threshold,
+            mode: config.mode,
+            clean_changeset_percent: config.clean_changeset_percent,
             commit_threshold: config.commit_threshold,
             etl_config,
         }
@@ -70,7 +78,7 @@ impl AccountHashingStage {
         >,
     {
         use alloy_primitives::U256;
-        use reth_db_api::models::AccountBeforeTx;
+        use reth_db_api::{cursor::DbCursorRW, models::AccountBeforeTx};
         use reth_provider::{StaticFileProviderFactory, StaticFileWriter};
         use reth_testing_utils::{
             generators,
@@ -126,6 +134,8 @@ impl Default for AccountHashingStage {
     fn default() -> Self {
         Self {
             clean_threshold: 500_000,
+            mode: HashingMode::Auto,
+            clean_changeset_percent: 10,
             commit_threshold: 100_000,
             etl_config: EtlConfig::default(),
         }
@@ -149,11 +159,17 @@ where
 
         let (from_block, to_block) = input.next_block_range().into_inner();
 
-        // if there are more blocks then threshold it is faster to go over Plain state and hash all
-        // account otherwise take changesets aggregate the sets and apply hashing to
-        // AccountHashing table. Also, if we start from genesis, we need to hash from scratch, as
-        // genesis accounts are not in changeset.
-        if to_block - from_block > self.clean_threshold || from_block == 1 {
+        let decision = hashing_decision::<_, tables::AccountChangeSets, tables::PlainAccountState>(
+            provider,
+            self.mode,
+            self.clean_threshold,
+            self.clean_changeset_percent,
+            from_block..=to_block,
+            |range| range,
+        )?;
+        debug!(target: "sync::stages::hashing_account", from_block, to_block, ?decision, "Selected hashing mode");
+
+        if decision.is_full() {
             let tx = provider.tx_ref();
 
             // clear table, load all accounts and hash it
@@ -189,6 +205,7 @@ where
 
             let mut hashed_account_cursor =
                 tx.cursor_write::<RawTable<tables::HashedAccounts>>()?;
+            let mut writer = SortedAppendWriter::new(&mut hashed_account_cursor)?;
 
             let total_hashes = collector.len();
             let interval = (total_hashes / 10).max(1);
@@ -202,8 +219,8 @@ where
                 }
 
                 let (key, value) = item?;
-                hashed_account_cursor
-                    .append(RawKey::<B256>::from_vec(key), &RawValue::<Account>::from_vec(value))?;
+                writer
+                    .write(RawKey::<B256>::from_vec(key), &RawValue::<Account>::from_vec(value))?;
             }
         } else {
             // Aggregate all transition changesets and make a list of accounts that have been
@@ -298,9 +315,12 @@ fn stage_checkpoint_progress(provider: &impl StatsReader) -> ProviderResult<Enti
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{
-        stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, TestRunnerError,
-        UnwindStageTestRunner,
+    use crate::{
+        stages::utils::HashingDecision,
+        test_utils::{
+            stage_test_suite_ext, ExecuteStageTestRunner, StageTestRunner, TestRunnerError,
+            UnwindStageTestRunner,
+        },
     };
     use alloy_primitives::U256;
     use assert_matches::assert_matches;
@@ -351,6 +371,44 @@ mod tests {
         assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
     }
 
+    #[tokio::test]
+    async fn hashing_decision_by_changeset_size() {
+        let (previous_stage, stage_progress) = (20, 5);
+        let mut runner = AccountHashingTestRunner::default();
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+
+        // seeds 10 accounts with a changeset in each of the blocks 1..=10
+        runner.seed_execution(input).expect("failed to seed execution");
+
+        let provider = runner.db.factory.provider().unwrap();
+        let range = input.next_block_range();
+        let decide = |mode, clean_changeset_percent| {
+            hashing_decision::<_, tables::AccountChangeSets, tables::PlainAccountState>(
+                &provider,
+                mode,
+                1000,
+                clean_changeset_percent,
+                range.clone(),
+                |range| range,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            decide(HashingMode::Auto, 50),
+            HashingDecision::IncrementalSmallChangesets { changesets: 5, plain_state: 10 }
+        );
+        assert_eq!(
+            decide(HashingMode::Auto, 40),
+            HashingDecision::FullLargeChangesets { changesets: 5, plain_state: 10 }
+        );
+        assert_eq!(decide(HashingMode::Full, 50), HashingDecision::FullConfigured);
+        assert_eq!(decide(HashingMode::Incremental, 0), HashingDecision::IncrementalConfigured);
+    }
+
     mod test_utils {
         use super::*;
         use crate::test_utils::TestStageDB;
@@ -442,6 +500,7 @@ mod tests {
                     commit_threshold: self.commit_threshold,
                     clean_threshold: self.clean_threshold,
                     etl_config: self.etl_config.clone(),
+                    ..Default::default()
                 }
             }
         }
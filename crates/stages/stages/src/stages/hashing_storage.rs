@@ -1,6 +1,7 @@
+use crate::stages::utils::hashing_decision;
 use alloy_primitives::{bytes::BufMut, keccak256, B256};
 use itertools::Itertools;
-use reth_config::config::{EtlConfig, HashingConfig};
+use reth_config::config::{EtlConfig, HashingConfig, HashingMode};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRW},
     models::{BlockNumberAddress, CompactU256},
@@ -9,7 +10,7 @@ use reth_db_api::{
     transaction::{DbTx, DbTxMut},
 };
 use reth_etl::Collector;
-use reth_primitives_traits::StorageEntry;
+use reth_primitives_traits::{hash_cache::keccak256_address, StorageEntry};
 use reth_provider::{DBProvider, HashingWriter, StatsReader, StorageReader};
 use reth_stages_api::{
     EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
@@ -35,6 +36,11 @@ pub struct StorageHashingStage {
     /// The threshold (in number of blocks) for switching between incremental
     /// hashing and full storage hashing.
     pub clean_threshold: u64,
+    /// How to choose between incremental and full hashing below the clean threshold.
+    pub mode: HashingMode,
+    /// The percentage of plain state entries that the changesets need to exceed for full hashing
+    /// in [`HashingMode::Auto`].
+    pub clean_changeset_percent: u64,
     /// The maximum number of slots to process before committing during unwind.
     pub commit_threshold: u64,
     /// ETL configuration
@@ -46,6 +52,8 @@ impl StorageHashingStage {
     pub const fn new(config: HashingConfig, etl_config: EtlConfig) -> Self {
         Self {
             clean_threshold: config.clean_threshold,
+            mode: config.mode,
+            clean_changeset_percent: config.clean_changeset_percent,
             commit_threshold: config.commit_threshold,
             etl_config,
         }
@@ -56,6 +64,8 @@ impl Default for StorageHashingStage {
     fn default() -> Self {
         Self {
             clean_threshold: 500_000,
+            mode: HashingMode::Auto,
+            clean_changeset_percent: 10,
             commit_threshold: 100_000,
             etl_config: EtlConfig::default(),
         }
@@ -80,11 +90,17 @@ where
 
         let (from_block, to_block) = input.next_block_range().into_inner();
 
-        // if there are more blocks then threshold it is faster to go over Plain state and hash all
-        // account otherwise take changesets aggregate the sets and apply hashing to
-        // AccountHashing table. Also, if we start from genesis, we need to hash from scratch, as
-        // genesis accounts are not in changeset, along with their storages.
-        if to_block - from_block > self.clean_threshold || from_block == 1 {
+        let decision = hashing_decision::<_, tables::StorageChangeSets, tables::PlainStorageState>(
+            provider,
+            self.mode,
+            self.clean_threshold,
+            self.clean_changeset_percent,
+            from_block..=to_block,
+            BlockNumberAddress::range,
+        )?;
+        debug!(target: "sync::stages::hashing_storage", from_block, to_block, ?decision, "Selected hashing mode");
+
+        if decision.is_full() {
             // clear table, load all accounts and hash it
             tx.clear::<tables::HashedStorages>()?;
 
@@ -103,7 +119,7 @@ where
                 rayon::spawn(move || {
                     for (address, slot) in chunk {
                         let mut addr_key = Vec::with_capacity(64);
-                        addr_key.put_slice(keccak256(address).as_slice());
+                        addr_key.put_slice(keccak256_address(&address).as_slice());
                         addr_key.put_slice(keccak256(slot.key).as_slice());
                         let _ = tx.send((addr_key, CompactU256::from(slot.value)));
                     }
@@ -324,6 +340,7 @@ mod tests {
                 commit_threshold: self.commit_threshold,
                 clean_threshold: self.clean_threshold,
                 etl_config: self.etl_config.clone(),
+                ..Default::default()
             }
         }
     }
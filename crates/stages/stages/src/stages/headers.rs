@@ -20,16 +20,21 @@ use reth_provider::{
     HeaderSyncGapProvider, StaticFileProviderFactory,
 };
 use reth_stages_api::{
-    CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput, HeadersCheckpoint, Stage,
-    StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+    CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput, HeadersCheckpoint,
+    HealthCheck, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_errors::provider::ProviderError;
-use std::task::{ready, Context, Poll};
+use std::{
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
 
 use tokio::sync::watch;
 use tracing::*;
 
+use super::QueueDepth;
+
 /// The headers stage.
 ///
 /// The headers stage downloads all block headers from the highest block in storage to
@@ -59,6 +64,8 @@ pub struct HeaderStage<Provider, Downloader: HeaderDownloader> {
     header_collector: Collector<BlockNumber, Bytes>,
     /// Returns true if the ETL collector has all necessary headers to fill the gap.
     is_etl_ready: bool,
+    /// The number of headers in the ETL collector, reported if the stage stalls.
+    collected_headers: Arc<QueueDepth>,
 }
 
 // === impl HeaderStage ===
@@ -82,6 +89,7 @@ where
             hash_collector: Collector::new(etl_config.file_size / 2, etl_config.dir.clone()),
             header_collector: Collector::new(etl_config.file_size / 2, etl_config.dir),
             is_etl_ready: false,
+            collected_headers: Arc::new(QueueDepth::new("collected_headers")),
         }
     }
 
@@ -255,6 +263,7 @@ where
                                 .map_err(|err| StageError::Fatal(Box::new(err)))?,
                             ),
                         )?;
+                        self.collected_headers.set(self.hash_collector.len());
 
                         // Headers are downloaded in reverse, so if we reach here, we know we have
                         // filled the gap.
@@ -306,6 +315,7 @@ where
         // Clear ETL collectors
         self.hash_collector.clear();
         self.header_collector.clear();
+        self.collected_headers.set(0);
 
         Ok(ExecOutput {
             checkpoint: StageCheckpoint::new(last_header_number).with_headers_stage_checkpoint(
@@ -392,6 +402,10 @@ where
 
         Ok(UnwindOutput { checkpoint })
     }
+
+    fn health_check(&self) -> Option<Arc<dyn HealthCheck>> {
+        Some(self.collected_headers.clone())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,192 @@
+use super::{collect_history_indices, load_history_indices};
+use alloy_primitives::Address;
+use reth_config::config::{EtlConfig, IndexSenderTransactionsConfig};
+use reth_db_api::{models::ShardedKey, table::Decode, tables, transaction::DbTxMut};
+use reth_provider::{BlockReader, DBProvider, HistoryWriter};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_storage_errors::provider::ProviderError;
+use tracing::info;
+
+/// Stage is indexing the transactions of each sender recovered in
+/// [`SenderRecoveryStage`][crate::stages::SenderRecoveryStage]. For more information on index
+/// sharding take a look at [`tables::SenderTransactions`].
+///
+/// This stage is optional and only part of the pipeline if enabled through
+/// [`IndexSenderTransactionsConfig::enabled`]. It relies on the
+/// [`tables::TransactionSenders`] table, so transactions whose senders have already been pruned
+/// are not indexed.
+#[derive(Debug)]
+pub struct IndexSenderTransactionsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexSenderTransactionsStage {
+    /// Create new instance of [`IndexSenderTransactionsStage`].
+    pub const fn new(config: IndexSenderTransactionsConfig, etl_config: EtlConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl Default for IndexSenderTransactionsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexSenderTransactionsStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + HistoryWriter + BlockReader,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexSenderTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync the index is rebuilt from scratch, since it's faster than merging with
+        // existing shards.
+        if first_sync {
+            provider.tx_ref().clear::<tables::SenderTransactions>()?;
+        }
+
+        let first_tx_num = provider
+            .block_body_indices(*range.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.start()))?
+            .first_tx_num();
+        let next_tx_num = provider
+            .block_body_indices(*range.end())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.end()))?
+            .next_tx_num();
+
+        info!(target: "sync::stages::index_sender_transactions::exec", ?first_sync, ?range, "Collecting indices");
+        let collector = collect_history_indices::<
+            _,
+            tables::TransactionSenders,
+            tables::SenderTransactions,
+            _,
+        >(
+            provider,
+            first_tx_num..next_tx_num,
+            ShardedKey::new,
+            |(tx_number, sender)| (tx_number, sender),
+            &self.etl_config,
+        )?;
+
+        info!(target: "sync::stages::index_sender_transactions::exec", "Loading indices into database");
+        load_history_indices::<_, tables::SenderTransactions, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        let first_tx_num = provider
+            .block_body_indices(*range.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.start()))?
+            .first_tx_num();
+
+        provider.unwind_sender_transactions_index_range(first_tx_num..)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::{address, TxNumber};
+    use reth_db_api::{
+        models::StoredBlockBodyIndices,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_provider::{DatabaseProviderFactory, SenderTransactionsProvider};
+
+    const SENDER: Address = address!("0x0000000000000000000000000000000000000001");
+    const OTHER: Address = address!("0x0000000000000000000000000000000000000002");
+
+    fn setup(db: &TestStageDB) {
+        db.commit(|tx| {
+            // Ten blocks with two transactions each, alternating between the two senders.
+            for block in 0..=10u64 {
+                let tx_count = if block == 0 { 0 } else { 2 };
+                let first_tx_num = block.saturating_sub(1) * 2;
+                tx.put::<tables::BlockBodyIndices>(
+                    block,
+                    StoredBlockBodyIndices { first_tx_num, tx_count },
+                )?;
+                for tx_number in first_tx_num..first_tx_num + tx_count {
+                    let sender = if tx_number % 2 == 0 { SENDER } else { OTHER };
+                    tx.put::<tables::TransactionSenders>(tx_number, sender)?;
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    fn ids(db: &TestStageDB, sender: Address, after: Option<TxNumber>, limit: usize) -> Vec<u64> {
+        db.factory.provider().unwrap().transaction_ids_by_sender(sender, after, limit).unwrap()
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        setup(&db);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let mut stage = IndexSenderTransactionsStage::default();
+        let out =
+            stage.execute(&provider, ExecInput { target: Some(10), checkpoint: None }).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(10), done: true });
+        provider.commit().unwrap();
+
+        assert_eq!(ids(&db, SENDER, None, usize::MAX), vec![0, 2, 4, 6, 8, 10, 12, 14, 16, 18]);
+        assert_eq!(ids(&db, OTHER, Some(9), 3), vec![11, 13, 15]);
+        assert_eq!(ids(&db, SENDER, Some(18), 10), Vec::<u64>::new());
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: StageCheckpoint::new(10), unwind_to: 5, bad_block: None },
+            )
+            .unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(5) });
+        provider.commit().unwrap();
+
+        assert_eq!(ids(&db, SENDER, None, usize::MAX), vec![0, 2, 4, 6, 8]);
+        assert_eq!(ids(&db, OTHER, None, usize::MAX), vec![1, 3, 5, 7, 9]);
+
+        let tx = db.factory.provider().unwrap().into_tx();
+        assert_eq!(tx.entries::<tables::SenderTransactions>().unwrap(), 2);
+    }
+}
@@ -12,6 +12,8 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index transactions by their sender
+mod index_sender_transactions;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -32,6 +34,7 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_sender_transactions::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use prune::*;
@@ -63,7 +66,7 @@ mod tests {
         AccountsHistory,
     };
     use reth_ethereum_consensus::EthBeaconConsensus;
-    use reth_ethereum_primitives::Block;
+    use reth_ethereum_primitives::{Block, EthPrimitives};
     use reth_evm_ethereum::EthEvmConfig;
     use reth_exex::ExExManagerHandle;
     use reth_primitives_traits::{Account, Bytecode, SealedBlock};
@@ -79,6 +82,7 @@ mod tests {
         ExecInput, ExecutionStageThresholds, PipelineTarget, Stage, StageCheckpoint, StageId,
     };
     use reth_static_file_types::StaticFileSegment;
+    use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, random_receipt, BlockRangeParams,
     };
@@ -478,6 +482,24 @@ mod tests {
         save_checkpoint_and_check(&db, StageId::Headers, 91, Some(PipelineTarget::Unwind(block)));
     }
 
+    #[test]
+    fn test_consistency_missing_static_files() {
+        let db = seed_data(90).unwrap();
+        let provider_rw = db.factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(90)).unwrap();
+        provider_rw.commit().unwrap();
+
+        // Static files pointed at an empty directory must not trigger an unwind to genesis.
+        let static_files_dir = tempfile::tempdir().unwrap();
+        let static_file_provider =
+            StaticFileProvider::<EthPrimitives>::read_write(static_files_dir.path()).unwrap();
+        assert!(matches!(
+            static_file_provider
+                .check_consistency(&db.factory.database_provider_ro().unwrap(), false),
+            Err(ProviderError::MissingStaticFiles(_, 90))
+        ));
+    }
+
     #[test]
     fn test_consistency_headers_gap() {
         let db = seed_data(90).unwrap();
@@ -1,9 +1,9 @@
 use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::{TxHash, TxNumber};
-use num_traits::Zero;
+use rayon::slice::ParallelSliceMut;
 use reth_config::config::{EtlConfig, TransactionLookupConfig};
 use reth_db_api::{
-    cursor::{DbCursorRO, DbCursorRW},
+    cursor::{DbCursorRO, DbCursorRW, SortedAppendWriter},
     table::Value,
     tables,
     transaction::DbTxMut,
@@ -30,7 +30,8 @@ use tracing::*;
 /// [`tables::TransactionHashNumbers`] This is used for looking up changesets via the transaction
 /// hash.
 ///
-/// It uses [`reth_etl::Collector`] to collect all entries before finally writing them to disk.
+/// It uses [`reth_etl::Collector`] to collect all entries before finally writing them to disk. If
+/// the range fits into a single chunk, the entries are sorted in memory instead.
 #[derive(Debug, Clone)]
 pub struct TransactionLookupStage {
     /// The maximum number of lookup entries to hold in memory before pushing them to
@@ -119,6 +120,8 @@ where
         // 500MB temporary files
         let mut hash_collector: Collector<TxHash, TxNumber> =
             Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        // Hashes of a range that fits into a single chunk are sorted in memory instead
+        let mut hashes = Vec::new();
 
         info!(
             target: "sync::stages::transaction_lookup",
@@ -134,8 +137,13 @@ where
 
             info!(target: "sync::stages::transaction_lookup", ?tx_range, "Calculating transaction hashes");
 
-            for (key, value) in provider.transaction_hashes_by_range(tx_range)? {
-                hash_collector.insert(key, value)?;
+            let chunk = provider.transaction_hashes_by_range(tx_range)?;
+            if is_final_range && hash_collector.is_empty() {
+                hashes = chunk;
+            } else {
+                for (key, value) in chunk {
+                    hash_collector.insert(key, value)?;
+                }
             }
 
             input.checkpoint = Some(
@@ -144,38 +152,31 @@ where
             );
 
             if is_final_range {
-                let append_only =
-                    provider.count_entries::<tables::TransactionHashNumbers>()?.is_zero();
-                let mut txhash_cursor = provider
-                    .tx_ref()
-                    .cursor_write::<tables::RawTable<tables::TransactionHashNumbers>>()?;
-
-                let total_hashes = hash_collector.len();
-                let interval = (total_hashes / 10).max(1);
-                for (index, hash_to_number) in hash_collector.iter()?.enumerate() {
-                    let (hash, number) = hash_to_number?;
-                    if index > 0 && index % interval == 0 {
-                        info!(
-                            target: "sync::stages::transaction_lookup",
-                            ?append_only,
-                            progress = %format!("{:.2}%", (index as f64 / total_hashes as f64) * 100.0),
-                            "Inserting hashes"
-                        );
-                    }
-
-                    let key = RawKey::<TxHash>::from_vec(hash);
-                    if append_only {
-                        txhash_cursor.append(key, &RawValue::<TxNumber>::from_vec(number))?
-                    } else {
-                        txhash_cursor.insert(key, &RawValue::<TxNumber>::from_vec(number))?
-                    }
+                if hash_collector.is_empty() {
+                    hashes.par_sort_unstable_by_key(|(hash, _)| *hash);
+                    let total_hashes = hashes.len();
+                    insert_hashes(
+                        provider,
+                        total_hashes,
+                        hashes
+                            .into_iter()
+                            .map(|(hash, number)| Ok((RawKey::new(hash), RawValue::new(number)))),
+                    )?;
+                } else {
+                    let total_hashes = hash_collector.len();
+                    insert_hashes(
+                        provider,
+                        total_hashes,
+                        hash_collector.iter()?.map(|entry| {
+                            let (hash, number) = entry?;
+                            Ok::<_, StageError>((
+                                RawKey::from_vec(hash),
+                                RawValue::from_vec(number),
+                            ))
+                        }),
+                    )?;
                 }
 
-                trace!(target: "sync::stages::transaction_lookup",
-                    total_hashes,
-                    "Transaction hashes inserted"
-                );
-
                 break;
             }
         }
@@ -228,6 +229,47 @@ where
     }
 }
 
+/// Writes the given hash to number entries, sorted by hash, to
+/// [`tables::TransactionHashNumbers`].
+///
+/// Entries that sort after the last hash in the table are appended, which is much cheaper than
+/// inserting them. If the table is empty, all entries are appended.
+fn insert_hashes<Provider>(
+    provider: &Provider,
+    total_hashes: usize,
+    hashes: impl Iterator<Item = Result<(RawKey<TxHash>, RawValue<TxNumber>), StageError>>,
+) -> Result<(), StageError>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    let mut txhash_cursor =
+        provider.tx_ref().cursor_write::<tables::RawTable<tables::TransactionHashNumbers>>()?;
+    let mut writer = SortedAppendWriter::new(&mut txhash_cursor)?;
+    let append_only = writer.is_append_only();
+
+    let interval = (total_hashes / 10).max(1);
+    for (index, hash_to_number) in hashes.enumerate() {
+        let (hash, number) = hash_to_number?;
+        if index > 0 && index % interval == 0 {
+            info!(
+                target: "sync::stages::transaction_lookup",
+                ?append_only,
+                progress = %format!("{:.2}%", (index as f64 / total_hashes as f64) * 100.0),
+                "Inserting hashes"
+            );
+        }
+
+        writer.write(hash, &number)?;
+    }
+
+    trace!(target: "sync::stages::transaction_lookup",
+        total_hashes,
+        "Transaction hashes inserted"
+    );
+
+    Ok(())
+}
+
 fn stage_checkpoint<Provider>(provider: &Provider) -> Result<EntitiesCheckpoint, StageError>
 where
     Provider: PruneCheckpointReader + StaticFileProviderFactory + StatsReader,
@@ -328,6 +370,52 @@ mod tests {
         assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
     }
 
+    #[tokio::test]
+    async fn execute_transaction_lookup_incrementally() {
+        let (stage_progress, intermediate_stage, previous_stage) = (100, 300, 500);
+
+        let mut runner = TransactionLookupTestRunner::default();
+        runner
+            .seed_execution(ExecInput {
+                target: Some(previous_stage),
+                checkpoint: Some(StageCheckpoint::new(stage_progress)),
+            })
+            .expect("failed to seed execution");
+
+        // Spread the first range over multiple chunks, so that the hashes go through ETL files
+        runner.chunk_size = 10;
+        let input = ExecInput {
+            target: Some(intermediate_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+        let result = runner.execute(input).await.unwrap();
+        assert_matches!(
+            result,
+            Ok(ExecOutput { checkpoint: StageCheckpoint { block_number, .. }, done: true })
+                if block_number == intermediate_stage
+        );
+
+        // The second range fits into a single chunk and is written to a non-empty table
+        runner.chunk_size = 1000;
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(intermediate_stage)),
+        };
+        let result = runner.execute(input).await.unwrap();
+        assert_matches!(
+            result,
+            Ok(ExecOutput { checkpoint: StageCheckpoint { block_number, .. }, done: true })
+                if block_number == previous_stage
+        );
+
+        // Validate the hashes of both ranges
+        let input = ExecInput {
+            target: Some(previous_stage),
+            checkpoint: Some(StageCheckpoint::new(stage_progress)),
+        };
+        assert!(runner.validate_execution(input, result.ok()).is_ok(), "execution validation");
+    }
+
     #[tokio::test]
     async fn execute_pruned_transaction_lookup() {
         let (previous_stage, prune_target, stage_progress) = (500, 400, 100);
@@ -1,6 +1,6 @@
 //! Utils for `stages`.
 use alloy_primitives::{BlockNumber, TxNumber};
-use reth_config::config::EtlConfig;
+use reth_config::config::{EtlConfig, HashingMode};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     models::sharded_key::NUM_OF_INDICES_IN_SHARD,
@@ -11,11 +11,16 @@ use reth_db_api::{
 use reth_etl::Collector;
 use reth_provider::{
     providers::StaticFileProvider, BlockReader, DBProvider, ProviderError,
-    StaticFileProviderFactory,
+    StaticFileProviderFactory, StatsReader,
 };
-use reth_stages_api::StageError;
+use reth_stages_api::{HealthCheck, StageError, StageHealth};
 use reth_static_file_types::StaticFileSegment;
-use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{RangeBounds, RangeInclusive},
+    sync::atomic::{AtomicUsize, Ordering},
+};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
@@ -248,6 +253,124 @@ impl LoadMode {
     }
 }
 
+/// Why a hashing stage hashes a block range incrementally or from scratch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HashingDecision {
+    /// The range starts at genesis, whose state is not part of the changesets.
+    FullFromGenesis,
+    /// The range has more blocks than the clean threshold, so its changesets may be pruned.
+    FullAboveCleanThreshold { blocks: u64, clean_threshold: u64 },
+    /// Full hashing is configured.
+    FullConfigured,
+    /// Incremental hashing is configured.
+    IncrementalConfigured,
+    /// The changesets of the range are large compared to the plain state.
+    ///
+    /// `changesets` is estimated from a sample of the blocks of the range.
+    FullLargeChangesets { changesets: usize, plain_state: usize },
+    /// The changesets of the range are small compared to the plain state.
+    ///
+    /// `changesets` is estimated from a sample of the blocks of the range.
+    IncrementalSmallChangesets { changesets: usize, plain_state: usize },
+}
+
+impl HashingDecision {
+    /// Returns true if the whole plain state is hashed from scratch.
+    pub(crate) const fn is_full(&self) -> bool {
+        matches!(
+            self,
+            Self::FullFromGenesis |
+                Self::FullAboveCleanThreshold { .. } |
+                Self::FullConfigured |
+                Self::FullLargeChangesets { .. }
+        )
+    }
+}
+
+/// Maximum number of blocks whose changesets are read to estimate the changeset size of a range
+/// in [`hashing_decision`].
+const HASHING_DECISION_SAMPLED_BLOCKS: u64 = 64;
+
+/// Maximum number of changeset entries read to estimate the changeset size of a range in
+/// [`hashing_decision`].
+const HASHING_DECISION_MAX_SAMPLED_ENTRIES: usize = 100_000;
+
+/// Decides whether a hashing stage hashes the changesets (`CS`) of the block range incrementally,
+/// or hashes the whole plain state table (`PS`) from scratch.
+///
+/// In [`HashingMode::Auto`], the number of changeset entries of the range is estimated and
+/// compared with the number of plain state entries. Hashing the plain state from scratch is a
+/// sequential walk, while incremental hashing does random reads and writes for every changed
+/// entry, so the full hashing is chosen once the changesets exceed `clean_changeset_percent` of
+/// the plain state.
+///
+/// The estimate reads the changesets of at most [`HASHING_DECISION_SAMPLED_BLOCKS`] evenly spaced
+/// blocks and at most [`HASHING_DECISION_MAX_SAMPLED_ENTRIES`] entries, `changeset_range` returns
+/// the changeset keys of a block range.
+pub(crate) fn hashing_decision<Provider, CS, PS, R>(
+    provider: &Provider,
+    mode: HashingMode,
+    clean_threshold: u64,
+    clean_changeset_percent: u64,
+    block_range: RangeInclusive<BlockNumber>,
+    changeset_range: impl Fn(RangeInclusive<BlockNumber>) -> R,
+) -> Result<HashingDecision, StageError>
+where
+    Provider: DBProvider + StatsReader,
+    CS: Table,
+    PS: Table,
+    R: RangeBounds<CS::Key>,
+{
+    let (from_block, to_block) = block_range.into_inner();
+    let blocks = to_block - from_block;
+    if from_block == 1 {
+        return Ok(HashingDecision::FullFromGenesis)
+    }
+    if blocks > clean_threshold {
+        return Ok(HashingDecision::FullAboveCleanThreshold { blocks, clean_threshold })
+    }
+
+    match mode {
+        HashingMode::Full => Ok(HashingDecision::FullConfigured),
+        HashingMode::Incremental => Ok(HashingDecision::IncrementalConfigured),
+        HashingMode::Auto => {
+            let plain_state = provider.count_entries::<PS>()?;
+            let limit = plain_state.saturating_mul(clean_changeset_percent as usize) / 100;
+
+            let range_blocks = blocks + 1;
+            let step = range_blocks.div_ceil(HASHING_DECISION_SAMPLED_BLOCKS);
+            let mut cursor = provider.tx_ref().cursor_read::<CS>()?;
+            let (mut sampled_blocks, mut sampled_entries) = (0u64, 0usize);
+            for block in (from_block..=to_block).step_by(step as usize) {
+                if sampled_entries >= HASHING_DECISION_MAX_SAMPLED_ENTRIES {
+                    break
+                }
+                for entry in cursor.walk_range(changeset_range(block..=block))? {
+                    entry?;
+                    sampled_entries += 1;
+                    // the sample alone exceeds the limit
+                    if sampled_entries > limit {
+                        return Ok(HashingDecision::FullLargeChangesets {
+                            changesets: sampled_entries,
+                            plain_state,
+                        })
+                    }
+                }
+                sampled_blocks += 1;
+            }
+
+            let changesets = (sampled_entries as u128 * range_blocks as u128 /
+                sampled_blocks.max(1) as u128)
+                .try_into()
+                .unwrap_or(usize::MAX);
+            if changesets > limit {
+                return Ok(HashingDecision::FullLargeChangesets { changesets, plain_state })
+            }
+            Ok(HashingDecision::IncrementalSmallChangesets { changesets, plain_state })
+        }
+    }
+}
+
 /// Called when database is ahead of static files. Attempts to find the first block we are missing
 /// transactions for.
 pub(crate) fn missing_static_data_error<Provider>(
@@ -283,3 +406,29 @@ where
         segment,
     })
 }
+
+/// A [`HealthCheck`] that reports the number of downloaded items a stage has queued, but not yet
+/// written.
+#[derive(Debug)]
+pub(crate) struct QueueDepth {
+    name: &'static str,
+    depth: AtomicUsize,
+}
+
+impl QueueDepth {
+    /// Creates a new, empty queue with the given name.
+    pub(crate) const fn new(name: &'static str) -> Self {
+        Self { name, depth: AtomicUsize::new(0) }
+    }
+
+    /// Sets the number of queued items.
+    pub(crate) fn set(&self, depth: usize) {
+        self.depth.store(depth, Ordering::Relaxed);
+    }
+}
+
+impl HealthCheck for QueueDepth {
+    fn health(&self) -> StageHealth {
+        StageHealth { queue_depths: vec![(self.name, self.depth.load(Ordering::Relaxed))] }
+    }
+}
@@ -21,6 +21,8 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Optional stage indexing transactions by their sender, not part of [`StageId::ALL`].
+    IndexSenderTransactions,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -78,6 +80,7 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexSenderTransactions => "IndexSenderTransactions",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -122,6 +125,7 @@ mod tests {
         assert_eq!(StageId::StorageHashing.to_string(), "StorageHashing");
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
+        assert_eq!(StageId::IndexSenderTransactions.to_string(), "IndexSenderTransactions");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
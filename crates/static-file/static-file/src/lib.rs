@@ -12,8 +12,8 @@ pub mod segments;
 mod static_file_producer;
 
 pub use static_file_producer::{
-    StaticFileProducer, StaticFileProducerInner, StaticFileProducerResult,
-    StaticFileProducerWithResult,
+    BlockProcessingActivity, StaticFileProducer, StaticFileProducerInner, StaticFileProducerResult,
+    StaticFileProducerThrottle, StaticFileProducerWithResult,
 };
 
 // Re-export for convenience.
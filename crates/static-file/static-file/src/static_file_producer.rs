@@ -15,11 +15,15 @@ use reth_prune_types::PruneModes;
 use reth_stages_types::StageId;
 use reth_static_file_types::{HighestStaticFiles, StaticFileTargets};
 use reth_storage_errors::provider::ProviderResult;
+use reth_tasks::supervisor::{SupervisionPolicy, Supervisor};
 use reth_tokio_util::{EventSender, EventStream};
 use std::{
     ops::{Deref, RangeInclusive},
-    sync::Arc,
-    time::Instant,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 use tracing::{debug, trace};
 
@@ -56,6 +60,103 @@ impl<Provider> Deref for StaticFileProducer<Provider> {
     }
 }
 
+/// Shared signal of whether the node is currently processing a block.
+///
+/// Used by a throttled [`StaticFileProducerInner`] to defer copying while blocks are being
+/// processed, see [`StaticFileProducerThrottle`].
+#[derive(Debug, Clone, Default)]
+pub struct BlockProcessingActivity(Arc<AtomicBool>);
+
+impl BlockProcessingActivity {
+    /// Marks block processing as started or finished.
+    pub fn set_active(&self, active: bool) {
+        self.0.store(active, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if a block is currently being processed.
+    pub fn is_active(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Throttling configuration of the [`StaticFileProducerInner`].
+///
+/// When set, segments are copied in chunks of at most `chunk_size` blocks, and each chunk is
+/// committed separately, so that an interrupted run doesn't lose the progress of the previous
+/// chunks. Before copying the next chunk of the segments, the producer waits for the ongoing block
+/// processing to finish, for at most `max_defer`, and then pauses for `pause` to leave room for
+/// other work.
+#[derive(Debug, Clone)]
+pub struct StaticFileProducerThrottle {
+    /// Maximum number of blocks copied and committed at once.
+    pub chunk_size: u64,
+    /// Time to pause before copying each chunk.
+    pub pause: Duration,
+    /// Maximum time to defer copying of a chunk while blocks are being processed.
+    pub max_defer: Duration,
+    /// Block processing activity to defer to.
+    pub activity: BlockProcessingActivity,
+}
+
+impl StaticFileProducerThrottle {
+    /// Interval at which the block processing activity is polled while deferring.
+    const ACTIVITY_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Creates a new throttle that copies at most `chunk_size` blocks at once, without pausing or
+    /// deferring.
+    pub fn new(chunk_size: u64) -> Self {
+        Self {
+            chunk_size: chunk_size.max(1),
+            pause: Duration::ZERO,
+            max_defer: Duration::ZERO,
+            activity: BlockProcessingActivity::default(),
+        }
+    }
+
+    /// Sets the time to pause before copying each chunk.
+    pub const fn with_pause(mut self, pause: Duration) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    /// Defers copying while the given activity reports that blocks are being processed, for at
+    /// most `max_defer` per chunk.
+    pub fn with_activity(mut self, activity: BlockProcessingActivity, max_defer: Duration) -> Self {
+        self.activity = activity;
+        self.max_defer = max_defer;
+        self
+    }
+
+    /// Splits the block range into chunks of at most `chunk_size` blocks.
+    fn chunks(
+        &self,
+        block_range: &RangeInclusive<BlockNumber>,
+    ) -> Vec<RangeInclusive<BlockNumber>> {
+        let mut chunks = Vec::new();
+        let mut start = *block_range.start();
+        while start <= *block_range.end() {
+            let end = start.saturating_add(self.chunk_size - 1).min(*block_range.end());
+            chunks.push(start..=end);
+            if end == u64::MAX {
+                break
+            }
+            start = end + 1;
+        }
+        chunks
+    }
+
+    /// Blocks until the next chunk can be copied.
+    fn wait(&self) {
+        let deadline = Instant::now() + self.max_defer;
+        while self.activity.is_active() && Instant::now() < deadline {
+            std::thread::sleep(Self::ACTIVITY_POLL_INTERVAL);
+        }
+        if !self.pause.is_zero() {
+            std::thread::sleep(self.pause);
+        }
+    }
+}
+
 /// Static File producer routine. See [`StaticFileProducerInner::run`] for more detailed
 /// description.
 #[derive(Debug)]
@@ -66,12 +167,35 @@ pub struct StaticFileProducerInner<Provider> {
     /// needed in [`StaticFileProducerInner`] to prevent attempting to move prunable data to static
     /// files. See [`StaticFileProducerInner::get_static_file_targets`].
     prune_modes: PruneModes,
+    /// Throttling of the copying, if enabled. See [`StaticFileProducerThrottle`].
+    throttle: Option<StaticFileProducerThrottle>,
+    /// Supervises the runs, see [`StaticFileProducerInner::set_supervision`].
+    supervisor: Option<Mutex<Supervisor>>,
     event_sender: EventSender<StaticFileProducerEvent>,
 }
 
 impl<Provider> StaticFileProducerInner<Provider> {
     fn new(provider: Provider, prune_modes: PruneModes) -> Self {
-        Self { provider, prune_modes, event_sender: Default::default() }
+        Self {
+            provider,
+            prune_modes,
+            throttle: None,
+            supervisor: None,
+            event_sender: Default::default(),
+        }
+    }
+
+    /// Sets the throttling of the copying. `None` copies every segment at once.
+    pub fn set_throttle(&mut self, throttle: Option<StaticFileProducerThrottle>) {
+        self.throttle = throttle;
+    }
+
+    /// Sets the policy applied if a [`StaticFileProducerInner::run`] panics.
+    ///
+    /// With [`SupervisionPolicy::Restart`], the uncommitted static file data of a panicked run is
+    /// discarded and the following runs are skipped until the backoff has elapsed.
+    pub fn set_supervision(&mut self, policy: SupervisionPolicy) {
+        self.supervisor = Some(Mutex::new(Supervisor::new("static_file_producer", policy)));
     }
 }
 
@@ -111,9 +235,39 @@ where
     /// and a read-only database transaction from [`DatabaseProviderFactory`]. All segments are run
     /// in parallel.
     ///
+    /// If a [`StaticFileProducerThrottle`] is set, each segment is copied and committed in chunks
+    /// instead.
+    ///
     /// NOTE: it doesn't delete the data from database, and the actual deleting (aka pruning) logic
     /// lives in the `prune` crate.
     pub fn run(&self, targets: StaticFileTargets) -> StaticFileProducerResult {
+        self.run_supervised(|| self.run_segments(targets))
+    }
+
+    fn run_supervised(
+        &self,
+        run: impl FnOnce() -> StaticFileProducerResult,
+    ) -> StaticFileProducerResult {
+        let Some(supervisor) = &self.supervisor else { return run() };
+
+        let mut supervisor = supervisor.lock();
+        let panicked = !supervisor.is_backing_off();
+        supervisor.run(run).unwrap_or_else(|| {
+            if panicked {
+                // The cached writers still hold the rows appended by the panicked run, which the
+                // next run would append again
+                self.provider.static_file_provider().discard_uncommitted_writes();
+            }
+            Ok(StaticFileTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                block_meta: None,
+            })
+        })
+    }
+
+    fn run_segments(&self, targets: StaticFileTargets) -> StaticFileProducerResult {
         // If there are no targets, do not produce any static files and return early
         if !targets.any() {
             return Ok(targets)
@@ -141,20 +295,51 @@ where
             segments.push((Box::new(segments::Receipts), block_range));
         }
 
-        segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
-            debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
-            let start = Instant::now();
+        if let Some(throttle) = &self.throttle {
+            // Copy the segments chunk by chunk. The throttle blocks the current thread, so it's
+            // applied outside of the rayon scope to not park the global rayon workers that are
+            // shared with other parallel work.
+            let mut chunks = segments
+                .iter()
+                .map(|(segment, block_range)| (segment, throttle.chunks(block_range).into_iter()))
+                .collect::<Vec<_>>();
+            loop {
+                let round = chunks
+                    .iter_mut()
+                    .filter_map(|(segment, chunks)| Some((segment, chunks.next()?)))
+                    .collect::<Vec<_>>();
+                if round.is_empty() {
+                    break
+                }
+
+                throttle.wait();
 
-            // Create a new database transaction on every segment to prevent long-lived read-only
-            // transactions
-            let provider = self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
-            segment.copy_to_static_files(provider,  block_range.clone())?;
+                round.into_par_iter().try_for_each(|(segment, chunk)| -> ProviderResult<()> {
+                    trace!(target: "static_file", segment = %segment.segment(), ?chunk, "StaticFileProducer segment chunk");
+                    let provider = self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
+                    segment.copy_to_static_files(provider, chunk)?;
+
+                    // Commit only the writer of this segment, so that the progress is persisted
+                    // even if a later chunk fails
+                    self.provider.static_file_provider().latest_writer(segment.segment())?.commit()
+                })?;
+            }
+        } else {
+            segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
+                debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
+                let start = Instant::now();
 
-            let elapsed = start.elapsed(); // TODO(alexey): track in metrics
-            debug!(target: "static_file", segment = %segment.segment(), ?block_range, ?elapsed, "Finished StaticFileProducer segment");
+                // Create a new database transaction on every segment to prevent long-lived
+                // read-only transactions
+                let provider = self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
+                segment.copy_to_static_files(provider,  block_range.clone())?;
 
-            Ok(())
-        })?;
+                let elapsed = start.elapsed(); // TODO(alexey): track in metrics
+                debug!(target: "static_file", segment = %segment.segment(), ?block_range, ?elapsed, "Finished StaticFileProducer segment");
+
+                Ok(())
+            })?;
+        }
 
         self.provider.static_file_provider().commit()?;
         for (segment, block_range) in segments {
@@ -190,7 +375,11 @@ where
             block_meta: stages_checkpoints[2],
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
-        self.run(targets)?;
+        if self.run(targets.clone())? != targets {
+            // The run panicked and was skipped, report only what has actually been copied, so that
+            // the data isn't pruned from the database
+            return Ok(self.provider.static_file_provider().get_highest_static_files())
+        }
 
         Ok(highest_static_files)
     }
@@ -257,7 +446,8 @@ where
 #[cfg(test)]
 mod tests {
     use crate::static_file_producer::{
-        StaticFileProducer, StaticFileProducerInner, StaticFileTargets,
+        BlockProcessingActivity, StaticFileProducer, StaticFileProducerInner,
+        StaticFileProducerThrottle, StaticFileTargets,
     };
     use alloy_primitives::{B256, U256};
     use assert_matches::assert_matches;
@@ -269,6 +459,7 @@ mod tests {
     use reth_prune_types::PruneModes;
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_static_file_types::{HighestStaticFiles, StaticFileSegment};
+    use reth_tasks::supervisor::{RestartPolicy, SupervisionPolicy};
     use reth_testing_utils::generators::{
         self, random_block_range, random_receipt, BlockRangeParams,
     };
@@ -409,6 +600,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn run_after_panic() {
+        let (provider_factory, _temp_static_files_dir) = setup();
+
+        let mut static_file_producer =
+            StaticFileProducerInner::new(provider_factory.clone(), PruneModes::default());
+        static_file_producer.set_supervision(SupervisionPolicy::Restart(RestartPolicy {
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            max_restarts: 1,
+        }));
+
+        // a run that panics after appending a header without committing it
+        let result = static_file_producer.run_supervised(|| {
+            let mut writer = provider_factory
+                .static_file_provider()
+                .latest_writer(StaticFileSegment::Headers)?;
+            writer.append_header(&Default::default(), U256::ZERO, &B256::ZERO)?;
+            panic!("intentionally panic")
+        });
+        assert_matches!(result, Ok(targets) if !targets.any());
+
+        // the retry starts from the last commit instead of the header appended by the panicked run
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                block_meta: None,
+            })
+            .expect("get static file targets");
+        assert_matches!(static_file_producer.run(targets), Ok(_));
+        assert_eq!(
+            provider_factory.static_file_provider().get_highest_static_files(),
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                block_meta: None
+            }
+        );
+    }
+
+    #[test]
+    fn run_throttled() {
+        let (provider_factory, _temp_static_files_dir) = setup();
+
+        let activity = BlockProcessingActivity::default();
+        let throttle = StaticFileProducerThrottle::new(2)
+            .with_activity(activity.clone(), Duration::from_millis(50));
+        assert_eq!(throttle.chunks(&(0..=6)), vec![0..=1, 2..=3, 4..=5, 6..=6]);
+        assert_eq!(throttle.chunks(&(4..=4)), vec![4..=4]);
+
+        let mut static_file_producer =
+            StaticFileProducerInner::new(provider_factory.clone(), PruneModes::default());
+        static_file_producer.set_throttle(Some(throttle));
+
+        // block processing never finishes, so every chunk is deferred for at most `max_defer`
+        activity.set_active(true);
+
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                headers: Some(4),
+                receipts: Some(4),
+                transactions: Some(4),
+                block_meta: None,
+            })
+            .expect("get static file targets");
+        assert_matches!(
+            static_file_producer.run(targets),
+            Err(ProviderError::BlockBodyIndicesNotFound(4))
+        );
+
+        // the chunks preceding the failed one are committed
+        assert_eq!(
+            provider_factory.static_file_provider().get_highest_static_files(),
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                block_meta: None
+            }
+        );
+    }
+
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
     #[test]
     fn only_one() {
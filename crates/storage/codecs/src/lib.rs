@@ -494,7 +494,8 @@ impl Compact for bool {
     }
 }
 
-fn encode_varuint<B>(mut n: usize, buf: &mut B)
+/// Encodes the number as a variable-length unsigned integer.
+pub fn encode_varuint<B>(mut n: usize, buf: &mut B)
 where
     B: bytes::BufMut + AsMut<[u8]>,
 {
@@ -505,7 +506,11 @@ where
     buf.put_u8(n as u8);
 }
 
-fn decode_varuint(buf: &[u8]) -> (usize, &[u8]) {
+/// Decodes a variable-length unsigned integer encoded with [`encode_varuint`], returning it with
+/// the remaining buffer.
+///
+/// Panics if the buffer doesn't start with a valid variable-length unsigned integer.
+pub fn decode_varuint(buf: &[u8]) -> (usize, &[u8]) {
     let mut value = 0;
 
     for i in 0..33 {
@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     common::{IterPairResult, PairResult, ValueOnlyResult},
-    table::{DupSort, Table, TableRow},
+    table::{DupSort, Encode, Table, TableRow},
     DatabaseError,
 };
 
@@ -361,3 +361,85 @@ impl<T: DupSort, CURSOR: DbDupCursorRO<T>> Iterator for DupWalker<'_, T, CURSOR>
         self.cursor.next_dup().transpose()
     }
 }
+
+/// Writes entries that are sorted by key to table `T`.
+///
+/// [`DbCursorRW::append`] skips the lookup of the insert position, which makes it much cheaper than
+/// [`DbCursorRW::insert`] for bulk writes, but it fails if the key does not sort after the last key
+/// of the table. This writer appends all entries that sort after the last key the table had when
+/// the writer was created and inserts the others. Because the entries are sorted, every entry that
+/// follows the first appended one is appended as well.
+///
+/// Keys are compared in their encoded form, which is the order of the table. In debug builds, the
+/// writer asserts that the keys are written in strictly increasing order.
+pub struct SortedAppendWriter<'cursor, T: Table, CURSOR> {
+    /// Cursor used to write the entries.
+    cursor: &'cursor mut CURSOR,
+    /// Encoded last key of the table, or `None` if all following entries can be appended.
+    last_table_key: Option<<T::Key as Encode>::Encoded>,
+    /// Encoded key of the previously written entry.
+    #[cfg(debug_assertions)]
+    last_written_key: Option<<T::Key as Encode>::Encoded>,
+}
+
+impl<T, CURSOR> fmt::Debug for SortedAppendWriter<'_, T, CURSOR>
+where
+    T: Table,
+    CURSOR: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SortedAppendWriter")
+            .field("cursor", &self.cursor)
+            .field("last_table_key", &self.last_table_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'cursor, T, CURSOR> SortedAppendWriter<'cursor, T, CURSOR>
+where
+    T: Table,
+    CURSOR: DbCursorRO<T> + DbCursorRW<T>,
+{
+    /// Creates a new writer that writes to the table of the given cursor.
+    pub fn new(cursor: &'cursor mut CURSOR) -> Result<Self, DatabaseError> {
+        let last_table_key = cursor.last()?.map(|(key, _)| key.encode());
+        Ok(Self {
+            cursor,
+            last_table_key,
+            #[cfg(debug_assertions)]
+            last_written_key: None,
+        })
+    }
+
+    /// Returns `true` if all following entries are appended to the table.
+    pub const fn is_append_only(&self) -> bool {
+        self.last_table_key.is_none()
+    }
+
+    /// Writes the entry to the table, appending it if possible.
+    ///
+    /// Entries must be written in increasing key order. Entries that are inserted fail if the key
+    /// is already present, see [`DbCursorRW::insert`].
+    pub fn write(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        #[cfg(debug_assertions)]
+        {
+            let encoded = key.clone().encode();
+            if let Some(last_written_key) = &self.last_written_key {
+                assert!(
+                    *last_written_key < encoded,
+                    "keys must be written in increasing order: {last_written_key:?} >= {encoded:?}"
+                );
+            }
+            self.last_written_key = Some(encoded);
+        }
+
+        if let Some(last_table_key) = &self.last_table_key {
+            if key.clone().encode() <= *last_table_key {
+                return self.cursor.insert(key, value)
+            }
+            self.last_table_key = None;
+        }
+
+        self.cursor.append(key, value)
+    }
+}
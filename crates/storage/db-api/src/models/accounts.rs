@@ -4,7 +4,7 @@ use std::ops::{Range, RangeInclusive};
 
 use crate::{
     impl_fixed_arbitrary,
-    table::{Decode, Encode},
+    table::{Decode, Encode, FixedSizeKey, OrderedKey},
     DatabaseError,
 };
 use alloy_primitives::{Address, BlockNumber, StorageKey};
@@ -71,6 +71,12 @@ impl Decode for BlockNumberAddress {
     }
 }
 
+impl OrderedKey for BlockNumberAddress {}
+
+impl FixedSizeKey for BlockNumberAddress {
+    const ENCODED_SIZE: usize = 28;
+}
+
 /// [`Address`] concatenated with [`StorageKey`]. Used by `reth_etl` and history stages.
 ///
 /// Since it's used as a key, it isn't compressed when encoding it.
@@ -102,6 +108,12 @@ impl Decode for AddressStorageKey {
     }
 }
 
+impl OrderedKey for AddressStorageKey {}
+
+impl FixedSizeKey for AddressStorageKey {
+    const ENCODED_SIZE: usize = 52;
+}
+
 impl_fixed_arbitrary!((BlockNumberAddress, 28), (AddressStorageKey, 52));
 
 #[cfg(test)]
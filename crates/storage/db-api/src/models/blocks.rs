@@ -1,7 +1,7 @@
 //! Block related models and types.
 
 use alloy_consensus::Header;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use reth_codecs::{add_arbitrary_tests, Compact};
 use serde::{Deserialize, Serialize};
 
@@ -37,6 +37,19 @@ impl<H: Compact> Compact for StoredBlockOmmers<H> {
 /// Hash of the block header.
 pub type HeaderHash = B256;
 
+/// The storage representation of a block that was found to be invalid by the engine.
+///
+/// It is keyed by the hash of the invalid block.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredInvalidBlock {
+    /// The number of the invalid block.
+    pub number: BlockNumber,
+    /// The hash of the parent of the invalid block.
+    pub parent_hash: B256,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
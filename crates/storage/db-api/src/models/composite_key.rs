@@ -0,0 +1,113 @@
+//! Composite key
+use crate::{
+    table::{Decode, Encode, FixedSizeKey, OrderedKey},
+    DatabaseError,
+};
+use serde::{Deserialize, Serialize};
+
+/// A key composed of two keys, encoded as the concatenation of their encodings.
+///
+/// Keys are ordered by their prefix first, and by their suffix if the prefixes are equal. Since
+/// the prefix is a [`FixedSizeKey`], the encoding preserves this ordering, which makes the
+/// composite key an [`OrderedKey`]. This allows tables to be keyed by multiple values, e.g. by
+/// address first, without implementing a new key codec.
+///
+/// Composite keys can be nested to compose more than two keys, e.g.
+/// `CompositeKey<Address, CompositeKey<B256, BlockNumber>>`.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct CompositeKey<P, S> {
+    /// The key that is compared first.
+    pub prefix: P,
+    /// The key that is compared if the prefixes are equal.
+    pub suffix: S,
+}
+
+impl<P, S> CompositeKey<P, S> {
+    /// Creates a new `CompositeKey`.
+    pub const fn new(prefix: P, suffix: S) -> Self {
+        Self { prefix, suffix }
+    }
+}
+
+impl<P: FixedSizeKey, S: OrderedKey> Encode for CompositeKey<P, S> {
+    type Encoded = Vec<u8>;
+
+    fn encode(self) -> Self::Encoded {
+        let mut buf: Vec<u8> = self.prefix.encode().into();
+        buf.extend_from_slice(self.suffix.encode().as_ref());
+        buf
+    }
+}
+
+impl<P: FixedSizeKey, S: OrderedKey> Decode for CompositeKey<P, S> {
+    fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+        if value.len() < P::ENCODED_SIZE {
+            return Err(DatabaseError::Decode)
+        }
+        let (prefix, suffix) = value.split_at(P::ENCODED_SIZE);
+        Ok(Self::new(P::decode(prefix)?, S::decode(suffix)?))
+    }
+}
+
+impl<P: FixedSizeKey, S: OrderedKey> OrderedKey for CompositeKey<P, S> {}
+
+impl<P: FixedSizeKey, S: FixedSizeKey> FixedSizeKey for CompositeKey<P, S> {
+    const ENCODED_SIZE: usize = P::ENCODED_SIZE + S::ENCODED_SIZE;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{check_key_ordering, KeyOrderingError};
+    use alloy_primitives::{address, Address};
+
+    #[test]
+    fn composite_key_ordering() {
+        let low = address!("0x0000000000000000000000000000000000000001");
+        let high = address!("0xff00000000000000000000000000000000000000");
+        let keys = [low, high].into_iter().flat_map(|address| {
+            [0u64, 1, 255, 256, u64::MAX].map(|number| CompositeKey::new(address, number))
+        });
+        assert_eq!(check_key_ordering(keys), Ok(()));
+
+        let keys = [low, high].into_iter().flat_map(|address| {
+            ["", "a", "ab", "b"].map(|suffix| CompositeKey::new(address, suffix.to_string()))
+        });
+        assert_eq!(check_key_ordering(keys), Ok(()));
+
+        let key = CompositeKey::<Address, CompositeKey<u32, u64>>::new(
+            high,
+            CompositeKey::new(1, u64::MAX),
+        );
+        let encoded = key.clone().encode();
+        assert_eq!(encoded.len(), CompositeKey::<Address, CompositeKey<u32, u64>>::ENCODED_SIZE);
+        assert_eq!(Decode::decode(&encoded), Ok(key));
+    }
+
+    #[test]
+    fn little_endian_key_ordering() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+        struct LittleEndian(u64);
+
+        impl Encode for LittleEndian {
+            type Encoded = [u8; 8];
+
+            fn encode(self) -> Self::Encoded {
+                self.0.to_le_bytes()
+            }
+        }
+
+        impl Decode for LittleEndian {
+            fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+                Ok(Self(u64::from_le_bytes(value.try_into().map_err(|_| DatabaseError::Decode)?)))
+            }
+        }
+
+        assert_eq!(
+            check_key_ordering([LittleEndian(1), LittleEndian(256)]),
+            Err(KeyOrderingError::Ordering(LittleEndian(256), LittleEndian(1)))
+        );
+    }
+}
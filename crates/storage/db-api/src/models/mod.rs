@@ -1,7 +1,7 @@
 //! Implements data structures specific to the database
 
 use crate::{
-    table::{Compress, Decode, Decompress, Encode},
+    table::{Compress, Decode, Decompress, Encode, FixedSizeKey, OrderedKey},
     DatabaseError,
 };
 use alloy_consensus::Header;
@@ -10,27 +10,31 @@ use alloy_primitives::{Address, Bytes, Log, B256, U256};
 use reth_codecs::{add_arbitrary_tests, Compact};
 use reth_ethereum_primitives::{Receipt, TransactionSigned, TxType};
 use reth_primitives_traits::{Account, Bytecode, StorageEntry};
-use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_prune_types::{PruneCheckpoint, PruneRun, PruneSegment};
 use reth_stages_types::StageCheckpoint;
 use reth_trie_common::{StoredNibbles, StoredNibblesSubKey, *};
 use serde::{Deserialize, Serialize};
 
 pub mod accounts;
 pub mod blocks;
+pub mod composite_key;
 pub mod integer_list;
 pub mod sharded_key;
 pub mod storage_sharded_key;
 
 pub use accounts::*;
 pub use blocks::*;
+pub use composite_key::CompositeKey;
 pub use integer_list::IntegerList;
 pub use reth_db_models::{
     AccountBeforeTx, ClientVersion, StaticFileBlockWithdrawals, StoredBlockBodyIndices,
-    StoredBlockWithdrawals,
+    StoredBlockWithdrawals, TableStat,
 };
 pub use sharded_key::ShardedKey;
 
-/// Macro that implements [`Encode`] and [`Decode`] for uint types.
+/// Macro that implements [`Encode`], [`Decode`] and [`FixedSizeKey`] for uint types.
+///
+/// Integers are encoded as big-endian, so that their encoding preserves their ordering.
 macro_rules! impl_uints {
     ($($name:tt),+) => {
         $(
@@ -51,6 +55,12 @@ macro_rules! impl_uints {
                     )
                 }
             }
+
+            impl OrderedKey for $name {}
+
+            impl FixedSizeKey for $name {
+                const ENCODED_SIZE: usize = std::mem::size_of::<$name>();
+            }
         )+
     };
 }
@@ -75,6 +85,8 @@ impl Decode for Vec<u8> {
     }
 }
 
+impl OrderedKey for Vec<u8> {}
+
 impl Encode for Address {
     type Encoded = [u8; 20];
 
@@ -89,6 +101,12 @@ impl Decode for Address {
     }
 }
 
+impl OrderedKey for Address {}
+
+impl FixedSizeKey for Address {
+    const ENCODED_SIZE: usize = 20;
+}
+
 impl Encode for B256 {
     type Encoded = [u8; 32];
 
@@ -103,6 +121,12 @@ impl Decode for B256 {
     }
 }
 
+impl OrderedKey for B256 {}
+
+impl FixedSizeKey for B256 {
+    const ENCODED_SIZE: usize = 32;
+}
+
 impl Encode for String {
     type Encoded = Vec<u8>;
 
@@ -121,6 +145,8 @@ impl Decode for String {
     }
 }
 
+impl OrderedKey for String {}
+
 impl Encode for StoredNibbles {
     type Encoded = Vec<u8>;
 
@@ -138,6 +164,8 @@ impl Decode for StoredNibbles {
     }
 }
 
+impl OrderedKey for StoredNibbles {}
+
 impl Encode for StoredNibblesSubKey {
     type Encoded = Vec<u8>;
 
@@ -155,6 +183,12 @@ impl Decode for StoredNibblesSubKey {
     }
 }
 
+impl OrderedKey for StoredNibblesSubKey {}
+
+impl FixedSizeKey for StoredNibblesSubKey {
+    const ENCODED_SIZE: usize = 65;
+}
+
 impl Encode for PruneSegment {
     type Encoded = [u8; 1];
 
@@ -171,6 +205,12 @@ impl Decode for PruneSegment {
     }
 }
 
+impl OrderedKey for PruneSegment {}
+
+impl FixedSizeKey for PruneSegment {
+    const ENCODED_SIZE: usize = 1;
+}
+
 impl Encode for ClientVersion {
     type Encoded = Vec<u8>;
 
@@ -225,6 +265,7 @@ impl_compression_for_compact!(
     StoredBlockBodyIndices,
     StoredBlockOmmers<H>,
     StoredBlockWithdrawals,
+    StoredInvalidBlock,
     StaticFileBlockWithdrawals,
     Bytecode,
     AccountBeforeTx,
@@ -232,7 +273,9 @@ impl_compression_for_compact!(
     CompactU256,
     StageCheckpoint,
     PruneCheckpoint,
+    PruneRun,
     ClientVersion,
+    TableStat,
     // Non-DB
     GenesisAccount
 );
@@ -324,7 +367,7 @@ mod tests {
         use super::*;
         use reth_codecs::{test_utils::UnusedBits, validate_bitflag_backwards_compat};
         use reth_primitives_traits::Account;
-        use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+        use reth_prune_types::{PruneCheckpoint, PruneMode, PruneRun, PruneSegment};
         use reth_stages_types::{
             AccountHashingCheckpoint, CheckpointBlockRange, EntitiesCheckpoint,
             ExecutionCheckpoint, HeadersCheckpoint, IndexHistoryCheckpoint, StageCheckpoint,
@@ -342,6 +385,7 @@ mod tests {
         assert_eq!(IndexHistoryCheckpoint::bitflag_encoded_bytes(), 0);
         assert_eq!(PruneCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(PruneMode::bitflag_encoded_bytes(), 1);
+        assert_eq!(PruneRun::bitflag_encoded_bytes(), 2);
         assert_eq!(PruneSegment::bitflag_encoded_bytes(), 1);
         assert_eq!(Receipt::bitflag_encoded_bytes(), 1);
         assert_eq!(StageCheckpoint::bitflag_encoded_bytes(), 1);
@@ -349,6 +393,7 @@ mod tests {
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockWithdrawals::bitflag_encoded_bytes(), 0);
         assert_eq!(StorageHashingCheckpoint::bitflag_encoded_bytes(), 1);
+        assert_eq!(TableStat::bitflag_encoded_bytes(), 1);
 
         validate_bitflag_backwards_compat!(Account, UnusedBits::NotZero);
         validate_bitflag_backwards_compat!(AccountHashingCheckpoint, UnusedBits::NotZero);
@@ -362,6 +407,7 @@ mod tests {
         validate_bitflag_backwards_compat!(IndexHistoryCheckpoint, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(PruneCheckpoint, UnusedBits::NotZero);
         validate_bitflag_backwards_compat!(PruneMode, UnusedBits::Zero);
+        validate_bitflag_backwards_compat!(PruneRun, UnusedBits::NotZero);
         validate_bitflag_backwards_compat!(PruneSegment, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(Receipt, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StageCheckpoint, UnusedBits::NotZero);
@@ -369,5 +415,6 @@ mod tests {
         validate_bitflag_backwards_compat!(StoredBlockBodyIndices, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StoredBlockWithdrawals, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StorageHashingCheckpoint, UnusedBits::NotZero);
+        validate_bitflag_backwards_compat!(TableStat, UnusedBits::Zero);
     }
 }
@@ -1,6 +1,6 @@
 //! Sharded key
 use crate::{
-    table::{Decode, Encode},
+    table::{Decode, Encode, FixedSizeKey, OrderedKey},
     DatabaseError,
 };
 use alloy_primitives::BlockNumber;
@@ -61,3 +61,11 @@ impl<T: Decode> Decode for ShardedKey<T> {
         Ok(Self::new(key, highest_tx_number))
     }
 }
+
+/// The key is followed by the block number, so its encoding must be fixed size to preserve the
+/// ordering.
+impl<T: FixedSizeKey> OrderedKey for ShardedKey<T> {}
+
+impl<T: FixedSizeKey> FixedSizeKey for ShardedKey<T> {
+    const ENCODED_SIZE: usize = T::ENCODED_SIZE + 8;
+}
@@ -1,6 +1,6 @@
 //! Storage sharded key
 use crate::{
-    table::{Decode, Encode},
+    table::{Decode, Encode, FixedSizeKey, OrderedKey},
     DatabaseError,
 };
 use alloy_primitives::{Address, BlockNumber, B256};
@@ -81,3 +81,9 @@ impl Decode for StorageShardedKey {
         Ok(Self { address, sharded_key: ShardedKey::new(storage_key, highest_tx_number) })
     }
 }
+
+impl OrderedKey for StorageShardedKey {}
+
+impl FixedSizeKey for StorageShardedKey {
+    const ENCODED_SIZE: usize = STORAGE_SHARD_KEY_BYTES_SIZE;
+}
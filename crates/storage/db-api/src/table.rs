@@ -71,6 +71,70 @@ pub trait Key: Encode + Decode + Ord + Clone + Serialize + for<'a> Deserialize<'
 
 impl<T> Key for T where T: Encode + Decode + Ord + Clone + Serialize + for<'a> Deserialize<'a> {}
 
+/// A [`Key`] whose encoding preserves its ordering.
+///
+/// The database sorts keys by their encoded bytes, while range queries and cursors compare keys
+/// using their [`Ord`] implementation. Both orderings must agree: for any two keys `a` and `b`,
+/// `a.cmp(&b)` must equal `a.encode().as_ref().cmp(b.encode().as_ref())`. For example, integers
+/// must be encoded as big-endian.
+///
+/// Implementing this trait asserts that the encoding upholds this, which can be verified with
+/// [`check_key_ordering`]. Keys that are composed of other keys can use
+/// [`CompositeKey`](crate::models::CompositeKey), which preserves the ordering by construction.
+pub trait OrderedKey: Key {}
+
+/// An [`OrderedKey`] that is always encoded to the same number of bytes.
+///
+/// Only fixed size keys can be followed by another key in a composite key, because the encoding
+/// of a shorter key would otherwise be compared against the encoding of the key that follows it.
+pub trait FixedSizeKey: OrderedKey {
+    /// The length of the encoded key.
+    const ENCODED_SIZE: usize;
+}
+
+/// An error returned by [`check_key_ordering`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum KeyOrderingError<K: Debug> {
+    /// The encoded key does not decode to the key.
+    #[error("key {0:?} does not decode to itself")]
+    Roundtrip(K),
+    /// The keys are ordered differently than their encodings.
+    #[error("keys {0:?} and {1:?} are ordered differently than their encodings")]
+    Ordering(K, K),
+}
+
+/// Checks that the encoding of the given keys roundtrips and preserves their ordering.
+///
+/// This can be used to audit the [`OrderedKey`] implementation of custom keys, e.g. with keys
+/// that are generated by a property test.
+pub fn check_key_ordering<K: Key>(
+    keys: impl IntoIterator<Item = K>,
+) -> Result<(), KeyOrderingError<K>> {
+    let mut keys = keys
+        .into_iter()
+        .map(|key| {
+            let encoded: Vec<u8> = key.clone().encode().into();
+            (key, encoded)
+        })
+        .collect::<Vec<_>>();
+
+    for (key, encoded) in &keys {
+        if K::decode(encoded).ok().as_ref() != Some(key) {
+            return Err(KeyOrderingError::Roundtrip(key.clone()))
+        }
+    }
+
+    keys.sort_unstable_by(|(_, a), (_, b)| a.cmp(b));
+    for pair in keys.windows(2) {
+        let ((a, encoded_a), (b, encoded_b)) = (&pair[0], &pair[1]);
+        if a.cmp(b) != encoded_a.cmp(encoded_b) {
+            return Err(KeyOrderingError::Ordering(a.clone(), b.clone()))
+        }
+    }
+
+    Ok(())
+}
+
 /// Generic trait that enforces the database value to implement [`Compress`] and [`Decompress`].
 pub trait Value: Compress + Decompress + Serialize {}
 
@@ -93,8 +157,8 @@ pub trait Table: Send + Sync + Debug + 'static {
 
     /// Key element of `Table`.
     ///
-    /// Sorting should be taken into account when encoding this.
-    type Key: Key;
+    /// Sorting should be taken into account when encoding this, see [`OrderedKey`].
+    type Key: OrderedKey;
 
     /// Value element of `Table`.
     type Value: Value;
@@ -118,10 +182,10 @@ pub type TableRow<T> = (<T as Table>::Key, <T as Table>::Value);
 pub trait DupSort: Table {
     /// The table subkey. This type must implement [`Encode`] and [`Decode`].
     ///
-    /// Sorting should be taken into account when encoding this.
+    /// Sorting should be taken into account when encoding this, see [`OrderedKey`].
     ///
     /// Upstream docs: <https://libmdbx.dqdkfa.ru/usage.html#autotoc_md48>
-    type SubKey: Key;
+    type SubKey: OrderedKey;
 }
 
 /// Allows duplicating tables across databases
@@ -19,18 +19,18 @@ pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 use crate::{
     models::{
         accounts::BlockNumberAddress,
-        blocks::{HeaderHash, StoredBlockOmmers},
+        blocks::{HeaderHash, StoredBlockOmmers, StoredInvalidBlock},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
-        StoredBlockBodyIndices, StoredBlockWithdrawals,
+        StoredBlockBodyIndices, StoredBlockWithdrawals, TableStat,
     },
-    table::{Decode, DupSort, Encode, Table, TableInfo},
+    table::{Decode, DupSort, Encode, FixedSizeKey, OrderedKey, Table, TableInfo},
 };
 use alloy_consensus::Header;
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_primitives_traits::{Account, Bytecode, StorageEntry};
-use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_prune_types::{PruneCheckpoint, PruneRun, PruneSegment};
 use reth_stages_types::StageCheckpoint;
 use reth_trie_common::{BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey};
 use serde::{Deserialize, Serialize};
@@ -436,6 +436,17 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the transactions sent by each address.
+    ///
+    /// This is an optional index, it is only populated if the sender transactions index is enabled
+    /// in the node configuration. Shards follow the same layout as [`AccountsHistory`], except
+    /// that the stored integers are [`TxNumber`]s instead of block numbers. The last shard of a
+    /// sender is keyed with `u64::MAX`.
+    table SenderTransactions {
+        type Key = ShardedKey<Address>;
+        type Value = TxNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -512,6 +523,12 @@ tables! {
         type Value = PruneCheckpoint;
     }
 
+    /// Stores the statistics of the last pruner run of each prune segment.
+    table PruneRuns {
+        type Key = PruneSegment;
+        type Value = PruneRun;
+    }
+
     /// Stores the history of client versions that have accessed the database with write privileges by unix timestamp in seconds.
     table VersionHistory {
         type Key = u64;
@@ -523,6 +540,20 @@ tables! {
         type Key = ChainStateKey;
         type Value = BlockNumber;
     }
+
+    /// Stores the blocks that were found to be invalid by the engine, so that they are not
+    /// executed again after a restart.
+    table InvalidBlocks {
+        type Key = BlockHash;
+        type Value = StoredInvalidBlock;
+    }
+
+    /// Stores the entry count and approximate size of each table by table name, updated by the
+    /// write transactions that modify the tables.
+    table TableStats {
+        type Key = String;
+        type Value = TableStat;
+    }
 }
 
 /// Keys for the `ChainState` table.
@@ -555,11 +586,20 @@ impl Decode for ChainStateKey {
     }
 }
 
+impl OrderedKey for ChainStateKey {}
+
+impl FixedSizeKey for ChainStateKey {
+    const ENCODED_SIZE: usize = 1;
+}
+
 // Alias types.
 
 /// List with transaction numbers.
 pub type BlockNumberList = IntegerList;
 
+/// List with transaction numbers, used by [`SenderTransactions`].
+pub type TxNumberList = IntegerList;
+
 /// Encoded stage id.
 pub type StageId = String;
 
@@ -1,5 +1,5 @@
 use crate::{
-    table::{Compress, Decode, Decompress, DupSort, Encode, Key, Table, Value},
+    table::{Compress, Decode, Decompress, DupSort, Encode, Key, OrderedKey, Table, Value},
     DatabaseError,
 };
 use serde::{Deserialize, Serialize};
@@ -98,6 +98,9 @@ impl<K: Key> Encode for RawKey<K> {
     }
 }
 
+// Raw keys are ordered by their encoding.
+impl<K: Key> OrderedKey for RawKey<K> {}
+
 // Decode
 impl<K: Key> Decode for RawKey<K> {
     fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
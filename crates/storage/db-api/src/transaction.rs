@@ -59,4 +59,11 @@ pub trait DbTxMut: Send + Sync {
     fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError>;
     /// `DupCursor` mut.
     fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError>;
+    /// Records the entry count and size of the tables modified in this transaction into the
+    /// [`TableStats`](crate::tables::TableStats) table.
+    ///
+    /// Should be called right before the transaction is committed. Does nothing by default.
+    fn update_table_stats(&self) -> Result<(), DatabaseError> {
+        Ok(())
+    }
 }
@@ -22,3 +22,7 @@ pub use blocks::{StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlock
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
+
+/// Table statistics
+pub mod table_stats;
+pub use table_stats::TableStat;
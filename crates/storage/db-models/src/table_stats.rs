@@ -0,0 +1,17 @@
+//! Table statistics model.
+
+/// Entry count and approximate size of a database table.
+///
+/// Updated by every write transaction that modifies the table, so it can be read without
+/// traversing the table.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableStat {
+    /// Number of entries in the table.
+    pub entries: u64,
+    /// Size of the pages used by the table, in bytes.
+    pub size_bytes: u64,
+}
@@ -0,0 +1,344 @@
+//! Encryption-at-rest for the values of selected tables.
+//!
+//! Values of the tables configured in [`TableEncryption`] are encrypted right before they are
+//! written to MDBX and decrypted right after they are read, so they never hit the disk in
+//! plaintext. Keys are left untouched to keep the ordering of the table intact, which is also why
+//! `DUPSORT` tables can't be encrypted: their values are sorted by the encoded subkey.
+
+use crate::DatabaseError;
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Nonce,
+};
+use alloy_primitives::hex;
+use reth_db_api::table::Table;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Identifier of an [`EncryptionKey`], stored alongside every encrypted value.
+pub type EncryptionKeyId = u32;
+
+/// Size of the nonce of an AES-GCM encrypted value.
+const NONCE_LEN: usize = 12;
+
+/// Size of the header of an encrypted value: the key id followed by the nonce.
+const HEADER_LEN: usize = size_of::<EncryptionKeyId>() + NONCE_LEN;
+
+/// A 256-bit key used to encrypt table values.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey([u8; 32]);
+
+impl EncryptionKey {
+    /// Creates a new key from raw bytes.
+    pub const fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+}
+
+impl Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptionKey(..)")
+    }
+}
+
+/// The set of keys values can be encrypted with.
+///
+/// New values are always encrypted with the current key, the other keys are kept to decrypt values
+/// that were written before the key was rotated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptionKeys {
+    current: EncryptionKeyId,
+    keys: BTreeMap<EncryptionKeyId, EncryptionKey>,
+}
+
+impl EncryptionKeys {
+    /// Creates a new key set. Returns an error if the current key is not part of `keys`.
+    pub fn new(
+        current: EncryptionKeyId,
+        keys: BTreeMap<EncryptionKeyId, EncryptionKey>,
+    ) -> Result<Self, DatabaseError> {
+        if !keys.contains_key(&current) {
+            return Err(DatabaseError::Other(format!("current encryption key {current} is missing")))
+        }
+        Ok(Self { current, keys })
+    }
+
+    /// Returns the id of the key new values are encrypted with.
+    pub const fn current(&self) -> EncryptionKeyId {
+        self.current
+    }
+}
+
+/// Source of the keys used to encrypt table values.
+///
+/// Implement this to fetch the keys from a key management service. [`FileKeyProvider`] reads them
+/// from a local file.
+pub trait EncryptionKeyProvider: Debug + Send + Sync {
+    /// Loads the encryption keys.
+    fn load_keys(&self) -> Result<EncryptionKeys, DatabaseError>;
+}
+
+/// Reads encryption keys from a file.
+///
+/// The file contains one `<id>:<hex encoded 32 byte key>` pair per line. Empty lines and lines
+/// starting with `#` are ignored. The key with the highest id is the current key, so a key is
+/// rotated by appending a new line with a higher id.
+#[derive(Debug, Clone)]
+pub struct FileKeyProvider {
+    path: PathBuf,
+}
+
+impl FileKeyProvider {
+    /// Creates a new provider reading the keys from the given file.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf() }
+    }
+}
+
+impl EncryptionKeyProvider for FileKeyProvider {
+    fn load_keys(&self) -> Result<EncryptionKeys, DatabaseError> {
+        let content = reth_fs_util::read_to_string(&self.path)
+            .map_err(|err| DatabaseError::Other(err.to_string()))?;
+        parse_keys(&content).map_err(|err| {
+            DatabaseError::Other(format!(
+                "invalid encryption key file {}: {err}",
+                self.path.display()
+            ))
+        })
+    }
+}
+
+/// Parses the content of a key file, see [`FileKeyProvider`].
+fn parse_keys(content: &str) -> Result<EncryptionKeys, String> {
+    let mut keys = BTreeMap::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue
+        }
+
+        let (id, key) =
+            line.split_once(':').ok_or_else(|| format!("line {}: expected <id>:<key>", idx + 1))?;
+        let id = id
+            .trim()
+            .parse::<EncryptionKeyId>()
+            .map_err(|err| format!("line {}: {err}", idx + 1))?;
+        let key = hex::decode_to_array::<_, 32>(key.trim())
+            .map_err(|err| format!("line {}: {err}", idx + 1))?;
+        if keys.insert(id, EncryptionKey::new(key)).is_some() {
+            return Err(format!("line {}: duplicate key id {id}", idx + 1))
+        }
+    }
+
+    let current = *keys.keys().next_back().ok_or("no keys")?;
+    EncryptionKeys::new(current, keys).map_err(|err| err.to_string())
+}
+
+/// Encrypts and decrypts table values.
+pub trait ValueCipher: Debug + Send + Sync {
+    /// Encrypts the `value` stored under the encoded `key` in `table`, appending the result to
+    /// `out`.
+    fn encrypt(
+        &self,
+        table: &str,
+        key: &[u8],
+        value: &[u8],
+        out: &mut Vec<u8>,
+    ) -> Result<(), DatabaseError>;
+
+    /// Decrypts the `value` stored under the encoded `key` in `table`.
+    fn decrypt(&self, table: &str, key: &[u8], value: &[u8]) -> Result<Vec<u8>, DatabaseError>;
+}
+
+/// [`ValueCipher`] using AES-256-GCM.
+///
+/// Every value is encrypted with a random nonce, and authenticated together with the table name
+/// and its key, so values can't be moved between rows unnoticed. Encrypted values are laid out as
+/// `key id (4 bytes, big endian) || nonce (12 bytes) || ciphertext || tag (16 bytes)`.
+pub struct AesGcmCipher {
+    current: EncryptionKeyId,
+    ciphers: BTreeMap<EncryptionKeyId, Aes256Gcm>,
+}
+
+impl AesGcmCipher {
+    /// Creates a new cipher with the given keys.
+    pub fn new(keys: EncryptionKeys) -> Self {
+        let ciphers =
+            keys.keys.iter().map(|(id, key)| (*id, Aes256Gcm::new(&key.0.into()))).collect();
+        Self { current: keys.current, ciphers }
+    }
+
+    /// Creates a new cipher with the keys loaded from the given provider.
+    pub fn from_provider(provider: &dyn EncryptionKeyProvider) -> Result<Self, DatabaseError> {
+        provider.load_keys().map(Self::new)
+    }
+
+    /// Returns the additional authenticated data for a value.
+    fn aad(table: &str, key: &[u8]) -> Vec<u8> {
+        [table.as_bytes(), &[0], key].concat()
+    }
+}
+
+impl Debug for AesGcmCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AesGcmCipher")
+            .field("current", &self.current)
+            .field("keys", &self.ciphers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ValueCipher for AesGcmCipher {
+    fn encrypt(
+        &self,
+        table: &str,
+        key: &[u8],
+        value: &[u8],
+        out: &mut Vec<u8>,
+    ) -> Result<(), DatabaseError> {
+        let cipher = &self.ciphers[&self.current];
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: value, aad: &Self::aad(table, key) })
+            .map_err(|_| DatabaseError::Other(format!("failed to encrypt value in {table}")))?;
+
+        out.reserve(HEADER_LEN + ciphertext.len());
+        out.extend_from_slice(&self.current.to_be_bytes());
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(())
+    }
+
+    fn decrypt(&self, table: &str, key: &[u8], value: &[u8]) -> Result<Vec<u8>, DatabaseError> {
+        if value.len() < HEADER_LEN {
+            return Err(DatabaseError::Other(format!("encrypted value in {table} is too short")))
+        }
+
+        let (key_id, rest) = value.split_at(size_of::<EncryptionKeyId>());
+        let key_id = EncryptionKeyId::from_be_bytes(key_id.try_into().expect("4 bytes"));
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let cipher = self.ciphers.get(&key_id).ok_or_else(|| {
+            DatabaseError::Other(format!("unknown encryption key {key_id} for value in {table}"))
+        })?;
+
+        cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload { msg: ciphertext, aad: &Self::aad(table, key) },
+            )
+            .map_err(|_| DatabaseError::Other(format!("failed to decrypt value in {table}")))
+    }
+}
+
+/// Configures which tables are encrypted and the [`ValueCipher`] used for them.
+#[derive(Debug, Clone)]
+pub struct TableEncryption {
+    cipher: Arc<dyn ValueCipher>,
+    /// Names of the encrypted tables and whether they are `DUPSORT` tables.
+    tables: Vec<(&'static str, bool)>,
+}
+
+impl TableEncryption {
+    /// Creates a new configuration that encrypts no tables yet.
+    pub fn new(cipher: impl ValueCipher + 'static) -> Self {
+        Self { cipher: Arc::new(cipher), tables: Vec::new() }
+    }
+
+    /// Encrypts the values of table `T`.
+    ///
+    /// `DUPSORT` tables are rejected when the database is opened.
+    pub fn with_table<T: Table>(mut self) -> Self {
+        if !self.tables.iter().any(|(name, _)| *name == T::NAME) {
+            self.tables.push((T::NAME, T::DUPSORT));
+        }
+        self
+    }
+
+    /// Returns the names of the encrypted tables.
+    pub fn tables(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.tables.iter().map(|(name, _)| *name)
+    }
+
+    /// Returns an error if any of the encrypted tables is a `DUPSORT` table.
+    pub(crate) fn validate(&self) -> Result<(), DatabaseError> {
+        if let Some((name, _)) = self.tables.iter().find(|(_, dupsort)| *dupsort) {
+            return Err(DatabaseError::Other(format!(
+                "encryption is not supported for DUPSORT table {name}"
+            )))
+        }
+        Ok(())
+    }
+
+    /// Returns the cipher if the given table is encrypted.
+    pub(crate) fn cipher_for(&self, table: &str) -> Option<&Arc<dyn ValueCipher>> {
+        self.tables.iter().any(|(name, _)| *name == table).then_some(&self.cipher)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables;
+
+    fn cipher(keys: &[(EncryptionKeyId, u8)], current: EncryptionKeyId) -> AesGcmCipher {
+        let keys = keys.iter().map(|(id, byte)| (*id, EncryptionKey::new([*byte; 32]))).collect();
+        AesGcmCipher::new(EncryptionKeys::new(current, keys).unwrap())
+    }
+
+    #[test]
+    fn roundtrip_and_rotation() {
+        let old = cipher(&[(1, 0xaa)], 1);
+        let mut encrypted = Vec::new();
+        old.encrypt("Headers", b"key", b"value", &mut encrypted).unwrap();
+        assert_ne!(&encrypted[HEADER_LEN..], b"value");
+        assert_eq!(old.decrypt("Headers", b"key", &encrypted).unwrap(), b"value");
+
+        // Value is bound to its table and key
+        assert!(old.decrypt("Bodies", b"key", &encrypted).is_err());
+        assert!(old.decrypt("Headers", b"other", &encrypted).is_err());
+
+        // Rotated cipher still decrypts values written with the previous key
+        let rotated = cipher(&[(1, 0xaa), (2, 0xbb)], 2);
+        assert_eq!(rotated.decrypt("Headers", b"key", &encrypted).unwrap(), b"value");
+        let mut reencrypted = Vec::new();
+        rotated.encrypt("Headers", b"key", b"value", &mut reencrypted).unwrap();
+        assert_eq!(&reencrypted[..4], &2u32.to_be_bytes());
+
+        // Once the previous key is dropped, old values can't be read anymore
+        let dropped = cipher(&[(2, 0xbb)], 2);
+        assert!(dropped.decrypt("Headers", b"key", &encrypted).is_err());
+        assert_eq!(dropped.decrypt("Headers", b"key", &reencrypted).unwrap(), b"value");
+    }
+
+    #[test]
+    fn parse_key_file() {
+        let keys = parse_keys(&format!(
+            "# rotated 2025-01-01\n1:{}\n\n2:0x{}\n",
+            hex::encode([1u8; 32]),
+            hex::encode([2u8; 32])
+        ))
+        .unwrap();
+        assert_eq!(keys.current(), 2);
+        assert_eq!(keys.keys.len(), 2);
+
+        assert!(parse_keys("").is_err());
+        assert!(parse_keys("1:abcd").is_err());
+        assert!(parse_keys(&format!("1:{0}\n1:{0}", hex::encode([1u8; 32]))).is_err());
+    }
+
+    #[test]
+    fn rejects_dupsort_tables() {
+        let encryption =
+            TableEncryption::new(cipher(&[(1, 0xaa)], 1)).with_table::<tables::Headers>();
+        assert!(encryption.validate().is_ok());
+        assert!(encryption.cipher_for(tables::Headers::NAME).is_some());
+        assert!(encryption.cipher_for(tables::Transactions::NAME).is_none());
+
+        let encryption = encryption.with_table::<tables::PlainStorageState>();
+        assert!(encryption.validate().is_err());
+    }
+}
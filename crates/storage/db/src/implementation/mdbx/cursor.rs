@@ -1,6 +1,6 @@
 //! Cursor wrapper for libmdbx-sys.
 
-use super::utils::*;
+use super::{cipher::ValueCipher, utils::*};
 use crate::{
     metrics::{DatabaseEnvMetrics, Operation},
     DatabaseError,
@@ -31,6 +31,8 @@ pub struct Cursor<K: TransactionKind, T: Table> {
     buf: Vec<u8>,
     /// Reference to metric handles in the DB environment. If `None`, metrics are not recorded.
     metrics: Option<Arc<DatabaseEnvMetrics>>,
+    /// Cipher for the values of the table. If `None`, the table is not encrypted.
+    cipher: Option<Arc<dyn ValueCipher>>,
     /// Phantom data to enforce encoding/decoding.
     _dbi: PhantomData<T>,
 }
@@ -40,7 +42,32 @@ impl<K: TransactionKind, T: Table> Cursor<K, T> {
         inner: reth_libmdbx::Cursor<K>,
         metrics: Option<Arc<DatabaseEnvMetrics>>,
     ) -> Self {
-        Self { inner, buf: Vec::new(), metrics, _dbi: PhantomData }
+        Self { inner, buf: Vec::new(), metrics, cipher: None, _dbi: PhantomData }
+    }
+
+    /// Sets the cipher for the values of the table.
+    pub(crate) fn with_cipher(mut self, cipher: Option<Arc<dyn ValueCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    /// Encrypts the compressed value if the table is encrypted, leaving the result in the buffer.
+    ///
+    /// Returns the value to write, following the convention of `compress_to_buf_or_ref!`.
+    fn encrypt_value<'a>(
+        &mut self,
+        key: &[u8],
+        value: Option<&'a [u8]>,
+    ) -> Result<Option<&'a [u8]>, DatabaseError> {
+        let Some(cipher) = &self.cipher else { return Ok(value) };
+
+        let plaintext = match value {
+            Some(value) => value.to_vec(),
+            None => std::mem::take(&mut self.buf),
+        };
+        self.buf.clear();
+        cipher.encrypt(T::NAME, key, &plaintext, &mut self.buf)?;
+        Ok(None)
     }
 
     /// If `self.metrics` is `Some(...)`, record a metric with the provided operation and value
@@ -74,6 +101,25 @@ where
     res.map_err(|e| DatabaseError::Read(e.into()))?.map(decoder::<T>).transpose()
 }
 
+/// Decodes a `(key, value)` pair from the database, decrypting the value if a cipher is given.
+#[expect(clippy::type_complexity)]
+fn decode_with_cipher<T>(
+    res: Result<Option<(Cow<'_, [u8]>, Cow<'_, [u8]>)>, impl Into<DatabaseErrorInfo>>,
+    cipher: Option<&dyn ValueCipher>,
+) -> PairResult<T>
+where
+    T: Table,
+    T::Key: Decode,
+    T::Value: Decompress,
+{
+    let Some(cipher) = cipher else { return decode::<T>(res) };
+    let Some((key, value)) = res.map_err(|e| DatabaseError::Read(e.into()))? else {
+        return Ok(None)
+    };
+    let value = cipher.decrypt(T::NAME, &key, &value)?;
+    decoder::<T>((key, Cow::Owned(value))).map(Some)
+}
+
 /// Some types don't support compression (eg. B256), and we don't want to be copying them to the
 /// allocated buffer when we can just use their reference.
 macro_rules! compress_to_buf_or_ref {
@@ -90,36 +136,40 @@ macro_rules! compress_to_buf_or_ref {
 
 impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
     fn first(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.first())
+        decode_with_cipher::<T>(self.inner.first(), self.cipher.as_deref())
     }
 
     fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
-        decode::<T>(self.inner.set_key(key.encode().as_ref()))
+        decode_with_cipher::<T>(self.inner.set_key(key.encode().as_ref()), self.cipher.as_deref())
     }
 
     fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
-        decode::<T>(self.inner.set_range(key.encode().as_ref()))
+        decode_with_cipher::<T>(self.inner.set_range(key.encode().as_ref()), self.cipher.as_deref())
     }
 
     fn next(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.next())
+        decode_with_cipher::<T>(self.inner.next(), self.cipher.as_deref())
     }
 
     fn prev(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.prev())
+        decode_with_cipher::<T>(self.inner.prev(), self.cipher.as_deref())
     }
 
     fn last(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.last())
+        decode_with_cipher::<T>(self.inner.last(), self.cipher.as_deref())
     }
 
     fn current(&mut self) -> PairResult<T> {
-        decode::<T>(self.inner.get_current())
+        decode_with_cipher::<T>(self.inner.get_current(), self.cipher.as_deref())
     }
 
     fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
         let start = if let Some(start_key) = start_key {
-            decode::<T>(self.inner.set_range(start_key.encode().as_ref())).transpose()
+            decode_with_cipher::<T>(
+                self.inner.set_range(start_key.encode().as_ref()),
+                self.cipher.as_deref(),
+            )
+            .transpose()
         } else {
             self.first().transpose()
         };
@@ -138,7 +188,7 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
             }
             Bound::Unbounded => self.inner.first(),
         };
-        let start = decode::<T>(start).transpose();
+        let start = decode_with_cipher::<T>(start, self.cipher.as_deref()).transpose();
         Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
     }
 
@@ -147,7 +197,10 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
         start_key: Option<T::Key>,
     ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
         let start = if let Some(start_key) = start_key {
-            decode::<T>(self.inner.set_range(start_key.encode().as_ref()))
+            decode_with_cipher::<T>(
+                self.inner.set_range(start_key.encode().as_ref()),
+                self.cipher.as_deref(),
+            )
         } else {
             self.last()
         }
@@ -244,6 +297,7 @@ impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
     fn upsert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
         let key = key.encode();
         let value = compress_to_buf_or_ref!(self, value);
+        let value = self.encrypt_value(key.as_ref(), value)?;
         self.execute_with_operation_metric(
             Operation::CursorUpsert,
             Some(value.unwrap_or(&self.buf).len()),
@@ -266,6 +320,7 @@ impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
     fn insert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
         let key = key.encode();
         let value = compress_to_buf_or_ref!(self, value);
+        let value = self.encrypt_value(key.as_ref(), value)?;
         self.execute_with_operation_metric(
             Operation::CursorInsert,
             Some(value.unwrap_or(&self.buf).len()),
@@ -290,6 +345,7 @@ impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
     fn append(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
         let key = key.encode();
         let value = compress_to_buf_or_ref!(self, value);
+        let value = self.encrypt_value(key.as_ref(), value)?;
         self.execute_with_operation_metric(
             Operation::CursorAppend,
             Some(value.unwrap_or(&self.buf).len()),
@@ -7,6 +7,7 @@ use crate::{
     utils::default_page_size,
     DatabaseError, TableSet,
 };
+use cipher::TableEncryption;
 use eyre::Context;
 use metrics::{gauge, Label};
 use reth_db_api::{
@@ -30,6 +31,7 @@ use std::{
 };
 use tx::Tx;
 
+pub mod cipher;
 pub mod cursor;
 pub mod tx;
 
@@ -99,6 +101,13 @@ pub struct DatabaseArguments {
     ///
     /// This flag affects only at environment opening but can't be changed after.
     exclusive: Option<bool>,
+    /// Sync mode of a read-write environment. Defaults to [`SyncMode::Durable`].
+    sync_mode: SyncMode,
+    /// Whether to enable OS readahead. Readahead improves performance for linear scans, but
+    /// worsens it for random access, so it's disabled by default.
+    read_ahead: bool,
+    /// Encryption of the values of selected tables. If [None], no table is encrypted.
+    encryption: Option<TableEncryption>,
 }
 
 impl Default for DatabaseArguments {
@@ -121,6 +130,9 @@ impl DatabaseArguments {
             log_level: None,
             max_read_transaction_duration: None,
             exclusive: None,
+            sync_mode: SyncMode::Durable,
+            read_ahead: false,
+            encryption: None,
         }
     }
 
@@ -169,6 +181,24 @@ impl DatabaseArguments {
         self
     }
 
+    /// Sets the sync mode of a read-write environment.
+    pub const fn with_sync_mode(mut self, sync_mode: SyncMode) -> Self {
+        self.sync_mode = sync_mode;
+        self
+    }
+
+    /// Enables or disables OS readahead.
+    pub const fn with_read_ahead(mut self, read_ahead: bool) -> Self {
+        self.read_ahead = read_ahead;
+        self
+    }
+
+    /// Encrypts the values of the tables configured in [`TableEncryption`].
+    pub fn with_encryption(mut self, encryption: Option<TableEncryption>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
     /// Returns the client version if any.
     pub const fn client_version(&self) -> &ClientVersion {
         &self.client_version
@@ -182,6 +212,8 @@ pub struct DatabaseEnv {
     inner: Environment,
     /// Cache for metric handles. If `None`, metrics are not recorded.
     metrics: Option<Arc<DatabaseEnvMetrics>>,
+    /// Encryption of table values. If `None`, no table is encrypted.
+    encryption: Option<Arc<TableEncryption>>,
     /// Write lock for when dealing with a read-write environment.
     _lock_file: Option<StorageLock>,
 }
@@ -195,6 +227,7 @@ impl Database for DatabaseEnv {
             self.inner.begin_ro_txn().map_err(|e| DatabaseError::InitTx(e.into()))?,
             self.metrics.clone(),
         )
+        .map(|tx| tx.with_encryption(self.encryption.clone()))
         .map_err(|e| DatabaseError::InitTx(e.into()))
     }
 
@@ -203,6 +236,7 @@ impl Database for DatabaseEnv {
             self.inner.begin_rw_txn().map_err(|e| DatabaseError::InitTx(e.into()))?,
             self.metrics.clone(),
         )
+        .map(|tx| tx.with_encryption(self.encryption.clone()))
         .map_err(|e| DatabaseError::InitTx(e.into()))
     }
 }
@@ -295,6 +329,10 @@ impl DatabaseEnv {
         kind: DatabaseEnvKind,
         args: DatabaseArguments,
     ) -> Result<Self, DatabaseError> {
+        if let Some(encryption) = &args.encryption {
+            encryption.validate()?;
+        }
+
         let _lock_file = if kind.is_rw() {
             StorageLock::try_acquire(path)
                 .map_err(|err| DatabaseError::Other(err.to_string()))?
@@ -310,7 +348,7 @@ impl DatabaseEnv {
             DatabaseEnvKind::RW => {
                 // enable writemap mode in RW mode
                 inner_env.write_map();
-                Mode::ReadWrite { sync_mode: SyncMode::Durable }
+                Mode::ReadWrite { sync_mode: args.sync_mode }
             }
         };
 
@@ -367,9 +405,9 @@ impl DatabaseEnv {
 
         inner_env.set_flags(EnvironmentFlags {
             mode,
-            // We disable readahead because it improves performance for linear scans, but
-            // worsens it for random access (which is our access pattern outside of sync)
-            no_rdahead: true,
+            // Readahead is disabled by default because it improves performance for linear scans,
+            // but worsens it for random access (which is our access pattern outside of sync)
+            no_rdahead: !args.read_ahead,
             coalesce: true,
             exclusive: args.exclusive.unwrap_or_default(),
             ..Default::default()
@@ -436,6 +474,7 @@ impl DatabaseEnv {
         let env = Self {
             inner: inner_env.open(path).map_err(|e| DatabaseError::Open(e.into()))?,
             metrics: None,
+            encryption: args.encryption.map(Arc::new),
             _lock_file,
         };
 
@@ -513,9 +552,9 @@ mod tests {
     use alloy_consensus::Header;
     use alloy_primitives::{address, Address, B256, U256};
     use reth_db_api::{
-        cursor::{DbDupCursorRO, DbDupCursorRW, ReverseWalker, Walker},
+        cursor::{DbDupCursorRO, DbDupCursorRW, ReverseWalker, SortedAppendWriter, Walker},
         models::{AccountBeforeTx, IntegerList, ShardedKey},
-        table::{Encode, Table},
+        table::{Compress, Encode, Table},
     };
     use reth_libmdbx::Error;
     use reth_primitives_traits::{Account, StorageEntry};
@@ -574,6 +613,74 @@ mod tests {
         tx.commit().expect(ERROR_COMMIT);
     }
 
+    #[test]
+    fn db_encrypted_table() {
+        use super::cipher::{AesGcmCipher, EncryptionKey, EncryptionKeys};
+
+        let encryption = |keys: &[(u32, u8)]| {
+            let current = keys.iter().map(|(id, _)| *id).max().unwrap();
+            let keys = keys.iter().map(|(id, byte)| (*id, EncryptionKey::new([*byte; 32])));
+            let cipher = AesGcmCipher::new(EncryptionKeys::new(current, keys.collect()).unwrap());
+            TableEncryption::new(cipher).with_table::<Headers>()
+        };
+        let open = |path: &Path, encryption: Option<TableEncryption>| {
+            let args = DatabaseArguments::new(ClientVersion::default()).with_encryption(encryption);
+            let env = DatabaseEnv::open(path, DatabaseEnvKind::RW, args).expect(ERROR_DB_CREATION);
+            env.create_tables().expect(ERROR_TABLE_CREATION);
+            env
+        };
+        let raw_value = |env: &DatabaseEnv, key: u64| {
+            let tx = env.tx().expect(ERROR_INIT_TX);
+            let dbi = tx.get_dbi::<Headers>().unwrap();
+            tx.inner.get::<Vec<u8>>(dbi, key.encode().as_ref()).unwrap().unwrap()
+        };
+
+        let path = TempDir::new().expect(ERROR_TEMPDIR).keep();
+        let header = Header { number: 1, gas_limit: 30_000_000, ..Default::default() };
+
+        // Values are encrypted on disk, but transparently decrypted on reads
+        let env = open(&path, Some(encryption(&[(1, 0xaa)])));
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<Headers>(1, header.clone()).expect(ERROR_PUT);
+        tx.cursor_write::<Headers>().unwrap().append(2, &header).expect(ERROR_APPEND);
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.get::<Headers>(1).expect(ERROR_GET), Some(header.clone()));
+        assert_eq!(
+            tx.cursor_read::<Headers>().unwrap().walk(None).unwrap().collect::<Result<Vec<_>, _>>(),
+            Ok(vec![(1, header.clone()), (2, header.clone())])
+        );
+        drop(tx);
+        let encrypted = raw_value(&env, 1);
+        assert_eq!(&encrypted[..4], &1u32.to_be_bytes());
+        assert_ne!(encrypted, header.clone().compress().to_vec());
+        drop(env);
+
+        // Without the key the values can't be read
+        let env = open(&path, None);
+        assert!(env.tx().unwrap().get::<Headers>(1).is_err());
+        drop(env);
+
+        // After rotating the key, values are re-encrypted with the new key
+        let env = open(&path, Some(encryption(&[(1, 0xaa), (2, 0xbb)])));
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        assert_eq!(tx.reencrypt_table::<Headers>(), Ok(2));
+        assert_eq!(tx.reencrypt_table::<CanonicalHeaders>(), Ok(0));
+        tx.commit().expect(ERROR_COMMIT);
+        assert_eq!(&raw_value(&env, 2)[..4], &2u32.to_be_bytes());
+        drop(env);
+
+        let env = open(&path, Some(encryption(&[(2, 0xbb)])));
+        assert_eq!(env.tx().unwrap().get::<Headers>(2).expect(ERROR_GET), Some(header));
+
+        // DUPSORT tables can't be encrypted
+        let args = DatabaseArguments::new(ClientVersion::default())
+            .with_encryption(Some(encryption(&[(1, 0xaa)]).with_table::<PlainStorageState>()));
+        let path = TempDir::new().expect(ERROR_TEMPDIR).keep();
+        assert!(DatabaseEnv::open(&path, DatabaseEnvKind::RW, args).is_err());
+    }
+
     #[test]
     fn db_dup_cursor_delete_first() {
         let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
@@ -1084,6 +1191,50 @@ mod tests {
         tx.commit().expect(ERROR_COMMIT);
     }
 
+    #[test]
+    fn db_sorted_append_writer() {
+        let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
+
+        // PUT
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        vec![0, 1, 3, 4]
+            .into_iter()
+            .try_for_each(|key| tx.put::<CanonicalHeaders>(key, B256::ZERO))
+            .expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        // WRITE: `2` is inserted, `5` and `6` are appended
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+        let mut writer = SortedAppendWriter::new(&mut cursor).unwrap();
+        assert!(!writer.is_append_only());
+        assert_eq!(writer.write(2, &B256::ZERO), Ok(()));
+        assert!(!writer.is_append_only());
+        assert_eq!(writer.write(5, &B256::ZERO), Ok(()));
+        assert!(writer.is_append_only());
+        assert_eq!(writer.write(6, &B256::ZERO), Ok(()));
+        tx.commit().expect(ERROR_COMMIT);
+
+        // Confirm the result
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+        let res = cursor.walk(None).unwrap().map(|res| res.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(res, vec![0, 1, 2, 3, 4, 5, 6]);
+        tx.commit().expect(ERROR_COMMIT);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "keys must be written in increasing order")]
+    fn db_sorted_append_writer_unsorted() {
+        let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+        let mut writer = SortedAppendWriter::new(&mut cursor).unwrap();
+        writer.write(1, &B256::ZERO).unwrap();
+        let _ = writer.write(0, &B256::ZERO);
+    }
+
     #[test]
     fn db_cursor_upsert() {
         let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
@@ -1,12 +1,20 @@
 //! Transaction wrapper for libmdbx-sys.
 
-use super::{cursor::Cursor, utils::*};
+use super::{
+    cipher::{TableEncryption, ValueCipher},
+    cursor::Cursor,
+    utils::*,
+};
 use crate::{
     metrics::{DatabaseEnvMetrics, Operation, TransactionMode, TransactionOutcome},
-    DatabaseError,
+    tables, DatabaseError,
 };
+use metrics::{gauge, Label};
 use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::TableStat,
     table::{Compress, DupSort, Encode, Table, TableImporter},
+    tables::RawTable,
     transaction::{DbTx, DbTxMut},
 };
 use reth_libmdbx::{ffi::MDBX_dbi, CommitLatency, Transaction, TransactionKind, WriteFlags, RW};
@@ -14,9 +22,10 @@ use reth_storage_errors::db::{DatabaseWriteError, DatabaseWriteOperation};
 use reth_tracing::tracing::{debug, trace, warn};
 use std::{
     backtrace::Backtrace,
+    borrow::Cow,
     marker::PhantomData,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
     time::{Duration, Instant},
@@ -25,6 +34,10 @@ use std::{
 /// Duration after which we emit the log about long-lived database transactions.
 const LONG_TRANSACTION_DURATION: Duration = Duration::from_secs(60);
 
+/// Number of words in the bitset of modified tables, enough to cover the maximum number of tables
+/// configured for the environment.
+const MODIFIED_DBIS_WORDS: usize = 4;
+
 /// Wrapper for the libmdbx transaction.
 #[derive(Debug)]
 pub struct Tx<K: TransactionKind> {
@@ -36,6 +49,13 @@ pub struct Tx<K: TransactionKind> {
     ///
     /// If [Some], then metrics are reported.
     metrics_handler: Option<MetricsHandler<K>>,
+
+    /// Bitset of the handles of the tables modified in this transaction, used to update the
+    /// [`TableStats`](tables::TableStats) table.
+    modified_dbis: [AtomicU64; MODIFIED_DBIS_WORDS],
+
+    /// Encryption of table values. If [None], no table is encrypted.
+    encryption: Option<Arc<TableEncryption>>,
 }
 
 impl<K: TransactionKind> Tx<K> {
@@ -65,7 +85,18 @@ impl<K: TransactionKind> Tx<K> {
 
     #[inline]
     const fn new_inner(inner: Transaction<K>, metrics_handler: Option<MetricsHandler<K>>) -> Self {
-        Self { inner, metrics_handler }
+        Self { inner, metrics_handler, modified_dbis: Default::default(), encryption: None }
+    }
+
+    /// Sets the encryption of table values.
+    pub(crate) fn with_encryption(mut self, encryption: Option<Arc<TableEncryption>>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    /// Returns the cipher for the values of table `T`, if it's encrypted.
+    fn cipher<T: Table>(&self) -> Option<&Arc<dyn ValueCipher>> {
+        self.encryption.as_ref().and_then(|encryption| encryption.cipher_for(T::NAME))
     }
 
     /// Gets this transaction ID.
@@ -81,17 +112,38 @@ impl<K: TransactionKind> Tx<K> {
             .map_err(|e| DatabaseError::Open(e.into()))
     }
 
+    /// Marks the table with the given handle as modified in this transaction.
+    #[inline]
+    fn record_modified_dbi(&self, dbi: MDBX_dbi) {
+        let (word, bit) = (dbi as usize / 64, dbi % 64);
+        if let Some(word) = self.modified_dbis.get(word) {
+            word.fetch_or(1 << bit, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns `true` if the table with the given handle was modified in this transaction.
+    fn is_modified_dbi(&self, dbi: MDBX_dbi) -> bool {
+        let (word, bit) = (dbi as usize / 64, dbi % 64);
+        self.modified_dbis
+            .get(word)
+            .is_some_and(|word| word.load(Ordering::Relaxed) & (1 << bit) != 0)
+    }
+
     /// Create db Cursor
     pub fn new_cursor<T: Table>(&self) -> Result<Cursor<K, T>, DatabaseError> {
-        let inner = self
-            .inner
-            .cursor_with_dbi(self.get_dbi::<T>()?)
-            .map_err(|e| DatabaseError::InitCursor(e.into()))?;
+        self.new_cursor_with_dbi(self.get_dbi::<T>()?)
+    }
+
+    /// Create db Cursor for the table with the given handle.
+    fn new_cursor_with_dbi<T: Table>(&self, dbi: MDBX_dbi) -> Result<Cursor<K, T>, DatabaseError> {
+        let inner =
+            self.inner.cursor_with_dbi(dbi).map_err(|e| DatabaseError::InitCursor(e.into()))?;
 
         Ok(Cursor::new_with_metrics(
             inner,
             self.metrics_handler.as_ref().map(|h| h.env_metrics.clone()),
-        ))
+        )
+        .with_cipher(self.cipher::<T>().cloned()))
     }
 
     /// If `self.metrics_handler == Some(_)`, measure the time it takes to execute the closure and
@@ -292,7 +344,14 @@ impl<K: TransactionKind> DbTx for Tx<K> {
         self.execute_with_operation_metric::<T, _>(Operation::Get, None, |tx| {
             tx.get(self.get_dbi::<T>()?, key.as_ref())
                 .map_err(|e| DatabaseError::Read(e.into()))?
-                .map(decode_one::<T>)
+                .map(|value| match self.cipher::<T>() {
+                    Some(cipher) => decode_one::<T>(Cow::Owned(cipher.decrypt(
+                        T::NAME,
+                        key.as_ref(),
+                        &value,
+                    )?)),
+                    None => decode_one::<T>(value),
+                })
                 .transpose()
         })
     }
@@ -342,18 +401,51 @@ impl<K: TransactionKind> DbTx for Tx<K> {
     }
 }
 
+impl Tx<RW> {
+    /// Re-encrypts all values of table `T` with the current key of its cipher.
+    ///
+    /// Run this after rotating the encryption key and before removing the previous key. Returns
+    /// the number of re-encrypted values, or zero if the table is not encrypted.
+    pub fn reencrypt_table<T: Table>(&self) -> Result<usize, DatabaseError> {
+        if self.cipher::<T>().is_none() {
+            return Ok(0)
+        }
+
+        let mut cursor = self.cursor_write::<RawTable<T>>()?;
+        let mut reencrypted = 0;
+        let mut entry = cursor.first()?;
+        while let Some((key, value)) = entry {
+            cursor.upsert(key, &value)?;
+            reencrypted += 1;
+            entry = cursor.next()?;
+        }
+
+        Ok(reencrypted)
+    }
+}
+
 impl DbTxMut for Tx<RW> {
     type CursorMut<T: Table> = Cursor<RW, T>;
     type DupCursorMut<T: DupSort> = Cursor<RW, T>;
 
     fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let dbi = self.get_dbi::<T>()?;
+        self.record_modified_dbi(dbi);
         let key = key.encode();
         let value = value.compress();
+        let value = match self.cipher::<T>() {
+            Some(cipher) => {
+                let mut encrypted = Vec::new();
+                cipher.encrypt(T::NAME, key.as_ref(), value.as_ref(), &mut encrypted)?;
+                Cow::Owned(encrypted)
+            }
+            None => Cow::Borrowed(value.as_ref()),
+        };
         self.execute_with_operation_metric::<T, _>(
             Operation::Put,
             Some(value.as_ref().len()),
             |tx| {
-                tx.put(self.get_dbi::<T>()?, key.as_ref(), value, WriteFlags::UPSERT).map_err(|e| {
+                tx.put(dbi, key.as_ref(), value, WriteFlags::UPSERT).map_err(|e| {
                     DatabaseWriteError {
                         info: e.into(),
                         operation: DatabaseWriteOperation::Put,
@@ -371,6 +463,8 @@ impl DbTxMut for Tx<RW> {
         key: T::Key,
         value: Option<T::Value>,
     ) -> Result<bool, DatabaseError> {
+        let dbi = self.get_dbi::<T>()?;
+        self.record_modified_dbi(dbi);
         let mut data = None;
 
         let value = value.map(Compress::compress);
@@ -379,30 +473,77 @@ impl DbTxMut for Tx<RW> {
         };
 
         self.execute_with_operation_metric::<T, _>(Operation::Delete, None, |tx| {
-            tx.del(self.get_dbi::<T>()?, key.encode(), data)
-                .map_err(|e| DatabaseError::Delete(e.into()))
+            tx.del(dbi, key.encode(), data).map_err(|e| DatabaseError::Delete(e.into()))
         })
     }
 
     fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
-        self.inner.clear_db(self.get_dbi::<T>()?).map_err(|e| DatabaseError::Delete(e.into()))?;
+        let dbi = self.get_dbi::<T>()?;
+        self.record_modified_dbi(dbi);
+        self.inner.clear_db(dbi).map_err(|e| DatabaseError::Delete(e.into()))?;
 
         Ok(())
     }
 
     fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
-        self.new_cursor()
+        let dbi = self.get_dbi::<T>()?;
+        self.record_modified_dbi(dbi);
+        self.new_cursor_with_dbi(dbi)
     }
 
     fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
-        self.new_cursor()
+        let dbi = self.get_dbi::<T>()?;
+        self.record_modified_dbi(dbi);
+        self.new_cursor_with_dbi(dbi)
+    }
+
+    fn update_table_stats(&self) -> Result<(), DatabaseError> {
+        if self.modified_dbis.iter().all(|word| word.load(Ordering::Relaxed) == 0) {
+            return Ok(())
+        }
+        let stats_dbi = self.get_dbi::<tables::TableStats>()?;
+
+        for table in tables::Tables::ALL {
+            let table = table.name();
+            let Ok(dbi) = self.inner.open_db(Some(table)).map(|db| db.dbi()) else { continue };
+            if dbi == stats_dbi || !self.is_modified_dbi(dbi) {
+                continue
+            }
+
+            let stats =
+                self.inner.db_stat_with_dbi(dbi).map_err(|e| DatabaseError::Stats(e.into()))?;
+            let pages = stats.leaf_pages() + stats.branch_pages() + stats.overflow_pages();
+            let stat = TableStat {
+                entries: stats.entries() as u64,
+                size_bytes: (pages * stats.page_size() as usize) as u64,
+            };
+
+            self.put::<tables::TableStats>(table.to_string(), stat)?;
+
+            if self.metrics_handler.is_some() {
+                let labels = vec![Label::new("table", table)];
+                gauge!("db.table_entries", labels.clone()).set(stat.entries as f64);
+                gauge!("db.table_size", labels).set(stat.size_bytes as f64);
+            }
+        }
+
+        for word in &self.modified_dbis {
+            word.store(0, Ordering::Relaxed);
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{mdbx::DatabaseArguments, tables, DatabaseEnv, DatabaseEnvKind};
-    use reth_db_api::{database::Database, models::ClientVersion, transaction::DbTx};
+    use reth_db_api::{
+        database::Database,
+        models::ClientVersion,
+        table::Table,
+        transaction::{DbTx, DbTxMut},
+    };
     use reth_libmdbx::MaxReadTransactionDuration;
     use reth_storage_errors::db::DatabaseError;
     use std::{sync::atomic::Ordering, thread::sleep, time::Duration};
@@ -458,4 +599,29 @@ mod tests {
         // Backtrace is recorded.
         assert!(tx.metrics_handler.unwrap().backtrace_recorded.load(Ordering::Relaxed));
     }
+
+    #[test]
+    fn update_table_stats_of_modified_tables() {
+        let dir = tempdir().unwrap();
+        let args = DatabaseArguments::new(ClientVersion::default());
+        let db = DatabaseEnv::open(dir.path(), DatabaseEnvKind::RW, args).unwrap();
+        db.create_tables().unwrap();
+
+        let tx = db.tx_mut().unwrap();
+        tx.put::<tables::CanonicalHeaders>(0, Default::default()).unwrap();
+        tx.put::<tables::CanonicalHeaders>(1, Default::default()).unwrap();
+        tx.update_table_stats().unwrap();
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let stat =
+            tx.get::<tables::TableStats>(tables::CanonicalHeaders::NAME.to_string()).unwrap();
+        assert_eq!(stat.map(|stat| stat.entries), Some(2));
+        // untouched tables are not recorded
+        assert_eq!(tx.get::<tables::TableStats>(tables::Headers::NAME.to_string()).unwrap(), None);
+        assert_eq!(
+            tx.get::<tables::TableStats>(tables::TableStats::NAME.to_string()).unwrap(),
+            None
+        );
+    }
 }
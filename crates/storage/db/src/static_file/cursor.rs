@@ -5,7 +5,7 @@ use reth_db_api::table::Decompress;
 use reth_nippy_jar::{DataReader, NippyJar, NippyJarCursor};
 use reth_static_file_types::SegmentHeader;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
 /// Cursor of a static file segment.
 #[derive(Debug, Deref, DerefMut)]
@@ -66,6 +66,23 @@ impl<'a> StaticFileCursor<'a> {
         }
     }
 
+    /// Gets the raw, still encoded, column values for each row in a range of block or transaction
+    /// numbers.
+    ///
+    /// Numbers outside of the static file are skipped.
+    pub fn get_raw_range<M: ColumnSelectorOne>(
+        &mut self,
+        range: Range<u64>,
+    ) -> ProviderResult<Vec<&'_ [u8]>> {
+        let Some(offset) = self.jar().user_header().start() else { return Ok(Vec::new()) };
+        if self.jar().rows() == 0 || range.end <= offset {
+            return Ok(Vec::new())
+        }
+
+        let rows = range.start.saturating_sub(offset) as usize..(range.end - offset) as usize;
+        self.column_range(M::MASK.trailing_zeros() as usize, rows).map_err(ProviderError::other)
+    }
+
     /// Gets two column values from a row.
     pub fn get_two<M: ColumnSelectorTwo>(
         &mut self,
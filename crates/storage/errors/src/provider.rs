@@ -100,6 +100,14 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// Receipts are not available for the given block number because they are pruned or expired.
+    #[error("receipts of block #{block} are pruned")]
+    ReceiptsPruned {
+        /// The block the receipts were requested for.
+        block: BlockNumber,
+        /// The earliest block receipts are still available for, if any.
+        earliest_available: Option<BlockNumber>,
+    },
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
@@ -107,6 +115,13 @@ pub enum ProviderError {
     #[cfg(feature = "std")]
     #[error("not able to find {_0} static file at {_1:?}")]
     MissingStaticFilePath(StaticFileSegment, std::path::PathBuf),
+    /// No static files were found, but the database has progressed past genesis.
+    #[cfg(feature = "std")]
+    #[error(
+        "no static files found at {_0:?}, but the database is synced to block #{_1}. \
+         If the static files were moved, pass their location with `--datadir.static-files`"
+    )]
+    MissingStaticFiles(std::path::PathBuf, BlockNumber),
     /// Static File is not found for requested block.
     #[error("not able to find {_0} static file for block number {_1}")]
     MissingStaticFileBlock(StaticFileSegment, BlockNumber),
@@ -225,6 +225,39 @@ impl Environment {
 
         Ok(freelist)
     }
+
+    /// Copies the environment into a new file at `dest`.
+    ///
+    /// The copy is taken from a consistent read snapshot, so it can be made while the environment
+    /// is being written to by another process. If `compact` is set, free pages are omitted and
+    /// all pages are renumbered sequentially, so the copy only takes up the space that is in use.
+    ///
+    /// The file at `dest` must not exist yet, and the path may not contain the null character.
+    pub fn copy(&self, dest: &Path, compact: bool) -> Result<()> {
+        let dest = path_to_cstring(dest)?;
+        let flags = if compact { ffi::MDBX_CP_COMPACT } else { ffi::MDBX_CP_DEFAULTS };
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), dest.as_ptr(), flags) })?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_ref().as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+    // really help.
+    path.as_ref().to_string_lossy().to_string().into_bytes()
+}
+
+/// Converts the path into a C string, failing if it contains the null character.
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    CString::new(path_to_bytes(path)).map_err(|_| Error::Invalid)
 }
 
 /// Container type for Environment internals.
@@ -690,24 +723,7 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
-                let path = match CString::new(path_to_bytes(path)) {
-                    Ok(path) => path,
-                    Err(_) => return Err(Error::Invalid),
-                };
+                let path = path_to_cstring(path)?;
                 mdbx_result(ffi::mdbx_env_open(
                     env,
                     path.as_ptr(),
@@ -167,3 +167,40 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    // Write some values and delete most of them again, leaving free pages behind.
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in 0..10_000u64 {
+        let mut key = [0u8; 8];
+        LittleEndian::write_u64(&mut key, i);
+        tx.put(db.dbi(), key, [0u8; 64], WriteFlags::default()).expect("tx.put");
+    }
+    tx.commit().expect("tx.commit");
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in 10..10_000u64 {
+        let mut key = [0u8; 8];
+        LittleEndian::write_u64(&mut key, i);
+        tx.del(db.dbi(), key, None).expect("tx.del");
+    }
+    tx.commit().expect("tx.commit");
+
+    let copy_dir = tempdir().unwrap();
+    env.copy(&copy_dir.path().join("mdbx.dat"), true).unwrap();
+    // The destination must not exist yet.
+    assert!(env.copy(&copy_dir.path().join("mdbx.dat"), true).is_err());
+
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.freelist().unwrap(), 0);
+    assert!(copy.info().unwrap().last_pgno() < env.info().unwrap().last_pgno());
+
+    let tx = copy.begin_ro_txn().expect("begin_ro_txn");
+    let db = tx.open_db(None).unwrap();
+    assert_eq!(tx.db_stat(&db).unwrap().entries(), 10);
+}
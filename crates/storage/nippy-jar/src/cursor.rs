@@ -143,6 +143,35 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
         ))
     }
 
+    /// Returns the values of a single column for a range of rows.
+    ///
+    /// Values of uncompressed jars are returned as slices of the memory-mapped data file without
+    /// copying, compressed values are decompressed into the internal buffer. Rows past the end of
+    /// the jar are ignored. The cursor is positioned after the last returned row.
+    pub fn column_range(
+        &mut self,
+        column: usize,
+        rows: Range<usize>,
+    ) -> Result<Vec<&[u8]>, NippyJarError> {
+        self.internal_buffer.clear();
+
+        let rows = rows.start..rows.end.min(self.jar.rows);
+        let mut values = Vec::with_capacity(rows.len());
+        for row in rows.clone() {
+            self.row = row as u64;
+            self.read_value(column, &mut values)?;
+        }
+        self.row = rows.end.max(rows.start) as u64;
+
+        Ok(values
+            .into_iter()
+            .map(|v| match v {
+                ValueRange::Mmap(range) => self.reader.data(range),
+                ValueRange::Internal(range) => &self.internal_buffer[range],
+            })
+            .collect())
+    }
+
     /// Takes the column index and reads the range value for the corresponding column.
     fn read_value(
         &mut self,
@@ -770,6 +770,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_column_range() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy = NippyJar::new_without_header(2, file_path.path()).with_lz4();
+        nippy.freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows).unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+
+        // Whole column
+        let values = cursor.column_range(1, 0..col2.len()).unwrap();
+        assert_eq!(values.iter().map(|v| v.to_vec()).collect::<Vec<_>>(), col2);
+
+        // Range is clamped to the number of rows
+        let values = cursor.column_range(0, 3..col1.len() + 10).unwrap();
+        assert_eq!(values.iter().map(|v| v.to_vec()).collect::<Vec<_>>(), col1[3..]);
+
+        // Empty range
+        assert!(cursor.column_range(0, 2..2).unwrap().is_empty());
+    }
+
     #[test]
     fn test_writer() {
         let (col1, col2) = test_data(None);
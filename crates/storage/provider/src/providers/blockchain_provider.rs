@@ -2,14 +2,15 @@
 use crate::{
     providers::{ConsistentProvider, ProviderNodeTypes, StaticFileProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
-    ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
-    DatabaseProviderFactory, FullProvider, HashedPostStateProvider, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    BlockSource, CanonChainTracker, CanonStateNotification, CanonStateNotifications,
+    CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader, ChangeSetReader,
+    DatabaseProvider, DatabaseProviderFactory, FullProvider, HashedPostStateProvider,
+    HeaderProvider, HistoryAvailability, ProviderError, ProviderFactory, PruneCheckpointReader,
+    ReceiptProvider, ReceiptProviderIdExt, SenderTransactionsProvider, StageCheckpointReader,
+    StateProviderBox, StateProviderFactory, StateReader, StaticFileProviderFactory,
+    TransactionVariant, TransactionsProvider,
 };
-use alloy_consensus::{transaction::TransactionMeta, Header};
+use alloy_consensus::{transaction::TransactionMeta, BlockHeader, Header};
 use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
     BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag,
@@ -33,7 +34,7 @@ use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
 use reth_primitives_traits::{
     Account, BlockBody, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader, StorageEntry,
 };
-use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_prune_types::{PruneCheckpoint, PruneRun, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
     BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, StateCommitmentProvider,
@@ -98,9 +99,11 @@ impl<N: ProviderNodeTypes> BlockchainProvider<N> {
         latest: SealedHeader<HeaderTy<N>>,
     ) -> ProviderResult<Self> {
         let provider = storage.provider()?;
+        // the persisted markers can be ahead of the latest block if they were written by an older
+        // version, in which case the latest block is the best known ancestor of the marker
         let finalized_header = provider
             .last_finalized_block_number()?
-            .map(|num| provider.sealed_header(num))
+            .map(|num| provider.sealed_header(num.min(latest.number())))
             .transpose()?
             .flatten();
         let safe_header = provider
@@ -110,7 +113,7 @@ impl<N: ProviderNodeTypes> BlockchainProvider<N> {
                 // safe block
                 provider.last_finalized_block_number().ok().flatten()
             })
-            .map(|num| provider.sealed_header(num))
+            .map(|num| provider.sealed_header(num.min(latest.number())))
             .transpose()?
             .flatten();
         Ok(Self {
@@ -211,6 +214,13 @@ impl<N: ProviderNodeTypes> HeaderProvider for BlockchainProvider<N> {
         self.consistent_provider()?.headers_range(range)
     }
 
+    fn headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Self::Header>> {
+        self.consistent_provider()?.headers_range_vectored(range)
+    }
+
     fn sealed_header(
         &self,
         number: BlockNumber,
@@ -225,6 +235,13 @@ impl<N: ProviderNodeTypes> HeaderProvider for BlockchainProvider<N> {
         self.consistent_provider()?.sealed_headers_range(range)
     }
 
+    fn sealed_headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader<Self::Header>>> {
+        self.consistent_provider()?.sealed_headers_range_vectored(range)
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -483,6 +500,10 @@ impl<N: ProviderNodeTypes> StageCheckpointReader for BlockchainProvider<N> {
     fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>> {
         self.consistent_provider()?.get_all_checkpoints()
     }
+
+    fn history_availability(&self) -> ProviderResult<HistoryAvailability> {
+        self.consistent_provider()?.history_availability()
+    }
 }
 
 impl<N: ProviderNodeTypes> PruneCheckpointReader for BlockchainProvider<N> {
@@ -496,6 +517,23 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for BlockchainProvider<N> {
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
         self.consistent_provider()?.get_prune_checkpoints()
     }
+
+    fn get_prune_runs(&self) -> ProviderResult<Vec<(PruneSegment, PruneRun)>> {
+        self.consistent_provider()?.get_prune_runs()
+    }
+}
+
+impl<N: ProviderNodeTypes> SenderTransactionsProvider for BlockchainProvider<N> {
+    /// Returns the sender's transactions from the database, blocks which are only held in memory
+    /// are not indexed yet.
+    fn transaction_ids_by_sender(
+        &self,
+        sender: Address,
+        after: Option<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.transaction_ids_by_sender(sender, after, limit)
+    }
 }
 
 impl<N: NodeTypesWithDB> ChainSpecProvider for BlockchainProvider<N> {
@@ -680,6 +718,13 @@ impl<N: ProviderNodeTypes> CanonStateSubscriptions for BlockchainProvider<N> {
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications<Self::Primitives> {
         self.canonical_in_memory_state.subscribe_canon_state()
     }
+
+    fn subscribe_to_canonical_state_with_history(
+        &self,
+    ) -> (Vec<CanonStateNotification<Self::Primitives>>, CanonStateNotifications<Self::Primitives>)
+    {
+        self.canonical_in_memory_state.subscribe_canon_state_with_history()
+    }
 }
 
 impl<N: ProviderNodeTypes> ForkChoiceSubscriptions for BlockchainProvider<N> {
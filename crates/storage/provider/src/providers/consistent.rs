@@ -2,9 +2,9 @@ use super::{DatabaseProviderRO, ProviderFactory, ProviderNodeTypes};
 use crate::{
     providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
     BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider, ChangeSetReader, HeaderProvider,
-    ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateReader, StaticFileProviderFactory, TransactionVariant,
-    TransactionsProvider,
+    HistoryAvailability, ProviderError, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, StageCheckpointReader, StateReader, StaticFileProviderFactory,
+    TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{
@@ -21,7 +21,7 @@ use reth_db_api::models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIn
 use reth_execution_types::{BundleStateInit, ExecutionOutcome, RevertsInit};
 use reth_node_types::{BlockTy, HeaderTy, ReceiptTy, TxTy};
 use reth_primitives_traits::{Account, BlockBody, RecoveredBlock, SealedHeader, StorageEntry};
-use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_prune_types::{PruneCheckpoint, PruneRun, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
     BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, StateProvider,
@@ -705,6 +705,18 @@ impl<N: ProviderNodeTypes> HeaderProvider for ConsistentProvider<N> {
         )
     }
 
+    fn headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Self::Header>> {
+        self.get_in_memory_or_storage_by_block_range_while(
+            range,
+            |db_provider, range, _| db_provider.headers_range_vectored(range),
+            |block_state, _| Some(block_state.block_ref().recovered_block().header().clone()),
+            |_| true,
+        )
+    }
+
     fn sealed_header(
         &self,
         number: BlockNumber,
@@ -728,6 +740,18 @@ impl<N: ProviderNodeTypes> HeaderProvider for ConsistentProvider<N> {
         )
     }
 
+    fn sealed_headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader<Self::Header>>> {
+        self.get_in_memory_or_storage_by_block_range_while(
+            range,
+            |db_provider, range, _| db_provider.sealed_headers_range_vectored(range),
+            |block_state, _| Some(block_state.block_ref().recovered_block().clone_sealed_header()),
+            |_| true,
+        )
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -1217,6 +1241,11 @@ impl<N: ProviderNodeTypes> StageCheckpointReader for ConsistentProvider<N> {
     fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>> {
         self.storage_provider.get_all_checkpoints()
     }
+
+    fn history_availability(&self) -> ProviderResult<HistoryAvailability> {
+        // blocks that are only held in memory have all data available
+        Ok(self.storage_provider.history_availability()?.with_latest(self.best_block_number()?))
+    }
 }
 
 impl<N: ProviderNodeTypes> PruneCheckpointReader for ConsistentProvider<N> {
@@ -1230,6 +1259,10 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ConsistentProvider<N> {
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
         self.storage_provider.get_prune_checkpoints()
     }
+
+    fn get_prune_runs(&self) -> ProviderResult<Vec<(PruneSegment, PruneRun)>> {
+        self.storage_provider.get_prune_runs()
+    }
 }
 
 impl<N: ProviderNodeTypes> ChainSpecProvider for ConsistentProvider<N> {
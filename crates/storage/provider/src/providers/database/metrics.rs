@@ -50,6 +50,7 @@ pub(crate) enum Action {
     InsertHeaderTerminalDifficulties,
     InsertBlockBodyIndices,
     InsertTransactionBlocks,
+    InsertSenderTransactions,
     GetNextTxNum,
     GetParentTD,
 }
@@ -86,6 +87,8 @@ struct DatabaseProviderMetrics {
     insert_block_body_indices: Histogram,
     /// Duration of insert transaction blocks
     insert_tx_blocks: Histogram,
+    /// Duration of insert sender transactions index
+    insert_sender_transactions: Histogram,
     /// Duration of get next tx num
     get_next_tx_num: Histogram,
     /// Duration of get parent TD
@@ -110,6 +113,7 @@ impl DatabaseProviderMetrics {
             Action::InsertHeaderTerminalDifficulties => self.insert_header_td.record(duration),
             Action::InsertBlockBodyIndices => self.insert_block_body_indices.record(duration),
             Action::InsertTransactionBlocks => self.insert_tx_blocks.record(duration),
+            Action::InsertSenderTransactions => self.insert_sender_transactions.record(duration),
             Action::GetNextTxNum => self.get_next_tx_num.record(duration),
             Action::GetParentTD => self.get_parent_td.record(duration),
         }
@@ -3,9 +3,9 @@ use crate::{
     to_range,
     traits::{BlockSource, ReceiptProvider},
     BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, HeaderProvider, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider,
+    HashedPostStateProvider, HeaderProvider, HeaderSyncGapProvider, HistoryAvailability,
+    ProviderError, PruneCheckpointReader, SenderTransactionsProvider, StageCheckpointReader,
+    StateProviderBox, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::BlockHashOrNumber;
@@ -19,7 +19,7 @@ use reth_node_types::{
     BlockTy, HeaderTy, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter, ReceiptTy, TxTy,
 };
 use reth_primitives_traits::{RecoveredBlock, SealedHeader};
-use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
+use reth_prune_types::{PruneCheckpoint, PruneModes, PruneRun, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
@@ -65,6 +65,8 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     prune_modes: PruneModes,
     /// The node storage handler.
     storage: Arc<N::Storage>,
+    /// Whether the sender transactions index is maintained.
+    sender_transactions_index: bool,
 }
 
 impl<N: NodeTypes> ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>> {
@@ -87,6 +89,7 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            sender_transactions_index: false,
         }
     }
 
@@ -96,12 +99,25 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
         self
     }
 
+    /// Enables the background verification of finalized static files.
+    pub fn with_static_files_verification(mut self) -> Self {
+        self.static_file_provider = self.static_file_provider.with_background_verification();
+        self
+    }
+
     /// Sets the pruning configuration for an existing [`ProviderFactory`].
     pub fn with_prune_modes(mut self, prune_modes: PruneModes) -> Self {
         self.prune_modes = prune_modes;
         self
     }
 
+    /// Sets whether the [`SenderTransactions`](reth_db_api::tables::SenderTransactions) index is
+    /// maintained by the providers created by this factory.
+    pub const fn with_sender_transactions_index(mut self, enabled: bool) -> Self {
+        self.sender_transactions_index = enabled;
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub const fn db_ref(&self) -> &N::DB {
         &self.db
@@ -129,6 +145,7 @@ impl<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>> ProviderFactory<N> {
             static_file_provider,
             prune_modes: PruneModes::none(),
             storage: Default::default(),
+            sender_transactions_index: false,
         })
     }
 }
@@ -148,7 +165,8 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             self.static_file_provider.clone(),
             self.prune_modes.clone(),
             self.storage.clone(),
-        ))
+        )
+        .with_sender_transactions_index(self.sender_transactions_index))
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -157,13 +175,16 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<N::DB, N>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-            self.storage.clone(),
-        )))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+                self.storage.clone(),
+            )
+            .with_sender_transactions_index(self.sender_transactions_index),
+        ))
     }
 
     /// State provider for latest block
@@ -273,6 +294,19 @@ impl<N: ProviderNodeTypes> HeaderProvider for ProviderFactory<N> {
         )
     }
 
+    fn headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Self::Header>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Headers,
+            to_range(range),
+            |static_file, range, _| static_file.headers_range_vectored(range),
+            |range, _| self.provider()?.headers_range(range),
+            |_| true,
+        )
+    }
+
     fn sealed_header(
         &self,
         number: BlockNumber,
@@ -292,6 +326,19 @@ impl<N: ProviderNodeTypes> HeaderProvider for ProviderFactory<N> {
         self.sealed_headers_while(range, |_| true)
     }
 
+    fn sealed_headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader<Self::Header>>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Headers,
+            to_range(range),
+            |static_file, range, _| static_file.sealed_headers_range_vectored(range),
+            |range, _| self.provider()?.sealed_headers_range(range),
+            |_| true,
+        )
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -577,6 +624,21 @@ impl<N: ProviderNodeTypes> StageCheckpointReader for ProviderFactory<N> {
     fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>> {
         self.provider()?.get_all_checkpoints()
     }
+
+    fn history_availability(&self) -> ProviderResult<HistoryAvailability> {
+        self.provider()?.history_availability()
+    }
+}
+
+impl<N: ProviderNodeTypes> SenderTransactionsProvider for ProviderFactory<N> {
+    fn transaction_ids_by_sender(
+        &self,
+        sender: Address,
+        after: Option<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.transaction_ids_by_sender(sender, after, limit)
+    }
 }
 
 impl<N: NodeTypesWithDB> ChainSpecProvider for ProviderFactory<N> {
@@ -598,6 +660,10 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ProviderFactory<N> {
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
         self.provider()?.get_prune_checkpoints()
     }
+
+    fn get_prune_runs(&self) -> ProviderResult<Vec<(PruneSegment, PruneRun)>> {
+        self.provider()?.get_prune_runs()
+    }
 }
 
 impl<N: ProviderNodeTypes> HashedPostStateProvider for ProviderFactory<N> {
@@ -613,13 +679,21 @@ where
     N: NodeTypesWithDB<DB: fmt::Debug, ChainSpec: fmt::Debug, Storage: fmt::Debug>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self { db, chain_spec, static_file_provider, prune_modes, storage } = self;
+        let Self {
+            db,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            sender_transactions_index,
+        } = self;
         f.debug_struct("ProviderFactory")
             .field("db", &db)
             .field("chain_spec", &chain_spec)
             .field("static_file_provider", &static_file_provider)
             .field("prune_modes", &prune_modes)
             .field("storage", &storage)
+            .field("sender_transactions_index", &sender_transactions_index)
             .finish()
     }
 }
@@ -632,6 +706,7 @@ impl<N: NodeTypesWithDB> Clone for ProviderFactory<N> {
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
             storage: self.storage.clone(),
+            sender_transactions_index: self.sender_transactions_index,
         }
     }
 }
@@ -643,7 +718,7 @@ mod tests {
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::{blocks::TEST_BLOCK, create_test_provider_factory, MockNodeTypesWithDB},
         BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGapProvider,
-        StorageLocation, TransactionsProvider,
+        PruneCheckpointWriter, ReceiptProvider, StorageLocation, TransactionsProvider,
     };
     use alloy_primitives::{TxNumber, B256, U256};
     use assert_matches::assert_matches;
@@ -654,7 +729,7 @@ mod tests {
     };
     use reth_db_api::tables;
     use reth_primitives_traits::SignerRecoverable;
-    use reth_prune_types::{PruneMode, PruneModes};
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneModes, PruneSegment};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::generators::{self, random_block, random_header, BlockParams};
     use std::{ops::RangeInclusive, sync::Arc};
@@ -746,6 +821,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pruned_receipts() {
+        let factory = create_test_provider_factory();
+        let block = TEST_BLOCK.clone();
+        let tx_hash = *block.body().transactions[0].tx_hash();
+
+        let provider = factory.provider_rw().unwrap();
+        assert_matches!(
+            provider.insert_block(block.clone().try_recover().unwrap(), StorageLocation::Database),
+            Ok(_)
+        );
+
+        // the block is not executed yet, so its receipts are missing, but not pruned
+        assert_matches!(
+            provider.receipts_by_block(block.number.into()),
+            Ok(Some(receipts)) if receipts.is_empty()
+        );
+        assert_matches!(provider.receipt_by_hash(tx_hash), Ok(None));
+
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::Receipts,
+                PruneCheckpoint {
+                    block_number: Some(block.number),
+                    tx_number: Some(0),
+                    prune_mode: PruneMode::Full,
+                },
+            )
+            .unwrap();
+
+        assert_matches!(
+            provider.receipts_by_block(block.number.into()),
+            Err(ProviderError::ReceiptsPruned { block: number, earliest_available: None })
+                if number == block.number
+        );
+        assert_matches!(
+            provider.receipt_by_hash(tx_hash),
+            Err(ProviderError::ReceiptsPruned { block: number, .. }) if number == block.number
+        );
+    }
+
     #[test]
     fn take_block_transaction_range_recover_senders() {
         let factory = create_test_provider_factory();
@@ -12,17 +12,21 @@ use crate::{
     AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
     BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
     DBProvider, HashingWriter, HeaderProvider, HeaderSyncGapProvider, HistoricalStateProvider,
-    HistoricalStateProviderRef, HistoryWriter, LatestStateProvider, LatestStateProviderRef,
+    HistoricalStateProviderRef, HistoryAvailability, HistoryDataClass, HistoryWriter,
+    InvalidBlockReader, InvalidBlockWriter, LatestStateProvider, LatestStateProviderRef,
     OriginalValuesKnown, ProviderError, PruneCheckpointReader, PruneCheckpointWriter, RevertsInit,
-    StageCheckpointReader, StateCommitmentProvider, StateProviderBox, StateWriter,
-    StaticFileProviderFactory, StatsReader, StorageLocation, StorageReader, StorageTrieWriter,
-    TransactionVariant, TransactionsProvider, TransactionsProviderExt, TrieWriter,
+    SenderTransactionsProvider, StageCheckpointReader, StateCommitmentProvider, StateProviderBox,
+    StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation, StorageReader,
+    StorageTrieWriter, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    TrieWriter,
 };
 use alloy_consensus::{
     transaction::{SignerRecoverable, TransactionMeta},
     BlockHeader, Header, TxReceipt,
 };
-use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
+use alloy_eips::{
+    eip1898::BlockWithParent, eip2718::Encodable2718, BlockHashOrNumber, BlockNumHash,
+};
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
@@ -36,21 +40,21 @@ use reth_db_api::{
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices,
+        ShardedKey, StoredBlockBodyIndices, StoredInvalidBlock,
     },
     table::Table,
     tables,
     transaction::{DbTx, DbTxMut},
-    BlockNumberList, DatabaseError, PlainAccountState, PlainStorageState,
+    BlockNumberList, DatabaseError, PlainAccountState, PlainStorageState, TxNumberList,
 };
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_node_types::{BlockTy, BodyTy, HeaderTy, NodeTypes, ReceiptTy, TxTy};
 use reth_primitives_traits::{
-    Account, Block as _, BlockBody as _, Bytecode, GotExpected, NodePrimitives, RecoveredBlock,
-    SealedHeader, SignedTransaction, StorageEntry,
+    hash_cache::keccak256_address, Account, Block as _, BlockBody as _, Bytecode, GotExpected,
+    NodePrimitives, RecoveredBlock, SealedHeader, SignedTransaction, StorageEntry,
 };
 use reth_prune_types::{
-    PruneCheckpoint, PruneMode, PruneModes, PruneSegment, MINIMUM_PRUNING_DISTANCE,
+    PruneCheckpoint, PruneMode, PruneModes, PruneRun, PruneSegment, MINIMUM_PRUNING_DISTANCE,
 };
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
@@ -145,6 +149,8 @@ pub struct DatabaseProvider<TX, N: NodeTypes> {
     prune_modes: PruneModes,
     /// Node storage handler.
     storage: Arc<N::Storage>,
+    /// Whether the [`SenderTransactions`](tables::SenderTransactions) index is maintained.
+    sender_transactions_index: bool,
 }
 
 impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -152,6 +158,24 @@ impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
     pub const fn prune_modes_ref(&self) -> &PruneModes {
         &self.prune_modes
     }
+
+    /// Sets whether the [`SenderTransactions`](tables::SenderTransactions) index is maintained
+    /// when inserting and removing blocks.
+    ///
+    /// The index is never maintained if sender recovery is fully pruned, because the senders of
+    /// removed blocks are read from [`TransactionSenders`](tables::TransactionSenders) to unwind
+    /// it.
+    pub fn with_sender_transactions_index(mut self, enabled: bool) -> Self {
+        self.sender_transactions_index =
+            enabled && !self.prune_modes.sender_recovery.is_some_and(|mode| mode.is_full());
+        self
+    }
+
+    /// Returns `true` if the [`SenderTransactions`](tables::SenderTransactions) index is
+    /// maintained.
+    pub const fn sender_transactions_index_enabled(&self) -> bool {
+        self.sender_transactions_index
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -204,6 +228,23 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         Ok(Box::new(state_provider))
     }
 
+    /// Returns [`ProviderError::ReceiptsPruned`] if the receipts of the given block were pruned or
+    /// expired.
+    ///
+    /// This is used to distinguish pruned receipts from receipts that don't exist yet.
+    fn ensure_receipts_available(&self, block: BlockNumber) -> ProviderResult<()> {
+        let availability = self.history_availability()?;
+        if availability.is_pruned(HistoryDataClass::Receipts, block) {
+            return Err(ProviderError::ReceiptsPruned {
+                block,
+                earliest_available: availability
+                    .range(HistoryDataClass::Receipts)
+                    .map(|range| *range.start()),
+            })
+        }
+        Ok(())
+    }
+
     #[cfg(feature = "test-utils")]
     /// Sets the prune modes for provider.
     pub fn set_prune_modes(&mut self, prune_modes: PruneModes) {
@@ -241,7 +282,14 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            sender_transactions_index: false,
+        }
     }
 }
 
@@ -524,7 +572,14 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         prune_modes: PruneModes,
         storage: Arc<N::Storage>,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes, storage }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            storage,
+            sender_transactions_index: false,
+        }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -805,7 +860,10 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
 
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     /// Commit database transaction.
+    ///
+    /// Updates the [`TableStats`](tables::TableStats) of the modified tables before committing.
     pub fn commit(self) -> ProviderResult<bool> {
+        self.tx.update_table_stats()?;
         Ok(self.tx.commit()?)
     }
 
@@ -1053,6 +1111,19 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderProvider for DatabasePro
         )
     }
 
+    fn headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Self::Header>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Headers,
+            to_range(range),
+            |static_file, range, _| static_file.headers_range_vectored(range),
+            |range, _| self.cursor_read_collect::<tables::Headers<Self::Header>>(range),
+            |_| true,
+        )
+    }
+
     fn sealed_header(
         &self,
         number: BlockNumber,
@@ -1074,6 +1145,19 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderProvider for DatabasePro
         )
     }
 
+    fn sealed_headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader<Self::Header>>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Headers,
+            to_range(range),
+            |static_file, range, _| static_file.sealed_headers_range_vectored(range),
+            |range, _| self.sealed_headers_range(range),
+            |_| true,
+        )
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -1537,7 +1621,14 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
 
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
         if let Some(id) = self.transaction_id(hash)? {
-            self.receipt(id)
+            let receipt = self.receipt(id)?;
+            if receipt.is_none() {
+                // the transaction exists, so the receipt might have been pruned
+                if let Some(block) = self.transaction_block(id)? {
+                    self.ensure_receipts_available(block)?;
+                }
+            }
+            Ok(receipt)
         } else {
             Ok(None)
         }
@@ -1550,11 +1641,16 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
         if let Some(number) = self.convert_hash_or_number(block)? {
             if let Some(body) = self.block_body_indices(number)? {
                 let tx_range = body.tx_num_range();
-                return if tx_range.is_empty() {
-                    Ok(Some(Vec::new()))
-                } else {
-                    self.receipts_by_tx_range(tx_range).map(Some)
+                if tx_range.is_empty() {
+                    return Ok(Some(Vec::new()))
                 }
+
+                let receipts = self.receipts_by_tx_range(tx_range)?;
+                if (receipts.len() as u64) < body.tx_count {
+                    // some receipts of the block are missing, they might have been pruned
+                    self.ensure_receipts_available(number)?;
+                }
+                return Ok(Some(receipts))
             }
         }
         Ok(None)
@@ -1657,7 +1753,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockBodyIndicesProvider
     }
 }
 
-impl<TX: DbTx, N: NodeTypes> StageCheckpointReader for DatabaseProvider<TX, N> {
+impl<TX: DbTx + 'static, N: NodeTypes> StageCheckpointReader for DatabaseProvider<TX, N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         Ok(self.tx.get::<tables::StageCheckpoints>(id.to_string())?)
     }
@@ -1674,6 +1770,25 @@ impl<TX: DbTx, N: NodeTypes> StageCheckpointReader for DatabaseProvider<TX, N> {
             .collect::<Result<Vec<(String, StageCheckpoint)>, _>>()
             .map_err(ProviderError::Database)
     }
+
+    fn history_availability(&self) -> ProviderResult<HistoryAvailability> {
+        // expired history is removed from the transactions and receipts static files
+        let earliest_history = self.static_file_provider.earliest_history_height();
+        let mut availability = HistoryAvailability::new(self.last_block_number()?)
+            .with_earliest(HistoryDataClass::Bodies, earliest_history)
+            .with_earliest(HistoryDataClass::Receipts, earliest_history);
+
+        for (segment, checkpoint) in self.get_prune_checkpoints()? {
+            let (Some(data), Some(pruned)) =
+                (HistoryDataClass::from_prune_segment(segment), checkpoint.block_number)
+            else {
+                continue
+            };
+            availability = availability.with_earliest(data, pruned + 1);
+        }
+
+        Ok(availability)
+    }
 }
 
 impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N> {
@@ -1702,7 +1817,9 @@ impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N
     ) -> ProviderResult<()> {
         // iterate over all existing stages in the table and update its progress.
         let mut cursor = self.tx.cursor_write::<tables::StageCheckpoints>()?;
-        for stage_id in StageId::ALL {
+        let optional_stages =
+            self.sender_transactions_index.then_some(StageId::IndexSenderTransactions);
+        for stage_id in StageId::ALL.into_iter().chain(optional_stages) {
             let (_, checkpoint) = cursor.seek_exact(stage_id.to_string())?.unwrap_or_default();
             cursor.upsert(
                 stage_id.to_string(),
@@ -1717,6 +1834,46 @@ impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> SenderTransactionsProvider for DatabaseProvider<TX, N> {
+    fn transaction_ids_by_sender(
+        &self,
+        sender: Address,
+        after: Option<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut ids = Vec::new();
+        let start = match after {
+            Some(after) => match after.checked_add(1) {
+                Some(start) => start,
+                None => return Ok(ids),
+            },
+            None => 0,
+        };
+        if limit == 0 {
+            return Ok(ids)
+        }
+
+        // Shards are keyed by the highest transaction number they contain, so the first shard that
+        // can contain `start` is the first one with a key greater or equal to it.
+        let mut cursor = self.tx.cursor_read::<tables::SenderTransactions>()?;
+        for entry in cursor.walk(Some(ShardedKey::new(sender, start)))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != sender {
+                break
+            }
+
+            for id in list.iter().skip_while(|id| *id < start) {
+                ids.push(id);
+                if ids.len() == limit {
+                    return Ok(ids)
+                }
+            }
+        }
+
+        Ok(ids)
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N> {
     fn plain_state_storages(
         &self,
@@ -2402,7 +2559,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         // changes are applied in the correct order.
         let hashed_accounts = changesets
             .into_iter()
-            .map(|(_, e)| (keccak256(e.address), e.info))
+            .map(|(_, e)| (keccak256_address(&e.address), e.info))
             .collect::<Vec<_>>()
             .into_iter()
             .rev()
@@ -2438,8 +2595,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         changesets: impl IntoIterator<Item = (Address, Option<Account>)>,
     ) -> ProviderResult<BTreeMap<B256, Option<Account>>> {
         let mut hashed_accounts_cursor = self.tx.cursor_write::<tables::HashedAccounts>()?;
-        let hashed_accounts =
-            changesets.into_iter().map(|(ad, ac)| (keccak256(ad), ac)).collect::<BTreeMap<_, _>>();
+        let hashed_accounts = changesets
+            .into_iter()
+            .map(|(ad, ac)| (keccak256_address(&ad), ac))
+            .collect::<BTreeMap<_, _>>();
         for (hashed_address, account) in &hashed_accounts {
             if let Some(account) = account {
                 hashed_accounts_cursor.upsert(*hashed_address, account)?;
@@ -2458,7 +2617,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         let mut hashed_storages = changesets
             .into_iter()
             .map(|(BlockNumberAddress((_, address)), storage_entry)| {
-                (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
+                (keccak256_address(&address), keccak256(storage_entry.key), storage_entry.value)
             })
             .collect::<Vec<_>>();
         hashed_storages.sort_by_key(|(ha, hk, _)| (*ha, *hk));
@@ -2508,7 +2667,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
                     map.insert(keccak256(entry.key), entry.value);
                     map
                 });
-                map.insert(keccak256(address), storage);
+                map.insert(keccak256_address(&address), storage);
                 map
             });
 
@@ -2734,6 +2893,49 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         )
     }
 
+    fn unwind_sender_transactions_index_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<usize> {
+        // Lowest removed transaction number of each sender. Senders are walked in ascending
+        // transaction number order, so the first entry is always the lowest one.
+        let mut first_indices = BTreeMap::<Address, TxNumber>::new();
+        let mut walked = 0;
+        for entry in self.tx.cursor_read::<tables::TransactionSenders>()?.walk_range(range)? {
+            let (tx_number, sender) = entry?;
+            first_indices.entry(sender).or_insert(tx_number);
+            walked += 1;
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::SenderTransactions>()?;
+        for (sender, rem_index) in first_indices {
+            let partial_shard = unwind_history_shards::<_, tables::SenderTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(sender),
+                rem_index,
+                |sharded_key| sharded_key.key == sender,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(sender),
+                    &TxNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(walked)
+    }
+
+    fn insert_sender_transactions_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = TxNumber>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::SenderTransactions>(index_updates, ShardedKey::new)
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -2878,6 +3080,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
 
         let tx_count = block.body().transaction_count() as u64;
 
+        let mut sender_transactions = BTreeMap::<Address, Vec<TxNumber>>::new();
+
         // Ensures we have all the senders for the block's transactions.
         for (transaction, sender) in block.body().transactions_iter().zip(block.senders_iter()) {
             let hash = transaction.tx_hash();
@@ -2889,9 +3093,18 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
             if self.prune_modes.transaction_lookup.is_none_or(|m| !m.is_full()) {
                 self.tx.put::<tables::TransactionHashNumbers>(*hash, next_tx_num)?;
             }
+
+            if self.sender_transactions_index {
+                sender_transactions.entry(*sender).or_default().push(next_tx_num);
+            }
             next_tx_num += 1;
         }
 
+        if !sender_transactions.is_empty() {
+            self.insert_sender_transactions_index(sender_transactions)?;
+            durations_recorder.record_relative(metrics::Action::InsertSenderTransactions);
+        }
+
         self.append_block_bodies(vec![(block_number, Some(block.into_body()))], write_to)?;
 
         debug!(
@@ -3011,6 +3224,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
             }
         }
 
+        if self.sender_transactions_index {
+            self.unwind_sender_transactions_index_range(unwind_tx_from..)?;
+        }
+
         self.remove::<tables::TransactionSenders>(unwind_tx_from..)?;
 
         self.remove_bodies_above(block, remove_from)?;
@@ -3117,6 +3334,10 @@ impl<TX: DbTx + 'static, N: NodeTypes> PruneCheckpointReader for DatabaseProvide
             .walk(None)?
             .collect::<Result<_, _>>()?)
     }
+
+    fn get_prune_runs(&self) -> ProviderResult<Vec<(PruneSegment, PruneRun)>> {
+        Ok(self.tx.cursor_read::<tables::PruneRuns>()?.walk(None)?.collect::<Result<_, _>>()?)
+    }
 }
 
 impl<TX: DbTxMut, N: NodeTypes> PruneCheckpointWriter for DatabaseProvider<TX, N> {
@@ -3127,6 +3348,10 @@ impl<TX: DbTxMut, N: NodeTypes> PruneCheckpointWriter for DatabaseProvider<TX, N
     ) -> ProviderResult<()> {
         Ok(self.tx.put::<tables::PruneCheckpoints>(segment, checkpoint)?)
     }
+
+    fn save_prune_run(&self, segment: PruneSegment, run: PruneRun) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::PruneRuns>(segment, run)?)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> StatsReader for DatabaseProvider<TX, N> {
@@ -3182,6 +3407,51 @@ impl<TX: DbTxMut, N: NodeTypes> ChainStateBlockWriter for DatabaseProvider<TX, N
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> InvalidBlockReader for DatabaseProvider<TX, N> {
+    fn invalid_blocks(&self) -> ProviderResult<Vec<BlockWithParent>> {
+        self.tx
+            .cursor_read::<tables::InvalidBlocks>()?
+            .walk(None)?
+            .map(|entry| {
+                let (hash, block) = entry?;
+                Ok(BlockWithParent::new(block.parent_hash, BlockNumHash::new(block.number, hash)))
+            })
+            .collect()
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> InvalidBlockWriter for DatabaseProvider<TX, N> {
+    fn save_invalid_block(&self, block: BlockWithParent, max_blocks: usize) -> ProviderResult<()> {
+        self.tx.put::<tables::InvalidBlocks>(
+            block.block.hash,
+            StoredInvalidBlock { number: block.block.number, parent_hash: block.parent },
+        )?;
+
+        let excess = self.tx.entries::<tables::InvalidBlocks>()?.saturating_sub(max_blocks);
+        if excess > 0 {
+            // evict the oldest invalid blocks
+            let mut blocks = self
+                .tx
+                .cursor_read::<tables::InvalidBlocks>()?
+                .walk(None)?
+                .map(|entry| entry.map(|(hash, block)| (block.number, hash)))
+                .collect::<Result<Vec<_>, _>>()?;
+            blocks.sort_unstable();
+            for (_, hash) in blocks.into_iter().take(excess) {
+                self.tx.delete::<tables::InvalidBlocks>(hash, None)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear_invalid_blocks(&self) -> ProviderResult<usize> {
+        let removed = self.tx.entries::<tables::InvalidBlocks>()?;
+        self.tx.clear::<tables::InvalidBlocks>()?;
+        Ok(removed)
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypes + 'static> DBProvider for DatabaseProvider<TX, N> {
     type Tx = TX;
 
@@ -3206,11 +3476,62 @@ impl<TX: DbTx + 'static, N: NodeTypes + 'static> DBProvider for DatabaseProvider
 mod tests {
     use super::*;
     use crate::{
-        test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
-        BlockWriter,
+        test_utils::{
+            blocks::BlockchainTestData, create_test_provider_factory, MockNodeTypesWithDB,
+        },
+        BlockWriter, ProviderFactory,
     };
+    use reth_node_types::NodeTypesWithDB;
     use reth_testing_utils::generators::{self, random_block, BlockParams};
 
+    /// Inserts the genesis and the first test block, which has a transaction, and returns the
+    /// uncommitted provider.
+    fn insert_test_blocks(
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+    ) -> DatabaseProviderRW<<MockNodeTypesWithDB as NodeTypesWithDB>::DB, MockNodeTypesWithDB> {
+        let data = BlockchainTestData::default();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_block(
+                data.genesis.clone().try_recover().unwrap(),
+                crate::StorageLocation::Database,
+            )
+            .unwrap();
+        provider_rw
+            .insert_block(data.blocks[0].0.clone(), crate::StorageLocation::Database)
+            .unwrap();
+        provider_rw
+    }
+
+    #[test]
+    fn test_sender_transactions_index_unwind() {
+        let factory = create_test_provider_factory().with_sender_transactions_index(true);
+        let provider_rw = insert_test_blocks(&factory);
+        assert!(provider_rw.sender_transactions_index_enabled());
+        assert_eq!(provider_rw.tx_ref().entries::<tables::SenderTransactions>().unwrap(), 1);
+
+        provider_rw.remove_blocks_above(0, crate::StorageLocation::Database).unwrap();
+        assert_eq!(provider_rw.tx_ref().entries::<tables::SenderTransactions>().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_sender_transactions_index_unwind_full_sender_pruning() {
+        let prune_modes =
+            PruneModes { sender_recovery: Some(PruneMode::Full), ..PruneModes::none() };
+        let factory = create_test_provider_factory()
+            .with_prune_modes(prune_modes)
+            .with_sender_transactions_index(true);
+        let provider_rw = insert_test_blocks(&factory);
+
+        // without the senders the index could not be unwound, so it is not maintained
+        assert!(!provider_rw.sender_transactions_index_enabled());
+        assert_eq!(provider_rw.tx_ref().entries::<tables::TransactionSenders>().unwrap(), 0);
+        assert_eq!(provider_rw.tx_ref().entries::<tables::SenderTransactions>().unwrap(), 0);
+
+        provider_rw.remove_blocks_above(0, crate::StorageLocation::Database).unwrap();
+        assert_eq!(provider_rw.tx_ref().entries::<tables::SenderTransactions>().unwrap(), 0);
+    }
+
     #[test]
     fn test_receipts_by_block_range_empty_range() {
         let factory = create_test_provider_factory();
@@ -3454,4 +3775,65 @@ mod tests {
 
         assert_eq!(range_result, individual_results);
     }
+
+    #[test]
+    fn test_invalid_blocks_keep_highest() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+
+        for number in [3, 1, 4, 2] {
+            let block = BlockWithParent::new(B256::ZERO, BlockNumHash::new(number, B256::random()));
+            provider_rw.save_invalid_block(block, 2).unwrap();
+        }
+
+        let mut numbers = provider_rw
+            .invalid_blocks()
+            .unwrap()
+            .iter()
+            .map(|b| b.block.number)
+            .collect::<Vec<_>>();
+        numbers.sort_unstable();
+        assert_eq!(numbers, vec![3, 4]);
+
+        assert_eq!(provider_rw.clear_invalid_blocks().unwrap(), 2);
+        assert!(provider_rw.invalid_blocks().unwrap().is_empty());
+    }
+    #[test]
+    fn test_prune_runs_keep_last_run() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+
+        let run = PruneRun { tip_block_number: 10, elapsed_ms: 5, pruned: 100, finished: false };
+        provider_rw.save_prune_run(PruneSegment::Receipts, run).unwrap();
+        let run = PruneRun { tip_block_number: 20, elapsed_ms: 3, pruned: 50, finished: true };
+        provider_rw.save_prune_run(PruneSegment::Receipts, run).unwrap();
+
+        assert_eq!(provider_rw.get_prune_runs().unwrap(), vec![(PruneSegment::Receipts, run)]);
+    }
+
+    #[test]
+    fn test_commit_updates_table_stats() {
+        let factory = create_test_provider_factory();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let run = PruneRun { tip_block_number: 10, elapsed_ms: 5, pruned: 100, finished: true };
+        for segment in [PruneSegment::Receipts, PruneSegment::SenderRecovery] {
+            provider_rw.save_prune_run(segment, run).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let stat = provider
+            .tx_ref()
+            .get::<tables::TableStats>(tables::PruneRuns::NAME.to_string())
+            .unwrap()
+            .unwrap();
+        assert_eq!(stat.entries, 2);
+        assert!(stat.size_bytes > 0);
+        // unmodified tables are not tracked
+        assert_eq!(
+            provider.tx_ref().get::<tables::TableStats>(tables::Headers::NAME.to_string()).unwrap(),
+            None
+        );
+    }
 }
@@ -0,0 +1,179 @@
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use reth_static_file_types::StaticFileSegment;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+thread_local! {
+    /// Addresses of the segment locks that are held by pins of the current thread.
+    static PINNED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Coordinates the truncation of static file segments with their concurrent readers.
+///
+/// Every segment has a generation, which is advanced whenever the segment is truncated. Readers
+/// pin a segment for the duration of a read operation. A truncation waits for all pins of the
+/// segment to be released, and blocks new pins until the files on disk and the index are updated.
+/// This way, readers never observe a partially truncated segment.
+///
+/// New pins wait for a pending truncation, so readers can't starve it. Only nested pins of a
+/// thread that already pins the segment are granted right away, since waiting would deadlock.
+#[derive(Debug)]
+pub(crate) struct SegmentGenerations {
+    segments: HashMap<StaticFileSegment, SegmentGeneration>,
+}
+
+impl Default for SegmentGenerations {
+    fn default() -> Self {
+        Self {
+            segments: StaticFileSegment::iter()
+                .map(|segment| (segment, SegmentGeneration::default()))
+                .collect(),
+        }
+    }
+}
+
+impl SegmentGenerations {
+    /// Pins the segment, waiting for a pending truncation to finish.
+    ///
+    /// Pins can be nested by the same thread, even if a truncation is pending.
+    pub(crate) fn pin(&self, segment: StaticFileSegment) -> SegmentPin<'_> {
+        let lock = &self.segment(segment).lock;
+        let key = lock as *const RwLock<()> as usize;
+        if PINNED.with_borrow(|pinned| pinned.contains(&key)) {
+            return SegmentPin { key, guard: None }
+        }
+
+        let guard = lock.read();
+        PINNED.with_borrow_mut(|pinned| pinned.push(key));
+        SegmentPin { key, guard: Some(guard) }
+    }
+
+    /// Starts the truncation of the segment, waiting for all pins of the segment to be released.
+    ///
+    /// The generation of the segment is advanced once the returned guard is dropped.
+    ///
+    /// CAUTION: the current thread must not hold a pin of the segment or IT WILL deadlock.
+    pub(crate) fn begin_truncation(&self, segment: StaticFileSegment) -> TruncationGuard<'_> {
+        let segment = self.segment(segment);
+        TruncationGuard { generation: &segment.generation, _guard: segment.lock.write() }
+    }
+
+    /// Returns the current generation of the segment.
+    pub(crate) fn generation(&self, segment: StaticFileSegment) -> u64 {
+        self.segment(segment).generation.load(Ordering::Acquire)
+    }
+
+    fn segment(&self, segment: StaticFileSegment) -> &SegmentGeneration {
+        self.segments.get(&segment).expect("all segments are initialized")
+    }
+}
+
+/// Generation of a single segment.
+#[derive(Debug, Default)]
+struct SegmentGeneration {
+    /// Number of truncations of the segment.
+    generation: AtomicU64,
+    /// Shared by the readers of the segment, exclusive to a truncation.
+    lock: RwLock<()>,
+}
+
+/// Pinned segment, which can't be truncated until the pin is dropped.
+#[derive(Debug)]
+pub(crate) struct SegmentPin<'a> {
+    /// Address of the lock of the segment.
+    key: usize,
+    /// Read guard of the lock, `None` if the pin is nested in another pin of the thread.
+    guard: Option<RwLockReadGuard<'a, ()>>,
+}
+
+impl Drop for SegmentPin<'_> {
+    fn drop(&mut self) {
+        if self.guard.is_some() {
+            PINNED.with_borrow_mut(|pinned| pinned.retain(|key| *key != self.key));
+        }
+    }
+}
+
+/// Exclusive access to a segment during its truncation.
+///
+/// Advances the generation of the segment when dropped.
+#[derive(Debug)]
+pub(crate) struct TruncationGuard<'a> {
+    generation: &'a AtomicU64,
+    _guard: RwLockWriteGuard<'a, ()>,
+}
+
+impl Drop for TruncationGuard<'_> {
+    fn drop(&mut self) {
+        // The generation is advanced before the lock is released, so new pins observe it.
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::mpsc, time::Duration};
+
+    #[test]
+    fn truncation_waits_for_pins() {
+        let generations = SegmentGenerations::default();
+        let segment = StaticFileSegment::Headers;
+
+        let pin = generations.pin(segment);
+        assert_eq!(generations.generation(segment), 0);
+        // Pins of other segments are independent.
+        drop(generations.begin_truncation(StaticFileSegment::Receipts));
+
+        std::thread::scope(|scope| {
+            let (tx, rx) = mpsc::channel();
+            scope.spawn(|| {
+                let _guard = generations.begin_truncation(segment);
+                tx.send(()).unwrap();
+            });
+
+            // The truncation can't start while the generation is pinned.
+            assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+            drop(pin);
+            rx.recv().unwrap();
+        });
+
+        assert_eq!(generations.generation(segment), 1);
+        assert_eq!(generations.generation(StaticFileSegment::Receipts), 1);
+    }
+
+    #[test]
+    fn pending_truncation_blocks_new_pins() {
+        let generations = SegmentGenerations::default();
+        let segment = StaticFileSegment::Headers;
+
+        let pin = generations.pin(segment);
+        std::thread::scope(|scope| {
+            let (truncating_tx, truncating_rx) = mpsc::channel();
+            let (pinned_tx, pinned_rx) = mpsc::channel();
+            scope.spawn(|| {
+                truncating_tx.send(()).unwrap();
+                let _guard = generations.begin_truncation(segment);
+            });
+            truncating_rx.recv().unwrap();
+            // Give the truncation time to start waiting for the pin.
+            std::thread::sleep(Duration::from_millis(50));
+
+            scope.spawn(|| {
+                let _pin = generations.pin(segment);
+                pinned_tx.send(generations.generation(segment)).unwrap();
+            });
+
+            // Nested pins of the same thread don't wait for the truncation.
+            drop(generations.pin(segment));
+
+            // Other readers wait for the pending truncation.
+            assert!(pinned_rx.recv_timeout(Duration::from_millis(100)).is_err());
+            drop(pin);
+            assert_eq!(pinned_rx.recv().unwrap(), 1);
+        });
+    }
+}
@@ -1,6 +1,12 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    generation::{SegmentGenerations, TruncationGuard},
+    metrics::StaticFileProviderMetrics,
+    verification::{
+        checksum_path, static_file_checksum, throttled_static_file_checksum, RecordedChecksum,
+        StaticFileVerification,
+    },
+    writer::StaticFileWriters,
+    LoadedJar, StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
 };
 use crate::{
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
@@ -18,12 +24,13 @@ use alloy_primitives::{
 use dashmap::DashMap;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use parking_lot::RwLock;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec};
 use reth_db::{
     lockfile::StorageLock,
     static_file::{
-        iter_static_files, BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask,
-        ReceiptMask, StaticFileCursor, TDWithHashMask, TransactionMask,
+        iter_static_files, BlockHashMask, BodyIndicesMask, ColumnSelectorOne, HeaderMask,
+        HeaderWithHashMask, ReceiptMask, StaticFileCursor, TDWithHashMask, TransactionMask,
     },
 };
 use reth_db_api::{
@@ -50,15 +57,22 @@ use std::{
     marker::PhantomData,
     ops::{Deref, Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
-    sync::{atomic::AtomicU64, mpsc, Arc},
+    sync::{atomic::AtomicU64, mpsc, Arc, OnceLock},
+    time::Instant,
 };
-use tracing::{debug, info, trace, warn};
+use tracing::{debug, error, info, trace, warn};
 
 /// Alias type for a map that can be queried for block ranges from a transaction
 /// segment respectively. It uses `TxNumber` to represent the transaction end of a static file
 /// range.
 type SegmentRanges = HashMap<StaticFileSegment, BTreeMap<TxNumber, SegmentRangeInclusive>>;
 
+/// Number of values from which vectorized static file reads decode values in parallel.
+const PARALLEL_DECODE_THRESHOLD: usize = 1024;
+
+/// Maximum number of bytes per second the background verifier reads static files with.
+const BACKGROUND_VERIFICATION_READ_RATE: u64 = 32 * 1024 * 1024;
+
 /// Access mode on a static file provider. RO/RW.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub enum StaticFileAccess {
@@ -254,6 +268,15 @@ pub struct StaticFileProviderInner<N> {
     static_files_max_block: RwLock<HashMap<StaticFileSegment, u64>>,
     /// Available static file block ranges on disk indexed by max transactions.
     static_files_tx_index: RwLock<SegmentRanges>,
+    /// Generations of each segment, coordinating truncations with concurrent readers.
+    generations: SegmentGenerations,
+    /// Verification status of the finalized static files verified since launch, keyed by the end
+    /// of their fixed block range.
+    verifications: RwLock<HashMap<(BlockNumber, StaticFileSegment), StaticFileVerification>>,
+    /// Queue of the background verifier of finalized static files, started on first use.
+    verifier: OnceLock<mpsc::Sender<(StaticFileSegment, SegmentRangeInclusive)>>,
+    /// Whether static files are verified in the background once they are finalized.
+    background_verification: bool,
     /// Directory where `static_files` are located
     path: PathBuf,
     /// Maintains a writer set of [`StaticFileSegment`].
@@ -286,6 +309,10 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             earliest_history_height: Default::default(),
             static_files_max_block: Default::default(),
             static_files_tx_index: Default::default(),
+            generations: Default::default(),
+            verifications: Default::default(),
+            verifier: Default::default(),
+            background_verification: false,
             path: path.as_ref().to_path_buf(),
             metrics: None,
             access,
@@ -301,6 +328,20 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
         self.access.is_read_only()
     }
 
+    /// Returns the current generation of the segment, which is advanced on every truncation.
+    pub fn segment_generation(&self, segment: StaticFileSegment) -> u64 {
+        self.generations.generation(segment)
+    }
+
+    /// Starts the truncation of the segment, waiting for all readers of the segment to release
+    /// their [`StaticFileJarProvider`]s, and blocking new ones until the guard is dropped.
+    ///
+    /// CAUTION: providers of the segment should be dropped before calling this or IT WILL
+    /// deadlock.
+    pub(crate) fn begin_truncation(&self, segment: StaticFileSegment) -> TruncationGuard<'_> {
+        self.generations.begin_truncation(segment)
+    }
+
     /// Each static file has a fixed number of blocks. This gives out the range where the requested
     /// block is positioned.
     pub const fn find_fixed_range(&self, block: BlockNumber) -> SegmentRangeInclusive {
@@ -326,6 +367,22 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Self(Arc::new(provider))
     }
 
+    /// Drops the cached writers, discarding the data that was appended but not committed, e.g. by
+    /// a run that panicked.
+    ///
+    /// The next writer of a segment reopens its static file at the last commit.
+    pub fn discard_uncommitted_writes(&self) {
+        self.writers.discard();
+    }
+
+    /// Enables the verification of static files on a background thread once they are finalized.
+    pub fn with_background_verification(self) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.background_verification = true;
+        Self(Arc::new(provider))
+    }
+
     /// Reports metrics for the static files.
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
@@ -400,12 +457,20 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     /// Gets the [`StaticFileJarProvider`] of the requested segment and block or transaction.
     ///
     /// `fn_range` should make sure the range goes through `find_fixed_range`.
+    ///
+    /// The segment is only pinned while the provider is created, so reads through the returned
+    /// provider are not coordinated with truncations of the segment. The read methods of
+    /// [`StaticFileProvider`] pin the segment for the duration of the read instead.
     pub fn get_segment_provider(
         &self,
         segment: StaticFileSegment,
         fn_range: impl Fn() -> Option<SegmentRangeInclusive>,
         path: Option<&Path>,
     ) -> ProviderResult<Option<StaticFileJarProvider<'_, N>>> {
+        // Pin the segment before looking up the index, so a truncation can't delete the file
+        // before the provider is created.
+        let _pin = self.generations.pin(segment);
+
         // If we have a path, then get the block range from its name.
         // Otherwise, check `self.available_static_files`
         let block_range = match path {
@@ -508,12 +573,198 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         };
 
         jar.delete().map_err(ProviderError::other)?;
+        self.invalidate_verification(segment, &fixed_block_range)?;
 
         self.initialize_index()?;
 
         Ok(())
     }
 
+    /// Schedules the verification of a finalized static file on a background thread.
+    ///
+    /// Static files are verified one after the other at a limited read rate, so that the
+    /// verification doesn't compete with the node for disk bandwidth.
+    pub fn schedule_verification(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: SegmentRangeInclusive,
+    ) {
+        let verifier = self.verifier.get_or_init(|| {
+            let (tx, rx) = mpsc::channel::<(StaticFileSegment, SegmentRangeInclusive)>();
+            let provider = Arc::downgrade(&self.0);
+            std::thread::Builder::new()
+                .name("static-file-verifier".to_string())
+                .spawn(move || {
+                    // Exits once the provider, and with it the sender, is dropped.
+                    while let Ok((segment, fixed_block_range)) = rx.recv() {
+                        let Some(provider) = provider.upgrade() else { break };
+                        if let Err(err) = Self(provider).verify_static_file_with_read_rate(
+                            segment,
+                            fixed_block_range,
+                            Some(BACKGROUND_VERIFICATION_READ_RATE),
+                        ) {
+                            warn!(target: "provider::static_file", ?segment, ?fixed_block_range, %err, "Failed to verify static file");
+                        }
+                    }
+                })
+                .expect("failed to spawn static file verifier thread");
+            tx
+        });
+        let _ = verifier.send((segment, fixed_block_range));
+    }
+
+    /// Records the checksum of a static file that was just finalized by a writer, and schedules
+    /// its verification if background verification is enabled.
+    pub(crate) fn on_finalized_static_file(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: SegmentRangeInclusive,
+    ) -> ProviderResult<()> {
+        let path = self.path.join(segment.filename(&fixed_block_range));
+        let jar = NippyJar::<SegmentHeader>::load(&path).map_err(ProviderError::other)?;
+        let checksum = static_file_checksum(&jar).map_err(ProviderError::other)?;
+        RecordedChecksum { checksum: checksum.to_string(), verified: false }
+            .write(&checksum_path(&path))
+            .map_err(ProviderError::other)?;
+
+        if self.background_verification {
+            self.schedule_verification(segment, fixed_block_range);
+        }
+        Ok(())
+    }
+
+    /// Verifies a finalized static file, and records the result in the index.
+    ///
+    /// The sizes of the data and offsets files need to match the configuration, and the checksum
+    /// of the static file needs to match the one recorded by the writer when it was finalized.
+    /// Static files finalized before writers recorded checksums only have their consistency
+    /// checked, and their checksum is recorded for the next verification.
+    pub fn verify_static_file(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: SegmentRangeInclusive,
+    ) -> ProviderResult<StaticFileVerification> {
+        self.verify_static_file_with_read_rate(segment, fixed_block_range, None)
+    }
+
+    /// Verifies a finalized static file like [`Self::verify_static_file`], reading at most
+    /// `max_read_rate` bytes per second if set.
+    fn verify_static_file_with_read_rate(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: SegmentRangeInclusive,
+        max_read_rate: Option<u64>,
+    ) -> ProviderResult<StaticFileVerification> {
+        let start = Instant::now();
+        let generation = self.segment_generation(segment);
+        let path = self.path.join(segment.filename(&fixed_block_range));
+
+        let jar = NippyJar::<SegmentHeader>::load(&path).map_err(ProviderError::other)?;
+        let checksum =
+            throttled_static_file_checksum(&jar, max_read_rate).map_err(ProviderError::other)?;
+        let consistent = NippyJarChecker::new(jar).check_consistency().is_ok();
+
+        let checksum = checksum.to_string();
+        let checksum_path = checksum_path(&path);
+        let recorded = RecordedChecksum::read(&checksum_path).map_err(ProviderError::other)?;
+        let status = if !consistent ||
+            recorded.as_ref().is_some_and(|recorded| recorded.checksum != checksum)
+        {
+            StaticFileVerification::Corrupted
+        } else {
+            StaticFileVerification::Verified
+        };
+
+        // A truncation of the segment may have modified the static file while it was being read.
+        if self.segment_generation(segment) != generation {
+            return Ok(StaticFileVerification::Unverified)
+        }
+
+        if status == StaticFileVerification::Verified &&
+            !recorded.is_some_and(|recorded| recorded.verified)
+        {
+            RecordedChecksum { checksum, verified: true }
+                .write(&checksum_path)
+                .map_err(ProviderError::other)?;
+        }
+        self.verifications.write().insert((fixed_block_range.end(), segment), status);
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_verification(segment, status, start.elapsed());
+        }
+        if status == StaticFileVerification::Corrupted {
+            error!(target: "provider::static_file", ?segment, ?fixed_block_range, ?path, "Static file is corrupted");
+        } else {
+            debug!(target: "provider::static_file", ?segment, ?fixed_block_range, elapsed = ?start.elapsed(), "Verified static file");
+        }
+
+        Ok(status)
+    }
+
+    /// Returns the verification status of a finalized static file, as recorded by a verification
+    /// since launch or by the checksum file of an earlier one.
+    pub fn static_file_verification(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) -> StaticFileVerification {
+        if let Some(status) = self.verifications.read().get(&(fixed_block_range.end(), segment)) {
+            return *status
+        }
+
+        let path = checksum_path(&self.path.join(segment.filename(fixed_block_range)));
+        match RecordedChecksum::read(&path) {
+            Ok(Some(recorded)) if recorded.verified => StaticFileVerification::Verified,
+            _ => StaticFileVerification::Unverified,
+        }
+    }
+
+    /// Returns the fixed block ranges of the finalized static files of every segment, i.e. all
+    /// files except the one that is currently written to.
+    pub fn finalized_static_files(
+        &self,
+    ) -> ProviderResult<Vec<(StaticFileSegment, SegmentRangeInclusive)>> {
+        let mut finalized = Vec::new();
+        for (segment, ranges) in iter_static_files(&self.path).map_err(ProviderError::other)? {
+            let Some((_, ranges)) = ranges.split_last() else { continue };
+            finalized.extend(
+                ranges
+                    .iter()
+                    .map(|(block_range, _)| (segment, self.find_fixed_range(block_range.start()))),
+            );
+        }
+        finalized.sort_unstable_by_key(|(segment, range)| (*segment, range.start()));
+        Ok(finalized)
+    }
+
+    /// Returns the finalized static files that haven't been verified yet.
+    pub fn unverified_static_files(
+        &self,
+    ) -> ProviderResult<Vec<(StaticFileSegment, SegmentRangeInclusive)>> {
+        Ok(self
+            .finalized_static_files()?
+            .into_iter()
+            .filter(|(segment, range)| {
+                self.static_file_verification(*segment, range) == StaticFileVerification::Unverified
+            })
+            .collect())
+    }
+
+    /// Discards the verification of a static file, when it's modified or deleted.
+    pub(crate) fn invalidate_verification(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) -> ProviderResult<()> {
+        self.verifications.write().remove(&(fixed_block_range.end(), segment));
+
+        let checksum_path = checksum_path(&self.path.join(segment.filename(fixed_block_range)));
+        if checksum_path.exists() {
+            reth_fs_util::remove_file(checksum_path).map_err(ProviderError::other)?;
+        }
+        Ok(())
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -544,7 +795,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
     /// Gets a static file segment's block range from the provider inner block
     /// index.
-    fn get_segment_ranges_from_block(
+    pub(crate) fn get_segment_ranges_from_block(
         &self,
         segment: StaticFileSegment,
         block: u64,
@@ -766,6 +1017,17 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
         info!(target: "reth::cli", "Verifying storage consistency.");
 
+        // Static files missing entirely while the database progressed past genesis usually means
+        // that they were moved without pointing reth to their new location. Healing would unwind
+        // the database to genesis, so bail out instead.
+        let headers_checkpoint =
+            provider.get_stage_checkpoint(StageId::Headers)?.unwrap_or_default().block_number;
+        if headers_checkpoint > 0 &&
+            self.get_highest_static_file_block(StaticFileSegment::Headers).is_none()
+        {
+            return Err(ProviderError::MissingStaticFiles(self.path.clone(), headers_checkpoint))
+        }
+
         let mut unwind_target: Option<BlockNumber> = None;
         let mut update_unwind_target = |new_target: BlockNumber| {
             if let Some(target) = unwind_target.as_mut() {
@@ -1081,6 +1343,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         func: impl Fn(StaticFileJarProvider<'_, N>) -> ProviderResult<Option<T>>,
     ) -> ProviderResult<Option<T>> {
+        // Keep the index consistent with the files on disk while iterating.
+        let _pin = self.generations.pin(segment);
+
         if let Some(highest_block) = self.get_highest_static_file_block(segment) {
             let mut range = self.find_fixed_range(highest_block);
             while range.end() > 0 {
@@ -1097,6 +1362,52 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(None)
     }
 
+    /// Fetches a single column for a range of blocks across multiple static files.
+    ///
+    /// Unlike [`Self::fetch_range_with_predicate`], the values of each static file are read from
+    /// the memory-mapped file in one pass and decoded as a batch, in parallel for large ranges.
+    pub fn fetch_block_range_vectored<M>(
+        &self,
+        segment: StaticFileSegment,
+        range: Range<u64>,
+    ) -> ProviderResult<Vec<M::FIRST>>
+    where
+        M: ColumnSelectorOne,
+        M::FIRST: Send,
+    {
+        // Keep the static files consistent with the index while reading.
+        let _pin = self.generations.pin(segment);
+
+        // the range can be supplied by a client, so the capacity is bounded by the blocks that
+        // are actually in the static files
+        let end = self
+            .get_highest_static_file_block(segment)
+            .map_or(range.start, |highest| range.end.min(highest.saturating_add(1)));
+        let mut result = Vec::with_capacity(end.saturating_sub(range.start) as usize);
+        let mut start = range.start;
+        while start < range.end {
+            let provider = self.get_segment_provider_from_block(segment, start, None)?;
+            let mut cursor = provider.cursor()?;
+            let values = cursor.get_raw_range::<M>(start..range.end)?;
+            if values.is_empty() {
+                return Err(ProviderError::MissingStaticFileBlock(segment, start))
+            }
+            start += values.len() as u64;
+
+            if values.len() >= PARALLEL_DECODE_THRESHOLD {
+                let decoded: Vec<_> =
+                    values.into_par_iter().map(M::FIRST::decompress).collect::<Result<_, _>>()?;
+                result.extend(decoded);
+            } else {
+                for value in values {
+                    result.push(M::FIRST::decompress(value)?);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Fetches data within a specified range across multiple static files.
     ///
     /// This function iteratively retrieves data using `get_fn` for each item in the given range.
@@ -1113,6 +1424,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         F: FnMut(&mut StaticFileCursor<'_>, u64) -> ProviderResult<Option<T>>,
         P: FnMut(&T) -> bool,
     {
+        // Keep the static files consistent with the index while reading.
+        let _pin = self.generations.pin(segment);
+
         let get_provider = |start: u64| {
             if segment.is_block_based() {
                 self.get_segment_provider_from_block(segment, start, None)
@@ -1177,7 +1491,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
     /// Fetches data within a specified range across multiple static files.
     ///
-    /// Returns an iterator over the data
+    /// Returns an iterator over the data. The segment is pinned while each item is read, so
+    /// truncations of the segment can proceed between items.
     pub fn fetch_range_iter<'a, T, F>(
         &'a self,
         segment: StaticFileSegment,
@@ -1196,20 +1511,13 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             }
         };
 
-        let mut provider = Some(get_provider(range.start)?);
+        // Fail early if the range starts outside of the static files.
+        drop(get_provider(range.start)?);
         Ok(range.filter_map(move |number| {
-            match get_fn(&mut provider.as_ref().expect("qed").cursor().ok()?, number).transpose() {
-                Some(result) => Some(result),
-                None => {
-                    // There is a very small chance of hitting a deadlock if two consecutive static
-                    // files share the same bucket in the internal dashmap and
-                    // we don't drop the current provider before requesting the
-                    // next one.
-                    provider.take();
-                    provider = Some(get_provider(number).ok()?);
-                    get_fn(&mut provider.as_ref().expect("qed").cursor().ok()?, number).transpose()
-                }
-            }
+            // Providers must be created while the segment is pinned, so they aren't kept across
+            // items.
+            let _pin = self.generations.pin(segment);
+            get_fn(&mut get_provider(number).ok()?.cursor().ok()?, number).transpose()
         }))
     }
 
@@ -1387,6 +1695,7 @@ impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileProvide
     }
 
     fn header_by_number(&self, num: BlockNumber) -> ProviderResult<Option<Self::Header>> {
+        let _pin = self.generations.pin(StaticFileSegment::Headers);
         self.get_segment_provider_from_block(StaticFileSegment::Headers, num, None)
             .and_then(|provider| provider.header_by_number(num))
             .or_else(|err| {
@@ -1408,6 +1717,7 @@ impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileProvide
     }
 
     fn header_td_by_number(&self, num: BlockNumber) -> ProviderResult<Option<U256>> {
+        let _pin = self.generations.pin(StaticFileSegment::Headers);
         self.get_segment_provider_from_block(StaticFileSegment::Headers, num, None)
             .and_then(|provider| provider.header_td_by_number(num))
             .or_else(|err| {
@@ -1431,10 +1741,21 @@ impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileProvide
         )
     }
 
+    fn headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Self::Header>> {
+        self.fetch_block_range_vectored::<HeaderMask<Self::Header>>(
+            StaticFileSegment::Headers,
+            to_range(range),
+        )
+    }
+
     fn sealed_header(
         &self,
         num: BlockNumber,
     ) -> ProviderResult<Option<SealedHeader<Self::Header>>> {
+        let _pin = self.generations.pin(StaticFileSegment::Headers);
         self.get_segment_provider_from_block(StaticFileSegment::Headers, num, None)
             .and_then(|provider| provider.sealed_header(num))
             .or_else(|err| {
@@ -1446,6 +1767,24 @@ impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileProvide
             })
     }
 
+    fn sealed_headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader<Self::Header>>> {
+        let range = to_range(range);
+        let headers = self.fetch_block_range_vectored::<HeaderMask<Self::Header>>(
+            StaticFileSegment::Headers,
+            range.clone(),
+        )?;
+        let hashes =
+            self.fetch_block_range_vectored::<BlockHashMask>(StaticFileSegment::Headers, range)?;
+        Ok(headers
+            .into_iter()
+            .zip(hashes)
+            .map(|(header, hash)| SealedHeader::new(header, hash))
+            .collect())
+    }
+
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
@@ -1466,6 +1805,7 @@ impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileProvide
 
 impl<N: NodePrimitives> BlockHashReader for StaticFileProvider<N> {
     fn block_hash(&self, num: u64) -> ProviderResult<Option<B256>> {
+        let _pin = self.generations.pin(StaticFileSegment::Headers);
         self.get_segment_provider_from_block(StaticFileSegment::Headers, num, None)?.block_hash(num)
     }
 
@@ -1489,6 +1829,7 @@ impl<N: NodePrimitives<SignedTx: Value + SignedTransaction, Receipt: Value>> Rec
     type Receipt = N::Receipt;
 
     fn receipt(&self, num: TxNumber) -> ProviderResult<Option<Self::Receipt>> {
+        let _pin = self.generations.pin(StaticFileSegment::Receipts);
         self.get_segment_provider_from_transaction(StaticFileSegment::Receipts, num, None)
             .and_then(|provider| provider.receipt(num))
             .or_else(|err| {
@@ -1562,11 +1903,11 @@ impl<N: FullNodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>>
             let manager = self.clone();
 
             // Spawn the task onto the global rayon pool
-            // This task will send the results through the channel after it has calculated
-            // the hash.
+            // This task will send the hashes of the whole chunk through the channel after it has
+            // calculated them.
             rayon::spawn(move || {
                 let mut rlp_buf = Vec::with_capacity(128);
-                let _ = manager.fetch_range_with_predicate(
+                let hashes = manager.fetch_range_with_predicate(
                     StaticFileSegment::Transactions,
                     chunk_range,
                     |cursor, number| {
@@ -1574,12 +1915,12 @@ impl<N: FullNodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>>
                             .get_one::<TransactionMask<Self::Transaction>>(number.into())?
                             .map(|transaction| {
                                 rlp_buf.clear();
-                                let _ = channel_tx
-                                    .send(calculate_hash((number, transaction), &mut rlp_buf));
+                                calculate_hash((number, transaction), &mut rlp_buf)
                             }))
                     },
                     |_| true,
                 );
+                let _ = channel_tx.send(hashes);
             });
         }
 
@@ -1587,9 +1928,8 @@ impl<N: FullNodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>>
 
         // Iterate over channels and append the tx hashes unsorted
         for channel in channels {
-            while let Ok(tx) = channel.recv() {
-                let (tx_hash, tx_id) = tx.map_err(|boxed| *boxed)?;
-                tx_list.push((tx_hash, tx_id));
+            if let Ok(hashes) = channel.recv() {
+                tx_list.extend(hashes?);
             }
         }
 
@@ -1618,6 +1958,7 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction>> TransactionsPr
     }
 
     fn transaction_by_id(&self, num: TxNumber) -> ProviderResult<Option<Self::Transaction>> {
+        let _pin = self.generations.pin(StaticFileSegment::Transactions);
         self.get_segment_provider_from_transaction(StaticFileSegment::Transactions, num, None)
             .and_then(|provider| provider.transaction_by_id(num))
             .or_else(|err| {
@@ -1633,6 +1974,7 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction>> TransactionsPr
         &self,
         num: TxNumber,
     ) -> ProviderResult<Option<Self::Transaction>> {
+        let _pin = self.generations.pin(StaticFileSegment::Transactions);
         self.get_segment_provider_from_transaction(StaticFileSegment::Transactions, num, None)
             .and_then(|provider| provider.transaction_by_id_unhashed(num))
             .or_else(|err| {
@@ -1805,6 +2147,7 @@ impl<N: FullNodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>>
 
 impl<N: NodePrimitives> BlockBodyIndicesProvider for StaticFileProvider<N> {
     fn block_body_indices(&self, num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+        let _pin = self.generations.pin(StaticFileSegment::BlockMeta);
         self.get_segment_provider_from_block(StaticFileSegment::BlockMeta, num, None)
             .and_then(|provider| provider.block_body_indices(num))
             .or_else(|err| {
@@ -1855,14 +2198,11 @@ impl<N: NodePrimitives> StatsReader for StaticFileProvider<N> {
 
 /// Calculates the tx hash for the given transaction and its id.
 #[inline]
-fn calculate_hash<T>(
-    entry: (TxNumber, T),
-    rlp_buf: &mut Vec<u8>,
-) -> Result<(B256, TxNumber), Box<ProviderError>>
+fn calculate_hash<T>(entry: (TxNumber, T), rlp_buf: &mut Vec<u8>) -> (B256, TxNumber)
 where
     T: Encodable2718,
 {
     let (tx_id, tx) = entry;
     tx.encode_2718(rlp_buf);
-    Ok((keccak256(rlp_buf), tx_id))
+    (keccak256(rlp_buf), tx_id)
 }
@@ -6,6 +6,8 @@ use reth_metrics::Metrics;
 use reth_static_file_types::StaticFileSegment;
 use strum::{EnumIter, IntoEnumIterator};
 
+use super::StaticFileVerification;
+
 /// Metrics for the static file provider.
 #[derive(Debug)]
 pub struct StaticFileProviderMetrics {
@@ -60,6 +62,21 @@ impl StaticFileProviderMetrics {
             .set(entries as f64);
     }
 
+    pub(crate) fn record_segment_verification(
+        &self,
+        segment: StaticFileSegment,
+        status: StaticFileVerification,
+        duration: Duration,
+    ) {
+        let metrics = self.segments.get(&segment).expect("segment metrics should exist");
+        match status {
+            StaticFileVerification::Verified => metrics.verified_files.increment(1),
+            StaticFileVerification::Corrupted => metrics.corrupted_files.increment(1),
+            StaticFileVerification::Unverified => {}
+        }
+        metrics.verification_duration_seconds.record(duration.as_secs_f64());
+    }
+
     pub(crate) fn record_segment_operation(
         &self,
         segment: StaticFileSegment,
@@ -137,6 +154,12 @@ pub(crate) struct StaticFileSegmentMetrics {
     files: Gauge,
     /// The number of entries for a static file segment
     entries: Gauge,
+    /// The number of static files of the segment that passed verification
+    verified_files: Counter,
+    /// The number of static files of the segment that failed verification
+    corrupted_files: Counter,
+    /// The time it took to verify a static file of the segment
+    verification_duration_seconds: Histogram,
 }
 
 #[derive(Metrics)]
@@ -8,6 +8,14 @@ mod writer;
 pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
+
+mod verification;
+pub use verification::{
+    checksum_path, static_file_checksum, throttled_static_file_checksum, StaticFileVerification,
+    CHECKSUM_FILE_EXTENSION,
+};
+
+mod generation;
 use reth_nippy_jar::NippyJar;
 use reth_static_file_types::{SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -59,7 +67,7 @@ mod tests {
     };
     use alloy_consensus::{Header, SignableTransaction, Transaction, TxLegacy};
     use alloy_primitives::{BlockHash, Signature, TxNumber, B256, U256};
-    use rand::seq::SliceRandom;
+    use rand::{seq::SliceRandom, Rng};
     use reth_db::test_utils::create_test_static_files_dir;
     use reth_db_api::{
         transaction::DbTxMut, CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers,
@@ -70,7 +78,13 @@ mod tests {
     };
     use reth_storage_api::{ReceiptProvider, TransactionsProvider};
     use reth_testing_utils::generators::{self, random_header_range};
-    use std::{fmt::Debug, fs, ops::Range, path::Path};
+    use std::{
+        fmt::Debug,
+        fs,
+        ops::Range,
+        path::Path,
+        sync::atomic::{AtomicBool, Ordering},
+    };
 
     fn assert_eyre<T: PartialEq + Debug>(got: T, expected: T, msg: &str) -> eyre::Result<()> {
         if got != expected {
@@ -295,6 +309,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_header_truncation_with_concurrent_readers() {
+        let (static_dir, _) = create_test_static_files_dir();
+
+        let blocks_per_file = 10;
+        let tip = blocks_per_file * 5 - 1;
+        let prune_count = 3;
+
+        let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+
+        let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut header = Header::default();
+        for num in 0..=tip {
+            header.number = num;
+            header_writer.append_header(&header, U256::default(), &BlockHash::default()).unwrap();
+        }
+        header_writer.commit().unwrap();
+
+        let unwinding = AtomicBool::new(true);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let mut rng = generators::rng();
+                    while unwinding.load(Ordering::Relaxed) {
+                        let num = rng.random_range(0..=tip);
+                        // Blocks are either fully readable, or missing after being truncated.
+                        match sf_rw.header_by_number(num) {
+                            Ok(Some(header)) => assert_eq!(header.number, num),
+                            Ok(None) | Err(ProviderError::MissingStaticFileBlock(_, _)) => {}
+                            Err(err) => panic!("failed to read header {num}: {err}"),
+                        }
+                    }
+                });
+            }
+
+            // Unwinds all blocks except genesis, crossing file boundaries.
+            let mut unwinds = 0;
+            let mut current_tip = tip;
+            while current_tip > prune_count {
+                header_writer.prune_headers(prune_count).unwrap();
+                header_writer.commit().unwrap();
+                current_tip -= prune_count;
+                unwinds += 1;
+
+                assert_eq!(
+                    sf_rw.get_highest_static_file_block(StaticFileSegment::Headers),
+                    Some(current_tip)
+                );
+                assert_eq!(sf_rw.segment_generation(StaticFileSegment::Headers), unwinds);
+            }
+            unwinding.store(false, Ordering::Relaxed);
+        });
+    }
+
     /// 3 block ranges are built
     ///
     /// for `blocks_per_file = 10`:
@@ -550,6 +620,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_static_file_verification() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let blocks_per_file = 10;
+        let segment = StaticFileSegment::Headers;
+
+        let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+        {
+            let mut header_writer = sf_rw.latest_writer(segment).unwrap();
+            let mut header = Header::default();
+            for num in 0..blocks_per_file * 2 {
+                header.number = num;
+                header_writer
+                    .append_header(&header, U256::default(), &BlockHash::default())
+                    .unwrap();
+            }
+            header_writer.commit().unwrap();
+        }
+
+        // Only the first file is finalized, the second one is still written to. The writer
+        // recorded the checksum of the first file when finalizing it.
+        let first = SegmentRangeInclusive::new(0, blocks_per_file - 1);
+        let data_path = static_dir.path().join(segment.filename(&first));
+        assert_eq!(sf_rw.finalized_static_files().unwrap(), vec![(segment, first)]);
+        assert_eq!(sf_rw.unverified_static_files().unwrap(), vec![(segment, first)]);
+        assert!(checksum_path(&data_path).exists());
+
+        // A flipped bit doesn't change the file sizes, but the checksum, so corruption is detected
+        // even before the first verification
+        let mut data = fs::read(&data_path).unwrap();
+        data[0] ^= 1;
+        fs::write(&data_path, &data).unwrap();
+        assert_eq!(
+            sf_rw.verify_static_file(segment, first).unwrap(),
+            StaticFileVerification::Corrupted
+        );
+        assert_eq!(
+            sf_rw.static_file_verification(segment, &first),
+            StaticFileVerification::Corrupted
+        );
+
+        // Once restored, the static file matches the checksum recorded by the writer
+        data[0] ^= 1;
+        fs::write(&data_path, &data).unwrap();
+        assert_eq!(
+            sf_rw.verify_static_file(segment, first).unwrap(),
+            StaticFileVerification::Verified
+        );
+        assert!(sf_rw.unverified_static_files().unwrap().is_empty());
+    }
+
     /// Returns the number of files in the provided path, excluding ".lock" files.
     fn count_files_without_lockfile(path: impl AsRef<Path>) -> eyre::Result<usize> {
         let is_lockfile = |entry: &fs::DirEntry| {
@@ -0,0 +1,116 @@
+use alloy_primitives::{Keccak256, B256};
+use reth_fs_util::FsPathError;
+use reth_nippy_jar::{NippyJar, NippyJarHeader};
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// Extension of the file recording the checksum of a verified static file.
+pub const CHECKSUM_FILE_EXTENSION: &str = "checksum";
+
+/// Size of the chunks static files are read in when computing their checksum.
+const CHECKSUM_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Line appended to a checksum file once its static file passed a verification.
+const VERIFIED_MARKER: &str = "verified";
+
+/// Verification status of a finalized static file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaticFileVerification {
+    /// The static file has not been verified yet.
+    Unverified,
+    /// The static file is consistent and matches its recorded checksum.
+    Verified,
+    /// The static file is inconsistent, or doesn't match its recorded checksum.
+    Corrupted,
+}
+
+impl StaticFileVerification {
+    /// Returns the string representation of the verification status.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unverified => "unverified",
+            Self::Verified => "verified",
+            Self::Corrupted => "corrupted",
+        }
+    }
+}
+
+/// Checksum of a finalized static file, as recorded in its checksum file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RecordedChecksum {
+    /// Checksum recorded when the static file was finalized.
+    pub(crate) checksum: String,
+    /// Whether the static file matched the checksum in a verification.
+    pub(crate) verified: bool,
+}
+
+impl RecordedChecksum {
+    /// Reads the checksum file at `path`, returning `None` if it doesn't exist.
+    pub(crate) fn read(path: &Path) -> Result<Option<Self>, FsPathError> {
+        if !path.exists() {
+            return Ok(None)
+        }
+        let contents = reth_fs_util::read_to_string(path)?;
+        let mut lines = contents.lines().map(str::trim);
+        let checksum = lines.next().unwrap_or_default().to_string();
+        let verified = lines.next() == Some(VERIFIED_MARKER);
+        Ok(Some(Self { checksum, verified }))
+    }
+
+    /// Atomically writes the checksum file at `path`.
+    pub(crate) fn write(&self, path: &Path) -> Result<(), FsPathError> {
+        let mut contents = self.checksum.clone();
+        if self.verified {
+            contents.push('\n');
+            contents.push_str(VERIFIED_MARKER);
+        }
+        reth_fs_util::atomic_write_file(path, |file| {
+            std::io::Write::write_all(file, contents.as_bytes())
+        })
+    }
+}
+
+/// Returns the path of the file recording the checksum of the static file at `data_path`.
+pub fn checksum_path(data_path: &Path) -> PathBuf {
+    data_path.with_extension(CHECKSUM_FILE_EXTENSION)
+}
+
+/// Computes the checksum of a static file, covering its data, offsets and configuration files.
+pub fn static_file_checksum<H: NippyJarHeader>(jar: &NippyJar<H>) -> io::Result<B256> {
+    throttled_static_file_checksum(jar, None)
+}
+
+/// Computes the checksum of a static file like [`static_file_checksum`], reading at most
+/// `max_read_rate` bytes per second if set.
+pub fn throttled_static_file_checksum<H: NippyJarHeader>(
+    jar: &NippyJar<H>,
+    max_read_rate: Option<u64>,
+) -> io::Result<B256> {
+    let start = Instant::now();
+    let mut total_read = 0u64;
+    let mut hasher = Keccak256::new();
+    let mut buf = vec![0; CHECKSUM_CHUNK_SIZE];
+    for path in [jar.data_path().to_path_buf(), jar.offsets_path(), jar.config_path()] {
+        let mut file = File::open(path)?;
+        loop {
+            let read = file.read(&mut buf)?;
+            if read == 0 {
+                break
+            }
+            hasher.update(&buf[..read]);
+
+            total_read += read as u64;
+            if let Some(max_read_rate) = max_read_rate.filter(|rate| *rate > 0) {
+                let due = Duration::from_secs_f64(total_read as f64 / max_read_rate as f64);
+                if let Some(ahead) = due.checked_sub(start.elapsed()) {
+                    std::thread::sleep(ahead);
+                }
+            }
+        }
+    }
+    Ok(hasher.finalize())
+}
@@ -65,6 +65,13 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
         Ok(StaticFileProviderRWRefMut(write_guard))
     }
 
+    /// Drops all writers without committing them.
+    pub(crate) fn discard(&self) {
+        for writer_lock in [&self.headers, &self.transactions, &self.receipts, &self.block_meta] {
+            writer_lock.write().take();
+        }
+    }
+
     pub(crate) fn commit(&self) -> ProviderResult<()> {
         for writer_lock in [&self.headers, &self.transactions, &self.receipts] {
             let mut writer = writer_lock.write();
@@ -155,20 +162,18 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         let static_file_provider = Self::upgrade_provider_to_strong_reference(&reader);
 
         let block_range = static_file_provider.find_fixed_range(block);
-        let (jar, path) = match static_file_provider.get_segment_provider_from_block(
-            segment,
-            block_range.start(),
-            None,
-        ) {
-            Ok(provider) => (
-                NippyJar::load(provider.data_path()).map_err(ProviderError::other)?,
-                provider.data_path().into(),
-            ),
-            Err(ProviderError::MissingStaticFileBlock(_, _)) => {
-                let path = static_file_provider.directory().join(segment.filename(&block_range));
-                (create_jar(segment, &path, block_range), path)
-            }
-            Err(err) => return Err(err),
+        let path = static_file_provider.directory().join(segment.filename(&block_range));
+        // The static file is about to be modified, so an earlier verification no longer holds.
+        static_file_provider.invalidate_verification(segment, &block_range)?;
+        // Loads the jar directly instead of going through a `StaticFileJarProvider`, since the
+        // writer might be opened during a truncation of the segment, which pinning would deadlock.
+        let jar = if static_file_provider
+            .get_segment_ranges_from_block(segment, block_range.start())
+            .is_some()
+        {
+            NippyJar::load(&path).map_err(ProviderError::other)?
+        } else {
+            create_jar(segment, &path, block_range)
         };
 
         let result = match NippyJarWriter::new(jar) {
@@ -224,6 +229,11 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
 
         // Truncates the data file if instructed to.
         if let Some((to_delete, last_block_number)) = self.prune_on_commit.take() {
+            // Readers of the segment are blocked until the truncated files and the index are
+            // updated, so they can't observe a partially truncated segment.
+            let reader = self.reader();
+            let _truncation = reader.begin_truncation(self.writer.user_header().segment());
+
             match self.writer.user_header().segment() {
                 StaticFileSegment::Headers => self.prune_header_data(to_delete)?,
                 StaticFileSegment::Transactions => self
@@ -344,6 +354,9 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
                     None,
                     segment,
                 );
+
+                let reader = self.reader();
+                reader.on_finalized_static_file(segment, reader.find_fixed_range(last_block))?;
             }
         }
 
@@ -25,12 +25,13 @@ use reth_node_types::NodeTypes;
 use reth_primitives_traits::{
     Account, Bytecode, GotExpected, NodePrimitives, RecoveredBlock, SealedHeader, SignerRecoverable,
 };
-use reth_prune_types::PruneModes;
+use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
     BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader,
-    StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    HashedPostStateProvider, HistoryAvailability, NodePrimitivesProvider, PruneCheckpointReader,
+    SenderTransactionsProvider, StageCheckpointReader, StateCommitmentProvider, StateProofProvider,
+    StorageRootProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -777,6 +778,25 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> StageCheckpointReader
     fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>> {
         Ok(vec![])
     }
+
+    fn history_availability(&self) -> ProviderResult<HistoryAvailability> {
+        Ok(HistoryAvailability::new(self.last_block_number()?))
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> PruneCheckpointReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn get_prune_checkpoint(
+        &self,
+        _segment: PruneSegment,
+    ) -> ProviderResult<Option<PruneCheckpoint>> {
+        Ok(None)
+    }
+
+    fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        Ok(vec![])
+    }
 }
 
 impl<T, ChainSpec> StateRootProvider for MockEthProvider<T, ChainSpec>
@@ -988,6 +1008,19 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvi
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> SenderTransactionsProvider
+    for MockEthProvider<T, ChainSpec>
+{
+    fn transaction_ids_by_sender(
+        &self,
+        _sender: Address,
+        _after: Option<TxNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::new())
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> StateReader for MockEthProvider<T, ChainSpec> {
     type Receipt = Receipt;
 
@@ -2,7 +2,8 @@
 
 use crate::{
     AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
+    PruneCheckpointReader, SenderTransactionsProvider, StageCheckpointReader, StateProviderFactory,
+    StaticFileProviderFactory,
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
@@ -23,9 +24,11 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + StateProviderFactory
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
+    + SenderTransactionsProvider
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + StageCheckpointReader
+    + PruneCheckpointReader
     + Clone
     + Debug
     + Unpin
@@ -46,9 +49,11 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + StateProviderFactory
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
+        + SenderTransactionsProvider
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + StageCheckpointReader
+        + PruneCheckpointReader
         + Clone
         + Debug
         + Unpin
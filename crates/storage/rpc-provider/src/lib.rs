@@ -52,7 +52,7 @@ use reth_provider::{
     StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, StorageReader,
     TransactionVariant, TransactionsProvider,
 };
-use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_prune_types::{PruneCheckpoint, PruneRun, PruneSegment};
 use reth_rpc_convert::{TryFromBlockResponse, TryFromReceiptResponse, TryFromTransactionResponse};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
@@ -1715,6 +1715,10 @@ where
     fn get_prune_checkpoints(&self) -> Result<Vec<(PruneSegment, PruneCheckpoint)>, ProviderError> {
         Err(ProviderError::UnsupportedProvider)
     }
+
+    fn get_prune_runs(&self) -> Result<Vec<(PruneSegment, PruneRun)>, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
 }
 
 impl<P, Node, N> StageCheckpointReader for RpcBlockchainStateProvider<P, Node, N>
@@ -3,7 +3,7 @@ use crate::{
     ReceiptProviderIdExt, TransactionVariant, TransactionsProvider,
 };
 use alloc::{sync::Arc, vec::Vec};
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use alloy_eips::{eip1898::BlockWithParent, BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{BlockNumber, B256};
 use core::ops::RangeInclusive;
 use reth_primitives_traits::{RecoveredBlock, SealedHeader};
@@ -392,3 +392,21 @@ pub trait ChainStateBlockWriter: Send + Sync {
     /// Saves the given safe block number in the DB.
     fn save_safe_block_number(&self, block_number: BlockNumber) -> ProviderResult<()>;
 }
+
+/// Functionality to read the blocks that were found to be invalid by the engine.
+pub trait InvalidBlockReader: Send + Sync {
+    /// Returns all invalid blocks stored in the DB.
+    fn invalid_blocks(&self) -> ProviderResult<Vec<BlockWithParent>>;
+}
+
+/// Functionality to write the blocks that were found to be invalid by the engine.
+pub trait InvalidBlockWriter: Send + Sync {
+    /// Saves the given invalid block in the DB.
+    ///
+    /// If this exceeds `max_blocks` stored invalid blocks, the blocks with the lowest block
+    /// numbers are removed.
+    fn save_invalid_block(&self, block: BlockWithParent, max_blocks: usize) -> ProviderResult<()>;
+
+    /// Removes all invalid blocks from the DB and returns the number of removed blocks.
+    fn clear_invalid_blocks(&self) -> ProviderResult<usize>;
+}
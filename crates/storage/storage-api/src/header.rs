@@ -56,6 +56,18 @@ pub trait HeaderProvider: Send + Sync {
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<Vec<Self::Header>>;
 
+    /// Get headers in range of block numbers, decoding them in bulk where supported.
+    ///
+    /// Returns the same headers as [`Self::headers_range`]. Providers backed by static files read
+    /// the whole range from the memory-mapped file at once instead of looking up every header
+    /// individually, which is considerably faster for large ranges.
+    fn headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Self::Header>> {
+        self.headers_range(range)
+    }
+
     /// Get a single sealed header by block number.
     fn sealed_header(
         &self,
@@ -70,6 +82,16 @@ pub trait HeaderProvider: Send + Sync {
         self.sealed_headers_while(range, |_| true)
     }
 
+    /// Get sealed headers in range of block numbers, decoding them in bulk where supported.
+    ///
+    /// See [`Self::headers_range_vectored`].
+    fn sealed_headers_range_vectored(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader<Self::Header>>> {
+        self.sealed_headers_range(range)
+    }
+
     /// Get sealed headers while `predicate` returns `true` or the range is exhausted.
     fn sealed_headers_while(
         &self,
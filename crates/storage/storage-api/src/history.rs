@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db_api::models::BlockNumberAddress;
@@ -53,6 +53,23 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: impl IntoIterator<Item = ((Address, B256), impl IntoIterator<Item = u64>)>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear the sender transactions index for all transactions in the given range.
+    ///
+    /// Senders are read from the `TransactionSenders` table, so this must be called before the
+    /// senders of the range are removed.
+    ///
+    /// Returns number of transactions walked.
+    fn unwind_sender_transactions_index_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert sender transaction index to database. Used inside `IndexSenderTransactions` stage
+    fn insert_sender_transactions_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = TxNumber>)>,
+    ) -> ProviderResult<()>;
+
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
@@ -0,0 +1,157 @@
+use alloy_primitives::BlockNumber;
+use core::{fmt, ops::RangeInclusive};
+use reth_prune_types::PruneSegment;
+
+/// A class of historical data that can be removed by pruning or history expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HistoryDataClass {
+    /// Block headers.
+    Headers,
+    /// Block bodies, including the transactions of the block.
+    Bodies,
+    /// Transaction receipts.
+    ///
+    /// Receipts that are pruned by a log filter are reported as available.
+    Receipts,
+    /// Account changesets, required for historical account state.
+    AccountChangeSets,
+    /// Storage changesets, required for historical storage state.
+    StorageChangeSets,
+}
+
+impl HistoryDataClass {
+    /// All data classes.
+    pub const ALL: [Self; 5] = [
+        Self::Headers,
+        Self::Bodies,
+        Self::Receipts,
+        Self::AccountChangeSets,
+        Self::StorageChangeSets,
+    ];
+
+    /// Returns the data class that is removed by pruning the given segment, if any.
+    pub const fn from_prune_segment(segment: PruneSegment) -> Option<Self> {
+        match segment {
+            PruneSegment::Headers => Some(Self::Headers),
+            PruneSegment::Transactions => Some(Self::Bodies),
+            PruneSegment::Receipts => Some(Self::Receipts),
+            PruneSegment::AccountHistory => Some(Self::AccountChangeSets),
+            PruneSegment::StorageHistory => Some(Self::StorageChangeSets),
+            _ => None,
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for HistoryDataClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Headers => f.write_str("Headers"),
+            Self::Bodies => f.write_str("Bodies"),
+            Self::Receipts => f.write_str("Receipts"),
+            Self::AccountChangeSets => f.write_str("AccountChangeSets"),
+            Self::StorageChangeSets => f.write_str("StorageChangeSets"),
+        }
+    }
+}
+
+/// The block ranges of historical data that are available, per [`HistoryDataClass`].
+///
+/// All data classes share the latest block of the node, and are available from their earliest
+/// block, which is raised by pruning and history expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryAvailability {
+    /// The latest block of the node.
+    latest: BlockNumber,
+    /// The earliest available block, indexed by [`HistoryDataClass`].
+    earliest: [BlockNumber; HistoryDataClass::ALL.len()],
+}
+
+impl HistoryAvailability {
+    /// Creates a new instance with all data available from genesis up to the given latest block.
+    pub const fn new(latest: BlockNumber) -> Self {
+        Self { latest, earliest: [0; HistoryDataClass::ALL.len()] }
+    }
+
+    /// Sets the latest block of the node.
+    pub const fn with_latest(mut self, latest: BlockNumber) -> Self {
+        self.latest = latest;
+        self
+    }
+
+    /// Marks the data below the given block as unavailable.
+    ///
+    /// The earliest available block is never lowered.
+    pub fn with_earliest(mut self, data: HistoryDataClass, earliest: BlockNumber) -> Self {
+        let current = &mut self.earliest[data.index()];
+        *current = (*current).max(earliest);
+        self
+    }
+
+    /// Returns the latest block of the node.
+    pub const fn latest(&self) -> BlockNumber {
+        self.latest
+    }
+
+    /// Returns the earliest available block of the data.
+    pub const fn earliest(&self, data: HistoryDataClass) -> BlockNumber {
+        self.earliest[data.index()]
+    }
+
+    /// Returns the available block range of the data, or `None` if no block is available.
+    pub fn range(&self, data: HistoryDataClass) -> Option<RangeInclusive<BlockNumber>> {
+        let earliest = self.earliest(data);
+        (earliest <= self.latest).then(|| earliest..=self.latest)
+    }
+
+    /// Returns `true` if the data of the given block is available.
+    pub fn is_available(&self, data: HistoryDataClass, block: BlockNumber) -> bool {
+        self.range(data).is_some_and(|range| range.contains(&block))
+    }
+
+    /// Returns `true` if the data of the given block existed, but was pruned or expired.
+    pub fn is_pruned(&self, data: HistoryDataClass, block: BlockNumber) -> bool {
+        block <= self.latest && block < self.earliest(data)
+    }
+
+    /// Returns the available block range of every data class.
+    pub fn ranges(
+        &self,
+    ) -> impl Iterator<Item = (HistoryDataClass, Option<RangeInclusive<BlockNumber>>)> + '_ {
+        HistoryDataClass::ALL.into_iter().map(|data| (data, self.range(data)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_availability_ranges() {
+        let availability = HistoryAvailability::new(100)
+            .with_earliest(HistoryDataClass::Receipts, 50)
+            .with_earliest(HistoryDataClass::Receipts, 10)
+            .with_earliest(HistoryDataClass::AccountChangeSets, 101);
+
+        assert_eq!(availability.range(HistoryDataClass::Headers), Some(0..=100));
+        assert_eq!(availability.range(HistoryDataClass::Receipts), Some(50..=100));
+        assert_eq!(availability.range(HistoryDataClass::AccountChangeSets), None);
+
+        assert!(availability.is_available(HistoryDataClass::Receipts, 50));
+        assert!(availability.is_pruned(HistoryDataClass::Receipts, 49));
+        assert!(!availability.is_available(HistoryDataClass::Receipts, 101));
+        assert!(!availability.is_pruned(HistoryDataClass::Receipts, 101));
+    }
+
+    #[test]
+    fn history_data_class_from_prune_segment() {
+        assert_eq!(
+            HistoryDataClass::from_prune_segment(PruneSegment::Transactions),
+            Some(HistoryDataClass::Bodies)
+        );
+        assert_eq!(HistoryDataClass::from_prune_segment(PruneSegment::SenderRecovery), None);
+    }
+}
@@ -33,6 +33,9 @@ pub use chain::*;
 mod header;
 pub use header::*;
 
+mod history_availability;
+pub use history_availability::*;
+
 mod prune_checkpoint;
 pub use prune_checkpoint::*;
 
@@ -1,5 +1,5 @@
 use alloc::vec::Vec;
-use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_prune_types::{PruneCheckpoint, PruneRun, PruneSegment};
 use reth_storage_errors::provider::ProviderResult;
 
 /// The trait for fetching prune checkpoint related data.
@@ -13,6 +13,13 @@ pub trait PruneCheckpointReader: Send + Sync {
 
     /// Fetch all the prune checkpoints.
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>>;
+
+    /// Fetch the statistics of the last pruner run of all segments.
+    ///
+    /// Returns no statistics by default, for providers that don't record them.
+    fn get_prune_runs(&self) -> ProviderResult<Vec<(PruneSegment, PruneRun)>> {
+        Ok(Vec::new())
+    }
 }
 
 /// The trait for updating prune checkpoint related data.
@@ -24,4 +31,11 @@ pub trait PruneCheckpointWriter: Send + Sync {
         segment: PruneSegment,
         checkpoint: PruneCheckpoint,
     ) -> ProviderResult<()>;
+
+    /// Save the statistics of the last pruner run of a segment.
+    ///
+    /// The statistics are discarded by default, for providers that don't record them.
+    fn save_prune_run(&self, _segment: PruneSegment, _run: PruneRun) -> ProviderResult<()> {
+        Ok(())
+    }
 }
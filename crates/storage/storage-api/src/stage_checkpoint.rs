@@ -1,7 +1,8 @@
+use crate::HistoryAvailability;
 use alloc::{string::String, vec::Vec};
 use alloy_primitives::BlockNumber;
 use reth_stages_types::{StageCheckpoint, StageId};
-use reth_storage_errors::provider::ProviderResult;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// The trait for fetching stage checkpoint related data.
 #[auto_impl::auto_impl(&, Arc)]
@@ -15,6 +16,15 @@ pub trait StageCheckpointReader: Send + Sync {
     /// Reads all stage checkpoints and returns a list with the name of the stage and the checkpoint
     /// data.
     fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>>;
+
+    /// Returns the block ranges that are available per data class, derived from the prune
+    /// checkpoints and the history that was expired from static files.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] by default, for providers that don't know
+    /// which history was removed.
+    fn history_availability(&self) -> ProviderResult<HistoryAvailability> {
+        Err(ProviderError::UnsupportedProvider)
+    }
 }
 
 /// The trait for updating stage checkpoint related data.
@@ -82,6 +82,24 @@ pub trait TransactionsProvider: BlockNumReader + Send + Sync {
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>>;
 }
 
+/// Client trait for looking up the transactions sent by an address.
+///
+/// This is backed by an optional index which is only maintained if the node was configured to do
+/// so, otherwise implementations return an empty list.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait SenderTransactionsProvider: Send + Sync {
+    /// Returns up to `limit` transaction ids sent by `sender` in ascending order.
+    ///
+    /// If `after` is set, only transaction ids strictly greater than it are returned, which allows
+    /// paging through the sender's history.
+    fn transaction_ids_by_sender(
+        &self,
+        sender: Address,
+        after: Option<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+}
+
 /// A helper type alias to access [`TransactionsProvider::Transaction`].
 pub type ProviderTx<P> = <P as TransactionsProvider>::Transaction;
 
@@ -41,6 +41,7 @@ use tracing_futures::Instrument;
 
 pub mod metrics;
 pub mod shutdown;
+pub mod supervisor;
 
 #[cfg(feature = "rayon")]
 pub mod pool;
@@ -450,6 +451,7 @@ impl TaskExecutor {
         F: Future<Output = ()> + Send + 'static,
     {
         let panicked_tasks_tx = self.task_events_tx.clone();
+        let panicked_critical_tasks_total = self.metrics.panicked_critical_tasks_total.clone();
         let on_shutdown = self.on_shutdown.clone();
 
         // wrap the task in catch unwind
@@ -458,6 +460,7 @@ impl TaskExecutor {
             .map_err(move |error| {
                 let task_error = PanickedTaskError::new(name, error);
                 error!("{task_error}");
+                panicked_critical_tasks_total.increment(1);
                 let _ = panicked_tasks_tx.send(TaskEvent::Panic(task_error));
             })
             .in_current_span();
@@ -509,6 +512,7 @@ impl TaskExecutor {
         F: Future<Output = ()> + Send + 'static,
     {
         let panicked_tasks_tx = self.task_events_tx.clone();
+        let panicked_critical_tasks_total = self.metrics.panicked_critical_tasks_total.clone();
         let on_shutdown = self.on_shutdown.clone();
         let fut = f(on_shutdown);
 
@@ -518,6 +522,7 @@ impl TaskExecutor {
             .map_err(move |error| {
                 let task_error = PanickedTaskError::new(name, error);
                 error!("{task_error}");
+                panicked_critical_tasks_total.increment(1);
                 let _ = panicked_tasks_tx.send(TaskEvent::Panic(task_error));
             })
             .map(drop)
@@ -555,6 +560,7 @@ impl TaskExecutor {
         F: Future<Output = ()> + Send + 'static,
     {
         let panicked_tasks_tx = self.task_events_tx.clone();
+        let panicked_critical_tasks_total = self.metrics.panicked_critical_tasks_total.clone();
         let on_shutdown = GracefulShutdown::new(
             self.on_shutdown.clone(),
             GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks)),
@@ -567,6 +573,7 @@ impl TaskExecutor {
             .map_err(move |error| {
                 let task_error = PanickedTaskError::new(name, error);
                 error!("{task_error}");
+                panicked_critical_tasks_total.increment(1);
                 let _ = panicked_tasks_tx.send(TaskEvent::Panic(task_error));
             })
             .map(drop)
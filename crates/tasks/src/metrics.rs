@@ -12,6 +12,8 @@ pub struct TaskExecutorMetrics {
     pub(crate) critical_tasks_total: Counter,
     /// Number of finished spawned critical tasks
     pub(crate) finished_critical_tasks_total: Counter,
+    /// Number of panicked critical tasks
+    pub(crate) panicked_critical_tasks_total: Counter,
     /// Number of spawned regular tasks
     pub(crate) regular_tasks_total: Counter,
     /// Number of finished spawned regular tasks
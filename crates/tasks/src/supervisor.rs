@@ -0,0 +1,205 @@
+//! Supervision of long-running subsystems that are driven in a loop.
+//!
+//! A [`Supervisor`] wraps every run of a subsystem, e.g. a single pruner run, and decides what
+//! happens if the run panics according to its [`SupervisionPolicy`].
+
+use reth_metrics::{metrics::Counter, Metrics};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+use tracing::error;
+
+/// What to do when a supervised subsystem panics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SupervisionPolicy {
+    /// Propagate the panic, shutting down the node.
+    #[default]
+    Shutdown,
+    /// Skip the failed run and retry after a backoff, see [`RestartPolicy`].
+    Restart(RestartPolicy),
+}
+
+/// Restart policy of a supervised subsystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// The backoff after the first panic. It is doubled on every consecutive panic.
+    pub initial_backoff: Duration,
+    /// The maximum backoff between two runs.
+    pub max_backoff: Duration,
+    /// The maximum number of restarts. Once exhausted, the next panic is propagated.
+    pub max_restarts: usize,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            max_restarts: 10,
+        }
+    }
+}
+
+/// Supervises the runs of a subsystem according to its [`SupervisionPolicy`].
+#[derive(Debug)]
+pub struct Supervisor {
+    /// Name of the supervised subsystem.
+    name: &'static str,
+    policy: SupervisionPolicy,
+    /// Number of restarts so far.
+    restarts: usize,
+    /// Number of panics since the last successful run.
+    consecutive_panics: u32,
+    /// Runs are skipped until this instant after a panic.
+    backoff_until: Option<Instant>,
+    metrics: SupervisorMetrics,
+}
+
+impl Supervisor {
+    /// Creates a new [`Supervisor`] for the subsystem with the given name.
+    pub fn new(name: &'static str, policy: SupervisionPolicy) -> Self {
+        Self {
+            name,
+            policy,
+            restarts: 0,
+            consecutive_panics: 0,
+            backoff_until: None,
+            metrics: SupervisorMetrics::new_with_labels(&[("subsystem", name)]),
+        }
+    }
+
+    /// Returns the supervision policy.
+    pub const fn policy(&self) -> SupervisionPolicy {
+        self.policy
+    }
+
+    /// Returns the number of restarts so far.
+    pub const fn restarts(&self) -> usize {
+        self.restarts
+    }
+
+    /// Returns `true` if the subsystem panicked and the backoff hasn't elapsed yet.
+    pub fn is_backing_off(&self) -> bool {
+        self.backoff_until.is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Runs the closure, catching a panic.
+    ///
+    /// Returns `None` if the subsystem is backing off after a previous panic, or if the closure
+    /// panicked and the subsystem is going to be restarted.
+    ///
+    /// # Panics
+    ///
+    /// Resumes the panic if the policy is [`SupervisionPolicy::Shutdown`], or if the restart
+    /// budget is exhausted.
+    pub fn run<R>(&mut self, f: impl FnOnce() -> R) -> Option<R> {
+        if self.is_backing_off() {
+            return None
+        }
+
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(result) => {
+                self.consecutive_panics = 0;
+                self.backoff_until = None;
+                Some(result)
+            }
+            Err(payload) => {
+                self.metrics.panics_total.increment(1);
+
+                let SupervisionPolicy::Restart(policy) = self.policy else {
+                    panic::resume_unwind(payload)
+                };
+                if self.restarts >= policy.max_restarts {
+                    error!(target: "tasks::supervisor", subsystem = self.name, restarts = self.restarts, "Subsystem panicked, restart budget exhausted");
+                    panic::resume_unwind(payload)
+                }
+
+                let backoff = policy
+                    .initial_backoff
+                    .saturating_mul(2u32.saturating_pow(self.consecutive_panics))
+                    .min(policy.max_backoff);
+                self.restarts += 1;
+                self.consecutive_panics = self.consecutive_panics.saturating_add(1);
+                self.backoff_until = Some(Instant::now() + backoff);
+                self.metrics.restarts_total.increment(1);
+
+                error!(target: "tasks::supervisor", subsystem = self.name, error = ?panic_message(&*payload), restarts = self.restarts, max_restarts = policy.max_restarts, ?backoff, "Subsystem panicked, restarting after backoff");
+                None
+            }
+        }
+    }
+}
+
+/// Returns the message of a panic payload, if it's a string.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> Option<&str> {
+    payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+}
+
+/// Metrics of a supervised subsystem.
+#[derive(Metrics)]
+#[metrics(scope = "supervisor")]
+struct SupervisorMetrics {
+    /// Number of panics of the subsystem
+    panics_total: Counter,
+    /// Number of restarts of the subsystem after a panic
+    restarts_total: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restart_policy(max_restarts: usize) -> SupervisionPolicy {
+        SupervisionPolicy::Restart(RestartPolicy {
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            max_restarts,
+        })
+    }
+
+    #[test]
+    fn restarts_until_budget_exhausted() {
+        let mut supervisor = Supervisor::new("test", restart_policy(2));
+
+        assert_eq!(supervisor.run(|| 1), Some(1));
+        assert_eq!(supervisor.run(|| -> u64 { panic!("intentionally panic") }), None);
+        assert_eq!(supervisor.run(|| -> u64 { panic!("intentionally panic") }), None);
+        assert_eq!(supervisor.restarts(), 2);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            supervisor.run(|| -> u64 { panic!("intentionally panic") })
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn backs_off_after_panic() {
+        let mut supervisor = Supervisor::new(
+            "test",
+            SupervisionPolicy::Restart(RestartPolicy {
+                initial_backoff: Duration::from_secs(60),
+                ..Default::default()
+            }),
+        );
+
+        assert_eq!(supervisor.run(|| -> u64 { panic!("intentionally panic") }), None);
+        assert!(supervisor.is_backing_off());
+        // The closure is not run while backing off
+        assert_eq!(supervisor.run(|| unreachable!()), None::<u64>);
+    }
+
+    #[test]
+    fn shutdown_propagates_panic() {
+        let mut supervisor = Supervisor::new("test", SupervisionPolicy::Shutdown);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            supervisor.run(|| -> u64 { panic!("intentionally panic") })
+        }));
+        assert!(result.is_err());
+        assert_eq!(supervisor.restarts(), 0);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
-    pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
+    pool::{
+        NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE, POOL_LISTENER_BUFFER_SIZE,
+    },
+    validate::ValidationBudgetConfig,
     PoolSize, TransactionOrigin,
 };
 use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
@@ -61,10 +64,21 @@ pub struct PoolConfig {
     pub pending_tx_listener_buffer_size: usize,
     /// Bound on number of new transactions from `reth_network::TransactionsManager` to buffer.
     pub new_tx_listener_buffer_size: usize,
+    /// Bound on number of events to buffer for each
+    /// [`PoolListener`](crate::PoolListener) before it lags.
+    pub pool_listener_buffer_size: usize,
     /// How many new pending transactions to buffer and send iterators in progress.
     pub max_new_pending_txs_notifications: usize,
     /// Maximum lifetime for transactions in the pool
     pub max_queued_lifetime: Duration,
+    /// Validation cost budget for transactions received over p2p, `None` disables the budget.
+    ///
+    /// See [`validation_cost`](crate::validate::validation_cost).
+    pub p2p_validation_budget: Option<ValidationBudgetConfig>,
+    /// Validation cost budget for transactions submitted over RPC, `None` disables the budget.
+    ///
+    /// See [`validation_cost`](crate::validate::validation_cost).
+    pub rpc_validation_budget: Option<ValidationBudgetConfig>,
 }
 
 impl PoolConfig {
@@ -94,8 +108,11 @@ impl Default for PoolConfig {
             local_transactions_config: Default::default(),
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
+            pool_listener_buffer_size: POOL_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            p2p_validation_budget: None,
+            rpc_validation_budget: None,
         }
     }
 }
@@ -66,6 +66,11 @@ pub enum PoolErrorKind {
     /// Thrown if the mutual exclusivity constraint (blob vs normal transaction) is violated.
     #[error("transaction type {1} conflicts with existing transaction for {0}")]
     ExistingConflictingTransactionType(Address, u8),
+    /// Thrown if the validation budget of the transaction's source is exhausted.
+    ///
+    /// See [`validation_cost`](crate::validate::validation_cost).
+    #[error("transaction validation budget exceeded")]
+    ValidationBudgetExceeded,
     /// Any other error that occurred while inserting/validating a transaction. e.g. IO database
     /// error
     #[error(transparent)]
@@ -130,6 +135,11 @@ impl PoolError {
                 // valid tx but dropped due to size constraints
                 false
             }
+            PoolErrorKind::ValidationBudgetExceeded => {
+                // the tx was never validated, because too many transactions were received from
+                // the same source
+                false
+            }
             PoolErrorKind::InvalidTransaction(err) => {
                 // transaction rejected because it violates constraints
                 err.is_bad_transaction()
@@ -282,8 +282,8 @@ pub use crate::{
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AddedTransactionOutcome,
-        AllTransactionsEvents, FullTransactionEvent, NewTransactionEvent, TransactionEvent,
-        TransactionEvents, TransactionListenerKind,
+        AllTransactionsEvents, DropReason, FullTransactionEvent, NewTransactionEvent, PoolListener,
+        PoolListenerEvent, TransactionEvent, TransactionEvents, TransactionListenerKind,
     },
     traits::*,
     validate::{
@@ -479,6 +479,7 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TransactionEvents> {
+        self.pool.consume_validation_budget(origin, &transaction)?;
         let (_, tx) = self.validate(origin, transaction).await;
         self.pool.add_transaction_and_subscribe(origin, tx)
     }
@@ -488,6 +489,7 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<AddedTransactionOutcome> {
+        self.pool.consume_validation_budget(origin, &transaction)?;
         let (_, tx) = self.validate(origin, transaction).await;
         let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
         results.pop().expect("result length is the same as the input")
@@ -501,8 +503,47 @@ where
         if transactions.is_empty() {
             return Vec::new()
         }
-        let validated = self.validate_all(origin, transactions).await;
 
+        // reject transactions exceeding the validation budget up front, `None` marks the
+        // transactions that are validated
+        let mut results = Vec::with_capacity(transactions.len());
+        let mut admitted = Vec::with_capacity(transactions.len());
+        for tx in transactions {
+            match self.pool.consume_validation_budget(origin, &tx) {
+                Ok(()) => {
+                    results.push(None);
+                    admitted.push(tx);
+                }
+                Err(err) => results.push(Some(Err(err))),
+            }
+        }
+        if admitted.is_empty() {
+            return results.into_iter().flatten().collect()
+        }
+
+        let validated = self.validate_all(origin, admitted).await;
+        let mut added =
+            self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx)).into_iter();
+
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    added.next().expect("result length is the same as the input")
+                })
+            })
+            .collect()
+    }
+
+    async fn reinject_transactions(
+        &self,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<AddedTransactionOutcome>> {
+        if transactions.is_empty() {
+            return Vec::new()
+        }
+        let validated = self.validate_all(origin, transactions).await;
         self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
     }
 
@@ -514,6 +555,14 @@ where
         self.pool.add_all_transactions_event_listener()
     }
 
+    fn pool_listener(&self) -> PoolListener<Self::Transaction> {
+        self.pool.add_pool_listener(self.pool.config().pool_listener_buffer_size)
+    }
+
+    fn pool_listener_with_capacity(&self, capacity: usize) -> PoolListener<Self::Transaction> {
+        self.pool.add_pool_listener(capacity)
+    }
+
     fn pending_transactions_listener_for(&self, kind: TransactionListenerKind) -> Receiver<TxHash> {
         self.pool.add_pending_listener(kind)
     }
@@ -635,6 +684,10 @@ where
         self.inner().get(tx_hash)
     }
 
+    fn transaction_status_detail(&self, tx_hash: &TxHash) -> Option<TransactionStatusDetail> {
+        self.inner().transaction_status_detail(tx_hash)
+    }
+
     fn get_all(&self, txs: Vec<TxHash>) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
         self.inner().get_all(txs)
     }
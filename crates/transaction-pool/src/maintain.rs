@@ -5,7 +5,7 @@ use crate::{
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction, PoolUpdateKind,
+    BlockInfo, PoolTransaction, PoolUpdateKind, TransactionOrigin,
 };
 use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::BlockNumberOrTag;
@@ -63,6 +63,12 @@ pub struct MaintainPoolConfig {
     ///   - no price exemptions
     ///   - no eviction exemptions
     pub no_local_exemptions: bool,
+
+    /// Whether transactions of blocks that were reorged out of the canonical chain, and are not
+    /// part of the new chain, are re-injected into the pool.
+    ///
+    /// Default: true
+    pub reinject_reorged_transactions: bool,
 }
 
 impl Default for MaintainPoolConfig {
@@ -72,6 +78,7 @@ impl Default for MaintainPoolConfig {
             max_reload_accounts: 100,
             max_tx_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
             no_local_exemptions: false,
+            reinject_reorged_transactions: true,
         }
     }
 }
@@ -416,8 +423,12 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 // Note: we no longer know if the tx was local or external
                 // Because the transactions are not finalized, the corresponding blobs are still in
                 // blob store (if we previously received them from the network)
-                metrics.inc_reinserted_transactions(pruned_old_transactions.len());
-                let _ = pool.add_external_transactions(pruned_old_transactions).await;
+                if config.reinject_reorged_transactions {
+                    metrics.inc_reinserted_transactions(pruned_old_transactions.len());
+                    let _ = pool
+                        .reinject_transactions(TransactionOrigin::External, pruned_old_transactions)
+                        .await;
+                }
 
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
@@ -634,7 +645,7 @@ where
         })
         .collect();
 
-    let outcome = pool.add_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
+    let outcome = pool.reinject_transactions(TransactionOrigin::Local, pool_transactions).await;
 
     info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted local transactions from file");
     reth_fs_util::remove_file(file_path)?;
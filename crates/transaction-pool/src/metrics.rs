@@ -139,4 +139,7 @@ pub struct AllTransactionsMetrics {
 pub struct TxPoolValidationMetrics {
     /// How long to successfully validate a blob
     pub(crate) blob_validation_duration: Histogram,
+    /// Number of transactions rejected because the validation budget of their source was
+    /// exhausted
+    pub(crate) validation_budget_exceeded: Counter,
 }
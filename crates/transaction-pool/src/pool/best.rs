@@ -6,14 +6,20 @@ use crate::{
 };
 use alloy_consensus::Transaction;
 use alloy_eips::Typed2718;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use core::fmt;
+use futures_util::{ready, Stream};
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 use std::{
-    collections::{BTreeMap, BTreeSet, HashSet, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    pin::Pin,
     sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::{
+    broadcast::{error::TryRecvError, Receiver},
+    mpsc,
 };
-use tokio::sync::broadcast::{error::TryRecvError, Receiver};
 use tracing::debug;
 
 /// An iterator that returns transactions that can be executed on the current state (*best*
@@ -90,12 +96,15 @@ pub struct BestTransactions<T: TransactionOrdering> {
     pub(crate) independent: BTreeSet<PendingTransaction<T>>,
     /// There might be the case where a yielded transactions is invalid, this will track it.
     pub(crate) invalid: HashSet<SenderId>,
-    /// Used to receive any new pending transactions that have been added to the pool after this
-    /// iterator was static filtered
+    /// The highest nonce of every sender that was already removed from this iterator.
+    ///
+    /// Used to ignore replacements of transactions that were already yielded.
+    pub(crate) yielded_nonces: HashMap<SenderId, u64>,
+    /// Used to receive any changes of the pending pool after this iterator was static filtered:
+    /// new pending transactions, replacements and removals.
     ///
-    /// These new pending transactions are inserted into this iterator's pool before yielding the
-    /// next value
-    pub(crate) new_transaction_receiver: Option<Receiver<PendingTransaction<T>>>,
+    /// These updates are applied to this iterator's pool before yielding the next value
+    pub(crate) new_transaction_receiver: Option<Receiver<PendingPoolUpdate<T>>>,
     /// Flag to control whether to skip blob transactions (EIP4844).
     pub(crate) skip_blobs: bool,
 }
@@ -118,8 +127,8 @@ impl<T: TransactionOrdering> BestTransactions<T> {
         self.all.get(&id.unchecked_ancestor()?)
     }
 
-    /// Non-blocking read on the new pending transactions subscription channel
-    fn try_recv(&mut self) -> Option<PendingTransaction<T>> {
+    /// Non-blocking read on the pending pool updates subscription channel
+    fn try_recv(&mut self) -> Option<PendingPoolUpdate<T>> {
         loop {
             match self.new_transaction_receiver.as_mut()?.try_recv() {
                 Ok(tx) => return Some(tx),
@@ -144,20 +153,76 @@ impl<T: TransactionOrdering> BestTransactions<T> {
     /// set.
     fn pop_best(&mut self) -> Option<PendingTransaction<T>> {
         self.independent.pop_last().inspect(|best| {
-            self.all.remove(best.transaction.id());
+            let id = best.transaction.id();
+            self.all.remove(id);
+            self.yielded_nonces.insert(id.sender, id.nonce);
         })
     }
 
-    /// Checks for new transactions that have come into the `PendingPool` after this iterator was
-    /// created and inserts them
+    /// Checks for changes of the `PendingPool` after this iterator was created and applies them:
+    /// new transactions are inserted and replaced or removed transactions are dropped.
     fn add_new_transactions(&mut self) {
-        while let Some(pending_tx) = self.try_recv() {
-            //  same logic as PendingPool::add_transaction/PendingPool::best_with_unlocked
-            let tx_id = *pending_tx.transaction.id();
-            if self.ancestor(&tx_id).is_none() {
-                self.independent.insert(pending_tx.clone());
+        while let Some(update) = self.try_recv() {
+            match update {
+                PendingPoolUpdate::Added(pending_tx) => self.add_transaction(pending_tx),
+                PendingPoolUpdate::Removed(tx_id) => self.remove_transaction(&tx_id),
+            }
+        }
+    }
+
+    /// Inserts a transaction that was added to the `PendingPool`, replacing the transaction with
+    /// the same id.
+    fn add_transaction(&mut self, pending_tx: PendingTransaction<T>) {
+        let tx_id = *pending_tx.transaction.id();
+        if self.yielded_nonces.get(&tx_id.sender).is_some_and(|nonce| *nonce >= tx_id.nonce) {
+            // the replaced transaction was already yielded
+            return
+        }
+
+        if let Some(replaced) = self.all.insert(tx_id, pending_tx.clone()) {
+            self.independent.remove(&replaced);
+        }
+
+        //  same logic as PendingPool::add_transaction/PendingPool::best_with_unlocked
+        if self.ancestor(&tx_id).is_none() {
+            // the descendant could have been unlocked by the removal of the replaced transaction
+            if let Some(descendant) = self.all.get(&tx_id.descendant()) {
+                self.independent.remove(descendant);
+            }
+            self.independent.insert(pending_tx);
+        }
+    }
+
+    /// Removes a transaction that was removed from the `PendingPool`, unlocking its descendant.
+    fn remove_transaction(&mut self, tx_id: &TransactionId) {
+        let Some(removed) = self.all.remove(tx_id) else { return };
+
+        // same logic as PendingPool::remove_transaction
+        if self.independent.remove(&removed) {
+            if let Some(unlocked) = self.all.get(&tx_id.descendant()) {
+                self.independent.insert(unlocked.clone());
             }
-            self.all.insert(tx_id, pending_tx);
+        }
+    }
+}
+
+/// A change of the [`PendingPool`](crate::pool::pending::PendingPool) that is sent to the
+/// [`BestTransactions`] iterators created before.
+#[derive(Debug)]
+pub(crate) enum PendingPoolUpdate<T: TransactionOrdering> {
+    /// A transaction was added to the pool, either a new one or the replacement of the
+    /// transaction with the same id.
+    Added(PendingTransaction<T>),
+    /// The transaction with the given id was removed from the pool, e.g. because it was mined,
+    /// replaced or no longer satisfies the base fee.
+    Removed(TransactionId),
+}
+
+impl<T: TransactionOrdering> Clone for PendingPoolUpdate<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Added(tx) => Self::Added(tx.clone()),
+            Self::Removed(id) => Self::Removed(*id),
         }
     }
 }
@@ -369,6 +434,73 @@ where
     }
 }
 
+/// A [`Stream`] of the best transactions for long-running block builders.
+///
+/// This wraps a [`BestTransactions`](crate::traits::BestTransactions) iterator that receives live
+/// updates of the pending pool, so new better transactions, replacements and removals are
+/// reflected in the yielded ordering without re-creating the iterator. Once the iterator is
+/// exhausted, the stream waits for new pending transactions instead of terminating.
+///
+/// The stream only terminates if the pool is dropped.
+pub struct BestTransactionsStream<T: PoolTransaction> {
+    best: Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>,
+    pending: mpsc::Receiver<TxHash>,
+}
+
+impl<T: PoolTransaction> BestTransactionsStream<T> {
+    /// Creates a new [`BestTransactionsStream`] from the best transactions iterator and a listener
+    /// for new pending transactions that wakes the stream up.
+    pub fn new(
+        best: Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>,
+        pending: mpsc::Receiver<TxHash>,
+    ) -> Self {
+        Self { best, pending }
+    }
+
+    /// Marks the transaction as invalid, so that it's not yielded again along with all
+    /// transactions that depend on it.
+    ///
+    /// See [`BestTransactions::mark_invalid`](crate::traits::BestTransactions::mark_invalid).
+    pub fn mark_invalid(
+        &mut self,
+        tx: &Arc<ValidPoolTransaction<T>>,
+        kind: InvalidPoolTransactionError,
+    ) {
+        self.best.mark_invalid(tx, kind)
+    }
+
+    /// Controls whether the stream skips blob transactions.
+    pub fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        self.best.set_skip_blobs(skip_blobs)
+    }
+}
+
+impl<T: PoolTransaction> Stream for BestTransactionsStream<T> {
+    type Item = Arc<ValidPoolTransaction<T>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(tx) = this.best.next() {
+                return Poll::Ready(Some(tx))
+            }
+
+            // the iterator receives the new pending transactions itself, the listener is only used
+            // to get notified about them
+            if ready!(this.pending.poll_recv(cx)).is_none() {
+                return Poll::Ready(None)
+            }
+            while this.pending.try_recv().is_ok() {}
+        }
+    }
+}
+
+impl<T: PoolTransaction> fmt::Debug for BestTransactionsStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BestTransactionsStream").finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -378,6 +510,7 @@ mod tests {
         BestTransactions, Priority,
     };
     use alloy_primitives::U256;
+    use futures_util::{FutureExt, StreamExt};
 
     #[test]
     fn test_best_iter() {
@@ -637,7 +770,7 @@ mod tests {
 
         // Use a broadcast channel for transaction updates
         let (tx_sender, tx_receiver) =
-            tokio::sync::broadcast::channel::<PendingTransaction<MockOrdering>>(1000);
+            tokio::sync::broadcast::channel::<PendingPoolUpdate<MockOrdering>>(1000);
         best.new_transaction_receiver = Some(tx_receiver);
 
         // Create a new transaction with nonce 5 and validate it
@@ -650,7 +783,7 @@ mod tests {
             transaction: Arc::new(valid_new_tx.clone()),
             priority: Priority::Value(U256::from(1000)),
         };
-        tx_sender.send(pending_tx.clone()).unwrap();
+        tx_sender.send(PendingPoolUpdate::Added(pending_tx.clone())).unwrap();
 
         // Add new transactions to the iterator
         best.add_new_transactions();
@@ -684,7 +817,7 @@ mod tests {
 
         // Use a broadcast channel for transaction updates
         let (tx_sender, tx_receiver) =
-            tokio::sync::broadcast::channel::<PendingTransaction<MockOrdering>>(1000);
+            tokio::sync::broadcast::channel::<PendingPoolUpdate<MockOrdering>>(1000);
         best.new_transaction_receiver = Some(tx_receiver);
 
         // Create a new transaction with nonce 5 and validate it
@@ -697,7 +830,7 @@ mod tests {
             transaction: Arc::new(valid_new_tx1.clone()),
             priority: Priority::Value(U256::from(1000)),
         };
-        tx_sender.send(pending_tx1.clone()).unwrap();
+        tx_sender.send(PendingPoolUpdate::Added(pending_tx1.clone())).unwrap();
 
         // Add new transactions to the iterator
         best.add_new_transactions();
@@ -720,7 +853,7 @@ mod tests {
             transaction: Arc::new(valid_new_tx2.clone()),
             priority: Priority::Value(U256::from(1000)),
         };
-        tx_sender.send(pending_tx2.clone()).unwrap();
+        tx_sender.send(PendingPoolUpdate::Added(pending_tx2.clone())).unwrap();
 
         // Add new transactions to the iterator
         best.add_new_transactions();
@@ -918,7 +1051,7 @@ mod tests {
 
         // Use a broadcast channel for transaction updates
         let (_tx_sender, tx_receiver) =
-            tokio::sync::broadcast::channel::<PendingTransaction<MockOrdering>>(1000);
+            tokio::sync::broadcast::channel::<PendingPoolUpdate<MockOrdering>>(1000);
         best.new_transaction_receiver = Some(tx_receiver);
 
         // Ensure receiver is set
@@ -931,5 +1064,103 @@ mod tests {
         assert!(best.new_transaction_receiver.is_none());
     }
 
-    // TODO: Same nonce test
+    #[test]
+    fn test_best_transactions_replacement() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let tx = MockTransaction::eip1559();
+        for nonce in 0..2 {
+            let valid_tx = f.validated(tx.clone().rng_hash().with_nonce(nonce));
+            pool.add_transaction(Arc::new(valid_tx), 0);
+        }
+
+        let mut best = pool.best();
+
+        // Replace the first transaction, the same way the pool does it
+        let replacement = Arc::new(f.validated(tx.inc_price().rng_hash().with_nonce(0)));
+        pool.remove_transaction(replacement.id());
+        pool.add_transaction(replacement.clone(), 0);
+
+        // Only the replacement and its descendant are yielded, in nonce order
+        assert_eq!(best.next().unwrap().hash(), replacement.hash());
+        assert_eq!(best.next().unwrap().nonce(), 1);
+        assert!(best.next().is_none());
+    }
+
+    #[test]
+    fn test_best_transactions_replacement_after_yield() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let tx = MockTransaction::eip1559();
+        for nonce in 0..2 {
+            let valid_tx = f.validated(tx.clone().rng_hash().with_nonce(nonce));
+            pool.add_transaction(Arc::new(valid_tx), 0);
+        }
+
+        let mut best = pool.best();
+        assert_eq!(best.next().unwrap().nonce(), 0);
+
+        // Replace the already yielded transaction
+        let replacement = Arc::new(f.validated(tx.inc_price().rng_hash().with_nonce(0)));
+        pool.remove_transaction(replacement.id());
+        pool.add_transaction(replacement, 0);
+
+        // The replacement is ignored
+        assert_eq!(best.next().unwrap().nonce(), 1);
+        assert!(best.next().is_none());
+    }
+
+    #[test]
+    fn test_best_transactions_removal() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let tx = MockTransaction::eip1559();
+        let mut ids = Vec::new();
+        for nonce in 0..3 {
+            let valid_tx = f.validated(tx.clone().rng_hash().with_nonce(nonce));
+            ids.push(*valid_tx.id());
+            pool.add_transaction(Arc::new(valid_tx), 0);
+        }
+
+        let mut best = pool.best();
+
+        // Remove the first transaction, e.g. because it was mined, and the last one
+        pool.remove_transaction(&ids[0]);
+        pool.remove_transaction(&ids[2]);
+
+        // The removal of the first transaction unlocks the second one
+        assert_eq!(best.next().unwrap().nonce(), 1);
+        assert!(best.next().is_none());
+    }
+
+    #[test]
+    fn test_best_transactions_stream() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let tx = MockTransaction::eip1559();
+        let valid_tx = f.validated(tx.clone().rng_hash().with_nonce(0));
+        pool.add_transaction(Arc::new(valid_tx), 0);
+
+        let (pending_tx, pending_rx) = mpsc::channel(10);
+        let mut stream = BestTransactionsStream::new(Box::new(pool.best()), pending_rx);
+
+        assert_eq!(stream.next().now_or_never().unwrap().unwrap().nonce(), 0);
+        // The stream waits for new pending transactions instead of terminating
+        assert!(stream.next().now_or_never().is_none());
+
+        let valid_tx = f.validated(tx.rng_hash().with_nonce(1));
+        let hash = *valid_tx.hash();
+        pool.add_transaction(Arc::new(valid_tx), 0);
+        pending_tx.try_send(hash).unwrap();
+
+        assert_eq!(stream.next().now_or_never().unwrap().unwrap().nonce(), 1);
+
+        // The stream terminates once the pool is dropped
+        drop(pending_tx);
+        assert!(stream.next().now_or_never().unwrap().is_none());
+    }
 }
@@ -84,6 +84,96 @@ impl TransactionEvent {
     }
 }
 
+/// An event emitted to a [`PoolListener`](crate::PoolListener).
+///
+/// Every transaction that enters the pool is announced with [`PoolListenerEvent::Added`] and
+/// leaves the pool with exactly one of [`PoolListenerEvent::Replaced`],
+/// [`PoolListenerEvent::Mined`], [`PoolListenerEvent::Removed`] or
+/// [`PoolListenerEvent::Dropped`], unless the listener lagged behind.
+#[derive(Debug)]
+pub enum PoolListenerEvent<T: PoolTransaction> {
+    /// Transaction has been added to the given subpool.
+    Added {
+        /// The added transaction.
+        transaction: Arc<ValidPoolTransaction<T>>,
+        /// The subpool the transaction was added to.
+        subpool: SubPool,
+    },
+    /// A queued transaction has been promoted to the pending subpool.
+    Promoted(TxHash),
+    /// Transaction has been replaced by the transaction belonging to the hash.
+    ///
+    /// E.g. same (sender + nonce) pair
+    Replaced {
+        /// The transaction that was replaced.
+        transaction: Arc<ValidPoolTransaction<T>>,
+        /// The transaction that replaced the event subject.
+        replaced_by: TxHash,
+    },
+    /// Transaction has been included in the block belonging to this hash.
+    Mined {
+        /// The hash of the mined transaction.
+        tx_hash: TxHash,
+        /// The hash of the mined block that contains the transaction.
+        block_hash: B256,
+    },
+    /// Transaction was removed on request, e.g. via
+    /// [`TransactionPool::remove_transactions`](crate::TransactionPool::remove_transactions).
+    Removed(TxHash),
+    /// Transaction was dropped by the pool.
+    Dropped {
+        /// The hash of the dropped transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was dropped.
+        reason: DropReason,
+    },
+    /// The listener didn't keep up with the pool and the given number of events were not
+    /// delivered.
+    ///
+    /// This is emitted before the next event that is delivered after the missed events.
+    Lagged {
+        /// The number of events that were missed.
+        missed: u64,
+    },
+}
+
+impl<T: PoolTransaction> Clone for PoolListenerEvent<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Added { transaction, subpool } => {
+                Self::Added { transaction: Arc::clone(transaction), subpool: *subpool }
+            }
+            Self::Promoted(hash) => Self::Promoted(*hash),
+            Self::Replaced { transaction, replaced_by } => {
+                Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
+            }
+            Self::Mined { tx_hash, block_hash } => {
+                Self::Mined { tx_hash: *tx_hash, block_hash: *block_hash }
+            }
+            Self::Removed(hash) => Self::Removed(*hash),
+            Self::Dropped { tx_hash, reason } => {
+                Self::Dropped { tx_hash: *tx_hash, reason: *reason }
+            }
+            Self::Lagged { missed } => Self::Lagged { missed: *missed },
+        }
+    }
+}
+
+/// The reason a transaction was dropped by the pool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DropReason {
+    /// Transaction was evicted to enforce the configured size limits of the pool.
+    SizeLimit,
+    /// Transaction can no longer be included after a state change of its sender, e.g. because
+    /// its nonce is outdated.
+    StateChange,
+    /// Transaction became invalid indefinitely.
+    Invalid,
+    /// Validating the transaction failed with an error.
+    ValidationError,
+}
+
 /// Represents a new transaction
 #[derive(Debug)]
 pub struct NewTransactionEvent<T: PoolTransaction> {
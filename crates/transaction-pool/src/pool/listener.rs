@@ -1,7 +1,13 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent},
+    pool::{
+        events::{
+            DropReason, FullTransactionEvent, NewTransactionEvent, PoolListenerEvent,
+            TransactionEvent,
+        },
+        state::SubPool,
+    },
     traits::{NewBlobSidecar, PropagateKind},
     PoolTransaction, ValidPoolTransaction,
 };
@@ -17,6 +23,7 @@ use tokio::sync::mpsc::{
     self as mpsc, error::TrySendError, Receiver, Sender, UnboundedReceiver, UnboundedSender,
 };
 use tracing::debug;
+
 /// The size of the event channel used to propagate transaction events.
 const TX_POOL_EVENT_CHANNEL_SIZE: usize = 1024;
 
@@ -68,6 +75,43 @@ impl<T: PoolTransaction> Stream for AllTransactionsEvents<T> {
     }
 }
 
+/// A Stream that receives [`PoolListenerEvent`]s for _all_ transactions.
+///
+/// Events are buffered in a bounded channel. If the listener doesn't keep up with the pool and the
+/// buffer is full, new events are skipped and a [`PoolListenerEvent::Lagged`] event with the
+/// number of skipped events is emitted once there's capacity again. The pool is never blocked by
+/// a slow listener.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct PoolListener<T: PoolTransaction> {
+    events: Receiver<PoolListenerEvent<T>>,
+}
+
+impl<T: PoolTransaction> PoolListener<T> {
+    /// Create a new instance of this stream.
+    pub const fn new(events: Receiver<PoolListenerEvent<T>>) -> Self {
+        Self { events }
+    }
+
+    /// Returns the number of events that are buffered and not yet received.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no events are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl<T: PoolTransaction> Stream for PoolListener<T> {
+    type Item = PoolListenerEvent<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().events.poll_recv(cx)
+    }
+}
+
 /// A type that broadcasts [`TransactionEvent`] to installed listeners.
 ///
 /// This is essentially a multi-producer, multi-consumer channel where each event is broadcast to
@@ -78,6 +122,8 @@ pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
+    /// All [`PoolListener`]s.
+    pool_listeners: Vec<PoolListenerSender<T>>,
 }
 
 impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
@@ -85,6 +131,7 @@ impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
+            pool_listeners: Vec::new(),
         }
     }
 }
@@ -125,6 +172,22 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         TransactionEvents { hash: tx_hash, events: rx }
     }
 
+    /// Broadcasts the event to all [`PoolListener`]s.
+    fn broadcast_pool_listener_event(&mut self, event: impl FnOnce() -> PoolListenerEvent<T>) {
+        if self.pool_listeners.is_empty() {
+            return
+        }
+        let event = event();
+        self.pool_listeners.retain_mut(|listener| listener.send(event.clone()))
+    }
+
+    /// Create a new [`PoolListener`] that buffers up to `capacity` events.
+    pub(crate) fn subscribe_pool_listener(&mut self, capacity: usize) -> PoolListener<T> {
+        let (sender, rx) = tokio::sync::mpsc::channel(capacity.max(1));
+        self.pool_listeners.push(PoolListenerSender { sender, missed: 0 });
+        PoolListener::new(rx)
+    }
+
     /// Create a new subscription for all transactions.
     pub(crate) fn subscribe_all(&mut self) -> AllTransactionsEvents<T> {
         let (tx, rx) = tokio::sync::mpsc::channel(TX_POOL_EVENT_CHANNEL_SIZE);
@@ -142,6 +205,20 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         }
     }
 
+    /// Notify listeners about a transaction that was added to the given subpool.
+    pub(crate) fn added(&mut self, tx: &Arc<ValidPoolTransaction<T>>, subpool: SubPool) {
+        self.broadcast_pool_listener_event(|| PoolListenerEvent::Added {
+            transaction: Arc::clone(tx),
+            subpool,
+        });
+    }
+
+    /// Notify listeners about a transaction that was promoted to the pending queue.
+    pub(crate) fn promoted(&mut self, tx: &TxHash) {
+        self.broadcast_event(tx, TransactionEvent::Pending, FullTransactionEvent::Pending(*tx));
+        self.broadcast_pool_listener_event(|| PoolListenerEvent::Promoted(*tx));
+    }
+
     /// Notify listeners about a transaction that was replaced.
     pub(crate) fn replaced(&mut self, tx: Arc<ValidPoolTransaction<T>>, replaced_by: TxHash) {
         let transaction = Arc::clone(&tx);
@@ -150,6 +227,10 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             TransactionEvent::Replaced(replaced_by),
             FullTransactionEvent::Replaced { transaction, replaced_by },
         );
+        self.broadcast_pool_listener_event(|| PoolListenerEvent::Replaced {
+            transaction: tx,
+            replaced_by,
+        });
     }
 
     /// Notify listeners about a transaction that was added to the queued pool.
@@ -168,13 +249,24 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DropReason) {
         self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+        self.broadcast_pool_listener_event(|| PoolListenerEvent::Dropped { tx_hash: *tx, reason });
+    }
+
+    /// Notify listeners about a transaction that was removed on request.
+    pub(crate) fn removed(&mut self, tx: &TxHash) {
+        self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+        self.broadcast_pool_listener_event(|| PoolListenerEvent::Removed(*tx));
     }
 
     /// Notify listeners about a transaction that was invalid.
     pub(crate) fn invalid(&mut self, tx: &TxHash) {
         self.broadcast_event(tx, TransactionEvent::Invalid, FullTransactionEvent::Invalid(*tx));
+        self.broadcast_pool_listener_event(|| PoolListenerEvent::Dropped {
+            tx_hash: *tx,
+            reason: DropReason::Invalid,
+        });
     }
 
     /// Notify listeners that the transaction was mined
@@ -184,6 +276,10 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             TransactionEvent::Mined(block_hash),
             FullTransactionEvent::Mined { tx_hash: *tx, block_hash },
         );
+        self.broadcast_pool_listener_event(|| PoolListenerEvent::Mined {
+            tx_hash: *tx,
+            block_hash,
+        });
     }
 }
 
@@ -233,6 +329,44 @@ impl PoolEventBroadcaster {
     }
 }
 
+/// Sender half of a [`PoolListener`].
+#[derive(Debug)]
+struct PoolListenerSender<T: PoolTransaction> {
+    sender: Sender<PoolListenerEvent<T>>,
+    /// Number of events that were skipped because the channel was full.
+    missed: u64,
+}
+
+impl<T: PoolTransaction> PoolListenerSender<T> {
+    /// Attempts to send the event to the listener, after reporting previously missed events.
+    ///
+    /// Returns false if the channel is closed (receiver dropped)
+    fn send(&mut self, event: PoolListenerEvent<T>) -> bool {
+        if self.missed > 0 {
+            match self.sender.try_send(PoolListenerEvent::Lagged { missed: self.missed }) {
+                Ok(()) => self.missed = 0,
+                Err(TrySendError::Full(_)) => {
+                    self.missed += 1;
+                    return true
+                }
+                Err(TrySendError::Closed(_)) => return false,
+            }
+        }
+
+        match self.sender.try_send(event) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                if self.missed == 0 {
+                    debug!(target: "txpool", "pool listener lagging; channel full");
+                }
+                self.missed += 1;
+                true
+            }
+            Err(TrySendError::Closed(_)) => false,
+        }
+    }
+}
+
 /// An active listener for new pending transactions.
 #[derive(Debug)]
 pub(crate) struct PendingTransactionHashListener {
@@ -82,8 +82,12 @@ use crate::{
     traits::{
         AllPoolTransactions, BestTransactionsAttributes, BlockInfo, GetPooledTransactionLimit,
         NewBlobSidecar, PoolSize, PoolTransaction, PropagatedTransactions, TransactionOrigin,
+        TransactionStatusDetail,
+    },
+    validate::{
+        SourceValidationBudgets, TransactionValidationOutcome, ValidPoolTransaction,
+        ValidTransaction,
     },
-    validate::{TransactionValidationOutcome, ValidPoolTransaction, ValidTransaction},
     CanonicalStateUpdate, EthPoolTransaction, PoolConfig, TransactionOrdering,
     TransactionValidator,
 };
@@ -101,10 +105,16 @@ use std::{collections::HashSet, fmt, sync::Arc, time::Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 mod events;
-pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
+pub use best::{
+    BestTransactionFilter, BestTransactionsStream, BestTransactionsWithPrioritizedSenders,
+};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
-pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
-pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
+pub use events::{
+    DropReason, FullTransactionEvent, NewTransactionEvent, PoolListenerEvent, TransactionEvent,
+};
+pub use listener::{
+    AllTransactionsEvents, PoolListener, TransactionEvents, TransactionListenerKind,
+};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
 use reth_primitives_traits::Block;
@@ -123,6 +133,8 @@ mod update;
 pub const PENDING_TX_LISTENER_BUFFER_SIZE: usize = 2048;
 /// Bound on number of new transactions from `reth_network::TransactionsManager` to buffer.
 pub const NEW_TX_LISTENER_BUFFER_SIZE: usize = 1024;
+/// Bound on number of events buffered by a [`PoolListener`].
+pub const POOL_LISTENER_BUFFER_SIZE: usize = 2048;
 
 const BLOB_SIDECAR_LISTENER_BUFFER_SIZE: usize = 512;
 
@@ -151,6 +163,8 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Validation cost budgets per transaction source.
+    validation_budgets: SourceValidationBudgets,
 }
 
 // === impl PoolInner ===
@@ -168,6 +182,10 @@ where
             validator,
             event_listener: Default::default(),
             pool: RwLock::new(TxPool::new(ordering, config.clone())),
+            validation_budgets: SourceValidationBudgets::new(
+                config.p2p_validation_budget,
+                config.rpc_validation_budget,
+            ),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
@@ -237,6 +255,21 @@ where
         &self.validator
     }
 
+    /// Spends the validation cost of the transaction from the validation budget of its origin.
+    ///
+    /// Returns [`PoolErrorKind::ValidationBudgetExceeded`] if the budget is exhausted, in which
+    /// case the transaction should be rejected without validating it.
+    pub fn consume_validation_budget(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T::Transaction,
+    ) -> PoolResult<()> {
+        if self.validation_budgets.try_consume(origin, transaction) {
+            return Ok(())
+        }
+        Err(PoolError::new(*transaction.hash(), PoolErrorKind::ValidationBudgetExceeded))
+    }
+
     /// Adds a new transaction listener to the pool that gets notified about every new _pending_
     /// transaction inserted into the pool
     pub fn add_pending_listener(&self, kind: TransactionListenerKind) -> mpsc::Receiver<TxHash> {
@@ -278,6 +311,11 @@ where
         self.event_listener.write().subscribe_all()
     }
 
+    /// Adds a [`PoolListener`] that buffers up to `capacity` events.
+    pub fn add_pool_listener(&self, capacity: usize) -> PoolListener<T::Transaction> {
+        self.event_listener.write().subscribe_pool_listener(capacity)
+    }
+
     /// Returns a read lock to the pool's data.
     pub fn get_pool_data(&self) -> RwLockReadGuard<'_, TxPool<T>> {
         self.pool.read()
@@ -437,10 +475,10 @@ where
             let mut listener = self.event_listener.write();
 
             for tx in &promoted {
-                listener.pending(tx.hash(), None);
+                listener.promoted(tx.hash());
             }
             for tx in &discarded {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DropReason::StateChange);
             }
         }
 
@@ -537,7 +575,7 @@ where
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
+                listener.discarded(&tx_hash, DropReason::ValidationError);
                 Err(PoolError::other(tx_hash, err))
             }
         }
@@ -596,7 +634,7 @@ where
             {
                 let mut listener = self.event_listener.write();
                 for hash in &discarded_hashes {
-                    listener.discarded(hash);
+                    listener.discarded(hash, DropReason::SizeLimit);
                 }
             }
 
@@ -696,10 +734,10 @@ where
             listener.mined(tx, block_hash);
         }
         for tx in &promoted {
-            listener.pending(tx.hash(), None);
+            listener.promoted(tx.hash());
         }
         for tx in &discarded {
-            listener.discarded(tx.hash());
+            listener.discarded(tx.hash(), DropReason::StateChange);
         }
     }
 
@@ -711,15 +749,17 @@ where
             AddedTransaction::Pending(tx) => {
                 let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
 
+                listener.added(transaction, SubPool::Pending);
                 listener.pending(transaction.hash(), replaced.clone());
                 for tx in promoted {
-                    listener.pending(tx.hash(), None);
+                    listener.promoted(tx.hash());
                 }
                 for tx in discarded {
-                    listener.discarded(tx.hash());
+                    listener.discarded(tx.hash(), DropReason::StateChange);
                 }
             }
-            AddedTransaction::Parked { transaction, replaced, .. } => {
+            AddedTransaction::Parked { transaction, replaced, subpool } => {
+                listener.added(transaction, *subpool);
                 listener.queued(transaction.hash());
                 if let Some(replaced) = replaced {
                     listener.replaced(replaced.clone(), *transaction.hash());
@@ -786,7 +826,7 @@ where
         let mut listener = self.event_listener.write();
 
         for tx in &removed {
-            listener.discarded(tx.hash());
+            listener.removed(tx.hash());
         }
 
         removed
@@ -806,7 +846,7 @@ where
         let mut listener = self.event_listener.write();
 
         for tx in &removed {
-            listener.discarded(tx.hash());
+            listener.removed(tx.hash());
         }
 
         removed
@@ -823,7 +863,7 @@ where
         let mut listener = self.event_listener.write();
 
         for tx in &removed {
-            listener.discarded(tx.hash());
+            listener.removed(tx.hash());
         }
 
         removed
@@ -846,6 +886,11 @@ where
         self.get_pool_data().get(tx_hash)
     }
 
+    /// Returns the sub-pool of the transaction and the reasons why it is not pending.
+    pub fn transaction_status_detail(&self, tx_hash: &TxHash) -> Option<TransactionStatusDetail> {
+        self.get_pool_data().status_detail(tx_hash)
+    }
+
     /// Returns all transactions of the address
     pub fn get_transactions_by_sender(
         &self,
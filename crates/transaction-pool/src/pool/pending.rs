@@ -1,7 +1,7 @@
 use crate::{
     identifier::{SenderId, TransactionId},
     pool::{
-        best::{BestTransactions, BestTransactionsWithFees},
+        best::{BestTransactions, BestTransactionsWithFees, PendingPoolUpdate},
         size::SizeTracker,
     },
     Priority, SubPoolLimit, TransactionOrdering, ValidPoolTransaction,
@@ -45,9 +45,9 @@ pub struct PendingPool<T: TransactionOrdering> {
     ///
     /// See also [`reth_primitives_traits::InMemorySize::size`].
     size_of: SizeTracker,
-    /// Used to broadcast new transactions that have been added to the `PendingPool` to existing
+    /// Used to broadcast new, replaced and removed transactions of the `PendingPool` to existing
     /// `static_files` of this pool.
-    new_transaction_notifier: broadcast::Sender<PendingTransaction<T>>,
+    new_transaction_notifier: broadcast::Sender<PendingPoolUpdate<T>>,
 }
 
 // === impl PendingPool ===
@@ -108,6 +108,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
             all: self.by_id.clone(),
             independent: self.independent_transactions.values().cloned().collect(),
             invalid: Default::default(),
+            yielded_nonces: Default::default(),
             new_transaction_receiver: Some(self.new_transaction_notifier.subscribe()),
             skip_blobs: false,
         }
@@ -201,6 +202,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
             }
         }
 
+        self.notify_removed(&removed);
         removed
     }
 
@@ -246,6 +248,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
             }
         }
 
+        self.notify_removed(&removed);
         removed
     }
 
@@ -310,9 +313,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
         self.update_independents_and_highest_nonces(&tx);
 
         // send the new transaction to any existing pendingpool static file iterators
-        if self.new_transaction_notifier.receiver_count() > 0 {
-            let _ = self.new_transaction_notifier.send(tx.clone());
-        }
+        self.notify(PendingPoolUpdate::Added(tx.clone()));
 
         self.by_id.insert(tx_id, tx);
     }
@@ -337,6 +338,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
 
         let tx = self.by_id.remove(id)?;
         self.size_of -= tx.transaction.size();
+        self.notify(PendingPoolUpdate::Removed(*id));
 
         if let Some(highest) = self.highest_nonces.get(&id.sender) {
             if highest.transaction.nonce() == id.nonce {
@@ -349,6 +351,20 @@ impl<T: TransactionOrdering> PendingPool<T> {
         Some(tx.transaction)
     }
 
+    /// Sends the update to any existing pendingpool static file iterators.
+    fn notify(&self, update: PendingPoolUpdate<T>) {
+        if self.new_transaction_notifier.receiver_count() > 0 {
+            let _ = self.new_transaction_notifier.send(update);
+        }
+    }
+
+    /// Notifies existing pendingpool static file iterators about the removed transactions.
+    fn notify_removed(&self, removed: &[Arc<ValidPoolTransaction<T::Transaction>>]) {
+        for tx in removed {
+            self.notify(PendingPoolUpdate::Removed(*tx.id()));
+        }
+    }
+
     const fn next_id(&mut self) -> u64 {
         let id = self.submission_id;
         self.submission_id = self.submission_id.wrapping_add(1);
@@ -17,7 +17,9 @@ use crate::{
         update::{Destination, PoolUpdate, UpdateOutcome},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
+    traits::{
+        BestTransactionsAttributes, BlockInfo, ParkedReason, PoolSize, TransactionStatusDetail,
+    },
     PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
@@ -518,6 +520,56 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.by_hash.get(tx_hash).cloned()
     }
 
+    /// Returns the sub-pool of the transaction with the given hash and the reasons why it is not
+    /// pending, derived from the transaction's [`TxState`].
+    pub(crate) fn status_detail(&self, tx_hash: &TxHash) -> Option<TransactionStatusDetail> {
+        let tx = self.all_transactions.by_hash.get(tx_hash)?;
+        let internal = self.all_transactions.get(&tx.transaction_id)?;
+        let state = internal.state;
+        let sender_info = self.sender_info.get(&tx.sender_id()).cloned().unwrap_or_default();
+
+        let mut parked_reasons = Vec::new();
+        if state.has_nonce_gap() {
+            // the first nonce after the on chain nonce that is not in the pool
+            let mut missing_nonce = sender_info.state_nonce;
+            for (id, _) in self.all_transactions.txs_iter(tx.sender_id()) {
+                if id.nonce > missing_nonce {
+                    break
+                }
+                missing_nonce = missing_nonce.max(id.nonce + 1);
+            }
+            parked_reasons.push(ParkedReason::NonceGap { missing_nonce });
+        } else if !state.contains(TxState::NO_PARKED_ANCESTORS) {
+            parked_reasons.push(ParkedReason::ParkedAncestor);
+        }
+        if !state.contains(TxState::ENOUGH_BALANCE) {
+            parked_reasons.push(ParkedReason::InsufficientBalance {
+                balance: sender_info.balance,
+                cost: internal.next_cumulative_cost(),
+            });
+        }
+        if !state.contains(TxState::NOT_TOO_MUCH_GAS) {
+            parked_reasons.push(ParkedReason::ExceedsBlockGasLimit {
+                gas_limit: tx.gas_limit(),
+                block_gas_limit: self.all_transactions.block_gas_limit,
+            });
+        }
+        if !state.contains(TxState::ENOUGH_FEE_CAP_BLOCK) {
+            parked_reasons.push(ParkedReason::FeeCapBelowBaseFee {
+                max_fee_per_gas: tx.max_fee_per_gas(),
+                base_fee: self.all_transactions.pending_fees.base_fee,
+            });
+        }
+        if !state.contains(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK) {
+            parked_reasons.push(ParkedReason::BlobFeeCapBelowBlobFee {
+                max_fee_per_blob_gas: tx.max_fee_per_blob_gas().unwrap_or_default(),
+                blob_fee: self.all_transactions.pending_fees.blob_fee,
+            });
+        }
+
+        Some(TransactionStatusDetail { subpool: internal.subpool, parked_reasons })
+    }
+
     /// Returns transactions for the multiple given hashes, if they exist.
     pub(crate) fn get_all(
         &self,
@@ -3252,6 +3304,33 @@ mod tests {
         assert_eq!(3, pool.queued_transactions().len());
     }
 
+    #[test]
+    fn status_detail_nonce_gap() {
+        let on_chain_balance = U256::from(10_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let tx_0 = MockTransaction::eip1559().set_gas_price(100).inc_limit();
+        let tx_1 = tx_0.next();
+        let tx_2 = tx_1.next();
+
+        let v0 = f.validated(tx_0);
+        let v2 = f.validated(tx_2);
+        pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        pool.add_transaction(v2.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+
+        let detail = pool.status_detail(v0.hash()).unwrap();
+        assert_eq!(detail.subpool, SubPool::Pending);
+        assert!(detail.parked_reasons.is_empty());
+
+        let detail = pool.status_detail(v2.hash()).unwrap();
+        assert_eq!(detail.subpool, SubPool::Queued);
+        assert_eq!(detail.parked_reasons, vec![ParkedReason::NonceGap { missing_nonce: 1 }]);
+
+        assert!(pool.status_detail(&B256::random()).is_none());
+    }
+
     #[test]
     fn account_updates_nonce_gap() {
         let on_chain_balance = U256::from(10_000);
@@ -54,11 +54,11 @@ use crate::{
     blobstore::BlobStoreError,
     error::{InvalidPoolTransactionError, PoolError, PoolResult},
     pool::{
-        state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
-        TransactionListenerKind,
+        state::SubPool, BestTransactionFilter, BestTransactionsStream, NewTransactionEvent,
+        TransactionEvents, TransactionListenerKind,
     },
     validate::ValidPoolTransaction,
-    AddedTransactionOutcome, AllTransactionsEvents,
+    AddedTransactionOutcome, AllTransactionsEvents, PoolListener,
 };
 use alloy_consensus::{error::ValueError, BlockHeader, Signed, Typed2718};
 use alloy_eips::{
@@ -176,6 +176,21 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         transactions: Vec<Self::Transaction>,
     ) -> impl Future<Output = Vec<PoolResult<AddedTransactionOutcome>>> + Send;
 
+    /// Re-injects _unvalidated_ transactions that were previously in the pool, for example
+    /// transactions of reorged blocks or transactions restored from a backup.
+    ///
+    /// Unlike [`TransactionPool::add_transactions`], re-injected transactions are not charged
+    /// against the validation budget of their origin.
+    ///
+    /// Consumer: Utility
+    fn reinject_transactions(
+        &self,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> impl Future<Output = Vec<PoolResult<AddedTransactionOutcome>>> + Send {
+        self.add_transactions(origin, transactions)
+    }
+
     /// Submit a consensus transaction directly to the pool
     fn add_consensus_transaction(
         &self,
@@ -220,6 +235,28 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Returns a new transaction change event stream for _all_ transactions in the pool.
     fn all_transactions_event_listener(&self) -> AllTransactionsEvents<Self::Transaction>;
 
+    /// Returns a new [`PoolListener`] that yields the lifecycle events of _all_ transactions in
+    /// the pool: when they're added, promoted, replaced, mined, removed or dropped.
+    ///
+    /// The listener buffers up to [`PoolConfig::pool_listener_buffer_size`](crate::PoolConfig)
+    /// events. A listener that falls behind never blocks the pool, instead it receives a
+    /// [`PoolListenerEvent::Lagged`](crate::PoolListenerEvent::Lagged) event with the number of
+    /// events it missed.
+    ///
+    /// Consumer: external mempool streaming services
+    fn pool_listener(&self) -> PoolListener<Self::Transaction> {
+        self.pool_listener_with_capacity(crate::pool::POOL_LISTENER_BUFFER_SIZE)
+    }
+
+    /// Returns a new [`PoolListener`] that buffers up to `capacity` events.
+    ///
+    /// See also [`Self::pool_listener`].
+    ///
+    /// By default, the returned listener doesn't yield any events.
+    fn pool_listener_with_capacity(&self, _capacity: usize) -> PoolListener<Self::Transaction> {
+        PoolListener::new(tokio::sync::mpsc::channel(1).1)
+    }
+
     /// Returns a new Stream that yields transactions hashes for new __pending__ transactions
     /// inserted into the pool that are allowed to be propagated.
     ///
@@ -355,6 +392,21 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         &self,
     ) -> Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<Self::Transaction>>>>;
 
+    /// Returns a stream that yields transactions that are ready for block production, for
+    /// long-running block builders.
+    ///
+    /// Unlike a snapshot, the stream always reflects the current best ordering: it applies new
+    /// pending transactions, replacements and removals, and waits for new pending transactions
+    /// once all ready transactions were yielded.
+    ///
+    /// Consumer: Block production
+    fn best_transactions_stream(&self) -> BestTransactionsStream<Self::Transaction> {
+        BestTransactionsStream::new(
+            self.best_transactions(),
+            self.pending_transactions_listener_for(TransactionListenerKind::All),
+        )
+    }
+
     /// Returns an iterator that yields transactions that are ready for block production with the
     /// given base fee and optional blob fee attributes.
     ///
@@ -448,6 +500,16 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Returns the transaction for the given hash.
     fn get(&self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns the sub-pool of the transaction with the given hash and all reasons why it is not
+    /// pending.
+    ///
+    /// Returns `None` if the transaction is not in the pool.
+    ///
+    /// By default, no details are available.
+    fn transaction_status_detail(&self, _tx_hash: &TxHash) -> Option<TransactionStatusDetail> {
+        None
+    }
+
     /// Returns all transactions objects for the given hashes.
     ///
     /// Caution: This in case of blob transactions, this does not include the sidecar.
@@ -1514,6 +1576,58 @@ impl EthBlobTransactionSidecar {
     }
 }
 
+/// Explains why a transaction in the pool is or is not pending, see
+/// [`TransactionPool::transaction_status_detail`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionStatusDetail {
+    /// The sub-pool that currently contains the transaction.
+    pub subpool: SubPool,
+    /// All reasons that keep the transaction out of the pending sub-pool, empty if the
+    /// transaction is pending.
+    pub parked_reasons: Vec<ParkedReason>,
+}
+
+/// A reason why a transaction is parked instead of pending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParkedReason {
+    /// A transaction of the sender with a lower nonce is missing.
+    NonceGap {
+        /// The lowest nonce of the sender that is neither mined nor in the pool.
+        missing_nonce: u64,
+    },
+    /// A transaction of the sender with a lower nonce is parked.
+    ParkedAncestor,
+    /// The sender's balance does not cover the cost of this and all prior transactions of the
+    /// sender.
+    InsufficientBalance {
+        /// The balance of the sender.
+        balance: U256,
+        /// The cumulative cost of the sender's transactions up to and including this one.
+        cost: U256,
+    },
+    /// The gas limit of the transaction exceeds the block gas limit.
+    ExceedsBlockGasLimit {
+        /// The gas limit of the transaction.
+        gas_limit: u64,
+        /// The block gas limit.
+        block_gas_limit: u64,
+    },
+    /// The max fee per gas is below the base fee of the pending block.
+    FeeCapBelowBaseFee {
+        /// The max fee per gas of the transaction.
+        max_fee_per_gas: u128,
+        /// The base fee of the pending block.
+        base_fee: u64,
+    },
+    /// The max fee per blob gas is below the blob fee of the pending block.
+    BlobFeeCapBelowBlobFee {
+        /// The max fee per blob gas of the transaction.
+        max_fee_per_blob_gas: u128,
+        /// The blob fee of the pending block.
+        blob_fee: u128,
+    },
+}
+
 /// Represents the current status of the pool.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct PoolSize {
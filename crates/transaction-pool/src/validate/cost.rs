@@ -0,0 +1,228 @@
+//! Validation cost accounting for transaction admission.
+//!
+//! Validating a transaction is not equally expensive for all transaction types: blob transactions
+//! require KZG proof verification, EIP-7702 transactions require recovering the authority of every
+//! authorization and contract creations with large initcode are more expensive to analyze. The
+//! [`validation_cost`] of a transaction is expressed in units of validating a plain transaction
+//! and is charged against a [`ValidationBudget`] before the transaction is validated, which bounds
+//! the validation work a single source can cause.
+
+use crate::{metrics::TxPoolValidationMetrics, PoolTransaction, TransactionOrigin};
+use parking_lot::Mutex;
+use std::time::Instant;
+
+/// Validation cost of any transaction, covering signature recovery and the stateful checks.
+pub const BASE_VALIDATION_COST: u64 = 1;
+
+/// Validation cost of each started KiB of initcode of a contract creation.
+pub const INITCODE_KIB_VALIDATION_COST: u64 = 1;
+
+/// Validation cost of each blob of an EIP-4844 transaction, dominated by the KZG proof
+/// verification.
+pub const BLOB_VALIDATION_COST: u64 = 16;
+
+/// Validation cost of each authorization of an EIP-7702 transaction, dominated by the authority
+/// recovery.
+pub const AUTHORIZATION_VALIDATION_COST: u64 = 1;
+
+/// Returns the validation cost of the transaction.
+pub fn validation_cost<T: PoolTransaction>(transaction: &T) -> u64 {
+    let mut cost = BASE_VALIDATION_COST;
+    if transaction.is_create() {
+        let initcode_kib = (transaction.input().len() as u64).div_ceil(1024);
+        cost += initcode_kib * INITCODE_KIB_VALIDATION_COST;
+    }
+    if let Some(blob_versioned_hashes) = transaction.blob_versioned_hashes() {
+        cost += blob_versioned_hashes.len() as u64 * BLOB_VALIDATION_COST;
+    }
+    if let Some(authorization_list) = transaction.authorization_list() {
+        cost += authorization_list.len() as u64 * AUTHORIZATION_VALIDATION_COST;
+    }
+    cost
+}
+
+/// Configuration of a [`ValidationBudget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationBudgetConfig {
+    /// Maximum validation cost that can be spent at once.
+    pub capacity: u64,
+    /// Validation cost that is refilled every second.
+    pub refill_per_sec: u64,
+}
+
+impl ValidationBudgetConfig {
+    /// Creates a new config with the given capacity and refill rate.
+    pub const fn new(capacity: u64, refill_per_sec: u64) -> Self {
+        Self { capacity, refill_per_sec }
+    }
+
+    /// Creates a new config refilling the given validation cost every second, that allows bursts
+    /// of two seconds worth of validation cost.
+    pub const fn per_second(refill_per_sec: u64) -> Self {
+        Self::new(refill_per_sec.saturating_mul(2), refill_per_sec)
+    }
+}
+
+/// A token bucket of validation cost that can be spent on admitting transactions.
+#[derive(Debug, Clone)]
+pub struct ValidationBudget {
+    config: ValidationBudgetConfig,
+    /// Validation cost that can currently be spent.
+    available: u64,
+    /// When the budget was last refilled.
+    last_refill: Instant,
+}
+
+impl ValidationBudget {
+    /// Creates a new, full budget.
+    pub fn new(config: ValidationBudgetConfig) -> Self {
+        Self { config, available: config.capacity, last_refill: Instant::now() }
+    }
+
+    /// Returns the config of the budget.
+    pub const fn config(&self) -> &ValidationBudgetConfig {
+        &self.config
+    }
+
+    /// Spends the given validation cost if the budget allows it, returns `false` otherwise.
+    ///
+    /// Costs above the capacity of the budget are capped at the capacity, so that every
+    /// transaction can be admitted with a full budget.
+    pub fn try_consume(&mut self, cost: u64) -> bool {
+        self.try_consume_at(cost, Instant::now())
+    }
+
+    fn try_consume_at(&mut self, cost: u64, now: Instant) -> bool {
+        self.refill(now);
+
+        let cost = cost.min(self.config.capacity);
+        if self.available < cost {
+            return false
+        }
+        self.available -= cost;
+        true
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refill = elapsed.as_nanos() * self.config.refill_per_sec as u128 / 1_000_000_000;
+        // only advance the refill time once at least one unit was refilled, so that frequent
+        // calls don't lose the fractional refill
+        if refill > 0 {
+            let available = (self.available as u128 + refill).min(self.config.capacity as u128);
+            self.available = available as u64;
+            self.last_refill = now;
+        }
+    }
+}
+
+/// Validation budgets of the pool for transactions received over p2p and submitted over RPC.
+#[derive(Debug)]
+pub(crate) struct SourceValidationBudgets {
+    /// Budget for [`TransactionOrigin::External`] transactions.
+    p2p: Option<Mutex<ValidationBudget>>,
+    /// Budget for [`TransactionOrigin::Local`] and [`TransactionOrigin::Private`] transactions.
+    rpc: Option<Mutex<ValidationBudget>>,
+    /// Validation metrics.
+    metrics: TxPoolValidationMetrics,
+}
+
+impl SourceValidationBudgets {
+    /// Creates new budgets, a missing config disables the budget of the source.
+    pub(crate) fn new(
+        p2p: Option<ValidationBudgetConfig>,
+        rpc: Option<ValidationBudgetConfig>,
+    ) -> Self {
+        Self {
+            p2p: p2p.map(|config| Mutex::new(ValidationBudget::new(config))),
+            rpc: rpc.map(|config| Mutex::new(ValidationBudget::new(config))),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Spends the validation cost of the transaction from the budget of its origin, returns
+    /// `false` if the budget is exhausted.
+    pub(crate) fn try_consume<T: PoolTransaction>(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> bool {
+        let budget = match origin {
+            TransactionOrigin::External => &self.p2p,
+            TransactionOrigin::Local | TransactionOrigin::Private => &self.rpc,
+        };
+        let admitted = budget
+            .as_ref()
+            .is_none_or(|budget| budget.lock().try_consume(validation_cost(transaction)));
+        if !admitted {
+            self.metrics.validation_budget_exceeded.increment(1);
+        }
+        admitted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_primitives::{Bytes, TxKind, B256};
+    use std::time::Duration;
+
+    #[test]
+    fn validation_cost_by_type() {
+        let transfer = MockTransaction::eip1559();
+        assert_eq!(validation_cost(&transfer), BASE_VALIDATION_COST);
+
+        let mut create = MockTransaction::eip1559().with_input(Bytes::from(vec![0; 1025]));
+        if let MockTransaction::Eip1559 { to, .. } = &mut create {
+            *to = TxKind::Create;
+        }
+        assert_eq!(
+            validation_cost(&create),
+            BASE_VALIDATION_COST + 2 * INITCODE_KIB_VALIDATION_COST
+        );
+
+        let mut blob = MockTransaction::eip4844();
+        if let MockTransaction::Eip4844 { blob_versioned_hashes, .. } = &mut blob {
+            *blob_versioned_hashes = vec![B256::ZERO; 3];
+        }
+        assert_eq!(validation_cost(&blob), BASE_VALIDATION_COST + 3 * BLOB_VALIDATION_COST);
+    }
+
+    #[test]
+    fn budget_refills() {
+        let mut budget = ValidationBudget::new(ValidationBudgetConfig::new(10, 10));
+        let start = budget.last_refill;
+
+        assert!(budget.try_consume_at(10, start));
+        assert!(!budget.try_consume_at(1, start));
+
+        // half a second refills half of the capacity
+        let now = start + Duration::from_millis(500);
+        assert!(budget.try_consume_at(5, now));
+        assert!(!budget.try_consume_at(1, now));
+
+        // the budget never exceeds its capacity
+        let now = now + Duration::from_secs(10);
+        assert!(budget.try_consume_at(10, now));
+        assert!(!budget.try_consume_at(1, now));
+
+        // costs above the capacity are admitted with a full budget
+        let now = now + Duration::from_secs(1);
+        assert!(budget.try_consume_at(100, now));
+    }
+
+    #[test]
+    fn source_budgets() {
+        let budgets = SourceValidationBudgets::new(Some(ValidationBudgetConfig::new(1, 0)), None);
+        let tx = MockTransaction::eip1559();
+
+        assert!(budgets.try_consume(TransactionOrigin::External, &tx));
+        assert!(!budgets.try_consume(TransactionOrigin::External, &tx));
+
+        // rpc budget is disabled
+        assert!(budgets.try_consume(TransactionOrigin::Local, &tx));
+        assert!(budgets.try_consume(TransactionOrigin::Private, &tx));
+    }
+}
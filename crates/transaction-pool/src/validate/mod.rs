@@ -13,9 +13,15 @@ use reth_primitives_traits::{Recovered, SealedBlock};
 use std::{fmt, fmt::Debug, future::Future, time::Instant};
 
 mod constants;
+mod cost;
 mod eth;
 mod task;
 
+pub(crate) use cost::SourceValidationBudgets;
+pub use cost::{
+    validation_cost, ValidationBudget, ValidationBudgetConfig, AUTHORIZATION_VALIDATION_COST,
+    BASE_VALIDATION_COST, BLOB_VALIDATION_COST, INITCODE_KIB_VALIDATION_COST,
+};
 pub use eth::*;
 
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
@@ -2,8 +2,8 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, PoolTransaction, TransactionEvent, TransactionListenerKind,
-    TransactionOrigin, TransactionPool,
+    DropReason, FullTransactionEvent, PoolListenerEvent, PoolTransaction, SubPool,
+    TransactionEvent, TransactionListenerKind, TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -192,3 +192,71 @@ async fn txpool_listener_blob_sidecar() {
     let inserted = listener_blob.recv().await.unwrap();
     assert_eq!(*inserted.tx_hash, expected);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_pool_listener() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = *transaction.transaction.get_hash();
+
+    let mut listener = txpool.pool_listener();
+
+    let result =
+        txpool.add_transaction(TransactionOrigin::External, transaction.transaction.clone()).await;
+    assert!(result.is_ok());
+    assert_matches!(
+        listener.next().await,
+        Some(PoolListenerEvent::Added { transaction, subpool: SubPool::Pending }) if *transaction.hash() == hash
+    );
+
+    txpool.remove_transactions(vec![hash]);
+    assert_matches!(listener.next().await, Some(PoolListenerEvent::Removed(removed)) if removed == hash);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_pool_listener_invalid() {
+    let txpool =
+        TestPoolBuilder::default().with_validator(MockTransactionValidator::return_invalid());
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559().transaction;
+
+    let mut listener = txpool.pool_listener();
+
+    let result = txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await;
+    assert!(result.is_err());
+    assert_matches!(
+        listener.next().await,
+        Some(PoolListenerEvent::Dropped { tx_hash, reason: DropReason::Invalid }) if tx_hash == *transaction.get_hash()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_pool_listener_lagged() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transactions = (0..4).map(|_| mock_tx_factory.create_eip1559()).collect::<Vec<_>>();
+
+    let mut listener = txpool.pool_listener_with_capacity(1);
+
+    for transaction in &transactions[..3] {
+        let result = txpool
+            .add_transaction(TransactionOrigin::External, transaction.transaction.clone())
+            .await;
+        assert!(result.is_ok());
+    }
+
+    // only the first event fits into the buffer
+    assert_matches!(
+        listener.next().await,
+        Some(PoolListenerEvent::Added { transaction, .. }) if transaction.hash() == transactions[0].transaction.get_hash()
+    );
+    assert!(listener.is_empty());
+
+    // the missed events are reported before the next event
+    let result = txpool
+        .add_transaction(TransactionOrigin::External, transactions[3].transaction.clone())
+        .await;
+    assert!(result.is_ok());
+    assert_matches!(listener.next().await, Some(PoolListenerEvent::Lagged { missed: 2 }));
+}
@@ -1,7 +1,9 @@
 use assert_matches::assert_matches;
 use reth_transaction_pool::{
+    error::PoolErrorKind,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    TransactionOrigin, TransactionPool,
+    validate::ValidationBudgetConfig,
+    PoolConfig, TransactionOrigin, TransactionPool,
 };
 
 #[tokio::test(flavor = "multi_thread")]
@@ -23,3 +25,30 @@ async fn txpool_new_pending_txs() {
     assert_matches!(added_result, Ok(outcome) if outcome.hash == *transaction.transaction.get_hash());
     assert_matches!(best_txns.next(), Some(tx) if tx.transaction.get_hash() == transaction.transaction.get_hash());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_reinjected_txs_skip_validation_budget() {
+    let config = PoolConfig {
+        p2p_validation_budget: Some(ValidationBudgetConfig::new(1, 0)),
+        ..Default::default()
+    };
+    let txpool = TestPoolBuilder::default().with_config(config);
+    let mut mock_tx_factory = MockTransactionFactory::default();
+
+    let transaction = mock_tx_factory.create_eip1559();
+    let added_result =
+        txpool.add_transaction(TransactionOrigin::External, transaction.transaction.clone()).await;
+    assert!(added_result.is_ok());
+
+    // the budget is exhausted
+    let transaction = mock_tx_factory.create_eip1559();
+    let added_result =
+        txpool.add_transaction(TransactionOrigin::External, transaction.transaction.clone()).await;
+    assert_matches!(added_result, Err(err) if matches!(err.kind, PoolErrorKind::ValidationBudgetExceeded));
+
+    // re-injected transactions are not charged against the budget
+    let added_results = txpool
+        .reinject_transactions(TransactionOrigin::External, vec![transaction.transaction.clone()])
+        .await;
+    assert_matches!(added_results.as_slice(), [Ok(outcome)] if outcome.hash == *transaction.transaction.get_hash());
+}
@@ -50,7 +50,7 @@ impl HashedPostState {
         let hashed = state
             .into_par_iter()
             .map(|(address, account)| {
-                let hashed_address = KH::hash_key(address);
+                let hashed_address = KH::hash_address(address);
                 let hashed_account = account.info.as_ref().map(Into::into);
                 let hashed_storage = HashedStorage::from_plain_storage(
                     account.status,
@@ -81,7 +81,7 @@ impl HashedPostState {
         let hashed = state
             .into_iter()
             .map(|(address, account)| {
-                let hashed_address = KH::hash_key(address);
+                let hashed_address = KH::hash_address(address);
                 let hashed_account = account.info.as_ref().map(Into::into);
                 let hashed_storage = HashedStorage::from_plain_storage(
                     account.status,
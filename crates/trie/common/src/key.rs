@@ -1,12 +1,23 @@
-use alloy_primitives::{keccak256, B256};
+use alloy_primitives::{keccak256, Address, B256};
 
 /// Trait for hashing keys in state.
 pub trait KeyHasher: Default + Clone + Send + Sync + 'static {
     /// Hashes the given bytes into a 256-bit hash.
     fn hash_key<T: AsRef<[u8]>>(bytes: T) -> B256;
+
+    /// Hashes the given account address into a 256-bit hash.
+    ///
+    /// Addresses of hot accounts are hashed repeatedly, so implementations may cache the result.
+    #[inline]
+    fn hash_address(address: &Address) -> B256 {
+        Self::hash_key(address)
+    }
 }
 
 /// A key hasher that uses the Keccak-256 hash function.
+///
+/// With the `std` feature, account addresses are hashed with the
+/// [`AddressHashCache`](reth_primitives_traits::hash_cache::AddressHashCache).
 #[derive(Clone, Debug, Default)]
 pub struct KeccakKeyHasher;
 
@@ -15,4 +26,10 @@ impl KeyHasher for KeccakKeyHasher {
     fn hash_key<T: AsRef<[u8]>>(bytes: T) -> B256 {
         keccak256(bytes)
     }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn hash_address(address: &Address) -> B256 {
+        reth_primitives_traits::hash_cache::keccak256_address(address)
+    }
 }
@@ -1,8 +1,78 @@
 //! Common root computation functions.
 
+use crate::Nibbles;
+use alloc::vec::Vec;
+use alloy_primitives::{Bytes, B256};
+use alloy_trie::{proof::ProofRetainer, root::adjust_index_for_rlp, HashBuilder};
+
 // Re-export for convenience.
 #[doc(inline)]
 pub use alloy_trie::root::{
     state_root, state_root_ref_unhashed, state_root_unhashed, state_root_unsorted, storage_root,
     storage_root_unhashed, storage_root_unsorted,
 };
+
+/// Computes the root of an ordered trie, like the transactions or receipts trie of a block, and
+/// the inclusion proof of the item at the given index.
+///
+/// The items are keyed by the RLP encoding of their index, and the values are encoded with the
+/// given encoder. The proof contains the trie nodes on the path from the root to the leaf of the
+/// item, starting with the root node.
+///
+/// Returns `None` if the index is out of bounds.
+pub fn ordered_trie_root_with_proof<T, F>(
+    items: &[T],
+    index: usize,
+    mut encode: F,
+) -> Option<(B256, Vec<Bytes>)>
+where
+    F: FnMut(&T, &mut Vec<u8>),
+{
+    if index >= items.len() {
+        return None
+    }
+
+    let target = Nibbles::unpack(alloy_rlp::encode_fixed_size(&index));
+    let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::from_iter([target]));
+    let mut value_buffer = Vec::new();
+    for i in 0..items.len() {
+        let index = adjust_index_for_rlp(i, items.len());
+        value_buffer.clear();
+        encode(&items[index], &mut value_buffer);
+        hb.add_leaf(Nibbles::unpack(alloy_rlp::encode_fixed_size(&index)), &value_buffer);
+    }
+
+    let root = hb.root();
+    let proof = hb.take_proof_nodes().into_nodes_sorted().into_iter().map(|(_, node)| node);
+    Some((root, proof.collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::proofs::ordered_trie_root_with_encoder;
+    use alloy_trie::proof::verify_proof;
+
+    #[test]
+    fn ordered_trie_proof() {
+        for len in [1usize, 2, 127, 128, 129, 300] {
+            let items = (0..len).map(|i| vec![i as u8; i % 50 + 1]).collect::<Vec<_>>();
+            let expected_root = ordered_trie_root_with_encoder(&items, |item, buf| {
+                buf.extend_from_slice(item);
+            });
+
+            for index in [0, len / 2, len - 1] {
+                let (root, proof) = ordered_trie_root_with_proof(&items, index, |item, buf| {
+                    buf.extend_from_slice(item)
+                })
+                .unwrap();
+                assert_eq!(root, expected_root);
+
+                let key = Nibbles::unpack(alloy_rlp::encode_fixed_size(&index));
+                verify_proof(root, key, Some(items[index].clone()), &proof).unwrap();
+            }
+
+            assert!(ordered_trie_root_with_proof(&items, len, |_, _| {}).is_none());
+        }
+    }
+}
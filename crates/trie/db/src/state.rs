@@ -240,14 +240,16 @@ impl<TX: DbTx> DatabaseHashedPostState<TX> for HashedPostState {
             account_storage.entry(storage.key).or_insert(storage.value);
         }
 
-        let hashed_accounts =
-            accounts.into_iter().map(|(address, info)| (KH::hash_key(address), info)).collect();
+        let hashed_accounts = accounts
+            .into_iter()
+            .map(|(address, info)| (KH::hash_address(&address), info))
+            .collect();
 
         let hashed_storages = storages
             .into_iter()
             .map(|(address, storage)| {
                 (
-                    KH::hash_key(address),
+                    KH::hash_address(&address),
                     HashedStorage::from_iter(
                         // The `wiped` flag indicates only whether previous storage entries
                         // should be looked up in db or not. For reverts it's a noop since all
@@ -0,0 +1,82 @@
+#![allow(missing_docs, unreachable_pub)]
+use alloy_primitives::{B256, U256};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+use reth_provider::{
+    providers::ConsistentDbView, test_utils::create_test_provider_factory, DBProvider, StateWriter,
+};
+use reth_trie::{
+    trie_cursor::noop::NoopTrieCursorFactory, HashedPostState, HashedStorage, StorageRoot,
+};
+use reth_trie_db::DatabaseHashedCursorFactory;
+use reth_trie_parallel::split_storage_root::SplitStorageRoot;
+
+#[cfg(feature = "metrics")]
+use reth_trie::metrics::{TrieRootMetrics, TrieType};
+
+/// Worst case for the state root computation: the storage trie of a single large contract has to
+/// be rebuilt from scratch, e.g. after its storage was wiped and recreated in the block.
+pub fn calculate_storage_root(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Calculate Storage Root");
+    group.sample_size(10);
+
+    for size in [10_000, 100_000, 500_000] {
+        // Too slow.
+        #[expect(unexpected_cfgs)]
+        if cfg!(codspeed) && size > 10_000 {
+            continue;
+        }
+
+        let hashed_address = B256::random();
+        let provider_factory = create_test_provider_factory();
+        {
+            let provider_rw = provider_factory.provider_rw().unwrap();
+            let state = HashedPostState::default()
+                .with_storages([(hashed_address, generate_storage(size))]);
+            provider_rw.write_hashed_state(&state.into_sorted()).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let view = ConsistentDbView::new(provider_factory.clone(), None);
+
+        group.bench_function(BenchmarkId::new("sync storage root", size), |b| {
+            b.iter_with_setup(
+                || provider_factory.provider().unwrap(),
+                |provider| {
+                    StorageRoot::new_hashed(
+                        NoopTrieCursorFactory,
+                        DatabaseHashedCursorFactory::new(provider.tx_ref()),
+                        hashed_address,
+                        Default::default(),
+                        #[cfg(feature = "metrics")]
+                        TrieRootMetrics::new(TrieType::Storage),
+                    )
+                    .root_with_updates()
+                },
+            )
+        });
+
+        group.bench_function(BenchmarkId::new("split storage root", size), |b| {
+            b.iter_with_setup(
+                || SplitStorageRoot::new(view.clone(), hashed_address).with_split_threshold(0),
+                |calculator| calculator.calculate(true),
+            )
+        });
+    }
+}
+
+fn generate_storage(size: usize) -> HashedStorage {
+    let mut runner = TestRunner::deterministic();
+    let storage = proptest::collection::hash_map(
+        any::<B256>(),
+        any::<U256>().prop_filter("non zero value", |v| !v.is_zero()),
+        size,
+    )
+    .new_tree(&mut runner)
+    .unwrap()
+    .current();
+    HashedStorage::from_iter(false, storage)
+}
+
+criterion_group!(storage_root, calculate_storage_root);
+criterion_main!(storage_root);
@@ -17,6 +17,12 @@ pub mod stats;
 /// Implementation of parallel state root computation.
 pub mod root;
 
+/// Implementation of parallel storage root verification.
+pub mod storage_root;
+
+/// Implementation of storage root computation of a single account in parallel sub-tries.
+pub mod split_storage_root;
+
 /// Implementation of parallel proof computation.
 pub mod proof;
 
@@ -1,6 +1,9 @@
 #[cfg(feature = "metrics")]
 use crate::metrics::ParallelStateRootMetrics;
-use crate::{stats::ParallelTrieTracker, storage_root_targets::StorageRootTargets};
+use crate::{
+    split_storage_root::SplitStorageRoot, stats::ParallelTrieTracker,
+    storage_root_targets::StorageRootTargets,
+};
 use alloy_primitives::B256;
 use alloy_rlp::{BufMut, Encodable};
 use itertools::Itertools;
@@ -108,6 +111,16 @@ where
 
             rayon::spawn_fifo(move || {
                 let result = (|| -> Result<_, ParallelStateRootError> {
+                    // The whole storage trie has to be rebuilt, so large storage tries are split
+                    // into sub-tries that are hashed in parallel instead of in a single task.
+                    if prefix_set.all() {
+                        let split_storage_root = SplitStorageRoot::new(view, hashed_address)
+                            .with_hashed_state(hashed_state_sorted);
+                        #[cfg(feature = "metrics")]
+                        let split_storage_root = split_storage_root.with_metrics(metrics);
+                        return split_storage_root.calculate(retain_updates)
+                    }
+
                     let provider_ro = view.provider_ro()?;
                     let trie_cursor_factory = InMemoryTrieCursorFactory::new(
                         DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
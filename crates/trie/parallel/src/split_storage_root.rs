@@ -0,0 +1,452 @@
+//! Storage root computation of a single account, split into sub-tries that are hashed in
+//! parallel.
+
+use crate::root::ParallelStateRootError;
+use alloy_primitives::{map::HashMap, B256, U256};
+use rayon::prelude::*;
+use reth_provider::{
+    providers::ConsistentDbView, BlockReader, DBProvider, DatabaseProviderFactory, ProviderError,
+    StateCommitmentProvider,
+};
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    stats::TrieTracker,
+    updates::StorageTrieUpdates,
+    BranchNodeCompact, HashBuilder, HashedPostStateSorted, Nibbles, EMPTY_ROOT_HASH,
+};
+use reth_trie_db::DatabaseHashedCursorFactory;
+use std::sync::Arc;
+use tracing::*;
+
+#[cfg(feature = "metrics")]
+use reth_trie::metrics::TrieRootMetrics;
+
+/// Number of nibbles of a hashed storage slot.
+const SLOT_NIBBLES: usize = 64;
+
+/// Default minimum number of storage slots of an account for its storage trie to be split.
+pub const DEFAULT_SPLIT_THRESHOLD: usize = 4_096;
+
+/// Storage root calculator that splits the storage trie of a single account into sub-tries.
+///
+/// The storage trie of an account is usually hashed by a single worker, so a contract with a huge
+/// storage, like a popular ERC20 token, serializes the state root computation. This calculator
+/// locates the topmost branch node of the storage trie with a few cursor seeks and hashes the
+/// sub-trie of every child of that branch node in a separate task. The sub-trie roots are then
+/// combined into the storage root, the same way the incremental root computation combines
+/// unchanged branch nodes from the database.
+///
+/// Splitting opens a database transaction per sub-trie, which only pays off for large storages.
+/// Storages with fewer slots than the split threshold are hashed by the calling worker.
+///
+/// The root is always computed from the hashed storage entries, stored storage trie nodes are not
+/// used. The returned [`StorageTrieUpdates`] therefore replace all stored nodes of the account.
+#[derive(Debug)]
+pub struct SplitStorageRoot<Factory> {
+    /// Consistent view of the database.
+    view: ConsistentDbView<Factory>,
+    /// Changed hashed state on top of the database.
+    hashed_state_sorted: Arc<HashedPostStateSorted>,
+    /// Hashed address of the account.
+    hashed_address: B256,
+    /// Minimum number of storage slots for the storage trie to be split.
+    split_threshold: usize,
+    /// Storage trie metrics.
+    #[cfg(feature = "metrics")]
+    metrics: Option<TrieRootMetrics>,
+}
+
+impl<Factory> SplitStorageRoot<Factory> {
+    /// Create new calculator for the storage root of the account with the given hashed address.
+    pub fn new(view: ConsistentDbView<Factory>, hashed_address: B256) -> Self {
+        Self {
+            view,
+            hashed_state_sorted: Default::default(),
+            hashed_address,
+            split_threshold: DEFAULT_SPLIT_THRESHOLD,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+        }
+    }
+
+    /// Set the changed hashed state that is applied on top of the database.
+    pub fn with_hashed_state(mut self, hashed_state_sorted: Arc<HashedPostStateSorted>) -> Self {
+        self.hashed_state_sorted = hashed_state_sorted;
+        self
+    }
+
+    /// Set the minimum number of storage slots for the storage trie to be split.
+    pub const fn with_split_threshold(mut self, split_threshold: usize) -> Self {
+        self.split_threshold = split_threshold;
+        self
+    }
+
+    /// Set the metrics that the calculation is recorded to.
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: TrieRootMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+}
+
+impl<Factory> SplitStorageRoot<Factory>
+where
+    Factory: DatabaseProviderFactory<Provider: BlockReader>
+        + StateCommitmentProvider
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    /// Calculate the storage root.
+    ///
+    /// Returns the storage root, the number of storage slots and the storage trie updates if
+    /// requested.
+    pub fn calculate(
+        self,
+        retain_updates: bool,
+    ) -> Result<(B256, usize, StorageTrieUpdates), ParallelStateRootError> {
+        let mut tracker = TrieTracker::default();
+        let result = self.calculate_with_tracker(retain_updates, &mut tracker)?;
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.record(tracker.finish());
+        }
+        Ok(result)
+    }
+
+    fn calculate_with_tracker(
+        &self,
+        retain_updates: bool,
+        tracker: &mut TrieTracker,
+    ) -> Result<(B256, usize, StorageTrieUpdates), ParallelStateRootError> {
+        if let Some(slots) = self.small_storage()? {
+            let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
+            for (hashed_slot, value) in &slots {
+                tracker.inc_leaf();
+                hash_builder.add_leaf(
+                    Nibbles::unpack(hashed_slot),
+                    alloy_rlp::encode_fixed_size(value).as_ref(),
+                );
+            }
+            let root = hash_builder.root();
+            let mut updates = StorageTrieUpdates::default();
+            if retain_updates {
+                updates.finalize(hash_builder, Default::default());
+                // the recomputed nodes replace all stored nodes of the storage trie
+                updates.is_deleted = true;
+            }
+            return Ok((root, slots.len(), updates))
+        }
+
+        let Some(top) = self.locate(Nibbles::default())? else {
+            return Ok((EMPTY_ROOT_HASH, 0, StorageTrieUpdates::deleted()))
+        };
+        let branch_path = match top {
+            SubTrie::Branch(path) => path,
+            // a single slot doesn't need to be split
+            SubTrie::Leaf(hashed_slot, value) => {
+                tracker.inc_leaf();
+                let mut hash_builder = HashBuilder::default();
+                hash_builder.add_leaf(
+                    Nibbles::unpack(hashed_slot),
+                    alloy_rlp::encode_fixed_size(&value).as_ref(),
+                );
+                let updates =
+                    if retain_updates { StorageTrieUpdates::deleted() } else { Default::default() };
+                return Ok((hash_builder.root(), 1, updates))
+            }
+        };
+
+        debug!(target: "trie::split_storage_root", hashed_address = ?self.hashed_address, ?branch_path, "calculating storage root of sub-tries");
+        let sub_tries = (0..16u8)
+            .into_par_iter()
+            .map(|nibble| {
+                let mut path = branch_path;
+                path.push_unchecked(nibble);
+                self.sub_trie_root(path, retain_updates)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
+        let mut updates = StorageTrieUpdates::default();
+        let mut slots = 0;
+        for sub_trie in sub_tries.into_iter().flatten() {
+            match sub_trie {
+                SubTrieRoot::Leaf(hashed_slot, value) => {
+                    tracker.inc_leaf();
+                    slots += 1;
+                    hash_builder.add_leaf(
+                        Nibbles::unpack(hashed_slot),
+                        alloy_rlp::encode_fixed_size(&value).as_ref(),
+                    );
+                }
+                SubTrieRoot::Branch { path, hash, slots: sub_trie_slots, nodes } => {
+                    tracker.inc_branch();
+                    slots += sub_trie_slots;
+                    let children_are_in_trie = nodes.contains_key(&path);
+                    hash_builder.add_branch(path, hash, children_are_in_trie);
+                    updates.storage_nodes.extend(nodes);
+                }
+            }
+        }
+
+        let root = hash_builder.root();
+        if retain_updates {
+            updates.finalize(hash_builder, Default::default());
+            // the recomputed nodes replace all stored nodes of the storage trie
+            updates.is_deleted = true;
+        }
+
+        trace!(target: "trie::split_storage_root", hashed_address = ?self.hashed_address, %root, slots, "calculated storage root");
+        Ok((root, slots, updates))
+    }
+
+    /// Returns all storage slots of the account if there are fewer of them than the split
+    /// threshold.
+    fn small_storage(&self) -> Result<Option<Vec<(B256, U256)>>, ParallelStateRootError> {
+        let provider_ro = self.view.provider_ro()?;
+        let mut cursor = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+            &self.hashed_state_sorted,
+        )
+        .hashed_storage_cursor(self.hashed_address)
+        .map_err(ProviderError::Database)?;
+
+        let mut slots = Vec::new();
+        let mut entry = cursor.seek(B256::ZERO).map_err(ProviderError::Database)?;
+        while let Some(slot) = entry {
+            if slots.len() + 1 >= self.split_threshold {
+                return Ok(None)
+            }
+            slots.push(slot);
+            entry = cursor.next().map_err(ProviderError::Database)?;
+        }
+        Ok(Some(slots))
+    }
+
+    /// Hashes the sub-trie of all storage slots with the given prefix.
+    ///
+    /// Returns `None` if there are no storage slots with the prefix.
+    fn sub_trie_root(
+        &self,
+        prefix: Nibbles,
+        retain_updates: bool,
+    ) -> Result<Option<SubTrieRoot>, ParallelStateRootError> {
+        let path = match self.locate(prefix)? {
+            None => return Ok(None),
+            Some(SubTrie::Leaf(hashed_slot, value)) => {
+                return Ok(Some(SubTrieRoot::Leaf(hashed_slot, value)))
+            }
+            Some(SubTrie::Branch(path)) => path,
+        };
+
+        let provider_ro = self.view.provider_ro()?;
+        let mut cursor = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+            &self.hashed_state_sorted,
+        )
+        .hashed_storage_cursor(self.hashed_address)
+        .map_err(ProviderError::Database)?;
+
+        // the keys are stripped of the common path, so the root of the hash builder is the branch
+        // node at the path
+        let mut hash_builder = HashBuilder::default().with_updates(retain_updates);
+        let mut slots = 0;
+        let mut entry = cursor.seek(seek_key(&path)).map_err(ProviderError::Database)?;
+        while let Some((hashed_slot, value)) = entry {
+            let key = Nibbles::unpack(hashed_slot);
+            if !key.starts_with(&path) {
+                break
+            }
+            slots += 1;
+            hash_builder
+                .add_leaf(key.slice(path.len()..), alloy_rlp::encode_fixed_size(&value).as_ref());
+            entry = cursor.next().map_err(ProviderError::Database)?;
+        }
+
+        let hash = hash_builder.root();
+        let (_, updated_nodes) = hash_builder.split();
+        let nodes = updated_nodes
+            .into_iter()
+            .map(|(node_path, mut node)| {
+                // only the root node of the storage trie carries its hash
+                node.root_hash = None;
+                let mut full_path = path;
+                full_path.extend(&node_path);
+                (full_path, node)
+            })
+            .collect();
+
+        Ok(Some(SubTrieRoot::Branch { path, hash, slots, nodes }))
+    }
+
+    /// Locates the topmost node of the sub-trie of all storage slots with the given prefix.
+    ///
+    /// Returns `None` if there are no storage slots with the prefix.
+    fn locate(&self, prefix: Nibbles) -> Result<Option<SubTrie>, ParallelStateRootError> {
+        let provider_ro = self.view.provider_ro()?;
+        let mut cursor = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+            &self.hashed_state_sorted,
+        )
+        .hashed_storage_cursor(self.hashed_address)
+        .map_err(ProviderError::Database)?;
+
+        let Some((first_slot, first_value)) =
+            cursor.seek(seek_key(&prefix)).map_err(ProviderError::Database)?
+        else {
+            return Ok(None)
+        };
+        let first = Nibbles::unpack(first_slot);
+        if !first.starts_with(&prefix) {
+            return Ok(None)
+        }
+
+        // Walk down the path of the first slot until another slot diverges from it. Since the
+        // first slot has the lowest key, other slots can only diverge with a higher nibble.
+        for len in prefix.len()..SLOT_NIBBLES {
+            let nibble = first.get_unchecked(len);
+            if nibble == 0xf {
+                continue
+            }
+            let mut next = first.slice(..len);
+            next.push_unchecked(nibble + 1);
+            let diverges = cursor
+                .seek(seek_key(&next))
+                .map_err(ProviderError::Database)?
+                .is_some_and(|(hashed_slot, _)| {
+                    Nibbles::unpack(hashed_slot).starts_with(&first.slice(..len))
+                });
+            if diverges {
+                return Ok(Some(SubTrie::Branch(first.slice(..len))))
+            }
+        }
+
+        Ok(Some(SubTrie::Leaf(first_slot, first_value)))
+    }
+}
+
+/// The topmost node of a sub-trie.
+#[derive(Debug)]
+enum SubTrie {
+    /// The sub-trie consists of a single storage slot.
+    Leaf(B256, U256),
+    /// The sub-trie starts with a branch node at the given path.
+    Branch(Nibbles),
+}
+
+/// A hashed sub-trie of the storage trie.
+#[derive(Debug)]
+enum SubTrieRoot {
+    /// The sub-trie consists of a single storage slot, which is hashed together with the parent
+    /// branch node.
+    Leaf(B256, U256),
+    /// The sub-trie starts with a branch node.
+    Branch {
+        /// Path of the branch node.
+        path: Nibbles,
+        /// Hash of the branch node.
+        hash: B256,
+        /// Number of storage slots in the sub-trie.
+        slots: usize,
+        /// Updated storage trie nodes of the sub-trie.
+        nodes: HashMap<Nibbles, BranchNodeCompact>,
+    },
+}
+
+/// Returns the lowest hashed storage slot that starts with the given path.
+fn seek_key(path: &Nibbles) -> B256 {
+    let packed = path.pack();
+    let mut key = B256::ZERO;
+    key[..packed.len()].copy_from_slice(&packed);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+    use reth_provider::{test_utils::create_test_provider_factory, StateWriter};
+    use reth_trie::{
+        trie_cursor::noop::NoopTrieCursorFactory, HashedPostState, HashedStorage, StorageRoot,
+    };
+
+    #[cfg(feature = "metrics")]
+    use reth_trie::metrics::{TrieRootMetrics, TrieType};
+
+    fn assert_split_storage_root(slots: impl IntoIterator<Item = B256>) {
+        let factory = create_test_provider_factory();
+        let hashed_address = B256::random();
+        let mut rng = rand::rng();
+        let storage = slots
+            .into_iter()
+            .map(|slot| (slot, U256::from(rng.random_range(1..u64::MAX))))
+            .collect::<Vec<_>>();
+
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            let state = HashedPostState::default()
+                .with_storages([(hashed_address, HashedStorage::from_iter(false, storage))]);
+            provider_rw.write_hashed_state(&state.into_sorted()).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let provider = factory.provider().unwrap();
+        let (expected_root, expected_slots, expected_updates) = StorageRoot::new_hashed(
+            NoopTrieCursorFactory,
+            DatabaseHashedCursorFactory::new(provider.tx_ref()),
+            hashed_address,
+            Default::default(),
+            #[cfg(feature = "metrics")]
+            TrieRootMetrics::new(TrieType::Storage),
+        )
+        .root_with_updates()
+        .unwrap();
+
+        let view = ConsistentDbView::new(factory, None);
+        for split_threshold in [0, DEFAULT_SPLIT_THRESHOLD] {
+            let (root, slots, updates) = SplitStorageRoot::new(view.clone(), hashed_address)
+                .with_split_threshold(split_threshold)
+                .calculate(true)
+                .unwrap();
+            assert_eq!(root, expected_root);
+            assert_eq!(slots, expected_slots);
+            assert!(updates.is_deleted);
+            assert_eq!(updates.storage_nodes, expected_updates.storage_nodes);
+        }
+    }
+
+    #[test]
+    fn split_storage_root_empty() {
+        assert_split_storage_root([]);
+    }
+
+    #[test]
+    fn split_storage_root_single_slot() {
+        assert_split_storage_root([B256::random()]);
+    }
+
+    #[test]
+    fn split_storage_root_random() {
+        assert_split_storage_root((0..5_000).map(|_| B256::random()));
+    }
+
+    #[test]
+    fn split_storage_root_common_prefix() {
+        // all slots share the first nibbles, so the topmost branch node is behind an extension,
+        // and a few sub-tries consist of a single slot or of another extension
+        let mut rng = rand::rng();
+        let slots = (0..1_000).map(|i| {
+            let mut slot = B256::random();
+            slot[0] = 0xab;
+            slot[1] = match i % 4 {
+                0 => 0x10,
+                1 => 0x2c,
+                _ => rng.random(),
+            };
+            slot
+        });
+        assert_split_storage_root(slots.chain([B256::repeat_byte(0xab), B256::repeat_byte(0xac)]));
+    }
+}
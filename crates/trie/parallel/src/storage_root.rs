@@ -0,0 +1,203 @@
+//! Parallel recomputation of the storage roots of targeted accounts.
+
+use crate::{root::ParallelStateRootError, split_storage_root::SplitStorageRoot};
+use alloy_primitives::B256;
+use rayon::prelude::*;
+use reth_provider::{
+    providers::ConsistentDbView, BlockReader, DBProvider, DatabaseProviderFactory,
+    StateCommitmentProvider,
+};
+use reth_trie::{prefix_set::PrefixSet, updates::StorageTrieUpdates, StorageRoot};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
+use tracing::*;
+
+#[cfg(feature = "metrics")]
+use reth_trie::metrics::{TrieRootMetrics, TrieType};
+
+/// Parallel storage root calculator for a set of accounts.
+///
+/// For every account, the storage root is calculated twice at the latest state of the database:
+/// once from the stored storage trie nodes, the way the incremental state root computation
+/// calculates it, and once from the hashed storage entries only. A mismatch between the two roots
+/// means that the stored storage trie of the account is corrupted, which can be repaired by
+/// writing [`StorageRootCheck::updates`] without rebuilding the whole state trie.
+///
+/// Internally, the calculator uses [`ConsistentDbView`] since
+/// it needs to rely on database state saying the same until
+/// the last transaction is open.
+/// See docs of using [`ConsistentDbView`] for caveats.
+#[derive(Debug)]
+pub struct ParallelStorageRoots<Factory> {
+    /// Consistent view of the database.
+    view: ConsistentDbView<Factory>,
+    /// Hashed addresses of the accounts to check.
+    targets: Vec<B256>,
+    /// Storage trie metrics.
+    #[cfg(feature = "metrics")]
+    metrics: TrieRootMetrics,
+}
+
+impl<Factory> ParallelStorageRoots<Factory> {
+    /// Create new parallel storage root calculator for the accounts with the given hashed
+    /// addresses.
+    pub fn new(view: ConsistentDbView<Factory>, targets: impl IntoIterator<Item = B256>) -> Self {
+        let mut targets = targets.into_iter().collect::<Vec<_>>();
+        targets.sort_unstable();
+        targets.dedup();
+        Self {
+            view,
+            targets,
+            #[cfg(feature = "metrics")]
+            metrics: TrieRootMetrics::new(TrieType::Storage),
+        }
+    }
+}
+
+impl<Factory> ParallelStorageRoots<Factory>
+where
+    Factory: DatabaseProviderFactory<Provider: BlockReader>
+        + StateCommitmentProvider
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    /// Calculate the storage roots of all accounts in parallel.
+    ///
+    /// Returns the results in the order of the hashed addresses.
+    pub fn verify(self) -> Result<Vec<StorageRootCheck>, ParallelStateRootError> {
+        debug!(target: "trie::parallel_storage_root", len = self.targets.len(), "verifying storage roots");
+        self.targets
+            .into_par_iter()
+            .map(|hashed_address| {
+                let provider_ro = self.view.provider_ro()?;
+                let tx = provider_ro.tx_ref();
+
+                let stored_root = StorageRoot::new_hashed(
+                    DatabaseTrieCursorFactory::new(tx),
+                    DatabaseHashedCursorFactory::new(tx),
+                    hashed_address,
+                    PrefixSet::default(),
+                    #[cfg(feature = "metrics")]
+                    self.metrics.clone(),
+                )
+                .root()?;
+
+                // the storage tries of large contracts are split, so that they're not hashed by a
+                // single worker
+                let split_storage_root = SplitStorageRoot::new(self.view.clone(), hashed_address);
+                #[cfg(feature = "metrics")]
+                let split_storage_root = split_storage_root.with_metrics(self.metrics.clone());
+                let (computed_root, slots, updates) = split_storage_root.calculate(true)?;
+
+                trace!(target: "trie::parallel_storage_root", ?hashed_address, ?stored_root, ?computed_root, slots, "verified storage root");
+                Ok(StorageRootCheck { hashed_address, stored_root, computed_root, slots, updates })
+            })
+            .collect()
+    }
+}
+
+/// The result of recomputing the storage root of an account.
+#[derive(Debug)]
+pub struct StorageRootCheck {
+    /// Hashed address of the account.
+    pub hashed_address: B256,
+    /// Storage root calculated from the stored storage trie nodes.
+    pub stored_root: B256,
+    /// Storage root recomputed from the hashed storage entries.
+    pub computed_root: B256,
+    /// Number of storage slots of the account.
+    pub slots: usize,
+    /// Updates that replace the stored storage trie of the account with the recomputed one.
+    pub updates: StorageTrieUpdates,
+}
+
+impl StorageRootCheck {
+    /// Returns `true` if the stored storage trie of the account is consistent with its storage.
+    pub fn is_valid(&self) -> bool {
+        self.stored_root == self.computed_root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, U256};
+    use reth_primitives_traits::{Account, StorageEntry};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, HashingWriter, StorageTrieWriter,
+    };
+    use reth_trie::{trie_cursor::noop::NoopTrieCursorFactory, BranchNodeCompact, Nibbles};
+
+    #[test]
+    fn verify_storage_roots() {
+        let factory = create_test_provider_factory();
+        let address = alloy_primitives::Address::random();
+        let hashed_address = keccak256(address);
+
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw.insert_account_for_hashing([(address, Some(Account::default()))]).unwrap();
+            provider_rw
+                .insert_storage_for_hashing([(
+                    address,
+                    (0..100u64).map(|slot| StorageEntry {
+                        key: B256::from(U256::from(slot)),
+                        value: U256::from(slot + 1),
+                    }),
+                )])
+                .unwrap();
+
+            // write the storage trie nodes of the account
+            let (_, _, updates) = StorageRoot::new_hashed(
+                NoopTrieCursorFactory,
+                DatabaseHashedCursorFactory::new(provider_rw.tx_ref()),
+                hashed_address,
+                PrefixSet::default(),
+                #[cfg(feature = "metrics")]
+                TrieRootMetrics::new(TrieType::Storage),
+            )
+            .root_with_updates()
+            .unwrap();
+            provider_rw.write_individual_storage_trie_updates(hashed_address, &updates).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let verify = || {
+            let view = ConsistentDbView::new(factory.clone(), None);
+            ParallelStorageRoots::new(view, [hashed_address]).verify().unwrap().remove(0)
+        };
+
+        let check = verify();
+        assert!(check.is_valid());
+        assert_eq!(check.slots, 100);
+
+        // corrupt the root node of the storage trie
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            let mut corrupted = StorageTrieUpdates::default();
+            corrupted.storage_nodes.insert(
+                Nibbles::default(),
+                BranchNodeCompact::new(0b11, 0b11, 0, vec![], Some(B256::random())),
+            );
+            provider_rw.write_individual_storage_trie_updates(hashed_address, &corrupted).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let check = verify();
+        assert!(!check.is_valid());
+
+        // repair the storage trie
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw
+                .write_individual_storage_trie_updates(hashed_address, &check.updates)
+                .unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let repaired = verify();
+        assert!(repaired.is_valid());
+        assert_eq!(repaired.stored_root, check.computed_root);
+    }
+}
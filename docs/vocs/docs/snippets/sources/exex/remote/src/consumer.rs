@@ -25,6 +25,10 @@ async fn main() -> eyre::Result<()> {
             ExExNotification::ChainReverted { old } => {
                 info!(reverted_chain = ?old.range(), "Received revert");
             }
+            ExExNotification::Shutdown => {
+                info!("Received shutdown");
+            }
+            _ => {}
         };
     }
 
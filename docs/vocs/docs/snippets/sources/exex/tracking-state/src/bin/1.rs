@@ -33,6 +33,11 @@ impl<Node: FullNodeComponents<Types: NodeTypes<Primitives = EthPrimitives>>> Fut
                 ExExNotification::ChainReverted { old } => {
                     info!(reverted_chain = ?old.range(), "Received revert");
                 }
+                ExExNotification::Shutdown => {
+                    info!("Received shutdown");
+                    this.ctx.events.send(ExExEvent::ShutdownAcknowledged)?;
+                }
+                _ => {}
             };
 
             if let Some(committed_chain) = notification.committed_chain() {
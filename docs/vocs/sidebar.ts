@@ -280,6 +280,10 @@ export const sidebar: SidebarItem[] = [
             {
                 text: "rpc",
                 link: "/jsonrpc/rpc"
+            },
+            {
+                text: "evm",
+                link: "/jsonrpc/evm"
             }
         ]
     },
@@ -338,6 +342,10 @@ export const sidebar: SidebarItem[] = [
                                 text: "reth db checksum",
                                 link: "/cli/reth/db/checksum"
                             },
+                            {
+                                text: "reth db audit-keys",
+                                link: "/cli/reth/db/audit-keys"
+                            },
                             {
                                 text: "reth db diff",
                                 link: "/cli/reth/db/diff"
@@ -357,6 +365,10 @@ export const sidebar: SidebarItem[] = [
                                     }
                                 ]
                             },
+                            {
+                                text: "reth db prune-status",
+                                link: "/cli/reth/db/prune-status"
+                            },
                             {
                                 text: "reth db drop",
                                 link: "/cli/reth/db/drop"
@@ -376,6 +388,22 @@ export const sidebar: SidebarItem[] = [
                                     }
                                 ]
                             },
+                            {
+                                text: "reth db import-archive",
+                                link: "/cli/reth/db/import-archive"
+                            },
+                            {
+                                text: "reth db compact",
+                                link: "/cli/reth/db/compact"
+                            },
+                            {
+                                text: "reth db relocate-static-files",
+                                link: "/cli/reth/db/relocate-static-files"
+                            },
+                            {
+                                text: "reth db verify-static-files",
+                                link: "/cli/reth/db/verify-static-files"
+                            },
                             {
                                 text: "reth db version",
                                 link: "/cli/reth/db/version"
@@ -504,6 +532,10 @@ export const sidebar: SidebarItem[] = [
                             {
                                 text: "reth recover storage-tries",
                                 link: "/cli/reth/recover/storage-tries"
+                            },
+                            {
+                                text: "reth recover storage-root",
+                                link: "/cli/reth/recover/storage-root"
                             }
                         ]
                     },
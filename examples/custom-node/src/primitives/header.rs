@@ -4,7 +4,9 @@ use alloy_primitives::{
 };
 use alloy_rlp::{Encodable, RlpDecodable, RlpEncodable};
 use reth_codecs::Compact;
-use reth_ethereum::primitives::{serde_bincode_compat::RlpBincode, BlockHeader, InMemorySize};
+use reth_ethereum::primitives::{
+    serde_bincode_compat::RlpBincode, BlockHeader, ExtendedBlockHeader, InMemorySize,
+};
 use revm_primitives::keccak256;
 use serde::{Deserialize, Serialize};
 
@@ -183,4 +185,12 @@ impl reth_db_api::table::Decompress for CustomHeader {
 
 impl BlockHeader for CustomHeader {}
 
+impl ExtendedBlockHeader for CustomHeader {
+    type ExtraFields = u64;
+
+    fn extra_fields(&self) -> &Self::ExtraFields {
+        &self.extension
+    }
+}
+
 impl RlpBincode for CustomHeader {}
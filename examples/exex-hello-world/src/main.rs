@@ -42,6 +42,11 @@ async fn my_exex<Node: FullNodeComponents>(mut ctx: ExExContext<Node>) -> eyre::
             ExExNotification::ChainReverted { old } => {
                 info!(reverted_chain = ?old.range(), "Received revert");
             }
+            ExExNotification::Shutdown => {
+                info!("Received shutdown");
+                ctx.events.send(ExExEvent::ShutdownAcknowledged)?;
+            }
+            _ => {}
         };
 
         if let Some(committed_chain) = notification.committed_chain() {